@@ -0,0 +1,372 @@
+//! `mechos bench` – tick-latency benchmark of the agent loop.
+//!
+//! ```text
+//! mechos bench [--ticks <n>] [--bus-events <n>] [--max-p99-ms <ms>]
+//! ```
+//!
+//! Runs `--ticks` OODA cycles of a fresh [`AgentLoop`] against a scripted
+//! LLM, publishing a synthetic LiDAR scan before each one so the collision
+//! octree fills up, and reports the p50 / p95 / p99 latency of the whole
+//! tick and of every phase it times with an `ooda.*` span: observe (with the
+//! octree probe), orient, decide, gatekeep (with the trajectory sweep
+//! through the octree) and act.  A second run pushes `--bus-events` events
+//! through an [`EventBus`] to one subscriber and reports the throughput.
+//!
+//! No model server, robot or database is involved, so the numbers move only
+//! when the hot loop does.  With `--max-p99-ms` the command fails when the
+//! tick p99 exceeds the limit, to catch regressions in CI.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use colored::Colorize;
+use mechos_middleware::EventBus;
+use mechos_runtime::llm_driver::LlmDriver;
+use mechos_runtime::{AgentLoop, AgentLoopConfig};
+use mechos_types::{Event, EventPayload};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::span;
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::cli::BenchArgs;
+use crate::stack::TICK_RATE_HZ;
+
+/// The spans timed, in report order, with their labels.
+const PHASES: [(&str, &str); 8] = [
+    ("agent_loop.tick", "tick"),
+    ("ooda.observe", "observe"),
+    ("ooda.probe", "octree probe"),
+    ("ooda.orient", "orient"),
+    ("ooda.decide", "decide"),
+    ("ooda.gatekeep", "gatekeep"),
+    ("ooda.simulate", "trajectory sweep"),
+    ("ooda.act", "act"),
+];
+
+/// Beams of the synthetic LiDAR scan published before every tick.
+const SCAN_BEAMS: usize = 360;
+
+/// Capacity of the bus in the throughput run.
+const BUS_CAPACITY: usize = 1024;
+
+/// Run the benchmark and print its report.
+pub fn run(args: BenchArgs) -> Result<(), String> {
+    if args.ticks == 0 {
+        return Err("--ticks must be at least 1".to_string());
+    }
+    println!(
+        "  Running {} ticks against a scripted LLM …",
+        args.ticks.to_string().yellow()
+    );
+    let ticks = bench_ticks(args.ticks)?;
+    let bus = bench_bus(args.bus_events)?;
+
+    println!("{}", "Tick Latency".bold().underline());
+    println!(
+        "  {:<18} {:>12} {:>12} {:>12}",
+        "phase".dimmed(),
+        "p50".dimmed(),
+        "p95".dimmed(),
+        "p99".dimmed()
+    );
+    for (label, latency) in &ticks.phases {
+        println!(
+            "  {label:<18} {:>12} {:>12} {:>12}",
+            millis(latency.p50),
+            millis(latency.p95),
+            millis(latency.p99)
+        );
+    }
+    print!(
+        "  {} approved, {} rejected",
+        ticks.approved.to_string().green(),
+        ticks.rejected.to_string().red()
+    );
+    match &ticks.first_rejection {
+        Some(reason) => println!(" (first: {reason})."),
+        None => println!("."),
+    }
+
+    println!("{}", "Event Bus".bold().underline());
+    let seconds = bus.elapsed.as_secs_f64().max(f64::EPSILON);
+    println!(
+        "  {} events published in {} – {:.0} events/s; the subscriber received {}, lagged {}",
+        bus.delivered + bus.lagged,
+        millis(bus.elapsed),
+        (bus.delivered + bus.lagged) as f64 / seconds,
+        bus.delivered,
+        bus.lagged
+    );
+
+    if let Some(limit) = args.max_p99_ms {
+        let p99 = ticks.tick().map_or(Duration::ZERO, |tick| tick.p99);
+        if p99.as_secs_f64() * 1e3 > limit {
+            return Err(format!(
+                "tick p99 of {} exceeds --max-p99-ms {limit}",
+                millis(p99)
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.3} ms", duration.as_secs_f64() * 1e3)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Percentiles
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Latency percentiles of one span.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Latency {
+    pub samples: usize,
+    pub p50: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+}
+
+impl Latency {
+    /// The nearest-rank percentiles of `samples`; `None` when there are
+    /// none.
+    pub(crate) fn of(mut samples: Vec<Duration>) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_unstable();
+        let rank = |p: usize| samples[(samples.len() * p).div_ceil(100).max(1) - 1];
+        Some(Self {
+            samples: samples.len(),
+            p50: rank(50),
+            p95: rank(95),
+            p99: rank(99),
+        })
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tick benchmark
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Outcome of [`bench_ticks`].
+#[derive(Debug)]
+pub(crate) struct TickRun {
+    /// Latency of each [`PHASES`] span that ran, in report order.
+    pub phases: Vec<(&'static str, Latency)>,
+    pub approved: usize,
+    pub rejected: usize,
+    pub first_rejection: Option<String>,
+}
+
+impl TickRun {
+    fn tick(&self) -> Option<&Latency> {
+        self.phases
+            .iter()
+            .find(|(label, _)| *label == PHASES[0].1)
+            .map(|(_, latency)| latency)
+    }
+}
+
+/// Time `ticks` ticks of a fresh agent loop.
+pub(crate) fn bench_ticks(ticks: usize) -> Result<TickRun, String> {
+    let timer = SpanTimer::default();
+    let samples = Arc::clone(&timer.samples);
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| e.to_string())?;
+    let subscriber = tracing_subscriber::registry().with(timer);
+    let mut run = tracing::subscriber::with_default(subscriber, || rt.block_on(tick_loop(ticks)))?;
+
+    let mut samples = samples.lock().unwrap_or_else(|e| e.into_inner());
+    run.phases = PHASES
+        .iter()
+        .filter_map(|(span, label)| Some((*label, Latency::of(samples.remove(span)?)?)))
+        .collect();
+    Ok(run)
+}
+
+async fn tick_loop(ticks: usize) -> Result<TickRun, String> {
+    let mut agent = AgentLoop::new(AgentLoopConfig::default()).map_err(|e| e.to_string())?;
+    let replies = (0..ticks).map(scripted_reply);
+    agent.set_llm_driver(LlmDriver::scripted(replies).map_err(|e| e.to_string())?);
+    let bus = agent.bus();
+    let mut run = TickRun {
+        phases: Vec::new(),
+        approved: 0,
+        rejected: 0,
+        first_rejection: None,
+    };
+    for tick in 0..ticks {
+        let _ = bus.publish(scan_event(tick));
+        match agent.tick(1.0 / TICK_RATE_HZ).await {
+            Ok(_) => run.approved += 1,
+            Err(e) => {
+                run.rejected += 1;
+                run.first_rejection.get_or_insert_with(|| e.to_string());
+            }
+        }
+    }
+    Ok(run)
+}
+
+/// A slow `Drive`, varied from tick to tick so the loop guard stays quiet.
+fn scripted_reply(tick: usize) -> String {
+    let linear = 0.1 + (tick % 10) as f32 * 0.01;
+    format!(
+        r#"{{"action":"Drive","payload":{{"linear_velocity":{linear},"angular_velocity":0.0}}}}"#
+    )
+}
+
+/// A full turn of LiDAR returns 2–4 m out, shifting with `tick`.
+fn scan_event(tick: usize) -> Event {
+    let ranges = (0..SCAN_BEAMS)
+        .map(|beam| 2.0 + ((beam + tick) % 40) as f32 * 0.05)
+        .collect();
+    Event {
+        id: uuid::Uuid::new_v4(),
+        timestamp: chrono::Utc::now(),
+        source: "mechos-cli::bench".to_string(),
+        payload: EventPayload::LidarScan {
+            ranges,
+            angle_min_rad: -std::f32::consts::PI,
+            angle_increment_rad: std::f32::consts::TAU / SCAN_BEAMS as f32,
+            stamp: None,
+        },
+        trace_id: None,
+        correlation_id: None,
+    }
+}
+
+/// Records how long every [`PHASES`] span lives.
+#[derive(Default)]
+struct SpanTimer {
+    samples: Arc<Mutex<HashMap<&'static str, Vec<Duration>>>>,
+}
+
+/// When a timed span was created.
+struct Opened(Instant);
+
+impl<S> Layer<S> for SpanTimer
+where
+    S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        let name = attrs.metadata().name();
+        if PHASES.iter().any(|(span, _)| *span == name)
+            && let Some(span) = ctx.span(id)
+        {
+            span.extensions_mut().insert(Opened(Instant::now()));
+        }
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        if let Some(Opened(at)) = span.extensions().get::<Opened>() {
+            self.samples
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .entry(span.name())
+                .or_default()
+                .push(at.elapsed());
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Bus benchmark
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Outcome of [`bench_bus`].
+#[derive(Debug)]
+pub(crate) struct BusRun {
+    /// Events the subscriber received.
+    pub delivered: u64,
+    /// Events the subscriber fell too far behind to receive.
+    pub lagged: u64,
+    pub elapsed: Duration,
+}
+
+/// Publish `events` events on a bus while one subscriber drains it on
+/// another thread, until the subscriber has seen them all.
+pub(crate) fn bench_bus(events: usize) -> Result<BusRun, String> {
+    let bus = EventBus::new(BUS_CAPACITY);
+    let mut rx = bus.subscribe();
+    let event = scan_event(0);
+    let started = Instant::now();
+    let subscriber = std::thread::spawn(move || {
+        let (mut delivered, mut lagged) = (0, 0);
+        loop {
+            match rx.blocking_recv() {
+                Ok(_) => delivered += 1,
+                Err(RecvError::Lagged(skipped)) => lagged += skipped,
+                Err(RecvError::Closed) => return (delivered, lagged),
+            }
+        }
+    });
+    for _ in 0..events {
+        bus.publish(event.clone()).map_err(|e| e.to_string())?;
+    }
+    drop(bus);
+    let (delivered, lagged) = subscriber
+        .join()
+        .map_err(|_| "the bus subscriber panicked".to_string())?;
+    Ok(BusRun {
+        delivered,
+        lagged,
+        elapsed: started.elapsed(),
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles_use_the_nearest_rank() {
+        assert_eq!(Latency::of(Vec::new()), None);
+        let samples = (1..=200).rev().map(Duration::from_millis).collect();
+        let latency = Latency::of(samples).unwrap();
+        assert_eq!(latency.samples, 200);
+        assert_eq!(latency.p50, Duration::from_millis(100));
+        assert_eq!(latency.p95, Duration::from_millis(190));
+        assert_eq!(latency.p99, Duration::from_millis(198));
+        let single = Latency::of(vec![Duration::from_millis(7)]).unwrap();
+        assert_eq!(single.p99, Duration::from_millis(7));
+    }
+
+    #[test]
+    fn every_phase_of_a_tick_is_timed() {
+        let run = bench_ticks(20).unwrap();
+        assert_eq!(run.approved + run.rejected, 20);
+        assert!(run.approved > 0, "{:?}", run.first_rejection);
+        let labels: Vec<_> = run.phases.iter().map(|(label, _)| *label).collect();
+        for label in [
+            "tick",
+            "observe",
+            "octree probe",
+            "decide",
+            "gatekeep",
+            "act",
+        ] {
+            assert!(labels.contains(&label), "{label} missing from {labels:?}");
+        }
+        assert_eq!(run.tick().unwrap().samples, 20);
+    }
+
+    #[test]
+    fn the_bus_run_accounts_for_every_event() {
+        let run = bench_bus(5_000).unwrap();
+        assert_eq!(run.delivered + run.lagged, 5_000);
+    }
+}
@@ -0,0 +1,432 @@
+//! Command-line arguments – the `mechos` subcommands, parsed with clap.
+//!
+//! ```text
+//! mechos                              interactive REPL (default)
+//! mechos run [--config <path>] ...    headless stack, see crate::daemon
+//! mechos config path|show|edit        ~/.mechos/config.toml
+//! mechos tasks list|show|post|claim|complete   fleet task board
+//! mechos memory list|search|forget    episodic memory
+//! mechos caps [list|grant|revoke]     the agent's capability grants
+//! mechos doctor [--config <path>]     installation diagnostics
+//! mechos replay <log> [--speed <x>]   re-run a recorded incident
+//! mechos bench [--ticks <n>]          agent loop tick latency
+//! mechos simulate --scenario <file>    scripted scenarios, JUnit report
+//! ```
+//!
+//! Every subcommand but `run` is one-shot: it prints its result and exits,
+//! non-zero on failure.  The handlers live in [`crate::commands`],
+//! [`crate::doctor`], [`crate::bench`] and [`crate::simulate`].
+
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
+use mechos_types::Capability;
+
+use crate::daemon::RunOptions;
+
+/// The `mechos` command line.
+#[derive(Debug, Parser)]
+#[command(
+    name = "mechos",
+    version,
+    about = "MechOS – the robot operating system CLI"
+)]
+pub struct Cli {
+    /// Without a subcommand, the interactive REPL starts.
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Boot the stack headless, e.g. as a systemd service.
+    Run(RunOptions),
+    /// Show or edit the configuration.
+    #[command(subcommand)]
+    Config(ConfigCommand),
+    /// Inspect and post fleet tasks.
+    Tasks(TasksArgs),
+    /// Inspect and prune the episodic memory.
+    Memory(MemoryArgs),
+    /// List, grant or revoke the agent's capabilities.
+    Caps(CapsArgs),
+    /// Check the configuration, the AI provider, ports and databases.
+    Doctor(DoctorArgs),
+    /// Re-run a recorded event log through the agent loop.
+    Replay(ReplayArgs),
+    /// Measure the agent loop's tick latency and the bus throughput.
+    Bench(BenchArgs),
+    /// Run scripted scenarios against a simulated robot and check outcomes.
+    Simulate(SimulateArgs),
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Print the path of the config file.
+    Path,
+    /// Print the effective configuration, API keys redacted.
+    Show,
+    /// Edit the settings interactively, like the REPL's `/settings`.
+    Edit,
+}
+
+#[derive(Debug, Args)]
+pub struct TasksArgs {
+    /// Task board database; defaults to the config's `task_board_path`.
+    #[arg(long, value_name = "PATH", global = true)]
+    pub db: Option<PathBuf>,
+    #[command(subcommand)]
+    pub command: TasksCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TasksCommand {
+    /// List open and claimed tasks.
+    List {
+        /// Include completed tasks.
+        #[arg(long)]
+        all: bool,
+    },
+    /// Print one task in full.
+    Show { id: String },
+    /// Post a new task.
+    #[command(alias = "add")]
+    Post {
+        title: String,
+        #[arg(long, short, default_value = "")]
+        description: String,
+        /// Higher values are handed out first.
+        #[arg(long, default_value_t = 0, allow_negative_numbers = true)]
+        priority: i32,
+        /// A task that must be completed first; repeatable.
+        #[arg(long = "after", value_name = "TASK_ID")]
+        depends_on: Vec<String>,
+    },
+    /// Claim an open task for a robot.
+    Claim {
+        id: String,
+        #[arg(long, value_name = "ROBOT_ID")]
+        robot: String,
+    },
+    /// Complete a task claimed by a robot.
+    Complete {
+        id: String,
+        #[arg(long, value_name = "ROBOT_ID")]
+        robot: String,
+        /// JSON result to store with the task.
+        #[arg(long, value_name = "JSON", value_parser = parse_json)]
+        result: Option<serde_json::Value>,
+    },
+}
+
+/// `/tasks` in the REPL, which takes the arguments of `mechos tasks`.
+#[derive(Debug, Parser)]
+#[command(name = "/tasks", no_binary_name = true, disable_version_flag = true)]
+pub struct ReplTasks {
+    #[command(flatten)]
+    pub args: TasksArgs,
+}
+
+fn parse_json(value: &str) -> Result<serde_json::Value, String> {
+    serde_json::from_str(value).map_err(|e| format!("invalid JSON: {e}"))
+}
+
+#[derive(Debug, Args)]
+pub struct MemoryArgs {
+    /// Memory database; defaults to `~/.mechos/memory.db`.
+    #[arg(long, value_name = "PATH", global = true)]
+    pub db: Option<PathBuf>,
+    #[command(subcommand)]
+    pub command: MemoryCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum MemoryCommand {
+    /// List the newest memories.
+    #[command(alias = "recent")]
+    List {
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// List the memories semantically closest to the search text.
+    Search {
+        #[arg(required = true, num_args = 1..)]
+        terms: Vec<String>,
+        /// How many memories to show.
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+    },
+    /// Delete the memories matching every `key=value` condition: `source`,
+    /// `tag`, `contains`, `below` (importance), `before` / `after` (RFC 3339
+    /// or an age like `7d`).
+    Forget {
+        #[arg(required = true, num_args = 1.., value_name = "KEY=VALUE")]
+        filter: Vec<String>,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct CapsArgs {
+    /// Without a subcommand, the grants are listed.
+    #[command(subcommand)]
+    pub command: Option<CapsCommand>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CapsCommand {
+    /// List every identity's grants.
+    List,
+    /// Grant a capability, e.g. `hardware_invoke:drive_base`.
+    Grant {
+        identity: String,
+        #[arg(value_parser = parse_capability)]
+        capability: Capability,
+        /// Grant a dangerous capability without asking.
+        #[arg(long, short)]
+        yes: bool,
+    },
+    /// Revoke a capability.
+    Revoke {
+        identity: String,
+        #[arg(value_parser = parse_capability)]
+        capability: Capability,
+    },
+}
+
+/// `/caps` in the REPL, which takes the arguments of `mechos caps`.
+#[derive(Debug, Parser)]
+#[command(name = "/caps", no_binary_name = true, disable_version_flag = true)]
+pub struct ReplCaps {
+    #[command(flatten)]
+    pub args: CapsArgs,
+}
+
+/// Parse `model_inference`, `fleet_communicate`, `task_board_access`,
+/// `answer_questions`, `approve_intents`, or `hardware_invoke:<id>`,
+/// `sensor_read:<topic>` and `memory_access:<store>`.
+pub(crate) fn parse_capability(value: &str) -> Result<Capability, String> {
+    let (kind, target) = match value.split_once(':') {
+        Some((kind, target)) if !target.is_empty() => (kind, Some(target.to_string())),
+        Some(_) => return Err(format!("'{value}' names no target")),
+        None => (value, None),
+    };
+    match (kind, target) {
+        ("hardware_invoke", Some(id)) => Ok(Capability::HardwareInvoke(id)),
+        ("sensor_read", Some(topic)) => Ok(Capability::SensorRead(topic)),
+        ("memory_access", Some(store)) => Ok(Capability::MemoryAccess(store)),
+        ("model_inference", None) => Ok(Capability::ModelInference),
+        ("fleet_communicate", None) => Ok(Capability::FleetCommunicate),
+        ("task_board_access", None) => Ok(Capability::TaskBoardAccess),
+        ("answer_questions", None) => Ok(Capability::AnswerQuestions),
+        ("approve_intents", None) => Ok(Capability::ApproveIntents),
+        ("hardware_invoke" | "sensor_read" | "memory_access", None) => {
+            Err(format!("'{kind}' needs a target, e.g. {kind}:<name>"))
+        }
+        (
+            "model_inference" | "fleet_communicate" | "task_board_access" | "answer_questions"
+            | "approve_intents",
+            Some(_),
+        ) => {
+            Err(format!("'{kind}' takes no target"))
+        }
+        _ => Err(format!(
+            "unknown capability '{kind}' (hardware_invoke, sensor_read, memory_access, \
+             model_inference, fleet_communicate, task_board_access, answer_questions, \
+             approve_intents)"
+        )),
+    }
+}
+
+/// `cap` as [`parse_capability`] reads it.
+pub(crate) fn capability_name(cap: &Capability) -> String {
+    match cap {
+        Capability::HardwareInvoke(id) => format!("hardware_invoke:{id}"),
+        Capability::SensorRead(topic) => format!("sensor_read:{topic}"),
+        Capability::MemoryAccess(store) => format!("memory_access:{store}"),
+        Capability::ModelInference => "model_inference".to_string(),
+        Capability::FleetCommunicate => "fleet_communicate".to_string(),
+        Capability::TaskBoardAccess => "task_board_access".to_string(),
+        Capability::AnswerQuestions => "answer_questions".to_string(),
+        Capability::ApproveIntents => "approve_intents".to_string(),
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct DoctorArgs {
+    /// Check this config file instead of `~/.mechos/config.toml`.
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct ReplayArgs {
+    /// A JSON-lines event log or a bus recorder journal directory.
+    pub log: PathBuf,
+    /// Playback speed; `1.0` is real time.
+    #[arg(long, default_value_t = 1.0, conflicts_with = "fast")]
+    pub speed: f64,
+    /// Tick back-to-back instead of in real time.
+    #[arg(long)]
+    pub fast: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct BenchArgs {
+    /// OODA ticks to run.
+    #[arg(long, short = 'n', default_value_t = 1000)]
+    pub ticks: usize,
+    /// Events to push through the bus for the throughput run.
+    #[arg(long, default_value_t = 100_000)]
+    pub bus_events: usize,
+    /// Fail when the tick p99 exceeds this many milliseconds.
+    #[arg(long, value_name = "MS")]
+    pub max_p99_ms: Option<f64>,
+}
+
+#[derive(Debug, Args)]
+pub struct SimulateArgs {
+    /// Scenario YAML files to run, in order.
+    #[arg(long = "scenario", short, value_name = "FILE", required = true)]
+    pub scenarios: Vec<PathBuf>,
+    /// Drive the simulation dashboard at this URL instead of the mock robot.
+    #[arg(long, value_name = "URL")]
+    pub dashboard: Option<String>,
+    /// Write the results as a JUnit XML report.
+    #[arg(long, value_name = "PATH")]
+    pub junit: Option<PathBuf>,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    fn parse(line: &str) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(line.split_whitespace())
+    }
+
+    #[test]
+    fn cli_definition_is_consistent() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn no_subcommand_means_the_repl() {
+        assert!(parse("mechos").unwrap().command.is_none());
+        assert!(parse("mechos launch").is_err());
+    }
+
+    #[test]
+    fn run_options_parse() {
+        let cli = parse("mechos run --config /etc/mechos.toml --daemonize").unwrap();
+        let Some(Command::Run(options)) = cli.command else {
+            panic!("expected run");
+        };
+        assert_eq!(options.config, Some(PathBuf::from("/etc/mechos.toml")));
+        assert!(options.daemonize);
+        assert!(options.pid_file.ends_with("mechos.pid"));
+
+        assert!(parse("mechos run --pid-file").is_err());
+        assert!(parse("mechos run --verbose").is_err());
+    }
+
+    #[test]
+    fn subcommands_parse() {
+        let cli = parse("mechos tasks post Dock --priority -2 --after t1 --after t2").unwrap();
+        let Some(Command::Tasks(TasksArgs {
+            db: None,
+            command:
+                TasksCommand::Post {
+                    title,
+                    priority,
+                    depends_on,
+                    ..
+                },
+        })) = cli.command
+        else {
+            panic!("expected tasks add");
+        };
+        assert_eq!((title.as_str(), priority), ("Dock", -2));
+        assert_eq!(depends_on, ["t1", "t2"]);
+        assert!(parse("mechos tasks add Dock").is_ok(), "add is an alias of post");
+
+        let cli = parse("mechos memory search red box --db /tmp/m.db").unwrap();
+        let Some(Command::Memory(MemoryArgs { db, command })) = cli.command else {
+            panic!("expected memory");
+        };
+        assert_eq!(db, Some(PathBuf::from("/tmp/m.db")));
+        assert!(matches!(command, MemoryCommand::Search { terms, top: 10 } if terms == ["red", "box"]));
+
+        assert!(
+            parse("mechos memory forget").is_err(),
+            "forgetting needs a filter"
+        );
+        let cli = parse("mechos memory forget source=camera after=7d").unwrap();
+        let Some(Command::Memory(MemoryArgs {
+            command: MemoryCommand::Forget { filter },
+            ..
+        })) = cli.command
+        else {
+            panic!("expected memory forget");
+        };
+        assert_eq!(filter, ["source=camera", "after=7d"]);
+        assert!(parse("mechos replay incident.jsonl --fast --speed 2").is_err());
+        let cli = parse("mechos bench -n 200 --max-p99-ms 25").unwrap();
+        let Some(Command::Bench(bench)) = cli.command else {
+            panic!("expected bench");
+        };
+        assert_eq!((bench.ticks, bench.bus_events, bench.max_p99_ms), (200, 100_000, Some(25.0)));
+        let cli = parse("mechos simulate -s a.yaml --scenario b.yaml --junit out.xml").unwrap();
+        let Some(Command::Simulate(sim)) = cli.command else {
+            panic!("expected simulate");
+        };
+        assert_eq!(sim.scenarios, [PathBuf::from("a.yaml"), PathBuf::from("b.yaml")]);
+        assert_eq!(sim.junit, Some(PathBuf::from("out.xml")));
+        assert!(parse("mechos simulate").is_err(), "a scenario is required");
+    }
+
+    #[test]
+    fn repl_tasks_take_the_subcommand_arguments() {
+        let tasks = ReplTasks::try_parse_from(["complete", "t1", "--robot", "rover-a", "--result", r#"{"boxes":3}"#])
+            .unwrap();
+        let TasksCommand::Complete { id, robot, result } = tasks.args.command else {
+            panic!("expected complete");
+        };
+        assert_eq!((id.as_str(), robot.as_str()), ("t1", "rover-a"));
+        assert_eq!(result, Some(serde_json::json!({"boxes": 3})));
+
+        assert!(ReplTasks::try_parse_from(["claim", "t1"]).is_err(), "claiming needs a robot");
+        assert!(ReplTasks::try_parse_from(["complete", "t1", "--robot", "a", "--result", "{"]).is_err());
+    }
+
+    #[test]
+    fn capabilities_parse_and_print_alike() {
+        for cap in [
+            Capability::HardwareInvoke("drive_base".into()),
+            Capability::SensorRead("lidar/scan".into()),
+            Capability::MemoryAccess("episodic".into()),
+            Capability::ModelInference,
+            Capability::FleetCommunicate,
+            Capability::TaskBoardAccess,
+            Capability::AnswerQuestions,
+            Capability::ApproveIntents,
+        ] {
+            assert_eq!(parse_capability(&capability_name(&cap)), Ok(cap));
+        }
+        for bad in ["hardware_invoke", "hardware_invoke:", "model_inference:x", "teleport"] {
+            assert!(parse_capability(bad).is_err(), "{bad}");
+        }
+
+        let caps = ReplCaps::try_parse_from(["grant", "runtime", "hardware_invoke:arm", "-y"]).unwrap();
+        assert!(matches!(
+            caps.args.command,
+            Some(CapsCommand::Grant { capability: Capability::HardwareInvoke(id), yes: true, .. }) if id == "arm"
+        ));
+        assert!(parse("mechos caps").unwrap().command.is_some());
+        assert!(parse("mechos caps revoke runtime").is_err(), "revoking needs a capability");
+    }
+}
@@ -0,0 +1,511 @@
+//! One-shot subcommands – `mechos config|tasks|memory|caps|replay`.
+//!
+//! Each handler prints its result and returns `Err` with a one-line reason
+//! on failure, which `main` turns into a non-zero exit status.  The stores
+//! are the ones `/start` and `mechos run` use under `~/.mechos`; reading a
+//! store that does not exist yet reports it as empty instead of creating
+//! it.
+
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use mechos_memory::encryption::KeySource;
+use mechos_memory::episodic::{EpisodicStore, MemoryEntry, MemoryFilter};
+use mechos_memory::task_board::{TaskBoard, TaskEntry, TaskSpec, TaskStatus};
+use mechos_runtime::replay::ReplayDriver;
+use mechos_runtime::{AgentLoop, AgentLoopConfig};
+use mechos_types::Capability;
+
+use crate::cli::{
+    self, CapsArgs, CapsCommand, ConfigCommand, MemoryArgs, MemoryCommand, ReplayArgs, TasksArgs,
+    TasksCommand,
+};
+use crate::config;
+use crate::{daemon, repl, stack};
+
+/// Run `future` to completion on a runtime of its own.
+pub(crate) fn block_on<F: Future>(future: F) -> Result<F::Output, String> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| format!("cannot start the async runtime: {e}"))?;
+    Ok(runtime.block_on(future))
+}
+
+/// Open the task board at `path`, encrypted with `key` if one is set.
+pub(crate) fn open_task_board(path: &str, key: Option<&KeySource>) -> Result<TaskBoard, String> {
+    match key {
+        Some(source) => {
+            let key = source.resolve().map_err(|e| format!("cannot resolve the memory key: {e}"))?;
+            TaskBoard::open_encrypted(path, &key)
+        }
+        None => TaskBoard::open(path),
+    }
+    .map_err(|e| format!("cannot open {path}: {e}"))
+}
+
+/// Open the episodic store at `path`, encrypted with `key` if one is set.
+pub(crate) fn open_episodic_store(
+    path: &str,
+    key: Option<&KeySource>,
+) -> Result<EpisodicStore, String> {
+    match key {
+        Some(source) => {
+            let key = source.resolve().map_err(|e| format!("cannot resolve the memory key: {e}"))?;
+            EpisodicStore::open_encrypted(path, &key)
+        }
+        None => EpisodicStore::open(path),
+    }
+    .map_err(|e| format!("cannot open {path}: {e}"))
+}
+
+/// `path`, or the file `name` in `~/.mechos`.
+fn store_path(path: Option<PathBuf>, name: &str) -> String {
+    path.unwrap_or_else(|| config::state_dir().join(name))
+        .to_string_lossy()
+        .into_owned()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// mechos config
+// ─────────────────────────────────────────────────────────────────────────────
+
+pub fn config(command: ConfigCommand) -> Result<(), String> {
+    match command {
+        ConfigCommand::Path => println!("{}", config::config_path().display()),
+        ConfigCommand::Show => {
+            if !config::config_path().exists() {
+                println!("{}", "No config file; showing the defaults.".dimmed());
+            }
+            println!("{:#?}", daemon::load_config(None)?);
+        }
+        ConfigCommand::Edit => repl::cmd_settings(),
+    }
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// mechos tasks
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Also behind the REPL's `/tasks`.
+pub fn tasks(args: TasksArgs) -> Result<(), String> {
+    let cfg = daemon::load_config(None)?;
+    let path = args.db.unwrap_or_else(|| cfg.task_board());
+    let path = path.to_string_lossy().into_owned();
+    let posting = matches!(args.command, TasksCommand::Post { .. });
+    if !posting && !Path::new(&path).exists() {
+        println!("{} {}", "No task board at".dimmed(), path.dimmed());
+        return Ok(());
+    }
+    let board = open_task_board(&path, cfg.memory_key().as_ref())?;
+
+    match args.command {
+        TasksCommand::List { all } => {
+            let tasks = block_on(board.list_all())?.map_err(|e| e.to_string())?;
+            let tasks: Vec<&TaskEntry> = tasks
+                .iter()
+                .filter(|task| all || task.status != TaskStatus::Completed)
+                .collect();
+            if tasks.is_empty() {
+                println!("{}", "  No tasks.".dimmed());
+                return Ok(());
+            }
+            println!("{}", "Fleet Task Board".bold().underline());
+            for task in &tasks {
+                let status = match task.status {
+                    TaskStatus::Open => "open".green(),
+                    TaskStatus::Claimed => "claimed".yellow(),
+                    TaskStatus::Completed => "completed".dimmed(),
+                };
+                let claim = task
+                    .claimed_by
+                    .as_deref()
+                    .map(|robot| format!(" by {robot} ({:.0}%)", task.progress_percent))
+                    .unwrap_or_default();
+                println!(
+                    "  {} {:<9} p{:<3} {}{}",
+                    task.id.dimmed(),
+                    status,
+                    task.priority,
+                    task.title.bold(),
+                    claim
+                );
+            }
+            println!("  {} tasks.", tasks.len().to_string().yellow());
+        }
+        TasksCommand::Show { id } => {
+            let task = block_on(board.get(&id))?.map_err(|e| e.to_string())?;
+            let json = serde_json::to_string_pretty(&task).map_err(|e| e.to_string())?;
+            println!("{json}");
+        }
+        TasksCommand::Post {
+            title,
+            description,
+            priority,
+            depends_on,
+        } => {
+            let spec = TaskSpec::new(title, description)
+                .with_priority(priority)
+                .with_dependencies(depends_on);
+            let id = block_on(board.post_task(&spec))?.map_err(|e| e.to_string())?;
+            println!("{} {}", "✓ Posted task".green(), id.bold());
+        }
+        TasksCommand::Claim { id, robot } => {
+            block_on(board.claim(&id, &robot))?.map_err(|e| e.to_string())?;
+            println!("{} {} for {}", "✓ Claimed".green(), id.bold(), robot.cyan());
+        }
+        TasksCommand::Complete { id, robot, result } => {
+            let result = result.unwrap_or_default();
+            block_on(board.complete_with_result(&id, &robot, result))?
+                .map_err(|e| e.to_string())?;
+            println!("{} {} by {}", "✓ Completed".green(), id.bold(), robot.cyan());
+        }
+    }
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// mechos memory
+// ─────────────────────────────────────────────────────────────────────────────
+
+pub fn memory(args: MemoryArgs) -> Result<(), String> {
+    let path = store_path(args.db, "memory.db");
+    if !Path::new(&path).exists() {
+        println!("{} {}", "No memory store at".dimmed(), path.dimmed());
+        return Ok(());
+    }
+    let key = daemon::load_config(None)?.memory_key();
+    let store = open_episodic_store(&path, key.as_ref())?;
+
+    match args.command {
+        MemoryCommand::List { limit } => {
+            let mut entries = block_on(store.all_entries())?.map_err(|e| e.to_string())?;
+            entries.sort_by_key(|entry| std::cmp::Reverse(entry.timestamp));
+            let total = entries.len();
+            entries.truncate(limit);
+            print_memories("Episodic Memory Store", &entries);
+            println!(
+                "  {} of {} entries.",
+                entries.len().to_string().yellow(),
+                total
+            );
+        }
+        MemoryCommand::Search { terms, top } => {
+            let query = terms.join(" ");
+            let results = block_on(store.recall_text(&query, top, &MemoryFilter::default()))?
+                .map_err(|e| e.to_string())?;
+            print_recall(&query, &results);
+        }
+        MemoryCommand::Forget { filter } => {
+            let filter = parse_memory_filter(&filter)?;
+            let removed = block_on(store.forget(&filter))?.map_err(|e| e.to_string())?;
+            println!(
+                "{} {} memories",
+                "✓ Forgot".green(),
+                removed.to_string().bold()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Parse `key=value` words into a [`MemoryFilter`]: `source=`, `tag=`,
+/// `contains=`, `below=<importance>` and `before=` / `after=`, which take an
+/// RFC 3339 time or an age such as `30m`, `12h` or `7d`.  Also behind the
+/// REPL's `/memory forget`; a filter without conditions would match every
+/// memory and is refused.
+pub(crate) fn parse_memory_filter(words: &[String]) -> Result<MemoryFilter, String> {
+    let mut filter = MemoryFilter::default();
+    for word in words {
+        let (key, value) = word
+            .split_once('=')
+            .ok_or_else(|| format!("'{word}' is not a key=value condition"))?;
+        filter = match key {
+            "source" => filter.with_source(value),
+            "tag" => filter.with_tag(value),
+            "contains" => filter.with_summary_containing(value),
+            "below" => filter.with_below_importance(
+                value
+                    .parse()
+                    .map_err(|_| format!("'{value}' is not an importance"))?,
+            ),
+            "before" => filter.with_before(parse_instant(value)?),
+            "after" => filter.with_after(parse_instant(value)?),
+            _ => {
+                return Err(format!(
+                    "unknown condition '{key}' (source, tag, contains, below, before, after)"
+                ));
+            }
+        };
+    }
+    if filter == MemoryFilter::default() {
+        return Err("the filter needs at least one condition".to_string());
+    }
+    Ok(filter)
+}
+
+/// An RFC 3339 time, or an age (`90s`, `30m`, `12h`, `7d`) before now.
+fn parse_instant(value: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    let invalid = || format!("'{value}' is neither an RFC 3339 time nor an age like 7d");
+    let unit = value.chars().last().ok_or_else(invalid)?;
+    let amount = &value[..value.len() - unit.len_utf8()];
+    let amount: i64 = amount.parse().map_err(|_| invalid())?;
+    let age = match unit {
+        's' => chrono::Duration::seconds(amount),
+        'm' => chrono::Duration::minutes(amount),
+        'h' => chrono::Duration::hours(amount),
+        'd' => chrono::Duration::days(amount),
+        _ => return Err(invalid()),
+    };
+    Ok(Utc::now() - age)
+}
+
+/// Semantic search results, best match first, with their similarity.
+pub(crate) fn print_recall(query: &str, results: &[(MemoryEntry, f32)]) {
+    if results.is_empty() {
+        println!("{}", "  No memories.".dimmed());
+        return;
+    }
+    println!(
+        "{} '{}'",
+        "Memories closest to".bold().underline(),
+        query.yellow()
+    );
+    for (entry, score) in results {
+        println!(
+            "  {} {} {} {}",
+            format!("{score:.3}").green(),
+            entry
+                .timestamp
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+                .dimmed(),
+            entry.source.cyan(),
+            entry.summary.bold()
+        );
+    }
+}
+
+pub(crate) fn print_memories(title: &str, entries: &[MemoryEntry]) {
+    if entries.is_empty() {
+        println!("{}", "  No memories.".dimmed());
+        return;
+    }
+    println!("{}", title.bold().underline());
+    for entry in entries {
+        println!(
+            "  {} {} {}",
+            entry
+                .timestamp
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+                .dimmed(),
+            entry.source.cyan(),
+            entry.summary.bold()
+        );
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// mechos caps
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// The grants the stack's agent loop starts with, kept in
+/// `~/.mechos/capabilities.json` once changed.  Also behind the REPL's
+/// `/caps`.
+pub fn caps(args: CapsArgs) -> Result<(), String> {
+    let mut manager = stack::capability_grants()?;
+    let (identity, capability, granted) = match args.command.unwrap_or(CapsCommand::List) {
+        CapsCommand::List => {
+            println!("{}", "Capability Grants".bold().underline());
+            for grant in manager.grants() {
+                println!("  {}", grant.agent_id.bold());
+                for cap in &grant.capabilities {
+                    println!("    • {}", cli::capability_name(cap));
+                }
+            }
+            return Ok(());
+        }
+        CapsCommand::Grant {
+            identity,
+            capability,
+            yes,
+        } => {
+            if is_dangerous(&capability) && !yes {
+                let question = format!(
+                    "  {} lets {} act outside the robot's sandbox. Grant it? [y/N] ",
+                    cli::capability_name(&capability).yellow(),
+                    identity.bold()
+                );
+                let answer = repl::prompt_str(&question, "n");
+                if !matches!(answer.to_lowercase().as_str(), "y" | "yes") {
+                    println!("{}", "  Nothing granted.".dimmed());
+                    return Ok(());
+                }
+            }
+            manager.grant(&identity, capability.clone());
+            (identity, capability, true)
+        }
+        CapsCommand::Revoke {
+            identity,
+            capability,
+        } => {
+            if !manager.holds(&identity, &capability) {
+                return Err(format!(
+                    "{identity} does not hold {}",
+                    cli::capability_name(&capability)
+                ));
+            }
+            manager.revoke(&identity, &capability);
+            (identity, capability, false)
+        }
+    };
+
+    let path = config::capabilities_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("cannot create {}: {e}", dir.display()))?;
+    }
+    manager.save(&path).map_err(|e| e.to_string())?;
+    println!(
+        "{} {} {} {}",
+        if granted { "✓ Granted" } else { "✓ Revoked" }.green(),
+        cli::capability_name(&capability).bold(),
+        if granted { "to" } else { "from" },
+        identity.cyan()
+    );
+    println!(
+        "{}",
+        "  Takes effect the next time the agent loop starts.".dimmed()
+    );
+    Ok(())
+}
+
+/// Capabilities that move hardware, directly or by approving or guiding the
+/// agent's intents, or reach other robots, which a grant asks to confirm.
+fn is_dangerous(capability: &Capability) -> bool {
+    matches!(
+        capability,
+        Capability::HardwareInvoke(_)
+            | Capability::FleetCommunicate
+            | Capability::ApproveIntents
+            | Capability::AnswerQuestions
+    )
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// mechos replay
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Replay `args.log` through an agent loop using the configured model.
+pub fn replay(args: ReplayArgs) -> Result<(), String> {
+    let cfg = daemon::load_config(None)?;
+    let driver = if args.log.is_dir() {
+        ReplayDriver::from_journal(&args.log)
+    } else {
+        ReplayDriver::from_jsonl(&args.log)
+    }
+    .map_err(|e| format!("cannot read {}: {e}", args.log.display()))?;
+    let speed = if args.fast {
+        ReplayDriver::UNPACED
+    } else {
+        args.speed
+    };
+    println!(
+        "  Replaying {} events from {} with {} …",
+        driver.len().to_string().yellow(),
+        args.log.display().to_string().bold(),
+        cfg.active_model.cyan()
+    );
+
+    let report = block_on(async {
+        let mut agent = AgentLoop::new(AgentLoopConfig {
+            llm_base_url: cfg.ollama_url.clone(),
+            llm_model: cfg.active_model.clone(),
+            ..Default::default()
+        })?;
+        Ok::<_, mechos_types::MechError>(driver.with_speed(speed).run(&mut agent).await)
+    })?
+    .map_err(|e| format!("cannot start the agent loop: {e}"))?;
+
+    println!("{}", "Replay Report".bold().underline());
+    println!(
+        "  {} events fed, {} skipped, {} ticks",
+        report.events_fed,
+        report.events_skipped,
+        report.ticks.len()
+    );
+    for tick in &report.ticks {
+        let at = format!("{:>8.1}s", tick.at.as_secs_f64());
+        match &tick.result {
+            Ok(intent) => println!(
+                "  {} {} {}",
+                at.dimmed(),
+                "APPROVED".green(),
+                serde_json::to_string(intent).unwrap_or_else(|_| format!("{intent:?}"))
+            ),
+            Err(e) => println!("  {} {} {}", at.dimmed(), "REJECTED".red(), e),
+        }
+    }
+    println!(
+        "  {} approved, {} rejected.",
+        report.approved().count().to_string().green(),
+        report.rejections().count().to_string().red()
+    );
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn memory_filters_parse() {
+        let filter = parse_memory_filter(&words("source=camera tag=kitchen below=0.3")).unwrap();
+        assert_eq!(
+            filter,
+            MemoryFilter::default()
+                .with_source("camera")
+                .with_tag("kitchen")
+                .with_below_importance(0.3)
+        );
+
+        let filter = parse_memory_filter(&words("before=2026-01-01T00:00:00Z after=7d")).unwrap();
+        assert_eq!(
+            filter.before.unwrap().to_rfc3339(),
+            "2026-01-01T00:00:00+00:00"
+        );
+        let age = Utc::now() - filter.after.unwrap();
+        assert!((age - chrono::Duration::days(7)).num_seconds().abs() < 5);
+    }
+
+    #[test]
+    fn memory_filters_refuse_nonsense() {
+        assert!(parse_memory_filter(&[]).is_err(), "would forget everything");
+        for line in ["camera", "colour=red", "below=high", "after=7w", "before=d"] {
+            assert!(parse_memory_filter(&words(line)).is_err(), "{line}");
+        }
+    }
+
+    #[test]
+    fn operator_grants_need_confirmation() {
+        assert!(is_dangerous(&Capability::AnswerQuestions));
+        assert!(is_dangerous(&Capability::ApproveIntents));
+        assert!(is_dangerous(&Capability::HardwareInvoke("drive_base".to_string())));
+        assert!(!is_dangerous(&Capability::TaskBoardAccess));
+    }
+}
@@ -1,7 +1,10 @@
 //! Configuration Vault – reads/writes `~/.mechos/config.toml`.
 
+use mechos_memory::encryption::KeySource;
+use serde::de::{self, Deserializer, MapAccess, Visitor};
 use serde::{Deserialize, Serialize};
 use std::fs;
+use std::net::{IpAddr, Ipv4Addr};
 use std::path::PathBuf;
 
 /// Supported AI provider choices.
@@ -26,6 +29,235 @@ impl std::fmt::Display for AiProvider {
     }
 }
 
+/// Hardware adapter `/start` drives the robot through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdapterChoice {
+    /// The Three.js simulation dashboard's rosbridge on `dashboard_port`.
+    #[default]
+    DashboardSim,
+    /// A Gazebo simulation at `url` (default `ws://localhost:9002`).
+    Gazebo,
+    /// A Webots robot controller at `url`.
+    Webots,
+    /// A ROS 2 robot through the `rosbridge_server` at `url` (default
+    /// `ws://localhost:9090`).
+    Ros2,
+    /// A microcontroller base on `serial_port` at `baud_rate`.
+    Serial,
+    /// An MQTT robot through the broker at `mqtt_host`:`mqtt_port`.
+    Mqtt,
+}
+
+impl std::fmt::Display for AdapterChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AdapterChoice::DashboardSim => write!(f, "dashboard_sim"),
+            AdapterChoice::Gazebo => write!(f, "gazebo"),
+            AdapterChoice::Webots => write!(f, "webots"),
+            AdapterChoice::Ros2 => write!(f, "ros2"),
+            AdapterChoice::Serial => write!(f, "serial"),
+            AdapterChoice::Mqtt => write!(f, "mqtt"),
+        }
+    }
+}
+
+/// The `[adapter]` section: the adapter `/start` connects and where to find
+/// the robot.  Fields the chosen `kind` does not use are ignored.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdapterConfig {
+    pub kind: AdapterChoice,
+    /// WebSocket URL of the Gazebo / Webots simulator or the
+    /// `rosbridge_server`; empty uses the backend's default.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub url: String,
+    /// Device of a `serial` base.
+    pub serial_port: String,
+    pub baud_rate: u32,
+    pub mqtt_host: String,
+    pub mqtt_port: u16,
+}
+
+impl Default for AdapterConfig {
+    fn default() -> Self {
+        Self {
+            kind: AdapterChoice::default(),
+            url: String::new(),
+            serial_port: "/dev/ttyUSB0".to_string(),
+            baud_rate: 115_200,
+            mqtt_host: "localhost".to_string(),
+            mqtt_port: 1883,
+        }
+    }
+}
+
+/// Read `adapter` as the `[adapter]` table, or as the bare adapter name
+/// older config files have (`adapter = "gazebo"`).
+fn adapter_section<'de, D: Deserializer<'de>>(deserializer: D) -> Result<AdapterConfig, D::Error> {
+    struct Section;
+
+    impl<'de> Visitor<'de> for Section {
+        type Value = AdapterConfig;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("an [adapter] table or an adapter name")
+        }
+
+        fn visit_str<E: de::Error>(self, name: &str) -> Result<AdapterConfig, E> {
+            let kind = AdapterChoice::deserialize(de::value::StrDeserializer::<E>::new(name))?;
+            Ok(AdapterConfig {
+                kind,
+                ..AdapterConfig::default()
+            })
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<AdapterConfig, A::Error> {
+            AdapterConfig::deserialize(de::value::MapAccessDeserializer::new(map))
+        }
+    }
+
+    deserializer.deserialize_any(Section)
+}
+
+/// The `[safety]` section: site limits the kernel enforces on top of its
+/// built-in interlocks.  Every limit is off until set.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SafetyConfig {
+    /// Fastest the base may drive, in m/s.  Takes effect together with
+    /// `max_angular_rps`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_linear_mps: Option<f32>,
+    /// Fastest the base may turn, in rad/s.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_angular_rps: Option<f32>,
+    /// Box the end effector must stay in, in the robot's base frame.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<WorkspaceBounds>,
+    /// Area of the map the base may drive in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geofence: Option<Geofence>,
+}
+
+impl SafetyConfig {
+    /// What is wrong with the limits: speeds that are not positive, a cap
+    /// set without its twin, or bounds whose minimum is not below their
+    /// maximum.
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (name, value) in [
+            ("max_linear_mps", self.max_linear_mps),
+            ("max_angular_rps", self.max_angular_rps),
+        ] {
+            if let Some(v) = value
+                && !(v.is_finite() && v > 0.0)
+            {
+                problems.push(format!("safety.{name} {v} is not a positive speed"));
+            }
+        }
+        if self.max_linear_mps.is_some() != self.max_angular_rps.is_some() {
+            problems.push("set both safety.max_linear_mps and max_angular_rps, or neither".to_string());
+        }
+        let mut bounds = Vec::new();
+        if let Some(b) = self.workspace {
+            bounds.extend([
+                ("workspace", 'x', b.min_x, b.max_x),
+                ("workspace", 'y', b.min_y, b.max_y),
+                ("workspace", 'z', b.min_z, b.max_z),
+            ]);
+        }
+        if let Some(g) = self.geofence {
+            bounds.extend([("geofence", 'x', g.min_x, g.max_x), ("geofence", 'y', g.min_y, g.max_y)]);
+        }
+        for (section, axis, min, max) in bounds {
+            if !(min.is_finite() && max.is_finite()) || min >= max {
+                problems.push(format!("safety.{section}: min_{axis} {min} is not below max_{axis} {max}"));
+            }
+        }
+        problems
+    }
+}
+
+/// `[safety.workspace]`, in metres.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WorkspaceBounds {
+    pub min_x: f32,
+    pub max_x: f32,
+    pub min_y: f32,
+    pub max_y: f32,
+    pub min_z: f32,
+    pub max_z: f32,
+}
+
+/// `[safety.geofence]`: opposite corners of a rectangle in the map frame,
+/// in metres.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Geofence {
+    pub min_x: f32,
+    pub min_y: f32,
+    pub max_x: f32,
+    pub max_y: f32,
+}
+
+impl Geofence {
+    /// The rectangle spanned by two opposite corners, in either order.
+    pub fn from_corners((ax, ay): (f32, f32), (bx, by): (f32, f32)) -> Self {
+        Self {
+            min_x: ax.min(bx),
+            min_y: ay.min(by),
+            max_x: ax.max(bx),
+            max_y: ay.max(by),
+        }
+    }
+}
+
+/// The `[memory]` section: at-rest encryption of the agent's memory and the
+/// fleet task board.  Needs a build with the `sqlcipher` feature.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MemoryConfig {
+    /// Key `memory.db` and the task board are encrypted with; unset keeps
+    /// them plaintext.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<MemoryKey>,
+}
+
+/// `[memory] key`: either `{ passphrase = "..." }` or
+/// `{ keyring = { service = "mechos", account = "memory" } }`.
+///
+/// A passphrase in the file is served by the Cockpit's `/api/config` like
+/// the API keys; prefer the keyring, or `MECHOS_MEMORY_KEY`.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryKey {
+    Passphrase(String),
+    Keyring { service: String, account: String },
+}
+
+impl MemoryKey {
+    /// Where [`KeySource::resolve`] reads this key from.
+    pub fn source(&self) -> KeySource {
+        match self {
+            Self::Passphrase(passphrase) => KeySource::passphrase(passphrase.clone()),
+            Self::Keyring { service, account } => KeySource::keyring(service, account),
+        }
+    }
+}
+
+impl std::fmt::Debug for MemoryKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Passphrase(_) => f.write_str("Passphrase(<redacted>)"),
+            Self::Keyring { service, account } => f
+                .debug_struct("Keyring")
+                .field("service", service)
+                .field("account", account)
+                .finish(),
+        }
+    }
+}
+
 /// Persisted user configuration stored in `~/.mechos/config.toml`.
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -37,12 +269,30 @@ pub struct Config {
     #[serde(default = "default_webui_port")]
     pub webui_port: u16,
 
+    /// Address the MechOS Web UI listens on.  Defaults to `127.0.0.1`; set
+    /// `0.0.0.0` to serve other machines on the network.
+    #[serde(default = "default_webui_bind")]
+    pub webui_bind: IpAddr,
+
     /// HTTP port of the external camera server that serves JPEG frames at
     /// `http://localhost:{camera_port}/frame`.  Set to `0` to disable the
     /// camera feed (default).
     #[serde(default = "default_camera_port")]
     pub camera_port: u16,
 
+    /// Hardware adapter started by `/start`.
+    #[serde(default, deserialize_with = "adapter_section")]
+    pub adapter: AdapterConfig,
+
+    /// The simulator URL of older config files, moved to `adapter.url` on
+    /// load.
+    #[serde(default, skip_serializing)]
+    pub sim_url: String,
+
+    /// Speed, workspace and geofence limits.
+    #[serde(default)]
+    pub safety: SafetyConfig,
+
     /// Chosen AI provider.
     #[serde(default)]
     pub ai_provider: AiProvider,
@@ -63,6 +313,31 @@ pub struct Config {
     /// Anthropic API key.
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub anthropic_api_key: String,
+
+    /// SQLite file of the fleet task board; empty uses
+    /// `~/.mechos/tasks.db`.  Point robots at one shared file to share work.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub task_board_path: String,
+
+    /// Encryption of the memory and task board databases.
+    #[serde(default)]
+    pub memory: MemoryConfig,
+
+    /// Serve the Cockpit Web UI over HTTPS / `wss://` with this certificate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<mechos_middleware::TlsConfig>,
+
+    /// Bearer token of the Cockpit's safety-policy endpoint; empty disables
+    /// it.  Only read from `MECHOS_ADMIN_TOKEN`, never from or to the config
+    /// file, which the Cockpit itself serves at `/api/config`.
+    #[serde(skip)]
+    pub admin_token: String,
+
+    /// `(operator, token)` pairs Cockpit tabs authenticate HITL answers and
+    /// approvals with; empty lets tabs name their own operator.  Only read
+    /// from `MECHOS_OPERATOR_TOKENS`, like `admin_token`.
+    #[serde(skip)]
+    pub operator_tokens: Vec<(String, String)>,
 }
 
 impl std::fmt::Debug for Config {
@@ -70,7 +345,10 @@ impl std::fmt::Debug for Config {
         f.debug_struct("Config")
             .field("dashboard_port", &self.dashboard_port)
             .field("webui_port", &self.webui_port)
+            .field("webui_bind", &self.webui_bind)
             .field("camera_port", &self.camera_port)
+            .field("adapter", &self.adapter)
+            .field("safety", &self.safety)
             .field("ai_provider", &self.ai_provider)
             .field("active_model", &self.active_model)
             .field("ollama_url", &self.ollama_url)
@@ -82,6 +360,17 @@ impl std::fmt::Debug for Config {
                 "anthropic_api_key",
                 if self.anthropic_api_key.is_empty() { &"<not set>" } else { &"<redacted>" },
             )
+            .field("task_board_path", &self.task_board_path)
+            .field("memory", &self.memory)
+            .field("tls", &self.tls)
+            .field(
+                "admin_token",
+                if self.admin_token.is_empty() { &"<not set>" } else { &"<redacted>" },
+            )
+            .field(
+                "operator_tokens",
+                &self.operator_tokens.iter().map(|(operator, _)| operator).collect::<Vec<_>>(),
+            )
             .finish()
     }
 }
@@ -92,6 +381,9 @@ fn default_dashboard_port() -> u16 {
 fn default_webui_port() -> u16 {
     8080
 }
+fn default_webui_bind() -> IpAddr {
+    IpAddr::V4(Ipv4Addr::LOCALHOST)
+}
 fn default_camera_port() -> u16 {
     0
 }
@@ -107,16 +399,43 @@ impl Default for Config {
         Self {
             dashboard_port: default_dashboard_port(),
             webui_port: default_webui_port(),
+            webui_bind: default_webui_bind(),
             camera_port: default_camera_port(),
+            adapter: AdapterConfig::default(),
+            sim_url: String::new(),
+            safety: SafetyConfig::default(),
             ai_provider: AiProvider::default(),
             active_model: default_model(),
             ollama_url: default_ollama_url(),
             openai_api_key: String::new(),
             anthropic_api_key: String::new(),
+            task_board_path: String::new(),
+            memory: MemoryConfig::default(),
+            tls: None,
+            admin_token: String::new(),
+            operator_tokens: Vec::new(),
         }
     }
 }
 
+impl Config {
+    /// The fleet task board's database: `task_board_path`, or
+    /// `~/.mechos/tasks.db`.
+    pub fn task_board(&self) -> PathBuf {
+        if self.task_board_path.is_empty() {
+            state_dir().join("tasks.db")
+        } else {
+            PathBuf::from(&self.task_board_path)
+        }
+    }
+
+    /// Where the memory and task board databases' key comes from, if they
+    /// are encrypted.
+    pub fn memory_key(&self) -> Option<KeySource> {
+        self.memory.key.as_ref().map(MemoryKey::source)
+    }
+}
+
 /// Return the path to `~/.mechos/config.toml`.
 pub fn config_path() -> PathBuf {
     config_path_for_home(
@@ -126,6 +445,21 @@ pub fn config_path() -> PathBuf {
     )
 }
 
+/// Return `~/.mechos`, which also holds the databases, the PID file and the
+/// daemon log.
+pub fn state_dir() -> PathBuf {
+    config_path()
+        .parent()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// Return `~/.mechos/capabilities.json`, the agent's capability grants as
+/// changed by `/caps`.
+pub fn capabilities_path() -> PathBuf {
+    state_dir().join("capabilities.json")
+}
+
 /// Build the config path relative to the given home directory.
 /// Extracted for testability without mutating environment variables.
 pub(crate) fn config_path_for_home(home: &str) -> PathBuf {
@@ -146,6 +480,9 @@ pub(crate) fn load_from(path: &PathBuf) -> Result<Option<Config>, String> {
         .map_err(|e| format!("Failed to read config at {}: {}", path.display(), e))?;
     let mut cfg: Config = toml::from_str(&raw)
         .map_err(|e| format!("Failed to parse config: {}", e))?;
+    if cfg.adapter.url.is_empty() {
+        cfg.adapter.url = std::mem::take(&mut cfg.sim_url);
+    }
     apply_env_overrides(&mut cfg);
     Ok(Some(cfg))
 }
@@ -160,9 +497,14 @@ pub(crate) fn load_from(path: &PathBuf) -> Result<Option<Config>, String> {
 /// | `MECHOS_MODEL` | `active_model` |
 /// | `MECHOS_DASHBOARD_PORT` | `dashboard_port` |
 /// | `MECHOS_WEBUI_PORT` | `webui_port` |
+/// | `MECHOS_WEBUI_BIND` | `webui_bind` |
 /// | `MECHOS_CAMERA_PORT` | `camera_port` |
 /// | `MECHOS_OPENAI_API_KEY` | `openai_api_key` |
 /// | `MECHOS_ANTHROPIC_API_KEY` | `anthropic_api_key` |
+/// | `MECHOS_TASK_BOARD` | `task_board_path` |
+/// | `MECHOS_MEMORY_KEY` | `memory.key`, as a passphrase |
+/// | `MECHOS_ADMIN_TOKEN` | `admin_token` |
+/// | `MECHOS_OPERATOR_TOKENS` | `operator_tokens`, as `alice=<token>,bob=<token>` |
 ///
 /// Using environment variables for API keys is the recommended approach for
 /// production deployments – it avoids storing secrets in the config file on
@@ -182,6 +524,10 @@ pub fn apply_env_overrides(cfg: &mut Config) {
         && let Ok(port) = v.parse::<u16>() {
             cfg.webui_port = port;
         }
+    if let Ok(v) = std::env::var("MECHOS_WEBUI_BIND")
+        && let Ok(address) = v.parse::<IpAddr>() {
+            cfg.webui_bind = address;
+        }
     if let Ok(v) = std::env::var("MECHOS_CAMERA_PORT")
         && let Ok(port) = v.parse::<u16>() {
             cfg.camera_port = port;
@@ -192,6 +538,23 @@ pub fn apply_env_overrides(cfg: &mut Config) {
     if let Ok(v) = std::env::var("MECHOS_ANTHROPIC_API_KEY") {
         cfg.anthropic_api_key = v;
     }
+    if let Ok(v) = std::env::var("MECHOS_TASK_BOARD") {
+        cfg.task_board_path = v;
+    }
+    if let Ok(v) = std::env::var("MECHOS_MEMORY_KEY") {
+        cfg.memory.key = Some(MemoryKey::Passphrase(v));
+    }
+    if let Ok(v) = std::env::var("MECHOS_ADMIN_TOKEN") {
+        cfg.admin_token = v;
+    }
+    if let Ok(v) = std::env::var("MECHOS_OPERATOR_TOKENS") {
+        cfg.operator_tokens = v
+            .split(',')
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(operator, token)| (operator.trim().to_string(), token.trim().to_string()))
+            .filter(|(operator, token)| !operator.is_empty() && !token.is_empty())
+            .collect();
+    }
 }
 
 /// Save the config to disk, creating `~/.mechos/` if necessary.
@@ -340,6 +703,74 @@ mod tests {
         unsafe { std::env::remove_var("MECHOS_DASHBOARD_PORT") };
     }
 
+    #[test]
+    fn adapter_defaults_to_the_dashboard() {
+        let cfg: Config = toml::from_str("").unwrap();
+        assert_eq!(cfg.adapter.kind, AdapterChoice::DashboardSim);
+        let cfg: Config =
+            toml::from_str("adapter = \"gazebo\"\nsim_url = \"ws://sim:9002\"").unwrap();
+        assert_eq!(cfg.adapter.kind, AdapterChoice::Gazebo);
+        assert_eq!(cfg.sim_url, "ws://sim:9002");
+    }
+
+    #[test]
+    fn legacy_adapter_settings_move_to_the_adapter_section() {
+        let dir = tempfile::tempdir().expect("tmp dir");
+        let path = config_path_for_home(&dir.path().to_string_lossy());
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "adapter = \"webots\"\nsim_url = \"ws://sim:1234\"\n").unwrap();
+
+        let cfg = load_from(&path).unwrap().unwrap();
+        assert_eq!(cfg.adapter.kind, AdapterChoice::Webots);
+        assert_eq!(cfg.adapter.url, "ws://sim:1234");
+        let saved = toml::to_string(&cfg).unwrap();
+        assert!(saved.contains("[adapter]") && !saved.contains("sim_url"));
+    }
+
+    #[test]
+    fn adapter_and_safety_sections_roundtrip() {
+        let dir = tempfile::tempdir().expect("tmp dir");
+        let path = config_path_for_home(&dir.path().to_string_lossy());
+        let cfg = Config {
+            adapter: AdapterConfig {
+                kind: AdapterChoice::Mqtt,
+                mqtt_host: "broker.lan".to_string(),
+                ..AdapterConfig::default()
+            },
+            safety: SafetyConfig {
+                max_linear_mps: Some(0.4),
+                max_angular_rps: Some(0.8),
+                workspace: None,
+                geofence: Some(Geofence::from_corners((5.0, -2.0), (-1.0, 3.0))),
+            },
+            ..Default::default()
+        };
+        save_to(&cfg, &path).expect("save");
+
+        let loaded = load_from(&path).expect("load ok").expect("some");
+        assert_eq!(loaded.adapter, cfg.adapter);
+        assert_eq!(loaded.safety, cfg.safety);
+        let fence = loaded.safety.geofence.unwrap();
+        assert_eq!((fence.min_x, fence.max_x, fence.min_y, fence.max_y), (-1.0, 5.0, -2.0, 3.0));
+        assert!(toml::from_str::<Config>("[adapter]\nkind = \"can\"").is_err());
+    }
+
+    #[test]
+    fn webui_bind_defaults_to_loopback() {
+        let cfg: Config = toml::from_str("").unwrap();
+        assert!(cfg.webui_bind.is_loopback());
+        let cfg: Config = toml::from_str(r#"webui_bind = "0.0.0.0""#).unwrap();
+        assert_eq!(cfg.webui_bind, IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+    }
+
+    #[test]
+    fn task_board_defaults_to_the_state_dir() {
+        let cfg = Config::default();
+        assert_eq!(cfg.task_board(), state_dir().join("tasks.db"));
+        let cfg: Config = toml::from_str(r#"task_board_path = "/srv/fleet/tasks.db""#).unwrap();
+        assert_eq!(cfg.task_board(), PathBuf::from("/srv/fleet/tasks.db"));
+    }
+
     #[test]
     fn apply_env_overrides_ignores_invalid_port() {
         // SAFETY: single-threaded test; no data races on env vars.
@@ -406,4 +837,60 @@ mod tests {
         assert_eq!(cfg.camera_port, 0);
         unsafe { std::env::remove_var("MECHOS_CAMERA_PORT") };
     }
+
+    #[test]
+    fn admin_token_comes_only_from_the_environment() {
+        let mut cfg: Config = toml::from_str(r#"admin_token = "from-file""#).unwrap();
+        assert!(cfg.admin_token.is_empty());
+        // SAFETY: single-threaded test; no data races on env vars.
+        unsafe { std::env::set_var("MECHOS_ADMIN_TOKEN", "from-env") };
+        apply_env_overrides(&mut cfg);
+        assert_eq!(cfg.admin_token, "from-env");
+        assert!(!toml::to_string(&cfg).unwrap().contains("from-env"));
+        unsafe { std::env::remove_var("MECHOS_ADMIN_TOKEN") };
+    }
+
+    #[test]
+    fn operator_tokens_come_from_the_environment() {
+        let mut cfg = Config::default();
+        // SAFETY: single-threaded test; no data races on env vars.
+        unsafe { std::env::set_var("MECHOS_OPERATOR_TOKENS", "alice=t-1, bob = t-2,broken,eve=") };
+        apply_env_overrides(&mut cfg);
+        assert_eq!(
+            cfg.operator_tokens,
+            [
+                ("alice".to_string(), "t-1".to_string()),
+                ("bob".to_string(), "t-2".to_string())
+            ]
+        );
+        assert!(!format!("{cfg:?}").contains("t-1"));
+        unsafe { std::env::remove_var("MECHOS_OPERATOR_TOKENS") };
+    }
+
+    #[test]
+    fn memory_key_takes_a_passphrase_or_a_keyring_entry() {
+        assert_eq!(Config::default().memory_key(), None);
+
+        let cfg: Config = toml::from_str("[memory]\nkey = { passphrase = \"hunter2\" }").unwrap();
+        assert_eq!(cfg.memory_key(), Some(KeySource::passphrase("hunter2")));
+        assert!(!format!("{cfg:?}").contains("hunter2"));
+
+        let cfg: Config = toml::from_str(
+            "[memory]\nkey = { keyring = { service = \"mechos\", account = \"memory\" } }",
+        )
+        .unwrap();
+        assert_eq!(cfg.memory_key(), Some(KeySource::keyring("mechos", "memory")));
+        let saved = toml::to_string_pretty(&cfg).unwrap();
+        assert_eq!(toml::from_str::<Config>(&saved).unwrap().memory, cfg.memory);
+    }
+
+    #[test]
+    fn apply_env_overrides_sets_the_memory_passphrase() {
+        // SAFETY: single-threaded test; no data races on env vars.
+        unsafe { std::env::set_var("MECHOS_MEMORY_KEY", "from-env") };
+        let mut cfg = Config::default();
+        apply_env_overrides(&mut cfg);
+        assert_eq!(cfg.memory_key(), Some(KeySource::passphrase("from-env")));
+        unsafe { std::env::remove_var("MECHOS_MEMORY_KEY") };
+    }
 }
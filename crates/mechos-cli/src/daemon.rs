@@ -0,0 +1,333 @@
+//! Headless mode – `mechos run`, for running MechOS as a service.
+//!
+//! ```text
+//! mechos run [--config <path>] [--pid-file <path>] [--daemonize] [--log-file <path>]
+//! ```
+//!
+//! Boots the same [`Stack`] as the REPL's `/start`, without the REPL or the
+//! first-run wizard:
+//!
+//! * `--config` reads the configuration from `<path>` instead of
+//!   `~/.mechos/config.toml`; without it a missing file means defaults.
+//! * The process ID is written to `--pid-file` (default
+//!   `~/.mechos/mechos.pid`) and removed on exit.  A PID file naming a live
+//!   process refuses the start.
+//! * `SIGTERM` or `SIGINT` publishes an `EmergencyStop` fault, halts the
+//!   robot and stops every service before exiting.
+//! * Under systemd (`NOTIFY_SOCKET` set) readiness and shutdown are
+//!   reported with `sd_notify`, for `Type=notify` units:
+//!
+//! ```ini
+//! [Service]
+//! Type=notify
+//! ExecStart=/usr/local/bin/mechos run --config /etc/mechos/config.toml --pid-file /run/mechos/mechos.pid
+//! ```
+//!
+//! * `--daemonize` starts a detached copy of itself in the background, its
+//!   output appended to `--log-file` (default `~/.mechos/mechos.log`), and
+//!   returns.  Leave it off under systemd.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use clap::Args;
+use mechos_middleware::{EventBus, Topic};
+use mechos_types::{Event, EventPayload, FaultCode};
+use tracing::{info, warn};
+
+use crate::config::{self, Config};
+use crate::stack::Stack;
+
+/// How often the daemon checks the stack's status while waiting for a
+/// signal.
+const STATUS_POLL: Duration = Duration::from_millis(500);
+
+/// `mechos run` arguments.
+#[derive(Debug, Clone, PartialEq, Args)]
+pub struct RunOptions {
+    /// Read the configuration from this file instead of
+    /// `~/.mechos/config.toml`.
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+    /// Where to write the process ID.
+    #[arg(long, value_name = "PATH", default_value_os_t = config::state_dir().join("mechos.pid"))]
+    pub pid_file: PathBuf,
+    /// Relaunch detached in the background.
+    #[arg(long)]
+    pub daemonize: bool,
+    /// Output of the detached process.
+    #[arg(long, value_name = "PATH", default_value_os_t = config::state_dir().join("mechos.log"))]
+    pub log_file: PathBuf,
+}
+
+/// Run MechOS headless until `SIGTERM` / `SIGINT`.
+pub fn run(options: RunOptions) -> Result<(), String> {
+    if options.daemonize {
+        return detach(&options);
+    }
+    let cfg = load_config(options.config.as_deref())?;
+    let pid_file = PidFile::create(&options.pid_file)?;
+
+    let (stop_tx, stop_rx) = mpsc::channel();
+    ctrlc::set_handler(move || {
+        let _ = stop_tx.send(());
+    })
+    .map_err(|e| format!("cannot install the signal handler: {e}"))?;
+
+    let stack = boot(&cfg)?;
+    info!(pid = std::process::id(), "MechOS running headless");
+    notify_systemd("READY=1\nSTATUS=MechOS running");
+
+    while let Err(mpsc::RecvTimeoutError::Timeout) = stop_rx.recv_timeout(STATUS_POLL) {
+        for status in stack.poll_status() {
+            warn!(%status, "stack service");
+        }
+    }
+
+    info!("termination signal received; stopping MechOS");
+    notify_systemd("STOPPING=1");
+    emergency_stop(stack.bus(), "EMERGENCY_STOP: service shutdown");
+    stack.stop("service shutdown");
+    drop(pid_file);
+    Ok(())
+}
+
+/// Load `path`, or `~/.mechos/config.toml` falling back to defaults.
+pub(crate) fn load_config(path: Option<&Path>) -> Result<Config, String> {
+    match path {
+        Some(path) => config::load_from(&path.to_path_buf())?
+            .ok_or_else(|| format!("no config file at {}", path.display())),
+        None => match config::load()? {
+            Some(cfg) => Ok(cfg),
+            None => {
+                info!(path = %config::config_path().display(), "no config file; using defaults");
+                let mut cfg = Config::default();
+                config::apply_env_overrides(&mut cfg);
+                Ok(cfg)
+            }
+        },
+    }
+}
+
+/// Start every service of the stack described by `cfg`.
+fn boot(cfg: &Config) -> Result<Stack, String> {
+    let dir = config::state_dir();
+    fs::create_dir_all(&dir).map_err(|e| format!("cannot create {}: {e}", dir.display()))?;
+    let bus = std::sync::Arc::new(EventBus::new(256));
+    let mut stack = Stack::new(bus).map_err(|e| format!("service runtime: {e}"))?;
+    let aborted = |stack: Stack, error: String| {
+        stack.stop("boot aborted");
+        error
+    };
+
+    let url = match stack.start_adapter(cfg) {
+        Ok(url) => url,
+        Err(e) => return Err(aborted(stack, e)),
+    };
+    info!(adapter = %cfg.adapter.kind, %url, "adapter connecting");
+    let watchdog = stack.start_watchdog();
+    stack.start_cockpit(cfg);
+    info!(bind = %cfg.webui_bind, port = cfg.webui_port, "Cockpit Web UI started");
+    if let Err(e) = stack.start_agent_loop(
+        cfg,
+        dir.join("memory.db").to_string_lossy().into_owned(),
+        dir.join("transcripts.db").to_string_lossy().into_owned(),
+        watchdog,
+    ) {
+        return Err(aborted(stack, e.to_string()));
+    }
+    info!(model = %cfg.active_model, "agent loop started");
+    Ok(stack)
+}
+
+/// Publish an `EmergencyStop` fault on `bus` so every listener stops.
+fn emergency_stop(bus: &EventBus, message: &str) {
+    let event = Event {
+        id: uuid::Uuid::new_v4(),
+        timestamp: chrono::Utc::now(),
+        source: "mechos-cli::daemon".to_string(),
+        payload: EventPayload::HardwareFault {
+            component: "cli".to_string(),
+            code: FaultCode::EmergencyStop,
+            message: message.to_string(),
+        },
+        trace_id: None,
+        correlation_id: None,
+    };
+    if let Err(e) = bus.publish_to(Topic::SystemAlerts, event) {
+        warn!(error = %e, "could not publish the EmergencyStop");
+    }
+}
+
+/// Start `mechos run` again, detached, without `--daemonize`.
+fn detach(options: &RunOptions) -> Result<(), String> {
+    let exe = std::env::current_exe().map_err(|e| format!("cannot locate mechos: {e}"))?;
+    if let Some(dir) = options.log_file.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("cannot create {}: {e}", dir.display()))?;
+    }
+    let log = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&options.log_file)
+        .map_err(|e| format!("cannot open {}: {e}", options.log_file.display()))?;
+    let log_err = log
+        .try_clone()
+        .map_err(|e| format!("cannot open {}: {e}", options.log_file.display()))?;
+
+    let mut command = Command::new(exe);
+    command.arg("run").arg("--pid-file").arg(&options.pid_file);
+    if let Some(config) = &options.config {
+        command.arg("--config").arg(config);
+    }
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::from(log))
+        .stderr(Stdio::from(log_err));
+    // Leave the terminal's process group so its Ctrl-C does not reach us.
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    let child = command
+        .spawn()
+        .map_err(|e| format!("cannot start the background process: {e}"))?;
+    println!(
+        "MechOS running in the background (pid {}); logs in {}",
+        child.id(),
+        options.log_file.display()
+    );
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// PID file
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// This process's PID file, removed when dropped.
+#[derive(Debug)]
+struct PidFile {
+    path: PathBuf,
+}
+
+impl PidFile {
+    /// Write our PID to `path`, unless it names a process still running.
+    fn create(path: &Path) -> Result<Self, String> {
+        if let Some(pid) = running_pid(path) {
+            return Err(format!(
+                "MechOS is already running (pid {pid}, see {})",
+                path.display()
+            ));
+        }
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("cannot create {}: {e}", dir.display()))?;
+        }
+        fs::write(path, format!("{}\n", std::process::id()))
+            .map_err(|e| format!("cannot write {}: {e}", path.display()))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+        })
+    }
+}
+
+impl Drop for PidFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// The PID in the PID file at `path`, if that process is still running.
+/// Only Linux can tell; elsewhere a stale PID file is always overwritten.
+pub(crate) fn running_pid(path: &Path) -> Option<u32> {
+    let pid = fs::read_to_string(path).ok()?.trim().parse::<u32>().ok()?;
+    Path::new("/proc")
+        .join(pid.to_string())
+        .exists()
+        .then_some(pid)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// systemd notification
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Report `state` (e.g. `"READY=1"`) to systemd when it set `NOTIFY_SOCKET`.
+fn notify_systemd(state: &str) {
+    let Ok(socket) = std::env::var("NOTIFY_SOCKET") else {
+        return;
+    };
+    if let Err(e) = notify(&socket, state) {
+        warn!(socket = %socket, error = %e, "sd_notify failed");
+    }
+}
+
+/// Send one `sd_notify` datagram to `socket`; a leading `@` names an
+/// abstract socket.
+#[cfg(unix)]
+fn notify(socket: &str, state: &str) -> std::io::Result<()> {
+    use std::os::unix::net::UnixDatagram;
+
+    let sender = UnixDatagram::unbound()?;
+    match socket.strip_prefix('@') {
+        #[cfg(target_os = "linux")]
+        Some(name) => {
+            use std::os::linux::net::SocketAddrExt;
+            let addr = std::os::unix::net::SocketAddr::from_abstract_name(name)?;
+            sender.send_to_addr(state.as_bytes(), &addr)?;
+        }
+        _ => {
+            sender.send_to(state.as_bytes(), socket)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn notify(_socket: &str, _state: &str) -> std::io::Result<()> {
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pid_file_is_exclusive_and_removed_on_drop() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run").join("mechos.pid");
+        let pid_file = PidFile::create(&path).unwrap();
+        let written = fs::read_to_string(&path).unwrap();
+        assert_eq!(written.trim(), std::process::id().to_string());
+
+        #[cfg(target_os = "linux")]
+        assert!(PidFile::create(&path).is_err(), "our own PID is running");
+
+        drop(pid_file);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn missing_explicit_config_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load_config(Some(&dir.path().join("absent.toml"))).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn readiness_reaches_the_notify_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notify.sock");
+        let systemd = std::os::unix::net::UnixDatagram::bind(&path).unwrap();
+
+        notify(path.to_str().unwrap(), "READY=1").unwrap();
+        let mut buf = [0u8; 64];
+        let n = systemd.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"READY=1");
+    }
+}
@@ -0,0 +1,544 @@
+//! `mechos doctor` – checks that this machine can run MechOS.
+//!
+//! Every check ends in a [`Health`]:
+//!
+//! | Check | Fails when |
+//! |---|---|
+//! | config | the file does not parse, or its values clash (see [`validate`]) |
+//! | state dir | `~/.mechos` cannot be created or written |
+//! | sqlite | a database there or the task board is not one of ours, is read-only, or does not open with the `[memory] key` |
+//! | ollama | Ollama is unreachable or lacks the active model |
+//! | ai provider | a cloud provider is selected without an API key |
+//! | adapter | the adapter has no endpoint (unreachable or unplugged is only a warning) |
+//! | cockpit port | never; a port held by another program is a warning |
+//! | tls | the certificate or key is missing and not generated |
+//!
+//! The [`Report`] prints one line per check and a summary; `mechos doctor`
+//! exits non-zero when a check failed.
+
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::time::Duration;
+
+use colored::Colorize;
+use mechos_memory::encryption::KeySource;
+use mechos_memory::episodic::EpisodicStore;
+use mechos_memory::task_board::TaskBoard;
+use mechos_memory::transcript::TranscriptStore;
+
+use crate::cli::DoctorArgs;
+use crate::config::{self, AdapterChoice, AiProvider, Config};
+use crate::{daemon, ollama, stack};
+
+/// How long the adapter's endpoint gets to accept a connection.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Shorter admin tokens are flagged as guessable.
+const MIN_ADMIN_TOKEN_LEN: usize = 16;
+
+/// The databases `/start` and `mechos run` keep in `~/.mechos`.
+const DATABASES: [&str; 2] = ["memory.db", "transcripts.db"];
+
+/// Outcome of one check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Health {
+    Pass,
+    /// Works, but probably not as intended.
+    Warn,
+    /// MechOS will not start or will misbehave.
+    Fail,
+}
+
+/// One line of the report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    pub name: &'static str,
+    pub health: Health,
+    pub detail: String,
+}
+
+impl Check {
+    fn new(name: &'static str, health: Health, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            health,
+            detail: detail.into(),
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mark = match self.health {
+            Health::Pass => "PASS".green(),
+            Health::Warn => "WARN".yellow(),
+            Health::Fail => "FAIL".red().bold(),
+        };
+        write!(f, "  {mark} {:<14} {}", self.name.bold(), self.detail)
+    }
+}
+
+/// Every check, in the order they ran.
+#[derive(Debug, Default)]
+pub struct Report {
+    pub checks: Vec<Check>,
+}
+
+impl Report {
+    /// Record `check` and print it.
+    fn push(&mut self, check: Check) {
+        println!("{check}");
+        self.checks.push(check);
+    }
+
+    /// Number of checks that ended in `health`.
+    pub fn count(&self, health: Health) -> usize {
+        self.checks.iter().filter(|c| c.health == health).count()
+    }
+}
+
+/// Run every check against the config at `args.config` (default
+/// `~/.mechos/config.toml`); `Err` when one failed.
+pub fn run(args: DoctorArgs) -> Result<(), String> {
+    println!("{}", "MechOS Doctor".bold().underline());
+    let mut report = Report::default();
+
+    let path = args.config.unwrap_or_else(config::config_path);
+    let cfg = match config::load_from(&path) {
+        Ok(Some(cfg)) => {
+            report.push(Check::new(
+                "config",
+                Health::Pass,
+                format!("loaded from {}", path.display()),
+            ));
+            cfg
+        }
+        Ok(None) => {
+            report.push(Check::new(
+                "config",
+                Health::Warn,
+                format!("{} missing; using defaults", path.display()),
+            ));
+            let mut cfg = Config::default();
+            config::apply_env_overrides(&mut cfg);
+            cfg
+        }
+        Err(e) => {
+            report.push(Check::new("config", Health::Fail, e));
+            Config::default()
+        }
+    };
+    for check in validate(&cfg) {
+        report.push(check);
+    }
+
+    let dir = config::state_dir();
+    report.push(check_state_dir(&dir));
+    let key = cfg.memory_key();
+    for name in DATABASES {
+        report.push(check_database(&dir.join(name), key.as_ref()));
+    }
+    report.push(check_database(&cfg.task_board(), key.as_ref()));
+    report.push(check_ai_provider(&cfg));
+    report.push(check_adapter(&cfg));
+
+    let running = daemon::running_pid(&dir.join("mechos.pid"));
+    report.push(match running {
+        Some(pid) => Check::new("daemon", Health::Pass, format!("running (pid {pid})")),
+        None => Check::new("daemon", Health::Pass, "not running"),
+    });
+    report.push(check_cockpit_port(&cfg, running.is_some()));
+    if let Some(check) = check_tls(&cfg) {
+        report.push(check);
+    }
+
+    let (failed, warned) = (report.count(Health::Fail), report.count(Health::Warn));
+    println!(
+        "\n  {} passed, {} warnings, {} failed",
+        report.count(Health::Pass).to_string().green(),
+        warned.to_string().yellow(),
+        failed.to_string().red()
+    );
+    match failed {
+        0 => Ok(()),
+        1 => Err("1 check failed".to_string()),
+        n => Err(format!("{n} checks failed")),
+    }
+}
+
+/// Values that parse but cannot work together.  Returns a single `Pass`
+/// when there is nothing to report.
+pub fn validate(cfg: &Config) -> Vec<Check> {
+    let mut checks = Vec::new();
+    let mut fail = |detail: String| checks.push(Check::new("config", Health::Fail, detail));
+
+    if cfg.webui_port == 0 {
+        fail("webui_port is 0".to_string());
+    }
+    if cfg.adapter.kind == AdapterChoice::DashboardSim {
+        if cfg.dashboard_port == 0 {
+            fail("dashboard_port is 0".to_string());
+        } else if cfg.dashboard_port == cfg.webui_port {
+            fail(format!(
+                "dashboard_port and webui_port are both {}",
+                cfg.webui_port
+            ));
+        }
+    }
+    if cfg.camera_port != 0 && cfg.camera_port == cfg.webui_port {
+        fail(format!(
+            "camera_port and webui_port are both {}",
+            cfg.webui_port
+        ));
+    }
+    for problem in cfg.safety.problems() {
+        fail(problem);
+    }
+    if cfg.active_model.trim().is_empty() {
+        fail("active_model is empty".to_string());
+    }
+    if cfg.ai_provider == AiProvider::Ollama
+        && !(cfg.ollama_url.starts_with("http://") || cfg.ollama_url.starts_with("https://"))
+    {
+        fail(format!(
+            "ollama_url {:?} is not an http(s) URL",
+            cfg.ollama_url
+        ));
+    }
+
+    if cfg.admin_token.is_empty() {
+        checks.push(Check::new(
+            "admin token",
+            Health::Warn,
+            "not set; Cockpit policy endpoints are disabled",
+        ));
+    } else if cfg.admin_token.len() < MIN_ADMIN_TOKEN_LEN {
+        checks.push(Check::new(
+            "admin token",
+            Health::Warn,
+            format!("shorter than {MIN_ADMIN_TOKEN_LEN} characters"),
+        ));
+    }
+    if !checks.iter().any(|c| c.name == "config") {
+        checks.insert(
+            0,
+            Check::new("config", Health::Pass, "values are consistent"),
+        );
+    }
+    checks
+}
+
+/// `dir` can be created and written.
+fn check_state_dir(dir: &Path) -> Check {
+    let probe = dir.join(".doctor");
+    let writable = fs::create_dir_all(dir)
+        .and_then(|()| fs::File::create(&probe)?.write_all(b"ok"))
+        .and_then(|()| fs::remove_file(&probe));
+    match writable {
+        Ok(()) => Check::new(
+            "state dir",
+            Health::Pass,
+            format!("{} is writable", dir.display()),
+        ),
+        Err(e) => Check::new("state dir", Health::Fail, format!("{}: {e}", dir.display())),
+    }
+}
+
+/// The database at `path` opens as its store and is writable.  A missing
+/// one is created on the first `/start`.  The memory store and the task
+/// board are opened with `key`, if one is set; transcripts are never
+/// encrypted.
+pub fn check_database(path: &Path, key: Option<&KeySource>) -> Check {
+    let shown = path.display();
+    if !path.exists() {
+        return Check::new("sqlite", Health::Pass, format!("{shown} will be created"));
+    }
+    if let Err(e) = fs::OpenOptions::new().append(true).open(path) {
+        return Check::new(
+            "sqlite",
+            Health::Fail,
+            format!("{shown} is not writable: {e}"),
+        );
+    }
+    let file = path.to_string_lossy();
+    let name = path.file_name().and_then(|n| n.to_str());
+    let key = match key {
+        Some(source) if name != Some("transcripts.db") => match source.resolve() {
+            Ok(key) => Some(key),
+            Err(e) => {
+                return Check::new(
+                    "sqlite",
+                    Health::Fail,
+                    format!("{shown}: cannot resolve the memory key: {e}"),
+                );
+            }
+        },
+        _ => None,
+    };
+    let opened = match (name, &key) {
+        (Some("memory.db"), Some(key)) => EpisodicStore::open_encrypted(&file, key)
+            .map(drop)
+            .map_err(|e| e.to_string()),
+        (Some("memory.db"), None) => EpisodicStore::open(&file)
+            .map(drop)
+            .map_err(|e| e.to_string()),
+        (Some("transcripts.db"), _) => TranscriptStore::open(&file)
+            .map(drop)
+            .map_err(|e| e.to_string()),
+        (_, Some(key)) => TaskBoard::open_encrypted(&file, key)
+            .map(drop)
+            .map_err(|e| e.to_string()),
+        (_, None) => TaskBoard::open(&file).map(drop).map_err(|e| e.to_string()),
+    };
+    match opened {
+        Ok(()) => Check::new("sqlite", Health::Pass, format!("{shown} opens read-write")),
+        Err(e) => Check::new("sqlite", Health::Fail, format!("{shown}: {e}")),
+    }
+}
+
+/// Ollama serves the active model, or the selected cloud provider has a key.
+fn check_ai_provider(cfg: &Config) -> Check {
+    let api_key = match cfg.ai_provider {
+        AiProvider::Ollama => None,
+        AiProvider::OpenAI => Some(&cfg.openai_api_key),
+        AiProvider::Anthropic => Some(&cfg.anthropic_api_key),
+    };
+    match api_key {
+        Some(key) if key.is_empty() => Check::new(
+            "ai provider",
+            Health::Fail,
+            format!("{} selected but no API key set", cfg.ai_provider),
+        ),
+        Some(_) => Check::new(
+            "ai provider",
+            Health::Pass,
+            format!("{} key set", cfg.ai_provider),
+        ),
+        None => match ollama::fetch_models(&cfg.ollama_url) {
+            Ok(models) => {
+                let installed = models.iter().any(|m| {
+                    m.name == cfg.active_model
+                        || m.name.strip_suffix(":latest") == Some(cfg.active_model.as_str())
+                });
+                if installed {
+                    Check::new(
+                        "ollama",
+                        Health::Pass,
+                        format!("{} serves {}", cfg.ollama_url, cfg.active_model),
+                    )
+                } else {
+                    Check::new(
+                        "ollama",
+                        Health::Fail,
+                        format!(
+                            "model {} not installed; run `ollama pull {}`",
+                            cfg.active_model, cfg.active_model
+                        ),
+                    )
+                }
+            }
+            Err(e) => Check::new("ollama", Health::Fail, e),
+        },
+    }
+}
+
+/// The adapter's endpoint is there: rosbridge, the simulator or the MQTT
+/// broker accepts connections, or the serial device exists.
+fn check_adapter(cfg: &Config) -> Check {
+    match stack::adapter_url(cfg) {
+        Ok(url) => match probe_adapter(cfg.adapter.kind, &url) {
+            Ok(()) => Check::new("adapter", Health::Pass, format!("{} at {url}", cfg.adapter.kind)),
+            // The adapter keeps retrying, so a simulator started later is fine.
+            Err(e) => Check::new("adapter", Health::Warn, format!("{url}: {e}")),
+        },
+        Err(e) => Check::new("adapter", Health::Fail, e),
+    }
+}
+
+/// Probe the endpoint `url` of an adapter of `kind`.
+fn probe_adapter(kind: AdapterChoice, url: &str) -> Result<(), String> {
+    if kind != AdapterChoice::Serial {
+        return reachable(url);
+    }
+    if Path::new(url).exists() {
+        Ok(())
+    } else {
+        Err("no such device".to_string())
+    }
+}
+
+/// Open a TCP connection to the host and port of the WebSocket `url`.
+fn reachable(url: &str) -> Result<(), String> {
+    let authority = url
+        .split_once("://")
+        .map_or(url, |(_, rest)| rest)
+        .split('/')
+        .next()
+        .unwrap_or_default();
+    let address = authority
+        .to_socket_addrs()
+        .map_err(|e| e.to_string())?
+        .next()
+        .ok_or_else(|| format!("{authority} does not resolve"))?;
+    TcpStream::connect_timeout(&address, PROBE_TIMEOUT)
+        .map(drop)
+        .map_err(|e| e.to_string())
+}
+
+/// The Cockpit can bind its address, unless the running daemon holds it.
+pub fn check_cockpit_port(cfg: &Config, daemon_running: bool) -> Check {
+    let (address, port) = (cfg.webui_bind, cfg.webui_port);
+    match TcpListener::bind((address, port)) {
+        Ok(_) => Check::new(
+            "cockpit port",
+            Health::Pass,
+            format!("{address}:{port} is free"),
+        ),
+        Err(_) if daemon_running => Check::new(
+            "cockpit port",
+            Health::Pass,
+            format!("{address}:{port} held by the running daemon"),
+        ),
+        Err(e) => Check::new(
+            "cockpit port",
+            Health::Warn,
+            format!("{address}:{port}: {e}"),
+        ),
+    }
+}
+
+/// The configured certificate and key exist or will be generated.
+fn check_tls(cfg: &Config) -> Option<Check> {
+    let tls = cfg.tls.as_ref()?;
+    let present = tls.cert_path.exists() && tls.key_path.exists();
+    Some(if present || tls.generate_self_signed {
+        Check::new(
+            "tls",
+            Health::Pass,
+            format!("certificate {}", tls.cert_path.display()),
+        )
+    } else {
+        Check::new(
+            "tls",
+            Health::Fail,
+            format!(
+                "{} or {} missing",
+                tls.cert_path.display(),
+                tls.key_path.display()
+            ),
+        )
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failures(checks: &[Check]) -> Vec<&str> {
+        checks
+            .iter()
+            .filter(|c| c.health == Health::Fail)
+            .map(|c| c.detail.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn default_config_is_consistent() {
+        let checks = validate(&Config::default());
+        assert!(failures(&checks).is_empty());
+        assert_eq!(checks[0].health, Health::Pass);
+    }
+
+    #[test]
+    fn clashing_ports_and_empty_model_fail() {
+        let cfg = Config {
+            dashboard_port: 8080,
+            webui_port: 8080,
+            active_model: " ".to_string(),
+            ollama_url: "localhost:11434".to_string(),
+            admin_token: "short".to_string(),
+            ..Default::default()
+        };
+        let checks = validate(&cfg);
+        assert_eq!(failures(&checks).len(), 3, "{checks:?}");
+        assert!(
+            checks
+                .iter()
+                .any(|c| c.name == "admin token" && c.health == Health::Warn)
+        );
+    }
+
+    #[test]
+    fn inconsistent_safety_limits_fail() {
+        let cfg = Config {
+            safety: config::SafetyConfig {
+                max_linear_mps: Some(-0.5),
+                max_angular_rps: None,
+                workspace: None,
+                geofence: Some(config::Geofence {
+                    min_x: 2.0,
+                    min_y: 0.0,
+                    max_x: 2.0,
+                    max_y: 5.0,
+                }),
+            },
+            ..Default::default()
+        };
+        let problems = validate(&cfg);
+        assert_eq!(failures(&problems).len(), 3, "{problems:?}");
+    }
+
+    #[test]
+    fn foreign_databases_fail() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = check_database(&dir.path().join("memory.db"), None);
+        assert_eq!(missing.health, Health::Pass);
+
+        let tasks = dir.path().join("tasks.db");
+        TaskBoard::open(&tasks.to_string_lossy()).unwrap();
+        assert_eq!(check_database(&tasks, None).health, Health::Pass);
+
+        let garbage = dir.path().join("transcripts.db");
+        fs::write(&garbage, b"not a database, just some text").unwrap();
+        assert_eq!(check_database(&garbage, None).health, Health::Fail);
+    }
+
+    #[test]
+    fn databases_are_checked_with_the_memory_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = KeySource::passphrase("correct horse");
+
+        // A plaintext board does not open with a key: SQLCipher rejects it
+        // as the wrong key, and plain SQLite refuses to encrypt at all.
+        let tasks = dir.path().join("tasks.db");
+        TaskBoard::open(&tasks.to_string_lossy()).unwrap();
+        assert_eq!(check_database(&tasks, Some(&key)).health, Health::Fail);
+
+        let transcripts = dir.path().join("transcripts.db");
+        TranscriptStore::open(&transcripts.to_string_lossy()).unwrap();
+        assert_eq!(check_database(&transcripts, Some(&key)).health, Health::Pass);
+
+        let missing = KeySource::keyring("mechos-doctor-test", "no-such-account");
+        let check = check_database(&tasks, Some(&missing));
+        assert_eq!(check.health, Health::Fail);
+        assert!(check.detail.contains("memory key"), "{}", check.detail);
+    }
+
+    #[test]
+    fn busy_cockpit_port_warns() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let cfg = Config {
+            webui_port: listener.local_addr().unwrap().port(),
+            ..Default::default()
+        };
+        assert_eq!(check_cockpit_port(&cfg, false).health, Health::Warn);
+        assert_eq!(check_cockpit_port(&cfg, true).health, Health::Pass);
+    }
+}
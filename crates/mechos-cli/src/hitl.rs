@@ -0,0 +1,263 @@
+//! Human-in-the-loop – answering the agent's `AskHuman` intents from the
+//! REPL.
+//!
+//! After dispatching [`HardwareIntent::AskHuman`] the agent loop parks until
+//! an [`EventPayload::HumanResponse`] arrives on the bus.  The Cockpit's HITL
+//! panel answers it from a browser; without one, [`watch_questions`] (run by
+//! `/start`) announces each question at the REPL prompt and queues it in the
+//! [`PendingQuestion`]s until the operator types the answer, which
+//! [`answer`] publishes for the oldest question.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use mechos_middleware::EventBus;
+use mechos_types::{Event, EventPayload, HardwareIntent, HumanAnswer};
+use tokio::sync::broadcast::error::RecvError;
+
+/// Source of the [`EventPayload::HumanResponse`]s typed into the REPL.
+const RESPONSE_SOURCE: &str = "mechos-cli::hitl";
+
+/// A question the agent asked with [`HardwareIntent::AskHuman`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Question {
+    /// The `question_id` answers must carry.
+    pub id: Option<String>,
+    pub text: String,
+    /// A camera frame to look at, as published in a `CameraFrame` event.
+    pub image_id: Option<String>,
+    /// The agent that asked.
+    pub asked_by: String,
+}
+
+impl Question {
+    /// The question carried by `event`, if it dispatches an `AskHuman`
+    /// intent.
+    pub fn from_event(event: &Event) -> Option<Self> {
+        let EventPayload::Intent(envelope) = &event.payload else {
+            return None;
+        };
+        let HardwareIntent::AskHuman {
+            question,
+            context_image_id,
+            question_id,
+        } = &envelope.intent
+        else {
+            return None;
+        };
+        Some(Self {
+            id: question_id.clone(),
+            text: question.clone(),
+            image_id: context_image_id.clone(),
+            asked_by: envelope.issued_by.clone(),
+        })
+    }
+}
+
+impl fmt::Display for Question {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} asks: {}", self.asked_by, self.text)?;
+        if let Some(image_id) = &self.image_id {
+            write!(f, " (image {image_id})")?;
+        }
+        Ok(())
+    }
+}
+
+/// The questions awaiting an answer, oldest first, shared between the REPL
+/// and [`watch_questions`].  Several agents, or one agent's queued contexts,
+/// can have questions open at once.
+#[derive(Debug, Clone, Default)]
+pub struct PendingQuestion(Arc<Mutex<VecDeque<Question>>>);
+
+impl PendingQuestion {
+    /// The oldest unanswered question, which the REPL answers next.
+    pub fn get(&self) -> Option<Question> {
+        self.lock().front().cloned()
+    }
+
+    /// Number of unanswered questions.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    fn push(&self, question: Question) {
+        self.lock().push_back(question);
+    }
+
+    /// Drop the question answered by `question_id`, or the oldest one for
+    /// an answer naming none.
+    fn remove(&self, question_id: Option<&str>) {
+        let mut questions = self.lock();
+        let index = match question_id {
+            Some(id) => questions.iter().position(|q| q.id.as_deref() == Some(id)),
+            None => (!questions.is_empty()).then_some(0),
+        };
+        if let Some(index) = index {
+            questions.remove(index);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<Question>> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Keep `pending` up to date with the questions asked on `bus`, calling
+/// `announce` for each new one, until the bus closes.  A `HumanResponse`
+/// from anywhere, e.g. the Cockpit, answers the question it names.
+pub async fn watch_questions(
+    bus: Arc<EventBus>,
+    pending: PendingQuestion,
+    mut announce: impl FnMut(&Question) + Send,
+) -> Result<(), String> {
+    let mut rx = bus.subscribe();
+    loop {
+        match rx.recv().await {
+            Ok(event) => {
+                if let Some(question) = Question::from_event(&event) {
+                    announce(&question);
+                    pending.push(question);
+                } else if let EventPayload::HumanResponse(answer) = &event.payload {
+                    pending.remove(answer.question_id.as_deref());
+                }
+            }
+            Err(RecvError::Lagged(_)) => {}
+            Err(RecvError::Closed) => return Err("event bus closed".to_string()),
+        }
+    }
+}
+
+/// Publish `text` on `bus` as the answer to the oldest pending question,
+/// which it returns.  The answer names the [`console_operator`], whose
+/// `answer_questions` grant the agent checks once operators need one.
+pub fn answer(bus: &EventBus, pending: &PendingQuestion, text: &str) -> Result<Question, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("the answer is empty".to_string());
+    }
+    let question = pending
+        .get()
+        .ok_or_else(|| "the agent has not asked anything".to_string())?;
+    let event = Event {
+        id: uuid::Uuid::new_v4(),
+        timestamp: chrono::Utc::now(),
+        source: RESPONSE_SOURCE.to_string(),
+        payload: EventPayload::HumanResponse(HumanAnswer {
+            question_id: question.id.clone(),
+            answer: text.to_string(),
+            operator: Some(console_operator()),
+        }),
+        trace_id: None,
+        correlation_id: None,
+    };
+    bus.publish(event).map_err(|e| e.to_string())?;
+    pending.remove(question.id.as_deref());
+    Ok(question)
+}
+
+/// The operator at the REPL: the login user, or `"console"` when unknown.
+///
+/// The name comes from `$USER` and is not authenticated: whoever runs the
+/// REPL already has the robot's shell, so a grant for the console operator
+/// limits mistakes, not attackers.  Operator grants only bind remote
+/// operators, whose Cockpit tabs must present a token.
+pub fn console_operator() -> String {
+    std::env::var("USER")
+        .ok()
+        .filter(|user| !user.is_empty())
+        .unwrap_or_else(|| "console".to_string())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mechos_types::IntentEnvelope;
+
+    fn ask(question: &str, id: &str) -> Event {
+        Event {
+            id: uuid::Uuid::new_v4(),
+            timestamp: chrono::Utc::now(),
+            source: "mechos-runtime::agent_loop/agent".to_string(),
+            payload: EventPayload::Intent(IntentEnvelope::new(
+                HardwareIntent::AskHuman {
+                    question: question.to_string(),
+                    context_image_id: Some("cam-7".to_string()),
+                    question_id: Some(id.to_string()),
+                },
+                "agent",
+            )),
+            trace_id: None,
+            correlation_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn questions_are_announced_and_answered_on_the_bus() {
+        let bus = Arc::new(EventBus::new(16));
+        let pending = PendingQuestion::default();
+        let (announced_tx, mut announced) = tokio::sync::mpsc::unbounded_channel();
+        let watcher = tokio::spawn(watch_questions(bus.clone(), pending.clone(), move |q| {
+            let _ = announced_tx.send(q.to_string());
+        }));
+        tokio::task::yield_now().await;
+
+        assert!(answer(&bus, &pending, "yes").is_err(), "nothing was asked");
+        bus.publish(ask("Push the red box?", "q-1")).unwrap();
+        bus.publish(ask("Open the door?", "q-2")).unwrap();
+        assert_eq!(
+            announced.recv().await.unwrap(),
+            "agent asks: Push the red box? (image cam-7)"
+        );
+        announced.recv().await.unwrap();
+        assert_eq!(pending.len(), 2);
+        assert!(answer(&bus, &pending, "  ").is_err());
+
+        let mut responses = bus.subscribe();
+        let question = answer(&bus, &pending, "Yes, gently").unwrap();
+        assert_eq!(question.text, "Push the red box?");
+        assert_eq!(pending.get().unwrap().text, "Open the door?");
+        let response = responses.recv().await.unwrap();
+        assert!(matches!(
+            response.payload,
+            EventPayload::HumanResponse(ref r)
+                if *r == HumanAnswer::to_question("q-1", "Yes, gently").with_operator(console_operator())
+        ));
+        watcher.abort();
+    }
+
+    #[tokio::test]
+    async fn an_answer_from_elsewhere_clears_the_question() {
+        let bus = Arc::new(EventBus::new(16));
+        let pending = PendingQuestion::default();
+        let watcher = tokio::spawn(watch_questions(bus.clone(), pending.clone(), |_| {}));
+        tokio::task::yield_now().await;
+
+        bus.publish(ask("Which door?", "q-1")).unwrap();
+        bus.publish(ask("Which floor?", "q-2")).unwrap();
+        for _ in 0..100 {
+            if pending.len() == 2 {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(pending.get().unwrap().text, "Which door?");
+
+        let mut cockpit = ask("", "");
+        cockpit.payload = EventPayload::HumanResponse(HumanAnswer::to_question("q-2", "Third"));
+        bus.publish(cockpit).unwrap();
+        for _ in 0..100 {
+            if pending.len() == 1 {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(pending.get().unwrap().text, "Which door?");
+        watcher.abort();
+    }
+}
@@ -7,29 +7,76 @@
 //!    file is absent.
 //! 2. Probes the local Ollama instance and reports available AI models.
 //! 3. Drops the user into an **interactive REPL** with slash-commands
-//!    (`/settings`, `/models`, `/connections`, `/start`, `/help`).
+//!    (`/settings`, `/models`, `/connections`, `/start`, `/stop`, `/help`).
 //! 4. Intercepts **Ctrl-C** to send an `EmergencyStop` intent and exit safely.
+//!
+//! Subcommands (see [`cli`]) skip the REPL: `mechos run` boots the stack
+//! headless for services, `config`, `tasks`, `memory`, `caps`, `doctor`
+//! and `replay` answer one question and exit, and `bench` and `simulate`
+//! exercise the agent loop for CI.
 
+mod bench;
+mod cli;
+mod commands;
 mod config;
+mod daemon;
+mod doctor;
+mod hitl;
 mod ollama;
 mod repl;
+mod simulate;
+mod stack;
 
+use clap::Parser;
 use colored::Colorize;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use tracing::warn;
 
 use mechos_middleware::{EventBus, Topic};
-use mechos_types::{Event, EventPayload};
+use mechos_types::{Event, EventPayload, FaultCode, HardwareIntent};
+
+use crate::cli::{Cli, Command};
+use crate::config::AdapterChoice;
+
+/// Env-var naming the `host:port` of the optional Prometheus scrape endpoint.
+const PROMETHEUS_ADDR_ENV: &str = "MECHOS_PROMETHEUS_ADDR";
 
 fn main() {
+    let cli = Cli::parse();
+
     // ── Structured logging + OpenTelemetry pipeline ───────────────────────
-    // `init_tracing` sets up tracing-subscriber and, when
-    // OTEL_EXPORTER_OTLP_ENDPOINT is set, wires in the OTLP span exporter.
-    // The guard must live for the entire process so that spans are flushed on
-    // exit.
-    let _otel_guard = mechos_runtime::init_tracing("mechos");
+    // `init_observability` sets up tracing-subscriber and, when
+    // OTEL_EXPORTER_OTLP_ENDPOINT is set, wires in the OTLP span and metric
+    // exporters.  The guard must live for the entire process so that spans
+    // and metrics are flushed on exit.
+    let _otel_guard = mechos_runtime::init_observability("mechos");
+
+    // ── Subcommands ───────────────────────────────────────────────────────
+    if let Some(command) = cli.command {
+        let result = match command {
+            Command::Run(options) => {
+                spawn_prometheus_endpoint();
+                daemon::run(options)
+            }
+            Command::Config(command) => commands::config(command),
+            Command::Tasks(args) => commands::tasks(args),
+            Command::Memory(args) => commands::memory(args),
+            Command::Caps(args) => commands::caps(args),
+            Command::Doctor(args) => doctor::run(args),
+            Command::Replay(args) => commands::replay(args),
+            Command::Bench(args) => bench::run(args),
+            Command::Simulate(args) => simulate::run(args),
+        };
+        if let Err(e) = result {
+            eprintln!("mechos: {e}");
+            drop(_otel_guard);
+            std::process::exit(1);
+        }
+        return;
+    }
 
+    spawn_prometheus_endpoint();
     print_banner();
 
     // ── Shared shutdown flag ──────────────────────────────────────────────
@@ -53,13 +100,29 @@ fn main() {
             source: "mechos-cli".to_string(),
             payload: EventPayload::HardwareFault {
                 component: "cli".to_string(),
-                code: 911,
+                code: FaultCode::EmergencyStop,
                 message: "EMERGENCY_STOP: operator Ctrl-C".to_string(),
             },
             trace_id: None,
+            correlation_id: None,
         };
         let _ = bus_ctrlc_ref.publish_to(Topic::SystemAlerts, stop_event);
 
+        // Command the stop itself through the typed intent channel so every
+        // adapter zeroes its velocities.
+        let halt = HardwareIntent::Halt {
+            reason: "operator Ctrl-C".to_string(),
+        };
+        let halt_event = Event {
+            id: uuid::Uuid::new_v4(),
+            timestamp: chrono::Utc::now(),
+            source: "mechos-cli".to_string(),
+            payload: EventPayload::IntentDispatched(halt),
+            trace_id: None,
+            correlation_id: None,
+        };
+        let _ = bus_ctrlc_ref.publish(halt_event);
+
         println!("{}", "  ✓ EmergencyStop published to Event Bus.".green());
         println!("{}", "  ✓ Exiting MechOS.".green());
 
@@ -146,14 +209,25 @@ fn run_first_run_wizard() {
         _   => cfg.ai_provider = config::AiProvider::Ollama,
     }
 
-    // Dashboard port
-    let port_str = prompt_line(
-        &format!("  Dashboard (rosbridge) WebSocket port [{}]: ", cfg.dashboard_port),
-        &cfg.dashboard_port.to_string(),
-    );
-    if let Ok(p) = port_str.trim().parse::<u16>() {
-        cfg.dashboard_port = p;
-    }
+    // Adapter
+    println!();
+    println!("  How does MechOS reach the robot?");
+    println!("    1) Simulation dashboard  (default)");
+    println!("    2) Gazebo simulation");
+    println!("    3) Webots simulation");
+    println!("    4) ROS 2 via rosbridge");
+    println!("    5) Serial microcontroller");
+    println!("    6) MQTT broker");
+    let choice = prompt_line("  Enter choice [1]: ", "1");
+    cfg.adapter.kind = match choice.trim() {
+        "2" => AdapterChoice::Gazebo,
+        "3" => AdapterChoice::Webots,
+        "4" => AdapterChoice::Ros2,
+        "5" => AdapterChoice::Serial,
+        "6" => AdapterChoice::Mqtt,
+        _   => AdapterChoice::DashboardSim,
+    };
+    wizard_adapter_settings(&mut cfg);
 
     // Web UI port
     let port_str = prompt_line(
@@ -173,6 +247,8 @@ fn run_first_run_wizard() {
         cfg.camera_port = p;
     }
 
+    wizard_safety_limits(&mut cfg.safety);
+
     match config::save(&cfg) {
         Ok(()) => println!(
             "\n  {} Config saved to {}\n",
@@ -183,6 +259,86 @@ fn run_first_run_wizard() {
     }
 }
 
+/// Ask for the endpoint of the adapter chosen in `cfg.adapter.kind`.
+fn wizard_adapter_settings(cfg: &mut config::Config) {
+    let adapter = &mut cfg.adapter;
+    match adapter.kind {
+        AdapterChoice::DashboardSim => {
+            let port_str = prompt_line(
+                &format!("  Dashboard (rosbridge) WebSocket port [{}]: ", cfg.dashboard_port),
+                &cfg.dashboard_port.to_string(),
+            );
+            if let Ok(p) = port_str.trim().parse::<u16>() {
+                cfg.dashboard_port = p;
+            }
+        }
+        AdapterChoice::Gazebo | AdapterChoice::Webots | AdapterChoice::Ros2 => {
+            let default = stack::adapter_url(cfg).unwrap_or_default();
+            let label = if cfg.adapter.kind == AdapterChoice::Ros2 { "rosbridge" } else { "Simulator" };
+            loop {
+                let url = prompt_line(&format!("  {label} WebSocket URL [{default}]: "), &default);
+                if url.starts_with("ws://") || url.starts_with("wss://") {
+                    cfg.adapter.url = url;
+                    break;
+                }
+                println!("  {}", "Enter a ws:// or wss:// URL.".yellow());
+            }
+        }
+        AdapterChoice::Serial => {
+            adapter.serial_port = prompt_line(
+                &format!("  Serial device [{}]: ", adapter.serial_port),
+                &adapter.serial_port,
+            );
+            let baud_str = prompt_line(
+                &format!("  Baud rate [{}]: ", adapter.baud_rate),
+                &adapter.baud_rate.to_string(),
+            );
+            if let Ok(baud) = baud_str.trim().parse::<u32>() {
+                adapter.baud_rate = baud;
+            }
+        }
+        AdapterChoice::Mqtt => {
+            adapter.mqtt_host = prompt_line(
+                &format!("  MQTT broker host [{}]: ", adapter.mqtt_host),
+                &adapter.mqtt_host,
+            );
+            let port_str = prompt_line(
+                &format!("  MQTT broker port [{}]: ", adapter.mqtt_port),
+                &adapter.mqtt_port.to_string(),
+            );
+            if let Ok(p) = port_str.trim().parse::<u16>() {
+                adapter.mqtt_port = p;
+            }
+        }
+    }
+}
+
+/// Ask for the speed caps, the arm's workspace and the geofence.
+fn wizard_safety_limits(safety: &mut config::SafetyConfig) {
+    println!();
+    println!("  Safety limits – the kernel refuses any intent that exceeds them.");
+    safety.max_linear_mps = Some(prompt_speed("  Max driving speed in m/s [0.5]: ", 0.5));
+    safety.max_angular_rps = Some(prompt_speed("  Max turn rate in rad/s [1.0]: ", 1.0));
+
+    println!("  Arm workspace in metres, base frame (blank = no arm):");
+    safety.workspace = prompt_range("x").and_then(|(min_x, max_x)| {
+        let (min_y, max_y) = prompt_range("y")?;
+        let (min_z, max_z) = prompt_range("z")?;
+        Some(config::WorkspaceBounds { min_x, max_x, min_y, max_y, min_z, max_z })
+    });
+
+    println!("  Geofence corners in metres, map frame (blank = no geofence):");
+    safety.geofence = loop {
+        let Some(a) = prompt_point("  First corner x,y: ") else { break None };
+        let Some(b) = prompt_point("  Opposite corner x,y: ") else { break None };
+        let fence = config::Geofence::from_corners(a, b);
+        if fence.min_x < fence.max_x && fence.min_y < fence.max_y {
+            break Some(fence);
+        }
+        println!("  {}", "The corners must span an area.".yellow());
+    };
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Banner
 // ─────────────────────────────────────────────────────────────────────────────
@@ -219,3 +375,72 @@ fn prompt_line(msg: &str, default: &str) -> String {
         Err(_) => default.to_string(),
     }
 }
+
+/// Prompt until a positive speed is entered; empty input gives `default`.
+fn prompt_speed(msg: &str, default: f32) -> f32 {
+    loop {
+        match prompt_line(msg, &default.to_string()).parse::<f32>() {
+            Ok(v) if v.is_finite() && v > 0.0 => return v,
+            _ => println!("  {}", "Enter a positive number.".yellow()),
+        }
+    }
+}
+
+/// Prompt for the `axis` range `min,max`, in either order; `None` when left
+/// blank.
+fn prompt_range(axis: &str) -> Option<(f32, f32)> {
+    loop {
+        let (a, b) = prompt_point(&format!("    {axis} range min,max: "))?;
+        if a != b {
+            return Some((a.min(b), a.max(b)));
+        }
+        println!("  {}", "The range must not be empty.".yellow());
+    }
+}
+
+/// Prompt for a pair of numbers `a,b`; `None` when left blank.
+fn prompt_point(msg: &str) -> Option<(f32, f32)> {
+    loop {
+        let line = prompt_line(msg, "");
+        if line.is_empty() {
+            return None;
+        }
+        let parsed = line
+            .split_once(',')
+            .and_then(|(a, b)| Some((a.trim().parse::<f32>().ok()?, b.trim().parse::<f32>().ok()?)));
+        match parsed {
+            Some((a, b)) if a.is_finite() && b.is_finite() => return Some((a, b)),
+            _ => println!("  {}", "Enter two numbers separated by a comma.".yellow()),
+        }
+    }
+}
+
+/// Serve runtime metrics for Prometheus when `MECHOS_PROMETHEUS_ADDR` is set
+/// (e.g. `127.0.0.1:9464`).  Runs on its own thread for the whole process.
+fn spawn_prometheus_endpoint() {
+    let Ok(raw) = std::env::var(PROMETHEUS_ADDR_ENV) else {
+        return;
+    };
+    let addr: std::net::SocketAddr = match raw.parse() {
+        Ok(addr) => addr,
+        Err(e) => {
+            warn!(value = %raw, error = %e, "Invalid {PROMETHEUS_ADDR_ENV}; Prometheus endpoint disabled");
+            return;
+        }
+    };
+    std::thread::spawn(move || {
+        let rt = match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(rt) => rt,
+            Err(e) => {
+                eprintln!("{}: Prometheus endpoint runtime: {}", "ERROR".red(), e);
+                return;
+            }
+        };
+        if let Err(e) = rt.block_on(mechos_runtime::serve_prometheus(addr)) {
+            eprintln!("{}: {}", "ERROR".red(), e);
+        }
+    });
+}
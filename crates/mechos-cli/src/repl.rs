@@ -5,12 +5,22 @@
 //!   /settings                   – interactively edit `~/.mechos/config.toml`
 //!   /models                     – list / switch the active AI model
 //!   /connections                – run an adapter connectivity diagnostic
-//!   /start                      – initiate the boot sequence
-//!   /logs                       – stream live Event Bus events (press Enter to stop)
+//!   /start                      – boot the full stack (see [`Stack`])
+//!   /stop                       – halt the robot and tear the stack down
+//!   /logs [level] [target]      – recent log records, e.g. `/logs warn kernel`
+//!   /events                     – stream live Event Bus events (press Enter to stop)
+//!   /watch [topic]              – tail one topic's (or every) event as raw JSON
 //!   /hardware <intent> [args…]  – manually send a HardwareIntent to the bus
 //!   /halt                       – emergency stop without exiting the REPL
-//!   /memory list|query <term>   – inspect the episodic memory store
+//!   /answer <text>              – answer the agent's AskHuman question
+//!   /memory list|recent|query|search|forget – inspect and prune the episodic memory
+//!   /tasks list|show|post|claim|complete – the fleet task board
+//!   /caps list|grant|revoke     – administer the agent's capability grants
 //!   /quit | /exit               – gracefully exit the CLI
+//!
+//! While the stack runs, the agent's `AskHuman` questions appear above the
+//! prompt (see [`crate::hitl`]); the next line typed without a leading `/`
+//! is sent back as the answer.
 
 use colored::Colorize;
 use rustyline::completion::{Completer, Pair};
@@ -19,13 +29,21 @@ use rustyline::highlight::{Highlighter, MatchingBracketHighlighter};
 use rustyline::hint::{Hinter, HistoryHinter};
 use rustyline::history::DefaultHistory;
 use rustyline::validate::{Validator, MatchingBracketValidator};
-use rustyline::{Context, Editor, Helper};
+use rustyline::{Context, Editor, ExternalPrinter, Helper};
 use std::io::{self, BufRead, Write};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::sync::atomic::{AtomicBool, Ordering};
 
+use clap::Parser;
+use mechos_middleware::LogBuffer;
+use mechos_middleware::log_buffer::LogFilter;
+
+use crate::cli::{ReplCaps, ReplTasks};
+use crate::commands;
 use crate::config::{self, AiProvider, Config};
+use crate::hitl::{self, PendingQuestion, Question};
 use crate::ollama;
+use crate::stack::Stack;
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Tab-completion helper
@@ -38,10 +56,16 @@ const COMMANDS: &[&str] = &[
     "/models",
     "/connections",
     "/start",
+    "/stop",
     "/logs",
+    "/events",
+    "/watch",
     "/hardware",
     "/halt",
+    "/answer",
     "/memory",
+    "/tasks",
+    "/caps",
     "/quit",
     "/exit",
 ];
@@ -108,12 +132,19 @@ impl Completer for MechCompleter {
 // ─────────────────────────────────────────────────────────────────────────────
 
 /// Runtime state shared across REPL command handlers.
-/// Both fields are `None` until `/start` completes successfully.
+/// The bus, store and stack are `None` until `/start` completes successfully.
+#[derive(Default)]
 pub struct ReplState {
     /// Reference to the live Event Bus (available after `/start`).
     pub bus: Option<Arc<mechos_middleware::EventBus>>,
     /// Reference to the episodic memory store (available after `/start`).
     pub store: Option<mechos_memory::episodic::EpisodicStore>,
+    /// The running services, torn down by `/stop`.
+    pub stack: Option<Stack>,
+    /// The agent's unanswered `AskHuman` question.
+    pub question: PendingQuestion,
+    /// Prints above the prompt while the REPL waits for input.
+    pub printer: Option<Arc<Mutex<dyn ExternalPrinter + Send>>>,
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -147,14 +178,23 @@ pub fn run(shutdown: Arc<AtomicBool>) {
         Editor::with_config(config).unwrap_or_else(|_| Editor::new().unwrap());
     rl.set_helper(Some(helper));
 
-    let mut state = ReplState { bus: None, store: None };
+    let mut state = ReplState::default();
+    if let Ok(printer) = rl.create_external_printer() {
+        state.printer = Some(Arc::new(Mutex::new(printer)));
+    }
 
     loop {
         if shutdown.load(Ordering::SeqCst) {
             break;
         }
+        print_stack_status(&state);
 
-        match rl.readline(&format!("{} ", "mechos>".bold().cyan())) {
+        let prompt = match state.question.len() {
+            0 => "mechos>".bold().cyan(),
+            1 => "answer>".bold().yellow(),
+            n => format!("answer (1/{n})>").bold().yellow(),
+        };
+        match rl.readline(&format!("{} ", prompt)) {
             Ok(line) => {
                 let cmd = line.trim().to_string();
                 if cmd.is_empty() {
@@ -177,10 +217,30 @@ pub fn run(shutdown: Arc<AtomicBool>) {
             }
         }
     }
+
+    if let Some(stack) = state.stack.take() {
+        stack.stop("MechOS CLI exiting");
+    }
+}
+
+/// Print what the running services reported since the last prompt.
+fn print_stack_status(state: &ReplState) {
+    let Some(stack) = &state.stack else {
+        return;
+    };
+    for status in stack.poll_status() {
+        println!("  {} {}", "⚠".yellow().bold(), status.to_string().yellow());
+    }
 }
 
 /// Dispatch a trimmed command string to the appropriate handler.
 fn dispatch(cmd: &str, state: &mut ReplState, shutdown: Arc<AtomicBool>) {
+    // A plain line answers the agent's pending question.
+    if !cmd.starts_with('/') && state.question.get().is_some() {
+        cmd_answer(cmd, state);
+        return;
+    }
+
     // Split into verb + arguments for multi-word commands.
     let mut parts = cmd.splitn(2, ' ');
     let verb = parts.next().unwrap_or("");
@@ -191,12 +251,21 @@ fn dispatch(cmd: &str, state: &mut ReplState, shutdown: Arc<AtomicBool>) {
         "/settings"    => cmd_settings(),
         "/models"      => cmd_models(),
         "/connections" => cmd_connections(),
-        "/start"       => cmd_start(shutdown, state),
-        "/logs"        => cmd_logs(state),
+        "/start"       => cmd_start(state),
+        "/stop"        => cmd_stop(state),
+        "/logs"        => cmd_logs(rest),
+        "/events"      => cmd_events(state),
+        "/watch"       => cmd_watch(rest, state),
         "/hardware"    => cmd_hardware(rest, state),
         "/halt"        => cmd_halt(state),
+        "/answer"      => cmd_answer(rest, state),
         "/memory"      => cmd_memory(rest, state),
+        "/tasks"       => cmd_tasks(rest),
+        "/caps"        => cmd_caps(rest),
         "/quit" | "/exit" => {
+            if state.stack.is_some() {
+                cmd_stop(state);
+            }
             println!("{}", "Goodbye.".green());
             shutdown.store(true, Ordering::SeqCst);
         }
@@ -221,21 +290,35 @@ fn cmd_help() {
     println!("  {}   – edit ~/.mechos/config.toml settings",      "/settings".bold().cyan());
     println!("  {}     – list and switch AI models",               "/models".bold().cyan());
     println!("  {} – adapter connectivity diagnostic",        "/connections".bold().cyan());
-    println!("  {}      – initiate the OS boot sequence",          "/start".bold().cyan());
-    println!("  {}       – stream live Event Bus events",           "/logs".bold().cyan());
+    println!("  {}      – boot the full MechOS stack",             "/start".bold().cyan());
+    println!("  {}       – halt the robot and stop the stack",      "/stop".bold().cyan());
+    println!("  {}       – recent log records, newest last",         "/logs".bold().cyan());
+    println!("     {}          [error|warn|info|debug|trace] [target]", "".dimmed());
+    println!("  {}     – stream live Event Bus events",             "/events".bold().cyan());
+    println!("  {}      – tail raw events, optionally of one topic", "/watch".bold().cyan());
+    println!("     {}          [telemetry|hardware_commands|system_alerts|swarm_comm|cognitive_stream]", "".dimmed());
     println!("  {}   – send a HardwareIntent to the bus",       "/hardware".bold().cyan());
     println!("     {}          drive <lin> <ang>",                  "".dimmed());
     println!("     {}          move  <x>   <y>  <z>",              "".dimmed());
     println!("     {}          relay <id>  on|off",                 "".dimmed());
     println!("  {}       – emergency stop (keeps REPL running)",    "/halt".bold().cyan());
+    println!("  {}     – answer the agent's question",            "/answer".bold().cyan());
     println!("  {}     – inspect episodic memory store",          "/memory".bold().cyan());
     println!("     {}          list",                               "".dimmed());
     println!("     {}          query <search terms>",               "".dimmed());
+    println!("     {}          recent [count]  |  search <text>",   "".dimmed());
+    println!("     {}          forget source=<s> tag=<t> after=7d …", "".dimmed());
+    println!("  {}      – inspect and edit the fleet task board",  "/tasks".bold().cyan());
+    println!("     {}          list [--all]  |  show <id>",         "".dimmed());
+    println!("     {}          post \"<title>\" [-d <text>] [--priority <n>]", "".dimmed());
+    println!("     {}          claim|complete <id> --robot <id>",   "".dimmed());
+    println!("  {}       – list, grant or revoke agent capabilities", "/caps".bold().cyan());
+    println!("     {}          list  |  grant|revoke <identity> <capability>", "".dimmed());
     println!("  {}  – exit the CLI",                   "/quit  /exit".bold().cyan());
     println!();
 }
 
-fn cmd_settings() {
+pub(crate) fn cmd_settings() {
     let mut cfg = match config::load() {
         Ok(Some(c)) => c,
         Ok(None) => Config::default(),
@@ -409,7 +492,11 @@ fn cmd_connections() {
     }
 }
 
-fn cmd_start(shutdown: Arc<AtomicBool>, state: &mut ReplState) {
+fn cmd_start(state: &mut ReplState) {
+    if state.stack.is_some() {
+        println!("{}", "MechOS is already running. Run /stop first.".yellow());
+        return;
+    }
     let cfg = load_config_or_default();
 
     println!();
@@ -418,8 +505,9 @@ fn cmd_start(shutdown: Arc<AtomicBool>, state: &mut ReplState) {
     println!("{}", "═══════════════════════════════════════".bold());
 
     // ── Step 1 – Memory ────────────────────────────────────────────────────
-    // Resolve a persistent path: ~/.mechos/memory.db
-    let memory_path = {
+    // Resolve persistent paths: ~/.mechos/memory.db and
+    // ~/.mechos/transcripts.db (LLM prompt/response audit log).
+    let (memory_path, transcript_path) = {
         let home = std::env::var("HOME")
             .or_else(|_| std::env::var("USERPROFILE"))
             .unwrap_or_else(|_| ".".to_string());
@@ -432,7 +520,10 @@ fn cmd_start(shutdown: Arc<AtomicBool>, state: &mut ReplState) {
                 e
             );
         }
-        dir.join("memory.db").to_string_lossy().into_owned()
+        (
+            dir.join("memory.db").to_string_lossy().into_owned(),
+            dir.join("transcripts.db").to_string_lossy().into_owned(),
+        )
     };
     print!(
         "  [1/7] {} {} … ",
@@ -440,7 +531,7 @@ fn cmd_start(shutdown: Arc<AtomicBool>, state: &mut ReplState) {
         memory_path.dimmed()
     );
     io::stdout().flush().ok();
-    let episodic_store = match mechos_memory::episodic::EpisodicStore::open(&memory_path) {
+    let episodic_store = match commands::open_episodic_store(&memory_path, cfg.memory_key().as_ref()) {
         Ok(s) => { println!("{}", "OK".green()); s }
         Err(e) => {
             println!("{}: {}", "FAILED".red(), e);
@@ -452,65 +543,58 @@ fn cmd_start(shutdown: Arc<AtomicBool>, state: &mut ReplState) {
     print!("  [2/7] {} … ", "Initializing Event Bus".bold());
     io::stdout().flush().ok();
     let bus = std::sync::Arc::new(mechos_middleware::EventBus::new(256));
+    let mut stack = match Stack::new(bus.clone()) {
+        Ok(stack) => stack,
+        Err(e) => {
+            println!("{}: service runtime: {}", "FAILED".red(), e);
+            return;
+        }
+    };
     println!("{}", "OK".green());
 
-    // ── Step 3 – Kernel Safety Interlocks ──────────────────────────────────
-    print!("  [3/7] {} … ", "Engaging Kernel Safety Interlocks".bold());
+    // ── Step 3 – Hardware Adapter ──────────────────────────────────────────
+    print!("  [3/7] {} {} … ", "Connecting adapter".bold(), cfg.adapter.kind.to_string().yellow());
     io::stdout().flush().ok();
-    let _watchdog = mechos_kernel::Watchdog::new();
-    let _cap_mgr  = mechos_kernel::CapabilityManager::new();
-    println!("{}", "OK".green());
+    match stack.start_adapter(&cfg) {
+        Ok(url) => println!("{} ({})", "OK".green(), url),
+        Err(e) => {
+            println!("{}: {}", "FAILED".red(), e);
+            stack.stop("boot aborted");
+            return;
+        }
+    }
 
-    // ── Step 4 – Dashboard Adapter ─────────────────────────────────────────
-    let dash_url = format!("ws://localhost:{}", cfg.dashboard_port);
-    print!("  [4/7] {} {} … ", "Binding DashboardSimAdapter on".bold(), dash_url.yellow());
+    // ── Step 4 – Kernel Watchdog ───────────────────────────────────────────
+    print!("  [4/7] {} … ", "Engaging Kernel Watchdog".bold());
     io::stdout().flush().ok();
-    let _adapter = mechos_middleware::DashboardSimAdapter::new(
-        bus.clone(),
-        dash_url.clone(),
-    );
+    let watchdog = stack.start_watchdog();
     println!("{}", "OK".green());
 
     // ── Step 5 – Cockpit Web UI ────────────────────────────────────────────
-    {
-        let webui_port = cfg.webui_port;
-        let camera_port = cfg.camera_port;
-        let bus_for_cockpit = bus.clone();
-        print!(
-            "  [5/7] {} {} … ",
-            "Starting Cockpit Web UI on port".bold(),
-            webui_port.to_string().yellow()
-        );
-        io::stdout().flush().ok();
-        std::thread::spawn(move || {
-            let rt = match tokio::runtime::Builder::new_current_thread()
-                .enable_all()
-                .build()
-            {
-                Ok(rt) => rt,
-                Err(e) => {
-                    eprintln!("{}: cockpit server runtime: {}", "ERROR".red(), e);
-                    return;
-                }
-            };
-            rt.block_on(async move {
-                let mut server = mechos_cockpit::CockpitServer::new(bus_for_cockpit)
-                    .with_port(webui_port);
-                if camera_port > 0 {
-                    server = server.with_camera_port(camera_port);
-                }
-                if let Err(e) = server.run().await {
-                    tracing::error!(error = %e, "Cockpit server failed");
-                }
-            });
-        });
-        if camera_port > 0 {
-            println!("{} (http://localhost:{}) · camera feed: /frame → port {}", "OK".green(), webui_port, camera_port);
-        } else {
-            println!("{} (http://localhost:{})", "OK".green(), webui_port);
-        }
+    let scheme = if cfg.tls.is_some() { "https" } else { "http" };
+    print!(
+        "  [5/7] {} {} … ",
+        "Starting Cockpit Web UI on port".bold(),
+        cfg.webui_port.to_string().yellow()
+    );
+    io::stdout().flush().ok();
+    stack.start_cockpit(&cfg);
+    if cfg.camera_port > 0 {
+        println!("{} ({}://{}:{}) · camera feed: /frame → port {}", "OK".green(), scheme, cfg.webui_bind, cfg.webui_port, cfg.camera_port);
+    } else {
+        println!("{} ({}://{}:{})", "OK".green(), scheme, cfg.webui_bind, cfg.webui_port);
     }
 
+    // Announce the agent's AskHuman questions; listening before the agent
+    // loop starts so that none is missed.
+    let printer = state.printer.clone();
+    stack.spawn(
+        "hitl",
+        hitl::watch_questions(Arc::clone(&bus), state.question.clone(), move |question| {
+            announce_question(printer.as_deref(), question)
+        }),
+    );
+
     // ── Step 6 – Runtime Brain ─────────────────────────────────────────────
     print!(
         "  [6/7] {} {} … ",
@@ -518,27 +602,19 @@ fn cmd_start(shutdown: Arc<AtomicBool>, state: &mut ReplState) {
         cfg.active_model.yellow()
     );
     io::stdout().flush().ok();
-    let loop_config = mechos_runtime::AgentLoopConfig {
-        llm_base_url: cfg.ollama_url.clone(),
-        llm_model: cfg.active_model.clone(),
-        memory_path: Some(memory_path),
-        bus: Some((*bus).clone()),
-        ..Default::default()
-    };
-    let agent = match mechos_runtime::AgentLoop::new(loop_config) {
-        Ok(agent) => agent,
-        Err(e) => {
-            println!("{} {}", "ERROR".red(), e);
-            return;
-        }
-    };
+    if let Err(e) = stack.start_agent_loop(&cfg, memory_path, transcript_path, watchdog) {
+        println!("{} {}", "ERROR".red(), e);
+        stack.stop("boot aborted");
+        return;
+    }
     println!("{}", "OK".green());
 
     // ── Step 7 – Store shared references in REPL state ─────────────────────
     print!("  [7/7] {} … ", "Registering runtime references".bold());
     io::stdout().flush().ok();
-    state.bus = Some(bus.clone());
+    state.bus = Some(bus);
     state.store = Some(episodic_store);
+    state.stack = Some(stack);
     println!("{}", "OK".green());
 
     println!("{}", "═══════════════════════════════════════".bold());
@@ -546,62 +622,96 @@ fn cmd_start(shutdown: Arc<AtomicBool>, state: &mut ReplState) {
         "  {} MechOS is {}. Type {} to stop.",
         "✓".green().bold(),
         "RUNNING".green().bold(),
-        "/quit".bold()
+        "/stop".bold()
     );
     println!("{}", "═══════════════════════════════════════".bold());
     println!();
+}
 
-    // ── Spawn the continuous OODA loop in a background thread ───────────────
-    // A dedicated thread owns a single-threaded tokio runtime so that the
-    // async tick() can run without blocking the interactive REPL.
-    // OODA loop target frequency: 10 Hz (100 ms per tick).
-    const TICK_RATE_HZ: f32 = 10.0;
-    let tick_interval =
-        std::time::Duration::from_millis((1000.0 / TICK_RATE_HZ) as u64);
-    let tick_dt = 1.0 / TICK_RATE_HZ;
+// ─────────────────────────────────────────────────────────────────────────────
+// /stop – tear the stack down
+// ─────────────────────────────────────────────────────────────────────────────
 
-    std::thread::spawn(move || {
-        let rt = match tokio::runtime::Builder::new_current_thread()
-            .enable_all()
-            .build()
-        {
-            Ok(rt) => rt,
-            Err(e) => {
-                eprintln!(
-                    "{}: failed to create async runtime for agent loop: {}\n  \
-                     Ensure your system supports async I/O (check ulimits / OS resources).",
-                    "ERROR".red(),
-                    e
-                );
-                return;
-            }
+fn cmd_stop(state: &mut ReplState) {
+    let Some(stack) = state.stack.take() else {
+        println!("{}", "System not started. Run /start first.".red());
+        return;
+    };
+    print!("  {} … ", "Halting the robot and stopping MechOS".bold());
+    io::stdout().flush().ok();
+    stack.stop("operator /stop");
+    state.bus = None;
+    state.store = None;
+    state.question = PendingQuestion::default();
+    println!("{}", "OK".green());
+    println!("  MechOS is {}.", "OFFLINE".yellow().bold());
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// /logs – captured tracing records
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Records `/logs` prints at most.
+const LOGS_LIMIT: usize = 50;
+
+/// Parse `/logs [level] [target]`; a lone argument that is not a level is
+/// the target.
+fn parse_logs_args(args: &str) -> Result<LogFilter, String> {
+    let mut words = args.split_whitespace().peekable();
+    let min_level = match words.peek().map(|w| w.parse::<tracing::Level>()) {
+        Some(Ok(level)) => {
+            words.next();
+            Some(level)
+        }
+        _ => None,
+    };
+    let target = words.next().map(str::to_string);
+    if let Some(extra) = words.next() {
+        return Err(format!("unexpected argument '{extra}'"));
+    }
+    Ok(LogFilter {
+        min_level,
+        target,
+        limit: Some(LOGS_LIMIT),
+    })
+}
+
+fn cmd_logs(args: &str) {
+    let filter = match parse_logs_args(args) {
+        Ok(filter) => filter,
+        Err(e) => {
+            println!("{} {e}. Usage: /logs [level] [target]", "Error:".red());
+            return;
+        }
+    };
+    let records = LogBuffer::global().query(&filter);
+    if records.is_empty() {
+        println!("{}", "  No matching log records.".dimmed());
+        return;
+    }
+    for record in &records {
+        let level = format!("{:5}", record.level.as_str());
+        let level = match record.level {
+            tracing::Level::ERROR => level.red().bold(),
+            tracing::Level::WARN => level.yellow().bold(),
+            tracing::Level::INFO => level.green(),
+            _ => level.dimmed(),
         };
-        rt.block_on(async move {
-            let mut agent = agent;
-            loop {
-                if shutdown.load(Ordering::SeqCst) {
-                    tracing::info!("agent loop shutting down");
-                    break;
-                }
-                tokio::time::sleep(tick_interval).await;
-                match agent.tick(tick_dt).await {
-                    Ok(intent) => {
-                        tracing::info!(intent = ?intent, "agent intent dispatched");
-                    }
-                    Err(e) => {
-                        tracing::debug!(error = %e, "agent tick skipped");
-                    }
-                }
-            }
-        });
-    });
+        println!(
+            "[{}] {} {}: {}",
+            record.timestamp.format("%H:%M:%S%.3f").to_string().dimmed(),
+            level,
+            record.target.dimmed(),
+            record.message
+        );
+    }
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
-// /logs – real-time Event Bus stream
+// /events – real-time Event Bus stream
 // ─────────────────────────────────────────────────────────────────────────────
 
-fn cmd_logs(state: &ReplState) {
+fn cmd_events(state: &ReplState) {
     let Some(bus) = &state.bus else {
         println!("{}", "System not started. Run /start first.".red());
         return;
@@ -655,7 +765,7 @@ fn cmd_logs(state: &ReplState) {
 
 /// Print a single event to stdout, coloured by its payload type.
 fn print_event_colored(event: &mechos_types::Event) {
-    use mechos_types::EventPayload;
+    use mechos_types::{EventPayload, LinkState};
 
     let ts = event.timestamp.format("%H:%M:%S%.3f");
     let src = &event.source;
@@ -671,7 +781,7 @@ fn print_event_colored(event: &mechos_types::Event) {
                 message.red()
             );
         }
-        EventPayload::AgentThought(thought) => {
+        EventPayload::Reasoning(thought) => {
             let truncated = if thought.len() > 120 {
                 format!("{}…", &thought[..120])
             } else {
@@ -684,12 +794,30 @@ fn print_event_colored(event: &mechos_types::Event) {
                 truncated
             );
         }
+        EventPayload::IntentDispatched(intent) => {
+            let intent = serde_json::to_string(intent).unwrap_or_else(|_| format!("{intent:?}"));
+            println!(
+                "[{}] {} {}",
+                ts.to_string().dimmed(),
+                "DISPATCH".green(),
+                intent
+            );
+        }
+        EventPayload::AdapterCommand { topic, json } => {
+            println!(
+                "[{}] {} {} {}",
+                ts.to_string().dimmed(),
+                "COMMAND".magenta(),
+                topic.bold(),
+                json
+            );
+        }
         EventPayload::HumanResponse(resp) => {
             println!(
                 "[{}] {} {}",
                 ts.to_string().dimmed(),
                 "HUMAN".bold().yellow(),
-                resp.yellow()
+                resp.answer.yellow()
             );
         }
         EventPayload::Telemetry(t) => {
@@ -697,8 +825,8 @@ fn print_event_colored(event: &mechos_types::Event) {
                 "[{}] {} x={:.2} y={:.2} hdg={:.2}° bat={}%",
                 ts.to_string().dimmed(),
                 "TELEM".blue(),
-                t.position_x,
-                t.position_y,
+                t.position_x.get(),
+                t.position_y.get(),
                 t.heading_rad.to_degrees(),
                 t.battery_percent
             );
@@ -713,6 +841,68 @@ fn print_event_colored(event: &mechos_types::Event) {
                 src.dimmed()
             );
         }
+        EventPayload::SafetyRequest { command, .. } => {
+            println!(
+                "[{}] {} {:?} requested by {}",
+                ts.to_string().dimmed(),
+                "SAFETY".red().bold(),
+                command,
+                src.dimmed()
+            );
+        }
+        EventPayload::SafetyStatus { estopped, mode, .. } => {
+            let latch = if *estopped { "E-STOPPED".red().bold() } else { "RUNNING".green() };
+            println!(
+                "[{}] {} {} in {:?} mode",
+                ts.to_string().dimmed(),
+                "SAFETY".red().bold(),
+                latch,
+                mode
+            );
+        }
+        EventPayload::ApprovalRequest { request_id, envelope, .. } => {
+            println!(
+                "[{}] {} {:?} awaiting operator approval ({})",
+                ts.to_string().dimmed(),
+                "APPROVAL".yellow().bold(),
+                envelope.intent,
+                request_id.dimmed()
+            );
+        }
+        EventPayload::ApprovalDecision { request_id, approved, operator, .. } => {
+            let verdict = if *approved { "APPROVED".green() } else { "REJECTED".red() };
+            println!(
+                "[{}] {} {} by {} ({})",
+                ts.to_string().dimmed(),
+                "APPROVAL".yellow().bold(),
+                verdict,
+                operator.bold(),
+                request_id.dimmed()
+            );
+        }
+        EventPayload::PolicyRequest { operator, command, .. } => {
+            println!(
+                "[{}] {} {:?} requested by {}",
+                ts.to_string().dimmed(),
+                "POLICY".magenta().bold(),
+                command,
+                operator.bold()
+            );
+        }
+        EventPayload::PolicyStatus { error, .. } => match error {
+            Some(error) => println!(
+                "[{}] {} change refused: {}",
+                ts.to_string().dimmed(),
+                "POLICY".magenta().bold(),
+                error.red()
+            ),
+            None => println!(
+                "[{}] {} {}",
+                ts.to_string().dimmed(),
+                "POLICY".magenta().bold(),
+                "up to date".green()
+            ),
+        },
         EventPayload::LidarScan { ranges, .. } => {
             println!(
                 "[{}] {} {} points",
@@ -730,9 +920,379 @@ fn print_event_colored(event: &mechos_types::Event) {
                 message
             );
         }
+        EventPayload::Intent(envelope) => {
+            let intent = serde_json::to_string(&envelope.intent)
+                .unwrap_or_else(|_| format!("{:?}", envelope.intent));
+            println!(
+                "[{}] {} {} by {} ({:?})",
+                ts.to_string().dimmed(),
+                "INTENT".green().bold(),
+                intent,
+                envelope.issued_by.bold(),
+                envelope.priority
+            );
+        }
+        EventPayload::PowerStatus {
+            voltage,
+            current,
+            charging,
+            percent,
+        } => {
+            let state = if *charging { " charging" } else { "" };
+            println!(
+                "[{}] {} {:.0}% {:.1} V {:.1} A{}",
+                ts.to_string().dimmed(),
+                "POWER".yellow(),
+                percent,
+                voltage,
+                current,
+                state.green()
+            );
+        }
+        EventPayload::CameraFrame {
+            image_id,
+            format,
+            width,
+            height,
+            ..
+        } => {
+            println!(
+                "[{}] {} {} {}x{} {:?}",
+                ts.to_string().dimmed(),
+                "CAMERA".magenta(),
+                image_id,
+                width,
+                height,
+                format
+            );
+        }
+        EventPayload::Attitude {
+            roll_rad,
+            pitch_rad,
+            yaw_rad,
+        } => {
+            println!(
+                "[{}] {} roll={:.1}° pitch={:.1}° yaw={:.1}°",
+                ts.to_string().dimmed(),
+                "ATTITUDE".cyan(),
+                roll_rad.to_degrees(),
+                pitch_rad.to_degrees(),
+                yaw_rad.to_degrees()
+            );
+        }
+        EventPayload::GpsFix {
+            latitude_deg,
+            longitude_deg,
+            altitude,
+        } => {
+            println!(
+                "[{}] {} {:.7}, {:.7} alt={:.1} m",
+                ts.to_string().dimmed(),
+                "GPS".cyan(),
+                latitude_deg,
+                longitude_deg,
+                altitude.get()
+            );
+        }
+        EventPayload::ConnectionState {
+            component,
+            state,
+            attempt,
+        } => {
+            let label = format!("{state:?}").to_uppercase();
+            let label = match state {
+                LinkState::Connected => label.green(),
+                LinkState::Connecting => label.yellow(),
+                LinkState::Disconnected => label.red(),
+            };
+            println!(
+                "[{}] {} {} (attempt {})",
+                ts.to_string().dimmed(),
+                label,
+                component,
+                attempt
+            );
+        }
+        EventPayload::TrackedObstacle {
+            id,
+            x,
+            y,
+            velocity_x,
+            velocity_y,
+            radius,
+        } => {
+            println!(
+                "[{}] {} #{} at ({:.2}, {:.2}) v=({:.2}, {:.2}) m/s r={:.2} m",
+                ts.to_string().dimmed(),
+                "TRACK".cyan(),
+                id,
+                x.get(),
+                y.get(),
+                velocity_x.get(),
+                velocity_y.get(),
+                radius.get()
+            );
+        }
+        EventPayload::TaskProgress {
+            task_id,
+            robot_id,
+            percent,
+            note,
+        } => {
+            println!(
+                "[{}] {} {} {} {:.0}% {}",
+                ts.to_string().dimmed(),
+                "TASK".cyan(),
+                task_id,
+                robot_id,
+                percent,
+                note
+            );
+        }
+        EventPayload::TaskCompleted {
+            task_id,
+            robot_id,
+            result,
+        } => {
+            println!(
+                "[{}] {} {} {} {}",
+                ts.to_string().dimmed(),
+                "DONE".green(),
+                task_id,
+                robot_id,
+                result
+            );
+        }
+        EventPayload::MapSnapshot {
+            width,
+            height,
+            resolution,
+            robot_x,
+            robot_y,
+            ..
+        } => {
+            println!(
+                "[{}] {} {}x{} cells @ {:.2} m, robot at ({:.2}, {:.2})",
+                ts.to_string().dimmed(),
+                "MAP".cyan(),
+                width,
+                height,
+                resolution.get(),
+                robot_x.get(),
+                robot_y.get()
+            );
+        }
+        EventPayload::BusHealth { lanes } => {
+            let summary: Vec<String> = lanes
+                .iter()
+                .filter(|lane| lane.subscribers > 0 || lane.published > 0)
+                .map(|lane| {
+                    let text = format!(
+                        "{} {:.1}/s ×{}",
+                        lane.lane, lane.publish_rate_hz, lane.subscribers
+                    );
+                    if lane.dropped > 0 {
+                        format!("{text} ({} dropped)", lane.dropped).yellow().to_string()
+                    } else {
+                        text
+                    }
+                })
+                .collect();
+            println!(
+                "[{}] {} {}",
+                ts.to_string().dimmed(),
+                "BUS".cyan(),
+                summary.join(", ")
+            );
+        }
+        EventPayload::IntentResult {
+            action,
+            success,
+            duration_ms,
+            message,
+            ..
+        } => {
+            let verdict = if *success { "SUCCEEDED".green() } else { "FAILED".red() };
+            println!(
+                "[{}] {} {} {} after {} ms: {}",
+                ts.to_string().dimmed(),
+                "RESULT".bold(),
+                action,
+                verdict,
+                duration_ms,
+                message
+            );
+        }
+        EventPayload::Unknown(raw) => {
+            println!(
+                "[{}] {} {} from {} (newer peer?)",
+                ts.to_string().dimmed(),
+                "UNKNOWN".dimmed(),
+                raw.to_string().dimmed(),
+                src.dimmed()
+            );
+        }
     }
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// /watch – live event tail, by topic
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Payloads longer than this many characters are truncated by `/watch`.
+const WATCH_PAYLOAD_CHARS: usize = 160;
+
+/// How many recent event IDs `/watch` remembers, to print an event that
+/// reaches it on both the global channel and a topic lane only once.
+const WATCH_DEDUPE_WINDOW: usize = 256;
+
+/// `/watch [topic]` – print every event on the bus, or only those of one
+/// [`Topic`], as raw JSON until ENTER is pressed.
+fn cmd_watch(args: &str, state: &ReplState) {
+    use mechos_middleware::Topic;
+    use mechos_middleware::typed::home_topic;
+    use tokio::sync::broadcast::error::RecvError;
+
+    let Some(bus) = &state.bus else {
+        println!("{}", "System not started. Run /start first.".red());
+        return;
+    };
+    let only = match args.trim() {
+        "" => None,
+        name => match Topic::ALL.into_iter().find(|topic| topic.as_str() == name) {
+            Some(topic) => Some(topic),
+            None => {
+                let names: Vec<&str> = Topic::ALL.iter().map(|topic| topic.as_str()).collect();
+                println!("{} /watch [{}]", "Usage:".yellow(), names.join("|"));
+                return;
+            }
+        },
+    };
+
+    // Events published with `publish` only reach the global channel and
+    // those published with `publish_to` only their topic's lane, so listen
+    // to both.
+    let topics: Vec<_> = Topic::ALL
+        .into_iter()
+        .filter(|topic| only.is_none_or(|only| only == *topic))
+        .collect();
+    let mut lanes = bus.subscribe_many_named(&topics, "repl-watch");
+    let mut global = bus.subscribe();
+    let stop = Arc::new(AtomicBool::new(false));
+    let stop_writer = stop.clone();
+
+    let handle = std::thread::spawn(move || {
+        let rt = match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(rt) => rt,
+            Err(_) => return,
+        };
+        rt.block_on(async move {
+            let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+            let lanes_tx = tx.clone();
+            tokio::spawn(async move {
+                loop {
+                    let received = match lanes.recv().await {
+                        Ok(received) => Ok(received),
+                        Err(RecvError::Lagged(n)) => Err(n),
+                        Err(RecvError::Closed) => break,
+                    };
+                    if lanes_tx.send(received).is_err() {
+                        break;
+                    }
+                }
+            });
+            tokio::spawn(async move {
+                loop {
+                    let received = match global.recv().await {
+                        Ok(event) => {
+                            let topic = home_topic(&event.payload);
+                            if only.is_some_and(|only| only != topic) {
+                                continue;
+                            }
+                            Ok((topic, event))
+                        }
+                        Err(RecvError::Lagged(n)) => Err(n),
+                        Err(RecvError::Closed) => break,
+                    };
+                    if tx.send(received).is_err() {
+                        break;
+                    }
+                }
+            });
+
+            let mut seen = std::collections::VecDeque::with_capacity(WATCH_DEDUPE_WINDOW);
+            while !stop_writer.load(Ordering::SeqCst) {
+                let next = tokio::time::timeout(std::time::Duration::from_millis(100), rx.recv());
+                match next.await {
+                    Ok(Some(Ok((topic, event)))) => {
+                        if seen.contains(&event.id) {
+                            continue;
+                        }
+                        if seen.len() == WATCH_DEDUPE_WINDOW {
+                            seen.pop_front();
+                        }
+                        seen.push_back(event.id);
+                        println!("{}", format_watched(topic, &event));
+                    }
+                    Ok(Some(Err(n))) => {
+                        println!("{}: {} events dropped by slow consumer", "WARN".yellow(), n);
+                    }
+                    Ok(None) => break,
+                    Err(_) => {}
+                }
+            }
+        });
+    });
+
+    println!(
+        "{} {}",
+        "  Watching".dimmed(),
+        format!(
+            "{}. Press ENTER to stop…",
+            only.map_or("every topic", |topic| topic.as_str())
+        )
+        .dimmed()
+    );
+    let mut line = String::new();
+    io::stdin().lock().read_line(&mut line).ok();
+
+    stop.store(true, Ordering::SeqCst);
+    handle.join().ok();
+    println!("{}", "  Watch stopped.".dimmed());
+}
+
+/// One `/watch` line: time, topic (colour-coded), source and the payload
+/// as JSON, truncated to [`WATCH_PAYLOAD_CHARS`].
+fn format_watched(topic: mechos_middleware::Topic, event: &mechos_types::Event) -> String {
+    use mechos_middleware::Topic;
+
+    let name = topic.as_str();
+    let name = match topic {
+        Topic::Telemetry => name.blue(),
+        Topic::HardwareCommands => name.yellow(),
+        Topic::SystemAlerts => name.red().bold(),
+        Topic::SwarmComm => name.magenta(),
+        Topic::CognitiveStream => name.cyan(),
+        Topic::SensorHighRate => name.bright_blue(),
+    };
+    let payload = serde_json::to_string(&event.payload)
+        .unwrap_or_else(|_| format!("{:?}", event.payload));
+    let payload = match payload.char_indices().nth(WATCH_PAYLOAD_CHARS) {
+        Some((end, _)) => format!("{}… ({} bytes)", &payload[..end], payload.len()),
+        None => payload,
+    };
+    format!(
+        "[{}] {:<17} {} {}",
+        event.timestamp.format("%H:%M:%S%.3f").to_string().dimmed(),
+        name,
+        event.source.dimmed(),
+        payload
+    )
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // /hardware – manual HardwareIntent injection
 // ─────────────────────────────────────────────────────────────────────────────
@@ -755,14 +1315,22 @@ fn cmd_hardware(args: &str, state: &ReplState) {
                 println!("{}: angular_velocity must be a number", "Error".red());
                 return;
             };
-            mechos_types::HardwareIntent::Drive { linear_velocity, angular_velocity }
+            mechos_types::HardwareIntent::Drive {
+                linear_velocity: mechos_types::MetersPerSecond(linear_velocity),
+                angular_velocity: mechos_types::RadiansPerSecond(angular_velocity),
+            }
         }
         ["move", xs, ys, zs] => {
             let (Ok(x), Ok(y), Ok(z)) = (xs.parse::<f32>(), ys.parse::<f32>(), zs.parse::<f32>()) else {
                 println!("{}: x, y, z must be numbers", "Error".red());
                 return;
             };
-            mechos_types::HardwareIntent::MoveEndEffector { x, y, z }
+            mechos_types::HardwareIntent::MoveEndEffector {
+                x: mechos_types::Meters(x),
+                y: mechos_types::Meters(y),
+                z: mechos_types::Meters(z),
+                frame_id: None,
+            }
         }
         ["relay", relay_id, state_str] => {
             let on = match *state_str {
@@ -787,15 +1355,16 @@ fn cmd_hardware(args: &str, state: &ReplState) {
         }
     };
 
-    // Serialise the intent and publish it as an AgentThought so the bus
-    // broadcast reaches any dashboard or log subscriber.
+    // Announce the intent on the bus so the broadcast reaches any dashboard
+    // or log subscriber.
     let payload_json = serde_json::to_string(&intent).unwrap_or_else(|_| format!("{intent:?}"));
     let event = mechos_types::Event {
         id: uuid::Uuid::new_v4(),
         timestamp: chrono::Utc::now(),
         source: "mechos-cli::hardware_override".to_string(),
-        payload: mechos_types::EventPayload::AgentThought(payload_json.clone()),
+        payload: mechos_types::EventPayload::IntentDispatched(intent),
         trace_id: None,
+        correlation_id: None,
     };
     match bus.publish_to(mechos_middleware::Topic::HardwareCommands, event) {
         Ok(_) => println!(
@@ -823,117 +1392,334 @@ fn cmd_halt(state: &ReplState) {
         source: "mechos-cli::halt".to_string(),
         payload: mechos_types::EventPayload::HardwareFault {
             component: "cli".to_string(),
-            code: 911,
+            code: mechos_types::FaultCode::EmergencyStop,
             message: "EMERGENCY_STOP: operator /halt".to_string(),
         },
         trace_id: None,
+        correlation_id: None,
     };
 
-    match bus.publish_to(mechos_middleware::Topic::SystemAlerts, event) {
+    if let Err(e) = bus.publish_to(mechos_middleware::Topic::SystemAlerts, event) {
+        println!("{}: {}", "Halt failed".red(), e);
+        return;
+    }
+
+    // Command the stop itself through the typed intent channel so every
+    // adapter zeroes its velocities.
+    let halt = mechos_types::HardwareIntent::Halt {
+        reason: "operator /halt".to_string(),
+    };
+    let halt_event = mechos_types::Event {
+        id: uuid::Uuid::new_v4(),
+        timestamp: chrono::Utc::now(),
+        source: "mechos-cli::halt".to_string(),
+        payload: mechos_types::EventPayload::IntentDispatched(halt),
+        trace_id: None,
+        correlation_id: None,
+    };
+    match bus.publish(halt_event) {
         Ok(_) => println!(
             "{}",
-            "⛔ EmergencyStop published to SystemAlerts. Agent loop suspended.".red().bold()
+            "⛔ EmergencyStop published to SystemAlerts. E-stop latched until reset from the Cockpit.".red().bold()
         ),
         Err(e) => println!("{}: {}", "Halt failed".red(), e),
     }
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// /answer – human-in-the-loop
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Show `question` above the prompt, or on stdout without a terminal.
+fn announce_question(printer: Option<&Mutex<dyn ExternalPrinter + Send>>, question: &Question) {
+    let message = format!(
+        "{} {}\n  {}",
+        "❓".bold(),
+        question.to_string().yellow().bold(),
+        "Type your answer and press ENTER.".dimmed()
+    );
+    match printer {
+        Some(printer) => {
+            let mut printer = printer.lock().unwrap_or_else(|e| e.into_inner());
+            if printer.print(message.clone()).is_err() {
+                println!("{message}");
+            }
+        }
+        None => println!("{message}"),
+    }
+}
+
+/// Send `text` to the agent as the answer to its pending question.
+fn cmd_answer(text: &str, state: &ReplState) {
+    let Some(bus) = &state.bus else {
+        println!("{}", "System not started. Run /start first.".red());
+        return;
+    };
+    match hitl::answer(bus, &state.question, text) {
+        Ok(question) => println!(
+            "{} {}",
+            "✓ Answer sent to".green(),
+            question.asked_by.bold()
+        ),
+        Err(e) => println!("{}: {}", "Cannot answer".red(), e),
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // /memory – episodic memory inspector
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// `/memory [list|recent [n]|query <terms>|search <text>|forget <filter>]`
+/// against the store opened by `/start`; bare `/memory` lists.
 fn cmd_memory(args: &str, state: &ReplState) {
     let Some(store) = &state.store else {
         println!("{}", "System not started. Run /start first.".red());
         return;
     };
 
-    let parts: Vec<&str> = args.splitn(2, ' ').collect();
-    let subcommand = parts.first().copied().unwrap_or("list");
-    let query = parts.get(1).copied().unwrap_or("").trim().trim_matches('"');
+    let words = split_args(args);
+    let (subcommand, rest) = match words.split_first() {
+        Some((subcommand, rest)) => (subcommand.as_str(), rest),
+        None => ("list", &[][..]),
+    };
+    let query = rest.join(" ");
+
+    match subcommand {
+        "list" => {
+            let all = match on_store(store, |s| async move { s.all_entries().await }) {
+                Ok(all) => all,
+                Err(e) => {
+                println!("{}: {}", "Memory read error".red(), e);
+                return;
+            }
+            };
+            if all.is_empty() {
+                println!("{}", "  Memory store is empty.".dimmed());
+                return;
+            }
+            println!("{}", "Episodic Memory Store".bold().underline());
+            for entry in &all {
+                println!(
+                    "  {} {} [{}] {}",
+                    entry.timestamp.format("%Y-%m-%d %H:%M:%S").to_string().dimmed(),
+                    entry.source.cyan(),
+                    entry.embedding.len().to_string().dimmed(),
+                    entry.summary.bold()
+                );
+            }
+            println!("  {} entries total.", all.len().to_string().yellow());
+        }
+        "recent" => {
+            let count = match rest.first().map(|n| n.parse::<usize>()) {
+                None => 10,
+                Some(Ok(count)) => count,
+                Some(Err(_)) => {
+                    println!("{}", "Usage: /memory recent [count]".yellow());
+                    return;
+                }
+            };
+            let mut entries = match on_store(store, |s| async move { s.all_entries().await }) {
+                Ok(entries) => entries,
+                Err(e) => {
+                println!("{}: {}", "Memory read error".red(), e);
+                return;
+            }
+            };
+            entries.reverse();
+            entries.truncate(count);
+            commands::print_memories("Recent Memories", &entries);
+        }
+        "query" => {
+            if query.is_empty() {
+                println!("{}", "Usage: /memory query <search terms>".yellow());
+                return;
+            }
+            let all = match on_store(store, |s| async move { s.all_entries().await }) {
+                Ok(all) => all,
+                Err(e) => {
+                println!("{}: {}", "Memory read error".red(), e);
+                return;
+            }
+            };
+            let needle = query.to_lowercase();
+            let matches: Vec<_> = all
+                .iter()
+                .filter(|e| e.summary.to_lowercase().contains(&needle))
+                .collect();
+
+            if matches.is_empty() {
+                println!(
+                    "{} '{}'",
+                    "  No memories match:".dimmed(),
+                    query.yellow()
+                );
+                return;
+            }
+            println!(
+                "{} '{}' ({} result{}):",
+                "Memory query for".bold().underline(),
+                query.yellow(),
+                matches.len().to_string().green(),
+                if matches.len() == 1 { "" } else { "s" }
+            );
+            for entry in &matches {
+                println!(
+                    "  {} {} {}",
+                    entry.timestamp.format("%Y-%m-%d %H:%M:%S").to_string().dimmed(),
+                    entry.source.cyan(),
+                    entry.summary.bold()
+                );
+            }
+        }
+        "search" => {
+            if query.is_empty() {
+                println!("{}", "Usage: /memory search <text>".yellow());
+                return;
+            }
+            let text = query.clone();
+            let filter = mechos_memory::episodic::MemoryFilter::default();
+            match on_store(store, move |s| async move { s.recall_text(&text, 10, &filter).await }) {
+                Ok(results) => commands::print_recall(&query, &results),
+                Err(e) => println!("{}: {}", "Memory search error".red(), e),
+            }
+        }
+        "forget" => {
+            let filter = match commands::parse_memory_filter(rest) {
+                Ok(filter) => filter,
+                Err(e) => {
+                    println!("{}: {}", "Invalid filter".red(), e);
+                    println!("{}", "Usage: /memory forget source=<s> tag=<t> contains=<text> below=<importance> before|after=<time or age, e.g. 7d>".yellow());
+                    return;
+                }
+            };
+            let matching = filter.clone();
+            let count = match on_store(store, move |s| async move { s.entries_matching(&matching).await }) {
+                Ok(entries) => entries.len(),
+                Err(e) => {
+                println!("{}: {}", "Memory read error".red(), e);
+                return;
+            }
+            };
+            if count == 0 {
+                println!("{}", "  No memories match the filter.".dimmed());
+                return;
+            }
+            let answer = prompt_str(&format!("  Forget {count} memories? [y/N] "), "n");
+            if !matches!(answer.to_lowercase().as_str(), "y" | "yes") {
+                println!("{}", "  Nothing forgotten.".dimmed());
+                return;
+            }
+            match on_store(store, move |s| async move { s.forget(&filter).await }) {
+                Ok(removed) => println!("{} {} memories", "✓ Forgot".green(), removed.to_string().bold()),
+                Err(e) => println!("{}: {}", "Memory forget error".red(), e),
+            }
+        }
+        _ => {
+            println!("{}", "Usage:".bold());
+            println!("  /memory list");
+            println!("  /memory recent [count]");
+            println!("  /memory query <search terms>");
+            println!("  /memory search <text>");
+            println!("  /memory forget <key=value …>");
+        }
+    }
+}
 
-    // Run the async store operations in a dedicated thread so that:
-    // (a) we avoid creating a nested runtime when called from inside tokio tests
-    // (b) the sync REPL caller doesn't need an async runtime
-    let store_clone = store.clone();
-    let entries = std::thread::spawn(move || {
+/// Run `op` against a clone of `store` on a dedicated thread, so that:
+/// (a) we avoid creating a nested runtime when called from inside tokio tests
+/// (b) the sync REPL caller doesn't need an async runtime
+fn on_store<T, F, Fut>(
+    store: &mechos_memory::episodic::EpisodicStore,
+    op: F,
+) -> Result<T, mechos_memory::episodic::EpisodicError>
+where
+    T: Send + 'static,
+    F: FnOnce(mechos_memory::episodic::EpisodicStore) -> Fut + Send + 'static,
+    Fut: std::future::Future<Output = Result<T, mechos_memory::episodic::EpisodicError>>,
+{
+    let store = store.clone();
+    std::thread::spawn(move || {
         let rt = tokio::runtime::Builder::new_current_thread()
             .enable_all()
             .build()
             .unwrap_or_else(|e| panic!("failed to create tokio runtime: {e}"));
-        rt.block_on(store_clone.all_entries())
+        rt.block_on(op(store))
     })
     .join()
     .unwrap_or_else(|e| {
         Err(mechos_memory::episodic::EpisodicError::TaskPanic(format!(
             "thread panicked: {e:?}"
         )))
-    });
+    })
+}
 
-    match entries {
+// ─────────────────────────────────────────────────────────────────────────────
+// /tasks – fleet task board
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// `/tasks` takes the arguments of `mechos tasks`; bare `/tasks` lists.
+fn cmd_tasks(args: &str) {
+    let mut args = split_args(args);
+    if args.is_empty() {
+        args.push("list".to_string());
+    }
+    match ReplTasks::try_parse_from(args) {
+        Ok(tasks) => {
+            if let Err(e) = commands::tasks(tasks.args) {
+                println!("{}: {}", "Task board error".red(), e);
+            }
+        }
         Err(e) => {
-            println!("{}: {}", "Memory read error".red(), e);
+            let _ = e.print();
         }
-        Ok(all) => match subcommand {
-            "list" => {
-                if all.is_empty() {
-                    println!("{}", "  Memory store is empty.".dimmed());
-                    return;
-                }
-                println!("{}", "Episodic Memory Store".bold().underline());
-                for entry in &all {
-                    println!(
-                        "  {} {} [{}] {}",
-                        entry.timestamp.format("%Y-%m-%d %H:%M:%S").to_string().dimmed(),
-                        entry.source.cyan(),
-                        entry.embedding.len().to_string().dimmed(),
-                        entry.summary.bold()
-                    );
-                }
-                println!("  {} entries total.", all.len().to_string().yellow());
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// /caps – capability administration
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// `/caps` takes the arguments of `mechos caps`; bare `/caps` lists.
+fn cmd_caps(args: &str) {
+    match ReplCaps::try_parse_from(split_args(args)) {
+        Ok(caps) => {
+            if let Err(e) = commands::caps(caps.args) {
+                println!("{}: {}", "Capability error".red(), e);
             }
-            "query" => {
-                if query.is_empty() {
-                    println!("{}", "Usage: /memory query <search terms>".yellow());
-                    return;
-                }
-                let needle = query.to_lowercase();
-                let matches: Vec<_> = all
-                    .iter()
-                    .filter(|e| e.summary.to_lowercase().contains(&needle))
-                    .collect();
-
-                if matches.is_empty() {
-                    println!(
-                        "{} '{}'",
-                        "  No memories match:".dimmed(),
-                        query.yellow()
-                    );
-                    return;
-                }
-                println!(
-                    "{} '{}' ({} result{}):",
-                    "Memory query for".bold().underline(),
-                    query.yellow(),
-                    matches.len().to_string().green(),
-                    if matches.len() == 1 { "" } else { "s" }
-                );
-                for entry in &matches {
-                    println!(
-                        "  {} {} {}",
-                        entry.timestamp.format("%Y-%m-%d %H:%M:%S").to_string().dimmed(),
-                        entry.source.cyan(),
-                        entry.summary.bold()
-                    );
+        }
+        Err(e) => {
+            let _ = e.print();
+        }
+    }
+}
+
+/// Split a command line into words; single or double quotes group words,
+/// e.g. `post "Move Box 1"` or `--result '{"boxes":3}'`.
+fn split_args(line: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut quote = None;
+    let mut started = false;
+    for c in line.chars() {
+        match (c, quote) {
+            ('"' | '\'', None) => (quote, started) = (Some(c), true),
+            (c, Some(open)) if c == open => quote = None,
+            (c, None) if c.is_whitespace() => {
+                if started {
+                    words.push(std::mem::take(&mut word));
                 }
+                started = false;
             }
-            _ => {
-                println!("{}", "Usage:".bold());
-                println!("  /memory list");
-                println!("  /memory query <search terms>");
+            (c, _) => {
+                word.push(c);
+                started = true;
             }
-        },
+        }
+    }
+    if started {
+        words.push(word);
     }
+    words
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -969,7 +1755,7 @@ fn prompt_u16(msg: &str, default: u16) -> u16 {
 }
 
 /// Prompt for a string value.  Returns `default` when the user presses Enter.
-fn prompt_str(msg: &str, default: &str) -> String {
+pub(crate) fn prompt_str(msg: &str, default: &str) -> String {
     print!("{}", msg);
     io::stdout().flush().ok();
 
@@ -1000,7 +1786,7 @@ mod tests {
     #[test]
     fn dispatch_unknown_command_does_not_panic() {
         let shutdown = Arc::new(AtomicBool::new(false));
-        let mut state = ReplState { bus: None, store: None };
+        let mut state = ReplState::default();
         // Should print "Unknown command" but not panic.
         dispatch("/foobar", &mut state, shutdown.clone());
         assert!(!shutdown.load(Ordering::SeqCst));
@@ -1009,7 +1795,7 @@ mod tests {
     #[test]
     fn dispatch_quit_sets_shutdown() {
         let shutdown = Arc::new(AtomicBool::new(false));
-        let mut state = ReplState { bus: None, store: None };
+        let mut state = ReplState::default();
         dispatch("/quit", &mut state, shutdown.clone());
         assert!(shutdown.load(Ordering::SeqCst));
     }
@@ -1017,35 +1803,111 @@ mod tests {
     #[test]
     fn dispatch_exit_sets_shutdown() {
         let shutdown = Arc::new(AtomicBool::new(false));
-        let mut state = ReplState { bus: None, store: None };
+        let mut state = ReplState::default();
         dispatch("/exit", &mut state, shutdown.clone());
         assert!(shutdown.load(Ordering::SeqCst));
     }
 
+    #[test]
+    fn tasks_arguments_keep_quoted_titles() {
+        assert_eq!(
+            split_args(r#"post "Move Box 1"  -d "" --priority 2"#),
+            ["post", "Move Box 1", "-d", "", "--priority", "2"]
+        );
+        assert_eq!(
+            split_args(r#"complete t1 --result '{"note":"Box 1"}'"#),
+            ["complete", "t1", "--result", r#"{"note":"Box 1"}"#]
+        );
+        assert!(split_args("  ").is_empty());
+    }
+
+    #[test]
+    fn tasks_command_round_trips_through_the_board() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = dir.path().join("tasks.db");
+        let db = db.to_string_lossy();
+        cmd_tasks(&format!(r#"post "Move Box 1" --db {db}"#));
+
+        let board = mechos_memory::task_board::TaskBoard::open(&db).unwrap();
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let tasks = runtime.block_on(board.list_all()).unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "Move Box 1");
+
+        let id = &tasks[0].id;
+        cmd_tasks(&format!("claim {id} --robot rover-a --db {db}"));
+        cmd_tasks(&format!(r#"complete {id} --robot rover-a --result '{{"boxes":1}}' --db {db}"#));
+        let task = runtime.block_on(board.get(id)).unwrap();
+        assert_eq!(task.status, mechos_memory::task_board::TaskStatus::Completed);
+        assert_eq!(task.result, Some(serde_json::json!({"boxes": 1})));
+    }
+
     #[test]
     fn hardware_command_without_start_prints_error() {
-        let state = ReplState { bus: None, store: None };
+        let state = ReplState::default();
         // Should not panic when bus is None.
         cmd_hardware("drive 1.0 0.0", &state);
     }
 
     #[test]
     fn halt_command_without_start_prints_error() {
-        let state = ReplState { bus: None, store: None };
+        let state = ReplState::default();
         // Should not panic when bus is None.
         cmd_halt(&state);
     }
 
     #[test]
-    fn logs_command_without_start_prints_error() {
-        let state = ReplState { bus: None, store: None };
+    fn events_command_without_start_prints_error() {
+        let state = ReplState::default();
         // Should not panic when bus is None.
-        cmd_logs(&state);
+        cmd_events(&state);
+    }
+
+    #[test]
+    fn logs_arguments_are_a_level_then_a_target() {
+        let filter = parse_logs_args("warn kernel").unwrap();
+        assert_eq!(filter.min_level, Some(tracing::Level::WARN));
+        assert_eq!(filter.target.as_deref(), Some("kernel"));
+        assert_eq!(filter.limit, Some(LOGS_LIMIT));
+
+        let filter = parse_logs_args("mechos_middleware").unwrap();
+        assert_eq!((filter.min_level, filter.target.as_deref()), (None, Some("mechos_middleware")));
+        assert_eq!(parse_logs_args("").unwrap().min_level, None);
+        assert!(parse_logs_args("warn kernel extra").is_err());
+        // Works without a running stack.
+        cmd_logs("error");
+    }
+
+    #[test]
+    fn watch_command_without_start_prints_error() {
+        let state = ReplState::default();
+        cmd_watch("telemetry", &state);
+    }
+
+    #[test]
+    fn watched_events_name_their_topic_and_truncate_payloads() {
+        let event = |payload| mechos_types::Event {
+            id: uuid::Uuid::new_v4(),
+            timestamp: chrono::Utc::now(),
+            source: "test".to_string(),
+            payload,
+            trace_id: None,
+            correlation_id: None,
+        };
+        let thought = event(mechos_types::EventPayload::Reasoning("ok".to_string()));
+        let line = format_watched(mechos_middleware::Topic::CognitiveStream, &thought);
+        assert!(line.contains("cognitive_stream"));
+        assert!(line.contains(r#"{"Reasoning":"ok"}"#), "{line}");
+
+        let long = event(mechos_types::EventPayload::Reasoning("é".repeat(500)));
+        let line = format_watched(mechos_middleware::Topic::CognitiveStream, &long);
+        assert!(line.contains("… ("), "{line}");
+        assert!(line.chars().count() < 300);
     }
 
     #[test]
     fn memory_command_without_start_prints_error() {
-        let state = ReplState { bus: None, store: None };
+        let state = ReplState::default();
         cmd_memory("list", &state);
     }
 
@@ -1056,6 +1918,8 @@ mod tests {
         let state = ReplState {
             bus: Some(bus),
             store: None,
+            stack: None,
+            ..Default::default()
         };
         cmd_hardware("drive 0.5 -0.3", &state);
         // The event should be in the topic channel.
@@ -1072,6 +1936,8 @@ mod tests {
         let state = ReplState {
             bus: Some(bus),
             store: None,
+            stack: None,
+            ..Default::default()
         };
         cmd_hardware("move 0.5 -0.1 0.3", &state);
         assert!(rx.recv().await.is_ok(), "expected event on bus after /hardware move");
@@ -1084,6 +1950,8 @@ mod tests {
         let state = ReplState {
             bus: Some(bus),
             store: None,
+            stack: None,
+            ..Default::default()
         };
         cmd_hardware("relay door_1 on", &state);
         assert!(rx.recv().await.is_ok(), "expected event on bus after /hardware relay on");
@@ -1098,6 +1966,8 @@ mod tests {
         let state = ReplState {
             bus: Some(bus),
             store: None,
+            stack: None,
+            ..Default::default()
         };
         // Should print usage, not panic, and not publish (no subscriber to check).
         cmd_hardware("drive not_a_number 0.0", &state);
@@ -1110,13 +1980,18 @@ mod tests {
         let state = ReplState {
             bus: Some(bus),
             store: None,
+            stack: None,
+            ..Default::default()
         };
         cmd_halt(&state);
         let event = rx.recv().await.expect("expected fault event after /halt");
         assert_eq!(event.source, "mechos-cli::halt");
         assert!(matches!(
             event.payload,
-            mechos_types::EventPayload::HardwareFault { code: 911, .. }
+            mechos_types::EventPayload::HardwareFault {
+                code: mechos_types::FaultCode::EmergencyStop,
+                ..
+            }
         ));
     }
 
@@ -1126,6 +2001,8 @@ mod tests {
         let state = ReplState {
             bus: None,
             store: Some(store),
+            stack: None,
+            ..Default::default()
         };
         // Should not panic on an empty store.
         cmd_memory("list", &state);
@@ -1156,8 +2033,35 @@ mod tests {
         let state = ReplState {
             bus: None,
             store: Some(store),
+            stack: None,
+            ..Default::default()
         };
         // Should not panic; no assertion on output but we verify no crash.
         cmd_memory("query blue table", &state);
     }
+
+    #[test]
+    fn memory_search_recent_and_forget_filters() {
+        let store = mechos_memory::episodic::EpisodicStore::open_in_memory().unwrap();
+        on_store(&store, |s| async move {
+            s.store_text("camera", "Left the red cup on the kitchen table").await?;
+            s.store_text("lidar", "Mapped the east corridor").await
+        })
+        .unwrap();
+
+        let state = ReplState {
+            bus: None,
+            store: Some(store.clone()),
+            stack: None,
+            ..Default::default()
+        };
+        cmd_memory("search \"where is the red cup\"", &state);
+        cmd_memory("recent 1", &state);
+        // Neither an invalid filter nor one matching nothing forgets anything
+        // (or prompts for confirmation).
+        cmd_memory("forget colour=red", &state);
+        cmd_memory("forget source=sonar", &state);
+        let left = on_store(&store, |s| async move { s.all_entries().await }).unwrap();
+        assert_eq!(left.len(), 2);
+    }
 }
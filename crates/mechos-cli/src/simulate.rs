@@ -0,0 +1,857 @@
+//! `mechos simulate` – scripted scenarios, for CI of robot behaviours.
+//!
+//! ```text
+//! mechos simulate --scenario <file.yaml>... [--dashboard <url>] [--junit <path>]
+//! ```
+//!
+//! A scenario scripts a world and what should happen in it:
+//!
+//! ```yaml
+//! name: corridor
+//! max_ticks: 150              # default 200
+//! tick_ms: 100                # simulated time per tick, default 100
+//! start: { x: 0.0, y: 0.0, heading: 0.0 }
+//! waypoints: [[3.0, 0.0], [3.0, 2.0]]   # visited in order; the last is the goal
+//! tolerance: 0.3              # metres, default 0.3
+//! obstacles:
+//!   - { x: 1.5, y: 1.0, radius: 0.2 }
+//! safety: { max_linear_mps: 0.5, max_angular_rps: 1.0 }  # as in [safety]
+//! llm:                        # scripted replies; omit to ask the configured model
+//!   - action: FollowWaypoints
+//!     payload: { points: [[3.0, 0.0], [3.0, 2.0]], max_speed: 0.4 }
+//! faults:                     # published as HardwareFault events
+//!   - { at_tick: 80, component: bumper, code: emergency_stop, message: contact }
+//! human_responses: ["Yes, go ahead"]   # answers to AskHuman, in order
+//! expect:
+//!   reached_goal: true
+//!   max_gate_violations: 0    # default 0
+//! ```
+//!
+//! Each scenario runs a fresh [`AgentLoop`] with the scenario's `safety`
+//! limits (or the config's), until the goal is reached or `max_ticks` run
+//! out.  The robot is a built-in [`MockRobot`]: a unicycle that executes the
+//! approved intents in simulated time, feeds its odometry to the loop and
+//! scans the obstacles with a simulated LiDAR.  With `--dashboard` the
+//! intents drive the Three.js simulation dashboard through a
+//! [`DashboardSimAdapter`] in real time instead, and its telemetry gives the
+//! pose; the scenario's obstacles are then the dashboard's own.
+//!
+//! Every expectation becomes a test case: reaching the goal (when
+//! `reached_goal` is set), the number of gate violations (intents the
+//! kernel or the trajectory check refused) and, with the mock robot, not
+//! hitting an obstacle.  `--junit` writes them as a JUnit XML report; the
+//! command fails when any case does.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use colored::Colorize;
+use mechos_middleware::{DashboardSimAdapter, EventBus, MechAdapter};
+use mechos_perception::fusion::OdometryData;
+use mechos_runtime::llm_driver::LlmDriver;
+use mechos_runtime::{AgentLoop, AgentLoopConfig};
+use mechos_types::{Event, EventPayload, FaultCode, HardwareIntent, HumanAnswer, MechError};
+use serde::Deserialize;
+use tokio::sync::broadcast;
+
+use crate::cli::SimulateArgs;
+use crate::config::{Config, SafetyConfig};
+use crate::{commands, daemon, stack};
+
+/// Half-width of the mock robot's footprint, in metres.
+const ROBOT_RADIUS_M: f32 = 0.3;
+
+/// How long an approved `Drive` keeps the mock robot moving, like the
+/// deadline the agent loop stamps on it.
+const DRIVE_HOLD_S: f32 = 0.5;
+
+/// Beams of the mock robot's LiDAR, a full turn.
+const SCAN_BEAMS: usize = 180;
+
+/// Range of the mock robot's LiDAR, in metres.
+const SCAN_RANGE_M: f32 = 10.0;
+
+/// Source of the events the simulator publishes.
+const SOURCE: &str = "mechos-cli::simulate";
+
+/// Run every scenario of `args` and report the outcome.
+pub fn run(args: SimulateArgs) -> Result<(), String> {
+    let cfg = daemon::load_config(None)?;
+    let mut suites = Vec::new();
+    for path in &args.scenarios {
+        let scenario = Scenario::load(path)?;
+        println!(
+            "  Scenario {} ({}) …",
+            scenario.name.bold(),
+            path.display().to_string().dimmed()
+        );
+        let suite = run_scenario(&scenario, &cfg, args.dashboard.as_deref())?;
+        for case in &suite.cases {
+            match &case.failure {
+                None => println!("    {} {}", "✓".green(), case.name),
+                Some(why) => println!("    {} {}: {why}", "✗".red(), case.name),
+            }
+        }
+        suites.push(suite);
+    }
+
+    if let Some(path) = &args.junit {
+        std::fs::write(path, junit_report(&suites))
+            .map_err(|e| format!("cannot write {}: {e}", path.display()))?;
+        println!("  JUnit report written to {}", path.display());
+    }
+    let failed = suites.iter().flat_map(|s| &s.cases).filter(|c| c.failure.is_some()).count();
+    if failed > 0 {
+        return Err(format!("{failed} expectation(s) failed"));
+    }
+    println!("  {} All expectations met.", "✓".green().bold());
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Scenario files
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A scripted world and its expected outcome, read from YAML.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Scenario {
+    /// Defaults to the file name.
+    #[serde(default)]
+    pub name: String,
+    #[serde(default = "default_max_ticks")]
+    pub max_ticks: usize,
+    #[serde(default = "default_tick_ms")]
+    pub tick_ms: u64,
+    #[serde(default)]
+    pub start: Pose,
+    /// Checkpoints to visit in order; the last one is the goal.
+    #[serde(default)]
+    pub waypoints: Vec<(f32, f32)>,
+    #[serde(default = "default_tolerance")]
+    pub tolerance: f32,
+    #[serde(default)]
+    pub obstacles: Vec<Obstacle>,
+    /// Limits of the run; the config's `[safety]` when absent.
+    #[serde(default)]
+    pub safety: Option<SafetyConfig>,
+    /// The agent's goal; by default, to visit the waypoints.
+    #[serde(default)]
+    pub mission: Option<String>,
+    /// Scripted LLM replies, in order.  Empty asks the configured model.
+    #[serde(default)]
+    pub llm: Vec<HardwareIntent>,
+    #[serde(default)]
+    pub faults: Vec<InjectedFault>,
+    /// Answers to the agent's `AskHuman` questions, in order.
+    #[serde(default)]
+    pub human_responses: Vec<String>,
+    #[serde(default)]
+    pub expect: Expectations,
+}
+
+fn default_max_ticks() -> usize {
+    200
+}
+fn default_tick_ms() -> u64 {
+    100
+}
+fn default_tolerance() -> f32 {
+    0.3
+}
+
+/// A pose in the map frame: metres and radians.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Pose {
+    pub x: f32,
+    pub y: f32,
+    #[serde(default)]
+    pub heading: f32,
+}
+
+/// A round obstacle in the map frame.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Obstacle {
+    pub x: f32,
+    pub y: f32,
+    pub radius: f32,
+}
+
+/// A `HardwareFault` event published before tick `at_tick`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct InjectedFault {
+    pub at_tick: usize,
+    pub component: String,
+    pub code: FaultCode,
+    #[serde(default)]
+    pub message: String,
+}
+
+/// What the run must show.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Expectations {
+    /// Whether the goal is reached; not checked when absent.
+    #[serde(default)]
+    pub reached_goal: Option<bool>,
+    #[serde(default)]
+    pub max_gate_violations: usize,
+    #[serde(default)]
+    pub min_gate_violations: usize,
+}
+
+impl Scenario {
+    /// Read and check the scenario at `path`.
+    pub(crate) fn load(path: &Path) -> Result<Self, String> {
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        let mut scenario = Self::parse(&raw).map_err(|e| format!("{}: {e}", path.display()))?;
+        if scenario.name.is_empty() {
+            scenario.name = path
+                .file_stem()
+                .map_or_else(|| "scenario".to_string(), |s| s.to_string_lossy().into_owned());
+        }
+        Ok(scenario)
+    }
+
+    fn parse(raw: &str) -> Result<Self, String> {
+        let scenario: Self = serde_yaml::from_str(raw).map_err(|e| e.to_string())?;
+        if scenario.max_ticks == 0 || scenario.tick_ms == 0 {
+            return Err("max_ticks and tick_ms must be positive".to_string());
+        }
+        if scenario.expect.reached_goal.is_some() && scenario.waypoints.is_empty() {
+            return Err("expect.reached_goal needs waypoints".to_string());
+        }
+        if scenario.expect.min_gate_violations > scenario.expect.max_gate_violations {
+            return Err("expect.min_gate_violations exceeds max_gate_violations".to_string());
+        }
+        if let Some(o) = scenario.obstacles.iter().find(|o| o.radius.is_nan() || o.radius <= 0.0) {
+            return Err(format!("obstacle at ({}, {}) has no radius", o.x, o.y));
+        }
+        if let Some(problem) = scenario.safety.iter().flat_map(|s| s.problems()).next() {
+            return Err(problem);
+        }
+        Ok(scenario)
+    }
+
+    /// The goal given to the agent.
+    fn mission(&self) -> Option<String> {
+        if self.mission.is_some() || self.waypoints.is_empty() {
+            return self.mission.clone();
+        }
+        let points: Vec<String> = self
+            .waypoints
+            .iter()
+            .map(|(x, y)| format!("({x}, {y})"))
+            .collect();
+        Some(format!("Visit the waypoints {} in order.", points.join(", ")))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Robots
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// The built-in robot: a unicycle that executes approved intents in
+/// simulated time and scans round obstacles.
+#[derive(Debug, Clone)]
+pub(crate) struct MockRobot {
+    pub pose: Pose,
+    obstacles: Vec<Obstacle>,
+    /// Linear and angular velocity of the last `Drive`.
+    velocity: (f32, f32),
+    /// Seconds the velocity stays valid.
+    hold: f32,
+    /// Remaining `FollowWaypoints` route and its speed.
+    route: VecDeque<(f32, f32)>,
+    route_speed: f32,
+}
+
+impl MockRobot {
+    pub(crate) fn new(start: Pose, obstacles: Vec<Obstacle>) -> Self {
+        Self {
+            pose: start,
+            obstacles,
+            velocity: (0.0, 0.0),
+            hold: 0.0,
+            route: VecDeque::new(),
+            route_speed: 0.0,
+        }
+    }
+
+    /// Start executing `intent`; a new motion replaces the last.
+    pub(crate) fn execute(&mut self, intent: &HardwareIntent) {
+        match intent {
+            HardwareIntent::Drive {
+                linear_velocity,
+                angular_velocity,
+            } => {
+                self.route.clear();
+                self.velocity = (linear_velocity.get(), angular_velocity.get());
+                self.hold = DRIVE_HOLD_S;
+            }
+            HardwareIntent::FollowWaypoints { points, max_speed } => {
+                self.hold = 0.0;
+                self.route = points.iter().map(|(x, y)| (x.get(), y.get())).collect();
+                self.route_speed = max_speed.get();
+            }
+            HardwareIntent::Halt { .. } => {
+                self.hold = 0.0;
+                self.route.clear();
+            }
+            _ => {}
+        }
+    }
+
+    /// Advance `dt` seconds.
+    pub(crate) fn step(&mut self, dt: f32) {
+        if self.hold > 0.0 {
+            let t = dt.min(self.hold);
+            self.hold -= t;
+            let (linear, angular) = self.velocity;
+            self.pose.heading += angular * t;
+            self.pose.x += linear * t * self.pose.heading.cos();
+            self.pose.y += linear * t * self.pose.heading.sin();
+            return;
+        }
+        let mut budget = self.route_speed * dt;
+        while let Some(&(x, y)) = self.route.front() {
+            let (dx, dy) = (x - self.pose.x, y - self.pose.y);
+            let distance = dx.hypot(dy);
+            if distance > 0.0 {
+                self.pose.heading = dy.atan2(dx);
+            }
+            if distance > budget {
+                self.pose.x += dx / distance * budget;
+                self.pose.y += dy / distance * budget;
+                return;
+            }
+            (self.pose.x, self.pose.y) = (x, y);
+            budget -= distance;
+            self.route.pop_front();
+        }
+    }
+
+    /// Ranges of a full-turn LiDAR scan from `-π`, capped at the sensor's
+    /// range.
+    pub(crate) fn scan(&self) -> Vec<f32> {
+        (0..SCAN_BEAMS)
+            .map(|beam| {
+                let angle = self.pose.heading - std::f32::consts::PI + beam as f32 * scan_increment();
+                let (sin, cos) = angle.sin_cos();
+                self.obstacles
+                    .iter()
+                    .filter_map(|o| ray_hits_circle((self.pose.x, self.pose.y), (cos, sin), o))
+                    .fold(SCAN_RANGE_M, f32::min)
+            })
+            .collect()
+    }
+
+    /// The obstacle the robot's footprint overlaps, if any.
+    pub(crate) fn collision(&self) -> Option<&Obstacle> {
+        self.obstacles.iter().find(|o| {
+            (o.x - self.pose.x).hypot(o.y - self.pose.y) < o.radius + ROBOT_RADIUS_M
+        })
+    }
+}
+
+fn scan_increment() -> f32 {
+    std::f32::consts::TAU / SCAN_BEAMS as f32
+}
+
+/// Distance along the unit ray from `origin` to circle `o`, if it hits.
+fn ray_hits_circle(origin: (f32, f32), (dx, dy): (f32, f32), o: &Obstacle) -> Option<f32> {
+    let (fx, fy) = (origin.0 - o.x, origin.1 - o.y);
+    let b = fx * dx + fy * dy;
+    let c = fx * fx + fy * fy - o.radius * o.radius;
+    let discriminant = b * b - c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let t = -b - discriminant.sqrt();
+    (t >= 0.0).then_some(t)
+}
+
+/// The robot a scenario drives.
+enum Robot {
+    Mock(MockRobot),
+    /// The simulation dashboard, posing through its telemetry.
+    Dashboard {
+        adapter: Arc<DashboardSimAdapter>,
+        telemetry: broadcast::Receiver<Event>,
+        pose: Pose,
+    },
+}
+
+impl Robot {
+    fn pose(&self) -> Pose {
+        match self {
+            Robot::Mock(robot) => robot.pose,
+            Robot::Dashboard { pose, .. } => *pose,
+        }
+    }
+
+    /// Give `agent` the robot's odometry and, for the mock, a scan.
+    fn sense(&mut self, agent: &mut AgentLoop, bus: &EventBus) {
+        if let Robot::Dashboard {
+            telemetry, pose, ..
+        } = self
+        {
+            while let Ok(event) = telemetry.try_recv() {
+                if let EventPayload::Telemetry(t) = event.payload {
+                    *pose = Pose {
+                        x: t.position_x.get(),
+                        y: t.position_y.get(),
+                        heading: t.heading_rad,
+                    };
+                }
+            }
+        }
+        let pose = self.pose();
+        agent.update_odometry(OdometryData {
+            position_x: pose.x,
+            position_y: pose.y,
+            heading_rad: pose.heading,
+            velocity_x: 0.0,
+            velocity_y: 0.0,
+        });
+        if let Robot::Mock(robot) = self {
+            let _ = bus.publish(event(EventPayload::LidarScan {
+                ranges: robot.scan(),
+                angle_min_rad: -std::f32::consts::PI,
+                angle_increment_rad: scan_increment(),
+                stamp: None,
+            }));
+        }
+    }
+
+    async fn execute(&mut self, intent: &HardwareIntent) -> Result<(), MechError> {
+        match self {
+            Robot::Mock(robot) => {
+                robot.execute(intent);
+                Ok(())
+            }
+            Robot::Dashboard { adapter, .. } => adapter.execute_intent(intent.clone()).await,
+        }
+    }
+
+    /// Let `dt` seconds pass: simulated for the mock, real for the
+    /// dashboard.
+    async fn step(&mut self, dt: f32) {
+        match self {
+            Robot::Mock(robot) => robot.step(dt),
+            Robot::Dashboard { .. } => tokio::time::sleep(Duration::from_secs_f32(dt)).await,
+        }
+    }
+
+    fn collision(&self) -> Option<&Obstacle> {
+        match self {
+            Robot::Mock(robot) => robot.collision(),
+            Robot::Dashboard { .. } => None,
+        }
+    }
+}
+
+fn event(payload: EventPayload) -> Event {
+    Event {
+        id: uuid::Uuid::new_v4(),
+        timestamp: chrono::Utc::now(),
+        source: SOURCE.to_string(),
+        payload,
+        trace_id: None,
+        correlation_id: None,
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Running
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// What happened in a run.
+#[derive(Debug, Default)]
+pub(crate) struct Outcome {
+    pub ticks: usize,
+    pub approved: usize,
+    /// Waypoints visited, in order.
+    pub visited: usize,
+    /// The tick after which the goal was reached.
+    pub reached_at: Option<usize>,
+    /// `tick N: <error>` for every refused intent.
+    pub violations: Vec<String>,
+    /// `tick N: <obstacle>` for every obstacle run into.
+    pub collisions: Vec<String>,
+}
+
+/// Whether `error` is the kernel gate or the trajectory check refusing an
+/// intent, rather than the loop idling (paused, e-stopped, waiting for a
+/// human or an approval, or out of LLM replies).
+fn is_gate_violation(error: &MechError) -> bool {
+    match error {
+        MechError::Unauthorized(_) => true,
+        MechError::HardwareFault {
+            code, component, ..
+        } => component != "agent_loop" && *code != FaultCode::ApprovalPending,
+        _ => false,
+    }
+}
+
+/// Run `scenario` and judge its outcome.
+fn run_scenario(scenario: &Scenario, cfg: &Config, dashboard: Option<&str>) -> Result<Suite, String> {
+    let started = Instant::now();
+    let outcome = commands::block_on(async {
+        let mut agent = build_agent(scenario, cfg)?;
+        let mut robot = match dashboard {
+            None => Robot::Mock(MockRobot::new(scenario.start, scenario.obstacles.clone())),
+            Some(url) => {
+                let bus = Arc::new(agent.bus());
+                let adapter = Arc::new(DashboardSimAdapter::new(Arc::clone(&bus), url));
+                let link = Arc::clone(&adapter);
+                tokio::spawn(async move { link.run().await });
+                Robot::Dashboard {
+                    adapter,
+                    telemetry: bus.subscribe(),
+                    pose: scenario.start,
+                }
+            }
+        };
+        Ok::<_, String>(drive(scenario, &mut agent, &mut robot).await)
+    })??;
+    let cases = judge(scenario, &outcome, dashboard.is_none());
+    Ok(Suite {
+        name: scenario.name.clone(),
+        elapsed: started.elapsed(),
+        cases,
+    })
+}
+
+fn build_agent(scenario: &Scenario, cfg: &Config) -> Result<AgentLoop, String> {
+    let safety = scenario.safety.as_ref().unwrap_or(&cfg.safety);
+    let mut agent = AgentLoop::new(AgentLoopConfig {
+        llm_base_url: cfg.ollama_url.clone(),
+        llm_model: cfg.active_model.clone(),
+        goal: scenario.mission(),
+        safety_rules: stack::safety_rules(safety),
+        ..Default::default()
+    })
+    .map_err(|e| format!("cannot start the agent loop: {e}"))?;
+    if !scenario.llm.is_empty() {
+        let replies = scenario
+            .llm
+            .iter()
+            .map(serde_json::to_string)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+        agent.set_llm_driver(LlmDriver::scripted(replies).map_err(|e| e.to_string())?);
+    }
+    Ok(agent)
+}
+
+/// Tick `agent` against `robot` until the goal is reached or the ticks run
+/// out.
+async fn drive(scenario: &Scenario, agent: &mut AgentLoop, robot: &mut Robot) -> Outcome {
+    let bus = agent.bus();
+    let dt = scenario.tick_ms as f32 / 1000.0;
+    let mut answers = scenario.human_responses.iter();
+    let mut outcome = Outcome::default();
+    let mut colliding = false;
+    for tick in 0..scenario.max_ticks {
+        for fault in scenario.faults.iter().filter(|f| f.at_tick == tick) {
+            let _ = bus.publish(event(EventPayload::HardwareFault {
+                component: fault.component.clone(),
+                code: fault.code,
+                message: fault.message.clone(),
+            }));
+        }
+        robot.sense(agent, &bus);
+        match agent.tick(dt).await {
+            Ok(intent) => {
+                outcome.approved += 1;
+                if let HardwareIntent::AskHuman { question_id, .. } = &intent
+                    && let Some(answer) = answers.next()
+                {
+                    let _ = bus.publish(event(EventPayload::HumanResponse(HumanAnswer {
+                        question_id: question_id.clone(),
+                        answer: answer.clone(),
+                        operator: None,
+                    })));
+                }
+                if let Err(e) = robot.execute(&intent).await {
+                    tracing::warn!(error = %e, "simulated robot refused the intent");
+                }
+            }
+            Err(e) if is_gate_violation(&e) => outcome.violations.push(format!("tick {tick}: {e}")),
+            Err(e) => tracing::debug!(tick, error = %e, "tick skipped"),
+        }
+        robot.step(dt).await;
+        outcome.ticks = tick + 1;
+
+        let hit = robot.collision().copied();
+        if let (Some(o), false) = (hit, colliding) {
+            outcome
+                .collisions
+                .push(format!("tick {tick}: obstacle at ({}, {})", o.x, o.y));
+        }
+        colliding = hit.is_some();
+
+        let pose = robot.pose();
+        while let Some(&(x, y)) = scenario.waypoints.get(outcome.visited) {
+            if (x - pose.x).hypot(y - pose.y) > scenario.tolerance {
+                break;
+            }
+            outcome.visited += 1;
+        }
+        if !scenario.waypoints.is_empty() && outcome.visited == scenario.waypoints.len() {
+            outcome.reached_at = Some(outcome.ticks);
+            break;
+        }
+    }
+    outcome
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Verdicts
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// One checked expectation.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Case {
+    pub name: String,
+    /// Why it failed; `None` when it passed.
+    pub failure: Option<String>,
+}
+
+/// The cases of one scenario.
+#[derive(Debug)]
+pub(crate) struct Suite {
+    pub name: String,
+    pub elapsed: Duration,
+    pub cases: Vec<Case>,
+}
+
+/// Check `outcome` against the scenario's expectations.
+pub(crate) fn judge(scenario: &Scenario, outcome: &Outcome, collisions_checked: bool) -> Vec<Case> {
+    let expect = &scenario.expect;
+    let mut cases = Vec::new();
+    if let Some(reach) = expect.reached_goal {
+        let summary = match outcome.reached_at {
+            Some(tick) => format!("reached the goal after {tick} ticks"),
+            None => format!(
+                "visited {} of {} waypoints in {} ticks",
+                outcome.visited,
+                scenario.waypoints.len(),
+                outcome.ticks
+            ),
+        };
+        cases.push(Case {
+            name: if reach { "reaches the goal" } else { "stays short of the goal" }.to_string(),
+            failure: (outcome.reached_at.is_some() != reach).then_some(summary),
+        });
+    }
+
+    let violations = outcome.violations.len();
+    let (min, max) = (expect.min_gate_violations, expect.max_gate_violations);
+    let name = match (min, max) {
+        (0, 0) => "no gate violations".to_string(),
+        (0, max) => format!("at most {max} gate violations"),
+        (min, max) if min == max => format!("exactly {min} gate violations"),
+        (min, max) => format!("{min} to {max} gate violations"),
+    };
+    let failure = (!(min..=max).contains(&violations)).then(|| {
+        let mut why = format!("{violations} gate violations");
+        if let Some(first) = outcome.violations.first() {
+            let _ = write!(why, " (first at {first})");
+        }
+        why
+    });
+    cases.push(Case { name, failure });
+
+    if collisions_checked {
+        cases.push(Case {
+            name: "no collisions".to_string(),
+            failure: outcome.collisions.first().map(|first| format!("hit an {first}")),
+        });
+    }
+    cases
+}
+
+/// `suites` as a JUnit XML report.
+pub(crate) fn junit_report(suites: &[Suite]) -> String {
+    let count = |f: fn(&Case) -> bool| suites.iter().flat_map(|s| &s.cases).filter(|c| f(c)).count();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"mechos simulate\" tests=\"{}\" failures=\"{}\">",
+        count(|_| true),
+        count(|c| c.failure.is_some())
+    );
+    for suite in suites {
+        let failures = suite.cases.iter().filter(|c| c.failure.is_some()).count();
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{failures}\" time=\"{:.3}\">",
+            escape(&suite.name),
+            suite.cases.len(),
+            suite.elapsed.as_secs_f64()
+        );
+        for case in &suite.cases {
+            let _ = write!(
+                xml,
+                "    <testcase classname=\"{}\" name=\"{}\"",
+                escape(&suite.name),
+                escape(&case.name)
+            );
+            match &case.failure {
+                None => xml.push_str("/>\n"),
+                Some(why) => {
+                    let _ = writeln!(
+                        xml,
+                        ">\n      <failure message=\"{0}\">{0}</failure>\n    </testcase>",
+                        escape(why)
+                    );
+                }
+            }
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// `text` with the XML special characters escaped.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CORRIDOR: &str = r#"
+name: corridor
+start: { x: 0.0, y: 0.0 }
+waypoints: [[2.0, 0.0], [2.0, 1.0]]
+obstacles:
+  - { x: 1.0, y: -1.5, radius: 0.3 }
+llm:
+  - action: FollowWaypoints
+    payload: { points: [[2.0, 0.0], [2.0, 1.0]], max_speed: 0.5 }
+expect:
+  reached_goal: true
+"#;
+
+    fn simulate(raw: &str) -> (Scenario, Outcome) {
+        let scenario = Scenario::parse(raw).unwrap();
+        let mut agent = build_agent(&scenario, &Config::default()).unwrap();
+        let mut robot = Robot::Mock(MockRobot::new(scenario.start, scenario.obstacles.clone()));
+        let outcome = crate::commands::block_on(drive(&scenario, &mut agent, &mut robot)).unwrap();
+        (scenario, outcome)
+    }
+
+    #[test]
+    fn scripted_route_reaches_the_goal() {
+        let (scenario, outcome) = simulate(CORRIDOR);
+        assert_eq!(outcome.visited, 2);
+        assert!(outcome.reached_at.is_some(), "{outcome:?}");
+        let cases = judge(&scenario, &outcome, true);
+        assert!(cases.iter().all(|c| c.failure.is_none()), "{cases:?}");
+    }
+
+    #[test]
+    fn a_geofence_violation_fails_the_expectations() {
+        let fenced = CORRIDOR.replace(
+            "expect:",
+            "safety:\n  geofence: { min_x: -1.0, min_y: -1.0, max_x: 1.5, max_y: 1.5 }\nmax_ticks: 20\nexpect:",
+        );
+        let (scenario, outcome) = simulate(&fenced);
+        assert_eq!(outcome.violations.len(), 1, "{outcome:?}");
+        let failed: Vec<_> = judge(&scenario, &outcome, true)
+            .into_iter()
+            .filter(|c| c.failure.is_some())
+            .map(|c| c.name)
+            .collect();
+        assert_eq!(failed, ["reaches the goal", "no gate violations"]);
+    }
+
+    #[test]
+    fn mock_robot_scans_and_hits_obstacles() {
+        let wall = Obstacle {
+            x: 2.0,
+            y: 0.0,
+            radius: 0.5,
+        };
+        let mut robot = MockRobot::new(Pose::default(), vec![wall]);
+        let ahead = robot.scan()[SCAN_BEAMS / 2];
+        assert!((ahead - 1.5).abs() < 1e-3, "{ahead}");
+        assert_eq!(robot.scan()[0], SCAN_RANGE_M);
+
+        robot.execute(&HardwareIntent::Drive {
+            linear_velocity: mechos_types::MetersPerSecond(1.0),
+            angular_velocity: mechos_types::RadiansPerSecond(0.0),
+        });
+        robot.step(1.0);
+        assert!((robot.pose.x - DRIVE_HOLD_S).abs() < 1e-6, "the Drive expires");
+        assert!(robot.collision().is_none());
+        robot.execute(&HardwareIntent::FollowWaypoints {
+            points: vec![(mechos_types::Meters(1.5), mechos_types::Meters(0.0))],
+            max_speed: mechos_types::MetersPerSecond(2.0),
+        });
+        robot.step(1.0);
+        assert_eq!(robot.pose.x, 1.5);
+        assert!(robot.collision().is_some());
+    }
+
+    #[test]
+    fn scenarios_are_checked_on_load() {
+        assert!(Scenario::parse("expect: { reached_goal: true }").is_err());
+        assert!(Scenario::parse("tick_ms: 0").is_err());
+        assert!(Scenario::parse("obstacles: [{ x: 1, y: 1, radius: 0 }]").is_err());
+        assert!(Scenario::parse("speed: 3").is_err(), "unknown keys are typos");
+        let scenario = Scenario::parse("faults: [{ at_tick: 3, component: bumper, code: emergency_stop }]").unwrap();
+        assert_eq!(scenario.faults[0].code, FaultCode::EmergencyStop);
+        assert_eq!(scenario.max_ticks, 200);
+    }
+
+    #[test]
+    fn junit_report_escapes_and_counts_failures() {
+        let suites = [Suite {
+            name: "dock <run>".to_string(),
+            elapsed: Duration::from_millis(1500),
+            cases: vec![
+                Case {
+                    name: "reaches the goal".to_string(),
+                    failure: None,
+                },
+                Case {
+                    name: "no gate violations".to_string(),
+                    failure: Some("1 gate violations (first at tick 3: \"speed\")".to_string()),
+                },
+            ],
+        }];
+        let xml = junit_report(&suites);
+        assert!(xml.contains(r#"<testsuites name="mechos simulate" tests="2" failures="1">"#));
+        assert!(xml.contains(r#"<testsuite name="dock &lt;run&gt;" tests="2" failures="1" time="1.500">"#));
+        assert!(xml.contains(r#"<testcase classname="dock &lt;run&gt;" name="reaches the goal"/>"#));
+        assert!(xml.contains("&quot;speed&quot;)</failure>"));
+    }
+}
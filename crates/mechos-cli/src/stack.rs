@@ -0,0 +1,711 @@
+//! Stack – the running MechOS services behind `/start` and `/stop`.
+//!
+//! `/start` (and headless `mechos run`, see [`crate::daemon`]) assembles the
+//! stack from `~/.mechos/config.toml` on one multi-threaded Tokio runtime:
+//!
+//! * the hardware adapter chosen by `[adapter]`, behind an
+//!   [`AdapterManager`] that executes the approved intents published on the
+//!   bus and forwards the adapter's sensor data,
+//! * the Cockpit Web UI,
+//! * the agent loop, ticking at [`TICK_RATE_HZ`] with the grants of
+//!   [`capability_grants`] and the limits of `[safety]`
+//!   ([`safety_rules`]); once an operator is granted `answer_questions` or
+//!   `approve_intents`, only granted operators may answer or approve
+//!   ([`operator_grants`]), and the Cockpit accepts answers and approvals
+//!   only with a token from `MECHOS_OPERATOR_TOKENS`
+//!   ([`operator_tokens_required`]),
+//! * the kernel [`Watchdog`], which halts the robot when the agent loop
+//!   stops ticking for [`AGENT_HEARTBEAT_TIMEOUT`].
+//!
+//! Services report ending, failing or freezing as a [`StackStatus`], which
+//! the REPL prints between commands and `mechos run` logs.  [`Stack::stop`] halts the robot,
+//! cancels every service and shuts the runtime down.
+
+use std::future::Future;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use mechos_kernel::{
+    CapabilityManager, ComponentHealth, EndEffectorWorkspaceRule, GeofenceRule, Rule,
+    SpeedCapRule, Watchdog,
+};
+use mechos_middleware::sim_adapter::{DEFAULT_GAZEBO_URL, SimBackend};
+use mechos_middleware::supervisor::Backoff;
+use mechos_middleware::{
+    AdapterManager, DashboardSimAdapter, EventBus, MechAdapter, MqttAdapter, Ros2Adapter,
+    Ros2Bridge, SerialAdapter, SimAdapter,
+};
+use mechos_runtime::{AgentLoop, AgentLoopConfig};
+use mechos_types::{Capability, CapabilityGrant, HardwareIntent, Meters, MechError, MetersPerSecond, RadiansPerSecond};
+use tokio::runtime::Runtime;
+use tokio_util::sync::CancellationToken;
+
+use crate::config::{self, AdapterChoice, Config, SafetyConfig};
+
+/// Target frequency of the agent loop's OODA ticks.
+pub const TICK_RATE_HZ: f32 = 10.0;
+
+/// Silence after which the watchdog considers the agent loop frozen.
+pub const AGENT_HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Watchdog component ID of the agent loop.
+const AGENT_COMPONENT: &str = "agent_loop";
+
+/// How often the adapters are told the agent loop is alive: a quarter of the
+/// HAL's 200 ms default motor command deadline, independent of how long a
+/// tick (and its LLM round trip) takes.
+const ADAPTER_HEARTBEAT_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Adapter ID under which the selected adapter is registered.
+const ADAPTER_ID: &str = "robot";
+
+/// Intents buffered while the adapter's link is down.
+const OFFLINE_QUEUE_LEN: usize = 32;
+
+/// Longest an intent waits for the adapter's link before it is dropped.
+const OFFLINE_MAX_AGE: Duration = Duration::from_secs(2);
+
+/// How long [`Stack::stop`] waits for services to wind down.
+const STOP_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Default endpoint of a `ros2` robot's `rosbridge_server`.
+const DEFAULT_ROSBRIDGE_URL: &str = "ws://localhost:9090";
+
+/// Longest waypoint path the configured geofence lets through; the planner's
+/// detours around obstacles can run to hundreds of points.
+const GEOFENCE_MAX_WAYPOINTS: usize = 1024;
+
+/// A change in a running service, reported to the REPL.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StackStatus {
+    /// The service ended on its own.
+    Stopped(&'static str),
+    /// The service gave up with an error.
+    Failed { service: &'static str, error: String },
+    /// The watchdog missed the component's heartbeats; the robot was halted.
+    Frozen(String),
+}
+
+impl std::fmt::Display for StackStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StackStatus::Stopped(service) => write!(f, "{service} stopped"),
+            StackStatus::Failed { service, error } => write!(f, "{service} failed: {error}"),
+            StackStatus::Frozen(component) => {
+                write!(f, "{component} stopped responding; robot halted")
+            }
+        }
+    }
+}
+
+/// The services started by `/start`, running until [`stop`][Self::stop].
+pub struct Stack {
+    runtime: Runtime,
+    bus: Arc<EventBus>,
+    adapters: Arc<AdapterManager>,
+    cancel: CancellationToken,
+    status_tx: mpsc::Sender<StackStatus>,
+    status_rx: mpsc::Receiver<StackStatus>,
+}
+
+/// The endpoint of the adapter selected in `cfg`: a WebSocket URL, the
+/// serial device or the MQTT broker's `host:port`.
+pub fn adapter_url(cfg: &Config) -> Result<String, String> {
+    let adapter = &cfg.adapter;
+    match (adapter.kind, adapter.url.as_str()) {
+        (AdapterChoice::DashboardSim, _) => Ok(format!("ws://localhost:{}", cfg.dashboard_port)),
+        (AdapterChoice::Serial, _) if adapter.serial_port.is_empty() => {
+            Err("set adapter.serial_port to drive a serial base".to_string())
+        }
+        (AdapterChoice::Serial, _) => Ok(adapter.serial_port.clone()),
+        (AdapterChoice::Mqtt, _) => Ok(format!("{}:{}", adapter.mqtt_host, adapter.mqtt_port)),
+        (AdapterChoice::Gazebo, "") => Ok(DEFAULT_GAZEBO_URL.to_string()),
+        (AdapterChoice::Ros2, "") => Ok(DEFAULT_ROSBRIDGE_URL.to_string()),
+        (AdapterChoice::Webots, "") => Err(format!("set adapter.url to drive {}", adapter.kind)),
+        (_, url) => Ok(url.to_string()),
+    }
+}
+
+/// The kernel rules enforcing `safety`, for the agent loop's gate.
+pub fn safety_rules(safety: &SafetyConfig) -> Vec<Box<dyn Rule>> {
+    let mut rules: Vec<Box<dyn Rule>> = Vec::new();
+    if let (Some(linear), Some(angular)) = (safety.max_linear_mps, safety.max_angular_rps) {
+        rules.push(Box::new(SpeedCapRule {
+            max_linear: MetersPerSecond(linear),
+            max_angular: RadiansPerSecond(angular),
+        }));
+    }
+    if let Some(b) = safety.workspace {
+        rules.push(Box::new(EndEffectorWorkspaceRule {
+            min_x: Meters(b.min_x),
+            max_x: Meters(b.max_x),
+            min_y: Meters(b.min_y),
+            max_y: Meters(b.max_y),
+            min_z: Meters(b.min_z),
+            max_z: Meters(b.max_z),
+        }));
+    }
+    if let Some(fence) = safety.geofence {
+        rules.push(Box::new(GeofenceRule {
+            min_x: Meters(fence.min_x),
+            max_x: Meters(fence.max_x),
+            min_y: Meters(fence.min_y),
+            max_y: Meters(fence.max_y),
+            max_waypoints: GEOFENCE_MAX_WAYPOINTS,
+        }));
+    }
+    rules
+}
+
+/// The capability grants saved by `/caps`, or the agent loop's defaults when
+/// none were saved.
+pub fn capability_grants() -> Result<CapabilityManager, String> {
+    let path = config::capabilities_path();
+    if path.exists() {
+        return CapabilityManager::load(&path).map_err(|e| e.to_string());
+    }
+    let defaults = AgentLoopConfig::default();
+    let mut manager = CapabilityManager::new();
+    for cap in defaults.capabilities {
+        manager.grant(&defaults.agent_id, cap);
+    }
+    Ok(manager)
+}
+
+/// Every identity's grants but `agent_id`'s, once any of them may answer
+/// questions or approve intents; `None` (any operator may) until then.
+pub fn operator_grants(manager: &CapabilityManager, agent_id: &str) -> Option<Vec<CapabilityGrant>> {
+    let grants: Vec<CapabilityGrant> = manager
+        .grants()
+        .into_iter()
+        .filter(|grant| grant.agent_id != agent_id)
+        .collect();
+    grants
+        .iter()
+        .flat_map(|grant| &grant.capabilities)
+        .any(|cap| matches!(cap, Capability::AnswerQuestions | Capability::ApproveIntents))
+        .then_some(grants)
+}
+
+/// `true` when the Cockpit must refuse operator names it cannot verify by
+/// token: the agent loop checks [`operator_grants`], or the grants could not
+/// be read (`None`).
+pub fn operator_tokens_required(grants: Option<&CapabilityManager>) -> bool {
+    let agent_id = AgentLoopConfig::default().agent_id;
+    grants.is_none_or(|grants| operator_grants(grants, &agent_id).is_some())
+}
+
+impl Stack {
+    /// Start a runtime for services on `bus`, with no services yet.
+    pub fn new(bus: Arc<EventBus>) -> std::io::Result<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .thread_name("mechos-stack")
+            .enable_all()
+            .build()?;
+        let (status_tx, status_rx) = mpsc::channel();
+        Ok(Self {
+            runtime,
+            bus,
+            adapters: Arc::new(AdapterManager::new()),
+            cancel: CancellationToken::new(),
+            status_tx,
+            status_rx,
+        })
+    }
+
+    /// The bus every service shares.
+    pub fn bus(&self) -> &Arc<EventBus> {
+        &self.bus
+    }
+
+    /// Cancelled by [`stop`][Self::stop]; hand to services with their own
+    /// shutdown hook, such as the Cockpit.
+    pub fn cancel_token(&self) -> CancellationToken {
+        self.cancel.clone()
+    }
+
+    /// Run `service` until it ends or the stack stops, reporting an early
+    /// end as a [`StackStatus`].
+    pub fn spawn<F>(&self, name: &'static str, service: F)
+    where
+        F: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        let cancel = self.cancel.clone();
+        let status = self.status_tx.clone();
+        self.runtime.spawn(async move {
+            let report = match cancel.run_until_cancelled(service).await {
+                None => return,
+                Some(Ok(())) => StackStatus::Stopped(name),
+                Some(Err(error)) => StackStatus::Failed {
+                    service: name,
+                    error,
+                },
+            };
+            let _ = status.send(report);
+        });
+    }
+
+    /// Connect the adapter selected in `cfg` and route every intent to it.
+    /// Returns the endpoint it drives.
+    pub fn start_adapter(&mut self, cfg: &Config) -> Result<String, String> {
+        let bus = Arc::clone(&self.bus);
+        let url = adapter_url(cfg)?;
+        let settings = &cfg.adapter;
+        let adapter: Arc<dyn MechAdapter> = match settings.kind {
+            AdapterChoice::DashboardSim => {
+                let adapter = Arc::new(DashboardSimAdapter::new(bus, url.clone()));
+                let link = Arc::clone(&adapter);
+                self.spawn("dashboard adapter", async move {
+                    link.run().await;
+                    Ok(())
+                });
+                adapter
+            }
+            AdapterChoice::Gazebo | AdapterChoice::Webots => {
+                let backend = match settings.kind {
+                    AdapterChoice::Gazebo => SimBackend::Gazebo,
+                    _ => SimBackend::Webots,
+                };
+                let adapter = Arc::new(SimAdapter::new(bus, backend, url.clone()));
+                let link = Arc::clone(&adapter);
+                self.spawn("simulator adapter", async move {
+                    link.run().await;
+                    Ok(())
+                });
+                adapter
+            }
+            AdapterChoice::Ros2 => {
+                let bridge = Ros2Bridge::new(Arc::clone(&bus));
+                let remote = url.clone();
+                self.spawn("rosbridge link", async move {
+                    bridge.run_ws_client(remote, Backoff::default()).await;
+                    Ok(())
+                });
+                Arc::new(Ros2Adapter::new(bus))
+            }
+            AdapterChoice::Serial => {
+                let adapter = Arc::new(SerialAdapter::new(bus, url.clone(), settings.baud_rate));
+                let link = Arc::clone(&adapter);
+                self.spawn("serial adapter", async move {
+                    link.run().await;
+                    Ok(())
+                });
+                adapter
+            }
+            AdapterChoice::Mqtt => {
+                let options = rumqttc::MqttOptions::new(
+                    format!("mechos-{}", uuid::Uuid::new_v4().simple()),
+                    settings.mqtt_host.clone(),
+                    settings.mqtt_port,
+                );
+                let (adapter, eventloop) = MqttAdapter::new(bus, options);
+                let adapter = Arc::new(adapter);
+                let link = Arc::clone(&adapter);
+                self.spawn("mqtt adapter", async move {
+                    link.run(eventloop).await;
+                    Ok(())
+                });
+                adapter
+            }
+        };
+
+        let mut manager = AdapterManager::new()
+            .with_adapter(ADAPTER_ID, adapter)
+            .with_default_route([ADAPTER_ID])
+            .with_offline_queue(OFFLINE_QUEUE_LEN, OFFLINE_MAX_AGE);
+        // Adapters behind a supervised WebSocket report when it drops.
+        match settings.kind {
+            AdapterChoice::DashboardSim => manager = manager.with_link(ADAPTER_ID, "dashboard_sim"),
+            AdapterChoice::Ros2 => manager = manager.with_link(ADAPTER_ID, "ros2_bridge"),
+            _ => {}
+        }
+        self.adapters = Arc::new(manager);
+        let (intents, sensors) = (Arc::clone(&self.adapters), Arc::clone(&self.adapters));
+        let (intent_bus, sensor_bus) = (Arc::clone(&self.bus), Arc::clone(&self.bus));
+        self.spawn("intent router", async move {
+            intents.run(&intent_bus).await;
+            Ok(())
+        });
+        self.spawn("sensor forwarder", async move {
+            sensors.forward_sensors(&sensor_bus).await;
+            Ok(())
+        });
+        Ok(url)
+    }
+
+    /// Serve the Cockpit Web UI described by `cfg`.
+    pub fn start_cockpit(&self, cfg: &Config) {
+        let mut server = mechos_cockpit::CockpitServer::new(Arc::clone(&self.bus))
+            .with_bind_address(cfg.webui_bind)
+            .with_port(cfg.webui_port)
+            .with_shutdown(self.cancel_token());
+        if cfg.camera_port > 0 {
+            server = server.with_camera_port(cfg.camera_port);
+        }
+        if let Some(tls) = cfg.tls.clone() {
+            server = server.with_tls(tls);
+        }
+        if !cfg.admin_token.is_empty() {
+            server = server.with_admin_token(cfg.admin_token.clone());
+        }
+        for (operator, token) in &cfg.operator_tokens {
+            server = server.with_operator_token(token.clone(), operator.clone());
+        }
+        if operator_tokens_required(capability_grants().as_ref().ok()) {
+            if cfg.operator_tokens.is_empty() {
+                tracing::warn!(
+                    "operators are granted answer_questions/approve_intents but \
+                     MECHOS_OPERATOR_TOKENS is empty: the Cockpit will refuse every answer and approval"
+                );
+            }
+            server = server.with_operator_tokens_required();
+        }
+        self.spawn("cockpit", async move {
+            server.run().await.map_err(|e| e.to_string())
+        });
+    }
+
+    /// Watch the agent loop's heartbeats; halt the robot when they stop.
+    pub fn start_watchdog(&self) -> Arc<Mutex<Watchdog>> {
+        let watchdog = Arc::new(Mutex::new(Watchdog::new()));
+        watchdog
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .register(AGENT_COMPONENT, AGENT_HEARTBEAT_TIMEOUT);
+        let watched = Arc::clone(&watchdog);
+        let adapters = Arc::clone(&self.adapters);
+        let status = self.status_tx.clone();
+        self.spawn("watchdog", async move {
+            let mut check = tokio::time::interval(AGENT_HEARTBEAT_TIMEOUT / 4);
+            let mut frozen = false;
+            loop {
+                check.tick().await;
+                let health = watched
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .health(AGENT_COMPONENT);
+                let now_frozen = health == ComponentHealth::TimedOut;
+                if now_frozen && !frozen {
+                    let halt = HardwareIntent::Halt {
+                        reason: format!("watchdog: {AGENT_COMPONENT} stopped responding"),
+                    };
+                    if let Err(e) = adapters.execute_intent(halt).await {
+                        tracing::error!(error = %e, "watchdog could not halt the robot");
+                    }
+                    let _ = status.send(StackStatus::Frozen(AGENT_COMPONENT.to_string()));
+                }
+                frozen = now_frozen;
+            }
+        });
+        watchdog
+    }
+
+    /// Build the agent loop described by `cfg`, keeping its episodic memory
+    /// at `memory_path`, encrypted with the `[memory] key` if one is set,
+    /// and its LLM transcripts at `transcript_path`, and
+    /// [`start_agent`][Self::start_agent] it.
+    pub fn start_agent_loop(
+        &self,
+        cfg: &Config,
+        memory_path: String,
+        transcript_path: String,
+        watchdog: Arc<Mutex<Watchdog>>,
+    ) -> Result<(), MechError> {
+        let defaults = AgentLoopConfig::default();
+        let grants = capability_grants().map_err(MechError::Parsing)?;
+        let agent = AgentLoop::new(AgentLoopConfig {
+            llm_base_url: cfg.ollama_url.clone(),
+            llm_model: cfg.active_model.clone(),
+            capabilities: grants.capabilities(&defaults.agent_id),
+            operator_grants: operator_grants(&grants, &defaults.agent_id),
+            memory_path: Some(memory_path),
+            memory_key: cfg.memory_key(),
+            transcript_path: Some(transcript_path),
+            bus: Some((*self.bus).clone()),
+            // Feeds the Cockpit map view.
+            map_snapshot_interval_ms: 1000,
+            safety_rules: safety_rules(&cfg.safety),
+            ..defaults
+        })?;
+        self.start_agent(agent, watchdog);
+        Ok(())
+    }
+
+    /// Tick `agent` at [`TICK_RATE_HZ`] on a thread of its own, sending a
+    /// heartbeat to `watchdog` after every tick.
+    ///
+    /// The adapters get their own heartbeat every
+    /// [`ADAPTER_HEARTBEAT_INTERVAL`] from the stack runtime for as long as
+    /// `watchdog` finds the agent alive, so hardware command deadlines (the
+    /// HAL's motor failsafe) do not expire during a slow LLM round trip but
+    /// still do once the agent freezes.
+    pub fn start_agent(&self, agent: AgentLoop, watchdog: Arc<Mutex<Watchdog>>) {
+        let tick_interval = Duration::from_secs_f32(1.0 / TICK_RATE_HZ);
+        let adapters = Arc::clone(&self.adapters);
+        let watched = Arc::clone(&watchdog);
+        self.spawn("adapter heartbeat", async move {
+            let mut beat = tokio::time::interval(ADAPTER_HEARTBEAT_INTERVAL);
+            loop {
+                beat.tick().await;
+                let health = watched
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .health(AGENT_COMPONENT);
+                if health == ComponentHealth::Healthy {
+                    adapters.heartbeat();
+                }
+            }
+        });
+        let cancel = self.cancel_token();
+        let status = self.status_tx.clone();
+        // The loop keeps its stores on this thread, so it gets a
+        // single-threaded runtime rather than a worker of the stack's.
+        let spawned = std::thread::Builder::new()
+            .name("mechos-agent".to_string())
+            .spawn(move || {
+                let rt = match tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                {
+                    Ok(rt) => rt,
+                    Err(e) => {
+                        let _ = status.send(StackStatus::Failed {
+                            service: AGENT_COMPONENT,
+                            error: e.to_string(),
+                        });
+                        return;
+                    }
+                };
+                rt.block_on(async move {
+                    let mut agent = agent;
+                    let mut ticks = tokio::time::interval(tick_interval);
+                    while cancel.run_until_cancelled(ticks.tick()).await.is_some() {
+                        match agent.tick(1.0 / TICK_RATE_HZ).await {
+                            Ok(intent) => tracing::info!(intent = ?intent, "agent intent dispatched"),
+                            Err(e) => tracing::debug!(error = %e, "agent tick skipped"),
+                        }
+                        watchdog
+                            .lock()
+                            .unwrap_or_else(|e| e.into_inner())
+                            .heartbeat(AGENT_COMPONENT);
+                    }
+                    tracing::info!("agent loop shutting down");
+                });
+            });
+        if let Err(e) = spawned {
+            let _ = self.status_tx.send(StackStatus::Failed {
+                service: AGENT_COMPONENT,
+                error: e.to_string(),
+            });
+        }
+    }
+
+    /// Status reports received since the last call.
+    pub fn poll_status(&self) -> Vec<StackStatus> {
+        self.status_rx.try_iter().collect()
+    }
+
+    /// Halt the robot, then stop every service and the runtime.
+    pub fn stop(self, reason: &str) {
+        let halt = HardwareIntent::Halt {
+            reason: reason.to_string(),
+        };
+        let adapters = Arc::clone(&self.adapters);
+        let halted = self.runtime.block_on(async move {
+            tokio::time::timeout(STOP_TIMEOUT, adapters.execute_intent(halt)).await
+        });
+        match halted {
+            Ok(Err(e)) => tracing::warn!(error = %e, "could not halt the robot while stopping"),
+            Err(_) => tracing::warn!("timed out halting the robot while stopping"),
+            Ok(Ok(())) => {}
+        }
+        self.cancel.cancel();
+        self.runtime.shutdown_timeout(STOP_TIMEOUT);
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn services_report_how_they_ended() {
+        let stack = Stack::new(Arc::new(EventBus::default())).unwrap();
+        stack.spawn("finished", async { Ok(()) });
+        stack.spawn("broken", async { Err("port in use".to_string()) });
+        stack.spawn("forever", std::future::pending());
+
+        let mut reports = Vec::new();
+        for _ in 0..100 {
+            reports.extend(stack.poll_status());
+            if reports.len() == 2 {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(reports.contains(&StackStatus::Stopped("finished")));
+        assert!(reports.contains(&StackStatus::Failed {
+            service: "broken",
+            error: "port in use".to_string(),
+        }));
+
+        stack.stop("test over");
+    }
+
+    #[test]
+    fn operator_grants_apply_once_an_operator_is_granted() {
+        let mut manager = CapabilityManager::new();
+        manager.grant("agent", Capability::AnswerQuestions);
+        manager.grant("planner", Capability::TaskBoardAccess);
+        assert_eq!(operator_grants(&manager, "agent"), None);
+
+        manager.grant("alice", Capability::ApproveIntents);
+        let grants = operator_grants(&manager, "agent").unwrap();
+        assert_eq!(
+            grants.iter().map(|g| g.agent_id.as_str()).collect::<Vec<_>>(),
+            ["alice", "planner"]
+        );
+    }
+
+    #[test]
+    fn granted_operators_must_present_a_token() {
+        let agent_id = AgentLoopConfig::default().agent_id;
+        let mut manager = CapabilityManager::new();
+        manager.grant(&agent_id, Capability::AnswerQuestions);
+        assert!(!operator_tokens_required(Some(&manager)));
+
+        manager.grant("alice", Capability::AnswerQuestions);
+        assert!(operator_tokens_required(Some(&manager)));
+        assert!(operator_tokens_required(None));
+
+        // With no tokens configured, the Cockpit's check refuses a tab that
+        // merely claims to be alice.
+        let mut tokens = mechos_cockpit::OperatorTokens::new();
+        tokens.require();
+        assert!(tokens.operator(&serde_json::json!({"operator": "alice"})).is_err());
+    }
+
+    #[test]
+    fn webots_needs_a_simulator_url() {
+        let mut stack = Stack::new(Arc::new(EventBus::default())).unwrap();
+        let adapter = |kind| config::AdapterConfig {
+            kind,
+            ..config::AdapterConfig::default()
+        };
+        let cfg = Config {
+            adapter: adapter(AdapterChoice::Webots),
+            ..Config::default()
+        };
+        assert!(stack.start_adapter(&cfg).is_err());
+        let cfg = Config {
+            adapter: adapter(AdapterChoice::Gazebo),
+            ..Config::default()
+        };
+        assert_eq!(stack.start_adapter(&cfg).unwrap(), DEFAULT_GAZEBO_URL);
+        stack.stop("test over");
+    }
+    #[test]
+    fn safety_limits_become_kernel_rules() {
+        assert!(safety_rules(&SafetyConfig::default()).is_empty());
+        let safety = SafetyConfig {
+            max_linear_mps: Some(0.3),
+            max_angular_rps: Some(0.6),
+            workspace: None,
+            geofence: Some(config::Geofence::from_corners((0.0, 0.0), (4.0, 3.0))),
+        };
+        let rules = safety_rules(&safety);
+        let names: Vec<_> = rules.iter().map(|rule| rule.name()).collect();
+        assert_eq!(names, ["speed_cap", "geofence"]);
+        let drive = HardwareIntent::Drive {
+            linear_velocity: MetersPerSecond(0.5),
+            angular_velocity: RadiansPerSecond(0.0),
+        };
+        assert!(rules[0].check(&drive).is_err());
+    }
+
+    /// A wheel sharing its last commanded velocity with the test.
+    struct SharedMotor(&'static str, Arc<Mutex<MetersPerSecond>>);
+
+    impl mechos_hal::MotorController for SharedMotor {
+        fn id(&self) -> &str {
+            self.0
+        }
+
+        fn set_velocity(&mut self, velocity: MetersPerSecond) -> Result<(), MechError> {
+            *self.1.lock().unwrap() = velocity;
+            Ok(())
+        }
+
+        fn velocity(&self) -> MetersPerSecond {
+            *self.1.lock().unwrap()
+        }
+    }
+
+    #[test]
+    fn slow_ticks_do_not_trip_the_motor_failsafe() {
+        // An LLM endpoint that takes 600 ms to hang up on every request.
+        let llm = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let llm_url = format!("http://{}", llm.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for conn in llm.incoming() {
+                std::thread::sleep(Duration::from_millis(600));
+                drop(conn);
+            }
+        });
+
+        let bus = Arc::new(EventBus::default());
+        let mut faults = bus.subscribe();
+        let mut stack = Stack::new(Arc::clone(&bus)).unwrap();
+        let wheel = Arc::new(Mutex::new(MetersPerSecond::ZERO));
+        let base = mechos_hal::DiffDrive::new(
+            Box::new(SharedMotor("left_wheel", Arc::clone(&wheel))),
+            Box::new(SharedMotor("right_wheel", Arc::new(Mutex::new(MetersPerSecond::ZERO)))),
+            Meters(0.5),
+        )
+        .unwrap();
+        let adapter = mechos_middleware::DirectHalAdapter::new(Arc::clone(&bus)).with_drive(base);
+        stack.adapters = Arc::new(
+            AdapterManager::new()
+                .with_adapter(ADAPTER_ID, Arc::new(adapter))
+                .with_default_route([ADAPTER_ID]),
+        );
+
+        let watchdog = stack.start_watchdog();
+        let agent = AgentLoop::new(AgentLoopConfig {
+            llm_base_url: llm_url,
+            ..AgentLoopConfig::default()
+        })
+        .unwrap();
+        stack.start_agent(agent, watchdog);
+        let adapters = Arc::clone(&stack.adapters);
+        stack
+            .runtime
+            .block_on(adapters.execute_intent(HardwareIntent::Drive {
+                linear_velocity: MetersPerSecond(0.4),
+                angular_velocity: RadiansPerSecond(0.0),
+            }))
+            .unwrap();
+        std::thread::sleep(Duration::from_millis(1500));
+
+        while let Ok(event) = faults.try_recv() {
+            assert!(
+                !matches!(
+                    event.payload,
+                    mechos_types::EventPayload::HardwareFault {
+                        code: mechos_types::FaultCode::DeadlineExpired,
+                        ..
+                    }
+                ),
+                "motor failsafe tripped: {event:?}"
+            );
+        }
+        assert_eq!(*wheel.lock().unwrap(), MetersPerSecond(0.4));
+        stack.stop("test over");
+    }
+}
@@ -1,510 +1,8 @@
-<!DOCTYPE html>
-<html lang="en">
-<head>
-<meta charset="UTF-8">
-<meta name="viewport" content="width=device-width, initial-scale=1.0">
-<title>MechOS Cockpit</title>
-<style>
-  *, *::before, *::after { box-sizing: border-box; margin: 0; padding: 0; }
-  :root {
-    --bg: #0d1117; --surface: #161b22; --border: #30363d; --accent: #58a6ff;
-    --green: #3fb950; --red: #f85149; --yellow: #d29922; --text: #c9d1d9;
-    --text-dim: #8b949e; --font: 'Segoe UI', system-ui, sans-serif;
-    --mono: 'Cascadia Code', 'Fira Code', 'Courier New', monospace;
-  }
-  body { background: var(--bg); color: var(--text); font-family: var(--font);
-         min-height: 100vh; display: flex; flex-direction: column; }
-  header { background: var(--surface); border-bottom: 1px solid var(--border);
-           padding: 0.5rem 1rem; display: flex; align-items: center; gap: 1rem; flex-wrap: wrap; }
-  header h1 { font-size: 1.1rem; font-weight: 700; color: var(--accent); flex: 1; }
-  .status-badge { font-size: 0.75rem; padding: 0.25rem 0.6rem; border-radius: 999px;
-                  font-weight: 700; letter-spacing: 0.05em; text-transform: uppercase; }
-  .badge-observing  { background: #1f3a5f; color: var(--accent); }
-  .badge-thinking   { background: #3a2f0a; color: var(--yellow); }
-  .badge-acting     { background: #1a3a1a; color: var(--green); }
-  .badge-suspended  { background: #3a1a1a; color: var(--red); }
-  .badge-paused     { background: #2d2d2d; color: var(--text-dim); }
-  .conn-indicator { display: flex; align-items: center; gap: 0.4rem; font-size: 0.8rem; }
-  .conn-dot { width: 8px; height: 8px; border-radius: 50%; background: var(--red); }
-  .conn-dot.connected { background: var(--green); animation: pulse 2s infinite; }
-  @keyframes pulse { 0%,100%{opacity:1} 50%{opacity:.5} }
-  .btn { border: 1px solid var(--border); background: var(--surface); color: var(--text);
-         padding: 0.35rem 0.8rem; border-radius: 6px; cursor: pointer; font-size: 0.8rem;
-         font-family: var(--font); transition: background .15s; }
-  .btn:hover { background: #21262d; }
-  .btn.active { background: #1f3a5f; border-color: var(--accent); color: var(--accent); }
-  .btn.danger { border-color: var(--red); color: var(--red); }
-  .btn.danger.active { background: #3a1a1a; }
-  /* Tab navigation */
-  .tab-bar { background: var(--surface); border-bottom: 1px solid var(--border);
-             display: flex; padding: 0 1rem; }
-  .tab-btn { background: transparent; border: none; border-bottom: 2px solid transparent;
-             color: var(--text-dim); padding: 0.5rem 1rem; cursor: pointer; font-size: 0.85rem;
-             font-family: var(--font); transition: color .15s, border-color .15s; }
-  .tab-btn:hover { color: var(--text); }
-  .tab-btn.active { color: var(--accent); border-bottom-color: var(--accent); }
-  .tab-panel { display: none; flex: 1; flex-direction: column; overflow: hidden; }
-  .tab-panel.active { display: flex; }
-  /* Dashboard grid */
-  main { flex: 1; display: grid; padding: 0.75rem; gap: 0.75rem;
-         grid-template-columns: 1fr 1fr 1fr; grid-template-rows: auto 1fr auto; }
-  .panel { background: var(--surface); border: 1px solid var(--border); border-radius: 8px;
-           display: flex; flex-direction: column; overflow: hidden; }
-  .panel-title { font-size: 0.75rem; font-weight: 700; padding: 0.5rem 0.75rem;
-                 border-bottom: 1px solid var(--border); color: var(--text-dim);
-                 text-transform: uppercase; letter-spacing: 0.08em; display: flex;
-                 align-items: center; justify-content: space-between; }
-  /* Sensory Visualizer */
-  #panel-sensor { grid-column: 1; grid-row: 1 / 3; }
-  #sensor-canvas { display: block; width: 100%; flex: 1; }
-  .sensor-info { padding: 0.4rem 0.75rem; font-size: 0.75rem; color: var(--text-dim);
-                 border-top: 1px solid var(--border); font-family: var(--mono); }
-  /* Brain Debugger */
-  #panel-brain { grid-column: 2; grid-row: 1 / 3; }
-  .brain-body { display: flex; flex-direction: column; flex: 1; overflow: hidden; }
-  .metrics-ribbon { display: flex; gap: 1rem; padding: 0.5rem 0.75rem;
-                    border-bottom: 1px solid var(--border); flex-wrap: wrap; }
-  .metric { display: flex; flex-direction: column; }
-  .metric-label { font-size: 0.65rem; color: var(--text-dim); text-transform: uppercase;
-                  letter-spacing: 0.06em; }
-  .metric-value { font-size: 0.85rem; font-weight: 700; font-family: var(--mono); }
-  .feeds { display: flex; flex: 1; overflow: hidden; gap: 0; }
-  .feed { flex: 1; display: flex; flex-direction: column; overflow: hidden;
-          border-right: 1px solid var(--border); }
-  .feed:last-child { border-right: none; }
-  .feed-title { font-size: 0.65rem; color: var(--text-dim); padding: 0.3rem 0.5rem;
-                text-transform: uppercase; letter-spacing: 0.06em;
-                border-bottom: 1px solid var(--border); }
-  .feed-content { flex: 1; overflow-y: auto; padding: 0.4rem 0.5rem;
-                  font-family: var(--mono); font-size: 0.7rem; line-height: 1.4;
-                  color: var(--text-dim); word-break: break-all; }
-  .feed-content::-webkit-scrollbar { width: 4px; }
-  .feed-content::-webkit-scrollbar-thumb { background: var(--border); border-radius: 2px; }
-  .feed-entry { padding: 0.15rem 0; border-bottom: 1px solid #1a1f27; }
-  .feed-entry .ts { color: #444c56; }
-  .feed-entry .txt { color: var(--text); }
-  .feed-entry .txt.json { color: #a5d6ff; }
-  /* Teleoperation */
-  #panel-teleop { grid-column: 3; grid-row: 1; }
-  .teleop-body { padding: 0.75rem; display: flex; flex-direction: column; gap: 0.75rem; }
-  .wasd-grid { display: grid; grid-template-columns: repeat(3, 44px);
-               grid-template-rows: repeat(2, 44px); gap: 4px; justify-content: center; }
-  .wasd-key { border: 1px solid var(--border); border-radius: 6px; display: flex;
-              align-items: center; justify-content: center; font-size: 0.75rem;
-              font-weight: 700; color: var(--text-dim); background: var(--bg);
-              user-select: none; transition: background .1s, color .1s; cursor: pointer;
-              position: relative; }
-  .wasd-key.pressed { background: var(--accent); color: #fff; border-color: var(--accent); }
-  .wasd-key.empty { visibility: hidden; }
-  .joystick-container { display: flex; flex-direction: column; align-items: center; gap: 0.4rem; }
-  #joystick-canvas { border-radius: 50%; border: 1px solid var(--border); cursor: grab;
-                     touch-action: none; background: var(--bg); }
-  #joystick-canvas:active { cursor: grabbing; }
-  .velocity-display { font-family: var(--mono); font-size: 0.72rem; color: var(--text-dim);
-                      text-align: center; }
-  .gamepad-info { font-size: 0.7rem; color: var(--text-dim); text-align: center; }
-  /* HITL Panel */
-  #panel-hitl { grid-column: 3; grid-row: 2; }
-  .hitl-body { padding: 0.75rem; display: flex; flex-direction: column; gap: 0.5rem; flex: 1; overflow-y: auto; }
-  .hitl-empty { color: var(--text-dim); font-size: 0.8rem; }
-  .hitl-queue-item { background: var(--bg); border: 1px solid var(--border); border-radius: 6px;
-                     padding: 0.5rem 0.6rem; font-size: 0.8rem; display: flex; flex-direction: column; gap: 0.25rem; }
-  .hitl-queue-item .hitl-q { color: var(--text); }
-  .hitl-queue-item .hitl-ts { font-size: 0.68rem; color: var(--text-dim); font-family: var(--mono); }
-  .hitl-input-row { display: flex; gap: 0.5rem; }
-  .count-badge { background: var(--accent); color: #000; border-radius: 999px; font-size: 0.65rem;
-                 font-weight: 700; padding: 0.1rem 0.4rem; min-width: 1.2em; text-align: center; }
-  /* OODA Loop Panel */
-  #panel-ooda { grid-column: 1 / 4; grid-row: 3; }
-  .ooda-body { padding: 0.6rem 0.75rem; display: flex; flex-direction: column; gap: 0.55rem; }
-  .ooda-phases { display: flex; gap: 0.5rem; flex-wrap: wrap; }
-  .ooda-phase { flex: 1; min-width: 100px; border: 1px solid var(--border); border-radius: 8px;
-                padding: 0.5rem 0.75rem; display: flex; flex-direction: column; gap: 0.2rem; }
-  .ooda-phase .phase-label { font-size: 0.7rem; font-weight: 700; text-transform: uppercase;
-                              letter-spacing: 0.08em; }
-  .ooda-phase .phase-detail { font-size: 0.7rem; color: var(--text-dim); font-family: var(--mono);
-                               white-space: nowrap; overflow: hidden; text-overflow: ellipsis; }
-  .ooda-phase.phase-observe .phase-label { color: var(--accent); }
-  .ooda-phase.phase-orient  .phase-label { color: var(--yellow); }
-  .ooda-phase.phase-decide  .phase-label { color: #a371f7; }
-  .ooda-phase.phase-act     .phase-label { color: var(--green); }
-  .ooda-phase.phase-observe.active { border-color: var(--accent); background: #0d1f3f; }
-  .ooda-phase.phase-orient.active  { border-color: var(--yellow); background: #2a1f0a; }
-  .ooda-phase.phase-decide.active  { border-color: #a371f7;       background: #1f1030; }
-  .ooda-phase.phase-act.active     { border-color: var(--green);  background: #0d2310; }
-  .ooda-footer { display: flex; align-items: center; gap: 1.5rem; font-size: 0.72rem;
-                 color: var(--text-dim); border-top: 1px solid var(--border); padding-top: 0.4rem; }
-  .ooda-tick { font-family: var(--mono); }
-  /* Hardware Interlock */
-  .interlock-row { display: flex; gap: 0.5rem; flex-wrap: wrap; }
-  .interlock-badge { display: flex; align-items: center; gap: 0.35rem; background: var(--bg);
-                     border: 1px solid var(--border); border-radius: 6px;
-                     padding: 0.25rem 0.5rem; font-size: 0.72rem; }
-  .interlock-dot { width: 8px; height: 8px; border-radius: 50%; flex-shrink: 0; }
-  .interlock-dot.ok    { background: var(--green); }
-  .interlock-dot.warn  { background: var(--yellow); }
-  .interlock-dot.fault { background: var(--red); }
-  .interlock-dot.idle  { background: #555; }
-  .interlock-label { color: var(--text-dim); }
-  .interlock-value { color: var(--text); font-family: var(--mono); }
-  .heartbeat-line { font-size: 0.68rem; color: var(--text-dim); font-family: var(--mono); }
-  /* HITL Modal overlay */
-  #hitl-modal { display: none; position: fixed; inset: 0; background: rgba(0,0,0,.75);
-                z-index: 100; align-items: center; justify-content: center; }
-  #hitl-modal.visible { display: flex; }
-  .modal-box { background: var(--surface); border: 1px solid var(--accent); border-radius: 12px;
-               padding: 1.5rem; max-width: 480px; width: 90%; display: flex;
-               flex-direction: column; gap: 1rem; }
-  .modal-box h2 { font-size: 1rem; color: var(--accent); }
-  .modal-question { font-size: 0.9rem; background: var(--bg); border-radius: 6px;
-                    padding: 0.75rem; border: 1px solid var(--border); }
-  .modal-context { font-size: 0.75rem; color: var(--text-dim); font-style: italic; }
-  .modal-input { background: var(--bg); border: 1px solid var(--border); border-radius: 6px;
-                 padding: 0.5rem 0.75rem; color: var(--text); font-size: 0.9rem;
-                 font-family: var(--font); width: 100%; outline: none; }
-  .modal-input:focus { border-color: var(--accent); }
-  .modal-actions { display: flex; gap: 0.5rem; justify-content: flex-end; }
-  /* Disconnected overlay */
-  #disconnected-banner { display: none; position: fixed; top: 0; left: 0; right: 0;
-                         background: #3a1a1a; border-bottom: 2px solid var(--red);
-                         padding: 0.5rem 1rem; text-align: center; font-size: 0.85rem;
-                         color: var(--red); z-index: 50; }
-  #disconnected-banner.visible { display: block; }
-  /* 3D View tab */
-  #tab-3dview { position: relative; }
-  #three-container { flex: 1; background: #050810; overflow: hidden; }
-  #three-container canvas { display: block; width: 100% !important; height: 100% !important; }
-  .three-legend { position: absolute; bottom: 1rem; right: 1rem;
-                  background: rgba(22,27,34,.88); border: 1px solid var(--border);
-                  border-radius: 8px; padding: 0.6rem 0.8rem; font-size: 0.72rem;
-                  display: flex; flex-direction: column; gap: 0.3rem; pointer-events: none; }
-  .legend-item { display: flex; align-items: center; gap: 0.5rem; }
-  .legend-swatch { width: 12px; height: 12px; border-radius: 2px; flex-shrink: 0; }
-  /* Camera tab */
-  #tab-camera { overflow: auto; align-items: center; justify-content: center; }
-  .camera-layout { display: flex; flex-direction: column; align-items: center;
-                   padding: 0.75rem; gap: 0.75rem; width: 100%; max-width: 900px;
-                   margin: 0 auto; }
-  .camera-viewport { background: #000; display: flex; align-items: center;
-                     justify-content: center; border-radius: 6px; overflow: hidden;
-                     min-height: 240px; width: 100%; }
-  #camera-img { max-width: 100%; max-height: 60vh; display: block; }
-  .camera-placeholder { color: var(--text-dim); font-size: 0.85rem; text-align: center;
-                        padding: 2rem; }
-  /* Config tab */
-  #tab-config { overflow: auto; }
-  .config-layout { display: flex; gap: 0.75rem; padding: 0.75rem; flex: 1; min-height: 0; }
-  .config-panel { background: var(--surface); border: 1px solid var(--border); border-radius: 8px;
-                  display: flex; flex-direction: column; overflow: hidden; flex: 1; }
-  .config-body { display: flex; flex-direction: column; gap: 0.5rem; padding: 0.75rem;
-                 flex: 1; overflow: hidden; }
-  .config-textarea { flex: 1; background: var(--bg); border: 1px solid var(--border);
-                     border-radius: 6px; padding: 0.5rem; color: var(--text);
-                     font-family: var(--mono); font-size: 0.75rem; resize: none;
-                     outline: none; min-height: 200px; }
-  .config-textarea:focus { border-color: var(--accent); }
-  .config-actions { display: flex; gap: 0.5rem; }
-  .config-status { font-size: 0.75rem; color: var(--text-dim); font-family: var(--mono); min-height: 1.2em; }
-  .ollama-url-row { display: flex; gap: 0.5rem; }
-  .ollama-url-input { flex: 1; background: var(--bg); border: 1px solid var(--border);
-                      border-radius: 6px; padding: 0.35rem 0.6rem; color: var(--text);
-                      font-family: var(--mono); font-size: 0.8rem; outline: none; }
-  .ollama-url-input:focus { border-color: var(--accent); }
-  .model-list { display: flex; flex-direction: column; gap: 0.35rem; overflow-y: auto; flex: 1; }
-  .model-item { background: var(--bg); border: 1px solid var(--border); border-radius: 6px;
-                padding: 0.4rem 0.6rem; font-size: 0.78rem; font-family: var(--mono); }
-  .model-item .model-name { color: var(--text); }
-  .model-item .model-size { color: var(--text-dim); font-size: 0.7rem; }
-  /* Responsive */
-  @media (max-width: 1100px) {
-    main { grid-template-columns: 1fr 1fr; grid-template-rows: auto auto 1fr auto; }
-    #panel-sensor { grid-column: 1; grid-row: 1; }
-    #panel-teleop  { grid-column: 2; grid-row: 1; }
-    #panel-brain   { grid-column: 1 / 3; grid-row: 2; }
-    #panel-hitl    { grid-column: 1 / 3; grid-row: 3; }
-    #panel-ooda    { grid-column: 1 / 3; grid-row: 4; }
-    .config-layout { flex-direction: column; }
-  }
-  @media (max-width: 700px) {
-    main { grid-template-columns: 1fr; }
-    #panel-sensor, #panel-teleop, #panel-brain, #panel-hitl, #panel-ooda { grid-column: 1; grid-row: auto; }
-    .config-layout { flex-direction: column; }
-  }
-</style>
-</head>
-<body>
-
-<div id="disconnected-banner">&#9888; Disconnected from MechOS &#8211; attempting to reconnect&#8230;</div>
-
-<header>
-  <h1>&#129302; MechOS Cockpit</h1>
-  <span id="state-badge" class="status-badge badge-observing">Observing</span>
-  <span style="flex:1"></span>
-  <span id="battery" style="font-size:.8rem;font-family:var(--mono)">&#128267; &#8212;%</span>
-  <button id="btn-pause" class="btn">&#9208; Pause Agent</button>
-  <div class="conn-indicator">
-    <div id="conn-dot" class="conn-dot"></div>
-    <span id="conn-label">Disconnected</span>
-  </div>
-</header>
-
-<nav class="tab-bar">
-  <button class="tab-btn active" data-tab="dashboard">&#128202; Dashboard</button>
-  <button class="tab-btn" data-tab="3dview">&#128506; 3D View</button>
-  <button class="tab-btn" data-tab="camera">&#128247; Camera</button>
-  <button class="tab-btn" data-tab="config">&#9881; Config</button>
-</nav>
-
-<!-- Dashboard Tab -->
-<div id="tab-dashboard" class="tab-panel active">
-<main>
-  <!-- Sensory Visualizer -->
-  <div class="panel" id="panel-sensor">
-    <div class="panel-title">
-      <span>&#128225; Sensory Visualizer</span>
-      <span id="lidar-count" style="font-size:.65rem;color:var(--text-dim)">0 pts</span>
-    </div>
-    <canvas id="sensor-canvas"></canvas>
-    <div class="sensor-info" id="sensor-info">X: &#8212; &nbsp; Y: &#8212; &nbsp; Heading: &#8212;&#176;</div>
-  </div>
-
-  <!-- Brain Debugger -->
-  <div class="panel" id="panel-brain">
-    <div class="panel-title">&#129504; Brain Debugger</div>
-    <div class="brain-body">
-      <div class="metrics-ribbon">
-        <div class="metric">
-          <span class="metric-label">State</span>
-          <span class="metric-value" id="met-state">&#8212;</span>
-        </div>
-        <div class="metric">
-          <span class="metric-label">Inference</span>
-          <span class="metric-value" id="met-latency">&#8212;</span>
-        </div>
-        <div class="metric">
-          <span class="metric-label">Last Action</span>
-          <span class="metric-value" id="met-action" style="color:var(--green)">&#8212;</span>
-        </div>
-        <div class="metric">
-          <span class="metric-label">Events/s</span>
-          <span class="metric-value" id="met-eps">0</span>
-        </div>
-      </div>
-      <div class="feeds">
-        <div class="feed">
-          <div class="feed-title">Context / Thoughts</div>
-          <div class="feed-content" id="feed-context"></div>
-        </div>
-        <div class="feed">
-          <div class="feed-title">Raw LLM Output</div>
-          <div class="feed-content" id="feed-output"></div>
-        </div>
-      </div>
-    </div>
-  </div>
-
-  <!-- Teleoperation -->
-  <div class="panel" id="panel-teleop">
-    <div class="panel-title">&#128369; Teleoperation</div>
-    <div class="teleop-body">
-      <div style="font-size:.72rem;color:var(--text-dim);text-align:center">
-        W-A-S-D / Arrow keys &nbsp;&#183;&nbsp; Virtual joystick &nbsp;&#183;&nbsp; Gamepad
-      </div>
-      <!-- WASD visual -->
-      <div class="wasd-grid">
-        <div class="wasd-key empty"></div>
-        <div class="wasd-key" id="key-w" data-key="KeyW">W</div>
-        <div class="wasd-key empty"></div>
-        <div class="wasd-key" id="key-a" data-key="KeyA">A</div>
-        <div class="wasd-key" id="key-s" data-key="KeyS">S</div>
-        <div class="wasd-key" id="key-d" data-key="KeyD">D</div>
-      </div>
-      <!-- Virtual joystick -->
-      <div class="joystick-container">
-        <canvas id="joystick-canvas" width="120" height="120"></canvas>
-        <div class="velocity-display" id="vel-display">Linear: 0.00 m/s &nbsp; Angular: 0.00 rad/s</div>
-      </div>
-      <div class="gamepad-info" id="gamepad-info">No gamepad detected</div>
-    </div>
-  </div>
-
-  <!-- HITL Panel -->
-  <div class="panel" id="panel-hitl">
-    <div class="panel-title">
-      <span>&#128587; HITL Alerts</span>
-      <span id="hitl-count-badge" class="count-badge" style="display:none">0</span>
-    </div>
-    <div class="hitl-body">
-      <div class="hitl-empty" id="hitl-empty">No pending questions from the robot.</div>
-      <div id="hitl-queue"></div>
-      <div id="hitl-input-row" class="hitl-input-row" style="display:none">
-        <input id="hitl-input" type="text" placeholder="Type your answer&#8230;"
-               style="flex:1;background:var(--bg);border:1px solid var(--border);border-radius:6px;padding:.4rem .6rem;color:var(--text);font-family:var(--font);font-size:.85rem;outline:none"/>
-        <button class="btn" id="hitl-submit">Send</button>
-      </div>
-    </div>
-  </div>
-
-  <!-- OODA Loop Status Panel -->
-  <div class="panel" id="panel-ooda">
-    <div class="panel-title">
-      <span>&#128260; OODA Loop Status</span>
-      <span id="ooda-tick" class="ooda-tick" style="font-size:.7rem;color:var(--text-dim)">Tick: 0</span>
-    </div>
-    <div class="ooda-body">
-      <div class="ooda-phases">
-        <div class="ooda-phase phase-observe" id="ooda-observe">
-          <span class="phase-label">&#128065; Observe</span>
-          <span class="phase-detail" id="ooda-observe-detail">&#8212;</span>
-        </div>
-        <div class="ooda-phase phase-orient" id="ooda-orient">
-          <span class="phase-label">&#129517; Orient</span>
-          <span class="phase-detail" id="ooda-orient-detail">&#8212;</span>
-        </div>
-        <div class="ooda-phase phase-decide" id="ooda-decide">
-          <span class="phase-label">&#128161; Decide</span>
-          <span class="phase-detail" id="ooda-decide-detail">&#8212;</span>
-        </div>
-        <div class="ooda-phase phase-act" id="ooda-act">
-          <span class="phase-label">&#9889; Act</span>
-          <span class="phase-detail" id="ooda-act-detail">&#8212;</span>
-        </div>
-      </div>
-      <!-- Hardware Interlock Monitor -->
-      <div class="interlock-row">
-        <div class="interlock-badge">
-          <span class="interlock-dot idle" id="ilk-kernel-dot"></span>
-          <span class="interlock-label">Kernel</span>
-          <span class="interlock-value" id="ilk-kernel-val">&#8212;</span>
-        </div>
-        <div class="interlock-badge">
-          <span class="interlock-dot idle" id="ilk-watchdog-dot"></span>
-          <span class="interlock-label">Watchdog</span>
-          <span class="interlock-value" id="ilk-watchdog-val">&#8212;</span>
-        </div>
-        <div class="interlock-badge">
-          <span class="interlock-dot idle" id="ilk-drive-dot"></span>
-          <span class="interlock-label">Drive</span>
-          <span class="interlock-value" id="ilk-drive-val">&#8212;</span>
-        </div>
-        <div class="interlock-badge">
-          <span class="interlock-dot idle" id="ilk-eventbus-dot"></span>
-          <span class="interlock-label">Event Bus</span>
-          <span class="interlock-value" id="ilk-eventbus-val">&#8212;</span>
-        </div>
-        <div class="interlock-badge">
-          <span class="interlock-dot idle" id="ilk-memory-dot"></span>
-          <span class="interlock-label">Memory</span>
-          <span class="interlock-value" id="ilk-memory-val">&#8212;</span>
-        </div>
-      </div>
-      <div class="ooda-footer">
-        <span class="heartbeat-line" id="heartbeat-line">Last event: &#8212;</span>
-      </div>
-    </div>
-  </div>
-</main>
-</div>
-
-<!-- 3D View Tab -->
-<div id="tab-3dview" class="tab-panel">
-  <div id="three-container"></div>
-  <div class="three-legend">
-    <div class="legend-item">
-      <span class="legend-swatch" style="background:#58a6ff"></span>
-      <span>Robot</span>
-    </div>
-    <div class="legend-item">
-      <span class="legend-swatch" style="border:1px solid #3fb950;background:transparent"></span>
-      <span>Octree Voxels</span>
-    </div>
-    <div class="legend-item">
-      <span class="legend-swatch" style="background:#d29922"></span>
-      <span>Planned Path</span>
-    </div>
-    <div class="legend-item">
-      <span class="legend-swatch" style="background:#30363d"></span>
-      <span>Grid</span>
-    </div>
-    <div style="font-size:.65rem;color:var(--text-dim);margin-top:.2rem">Drag: rotate &#183; Scroll: zoom</div>
-  </div>
-</div>
-
-<!-- Camera Tab -->
-<div id="tab-camera" class="tab-panel">
-  <div class="camera-layout">
-    <div class="panel" style="width:100%">
-      <div class="panel-title">
-        <span>&#128247; Camera Feed</span>
-        <span id="camera-status" style="font-size:.65rem;color:var(--text-dim)">Idle</span>
-      </div>
-      <div class="camera-viewport">
-        <img id="camera-img" alt="" style="display:none"/>
-        <div class="camera-placeholder" id="camera-placeholder">
-          &#128247; Camera feed will appear here when the camera tab is active.<br>
-          <span style="font-size:.75rem">Frames are fetched from <code style="color:var(--accent)">/frame</code> on this server.</span>
-        </div>
-      </div>
-      <div style="padding:.4rem .75rem;font-size:.72rem;color:var(--text-dim);font-family:var(--mono);border-top:1px solid var(--border)">
-        Source: <span style="color:var(--accent)">/frame</span>
-        &nbsp;&#183;&nbsp; <span id="camera-fps-label">0 fps</span>
-      </div>
-    </div>
-  </div>
-</div>
-
-<!-- Config Tab -->
-<div id="tab-config" class="tab-panel">
-  <div class="config-layout">
-    <div class="config-panel">
-      <div class="panel-title">&#9881; ~/.mechos/config.toml</div>
-      <div class="config-body">
-        <textarea id="config-textarea" class="config-textarea" spellcheck="false"
-                  placeholder="Loading config&#8230;"></textarea>
-        <div class="config-actions">
-          <button class="btn" id="btn-config-reload">&#128260; Reload</button>
-          <button class="btn active" id="btn-config-save">&#128190; Save</button>
-        </div>
-        <div class="config-status" id="config-status"></div>
-      </div>
-    </div>
-    <div class="config-panel">
-      <div class="panel-title">&#129302; Ollama Model Availability</div>
-      <div class="config-body">
-        <div class="ollama-url-row">
-          <input id="ollama-url" class="ollama-url-input" type="text"
-                 value="http://localhost:11434" placeholder="Ollama base URL"/>
-          <button class="btn active" id="btn-ollama-test">Test</button>
-        </div>
-        <div class="config-status" id="ollama-status"></div>
-        <div class="model-list" id="model-list"></div>
-      </div>
-    </div>
-  </div>
-</div>
-
-<!-- HITL Modal -->
-<div id="hitl-modal">
-  <div class="modal-box">
-    <h2>&#128587; Robot needs your help</h2>
-    <div class="modal-question" id="modal-question"></div>
-    <div class="modal-context" id="modal-context" style="display:none"></div>
-    <input id="modal-input" class="modal-input" type="text"
-           placeholder="Type your answer and press Enter&#8230;" autocomplete="off"/>
-    <div class="modal-actions">
-      <button class="btn danger" id="modal-dismiss">Dismiss</button>
-      <button class="btn active" id="modal-submit">Send Answer</button>
-    </div>
-  </div>
-</div>
-
-<script src="https://cdnjs.cloudflare.com/ajax/libs/three.js/r128/three.min.js"></script>
-<script>
 'use strict';
 // =========================================================================
 // Constants & state
 // =========================================================================
-const WS_URL = `ws://${location.host}/ws`;
+const WS_URL = `${location.protocol === 'https:' ? 'wss' : 'ws'}://${location.host}/ws`;
 const LINEAR_SPEED  = 0.5;
 const ANGULAR_SPEED = 0.8;
 const DRIVE_HZ = 20;
@@ -515,6 +13,7 @@ let reconnectTimer = null;
 let agentPaused = false;
 let robotX = 0, robotY = 0, robotHeading = 0, battery = 0;
 let lidarRanges = [], lidarAngleMin = -Math.PI/2, lidarAngleIncrement = 0.017453;
+let mapImage = null, mapInfo = null;
 let keysDown = new Set();
 let joystickDragging = false;
 let joystickX = 0, joystickY = 0;
@@ -523,7 +22,11 @@ let currentState = 'Observing';
 let eventCount = 0, epsDisplay = 0;
 let lastThinkTime = null;
 let teleopInterval = null;
-let pendingHITL = null;
+let overrideHeld = false;
+let pendingHITL = null;  // hitlQueue item shown in the modal
+let estopped = false, safetyMode = 'normal';
+let localRobot = null, selectedRobot = null;
+let pendingSafety = {};  // request id -> {frame, timer}
 
 // OODA state
 let oodaTick = 0;
@@ -531,6 +34,7 @@ let currentOodaPhase = null;
 
 // HITL queue
 let hitlQueue = [];
+let approvals = {};  // request id -> ApprovalRequest
 
 // =========================================================================
 // Tab navigation
@@ -562,6 +66,8 @@ function connect() {
     document.getElementById('disconnected-banner').classList.remove('visible');
     setInterlockStatus('kernel', 'ok', 'online');
     setInterlockStatus('eventbus', 'ok', 'live');
+    // Every topic, but LiDAR scans no faster than the visualizer redraws.
+    send({op: 'subscribe', max_rate_hz: {LidarScan: 2}});
   });
 
   ws.addEventListener('close', function() {
@@ -599,8 +105,33 @@ function send(obj) {
 // Event handling (downstream: server to browser)
 // =========================================================================
 function handleEvent(event) {
+  if (event.op === 'safety_ack' || event.op === 'safety_nack') {
+    confirmSafety(event);
+    return;
+  }
+  if (event.op === 'approval_nack') {
+    appendFeed('feed-context', '\u26A0 Approval not sent: ' + event.error);
+    return;
+  }
+  if (event.op === 'hitl_nack') {
+    appendFeed('feed-context', '\u26A0 Answer not sent: ' + event.error);
+    return;
+  }
+  if (event.op === 'robots') {
+    updateRobotSelect(event);
+    return;
+  }
   var payload = event.payload;
   if (!payload) return;
+  // Only the selected robot drives the UI; other robots' faults still show.
+  if (event.robot_id && selectedRobot && event.robot_id !== selectedRobot) {
+    if (payload.HardwareFault !== undefined) {
+      var other = payload.HardwareFault;
+      appendFeed('feed-context', '\u26A0 [' + event.robot_id + '] Fault on ' + other.component +
+        ' [' + other.code + ']: ' + other.message);
+    }
+    return;
+  }
 
   if (payload.Telemetry) {
     var t = payload.Telemetry;
@@ -621,6 +152,25 @@ function handleEvent(event) {
     return;
   }
 
+  if (payload.PowerStatus) {
+    var p = payload.PowerStatus;
+    battery = Math.round(p.percent);
+    document.getElementById('battery').textContent =
+      (p.charging ? '\u26A1 ' : '\uD83D\uDD0B ') + battery + '% ' + p.voltage.toFixed(1) + ' V';
+    setInterlockStatus('drive', battery < 20 && !p.charging ? 'warn' : 'ok', battery + '% bat');
+    return;
+  }
+
+  if (payload.CameraFrame) {
+    showBusCameraFrame(payload.CameraFrame);
+    return;
+  }
+
+  if (payload.MapSnapshot) {
+    updateMap(payload.MapSnapshot);
+    return;
+  }
+
   if (payload.LidarScan) {
     lidarRanges = payload.LidarScan.ranges;
     lidarAngleMin = payload.LidarScan.angle_min_rad;
@@ -629,47 +179,45 @@ function handleEvent(event) {
     return;
   }
 
-  if (payload.AgentThought !== undefined) {
-    var thought = payload.AgentThought;
+  if (payload.AdapterCommand !== undefined) {
+    var command = payload.AdapterCommand;
+    var frame = command.json || {};
+    var msg = frame.msg || {};
     var src = event.source || '';
-    try {
-      var parsed = JSON.parse(thought);
-      if (parsed.topic === '/hitl/ask_human' && parsed.msg && parsed.msg.question) {
-        showHITLModal(parsed.msg.question, parsed.msg.context_image_id || null);
-        setState('Suspended');
-        setOodaPhase('decide', 'AskHuman: ' + parsed.msg.question.slice(0, 50));
-        return;
-      }
-      if (parsed.topic === '/cmd_vel' && src.includes('manual_override')) {
+    if (command.topic === '/hitl/ask_human' && msg.question) {
+      showHITLModal(msg.question, msg.context_image_id || null, msg.question_id || null);
+      setState('Suspended');
+      setOodaPhase('decide', 'AskHuman: ' + msg.question.slice(0, 50));
+      return;
+    }
+    if (command.topic === '/cmd_vel') {
+      var lin = msg.linear ? msg.linear.x : '?';
+      var ang = msg.angular ? msg.angular.z : '?';
+      if (src.includes('manual_override')) {
         setState('Suspended');
-        appendFeed('feed-context',
-          '[OVERRIDE] Drive lin=' + (parsed.msg && parsed.msg.linear ? parsed.msg.linear.x : '?') +
-          ' ang=' + (parsed.msg && parsed.msg.angular ? parsed.msg.angular.z : '?'));
-        return;
-      }
-      if (parsed.topic === '/cmd_vel') {
-        setState('Acting');
-        var action = 'Drive(lin=' + (parsed.msg && parsed.msg.linear ? parsed.msg.linear.x : '?') +
-                     ', ang=' + (parsed.msg && parsed.msg.angular ? parsed.msg.angular.z : '?') + ')';
-        document.getElementById('met-action').textContent = action;
-        appendFeed('feed-output', thought, true);
-        setOodaPhase('act', action);
-        return;
-      }
-    } catch(_) {}
-    try {
-      var parsed2 = JSON.parse(thought);
-      if (parsed2.action) {
-        setState('Acting');
-        document.getElementById('met-action').textContent = parsed2.action;
-        appendFeed('feed-output', thought, true);
-        setOodaPhase('act', 'action=' + parsed2.action);
-        if (lastThinkTime) {
-          document.getElementById('met-latency').textContent = (Date.now() - lastThinkTime) + 'ms';
-        }
+        appendFeed('feed-context', '[OVERRIDE] Drive lin=' + lin + ' ang=' + ang);
         return;
       }
-    } catch(_) {}
+      setState('Acting');
+      var action = 'Drive(lin=' + lin + ', ang=' + ang + ')';
+      document.getElementById('met-action').textContent = action;
+      appendFeed('feed-output', JSON.stringify(frame), true);
+      setOodaPhase('act', action);
+    }
+    return;
+  }
+
+  if (payload.IntentDispatched !== undefined) {
+    var dispatched = payload.IntentDispatched;
+    setState('Acting');
+    document.getElementById('met-action').textContent = dispatched.action || '?';
+    appendFeed('feed-output', JSON.stringify(dispatched), true);
+    setOodaPhase('act', 'action=' + dispatched.action);
+    return;
+  }
+
+  if (payload.Reasoning !== undefined) {
+    var thought = payload.Reasoning;
     setState('Thinking');
     lastThinkTime = Date.now();
     appendFeed('feed-context', thought, false);
@@ -677,8 +225,32 @@ function handleEvent(event) {
     return;
   }
 
+  if (payload.Intent !== undefined) {
+    var intent = payload.Intent.intent || {};
+    var intentJson = JSON.stringify(intent);
+    if (intent.action === 'AskHuman' && intent.payload) {
+      var ask = intent.payload;
+      showHITLModal(ask.question, ask.context_image_id || null, ask.question_id || null);
+      setState('Suspended');
+      setOodaPhase('decide', 'AskHuman: ' + ask.question.slice(0, 50));
+      return;
+    }
+    setState('Acting');
+    document.getElementById('met-action').textContent = intent.action || '?';
+    appendFeed('feed-output', intentJson, true);
+    setOodaPhase('act', 'action=' + intent.action);
+    if (lastThinkTime) {
+      document.getElementById('met-latency').textContent = (Date.now() - lastThinkTime) + 'ms';
+    }
+    return;
+  }
+
   if (payload.HumanResponse !== undefined) {
-    appendFeed('feed-context', '[Human] ' + payload.HumanResponse);
+    var reply = payload.HumanResponse;
+    // Pre-v5 peers send the bare answer string.
+    if (typeof reply === 'string') reply = { answer: reply };
+    removeHITLQuestion(reply.question_id || null);
+    appendFeed('feed-context', '[Human] ' + reply.answer);
     setState('Thinking');
     return;
   }
@@ -689,7 +261,9 @@ function handleEvent(event) {
       '\u26A0 Fault on ' + f.component + ' [' + f.code + ']: ' + f.message);
     setState('Suspended');
     var comp = (f.component || '').toLowerCase();
-    if (comp.indexOf('kernel') >= 0)                            setInterlockStatus('kernel',   'fault', f.code || 'fault');
+    var byCode = FAULT_INTERLOCK[f.code];
+    if (byCode)                                                 setInterlockStatus(byCode,     'fault', f.code);
+    else if (comp.indexOf('kernel') >= 0)                       setInterlockStatus('kernel',   'fault', f.code || 'fault');
     else if (comp.indexOf('watchdog') >= 0)                     setInterlockStatus('watchdog', 'fault', f.code || 'fault');
     else if (comp.indexOf('drive') >= 0 || comp.indexOf('motor') >= 0) setInterlockStatus('drive', 'fault', f.code || 'fault');
     else if (comp.indexOf('bus') >= 0   || comp.indexOf('event') >= 0) setInterlockStatus('eventbus', 'fault', f.code || 'fault');
@@ -703,6 +277,32 @@ function handleEvent(event) {
     if (agentPaused) setState('Paused');
     return;
   }
+
+  if (payload.SafetyStatus !== undefined) {
+    estopped = payload.SafetyStatus.estopped;
+    safetyMode = payload.SafetyStatus.mode;
+    updateSafetyButtons();
+    return;
+  }
+
+  if (payload.ApprovalRequest !== undefined) {
+    approvals[payload.ApprovalRequest.request_id] = payload.ApprovalRequest;
+    renderApprovals();
+    setState('Suspended');
+    setOodaPhase('decide', 'awaiting approval: ' + (payload.ApprovalRequest.envelope.intent.action || '?'));
+    return;
+  }
+
+  if (payload.ApprovalDecision !== undefined) {
+    var d = payload.ApprovalDecision;
+    if (approvals[d.request_id]) {
+      delete approvals[d.request_id];
+      renderApprovals();
+    }
+    appendFeed('feed-context', (d.approved ? '\u2714 Approved' : '\u2716 Rejected') + ' by ' + d.operator +
+      (d.reason ? ': ' + d.reason : ''));
+    return;
+  }
 }
 
 // =========================================================================
@@ -758,6 +358,20 @@ function setOodaPhase(phase, detail) {
 // =========================================================================
 // Hardware Interlock Monitor
 // =========================================================================
+// Interlock badge that a FaultCode lights up, regardless of which component
+// reported it.  Codes not listed here fall back to the component name.
+var FAULT_INTERLOCK = {
+  speed_cap_exceeded:   'kernel',
+  workspace_violation:  'kernel',
+  geofence_violation:   'kernel',
+  joint_limit_exceeded: 'kernel',
+  override_active:      'kernel',
+  deadline_expired:     'kernel',
+  stale_sensor:         'watchdog',
+  emergency_stop:       'drive',
+  collision_predicted:  'drive'
+};
+
 function setInterlockStatus(component, status, value) {
   var dot = document.getElementById('ilk-' + component + '-dot');
   var val = document.getElementById('ilk-' + component + '-val');
@@ -788,6 +402,85 @@ document.getElementById('btn-pause').addEventListener('click', function() {
   send({ topic: '/agent/mode', msg: { paused: agentPaused } });
 });
 
+// =========================================================================
+// Robot selection (multi-robot Cockpit)
+// =========================================================================
+function updateRobotSelect(msg) {
+  localRobot = msg.local;
+  if (selectedRobot !== msg.selected) {
+    // The replayed history brings the new robot's pending approvals.
+    approvals = {};
+    renderApprovals();
+  }
+  selectedRobot = msg.selected;
+  var select = document.getElementById('robot-select');
+  select.innerHTML = '';
+  msg.robots.forEach(function(id) {
+    var option = document.createElement('option');
+    option.value = id;
+    option.textContent = '\uD83E\uDD16 ' + id;
+    select.appendChild(option);
+  });
+  select.value = selectedRobot;
+  select.style.display = msg.robots.length > 1 ? '' : 'none';
+}
+
+document.getElementById('robot-select').addEventListener('change', function(e) {
+  // The server answers with a fresh robot list and the robot's history.
+  send({ op: 'select_robot', robot_id: e.target.value });
+});
+
+// =========================================================================
+// E-stop & safety mode (resent until the server acknowledges them)
+// =========================================================================
+var SAFETY_RETRY_MS = 500;
+
+function sendSafety(topic, msg) {
+  var id = Date.now().toString(36) + Math.random().toString(36).slice(2, 8);
+  var frame = { topic: topic, msg: Object.assign({ id: id }, msg || {}) };
+  var entry = { frame: frame, timer: null };
+  pendingSafety[id] = entry;
+  (function attempt() {
+    send(frame);
+    entry.timer = setTimeout(attempt, SAFETY_RETRY_MS);
+  })();
+  updateSafetyButtons();
+}
+
+function confirmSafety(ack) {
+  var entry = pendingSafety[ack.id];
+  if (!entry) return;
+  clearTimeout(entry.timer);
+  delete pendingSafety[ack.id];
+  if (ack.op === 'safety_nack') {
+    appendFeed('feed-context', '\u26A0 Safety command rejected: ' + ack.error);
+  } else {
+    estopped = ack.estopped;
+    safetyMode = ack.mode;
+  }
+  updateSafetyButtons();
+}
+
+function updateSafetyButtons() {
+  var unconfirmed = Object.keys(pendingSafety).length > 0;
+  var estopBtn = document.getElementById('btn-estop');
+  estopBtn.textContent = estopped ? '\u21BA Reset E-Stop' : '\u26D4 E-STOP';
+  if (unconfirmed) estopBtn.textContent += ' (unconfirmed)';
+  estopBtn.classList.toggle('active', estopped);
+  var modeBtn = document.getElementById('btn-mode');
+  modeBtn.classList.toggle('active', safetyMode === 'reduced');
+  modeBtn.disabled = estopped;
+  setInterlockStatus('kernel', estopped ? 'fault' : 'ok', estopped ? 'e-stop' : safetyMode);
+}
+
+document.getElementById('btn-estop').addEventListener('click', function() {
+  sendSafety(estopped ? '/safety/reset' : '/safety/estop');
+});
+
+document.getElementById('btn-mode').addEventListener('click', function() {
+  sendSafety('/safety/mode', { mode: safetyMode === 'reduced' ? 'normal' : 'reduced' });
+});
+
 // =========================================================================
 // Teleoperation - WASD keyboard
 // =========================================================================
@@ -918,10 +611,18 @@ teleopInterval = setInterval(function() {
   var jsAng = -joystickX * ANGULAR_SPEED;
   var linear  = gpv[0] || kv[0] || jsLin;
   var angular = gpv[1] || kv[1] || jsAng;
-  if (Math.abs(linear) > 0.01 || Math.abs(angular) > 0.01) {
+  var moving = Math.abs(linear) > 0.01 || Math.abs(angular) > 0.01;
+  // Dead-man heartbeat: the server stops the robot if these stop arriving.
+  var held = moving || joystickDragging || keysDown.size > 0;
+  if (moving) {
     sendDrive(linear, angular);
     setState('Suspended');
+  } else if (held) {
+    send({ op: 'override_heartbeat' });
+  } else if (overrideHeld) {
+    send({ op: 'override_end' });
   }
+  overrideHeld = held;
 }, 1000 / DRIVE_HZ);
 
 document.querySelectorAll('.wasd-key:not(.empty)').forEach(function(el) {
@@ -943,7 +644,8 @@ function renderHITLQueue() {
   var inputRow = document.getElementById('hitl-input-row');
   queueEl.innerHTML = '';
   if (hitlQueue.length === 0) {
-    emptyEl.style.display = ''; badge.style.display = 'none'; inputRow.style.display = 'none';
+    emptyEl.style.display = Object.keys(approvals).length > 0 ? 'none' : '';
+    badge.style.display = 'none'; inputRow.style.display = 'none';
     return;
   }
   emptyEl.style.display = 'none';
@@ -958,39 +660,128 @@ function renderHITLQueue() {
   });
 }
 
-function showHITLModal(question, contextImageId) {
+// =========================================================================
+// Operator approval of held intents
+// =========================================================================
+function renderApprovals() {
+  var queueEl = document.getElementById('approval-queue');
+  queueEl.innerHTML = '';
+  Object.keys(approvals).forEach(function(id) {
+    var req = approvals[id];
+    var div = document.createElement('div');
+    div.className = 'hitl-queue-item';
+    var rules = (req.report || []).map(function(v) {
+      return '<span class="approval-rule' + (v.passed ? '' : ' failed') + '">' +
+             (v.passed ? '\u2714 ' : '\u2716 ') + escHtml(v.rule) +
+             (v.details ? ': ' + escHtml(v.details) : '') + '</span>';
+    }).join('');
+    div.innerHTML = '<span class="hitl-q">Approve ' + escHtml(req.envelope.intent.action || '?') +
+                    ' from ' + escHtml(req.envelope.issued_by) + '?</span>' +
+                    '<pre class="approval-intent">' + escHtml(JSON.stringify(req.envelope.intent, null, 1)) + '</pre>' +
+                    rules +
+                    '<div class="hitl-input-row"><button class="btn active">Approve</button>' +
+                    '<button class="btn danger">Reject</button></div>';
+    var buttons = div.querySelectorAll('button');
+    buttons[0].addEventListener('click', function() { decideApproval(id, true); });
+    buttons[1].addEventListener('click', function() { decideApproval(id, false); });
+    queueEl.appendChild(div);
+  });
+  if (Object.keys(approvals).length > 0) document.getElementById('hitl-empty').style.display = 'none';
+  else if (hitlQueue.length === 0) document.getElementById('hitl-empty').style.display = '';
+}
+
+function decideApproval(id, approved) {
+  var operatorEl = document.getElementById('operator-name');
+  var operator = operatorEl.value.trim();
+  if (!operator) { operatorEl.focus(); return; }
+  localStorage.setItem('mechos-operator', operator);
+  var msg = withOperatorToken({ op: approved ? 'approve' : 'reject', id: id, operator: operator });
+  if (!approved) {
+    var reason = prompt('Reason for rejecting (optional)');
+    if (reason) msg.reason = reason;
+  }
+  send(msg);
+}
+
+document.getElementById('operator-name').value = localStorage.getItem('mechos-operator') || '';
+
+// The operator token proves who answers and approves when the server
+// requires one; it stays in this tab's session only.
+function withOperatorToken(msg) {
+  var token = document.getElementById('operator-token').value.trim();
+  if (token) {
+    sessionStorage.setItem('mechos-operator-token', token);
+    msg.token = token;
+  }
+  return msg;
+}
+document.getElementById('operator-token').value = sessionStorage.getItem('mechos-operator-token') || '';
+
+function showHITLModal(question, contextImageId, questionId) {
+  // The same question can arrive in the history snapshot and live.
+  if (questionId && hitlQueue.some(function(item) { return item.questionId === questionId; })) return;
   var ts = new Date().toTimeString().slice(0, 8);
-  hitlQueue.push({ question: question, contextImageId: contextImageId || null, ts: ts });
-  pendingHITL = question;
+  var item = { question: question, contextImageId: contextImageId || null, questionId: questionId || null, ts: ts };
+  hitlQueue.push(item);
+  pendingHITL = item;
   renderHITLQueue();
   document.getElementById('modal-question').textContent = question;
   var ctx = document.getElementById('modal-context');
-  if (contextImageId) { ctx.textContent = 'Context: frame ' + contextImageId; ctx.style.display = ''; }
-  else { ctx.style.display = 'none'; }
+  var image = document.getElementById('modal-image');
+  if (contextImageId) {
+    ctx.textContent = 'Context: frame ' + contextImageId; ctx.style.display = '';
+    image.onerror = function() { image.style.display = 'none'; };
+    // Other robots' frames are namespaced by robot ID on the server.
+    var prefix = selectedRobot && selectedRobot !== localRobot ? selectedRobot + '/' : '';
+    image.src = '/images/' + prefix + contextImageId;
+    image.style.display = '';
+  } else { ctx.style.display = 'none'; image.style.display = 'none'; }
   document.getElementById('modal-input').value = '';
   document.getElementById('hitl-modal').classList.add('visible');
   document.getElementById('modal-input').focus();
 }
 
-function dismissHITL() { document.getElementById('hitl-modal').classList.remove('visible'); }
+function dismissHITL() {
+  document.getElementById('hitl-modal').classList.remove('visible');
+  pendingHITL = null;
+}
+
+// Drop the question answered by `questionId`, or the oldest one when the
+// answer names no question.
+function removeHITLQuestion(questionId) {
+  var idx = questionId
+    ? hitlQueue.findIndex(function(item) { return item.questionId === questionId; })
+    : 0;
+  if (idx < 0 || idx >= hitlQueue.length) return;
+  if (hitlQueue[idx] === pendingHITL) dismissHITL();
+  hitlQueue.splice(idx, 1);
+  renderHITLQueue();
+}
 
-function submitHITL(answer) {
+// Answer `item` (the oldest queued question by default).
+function submitHITL(answer, item) {
   if (!answer.trim()) return;
-  send({ op: 'publish', topic: '/hitl/human_response', msg: { response: answer.trim() } });
-  dismissHITL();
-  if (hitlQueue.length > 0) hitlQueue.shift();
-  pendingHITL = hitlQueue.length > 0 ? hitlQueue[0].question : null;
+  item = item || hitlQueue[0] || null;
+  var msg = { response: answer.trim() };
+  if (item && item.questionId) msg.question_id = item.questionId;
+  var operator = document.getElementById('operator-name').value.trim();
+  send(withOperatorToken({
+    op: 'publish', topic: '/hitl/human_response', msg: msg, operator: operator || undefined
+  }));
+  if (item === pendingHITL) dismissHITL();
+  var idx = hitlQueue.indexOf(item);
+  if (idx >= 0) hitlQueue.splice(idx, 1);
   renderHITLQueue();
   appendFeed('feed-context', '[You \u2192 Robot] ' + answer.trim());
   setState('Thinking');
 }
 
 document.getElementById('modal-submit').addEventListener('click', function() {
-  submitHITL(document.getElementById('modal-input').value);
+  submitHITL(document.getElementById('modal-input').value, pendingHITL);
 });
 document.getElementById('modal-dismiss').addEventListener('click', dismissHITL);
 document.getElementById('modal-input').addEventListener('keydown', function(e) {
-  if (e.key === 'Enter') submitHITL(e.target.value);
+  if (e.key === 'Enter') submitHITL(e.target.value, pendingHITL);
   if (e.key === 'Escape') dismissHITL();
 });
 document.getElementById('hitl-submit').addEventListener('click', function() {
@@ -1015,6 +806,28 @@ function resizeSensorCanvas() {
   sCanvas.height = Math.max(panel.clientHeight - titleH - infoH, 200);
 }
 
+// Paint an occupancy grid (0 free, 100 occupied, 255 unknown) onto an
+// offscreen canvas, one pixel per cell, flipped so +Y points up.
+function updateMap(m) {
+  var raw = atob(m.cells_b64);
+  var canvas = document.createElement('canvas');
+  canvas.width = m.width;
+  canvas.height = m.height;
+  var ctx = canvas.getContext('2d');
+  var image = ctx.createImageData(m.width, m.height);
+  for (var row = 0; row < m.height; row++) {
+    for (var col = 0; col < m.width; col++) {
+      var v = raw.charCodeAt(row * m.width + col);
+      var o = ((m.height - 1 - row) * m.width + col) * 4;
+      if (v === 100)    { image.data[o] = 139; image.data[o + 1] = 148; image.data[o + 2] = 158; image.data[o + 3] = 230; }
+      else if (v === 0) { image.data[o] = 63;  image.data[o + 1] = 185; image.data[o + 2] = 80;  image.data[o + 3] = 40; }
+    }
+  }
+  ctx.putImageData(image, 0, 0);
+  mapImage = canvas;
+  mapInfo = m;
+}
+
 function renderSensor() {
   resizeSensorCanvas();
   var w = sCanvas.width, h = sCanvas.height;
@@ -1030,6 +843,14 @@ function renderSensor() {
     sCtx.beginPath(); sCtx.moveTo(0, gy); sCtx.lineTo(w, gy); sCtx.stroke();
   }
   var RX = w / 2, RY = h / 2;
+  if (mapImage) {
+    var mw = mapInfo.width * mapInfo.resolution, mh = mapInfo.height * mapInfo.resolution;
+    sCtx.imageSmoothingEnabled = false;
+    sCtx.drawImage(mapImage,
+      RX + (mapInfo.origin_x - robotX) * SCALE,
+      RY - (mapInfo.origin_y + mh - robotY) * SCALE,
+      mw * SCALE, mh * SCALE);
+  }
   if (lidarRanges.length > 0) {
     sCtx.fillStyle = '#f8514966';
     for (var i = 0; i < lidarRanges.length; i++) {
@@ -1274,6 +1095,44 @@ function refreshCameraFrame() {
   tmp.src = '/frame?_t=' + Date.now();
 }
 
+// Frames published on the event bus (EventPayload::CameraFrame).  Compressed
+// frames are shown as-is; raw frames are painted onto a canvas first.
+function showBusCameraFrame(f) {
+  var img = document.getElementById('camera-img');
+  var placeholder = document.getElementById('camera-placeholder');
+  if (!img) return;
+  if (f.format === 'jpeg' || f.format === 'png') {
+    img.src = 'data:image/' + f.format + ';base64,' + f.data_b64;
+  } else {
+    var bpp = { mono8: 1, rgb8: 3, bgr8: 3, rgba8: 4 }[f.format];
+    if (!bpp) return;
+    var raw = atob(f.data_b64);
+    var canvas = document.createElement('canvas');
+    canvas.width = f.width;
+    canvas.height = f.height;
+    var ctx = canvas.getContext('2d');
+    var image = ctx.createImageData(f.width, f.height);
+    for (var i = 0, n = f.width * f.height; i < n; i++) {
+      var o = i * bpp;
+      var r = raw.charCodeAt(o), g = r, b = r;
+      if (bpp >= 3) { g = raw.charCodeAt(o + 1); b = raw.charCodeAt(o + 2); }
+      if (f.format === 'bgr8') { var t = r; r = b; b = t; }
+      image.data[i * 4] = r;
+      image.data[i * 4 + 1] = g;
+      image.data[i * 4 + 2] = b;
+      image.data[i * 4 + 3] = 255;
+    }
+    ctx.putImageData(image, 0, 0);
+    img.src = canvas.toDataURL();
+  }
+  img.style.display = 'block';
+  if (placeholder) placeholder.style.display = 'none';
+  cameraFrameCount++;
+  cameraFpsCount++;
+  var status = document.getElementById('camera-status');
+  if (status) status.textContent = 'Bus \u00B7 ' + f.image_id + ' (' + f.width + '\u00D7' + f.height + ')';
+}
+
 setInterval(function() {
   var fpsLabel = document.getElementById('camera-fps-label');
   if (fpsLabel) fpsLabel.textContent = cameraFpsCount + ' fps';
@@ -1285,6 +1144,3 @@ setInterval(function() {
 // =========================================================================
 connect();
 loop();
-</script>
-</body>
-</html>
\ No newline at end of file
@@ -0,0 +1,175 @@
+//! [`ApprovalMessage`] – an operator's approve / reject from a Cockpit tab.
+//!
+//! Intents held by the kernel's approval queue reach every tab as
+//! [`EventPayload::ApprovalRequest`] events carrying the full intent JSON
+//! and each safety rule's verdict.  The operator answers with
+//!
+//! ```json
+//! {"op":"approve","id":"<request_id>","operator":"alice"}
+//! {"op":"reject","id":"<request_id>","operator":"alice","reason":"too close to the stairs"}
+//! ```
+//!
+//! which is published on the selected robot's bus as an
+//! [`EventPayload::ApprovalDecision`].  The decision itself streams back to
+//! every tab, so all of them clear the prompt.  A message without a request
+//! `id` or an `operator` is answered with
+//! `{"op":"approval_nack","id":…,"error":…}`: every decision names who made
+//! it.  With operator tokens registered, the decision must also carry a
+//! `"token"`, and names the token's owner instead (see [`OperatorTokens`]).
+
+use chrono::Utc;
+use mechos_types::{Event, EventPayload};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::operator::OperatorTokens;
+
+/// A parsed `approve` / `reject` upstream message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApprovalMessage {
+    /// The `request_id` of the [`EventPayload::ApprovalRequest`] answered.
+    pub request_id: String,
+    pub approved: bool,
+    pub operator: String,
+    pub reason: Option<String>,
+}
+
+impl ApprovalMessage {
+    /// Parse an `approve` / `reject` message; `None` for any other op, and
+    /// the `approval_nack` frame to send back for an incomplete one.
+    pub fn parse(json: &Value) -> Option<Result<Self, Value>> {
+        let approved = match json.get("op").and_then(Value::as_str)? {
+            "approve" => true,
+            "reject" => false,
+            _ => return None,
+        };
+        let field = |name| {
+            json.get(name)
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+        let nack = |error: &str| {
+            Some(Err(json!({
+                "op": "approval_nack",
+                "id": json.get("id"),
+                "error": error,
+            })))
+        };
+        let Some(request_id) = field("id") else {
+            return nack("missing approval request id");
+        };
+        let Some(operator) = field("operator") else {
+            return nack("missing operator identity");
+        };
+        Some(Ok(Self {
+            request_id: request_id.to_string(),
+            approved,
+            operator: operator.to_string(),
+            reason: field("reason").map(str::to_string),
+        }))
+    }
+
+    /// Name the owner of `json`'s operator token as the one deciding; the
+    /// `approval_nack` frame to send back when `operators` refuses the
+    /// message.
+    pub fn authenticate(mut self, json: &Value, operators: &OperatorTokens) -> Result<Self, Value> {
+        match operators.operator(json) {
+            Ok(operator) => {
+                if let Some(operator) = operator {
+                    self.operator = operator;
+                }
+                Ok(self)
+            }
+            Err(error) => Err(json!({
+                "op": "approval_nack",
+                "id": self.request_id,
+                "error": error,
+            })),
+        }
+    }
+
+    /// The bus event carrying this decision.
+    pub fn to_event(&self) -> Event {
+        Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: "mechos-cockpit::server".to_string(),
+            payload: EventPayload::ApprovalDecision {
+                request_id: self.request_id.clone(),
+                approved: self.approved,
+                operator: self.operator.clone(),
+                reason: self.reason.clone(),
+            },
+            trace_id: None,
+            correlation_id: None,
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Option<Result<ApprovalMessage, Value>> {
+        ApprovalMessage::parse(&serde_json::from_str(text).unwrap())
+    }
+
+    #[test]
+    fn approve_and_reject_carry_the_operator() {
+        let approve = parse(r#"{"op":"approve","id":"a1","operator":"alice"}"#)
+            .unwrap()
+            .unwrap();
+        assert!(approve.approved);
+        assert_eq!(approve.operator, "alice");
+        assert_eq!(approve.reason, None);
+
+        let reject = parse(r#"{"op":"reject","id":"a2","operator":"bob","reason":"stairs"}"#)
+            .unwrap()
+            .unwrap();
+        assert!(!reject.approved);
+        assert!(matches!(
+            reject.to_event().payload,
+            EventPayload::ApprovalDecision { approved: false, ref reason, .. }
+                if reason.as_deref() == Some("stairs")
+        ));
+        assert!(parse(r#"{"op":"subscribe"}"#).is_none());
+    }
+
+    #[test]
+    fn anonymous_decisions_are_nacked() {
+        let nack = parse(r#"{"op":"approve","id":"a1","operator":"  "}"#)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(nack["op"], "approval_nack");
+        assert_eq!(nack["id"], "a1");
+        assert!(
+            parse(r#"{"op":"reject","operator":"alice"}"#)
+                .unwrap()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn tokens_decide_who_approved() {
+        let mut operators = OperatorTokens::new();
+        operators.insert("t-bob", "bob");
+        let text = r#"{"op":"approve","id":"a1","operator":"alice","token":"t-bob"}"#;
+        let json = serde_json::from_str(text).unwrap();
+        let approve = ApprovalMessage::parse(&json).unwrap().unwrap();
+        assert_eq!(approve.authenticate(&json, &operators).unwrap().operator, "bob");
+
+        let json = serde_json::from_str(r#"{"op":"approve","id":"a2","operator":"alice"}"#).unwrap();
+        let nack = ApprovalMessage::parse(&json)
+            .unwrap()
+            .unwrap()
+            .authenticate(&json, &operators)
+            .unwrap_err();
+        assert_eq!(nack["id"], "a2");
+        assert_eq!(nack["error"], "missing operator token");
+    }
+}
@@ -0,0 +1,232 @@
+//! [`StaticAsset`] – the Cockpit frontend bundle compiled into the binary.
+//!
+//! Every file under `crates/mechos-cockpit/assets/` (HTML, JS, CSS, icons,
+//! web fonts) is embedded at build time and served by
+//! [`CockpitServer`][crate::CockpitServer] at its path relative to that
+//! directory: `assets/cockpit.js` is `GET /cockpit.js`.  Debug builds read
+//! the files from disk instead, so frontend edits show up on reload without
+//! recompiling.
+//!
+//! * `GET /` and any extension-less path without a file of its own (e.g.
+//!   `/robots/rover-b`) serve [`INDEX`], so the single-page app can route on
+//!   the client.  A missing file with an extension is a `404`.
+//! * Text assets are gzip-compressed for clients sending
+//!   `Accept-Encoding: gzip`, once per asset.
+//! * Every response carries an `ETag`; a matching `If-None-Match` gets
+//!   `304 Not Modified`.  [`INDEX`] is revalidated on every load, other
+//!   assets are cached for [`ASSET_MAX_AGE_SECS`].
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::{Arc, LazyLock, Mutex};
+
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use rust_embed::RustEmbed;
+
+/// The page served for `/` and for client-side routes.
+pub const INDEX: &str = "index.html";
+
+/// `max-age` of every asset but [`INDEX`].
+pub const ASSET_MAX_AGE_SECS: u32 = 3600;
+
+/// Smaller text assets are sent uncompressed.
+const GZIP_MIN_BYTES: usize = 1024;
+
+#[derive(RustEmbed)]
+#[folder = "assets/"]
+struct Bundle;
+
+/// Gzipped bodies by ETag, compressed on first request.
+static GZIPPED: LazyLock<Mutex<HashMap<String, Arc<[u8]>>>> = LazyLock::new(Default::default);
+
+/// One file of the embedded frontend bundle.
+#[derive(Debug, Clone)]
+pub struct StaticAsset {
+    /// Path within the bundle, e.g. `"cockpit.js"`.
+    pub path: String,
+    pub content_type: &'static str,
+    pub body: Cow<'static, [u8]>,
+    /// Quoted strong validator derived from the file's SHA-256.
+    pub etag: String,
+}
+
+impl StaticAsset {
+    /// The bundle file at `path`, relative to the `assets/` directory.
+    pub fn get(path: &str) -> Option<Self> {
+        if path.split('/').any(|segment| segment == "..") {
+            return None;
+        }
+        let file = Bundle::get(path)?;
+        let hash = file.metadata.sha256_hash();
+        let etag = hash[..8]
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect::<String>();
+        Some(Self {
+            path: path.to_string(),
+            content_type: content_type(path),
+            body: file.data,
+            etag: format!("\"{etag}\""),
+        })
+    }
+
+    /// The asset answering the request path `path` (query string allowed),
+    /// falling back to [`INDEX`] for client-side routes.
+    pub fn resolve(path: &str) -> Option<Self> {
+        let path = path.split(['?', '#']).next().unwrap_or(path);
+        let path = path.trim_start_matches('/');
+        if path.is_empty() {
+            return Self::get(INDEX);
+        }
+        if let Some(asset) = Self::get(path) {
+            return Some(asset);
+        }
+        let file_name = path.rsplit('/').next().unwrap_or(path);
+        if file_name.contains('.') {
+            None
+        } else {
+            Self::get(INDEX)
+        }
+    }
+
+    /// The `Cache-Control` header value.
+    pub fn cache_control(&self) -> Cow<'static, str> {
+        if self.path == INDEX {
+            Cow::Borrowed("no-cache")
+        } else {
+            Cow::Owned(format!("public, max-age={ASSET_MAX_AGE_SECS}"))
+        }
+    }
+
+    /// Whether gzip is worth it: text formats above a minimum size.
+    pub fn compressible(&self) -> bool {
+        self.body.len() >= GZIP_MIN_BYTES
+            && (self.content_type.starts_with("text/")
+                || self.content_type.starts_with("application/javascript")
+                || self.content_type.starts_with("application/json")
+                || self.content_type.starts_with("image/svg+xml"))
+    }
+
+    /// The gzip-compressed body, compressed once and then cached.
+    pub fn gzipped(&self) -> Arc<[u8]> {
+        let mut cache = GZIPPED.lock().unwrap_or_else(|e| e.into_inner());
+        Arc::clone(cache.entry(self.etag.clone()).or_insert_with(|| {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+            // Writing to a Vec cannot fail.
+            let _ = encoder.write_all(&self.body);
+            encoder.finish().unwrap_or_default().into()
+        }))
+    }
+}
+
+/// `Content-Type` of a bundle file, by extension.
+pub fn content_type(path: &str) -> &'static str {
+    let extension = path.rsplit_once('.').map(|(_, ext)| ext).unwrap_or("");
+    match extension.to_ascii_lowercase().as_str() {
+        "html" | "htm" => "text/html; charset=utf-8",
+        "js" | "mjs" => "application/javascript; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "json" | "map" => "application/json",
+        "webmanifest" => "application/manifest+json",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "ico" => "image/x-icon",
+        "woff2" => "font/woff2",
+        "woff" => "font/woff",
+        "ttf" => "font/ttf",
+        "txt" => "text/plain; charset=utf-8",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Value of the request header `name` in `head`, if present.
+fn header<'a>(head: &'a str, name: &str) -> Option<&'a str> {
+    head.lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(key, _)| key.trim().eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.trim())
+}
+
+/// `true` when the request `head` accepts a gzip-encoded body.
+pub fn accepts_gzip(head: &str) -> bool {
+    header(head, "accept-encoding").is_some_and(|value| {
+        value.split(',').any(|coding| {
+            let mut parts = coding.split(';');
+            let name = parts.next().unwrap_or("").trim();
+            let refused = parts.any(|param| {
+                param
+                    .trim()
+                    .strip_prefix("q=")
+                    .and_then(|q| q.parse::<f32>().ok())
+                    == Some(0.0)
+            });
+            (name.eq_ignore_ascii_case("gzip") || name == "*") && !refused
+        })
+    })
+}
+
+/// `true` when the request `head` already holds the asset tagged `etag`.
+pub fn not_modified(head: &str, etag: &str) -> bool {
+    header(head, "if-none-match").is_some_and(|value| {
+        value
+            .split(',')
+            .map(|tag| tag.trim().trim_start_matches("W/"))
+            .any(|tag| tag == etag || tag == "*")
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn client_routes_fall_back_to_the_index() {
+        assert_eq!(StaticAsset::resolve("/").unwrap().path, INDEX);
+        assert_eq!(StaticAsset::resolve("/?robot=rover-b").unwrap().path, INDEX);
+        assert_eq!(StaticAsset::resolve("/robots/rover-b").unwrap().path, INDEX);
+        let script = StaticAsset::resolve("/cockpit.js").unwrap();
+        assert_eq!(script.content_type, "application/javascript; charset=utf-8");
+        assert!(StaticAsset::resolve("/missing.js").is_none());
+        assert!(StaticAsset::resolve("/../Cargo.toml").is_none());
+    }
+
+    #[test]
+    fn only_the_index_is_revalidated_every_load() {
+        let index = StaticAsset::get(INDEX).unwrap();
+        assert_eq!(index.cache_control(), "no-cache");
+        let style = StaticAsset::get("cockpit.css").unwrap();
+        assert_eq!(style.cache_control(), "public, max-age=3600");
+        assert_ne!(index.etag, style.etag);
+        assert!(!not_modified("If-None-Match: W/\"x\"", "\"y\""));
+        assert!(not_modified(
+            &format!("if-none-match: \"x\", W/{}", style.etag),
+            &style.etag
+        ));
+    }
+
+    #[test]
+    fn text_assets_are_gzipped_once() {
+        assert!(accepts_gzip("Accept-Encoding: deflate, gzip;q=0.8"));
+        assert!(!accepts_gzip("Accept-Encoding: gzip;q=0, br"));
+        assert!(!accepts_gzip("Host: robot"));
+
+        let script = StaticAsset::get("cockpit.js").unwrap();
+        assert!(script.compressible());
+        let gzipped = script.gzipped();
+        assert!(gzipped.len() < script.body.len());
+        assert!(Arc::ptr_eq(&gzipped, &script.gzipped()), "compressed once");
+        let mut body = Vec::new();
+        flate2::read::GzDecoder::new(&gzipped[..])
+            .read_to_end(&mut body)
+            .unwrap();
+        assert_eq!(body, &script.body[..]);
+    }
+}
@@ -0,0 +1,294 @@
+//! [`CameraRelay`] – bus camera frames served as MJPEG and by image ID.
+//!
+//! [`EventPayload::CameraFrame`] events are recorded per camera and exposed
+//! over plain HTTP by [`CockpitServer`][crate::CockpitServer]:
+//!
+//! | Endpoint | Response |
+//! |---|---|
+//! | `GET /cameras` | JSON array of the cameras seen so far |
+//! | `GET /cameras/{camera}/mjpeg` | `multipart/x-mixed-replace` MJPEG stream |
+//! | `GET /images/{image_id}` | One recent frame, e.g. an `AskHuman` prompt's `context_image_id` |
+//!
+//! A frame's camera is its `image_id` up to the last `/` (`"front/42"` →
+//! `"front"`); IDs without a `/` belong to [`DEFAULT_CAMERA`].  Raw frames
+//! (`mono8`, `rgb8`, `bgr8`, `rgba8`) are JPEG-encoded once on arrival;
+//! PNG frames are kept for `/images` but cannot join an MJPEG stream.
+//!
+//! Frames of other robots in a multi-robot Cockpit are recorded with
+//! [`record_for_robot`][CameraRelay::record_for_robot], which prefixes their
+//! image IDs with the robot ID: `"front/42"` from `rover-b` is served as
+//! `/images/rover-b/front/42` and streamed as camera `"rover-b/front"`.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use jpeg_encoder::{ColorType, Encoder};
+use mechos_types::{EventPayload, ImageFormat};
+use tokio::sync::broadcast;
+use tracing::warn;
+
+/// Camera of frames whose `image_id` has no `/`.
+pub const DEFAULT_CAMERA: &str = "default";
+
+/// Number of recent frames kept for lookup by image ID.
+pub const RECENT_IMAGES: usize = 64;
+
+/// Quality of JPEG-encoded raw frames.
+const JPEG_QUALITY: u8 = 80;
+
+/// Capacity of the new-frame channel feeding MJPEG streams.
+const UPDATE_CAPACITY: usize = 16;
+
+/// One encoded image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodedImage {
+    /// `image/jpeg` or `image/png`.
+    pub content_type: &'static str,
+    pub data: Arc<[u8]>,
+}
+
+/// A new JPEG frame of `camera`, as pushed to MJPEG streams.
+#[derive(Debug, Clone)]
+pub struct CameraUpdate {
+    pub camera: String,
+    pub jpeg: Arc<[u8]>,
+}
+
+#[derive(Default)]
+struct RelayState {
+    latest: BTreeMap<String, Arc<[u8]>>,
+    recent: VecDeque<(String, EncodedImage)>,
+}
+
+/// Latest frame of every camera plus recent frames by image ID.
+pub struct CameraRelay {
+    state: Mutex<RelayState>,
+    updates: broadcast::Sender<CameraUpdate>,
+}
+
+impl Default for CameraRelay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CameraRelay {
+    /// Create an empty relay.
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(RelayState::default()),
+            updates: broadcast::channel(UPDATE_CAPACITY).0,
+        }
+    }
+
+    /// Record `payload` if it is a [`EventPayload::CameraFrame`]; other
+    /// payloads are ignored.
+    pub fn record(&self, payload: &EventPayload) {
+        self.record_as(None, payload);
+    }
+
+    /// Record `payload` from `robot_id`, under image ID
+    /// `"{robot_id}/{image_id}"`.
+    pub fn record_for_robot(&self, robot_id: &str, payload: &EventPayload) {
+        self.record_as(Some(robot_id), payload);
+    }
+
+    fn record_as(&self, robot_id: Option<&str>, payload: &EventPayload) {
+        let EventPayload::CameraFrame {
+            image_id,
+            format,
+            width,
+            height,
+            data_b64,
+        } = payload
+        else {
+            return;
+        };
+        let data = match STANDARD.decode(data_b64) {
+            Ok(data) => data,
+            Err(e) => {
+                warn!(image_id = %image_id, error = %e, "camera frame is not valid base64");
+                return;
+            }
+        };
+        let image = match encode(*format, *width, *height, data) {
+            Ok(image) => image,
+            Err(e) => {
+                warn!(image_id = %image_id, error = %e, "camera frame could not be encoded");
+                return;
+            }
+        };
+
+        let image_id = match robot_id {
+            Some(robot_id) => format!("{robot_id}/{image_id}"),
+            None => image_id.clone(),
+        };
+        let camera = camera_name(&image_id).to_string();
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if image.content_type == "image/jpeg" {
+            state.latest.insert(camera.clone(), Arc::clone(&image.data));
+            let _ = self.updates.send(CameraUpdate {
+                camera,
+                jpeg: Arc::clone(&image.data),
+            });
+        }
+        if state.recent.len() == RECENT_IMAGES {
+            state.recent.pop_front();
+        }
+        state.recent.push_back((image_id, image));
+    }
+
+    /// Names of every camera that has produced a JPEG-able frame.
+    pub fn cameras(&self) -> Vec<String> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.latest.keys().cloned().collect()
+    }
+
+    /// The latest JPEG frame of `camera`.
+    pub fn latest(&self, camera: &str) -> Option<Arc<[u8]>> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.latest.get(camera).cloned()
+    }
+
+    /// The frame published as `image_id`, if it is still among the last
+    /// [`RECENT_IMAGES`].
+    pub fn image(&self, image_id: &str) -> Option<EncodedImage> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state
+            .recent
+            .iter()
+            .rev()
+            .find(|(id, _)| id == image_id)
+            .map(|(_, image)| image.clone())
+    }
+
+    /// Receive every new JPEG frame, of any camera.
+    pub fn subscribe(&self) -> broadcast::Receiver<CameraUpdate> {
+        self.updates.subscribe()
+    }
+}
+
+/// The camera an image ID belongs to.
+pub fn camera_name(image_id: &str) -> &str {
+    match image_id.rsplit_once('/') {
+        Some((camera, _)) if !camera.is_empty() => camera,
+        _ => DEFAULT_CAMERA,
+    }
+}
+
+/// Encode a raw frame as JPEG; compressed frames pass through.
+fn encode(
+    format: ImageFormat,
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+) -> Result<EncodedImage, jpeg_encoder::EncodingError> {
+    let color = match format {
+        ImageFormat::Jpeg => {
+            return Ok(EncodedImage {
+                content_type: "image/jpeg",
+                data: data.into(),
+            });
+        }
+        ImageFormat::Png => {
+            return Ok(EncodedImage {
+                content_type: "image/png",
+                data: data.into(),
+            });
+        }
+        ImageFormat::Mono8 => ColorType::Luma,
+        ImageFormat::Rgb8 => ColorType::Rgb,
+        ImageFormat::Bgr8 => ColorType::Bgr,
+        ImageFormat::Rgba8 => ColorType::Rgba,
+    };
+    // Ingestion caps frames at 640×480, well inside JPEG's u16 dimensions.
+    let (width, height) = (width as u16, height as u16);
+    let mut jpeg = Vec::new();
+    Encoder::new(&mut jpeg, JPEG_QUALITY).encode(&data, width, height, color)?;
+    Ok(EncodedImage {
+        content_type: "image/jpeg",
+        data: jpeg.into(),
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(image_id: &str, format: ImageFormat, data: &[u8]) -> EventPayload {
+        EventPayload::CameraFrame {
+            image_id: image_id.to_string(),
+            format,
+            width: 2,
+            height: 2,
+            data_b64: STANDARD.encode(data),
+        }
+    }
+
+    #[test]
+    fn camera_name_is_the_image_id_prefix() {
+        assert_eq!(camera_name("front/42"), "front");
+        assert_eq!(camera_name("arm/wrist/7"), "arm/wrist");
+        assert_eq!(camera_name("42"), DEFAULT_CAMERA);
+    }
+
+    #[tokio::test]
+    async fn raw_frames_are_jpeg_encoded_and_streamed() {
+        let relay = CameraRelay::new();
+        let mut updates = relay.subscribe();
+        relay.record(&frame("front/1", ImageFormat::Mono8, &[0, 64, 128, 255]));
+
+        let update = updates.recv().await.unwrap();
+        assert_eq!(update.camera, "front");
+        assert_eq!(
+            &update.jpeg[..2],
+            &[0xFF, 0xD8],
+            "JPEG start-of-image marker"
+        );
+        assert_eq!(relay.cameras(), ["front"]);
+        assert_eq!(relay.latest("front").unwrap(), update.jpeg);
+        assert_eq!(relay.image("front/1").unwrap().content_type, "image/jpeg");
+    }
+
+    #[test]
+    fn png_frames_are_kept_by_id_only() {
+        let relay = CameraRelay::new();
+        relay.record(&frame("rear/1", ImageFormat::Png, b"\x89PNG"));
+        assert!(relay.cameras().is_empty());
+        let image = relay.image("rear/1").unwrap();
+        assert_eq!(image.content_type, "image/png");
+        assert_eq!(&image.data[..], b"\x89PNG");
+    }
+
+    #[test]
+    fn other_robots_frames_are_namespaced() {
+        let relay = CameraRelay::new();
+        relay.record_for_robot(
+            "rover-b",
+            &frame("front/1", ImageFormat::Jpeg, &[0xFF, 0xD8]),
+        );
+        assert_eq!(relay.cameras(), ["rover-b/front"]);
+        assert!(relay.image("rover-b/front/1").is_some());
+        assert!(relay.image("front/1").is_none());
+    }
+
+    #[test]
+    fn only_recent_images_are_kept() {
+        let relay = CameraRelay::new();
+        for i in 0..=RECENT_IMAGES {
+            relay.record(&frame(
+                &format!("front/{i}"),
+                ImageFormat::Jpeg,
+                &[0xFF, 0xD8],
+            ));
+        }
+        assert!(relay.image("front/0").is_none());
+        assert!(relay.image(&format!("front/{RECENT_IMAGES}")).is_some());
+    }
+}
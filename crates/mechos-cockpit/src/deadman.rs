@@ -0,0 +1,162 @@
+//! [`DeadManSwitch`] – stops a dashboard override when its tab goes silent.
+//!
+//! While the operator holds the joystick, a tab sends its
+//! `"/cmd_vel"` override Twists plus
+//!
+//! ```json
+//! {"op":"override_heartbeat"}
+//! ```
+//!
+//! on every teleop tick, even when the stick rests at zero.  If neither
+//! arrives for the [`CockpitServer`][crate::CockpitServer]'s override
+//! timeout ([`DEFAULT_OVERRIDE_TIMEOUT`]), or the tab disconnects, the
+//! browser has crashed or lost its link: the server publishes a
+//! zero-velocity override with `"release": true`, which stops the robot and
+//! ends the AI suspension instead of letting the last Twist coast.
+//!
+//! Letting go of the stick sends `{"op":"override_end"}`, which stops the
+//! robot but keeps the usual post-override AI suspension.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use mechos_middleware::EventBus;
+use mechos_types::{Event, EventPayload};
+use serde_json::{Value, json};
+use tokio::time::Instant;
+use uuid::Uuid;
+
+/// Default silence after which a held override is released.
+pub const DEFAULT_OVERRIDE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// An upstream message that feeds the dead-man switch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverrideSignal {
+    /// An override Twist or an `override_heartbeat`: the stick is held.
+    Beat,
+    /// `override_end`: the operator let go.
+    End,
+}
+
+impl OverrideSignal {
+    /// Classify an upstream message; `None` for anything else.
+    pub fn parse(json: &Value) -> Option<Self> {
+        match json.get("op").and_then(Value::as_str) {
+            Some("override_heartbeat") => return Some(Self::Beat),
+            Some("override_end") => return Some(Self::End),
+            _ => {}
+        }
+        let is_override = json.get("topic").and_then(Value::as_str) == Some("/cmd_vel")
+            && json.get("source").and_then(Value::as_str) == Some("dashboard_override");
+        is_override.then_some(Self::Beat)
+    }
+}
+
+/// The override one tab holds, and the bus it drives.
+pub struct DeadManSwitch {
+    timeout: Duration,
+    held: Option<(Instant, Arc<EventBus>)>,
+}
+
+impl DeadManSwitch {
+    /// A released switch that trips after `timeout` of silence.
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            held: None,
+        }
+    }
+
+    /// Record a heartbeat for an override on `bus`.
+    pub fn beat(&mut self, bus: &Arc<EventBus>) {
+        self.held = Some((Instant::now(), Arc::clone(bus)));
+    }
+
+    /// When the held override trips, if nothing arrives before.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.held.as_ref().map(|(last, _)| *last + self.timeout)
+    }
+
+    /// Release the switch; returns the bus of the override that was held.
+    pub fn end(&mut self) -> Option<Arc<EventBus>> {
+        self.held.take().map(|(_, bus)| bus)
+    }
+
+    /// Release the switch if its deadline has passed by `now`.
+    pub fn expire(&mut self, now: Instant) -> Option<Arc<EventBus>> {
+        if self.deadline()? > now {
+            return None;
+        }
+        self.end()
+    }
+}
+
+/// A zero-velocity dashboard override; `release` also ends the AI
+/// suspension.
+pub fn stop_event(release: bool) -> Event {
+    let frame = json!({
+        "op": "publish",
+        "topic": "/cmd_vel",
+        "source": "dashboard_override",
+        "msg": {
+            "linear": { "x": 0.0, "y": 0.0, "z": 0.0 },
+            "angular": { "x": 0.0, "y": 0.0, "z": 0.0 }
+        },
+        "release": release,
+    });
+    Event {
+        id: Uuid::new_v4(),
+        timestamp: Utc::now(),
+        source: "mechos-middleware::dashboard_override".to_string(),
+        payload: EventPayload::adapter_command(frame),
+        trace_id: None,
+        correlation_id: None,
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn twists_and_heartbeats_keep_the_switch_held() {
+        let twist = json!({"op": "publish", "topic": "/cmd_vel", "source": "dashboard_override"});
+        assert_eq!(OverrideSignal::parse(&twist), Some(OverrideSignal::Beat));
+        let heartbeat = json!({"op": "override_heartbeat"});
+        assert_eq!(
+            OverrideSignal::parse(&heartbeat),
+            Some(OverrideSignal::Beat)
+        );
+        let end = json!({"op": "override_end"});
+        assert_eq!(OverrideSignal::parse(&end), Some(OverrideSignal::End));
+        let ai = json!({"op": "publish", "topic": "/cmd_vel"});
+        assert_eq!(OverrideSignal::parse(&ai), None);
+    }
+
+    #[test]
+    fn silence_trips_the_switch_once() {
+        let bus = Arc::new(EventBus::default());
+        let mut switch = DeadManSwitch::new(Duration::from_millis(500));
+        assert!(switch.deadline().is_none());
+
+        switch.beat(&bus);
+        let deadline = switch.deadline().unwrap();
+        assert!(switch.expire(deadline - Duration::from_millis(1)).is_none());
+        assert!(switch.expire(deadline).is_some());
+        assert!(switch.expire(deadline).is_none(), "already released");
+    }
+
+    #[test]
+    fn stop_event_is_a_zero_override() {
+        let EventPayload::AdapterCommand { json: frame, .. } = stop_event(true).payload else {
+            panic!("expected an AdapterCommand");
+        };
+        assert_eq!(frame["msg"]["linear"]["x"], 0.0);
+        assert_eq!(frame["release"], true);
+    }
+}
@@ -0,0 +1,435 @@
+//! [`EventHistory`] – recent bus events for late-joining Cockpit clients.
+//!
+//! A browser tab opened mid-mission would stay blank until the next event
+//! happened to arrive.  [`CockpitServer`][crate::CockpitServer] records every
+//! bus event here and sends each new WebSocket client a
+//! [`snapshot`][EventHistory::snapshot] before the live stream:
+//!
+//! * the last `N` events of every [`Topic`],
+//! * the latest telemetry, map snapshot, agent thought and safety status,
+//!   even once other events on their lane have pushed them out of the ring
+//!   buffer,
+//! * every pending `AskHuman` prompt, until a `HumanResponse` answers it
+//!   (by question ID, or the oldest prompt for an answer without one),
+//! * every pending `ApprovalRequest`, until an `ApprovalDecision` answers it,
+//! * the latest fault of every component.
+//!
+//! A Cockpit aggregating several robots keeps one history per robot in a
+//! [`FleetHistory`], so one robot's telemetry never hides another's.
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use mechos_middleware::Topic;
+use mechos_middleware::typed::home_topic;
+use mechos_types::{Event, EventPayload, HardwareIntent};
+
+/// Default number of events kept per topic.
+pub const DEFAULT_HISTORY_LEN: usize = 32;
+
+/// Ring buffer of recent events plus the state a fresh Cockpit tab needs.
+#[derive(Debug, Clone)]
+pub struct EventHistory {
+    per_topic: usize,
+    recent: HashMap<Topic, VecDeque<Event>>,
+    telemetry: Option<Event>,
+    map: Option<Event>,
+    thought: Option<Event>,
+    safety: Option<Event>,
+    /// Unanswered `AskHuman` prompts, oldest first.
+    ask_human: Vec<Event>,
+    approvals: BTreeMap<String, Event>,
+    faults: BTreeMap<String, Event>,
+}
+
+impl Default for EventHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_LEN)
+    }
+}
+
+impl EventHistory {
+    /// Keep the last `per_topic` events of every topic.  `0` keeps only the
+    /// latest telemetry, map, thought, safety status, pending `AskHuman`,
+    /// pending approvals and faults.
+    pub fn new(per_topic: usize) -> Self {
+        Self {
+            per_topic,
+            recent: HashMap::new(),
+            telemetry: None,
+            map: None,
+            thought: None,
+            safety: None,
+            ask_human: Vec::new(),
+            approvals: BTreeMap::new(),
+            faults: BTreeMap::new(),
+        }
+    }
+
+    /// Number of events kept per topic.
+    pub fn per_topic(&self) -> usize {
+        self.per_topic
+    }
+
+    /// Record `event`.
+    pub fn record(&mut self, event: &Event) {
+        if is_ask_human(&event.payload) {
+            // Kept out of the ring buffer so an answered prompt is never
+            // shown again.
+            self.ask_human.push(event.clone());
+            return;
+        }
+        match &event.payload {
+            EventPayload::ApprovalRequest { request_id, .. } => {
+                // Kept out of the ring buffer so a decided request is never
+                // shown again.
+                self.approvals.insert(request_id.clone(), event.clone());
+                return;
+            }
+            EventPayload::ApprovalDecision { request_id, .. } => {
+                self.approvals.remove(request_id);
+            }
+            EventPayload::Telemetry(_) => self.telemetry = Some(event.clone()),
+            EventPayload::MapSnapshot { .. } => self.map = Some(event.clone()),
+            EventPayload::Reasoning(_) => self.thought = Some(event.clone()),
+            EventPayload::SafetyStatus { .. } => self.safety = Some(event.clone()),
+            EventPayload::HumanResponse(answer) => {
+                let answered = match &answer.question_id {
+                    Some(id) => self
+                        .ask_human
+                        .iter()
+                        .position(|ask| question_id(&ask.payload) == Some(id.as_str())),
+                    None => (!self.ask_human.is_empty()).then_some(0),
+                };
+                if let Some(index) = answered {
+                    self.ask_human.remove(index);
+                }
+            }
+            EventPayload::HardwareFault { component, .. } => {
+                self.faults.insert(component.clone(), event.clone());
+            }
+            _ => {}
+        }
+        if self.per_topic == 0 {
+            return;
+        }
+        let recent = self.recent.entry(home_topic(&event.payload)).or_default();
+        if recent.len() == self.per_topic {
+            recent.pop_front();
+        }
+        recent.push_back(event.clone());
+    }
+
+    /// `AskHuman` prompts no `HumanResponse` has answered yet, oldest
+    /// first.
+    pub fn pending_ask_human(&self) -> impl Iterator<Item = &Event> {
+        self.ask_human.iter()
+    }
+
+    /// `ApprovalRequest`s no `ApprovalDecision` has answered yet.
+    pub fn pending_approvals(&self) -> impl Iterator<Item = &Event> {
+        self.approvals.values()
+    }
+
+    /// Every retained event, oldest first, each once.
+    pub fn snapshot(&self) -> Vec<Event> {
+        let mut seen = HashSet::new();
+        let mut events: Vec<Event> = self
+            .recent
+            .values()
+            .flatten()
+            .chain(&self.telemetry)
+            .chain(&self.map)
+            .chain(&self.thought)
+            .chain(&self.safety)
+            .chain(self.faults.values())
+            .chain(&self.ask_human)
+            .chain(self.approvals.values())
+            .filter(|event| seen.insert(event.id))
+            .cloned()
+            .collect();
+        events.sort_by_key(|event| event.timestamp);
+        events
+    }
+}
+
+/// One [`EventHistory`] per robot of a multi-robot Cockpit.
+#[derive(Debug, Clone)]
+pub struct FleetHistory {
+    per_topic: usize,
+    robots: BTreeMap<String, EventHistory>,
+}
+
+impl Default for FleetHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_LEN)
+    }
+}
+
+impl FleetHistory {
+    /// Keep the last `per_topic` events of every topic of every robot.
+    pub fn new(per_topic: usize) -> Self {
+        Self {
+            per_topic,
+            robots: BTreeMap::new(),
+        }
+    }
+
+    /// Record `event` from `robot_id`'s bus.
+    pub fn record(&mut self, robot_id: &str, event: &Event) {
+        match self.robots.get_mut(robot_id) {
+            Some(history) => history.record(event),
+            None => {
+                let mut history = EventHistory::new(self.per_topic);
+                history.record(event);
+                self.robots.insert(robot_id.to_string(), history);
+            }
+        }
+    }
+
+    /// The history of `robot_id`, once it has published anything.
+    pub fn robot(&self, robot_id: &str) -> Option<&EventHistory> {
+        self.robots.get(robot_id)
+    }
+
+    /// Every robot's retained events, oldest first, each with its robot ID.
+    pub fn snapshot(&self) -> Vec<(String, Event)> {
+        let mut events: Vec<(String, Event)> = self
+            .robots
+            .iter()
+            .flat_map(|(robot_id, history)| {
+                history
+                    .snapshot()
+                    .into_iter()
+                    .map(move |event| (robot_id.clone(), event))
+            })
+            .collect();
+        events.sort_by_key(|(_, event)| event.timestamp);
+        events
+    }
+}
+
+/// `true` for an `AskHuman` intent, whether it travels as an approved
+/// [`EventPayload::Intent`] or as a rosbridge-style `/hitl/ask_human`
+/// [`EventPayload::AdapterCommand`].
+fn is_ask_human(payload: &EventPayload) -> bool {
+    match payload {
+        EventPayload::Intent(envelope) => {
+            matches!(envelope.intent, HardwareIntent::AskHuman { .. })
+        }
+        EventPayload::AdapterCommand { topic, .. } => topic == "/hitl/ask_human",
+        _ => false,
+    }
+}
+
+/// The `question_id` of an `AskHuman` payload, if it carries one.
+fn question_id(payload: &EventPayload) -> Option<&str> {
+    match payload {
+        EventPayload::Intent(envelope) => match &envelope.intent {
+            HardwareIntent::AskHuman { question_id, .. } => question_id.as_deref(),
+            _ => None,
+        },
+        EventPayload::AdapterCommand { json, .. } => json["msg"]["question_id"].as_str(),
+        _ => None,
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use mechos_types::{FaultCode, HumanAnswer, IntentEnvelope, Meters, TelemetryData};
+    use uuid::Uuid;
+
+    fn event(seconds: i64, payload: EventPayload) -> Event {
+        Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now() + Duration::seconds(seconds),
+            source: "test".to_string(),
+            payload,
+            trace_id: None,
+            correlation_id: None,
+        }
+    }
+
+    fn telemetry(x: f32) -> EventPayload {
+        EventPayload::Telemetry(TelemetryData {
+            position_x: Meters(x),
+            position_y: Meters(0.0),
+            heading_rad: 0.0,
+            battery_percent: 90,
+            stamp: None,
+        })
+    }
+
+    fn fault(component: &str, message: &str) -> EventPayload {
+        EventPayload::HardwareFault {
+            component: component.to_string(),
+            code: FaultCode::StaleSensor,
+            message: message.to_string(),
+        }
+    }
+
+    fn ask_human(question_id: Option<&str>) -> EventPayload {
+        EventPayload::Intent(IntentEnvelope::new(
+            HardwareIntent::AskHuman {
+                question: "Push the box?".to_string(),
+                context_image_id: None,
+                question_id: question_id.map(str::to_string),
+            },
+            "agent",
+        ))
+    }
+
+    #[test]
+    fn ring_buffer_keeps_last_events_per_topic() {
+        let mut history = EventHistory::new(2);
+        let thought = event(0, EventPayload::Reasoning("plan".to_string()));
+        history.record(&thought);
+        for i in 1..=3 {
+            history.record(&event(i, telemetry(i as f32)));
+        }
+
+        let snapshot = history.snapshot();
+        assert_eq!(snapshot.len(), 3);
+        assert_eq!(snapshot[0].id, thought.id);
+        assert!(
+            snapshot[1..]
+                .iter()
+                .all(|e| matches!(e.payload, EventPayload::Telemetry(_)))
+        );
+    }
+
+    #[test]
+    fn latest_state_survives_ring_buffer_eviction() {
+        let mut history = EventHistory::new(1);
+        let position = event(0, telemetry(1.0));
+        history.record(&position);
+        history.record(&event(1, fault("lidar", "no scans")));
+        history.record(&event(2, fault("lidar", "still no scans")));
+        history.record(&event(3, fault("camera", "no frames")));
+        history.record(&event(
+            4,
+            EventPayload::LidarScan {
+                ranges: vec![1.0],
+                angle_min_rad: 0.0,
+                angle_increment_rad: 0.1,
+                stamp: None,
+            },
+        ));
+
+        let snapshot = history.snapshot();
+        assert_eq!(snapshot[0].id, position.id);
+        let faults: Vec<_> = snapshot
+            .iter()
+            .filter_map(|e| match &e.payload {
+                EventPayload::HardwareFault { message, .. } => Some(message.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(faults, ["still no scans", "no frames"]);
+    }
+
+    #[test]
+    fn human_response_clears_pending_ask_human() {
+        let mut history = EventHistory::default();
+        history.record(&event(0, ask_human(None)));
+        assert_eq!(history.pending_ask_human().count(), 1);
+        assert!(history.snapshot().iter().any(|e| is_ask_human(&e.payload)));
+
+        history.record(&event(1, EventPayload::HumanResponse(HumanAnswer::new("yes"))));
+        assert_eq!(history.pending_ask_human().count(), 0);
+        assert!(!history.snapshot().iter().any(|e| is_ask_human(&e.payload)));
+    }
+
+    #[test]
+    fn answers_clear_the_question_they_name() {
+        let mut history = EventHistory::default();
+        history.record(&event(0, ask_human(Some("q-1"))));
+        history.record(&event(1, ask_human(Some("q-2"))));
+        history.record(&event(2, ask_human(Some("q-3"))));
+
+        history.record(&event(3, HumanAnswer::to_question("q-2", "left").into()));
+        history.record(&event(4, HumanAnswer::to_question("q-9", "other robot").into()));
+        let pending: Vec<_> = history
+            .pending_ask_human()
+            .filter_map(|e| question_id(&e.payload))
+            .collect();
+        assert_eq!(pending, ["q-1", "q-3"]);
+
+        history.record(&event(5, HumanAnswer::new("yes").into()));
+        let pending: Vec<_> = history
+            .pending_ask_human()
+            .filter_map(|e| question_id(&e.payload))
+            .collect();
+        assert_eq!(pending, ["q-3"], "an answer without an ID takes the oldest question");
+    }
+
+    #[test]
+    fn approval_requests_stay_until_decided() {
+        let mut history = EventHistory::new(0);
+        let request = |request_id: &str| EventPayload::ApprovalRequest {
+            request_id: request_id.to_string(),
+            envelope: Box::new(IntentEnvelope::new(HardwareIntent::Dock, "agent")),
+            report: Vec::new(),
+        };
+        history.record(&event(0, request("a1")));
+        history.record(&event(1, request("a2")));
+        history.record(&event(
+            2,
+            EventPayload::ApprovalDecision {
+                request_id: "a1".to_string(),
+                approved: true,
+                operator: "alice".to_string(),
+                reason: None,
+            },
+        ));
+
+        let pending: Vec<_> = history
+            .pending_approvals()
+            .filter_map(|e| match &e.payload {
+                EventPayload::ApprovalRequest { request_id, .. } => Some(request_id.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(pending, ["a2"]);
+        assert_eq!(history.snapshot().len(), 1);
+    }
+
+    #[test]
+    fn fleet_history_keeps_each_robots_latest_state() {
+        let mut fleet = FleetHistory::new(0);
+        let a = event(0, telemetry(1.0));
+        let b = event(1, telemetry(2.0));
+        fleet.record("rover-a", &a);
+        fleet.record("rover-b", &b);
+
+        let snapshot = fleet.snapshot();
+        assert_eq!(
+            snapshot.len(),
+            2,
+            "rover-b's telemetry does not replace rover-a's"
+        );
+        assert_eq!(
+            (snapshot[0].0.as_str(), snapshot[0].1.id),
+            ("rover-a", a.id)
+        );
+        assert_eq!(
+            (snapshot[1].0.as_str(), snapshot[1].1.id),
+            ("rover-b", b.id)
+        );
+        assert!(fleet.robot("rover-c").is_none());
+    }
+
+    #[test]
+    fn rosbridge_style_command_counts_as_ask_human() {
+        let frame = serde_json::json!({"topic": "/hitl/ask_human", "msg": {"question": "Which door?"}});
+        assert!(is_ask_human(&EventPayload::adapter_command(frame)));
+        assert!(!is_ask_human(&EventPayload::Reasoning(
+            "plan".to_string()
+        )));
+    }
+}
@@ -1,22 +1,63 @@
 //! `mechos-cockpit` – The Observability & Teleop Web UI Server
 //!
-//! Boots a lightweight HTTP + WebSocket server (default port `8080`) that:
+//! Boots a lightweight HTTP + WebSocket server (default `127.0.0.1:8080`)
+//! that:
 //!
-//! 1. **Serves** the static Cockpit Single-Page Application (HTML/CSS/JS)
-//!    at every non-WebSocket HTTP path.
+//! 1. **Serves** the static Cockpit Single-Page Application (HTML/CSS/JS,
+//!    icons, fonts) embedded from `assets/`, gzip-compressed and with cache
+//!    validators, at every non-WebSocket HTTP path (see [`StaticAsset`]).
 //!
 //! 2. **Bridges** the internal [`EventBus`] to every connected browser tab
 //!    over a persistent WebSocket connection so that [`TelemetryData`],
-//!    [`AgentThought`], [`LidarScan`], and [`AskHuman`] events stream to the
-//!    UI in real-time.
+//!    [`Reasoning`], [`LidarScan`], and [`AskHuman`] events stream to the
+//!    UI in real-time.  A newly connected tab first receives a snapshot of
+//!    recent events (latest telemetry, agent thought, pending `AskHuman` and
+//!    approvals, active faults) from the server's [`EventHistory`].
 //!
-//! 3. **Accepts** upstream messages from the browser:
+//!    With several robots added via [`CockpitServer::with_robot`], every
+//!    event carries the `robot_id` of the bus it came from, so one tab
+//!    watches the whole fleet.
+//!
+//! 3. **Relays** [`CameraFrame`] events as per-camera MJPEG streams and
+//!    serves recent frames by image ID, so an `AskHuman` prompt's
+//!    `context_image_id` shows the operator what the robot sees (see
+//!    [`CameraRelay`]).
+//!
+//! 4. **Accepts** upstream messages from the browser:
 //!    - `"/cmd_vel"` with `source: "dashboard_override"` → arms the
 //!      10-second AI suspension and forwards a `Drive` command.
+//!    - `{"op":"override_heartbeat"}` / `{"op":"override_end"}` → keep a
+//!      held joystick alive or let it go; a tab that goes silent mid-drive
+//!      has its robot stopped and the override released (see
+//!      [`DeadManSwitch`]).
 //!    - `"/hitl/human_response"` → publishes an
 //!      [`EventPayload::HumanResponse`] so the [`AgentLoop`] can resume.
+//!      With [`CockpitServer::with_operator_token`], answers and approval
+//!      decisions must carry an operator token and are stamped with its
+//!      owner (see [`OperatorTokens`]).
 //!    - `"/agent/mode"` → publishes an [`EventPayload::AgentModeToggle`] to
 //!      pause or resume the autonomous loop independently of the joystick.
+//!    - `{"op":"subscribe",…}` → limits the tab to the listed topics, with
+//!      optional per-topic rate limits (see [`ClientSubscription`]).
+//!    - `{"op":"select_robot",…}` → directs the tab's commands to another
+//!      robot added with [`CockpitServer::with_robot`].
+//!    - `"/safety/estop"`, `"/safety/reset"`, `"/safety/mode"` → drive the
+//!      kernel's e-stop latch and safety mode, each confirmed back to the
+//!      tab with a `safety_ack` frame (see [`SafetyMessage`]).
+//!    - `{"op":"approve",…}` / `{"op":"reject",…}` → answer an intent held
+//!      for operator approval, naming the operator (see [`ApprovalMessage`]).
+//!
+//! 5. **Manages** the kernel's safety policy at `GET` / `POST /api/policy`
+//!    for requests bearing the admin token set with
+//!    [`CockpitServer::with_admin_token`]: lists every rule and capability
+//!    grant, switches rules between blocking and warning and adjusts their
+//!    parameters, each change validated and audited by the kernel (see
+//!    [`PolicyMessage`]).
+//!
+//! 6. **Shows** the newest captured `tracing` events at `GET /api/logs`,
+//!    filtered by `level`, `target` and `limit`, so gate rejections and
+//!    adapter reconnects can be read without a shell on the robot (see
+//!    [`CockpitServer::with_log_buffer`]).
 //!
 //! # Usage
 //!
@@ -36,13 +77,34 @@
 //! ```
 //!
 //! [`TelemetryData`]: mechos_types::TelemetryData
-//! [`AgentThought`]: mechos_types::EventPayload::AgentThought
+//! [`Reasoning`]: mechos_types::EventPayload::Reasoning
 //! [`LidarScan`]: mechos_types::EventPayload::LidarScan
+//! [`CameraFrame`]: mechos_types::EventPayload::CameraFrame
 //! [`AskHuman`]: mechos_types::HardwareIntent::AskHuman
 //! [`EventPayload::HumanResponse`]: mechos_types::EventPayload::HumanResponse
 //! [`EventPayload::AgentModeToggle`]: mechos_types::EventPayload::AgentModeToggle
 //! [`AgentLoop`]: mechos_runtime::AgentLoop
 
+pub mod approval;
+pub mod assets;
+pub mod camera_relay;
+pub mod deadman;
+pub mod history;
+pub mod operator;
+pub mod policy;
+pub mod safety;
 pub mod server;
+pub mod subscription;
 
-pub use server::{CockpitServer, DEFAULT_PORT};
+pub use approval::ApprovalMessage;
+pub use assets::StaticAsset;
+pub use camera_relay::CameraRelay;
+pub use deadman::{DEFAULT_OVERRIDE_TIMEOUT, DeadManSwitch, OverrideSignal};
+pub use history::{DEFAULT_HISTORY_LEN, EventHistory, FleetHistory};
+pub use operator::OperatorTokens;
+pub use policy::{POLICY_REPLY_TIMEOUT, PolicyMessage};
+pub use safety::SafetyMessage;
+pub use server::{
+    CockpitServer, DEFAULT_BIND_ADDRESS, DEFAULT_PORT, LOCAL_ROBOT, SHUTDOWN_DRAIN_TIMEOUT,
+};
+pub use subscription::ClientSubscription;
@@ -0,0 +1,162 @@
+//! [`OperatorTokens`] – who is answering for the robot.
+//!
+//! HITL answers and approval decisions name the operator who gave them, and
+//! an agent loop with operator grants only honours operators holding
+//! [`Capability::AnswerQuestions`] or [`Capability::ApproveIntents`].
+//! Without tokens the name is whatever the tab claims.  With tokens
+//! registered by
+//! [`CockpitServer::with_operator_token`][crate::CockpitServer::with_operator_token],
+//! a tab proves who it is by sending its token with each answer or
+//! decision:
+//!
+//! ```json
+//! {"op":"approve","id":"<request_id>","operator":"alice","token":"<token>"}
+//! {"op":"publish","topic":"/hitl/human_response","msg":{"response":"Yes","question_id":"q-1"},"token":"<token>"}
+//! ```
+//!
+//! and the server replaces the claimed name with the token's owner.  A
+//! missing or unknown token is refused with an `approval_nack` or
+//! `hitl_nack` frame, so no tab can impersonate another operator.
+//!
+//! When the agent loop checks operator grants, claimed names must never be
+//! trusted: [`OperatorTokens::require`] refuses every answer and decision
+//! without a registered token, even before any token is registered.
+//!
+//! [`Capability::AnswerQuestions`]: mechos_types::Capability::AnswerQuestions
+//! [`Capability::ApproveIntents`]: mechos_types::Capability::ApproveIntents
+
+use serde_json::Value;
+
+use crate::policy::constant_time_eq;
+
+/// Operator identities by the tokens their tabs authenticate with.
+#[derive(Debug, Clone, Default)]
+pub struct OperatorTokens {
+    /// `(token, operator)` pairs.
+    tokens: Vec<(String, String)>,
+    /// Refuse claimed names even when no token is registered.
+    required: bool,
+}
+
+impl OperatorTokens {
+    /// No tokens: every tab names its own operator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Let tabs sending `token` act as `operator`.  Replaces the operator
+    /// of a token registered before.
+    pub fn insert(&mut self, token: impl Into<String>, operator: impl Into<String>) {
+        let token = token.into();
+        self.tokens.retain(|(known, _)| *known != token);
+        self.tokens.push((token, operator.into()));
+    }
+
+    /// Refuse every answer and decision without a registered token, so a
+    /// tab can never act under a name it merely claims.
+    pub fn require(&mut self) {
+        self.required = true;
+    }
+
+    /// `true` when no token is registered.
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// The operator an upstream message acts for: the owner of its `token`
+    /// when tokens are registered or [required][Self::require], otherwise
+    /// the `operator` it claims, if any.
+    ///
+    /// # Errors
+    ///
+    /// The reason to send back when tokens are registered or required and
+    /// the message carries none of them.
+    pub fn operator(&self, json: &Value) -> Result<Option<String>, &'static str> {
+        let field = |name| {
+            json.get(name)
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+        if self.is_empty() && !self.required {
+            return Ok(field("operator").map(str::to_string));
+        }
+        let Some(given) = field("token") else {
+            return Err("missing operator token");
+        };
+        // Compare against every token so the time taken does not reveal
+        // which one matched.
+        self.tokens
+            .iter()
+            .fold(None, |owner, (token, operator)| {
+                if constant_time_eq(given.as_bytes(), token.as_bytes()) {
+                    Some(operator.clone())
+                } else {
+                    owner
+                }
+            })
+            .map(Some)
+            .ok_or(if self.is_empty() {
+                "no operator tokens are configured"
+            } else {
+                "unknown operator token"
+            })
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn without_tokens_tabs_name_themselves() {
+        let tokens = OperatorTokens::new();
+        assert_eq!(tokens.operator(&json!({"operator": " alice "})), Ok(Some("alice".to_string())));
+        assert_eq!(tokens.operator(&json!({"op": "publish"})), Ok(None));
+    }
+
+    #[test]
+    fn tokens_override_the_claimed_operator() {
+        let mut tokens = OperatorTokens::new();
+        tokens.insert("t-alice", "alice");
+        tokens.insert("t-bob", "bob");
+        tokens.insert("t-bob", "robert");
+        assert_eq!(
+            tokens.operator(&json!({"operator": "alice", "token": "t-bob"})),
+            Ok(Some("robert".to_string()))
+        );
+        assert_eq!(
+            tokens.operator(&json!({"operator": "alice"})),
+            Err("missing operator token")
+        );
+        assert_eq!(
+            tokens.operator(&json!({"operator": "alice", "token": "t-alic"})),
+            Err("unknown operator token")
+        );
+    }
+
+    #[test]
+    fn required_tokens_refuse_claimed_names() {
+        let mut tokens = OperatorTokens::new();
+        tokens.require();
+        assert_eq!(
+            tokens.operator(&json!({"operator": "alice"})),
+            Err("missing operator token")
+        );
+        assert_eq!(
+            tokens.operator(&json!({"operator": "alice", "token": "anything"})),
+            Err("no operator tokens are configured")
+        );
+
+        tokens.insert("t-alice", "alice");
+        assert_eq!(
+            tokens.operator(&json!({"token": "t-alice"})),
+            Ok(Some("alice".to_string()))
+        );
+    }
+}
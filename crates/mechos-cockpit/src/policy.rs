@@ -0,0 +1,144 @@
+//! [`PolicyMessage`] – the authenticated safety-policy endpoint.
+//!
+//! With an admin token set by
+//! [`CockpitServer::with_admin_token`][crate::CockpitServer::with_admin_token],
+//! operators manage the kernel's safety policy over HTTP:
+//!
+//! ```text
+//! GET  /api/policy[?robot=<id>]
+//! POST /api/policy[?robot=<id>]
+//! Authorization: Bearer <token>
+//!
+//! {"operator":"alice","command":{"set_rule_mode":{"rule":"geofence","mode":"warn"}}}
+//! {"operator":"alice","command":{"set_rule_param":{"rule":"speed_cap","param":"max_linear","value":0.5}}}
+//! ```
+//!
+//! Both are published on the robot's bus as an
+//! [`EventPayload::PolicyRequest`]; the agent loop applies the change to its
+//! kernel gate, which validates it and writes an audit-log entry.  The
+//! answering [`EventPayload::PolicyStatus`] is returned as the JSON
+//! [`PolicyReport`][mechos_types::PolicyReport] (every rule with its mode
+//! and parameters, every capability grant, the audit log), with status `422`
+//! and an `"error"` field when the kernel refused the change.
+//!
+//! Without an admin token the endpoint answers `403`; a missing or wrong
+//! bearer token gets `401`.
+
+use std::time::Duration;
+
+use chrono::Utc;
+use mechos_types::{Event, EventPayload, PolicyCommand};
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// How long the endpoint waits for the agent loop's
+/// [`EventPayload::PolicyStatus`] before answering `504`.
+pub const POLICY_REPLY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A parsed `POST /api/policy` body.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PolicyMessage {
+    /// Who made the change, for the kernel's audit log.
+    pub operator: String,
+    pub command: PolicyCommand,
+}
+
+impl PolicyMessage {
+    /// The `GET /api/policy` request: change nothing, report everything.
+    pub fn list() -> Self {
+        Self {
+            operator: String::new(),
+            command: PolicyCommand::List,
+        }
+    }
+
+    /// Parse a `POST /api/policy` body; the error is returned to the client
+    /// as a `400`.
+    pub fn parse(body: &[u8]) -> Result<Self, String> {
+        let message: Self =
+            serde_json::from_slice(body).map_err(|e| format!("invalid policy request: {e}"))?;
+        if message.operator.trim().is_empty() {
+            return Err("missing operator identity".to_string());
+        }
+        Ok(message)
+    }
+
+    /// The bus event carrying this request.
+    pub fn to_event(&self, request_id: &str) -> Event {
+        Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: "mechos-cockpit::server".to_string(),
+            payload: EventPayload::PolicyRequest {
+                request_id: request_id.to_string(),
+                operator: self.operator.trim().to_string(),
+                command: self.command.clone(),
+            },
+            trace_id: None,
+            correlation_id: None,
+        }
+    }
+}
+
+/// `true` when the request `head` carries `Authorization: Bearer <token>`.
+///
+/// The token is compared in constant time.
+pub fn authorized(head: &str, token: &str) -> bool {
+    head.lines()
+        .filter_map(|line| line.split_once(':'))
+        .filter(|(name, _)| name.trim().eq_ignore_ascii_case("authorization"))
+        .filter_map(|(_, value)| value.trim().strip_prefix("Bearer "))
+        .any(|given| constant_time_eq(given.trim().as_bytes(), token.as_bytes()))
+}
+
+/// `true` when `given` equals `secret`, in time independent of where they
+/// differ.
+pub(crate) fn constant_time_eq(given: &[u8], secret: &[u8]) -> bool {
+    given.len() == secret.len()
+        && given
+            .iter()
+            .zip(secret)
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mechos_types::RuleMode;
+
+    #[test]
+    fn bearer_token_must_match() {
+        let head = "POST /api/policy HTTP/1.1\r\nauthorization: Bearer s3cret\r\n\r\n";
+        assert!(authorized(head, "s3cret"));
+        assert!(!authorized(head, "s3cre"));
+        assert!(!authorized(head, "other!"));
+        assert!(!authorized("GET /api/policy HTTP/1.1\r\n\r\n", "s3cret"));
+    }
+
+    #[test]
+    fn changes_name_their_operator() {
+        let message = PolicyMessage::parse(
+            br#"{"operator":"alice","command":{"set_rule_mode":{"rule":"geofence","mode":"warn"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            message.command,
+            PolicyCommand::SetRuleMode {
+                rule: "geofence".to_string(),
+                mode: RuleMode::Warn,
+            }
+        );
+        assert!(matches!(
+            message.to_event("p1").payload,
+            EventPayload::PolicyRequest { ref operator, .. } if operator == "alice"
+        ));
+
+        assert!(PolicyMessage::parse(br#"{"operator":" ","command":"list"}"#).is_err());
+        assert!(PolicyMessage::parse(br#"{"operator":"alice","command":"drop_all"}"#).is_err());
+    }
+}
@@ -0,0 +1,169 @@
+//! [`SafetyMessage`] – e-stop and safety-mode controls from a Cockpit tab.
+//!
+//! Three upstream WebSocket messages drive the kernel's e-stop latch and
+//! safety mode (`mechos_kernel::SafetyState`) through the agent loop:
+//!
+//! ```json
+//! {"topic":"/safety/estop","msg":{"id":"7f3c"}}
+//! {"topic":"/safety/reset","msg":{"id":"7f3d"}}
+//! {"topic":"/safety/mode","msg":{"id":"7f3e","mode":"reduced"}}
+//! ```
+//!
+//! Each is published as an [`EventPayload::SafetyRequest`].  Once an agent
+//! loop confirms it with an [`EventPayload::SafetyStatus`] echoing the `id`,
+//! the sending tab receives
+//!
+//! ```json
+//! {"op":"safety_ack","id":"7f3c","estopped":true,"mode":"normal"}
+//! ```
+//!
+//! A tab resends the same message, with the same `id`, until the ack
+//! arrives; repeating a command is harmless, so a dropped frame shows up as
+//! an unconfirmed stop rather than silently failing to stop the robot.  A
+//! message the server cannot act on (an unknown mode) is answered with
+//! `{"op":"safety_nack","id":…,"error":…}` instead.
+
+use chrono::Utc;
+use mechos_types::{Event, EventPayload, SafetyCommand, SafetyMode};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+/// A parsed `/safety/*` upstream message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SafetyMessage {
+    /// The client's `msg.id`, or a generated one when it sent none (an
+    /// e-stop is never dropped for lack of an ID).
+    pub request_id: String,
+    pub command: SafetyCommand,
+}
+
+impl SafetyMessage {
+    /// Parse a `/safety/*` message; `None` for any other topic, and the
+    /// `safety_nack` frame to send back for an unusable one.
+    pub fn parse(json: &Value) -> Option<Result<Self, Value>> {
+        let topic = json.get("topic").and_then(Value::as_str)?;
+        let msg = json.get("msg");
+        let request_id = msg
+            .and_then(|m| m.get("id"))
+            .and_then(Value::as_str)
+            .map_or_else(|| Uuid::new_v4().to_string(), str::to_string);
+        let command = match topic {
+            "/safety/estop" => SafetyCommand::EStop,
+            "/safety/reset" => SafetyCommand::Reset,
+            "/safety/mode" => {
+                let mode = msg.and_then(|m| m.get("mode")).cloned().unwrap_or_default();
+                match serde_json::from_value::<SafetyMode>(mode) {
+                    Ok(mode) => SafetyCommand::SetMode(mode),
+                    Err(e) => {
+                        return Some(Err(json!({
+                            "op": "safety_nack",
+                            "id": request_id,
+                            "error": format!("invalid safety mode: {e}"),
+                        })));
+                    }
+                }
+            }
+            _ => return None,
+        };
+        Some(Ok(Self {
+            request_id,
+            command,
+        }))
+    }
+
+    /// The bus event carrying this request.
+    pub fn to_event(&self) -> Event {
+        Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: "mechos-cockpit::server".to_string(),
+            payload: EventPayload::SafetyRequest {
+                request_id: self.request_id.clone(),
+                command: self.command,
+            },
+            trace_id: None,
+            correlation_id: None,
+        }
+    }
+}
+
+/// The `safety_ack` frame for a [`EventPayload::SafetyStatus`] confirming a
+/// request; `None` for any other payload or an unsolicited status.
+pub fn safety_ack(payload: &EventPayload) -> Option<(&str, Value)> {
+    let EventPayload::SafetyStatus {
+        request_id: Some(request_id),
+        estopped,
+        mode,
+    } = payload
+    else {
+        return None;
+    };
+    Some((
+        request_id,
+        json!({
+            "op": "safety_ack",
+            "id": request_id,
+            "estopped": estopped,
+            "mode": mode,
+        }),
+    ))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Option<Result<SafetyMessage, Value>> {
+        SafetyMessage::parse(&serde_json::from_str(text).unwrap())
+    }
+
+    #[test]
+    fn safety_topics_map_to_commands() {
+        let estop = parse(r#"{"topic":"/safety/estop","msg":{"id":"a"}}"#)
+            .unwrap()
+            .unwrap();
+        assert_eq!(estop.request_id, "a");
+        assert_eq!(estop.command, SafetyCommand::EStop);
+        let mode = parse(r#"{"topic":"/safety/mode","msg":{"id":"b","mode":"reduced"}}"#)
+            .unwrap()
+            .unwrap();
+        assert_eq!(mode.command, SafetyCommand::SetMode(SafetyMode::Reduced));
+        assert!(parse(r#"{"topic":"/agent/mode","msg":{"paused":true}}"#).is_none());
+    }
+
+    #[test]
+    fn estop_without_id_still_goes_through() {
+        let estop = parse(r#"{"topic":"/safety/estop"}"#).unwrap().unwrap();
+        assert!(!estop.request_id.is_empty());
+    }
+
+    #[test]
+    fn unknown_mode_is_nacked() {
+        let nack = parse(r#"{"topic":"/safety/mode","msg":{"id":"c","mode":"turbo"}}"#)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(nack["op"], "safety_nack");
+        assert_eq!(nack["id"], "c");
+    }
+
+    #[test]
+    fn only_solicited_status_is_acked() {
+        let status = |request_id: Option<&str>| EventPayload::SafetyStatus {
+            request_id: request_id.map(str::to_string),
+            estopped: true,
+            mode: SafetyMode::Normal,
+        };
+        let confirmed = status(Some("a"));
+        let (id, ack) = safety_ack(&confirmed).unwrap();
+        assert_eq!(id, "a");
+        assert_eq!(
+            ack,
+            json!({"op": "safety_ack", "id": "a", "estopped": true, "mode": "normal"})
+        );
+        assert!(safety_ack(&status(None)).is_none());
+    }
+}
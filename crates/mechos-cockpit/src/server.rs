@@ -1,29 +1,94 @@
 //! [`CockpitServer`] – HTTP + WebSocket server for the Cockpit UI.
 //!
-//! Listens on `0.0.0.0:8080` (configurable via [`CockpitServer::with_port`]).
+//! Listens on `127.0.0.1:8080` (configurable via
+//! [`CockpitServer::with_bind_address`] and [`CockpitServer::with_port`]).
 //!
-//! * Regular HTTP requests → 200 OK with the embedded Cockpit HTML.
-//! * WebSocket upgrades → bidirectional bridge to the [`EventBus`].
+//! * Regular HTTP requests → the embedded Cockpit frontend bundle, with
+//!   client-side routes falling back to its `index.html` (see
+//!   [`StaticAsset`]).
+//! * WebSocket upgrades → bidirectional bridge to the [`EventBus`], opened
+//!   with a snapshot of the server's [`EventHistory`] so a late-joining tab
+//!   is not blank until the next event arrives.
+//! * `GET /cameras`, `GET /cameras/{camera}/mjpeg` and `GET /images/{id}` →
+//!   bus camera frames from the [`CameraRelay`].
+//! * `GET /api/logs[?level=warn&target=kernel&limit=200]` → the newest
+//!   captured `tracing` events from the [`LogBuffer`], as JSON.
+//!
+//! With [`CockpitServer::with_robot`] one server aggregates several robots'
+//! buses: every event sent to a tab carries the `robot_id` of the bus it
+//! came from, and a tab picks the robot its teleop, HITL and safety
+//! commands go to with `{"op":"select_robot","robot_id":"rover-b"}`.  The
+//! server lists the robots on connect and after each selection:
+//!
+//! ```json
+//! {"op":"robots","robots":["rover-a","rover-b"],"local":"rover-a","selected":"rover-b"}
+//! ```
+//!
+//! With [`CockpitServer::with_operator_token`] every HITL answer and
+//! approval decision must carry a registered operator token, and is
+//! published under the token owner's identity (see [`OperatorTokens`]).
+//!
+//! With [`CockpitServer::with_tls`] both are served over TLS (`https://` /
+//! `wss://`), so teleop commands and human responses are not plaintext on
+//! the shop-floor network.
+//!
+//! Cancelling the token passed to [`CockpitServer::with_shutdown`] stops the
+//! server: the listener is closed, every WebSocket tab is sent a close frame
+//! and [`run`][CockpitServer::run] returns once the open connections have
+//! finished, or after [`SHUTDOWN_DRAIN_TIMEOUT`].
 
-use std::net::SocketAddr;
-use std::sync::Arc;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use futures_util::{SinkExt, StreamExt};
-use mechos_middleware::EventBus;
-use mechos_types::{Event, EventPayload, MechError};
+use mechos_middleware::log_buffer::LogFilter;
+use mechos_middleware::{EventBus, LogBuffer, TlsConfig};
+use mechos_types::{Event, EventPayload, HumanAnswer, MechError};
 use serde_json::Value;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
 use tracing::{error, info, warn};
-use tokio::net::{TcpListener, TcpStream};
+use tokio::net::TcpListener;
+use tokio::task::JoinSet;
 use tokio_tungstenite::{accept_async_with_config, tungstenite::{Message, protocol::WebSocketConfig}};
+use tokio_tungstenite::tungstenite::protocol::{CloseFrame, frame::coding::CloseCode};
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 use chrono::Utc;
 
+use crate::approval::ApprovalMessage;
+use crate::assets::{StaticAsset, accepts_gzip, not_modified};
+use crate::camera_relay::{CameraRelay, CameraUpdate};
+use crate::deadman::{DEFAULT_OVERRIDE_TIMEOUT, DeadManSwitch, OverrideSignal, stop_event};
+use crate::history::{DEFAULT_HISTORY_LEN, EventHistory, FleetHistory};
+use crate::operator::OperatorTokens;
+use crate::policy::{POLICY_REPLY_TIMEOUT, PolicyMessage, authorized};
+use crate::safety::{SafetyMessage, safety_ack};
+use crate::subscription::ClientSubscription;
+
 /// Default TCP port for the Cockpit HTTP/WebSocket server.
 pub const DEFAULT_PORT: u16 = 8080;
 
-/// The compiled-in Cockpit single-page application (HTML + CSS + JS).
-const COCKPIT_HTML: &str = include_str!("cockpit.html");
+/// Default address the server listens on: loopback only, so the Cockpit is
+/// not exposed to the network unless asked with
+/// [`CockpitServer::with_bind_address`].
+pub const DEFAULT_BIND_ADDRESS: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+/// How long [`CockpitServer::run`] waits for open connections to finish
+/// after shutdown before dropping them.
+pub const SHUTDOWN_DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Robot ID of the bus passed to [`CockpitServer::new`], unless renamed
+/// with [`CockpitServer::with_robot_id`].
+pub const LOCAL_ROBOT: &str = "local";
+
+/// Records `GET /api/logs` returns when the request sets no `limit`.
+const DEFAULT_LOGS_LIMIT: usize = 200;
+
+/// Capacity of the channel merging every robot's bus.
+const FLEET_CHANNEL_CAPACITY: usize = 1024;
 
 // ---------------------------------------------------------------------------
 // CockpitServer
@@ -50,10 +115,29 @@ const COCKPIT_HTML: &str = include_str!("cockpit.html");
 /// ```
 pub struct CockpitServer {
     bus: Arc<EventBus>,
+    /// Robot ID of `bus`.
+    robot_id: String,
+    /// Further robots' buses, e.g. fed by a fleet bridge.
+    robots: Vec<(String, Arc<EventBus>)>,
+    bind_address: IpAddr,
     port: u16,
     /// When `Some(port)`, GET /frame requests are proxied to
     /// `http://127.0.0.1:{port}/frame` on the external camera server.
     camera_port: Option<u16>,
+    /// Certificate the server terminates TLS with; `None` serves plaintext.
+    tls: Option<TlsConfig>,
+    /// Events kept per topic for late-joining clients.
+    history_len: usize,
+    /// Silence after which a tab's held joystick override is released.
+    override_timeout: Duration,
+    /// Bearer token of the policy endpoint; `None` disables it.
+    admin_token: Option<String>,
+    /// Tokens HITL answers and approvals authenticate with.
+    operators: OperatorTokens,
+    /// Captured log records served at `/api/logs`.
+    logs: LogBuffer,
+    /// Cancelled to stop the server.
+    shutdown: CancellationToken,
 }
 
 impl CockpitServer {
@@ -61,9 +145,61 @@ impl CockpitServer {
     pub fn new(bus: Arc<EventBus>) -> Self {
         Self {
             bus,
+            robot_id: LOCAL_ROBOT.to_string(),
+            robots: Vec::new(),
+            bind_address: DEFAULT_BIND_ADDRESS,
             port: DEFAULT_PORT,
             camera_port: None,
+            tls: None,
+            history_len: DEFAULT_HISTORY_LEN,
+            override_timeout: DEFAULT_OVERRIDE_TIMEOUT,
+            admin_token: None,
+            operators: OperatorTokens::new(),
+            logs: LogBuffer::global().clone(),
+            shutdown: CancellationToken::new(),
+        }
+    }
+
+    /// Name the robot behind the bus passed to [`new`][Self::new]
+    /// (builder-style).  Defaults to [`LOCAL_ROBOT`].
+    pub fn with_robot_id(mut self, robot_id: impl Into<String>) -> Self {
+        self.robot_id = robot_id.into();
+        self
+    }
+
+    /// Also stream the events of `robot_id`'s `bus` (e.g. one fed by a
+    /// fleet bridge) and let tabs select it for teleoperation
+    /// (builder-style).  Replaces the bus already registered under that ID,
+    /// if any.
+    pub fn with_robot(mut self, robot_id: impl Into<String>, bus: Arc<EventBus>) -> Self {
+        let robot_id = robot_id.into();
+        if robot_id == self.robot_id {
+            self.bus = bus;
+        } else {
+            self.robots.retain(|(id, _)| *id != robot_id);
+            self.robots.push((robot_id, bus));
         }
+        self
+    }
+
+    /// IDs of every robot the server streams, the local one first.
+    pub fn robot_ids(&self) -> Vec<&str> {
+        std::iter::once(self.robot_id.as_str())
+            .chain(self.robots.iter().map(|(id, _)| id.as_str()))
+            .collect()
+    }
+
+    /// Override the listening address (builder-style).  Defaults to
+    /// [`DEFAULT_BIND_ADDRESS`]; pass `Ipv4Addr::UNSPECIFIED` to serve every
+    /// interface.
+    pub fn with_bind_address(mut self, address: impl Into<IpAddr>) -> Self {
+        self.bind_address = address.into();
+        self
+    }
+
+    /// Return the configured listening address.
+    pub fn bind_address(&self) -> IpAddr {
+        self.bind_address
     }
 
     /// Override the listening port (builder-style).
@@ -83,6 +219,87 @@ impl CockpitServer {
         self
     }
 
+    /// Serve HTTPS and `wss://` with the certificate and key named by `tls`
+    /// (builder-style).
+    pub fn with_tls(mut self, tls: TlsConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    /// Return the configured TLS settings, if any.
+    pub fn tls(&self) -> Option<&TlsConfig> {
+        self.tls.as_ref()
+    }
+
+    /// Replay the last `len` events of every topic to each newly connected
+    /// WebSocket client (builder-style).  Defaults to [`DEFAULT_HISTORY_LEN`].
+    pub fn with_history_len(mut self, len: usize) -> Self {
+        self.history_len = len;
+        self
+    }
+
+    /// Return the number of events kept per topic for new clients.
+    pub fn history_len(&self) -> usize {
+        self.history_len
+    }
+
+    /// Stop the robot and release a tab's joystick override when neither
+    /// override Twists nor heartbeats arrive for `timeout` (builder-style).
+    /// Defaults to [`DEFAULT_OVERRIDE_TIMEOUT`].
+    pub fn with_override_timeout(mut self, timeout: Duration) -> Self {
+        self.override_timeout = timeout;
+        self
+    }
+
+    /// Return the joystick dead-man timeout.
+    pub fn override_timeout(&self) -> Duration {
+        self.override_timeout
+    }
+
+    /// Serve the safety-policy endpoint (`/api/policy`) to requests carrying
+    /// `Authorization: Bearer <token>` (builder-style).  Without a token the
+    /// endpoint is disabled.  Combine with [`with_tls`][Self::with_tls] so
+    /// the token is not sent in the clear.
+    pub fn with_admin_token(mut self, token: impl Into<String>) -> Self {
+        self.admin_token = Some(token.into());
+        self
+    }
+
+    /// Let tabs sending `token` answer HITL questions and decide approvals
+    /// as `operator` (builder-style).  Once any token is registered, answers
+    /// and decisions without a registered token are refused.  Combine with
+    /// [`with_tls`][Self::with_tls] so tokens are not sent in the clear.
+    pub fn with_operator_token(
+        mut self,
+        token: impl Into<String>,
+        operator: impl Into<String>,
+    ) -> Self {
+        self.operators.insert(token, operator);
+        self
+    }
+
+    /// Refuse HITL answers and approval decisions without a registered
+    /// operator token, even while none is registered (builder-style).  Use
+    /// when operators' grants are checked, so a tab cannot answer under a
+    /// name it merely claims.
+    pub fn with_operator_tokens_required(mut self) -> Self {
+        self.operators.require();
+        self
+    }
+
+    /// Serve `GET /api/logs` from `logs` (builder-style).  Defaults to
+    /// [`LogBuffer::global`], which the MechOS tracing subscriber fills.
+    pub fn with_log_buffer(mut self, logs: LogBuffer) -> Self {
+        self.logs = logs;
+        self
+    }
+
+    /// Stop the server once `shutdown` is cancelled (builder-style).
+    pub fn with_shutdown(mut self, shutdown: CancellationToken) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
     /// Return the configured port.
     pub fn port(&self) -> u16 {
         self.port
@@ -99,24 +316,98 @@ impl CockpitServer {
     /// WebSocket bridge (when the HTTP request contains `Upgrade: websocket`)
     /// or a plain HTTP response serving the Cockpit HTML.
     ///
+    /// With [`with_tls`][Self::with_tls] every connection is TLS-encrypted
+    /// first.
+    ///
+    /// Runs until the [`with_shutdown`][Self::with_shutdown] token is
+    /// cancelled, then drains the open connections and returns `Ok(())`.
+    ///
     /// # Errors
     ///
-    /// Returns [`MechError::Serialization`] if the TCP listener cannot bind.
+    /// Returns [`MechError::Serialization`] if the TLS certificate cannot be
+    /// loaded or the TCP listener cannot bind.
     pub async fn run(self) -> Result<(), MechError> {
-        let addr = SocketAddr::from(([0, 0, 0, 0], self.port));
+        let acceptor = self.tls.as_ref().map(TlsConfig::acceptor).transpose()?;
+        let history = Arc::new(Mutex::new(FleetHistory::new(self.history_len)));
+        let cameras = Arc::new(CameraRelay::new());
+        let fleet = Arc::new(Fleet::new(
+            &self.robot_id,
+            std::iter::once((self.robot_id.clone(), Arc::clone(&self.bus)))
+                .chain(self.robots.iter().cloned()),
+            self.override_timeout,
+            self.admin_token.clone(),
+            self.operators.clone(),
+            self.logs.clone(),
+            self.shutdown.clone(),
+        ));
+        tokio::spawn(self.shutdown.clone().run_until_cancelled_owned(record_events(
+            fleet.events.subscribe(),
+            Arc::clone(&fleet.local),
+            Arc::clone(&history),
+            Arc::clone(&cameras),
+        )));
+        for (robot_id, bus) in &fleet.buses {
+            tokio::spawn(self.shutdown.clone().run_until_cancelled_owned(forward_robot_events(
+                Arc::clone(robot_id),
+                bus.subscribe(),
+                fleet.events.clone(),
+            )));
+        }
+        let addr = SocketAddr::new(self.bind_address, self.port);
         let listener = TcpListener::bind(addr).await.map_err(|e| {
             MechError::Serialization(format!("[mechos-cockpit] bind error on {addr}: {e}"))
         })?;
 
-        info!("Cockpit UI listening on http://localhost:{}", self.port);
+        let scheme = if acceptor.is_some() { "https" } else { "http" };
+        info!("Cockpit UI listening on {scheme}://{addr}");
 
+        let mut connections = JoinSet::new();
         loop {
-            match listener.accept().await {
+            let accepted = tokio::select! {
+                biased;
+                () = self.shutdown.cancelled() => break,
+                // Reap finished connections so the set does not grow.
+                Some(_) = connections.join_next(), if !connections.is_empty() => continue,
+                accepted = listener.accept() => accepted,
+            };
+            match accepted {
                 Ok((stream, peer)) => {
-                    let bus = Arc::clone(&self.bus);
+                    let fleet = Arc::clone(&fleet);
                     let camera_port = self.camera_port;
-                    tokio::spawn(async move {
-                        if let Err(e) = handle_connection(stream, peer, bus, camera_port).await {
+                    let history = Arc::clone(&history);
+                    let cameras = Arc::clone(&cameras);
+                    let acceptor = acceptor.clone();
+                    connections.spawn(async move {
+                        let result = match acceptor {
+                            Some(acceptor) => match acceptor.accept(stream).await {
+                                Ok(stream) => {
+                                    handle_connection(
+                                        stream,
+                                        peer,
+                                        fleet,
+                                        history,
+                                        cameras,
+                                        camera_port,
+                                    )
+                                    .await
+                                }
+                                Err(e) => Err(MechError::Serialization(format!(
+                                    "[mechos-cockpit] TLS handshake from {peer}: {e}"
+                                ))),
+                            },
+                            None => {
+                                handle_connection(
+                                    stream,
+                                    peer,
+                                    fleet,
+                                    history,
+                                    cameras,
+                                    camera_port,
+                                )
+                                .await
+                            }
+                        };
+                        if let Err(e) = result {
                             error!(peer = %peer, error = %e, "client connection error");
                         }
                     });
@@ -126,6 +417,177 @@ impl CockpitServer {
                 }
             }
         }
+
+        drop(listener);
+        info!(open = connections.len(), "Cockpit UI shutting down");
+        let drained = tokio::time::timeout(SHUTDOWN_DRAIN_TIMEOUT, async {
+            while connections.join_next().await.is_some() {}
+        })
+        .await;
+        if drained.is_err() {
+            warn!(open = connections.len(), "dropping connections still open after shutdown");
+            connections.shutdown().await;
+        }
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Robot buses, event history and camera relay
+// ---------------------------------------------------------------------------
+
+/// A bus event tagged with the robot whose bus carried it.
+#[derive(Debug, Clone)]
+struct RobotEvent {
+    robot_id: Arc<str>,
+    event: Event,
+}
+
+/// Every robot's bus plus the channel merging their events.
+struct Fleet {
+    /// Robot ID of the server's own bus.
+    local: Arc<str>,
+    buses: Vec<(Arc<str>, Arc<EventBus>)>,
+    events: tokio::sync::broadcast::Sender<RobotEvent>,
+    /// Silence after which a tab's held joystick override is released.
+    override_timeout: Duration,
+    /// Bearer token of the policy endpoint; `None` disables it.
+    admin_token: Option<String>,
+    /// Tokens HITL answers and approvals authenticate with.
+    operators: OperatorTokens,
+    /// Captured log records served at `/api/logs`.
+    logs: LogBuffer,
+    /// Cancelled when the server shuts down.
+    shutdown: CancellationToken,
+}
+
+impl Fleet {
+    fn new(
+        local: &str,
+        buses: impl IntoIterator<Item = (String, Arc<EventBus>)>,
+        override_timeout: Duration,
+        admin_token: Option<String>,
+        operators: OperatorTokens,
+        logs: LogBuffer,
+        shutdown: CancellationToken,
+    ) -> Self {
+        Self {
+            local: local.into(),
+            buses: buses
+                .into_iter()
+                .map(|(id, bus)| (id.into(), bus))
+                .collect(),
+            events: tokio::sync::broadcast::channel(FLEET_CHANNEL_CAPACITY).0,
+            override_timeout,
+            admin_token,
+            operators,
+            logs,
+            shutdown,
+        }
+    }
+
+    /// The bus of `robot_id`, with its interned ID.
+    fn robot(&self, robot_id: &str) -> Option<&(Arc<str>, Arc<EventBus>)> {
+        self.buses.iter().find(|(id, _)| &**id == robot_id)
+    }
+
+    /// The `{"op":"robots",…}` frame listing every robot.
+    fn robots_frame(&self, selected: &str) -> String {
+        let robots: Vec<&str> = self.buses.iter().map(|(id, _)| &**id).collect();
+        serde_json::json!({
+            "op": "robots",
+            "robots": robots,
+            "local": &*self.local,
+            "selected": selected,
+        })
+        .to_string()
+    }
+}
+
+/// Re-publish every event of `robot_id`'s bus on the merged `events`
+/// channel until the bus closes.
+///
+/// Safety-policy traffic stays off it: only the authenticated
+/// `/api/policy` endpoint may see rules, grants and the audit log.
+async fn forward_robot_events(
+    robot_id: Arc<str>,
+    mut bus_rx: tokio::sync::broadcast::Receiver<Event>,
+    events: tokio::sync::broadcast::Sender<RobotEvent>,
+) {
+    loop {
+        match bus_rx.recv().await {
+            Ok(Event {
+                payload: EventPayload::PolicyRequest { .. } | EventPayload::PolicyStatus { .. },
+                ..
+            }) => {}
+            Ok(event) => {
+                let _ = events.send(RobotEvent {
+                    robot_id: Arc::clone(&robot_id),
+                    event,
+                });
+            }
+            Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                warn!(robot_id = %robot_id, lagged_by = n, "cockpit robot bus lagged");
+            }
+            Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
+/// Serialise `event` for a browser tab, tagged with its robot ID.
+fn robot_frame(robot_id: &str, event: &Event) -> Result<String, serde_json::Error> {
+    let mut json = serde_json::to_value(event)?;
+    if let Value::Object(fields) = &mut json {
+        fields.insert("robot_id".to_string(), Value::from(robot_id));
+    }
+    serde_json::to_string(&json)
+}
+
+/// Send serialised `frames` to a tab; `false` once the tab has gone.
+async fn send_frames<S>(ws_tx: &mut S, frames: Vec<Result<String, serde_json::Error>>) -> bool
+where
+    S: futures_util::Sink<Message> + Unpin,
+{
+    for frame in frames {
+        match frame {
+            Ok(json) => {
+                if ws_tx.send(Message::Text(json.into())).await.is_err() {
+                    return false;
+                }
+            }
+            Err(e) => error!(error = %e, "serialization error"),
+        }
+    }
+    true
+}
+
+/// Record every robot's events into `history` and camera frames into
+/// `cameras` until the merged channel closes.  Frames of robots other than
+/// `local` are namespaced by robot ID.
+async fn record_events(
+    mut events_rx: tokio::sync::broadcast::Receiver<RobotEvent>,
+    local: Arc<str>,
+    history: Arc<Mutex<FleetHistory>>,
+    cameras: Arc<CameraRelay>,
+) {
+    loop {
+        match events_rx.recv().await {
+            Ok(RobotEvent { robot_id, event }) => {
+                if robot_id == local {
+                    cameras.record(&event.payload);
+                } else {
+                    cameras.record_for_robot(&robot_id, &event.payload);
+                }
+                history
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .record(&robot_id, &event);
+            }
+            Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                warn!(lagged_by = n, "cockpit history lagged");
+            }
+            Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+        }
     }
 }
 
@@ -133,21 +595,28 @@ impl CockpitServer {
 // Per-connection handler
 // ---------------------------------------------------------------------------
 
+/// A client connection: plain TCP or TLS over TCP.
+trait Connection: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> Connection for T {}
+
 async fn handle_connection(
-    stream: TcpStream,
+    stream: impl Connection,
     peer: SocketAddr,
-    bus: Arc<EventBus>,
+    fleet: Arc<Fleet>,
+    history: Arc<Mutex<FleetHistory>>,
+    cameras: Arc<CameraRelay>,
     camera_port: Option<u16>,
 ) -> Result<(), MechError> {
-    // Peek at the first bytes of the request to decide whether to upgrade
-    // to WebSocket or serve the static HTML.  `peek` does not consume the
-    // data, so tungstenite's handshaker sees the full HTTP request.
-    let mut buf = [0u8; 2048];
-    let n = stream.peek(&mut buf).await.map_err(|e| {
-        MechError::Serialization(format!("peek error from {peer}: {e}"))
+    // Buffer the first bytes of the request to decide whether to upgrade
+    // to WebSocket or serve the static HTML.  Buffered data is not consumed,
+    // so tungstenite's handshaker still sees the full HTTP request.
+    let mut stream = BufReader::with_capacity(2048, stream);
+    let buf = stream.fill_buf().await.map_err(|e| {
+        MechError::Serialization(format!("read error from {peer}: {e}"))
     })?;
 
-    let header_preview = String::from_utf8_lossy(&buf[..n]);
+    let header_preview = String::from_utf8_lossy(buf).into_owned();
     let first_line = header_preview.lines().next().unwrap_or("");
 
     let is_ws_upgrade = header_preview
@@ -155,23 +624,189 @@ async fn handle_connection(
         .any(|line| line.to_lowercase().starts_with("upgrade:") && line.to_lowercase().contains("websocket"));
 
     if is_ws_upgrade {
-        handle_ws(stream, peer, bus).await
+        handle_ws(stream, peer, fleet, history).await
+    } else if first_line.starts_with("GET /cameras") || first_line.starts_with("GET /images/") {
+        let path = first_line.split_whitespace().nth(1).unwrap_or("/");
+        let path = path.split('?').next().unwrap_or(path);
+        // MJPEG streams never end on their own.
+        let relay = serve_camera_relay(stream, path, &cameras);
+        fleet.shutdown.run_until_cancelled(relay).await.unwrap_or(Ok(()))
     } else if first_line.starts_with("GET /frame") {
         serve_camera_frame(stream, camera_port).await
+    } else if first_line.starts_with("GET /api/policy") || first_line.starts_with("POST /api/policy") {
+        serve_policy(stream, &fleet).await
+    } else if first_line.starts_with("GET /api/logs") {
+        serve_logs(stream, &fleet.logs).await
     } else if first_line.starts_with("GET /api/config") {
         serve_config_get(stream).await
     } else if first_line.starts_with("POST /api/config") {
         serve_config_post(stream).await
     } else {
-        serve_html(stream).await
+        serve_asset(stream).await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Safety policy – list and change the kernel's rules (see crate::policy)
+// ---------------------------------------------------------------------------
+
+/// Serve `GET` / `POST /api/policy[?robot=<id>]`: authenticate, forward the
+/// request to the robot's agent loop and return its policy report.
+async fn serve_policy(mut stream: impl Connection, fleet: &Fleet) -> Result<(), MechError> {
+    let (head, body) = read_request(&mut stream).await?;
+    let json = "application/json";
+    let error = |message: &str| serde_json::json!({ "error": message }).to_string();
+
+    let Some(token) = fleet.admin_token.as_deref() else {
+        let body = error("policy endpoint disabled: no admin token configured");
+        return write_response(&mut stream, "403 Forbidden", json, body.as_bytes()).await;
+    };
+    if !authorized(&head, token) {
+        let body = error("missing or invalid bearer token");
+        return write_response(&mut stream, "401 Unauthorized", json, body.as_bytes()).await;
+    }
+    let message = if head.starts_with("POST") {
+        match PolicyMessage::parse(&body) {
+            Ok(message) => message,
+            Err(e) => {
+                return write_response(&mut stream, "400 Bad Request", json, error(&e).as_bytes())
+                    .await;
+            }
+        }
+    } else {
+        PolicyMessage::list()
+    };
+    let target = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|path| path.split_once('?'))
+        .and_then(|(_, query)| query.split('&').find_map(|pair| pair.strip_prefix("robot=")))
+        .unwrap_or(&fleet.local);
+    let Some((_, bus)) = fleet.robot(target) else {
+        let body = error(&format!("unknown robot \"{target}\""));
+        return write_response(&mut stream, "404 Not Found", json, body.as_bytes()).await;
+    };
+
+    // Subscribe before publishing so the reply cannot slip past.
+    let mut replies = bus.subscribe();
+    let request_id = Uuid::new_v4().to_string();
+    let _ = bus.publish(message.to_event(&request_id));
+    let reply = tokio::time::timeout(POLICY_REPLY_TIMEOUT, async {
+        loop {
+            match replies.recv().await {
+                Ok(Event {
+                    payload:
+                        EventPayload::PolicyStatus {
+                            request_id: answered,
+                            report,
+                            error,
+                        },
+                    ..
+                }) if answered == request_id => return Some((report, error)),
+                Ok(_) | Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {}
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    })
+    .await
+    .ok()
+    .flatten();
+    let Some((report, refused)) = reply else {
+        let body = error("no agent loop answered the policy request");
+        return write_response(&mut stream, "504 Gateway Timeout", json, body.as_bytes()).await;
+    };
+    let mut body =
+        serde_json::to_value(&*report).map_err(|e| MechError::Serialization(e.to_string()))?;
+    let status = match refused {
+        Some(refused) => {
+            body["error"] = refused.into();
+            "422 Unprocessable Entity"
+        }
+        None => "200 OK",
+    };
+    write_response(&mut stream, status, json, body.to_string().as_bytes()).await
+}
+
+/// Read an HTTP request's head and its `Content-Length` body, up to
+/// [`MAX_UPSTREAM_MSG_BYTES`] in total.
+async fn read_request(stream: &mut impl Connection) -> Result<(String, Vec<u8>), MechError> {
+    let mut raw = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        if let Some(end) = raw.windows(4).position(|w| w == b"\r\n\r\n") {
+            let head = String::from_utf8_lossy(&raw[..end]).into_owned();
+            let length = head
+                .lines()
+                .filter_map(|line| line.split_once(':'))
+                .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+                .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+                .unwrap_or(0)
+                .min(MAX_UPSTREAM_MSG_BYTES);
+            let mut body = raw.split_off(end + 4);
+            while body.len() < length {
+                match stream.read(&mut chunk).await {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => body.extend_from_slice(&chunk[..n]),
+                }
+            }
+            body.truncate(length);
+            return Ok((head, body));
+        }
+        if raw.len() >= MAX_UPSTREAM_MSG_BYTES {
+            return Err(MechError::Serialization("HTTP request head too large".to_string()));
+        }
+        match stream.read(&mut chunk).await {
+            Ok(0) => return Ok((String::from_utf8_lossy(&raw).into_owned(), Vec::new())),
+            Ok(n) => raw.extend_from_slice(&chunk[..n]),
+            Err(e) => return Err(MechError::Serialization(format!("HTTP read error: {e}"))),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Logs – the newest captured tracing events (see mechos_middleware::log_buffer)
+// ---------------------------------------------------------------------------
+
+/// Serve `GET /api/logs[?level=<level>&target=<text>&limit=<n>]`: the
+/// newest records of `logs` at least as severe as `level` whose target
+/// contains `target`, oldest first, as `{"logs":[…]}`.
+async fn serve_logs(mut stream: impl Connection, logs: &LogBuffer) -> Result<(), MechError> {
+    let (head, _) = read_request(&mut stream).await?;
+    let json = "application/json";
+    let query = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|path| path.split_once('?'))
+        .map_or("", |(_, query)| query);
+    let mut filter = LogFilter {
+        limit: Some(DEFAULT_LOGS_LIMIT),
+        ..LogFilter::default()
+    };
+    for (name, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+        let parsed = match name {
+            "level" => value.parse().map(|level| filter.min_level = Some(level)).is_ok(),
+            "target" => {
+                filter.target = Some(value.to_string());
+                true
+            }
+            "limit" => value.parse().map(|limit| filter.limit = Some(limit)).is_ok(),
+            _ => true,
+        };
+        if !parsed {
+            let body = serde_json::json!({ "error": format!("invalid {name} \"{value}\"") });
+            return write_response(&mut stream, "400 Bad Request", json, body.to_string().as_bytes())
+                .await;
+        }
     }
+    let body = serde_json::json!({ "logs": logs.query(&filter) });
+    write_response(&mut stream, "200 OK", json, body.to_string().as_bytes()).await
 }
 
 // ---------------------------------------------------------------------------
 // Config GET – return ~/.mechos/config.toml as raw text
 // ---------------------------------------------------------------------------
 
-async fn serve_config_get(mut stream: TcpStream) -> Result<(), MechError> {
+async fn serve_config_get(mut stream: impl Connection) -> Result<(), MechError> {
     let path = mechos_config_path();
     let response = match tokio::fs::read_to_string(&path).await {
         Ok(body) => format!(
@@ -208,7 +843,7 @@ async fn serve_config_get(mut stream: TcpStream) -> Result<(), MechError> {
 // Config POST – write the request body to ~/.mechos/config.toml
 // ---------------------------------------------------------------------------
 
-async fn serve_config_post(mut stream: TcpStream) -> Result<(), MechError> {
+async fn serve_config_post(mut stream: impl Connection) -> Result<(), MechError> {
     // Read the full HTTP request (header + body).
     let mut raw = Vec::new();
     let mut tmp = [0u8; 4096];
@@ -287,7 +922,7 @@ fn mechos_config_path() -> std::path::PathBuf {
 /// returned immediately.  Otherwise the request is forwarded to
 /// `http://127.0.0.1:{camera_port}/frame` using a raw HTTP/1.0 connection and
 /// the full response (headers + body) is relayed back to the browser client.
-async fn serve_camera_frame(mut stream: TcpStream, camera_port: Option<u16>) -> Result<(), MechError> {
+async fn serve_camera_frame(mut stream: impl Connection, camera_port: Option<u16>) -> Result<(), MechError> {
     let Some(port) = camera_port else {
         let body = "Camera not configured";
         let response = format!(
@@ -376,25 +1011,168 @@ async fn serve_camera_frame(mut stream: TcpStream, camera_port: Option<u16>) ->
 }
 
 // ---------------------------------------------------------------------------
-// Plain HTTP: serve the embedded Cockpit HTML
+// Camera relay – bus camera frames over HTTP
 // ---------------------------------------------------------------------------
 
-async fn serve_html(mut stream: TcpStream) -> Result<(), MechError> {
-    let body = COCKPIT_HTML;
-    let response = format!(
+/// MIME boundary between the parts of an MJPEG stream.
+const MJPEG_BOUNDARY: &str = "mechosframe";
+
+/// Serve `GET /cameras`, `GET /cameras/{camera}/mjpeg` or
+/// `GET /images/{image_id}` from `cameras`; anything else is a `404`.
+async fn serve_camera_relay(
+    mut stream: impl Connection,
+    path: &str,
+    cameras: &CameraRelay,
+) -> Result<(), MechError> {
+    if path == "/cameras" {
+        let body = serde_json::to_string(&cameras.cameras())
+            .map_err(|e| MechError::Serialization(e.to_string()))?;
+        return write_response(&mut stream, "200 OK", "application/json", body.as_bytes()).await;
+    }
+    if let Some(camera) = path
+        .strip_prefix("/cameras/")
+        .and_then(|rest| rest.strip_suffix("/mjpeg"))
+    {
+        return stream_mjpeg(stream, camera, cameras).await;
+    }
+    if let Some(image_id) = path.strip_prefix("/images/")
+        && let Some(image) = cameras.image(image_id)
+    {
+        return write_response(&mut stream, "200 OK", image.content_type, &image.data).await;
+    }
+    write_response(
+        &mut stream,
+        "404 Not Found",
+        "text/plain; charset=utf-8",
+        b"Unknown camera or image",
+    )
+    .await
+}
+
+/// Stream every new frame of `camera` as `multipart/x-mixed-replace` until
+/// the client disconnects, starting with the latest one.
+async fn stream_mjpeg(
+    mut stream: impl Connection,
+    camera: &str,
+    cameras: &CameraRelay,
+) -> Result<(), MechError> {
+    // Subscribe first so no frame falls between the latest one and the feed.
+    let mut updates = cameras.subscribe();
+    let header = format!(
         "HTTP/1.1 200 OK\r\n\
-         Content-Type: text/html; charset=utf-8\r\n\
+         Content-Type: multipart/x-mixed-replace; boundary={MJPEG_BOUNDARY}\r\n\
+         Cache-Control: no-cache\r\n\
+         Connection: close\r\n\
+         \r\n"
+    );
+    if stream.write_all(header.as_bytes()).await.is_err() {
+        return Ok(());
+    }
+    let mut next = cameras.latest(camera);
+    loop {
+        if let Some(jpeg) = next.take() {
+            let part = format!(
+                "--{MJPEG_BOUNDARY}\r\nContent-Type: image/jpeg\r\nContent-Length: {}\r\n\r\n",
+                jpeg.len()
+            );
+            let written = async {
+                stream.write_all(part.as_bytes()).await?;
+                stream.write_all(&jpeg).await?;
+                stream.write_all(b"\r\n").await?;
+                stream.flush().await
+            };
+            if written.await.is_err() {
+                // The viewer went away.
+                return Ok(());
+            }
+        }
+        match updates.recv().await {
+            Ok(CameraUpdate { camera: from, jpeg }) if from == camera => next = Some(jpeg),
+            Ok(_) => {}
+            Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {}
+            Err(tokio::sync::broadcast::error::RecvError::Closed) => return Ok(()),
+        }
+    }
+}
+
+async fn write_response(
+    stream: &mut impl Connection,
+    status: &str,
+    content_type: &str,
+    body: &[u8],
+) -> Result<(), MechError> {
+    let header = format!(
+        "HTTP/1.1 {status}\r\n\
+         Content-Type: {content_type}\r\n\
          Content-Length: {}\r\n\
          Connection: close\r\n\
-         \r\n\
-         {}",
+         \r\n",
+        body.len()
+    );
+    stream
+        .write_all(header.as_bytes())
+        .await
+        .map_err(|e| MechError::Serialization(format!("HTTP write error: {e}")))?;
+    stream
+        .write_all(body)
+        .await
+        .map_err(|e| MechError::Serialization(format!("HTTP write error: {e}")))
+}
+
+// ---------------------------------------------------------------------------
+// Plain HTTP: serve the embedded frontend bundle (see crate::assets)
+// ---------------------------------------------------------------------------
+
+/// Serve `GET` / `HEAD` of a bundle file, negotiating gzip and answering
+/// `304` to a matching `If-None-Match`.
+async fn serve_asset(mut stream: impl Connection) -> Result<(), MechError> {
+    let (head, _) = read_request(&mut stream).await?;
+    let mut request_line = head.lines().next().unwrap_or("").split_whitespace();
+    let method = request_line.next().unwrap_or("");
+    let path = request_line.next().unwrap_or("/");
+    let text = "text/plain; charset=utf-8";
+    if method != "GET" && method != "HEAD" {
+        return write_response(&mut stream, "405 Method Not Allowed", text, b"method not allowed")
+            .await;
+    }
+    let Some(asset) = StaticAsset::resolve(path) else {
+        return write_response(&mut stream, "404 Not Found", text, b"not found").await;
+    };
+
+    let gzip = asset.compressible() && accepts_gzip(&head);
+    let (status, body): (_, Cow<[u8]>) = if not_modified(&head, &asset.etag) {
+        ("304 Not Modified", Cow::Borrowed(&[]))
+    } else if gzip {
+        ("200 OK", Cow::Owned(asset.gzipped().to_vec()))
+    } else {
+        ("200 OK", Cow::Borrowed(&asset.body))
+    };
+    let mut header = format!(
+        "HTTP/1.1 {status}\r\n\
+         Content-Type: {}\r\n\
+         Content-Length: {}\r\n\
+         ETag: {}\r\n\
+         Cache-Control: {}\r\n\
+         Vary: Accept-Encoding\r\n",
+        asset.content_type,
         body.len(),
-        body
+        asset.etag,
+        asset.cache_control(),
     );
+    if gzip && !body.is_empty() {
+        header.push_str("Content-Encoding: gzip\r\n");
+    }
+    header.push_str("Connection: close\r\n\r\n");
     stream
-        .write_all(response.as_bytes())
+        .write_all(header.as_bytes())
         .await
         .map_err(|e| MechError::Serialization(format!("HTTP write error: {e}")))?;
+    if method == "GET" {
+        stream
+            .write_all(&body)
+            .await
+            .map_err(|e| MechError::Serialization(format!("HTTP write error: {e}")))?;
+    }
     Ok(())
 }
 
@@ -403,9 +1181,10 @@ async fn serve_html(mut stream: TcpStream) -> Result<(), MechError> {
 // ---------------------------------------------------------------------------
 
 async fn handle_ws(
-    stream: TcpStream,
+    stream: impl Connection,
     peer: SocketAddr,
-    bus: Arc<EventBus>,
+    fleet: Arc<Fleet>,
+    history: Arc<Mutex<FleetHistory>>,
 ) -> Result<(), MechError> {
     let mut ws_config = WebSocketConfig::default();
     ws_config.max_message_size = Some(MAX_UPSTREAM_MSG_BYTES);
@@ -414,15 +1193,55 @@ async fn handle_ws(
     })?;
 
     let (mut ws_tx, mut ws_rx) = ws_stream.split();
-    let mut bus_rx = bus.subscribe();
+    // Subscribe before taking the snapshot so no event falls in between;
+    // live events already replayed are skipped below.
+    let mut events_rx = fleet.events.subscribe();
+    let snapshot = history.lock().unwrap_or_else(|e| e.into_inner()).snapshot();
+    let mut replayed: HashSet<Uuid> = snapshot.iter().map(|(_, event)| event.id).collect();
+    // The tab's subscription, applied to each robot separately so one
+    // robot's throttled streams never use up another's rate.
+    let mut subscription = ClientSubscription::default();
+    let mut robot_subscriptions: HashMap<Arc<str>, ClientSubscription> = HashMap::new();
+    // `/safety/*` requests from this tab still awaiting confirmation.
+    let mut pending_safety = HashSet::new();
+    // The joystick override this tab holds, released when it goes silent.
+    let mut deadman = DeadManSwitch::new(fleet.override_timeout);
+    // The robot this tab's commands go to.
+    let Some((mut selected, mut bus)) = fleet.robot(&fleet.local).cloned() else {
+        return Ok(());
+    };
+    let frames = std::iter::once(Ok(fleet.robots_frame(&selected)))
+        .chain(
+            snapshot
+                .iter()
+                .map(|(robot_id, event)| robot_frame(robot_id, event)),
+        )
+        .collect();
+    if !send_frames(&mut ws_tx, frames).await {
+        return Ok(());
+    }
 
     loop {
         tokio::select! {
             // ── Downstream: EventBus → browser ─────────────────────────────
-            result = bus_rx.recv() => {
+            result = events_rx.recv() => {
                 match result {
-                    Ok(event) => {
-                        match serde_json::to_string(&event) {
+                    Ok(RobotEvent { robot_id, event }) => {
+                        if let Some((request_id, ack)) = safety_ack(&event.payload)
+                            && pending_safety.remove(request_id)
+                            && ws_tx.send(Message::Text(ack.to_string().into())).await.is_err()
+                        {
+                            break;
+                        }
+                        if replayed.remove(&event.id)
+                            || !robot_subscriptions
+                                .entry(robot_id.clone())
+                                .or_insert_with(|| subscription.clone())
+                                .admit(&event)
+                        {
+                            continue;
+                        }
+                        match robot_frame(&robot_id, &event) {
                             Ok(json) => {
                                 if ws_tx.send(Message::Text(json.into())).await.is_err() {
                                     break;
@@ -439,6 +1258,22 @@ async fn handle_ws(
                     Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
                 }
             }
+            // ── Dead-man: the held joystick went silent ────────────────────
+            () = until(deadman.deadline()) => {
+                if let Some(held) = deadman.expire(tokio::time::Instant::now()) {
+                    warn!(peer = %peer, "override heartbeats stopped; stopping the robot");
+                    let _ = held.publish(stop_event(true));
+                }
+            }
+            // ── Shutdown: tell the tab the server is going away ────────────
+            () = fleet.shutdown.cancelled() => {
+                let close = CloseFrame {
+                    code: CloseCode::Away,
+                    reason: "server shutting down".into(),
+                };
+                let _ = ws_tx.send(Message::Close(Some(close))).await;
+                break;
+            }
             // ── Upstream: browser → EventBus ────────────────────────────────
             msg = ws_rx.next() => {
                 match msg {
@@ -452,7 +1287,88 @@ async fn handle_ws(
                             );
                             break;
                         }
-                        handle_upstream_message(text.as_str(), &bus);
+                        let json = serde_json::from_str::<Value>(text.as_str()).ok();
+                        match json.as_ref().and_then(OverrideSignal::parse) {
+                            Some(OverrideSignal::Beat) => deadman.beat(&bus),
+                            Some(OverrideSignal::End) => {
+                                if let Some(held) = deadman.end() {
+                                    let _ = held.publish(stop_event(false));
+                                }
+                            }
+                            None => {}
+                        }
+                        if let Some(update) = json.as_ref().and_then(ClientSubscription::parse) {
+                            subscription = update;
+                            robot_subscriptions.clear();
+                        } else if let Some(robot_id) = json.as_ref().and_then(parse_select_robot) {
+                            // The joystick drives the selected robot only.
+                            if let Some(held) = deadman.end() {
+                                let _ = held.publish(stop_event(true));
+                            }
+                            match fleet.robot(robot_id) {
+                                Some(robot) => (selected, bus) = robot.clone(),
+                                None => warn!(peer = %peer, robot_id, "tab selected an unknown robot"),
+                            }
+                            // Bring the tab up to date on the selected robot.
+                            let replay = history
+                                .lock()
+                                .unwrap_or_else(|e| e.into_inner())
+                                .robot(&selected)
+                                .map(EventHistory::snapshot)
+                                .unwrap_or_default();
+                            let frames = std::iter::once(Ok(fleet.robots_frame(&selected)))
+                                .chain(replay.iter().map(|event| robot_frame(&selected, event)))
+                                .collect();
+                            if !send_frames(&mut ws_tx, frames).await {
+                                break;
+                            }
+                        } else if let Some(request) = json.as_ref().and_then(SafetyMessage::parse) {
+                            match request {
+                                Ok(request) => {
+                                    pending_safety.insert(request.request_id.clone());
+                                    let _ = bus.publish(request.to_event());
+                                }
+                                Err(nack) => {
+                                    if ws_tx.send(Message::Text(nack.to_string().into())).await.is_err() {
+                                        break;
+                                    }
+                                }
+                            }
+                        } else if let Some(decision) = json.as_ref().and_then(|json| {
+                            ApprovalMessage::parse(json)
+                                .map(|parsed| parsed.and_then(|d| d.authenticate(json, &fleet.operators)))
+                        }) {
+                            match decision {
+                                Ok(decision) => {
+                                    let _ = bus.publish(decision.to_event());
+                                }
+                                Err(nack) => {
+                                    if ws_tx.send(Message::Text(nack.to_string().into())).await.is_err() {
+                                        break;
+                                    }
+                                }
+                            }
+                        } else if let Some(Err(error)) = json
+                            .as_ref()
+                            .filter(|json| is_human_response(json))
+                            .map(|json| fleet.operators.operator(json))
+                        {
+                            warn!(peer = %peer, error, "HITL answer refused");
+                            let nack = serde_json::json!({
+                                "op": "hitl_nack",
+                                "question_id": json.as_ref().and_then(|json| json.pointer("/msg/question_id")),
+                                "error": error,
+                            });
+                            if ws_tx.send(Message::Text(nack.to_string().into())).await.is_err() {
+                                break;
+                            }
+                        } else {
+                            let operator = json
+                                .as_ref()
+                                .and_then(|json| fleet.operators.operator(json).ok())
+                                .flatten();
+                            handle_upstream_message(text.as_str(), &bus, operator.as_deref());
+                        }
                     }
                     Some(Ok(Message::Close(_))) | None => break,
                     Some(Err(_)) => break,
@@ -462,13 +1378,39 @@ async fn handle_ws(
         }
     }
 
+    // A tab that vanishes mid-drive must not leave its last Twist coasting.
+    if let Some(held) = deadman.end() {
+        warn!(peer = %peer, "tab closed while overriding; stopping the robot");
+        let _ = held.publish(stop_event(true));
+    }
     Ok(())
 }
 
+/// Sleep until `deadline`, or forever without one.
+async fn until(deadline: Option<tokio::time::Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Upstream message parser
 // ---------------------------------------------------------------------------
 
+/// The robot ID of a `{"op":"select_robot","robot_id":…}` message.
+fn parse_select_robot(json: &Value) -> Option<&str> {
+    if json.get("op").and_then(Value::as_str) != Some("select_robot") {
+        return None;
+    }
+    json.get("robot_id").and_then(Value::as_str)
+}
+
+/// `true` for a `/hitl/human_response` message.
+fn is_human_response(json: &Value) -> bool {
+    json.get("topic").and_then(Value::as_str) == Some("/hitl/human_response")
+}
+
 /// Maximum byte length of an upstream WebSocket message accepted from the
 /// Cockpit browser.
 ///
@@ -486,12 +1428,12 @@ pub(crate) const MAX_UPSTREAM_MSG_BYTES: usize = 65_536; // 64 KiB
 /// | Topic | Effect |
 /// |---|---|
 /// | `/cmd_vel` + `source: "dashboard_override"` | Arms AI suspension; publishes override event |
-/// | `/hitl/human_response` | Publishes [`EventPayload::HumanResponse`] |
+/// | `/hitl/human_response` | Publishes [`EventPayload::HumanResponse`] from `operator` answering `msg.question_id` |
 /// | `/agent/mode` | Publishes [`EventPayload::AgentModeToggle`] |
 ///
 /// Messages exceeding [`MAX_UPSTREAM_MSG_BYTES`] are silently discarded.
 /// Unknown messages are silently ignored.
-pub(crate) fn handle_upstream_message(text: &str, bus: &Arc<EventBus>) {
+pub(crate) fn handle_upstream_message(text: &str, bus: &Arc<EventBus>, operator: Option<&str>) {
     // ── Input size guard ────────────────────────────────────────────────────
     if text.len() > MAX_UPSTREAM_MSG_BYTES {
         warn!(
@@ -515,8 +1457,9 @@ pub(crate) fn handle_upstream_message(text: &str, bus: &Arc<EventBus>) {
             id: Uuid::new_v4(),
             timestamp: Utc::now(),
             source: "mechos-middleware::dashboard_override".to_string(),
-            payload: EventPayload::AgentThought(text.to_string()),
+            payload: EventPayload::adapter_command(json),
             trace_id: None,
+            correlation_id: None,
         };
         let _ = bus.publish(event);
         return;
@@ -524,17 +1467,23 @@ pub(crate) fn handle_upstream_message(text: &str, bus: &Arc<EventBus>) {
 
     // ── HITL human response ─────────────────────────────────────────────────
     if topic == "/hitl/human_response" {
-        if let Some(response) = json
-            .get("msg")
-            .and_then(|m| m.get("response"))
-            .and_then(|r| r.as_str())
+        if let Some(msg) = json.get("msg")
+            && let Some(response) = msg.get("response").and_then(|r| r.as_str())
         {
             let event = Event {
                 id: Uuid::new_v4(),
                 timestamp: Utc::now(),
                 source: "mechos-middleware::dashboard/human_response".to_string(),
-                payload: EventPayload::HumanResponse(response.to_string()),
+                payload: EventPayload::HumanResponse(HumanAnswer {
+                    question_id: msg
+                        .get("question_id")
+                        .and_then(|q| q.as_str())
+                        .map(str::to_string),
+                    answer: response.to_string(),
+                    operator: operator.map(str::to_string),
+                }),
                 trace_id: None,
+                correlation_id: None,
             };
             let _ = bus.publish(event);
         }
@@ -554,6 +1503,7 @@ pub(crate) fn handle_upstream_message(text: &str, bus: &Arc<EventBus>) {
             source: "mechos-cockpit::server".to_string(),
             payload: EventPayload::AgentModeToggle { paused },
             trace_id: None,
+            correlation_id: None,
         };
         let _ = bus.publish(event);
     }
@@ -566,6 +1516,7 @@ pub(crate) fn handle_upstream_message(text: &str, bus: &Arc<EventBus>) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::LazyLock;
     use mechos_middleware::EventBus;
     use mechos_types::EventPayload;
 
@@ -589,6 +1540,14 @@ mod tests {
         assert_eq!(server.port(), 9999);
     }
 
+    #[test]
+    fn default_bind_address_is_loopback() {
+        let server = CockpitServer::new(make_bus());
+        assert!(server.bind_address().is_loopback());
+        let server = server.with_bind_address(Ipv4Addr::UNSPECIFIED);
+        assert_eq!(server.bind_address(), IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+    }
+
     #[test]
     fn default_camera_port_is_none() {
         let bus = make_bus();
@@ -606,16 +1565,19 @@ mod tests {
     // ── Upstream message handling ─────────────────────────────────────────────
 
     #[tokio::test]
-    async fn upstream_override_publishes_agent_thought() {
+    async fn upstream_override_publishes_adapter_command() {
         let bus = make_bus();
         let mut rx = bus.subscribe();
 
         let msg = r#"{"op":"publish","topic":"/cmd_vel","msg":{"linear":{"x":0.5,"y":0,"z":0},"angular":{"x":0,"y":0,"z":-0.2}},"source":"dashboard_override"}"#;
-        handle_upstream_message(msg, &bus);
+        handle_upstream_message(msg, &bus, None);
 
         let event = rx.recv().await.unwrap();
         assert_eq!(event.source, "mechos-middleware::dashboard_override");
-        assert!(matches!(event.payload, EventPayload::AgentThought(_)));
+        assert!(matches!(
+            &event.payload,
+            EventPayload::AdapterCommand { topic, .. } if topic == "/cmd_vel"
+        ));
     }
 
     #[tokio::test]
@@ -623,13 +1585,16 @@ mod tests {
         let bus = make_bus();
         let mut rx = bus.subscribe();
 
-        let msg = r#"{"op":"publish","topic":"/hitl/human_response","msg":{"response":"Yes, push the box"}}"#;
-        handle_upstream_message(msg, &bus);
+        let msg = r#"{"op":"publish","topic":"/hitl/human_response","msg":{"response":"Yes, push the box","question_id":"q-2"}}"#;
+        handle_upstream_message(msg, &bus, Some("alice"));
 
         let event = rx.recv().await.unwrap();
         assert_eq!(event.source, "mechos-middleware::dashboard/human_response");
         if let EventPayload::HumanResponse(resp) = event.payload {
-            assert_eq!(resp, "Yes, push the box");
+            assert_eq!(
+                resp,
+                HumanAnswer::to_question("q-2", "Yes, push the box").with_operator("alice")
+            );
         } else {
             panic!("expected HumanResponse");
         }
@@ -641,7 +1606,7 @@ mod tests {
         let mut rx = bus.subscribe();
 
         let msg = r#"{"topic":"/agent/mode","msg":{"paused":true}}"#;
-        handle_upstream_message(msg, &bus);
+        handle_upstream_message(msg, &bus, None);
 
         let event = rx.recv().await.unwrap();
         assert_eq!(event.source, "mechos-cockpit::server");
@@ -657,7 +1622,7 @@ mod tests {
         let mut rx = bus.subscribe();
 
         let msg = r#"{"topic":"/agent/mode","msg":{"paused":false}}"#;
-        handle_upstream_message(msg, &bus);
+        handle_upstream_message(msg, &bus, None);
 
         let event = rx.recv().await.unwrap();
         assert!(
@@ -676,17 +1641,18 @@ mod tests {
             id: Uuid::new_v4(),
             timestamp: Utc::now(),
             source: "test".to_string(),
-            payload: EventPayload::AgentThought("sentinel".to_string()),
+            payload: EventPayload::Reasoning("sentinel".to_string()),
             trace_id: None,
+            correlation_id: None,
         };
         let _ = bus.publish(known_event);
 
         // Send an unknown message.
-        handle_upstream_message(r#"{"op":"subscribe","topic":"/unknown"}"#, &bus);
+        handle_upstream_message(r#"{"op":"subscribe","topic":"/unknown"}"#, &bus, None);
 
         // Only the sentinel event should be in the channel.
         let event = rx.recv().await.unwrap();
-        if let EventPayload::AgentThought(s) = event.payload {
+        if let EventPayload::Reasoning(s) = event.payload {
             assert_eq!(s, "sentinel");
         } else {
             panic!("unexpected payload");
@@ -704,20 +1670,31 @@ mod tests {
             id: Uuid::new_v4(),
             timestamp: Utc::now(),
             source: "test".to_string(),
-            payload: EventPayload::AgentThought("sentinel".to_string()),
+            payload: EventPayload::Reasoning("sentinel".to_string()),
             trace_id: None,
+            correlation_id: None,
         };
         let _ = bus.publish(known_event);
 
-        handle_upstream_message("not json at all", &bus);
+        handle_upstream_message("not json at all", &bus, None);
 
         let event = rx.recv().await.unwrap();
-        assert!(matches!(event.payload, EventPayload::AgentThought(_)));
+        assert!(matches!(event.payload, EventPayload::Reasoning(_)));
         assert!(rx.try_recv().is_err());
     }
 
     // ── HTML embedding ────────────────────────────────────────────────────────
 
+    /// The Cockpit page plus its script, as a browser loads them.
+    static COCKPIT_HTML: LazyLock<String> = LazyLock::new(|| {
+        ["index.html", "cockpit.js"]
+            .into_iter()
+            .map(|path| {
+                String::from_utf8_lossy(&StaticAsset::get(path).unwrap().body).into_owned()
+            })
+            .collect()
+    });
+
     #[test]
     fn cockpit_html_is_non_empty() {
         assert!(!COCKPIT_HTML.is_empty(), "embedded Cockpit HTML must not be empty");
@@ -763,6 +1740,127 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn frontend_assets_are_negotiated_and_cached() {
+        /// Send `request`; return the response head and body.
+        async fn fetch(port: u16, request: &str) -> (String, Vec<u8>) {
+            let mut stream = tokio::net::TcpStream::connect(("127.0.0.1", port))
+                .await
+                .unwrap();
+            stream.write_all(request.as_bytes()).await.unwrap();
+            let mut response = Vec::new();
+            stream.read_to_end(&mut response).await.unwrap();
+            let end = response.windows(4).position(|w| w == b"\r\n\r\n").unwrap();
+            let body = response.split_off(end + 4);
+            (String::from_utf8(response).unwrap(), body)
+        }
+
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let server = tokio::spawn(CockpitServer::new(make_bus()).with_port(port).run());
+        while tokio::net::TcpStream::connect(("127.0.0.1", port)).await.is_err() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        // A client-side route gets the index page.
+        let (head, body) = fetch(port, "GET /robots/rover-b HTTP/1.1\r\n\r\n").await;
+        assert!(head.starts_with("HTTP/1.1 200 OK"), "got: {head}");
+        assert!(head.contains("Content-Type: text/html; charset=utf-8"));
+        assert!(head.contains("Cache-Control: no-cache"));
+        assert!(String::from_utf8(body).unwrap().contains("/cockpit.js"));
+
+        let script = StaticAsset::get("cockpit.js").unwrap();
+        let (head, body) = fetch(
+            port,
+            "GET /cockpit.js HTTP/1.1\r\nAccept-Encoding: gzip, br\r\n\r\n",
+        )
+        .await;
+        assert!(head.contains("Content-Encoding: gzip"), "got: {head}");
+        assert!(head.contains(&format!("ETag: {}", script.etag)));
+        assert_eq!(body, &script.gzipped()[..]);
+
+        let revalidate = format!(
+            "GET /cockpit.js HTTP/1.1\r\nIf-None-Match: {}\r\n\r\n",
+            script.etag
+        );
+        let (head, body) = fetch(port, &revalidate).await;
+        assert!(head.starts_with("HTTP/1.1 304 Not Modified"), "got: {head}");
+        assert!(body.is_empty());
+
+        let (head, _) = fetch(port, "GET /missing.js HTTP/1.1\r\n\r\n").await;
+        assert!(head.starts_with("HTTP/1.1 404 Not Found"), "got: {head}");
+        let (head, _) = fetch(port, "DELETE / HTTP/1.1\r\n\r\n").await;
+        assert!(head.starts_with("HTTP/1.1 405 Method Not Allowed"), "got: {head}");
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn logs_endpoint_filters_captured_records() {
+        use mechos_middleware::log_buffer::LogRecord;
+
+        let logs = LogBuffer::new(10);
+        for (level, target, message) in [
+            (tracing::Level::WARN, "mechos_kernel::gate", "intent rejected"),
+            (tracing::Level::INFO, "mechos_kernel::gate", "intent approved"),
+            (tracing::Level::WARN, "mechos_middleware::supervisor", "reconnecting"),
+        ] {
+            logs.push(LogRecord {
+                timestamp: Utc::now(),
+                level,
+                target: target.to_string(),
+                message: message.to_string(),
+            });
+        }
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let server = tokio::spawn(
+            CockpitServer::new(make_bus())
+                .with_port(port)
+                .with_log_buffer(logs)
+                .run(),
+        );
+        let get = |path: &'static str| async move {
+            loop {
+                if let Ok(mut stream) = tokio::net::TcpStream::connect(("127.0.0.1", port)).await {
+                    let request = format!("GET {path} HTTP/1.1\r\n\r\n");
+                    stream.write_all(request.as_bytes()).await.unwrap();
+                    let mut response = String::new();
+                    stream.read_to_string(&mut response).await.unwrap();
+                    return response;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        };
+        let messages = |response: String| -> Vec<String> {
+            let (_, body) = response.split_once("\r\n\r\n").unwrap();
+            let json: Value = serde_json::from_str(body).unwrap();
+            json["logs"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|r| r["message"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        assert_eq!(messages(get("/api/logs").await).len(), 3);
+        assert_eq!(
+            messages(get("/api/logs?level=warn&target=kernel").await),
+            ["intent rejected"]
+        );
+        assert_eq!(messages(get("/api/logs?limit=1").await), ["reconnecting"]);
+        let bad = get("/api/logs?level=loud").await;
+        assert!(bad.starts_with("HTTP/1.1 400 Bad Request"), "got: {bad}");
+
+        server.abort();
+    }
+
     // ── Input size validation ─────────────────────────────────────────────────
 
     #[test]
@@ -776,7 +1874,7 @@ mod tests {
         // No subscriber – but if handle_upstream_message respects the size
         // limit it will return before trying to publish, which means no
         // attempt to send on the bus and no panic.
-        handle_upstream_message(&oversized, &bus);
+        handle_upstream_message(&oversized, &bus, None);
         // If we reach here the oversized message was correctly discarded.
     }
 
@@ -801,7 +1899,7 @@ mod tests {
             "test message must be exactly at the size limit"
         );
 
-        handle_upstream_message(&msg, &bus);
+        handle_upstream_message(&msg, &bus, None);
 
         // A valid cmd_vel override at the size limit must still be published.
         // publish() is synchronous so the event is immediately in the channel.
@@ -810,4 +1908,669 @@ mod tests {
             "message at size limit should be accepted and published"
         );
     }
+
+    // ── Event history ────────────────────────────────────────────────────────
+
+    #[test]
+    fn with_history_len_overrides_default() {
+        let server = CockpitServer::new(make_bus());
+        assert_eq!(server.history_len(), DEFAULT_HISTORY_LEN);
+        assert_eq!(server.with_history_len(5).history_len(), 5);
+    }
+
+    #[test]
+    fn with_robot_adds_and_replaces_robots() {
+        let server = CockpitServer::new(make_bus())
+            .with_robot_id("rover-a")
+            .with_robot("rover-b", make_bus())
+            .with_robot("rover-c", make_bus())
+            .with_robot("rover-b", make_bus())
+            .with_robot("rover-a", make_bus());
+        assert_eq!(server.robot_ids(), ["rover-a", "rover-c", "rover-b"]);
+        assert_eq!(CockpitServer::new(make_bus()).robot_ids(), [LOCAL_ROBOT]);
+    }
+
+    #[tokio::test]
+    async fn late_joining_client_receives_snapshot() {
+        let bus = make_bus();
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let server = tokio::spawn(CockpitServer::new(Arc::clone(&bus)).with_port(port).run());
+        // The history recorder subscribes before the listener binds.
+        while tokio::net::TcpStream::connect(("127.0.0.1", port)).await.is_err() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        bus.publish(Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: "mechos-runtime::agent_loop".to_string(),
+            payload: EventPayload::Reasoning("Heading to the east door".to_string()),
+            trace_id: None,
+            correlation_id: None,
+        })
+        .unwrap();
+
+        let url = format!("ws://127.0.0.1:{port}/ws");
+        let mut frame = None;
+        for _ in 0..50 {
+            let (mut ws, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+            let robots = ws.next().await.unwrap().unwrap().into_text().unwrap();
+            assert!(
+                robots.contains(r#""op":"robots""#),
+                "the robot list comes first"
+            );
+            if let Ok(Some(Ok(msg))) =
+                tokio::time::timeout(std::time::Duration::from_millis(100), ws.next()).await
+            {
+                frame = Some(msg.into_text().unwrap());
+                break;
+            }
+        }
+        assert!(frame.unwrap().contains("Heading to the east door"));
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn shutdown_closes_tabs_and_the_listener() {
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let shutdown = CancellationToken::new();
+        let server = tokio::spawn(
+            CockpitServer::new(make_bus())
+                .with_port(port)
+                .with_shutdown(shutdown.clone())
+                .run(),
+        );
+        let url = format!("ws://127.0.0.1:{port}/ws");
+        let mut ws = loop {
+            if let Ok((ws, _)) = tokio_tungstenite::connect_async(&url).await {
+                break ws;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        };
+        let robots = ws.next().await.unwrap().unwrap();
+        assert!(robots.into_text().unwrap().contains(r#""op":"robots""#));
+
+        shutdown.cancel();
+        let close = tokio::time::timeout(Duration::from_secs(2), ws.next())
+            .await
+            .expect("the tab is told the server is going away");
+        assert!(matches!(
+            close,
+            Some(Ok(Message::Close(Some(ref frame)))) if frame.code == CloseCode::Away
+        ));
+        drop(ws);
+        let result = tokio::time::timeout(Duration::from_secs(2), server)
+            .await
+            .expect("run returns once drained");
+        assert!(result.unwrap().is_ok());
+        assert!(tokio::net::TcpStream::connect(("127.0.0.1", port)).await.is_err());
+    }
+
+    // ── Camera relay ─────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn camera_frames_are_served_by_id_and_as_mjpeg() {
+        use base64::Engine;
+
+        let bus = make_bus();
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let server = tokio::spawn(CockpitServer::new(Arc::clone(&bus)).with_port(port).run());
+        while tokio::net::TcpStream::connect(("127.0.0.1", port)).await.is_err() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        let mut mjpeg = tokio::net::TcpStream::connect(("127.0.0.1", port))
+            .await
+            .unwrap();
+        mjpeg
+            .write_all(b"GET /cameras/front/mjpeg HTTP/1.1\r\n\r\n")
+            .await
+            .unwrap();
+        let mut header = vec![0u8; 512];
+        let n = mjpeg.read(&mut header).await.unwrap();
+        assert!(String::from_utf8_lossy(&header[..n]).contains("multipart/x-mixed-replace"));
+
+        bus.publish(Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: "mechos-middleware::ros2/camera".to_string(),
+            payload: EventPayload::CameraFrame {
+                image_id: "front/7".to_string(),
+                format: mechos_types::ImageFormat::Mono8,
+                width: 2,
+                height: 2,
+                data_b64: base64::engine::general_purpose::STANDARD.encode([0u8, 80, 160, 240]),
+            },
+            trace_id: None,
+            correlation_id: None,
+        })
+        .unwrap();
+        let mut part = vec![0u8; 4096];
+        let n = tokio::time::timeout(std::time::Duration::from_secs(5), mjpeg.read(&mut part))
+            .await
+            .unwrap()
+            .unwrap();
+        let part = String::from_utf8_lossy(&part[..n]);
+        assert!(
+            part.starts_with("--mechosframe\r\nContent-Type: image/jpeg"),
+            "got: {part}"
+        );
+
+        let mut image = tokio::net::TcpStream::connect(("127.0.0.1", port))
+            .await
+            .unwrap();
+        image
+            .write_all(b"GET /images/front/7 HTTP/1.1\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = Vec::new();
+        image.read_to_end(&mut response).await.unwrap();
+        let response = String::from_utf8_lossy(&response);
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("Content-Type: image/jpeg"));
+
+        server.abort();
+    }
+
+    // ── TLS ──────────────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn run_fails_without_tls_certificate() {
+        let dir = std::env::temp_dir().join(format!("mechos-cockpit-{}", Uuid::new_v4()));
+        let tls = TlsConfig::new(dir.join("cert.pem"), dir.join("key.pem"));
+        let server = CockpitServer::new(make_bus()).with_port(0).with_tls(tls);
+        assert!(server.tls().is_some());
+        assert!(matches!(server.run().await, Err(MechError::Serialization(_))));
+    }
+
+    #[tokio::test]
+    async fn tls_server_serves_dashboard_over_https() {
+        use tokio_rustls::rustls::crypto::ring;
+        use tokio_rustls::rustls::pki_types::ServerName;
+        use tokio_rustls::rustls::{ClientConfig, RootCertStore};
+
+        let dir = std::env::temp_dir().join(format!("mechos-cockpit-{}", Uuid::new_v4()));
+        let tls = TlsConfig::new(dir.join("cert.pem"), dir.join("key.pem"))
+            .with_self_signed(["localhost"]);
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let server = tokio::spawn(
+            CockpitServer::new(make_bus())
+                .with_port(port)
+                .with_tls(tls.clone())
+                .run(),
+        );
+        let tcp = loop {
+            match tokio::net::TcpStream::connect(("127.0.0.1", port)).await {
+                Ok(stream) => break stream,
+                Err(_) => tokio::time::sleep(std::time::Duration::from_millis(10)).await,
+            }
+        };
+
+        let mut roots = RootCertStore::empty();
+        let pem = std::fs::read(&tls.cert_path).unwrap();
+        for cert in rustls_pemfile::certs(&mut pem.as_slice()) {
+            roots.add(cert.unwrap()).unwrap();
+        }
+        let config = ClientConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        let mut stream = tokio_rustls::TlsConnector::from(Arc::new(config))
+            .connect(ServerName::try_from("localhost").unwrap(), tcp)
+            .await
+            .unwrap();
+        // The script picks `wss://` when the page came over HTTPS.
+        stream
+            .write_all(b"GET /cockpit.js HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).await.ok();
+        let response = String::from_utf8_lossy(&response);
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "got: {response}");
+        assert!(response.contains("wss"));
+
+        server.abort();
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    // ── Safety controls ──────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn estop_is_acknowledged_once_confirmed() {
+        let bus = make_bus();
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let server = tokio::spawn(CockpitServer::new(Arc::clone(&bus)).with_port(port).run());
+        while tokio::net::TcpStream::connect(("127.0.0.1", port)).await.is_err() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        // Stand-in for the agent loop: confirm every request as latched.
+        let mut requests = bus.subscribe();
+        let agent_bus = Arc::clone(&bus);
+        let agent = tokio::spawn(async move {
+            while let Ok(event) = requests.recv().await {
+                if let EventPayload::SafetyRequest { request_id, .. } = event.payload {
+                    agent_bus
+                        .publish(Event {
+                            id: Uuid::new_v4(),
+                            timestamp: Utc::now(),
+                            source: "mechos-runtime::agent_loop".to_string(),
+                            payload: EventPayload::SafetyStatus {
+                                request_id: Some(request_id),
+                                estopped: true,
+                                mode: mechos_types::SafetyMode::Normal,
+                            },
+                            trace_id: None,
+                            correlation_id: None,
+                        })
+                        .unwrap();
+                }
+            }
+        });
+
+        let url = format!("ws://127.0.0.1:{port}/ws");
+        let (mut ws, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+        ws.send(Message::Text(
+            r#"{"topic":"/safety/estop","msg":{"id":"stop-1"}}"#.into(),
+        ))
+        .await
+        .unwrap();
+        let ack = loop {
+            let msg = tokio::time::timeout(std::time::Duration::from_secs(2), ws.next())
+                .await
+                .expect("e-stop was not acknowledged")
+                .unwrap()
+                .unwrap();
+            let json: Value = serde_json::from_str(msg.to_text().unwrap()).unwrap();
+            if json["op"] == "safety_ack" {
+                break json;
+            }
+        };
+        assert_eq!(ack["id"], "stop-1");
+        assert_eq!(ack["estopped"], true);
+
+        agent.abort();
+        server.abort();
+    }
+
+    // ── Operator approval ────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn approve_message_publishes_operator_decision() {
+        let bus = make_bus();
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let server = tokio::spawn(CockpitServer::new(Arc::clone(&bus)).with_port(port).run());
+        while tokio::net::TcpStream::connect(("127.0.0.1", port)).await.is_err() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let mut decisions = bus.subscribe();
+
+        let url = format!("ws://127.0.0.1:{port}/ws");
+        let (mut ws, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+        ws.send(Message::Text(r#"{"op":"approve","id":"a1"}"#.into()))
+            .await
+            .unwrap();
+        let nack = loop {
+            let msg = tokio::time::timeout(std::time::Duration::from_secs(2), ws.next())
+                .await
+                .expect("anonymous approval was not nacked")
+                .unwrap()
+                .unwrap();
+            let json: Value = serde_json::from_str(msg.to_text().unwrap()).unwrap();
+            if json["op"] == "approval_nack" {
+                break json;
+            }
+        };
+        assert_eq!(nack["id"], "a1");
+
+        ws.send(Message::Text(
+            r#"{"op":"approve","id":"a1","operator":"alice"}"#.into(),
+        ))
+        .await
+        .unwrap();
+        let event = tokio::time::timeout(std::time::Duration::from_secs(2), decisions.recv())
+            .await
+            .expect("no decision was published")
+            .unwrap();
+        assert!(matches!(
+            event.payload,
+            EventPayload::ApprovalDecision { approved: true, ref operator, .. } if operator == "alice"
+        ));
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn operator_tokens_stamp_answers_and_refuse_strangers() {
+        let bus = make_bus();
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let server = tokio::spawn(
+            CockpitServer::new(Arc::clone(&bus))
+                .with_port(port)
+                .with_operator_token("t-alice", "alice")
+                .run(),
+        );
+        while tokio::net::TcpStream::connect(("127.0.0.1", port)).await.is_err() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let mut answers = bus.subscribe();
+
+        let url = format!("ws://127.0.0.1:{port}/ws");
+        let (mut ws, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+        ws.send(Message::Text(
+            r#"{"op":"publish","topic":"/hitl/human_response","msg":{"response":"Go","question_id":"q-1"},"operator":"alice"}"#.into(),
+        ))
+        .await
+        .unwrap();
+        let nack = loop {
+            let msg = tokio::time::timeout(std::time::Duration::from_secs(2), ws.next())
+                .await
+                .expect("impersonated answer was not nacked")
+                .unwrap()
+                .unwrap();
+            let json: Value = serde_json::from_str(msg.to_text().unwrap()).unwrap();
+            if json["op"] == "hitl_nack" {
+                break json;
+            }
+        };
+        assert_eq!(nack["question_id"], "q-1");
+
+        ws.send(Message::Text(
+            r#"{"op":"publish","topic":"/hitl/human_response","msg":{"response":"Go","question_id":"q-1"},"operator":"mallory","token":"t-alice"}"#.into(),
+        ))
+        .await
+        .unwrap();
+        let event = tokio::time::timeout(std::time::Duration::from_secs(2), answers.recv())
+            .await
+            .expect("no answer was published")
+            .unwrap();
+        assert!(matches!(
+            event.payload,
+            EventPayload::HumanResponse(ref answer) if answer.operator.as_deref() == Some("alice")
+        ));
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn required_operator_tokens_refuse_claimed_names() {
+        let bus = make_bus();
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let server = tokio::spawn(
+            CockpitServer::new(Arc::clone(&bus))
+                .with_port(port)
+                .with_operator_tokens_required()
+                .run(),
+        );
+        while tokio::net::TcpStream::connect(("127.0.0.1", port)).await.is_err() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let mut answers = bus.subscribe();
+
+        let url = format!("ws://127.0.0.1:{port}/ws");
+        let (mut ws, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+        ws.send(Message::Text(
+            r#"{"op":"publish","topic":"/hitl/human_response","msg":{"response":"Go","question_id":"q-1"},"operator":"alice"}"#.into(),
+        ))
+        .await
+        .unwrap();
+        let nack = loop {
+            let msg = tokio::time::timeout(std::time::Duration::from_secs(2), ws.next())
+                .await
+                .expect("claimed name was not nacked")
+                .unwrap()
+                .unwrap();
+            let json: Value = serde_json::from_str(msg.to_text().unwrap()).unwrap();
+            if json["op"] == "hitl_nack" {
+                break json;
+            }
+        };
+        assert_eq!(nack["error"], "missing operator token");
+        while let Ok(event) = answers.try_recv() {
+            assert!(!matches!(event.payload, EventPayload::HumanResponse(_)));
+        }
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn silent_override_is_stopped_and_released() {
+        let bus = make_bus();
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let server = tokio::spawn(
+            CockpitServer::new(Arc::clone(&bus))
+                .with_port(port)
+                .with_override_timeout(std::time::Duration::from_millis(100))
+                .run(),
+        );
+        while tokio::net::TcpStream::connect(("127.0.0.1", port)).await.is_err() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let mut overrides = bus.subscribe();
+
+        let url = format!("ws://127.0.0.1:{port}/ws");
+        let (mut ws, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+        ws.send(Message::Text(
+            r#"{"op":"publish","topic":"/cmd_vel","source":"dashboard_override","msg":{"linear":{"x":0.5},"angular":{"z":0.0}}}"#.into(),
+        ))
+        .await
+        .unwrap();
+        // The tab goes silent with the stick deflected.
+        let stop = loop {
+            let event = tokio::time::timeout(std::time::Duration::from_secs(2), overrides.recv())
+                .await
+                .expect("silent override was never stopped")
+                .unwrap();
+            let EventPayload::AdapterCommand { json: frame, .. } = event.payload else {
+                continue;
+            };
+            if frame["release"] == true {
+                break frame;
+            }
+        };
+        assert_eq!(stop["msg"]["linear"]["x"], 0.0);
+
+        server.abort();
+    }
+
+    // ── Safety policy endpoint ───────────────────────────────────────────────
+
+    /// Send a raw HTTP request and return the status code and body.
+    async fn http(port: u16, request: &str) -> (u16, Value) {
+        let mut stream = tokio::net::TcpStream::connect(("127.0.0.1", port))
+            .await
+            .unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        let status = response[9..12].parse().unwrap();
+        let body = response.split_once("\r\n\r\n").unwrap().1;
+        (status, serde_json::from_str(body).unwrap_or(Value::Null))
+    }
+
+    #[tokio::test]
+    async fn policy_endpoint_requires_the_admin_token() {
+        let bus = make_bus();
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let server = tokio::spawn(
+            CockpitServer::new(Arc::clone(&bus))
+                .with_port(port)
+                .with_admin_token("s3cret")
+                .run(),
+        );
+        while tokio::net::TcpStream::connect(("127.0.0.1", port)).await.is_err() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        // Stand-in for the agent loop: refuse every change but listing.
+        let mut requests = bus.subscribe();
+        let responder_bus = Arc::clone(&bus);
+        let responder = tokio::spawn(async move {
+            while let Ok(event) = requests.recv().await {
+                let EventPayload::PolicyRequest {
+                    request_id,
+                    command,
+                    ..
+                } = event.payload
+                else {
+                    continue;
+                };
+                let error = (command != mechos_types::PolicyCommand::List)
+                    .then(|| "no rule named \"warp_drive\"".to_string());
+                let _ = responder_bus.publish(Event {
+                    id: Uuid::new_v4(),
+                    timestamp: Utc::now(),
+                    source: "test".to_string(),
+                    payload: EventPayload::PolicyStatus {
+                        request_id,
+                        report: Box::default(),
+                        error,
+                    },
+                    trace_id: None,
+                    correlation_id: None,
+                });
+            }
+        });
+
+        let (status, _) = http(port, "GET /api/policy HTTP/1.1\r\n\r\n").await;
+        assert_eq!(status, 401);
+
+        let (status, report) = http(
+            port,
+            "GET /api/policy HTTP/1.1\r\nAuthorization: Bearer s3cret\r\n\r\n",
+        )
+        .await;
+        assert_eq!(status, 200);
+        assert!(report["rules"].is_array());
+
+        let body = r#"{"operator":"alice","command":{"set_rule_mode":{"rule":"warp_drive","mode":"warn"}}}"#;
+        let (status, report) = http(
+            port,
+            &format!(
+                "POST /api/policy HTTP/1.1\r\nAuthorization: Bearer s3cret\r\nContent-Length: {}\r\n\r\n{body}",
+                body.len()
+            ),
+        )
+        .await;
+        assert_eq!(status, 422);
+        assert_eq!(report["error"], "no rule named \"warp_drive\"");
+
+        responder.abort();
+        server.abort();
+    }
+
+    // ── Multiple robots ──────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn events_are_tagged_by_robot_and_commands_follow_selection() {
+        let (rover_a, rover_b) = (make_bus(), make_bus());
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let server = tokio::spawn(
+            CockpitServer::new(Arc::clone(&rover_a))
+                .with_robot_id("rover-a")
+                .with_robot("rover-b", Arc::clone(&rover_b))
+                .with_port(port)
+                .run(),
+        );
+        while tokio::net::TcpStream::connect(("127.0.0.1", port)).await.is_err() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let url = format!("ws://127.0.0.1:{port}/ws");
+        let (mut ws, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+        let mut next_frame = async || -> Value {
+            let msg = tokio::time::timeout(std::time::Duration::from_secs(2), ws.next())
+                .await
+                .expect("no frame from the cockpit")
+                .unwrap()
+                .unwrap();
+            serde_json::from_str(msg.to_text().unwrap()).unwrap()
+        };
+        let robots = next_frame().await;
+        assert_eq!(robots["op"], "robots");
+        assert_eq!(robots["robots"], serde_json::json!(["rover-a", "rover-b"]));
+        assert_eq!(robots["selected"], "rover-a");
+
+        rover_b
+            .publish(Event {
+                id: Uuid::new_v4(),
+                timestamp: Utc::now(),
+                source: "mechos-runtime::agent_loop".to_string(),
+                payload: EventPayload::Reasoning("rover-b reporting".to_string()),
+                trace_id: None,
+                correlation_id: None,
+            })
+            .unwrap();
+        let frame = next_frame().await;
+        assert_eq!(frame["robot_id"], "rover-b");
+        assert_eq!(frame["payload"]["Reasoning"], "rover-b reporting");
+
+        let mut b_rx = rover_b.subscribe();
+        ws.send(Message::Text(
+            r#"{"op":"select_robot","robot_id":"rover-b"}"#.into(),
+        ))
+        .await
+        .unwrap();
+        ws.send(Message::Text(
+            r#"{"topic":"/agent/mode","msg":{"paused":true}}"#.into(),
+        ))
+        .await
+        .unwrap();
+        let toggle = tokio::time::timeout(std::time::Duration::from_secs(2), async {
+            loop {
+                let event = b_rx.recv().await.unwrap();
+                if let EventPayload::AgentModeToggle { paused } = event.payload {
+                    return paused;
+                }
+            }
+        })
+        .await
+        .expect("the mode toggle did not reach rover-b");
+        assert!(toggle);
+
+        server.abort();
+    }
 }
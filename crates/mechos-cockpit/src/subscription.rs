@@ -0,0 +1,263 @@
+//! [`ClientSubscription`] – which bus events one Cockpit tab receives.
+//!
+//! By default every WebSocket client receives every bus event.  A tab can
+//! narrow that down and throttle heavy streams by sending:
+//!
+//! ```json
+//! {"op":"subscribe","topics":["SensorHighRate","CognitiveStream"],"max_rate_hz":{"LidarScan":2}}
+//! ```
+//!
+//! * `topics` – the [`Topic`]s to forward, by name (`"Telemetry"` or
+//!   `"telemetry"`).  Omitted means every topic.
+//! * `max_rate_hz` – upper rate per topic or per payload kind (e.g.
+//!   `"LidarScan"`; `"Telemetry"` names the topic).  A payload-kind rate
+//!   wins over its topic's.  Limits apply to each payload kind separately,
+//!   so LiDAR scans throttled to 2 Hz never crowd out camera frames on the
+//!   same lane; events over the limit are dropped for that client.
+//!
+//! Each `subscribe` message replaces the previous subscription.
+
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use mechos_middleware::Topic;
+use mechos_middleware::typed::home_topic;
+use mechos_types::{Event, EventPayload};
+use serde_json::Value;
+use tracing::warn;
+
+/// Topic filter and rate limits of one WebSocket client.
+#[derive(Debug, Clone, Default)]
+pub struct ClientSubscription {
+    /// `None` forwards every topic.
+    topics: Option<HashSet<Topic>>,
+    /// Minimum spacing between two forwarded events, by topic or payload
+    /// kind name.
+    min_interval: HashMap<String, Duration>,
+    last_sent: HashMap<&'static str, Instant>,
+}
+
+impl ClientSubscription {
+    /// Parse a `{"op":"subscribe",…}` message; `None` for any other message.
+    ///
+    /// Unknown topic names and non-positive rates are ignored with a warning.
+    pub fn parse(json: &Value) -> Option<Self> {
+        if json.get("op").and_then(Value::as_str) != Some("subscribe") {
+            return None;
+        }
+        let topics = json.get("topics").and_then(Value::as_array).map(|names| {
+            names
+                .iter()
+                .filter_map(Value::as_str)
+                .filter_map(|name| {
+                    let topic = parse_topic(name);
+                    if topic.is_none() {
+                        warn!(topic = name, "cockpit subscription names an unknown topic");
+                    }
+                    topic
+                })
+                .collect()
+        });
+        let mut min_interval = HashMap::new();
+        if let Some(rates) = json.get("max_rate_hz").and_then(Value::as_object) {
+            for (name, rate) in rates {
+                match rate.as_f64() {
+                    Some(hz) if hz > 0.0 => {
+                        let key = match parse_topic(name) {
+                            Some(topic) => topic.as_str().to_string(),
+                            // The former name of `Reasoning`.
+                            None if name == "AgentThought" => "Reasoning".to_string(),
+                            None => name.clone(),
+                        };
+                        min_interval.insert(key, Duration::from_secs_f64(1.0 / hz));
+                    }
+                    _ => warn!(key = %name, "cockpit subscription rate must be a positive number"),
+                }
+            }
+        }
+        Some(Self {
+            topics,
+            min_interval,
+            last_sent: HashMap::new(),
+        })
+    }
+
+    /// Decide whether to forward `event` now, recording it as sent if so.
+    pub fn admit(&mut self, event: &Event) -> bool {
+        self.admit_at(event, Instant::now())
+    }
+
+    fn admit_at(&mut self, event: &Event, now: Instant) -> bool {
+        let topic = home_topic(&event.payload);
+        if self
+            .topics
+            .as_ref()
+            .is_some_and(|topics| !topics.contains(&topic))
+        {
+            return false;
+        }
+        let kind = payload_kind(&event.payload);
+        let Some(interval) = self
+            .min_interval
+            .get(kind)
+            .or_else(|| self.min_interval.get(topic.as_str()))
+        else {
+            return true;
+        };
+        match self.last_sent.get(kind) {
+            Some(last) if now.duration_since(*last) < *interval => false,
+            _ => {
+                self.last_sent.insert(kind, now);
+                true
+            }
+        }
+    }
+}
+
+/// Accept both the serialised (`"system_alerts"`) and the Rust
+/// (`"SystemAlerts"`) spelling of a topic.
+fn parse_topic(name: &str) -> Option<Topic> {
+    Topic::ALL
+        .into_iter()
+        .find(|topic| topic.as_str() == name || format!("{topic:?}") == name)
+}
+
+/// The [`EventPayload`] variant name, as it appears in the event JSON.
+fn payload_kind(payload: &EventPayload) -> &'static str {
+    match payload {
+        EventPayload::Telemetry(_) => "Telemetry",
+        EventPayload::HardwareFault { .. } => "HardwareFault",
+        EventPayload::Reasoning(_) => "Reasoning",
+        EventPayload::IntentDispatched(_) => "IntentDispatched",
+        EventPayload::AdapterCommand { .. } => "AdapterCommand",
+        EventPayload::HumanResponse(_) => "HumanResponse",
+        EventPayload::PeerMessage { .. } => "PeerMessage",
+        EventPayload::LidarScan { .. } => "LidarScan",
+        EventPayload::AgentModeToggle { .. } => "AgentModeToggle",
+        EventPayload::Intent(_) => "Intent",
+        EventPayload::PowerStatus { .. } => "PowerStatus",
+        EventPayload::CameraFrame { .. } => "CameraFrame",
+        EventPayload::Attitude { .. } => "Attitude",
+        EventPayload::GpsFix { .. } => "GpsFix",
+        EventPayload::ConnectionState { .. } => "ConnectionState",
+        EventPayload::BusHealth { .. } => "BusHealth",
+        EventPayload::TrackedObstacle { .. } => "TrackedObstacle",
+        EventPayload::TaskProgress { .. } => "TaskProgress",
+        EventPayload::TaskCompleted { .. } => "TaskCompleted",
+        EventPayload::MapSnapshot { .. } => "MapSnapshot",
+        EventPayload::SafetyRequest { .. } => "SafetyRequest",
+        EventPayload::SafetyStatus { .. } => "SafetyStatus",
+        EventPayload::ApprovalRequest { .. } => "ApprovalRequest",
+        EventPayload::ApprovalDecision { .. } => "ApprovalDecision",
+        EventPayload::PolicyRequest { .. } => "PolicyRequest",
+        EventPayload::PolicyStatus { .. } => "PolicyStatus",
+        EventPayload::IntentResult { .. } => "IntentResult",
+        EventPayload::Unknown(_) => "Unknown",
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use mechos_types::{HumanAnswer, Meters, TelemetryData};
+    use uuid::Uuid;
+
+    fn event(payload: EventPayload) -> Event {
+        Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: "test".to_string(),
+            payload,
+            trace_id: None,
+            correlation_id: None,
+        }
+    }
+
+    fn scan() -> Event {
+        event(EventPayload::LidarScan {
+            ranges: vec![1.0],
+            angle_min_rad: 0.0,
+            angle_increment_rad: 0.1,
+            stamp: None,
+        })
+    }
+
+    fn odometry() -> Event {
+        event(EventPayload::Telemetry(TelemetryData {
+            position_x: Meters(0.0),
+            position_y: Meters(0.0),
+            heading_rad: 0.0,
+            battery_percent: 80,
+            stamp: None,
+        }))
+    }
+
+    fn subscribe(json: &str) -> ClientSubscription {
+        ClientSubscription::parse(&serde_json::from_str(json).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn other_ops_are_not_subscriptions() {
+        let json = serde_json::json!({"op": "publish", "topic": "/agent/mode"});
+        assert!(ClientSubscription::parse(&json).is_none());
+    }
+
+    #[test]
+    fn default_subscription_forwards_everything() {
+        let mut subscription = ClientSubscription::default();
+        assert!(subscription.admit(&scan()));
+        assert!(subscription.admit(&scan()));
+        assert!(subscription.admit(&event(EventPayload::Reasoning("hi".to_string()))));
+    }
+
+    #[test]
+    fn topic_filter_accepts_both_spellings() {
+        let mut subscription =
+            subscribe(r#"{"op":"subscribe","topics":["Telemetry","cognitive_stream","Bogus"]}"#);
+        assert!(subscription.admit(&odometry()));
+        assert!(subscription.admit(&event(EventPayload::HumanResponse(HumanAnswer::new("yes")))));
+        assert!(!subscription.admit(&event(EventPayload::AgentModeToggle { paused: true })));
+    }
+
+    #[test]
+    fn payload_rate_throttles_only_that_kind() {
+        let mut subscription =
+            subscribe(r#"{"op":"subscribe","topics":["Telemetry","SensorHighRate"],"max_rate_hz":{"LidarScan":2}}"#);
+        let start = Instant::now();
+        assert!(subscription.admit_at(&scan(), start));
+        assert!(!subscription.admit_at(&scan(), start + Duration::from_millis(100)));
+        assert!(subscription.admit_at(&odometry(), start + Duration::from_millis(100)));
+        assert!(subscription.admit_at(&odometry(), start + Duration::from_millis(110)));
+        assert!(subscription.admit_at(&scan(), start + Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn topic_rate_applies_to_each_kind_on_the_lane() {
+        let mut subscription = subscribe(r#"{"op":"subscribe","max_rate_hz":{"Telemetry":1}}"#);
+        let start = Instant::now();
+        assert!(subscription.admit_at(&event(EventPayload::PowerStatus {
+            voltage: 12.0,
+            current: 1.0,
+            charging: false,
+            percent: 80.0,
+        }), start));
+        assert!(subscription.admit_at(&scan(), start + Duration::from_millis(500)));
+        assert!(subscription.admit_at(&odometry(), start));
+        assert!(!subscription.admit_at(&odometry(), start + Duration::from_millis(500)));
+        assert!(subscription.admit_at(&odometry(), start + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn former_agent_thought_name_throttles_reasoning() {
+        let mut subscription = subscribe(r#"{"op":"subscribe","max_rate_hz":{"AgentThought":1}}"#);
+        let thought = event(EventPayload::Reasoning("plan".to_string()));
+        let start = Instant::now();
+        assert!(subscription.admit_at(&thought, start));
+        assert!(!subscription.admit_at(&thought, start + Duration::from_millis(500)));
+    }
+}
@@ -0,0 +1,149 @@
+//! [`MotorFailsafe`] – zeroes a [`DiffDrive`] whose commands stop arriving.
+//!
+//! A [`DiffDrive`] expects a fresh `drive` command or a
+//! [`refresh`][DiffDrive::refresh] heartbeat within its command deadline
+//! ([`DEFAULT_COMMAND_DEADLINE`] unless changed).  The failsafe checks that
+//! deadline from a thread of its own, so a stalled async runtime, a wedged
+//! event bus or a frozen agent loop cannot keep the wheels turning: the
+//! motors are stopped and the trip is reported as an
+//! [`EventPayload::HardwareFault`].  It is the hardware-side complement to
+//! the kernel's software watchdog, which halts the robot only while the
+//! runtime itself is still running.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use mechos_types::EventPayload;
+
+use crate::motor::DiffDrive;
+
+/// Default time a [`DiffDrive`] keeps moving without a command or heartbeat.
+pub const DEFAULT_COMMAND_DEADLINE: Duration = Duration::from_millis(200);
+
+/// Shortest interval between two deadline checks.
+const MIN_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// A thread enforcing a [`DiffDrive`]'s command deadline; stops when
+/// dropped.
+pub struct MotorFailsafe {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MotorFailsafe {
+    /// Check `drive` four times per command deadline, passing the fault of
+    /// every trip to `on_trip`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the OS cannot spawn a thread.
+    pub fn spawn<F>(drive: Arc<Mutex<DiffDrive>>, on_trip: F) -> Self
+    where
+        F: Fn(EventPayload) + Send + 'static,
+    {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = Arc::clone(&stop);
+        let thread = std::thread::Builder::new()
+            .name("mechos-failsafe".to_string())
+            .spawn(move || {
+                while !stopped.load(Ordering::Acquire) {
+                    let (fault, poll) = {
+                        let mut drive = drive.lock().unwrap_or_else(|e| e.into_inner());
+                        (drive.check_deadline(), drive.command_deadline() / 4)
+                    };
+                    if let Some(fault) = fault {
+                        tracing::warn!(?fault, "motor failsafe tripped");
+                        on_trip(fault);
+                    }
+                    std::thread::park_timeout(poll.max(MIN_POLL_INTERVAL));
+                }
+            })
+            .expect("spawn the motor failsafe thread");
+        Self {
+            stop,
+            thread: Some(thread),
+        }
+    }
+}
+
+impl Drop for MotorFailsafe {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        if let Some(thread) = self.thread.take() {
+            thread.thread().unpark();
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::motor::MotorController;
+    use mechos_types::{FaultCode, MechError, Meters, MetersPerSecond, RadiansPerSecond};
+
+    struct Wheel {
+        id: &'static str,
+        velocity: Arc<Mutex<MetersPerSecond>>,
+    }
+
+    impl MotorController for Wheel {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn set_velocity(&mut self, velocity: MetersPerSecond) -> Result<(), MechError> {
+            *self.velocity.lock().unwrap() = velocity;
+            Ok(())
+        }
+
+        fn velocity(&self) -> MetersPerSecond {
+            *self.velocity.lock().unwrap()
+        }
+    }
+
+    #[test]
+    fn stalled_commands_zero_the_motors_once() {
+        let left = Arc::new(Mutex::new(MetersPerSecond::ZERO));
+        let drive = DiffDrive::new(
+            Box::new(Wheel { id: "left", velocity: Arc::clone(&left) }),
+            Box::new(Wheel { id: "right", velocity: Arc::new(Mutex::new(MetersPerSecond::ZERO)) }),
+            Meters(0.4),
+        )
+        .unwrap()
+        .with_command_deadline(Duration::from_millis(40));
+        let drive = Arc::new(Mutex::new(drive));
+        let trips = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&trips);
+        let _failsafe = MotorFailsafe::spawn(Arc::clone(&drive), move |fault| {
+            recorded.lock().unwrap().push(fault);
+        });
+
+        drive
+            .lock()
+            .unwrap()
+            .drive(MetersPerSecond(0.5), RadiansPerSecond::ZERO)
+            .unwrap();
+        // Heartbeats keep the base moving past the deadline.
+        for _ in 0..4 {
+            std::thread::sleep(Duration::from_millis(20));
+            drive.lock().unwrap().refresh();
+        }
+        assert_eq!(*left.lock().unwrap(), MetersPerSecond(0.5));
+        assert!(trips.lock().unwrap().is_empty());
+
+        std::thread::sleep(Duration::from_millis(150));
+        assert_eq!(*left.lock().unwrap(), MetersPerSecond::ZERO);
+        let trips = trips.lock().unwrap();
+        assert_eq!(trips.len(), 1);
+        assert!(matches!(
+            trips[0],
+            EventPayload::HardwareFault {
+                code: FaultCode::DeadlineExpired,
+                ..
+            }
+        ));
+    }
+}
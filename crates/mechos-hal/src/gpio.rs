@@ -0,0 +1,285 @@
+//! Linux GPIO and PWM drivers over sysfs.
+//!
+//! * [`SysfsGpioRelay`] – a [`Relay`] on a GPIO line
+//!   (`/sys/class/gpio/gpioN`), exported and set to output on first use.
+//! * [`SysfsPwmServo`] – an [`Actuator`] for a hobby servo on a PWM channel
+//!   (`/sys/class/pwm/pwmchipN/pwmM`), mapping the target angle linearly onto
+//!   the pulse width.
+//!
+//! Both only write small text files, so they work on any board whose kernel
+//! exposes the sysfs GPIO / PWM interfaces (Raspberry Pi, BeagleBone,
+//! Jetson, …) without extra libraries.  `with_root` points a driver at
+//! another directory, for boards that mount sysfs elsewhere and for tests.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use mechos_types::{FaultCode, MechError};
+
+use crate::actuator::Actuator;
+use crate::relay::Relay;
+
+/// Standard hobby-servo PWM period: 50 Hz.
+pub const DEFAULT_SERVO_PERIOD_NS: u64 = 20_000_000;
+
+fn write_attr(component: &str, path: &Path, value: &str) -> Result<(), MechError> {
+    fs::write(path, value).map_err(|e| MechError::HardwareFault {
+        code: FaultCode::Unknown,
+        component: component.to_string(),
+        details: format!("cannot write '{value}' to {}: {e}", path.display()),
+    })
+}
+
+/// A relay wired to a GPIO output line.
+pub struct SysfsGpioRelay {
+    id: String,
+    line: u32,
+    root: PathBuf,
+    active_low: bool,
+    exported: bool,
+    state: bool,
+}
+
+impl SysfsGpioRelay {
+    /// Create a relay on GPIO `line` under `/sys/class/gpio`.
+    pub fn new(id: impl Into<String>, line: u32) -> Self {
+        Self {
+            id: id.into(),
+            line,
+            root: PathBuf::from("/sys/class/gpio"),
+            active_low: false,
+            exported: false,
+            state: false,
+        }
+    }
+
+    /// Use `root` instead of `/sys/class/gpio`.
+    pub fn with_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.root = root.into();
+        self
+    }
+
+    /// Energise the relay by driving the line low (common for relay boards
+    /// with opto-isolated inputs).
+    pub fn with_active_low(mut self, active_low: bool) -> Self {
+        self.active_low = active_low;
+        self
+    }
+
+    fn line_dir(&self) -> PathBuf {
+        self.root.join(format!("gpio{}", self.line))
+    }
+
+    /// Export the line and make it an output, once.
+    fn export(&mut self) -> Result<(), MechError> {
+        if self.exported {
+            return Ok(());
+        }
+        if !self.line_dir().exists() {
+            write_attr(&self.id, &self.root.join("export"), &self.line.to_string())?;
+        }
+        write_attr(&self.id, &self.line_dir().join("direction"), "out")?;
+        self.exported = true;
+        Ok(())
+    }
+}
+
+impl Relay for SysfsGpioRelay {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn set_state(&mut self, active: bool) -> Result<(), MechError> {
+        self.export()?;
+        let level = if active != self.active_low { "1" } else { "0" };
+        write_attr(&self.id, &self.line_dir().join("value"), level)?;
+        self.state = active;
+        Ok(())
+    }
+
+    fn state(&self) -> bool {
+        self.state
+    }
+}
+
+/// A hobby servo driven by a PWM channel.
+///
+/// The angle range `[min_rad, max_rad]` maps linearly onto pulse widths
+/// `[min_pulse_ns, max_pulse_ns]`; targets outside the range are rejected
+/// with [`FaultCode::JointLimitExceeded`].
+pub struct SysfsPwmServo {
+    id: String,
+    chip: u32,
+    channel: u32,
+    root: PathBuf,
+    period_ns: u64,
+    min_pulse_ns: u64,
+    max_pulse_ns: u64,
+    min_rad: f32,
+    max_rad: f32,
+    enabled: bool,
+    position: f32,
+}
+
+impl SysfsPwmServo {
+    /// Create a servo on `pwmchip{chip}/pwm{channel}` under `/sys/class/pwm`,
+    /// with a 50 Hz period and 1–2 ms pulses spanning ±90°.
+    pub fn new(id: impl Into<String>, chip: u32, channel: u32) -> Self {
+        Self {
+            id: id.into(),
+            chip,
+            channel,
+            root: PathBuf::from("/sys/class/pwm"),
+            period_ns: DEFAULT_SERVO_PERIOD_NS,
+            min_pulse_ns: 1_000_000,
+            max_pulse_ns: 2_000_000,
+            min_rad: -std::f32::consts::FRAC_PI_2,
+            max_rad: std::f32::consts::FRAC_PI_2,
+            enabled: false,
+            position: 0.0,
+        }
+    }
+
+    /// Use `root` instead of `/sys/class/pwm`.
+    pub fn with_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.root = root.into();
+        self
+    }
+
+    /// Replace the pulse widths (ns) at the ends of the angle range.
+    pub fn with_pulse_range(mut self, min_pulse_ns: u64, max_pulse_ns: u64) -> Self {
+        self.min_pulse_ns = min_pulse_ns;
+        self.max_pulse_ns = max_pulse_ns;
+        self
+    }
+
+    /// Replace the angle range (rad) the pulse range spans.
+    pub fn with_angle_range(mut self, min_rad: f32, max_rad: f32) -> Self {
+        self.min_rad = min_rad;
+        self.max_rad = max_rad;
+        self
+    }
+
+    /// The pulse width (ns) that holds the servo at `angle_rad`.
+    pub fn pulse_ns(&self, angle_rad: f32) -> u64 {
+        let span = self.max_rad - self.min_rad;
+        let fraction = if span > 0.0 {
+            ((angle_rad - self.min_rad) / span).clamp(0.0, 1.0)
+        } else {
+            0.5
+        };
+        let pulses = self.max_pulse_ns as f64 - self.min_pulse_ns as f64;
+        (self.min_pulse_ns as f64 + pulses * fraction as f64).round() as u64
+    }
+
+    fn channel_dir(&self) -> PathBuf {
+        self.root
+            .join(format!("pwmchip{}", self.chip))
+            .join(format!("pwm{}", self.channel))
+    }
+
+    /// Export the channel, set its period and enable it, once.
+    fn enable(&mut self, duty_ns: u64) -> Result<(), MechError> {
+        if self.enabled {
+            return Ok(());
+        }
+        let chip_dir = self.root.join(format!("pwmchip{}", self.chip));
+        if !self.channel_dir().exists() {
+            write_attr(&self.id, &chip_dir.join("export"), &self.channel.to_string())?;
+        }
+        // The duty cycle must never exceed the period, so set it first.
+        write_attr(&self.id, &self.channel_dir().join("duty_cycle"), &duty_ns.to_string())?;
+        write_attr(&self.id, &self.channel_dir().join("period"), &self.period_ns.to_string())?;
+        write_attr(&self.id, &self.channel_dir().join("enable"), "1")?;
+        self.enabled = true;
+        Ok(())
+    }
+}
+
+impl Actuator for SysfsPwmServo {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn set_position(&mut self, target_rad: f32) -> Result<(), MechError> {
+        if !(self.min_rad..=self.max_rad).contains(&target_rad) {
+            return Err(MechError::HardwareFault {
+                code: FaultCode::JointLimitExceeded,
+                component: self.id.clone(),
+                details: format!(
+                    "target {target_rad} rad is outside [{}, {}]",
+                    self.min_rad, self.max_rad
+                ),
+            });
+        }
+        let duty_ns = self.pulse_ns(target_rad);
+        self.enable(duty_ns)?;
+        write_attr(&self.id, &self.channel_dir().join("duty_cycle"), &duty_ns.to_string())?;
+        self.position = target_rad;
+        Ok(())
+    }
+
+    fn position(&self) -> f32 {
+        self.position
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mechos-hal-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn gpio_relay_exports_and_writes_levels() {
+        let root = scratch_dir("gpio");
+        // The kernel creates the line directory on export; fake it here.
+        fs::create_dir_all(root.join("gpio17")).unwrap();
+        let mut relay = SysfsGpioRelay::new("pump", 17)
+            .with_root(&root)
+            .with_active_low(true);
+
+        relay.set_state(true).unwrap();
+        assert!(relay.state());
+        assert_eq!(fs::read_to_string(root.join("gpio17/direction")).unwrap(), "out");
+        assert_eq!(fs::read_to_string(root.join("gpio17/value")).unwrap(), "0");
+        relay.set_state(false).unwrap();
+        assert_eq!(fs::read_to_string(root.join("gpio17/value")).unwrap(), "1");
+
+        let mut missing = SysfsGpioRelay::new("x", 3).with_root(root.join("absent"));
+        assert!(matches!(
+            missing.set_state(true),
+            Err(MechError::HardwareFault { .. })
+        ));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn pwm_servo_maps_angles_to_pulses() {
+        let root = scratch_dir("pwm");
+        fs::create_dir_all(root.join("pwmchip0/pwm1")).unwrap();
+        let mut servo = SysfsPwmServo::new("pan", 0, 1).with_root(&root);
+        assert_eq!(servo.pulse_ns(0.0), 1_500_000);
+        assert_eq!(servo.pulse_ns(-std::f32::consts::FRAC_PI_2), 1_000_000);
+
+        servo.set_position(std::f32::consts::FRAC_PI_2).unwrap();
+        let attr = |name: &str| fs::read_to_string(root.join("pwmchip0/pwm1").join(name)).unwrap();
+        assert_eq!(attr("duty_cycle"), "2000000");
+        assert_eq!(attr("period"), "20000000");
+        assert_eq!(attr("enable"), "1");
+
+        assert!(matches!(
+            servo.set_position(2.0),
+            Err(MechError::HardwareFault {
+                code: FaultCode::JointLimitExceeded,
+                ..
+            })
+        ));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -19,17 +19,33 @@
 //!   [`HardwareIntent`][mechos_types::HardwareIntent] commands to them.
 //! - [`sim`] – [`SimRegistry`]: in-process simulation builder for CI/CD
 //!   testing without physical hardware.
+//! - [`motor`] – [`MotorController`] trait for velocity-controlled motors and
+//!   the [`DiffDrive`] base built from two of them.
+//! - [`failsafe`] – [`MotorFailsafe`]: zeroes a [`DiffDrive`] whose commands
+//!   and heartbeats stop arriving.
+//! - [`gpio`] – Linux sysfs drivers: [`SysfsGpioRelay`] and
+//!   [`SysfsPwmServo`].
+//! - [`serial_motor`] – RoboClaw and ODrive motor controllers over a serial
+//!   link.
 
 pub mod actuator;
 pub mod camera;
+pub mod failsafe;
+pub mod gpio;
+pub mod motor;
 pub mod pid;
 pub mod registry;
 pub mod relay;
+pub mod serial_motor;
 pub mod sim;
 
 pub use actuator::Actuator;
 pub use camera::{Camera, CameraFrame};
+pub use failsafe::MotorFailsafe;
+pub use gpio::{SysfsGpioRelay, SysfsPwmServo};
+pub use motor::{DiffDrive, MotorController};
 pub use pid::PidController;
 pub use registry::HardwareRegistry;
 pub use relay::Relay;
+pub use serial_motor::{ODrive, RoboClaw};
 pub use sim::SimRegistry;
@@ -0,0 +1,258 @@
+//! Generic `MotorController` trait for velocity-controlled motors, and the
+//! [`DiffDrive`] base that turns `(v, ω)` into wheel speeds.
+//!
+//! Wheel motors are commanded in metres per second at the tyre; each driver
+//! converts that into its own unit (encoder counts, turns, duty cycle) with
+//! the wheel geometry it was configured with.
+
+use std::time::{Duration, Instant};
+
+use mechos_types::{EventPayload, FaultCode, MechError, Meters, MetersPerSecond, RadiansPerSecond};
+
+use crate::failsafe::DEFAULT_COMMAND_DEADLINE;
+
+/// A velocity-controlled motor (drive wheel, track, conveyor, …).
+pub trait MotorController: Send + Sync {
+    /// Stable identifier for this motor, e.g. `"left_wheel"`.
+    fn id(&self) -> &str;
+
+    /// Command the motor to turn at `velocity` (positive = forward).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::HardwareFault`] if the command cannot be applied
+    /// (e.g. the controller does not answer or reports a fault).
+    fn set_velocity(&mut self, velocity: MetersPerSecond) -> Result<(), MechError>;
+
+    /// Return the most recently commanded velocity.
+    fn velocity(&self) -> MetersPerSecond;
+
+    /// Bring the motor to a stop.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::HardwareFault`] if the command cannot be applied.
+    fn stop(&mut self) -> Result<(), MechError> {
+        self.set_velocity(MetersPerSecond::ZERO)
+    }
+}
+
+/// A differential-drive base: two [`MotorController`]s a `track_width`
+/// apart.
+///
+/// Dropping the base stops both motors.  A moving base needs a new command
+/// or a [`refresh`][Self::refresh] within its command deadline, enforced by
+/// [`check_deadline`][Self::check_deadline] (usually from a
+/// [`MotorFailsafe`][crate::failsafe::MotorFailsafe]).
+pub struct DiffDrive {
+    left: Box<dyn MotorController>,
+    right: Box<dyn MotorController>,
+    track_width: Meters,
+    command_deadline: Duration,
+    last_refresh: Instant,
+}
+
+impl DiffDrive {
+    /// Create a base from its left and right motors and the distance between
+    /// the wheels' contact points.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::HardwareFault`] with
+    /// [`FaultCode::InvalidConfiguration`] if `track_width` is not positive.
+    pub fn new(
+        left: Box<dyn MotorController>,
+        right: Box<dyn MotorController>,
+        track_width: Meters,
+    ) -> Result<Self, MechError> {
+        if !track_width.get().is_finite() || track_width.get() <= 0.0 {
+            return Err(MechError::HardwareFault {
+                code: FaultCode::InvalidConfiguration,
+                component: "drive_base".to_string(),
+                details: format!("track width {track_width} must be positive"),
+            });
+        }
+        Ok(Self {
+            left,
+            right,
+            track_width,
+            command_deadline: DEFAULT_COMMAND_DEADLINE,
+            last_refresh: Instant::now(),
+        })
+    }
+
+    /// Replace the command deadline (default: [`DEFAULT_COMMAND_DEADLINE`]).
+    pub fn with_command_deadline(mut self, deadline: Duration) -> Self {
+        self.command_deadline = deadline;
+        self
+    }
+
+    /// How long the base keeps moving without a command or heartbeat.
+    pub fn command_deadline(&self) -> Duration {
+        self.command_deadline
+    }
+
+    /// Heartbeat: the runtime is alive, keep executing the last command.
+    pub fn refresh(&mut self) {
+        self.last_refresh = Instant::now();
+    }
+
+    /// Stop the base if it is moving and neither a command nor a heartbeat
+    /// arrived within the command deadline.  Returns the fault to report
+    /// for such a trip.
+    pub fn check_deadline(&mut self) -> Option<EventPayload> {
+        let silent = self.last_refresh.elapsed();
+        let (left, right) = self.velocities();
+        if silent <= self.command_deadline
+            || (left == MetersPerSecond::ZERO && right == MetersPerSecond::ZERO)
+        {
+            return None;
+        }
+        self.stop();
+        Some(EventPayload::HardwareFault {
+            component: "drive_base".to_string(),
+            code: FaultCode::DeadlineExpired,
+            message: format!(
+                "no command or heartbeat for {} ms (deadline {} ms); motors zeroed",
+                silent.as_millis(),
+                self.command_deadline.as_millis()
+            ),
+        })
+    }
+
+    /// The wheel speeds `(left, right)` that realise `(linear, angular)`.
+    pub fn wheel_speeds(
+        &self,
+        linear: MetersPerSecond,
+        angular: RadiansPerSecond,
+    ) -> (MetersPerSecond, MetersPerSecond) {
+        let turn = angular * Meters(self.track_width.get() / 2.0);
+        (linear - turn, linear + turn)
+    }
+
+    /// Drive at `linear` while turning at `angular`.  If one motor rejects
+    /// its command the other is stopped.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first motor's [`MechError::HardwareFault`].
+    pub fn drive(
+        &mut self,
+        linear: MetersPerSecond,
+        angular: RadiansPerSecond,
+    ) -> Result<(), MechError> {
+        self.refresh();
+        let (left, right) = self.wheel_speeds(linear, angular);
+        let result = self
+            .left
+            .set_velocity(left)
+            .and_then(|()| self.right.set_velocity(right));
+        if result.is_err() {
+            self.stop();
+        }
+        result
+    }
+
+    /// Stop both motors, ignoring individual failures so one faulty
+    /// controller cannot keep the other moving.
+    pub fn stop(&mut self) {
+        let _ = self.left.stop();
+        let _ = self.right.stop();
+    }
+
+    /// The most recently commanded `(left, right)` wheel speeds.
+    pub fn velocities(&self) -> (MetersPerSecond, MetersPerSecond) {
+        (self.left.velocity(), self.right.velocity())
+    }
+}
+
+impl Drop for DiffDrive {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    struct MockMotor {
+        id: String,
+        log: Arc<Mutex<Vec<(String, f32)>>>,
+        velocity: MetersPerSecond,
+        fail: bool,
+    }
+
+    impl MockMotor {
+        fn new(id: &str, log: &Arc<Mutex<Vec<(String, f32)>>>, fail: bool) -> Box<Self> {
+            Box::new(Self {
+                id: id.to_string(),
+                log: Arc::clone(log),
+                velocity: MetersPerSecond::ZERO,
+                fail,
+            })
+        }
+    }
+
+    impl MotorController for MockMotor {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn set_velocity(&mut self, velocity: MetersPerSecond) -> Result<(), MechError> {
+            if self.fail && velocity != MetersPerSecond::ZERO {
+                return Err(MechError::HardwareFault {
+                    code: FaultCode::Unknown,
+                    component: self.id.clone(),
+                    details: "stalled".to_string(),
+                });
+            }
+            self.velocity = velocity;
+            self.log.lock().unwrap().push((self.id.clone(), velocity.get()));
+            Ok(())
+        }
+
+        fn velocity(&self) -> MetersPerSecond {
+            self.velocity
+        }
+    }
+
+    #[test]
+    fn drive_splits_velocity_over_the_track() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut base = DiffDrive::new(
+            MockMotor::new("left", &log, false),
+            MockMotor::new("right", &log, false),
+            Meters(0.4),
+        )
+        .unwrap();
+        base.drive(MetersPerSecond(1.0), RadiansPerSecond(1.0)).unwrap();
+        let (left, right) = base.velocities();
+        assert!((left.get() - 0.8).abs() < 1e-6);
+        assert!((right.get() - 1.2).abs() < 1e-6);
+
+        drop(base);
+        let log = log.lock().unwrap();
+        assert_eq!(log[log.len() - 2..], [("left".to_string(), 0.0), ("right".to_string(), 0.0)]);
+    }
+
+    #[test]
+    fn failing_motor_stops_the_other() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut base = DiffDrive::new(
+            MockMotor::new("left", &log, false),
+            MockMotor::new("right", &log, true),
+            Meters(0.5),
+        )
+        .unwrap();
+        assert!(base.drive(MetersPerSecond(0.5), RadiansPerSecond::ZERO).is_err());
+        assert_eq!(base.velocities().0, MetersPerSecond::ZERO);
+        assert!(DiffDrive::new(
+            MockMotor::new("l", &log, false),
+            MockMotor::new("r", &log, false),
+            Meters(0.0)
+        )
+        .is_err());
+    }
+}
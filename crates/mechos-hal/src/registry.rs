@@ -15,7 +15,7 @@
 
 use std::collections::HashMap;
 
-use mechos_types::{HardwareIntent, MechError};
+use mechos_types::{FaultCode, HardwareIntent, MechError, Meters};
 use tracing::instrument;
 
 use crate::actuator::Actuator;
@@ -100,8 +100,9 @@ impl HardwareRegistry {
             // IK adapter is wired in, the registry stores x as the representative
             // position and reports the full target in any error message.
             // ----------------------------------------------------------------
-            HardwareIntent::MoveEndEffector { x, y, z } => {
-                self.actuate("end_effector", x).map_err(|_| MechError::HardwareFault {
+            HardwareIntent::MoveEndEffector { x, y, z, .. } => {
+                self.actuate("end_effector", x.get()).map_err(|_| MechError::HardwareFault {
+                    code: FaultCode::DeviceNotRegistered,
                     component: "end_effector".to_string(),
                     details: format!(
                         "end_effector actuator not registered (target x={x}, y={y}, z={z})"
@@ -111,16 +112,77 @@ impl HardwareRegistry {
 
             // ----------------------------------------------------------------
             // Differential drive: decompose (v, ω) → left/right wheel targets.
-            // Assumes a unit wheelbase (track width = 1 m or 1 rad unit).
+            // Assumes a unit wheelbase (track width = 1 m).
             // ----------------------------------------------------------------
             HardwareIntent::Drive {
                 linear_velocity,
                 angular_velocity,
             } => {
-                let left_target = linear_velocity - angular_velocity * 0.5;
-                let right_target = linear_velocity + angular_velocity * 0.5;
-                self.actuate("left_wheel", left_target)?;
-                self.actuate("right_wheel", right_target)?;
+                let turn = angular_velocity * Meters(0.5);
+                let left_target = linear_velocity - turn;
+                let right_target = linear_velocity + turn;
+                self.actuate("left_wheel", left_target.get())?;
+                self.actuate("right_wheel", right_target.get())?;
+                Ok(())
+            }
+
+            // ----------------------------------------------------------------
+            // Joint-space commands: forward to the actuator registered under
+            // the joint's name.  The gripper is the "gripper" actuator, whose
+            // position is the normalised closure (0.0 open … 1.0 closed).
+            // Velocity limits are enforced upstream by the kernel's
+            // `JointLimitRule`.
+            // ----------------------------------------------------------------
+            HardwareIntent::MoveJoint {
+                joint, angle_rad, ..
+            } => self.actuate(&joint, angle_rad),
+            HardwareIntent::SetGripper { position } => self.actuate("gripper", position),
+
+            // ----------------------------------------------------------------
+            // Path following needs a planner and localisation, which live in
+            // the navigation stack behind the middleware adapters.  The HAL
+            // only executes the resulting `Drive` commands.
+            // ----------------------------------------------------------------
+            HardwareIntent::FollowWaypoints { .. } => Err(MechError::HardwareFault {
+                code: FaultCode::Unsupported,
+                component: "drive_base".to_string(),
+                details: "FollowWaypoints must be executed by a navigation stack, \
+                          not dispatched to the HAL directly"
+                    .to_string(),
+            }),
+
+            // ----------------------------------------------------------------
+            // Docking is run by the charging-dock controller behind the
+            // middleware adapters; the HAL has no dock driver.
+            // ----------------------------------------------------------------
+            HardwareIntent::Dock | HardwareIntent::Undock => Err(MechError::HardwareFault {
+                code: FaultCode::Unsupported,
+                component: "dock".to_string(),
+                details: "Dock / Undock must be executed by a docking controller, \
+                          not dispatched to the HAL directly"
+                    .to_string(),
+            }),
+
+            // ----------------------------------------------------------------
+            // Flight intents go to a flight controller (ArduPilot / PX4)
+            // through the MAVLink adapter, which runs its own motor mixing.
+            // ----------------------------------------------------------------
+            HardwareIntent::Arm { .. }
+            | HardwareIntent::SetAltitude { .. }
+            | HardwareIntent::Goto { .. } => Err(MechError::HardwareFault {
+                code: FaultCode::Unsupported,
+                component: "flight_controller".to_string(),
+                details: "flight intents must be executed by a flight controller, \
+                          not dispatched to the HAL directly"
+                    .to_string(),
+            }),
+
+            // ----------------------------------------------------------------
+            // Halt: same zero-velocity E-stop as on drop.  Always succeeds so
+            // one faulty actuator cannot keep the others moving.
+            // ----------------------------------------------------------------
+            HardwareIntent::Halt { .. } => {
+                self.stop_all();
                 Ok(())
             }
 
@@ -131,6 +193,7 @@ impl HardwareRegistry {
                 match self.relays.get_mut(&relay_id) {
                     Some(relay) => relay.set_state(state),
                     None => Err(MechError::HardwareFault {
+                        code: FaultCode::DeviceNotRegistered,
                         component: relay_id.clone(),
                         details: format!("relay '{relay_id}' is not registered"),
                     }),
@@ -146,12 +209,26 @@ impl HardwareRegistry {
 
             // ----------------------------------------------------------------
             // Fleet communication intents are handled by the middleware layer
-            // (Ros2Adapter / DashboardSimAdapter); no physical actuator is
-            // involved at the HAL level.
+            // (FleetTransport / Ros2Adapter / DashboardSimAdapter); no
+            // physical actuator is involved at the HAL level.
             // ----------------------------------------------------------------
             HardwareIntent::MessagePeer { .. }
             | HardwareIntent::BroadcastFleet { .. }
             | HardwareIntent::PostTask { .. } => Ok(()),
+
+            // ----------------------------------------------------------------
+            // Audio intents are rendered by the TTS / sound nodes behind the
+            // middleware adapters; the HAL has no speaker driver.
+            // ----------------------------------------------------------------
+            HardwareIntent::Speak { .. } | HardwareIntent::PlaySound { .. } => Ok(()),
+        }
+    }
+
+    // Internal helper: command every actuator to position 0.0, ignoring
+    // individual failures.
+    fn stop_all(&mut self) {
+        for actuator in self.actuators.values_mut() {
+            let _ = actuator.set_position(0.0);
         }
     }
 
@@ -160,6 +237,7 @@ impl HardwareRegistry {
         match self.actuators.get_mut(id) {
             Some(act) => act.set_position(target_rad),
             None => Err(MechError::HardwareFault {
+                code: FaultCode::DeviceNotRegistered,
                 component: id.to_string(),
                 details: format!("actuator '{id}' is not registered"),
             }),
@@ -171,9 +249,7 @@ impl Drop for HardwareRegistry {
     /// Zero-velocity E-stop: command all actuators to position `0.0` so that
     /// motors are halted if the OS exits unexpectedly or panics.
     fn drop(&mut self) {
-        for actuator in self.actuators.values_mut() {
-            let _ = actuator.set_position(0.0);
-        }
+        self.stop_all();
     }
 }
 
@@ -183,6 +259,7 @@ mod tests {
     use crate::actuator::Actuator;
     use crate::camera::{Camera, CameraFrame};
     use crate::relay::Relay;
+    use mechos_types::{MetersPerSecond, RadiansPerSecond};
 
     // ------------------------------------------------------------------
     // Test doubles
@@ -265,9 +342,10 @@ mod tests {
 
         registry
             .dispatch(HardwareIntent::MoveEndEffector {
-                x: 0.3,
-                y: 0.1,
-                z: 0.5,
+                x: Meters(0.3),
+                y: Meters(0.1),
+                z: Meters(0.5),
+                frame_id: None,
             })
             .unwrap();
 
@@ -300,8 +378,8 @@ mod tests {
         // linear=1.0, angular=0.0 → both wheels = 1.0
         registry
             .dispatch(HardwareIntent::Drive {
-                linear_velocity: 1.0,
-                angular_velocity: 0.0,
+                linear_velocity: MetersPerSecond(1.0),
+                angular_velocity: RadiansPerSecond(0.0),
             })
             .unwrap();
 
@@ -313,8 +391,8 @@ mod tests {
         // Turn in place: linear=0, angular=1.0 → left=-0.5, right=0.5
         registry
             .dispatch(HardwareIntent::Drive {
-                linear_velocity: 0.0,
-                angular_velocity: 1.0,
+                linear_velocity: MetersPerSecond(0.0),
+                angular_velocity: RadiansPerSecond(1.0),
             })
             .unwrap();
 
@@ -332,17 +410,105 @@ mod tests {
             .dispatch(HardwareIntent::AskHuman {
                 question: "Which direction?".to_string(),
                 context_image_id: None,
+                question_id: None,
             })
             .is_ok());
     }
 
+    #[test]
+    fn dispatch_move_joint_and_gripper_reach_named_actuators() {
+        let mut registry = HardwareRegistry::new();
+        registry.register_actuator(MockActuator::new("elbow"));
+        registry.register_actuator(MockActuator::new("gripper"));
+
+        registry
+            .dispatch(HardwareIntent::MoveJoint {
+                joint: "elbow".to_string(),
+                angle_rad: 0.8,
+                max_velocity: RadiansPerSecond(0.3),
+            })
+            .unwrap();
+        registry
+            .dispatch(HardwareIntent::SetGripper { position: 1.0 })
+            .unwrap();
+
+        assert!((registry.actuators["elbow"].position() - 0.8).abs() < f32::EPSILON);
+        assert!((registry.actuators["gripper"].position() - 1.0).abs() < f32::EPSILON);
+        assert!(registry
+            .dispatch(HardwareIntent::MoveJoint {
+                joint: "wrist".to_string(),
+                angle_rad: 0.1,
+                max_velocity: RadiansPerSecond(0.3),
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn dispatch_halt_zeroes_every_actuator() {
+        let mut registry = HardwareRegistry::new();
+        registry.register_actuator(MockActuator::new("left_wheel"));
+        registry.register_actuator(MockActuator::new("right_wheel"));
+        registry
+            .dispatch(HardwareIntent::Drive {
+                linear_velocity: MetersPerSecond(1.0),
+                angular_velocity: RadiansPerSecond(0.0),
+            })
+            .unwrap();
+
+        registry
+            .dispatch(HardwareIntent::Halt {
+                reason: "test".to_string(),
+            })
+            .unwrap();
+        assert_eq!(registry.actuators["left_wheel"].position(), 0.0);
+        assert_eq!(registry.actuators["right_wheel"].position(), 0.0);
+    }
+
+    #[test]
+    fn dispatch_follow_waypoints_is_rejected() {
+        let mut registry = HardwareRegistry::new();
+        let result = registry.dispatch(HardwareIntent::FollowWaypoints {
+            points: vec![(Meters(1.0), Meters(0.0))],
+            max_speed: MetersPerSecond(0.5),
+        });
+        assert!(matches!(
+            result,
+            Err(MechError::HardwareFault { ref component, .. }) if component == "drive_base"
+        ));
+    }
+
+    #[test]
+    fn dispatch_dock_and_undock_are_rejected() {
+        let mut registry = HardwareRegistry::new();
+        for intent in [HardwareIntent::Dock, HardwareIntent::Undock] {
+            let err = registry.dispatch(intent).unwrap_err();
+            assert_eq!(err.fault_code(), Some(FaultCode::Unsupported));
+        }
+    }
+
+    #[test]
+    fn dispatch_flight_intents_are_rejected() {
+        let mut registry = HardwareRegistry::new();
+        let err = registry
+            .dispatch(HardwareIntent::SetAltitude {
+                altitude: Meters(5.0),
+            })
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            MechError::HardwareFault { code: FaultCode::Unsupported, ref component, .. }
+                if component == "flight_controller"
+        ));
+    }
+
     #[test]
     fn dispatch_missing_end_effector_returns_error() {
         let mut registry = HardwareRegistry::new();
         let result = registry.dispatch(HardwareIntent::MoveEndEffector {
-            x: 0.5,
-            y: 0.0,
-            z: 1.0,
+            x: Meters(0.5),
+            y: Meters(0.0),
+            z: Meters(1.0),
+            frame_id: None,
         });
         assert!(matches!(result, Err(MechError::HardwareFault { .. })));
     }
@@ -351,8 +517,8 @@ mod tests {
     fn dispatch_missing_actuator_returns_error() {
         let mut registry = HardwareRegistry::new();
         let result = registry.dispatch(HardwareIntent::Drive {
-            linear_velocity: 1.0,
-            angular_velocity: 0.0,
+            linear_velocity: MetersPerSecond(1.0),
+            angular_velocity: RadiansPerSecond(0.0),
         });
         assert!(matches!(result, Err(MechError::HardwareFault { .. })));
     }
@@ -388,9 +554,10 @@ mod tests {
         registry.register_actuator(MockActuator::new("end_effector"));
         registry
             .dispatch(HardwareIntent::MoveEndEffector {
-                x: 3.0,
-                y: 0.0,
-                z: 0.0,
+                x: Meters(3.0),
+                y: Meters(0.0),
+                z: Meters(0.0),
+                frame_id: None,
             })
             .unwrap();
 
@@ -441,8 +608,8 @@ mod tests {
             // Move actuators to non-zero positions.
             registry
                 .dispatch(HardwareIntent::Drive {
-                    linear_velocity: 1.0,
-                    angular_velocity: 0.0,
+                    linear_velocity: MetersPerSecond(1.0),
+                    angular_velocity: RadiansPerSecond(0.0),
                 })
                 .unwrap_or_default();
             // Registry drops here – Drop impl must zero both actuators.
@@ -0,0 +1,334 @@
+//! Serial motor-controller drivers: Basicmicro RoboClaw and ODrive.
+//!
+//! Each controller drives two motors over one serial link, so the link is
+//! opened once ([`RoboClaw::open`], [`ODrive::open`]) and split into one
+//! [`MotorController`] per channel ([`RoboClaw::motor`], [`ODrive::axis`]).
+//! Velocities are converted with the wheel geometry given per motor:
+//! encoder counts per metre for the RoboClaw, motor turns per metre for the
+//! ODrive.
+//!
+//! * **RoboClaw** – packet serial: `address, command, i32 speed (QPPS, big
+//!   endian), CRC-16/XMODEM`, acknowledged with `0xFF`.  Commands 35 / 36
+//!   set the signed speed of M1 / M2.
+//! * **ODrive** – the ASCII protocol: `v <axis> <turns/s> 0`.  The first
+//!   command also requests closed-loop control for the axis.
+//!
+//! Both constructors also accept any `Read + Write` port, which is how the
+//! tests exercise the wire format.
+
+use std::io::{Read, Write};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use mechos_types::{FaultCode, MechError, MetersPerSecond};
+
+use crate::motor::MotorController;
+
+/// Default RoboClaw packet-serial address.
+pub const ROBOCLAW_DEFAULT_ADDRESS: u8 = 0x80;
+
+/// How long to wait for a controller's reply.
+const SERIAL_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// A serial port opened with [`RoboClaw::open`] or [`ODrive::open`].
+pub type SerialPort = Box<dyn serialport::SerialPort>;
+
+fn open_serial(component: &str, path: &str, baud_rate: u32) -> Result<SerialPort, MechError> {
+    serialport::new(path, baud_rate)
+        .timeout(SERIAL_TIMEOUT)
+        .open()
+        .map_err(|e| MechError::HardwareFault {
+            code: FaultCode::Unknown,
+            component: component.to_string(),
+            details: format!("cannot open {path}: {e}"),
+        })
+}
+
+fn lock<P>(port: &Mutex<P>) -> MutexGuard<'_, P> {
+    port.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn link_fault(component: &str, e: impl std::fmt::Display) -> MechError {
+    MechError::HardwareFault {
+        code: FaultCode::Unknown,
+        component: component.to_string(),
+        details: format!("serial link error: {e}"),
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// RoboClaw
+// ────────────────────────────────────────────────────────────────────────────
+
+/// CRC-16/XMODEM (polynomial 0x1021, initial value 0), as used by RoboClaw
+/// packet serial.
+pub fn roboclaw_crc16(bytes: &[u8]) -> u16 {
+    bytes.iter().fold(0u16, |crc, &byte| {
+        (0..8).fold(crc ^ (u16::from(byte) << 8), |crc, _| {
+            if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+/// One of a RoboClaw's two motor channels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoboClawChannel {
+    M1,
+    M2,
+}
+
+/// A RoboClaw motor controller on a serial link.
+pub struct RoboClaw<P = SerialPort> {
+    port: Arc<Mutex<P>>,
+    address: u8,
+}
+
+impl RoboClaw {
+    /// Open the controller on `path` (e.g. `/dev/ttyACM0`) at `baud_rate`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::HardwareFault`] if the port cannot be opened.
+    pub fn open(path: &str, baud_rate: u32) -> Result<Self, MechError> {
+        Ok(Self::new(open_serial("roboclaw", path, baud_rate)?))
+    }
+}
+
+impl<P: Read + Write + Send> RoboClaw<P> {
+    /// Talk to the controller over `port`, at [`ROBOCLAW_DEFAULT_ADDRESS`].
+    pub fn new(port: P) -> Self {
+        Self {
+            port: Arc::new(Mutex::new(port)),
+            address: ROBOCLAW_DEFAULT_ADDRESS,
+        }
+    }
+
+    /// Replace the packet-serial address (`0x80`–`0x87`).
+    pub fn with_address(mut self, address: u8) -> Self {
+        self.address = address;
+        self
+    }
+
+    /// The motor on `channel`, whose encoder counts `counts_per_meter` per
+    /// metre of wheel travel.
+    pub fn motor(
+        &self,
+        id: impl Into<String>,
+        channel: RoboClawChannel,
+        counts_per_meter: f32,
+    ) -> RoboClawMotor<P> {
+        RoboClawMotor {
+            id: id.into(),
+            port: Arc::clone(&self.port),
+            address: self.address,
+            channel,
+            counts_per_meter,
+            velocity: MetersPerSecond::ZERO,
+        }
+    }
+}
+
+/// One channel of a [`RoboClaw`].
+pub struct RoboClawMotor<P = SerialPort> {
+    id: String,
+    port: Arc<Mutex<P>>,
+    address: u8,
+    channel: RoboClawChannel,
+    counts_per_meter: f32,
+    velocity: MetersPerSecond,
+}
+
+impl<P> RoboClawMotor<P> {
+    /// The packet that sets this channel to `velocity`.
+    pub fn speed_packet(&self, velocity: MetersPerSecond) -> Vec<u8> {
+        let command = match self.channel {
+            RoboClawChannel::M1 => 35,
+            RoboClawChannel::M2 => 36,
+        };
+        let qpps = (velocity.get() * self.counts_per_meter).round() as i32;
+        let mut packet = vec![self.address, command];
+        packet.extend_from_slice(&qpps.to_be_bytes());
+        let crc = roboclaw_crc16(&packet);
+        packet.extend_from_slice(&crc.to_be_bytes());
+        packet
+    }
+}
+
+impl<P: Read + Write + Send> MotorController for RoboClawMotor<P> {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn set_velocity(&mut self, velocity: MetersPerSecond) -> Result<(), MechError> {
+        let packet = self.speed_packet(velocity);
+        let mut port = lock(&self.port);
+        port.write_all(&packet).map_err(|e| link_fault(&self.id, e))?;
+        let mut ack = [0u8; 1];
+        port.read_exact(&mut ack).map_err(|e| link_fault(&self.id, e))?;
+        if ack[0] != 0xFF {
+            return Err(MechError::HardwareFault {
+                code: FaultCode::Unknown,
+                component: self.id.clone(),
+                details: format!("RoboClaw answered {:#04x} instead of 0xff", ack[0]),
+            });
+        }
+        self.velocity = velocity;
+        Ok(())
+    }
+
+    fn velocity(&self) -> MetersPerSecond {
+        self.velocity
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// ODrive
+// ────────────────────────────────────────────────────────────────────────────
+
+/// An ODrive motor controller on a serial (or USB CDC) link.
+pub struct ODrive<P = SerialPort> {
+    port: Arc<Mutex<P>>,
+}
+
+impl ODrive {
+    /// Open the controller on `path` (e.g. `/dev/ttyACM0`) at `baud_rate`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::HardwareFault`] if the port cannot be opened.
+    pub fn open(path: &str, baud_rate: u32) -> Result<Self, MechError> {
+        Ok(Self::new(open_serial("odrive", path, baud_rate)?))
+    }
+}
+
+impl<P: Read + Write + Send> ODrive<P> {
+    /// Talk to the controller over `port`.
+    pub fn new(port: P) -> Self {
+        Self {
+            port: Arc::new(Mutex::new(port)),
+        }
+    }
+
+    /// The motor on `axis` (0 or 1), which turns `turns_per_meter` times per
+    /// metre of wheel travel.
+    pub fn axis(&self, id: impl Into<String>, axis: u8, turns_per_meter: f32) -> ODriveAxis<P> {
+        ODriveAxis {
+            id: id.into(),
+            port: Arc::clone(&self.port),
+            axis,
+            turns_per_meter,
+            closed_loop: false,
+            velocity: MetersPerSecond::ZERO,
+        }
+    }
+}
+
+/// One axis of an [`ODrive`].
+pub struct ODriveAxis<P = SerialPort> {
+    id: String,
+    port: Arc<Mutex<P>>,
+    axis: u8,
+    turns_per_meter: f32,
+    closed_loop: bool,
+    velocity: MetersPerSecond,
+}
+
+impl<P: Read + Write + Send> MotorController for ODriveAxis<P> {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn set_velocity(&mut self, velocity: MetersPerSecond) -> Result<(), MechError> {
+        let mut commands = String::new();
+        if !self.closed_loop {
+            // AXIS_STATE_CLOSED_LOOP_CONTROL
+            commands.push_str(&format!("w axis{}.requested_state 8\n", self.axis));
+        }
+        let turns_per_s = velocity.get() * self.turns_per_meter;
+        commands.push_str(&format!("v {} {turns_per_s:.4} 0\n", self.axis));
+        lock(&self.port)
+            .write_all(commands.as_bytes())
+            .map_err(|e| link_fault(&self.id, e))?;
+        self.closed_loop = true;
+        self.velocity = velocity;
+        Ok(())
+    }
+
+    fn velocity(&self) -> MetersPerSecond {
+        self.velocity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{self, Cursor};
+
+    /// A port that records what is written and replays canned replies.
+    #[derive(Default)]
+    struct LoopPort {
+        written: Vec<u8>,
+        replies: Cursor<Vec<u8>>,
+    }
+
+    impl Read for LoopPort {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.replies.read(buf)
+        }
+    }
+
+    impl Write for LoopPort {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.written.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn crc16_matches_the_xmodem_check_value() {
+        assert_eq!(roboclaw_crc16(b"123456789"), 0x31C3);
+    }
+
+    #[test]
+    fn roboclaw_sends_signed_speed_packets() {
+        let claw = RoboClaw::new(LoopPort {
+            replies: Cursor::new(vec![0xFF, 0x00]),
+            ..LoopPort::default()
+        });
+        let mut left = claw.motor("left_wheel", RoboClawChannel::M2, 1000.0);
+        left.set_velocity(MetersPerSecond(-0.5)).unwrap();
+        assert_eq!(left.velocity(), MetersPerSecond(-0.5));
+
+        let packet = lock(&claw.port).written.clone();
+        assert_eq!(packet[..6], [0x80, 36, 0xFF, 0xFF, 0xFE, 0x0C]);
+        assert_eq!(
+            u16::from_be_bytes([packet[6], packet[7]]),
+            roboclaw_crc16(&packet[..6])
+        );
+
+        // A missing acknowledgement is a fault and keeps the old velocity.
+        assert!(left.set_velocity(MetersPerSecond(1.0)).is_err());
+        assert_eq!(left.velocity(), MetersPerSecond(-0.5));
+    }
+
+    #[test]
+    fn odrive_enters_closed_loop_then_sends_velocities() {
+        let drive = ODrive::new(LoopPort::default());
+        let mut axis = drive.axis("right_wheel", 1, 2.0);
+        axis.set_velocity(MetersPerSecond(0.25)).unwrap();
+        axis.stop().unwrap();
+        let written = String::from_utf8(lock(&drive.port).written.clone()).unwrap();
+        assert_eq!(
+            written,
+            "w axis1.requested_state 8\nv 1 0.5000 0\nv 1 0.0000 0\n"
+        );
+    }
+}
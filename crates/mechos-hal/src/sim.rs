@@ -9,7 +9,7 @@
 //!
 //! ```rust
 //! use mechos_hal::sim::SimRegistry;
-//! use mechos_types::HardwareIntent;
+//! use mechos_types::{HardwareIntent, MetersPerSecond, RadiansPerSecond};
 //!
 //! let mut registry = SimRegistry::new()
 //!     .with_drive_base()
@@ -17,8 +17,8 @@
 //!
 //! registry
 //!     .dispatch(HardwareIntent::Drive {
-//!         linear_velocity: 0.5,
-//!         angular_velocity: 0.1,
+//!         linear_velocity: MetersPerSecond(0.5),
+//!         angular_velocity: RadiansPerSecond(0.1),
 //!     })
 //!     .expect("sim drive must succeed");
 //! ```
@@ -211,15 +211,15 @@ impl SimRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use mechos_types::HardwareIntent;
+    use mechos_types::{HardwareIntent, Meters, MetersPerSecond, RadiansPerSecond};
 
     #[test]
     fn sim_registry_drive_base_dispatches_successfully() {
         let mut registry = SimRegistry::new().with_drive_base().build();
         registry
             .dispatch(HardwareIntent::Drive {
-                linear_velocity: 1.0,
-                angular_velocity: 0.0,
+                linear_velocity: MetersPerSecond(1.0),
+                angular_velocity: RadiansPerSecond(0.0),
             })
             .expect("sim drive must succeed");
     }
@@ -229,9 +229,10 @@ mod tests {
         let mut registry = SimRegistry::new().with_end_effector().build();
         registry
             .dispatch(HardwareIntent::MoveEndEffector {
-                x: 0.5,
-                y: 0.2,
-                z: 0.3,
+                x: Meters(0.5),
+                y: Meters(0.2),
+                z: Meters(0.3),
+                frame_id: None,
             })
             .expect("sim end_effector must succeed");
     }
@@ -287,16 +288,17 @@ mod tests {
 
         registry
             .dispatch(HardwareIntent::Drive {
-                linear_velocity: 0.5,
-                angular_velocity: -0.2,
+                linear_velocity: MetersPerSecond(0.5),
+                angular_velocity: RadiansPerSecond(-0.2),
             })
             .expect("drive must succeed");
 
         registry
             .dispatch(HardwareIntent::MoveEndEffector {
-                x: 0.1,
-                y: 0.2,
-                z: 0.3,
+                x: Meters(0.1),
+                y: Meters(0.2),
+                z: Meters(0.3),
+                frame_id: None,
             })
             .expect("move_end_effector must succeed");
 
@@ -311,6 +313,7 @@ mod tests {
             .dispatch(HardwareIntent::AskHuman {
                 question: "Continue?".to_string(),
                 context_image_id: None,
+                question_id: None,
             })
             .expect("ask_human must succeed");
     }
@@ -326,8 +329,8 @@ mod tests {
         let mut registry = SimRegistry::new().with_drive_base().build();
         registry
             .dispatch(HardwareIntent::Drive {
-                linear_velocity: 1.0,
-                angular_velocity: 0.0,
+                linear_velocity: MetersPerSecond(1.0),
+                angular_velocity: RadiansPerSecond(0.0),
             })
             .unwrap();
         let left = registry.actuator_position("left_wheel").expect("left_wheel registered");
@@ -342,8 +345,8 @@ mod tests {
         let mut registry = SimRegistry::new().with_drive_base().build();
         registry
             .dispatch(HardwareIntent::Drive {
-                linear_velocity: 0.0,
-                angular_velocity: 1.0,
+                linear_velocity: MetersPerSecond(0.0),
+                angular_velocity: RadiansPerSecond(1.0),
             })
             .unwrap();
         let left = registry.actuator_position("left_wheel").unwrap();
@@ -357,9 +360,10 @@ mod tests {
         let mut registry = SimRegistry::new().with_end_effector().build();
         registry
             .dispatch(HardwareIntent::MoveEndEffector {
-                x: 0.42,
-                y: 0.0,
-                z: 0.0,
+                x: Meters(0.42),
+                y: Meters(0.0),
+                z: Meters(0.0),
+                frame_id: None,
             })
             .unwrap();
         let pos = registry.actuator_position("end_effector").unwrap();
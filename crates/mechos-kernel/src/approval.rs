@@ -0,0 +1,136 @@
+//! [`ApprovalQueue`] – two-phase approval of intents by a human operator.
+//!
+//! Some intents are too consequential to run on the agent's word alone, e.g.
+//! opening a door relay or undocking.  When the
+//! [`KernelGate`][crate::KernelGate]'s approval policy matches an intent:
+//!
+//! 1. **Hold** – the intent is authorized and checked against every rule as
+//!    usual, then parked here with the full rule report instead of being
+//!    dispatched.
+//! 2. **Decide** – an operator approves or rejects it.  An approved intent is
+//!    authorized and verified **again**, since the robot may have moved, a
+//!    rule may have tripped or the e-stop may have latched while the operator
+//!    was deciding; only then is it released.
+//!
+//! Pending approvals are keyed by the envelope's correlation ID, which is
+//! also the `request_id` of the bus
+//! [`ApprovalRequest`][mechos_types::EventPayload::ApprovalRequest].
+
+use mechos_types::{HardwareIntent, IntentEnvelope, RuleVerdict};
+
+/// Decides which intents must wait for an operator.
+pub type ApprovalPolicy = Box<dyn Fn(&HardwareIntent) -> bool + Send + Sync>;
+
+/// An intent held for an operator's decision.
+#[derive(Debug, Clone)]
+pub struct PendingApproval {
+    pub request_id: String,
+    pub envelope: IntentEnvelope,
+    /// The verdict of every rule when the intent was held.
+    pub report: Vec<RuleVerdict>,
+}
+
+/// Intents awaiting an operator's decision, oldest first.
+#[derive(Default)]
+pub struct ApprovalQueue {
+    policy: Option<ApprovalPolicy>,
+    pending: Vec<PendingApproval>,
+}
+
+impl ApprovalQueue {
+    /// An empty queue whose policy holds nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hold every intent `policy` returns `true` for.  Replaces any previous
+    /// policy.
+    pub fn set_policy(&mut self, policy: impl Fn(&HardwareIntent) -> bool + Send + Sync + 'static) {
+        self.policy = Some(Box::new(policy));
+    }
+
+    /// `true` when `intent` must be approved before it runs.
+    /// [`HardwareIntent::Halt`] never waits.
+    pub fn requires_approval(&self, intent: &HardwareIntent) -> bool {
+        !matches!(intent, HardwareIntent::Halt { .. })
+            && self.policy.as_ref().is_some_and(|policy| policy(intent))
+    }
+
+    /// Park `envelope` until [`take`][Self::take]n, replacing an approval
+    /// already pending under the same correlation ID.
+    pub fn hold(&mut self, envelope: IntentEnvelope, report: Vec<RuleVerdict>) -> &PendingApproval {
+        let request_id = envelope.correlation_id.to_string();
+        self.pending
+            .retain(|pending| pending.request_id != request_id);
+        self.pending.push(PendingApproval {
+            request_id,
+            envelope,
+            report,
+        });
+        self.pending.last().expect("just pushed")
+    }
+
+    /// Remove and return the approval pending under `request_id`.
+    pub fn take(&mut self, request_id: &str) -> Option<PendingApproval> {
+        let index = self
+            .pending
+            .iter()
+            .position(|pending| pending.request_id == request_id)?;
+        Some(self.pending.remove(index))
+    }
+
+    /// Every pending approval, oldest first.
+    pub fn pending(&self) -> &[PendingApproval] {
+        &self.pending
+    }
+
+    /// `true` when nothing awaits a decision.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relay() -> HardwareIntent {
+        HardwareIntent::TriggerRelay {
+            relay_id: "door".to_string(),
+            state: true,
+        }
+    }
+
+    #[test]
+    fn policy_selects_intents_but_never_halt() {
+        let mut queue = ApprovalQueue::new();
+        assert!(
+            !queue.requires_approval(&relay()),
+            "default policy holds nothing"
+        );
+
+        queue.set_policy(|_| true);
+        assert!(queue.requires_approval(&relay()));
+        assert!(!queue.requires_approval(&HardwareIntent::Halt {
+            reason: "stop".to_string(),
+        }));
+    }
+
+    #[test]
+    fn held_intents_are_taken_once() {
+        let mut queue = ApprovalQueue::new();
+        let envelope = IntentEnvelope::new(relay(), "agent");
+        let request_id = queue.hold(envelope.clone(), Vec::new()).request_id.clone();
+        assert_eq!(request_id, envelope.correlation_id.to_string());
+        assert_eq!(queue.pending().len(), 1);
+
+        assert!(queue.take("unknown").is_none());
+        assert!(queue.take(&request_id).is_some());
+        assert!(queue.take(&request_id).is_none());
+        assert!(queue.is_empty());
+    }
+}
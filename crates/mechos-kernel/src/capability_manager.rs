@@ -4,10 +4,26 @@
 //! to verify the requesting agent holds the required [`Capability`].  If the
 //! check fails a [`MechError::Unauthorized`] is returned and the action must
 //! not be executed.
+//!
+//! The grants can be kept in a JSON file with [`CapabilityManager::save`] and
+//! [`CapabilityManager::load`], so an operator can change them without
+//! rebuilding (see `/caps` in `mechos-cli`).
+//!
+//! Checks only ask; a grant is used when [`CapabilityManager::record_use`]
+//! is called for it, which the [`KernelGate`][crate::KernelGate] does once
+//! it permits an intent.  [`CapabilityManager::usage_report`] lists each
+//! grant with its count and last use, so grants that are never exercised
+//! can be pruned.
 
 use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+
+use chrono::{DateTime, Utc};
+use mechos_types::{Capability, CapabilityGrant, CapabilityUsage, MechError};
 
-use mechos_types::{Capability, MechError};
+/// Use count and last use of each exercised `(agent, capability)`.
+type UsageCounters = HashMap<(String, Capability), (u64, DateTime<Utc>)>;
 
 /// Manages the set of [`Capability`] grants for each registered agent.
 ///
@@ -26,6 +42,7 @@ use mechos_types::{Capability, MechError};
 #[derive(Default)]
 pub struct CapabilityManager {
     grants: HashMap<String, HashSet<Capability>>,
+    usage: Mutex<UsageCounters>,
 }
 
 impl CapabilityManager {
@@ -51,19 +68,131 @@ impl CapabilityManager {
     }
 
     /// Return `Ok(())` when `agent_id` holds `cap`, or
-    /// [`MechError::Unauthorized`] otherwise.
+    /// [`MechError::Unauthorized`] otherwise.  The check is not counted as
+    /// a use; see [`record_use`][Self::record_use].
     pub fn check(&self, agent_id: &str, cap: &Capability) -> Result<(), MechError> {
-        let has = self
-            .grants
-            .get(agent_id)
-            .map(|s| s.contains(cap))
-            .unwrap_or(false);
-        if has {
+        if self.holds(agent_id, cap) {
             Ok(())
         } else {
             Err(MechError::Unauthorized(cap.clone()))
         }
     }
+
+    /// `true` when `agent_id` holds `cap`.
+    pub fn holds(&self, agent_id: &str, cap: &Capability) -> bool {
+        self.grants
+            .get(agent_id)
+            .is_some_and(|caps| caps.contains(cap))
+    }
+
+    /// Count a use of `agent_id`'s grant of `cap` in the
+    /// [`usage_report`][Self::usage_report].  Call it once the action the
+    /// grant allowed is actually going ahead, not for probes.
+    pub fn record_use(&self, agent_id: &str, cap: &Capability) {
+        let mut usage = self.usage();
+        let entry = usage
+            .entry((agent_id.to_string(), cap.clone()))
+            .or_insert((0, Utc::now()));
+        *entry = (entry.0 + 1, Utc::now());
+    }
+
+    /// Every current grant with how often it was exercised and when last,
+    /// in [`grants`][Self::grants] order.  Grants with `uses == 0` were
+    /// never needed and are candidates for revocation.
+    pub fn usage_report(&self) -> Vec<CapabilityUsage> {
+        let usage = self.usage();
+        self.grants()
+            .into_iter()
+            .flat_map(|grant| {
+                let agent_id = grant.agent_id;
+                grant.capabilities.into_iter().map(move |capability| (agent_id.clone(), capability))
+            })
+            .map(|(agent_id, capability)| {
+                let used = usage.get(&(agent_id.clone(), capability.clone()));
+                CapabilityUsage {
+                    uses: used.map_or(0, |(uses, _)| *uses),
+                    last_used: used.map(|(_, at)| *at),
+                    agent_id,
+                    capability,
+                }
+            })
+            .collect()
+    }
+
+    fn usage(&self) -> MutexGuard<'_, UsageCounters> {
+        self.usage.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// The capabilities granted to `agent_id`, sorted; empty for an unknown
+    /// agent.
+    pub fn capabilities(&self, agent_id: &str) -> Vec<Capability> {
+        let mut capabilities: Vec<Capability> = self
+            .grants
+            .get(agent_id)
+            .map(|caps| caps.iter().cloned().collect())
+            .unwrap_or_default();
+        capabilities.sort_by_cached_key(|cap| format!("{cap:?}"));
+        capabilities
+    }
+
+    /// Every agent's grants, sorted by agent ID and then capability, e.g. to
+    /// show an operator.  Agents whose grants were all revoked are omitted.
+    pub fn grants(&self) -> Vec<CapabilityGrant> {
+        let mut grants: Vec<CapabilityGrant> = self
+            .grants
+            .iter()
+            .filter(|(_, caps)| !caps.is_empty())
+            .map(|(agent_id, _)| CapabilityGrant {
+                agent_id: agent_id.clone(),
+                capabilities: self.capabilities(agent_id),
+            })
+            .collect();
+        grants.sort_by(|a, b| a.agent_id.cmp(&b.agent_id));
+        grants
+    }
+
+    /// Read the grants [`save`][Self::save]d to `path`.
+    ///
+    /// # Errors
+    ///
+    /// - [`MechError::Serialization`] – the file cannot be read.
+    /// - [`MechError::Parsing`] – the file is not a list of grants.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, MechError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            MechError::Serialization(format!(
+                "failed to read capability grants '{}': {e}",
+                path.display()
+            ))
+        })?;
+        let grants: Vec<CapabilityGrant> = serde_json::from_str(&text).map_err(|e| {
+            MechError::Parsing(format!("invalid capability grants '{}': {e}", path.display()))
+        })?;
+        let mut manager = Self::new();
+        for grant in grants {
+            for cap in grant.capabilities {
+                manager.grant(&grant.agent_id, cap);
+            }
+        }
+        Ok(manager)
+    }
+
+    /// Write every grant to `path` as a JSON list of [`CapabilityGrant`]s.
+    ///
+    /// # Errors
+    ///
+    /// [`MechError::Serialization`] when the file cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), MechError> {
+        let path = path.as_ref();
+        let json = serde_json::to_string_pretty(&self.grants())
+            .map_err(|e| MechError::Serialization(e.to_string()))?;
+        std::fs::write(path, json).map_err(|e| {
+            MechError::Serialization(format!(
+                "failed to write capability grants '{}': {e}",
+                path.display()
+            ))
+        })
+    }
 }
 
 #[cfg(test)]
@@ -168,4 +297,83 @@ mod tests {
             .check("robot_agent", &Capability::ModelInference)
             .is_err());
     }
+
+    #[test]
+    fn grants_are_listed_in_order() {
+        let mut mgr = CapabilityManager::new();
+        mgr.grant("runtime", Capability::ModelInference);
+        mgr.grant("runtime", Capability::HardwareInvoke("drive_base".into()));
+        mgr.grant("planner", Capability::TaskBoardAccess);
+        mgr.grant("retired", Capability::FleetCommunicate);
+        mgr.revoke("retired", &Capability::FleetCommunicate);
+
+        let grants = mgr.grants();
+        let agents: Vec<&str> = grants.iter().map(|g| g.agent_id.as_str()).collect();
+        assert_eq!(agents, ["planner", "runtime"]);
+        assert_eq!(
+            grants[1].capabilities,
+            [
+                Capability::HardwareInvoke("drive_base".into()),
+                Capability::ModelInference
+            ]
+        );
+        assert!(mgr.capabilities("retired").is_empty());
+    }
+
+    #[test]
+    fn usage_report_counts_recorded_uses_per_grant() {
+        let mut mgr = CapabilityManager::new();
+        let drive = Capability::HardwareInvoke("drive_base".into());
+        mgr.grant("runtime", drive.clone());
+        mgr.grant("runtime", Capability::FleetCommunicate);
+        mgr.grant("planner", Capability::TaskBoardAccess);
+
+        let before = Utc::now();
+        mgr.check("runtime", &drive).unwrap();
+        assert!(mgr.holds("runtime", &Capability::FleetCommunicate));
+        assert!(!mgr.holds("planner", &drive));
+        mgr.record_use("runtime", &drive);
+        mgr.record_use("runtime", &drive);
+
+        let report = mgr.usage_report();
+        let rows: Vec<(&str, &Capability, u64)> = report
+            .iter()
+            .map(|u| (u.agent_id.as_str(), &u.capability, u.uses))
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                ("planner", &Capability::TaskBoardAccess, 0),
+                ("runtime", &Capability::FleetCommunicate, 0),
+                ("runtime", &drive, 2),
+            ],
+            "checks are not uses; unused grants are listed with zero"
+        );
+        assert!(report[2].last_used.is_some_and(|at| at >= before));
+        assert!(report[0].last_used.is_none());
+    }
+
+    #[test]
+    fn grants_survive_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("capabilities.json");
+        let mut mgr = CapabilityManager::new();
+        mgr.grant("runtime", Capability::HardwareInvoke("drive_base".into()));
+        mgr.grant("runtime", Capability::ModelInference);
+        mgr.grant("planner", Capability::TaskBoardAccess);
+        mgr.save(&path).unwrap();
+
+        let loaded = CapabilityManager::load(&path).unwrap();
+        assert_eq!(loaded.grants(), mgr.grants());
+        assert!(loaded
+            .check("runtime", &Capability::HardwareInvoke("drive_base".into()))
+            .is_ok());
+
+        std::fs::write(&path, "{\"runtime\": 1}").unwrap();
+        assert!(matches!(CapabilityManager::load(&path), Err(MechError::Parsing(_))));
+        assert!(matches!(
+            CapabilityManager::load(dir.path().join("absent.json")),
+            Err(MechError::Serialization(_))
+        ));
+    }
 }
@@ -0,0 +1,276 @@
+//! [`GateHook`] – user code plugged into every [`KernelGate`] decision.
+//!
+//! Hooks let a deployment add logging, metrics, intent rewriting or an
+//! external policy engine (e.g. an OPA sidecar) without forking the gate.
+//! They are registered with [`KernelGate::add_hook`] and run in
+//! registration order around the built-in capability and rule checks:
+//!
+//! 1. every hook's [`before_check`][GateHook::before_check] may rewrite the
+//!    intent or refuse it; the first refusal skips the remaining hooks and
+//!    the built-in checks,
+//! 2. the gate checks the (possibly rewritten) intent,
+//! 3. every hook's [`after_decision`][GateHook::after_decision] sees the
+//!    decision so far and may veto an allowed intent.
+//!
+//! Hooks fail closed: an error or a panic in a hook rejects the intent, and
+//! no hook can turn a rejection into an approval.  `Halt` is the exception
+//! the gate always makes – it skips `before_check`, and `after_decision`
+//! only observes it.
+//!
+//! ```
+//! use mechos_kernel::{CapabilityManager, GateHook, KernelGate, StateVerifier};
+//! use mechos_types::{Capability, HardwareIntent, MechError, MetersPerSecond, RadiansPerSecond};
+//!
+//! /// Halves every drive command.
+//! struct Gentle;
+//!
+//! impl GateHook for Gentle {
+//!     fn name(&self) -> &str {
+//!         "gentle"
+//!     }
+//!
+//!     fn before_check(&self, _agent: &str, intent: &mut HardwareIntent) -> Result<(), MechError> {
+//!         if let HardwareIntent::Drive { linear_velocity, .. } = intent {
+//!             *linear_velocity = MetersPerSecond(linear_velocity.get() / 2.0);
+//!         }
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let mut caps = CapabilityManager::new();
+//! caps.grant("runtime", Capability::HardwareInvoke("drive_base".into()));
+//! let mut gate = KernelGate::new(caps, StateVerifier::new());
+//! gate.add_hook(Box::new(Gentle));
+//!
+//! let mut intent = HardwareIntent::Drive {
+//!     linear_velocity: MetersPerSecond(1.0),
+//!     angular_velocity: RadiansPerSecond(0.0),
+//! };
+//! gate.authorize_and_verify("runtime", &mut intent)?;
+//! if let HardwareIntent::Drive { linear_velocity, .. } = intent {
+//!     assert_eq!(linear_velocity, MetersPerSecond(0.5));
+//! }
+//! # Ok::<(), MechError>(())
+//! ```
+//!
+//! [`KernelGate`]: crate::kernel_gate::KernelGate
+//! [`KernelGate::add_hook`]: crate::kernel_gate::KernelGate::add_hook
+
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+use mechos_types::{FaultCode, HardwareIntent, MechError};
+
+/// Code run by the [`KernelGate`][crate::kernel_gate::KernelGate] around
+/// each decision; see the [module docs](self).
+pub trait GateHook: Send + Sync {
+    /// Stable name, used in logs and in the fault reported when the hook
+    /// panics.
+    fn name(&self) -> &str;
+
+    /// Inspect or rewrite `intent` before the gate checks it.
+    ///
+    /// # Errors
+    ///
+    /// Any error rejects the intent with that error.
+    fn before_check(&self, agent_id: &str, intent: &mut HardwareIntent) -> Result<(), MechError> {
+        let _ = (agent_id, intent);
+        Ok(())
+    }
+
+    /// Observe the gate's decision on `intent`: `Ok(())` when it is allowed
+    /// so far, or the rejection.
+    ///
+    /// # Errors
+    ///
+    /// An error vetoes an allowed intent; it is ignored for an intent that
+    /// is already rejected.
+    fn after_decision(
+        &self,
+        agent_id: &str,
+        intent: &HardwareIntent,
+        decision: Result<(), &MechError>,
+    ) -> Result<(), MechError> {
+        let _ = (agent_id, intent, decision);
+        Ok(())
+    }
+}
+
+/// Run one hook callback, turning a panic into a rejection.
+pub(crate) fn guarded(
+    hook: &dyn GateHook,
+    call: impl FnOnce() -> Result<(), MechError>,
+) -> Result<(), MechError> {
+    catch_unwind(AssertUnwindSafe(call)).unwrap_or_else(|_| {
+        Err(MechError::HardwareFault {
+            code: FaultCode::Unknown,
+            component: format!("gate_hook:{}", hook.name()),
+            details: "gate hook panicked".to_string(),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capability_manager::CapabilityManager;
+    use crate::kernel_gate::KernelGate;
+    use crate::state_verifier::{SpeedCapRule, StateVerifier};
+    use mechos_types::{Capability, MetersPerSecond, RadiansPerSecond};
+    use std::sync::{Arc, Mutex};
+
+    /// Records its calls into a shared log and optionally misbehaves.
+    struct Probe {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+        refuse: bool,
+        veto: bool,
+        panic: bool,
+    }
+
+    impl Probe {
+        fn new(name: &'static str, log: &Arc<Mutex<Vec<String>>>) -> Self {
+            Self {
+                name,
+                log: Arc::clone(log),
+                refuse: false,
+                veto: false,
+                panic: false,
+            }
+        }
+    }
+
+    impl GateHook for Probe {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn before_check(&self, _: &str, _: &mut HardwareIntent) -> Result<(), MechError> {
+            self.log.lock().unwrap().push(format!("{}:before", self.name));
+            if self.panic {
+                panic!("probe");
+            }
+            if self.refuse {
+                return Err(refusal(self.name, "says no"));
+            }
+            Ok(())
+        }
+
+        fn after_decision(
+            &self,
+            _: &str,
+            _: &HardwareIntent,
+            decision: Result<(), &MechError>,
+        ) -> Result<(), MechError> {
+            let verdict = if decision.is_ok() { "ok" } else { "rejected" };
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{}:after:{verdict}", self.name));
+            if self.veto {
+                return Err(refusal(self.name, "vetoed"));
+            }
+            Ok(())
+        }
+    }
+
+    fn refusal(hook: &str, details: &str) -> MechError {
+        MechError::HardwareFault {
+            code: FaultCode::Unknown,
+            component: hook.to_string(),
+            details: details.to_string(),
+        }
+    }
+
+    fn gate() -> KernelGate {
+        let mut caps = CapabilityManager::new();
+        caps.grant("runtime", Capability::HardwareInvoke("drive_base".into()));
+        let mut verifier = StateVerifier::new();
+        verifier.add_rule(Box::new(SpeedCapRule {
+            max_linear: MetersPerSecond(1.0),
+            max_angular: RadiansPerSecond(1.0),
+        }));
+        KernelGate::new(caps, verifier)
+    }
+
+    fn drive(linear: f32) -> HardwareIntent {
+        HardwareIntent::Drive {
+            linear_velocity: MetersPerSecond(linear),
+            angular_velocity: RadiansPerSecond(0.0),
+        }
+    }
+
+    #[test]
+    fn hooks_run_in_order_around_the_checks() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut gate = gate();
+        gate.add_hook(Box::new(Probe::new("a", &log)));
+        gate.add_hook(Box::new(Probe::new("b", &log)));
+
+        gate.authorize_and_verify("runtime", &mut drive(0.5)).unwrap();
+        assert!(gate.authorize_and_verify("runtime", &mut drive(5.0)).is_err());
+        assert_eq!(
+            *log.lock().unwrap(),
+            [
+                "a:before", "b:before", "a:after:ok", "b:after:ok",
+                "a:before", "b:before", "a:after:rejected", "b:after:rejected",
+            ]
+        );
+        assert_eq!(gate.hook_names(), ["a", "b"]);
+    }
+
+    #[test]
+    fn refusals_vetoes_and_panics_fail_closed() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut gate = gate();
+        gate.add_hook(Box::new(Probe {
+            refuse: true,
+            ..Probe::new("opa", &log)
+        }));
+        gate.add_hook(Box::new(Probe::new("metrics", &log)));
+        let refused = gate.authorize_and_verify("runtime", &mut drive(0.5));
+        assert!(matches!(
+            refused,
+            Err(MechError::HardwareFault { ref component, ref details, .. })
+                if component == "opa" && details == "says no"
+        ));
+        assert_eq!(
+            *log.lock().unwrap(),
+            ["opa:before", "opa:after:rejected", "metrics:after:rejected"],
+            "a refusal skips later before_check hooks but every hook sees the decision"
+        );
+
+        let mut gate = self::gate();
+        gate.add_hook(Box::new(Probe {
+            veto: true,
+            ..Probe::new("audit", &log)
+        }));
+        assert!(gate.authorize_and_verify("runtime", &mut drive(0.5)).is_err());
+
+        let mut gate = self::gate();
+        gate.add_hook(Box::new(Probe {
+            panic: true,
+            ..Probe::new("buggy", &log)
+        }));
+        let panicked = gate.authorize_and_verify("runtime", &mut drive(0.5));
+        assert!(matches!(
+            panicked,
+            Err(MechError::HardwareFault { ref component, .. }) if component == "gate_hook:buggy"
+        ));
+    }
+
+    #[test]
+    fn halt_cannot_be_refused_by_a_hook() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut gate = gate();
+        gate.add_hook(Box::new(Probe {
+            refuse: true,
+            veto: true,
+            ..Probe::new("strict", &log)
+        }));
+        let mut halt = HardwareIntent::Halt {
+            reason: "operator".to_string(),
+        };
+        assert!(gate.authorize_and_verify("anyone", &mut halt).is_ok());
+        assert_eq!(*log.lock().unwrap(), ["strict:after:ok"]);
+    }
+}
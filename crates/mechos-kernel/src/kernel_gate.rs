@@ -16,6 +16,26 @@
 //! Only when both checks pass is the caller permitted to forward the intent to
 //! the HAL.
 //!
+//! [`HardwareIntent::Halt`] takes a fast path: it needs no capability and
+//! skips the rule engine, so a stop can never be refused.
+//!
+//! [`GateHook`]s registered with [`add_hook`][KernelGate::add_hook] run
+//! around both checks, in registration order, and may rewrite the intent
+//! before it is checked – which is why the checks take it by `&mut`.
+//!
+//! Intents matching the gate's approval policy go through a second phase:
+//! [`hold_for_approval`][KernelGate::hold_for_approval] parks them with a
+//! report of every rule's verdict, and
+//! [`decide`][KernelGate::decide] releases them once an operator approves
+//! (see [`ApprovalQueue`]).
+//!
+//! An operator may also change the safety policy at runtime with
+//! [`apply_policy`][KernelGate::apply_policy]: switch a rule between
+//! blocking and warning, or adjust its numeric parameters.  Each attempt,
+//! applied or refused, is kept in an audit log that
+//! [`policy_report`][KernelGate::policy_report] returns with every rule and
+//! capability grant.
+//!
 //! # Example
 //!
 //! ```
@@ -25,36 +45,61 @@
 //!     SpeedCapRule,
 //!     StateVerifier,
 //! };
-//! use mechos_types::{Capability, HardwareIntent};
+//! use mechos_types::{Capability, HardwareIntent, MetersPerSecond, RadiansPerSecond};
 //!
 //! let mut caps = CapabilityManager::new();
 //! caps.grant("runtime", Capability::HardwareInvoke("drive_base".into()));
 //!
 //! let mut verifier = StateVerifier::new();
-//! verifier.add_rule(Box::new(SpeedCapRule { max_linear: 1.0, max_angular: 1.0 }));
+//! verifier.add_rule(Box::new(SpeedCapRule {
+//!     max_linear: MetersPerSecond(1.0),
+//!     max_angular: RadiansPerSecond(1.0),
+//! }));
 //!
 //! let gate = KernelGate::new(caps, verifier);
 //!
 //! // Authorized + within caps → allowed.
-//! let ok = HardwareIntent::Drive { linear_velocity: 0.5, angular_velocity: 0.0 };
-//! assert!(gate.authorize_and_verify("runtime", &ok).is_ok());
+//! let mut ok = HardwareIntent::Drive {
+//!     linear_velocity: MetersPerSecond(0.5),
+//!     angular_velocity: RadiansPerSecond(0.0),
+//! };
+//! assert!(gate.authorize_and_verify("runtime", &mut ok).is_ok());
 //!
 //! // Over speed cap → rejected.
-//! let fast = HardwareIntent::Drive { linear_velocity: 5.0, angular_velocity: 0.0 };
-//! assert!(gate.authorize_and_verify("runtime", &fast).is_err());
+//! let mut fast = HardwareIntent::Drive {
+//!     linear_velocity: MetersPerSecond(5.0),
+//!     angular_velocity: RadiansPerSecond(0.0),
+//! };
+//! assert!(gate.authorize_and_verify("runtime", &mut fast).is_err());
 //! ```
 
-use mechos_types::{Capability, HardwareIntent, MechError};
-use tracing::instrument;
+use std::collections::VecDeque;
+
+use chrono::Utc;
+use mechos_types::{
+    Capability, FaultCode, HardwareIntent, IntentEnvelope, MechError, PolicyAuditEntry,
+    PolicyCommand, PolicyReport,
+};
+use tracing::{info, instrument, warn};
 
+use crate::approval::{ApprovalQueue, PendingApproval};
 use crate::capability_manager::CapabilityManager;
+use crate::gate_hook::{GateHook, guarded};
 use crate::state_verifier::StateVerifier;
 
+/// Policy changes kept in the [`KernelGate`]'s audit log.
+pub const POLICY_AUDIT_LEN: usize = 256;
+
 /// The single gateway that `mechos-runtime` must use before forwarding any
 /// [`HardwareIntent`] to `mechos-hal`.
 pub struct KernelGate {
     capability_manager: CapabilityManager,
     state_verifier: StateVerifier,
+    approvals: ApprovalQueue,
+    /// The last [`POLICY_AUDIT_LEN`] policy changes, oldest first.
+    audit: VecDeque<PolicyAuditEntry>,
+    /// Run around every check, in registration order.
+    hooks: Vec<Box<dyn GateHook>>,
 }
 
 impl KernelGate {
@@ -64,9 +109,40 @@ impl KernelGate {
         Self {
             capability_manager,
             state_verifier,
+            approvals: ApprovalQueue::new(),
+            audit: VecDeque::new(),
+            hooks: Vec::new(),
         }
     }
 
+    /// Register `hook` to run around every check, after the hooks
+    /// registered before it.
+    pub fn add_hook(&mut self, hook: Box<dyn GateHook>) {
+        self.hooks.push(hook);
+    }
+
+    /// Names of the registered hooks, in the order they run.
+    pub fn hook_names(&self) -> Vec<&str> {
+        self.hooks.iter().map(|hook| hook.name()).collect()
+    }
+
+    /// The gate's [`CapabilityManager`], e.g. to check an operator's grants.
+    pub fn capability_manager(&self) -> &CapabilityManager {
+        &self.capability_manager
+    }
+
+    /// Mutable access to the gate's [`CapabilityManager`], e.g. to grant
+    /// capabilities to an agent identity registered after construction.
+    pub fn capability_manager_mut(&mut self) -> &mut CapabilityManager {
+        &mut self.capability_manager
+    }
+
+    /// Mutable access to the gate's [`StateVerifier`], e.g. to register an
+    /// additional [`Rule`][crate::state_verifier::Rule] at runtime.
+    pub fn state_verifier_mut(&mut self) -> &mut StateVerifier {
+        &mut self.state_verifier
+    }
+
     /// Authorize `agent_id` for `intent` and validate the intent against all
     /// physical invariants.
     ///
@@ -75,36 +151,269 @@ impl KernelGate {
     /// | Intent | Required [`Capability`] |
     /// |--------|------------------------|
     /// | `MoveEndEffector { .. }` | `HardwareInvoke("end_effector")` |
+    /// | `SetGripper { .. }` | `HardwareInvoke("gripper")` |
+    /// | `MoveJoint { joint, .. }` | `HardwareInvoke(joint)` |
     /// | `Drive` | `HardwareInvoke("drive_base")` |
+    /// | `FollowWaypoints { .. }` | `HardwareInvoke("drive_base")` |
+    /// | `Dock` / `Undock` | `HardwareInvoke("drive_base")` |
     /// | `TriggerRelay { relay_id, .. }` | `HardwareInvoke(relay_id)` |
     /// | `AskHuman { .. }` | `HardwareInvoke("hitl")` |
     /// | `MessagePeer { .. }` | `FleetCommunicate` |
     /// | `BroadcastFleet { .. }` | `FleetCommunicate` |
     /// | `PostTask { .. }` | `TaskBoardAccess` |
+    /// | `Speak { .. }` | `HardwareInvoke("speaker")` |
+    /// | `PlaySound { .. }` | `HardwareInvoke("speaker")` |
+    /// | `Arm { .. }` | `HardwareInvoke("arming")` |
+    /// | `SetAltitude { .. }` / `Goto { .. }` | `HardwareInvoke("flight_controller")` |
+    /// | `Halt { .. }` | none – always permitted, rules are skipped |
+    ///
+    /// Registered [`GateHook`]s run around the checks and may rewrite
+    /// `intent`; dispatch `intent` as it is after this call.  A permitted
+    /// intent counts as a use of its capability in the
+    /// [`CapabilityManager::usage_report`] and is
+    /// [recorded][StateVerifier::record] by stateful rules (e.g. the speech
+    /// rate limit); use [`preview`][Self::preview] for candidates that may
+    /// never be dispatched.
     ///
     /// # Errors
     ///
     /// - [`MechError::Unauthorized`] – agent is missing the required capability.
     /// - [`MechError::HardwareFault`] – a physical safety rule was violated.
+    /// - Any error raised by a [`GateHook`].
     #[instrument(name = "kernel_gate.authorize", skip(self), fields(agent_id, intent = ?intent))]
     pub fn authorize_and_verify(
         &self,
         agent_id: &str,
-        intent: &HardwareIntent,
+        intent: &mut HardwareIntent,
     ) -> Result<(), MechError> {
-        let required_cap = Self::capability_for(intent);
-        self.capability_manager.check(agent_id, &required_cap)?;
-        self.state_verifier.verify(intent)?;
+        self.preview(agent_id, intent)?;
+        if let Some(cap) = Self::capability_for(intent) {
+            self.capability_manager.record_use(agent_id, &cap);
+            self.state_verifier.record(intent);
+        }
         Ok(())
     }
 
-    /// Map a [`HardwareIntent`] to the [`Capability`] the agent must hold.
-    fn capability_for(intent: &HardwareIntent) -> Capability {
-        match intent {
+    /// Run the checks of [`authorize_and_verify`][Self::authorize_and_verify]
+    /// without counting a use of the capability or recording the intent with
+    /// stateful rules, e.g. to filter candidate intents of which at most one
+    /// is dispatched.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`authorize_and_verify`][Self::authorize_and_verify].
+    pub fn preview(&self, agent_id: &str, intent: &mut HardwareIntent) -> Result<(), MechError> {
+        self.hooked(agent_id, intent, |gate, intent| {
+            let Some(required_cap) = Self::capability_for(intent) else {
+                // Halt fast path: stopping is always safe.
+                return Ok(());
+            };
+            gate.capability_manager.check(agent_id, &required_cap)?;
+            gate.state_verifier.verify(intent)
+        })
+    }
+
+    /// Authorize an [`IntentEnvelope`]: reject it if its deadline has already
+    /// passed, then run [`authorize_and_verify`][Self::authorize_and_verify]
+    /// for the envelope's `issued_by` identity.
+    ///
+    /// # Errors
+    ///
+    /// - [`MechError::HardwareFault`] from component `"kernel_gate"` – the
+    ///   envelope is stale.
+    /// - Any error returned by [`authorize_and_verify`][Self::authorize_and_verify].
+    #[instrument(
+        name = "kernel_gate.authorize_envelope",
+        skip_all,
+        fields(agent_id = %envelope.issued_by, correlation_id = %envelope.correlation_id)
+    )]
+    pub fn authorize_envelope(&self, envelope: &mut IntentEnvelope) -> Result<(), MechError> {
+        Self::check_deadline(envelope)?;
+        self.authorize_and_verify(&envelope.issued_by, &mut envelope.intent)
+    }
+
+    /// Hold every intent `policy` returns `true` for until an operator
+    /// approves it.  `Halt` is never held.
+    pub fn set_approval_policy(
+        &mut self,
+        policy: impl Fn(&HardwareIntent) -> bool + Send + Sync + 'static,
+    ) {
+        self.approvals.set_policy(policy);
+    }
+
+    /// `true` when `intent` must go through
+    /// [`hold_for_approval`][Self::hold_for_approval] instead of being
+    /// dispatched straight away.
+    pub fn requires_approval(&self, intent: &HardwareIntent) -> bool {
+        self.approvals.requires_approval(intent)
+    }
+
+    /// First phase of a two-phase approval: run the same checks and hooks as
+    /// [`authorize_envelope`][Self::authorize_envelope], evaluating every
+    /// rule for the operator's report, and park the (possibly rewritten)
+    /// envelope.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`authorize_envelope`][Self::authorize_envelope]; a rejected
+    /// intent is not held.  Holding is not a use of the capability; an
+    /// approved [`decide`][Self::decide] is.
+    pub fn hold_for_approval(
+        &mut self,
+        mut envelope: IntentEnvelope,
+    ) -> Result<&PendingApproval, MechError> {
+        Self::check_deadline(&envelope)?;
+        let mut report = None;
+        self.hooked(&envelope.issued_by, &mut envelope.intent, |gate, intent| {
+            if let Some(required_cap) = Self::capability_for(intent) {
+                gate.capability_manager
+                    .check(&envelope.issued_by, &required_cap)?;
+            }
+            let (rules, violation) = gate.state_verifier.evaluate(intent);
+            report = Some(rules);
+            violation.map_or(Ok(()), Err)
+        })?;
+        Ok(self.approvals.hold(envelope, report.unwrap_or_default()))
+    }
+
+    /// Second phase: apply an operator's decision on the approval pending
+    /// under `request_id`.
+    ///
+    /// Returns the envelope to dispatch when it was approved and still passes
+    /// [`authorize_and_verify`][Self::authorize_and_verify]; `None` when it
+    /// was rejected or nothing is pending under `request_id`.  The envelope's
+    /// deadline is not re-checked: it was set for the agent, not for the
+    /// operator's response time.
+    ///
+    /// # Errors
+    ///
+    /// Any error returned by [`authorize_and_verify`][Self::authorize_and_verify];
+    /// the approval is dropped either way.
+    pub fn decide(
+        &mut self,
+        request_id: &str,
+        approved: bool,
+    ) -> Result<Option<IntentEnvelope>, MechError> {
+        let Some(mut pending) = self.approvals.take(request_id) else {
+            return Ok(None);
+        };
+        if !approved {
+            return Ok(None);
+        }
+        self.authorize_and_verify(&pending.envelope.issued_by, &mut pending.envelope.intent)?;
+        Ok(Some(pending.envelope))
+    }
+
+    /// Every intent awaiting an operator's decision, oldest first.
+    pub fn pending_approvals(&self) -> &[PendingApproval] {
+        self.approvals.pending()
+    }
+
+    /// Apply `operator`'s change to the safety policy and record it in the
+    /// audit log, whether it was applied or refused.
+    /// [`PolicyCommand::List`] changes nothing and is not recorded.
+    ///
+    /// # Errors
+    ///
+    /// [`FaultCode::InvalidConfiguration`] when the rule does not exist,
+    /// must always block, or refuses the parameter value (see
+    /// [`StateVerifier::set_mode`] and [`StateVerifier::set_param`]).
+    pub fn apply_policy(&mut self, operator: &str, command: PolicyCommand) -> Result<(), MechError> {
+        let result = match &command {
+            PolicyCommand::List => return Ok(()),
+            PolicyCommand::SetRuleMode { rule, mode } => self.state_verifier.set_mode(rule, *mode),
+            PolicyCommand::SetRuleParam { rule, param, value } => {
+                self.state_verifier.set_param(rule, param, *value)
+            }
+        };
+        match &result {
+            Ok(()) => info!(operator, command = ?command, "safety policy changed"),
+            Err(e) => warn!(operator, command = ?command, error = %e, "safety policy change refused"),
+        }
+        if self.audit.len() == POLICY_AUDIT_LEN {
+            self.audit.pop_front();
+        }
+        self.audit.push_back(PolicyAuditEntry {
+            timestamp: Utc::now(),
+            operator: operator.to_string(),
+            command,
+            error: result.as_ref().err().map(ToString::to_string),
+        });
+        result
+    }
+
+    /// Every rule with its mode and parameters, every capability grant and
+    /// the audit log of policy changes.
+    pub fn policy_report(&self) -> PolicyReport {
+        PolicyReport {
+            rules: self.state_verifier.rules(),
+            grants: self.capability_manager.grants(),
+            usage: self.capability_manager.usage_report(),
+            audit: self.audit.iter().cloned().collect(),
+        }
+    }
+
+    /// Run `check` on `intent` between the hooks' `before_check` and
+    /// `after_decision`; see [`GateHook`] for the order and semantics.
+    fn hooked(
+        &self,
+        agent_id: &str,
+        intent: &mut HardwareIntent,
+        check: impl FnOnce(&Self, &HardwareIntent) -> Result<(), MechError>,
+    ) -> Result<(), MechError> {
+        let halt = matches!(intent, HardwareIntent::Halt { .. });
+        let mut decision = Ok(());
+        if !halt {
+            for hook in &self.hooks {
+                decision = guarded(hook.as_ref(), || hook.before_check(agent_id, intent));
+                if decision.is_err() {
+                    break;
+                }
+            }
+        }
+        if decision.is_ok() {
+            decision = check(self, intent);
+        }
+        for hook in &self.hooks {
+            let verdict = guarded(hook.as_ref(), || {
+                hook.after_decision(agent_id, intent, decision.as_ref().map(|_| ()))
+            });
+            match verdict {
+                Err(e) if halt => warn!(hook = hook.name(), error = %e, "gate hook cannot refuse a halt"),
+                Err(e) if decision.is_ok() => decision = Err(e),
+                _ => {}
+            }
+        }
+        decision
+    }
+
+    fn check_deadline(envelope: &IntentEnvelope) -> Result<(), MechError> {
+        if envelope.is_expired() {
+            return Err(MechError::HardwareFault {
+                code: FaultCode::DeadlineExpired,
+                component: "kernel_gate".to_string(),
+                details: format!("intent {} is past its deadline", envelope.correlation_id),
+            });
+        }
+        Ok(())
+    }
+
+    /// Map a [`HardwareIntent`] to the [`Capability`] the agent must hold, or
+    /// `None` for [`HardwareIntent::Halt`], which needs none.
+    ///
+    /// See [`authorize_and_verify`][Self::authorize_and_verify] for the full
+    /// mapping table.
+    pub fn capability_for(intent: &HardwareIntent) -> Option<Capability> {
+        let cap = match intent {
             HardwareIntent::MoveEndEffector { .. } => {
                 Capability::HardwareInvoke("end_effector".to_string())
             }
-            HardwareIntent::Drive { .. } => Capability::HardwareInvoke("drive_base".to_string()),
+            HardwareIntent::SetGripper { .. } => Capability::HardwareInvoke("gripper".to_string()),
+            HardwareIntent::MoveJoint { joint, .. } => Capability::HardwareInvoke(joint.clone()),
+            HardwareIntent::Drive { .. }
+            | HardwareIntent::FollowWaypoints { .. }
+            | HardwareIntent::Dock
+            | HardwareIntent::Undock => Capability::HardwareInvoke("drive_base".to_string()),
             HardwareIntent::TriggerRelay { relay_id, .. } => {
                 Capability::HardwareInvoke(relay_id.clone())
             }
@@ -113,7 +422,16 @@ impl KernelGate {
                 Capability::FleetCommunicate
             }
             HardwareIntent::PostTask { .. } => Capability::TaskBoardAccess,
-        }
+            HardwareIntent::Speak { .. } | HardwareIntent::PlaySound { .. } => {
+                Capability::HardwareInvoke("speaker".to_string())
+            }
+            HardwareIntent::Arm { .. } => Capability::HardwareInvoke("arming".to_string()),
+            HardwareIntent::SetAltitude { .. } | HardwareIntent::Goto { .. } => {
+                Capability::HardwareInvoke("flight_controller".to_string())
+            }
+            HardwareIntent::Halt { .. } => return None,
+        };
+        Some(cap)
     }
 }
 
@@ -121,6 +439,7 @@ impl KernelGate {
 mod tests {
     use super::*;
     use crate::state_verifier::SpeedCapRule;
+    use mechos_types::{Meters, MetersPerSecond, RadiansPerSecond};
 
     fn gated_drive(max_linear: f32, max_angular: f32) -> KernelGate {
         let mut caps = CapabilityManager::new();
@@ -128,8 +447,8 @@ mod tests {
 
         let mut verifier = StateVerifier::new();
         verifier.add_rule(Box::new(SpeedCapRule {
-            max_linear,
-            max_angular,
+            max_linear: MetersPerSecond(max_linear),
+            max_angular: RadiansPerSecond(max_angular),
         }));
 
         KernelGate::new(caps, verifier)
@@ -138,12 +457,12 @@ mod tests {
     #[test]
     fn authorized_and_within_caps_passes() {
         let gate = gated_drive(1.0, 1.0);
-        assert!(gate
-            .authorize_and_verify(
+        assert!(
+            gate.authorize_and_verify(
                 "runtime",
-                &HardwareIntent::Drive {
-                    linear_velocity: 0.5,
-                    angular_velocity: 0.0,
+                &mut HardwareIntent::Drive {
+                    linear_velocity: MetersPerSecond(0.5),
+                    angular_velocity: RadiansPerSecond(0.0),
                 }
             )
             .is_ok());
@@ -155,9 +474,9 @@ mod tests {
         // "rogue" has no grants.
         let result = gate.authorize_and_verify(
             "rogue",
-            &HardwareIntent::Drive {
-                linear_velocity: 0.1,
-                angular_velocity: 0.0,
+            &mut HardwareIntent::Drive {
+                linear_velocity: MetersPerSecond(0.1),
+                angular_velocity: RadiansPerSecond(0.0),
             },
         );
         assert!(matches!(result, Err(MechError::Unauthorized(_))));
@@ -169,9 +488,9 @@ mod tests {
         let gate = gated_drive(1.0, 1.0);
         let result = gate.authorize_and_verify(
             "unknown_agent",
-            &HardwareIntent::Drive {
-                linear_velocity: 0.0,
-                angular_velocity: 0.0,
+            &mut HardwareIntent::Drive {
+                linear_velocity: MetersPerSecond(0.0),
+                angular_velocity: RadiansPerSecond(0.0),
             },
         );
         assert!(matches!(result, Err(MechError::Unauthorized(_))));
@@ -182,9 +501,9 @@ mod tests {
         let gate = gated_drive(1.0, 1.0);
         let result = gate.authorize_and_verify(
             "runtime",
-            &HardwareIntent::Drive {
-                linear_velocity: 5.0,
-                angular_velocity: 0.0,
+            &mut HardwareIntent::Drive {
+                linear_velocity: MetersPerSecond(5.0),
+                angular_velocity: RadiansPerSecond(0.0),
             },
         );
         assert!(matches!(result, Err(MechError::HardwareFault { .. })));
@@ -197,25 +516,27 @@ mod tests {
 
         let gate = KernelGate::new(caps, StateVerifier::new());
 
-        assert!(gate
-            .authorize_and_verify(
+        assert!(
+            gate.authorize_and_verify(
                 "runtime",
-                &HardwareIntent::MoveEndEffector {
-                    x: 0.1,
-                    y: 0.2,
-                    z: 0.5,
+                &mut HardwareIntent::MoveEndEffector {
+                    x: Meters(0.1),
+                    y: Meters(0.2),
+                    z: Meters(0.5),
+                    frame_id: None,
                 }
             )
             .is_ok());
 
         // Missing capability → denied.
-        assert!(gate
-            .authorize_and_verify(
+        assert!(
+            gate.authorize_and_verify(
                 "unknown",
-                &HardwareIntent::MoveEndEffector {
-                    x: 0.1,
-                    y: 0.2,
-                    z: 0.5,
+                &mut HardwareIntent::MoveEndEffector {
+                    x: Meters(0.1),
+                    y: Meters(0.2),
+                    z: Meters(0.5),
+                    frame_id: None,
                 }
             )
             .is_err());
@@ -228,10 +549,10 @@ mod tests {
 
         let gate = KernelGate::new(caps, StateVerifier::new());
 
-        assert!(gate
-            .authorize_and_verify(
+        assert!(
+            gate.authorize_and_verify(
                 "runtime",
-                &HardwareIntent::TriggerRelay {
+                &mut HardwareIntent::TriggerRelay {
                     relay_id: "gripper".into(),
                     state: true,
                 }
@@ -246,12 +567,13 @@ mod tests {
 
         let gate = KernelGate::new(caps, StateVerifier::new());
 
-        assert!(gate
-            .authorize_and_verify(
+        assert!(
+            gate.authorize_and_verify(
                 "runtime",
-                &HardwareIntent::AskHuman {
+                &mut HardwareIntent::AskHuman {
                     question: "Which path is safe?".to_string(),
                     context_image_id: None,
+                    question_id: None,
                 }
             )
             .is_ok());
@@ -264,20 +586,20 @@ mod tests {
 
         let gate = KernelGate::new(caps, StateVerifier::new());
 
-        assert!(gate
-            .authorize_and_verify(
+        assert!(
+            gate.authorize_and_verify(
                 "runtime",
-                &HardwareIntent::BroadcastFleet {
+                &mut HardwareIntent::BroadcastFleet {
                     message: "I am at X:5, Y:5.".to_string(),
                 }
             )
             .is_ok());
 
         // Missing capability → denied.
-        assert!(gate
-            .authorize_and_verify(
+        assert!(
+            gate.authorize_and_verify(
                 "unknown",
-                &HardwareIntent::BroadcastFleet {
+                &mut HardwareIntent::BroadcastFleet {
                     message: "Hello fleet.".to_string(),
                 }
             )
@@ -291,10 +613,10 @@ mod tests {
 
         let gate = KernelGate::new(caps, StateVerifier::new());
 
-        assert!(gate
-            .authorize_and_verify(
+        assert!(
+            gate.authorize_and_verify(
                 "runtime",
-                &HardwareIntent::MessagePeer {
+                &mut HardwareIntent::MessagePeer {
                     target_robot_id: "robot_bravo".to_string(),
                     message: "Need help.".to_string(),
                 }
@@ -309,10 +631,10 @@ mod tests {
 
         let gate = KernelGate::new(caps, StateVerifier::new());
 
-        assert!(gate
-            .authorize_and_verify(
+        assert!(
+            gate.authorize_and_verify(
                 "runtime",
-                &HardwareIntent::PostTask {
+                &mut HardwareIntent::PostTask {
                     title: "Move Box 1".to_string(),
                     description: "Move red box.".to_string(),
                 }
@@ -320,14 +642,272 @@ mod tests {
             .is_ok());
 
         // Missing capability → denied.
-        assert!(gate
-            .authorize_and_verify(
+        assert!(
+            gate.authorize_and_verify(
                 "unknown",
-                &HardwareIntent::PostTask {
+                &mut HardwareIntent::PostTask {
                     title: "Task".to_string(),
                     description: "desc".to_string(),
                 }
             )
             .is_err());
     }
+
+    #[test]
+    fn move_joint_requires_per_joint_capability() {
+        let mut caps = CapabilityManager::new();
+        caps.grant("runtime", Capability::HardwareInvoke("elbow".into()));
+        let gate = KernelGate::new(caps, StateVerifier::new());
+
+        let mut elbow = HardwareIntent::MoveJoint {
+            joint: "elbow".to_string(),
+            angle_rad: 0.5,
+            max_velocity: RadiansPerSecond(0.2),
+        };
+        assert!(gate.authorize_and_verify("runtime", &mut elbow).is_ok());
+
+        // Holding "elbow" grants nothing for another joint.
+        let mut wrist = HardwareIntent::MoveJoint {
+            joint: "wrist".to_string(),
+            angle_rad: 0.5,
+            max_velocity: RadiansPerSecond(0.2),
+        };
+        assert!(matches!(
+            gate.authorize_and_verify("runtime", &mut wrist),
+            Err(MechError::Unauthorized(_))
+        ));
+    }
+
+    #[test]
+    fn arming_requires_its_own_capability() {
+        let mut caps = CapabilityManager::new();
+        caps.grant(
+            "runtime",
+            Capability::HardwareInvoke("flight_controller".into()),
+        );
+        let mut gate = KernelGate::new(caps, StateVerifier::new());
+
+        let mut goto = HardwareIntent::Goto {
+            latitude_deg: 47.397742,
+            longitude_deg: 8.545594,
+            altitude: Meters(10.0),
+        };
+        assert!(gate.authorize_and_verify("runtime", &mut goto).is_ok());
+
+        // Flying does not imply permission to arm the motors.
+        assert!(matches!(
+            gate.authorize_and_verify("runtime", &mut HardwareIntent::Arm { armed: true }),
+            Err(MechError::Unauthorized(_))
+        ));
+
+        gate.capability_manager_mut()
+            .grant("runtime", Capability::HardwareInvoke("arming".into()));
+        assert!(
+            gate.authorize_and_verify("runtime", &mut HardwareIntent::Arm { armed: true })
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn halt_is_always_permitted() {
+        // Zero speed caps and no grants at all: Halt still passes.
+        let gate = gated_drive(0.0, 0.0);
+        let mut halt = HardwareIntent::Halt {
+            reason: "operator e-stop".to_string(),
+        };
+        assert!(gate.authorize_and_verify("unknown_agent", &mut halt).is_ok());
+        assert_eq!(KernelGate::capability_for(&halt), None);
+    }
+
+    #[test]
+    fn authorize_envelope_rejects_stale_intents() {
+        let gate = gated_drive(1.0, 1.0);
+        let drive = HardwareIntent::Drive {
+            linear_velocity: MetersPerSecond(0.5),
+            angular_velocity: RadiansPerSecond(0.0),
+        };
+        let now = chrono::Utc::now();
+
+        let mut fresh = IntentEnvelope::new(drive.clone(), "runtime")
+            .with_deadline(now + chrono::Duration::seconds(5));
+        assert!(gate.authorize_envelope(&mut fresh).is_ok());
+
+        let mut stale =
+            IntentEnvelope::new(drive, "runtime").with_deadline(now - chrono::Duration::seconds(1));
+        assert!(matches!(
+            gate.authorize_envelope(&mut stale),
+            Err(MechError::HardwareFault { ref component, .. }) if component == "kernel_gate"
+        ));
+    }
+
+    #[test]
+    fn capability_manager_mut_grants_late_identity() {
+        let mut gate = KernelGate::new(CapabilityManager::new(), StateVerifier::new());
+        let mut intent = HardwareIntent::Drive {
+            linear_velocity: MetersPerSecond(0.1),
+            angular_velocity: RadiansPerSecond(0.0),
+        };
+        assert!(gate.authorize_and_verify("navigator", &mut intent).is_err());
+
+        gate.capability_manager_mut()
+            .grant("navigator", Capability::HardwareInvoke("drive_base".into()));
+        assert!(gate.authorize_and_verify("navigator", &mut intent).is_ok());
+    }
+
+    #[test]
+    fn approval_holds_until_operator_decides() {
+        let mut gate = gated_drive(1.0, 1.0);
+        gate.set_approval_policy(|intent| matches!(intent, HardwareIntent::Drive { .. }));
+        let drive = |linear| HardwareIntent::Drive {
+            linear_velocity: MetersPerSecond(linear),
+            angular_velocity: RadiansPerSecond(0.0),
+        };
+        assert!(gate.requires_approval(&drive(0.5)));
+
+        // A violating intent is rejected outright, never queued.
+        assert!(
+            gate.hold_for_approval(IntentEnvelope::new(drive(5.0), "runtime"))
+                .is_err()
+        );
+        assert!(gate.pending_approvals().is_empty());
+
+        let pending = gate
+            .hold_for_approval(IntentEnvelope::new(drive(0.5), "runtime"))
+            .unwrap();
+        assert_eq!(pending.report.len(), 1);
+        assert!(pending.report[0].passed);
+        let approved = pending.request_id.clone();
+        let rejected = gate
+            .hold_for_approval(IntentEnvelope::new(drive(0.2), "runtime"))
+            .unwrap()
+            .request_id
+            .clone();
+        assert_eq!(gate.pending_approvals().len(), 2);
+
+        assert!(gate.decide(&rejected, false).unwrap().is_none());
+        let envelope = gate.decide(&approved, true).unwrap().unwrap();
+        assert!(matches!(envelope.intent, HardwareIntent::Drive { .. }));
+        assert!(
+            gate.decide(&approved, true).unwrap().is_none(),
+            "decided once"
+        );
+        assert!(gate.pending_approvals().is_empty());
+    }
+
+    #[test]
+    fn only_permitted_intents_count_as_uses() {
+        let mut gate = gated_drive(1.0, 1.0);
+        gate.set_approval_policy(|intent| matches!(intent, HardwareIntent::Drive { .. }));
+        let drive = |linear| HardwareIntent::Drive {
+            linear_velocity: MetersPerSecond(linear),
+            angular_velocity: RadiansPerSecond(0.0),
+        };
+        let uses = |gate: &KernelGate| gate.capability_manager().usage_report()[0].uses;
+
+        // Probes and rule violations are not uses.
+        assert!(gate.preview("runtime", &mut drive(0.5)).is_ok());
+        assert!(gate.authorize_and_verify("runtime", &mut drive(5.0)).is_err());
+        assert_eq!(uses(&gate), 0);
+
+        // A held intent counts once, when it is approved.
+        let request_id = gate
+            .hold_for_approval(IntentEnvelope::new(drive(0.5), "runtime"))
+            .unwrap()
+            .request_id
+            .clone();
+        assert_eq!(uses(&gate), 0);
+        gate.decide(&request_id, true).unwrap().unwrap();
+        assert_eq!(uses(&gate), 1);
+
+        assert!(gate.authorize_and_verify("runtime", &mut drive(0.5)).is_ok());
+        assert_eq!(uses(&gate), 2);
+    }
+
+    /// Refuses to say the word "secret".
+    struct NoSecrets;
+
+    impl crate::state_verifier::Rule for NoSecrets {
+        fn name(&self) -> &str {
+            "no_secrets"
+        }
+
+        fn check(&self, intent: &HardwareIntent) -> Result<(), MechError> {
+            match intent {
+                HardwareIntent::Speak { text, .. } if text.contains("secret") => {
+                    Err(MechError::HardwareFault {
+                        code: FaultCode::Unknown,
+                        component: "speaker".to_string(),
+                        details: "that is a secret".to_string(),
+                    })
+                }
+                _ => Ok(()),
+            }
+        }
+    }
+
+    #[test]
+    fn only_dispatched_speech_counts_towards_the_rate_limit() {
+        let mut caps = CapabilityManager::new();
+        caps.grant("runtime", Capability::HardwareInvoke("speaker".into()));
+        let mut verifier = StateVerifier::new();
+        verifier.add_rule(Box::new(crate::state_verifier::SpeechRule::new(100, 1)));
+        verifier.add_rule(Box::new(NoSecrets));
+        let mut gate = KernelGate::new(caps, verifier);
+        gate.set_approval_policy(|intent| matches!(intent, HardwareIntent::Speak { .. }));
+        let speak = |text: &str| HardwareIntent::Speak {
+            text: text.to_string(),
+            voice: None,
+        };
+
+        // Previews, held intents and intents a later rule rejects are not
+        // utterances.
+        assert!(gate.preview("runtime", &mut speak("hello")).is_ok());
+        gate.hold_for_approval(IntentEnvelope::new(speak("hello"), "runtime"))
+            .unwrap();
+        assert!(gate.authorize_and_verify("runtime", &mut speak("a secret")).is_err());
+
+        assert!(gate.authorize_and_verify("runtime", &mut speak("hello")).is_ok());
+        assert!(gate.authorize_and_verify("runtime", &mut speak("hello")).is_err());
+    }
+
+    #[test]
+    fn policy_changes_are_validated_and_audited() {
+        let mut gate = gated_drive(1.0, 1.0);
+        let mut fast = HardwareIntent::Drive {
+            linear_velocity: MetersPerSecond(0.8),
+            angular_velocity: RadiansPerSecond(0.0),
+        };
+
+        gate.apply_policy(
+            "alice",
+            PolicyCommand::SetRuleParam {
+                rule: "speed_cap".to_string(),
+                param: "max_linear".to_string(),
+                value: 0.5,
+            },
+        )
+        .unwrap();
+        assert!(gate.authorize_and_verify("runtime", &mut fast).is_err());
+
+        let refused = gate.apply_policy(
+            "bob",
+            PolicyCommand::SetRuleParam {
+                rule: "speed_cap".to_string(),
+                param: "max_linear".to_string(),
+                value: 0.0,
+            },
+        );
+        assert!(refused.is_err());
+        gate.apply_policy("bob", PolicyCommand::List).unwrap();
+
+        let report = gate.policy_report();
+        assert_eq!(report.rules[0].params["max_linear"], 0.5);
+        assert_eq!(report.grants[0].agent_id, "runtime");
+        let audit: Vec<(&str, bool)> = report
+            .audit
+            .iter()
+            .map(|entry| (entry.operator.as_str(), entry.error.is_none()))
+            .collect();
+        assert_eq!(audit, [("alice", true), ("bob", false)]);
+    }
 }
@@ -11,24 +11,48 @@
 //!   any tool or hardware is invoked.
 //! - [`state_verifier`] – [`StateVerifier`][state_verifier::StateVerifier]:
 //!   a rule engine that validates every [`HardwareIntent`][mechos_types::HardwareIntent]
-//!   against registered physical invariants (workspace bounds, speed caps, etc.)
-//!   and returns a fault if any invariant is violated.
+//!   against registered physical invariants (workspace bounds, geofence, joint
+//!   limits, speed caps, speech length and rate, battery level, localization
+//!   staleness, etc.) and returns a fault if any invariant is violated.
 //! - [`kernel_gate`] – [`KernelGate`][kernel_gate::KernelGate]:
 //!   the single interception point that `mechos-runtime` must pass through
 //!   before forwarding a [`HardwareIntent`][mechos_types::HardwareIntent] to
 //!   `mechos-hal`.  Combines capability checking and physical invariant
-//!   validation in one call.
+//!   validation in one call.  Lets an operator switch rules between
+//!   blocking and warning and adjust their parameters at runtime, keeping an
+//!   audit log of every change.
+//! - [`gate_hook`] – [`GateHook`][gate_hook::GateHook]:
+//!   user hooks run around every gate decision, for logging, metrics,
+//!   intent rewriting or external policy engines.
+//! - [`approval`] – [`ApprovalQueue`][approval::ApprovalQueue]:
+//!   intents the [`KernelGate`][kernel_gate::KernelGate]'s approval policy
+//!   holds, with every rule's verdict, until an operator approves or rejects
+//!   them.
+//! - [`safety`] – [`SafetyState`][safety::SafetyState]:
+//!   the operator e-stop latch and safety mode, enforced by a
+//!   [`SafetyInterlock`][safety::SafetyInterlock] rule that rejects every
+//!   command but `Halt` while latched and caps speed in reduced mode.
 //! - [`watchdog`] – [`Watchdog`][watchdog::Watchdog]:
 //!   tracks heartbeats from registered subsystems and detects frozen
 //!   components so that a supervisor can trigger restarts.
 
+pub mod approval;
 pub mod capability_manager;
+pub mod gate_hook;
 pub mod kernel_gate;
+pub mod safety;
 pub mod state_verifier;
 pub mod watchdog;
 
+pub use approval::{ApprovalPolicy, ApprovalQueue, PendingApproval};
 pub use capability_manager::CapabilityManager;
-pub use kernel_gate::KernelGate;
-pub use state_verifier::{EndEffectorWorkspaceRule, ManualOverrideInterlock, Rule, SpeedCapRule, StateVerifier};
+pub use gate_hook::GateHook;
+pub use kernel_gate::{KernelGate, POLICY_AUDIT_LEN};
+pub use safety::{SafetyInterlock, SafetyState};
+pub use state_verifier::{
+    BatteryInterlock, EndEffectorWorkspaceRule, GeofenceRule, JointLimit, JointLimitRule,
+    ManualOverrideInterlock, MovingObstacleRule, Rule, SpeechRule, SpeedCapRule, StaleDataRule,
+    StateVerifier,
+};
 pub use watchdog::{ComponentHealth, Watchdog};
 
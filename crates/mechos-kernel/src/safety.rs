@@ -0,0 +1,283 @@
+//! [`SafetyState`] – the e-stop latch and safety-mode state machine.
+//!
+//! Two independent pieces of state, driven by operator
+//! [`SafetyCommand`]s:
+//!
+//! * `EStop` latches from any state; the robot stays stopped until an
+//!   explicit `Reset`, even if the command that stopped it is repeated or
+//!   the operator's link drops.
+//! * `SetMode` is refused while latched, so a mode change can never be
+//!   mistaken for a release.
+//!
+//! Register a [`SafetyInterlock`] on the [`StateVerifier`][crate::StateVerifier]
+//! to enforce the state: every intent except `Halt` is rejected while
+//! latched, and [`SafetyMode::Reduced`] caps motion speed.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use mechos_types::{
+    FaultCode, HardwareIntent, MechError, MetersPerSecond, SafetyCommand, SafetyMode,
+};
+
+use crate::state_verifier::{Rule, positive, unknown_param};
+
+/// Default linear speed cap (m/s) applied by [`SafetyInterlock`] in
+/// [`SafetyMode::Reduced`].
+pub const DEFAULT_REDUCED_MAX_LINEAR: MetersPerSecond = MetersPerSecond::new(0.25);
+
+/// The e-stop latch and operating mode, shared between whoever receives
+/// operator commands and the [`SafetyInterlock`] enforcing them.
+#[derive(Debug, Default)]
+pub struct SafetyState {
+    estopped: AtomicBool,
+    mode: Mutex<SafetyMode>,
+}
+
+impl SafetyState {
+    /// A released latch in [`SafetyMode::Normal`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `true` while the e-stop latch is engaged.
+    pub fn is_estopped(&self) -> bool {
+        self.estopped.load(Ordering::Acquire)
+    }
+
+    /// The current operating mode.
+    pub fn mode(&self) -> SafetyMode {
+        *self.mode.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Engage the e-stop latch.  Returns `true` if it was released before.
+    pub fn engage_estop(&self) -> bool {
+        !self.estopped.swap(true, Ordering::AcqRel)
+    }
+
+    /// Release the e-stop latch.  Returns `true` if it was engaged before.
+    pub fn reset_estop(&self) -> bool {
+        self.estopped.swap(false, Ordering::AcqRel)
+    }
+
+    /// Switch the operating mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::HardwareFault`] with
+    /// [`FaultCode::EmergencyStop`] while the e-stop is latched.
+    pub fn set_mode(&self, mode: SafetyMode) -> Result<(), MechError> {
+        let mut current = self.mode.lock().unwrap_or_else(|e| e.into_inner());
+        if self.is_estopped() {
+            return Err(estop_fault(format!(
+                "e-stop latched; reset before switching to {mode:?} mode"
+            )));
+        }
+        *current = mode;
+        Ok(())
+    }
+
+    /// Apply an operator [`SafetyCommand`].
+    ///
+    /// # Errors
+    ///
+    /// See [`set_mode`][Self::set_mode].
+    pub fn apply(&self, command: SafetyCommand) -> Result<(), MechError> {
+        match command {
+            SafetyCommand::EStop => {
+                self.engage_estop();
+            }
+            SafetyCommand::Reset => {
+                self.reset_estop();
+            }
+            SafetyCommand::SetMode(mode) => self.set_mode(mode)?,
+        }
+        Ok(())
+    }
+}
+
+/// Safety rule enforcing a shared [`SafetyState`]: while the e-stop is
+/// latched every intent is rejected (only `Halt`, which the
+/// [`KernelGate`][crate::KernelGate] never checks against rules, gets
+/// through); in [`SafetyMode::Reduced`], [`HardwareIntent::Drive`] faster
+/// than [`reduced_max_linear`][Self::reduced_max_linear] and
+/// [`HardwareIntent::FollowWaypoints`] with a higher `max_speed` are
+/// rejected.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use mechos_kernel::{SafetyInterlock, SafetyState, StateVerifier};
+/// use mechos_types::{HardwareIntent, MetersPerSecond, RadiansPerSecond};
+///
+/// let safety = Arc::new(SafetyState::new());
+/// let mut verifier = StateVerifier::new();
+/// verifier.add_rule(Box::new(SafetyInterlock::new(Arc::clone(&safety))));
+///
+/// let drive = HardwareIntent::Drive {
+///     linear_velocity: MetersPerSecond(0.1), angular_velocity: RadiansPerSecond(0.0),
+/// };
+/// assert!(verifier.verify(&drive).is_ok());
+///
+/// safety.engage_estop();
+/// assert!(verifier.verify(&drive).is_err());
+/// ```
+pub struct SafetyInterlock {
+    /// The latch and mode to enforce.
+    pub state: Arc<SafetyState>,
+    /// Linear speed cap in [`SafetyMode::Reduced`].
+    pub reduced_max_linear: MetersPerSecond,
+}
+
+impl SafetyInterlock {
+    /// Create an interlock enforcing `state`, with the
+    /// [`DEFAULT_REDUCED_MAX_LINEAR`] cap.
+    pub fn new(state: Arc<SafetyState>) -> Self {
+        Self {
+            state,
+            reduced_max_linear: DEFAULT_REDUCED_MAX_LINEAR,
+        }
+    }
+
+    /// Override the reduced-mode speed cap.
+    pub fn with_reduced_max_linear(mut self, reduced_max_linear: MetersPerSecond) -> Self {
+        self.reduced_max_linear = reduced_max_linear;
+        self
+    }
+}
+
+impl Rule for SafetyInterlock {
+    fn name(&self) -> &str {
+        "safety_interlock"
+    }
+
+    fn params(&self) -> Vec<(&'static str, f32)> {
+        vec![("reduced_max_linear", self.reduced_max_linear.get())]
+    }
+
+    fn set_param(&mut self, param: &str, value: f32) -> Result<(), MechError> {
+        match param {
+            "reduced_max_linear" => {
+                self.reduced_max_linear = MetersPerSecond(positive(param, value)?);
+            }
+            _ => return Err(unknown_param(self.name(), param)),
+        }
+        Ok(())
+    }
+
+    /// A latched e-stop must stop everything.
+    fn may_warn(&self) -> bool {
+        false
+    }
+
+    fn check(&self, intent: &HardwareIntent) -> Result<(), MechError> {
+        if self.state.is_estopped() {
+            return Err(estop_fault(
+                "e-stop latched; all commands suspended until reset".to_string(),
+            ));
+        }
+        if self.state.mode() != SafetyMode::Reduced {
+            return Ok(());
+        }
+        let cap = self.reduced_max_linear.get();
+        let speed = match intent {
+            HardwareIntent::Drive {
+                linear_velocity, ..
+            } => linear_velocity.get().abs(),
+            HardwareIntent::FollowWaypoints { max_speed, .. } => max_speed.get(),
+            _ => return Ok(()),
+        };
+        if speed > cap {
+            return Err(MechError::HardwareFault {
+                code: FaultCode::SpeedCapExceeded,
+                component: "drive_base".to_string(),
+                details: format!("reduced mode; {speed} m/s exceeds the {cap} m/s cap"),
+            });
+        }
+        Ok(())
+    }
+}
+
+fn estop_fault(details: String) -> MechError {
+    MechError::HardwareFault {
+        code: FaultCode::EmergencyStop,
+        component: "safety".to_string(),
+        details,
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mechos_types::{Meters, RadiansPerSecond};
+
+    fn drive(linear: f32) -> HardwareIntent {
+        HardwareIntent::Drive {
+            linear_velocity: MetersPerSecond(linear),
+            angular_velocity: RadiansPerSecond(0.0),
+        }
+    }
+
+    #[test]
+    fn estop_latches_until_reset() {
+        let state = Arc::new(SafetyState::new());
+        let rule = SafetyInterlock::new(Arc::clone(&state));
+        assert!(rule.check(&drive(0.5)).is_ok());
+
+        assert!(state.engage_estop());
+        assert!(!state.engage_estop(), "a repeated e-stop is idempotent");
+        let err = rule.check(&HardwareIntent::Speak {
+            text: "hello".to_string(),
+            voice: None,
+        });
+        assert!(matches!(
+            err,
+            Err(MechError::HardwareFault {
+                code: FaultCode::EmergencyStop,
+                ..
+            })
+        ));
+
+        assert!(state.reset_estop());
+        assert!(rule.check(&drive(0.5)).is_ok());
+    }
+
+    #[test]
+    fn mode_change_is_refused_while_latched() {
+        let state = SafetyState::new();
+        state.apply(SafetyCommand::EStop).unwrap();
+        assert!(
+            state
+                .apply(SafetyCommand::SetMode(SafetyMode::Reduced))
+                .is_err()
+        );
+        assert_eq!(state.mode(), SafetyMode::Normal);
+
+        state.apply(SafetyCommand::Reset).unwrap();
+        state
+            .apply(SafetyCommand::SetMode(SafetyMode::Reduced))
+            .unwrap();
+        assert_eq!(state.mode(), SafetyMode::Reduced);
+    }
+
+    #[test]
+    fn reduced_mode_caps_motion_speed() {
+        let state = Arc::new(SafetyState::new());
+        let rule = SafetyInterlock::new(Arc::clone(&state));
+        state.set_mode(SafetyMode::Reduced).unwrap();
+
+        assert!(rule.check(&drive(0.2)).is_ok());
+        assert!(rule.check(&drive(-0.5)).is_err());
+        let follow = HardwareIntent::FollowWaypoints {
+            points: vec![(Meters(1.0), Meters(0.0))],
+            max_speed: MetersPerSecond(1.0),
+        };
+        assert!(rule.check(&follow).is_err());
+        assert!(rule.check(&HardwareIntent::Dock).is_ok());
+    }
+}
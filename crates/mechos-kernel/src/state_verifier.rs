@@ -5,17 +5,47 @@
 //! order; the first violation returns a [`MechError::HardwareFault`] and the
 //! intent is **not** executed.
 //!
-//! Two built-in rules are provided:
+//! Built-in rules:
 //! - [`SpeedCapRule`] – rejects `Drive` commands whose linear or angular
-//!   velocities exceed configured caps.
+//!   velocities exceed configured caps, and `FollowWaypoints` paths whose
+//!   `max_speed` exceeds the linear cap.
 //! - [`EndEffectorWorkspaceRule`] – rejects `MoveEndEffector` commands that
 //!   place the end-effector outside its safe cubic workspace.
+//! - [`GeofenceRule`] – rejects `FollowWaypoints` paths with any waypoint
+//!   outside the robot's rectangular operating area.
+//! - [`JointLimitRule`] – rejects `MoveJoint` commands outside a joint's
+//!   configured range or velocity limit, and `SetGripper` positions outside
+//!   `[0.0, 1.0]`.
+//! - [`SpeechRule`] – rejects over-long `Speak` utterances and rate-limits
+//!   `Speak` / `PlaySound` so the agent cannot flood the speaker.
+//! - [`ManualOverrideInterlock`] – suspends AI `Drive` commands while a
+//!   human holds the dashboard joystick.
+//! - [`BatteryInterlock`] – refuses `Undock` while the battery is below its
+//!   undock threshold.
+//! - [`StaleDataRule`] – while localization is degraded, refuses
+//!   `FollowWaypoints` and caps `Drive` speed.
+//! - [`MovingObstacleRule`] – caps `Drive` and `FollowWaypoints` speed while
+//!   a moving obstacle is within its safety margin.
+//!
+//! # Runtime policy
+//!
+//! An operator may switch a rule to [`RuleMode::Warn`], so its violations
+//! are logged and reported but no longer block, and adjust the numeric
+//! parameters a rule exposes through [`Rule::params`] (speed caps, workspace
+//! and geofence bounds).  Every new value is validated by the rule itself;
+//! interlocks whose [`Rule::may_warn`] is `false` always block.
 
-use mechos_types::{HardwareIntent, MechError};
+use mechos_types::{
+    BASE_FRAME, FaultCode, HardwareIntent, MechError, Meters, MetersPerSecond, RadiansPerSecond,
+    RuleInfo, RuleMode, RuleVerdict,
+};
+use std::collections::{HashMap, VecDeque};
 use std::sync::{
-    Arc,
-    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+    atomic::{AtomicBool, AtomicU8, AtomicU32, Ordering},
 };
+use std::time::{Duration, Instant};
+use tracing::warn;
 
 // ────────────────────────────────────────────────────────────────────────────
 // Rule trait
@@ -31,7 +61,93 @@ pub trait Rule: Send + Sync {
 
     /// Return `Ok(())` when the intent satisfies the invariant, or
     /// [`MechError::HardwareFault`] when it is violated.
+    ///
+    /// Checks may run for intents that are never dispatched (previews,
+    /// approval reports, intents another rule rejects), so they must not
+    /// change the rule's state; see [`record`][Self::record].
     fn check(&self, intent: &HardwareIntent) -> Result<(), MechError>;
+
+    /// `intent` passed every check and is being dispatched.  Stateful rules
+    /// such as [`SpeechRule`] account for it here.  Does nothing by default.
+    fn record(&self, intent: &HardwareIntent) {
+        let _ = intent;
+    }
+
+    /// Numeric parameters an operator may adjust at runtime, with their
+    /// current values.  None by default.
+    fn params(&self) -> Vec<(&'static str, f32)> {
+        Vec::new()
+    }
+
+    /// Validate `value` and make it the new value of `param`.
+    ///
+    /// # Errors
+    ///
+    /// [`FaultCode::InvalidConfiguration`] for an unknown parameter or a
+    /// value the rule cannot enforce; the rule is left unchanged.
+    fn set_param(&mut self, param: &str, value: f32) -> Result<(), MechError> {
+        let _ = value;
+        Err(unknown_param(self.name(), param))
+    }
+
+    /// `false` for interlocks an operator must not demote to
+    /// [`RuleMode::Warn`].
+    fn may_warn(&self) -> bool {
+        true
+    }
+}
+
+/// A policy change the kernel refuses.
+pub(crate) fn invalid_policy(details: String) -> MechError {
+    MechError::HardwareFault {
+        code: FaultCode::InvalidConfiguration,
+        component: "state_verifier".to_string(),
+        details,
+    }
+}
+
+/// A parameter `rule` does not expose.
+pub(crate) fn unknown_param(rule: &str, param: &str) -> MechError {
+    invalid_policy(format!("rule \"{rule}\" has no parameter \"{param}\""))
+}
+
+/// Validate a new speed cap: finite and positive.
+pub(crate) fn positive(param: &str, value: f32) -> Result<f32, MechError> {
+    if value.is_finite() && value > 0.0 {
+        Ok(value)
+    } else {
+        Err(invalid_policy(format!(
+            "{param} must be positive, got {value}"
+        )))
+    }
+}
+
+/// Set one of the `(min, max)` axis bounds named `min_<axis>` / `max_<axis>`
+/// to `value`, keeping the range non-empty.
+fn set_bound<'a>(
+    bounds: impl IntoIterator<Item = (&'a str, &'a mut Meters, &'a mut Meters)>,
+    param: &str,
+    value: f32,
+) -> Option<Result<(), MechError>> {
+    for (axis, min, max) in bounds {
+        let is_min = param.strip_prefix("min_") == Some(axis);
+        if !is_min && param.strip_prefix("max_") != Some(axis) {
+            continue;
+        }
+        let (lo, hi) = if is_min {
+            (value, max.0)
+        } else {
+            (min.0, value)
+        };
+        if !(value.is_finite() && lo < hi) {
+            return Some(Err(invalid_policy(format!(
+                "{param}={value} leaves an empty {axis} range [{lo}, {hi}]"
+            ))));
+        }
+        *if is_min { min } else { max } = Meters(value);
+        return Some(Ok(()));
+    }
+    None
 }
 
 // ────────────────────────────────────────────────────────────────────────────
@@ -45,20 +161,29 @@ pub trait Rule: Send + Sync {
 ///
 /// ```
 /// use mechos_kernel::state_verifier::{StateVerifier, SpeedCapRule};
-/// use mechos_types::HardwareIntent;
+/// use mechos_types::{HardwareIntent, MetersPerSecond, RadiansPerSecond};
 ///
 /// let mut verifier = StateVerifier::new();
-/// verifier.add_rule(Box::new(SpeedCapRule { max_linear: 1.0, max_angular: 1.0 }));
+/// verifier.add_rule(Box::new(SpeedCapRule {
+///     max_linear: MetersPerSecond(1.0),
+///     max_angular: RadiansPerSecond(1.0),
+/// }));
 ///
-/// let safe = HardwareIntent::Drive { linear_velocity: 0.5, angular_velocity: 0.2 };
+/// let safe = HardwareIntent::Drive {
+///     linear_velocity: MetersPerSecond(0.5),
+///     angular_velocity: RadiansPerSecond(0.2),
+/// };
 /// assert!(verifier.verify(&safe).is_ok());
 ///
-/// let too_fast = HardwareIntent::Drive { linear_velocity: 2.0, angular_velocity: 0.0 };
+/// let too_fast = HardwareIntent::Drive {
+///     linear_velocity: MetersPerSecond(2.0),
+///     angular_velocity: RadiansPerSecond(0.0),
+/// };
 /// assert!(verifier.verify(&too_fast).is_err());
 /// ```
 #[derive(Default)]
 pub struct StateVerifier {
-    rules: Vec<Box<dyn Rule>>,
+    rules: Vec<(Box<dyn Rule>, RuleMode)>,
 }
 
 impl StateVerifier {
@@ -67,21 +192,123 @@ impl StateVerifier {
         Self::default()
     }
 
-    /// Register a new [`Rule`].  Rules are evaluated in insertion order.
+    /// Register a new [`Rule`] in [`RuleMode::Block`].  Rules are evaluated
+    /// in insertion order.
     pub fn add_rule(&mut self, rule: Box<dyn Rule>) {
-        self.rules.push(rule);
+        self.rules.push((rule, RuleMode::Block));
     }
 
     /// Validate `intent` against every registered rule.
     ///
-    /// Returns the first [`MechError::HardwareFault`] encountered, or `Ok(())`
-    /// when all rules pass.
+    /// Returns the first [`MechError::HardwareFault`] raised by a rule in
+    /// [`RuleMode::Block`], or `Ok(())` when all of them pass.  Violations of
+    /// rules in [`RuleMode::Warn`] are only logged.
     pub fn verify(&self, intent: &HardwareIntent) -> Result<(), MechError> {
-        for rule in &self.rules {
-            rule.check(intent)?;
+        for (rule, mode) in &self.rules {
+            match (rule.check(intent), mode) {
+                (Err(e), RuleMode::Block) => return Err(e),
+                (Err(e), RuleMode::Warn) => {
+                    warn!(rule = rule.name(), error = %e, "rule in warn mode violated");
+                }
+                (Ok(()), _) => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Tell every rule that `intent` passed [`verify`][Self::verify] and is
+    /// being dispatched, so stateful rules can account for it.
+    pub fn record(&self, intent: &HardwareIntent) {
+        for (rule, _) in &self.rules {
+            rule.record(intent);
+        }
+    }
+
+    /// Every registered rule with its mode and adjustable parameters, in
+    /// evaluation order.
+    pub fn rules(&self) -> Vec<RuleInfo> {
+        self.rules
+            .iter()
+            .map(|(rule, mode)| RuleInfo {
+                rule: rule.name().to_string(),
+                mode: *mode,
+                may_warn: rule.may_warn(),
+                params: rule
+                    .params()
+                    .into_iter()
+                    .map(|(param, value)| (param.to_string(), value))
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Make the first rule named `rule` block or only warn.
+    ///
+    /// # Errors
+    ///
+    /// [`FaultCode::InvalidConfiguration`] when no rule has that name, or
+    /// the rule may not warn.
+    pub fn set_mode(&mut self, rule: &str, mode: RuleMode) -> Result<(), MechError> {
+        let (found, current) = self.rule_mut(rule)?;
+        if mode == RuleMode::Warn && !found.may_warn() {
+            return Err(invalid_policy(format!("rule \"{rule}\" must always block")));
         }
+        *current = mode;
         Ok(())
     }
+
+    /// Set parameter `param` of the first rule named `rule`, after the rule
+    /// validated `value`.
+    ///
+    /// # Errors
+    ///
+    /// [`FaultCode::InvalidConfiguration`] when no rule has that name, or
+    /// the rule refuses the parameter or value.
+    pub fn set_param(&mut self, rule: &str, param: &str, value: f32) -> Result<(), MechError> {
+        self.rule_mut(rule)?.0.set_param(param, value)
+    }
+
+    fn rule_mut(&mut self, name: &str) -> Result<(&mut Box<dyn Rule>, &mut RuleMode), MechError> {
+        self.rules
+            .iter_mut()
+            .find(|(rule, _)| rule.name() == name)
+            .map(|(rule, mode)| (rule, mode))
+            .ok_or_else(|| invalid_policy(format!("no rule named \"{name}\"")))
+    }
+
+    /// Evaluate `intent` against every registered rule without stopping at
+    /// the first violation, e.g. to show an operator the full picture.
+    pub fn report(&self, intent: &HardwareIntent) -> Vec<RuleVerdict> {
+        self.evaluate(intent).0
+    }
+
+    /// [`report`][Self::report] plus the first blocking violation, which
+    /// [`verify`][Self::verify] would have returned.
+    pub(crate) fn evaluate(
+        &self,
+        intent: &HardwareIntent,
+    ) -> (Vec<RuleVerdict>, Option<MechError>) {
+        let mut first = None;
+        let report = self
+            .rules
+            .iter()
+            .map(|(rule, mode)| {
+                let details = rule.check(intent).err().map(|e| {
+                    let details = e.to_string();
+                    if *mode == RuleMode::Block {
+                        first.get_or_insert(e);
+                    }
+                    details
+                });
+                RuleVerdict {
+                    rule: rule.name().to_string(),
+                    passed: details.is_none(),
+                    details,
+                }
+            })
+            .collect();
+        (report, first)
+    }
 }
 
 // ────────────────────────────────────────────────────────────────────────────
@@ -90,11 +317,14 @@ impl StateVerifier {
 
 /// Rejects [`HardwareIntent::Drive`] commands whose `linear_velocity` or
 /// `angular_velocity` magnitudes exceed configured caps.
+///
+/// [`HardwareIntent::FollowWaypoints`] paths are held to `max_linear`: their
+/// `max_speed` must be positive and no greater than the cap.
 pub struct SpeedCapRule {
-    /// Maximum allowed absolute linear velocity (m/s or equivalent units).
-    pub max_linear: f32,
-    /// Maximum allowed absolute angular velocity (rad/s or equivalent units).
-    pub max_angular: f32,
+    /// Maximum allowed absolute linear velocity.
+    pub max_linear: MetersPerSecond,
+    /// Maximum allowed absolute angular velocity.
+    pub max_angular: RadiansPerSecond,
 }
 
 impl Rule for SpeedCapRule {
@@ -102,6 +332,22 @@ impl Rule for SpeedCapRule {
         "speed_cap"
     }
 
+    fn params(&self) -> Vec<(&'static str, f32)> {
+        vec![
+            ("max_linear", self.max_linear.0),
+            ("max_angular", self.max_angular.0),
+        ]
+    }
+
+    fn set_param(&mut self, param: &str, value: f32) -> Result<(), MechError> {
+        match param {
+            "max_linear" => self.max_linear = MetersPerSecond(positive(param, value)?),
+            "max_angular" => self.max_angular = RadiansPerSecond(positive(param, value)?),
+            _ => return Err(unknown_param(self.name(), param)),
+        }
+        Ok(())
+    }
+
     fn check(&self, intent: &HardwareIntent) -> Result<(), MechError> {
         if let HardwareIntent::Drive {
             linear_velocity,
@@ -110,6 +356,7 @@ impl Rule for SpeedCapRule {
         {
             if linear_velocity.abs() > self.max_linear {
                 return Err(MechError::HardwareFault {
+                    code: FaultCode::SpeedCapExceeded,
                     component: "drive_base".to_string(),
                     details: format!(
                         "linear_velocity {linear_velocity} exceeds cap {}",
@@ -119,6 +366,7 @@ impl Rule for SpeedCapRule {
             }
             if angular_velocity.abs() > self.max_angular {
                 return Err(MechError::HardwareFault {
+                    code: FaultCode::SpeedCapExceeded,
                     component: "drive_base".to_string(),
                     details: format!(
                         "angular_velocity {angular_velocity} exceeds cap {}",
@@ -127,25 +375,40 @@ impl Rule for SpeedCapRule {
                 });
             }
         }
+        if let HardwareIntent::FollowWaypoints { max_speed, .. } = intent
+            && !(*max_speed > MetersPerSecond::ZERO && *max_speed <= self.max_linear)
+        {
+            return Err(MechError::HardwareFault {
+                code: FaultCode::SpeedCapExceeded,
+                component: "drive_base".to_string(),
+                details: format!(
+                    "waypoint max_speed {max_speed} outside (0, {}]",
+                    self.max_linear
+                ),
+            });
+        }
         Ok(())
     }
 }
 
 /// Rejects [`HardwareIntent::MoveEndEffector`] commands that would place the
 /// end-effector outside its safe cubic workspace `[min, max]` on each axis.
+///
+/// The workspace is expressed in [`BASE_FRAME`]; targets in any other frame
+/// are rejected, since they cannot be checked without a transform.
 pub struct EndEffectorWorkspaceRule {
-    /// Minimum allowed X coordinate (metres).
-    pub min_x: f32,
-    /// Maximum allowed X coordinate (metres).
-    pub max_x: f32,
-    /// Minimum allowed Y coordinate (metres).
-    pub min_y: f32,
-    /// Maximum allowed Y coordinate (metres).
-    pub max_y: f32,
-    /// Minimum allowed Z coordinate (metres).
-    pub min_z: f32,
-    /// Maximum allowed Z coordinate (metres).
-    pub max_z: f32,
+    /// Minimum allowed X coordinate.
+    pub min_x: Meters,
+    /// Maximum allowed X coordinate.
+    pub max_x: Meters,
+    /// Minimum allowed Y coordinate.
+    pub min_y: Meters,
+    /// Maximum allowed Y coordinate.
+    pub max_y: Meters,
+    /// Minimum allowed Z coordinate.
+    pub min_z: Meters,
+    /// Maximum allowed Z coordinate.
+    pub max_z: Meters,
 }
 
 impl Rule for EndEffectorWorkspaceRule {
@@ -153,8 +416,38 @@ impl Rule for EndEffectorWorkspaceRule {
         "end_effector_workspace"
     }
 
+    fn params(&self) -> Vec<(&'static str, f32)> {
+        vec![
+            ("min_x", self.min_x.0),
+            ("max_x", self.max_x.0),
+            ("min_y", self.min_y.0),
+            ("max_y", self.max_y.0),
+            ("min_z", self.min_z.0),
+            ("max_z", self.max_z.0),
+        ]
+    }
+
+    fn set_param(&mut self, param: &str, value: f32) -> Result<(), MechError> {
+        let bounds = [
+            ("x", &mut self.min_x, &mut self.max_x),
+            ("y", &mut self.min_y, &mut self.max_y),
+            ("z", &mut self.min_z, &mut self.max_z),
+        ];
+        set_bound(bounds, param, value)
+            .unwrap_or_else(|| Err(unknown_param("end_effector_workspace", param)))
+    }
+
     fn check(&self, intent: &HardwareIntent) -> Result<(), MechError> {
-        if let HardwareIntent::MoveEndEffector { x, y, z } = intent {
+        if let HardwareIntent::MoveEndEffector { x, y, z, frame_id } = intent {
+            if let Some(frame) = frame_id.as_deref().filter(|f| *f != BASE_FRAME) {
+                return Err(MechError::HardwareFault {
+                    code: FaultCode::WorkspaceViolation,
+                    component: "end_effector".to_string(),
+                    details: format!(
+                        "target in frame \"{frame}\"; the workspace is checked in \"{BASE_FRAME}\""
+                    ),
+                });
+            }
             for (axis, val, min, max) in [
                 ("x", x, &self.min_x, &self.max_x),
                 ("y", y, &self.min_y, &self.max_y),
@@ -162,6 +455,7 @@ impl Rule for EndEffectorWorkspaceRule {
             ] {
                 if *val < *min || *val > *max {
                     return Err(MechError::HardwareFault {
+                        code: FaultCode::WorkspaceViolation,
                         component: "end_effector".to_string(),
                         details: format!(
                             "{axis}={val} out of [{min}, {max}]"
@@ -174,12 +468,360 @@ impl Rule for EndEffectorWorkspaceRule {
     }
 }
 
+/// Rejects [`HardwareIntent::FollowWaypoints`] paths that leave the robot's
+/// rectangular operating area `[min, max]` on the X and Y axes.
+///
+/// Every waypoint is checked, so a path that merely passes outside the fence
+/// is rejected as a whole.  Empty paths, paths longer than `max_waypoints`
+/// and non-finite coordinates are rejected as well.
+///
+/// # Example
+///
+/// ```
+/// use mechos_kernel::{GeofenceRule, StateVerifier};
+/// use mechos_types::{HardwareIntent, Meters, MetersPerSecond};
+///
+/// let mut verifier = StateVerifier::new();
+/// verifier.add_rule(Box::new(GeofenceRule {
+///     min_x: Meters(0.0), max_x: Meters(10.0),
+///     min_y: Meters(0.0), max_y: Meters(5.0),
+///     max_waypoints: 32,
+/// }));
+///
+/// assert!(verifier.verify(&HardwareIntent::FollowWaypoints {
+///     points: vec![(Meters(1.0), Meters(1.0)), (Meters(8.0), Meters(4.0))],
+///     max_speed: MetersPerSecond(0.5),
+/// }).is_ok());
+/// assert!(verifier.verify(&HardwareIntent::FollowWaypoints {
+///     points: vec![(Meters(1.0), Meters(1.0)), (Meters(12.0), Meters(4.0))],
+///     max_speed: MetersPerSecond(0.5),
+/// }).is_err());
+/// ```
+pub struct GeofenceRule {
+    /// Minimum allowed X coordinate (world frame).
+    pub min_x: Meters,
+    /// Maximum allowed X coordinate (world frame).
+    pub max_x: Meters,
+    /// Minimum allowed Y coordinate (world frame).
+    pub min_y: Meters,
+    /// Maximum allowed Y coordinate (world frame).
+    pub max_y: Meters,
+    /// Maximum number of waypoints in a single path.
+    pub max_waypoints: usize,
+}
+
+impl Rule for GeofenceRule {
+    fn name(&self) -> &str {
+        "geofence"
+    }
+
+    fn params(&self) -> Vec<(&'static str, f32)> {
+        vec![
+            ("min_x", self.min_x.0),
+            ("max_x", self.max_x.0),
+            ("min_y", self.min_y.0),
+            ("max_y", self.max_y.0),
+        ]
+    }
+
+    fn set_param(&mut self, param: &str, value: f32) -> Result<(), MechError> {
+        let bounds = [
+            ("x", &mut self.min_x, &mut self.max_x),
+            ("y", &mut self.min_y, &mut self.max_y),
+        ];
+        set_bound(bounds, param, value).unwrap_or_else(|| Err(unknown_param("geofence", param)))
+    }
+
+    fn check(&self, intent: &HardwareIntent) -> Result<(), MechError> {
+        let HardwareIntent::FollowWaypoints { points, .. } = intent else {
+            return Ok(());
+        };
+        let fault = |details: String| MechError::HardwareFault {
+            code: FaultCode::GeofenceViolation,
+            component: "drive_base".to_string(),
+            details,
+        };
+        if points.is_empty() {
+            return Err(fault("waypoint path is empty".to_string()));
+        }
+        if points.len() > self.max_waypoints {
+            return Err(fault(format!(
+                "waypoint path has {} points, exceeds cap {}",
+                points.len(),
+                self.max_waypoints
+            )));
+        }
+        for (i, (x, y)) in points.iter().enumerate() {
+            if !(x.is_finite() && y.is_finite()) {
+                return Err(fault(format!("waypoint {i} ({x}, {y}) is not finite")));
+            }
+            if *x < self.min_x || *x > self.max_x || *y < self.min_y || *y > self.max_y {
+                return Err(fault(format!(
+                    "waypoint {i} ({x}, {y}) outside geofence x[{}, {}] y[{}, {}]",
+                    self.min_x, self.max_x, self.min_y, self.max_y
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Range and speed limits of one arm joint, used by [`JointLimitRule`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JointLimit {
+    /// Minimum allowed angle (radians).
+    pub min_rad: f32,
+    /// Maximum allowed angle (radians).
+    pub max_rad: f32,
+    /// Maximum allowed `max_velocity`.
+    pub max_velocity: RadiansPerSecond,
+}
+
+/// Rejects [`HardwareIntent::MoveJoint`] commands that target an unknown
+/// joint, leave the joint's `[min_rad, max_rad]` range or request a
+/// `max_velocity` above its limit, and [`HardwareIntent::SetGripper`]
+/// positions outside `[0.0, 1.0]`.
+///
+/// Joints must be declared with [`with_joint`][Self::with_joint]; a joint
+/// without limits is never moved.
+///
+/// # Example
+///
+/// ```
+/// use mechos_kernel::{JointLimitRule, StateVerifier};
+/// use mechos_types::{HardwareIntent, RadiansPerSecond};
+///
+/// let mut verifier = StateVerifier::new();
+/// verifier.add_rule(Box::new(
+///     JointLimitRule::new().with_joint("elbow", -1.5, 1.5, RadiansPerSecond(0.8)),
+/// ));
+///
+/// let ok = HardwareIntent::MoveJoint {
+///     joint: "elbow".into(), angle_rad: 1.0, max_velocity: RadiansPerSecond(0.5),
+/// };
+/// assert!(verifier.verify(&ok).is_ok());
+///
+/// let past_stop = HardwareIntent::MoveJoint {
+///     joint: "elbow".into(), angle_rad: 2.0, max_velocity: RadiansPerSecond(0.5),
+/// };
+/// assert!(verifier.verify(&past_stop).is_err());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct JointLimitRule {
+    limits: HashMap<String, JointLimit>,
+}
+
+impl JointLimitRule {
+    /// Create a rule with no joints declared.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare the limits of `joint`, replacing any previous declaration.
+    pub fn with_joint(
+        mut self,
+        joint: impl Into<String>,
+        min_rad: f32,
+        max_rad: f32,
+        max_velocity: RadiansPerSecond,
+    ) -> Self {
+        self.limits.insert(
+            joint.into(),
+            JointLimit {
+                min_rad,
+                max_rad,
+                max_velocity,
+            },
+        );
+        self
+    }
+
+    /// Limits declared for `joint`, if any.
+    pub fn limit(&self, joint: &str) -> Option<&JointLimit> {
+        self.limits.get(joint)
+    }
+}
+
+impl Rule for JointLimitRule {
+    fn name(&self) -> &str {
+        "joint_limit"
+    }
+
+    fn check(&self, intent: &HardwareIntent) -> Result<(), MechError> {
+        match intent {
+            HardwareIntent::SetGripper { position } if !(0.0..=1.0).contains(position) => {
+                return Err(MechError::HardwareFault {
+                    code: FaultCode::JointLimitExceeded,
+                    component: "gripper".to_string(),
+                    details: format!("position {position} out of [0, 1]"),
+                });
+            }
+            HardwareIntent::MoveJoint {
+                joint,
+                angle_rad,
+                max_velocity,
+            } => {
+                let fault = |details: String| MechError::HardwareFault {
+                    code: FaultCode::JointLimitExceeded,
+                    component: joint.clone(),
+                    details,
+                };
+                let Some(limit) = self.limits.get(joint) else {
+                    return Err(fault(format!("joint '{joint}' has no declared limits")));
+                };
+                if !(limit.min_rad..=limit.max_rad).contains(angle_rad) {
+                    return Err(fault(format!(
+                        "angle_rad {angle_rad} out of [{}, {}]",
+                        limit.min_rad, limit.max_rad
+                    )));
+                }
+                if !(*max_velocity > RadiansPerSecond::ZERO && *max_velocity <= limit.max_velocity) {
+                    return Err(fault(format!(
+                        "max_velocity {max_velocity} outside (0, {}]",
+                        limit.max_velocity
+                    )));
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// Default maximum length of a single [`HardwareIntent::Speak`] text, in
+/// characters (roughly 30 seconds of speech).
+pub const DEFAULT_MAX_SPEECH_CHARS: usize = 400;
+
+/// Default maximum number of audio intents accepted per minute.
+pub const DEFAULT_MAX_UTTERANCES_PER_MINUTE: usize = 20;
+
+/// Bounds the robot's voice channel.
+///
+/// * [`HardwareIntent::Speak`] texts longer than `max_chars` characters, or
+///   that are blank, are rejected.
+/// * At most `max_utterances_per_minute` [`HardwareIntent::Speak`] and
+///   [`HardwareIntent::PlaySound`] intents are dispatched in any sliding
+///   60-second window; further ones are rejected until the window drains.
+///   Only intents passed to [`Rule::record`] count.
+///
+/// Violations return [`MechError::HardwareFault`] with component
+/// `"speaker"`.  All other intent variants pass through unaffected.
+///
+/// # Example
+///
+/// ```
+/// use mechos_kernel::{SpeechRule, StateVerifier};
+/// use mechos_types::HardwareIntent;
+///
+/// let mut verifier = StateVerifier::new();
+/// verifier.add_rule(Box::new(SpeechRule::new(20, 10)));
+///
+/// assert!(verifier.verify(&HardwareIntent::Speak {
+///     text: "Hello!".into(), voice: None,
+/// }).is_ok());
+/// assert!(verifier.verify(&HardwareIntent::Speak {
+///     text: "This sentence is far too long for the cap.".into(), voice: None,
+/// }).is_err());
+/// ```
+pub struct SpeechRule {
+    /// Maximum number of characters in a single `Speak` text.
+    pub max_chars: usize,
+    /// Maximum number of `Speak` / `PlaySound` intents per minute.
+    pub max_utterances_per_minute: usize,
+    /// Dispatch times of the audio intents inside the current window.
+    recent: Mutex<VecDeque<Instant>>,
+}
+
+impl SpeechRule {
+    /// Length of the sliding rate-limit window.
+    const WINDOW: Duration = Duration::from_secs(60);
+
+    /// Create a rule with the given length cap and rate limit.
+    pub fn new(max_chars: usize, max_utterances_per_minute: usize) -> Self {
+        Self {
+            max_chars,
+            max_utterances_per_minute,
+            recent: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    fn fault(details: String) -> MechError {
+        MechError::HardwareFault {
+            code: FaultCode::SpeechLimitExceeded,
+            component: "speaker".to_string(),
+            details,
+        }
+    }
+}
+
+impl Default for SpeechRule {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_SPEECH_CHARS, DEFAULT_MAX_UTTERANCES_PER_MINUTE)
+    }
+}
+
+impl Rule for SpeechRule {
+    fn name(&self) -> &str {
+        "speech"
+    }
+
+    fn check(&self, intent: &HardwareIntent) -> Result<(), MechError> {
+        match intent {
+            HardwareIntent::Speak { text, .. } => {
+                if text.trim().is_empty() {
+                    return Err(Self::fault("speak text is empty".to_string()));
+                }
+                let chars = text.chars().count();
+                if chars > self.max_chars {
+                    return Err(Self::fault(format!(
+                        "speak text has {chars} characters, exceeds cap {}",
+                        self.max_chars
+                    )));
+                }
+            }
+            HardwareIntent::PlaySound { .. } => {}
+            _ => return Ok(()),
+        }
+
+        let recent = self.recent.lock().unwrap_or_else(|e| e.into_inner());
+        let in_window = recent
+            .iter()
+            .filter(|t| t.elapsed() < Self::WINDOW)
+            .count();
+        if in_window >= self.max_utterances_per_minute {
+            return Err(Self::fault(format!(
+                "audio rate limit of {} per minute reached",
+                self.max_utterances_per_minute
+            )));
+        }
+        Ok(())
+    }
+
+    fn record(&self, intent: &HardwareIntent) {
+        if !matches!(
+            intent,
+            HardwareIntent::Speak { .. } | HardwareIntent::PlaySound { .. }
+        ) {
+            return;
+        }
+        let now = Instant::now();
+        let mut recent = self.recent.lock().unwrap_or_else(|e| e.into_inner());
+        while recent
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= Self::WINDOW)
+        {
+            recent.pop_front();
+        }
+        recent.push_back(now);
+    }
+}
+
 /// Safety interlock that blocks AI-issued [`HardwareIntent::Drive`] commands
 /// while a manual dashboard override session is active.
 ///
 /// The interlock is armed and disarmed through the shared `active` flag.  When
 /// the flag is `true` (the human operator has grabbed the on-screen joystick),
-/// any AI-sourced `Drive` command is rejected so the LLM cannot fight the
+/// any AI-sourced `Drive` or `FollowWaypoints` command is rejected so the LLM cannot fight the
 /// human for control of the motors.  All other intent variants pass through
 /// unaffected.
 ///
@@ -188,7 +830,7 @@ impl Rule for EndEffectorWorkspaceRule {
 /// ```
 /// use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
 /// use mechos_kernel::{ManualOverrideInterlock, StateVerifier};
-/// use mechos_types::HardwareIntent;
+/// use mechos_types::{HardwareIntent, MetersPerSecond, RadiansPerSecond};
 ///
 /// let flag = Arc::new(AtomicBool::new(false));
 /// let mut verifier = StateVerifier::new();
@@ -196,7 +838,7 @@ impl Rule for EndEffectorWorkspaceRule {
 ///
 /// // Override not active – Drive passes.
 /// assert!(verifier.verify(&HardwareIntent::Drive {
-///     linear_velocity: 0.5, angular_velocity: 0.0,
+///     linear_velocity: MetersPerSecond(0.5), angular_velocity: RadiansPerSecond(0.0),
 /// }).is_ok());
 ///
 /// // Arm the interlock.
@@ -204,7 +846,7 @@ impl Rule for EndEffectorWorkspaceRule {
 ///
 /// // Override active – Drive is rejected.
 /// assert!(verifier.verify(&HardwareIntent::Drive {
-///     linear_velocity: 0.5, angular_velocity: 0.0,
+///     linear_velocity: MetersPerSecond(0.5), angular_velocity: RadiansPerSecond(0.0),
 /// }).is_err());
 /// ```
 pub struct ManualOverrideInterlock {
@@ -224,13 +866,23 @@ impl Rule for ManualOverrideInterlock {
         "manual_override_interlock"
     }
 
-    /// Reject any [`HardwareIntent::Drive`] command while the override flag is
+    /// The operator's joystick always wins over the AI.
+    fn may_warn(&self) -> bool {
+        false
+    }
+
+    /// Reject any [`HardwareIntent::Drive`] or
+    /// [`HardwareIntent::FollowWaypoints`] command while the override flag is
     /// set.  All other intent variants always pass this rule.
     fn check(&self, intent: &HardwareIntent) -> Result<(), MechError> {
         if self.active.load(Ordering::Acquire)
-            && let HardwareIntent::Drive { .. } = intent
+            && matches!(
+                intent,
+                HardwareIntent::Drive { .. } | HardwareIntent::FollowWaypoints { .. }
+            )
         {
             return Err(MechError::HardwareFault {
+                code: FaultCode::OverrideActive,
                 component: "drive_base".to_string(),
                 details: "manual override active; AI drive commands suspended".to_string(),
             });
@@ -239,6 +891,301 @@ impl Rule for ManualOverrideInterlock {
     }
 }
 
+/// Default minimum state of charge, in percent, below which
+/// [`BatteryInterlock`] refuses to undock.
+pub const DEFAULT_MIN_UNDOCK_PERCENT: u8 = 20;
+
+/// Safety interlock that keeps the robot on its charger: rejects
+/// [`HardwareIntent::Undock`] while the shared battery level is below
+/// [`min_undock_percent`][Self::min_undock_percent].
+///
+/// The level is updated by whoever consumes power readings (the agent loop
+/// stores every [`EventPayload::PowerStatus`][mechos_types::EventPayload::PowerStatus]
+/// it sees).  It starts at whatever the owner initialises it to; a level of
+/// `0` keeps the robot docked until the first reading arrives.  All other
+/// intent variants pass through unaffected.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::{Arc, atomic::{AtomicU8, Ordering}};
+/// use mechos_kernel::{BatteryInterlock, StateVerifier};
+/// use mechos_types::HardwareIntent;
+///
+/// let level = Arc::new(AtomicU8::new(12));
+/// let mut verifier = StateVerifier::new();
+/// verifier.add_rule(Box::new(BatteryInterlock::new(Arc::clone(&level))));
+///
+/// assert!(verifier.verify(&HardwareIntent::Undock).is_err());
+///
+/// level.store(80, Ordering::Release);
+/// assert!(verifier.verify(&HardwareIntent::Undock).is_ok());
+/// ```
+pub struct BatteryInterlock {
+    /// Current state of charge, in percent.
+    pub percent: Arc<AtomicU8>,
+    /// `Undock` is rejected below this state of charge, in percent.
+    pub min_undock_percent: u8,
+}
+
+impl BatteryInterlock {
+    /// Create an interlock that reads the given shared battery level, with
+    /// the [`DEFAULT_MIN_UNDOCK_PERCENT`] threshold.
+    pub fn new(percent: Arc<AtomicU8>) -> Self {
+        Self {
+            percent,
+            min_undock_percent: DEFAULT_MIN_UNDOCK_PERCENT,
+        }
+    }
+
+    /// Override the undock threshold.
+    pub fn with_min_undock_percent(mut self, min_undock_percent: u8) -> Self {
+        self.min_undock_percent = min_undock_percent;
+        self
+    }
+}
+
+impl Rule for BatteryInterlock {
+    fn name(&self) -> &str {
+        "battery_interlock"
+    }
+
+    fn check(&self, intent: &HardwareIntent) -> Result<(), MechError> {
+        let percent = self.percent.load(Ordering::Acquire);
+        if matches!(intent, HardwareIntent::Undock) && percent < self.min_undock_percent {
+            return Err(MechError::HardwareFault {
+                code: FaultCode::BatteryLow,
+                component: "battery".to_string(),
+                details: format!(
+                    "battery at {percent}%, below the {}% undock threshold",
+                    self.min_undock_percent
+                ),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Default linear speed cap (m/s) applied by [`StaleDataRule`] while
+/// localization is degraded.
+pub const DEFAULT_DEGRADED_MAX_LINEAR: MetersPerSecond = MetersPerSecond::new(0.2);
+
+/// Safety rule for driving on stale position data: while the shared
+/// `degraded` flag is set, rejects [`HardwareIntent::FollowWaypoints`]
+/// (whose world-frame points cannot be tracked without a trustworthy pose)
+/// and [`HardwareIntent::Drive`] faster than
+/// [`max_linear`][Self::max_linear].
+///
+/// The flag is raised by whoever owns sensor fusion (the agent loop stores
+/// `FusedState::degraded` every tick) while odometry has dropped out and the
+/// pose is dead-reckoned from the IMU alone.  All other intent variants pass
+/// through unaffected.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
+/// use mechos_kernel::{StaleDataRule, StateVerifier};
+/// use mechos_types::{HardwareIntent, MetersPerSecond, RadiansPerSecond};
+///
+/// let degraded = Arc::new(AtomicBool::new(false));
+/// let mut verifier = StateVerifier::new();
+/// verifier.add_rule(Box::new(StaleDataRule::new(Arc::clone(&degraded))));
+///
+/// let drive = HardwareIntent::Drive {
+///     linear_velocity: MetersPerSecond(0.5), angular_velocity: RadiansPerSecond(0.0),
+/// };
+/// assert!(verifier.verify(&drive).is_ok());
+///
+/// degraded.store(true, Ordering::Release);
+/// assert!(verifier.verify(&drive).is_err());
+/// ```
+pub struct StaleDataRule {
+    /// `true` while localization is degraded.
+    pub degraded: Arc<AtomicBool>,
+    /// Fastest `Drive` linear velocity allowed while degraded.
+    pub max_linear: MetersPerSecond,
+}
+
+impl StaleDataRule {
+    /// Create a rule that reads the given shared `degraded` flag, with the
+    /// [`DEFAULT_DEGRADED_MAX_LINEAR`] speed cap.
+    pub fn new(degraded: Arc<AtomicBool>) -> Self {
+        Self {
+            degraded,
+            max_linear: DEFAULT_DEGRADED_MAX_LINEAR,
+        }
+    }
+
+    /// Override the degraded-mode speed cap.
+    pub fn with_max_linear(mut self, max_linear: MetersPerSecond) -> Self {
+        self.max_linear = max_linear;
+        self
+    }
+}
+
+impl Rule for StaleDataRule {
+    fn name(&self) -> &str {
+        "stale_data"
+    }
+
+    fn params(&self) -> Vec<(&'static str, f32)> {
+        vec![("max_linear", self.max_linear.0)]
+    }
+
+    fn set_param(&mut self, param: &str, value: f32) -> Result<(), MechError> {
+        match param {
+            "max_linear" => self.max_linear = MetersPerSecond(positive(param, value)?),
+            _ => return Err(unknown_param(self.name(), param)),
+        }
+        Ok(())
+    }
+
+    fn check(&self, intent: &HardwareIntent) -> Result<(), MechError> {
+        if !self.degraded.load(Ordering::Acquire) {
+            return Ok(());
+        }
+        let details = match intent {
+            HardwareIntent::FollowWaypoints { .. } => {
+                "localization degraded; waypoint following suspended".to_string()
+            }
+            HardwareIntent::Drive {
+                linear_velocity, ..
+            } if linear_velocity.get().abs() > self.max_linear.get() => format!(
+                "localization degraded; linear velocity {} m/s exceeds the {} m/s cap",
+                linear_velocity.get(),
+                self.max_linear.get()
+            ),
+            _ => return Ok(()),
+        };
+        Err(MechError::HardwareFault {
+            code: FaultCode::StaleSensor,
+            component: "localization".to_string(),
+            details,
+        })
+    }
+}
+
+/// Default distance (metres) to a moving obstacle below which
+/// [`MovingObstacleRule`] caps speed.
+pub const DEFAULT_MOVING_OBSTACLE_MARGIN: Meters = Meters::new(1.0);
+
+/// Default speed cap (m/s) applied by [`MovingObstacleRule`] inside the
+/// margin.
+pub const DEFAULT_MOVING_OBSTACLE_MAX_LINEAR: MetersPerSecond = MetersPerSecond::new(0.1);
+
+/// Safety rule for sharing space with people: while the closest moving
+/// obstacle is nearer than [`margin`][Self::margin], rejects
+/// [`HardwareIntent::Drive`] faster than [`max_linear`][Self::max_linear]
+/// and [`HardwareIntent::FollowWaypoints`] with a higher `max_speed`.
+///
+/// Static obstacles are left to the trajectory check, which only keeps the
+/// robot footprint clear; moving ones get this wider margin because they
+/// can step into the path.  The shared `clearance` holds the distance
+/// (metres) to the edge of the nearest moving obstacle as
+/// [`f32::to_bits`], and is kept up to date by the owner of the obstacle
+/// tracker; [`f32::INFINITY`] means nothing moves nearby.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::{Arc, atomic::{AtomicU32, Ordering}};
+/// use mechos_kernel::{MovingObstacleRule, StateVerifier};
+/// use mechos_types::{HardwareIntent, MetersPerSecond, RadiansPerSecond};
+///
+/// let clearance = Arc::new(AtomicU32::new(f32::INFINITY.to_bits()));
+/// let mut verifier = StateVerifier::new();
+/// verifier.add_rule(Box::new(MovingObstacleRule::new(Arc::clone(&clearance))));
+///
+/// let drive = HardwareIntent::Drive {
+///     linear_velocity: MetersPerSecond(0.5), angular_velocity: RadiansPerSecond(0.0),
+/// };
+/// assert!(verifier.verify(&drive).is_ok());
+///
+/// // A person walks past 0.6 m away.
+/// clearance.store(0.6f32.to_bits(), Ordering::Release);
+/// assert!(verifier.verify(&drive).is_err());
+/// ```
+pub struct MovingObstacleRule {
+    /// Distance to the nearest moving obstacle, as `f32` bits.
+    pub clearance: Arc<AtomicU32>,
+    /// Speed is capped while a moving obstacle is nearer than this.
+    pub margin: Meters,
+    /// Fastest linear speed allowed inside the margin.
+    pub max_linear: MetersPerSecond,
+}
+
+impl MovingObstacleRule {
+    /// Create a rule that reads the given shared clearance, with the
+    /// [`DEFAULT_MOVING_OBSTACLE_MARGIN`] and
+    /// [`DEFAULT_MOVING_OBSTACLE_MAX_LINEAR`].
+    pub fn new(clearance: Arc<AtomicU32>) -> Self {
+        Self {
+            clearance,
+            margin: DEFAULT_MOVING_OBSTACLE_MARGIN,
+            max_linear: DEFAULT_MOVING_OBSTACLE_MAX_LINEAR,
+        }
+    }
+
+    /// Override the safety margin.
+    pub fn with_margin(mut self, margin: Meters) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Override the speed cap inside the margin.
+    pub fn with_max_linear(mut self, max_linear: MetersPerSecond) -> Self {
+        self.max_linear = max_linear;
+        self
+    }
+}
+
+impl Rule for MovingObstacleRule {
+    fn name(&self) -> &str {
+        "moving_obstacle"
+    }
+
+    fn params(&self) -> Vec<(&'static str, f32)> {
+        vec![("margin", self.margin.0), ("max_linear", self.max_linear.0)]
+    }
+
+    fn set_param(&mut self, param: &str, value: f32) -> Result<(), MechError> {
+        match param {
+            "margin" => self.margin = Meters(positive(param, value)?),
+            "max_linear" => self.max_linear = MetersPerSecond(positive(param, value)?),
+            _ => return Err(unknown_param(self.name(), param)),
+        }
+        Ok(())
+    }
+
+    fn check(&self, intent: &HardwareIntent) -> Result<(), MechError> {
+        let clearance = f32::from_bits(self.clearance.load(Ordering::Acquire));
+        if clearance >= self.margin.get() {
+            return Ok(());
+        }
+        let speed = match intent {
+            HardwareIntent::Drive {
+                linear_velocity, ..
+            } => linear_velocity.get().abs(),
+            HardwareIntent::FollowWaypoints { max_speed, .. } => max_speed.get(),
+            _ => return Ok(()),
+        };
+        if speed <= self.max_linear.get() {
+            return Ok(());
+        }
+        Err(MechError::HardwareFault {
+            code: FaultCode::CollisionPredicted,
+            component: "moving_obstacle".to_string(),
+            details: format!(
+                "moving obstacle {clearance:.2} m away, inside the {} m margin; \
+                 speed {speed} m/s exceeds the {} m/s cap",
+                self.margin.get(),
+                self.max_linear.get()
+            ),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -248,8 +1195,8 @@ mod tests {
     fn speed_verifier(max_linear: f32, max_angular: f32) -> StateVerifier {
         let mut v = StateVerifier::new();
         v.add_rule(Box::new(SpeedCapRule {
-            max_linear,
-            max_angular,
+            max_linear: MetersPerSecond(max_linear),
+            max_angular: RadiansPerSecond(max_angular),
         }));
         v
     }
@@ -264,12 +1211,12 @@ mod tests {
     ) -> StateVerifier {
         let mut v = StateVerifier::new();
         v.add_rule(Box::new(EndEffectorWorkspaceRule {
-            min_x,
-            max_x,
-            min_y,
-            max_y,
-            min_z,
-            max_z,
+            min_x: Meters(min_x),
+            max_x: Meters(max_x),
+            min_y: Meters(min_y),
+            max_y: Meters(max_y),
+            min_z: Meters(min_z),
+            max_z: Meters(max_z),
         }));
         v
     }
@@ -281,8 +1228,8 @@ mod tests {
         let v = speed_verifier(1.0, 1.0);
         assert!(v
             .verify(&HardwareIntent::Drive {
-                linear_velocity: 0.5,
-                angular_velocity: 0.5
+                linear_velocity: MetersPerSecond(0.5),
+                angular_velocity: RadiansPerSecond(0.5)
             })
             .is_ok());
     }
@@ -292,8 +1239,8 @@ mod tests {
         let v = speed_verifier(1.0, 1.0);
         assert!(v
             .verify(&HardwareIntent::Drive {
-                linear_velocity: 1.0,
-                angular_velocity: 1.0
+                linear_velocity: MetersPerSecond(1.0),
+                angular_velocity: RadiansPerSecond(1.0)
             })
             .is_ok());
     }
@@ -303,10 +1250,13 @@ mod tests {
         let v = speed_verifier(1.0, 1.0);
         assert!(matches!(
             v.verify(&HardwareIntent::Drive {
-                linear_velocity: 1.1,
-                angular_velocity: 0.0
+                linear_velocity: MetersPerSecond(1.1),
+                angular_velocity: RadiansPerSecond(0.0)
             }),
-            Err(MechError::HardwareFault { .. })
+            Err(MechError::HardwareFault {
+                code: FaultCode::SpeedCapExceeded,
+                ..
+            })
         ));
     }
 
@@ -315,10 +1265,13 @@ mod tests {
         let v = speed_verifier(1.0, 1.0);
         assert!(matches!(
             v.verify(&HardwareIntent::Drive {
-                linear_velocity: 0.0,
-                angular_velocity: 1.5
+                linear_velocity: MetersPerSecond(0.0),
+                angular_velocity: RadiansPerSecond(1.5)
             }),
-            Err(MechError::HardwareFault { .. })
+            Err(MechError::HardwareFault {
+                code: FaultCode::SpeedCapExceeded,
+                ..
+            })
         ));
     }
 
@@ -327,8 +1280,8 @@ mod tests {
         let v = speed_verifier(1.0, 1.0);
         assert!(matches!(
             v.verify(&HardwareIntent::Drive {
-                linear_velocity: -2.0,
-                angular_velocity: 0.0
+                linear_velocity: MetersPerSecond(-2.0),
+                angular_velocity: RadiansPerSecond(0.0)
             }),
             Err(MechError::HardwareFault { .. })
         ));
@@ -340,9 +1293,10 @@ mod tests {
         // MoveEndEffector is irrelevant to the speed cap rule.
         assert!(v
             .verify(&HardwareIntent::MoveEndEffector {
-                x: 999.0,
-                y: 999.0,
-                z: 999.0,
+                x: Meters(999.0),
+                y: Meters(999.0),
+                z: Meters(999.0),
+                frame_id: None,
             })
             .is_ok());
     }
@@ -354,9 +1308,10 @@ mod tests {
         let v = workspace_verifier(-1.0, 1.0, -1.0, 1.0, 0.0, 2.0);
         assert!(v
             .verify(&HardwareIntent::MoveEndEffector {
-                x: 0.0,
-                y: 0.0,
-                z: 1.0,
+                x: Meters(0.0),
+                y: Meters(0.0),
+                z: Meters(1.0),
+                frame_id: None,
             })
             .is_ok());
     }
@@ -366,9 +1321,10 @@ mod tests {
         let v = workspace_verifier(-1.0, 1.0, -1.0, 1.0, 0.0, 2.0);
         assert!(v
             .verify(&HardwareIntent::MoveEndEffector {
-                x: 1.0,
-                y: -1.0,
-                z: 2.0,
+                x: Meters(1.0),
+                y: Meters(-1.0),
+                z: Meters(2.0),
+                frame_id: None,
             })
             .is_ok());
     }
@@ -378,9 +1334,10 @@ mod tests {
         let v = workspace_verifier(-1.0, 1.0, -1.0, 1.0, 0.0, 2.0);
         assert!(matches!(
             v.verify(&HardwareIntent::MoveEndEffector {
-                x: 1.5,
-                y: 0.0,
-                z: 1.0,
+                x: Meters(1.5),
+                y: Meters(0.0),
+                z: Meters(1.0),
+                frame_id: None,
             }),
             Err(MechError::HardwareFault { .. })
         ));
@@ -391,11 +1348,34 @@ mod tests {
         let v = workspace_verifier(-1.0, 1.0, -1.0, 1.0, 0.0, 2.0);
         assert!(matches!(
             v.verify(&HardwareIntent::MoveEndEffector {
-                x: 0.0,
-                y: 0.0,
-                z: -0.1,
+                x: Meters(0.0),
+                y: Meters(0.0),
+                z: Meters(-0.1),
+                frame_id: None,
             }),
-            Err(MechError::HardwareFault { .. })
+            Err(MechError::HardwareFault {
+                code: FaultCode::WorkspaceViolation,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn end_effector_outside_the_base_frame_rejected() {
+        let v = workspace_verifier(-1.0, 1.0, -1.0, 1.0, 0.0, 2.0);
+        let target = |frame_id: &str| HardwareIntent::MoveEndEffector {
+            x: Meters(0.0),
+            y: Meters(0.0),
+            z: Meters(1.0),
+            frame_id: Some(frame_id.to_string()),
+        };
+        assert!(v.verify(&target(BASE_FRAME)).is_ok());
+        assert!(matches!(
+            v.verify(&target("map")),
+            Err(MechError::HardwareFault {
+                code: FaultCode::WorkspaceViolation,
+                ..
+            })
         ));
     }
 
@@ -404,8 +1384,193 @@ mod tests {
         let v = workspace_verifier(-1.0, 1.0, -1.0, 1.0, 0.0, 2.0);
         assert!(v
             .verify(&HardwareIntent::Drive {
-                linear_velocity: 0.0,
-                angular_velocity: 0.0
+                linear_velocity: MetersPerSecond(0.0),
+                angular_velocity: RadiansPerSecond(0.0)
+            })
+            .is_ok());
+    }
+
+    // ------------------------------------------------------------------ FollowWaypoints
+
+    fn path(points: &[(f32, f32)], max_speed: f32) -> HardwareIntent {
+        HardwareIntent::FollowWaypoints {
+            points: points.iter().map(|&(x, y)| (Meters(x), Meters(y))).collect(),
+            max_speed: MetersPerSecond(max_speed),
+        }
+    }
+
+    fn geofence() -> GeofenceRule {
+        GeofenceRule {
+            min_x: Meters(0.0),
+            max_x: Meters(10.0),
+            min_y: Meters(-5.0),
+            max_y: Meters(5.0),
+            max_waypoints: 4,
+        }
+    }
+
+    #[test]
+    fn path_inside_geofence_passes() {
+        assert!(geofence().check(&path(&[(1.0, 0.0), (9.0, -4.0)], 0.5)).is_ok());
+    }
+
+    #[test]
+    fn path_with_one_waypoint_outside_geofence_rejected() {
+        assert!(matches!(
+            geofence().check(&path(&[(1.0, 0.0), (11.0, 0.0), (2.0, 0.0)], 0.5)),
+            Err(MechError::HardwareFault { code: FaultCode::GeofenceViolation, ref details, .. }) if details.starts_with("waypoint 1 ")
+        ));
+    }
+
+    #[test]
+    fn empty_overlong_and_nan_paths_rejected() {
+        let rule = geofence();
+        assert!(rule.check(&path(&[], 0.5)).is_err());
+        assert!(rule.check(&path(&[(1.0, 0.0); 5], 0.5)).is_err());
+        assert!(rule.check(&path(&[(f32::NAN, 0.0)], 0.5)).is_err());
+    }
+
+    #[test]
+    fn waypoint_max_speed_held_to_linear_cap() {
+        let v = speed_verifier(1.0, 1.0);
+        assert!(v.verify(&path(&[(1.0, 0.0)], 0.8)).is_ok());
+        assert!(v.verify(&path(&[(1.0, 0.0)], 1.5)).is_err());
+        assert!(v.verify(&path(&[(1.0, 0.0)], 0.0)).is_err());
+    }
+
+    #[test]
+    fn follow_waypoints_rejected_when_override_active() {
+        let v = override_verifier(Arc::new(AtomicBool::new(true)));
+        assert!(v.verify(&path(&[(1.0, 0.0)], 0.5)).is_err());
+    }
+
+    // ------------------------------------------------------------------ JointLimitRule
+
+    fn move_joint(joint: &str, angle_rad: f32, max_velocity: f32) -> HardwareIntent {
+        HardwareIntent::MoveJoint {
+            joint: joint.to_string(),
+            angle_rad,
+            max_velocity: RadiansPerSecond(max_velocity),
+        }
+    }
+
+    fn arm_limits() -> JointLimitRule {
+        JointLimitRule::new()
+            .with_joint("shoulder", -1.0, 1.0, RadiansPerSecond(0.5))
+            .with_joint("elbow", 0.0, 2.5, RadiansPerSecond(1.0))
+    }
+
+    #[test]
+    fn joint_within_limits_passes() {
+        assert!(arm_limits().check(&move_joint("elbow", 2.0, 1.0)).is_ok());
+    }
+
+    #[test]
+    fn joint_angle_out_of_range_rejected() {
+        assert!(matches!(
+            arm_limits().check(&move_joint("shoulder", -1.2, 0.1)),
+            Err(MechError::HardwareFault { code: FaultCode::JointLimitExceeded, ref component, .. }) if component == "shoulder"
+        ));
+    }
+
+    #[test]
+    fn joint_velocity_over_limit_rejected() {
+        let rule = arm_limits();
+        assert!(rule.check(&move_joint("shoulder", 0.0, 0.6)).is_err());
+        assert!(rule.check(&move_joint("shoulder", 0.0, 0.0)).is_err());
+    }
+
+    #[test]
+    fn undeclared_joint_rejected() {
+        assert!(matches!(
+            arm_limits().check(&move_joint("wrist", 0.0, 0.1)),
+            Err(MechError::HardwareFault { ref details, .. }) if details.contains("no declared limits")
+        ));
+    }
+
+    #[test]
+    fn gripper_position_must_be_normalised() {
+        let rule = JointLimitRule::new();
+        assert!(rule.check(&HardwareIntent::SetGripper { position: 0.0 }).is_ok());
+        assert!(rule.check(&HardwareIntent::SetGripper { position: 1.0 }).is_ok());
+        assert!(rule.check(&HardwareIntent::SetGripper { position: 1.1 }).is_err());
+        assert!(rule.check(&HardwareIntent::SetGripper { position: f32::NAN }).is_err());
+    }
+
+    // ------------------------------------------------------------------ SpeechRule
+
+    fn speak(text: &str) -> HardwareIntent {
+        HardwareIntent::Speak {
+            text: text.to_string(),
+            voice: None,
+        }
+    }
+
+    #[test]
+    fn speech_within_cap_passes() {
+        let rule = SpeechRule::new(10, 5);
+        assert!(rule.check(&speak("Hello")).is_ok());
+    }
+
+    #[test]
+    fn speech_over_length_cap_rejected() {
+        let rule = SpeechRule::new(10, 5);
+        assert!(matches!(
+            rule.check(&speak("Hello, is anybody there?")),
+            Err(MechError::HardwareFault { code: FaultCode::SpeechLimitExceeded, ref component, .. }) if component == "speaker"
+        ));
+    }
+
+    #[test]
+    fn speech_length_counts_characters_not_bytes() {
+        let rule = SpeechRule::new(5, 5);
+        // 5 characters, 10 bytes.
+        assert!(rule.check(&speak("ñañañ")).is_ok());
+    }
+
+    #[test]
+    fn blank_speech_rejected() {
+        let rule = SpeechRule::new(10, 5);
+        assert!(rule.check(&speak("   ")).is_err());
+    }
+
+    #[test]
+    fn audio_rate_limit_counts_speak_and_play_sound() {
+        let rule = SpeechRule::new(10, 2);
+        let chime = HardwareIntent::PlaySound {
+            sound_id: "chime".to_string(),
+        };
+        assert!(rule.check(&speak("Hi")).is_ok());
+        rule.record(&speak("Hi"));
+        assert!(rule.check(&chime).is_ok());
+        rule.record(&chime);
+        assert!(matches!(
+            rule.check(&speak("Hi")),
+            Err(MechError::HardwareFault { ref details, .. }) if details.contains("rate limit")
+        ));
+    }
+
+    #[test]
+    fn checks_alone_do_not_consume_rate_budget() {
+        let rule = SpeechRule::new(10, 1);
+        for _ in 0..3 {
+            assert!(rule.check(&speak("ok")).is_ok());
+        }
+        rule.record(&HardwareIntent::Halt {
+            reason: "not audio".to_string(),
+        });
+        assert!(rule.check(&speak("ok")).is_ok());
+        rule.record(&speak("ok"));
+        assert!(rule.check(&speak("ok")).is_err());
+    }
+
+    #[test]
+    fn speech_rule_ignores_motion_intents() {
+        let rule = SpeechRule::new(0, 0);
+        assert!(rule
+            .check(&HardwareIntent::Drive {
+                linear_velocity: MetersPerSecond(1.0),
+                angular_velocity: RadiansPerSecond(0.0)
             })
             .is_ok());
     }
@@ -416,24 +1581,28 @@ mod tests {
     fn first_failing_rule_short_circuits() {
         let mut v = StateVerifier::new();
         v.add_rule(Box::new(SpeedCapRule {
-            max_linear: 1.0,
-            max_angular: 1.0,
+            max_linear: MetersPerSecond(1.0),
+            max_angular: RadiansPerSecond(1.0),
         }));
         v.add_rule(Box::new(EndEffectorWorkspaceRule {
-            min_x: -1.0,
-            max_x: 1.0,
-            min_y: -1.0,
-            max_y: 1.0,
-            min_z: 0.0,
-            max_z: 2.0,
+            min_x: Meters(-1.0),
+            max_x: Meters(1.0),
+            min_y: Meters(-1.0),
+            max_y: Meters(1.0),
+            min_z: Meters(0.0),
+            max_z: Meters(2.0),
         }));
 
         // Speed cap fires first even though the workspace rule is also registered.
         let result = v.verify(&HardwareIntent::Drive {
-            linear_velocity: 5.0,
-            angular_velocity: 0.0,
+            linear_velocity: MetersPerSecond(5.0),
+            angular_velocity: RadiansPerSecond(0.0),
         });
         assert!(matches!(result, Err(MechError::HardwareFault { ref component, .. }) if component == "drive_base"));
+        assert_eq!(
+            result.unwrap_err().fault_code(),
+            Some(FaultCode::SpeedCapExceeded)
+        );
     }
 
     #[test]
@@ -443,12 +1612,13 @@ mod tests {
             .verify(&HardwareIntent::AskHuman {
                 question: "Help?".to_string(),
                 context_image_id: None,
+                question_id: None,
             })
             .is_ok());
         assert!(v
             .verify(&HardwareIntent::Drive {
-                linear_velocity: 999.0,
-                angular_velocity: 999.0
+                linear_velocity: MetersPerSecond(999.0),
+                angular_velocity: RadiansPerSecond(999.0)
             })
             .is_ok());
     }
@@ -461,6 +1631,7 @@ mod tests {
             .verify(&HardwareIntent::AskHuman {
                 question: "What should I do?".to_string(),
                 context_image_id: None,
+                question_id: None,
             })
             .is_ok());
     }
@@ -479,8 +1650,8 @@ mod tests {
         let v = override_verifier(Arc::clone(&flag));
         assert!(v
             .verify(&HardwareIntent::Drive {
-                linear_velocity: 0.5,
-                angular_velocity: 0.0,
+                linear_velocity: MetersPerSecond(0.5),
+                angular_velocity: RadiansPerSecond(0.0),
             })
             .is_ok());
     }
@@ -491,10 +1662,10 @@ mod tests {
         let v = override_verifier(Arc::clone(&flag));
         assert!(matches!(
             v.verify(&HardwareIntent::Drive {
-                linear_velocity: 0.5,
-                angular_velocity: 0.0,
+                linear_velocity: MetersPerSecond(0.5),
+                angular_velocity: RadiansPerSecond(0.0),
             }),
-            Err(MechError::HardwareFault { ref details, .. })
+            Err(MechError::HardwareFault { code: FaultCode::OverrideActive, ref details, .. })
                 if details.contains("manual override active")
         ));
     }
@@ -507,6 +1678,7 @@ mod tests {
             .verify(&HardwareIntent::AskHuman {
                 question: "Override active – what should I do?".to_string(),
                 context_image_id: None,
+                question_id: None,
             })
             .is_ok());
     }
@@ -518,17 +1690,187 @@ mod tests {
         // Active: Drive is blocked.
         assert!(v
             .verify(&HardwareIntent::Drive {
-                linear_velocity: 0.3,
-                angular_velocity: 0.0,
+                linear_velocity: MetersPerSecond(0.3),
+                angular_velocity: RadiansPerSecond(0.0),
             })
             .is_err());
         // Clear the flag – Drive should pass again.
         flag.store(false, Ordering::Release);
         assert!(v
             .verify(&HardwareIntent::Drive {
-                linear_velocity: 0.3,
-                angular_velocity: 0.0,
+                linear_velocity: MetersPerSecond(0.3),
+                angular_velocity: RadiansPerSecond(0.0),
             })
             .is_ok());
     }
+
+    // ------------------------------------------------------------------ BatteryInterlock
+
+    #[test]
+    fn undock_blocked_below_threshold() {
+        let level = Arc::new(AtomicU8::new(19));
+        let rule = BatteryInterlock::new(Arc::clone(&level));
+        assert_eq!(
+            rule.check(&HardwareIntent::Undock)
+                .unwrap_err()
+                .fault_code(),
+            Some(FaultCode::BatteryLow)
+        );
+
+        level.store(DEFAULT_MIN_UNDOCK_PERCENT, Ordering::Release);
+        assert!(rule.check(&HardwareIntent::Undock).is_ok());
+    }
+
+    #[test]
+    fn battery_interlock_only_gates_undock() {
+        let rule = BatteryInterlock::new(Arc::new(AtomicU8::new(0))).with_min_undock_percent(50);
+        assert!(rule.check(&HardwareIntent::Dock).is_ok());
+        assert!(
+            rule.check(&HardwareIntent::Drive {
+                linear_velocity: MetersPerSecond(0.2),
+                angular_velocity: RadiansPerSecond(0.0),
+            })
+            .is_ok()
+        );
+        assert!(rule.check(&HardwareIntent::Undock).is_err());
+    }
+
+    // ------------------------------------------------------------------ StaleDataRule
+
+    #[test]
+    fn stale_data_rule_passes_everything_while_localized() {
+        let rule = StaleDataRule::new(Arc::new(AtomicBool::new(false)));
+        assert!(
+            rule.check(&HardwareIntent::Drive {
+                linear_velocity: MetersPerSecond(1.0),
+                angular_velocity: RadiansPerSecond(0.0),
+            })
+            .is_ok()
+        );
+        assert!(
+            rule.check(&HardwareIntent::FollowWaypoints {
+                points: vec![(Meters(1.0), Meters(0.0))],
+                max_speed: MetersPerSecond(0.5),
+            })
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn stale_data_rule_restricts_motion_while_degraded() {
+        let rule = StaleDataRule::new(Arc::new(AtomicBool::new(true)))
+            .with_max_linear(MetersPerSecond(0.3));
+        let drive = |v: f32| HardwareIntent::Drive {
+            linear_velocity: MetersPerSecond(v),
+            angular_velocity: RadiansPerSecond(0.5),
+        };
+        assert!(rule.check(&drive(0.3)).is_ok());
+        assert!(rule.check(&drive(-0.2)).is_ok());
+        assert_eq!(
+            rule.check(&drive(-0.4)).unwrap_err().fault_code(),
+            Some(FaultCode::StaleSensor)
+        );
+        assert!(
+            rule.check(&HardwareIntent::FollowWaypoints {
+                points: vec![(Meters(1.0), Meters(0.0))],
+                max_speed: MetersPerSecond(0.1),
+            })
+            .is_err()
+        );
+        assert!(
+            rule.check(&HardwareIntent::Halt {
+                reason: "test".to_string()
+            })
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn moving_obstacle_rule_caps_speed_inside_the_margin() {
+        let clearance = Arc::new(AtomicU32::new(f32::INFINITY.to_bits()));
+        let rule = MovingObstacleRule::new(Arc::clone(&clearance))
+            .with_margin(Meters(1.5))
+            .with_max_linear(MetersPerSecond(0.2));
+        let drive = |v: f32| HardwareIntent::Drive {
+            linear_velocity: MetersPerSecond(v),
+            angular_velocity: RadiansPerSecond(0.0),
+        };
+        let follow = HardwareIntent::FollowWaypoints {
+            points: vec![(Meters(1.0), Meters(0.0))],
+            max_speed: MetersPerSecond(0.5),
+        };
+        assert!(rule.check(&drive(1.0)).is_ok());
+        assert!(rule.check(&follow).is_ok());
+
+        clearance.store(1.8f32.to_bits(), Ordering::Release);
+        assert!(rule.check(&drive(1.0)).is_ok());
+
+        clearance.store(1.0f32.to_bits(), Ordering::Release);
+        assert!(rule.check(&drive(0.2)).is_ok());
+        assert_eq!(
+            rule.check(&drive(-0.5)).unwrap_err().fault_code(),
+            Some(FaultCode::CollisionPredicted)
+        );
+        assert!(rule.check(&follow).is_err());
+        assert!(rule.check(&HardwareIntent::Undock).is_ok());
+    }
+
+    // ------------------------------------------------------------------ Runtime policy
+
+    #[test]
+    fn warn_mode_reports_but_does_not_block() {
+        let mut v = speed_verifier(1.0, 1.0);
+        v.add_rule(Box::new(ManualOverrideInterlock::new(Arc::new(
+            AtomicBool::new(false),
+        ))));
+        let fast = HardwareIntent::Drive {
+            linear_velocity: MetersPerSecond(2.0),
+            angular_velocity: RadiansPerSecond(0.0),
+        };
+        assert!(v.verify(&fast).is_err());
+
+        v.set_mode("speed_cap", RuleMode::Warn).unwrap();
+        assert!(v.verify(&fast).is_ok());
+        let (report, first) = v.evaluate(&fast);
+        assert!(!report[0].passed, "still reported");
+        assert!(first.is_none());
+
+        assert!(
+            v.set_mode("manual_override_interlock", RuleMode::Warn)
+                .is_err()
+        );
+        assert!(v.set_mode("no_such_rule", RuleMode::Block).is_err());
+        let modes: Vec<RuleMode> = v.rules().iter().map(|info| info.mode).collect();
+        assert_eq!(modes, [RuleMode::Warn, RuleMode::Block]);
+    }
+
+    #[test]
+    fn params_are_validated_before_they_apply() {
+        let mut v = workspace_verifier(-1.0, 1.0, -1.0, 1.0, 0.0, 2.0);
+        v.add_rule(Box::new(SpeedCapRule {
+            max_linear: MetersPerSecond(1.0),
+            max_angular: RadiansPerSecond(1.0),
+        }));
+
+        v.set_param("speed_cap", "max_linear", 0.5).unwrap();
+        assert!(v.set_param("speed_cap", "max_linear", -0.5).is_err());
+        assert!(v.set_param("speed_cap", "max_linear", f32::NAN).is_err());
+        assert!(v.set_param("speed_cap", "max_jerk", 1.0).is_err());
+        v.set_param("end_effector_workspace", "max_z", 1.5).unwrap();
+        let err = v
+            .set_param("end_effector_workspace", "min_x", 1.0)
+            .unwrap_err();
+        assert_eq!(err.fault_code(), Some(FaultCode::InvalidConfiguration));
+
+        let rules = v.rules();
+        assert_eq!(rules[0].params["max_z"], 1.5);
+        assert_eq!(rules[0].params["min_x"], -1.0);
+        assert_eq!(rules[1].params["max_linear"], 0.5);
+        assert!(
+            v.verify(&HardwareIntent::Drive {
+                linear_velocity: MetersPerSecond(0.8),
+                angular_velocity: RadiansPerSecond(0.0),
+            })
+            .is_err());
+    }
 }
@@ -87,6 +87,18 @@ impl Watchdog {
         }
     }
 
+    /// Record a heartbeat that happened at `at`, for components that report
+    /// their own liveness (e.g. the time an adapter last heard from its
+    /// device).
+    ///
+    /// Heartbeats older than the one already recorded are ignored.  No-ops
+    /// for components that have not been registered.
+    pub fn heartbeat_at(&mut self, component_id: &str, at: Instant) {
+        if let Some(entry) = self.components.get_mut(component_id) {
+            entry.last_heartbeat = entry.last_heartbeat.max(at);
+        }
+    }
+
     /// Return the [`ComponentHealth`] of `component_id`.
     ///
     /// Returns [`ComponentHealth::TimedOut`] for unknown components.
@@ -135,6 +147,18 @@ mod tests {
         assert_eq!(wd.health("sensor_bridge"), ComponentHealth::Healthy);
     }
 
+    #[test]
+    fn heartbeat_at_only_moves_forward() {
+        let mut wd = Watchdog::new();
+        wd.register("serial_adapter", Duration::from_millis(20));
+        let stale = Instant::now();
+        thread::sleep(Duration::from_millis(30));
+        wd.heartbeat_at("serial_adapter", stale);
+        assert_eq!(wd.health("serial_adapter"), ComponentHealth::TimedOut);
+        wd.heartbeat_at("serial_adapter", Instant::now());
+        assert_eq!(wd.health("serial_adapter"), ComponentHealth::Healthy);
+    }
+
     #[test]
     fn component_times_out_when_silent() {
         let mut wd = Watchdog::new();
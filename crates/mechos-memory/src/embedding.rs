@@ -0,0 +1,248 @@
+//! Text Embedding Generation.
+//!
+//! Turns free-form text into the dense vectors that
+//! [`EpisodicStore`][crate::episodic::EpisodicStore] persists and ranks, so
+//! callers can store and recall memories by text instead of hand-rolling
+//! vectors.
+//!
+//! Two [`Embedder`] implementations are provided:
+//!
+//! | embedder            | backend                                        | quality  |
+//! |---------------------|------------------------------------------------|----------|
+//! | [`OllamaEmbedder`]  | Ollama `/api/embeddings` (`nomic-embed-text`)  | semantic |
+//! | [`HashingEmbedder`] | offline feature hashing of word tokens         | lexical  |
+//!
+//! [`HashingEmbedder`] needs no network and no model download, which makes it
+//! the default for [`EpisodicStore`][crate::episodic::EpisodicStore] and a
+//! fallback when no Ollama server is reachable.  Texts that share words score
+//! higher than texts that do not, but synonyms are not recognised.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Error type
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Errors that can arise while computing an embedding.
+#[derive(Error, Debug)]
+pub enum EmbedError {
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Invalid embedding response: {0}")]
+    InvalidResponse(String),
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Embedder trait
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A source of dense text embeddings.
+///
+/// Every vector an embedder returns for the same configuration must have the
+/// same length, because [`EpisodicStore::recall_similar`][crate::episodic::EpisodicStore::recall_similar]
+/// skips entries whose dimension differs from the query.
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    /// Compute the embedding vector for `text`.
+    async fn embed(&self, text: &str) -> Result<Vec<f32>, EmbedError>;
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// HashingEmbedder
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Default dimension of [`HashingEmbedder`] vectors.
+pub const DEFAULT_HASHING_DIMENSIONS: usize = 256;
+
+/// Offline embedder that hashes lower-cased word tokens into a fixed number
+/// of buckets and L2-normalises the result.
+///
+/// Each token adds `±1` to one bucket chosen by its FNV-1a hash; the sign is
+/// taken from another bit of the same hash so collisions tend to cancel out
+/// rather than pile up.  The output is deterministic across runs and
+/// platforms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HashingEmbedder {
+    dimensions: usize,
+}
+
+impl HashingEmbedder {
+    /// Create an embedder producing vectors of `dimensions` components
+    /// (clamped to at least 1).
+    pub fn new(dimensions: usize) -> Self {
+        Self {
+            dimensions: dimensions.max(1),
+        }
+    }
+
+    /// Length of every vector this embedder produces.
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// Synchronous variant of [`Embedder::embed`]; hashing never fails.
+    pub fn embed_sync(&self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0.0f32; self.dimensions];
+        let tokens = text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|t| !t.is_empty())
+            .map(str::to_lowercase);
+        for token in tokens {
+            let hash = fnv1a(token.as_bytes());
+            let bucket = (hash % self.dimensions as u64) as usize;
+            let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+            vector[bucket] += sign;
+        }
+        let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            vector.iter_mut().for_each(|v| *v /= norm);
+        }
+        vector
+    }
+}
+
+impl Default for HashingEmbedder {
+    fn default() -> Self {
+        Self::new(DEFAULT_HASHING_DIMENSIONS)
+    }
+}
+
+#[async_trait]
+impl Embedder for HashingEmbedder {
+    async fn embed(&self, text: &str) -> Result<Vec<f32>, EmbedError> {
+        Ok(self.embed_sync(text))
+    }
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325u64, |hash, b| {
+        (hash ^ u64::from(*b)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// OllamaEmbedder
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Default Ollama embedding model.
+pub const DEFAULT_OLLAMA_EMBED_MODEL: &str = "nomic-embed-text";
+
+#[derive(Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    prompt: &'a str,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    embedding: Vec<f32>,
+}
+
+/// Embedder backed by a local Ollama server's `/api/embeddings` endpoint.
+///
+/// The model must already be pulled (`ollama pull nomic-embed-text`).
+#[derive(Debug, Clone)]
+pub struct OllamaEmbedder {
+    base_url: String,
+    model: String,
+    client: reqwest::Client,
+}
+
+impl OllamaEmbedder {
+    /// Create an embedder talking to the Ollama server at `base_url`
+    /// (e.g. `"http://localhost:11434"`) with
+    /// [`DEFAULT_OLLAMA_EMBED_MODEL`].
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            model: DEFAULT_OLLAMA_EMBED_MODEL.to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Use `model` instead of [`DEFAULT_OLLAMA_EMBED_MODEL`].
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    /// Name of the embedding model requested from Ollama.
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+}
+
+#[async_trait]
+impl Embedder for OllamaEmbedder {
+    async fn embed(&self, text: &str) -> Result<Vec<f32>, EmbedError> {
+        let url = format!("{}/api/embeddings", self.base_url.trim_end_matches('/'));
+        let response: EmbeddingResponse = self
+            .client
+            .post(url)
+            .json(&EmbeddingRequest {
+                model: &self.model,
+                prompt: text,
+            })
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        if response.embedding.is_empty() {
+            return Err(EmbedError::InvalidResponse(format!(
+                "model '{}' returned an empty embedding",
+                self.model
+            )));
+        }
+        Ok(response.embedding)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::episodic::cosine_similarity;
+
+    #[tokio::test]
+    async fn hashing_embedder_is_deterministic_and_normalised() {
+        let embedder = HashingEmbedder::new(64);
+        let a = embedder
+            .embed("The robot docked at the charger")
+            .await
+            .unwrap();
+        let b = embedder
+            .embed("the ROBOT docked at the charger!")
+            .await
+            .unwrap();
+        assert_eq!(a.len(), 64);
+        assert_eq!(a, b, "case and punctuation must not change the embedding");
+        let norm = a.iter().map(|v| v * v).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 1e-5);
+        assert!(embedder.embed_sync("").iter().all(|v| *v == 0.0));
+    }
+
+    #[test]
+    fn hashing_embedder_ranks_shared_words_higher() {
+        let embedder = HashingEmbedder::default();
+        let query = embedder.embed_sync("red cup on the kitchen table");
+        let related = embedder.embed_sync("picked up the red cup from the kitchen");
+        let unrelated = embedder.embed_sync("battery voltage dropped below threshold");
+        assert!(cosine_similarity(&query, &related) > cosine_similarity(&query, &unrelated));
+    }
+
+    #[tokio::test]
+    async fn ollama_embedder_reports_unreachable_server() {
+        let embedder = OllamaEmbedder::new("http://127.0.0.1:1/");
+        assert_eq!(embedder.model(), DEFAULT_OLLAMA_EMBED_MODEL);
+        assert!(matches!(
+            embedder.embed("hello").await,
+            Err(EmbedError::Http(_))
+        ));
+    }
+}
@@ -0,0 +1,204 @@
+//! At-rest encryption for the memory databases.
+//!
+//! Robots deployed at customer sites keep conversation summaries, camera
+//! references and fleet tasks on disk.  With the `sqlcipher` feature enabled,
+//! `mechos-memory` links SQLCipher instead of plain SQLite and
+//! [`EpisodicStore::open_encrypted`][crate::episodic::EpisodicStore::open_encrypted]
+//! and [`TaskBoard::open_encrypted`][crate::task_board::TaskBoard::open_encrypted]
+//! encrypt the whole database file with a [`DatabaseKey`].
+//!
+//! The key comes from a [`KeySource`]: either a passphrase taken from the
+//! configuration, or an entry in the OS keyring (`secret-tool` on Linux,
+//! `security` on macOS), so the passphrase never has to live next to the
+//! database.
+//!
+//! Opening an encrypted database without SQLCipher support fails with
+//! [`EncryptionError::Unsupported`] rather than silently writing plaintext.
+//!
+//! ```rust,ignore
+//! let key = KeySource::keyring("mechos", "memory").resolve()?;
+//! let store = EpisodicStore::open_encrypted("memory.db", &key)?;
+//! ```
+
+use rusqlite::{Connection, OptionalExtension};
+use thiserror::Error;
+
+use std::fmt;
+use std::process::Command;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Error type
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Errors that can arise while resolving a key or unlocking a database.
+#[derive(Error, Debug)]
+pub enum EncryptionError {
+    #[error("SQLite was built without SQLCipher; enable the `sqlcipher` feature")]
+    Unsupported,
+    #[error("wrong key, or the database is not encrypted")]
+    WrongKey,
+    #[error("OS keyring lookup failed: {0}")]
+    Keyring(String),
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Keys
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Passphrase a database is encrypted with.
+///
+/// Its [`Debug`] output is redacted so the key never ends up in logs.
+#[derive(Clone, PartialEq, Eq)]
+pub struct DatabaseKey(String);
+
+impl DatabaseKey {
+    /// Wrap `passphrase`.
+    pub fn new(passphrase: impl Into<String>) -> Self {
+        Self(passphrase.into())
+    }
+}
+
+impl fmt::Debug for DatabaseKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DatabaseKey(<redacted>)")
+    }
+}
+
+/// Where a [`DatabaseKey`] is read from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KeySource {
+    /// A passphrase given directly, e.g. from the configuration file.
+    Passphrase(DatabaseKey),
+    /// A secret stored in the OS keyring under `service` / `account`.
+    Keyring { service: String, account: String },
+}
+
+impl KeySource {
+    /// Use `passphrase` as the key.
+    pub fn passphrase(passphrase: impl Into<String>) -> Self {
+        Self::Passphrase(DatabaseKey::new(passphrase))
+    }
+
+    /// Read the key from the OS keyring entry `service` / `account`.
+    pub fn keyring(service: impl Into<String>, account: impl Into<String>) -> Self {
+        Self::Keyring {
+            service: service.into(),
+            account: account.into(),
+        }
+    }
+
+    /// Produce the key, querying the OS keyring if needed.
+    ///
+    /// Returns [`EncryptionError::Keyring`] if the keyring tool is missing,
+    /// fails, or holds an empty secret.
+    pub fn resolve(&self) -> Result<DatabaseKey, EncryptionError> {
+        match self {
+            Self::Passphrase(key) => Ok(key.clone()),
+            Self::Keyring { service, account } => keyring_lookup(service, account),
+        }
+    }
+}
+
+fn keyring_lookup(service: &str, account: &str) -> Result<DatabaseKey, EncryptionError> {
+    let mut command = if cfg!(target_os = "macos") {
+        let mut command = Command::new("security");
+        command.args(["find-generic-password", "-s", service, "-a", account, "-w"]);
+        command
+    } else {
+        let mut command = Command::new("secret-tool");
+        command.args(["lookup", "service", service, "account", account]);
+        command
+    };
+    let output = command
+        .output()
+        .map_err(|e| EncryptionError::Keyring(e.to_string()))?;
+    if !output.status.success() {
+        return Err(EncryptionError::Keyring(format!(
+            "no secret for {service}/{account} ({})",
+            output.status
+        )));
+    }
+    let secret =
+        String::from_utf8(output.stdout).map_err(|e| EncryptionError::Keyring(e.to_string()))?;
+    let secret = secret.trim_end_matches(['\r', '\n']);
+    if secret.is_empty() {
+        return Err(EncryptionError::Keyring(format!(
+            "empty secret for {service}/{account}"
+        )));
+    }
+    Ok(DatabaseKey::new(secret))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Unlocking
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Unlock (or, for a new file, encrypt) `conn` with `key`.
+///
+/// Must run before any other statement on the connection.
+pub(crate) fn apply_key(conn: &Connection, key: &DatabaseKey) -> Result<(), EncryptionError> {
+    // Plain SQLite ignores `PRAGMA key`, so check for SQLCipher first.
+    let cipher: Option<String> = conn
+        .query_row("PRAGMA cipher_version", [], |row| row.get(0))
+        .optional()?;
+    if cipher.is_none() {
+        return Err(EncryptionError::Unsupported);
+    }
+    conn.pragma_update(None, "key", &key.0)?;
+    // The key is only checked when the first page is read.
+    conn.query_row("SELECT count(*) FROM sqlite_master", [], |row| {
+        row.get::<_, i64>(0)
+    })
+    .map_err(|_| EncryptionError::WrongKey)?;
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_debug_is_redacted() {
+        let source = KeySource::passphrase("hunter2");
+        assert!(!format!("{source:?}").contains("hunter2"));
+        assert_eq!(source.resolve().unwrap(), DatabaseKey::new("hunter2"));
+    }
+
+    #[cfg(not(feature = "sqlcipher"))]
+    #[test]
+    fn plain_sqlite_refuses_to_encrypt() {
+        let conn = Connection::open_in_memory().unwrap();
+        let err = apply_key(&conn, &DatabaseKey::new("secret")).unwrap_err();
+        assert!(matches!(err, EncryptionError::Unsupported));
+    }
+
+    #[cfg(feature = "sqlcipher")]
+    #[test]
+    fn encrypted_file_needs_the_right_key() {
+        let path = std::env::temp_dir().join(format!("mechos-key-{}.db", uuid::Uuid::new_v4()));
+        let key = DatabaseKey::new("correct horse");
+        {
+            let conn = Connection::open(&path).unwrap();
+            apply_key(&conn, &key).unwrap();
+            conn.execute_batch("CREATE TABLE t (x TEXT); INSERT INTO t VALUES ('secret');")
+                .unwrap();
+        }
+        let raw = std::fs::read(&path).unwrap();
+        assert!(!raw.windows(6).any(|w| w == b"secret"));
+
+        let conn = Connection::open(&path).unwrap();
+        let err = apply_key(&conn, &DatabaseKey::new("wrong")).unwrap_err();
+        assert!(matches!(err, EncryptionError::WrongKey));
+        let conn = Connection::open(&path).unwrap();
+        apply_key(&conn, &key).unwrap();
+        let x: String = conn.query_row("SELECT x FROM t", [], |r| r.get(0)).unwrap();
+        assert_eq!(x, "secret");
+        std::fs::remove_file(&path).ok();
+    }
+}
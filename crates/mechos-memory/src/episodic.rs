@@ -17,6 +17,44 @@
 //! | source      | TEXT    | Originating component label                    |
 //! | summary     | TEXT    | Human-readable interaction summary             |
 //! | embedding   | BLOB    | Little-endian f32 vector (4 × N bytes)         |
+//! | importance  | REAL    | Retention weight in `[0.0, 1.0]`               |
+//! | tags        | TEXT    | JSON array of free-form labels                 |
+//! | metadata    | TEXT    | Arbitrary JSON value                           |
+//! | origin      | TEXT    | Robot that first recorded an imported memory   |
+//! | imported_at | TEXT    | RFC-3339 time the memory was imported          |
+//!
+//! Databases created before the `importance`, `tags`, `metadata`, `origin`
+//! or `imported_at` columns existed are migrated on open; their rows get
+//! [`DEFAULT_IMPORTANCE`], no tags, `null` metadata and no provenance.
+//!
+//! # Filtered recall
+//!
+//! [`EpisodicStore::recall_similar_filtered`] ranks only the entries matching
+//! a [`MemoryFilter`], so the agent can ask for "memories about the kitchen
+//! from today" instead of the globally most similar vectors:
+//!
+//! ```rust,ignore
+//! let filter = MemoryFilter::default().with_tag("kitchen").with_after(start_of_day);
+//! let results = store.recall_similar_filtered(&query, 5, &filter).await?;
+//! ```
+//!
+//! # Forgetting
+//!
+//! A [`RetentionPolicy`] installed with [`EpisodicStore::with_retention`] is
+//! enforced after every write: entries older than `max_age` are dropped, then
+//! the least important (oldest first on ties) are dropped until at most
+//! `max_entries` remain.  Entries at or above `keep_importance` are exempt
+//! from both rules.  [`EpisodicStore::forget`] deletes every entry matching a
+//! [`MemoryFilter`], e.g. all memories from one source for privacy reasons.
+//!
+//! # Fleet sharing
+//!
+//! [`EpisodicStore::export`] packs the entries matching a filter into a
+//! serialisable [`MemoryBundle`] that another robot feeds to
+//! [`EpisodicStore::import`], so one robot's experience ("the door on the
+//! east side sticks") reaches the whole fleet.  Entries keep their UUID, so
+//! importing the same bundle twice is harmless with `dedupe`, and record the
+//! robot that first learned them in [`MemoryEntry::origin`].
 //!
 //! # Example
 //!
@@ -38,16 +76,26 @@
 //!     assert_eq!(results[0].0.id, entry.id);
 //! }
 //! ```
+//!
+//! [`EpisodicStore::store_text`] and [`EpisodicStore::recall_text`] compute
+//! the embedding themselves with the store's [`Embedder`] (an offline
+//! [`HashingEmbedder`] unless replaced via [`EpisodicStore::with_embedder`],
+//! e.g. with an [`OllamaEmbedder`][crate::embedding::OllamaEmbedder]).
 
 use chrono::{DateTime, Utc};
-use rusqlite::{Connection, params};
+use rusqlite::types::Value;
+use rusqlite::{Connection, params, params_from_iter};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::embedding::{EmbedError, Embedder, HashingEmbedder};
+use crate::encryption::{DatabaseKey, EncryptionError, apply_key};
+
 use std::cmp::Ordering as CmpOrdering;
 use std::collections::BinaryHeap;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Error type
@@ -62,12 +110,25 @@ pub enum EpisodicError {
     DimensionMismatch,
     #[error("blocking task panicked: {0}")]
     TaskPanic(String),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Embedding failed: {0}")]
+    Embedding(#[from] EmbedError),
+    #[error("Encryption error: {0}")]
+    Encryption(#[from] EncryptionError),
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
 // MemoryEntry
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// Importance assigned to entries that do not set one explicitly.
+pub const DEFAULT_IMPORTANCE: f32 = 0.5;
+
+fn default_importance() -> f32 {
+    DEFAULT_IMPORTANCE
+}
+
 /// A single episodic memory record.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemoryEntry {
@@ -81,6 +142,24 @@ pub struct MemoryEntry {
     pub summary: String,
     /// Dense embedding vector representing the semantic content of the summary.
     pub embedding: Vec<f32>,
+    /// Retention weight in `[0.0, 1.0]`; low-importance entries are forgotten
+    /// first under a [`RetentionPolicy`].
+    #[serde(default = "default_importance")]
+    pub importance: f32,
+    /// Free-form labels (e.g. `"kitchen"`) usable as recall pre-filters.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Arbitrary structured data attached by the producer.
+    #[serde(default)]
+    pub metadata: serde_json::Value,
+    /// Robot that originally recorded this memory, or `None` if it was
+    /// recorded by an unnamed store.
+    #[serde(default)]
+    pub origin: Option<String>,
+    /// When this memory was imported from another robot, or `None` if it
+    /// was recorded locally.
+    #[serde(default)]
+    pub imported_at: Option<DateTime<Utc>>,
 }
 
 impl MemoryEntry {
@@ -93,19 +172,191 @@ impl MemoryEntry {
             source,
             summary,
             embedding,
+            importance: DEFAULT_IMPORTANCE,
+            tags: Vec::new(),
+            metadata: serde_json::Value::Null,
+            origin: None,
+            imported_at: None,
+        }
+    }
+
+    /// Set the entry's importance (clamped to `[0.0, 1.0]`).
+    pub fn with_importance(mut self, importance: f32) -> Self {
+        self.importance = importance.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Attach `tags` to the entry.
+    pub fn with_tags<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tags = tags.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Attach structured `metadata` to the entry.
+    pub fn with_metadata(mut self, metadata: serde_json::Value) -> Self {
+        self.metadata = metadata;
+        self
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Retention & forgetting
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Rules deciding which memories an [`EpisodicStore`] drops on its own.
+///
+/// The default policy keeps everything.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RetentionPolicy {
+    /// Maximum number of entries kept, or `None` for no limit.
+    pub max_entries: Option<usize>,
+    /// Maximum age of an entry, or `None` for no limit.
+    pub max_age: Option<Duration>,
+    /// Entries with at least this importance are never dropped by the policy.
+    pub keep_importance: Option<f32>,
+}
+
+impl RetentionPolicy {
+    /// Keep at most `max_entries` entries.
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(max_entries);
+        self
+    }
+
+    /// Drop entries older than `max_age`.
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Never drop entries whose importance is at least `importance`.
+    pub fn with_keep_importance(mut self, importance: f32) -> Self {
+        self.keep_importance = Some(importance);
+        self
+    }
+}
+
+/// Selects the entries removed by [`EpisodicStore::forget`] or ranked by
+/// [`EpisodicStore::recall_similar_filtered`].
+///
+/// Every condition that is set must match; an empty filter matches all
+/// entries.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MemoryFilter {
+    /// Only entries from this source.
+    pub source: Option<String>,
+    /// Only entries carrying this tag.
+    pub tag: Option<String>,
+    /// Only entries created at or after this time.
+    pub after: Option<DateTime<Utc>>,
+    /// Only entries created strictly before this time.
+    pub before: Option<DateTime<Utc>>,
+    /// Only entries with importance strictly below this value.
+    pub below_importance: Option<f32>,
+    /// Only entries whose summary contains this text (case-sensitive).
+    pub summary_contains: Option<String>,
+}
+
+impl MemoryFilter {
+    /// Match entries from `source`.
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Match entries tagged `tag`.
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    /// Match entries created at or after `after`.
+    pub fn with_after(mut self, after: DateTime<Utc>) -> Self {
+        self.after = Some(after);
+        self
+    }
+
+    /// Match entries created before `before`.
+    pub fn with_before(mut self, before: DateTime<Utc>) -> Self {
+        self.before = Some(before);
+        self
+    }
+
+    /// Match entries with importance below `importance`.
+    pub fn with_below_importance(mut self, importance: f32) -> Self {
+        self.below_importance = Some(importance);
+        self
+    }
+
+    /// Match entries whose summary contains `text`.
+    pub fn with_summary_containing(mut self, text: impl Into<String>) -> Self {
+        self.summary_contains = Some(text.into());
+        self
+    }
+
+    fn where_clause(&self) -> (String, Vec<Value>) {
+        let mut conditions = Vec::new();
+        let mut values = Vec::new();
+        if let Some(source) = &self.source {
+            conditions.push("source = ?");
+            values.push(Value::Text(source.clone()));
+        }
+        if let Some(tag) = &self.tag {
+            conditions.push("EXISTS (SELECT 1 FROM json_each(tags) WHERE value = ?)");
+            values.push(Value::Text(tag.clone()));
+        }
+        if let Some(after) = self.after {
+            conditions.push("timestamp >= ?");
+            values.push(Value::Text(after.to_rfc3339()));
+        }
+        if let Some(before) = self.before {
+            conditions.push("timestamp < ?");
+            values.push(Value::Text(before.to_rfc3339()));
+        }
+        if let Some(importance) = self.below_importance {
+            conditions.push("importance < ?");
+            values.push(Value::Real(f64::from(importance)));
+        }
+        if let Some(text) = &self.summary_contains {
+            conditions.push("instr(summary, ?) > 0");
+            values.push(Value::Text(text.clone()));
+        }
+        if conditions.is_empty() {
+            ("1".to_string(), values)
+        } else {
+            (conditions.join(" AND "), values)
         }
     }
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Fleet sharing
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A batch of memories exported by one robot for the rest of the fleet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryBundle {
+    /// Robot that produced the bundle, if the exporting store is named.
+    pub origin: Option<String>,
+    /// When the bundle was exported.
+    pub exported_at: DateTime<Utc>,
+    /// The exported memories, oldest first.
+    pub entries: Vec<MemoryEntry>,
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Embedding serialisation helpers
 // ─────────────────────────────────────────────────────────────────────────────
 
-fn embedding_to_bytes(embedding: &[f32]) -> Vec<u8> {
+pub(crate) fn embedding_to_bytes(embedding: &[f32]) -> Vec<u8> {
     embedding.iter().flat_map(|f| f.to_le_bytes()).collect()
 }
 
-fn bytes_to_embedding(bytes: &[u8]) -> Vec<f32> {
+pub(crate) fn bytes_to_embedding(bytes: &[u8]) -> Vec<f32> {
     bytes
         .chunks_exact(4)
         .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
@@ -183,6 +434,9 @@ impl Ord for HeapEntry {
 #[derive(Clone)]
 pub struct EpisodicStore {
     conn: Arc<Mutex<Connection>>,
+    embedder: Arc<dyn Embedder>,
+    retention: RetentionPolicy,
+    origin: Option<String>,
 }
 
 impl EpisodicStore {
@@ -193,30 +447,100 @@ impl EpisodicStore {
     pub fn open(path: &str) -> Result<Self, EpisodicError> {
         let conn = Connection::open(path)?;
         conn.execute_batch("PRAGMA journal_mode=WAL;")?;
-        let store = Self { conn: Arc::new(Mutex::new(conn)) };
-        store.init_schema()?;
-        Ok(store)
+        Self::from_connection(conn)
+    }
+
+    /// Like [`open`][Self::open], but encrypts the database file with `key`
+    /// (see [`crate::encryption`]).
+    ///
+    /// Returns [`EpisodicError::Encryption`] if `key` does not unlock an
+    /// existing file or SQLCipher support is not compiled in.
+    pub fn open_encrypted(path: &str, key: &DatabaseKey) -> Result<Self, EpisodicError> {
+        let conn = Connection::open(path)?;
+        apply_key(&conn, key)?;
+        conn.execute_batch("PRAGMA journal_mode=WAL;")?;
+        Self::from_connection(conn)
     }
 
     /// Open a temporary in-memory database (useful for testing).
     pub fn open_in_memory() -> Result<Self, EpisodicError> {
-        let conn = Connection::open_in_memory()?;
-        let store = Self { conn: Arc::new(Mutex::new(conn)) };
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    fn from_connection(conn: Connection) -> Result<Self, EpisodicError> {
+        let store = Self {
+            conn: Arc::new(Mutex::new(conn)),
+            embedder: Arc::new(HashingEmbedder::default()),
+            retention: RetentionPolicy::default(),
+            origin: None,
+        };
         store.init_schema()?;
         Ok(store)
     }
 
+    /// Use `embedder` to compute the vectors for [`store_text`][Self::store_text]
+    /// instead of the default [`HashingEmbedder`].
+    pub fn with_embedder(mut self, embedder: impl Embedder + 'static) -> Self {
+        self.embedder = Arc::new(embedder);
+        self
+    }
+
+    /// Enforce `policy` after every write (see [`RetentionPolicy`]).
+    pub fn with_retention(mut self, policy: RetentionPolicy) -> Self {
+        self.retention = policy;
+        self
+    }
+
+    /// The retention policy enforced by this store.
+    pub fn retention(&self) -> RetentionPolicy {
+        self.retention
+    }
+
+    /// Name this store's robot, recorded as the [`MemoryEntry::origin`] of
+    /// the locally recorded memories it exports.
+    pub fn with_origin(mut self, robot_id: impl Into<String>) -> Self {
+        self.origin = Some(robot_id.into());
+        self
+    }
+
+    /// The robot this store belongs to, if named.
+    pub fn origin(&self) -> Option<&str> {
+        self.origin.as_deref()
+    }
+
     fn init_schema(&self) -> Result<(), EpisodicError> {
         let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
         conn.execute_batch(
             "CREATE TABLE IF NOT EXISTS episodic_memories (
-                id        TEXT NOT NULL PRIMARY KEY,
-                timestamp TEXT NOT NULL,
-                source    TEXT NOT NULL,
-                summary   TEXT NOT NULL,
-                embedding BLOB NOT NULL
+                id         TEXT NOT NULL PRIMARY KEY,
+                timestamp  TEXT NOT NULL,
+                source     TEXT NOT NULL,
+                summary    TEXT NOT NULL,
+                embedding  BLOB NOT NULL,
+                importance REAL NOT NULL DEFAULT 0.5,
+                tags       TEXT NOT NULL DEFAULT '[]',
+                metadata   TEXT NOT NULL DEFAULT 'null',
+                origin     TEXT,
+                imported_at TEXT
             );",
         )?;
+        // Migrate databases created before these columns existed.
+        for (column, definition) in [
+            ("importance", "REAL NOT NULL DEFAULT 0.5"),
+            ("tags", "TEXT NOT NULL DEFAULT '[]'"),
+            ("metadata", "TEXT NOT NULL DEFAULT 'null'"),
+            ("origin", "TEXT"),
+            ("imported_at", "TEXT"),
+        ] {
+            let exists = conn
+                .prepare("SELECT 1 FROM pragma_table_info('episodic_memories') WHERE name = ?1")?
+                .exists([column])?;
+            if !exists {
+                conn.execute_batch(&format!(
+                    "ALTER TABLE episodic_memories ADD COLUMN {column} {definition};"
+                ))?;
+            }
+        }
         Ok(())
     }
 
@@ -226,47 +550,172 @@ impl EpisodicStore {
             return Err(EpisodicError::DimensionMismatch);
         }
         let conn = Arc::clone(&self.conn);
-        let blob = embedding_to_bytes(&entry.embedding);
-        let id = entry.id.to_string();
-        let ts = entry.timestamp.to_rfc3339();
-        let source = entry.source.clone();
-        let summary = entry.summary.clone();
+        let entry = entry.clone();
+        let retention = self.retention;
         tokio::task::spawn_blocking(move || {
             let conn = conn.lock().unwrap_or_else(|e| e.into_inner());
-            conn.execute(
-                "INSERT OR REPLACE INTO episodic_memories
-                     (id, timestamp, source, summary, embedding)
-                 VALUES (?1, ?2, ?3, ?4, ?5)",
-                params![id, ts, source, summary, blob],
-            )?;
+            insert_entry(&conn, &entry, "REPLACE")?;
+            apply_retention(&conn, &retention)?;
             Ok(())
         })
         .await
         .map_err(|e| EpisodicError::TaskPanic(e.to_string()))?
     }
 
+    /// Enforce the store's [`RetentionPolicy`] now and return the number of
+    /// entries dropped.
+    ///
+    /// Writes already do this; call it to expire entries by `max_age` while
+    /// nothing is being stored.
+    pub async fn enforce_retention(&self) -> Result<usize, EpisodicError> {
+        let conn = Arc::clone(&self.conn);
+        let retention = self.retention;
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap_or_else(|e| e.into_inner());
+            apply_retention(&conn, &retention)
+        })
+        .await
+        .map_err(|e| EpisodicError::TaskPanic(e.to_string()))?
+    }
+
+    /// Delete every entry matching `filter` and return how many were removed.
+    pub async fn forget(&self, filter: &MemoryFilter) -> Result<usize, EpisodicError> {
+        let conn = Arc::clone(&self.conn);
+        let (clause, values) = filter.where_clause();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap_or_else(|e| e.into_inner());
+            let removed = conn.execute(
+                &format!("DELETE FROM episodic_memories WHERE {clause}"),
+                params_from_iter(values),
+            )?;
+            Ok(removed)
+        })
+        .await
+        .map_err(|e| EpisodicError::TaskPanic(e.to_string()))?
+    }
+
+    /// Embed `summary` with the store's [`Embedder`], persist it as a new
+    /// [`MemoryEntry`] from `source` and return that entry.
+    ///
+    /// Returns [`EpisodicError::Embedding`] if the embedder fails and
+    /// [`EpisodicError::DimensionMismatch`] if it returns an empty vector.
+    pub async fn store_text(
+        &self,
+        source: impl Into<String>,
+        summary: impl Into<String>,
+    ) -> Result<MemoryEntry, EpisodicError> {
+        let summary = summary.into();
+        let embedding = self.embedder.embed(&summary).await?;
+        let entry = MemoryEntry::new(source.into(), summary, embedding);
+        self.store(&entry).await?;
+        Ok(entry)
+    }
+
+    /// Export the entries matching `filter` as a [`MemoryBundle`] for other
+    /// robots.
+    ///
+    /// Locally recorded entries are stamped with this store's
+    /// [`origin`][Self::with_origin]; imported ones keep theirs.
+    pub async fn export(&self, filter: &MemoryFilter) -> Result<MemoryBundle, EpisodicError> {
+        let mut entries = self.entries_matching(filter).await?;
+        for entry in &mut entries {
+            if entry.origin.is_none() {
+                entry.origin = self.origin.clone();
+            }
+        }
+        Ok(MemoryBundle {
+            origin: self.origin.clone(),
+            exported_at: Utc::now(),
+            entries,
+        })
+    }
+
+    /// Store the memories of `bundle` and return how many were written.
+    ///
+    /// Each entry is marked as imported now and keeps its provenance,
+    /// falling back to the bundle's origin.  With `dedupe`, entries whose
+    /// UUID is already stored are skipped (so re-importing a bundle is a
+    /// no-op); without it they overwrite the stored copy.  Entries with an
+    /// empty embedding are skipped.
+    pub async fn import(
+        &self,
+        bundle: &MemoryBundle,
+        dedupe: bool,
+    ) -> Result<usize, EpisodicError> {
+        let conn = Arc::clone(&self.conn);
+        let now = Utc::now();
+        let entries: Vec<MemoryEntry> = bundle
+            .entries
+            .iter()
+            .filter(|entry| !entry.embedding.is_empty())
+            .map(|entry| MemoryEntry {
+                origin: entry.origin.clone().or_else(|| bundle.origin.clone()),
+                imported_at: Some(now),
+                ..entry.clone()
+            })
+            .collect();
+        let conflict = if dedupe { "IGNORE" } else { "REPLACE" };
+        let retention = self.retention;
+        tokio::task::spawn_blocking(move || {
+            let mut conn = conn.lock().unwrap_or_else(|e| e.into_inner());
+            let tx = conn.transaction()?;
+            let mut written = 0;
+            for entry in &entries {
+                written += insert_entry(&tx, entry, conflict)?;
+            }
+            tx.commit()?;
+            apply_retention(&conn, &retention)?;
+            Ok(written)
+        })
+        .await
+        .map_err(|e| EpisodicError::TaskPanic(e.to_string()))?
+    }
+
     /// Retrieve all stored entries ordered by timestamp (oldest first).
     pub async fn all_entries(&self) -> Result<Vec<MemoryEntry>, EpisodicError> {
+        self.entries_matching(&MemoryFilter::default()).await
+    }
+
+    /// Retrieve the entries matching `filter`, ordered by timestamp (oldest
+    /// first).
+    pub async fn entries_matching(
+        &self,
+        filter: &MemoryFilter,
+    ) -> Result<Vec<MemoryEntry>, EpisodicError> {
         let conn = Arc::clone(&self.conn);
+        let (clause, values) = filter.where_clause();
         tokio::task::spawn_blocking(move || {
             let conn = conn.lock().unwrap_or_else(|e| e.into_inner());
-            let mut stmt = conn.prepare(
-                "SELECT id, timestamp, source, summary, embedding
+            let mut stmt = conn.prepare(&format!(
+                "SELECT id, timestamp, source, summary, embedding, importance, tags, metadata,
+                        origin, imported_at
                  FROM episodic_memories
-                 ORDER BY timestamp ASC",
-            )?;
-            let rows = stmt.query_map([], |row| {
+                 WHERE {clause}
+                 ORDER BY timestamp ASC"
+            ))?;
+            let rows = stmt.query_map(params_from_iter(values), |row| {
                 let id_str: String = row.get(0)?;
                 let ts_str: String = row.get(1)?;
                 let source: String = row.get(2)?;
                 let summary: String = row.get(3)?;
                 let blob: Vec<u8> = row.get(4)?;
-                Ok((id_str, ts_str, source, summary, blob))
+                let importance: f64 = row.get(5)?;
+                let tags: String = row.get(6)?;
+                let metadata: String = row.get(7)?;
+                let origin: Option<String> = row.get(8)?;
+                let imported_at: Option<String> = row.get(9)?;
+                Ok((
+                    (id_str, ts_str, source, summary, blob),
+                    (importance, tags, metadata, origin, imported_at),
+                ))
             })?;
 
             let mut entries = Vec::new();
             for row in rows {
-                let (id_str, ts_str, source, summary, blob) = row?;
+                let (
+                    (id_str, ts_str, source, summary, blob),
+                    (importance, tags, metadata, origin, imported_at),
+                ) = row?;
                 let id = Uuid::parse_str(&id_str)
                     .map_err(|e| rusqlite::Error::InvalidColumnType(0, e.to_string(), rusqlite::types::Type::Text))?;
                 let timestamp = ts_str.parse::<DateTime<Utc>>().map_err(|e| {
@@ -278,6 +727,11 @@ impl EpisodicStore {
                     source,
                     summary,
                     embedding: bytes_to_embedding(&blob),
+                    importance: importance as f32,
+                    tags: serde_json::from_str(&tags)?,
+                    metadata: serde_json::from_str(&metadata)?,
+                    origin,
+                    imported_at: imported_at.and_then(|ts| ts.parse().ok()),
                 });
             }
             Ok(entries)
@@ -300,6 +754,18 @@ impl EpisodicStore {
         &self,
         query_embedding: &[f32],
         top_k: usize,
+    ) -> Result<Vec<(MemoryEntry, f32)>, EpisodicError> {
+        self.recall_similar_filtered(query_embedding, top_k, &MemoryFilter::default())
+            .await
+    }
+
+    /// Like [`recall_similar`][Self::recall_similar], but only ranks entries
+    /// matching `filter` (e.g. a source, tag or time range).
+    pub async fn recall_similar_filtered(
+        &self,
+        query_embedding: &[f32],
+        top_k: usize,
+        filter: &MemoryFilter,
     ) -> Result<Vec<(MemoryEntry, f32)>, EpisodicError> {
         if query_embedding.is_empty() {
             return Err(EpisodicError::DimensionMismatch);
@@ -307,7 +773,7 @@ impl EpisodicStore {
         if top_k == 0 {
             return Ok(vec![]);
         }
-        let entries = self.all_entries().await?;
+        let entries = self.entries_matching(filter).await?;
         let query = query_embedding.to_vec();
 
         // Min-heap of capacity `top_k`: the entry with the lowest similarity
@@ -334,6 +800,88 @@ impl EpisodicStore {
         result.sort_by(|a, b| b.1.total_cmp(&a.1));
         Ok(result)
     }
+
+    /// Like [`recall_similar_filtered`][Self::recall_similar_filtered], but
+    /// embeds the free-text `query` with the store's [`Embedder`] first, e.g.
+    /// to search what the robot remembers from a debugging shell.
+    ///
+    /// Returns [`EpisodicError::Embedding`] if the embedder fails.
+    pub async fn recall_text(
+        &self,
+        query: &str,
+        top_k: usize,
+        filter: &MemoryFilter,
+    ) -> Result<Vec<(MemoryEntry, f32)>, EpisodicError> {
+        let embedding = self.embedder.embed(query).await?;
+        self.recall_similar_filtered(&embedding, top_k, filter)
+            .await
+    }
+}
+
+/// Write `entry`, resolving an id clash with `conflict` (`"REPLACE"` or
+/// `"IGNORE"`), and return the number of rows written.
+fn insert_entry(
+    conn: &Connection,
+    entry: &MemoryEntry,
+    conflict: &str,
+) -> Result<usize, EpisodicError> {
+    let written = conn.execute(
+        &format!(
+            "INSERT OR {conflict} INTO episodic_memories
+                 (id, timestamp, source, summary, embedding, importance, tags, metadata, origin,
+                  imported_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"
+        ),
+        params![
+            entry.id.to_string(),
+            entry.timestamp.to_rfc3339(),
+            entry.source,
+            entry.summary,
+            embedding_to_bytes(&entry.embedding),
+            f64::from(entry.importance.clamp(0.0, 1.0)),
+            serde_json::to_string(&entry.tags)?,
+            serde_json::to_string(&entry.metadata)?,
+            entry.origin,
+            entry.imported_at.map(|ts| ts.to_rfc3339()),
+        ],
+    )?;
+    Ok(written)
+}
+
+/// Drop the entries `retention` no longer allows and return how many were
+/// removed.
+fn apply_retention(conn: &Connection, retention: &RetentionPolicy) -> Result<usize, EpisodicError> {
+    // Entries at or above the keep threshold are exempt; without a threshold
+    // nothing is (importance never exceeds 1.0).
+    let keep = retention.keep_importance.map_or(f64::INFINITY, f64::from);
+    let mut removed = 0;
+    if let Some(max_age) = retention.max_age {
+        let age = chrono::Duration::from_std(max_age).unwrap_or(chrono::Duration::MAX);
+        if let Some(cutoff) = Utc::now().checked_sub_signed(age) {
+            removed += conn.execute(
+                "DELETE FROM episodic_memories WHERE timestamp < ?1 AND importance < ?2",
+                params![cutoff.to_rfc3339(), keep],
+            )?;
+        }
+    }
+    if let Some(max_entries) = retention.max_entries {
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM episodic_memories", [], |row| {
+            row.get(0)
+        })?;
+        let excess = count - max_entries as i64;
+        if excess > 0 {
+            removed += conn.execute(
+                "DELETE FROM episodic_memories WHERE id IN (
+                     SELECT id FROM episodic_memories
+                     WHERE importance < ?1
+                     ORDER BY importance ASC, timestamp ASC
+                     LIMIT ?2
+                 )",
+                params![keep, excess],
+            )?;
+        }
+    }
+    Ok(removed)
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -416,6 +964,38 @@ mod tests {
         assert!((results[0].1 - 1.0).abs() < 1e-6);
     }
 
+    #[tokio::test]
+    async fn store_text_embeds_summary_for_recall() {
+        let store = EpisodicStore::open_in_memory()
+            .unwrap()
+            .with_embedder(HashingEmbedder::new(128));
+        let cup = store
+            .store_text("rt", "Left the red cup on the kitchen table")
+            .await
+            .unwrap();
+        store
+            .store_text("rt", "Battery dropped to twenty percent")
+            .await
+            .unwrap();
+        assert_eq!(cup.embedding.len(), 128);
+
+        let query = HashingEmbedder::new(128).embed_sync("where is the red cup");
+        let results = store.recall_similar(&query, 1).await.unwrap();
+        assert_eq!(results[0].0.id, cup.id);
+        assert_eq!(
+            results[0].0.summary,
+            "Left the red cup on the kitchen table"
+        );
+
+        let results = store
+            .recall_text("where is the red cup", 1, &MemoryFilter::default())
+            .await
+            .unwrap();
+        assert_eq!(results[0].0.id, cup.id);
+        let filtered = MemoryFilter::default().with_source("other");
+        assert!(store.recall_text("red cup", 1, &filtered).await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn recall_similar_top_k_limits_results() {
         let store = EpisodicStore::open_in_memory().unwrap();
@@ -443,6 +1023,11 @@ mod tests {
             source: "test".to_string(),
             summary: "no embedding".to_string(),
             embedding: vec![],
+            importance: DEFAULT_IMPORTANCE,
+            tags: Vec::new(),
+            metadata: serde_json::Value::Null,
+            origin: None,
+            imported_at: None,
         };
         let err = store.store(&e).await.unwrap_err();
         assert!(matches!(err, EpisodicError::DimensionMismatch));
@@ -478,4 +1063,242 @@ mod tests {
         let all = store.all_entries().await.unwrap();
         assert!(all.is_empty());
     }
+
+    // ── retention & forgetting ───────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn importance_roundtrips_and_is_clamped() {
+        let store = EpisodicStore::open_in_memory().unwrap();
+        let e = make_entry("rt", "vital", vec![1.0]).with_importance(3.0);
+        assert_eq!(e.importance, 1.0);
+        store.store(&e).await.unwrap();
+        assert_eq!(store.all_entries().await.unwrap()[0].importance, 1.0);
+    }
+
+    #[tokio::test]
+    async fn max_entries_drops_least_important_but_keeps_protected() {
+        let policy = RetentionPolicy::default()
+            .with_max_entries(2)
+            .with_keep_importance(0.9);
+        let store = EpisodicStore::open_in_memory()
+            .unwrap()
+            .with_retention(policy);
+        let vital = make_entry("rt", "vital", vec![1.0]).with_importance(0.95);
+        let low = make_entry("rt", "low", vec![1.0]).with_importance(0.1);
+        let mid = make_entry("rt", "mid", vec![1.0]).with_importance(0.5);
+        let vital2 = make_entry("rt", "vital2", vec![1.0]).with_importance(1.0);
+        for e in [&vital, &low, &mid] {
+            store.store(e).await.unwrap();
+        }
+        let summaries: Vec<_> = store
+            .all_entries()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|e| e.summary)
+            .collect();
+        assert_eq!(summaries, vec!["vital", "mid"]);
+
+        // Only protected entries remain over the limit: nothing else to drop.
+        store.store(&vital2).await.unwrap();
+        let summaries: Vec<_> = store
+            .all_entries()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|e| e.summary)
+            .collect();
+        assert_eq!(summaries, vec!["vital", "vital2"]);
+    }
+
+    #[tokio::test]
+    async fn max_age_expires_old_unprotected_entries() {
+        let policy = RetentionPolicy::default()
+            .with_max_age(Duration::from_secs(3600))
+            .with_keep_importance(0.9);
+        let store = EpisodicStore::open_in_memory()
+            .unwrap()
+            .with_retention(policy);
+        let mut old = make_entry("rt", "old", vec![1.0]);
+        old.timestamp = Utc::now() - chrono::Duration::hours(2);
+        let mut old_vital = make_entry("rt", "old vital", vec![1.0]).with_importance(0.9);
+        old_vital.timestamp = old.timestamp;
+        store.store(&old_vital).await.unwrap();
+        store.store(&old).await.unwrap();
+        store
+            .store(&make_entry("rt", "fresh", vec![1.0]))
+            .await
+            .unwrap();
+
+        assert_eq!(store.enforce_retention().await.unwrap(), 0);
+        let summaries: Vec<_> = store
+            .all_entries()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|e| e.summary)
+            .collect();
+        assert_eq!(summaries, vec!["old vital", "fresh"]);
+    }
+
+    #[tokio::test]
+    async fn forget_removes_only_matching_entries() {
+        let store = EpisodicStore::open_in_memory().unwrap();
+        store
+            .store(&make_entry("camera", "saw Alice's face", vec![1.0]))
+            .await
+            .unwrap();
+        store
+            .store(&make_entry("camera", "saw a chair", vec![1.0]).with_importance(0.8))
+            .await
+            .unwrap();
+        store
+            .store(&make_entry("lidar", "wall ahead", vec![1.0]).with_importance(0.1))
+            .await
+            .unwrap();
+
+        let by_text = MemoryFilter::default()
+            .with_source("camera")
+            .with_summary_containing("Alice");
+        assert_eq!(store.forget(&by_text).await.unwrap(), 1);
+        let low = MemoryFilter::default().with_below_importance(0.5);
+        assert_eq!(store.forget(&low).await.unwrap(), 1);
+        let all = store.all_entries().await.unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].summary, "saw a chair");
+
+        assert_eq!(store.forget(&MemoryFilter::default()).await.unwrap(), 1);
+        assert!(store.all_entries().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn legacy_schema_gains_importance_column() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE episodic_memories (
+                id TEXT NOT NULL PRIMARY KEY, timestamp TEXT NOT NULL, source TEXT NOT NULL,
+                summary TEXT NOT NULL, embedding BLOB NOT NULL
+            );
+            INSERT INTO episodic_memories VALUES ('6f9619ff-8b86-d011-b42d-00cf4fc964ff',
+                '2024-01-01T00:00:00+00:00', 'rt', 'legacy', x'0000803f');",
+        )
+        .unwrap();
+        let store = EpisodicStore {
+            conn: Arc::new(Mutex::new(conn)),
+            embedder: Arc::new(HashingEmbedder::default()),
+            retention: RetentionPolicy::default(),
+            origin: None,
+        };
+        store.init_schema().unwrap();
+        let all = store.all_entries().await.unwrap();
+        assert_eq!(all[0].summary, "legacy");
+        assert_eq!(all[0].importance, DEFAULT_IMPORTANCE);
+        assert!(all[0].tags.is_empty());
+        assert!(all[0].metadata.is_null());
+    }
+
+    // ── tags, metadata & filtered recall ─────────────────────────────────────
+
+    #[tokio::test]
+    async fn tags_and_metadata_roundtrip() {
+        let store = EpisodicStore::open_in_memory().unwrap();
+        let e = make_entry("vision", "cup on table", vec![1.0])
+            .with_tags(["kitchen", "objects"])
+            .with_metadata(serde_json::json!({ "room": "kitchen", "x": 1.5 }));
+        store.store(&e).await.unwrap();
+        let all = store.all_entries().await.unwrap();
+        assert_eq!(all[0].tags, vec!["kitchen", "objects"]);
+        assert_eq!(all[0].metadata["x"], 1.5);
+    }
+
+    #[tokio::test]
+    async fn recall_similar_filtered_applies_tag_source_and_time() {
+        let store = EpisodicStore::open_in_memory().unwrap();
+        let mut yesterday =
+            make_entry("vision", "kitchen yesterday", vec![1.0, 0.0]).with_tags(["kitchen"]);
+        yesterday.timestamp = Utc::now() - chrono::Duration::days(1);
+        let today = make_entry("vision", "kitchen today", vec![0.6, 0.8]).with_tags(["kitchen"]);
+        let hallway = make_entry("vision", "hallway today", vec![1.0, 0.0]).with_tags(["hallway"]);
+        let audio = make_entry("audio", "kitchen sound", vec![1.0, 0.0]).with_tags(["kitchen"]);
+        for e in [&yesterday, &today, &hallway, &audio] {
+            store.store(e).await.unwrap();
+        }
+
+        let filter = MemoryFilter::default()
+            .with_tag("kitchen")
+            .with_source("vision")
+            .with_after(Utc::now() - chrono::Duration::hours(1));
+        let results = store
+            .recall_similar_filtered(&[1.0, 0.0], 5, &filter)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0.id, today.id);
+
+        // Unfiltered recall still sees every entry.
+        assert_eq!(store.recall_similar(&[1.0, 0.0], 5).await.unwrap().len(), 4);
+    }
+
+    // ── export / import ──────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn export_import_shares_memories_with_provenance() {
+        let alpha = EpisodicStore::open_in_memory()
+            .unwrap()
+            .with_origin("robot_alpha");
+        assert_eq!(alpha.origin(), Some("robot_alpha"));
+        let door = make_entry("rt", "door on the east side sticks", vec![1.0, 0.0])
+            .with_tags(["navigation"]);
+        alpha.store(&door).await.unwrap();
+        alpha
+            .store(&make_entry("rt", "private note", vec![0.0, 1.0]))
+            .await
+            .unwrap();
+
+        let bundle = alpha
+            .export(&MemoryFilter::default().with_tag("navigation"))
+            .await
+            .unwrap();
+        assert_eq!(bundle.origin.as_deref(), Some("robot_alpha"));
+        assert_eq!(bundle.entries.len(), 1);
+        let json = serde_json::to_string(&bundle).unwrap();
+        let bundle: MemoryBundle = serde_json::from_str(&json).unwrap();
+
+        let bravo = EpisodicStore::open_in_memory()
+            .unwrap()
+            .with_origin("robot_bravo");
+        assert_eq!(bravo.import(&bundle, true).await.unwrap(), 1);
+        let imported = &bravo.all_entries().await.unwrap()[0];
+        assert_eq!(imported.id, door.id);
+        assert_eq!(imported.origin.as_deref(), Some("robot_alpha"));
+        assert!(imported.imported_at.is_some());
+        assert_eq!(imported.tags, vec!["navigation"]);
+
+        // Re-exporting keeps the original robot as the origin.
+        let relayed = bravo.export(&MemoryFilter::default()).await.unwrap();
+        assert_eq!(relayed.origin.as_deref(), Some("robot_bravo"));
+        assert_eq!(relayed.entries[0].origin.as_deref(), Some("robot_alpha"));
+    }
+
+    #[tokio::test]
+    async fn import_dedupes_by_uuid_unless_asked_to_overwrite() {
+        let store = EpisodicStore::open_in_memory().unwrap();
+        let local = make_entry("rt", "local copy", vec![1.0, 0.0]);
+        store.store(&local).await.unwrap();
+        let mut remote = local.clone();
+        remote.summary = "remote copy".to_string();
+        let bundle = MemoryBundle {
+            origin: Some("robot_alpha".to_string()),
+            exported_at: Utc::now(),
+            entries: vec![remote, make_entry("rt", "", vec![])],
+        };
+
+        assert_eq!(store.import(&bundle, true).await.unwrap(), 0);
+        assert_eq!(store.all_entries().await.unwrap()[0].summary, "local copy");
+        assert_eq!(store.import(&bundle, false).await.unwrap(), 1);
+        let all = store.all_entries().await.unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].summary, "remote copy");
+        assert_eq!(all[0].origin.as_deref(), Some("robot_alpha"));
+    }
 }
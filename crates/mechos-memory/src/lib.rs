@@ -5,14 +5,35 @@
 //!
 //! # Modules
 //!
+//! - [`embedding`] – the [`Embedder`][embedding::Embedder] trait, with an
+//!   Ollama-backed [`OllamaEmbedder`][embedding::OllamaEmbedder] and an
+//!   offline [`HashingEmbedder`][embedding::HashingEmbedder] fallback.
+//! - [`encryption`] – optional SQLCipher at-rest encryption of the memory
+//!   databases, keyed from the configuration or the OS keyring.
 //! - [`episodic`] – [`EpisodicStore`][episodic::EpisodicStore]: a local vector
 //!   database that persists interaction summaries and their embedding vectors to
 //!   SQLite and supports cosine-similarity recall.
+//! - [`procedural`] – [`ProceduralStore`][procedural::ProceduralStore]: the
+//!   intent sequences that achieved past goals, recalled by goal and context
+//!   similarity so successful plans can be reused.
+//! - [`replicated_board`] – [`ReplicatedTaskBoard`][replicated_board::ReplicatedTaskBoard]:
+//!   a per-robot replica of the fleet task board that peers keep in sync by
+//!   exchanging state messages, resolving simultaneous claims in favour of
+//!   the earliest one.
 //! - [`semantic`] – [`SemanticStateEstimator`][semantic::SemanticStateEstimator]:
 //!   fuses past visual/conceptual embeddings with a time-decay probability model
 //!   to track the semantic state of the world over time (e.g. remembering where
 //!   an object was last placed).
+//! - [`transcript`] – [`TranscriptStore`][transcript::TranscriptStore]:
+//!   a rotating SQLite log of every LLM prompt/response pair, tagged with
+//!   mission ID, agent ID, timestamp and trace ID, fetchable per mission for
+//!   post-hoc audits.
 
+pub mod embedding;
+pub mod encryption;
 pub mod episodic;
+pub mod procedural;
+pub mod replicated_board;
 pub mod semantic;
 pub mod task_board;
+pub mod transcript;
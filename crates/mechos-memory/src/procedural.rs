@@ -0,0 +1,394 @@
+//! Procedural Memory Store.
+//!
+//! Persists the intent sequences that achieved a goal, keyed by the goal's
+//! description and a context embedding, so the runtime can tell the LLM
+//! "last time you did X, this sequence worked" instead of re-discovering the
+//! same plan on every mission.  This closes the loop between the episodic
+//! memory (what happened) and action selection (what to do).
+//!
+//! # Storage layout
+//!
+//! A single table `procedures` is created (if it does not already exist)
+//! with the following columns:
+//!
+//! | column       | type    | description                                   |
+//! |--------------|---------|-----------------------------------------------|
+//! | id           | TEXT    | UUID v4 primary key                           |
+//! | goal         | TEXT    | Description of the goal the sequence achieved |
+//! | context      | BLOB    | Little-endian f32 context embedding           |
+//! | steps        | TEXT    | JSON array of `HardwareIntent`s               |
+//! | successes    | INTEGER | How many times the sequence succeeded         |
+//! | created_at   | TEXT    | RFC-3339 time of the first success (UTC)      |
+//! | last_used_at | TEXT    | RFC-3339 time of the latest success (UTC)     |
+//!
+//! Recording the same sequence for the same goal again bumps `successes`
+//! instead of adding a duplicate, so proven procedures rank first.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mechos_memory::procedural::ProceduralStore;
+//! use mechos_types::HardwareIntent;
+//!
+//! #[tokio::main(flavor = "current_thread")]
+//! async fn main() {
+//!     let store = ProceduralStore::open_in_memory().unwrap();
+//!     let steps = vec![HardwareIntent::TriggerRelay { relay_id: "door".into(), state: true }];
+//!     store.record_text("open the east door", &steps).await.unwrap();
+//!
+//!     let recalled = store.recall_text("open the east door", 1).await.unwrap();
+//!     assert_eq!(recalled[0].0.goal, "open the east door");
+//! }
+//! ```
+
+use chrono::{DateTime, Utc};
+use mechos_types::HardwareIntent;
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::embedding::{EmbedError, Embedder, HashingEmbedder};
+use crate::episodic::{bytes_to_embedding, cosine_similarity, embedding_to_bytes};
+
+use std::sync::{Arc, Mutex};
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Error type
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Errors that can arise from procedural memory operations.
+#[derive(Error, Debug)]
+pub enum ProceduralError {
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Embedding failed: {0}")]
+    Embedding(#[from] EmbedError),
+    #[error("Context embeddings must be non-empty")]
+    DimensionMismatch,
+    #[error("A procedure needs at least one step")]
+    EmptySequence,
+    #[error("blocking task panicked: {0}")]
+    TaskPanic(String),
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Procedure
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// An intent sequence that achieved a goal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Procedure {
+    /// Unique identifier for this procedure.
+    pub id: Uuid,
+    /// Description of the goal the sequence achieved.
+    pub goal: String,
+    /// Embedding of the context the sequence succeeded in.
+    pub context: Vec<f32>,
+    /// The intents, in dispatch order.
+    pub steps: Vec<HardwareIntent>,
+    /// How many times this sequence achieved the goal.
+    pub successes: u32,
+    /// When the sequence first succeeded.
+    pub created_at: DateTime<Utc>,
+    /// When the sequence most recently succeeded.
+    pub last_used_at: DateTime<Utc>,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// ProceduralStore
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// SQLite-backed store of learned [`Procedure`]s.
+#[derive(Clone)]
+pub struct ProceduralStore {
+    conn: Arc<Mutex<Connection>>,
+    embedder: Arc<dyn Embedder>,
+}
+
+impl ProceduralStore {
+    /// Open (or create) a persistent SQLite database at `path`.
+    pub fn open(path: &str) -> Result<Self, ProceduralError> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch("PRAGMA journal_mode=WAL;")?;
+        Self::from_connection(conn)
+    }
+
+    /// Open a temporary in-memory database (useful for testing).
+    pub fn open_in_memory() -> Result<Self, ProceduralError> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    fn from_connection(conn: Connection) -> Result<Self, ProceduralError> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS procedures (
+                id           TEXT NOT NULL PRIMARY KEY,
+                goal         TEXT NOT NULL,
+                context      BLOB NOT NULL,
+                steps        TEXT NOT NULL,
+                successes    INTEGER NOT NULL DEFAULT 1,
+                created_at   TEXT NOT NULL,
+                last_used_at TEXT NOT NULL
+            );",
+        )?;
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+            embedder: Arc::new(HashingEmbedder::default()),
+        })
+    }
+
+    /// Use `embedder` to compute the context of
+    /// [`record_text`][Self::record_text] and
+    /// [`recall_text`][Self::recall_text] instead of the default
+    /// [`HashingEmbedder`].
+    pub fn with_embedder(mut self, embedder: impl Embedder + 'static) -> Self {
+        self.embedder = Arc::new(embedder);
+        self
+    }
+
+    /// Record that `steps` achieved `goal` in `context` and return the
+    /// stored procedure.
+    ///
+    /// If the same sequence already achieved the same goal its success count
+    /// is bumped and its context replaced by the latest one.
+    pub async fn record_success(
+        &self,
+        goal: &str,
+        context: &[f32],
+        steps: &[HardwareIntent],
+    ) -> Result<Procedure, ProceduralError> {
+        if context.is_empty() {
+            return Err(ProceduralError::DimensionMismatch);
+        }
+        if steps.is_empty() {
+            return Err(ProceduralError::EmptySequence);
+        }
+        let conn = Arc::clone(&self.conn);
+        let goal = goal.to_owned();
+        let blob = embedding_to_bytes(context);
+        let steps_json = serde_json::to_string(steps)?;
+        let now = Utc::now().to_rfc3339();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap_or_else(|e| e.into_inner());
+            let existing: Option<String> = conn
+                .query_row(
+                    "SELECT id FROM procedures WHERE goal = ?1 AND steps = ?2",
+                    params![goal, steps_json],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            let id = match existing {
+                Some(id) => {
+                    conn.execute(
+                        "UPDATE procedures
+                         SET successes = successes + 1, context = ?1, last_used_at = ?2
+                         WHERE id = ?3",
+                        params![blob, now, id],
+                    )?;
+                    id
+                }
+                None => {
+                    let id = Uuid::new_v4().to_string();
+                    conn.execute(
+                        "INSERT INTO procedures
+                             (id, goal, context, steps, successes, created_at, last_used_at)
+                         VALUES (?1, ?2, ?3, ?4, 1, ?5, ?5)",
+                        params![id, goal, blob, steps_json, now],
+                    )?;
+                    id
+                }
+            };
+            conn.query_row(
+                &format!("{SELECT_PROCEDURES} WHERE id = ?1"),
+                [id],
+                row_to_procedure,
+            )?
+        })
+        .await
+        .map_err(|e| ProceduralError::TaskPanic(e.to_string()))?
+    }
+
+    /// Like [`record_success`][Self::record_success], using the store's
+    /// [`Embedder`] on `goal` as the context.
+    pub async fn record_text(
+        &self,
+        goal: &str,
+        steps: &[HardwareIntent],
+    ) -> Result<Procedure, ProceduralError> {
+        let context = self.embedder.embed(goal).await?;
+        self.record_success(goal, &context, steps).await
+    }
+
+    /// Every stored procedure, most successful first.
+    pub async fn all(&self) -> Result<Vec<Procedure>, ProceduralError> {
+        let conn = Arc::clone(&self.conn);
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap_or_else(|e| e.into_inner());
+            let mut stmt = conn.prepare(&format!(
+                "{SELECT_PROCEDURES} ORDER BY successes DESC, last_used_at DESC"
+            ))?;
+            stmt.query_map([], row_to_procedure)?
+                .map(|row| row?)
+                .collect()
+        })
+        .await
+        .map_err(|e| ProceduralError::TaskPanic(e.to_string()))?
+    }
+
+    /// Return the `top_k` procedures whose context is most similar to
+    /// `context`, as `(procedure, similarity)` pairs ranked by similarity,
+    /// then by success count.
+    ///
+    /// Procedures whose context has a different dimension are skipped.
+    pub async fn recall(
+        &self,
+        context: &[f32],
+        top_k: usize,
+    ) -> Result<Vec<(Procedure, f32)>, ProceduralError> {
+        if context.is_empty() {
+            return Err(ProceduralError::DimensionMismatch);
+        }
+        let mut ranked: Vec<(Procedure, f32)> = self
+            .all()
+            .await?
+            .into_iter()
+            .filter(|p| p.context.len() == context.len())
+            .map(|p| {
+                let score = cosine_similarity(&p.context, context);
+                (p, score)
+            })
+            .collect();
+        ranked.sort_by(|a, b| {
+            b.1.total_cmp(&a.1)
+                .then_with(|| b.0.successes.cmp(&a.0.successes))
+        });
+        ranked.truncate(top_k);
+        Ok(ranked)
+    }
+
+    /// Like [`recall`][Self::recall], using the store's [`Embedder`] on
+    /// `goal` as the context.
+    pub async fn recall_text(
+        &self,
+        goal: &str,
+        top_k: usize,
+    ) -> Result<Vec<(Procedure, f32)>, ProceduralError> {
+        let context = self.embedder.embed(goal).await?;
+        self.recall(&context, top_k).await
+    }
+}
+
+const SELECT_PROCEDURES: &str =
+    "SELECT id, goal, context, steps, successes, created_at, last_used_at FROM procedures";
+
+fn row_to_procedure(
+    row: &rusqlite::Row<'_>,
+) -> rusqlite::Result<Result<Procedure, ProceduralError>> {
+    let id: String = row.get(0)?;
+    let goal: String = row.get(1)?;
+    let context: Vec<u8> = row.get(2)?;
+    let steps: String = row.get(3)?;
+    let successes: u32 = row.get(4)?;
+    let created_at: String = row.get(5)?;
+    let last_used_at: String = row.get(6)?;
+    let parse_time = |idx: usize, ts: &str| {
+        ts.parse::<DateTime<Utc>>().map_err(|e| {
+            rusqlite::Error::InvalidColumnType(idx, e.to_string(), rusqlite::types::Type::Text)
+        })
+    };
+    let id = Uuid::parse_str(&id).map_err(|e| {
+        rusqlite::Error::InvalidColumnType(0, e.to_string(), rusqlite::types::Type::Text)
+    })?;
+    let created_at = parse_time(5, &created_at)?;
+    let last_used_at = parse_time(6, &last_used_at)?;
+    Ok(serde_json::from_str(&steps)
+        .map(|steps| Procedure {
+            id,
+            goal,
+            context: bytes_to_embedding(&context),
+            steps,
+            successes,
+            created_at,
+            last_used_at,
+        })
+        .map_err(ProceduralError::from))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mechos_types::{MetersPerSecond, RadiansPerSecond};
+
+    fn drive(linear: f32) -> HardwareIntent {
+        HardwareIntent::Drive {
+            linear_velocity: MetersPerSecond(linear),
+            angular_velocity: RadiansPerSecond(0.0),
+        }
+    }
+
+    #[tokio::test]
+    async fn repeated_success_bumps_the_count_instead_of_duplicating() {
+        let store = ProceduralStore::open_in_memory().unwrap();
+        let steps = vec![drive(0.5), drive(0.0)];
+        let first = store
+            .record_text("dock at the charger", &steps)
+            .await
+            .unwrap();
+        assert_eq!(first.successes, 1);
+        let second = store
+            .record_text("dock at the charger", &steps)
+            .await
+            .unwrap();
+        assert_eq!((second.id, second.successes), (first.id, 2));
+
+        store
+            .record_text("dock at the charger", &[drive(0.2)])
+            .await
+            .unwrap();
+        let all = store.all().await.unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].steps.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn recall_ranks_by_context_similarity() {
+        let store = ProceduralStore::open_in_memory().unwrap();
+        store
+            .record_success("near", &[1.0, 0.0], &[drive(1.0)])
+            .await
+            .unwrap();
+        store
+            .record_success("far", &[0.0, 1.0], &[drive(2.0)])
+            .await
+            .unwrap();
+        store
+            .record_success("other dims", &[1.0, 0.0, 0.0], &[drive(3.0)])
+            .await
+            .unwrap();
+
+        let recalled = store.recall(&[0.9, 0.1], 5).await.unwrap();
+        assert_eq!(recalled.len(), 2);
+        assert_eq!(recalled[0].0.goal, "near");
+        assert!(recalled[0].1 > recalled[1].1);
+        assert_eq!(store.recall(&[0.9, 0.1], 1).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn empty_context_or_sequence_is_rejected() {
+        let store = ProceduralStore::open_in_memory().unwrap();
+        assert!(matches!(
+            store.record_success("goal", &[], &[drive(1.0)]).await,
+            Err(ProceduralError::DimensionMismatch)
+        ));
+        assert!(matches!(
+            store.record_text("goal", &[]).await,
+            Err(ProceduralError::EmptySequence)
+        ));
+    }
+}
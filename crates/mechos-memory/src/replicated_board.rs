@@ -0,0 +1,415 @@
+//! Replicated Fleet Task Board.
+//!
+//! [`TaskBoard`](crate::task_board::TaskBoard) coordinates a fleet only when
+//! every robot mounts the same SQLite file.  [`ReplicatedTaskBoard`] is the
+//! alternative for fleets without shared storage: every robot keeps its own
+//! replica and the replicas exchange their state over the network (e.g. as
+//! `BroadcastFleet` messages, merging received `PeerMessage`s).
+//!
+//! # Conflict resolution
+//!
+//! The replica is a state-based CRDT, so replicas that have seen the same
+//! messages agree on every task no matter in which order the messages arrived:
+//!
+//! * Tasks are only ever added, and a task's title and description never
+//!   change.
+//! * Claims and completions are grow-only sets of `(timestamp, robot_id)`
+//!   stamps, merged by union.
+//! * When two robots claim the same task before seeing each other's claim,
+//!   the **earliest** claim wins, with ties broken by robot ID.  The loser
+//!   learns about it from the [`ClaimConflict`] returned by
+//!   [`ReplicatedTaskBoard::merge_message`] and should move on to another
+//!   task.
+//! * A completion is final: once any claimant completed the task it stays
+//!   completed and is credited to the earliest completer, because the work is
+//!   done even if that robot lost the claim race.
+//!
+//! Claim timestamps come from each robot's wall clock, so keep fleet clocks
+//! synchronised (NTP) for the "earliest claim" rule to be fair; it stays
+//! deterministic regardless.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mechos_memory::replicated_board::ReplicatedTaskBoard;
+//!
+//! let alpha = ReplicatedTaskBoard::new();
+//! let bravo = ReplicatedTaskBoard::new();
+//!
+//! let id = alpha.post("Move Box 1", "Move the red box from shelf A to shelf B.");
+//! bravo.merge_message(&alpha.sync_message()).unwrap();
+//!
+//! // Both robots claim the task before hearing from each other.
+//! alpha.claim(&id, "robot_alpha").unwrap();
+//! bravo.claim(&id, "robot_bravo").unwrap();
+//!
+//! // After exchanging state both replicas agree on a single winner.
+//! let conflicts = bravo.merge_message(&alpha.sync_message()).unwrap();
+//! alpha.merge_message(&bravo.sync_message()).unwrap();
+//! assert_eq!(conflicts.len(), 1);
+//! assert_eq!(alpha.get(&id).unwrap().claimed_by, Some("robot_alpha".to_string()));
+//! assert_eq!(bravo.get(&id).unwrap().claimed_by, Some("robot_alpha".to_string()));
+//! ```
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Arc, Mutex};
+
+use crate::task_board::{TaskBoardError, TaskEntry, TaskStatus};
+
+/// Version of the sync message format, carried in its `mechos_tasks` field.
+const SYNC_FORMAT_VERSION: u32 = 1;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// ClaimConflict
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A claim that was overturned while merging a peer's replica.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClaimConflict {
+    /// The contested task.
+    pub task_id: String,
+    /// The robot that held the claim before the merge.
+    pub lost: String,
+    /// The robot that holds it now.
+    pub won: String,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Replica state
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// One claim or completion.  Orders by time first, then robot ID.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct Stamp {
+    at: DateTime<Utc>,
+    robot_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TaskRecord {
+    id: String,
+    title: String,
+    description: String,
+    created_at: DateTime<Utc>,
+    claims: BTreeSet<Stamp>,
+    completions: BTreeSet<Stamp>,
+}
+
+impl TaskRecord {
+    fn holder(&self) -> Option<&str> {
+        self.completions
+            .first()
+            .or_else(|| self.claims.first())
+            .map(|stamp| stamp.robot_id.as_str())
+    }
+
+    fn entry(&self) -> TaskEntry {
+        let status = if !self.completions.is_empty() {
+            TaskStatus::Completed
+        } else if !self.claims.is_empty() {
+            TaskStatus::Claimed
+        } else {
+            TaskStatus::Open
+        };
+        let updated_at = self
+            .claims
+            .iter()
+            .chain(&self.completions)
+            .map(|stamp| stamp.at)
+            .fold(self.created_at, DateTime::max);
+        TaskEntry {
+            id: self.id.clone(),
+            title: self.title.clone(),
+            description: self.description.clone(),
+            status,
+            claimed_by: self.holder().map(str::to_string),
+            created_at: self.created_at.to_rfc3339(),
+            updated_at: updated_at.to_rfc3339(),
+            depends_on: Vec::new(),
+            priority: 0,
+            due_at: None,
+            heartbeat_at: None,
+            lease_expires_at: None,
+            progress_percent: 0.0,
+            progress_note: None,
+            result: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SyncMessage {
+    mechos_tasks: u32,
+    tasks: Vec<TaskRecord>,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// ReplicatedTaskBoard
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// One robot's replica of a fleet task board synchronised between peers.
+///
+/// Offers the same operations as [`TaskBoard`](crate::task_board::TaskBoard)
+/// with the same errors, but they are synchronous because the replica lives
+/// in memory.  A restarted robot recovers the board from its peers' next
+/// sync message.  Clones share the same replica.
+#[derive(Debug, Clone, Default)]
+pub struct ReplicatedTaskBoard {
+    tasks: Arc<Mutex<BTreeMap<String, TaskRecord>>>,
+}
+
+impl ReplicatedTaskBoard {
+    /// Create an empty replica.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Post a new open task and return its UUID.
+    pub fn post(&self, title: &str, description: &str) -> String {
+        let id = Uuid::new_v4().to_string();
+        let record = TaskRecord {
+            id: id.clone(),
+            title: title.to_owned(),
+            description: description.to_owned(),
+            created_at: Utc::now(),
+            claims: BTreeSet::new(),
+            completions: BTreeSet::new(),
+        };
+        self.lock().insert(id.clone(), record);
+        id
+    }
+
+    /// Claim a task on behalf of `robot_id`.
+    ///
+    /// Fails with [`TaskBoardError::AlreadyClaimed`] or
+    /// [`TaskBoardError::AlreadyCompleted`] when this replica already knows
+    /// of a claim or completion.  A claim that succeeds here can still lose
+    /// to an earlier one when peers sync; see the [module docs](self).
+    pub fn claim(&self, task_id: &str, robot_id: &str) -> Result<(), TaskBoardError> {
+        let mut tasks = self.lock();
+        let record = tasks
+            .get_mut(task_id)
+            .ok_or_else(|| TaskBoardError::NotFound(task_id.to_string()))?;
+        if !record.completions.is_empty() {
+            return Err(TaskBoardError::AlreadyCompleted);
+        }
+        if !record.claims.is_empty() {
+            return Err(TaskBoardError::AlreadyClaimed);
+        }
+        record.claims.insert(Stamp {
+            at: Utc::now(),
+            robot_id: robot_id.to_owned(),
+        });
+        Ok(())
+    }
+
+    /// Mark a task as completed by `robot_id`, which must hold the claim in
+    /// this replica.
+    pub fn complete(&self, task_id: &str, robot_id: &str) -> Result<(), TaskBoardError> {
+        let mut tasks = self.lock();
+        let record = tasks
+            .get_mut(task_id)
+            .ok_or_else(|| TaskBoardError::NotFound(task_id.to_string()))?;
+        if !record.completions.is_empty() {
+            return Err(TaskBoardError::AlreadyCompleted);
+        }
+        if record.holder() != Some(robot_id) {
+            return Err(TaskBoardError::NotClaimed(robot_id.to_string()));
+        }
+        record.completions.insert(Stamp {
+            at: Utc::now(),
+            robot_id: robot_id.to_owned(),
+        });
+        Ok(())
+    }
+
+    /// Fetch a single task by its UUID.
+    pub fn get(&self, task_id: &str) -> Result<TaskEntry, TaskBoardError> {
+        self.lock()
+            .get(task_id)
+            .map(TaskRecord::entry)
+            .ok_or_else(|| TaskBoardError::NotFound(task_id.to_string()))
+    }
+
+    /// Return all open tasks, oldest first.
+    pub fn list_available(&self) -> Vec<TaskEntry> {
+        let mut entries = self.list_all();
+        entries.retain(|entry| entry.status == TaskStatus::Open);
+        entries
+    }
+
+    /// Return all tasks regardless of status, oldest first.
+    pub fn list_all(&self) -> Vec<TaskEntry> {
+        let tasks = self.lock();
+        let mut records: Vec<&TaskRecord> = tasks.values().collect();
+        records.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+        records.into_iter().map(TaskRecord::entry).collect()
+    }
+
+    /// Serialise the whole replica for peers to pass to
+    /// [`merge_message`](Self::merge_message).
+    pub fn sync_message(&self) -> String {
+        let message = SyncMessage {
+            mechos_tasks: SYNC_FORMAT_VERSION,
+            tasks: self.lock().values().cloned().collect(),
+        };
+        serde_json::to_string(&message).unwrap_or_default()
+    }
+
+    /// Merge a peer's [`sync_message`](Self::sync_message) into this
+    /// replica.
+    ///
+    /// Returns the claims the merge overturned, or `None` when `message` is
+    /// not a task board sync message (so any fleet message can be offered).
+    pub fn merge_message(&self, message: &str) -> Option<Vec<ClaimConflict>> {
+        let message: SyncMessage = serde_json::from_str(message).ok()?;
+        if message.mechos_tasks != SYNC_FORMAT_VERSION {
+            return None;
+        }
+        let mut tasks = self.lock();
+        let mut conflicts = Vec::new();
+        for incoming in message.tasks {
+            let Some(record) = tasks.get_mut(&incoming.id) else {
+                tasks.insert(incoming.id.clone(), incoming);
+                continue;
+            };
+            let before = record.holder().map(str::to_string);
+            record.claims.extend(incoming.claims);
+            record.completions.extend(incoming.completions);
+            if let (Some(lost), Some(won)) = (before, record.holder())
+                && lost != won
+            {
+                conflicts.push(ClaimConflict {
+                    task_id: record.id.clone(),
+                    lost,
+                    won: won.to_string(),
+                });
+            }
+        }
+        Some(conflicts)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, TaskRecord>> {
+        self.tasks.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sync(a: &ReplicatedTaskBoard, b: &ReplicatedTaskBoard) {
+        a.merge_message(&b.sync_message()).unwrap();
+        b.merge_message(&a.sync_message()).unwrap();
+    }
+
+    #[test]
+    fn single_replica_enforces_task_board_rules() {
+        let board = ReplicatedTaskBoard::new();
+        let id = board.post("Task A", "Do something.");
+        assert_eq!(board.list_available().len(), 1);
+        board.claim(&id, "robot_alpha").unwrap();
+        assert!(matches!(
+            board.claim(&id, "robot_bravo"),
+            Err(TaskBoardError::AlreadyClaimed)
+        ));
+        assert!(matches!(
+            board.complete(&id, "robot_bravo"),
+            Err(TaskBoardError::NotClaimed(_))
+        ));
+        board.complete(&id, "robot_alpha").unwrap();
+        assert!(matches!(
+            board.claim(&id, "robot_bravo"),
+            Err(TaskBoardError::AlreadyCompleted)
+        ));
+        assert_eq!(board.get(&id).unwrap().status, TaskStatus::Completed);
+        assert!(matches!(
+            board.get("nonexistent-id"),
+            Err(TaskBoardError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn simultaneous_claims_converge_on_the_earliest() {
+        let (alpha, bravo, charlie) = (
+            ReplicatedTaskBoard::new(),
+            ReplicatedTaskBoard::new(),
+            ReplicatedTaskBoard::new(),
+        );
+        let id = charlie.post("Deliver Package", "Take package to room 5.");
+        sync(&alpha, &charlie);
+        sync(&bravo, &charlie);
+
+        bravo.claim(&id, "robot_bravo").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        alpha.claim(&id, "robot_alpha").unwrap();
+
+        let conflicts = alpha.merge_message(&bravo.sync_message()).unwrap();
+        assert_eq!(
+            conflicts,
+            vec![ClaimConflict {
+                task_id: id.clone(),
+                lost: "robot_alpha".to_string(),
+                won: "robot_bravo".to_string(),
+            }]
+        );
+        // Merge order does not matter, and re-merging changes nothing.
+        charlie.merge_message(&alpha.sync_message()).unwrap();
+        charlie.merge_message(&bravo.sync_message()).unwrap();
+        assert!(
+            bravo
+                .merge_message(&alpha.sync_message())
+                .unwrap()
+                .is_empty()
+        );
+        for replica in [&alpha, &bravo, &charlie] {
+            let task = replica.get(&id).unwrap();
+            assert_eq!(task.status, TaskStatus::Claimed);
+            assert_eq!(task.claimed_by.as_deref(), Some("robot_bravo"));
+        }
+        assert!(matches!(
+            alpha.complete(&id, "robot_alpha"),
+            Err(TaskBoardError::NotClaimed(_))
+        ));
+    }
+
+    #[test]
+    fn completion_survives_a_lost_claim_race() {
+        let (alpha, bravo) = (ReplicatedTaskBoard::new(), ReplicatedTaskBoard::new());
+        let id = alpha.post("Scan Room", "Scan room 3.");
+        sync(&alpha, &bravo);
+
+        bravo.claim(&id, "robot_bravo").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        alpha.claim(&id, "robot_alpha").unwrap();
+        alpha.complete(&id, "robot_alpha").unwrap();
+        sync(&alpha, &bravo);
+
+        for replica in [&alpha, &bravo] {
+            let task = replica.get(&id).unwrap();
+            assert_eq!(task.status, TaskStatus::Completed);
+            assert_eq!(task.claimed_by.as_deref(), Some("robot_alpha"));
+        }
+    }
+
+    #[test]
+    fn unrelated_messages_are_ignored() {
+        let board = ReplicatedTaskBoard::new();
+        assert!(board.merge_message("meet at dock 3").is_none());
+        assert!(board.merge_message(r#"{"mechos_map":1}"#).is_none());
+        assert!(
+            board
+                .merge_message(r#"{"mechos_tasks":2,"tasks":[]}"#)
+                .is_none()
+        );
+        assert!(board.list_all().is_empty());
+    }
+}
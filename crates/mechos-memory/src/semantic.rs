@@ -37,6 +37,21 @@
 //!    average: `mean = (1 − obs_conf) * mean + obs_conf * new_embedding`
 //! 2. The entity's confidence is set to `min(1.0, current + obs_conf)`.
 //!
+//! ### Object locations
+//!
+//! Alongside the embedding belief, the estimator tracks *where* each object
+//! is.  [`SemanticStateEstimator::update_observation`] records that an object
+//! was just seen at a named location: the probability of that location moves
+//! [`LOCATION_OBSERVATION_WEIGHT`] of the way towards `1.0`, and every other
+//! location known for the object loses the same fraction.  Location
+//! probabilities decay with [`decay_all`][SemanticStateEstimator::decay_all]
+//! like entity confidence, and [`where_is`][SemanticStateEstimator::where_is]
+//! returns them most likely first.
+//!
+//! An estimator created with [`SemanticStateEstimator::open`] persists object
+//! locations to the SQLite table `object_locations`, so the robot remembers
+//! where things are across restarts.  Embedding beliefs stay in memory.
+//!
 //! # Example
 //!
 //! ```rust
@@ -54,10 +69,30 @@
 //! est.decay_all();
 //! let state = est.query("coffee_mug").unwrap();
 //! assert!(state.confidence < 0.85);
+//!
+//! // Remember where the mug was seen.
+//! est.update_observation("coffee_mug", "kitchen table");
+//! assert_eq!(est.where_is("coffee_mug")[0].location, "kitchen table");
 //! ```
 
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, params};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use thiserror::Error;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Error type
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Errors that can arise from persisting semantic state.
+#[derive(Error, Debug)]
+pub enum SemanticError {
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+    #[error("Corrupt object location row: {0}")]
+    Corrupt(String),
+}
 
 // ─────────────────────────────────────────────────────────────────────────────
 // SemanticState
@@ -89,6 +124,24 @@ impl SemanticState {
     }
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// ObjectLocation
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Fraction by which a single sighting moves an object's location belief.
+pub const LOCATION_OBSERVATION_WEIGHT: f32 = 0.8;
+
+/// The belief that an object is at one named location.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObjectLocation {
+    /// Named place (e.g. `"kitchen table"`).
+    pub location: String,
+    /// Probability in `[0.0, 1.0]` that the object is there now.
+    pub probability: f32,
+    /// When the object was last seen at this location.
+    pub last_seen: DateTime<Utc>,
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // SemanticStateEstimator
 // ─────────────────────────────────────────────────────────────────────────────
@@ -102,11 +155,18 @@ impl SemanticState {
 /// [`observe`][SemanticStateEstimator::observe], tick the decay clock with
 /// [`decay_all`][SemanticStateEstimator::decay_all], and read beliefs with
 /// [`query`][SemanticStateEstimator::query] or
-/// [`most_likely_state`][SemanticStateEstimator::most_likely_state].
+/// [`most_likely_state`][SemanticStateEstimator::most_likely_state].  Record
+/// and look up object locations with
+/// [`update_observation`][SemanticStateEstimator::update_observation] and
+/// [`where_is`][SemanticStateEstimator::where_is].
 pub struct SemanticStateEstimator {
     /// Per-tick exponential decay factor applied to every entity's confidence.
     decay_factor: f32,
     states: HashMap<String, SemanticState>,
+    /// Location beliefs per object, in no particular order.
+    locations: HashMap<String, Vec<ObjectLocation>>,
+    /// Backing database for `locations`, when opened with [`Self::open`].
+    db: Option<Connection>,
 }
 
 impl SemanticStateEstimator {
@@ -118,9 +178,126 @@ impl SemanticStateEstimator {
         Self {
             decay_factor: decay_factor.clamp(0.001, 0.9999),
             states: HashMap::new(),
+            locations: HashMap::new(),
+            db: None,
+        }
+    }
+
+    /// Create an estimator whose object locations are persisted to the
+    /// SQLite database at `path`, loading any locations stored there.
+    pub fn open(path: &str, decay_factor: f32) -> Result<Self, SemanticError> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS object_locations (
+                object      TEXT NOT NULL,
+                location    TEXT NOT NULL,
+                probability REAL NOT NULL,
+                last_seen   TEXT NOT NULL,
+                PRIMARY KEY (object, location)
+            );",
+        )?;
+        let mut locations: HashMap<String, Vec<ObjectLocation>> = HashMap::new();
+        {
+            let mut stmt = conn
+                .prepare("SELECT object, location, probability, last_seen FROM object_locations")?;
+            let rows = stmt.query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, f64>(2)?,
+                    row.get::<_, String>(3)?,
+                ))
+            })?;
+            for row in rows {
+                let (object, location, probability, last_seen) = row?;
+                let last_seen = last_seen
+                    .parse::<DateTime<Utc>>()
+                    .map_err(|e| SemanticError::Corrupt(e.to_string()))?;
+                locations.entry(object).or_default().push(ObjectLocation {
+                    location,
+                    probability: probability as f32,
+                    last_seen,
+                });
+            }
+        }
+        let mut estimator = Self::new(decay_factor);
+        estimator.locations = locations;
+        estimator.db = Some(conn);
+        Ok(estimator)
+    }
+
+    /// Record that `object` was just seen at `location`.
+    ///
+    /// Raises that location's probability by [`LOCATION_OBSERVATION_WEIGHT`]
+    /// of its distance to `1.0` and lowers every other location known for
+    /// `object` by the same fraction.
+    pub fn update_observation(&mut self, object: &str, location: &str) {
+        let now = Utc::now();
+        let beliefs = self.locations.entry(object.to_string()).or_default();
+        for belief in beliefs.iter_mut().filter(|b| b.location != location) {
+            belief.probability *= 1.0 - LOCATION_OBSERVATION_WEIGHT;
+        }
+        match beliefs.iter_mut().find(|b| b.location == location) {
+            Some(belief) => {
+                belief.probability += LOCATION_OBSERVATION_WEIGHT * (1.0 - belief.probability);
+                belief.last_seen = now;
+            }
+            None => beliefs.push(ObjectLocation {
+                location: location.to_string(),
+                probability: LOCATION_OBSERVATION_WEIGHT,
+                last_seen: now,
+            }),
+        }
+        if let Some(conn) = &self.db {
+            let result = beliefs.iter().try_for_each(|b| {
+                conn.execute(
+                    "INSERT OR REPLACE INTO object_locations
+                         (object, location, probability, last_seen)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![
+                        object,
+                        b.location,
+                        f64::from(b.probability),
+                        b.last_seen.to_rfc3339()
+                    ],
+                )
+                .map(|_| ())
+            });
+            if let Err(e) = result {
+                tracing::warn!(object, error = %e, "failed to persist object location");
+            }
         }
     }
 
+    /// Where `object` may be, most likely location first; empty when it has
+    /// never been seen (or every location was pruned).
+    pub fn where_is(&self, object: &str) -> Vec<ObjectLocation> {
+        let mut beliefs = self.locations.get(object).cloned().unwrap_or_default();
+        beliefs.sort_by(|a, b| b.probability.total_cmp(&a.probability));
+        beliefs
+    }
+
+    /// The most likely location of every object with a known location,
+    /// highest probability first.
+    pub fn most_likely_locations(&self) -> Vec<(&str, &ObjectLocation)> {
+        let mut best: Vec<(&str, &ObjectLocation)> = self
+            .locations
+            .iter()
+            .filter_map(|(object, beliefs)| {
+                beliefs
+                    .iter()
+                    .max_by(|a, b| a.probability.total_cmp(&b.probability))
+                    .map(|b| (object.as_str(), b))
+            })
+            .collect();
+        best.sort_by(|a, b| {
+            b.1.probability
+                .total_cmp(&a.1.probability)
+                .then(a.0.cmp(b.0))
+        });
+        best
+    }
+
     /// Incorporate a new observation of `label`.
     ///
     /// * `embedding` – the dense embedding vector for this observation.
@@ -163,14 +340,38 @@ impl SemanticStateEstimator {
         for state in self.states.values_mut() {
             state.confidence *= self.decay_factor;
         }
+        for belief in self.locations.values_mut().flatten() {
+            belief.probability *= self.decay_factor;
+        }
+        if let Some(conn) = &self.db
+            && let Err(e) = conn.execute(
+                "UPDATE object_locations SET probability = probability * ?1",
+                params![f64::from(self.decay_factor)],
+            )
+        {
+            tracing::warn!(error = %e, "failed to persist object location decay");
+        }
     }
 
-    /// Remove all entities whose confidence is below `threshold`.
+    /// Remove all entities whose confidence is below `threshold`, and all
+    /// object locations whose probability is below it.
     ///
     /// Returns the number of entities pruned.
     pub fn prune(&mut self, threshold: f32) -> usize {
         let before = self.states.len();
         self.states.retain(|_, s| s.confidence >= threshold);
+        for beliefs in self.locations.values_mut() {
+            beliefs.retain(|b| b.probability >= threshold);
+        }
+        self.locations.retain(|_, beliefs| !beliefs.is_empty());
+        if let Some(conn) = &self.db
+            && let Err(e) = conn.execute(
+                "DELETE FROM object_locations WHERE probability < ?1",
+                params![f64::from(threshold)],
+            )
+        {
+            tracing::warn!(error = %e, "failed to persist object location pruning");
+        }
         before - self.states.len()
     }
 
@@ -369,4 +570,62 @@ mod tests {
         let est_lo = SemanticStateEstimator::new(0.0);
         assert!((est_lo.decay_factor - 0.001).abs() < 1e-4);
     }
+
+    // ── object locations ─────────────────────────────────────────────────────
+
+    #[test]
+    fn where_is_ranks_latest_sighting_first() {
+        let mut est = SemanticStateEstimator::new(0.9);
+        assert!(est.where_is("red box").is_empty());
+        est.update_observation("red box", "garage");
+        est.update_observation("red box", "kitchen");
+
+        let beliefs = est.where_is("red box");
+        assert_eq!(beliefs.len(), 2);
+        assert_eq!(beliefs[0].location, "kitchen");
+        assert!((beliefs[0].probability - 0.8).abs() < 1e-6);
+        assert!((beliefs[1].probability - 0.16).abs() < 1e-6);
+        assert!(beliefs[0].last_seen >= beliefs[1].last_seen);
+
+        // Seeing it in the kitchen again strengthens that belief.
+        est.update_observation("red box", "kitchen");
+        assert!((est.where_is("red box")[0].probability - 0.96).abs() < 1e-6);
+    }
+
+    #[test]
+    fn location_beliefs_decay_and_prune() {
+        let mut est = SemanticStateEstimator::new(0.5);
+        est.update_observation("mug", "desk");
+        est.update_observation("keys", "hallway");
+        est.update_observation("keys", "hallway");
+        est.decay_all();
+        assert!((est.where_is("mug")[0].probability - 0.4).abs() < 1e-6);
+
+        let best = est.most_likely_locations();
+        assert_eq!(best[0].0, "keys");
+        assert_eq!(best[1].1.location, "desk");
+
+        est.prune(0.45);
+        assert!(est.where_is("mug").is_empty());
+        assert_eq!(est.most_likely_locations().len(), 1);
+    }
+
+    #[test]
+    fn object_locations_persist_across_reopen() {
+        let path =
+            std::env::temp_dir().join(format!("mechos-semantic-{}.db", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap();
+        {
+            let mut est = SemanticStateEstimator::open(path, 0.5).unwrap();
+            est.update_observation("red box", "garage");
+            est.update_observation("red box", "kitchen");
+            est.decay_all();
+        }
+        let est = SemanticStateEstimator::open(path, 0.5).unwrap();
+        let beliefs = est.where_is("red box");
+        assert_eq!(beliefs.len(), 2);
+        assert_eq!(beliefs[0].location, "kitchen");
+        assert!((beliefs[0].probability - 0.4).abs() < 1e-6);
+        let _ = std::fs::remove_file(path);
+    }
 }
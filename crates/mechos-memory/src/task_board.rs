@@ -9,15 +9,56 @@
 //! A single SQLite table `fleet_tasks` is created (if it does not already
 //! exist) with the following columns:
 //!
-//! | column       | type | description                                         |
-//! |--------------|------|-----------------------------------------------------|
-//! | id           | TEXT | UUID v4 primary key                                 |
-//! | title        | TEXT | Short human-readable task name                      |
-//! | description  | TEXT | Full task description                               |
-//! | status       | TEXT | One of `"open"`, `"claimed"`, `"completed"`         |
-//! | claimed_by   | TEXT | Robot ID that holds the claim (NULL when unclaimed) |
-//! | created_at   | TEXT | RFC-3339 creation timestamp (UTC)                   |
-//! | updated_at   | TEXT | RFC-3339 last-update timestamp (UTC)                |
+//! | column           | type | description                                         |
+//! |------------------|------|-----------------------------------------------------|
+//! | id               | TEXT | UUID v4 primary key                                 |
+//! | title            | TEXT | Short human-readable task name                      |
+//! | description      | TEXT | Full task description                               |
+//! | status           | TEXT | One of `"open"`, `"claimed"`, `"completed"`         |
+//! | claimed_by       | TEXT | Robot ID that holds the claim (NULL when unclaimed) |
+//! | created_at       | TEXT | RFC-3339 creation timestamp (UTC)                   |
+//! | updated_at       | TEXT | RFC-3339 last-update timestamp (UTC)                |
+//! | depends_on       | TEXT | JSON array of prerequisite task IDs                 |
+//! | priority         | INT  | Higher values are handed out first                  |
+//! | due_at           | TEXT | Optional RFC-3339 deadline (UTC)                    |
+//! | heartbeat_at     | TEXT | Claimer's last renewal (NULL when unclaimed)        |
+//! | lease_expires_at | TEXT | When the claim lapses unless renewed                |
+//! | progress         | REAL | Claimer's completion estimate, `0`–`100`            |
+//! | progress_note    | TEXT | Claimer's latest status note                        |
+//! | result           | TEXT | JSON result reported on completion                  |
+//!
+//! # Dependencies
+//!
+//! A task posted with [`TaskBoard::post_with_dependencies`] cannot be claimed
+//! until every prerequisite is completed, so multi-step missions ("clear
+//! shelf A" before "restock shelf A") run in order.
+//! [`TaskBoard::list_ready`] returns the open tasks that can be claimed now.
+//!
+//! # Priorities, deadlines and leases
+//!
+//! Tasks posted with [`TaskBoard::post_task`] may carry a priority and a
+//! deadline; [`TaskBoard::list_available`] and [`TaskBoard::list_ready`]
+//! return the highest priority first, then the earliest deadline, then the
+//! oldest task.
+//!
+//! A claim is a lease of [`TaskBoard::claim_lease`] (default
+//! [`DEFAULT_CLAIM_LEASE_SECS`]): the claimer calls [`TaskBoard::renew`]
+//! periodically to push `lease_expires_at` forward, and
+//! [`TaskBoard::reassign_stale`] (or the background task started by
+//! [`TaskBoard::spawn_lease_sweeper`]) re-opens tasks whose lease has lapsed,
+//! so a crashed robot does not hold its claim forever.
+//! [`TaskBoard::expire_stale_claims`] applies an explicit lease to the time
+//! of the last renewal instead.
+//!
+//! # Progress and results
+//!
+//! The claimer reports how far along it is with
+//! [`TaskBoard::report_progress`] and attaches a JSON result with
+//! [`TaskBoard::complete_with_result`].  A board given an [`EventBus`] with
+//! [`TaskBoard::with_bus`] also publishes both as
+//! [`TaskProgress`][mechos_middleware::typed::TaskProgress] and
+//! [`TaskCompleted`][mechos_middleware::typed::TaskCompleted] events on
+//! `Topic::SwarmComm`, so operators and peer robots can follow along.
 //!
 //! # Example
 //!
@@ -42,13 +83,34 @@
 //! }
 //! ```
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use mechos_middleware::EventBus;
+use mechos_middleware::typed::{TaskCompleted, TaskProgress};
 use rusqlite::{Connection, params};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::encryption::{DatabaseKey, EncryptionError, apply_key};
+
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Columns of `fleet_tasks` in the order [`row_to_entry`] reads them.
+const SELECT_TASKS: &str = "SELECT id, title, description, status, claimed_by, created_at,
+        updated_at, depends_on, priority, due_at, heartbeat_at, lease_expires_at, progress,
+        progress_note, result
+    FROM fleet_tasks";
+
+/// Source of the events a [`TaskBoard`] publishes.
+const EVENT_SOURCE: &str = "mechos-memory::task_board";
+
+/// Default time a claim stays valid without being renewed (seconds).
+pub const DEFAULT_CLAIM_LEASE_SECS: u64 = 30;
+
+/// Order in which open tasks are handed out: highest priority, then earliest
+/// deadline (tasks without one last), then oldest.
+const TASK_ORDER: &str = "ORDER BY priority DESC, due_at IS NULL, due_at ASC, created_at ASC";
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Error type
@@ -67,6 +129,12 @@ pub enum TaskBoardError {
     NotClaimed(String),
     #[error("Task is already completed")]
     AlreadyCompleted,
+    #[error("Task is waiting on unfinished dependencies: {0:?}")]
+    DependenciesPending(Vec<String>),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Encryption error: {0}")]
+    Encryption(#[from] EncryptionError),
     #[error("blocking task panicked: {0}")]
     TaskPanic(String),
 }
@@ -127,6 +195,79 @@ pub struct TaskEntry {
     pub created_at: String,
     /// RFC-3339 timestamp when the task was last updated.
     pub updated_at: String,
+    /// IDs of the tasks that must be completed before this one can be
+    /// claimed.
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// Scheduling priority; higher values are handed out first.
+    #[serde(default)]
+    pub priority: i32,
+    /// Optional RFC-3339 deadline.
+    #[serde(default)]
+    pub due_at: Option<String>,
+    /// RFC-3339 timestamp of the claimer's last renewal, if claimed.
+    #[serde(default)]
+    pub heartbeat_at: Option<String>,
+    /// RFC-3339 time at which the claim lapses unless renewed, if claimed.
+    #[serde(default)]
+    pub lease_expires_at: Option<String>,
+    /// Claimer's completion estimate, `0.0`–`100.0`.
+    #[serde(default)]
+    pub progress_percent: f32,
+    /// Claimer's latest status note, if any.
+    #[serde(default)]
+    pub progress_note: Option<String>,
+    /// Result reported on completion, if any.
+    #[serde(default)]
+    pub result: Option<serde_json::Value>,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// TaskSpec
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Everything needed to post a task with [`TaskBoard::post_task`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskSpec {
+    /// Short human-readable task name.
+    pub title: String,
+    /// Full description of what needs to be done.
+    pub description: String,
+    /// IDs of the tasks that must be completed first.
+    pub depends_on: Vec<String>,
+    /// Scheduling priority; higher values are handed out first.
+    pub priority: i32,
+    /// Optional deadline.
+    pub due_at: Option<DateTime<Utc>>,
+}
+
+impl TaskSpec {
+    /// A task with default priority `0`, no deadline and no dependencies.
+    pub fn new(title: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            description: description.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Require `depends_on` to be completed before the task can be claimed.
+    pub fn with_dependencies(mut self, depends_on: Vec<String>) -> Self {
+        self.depends_on = depends_on;
+        self
+    }
+
+    /// Set the scheduling priority.
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Set the deadline.
+    pub fn with_due_at(mut self, due_at: DateTime<Utc>) -> Self {
+        self.due_at = Some(due_at);
+        self
+    }
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -137,10 +278,15 @@ pub struct TaskEntry {
 ///
 /// Multiple robots in a fleet can share a single [`TaskBoard`] (backed by the
 /// same SQLite file on a shared filesystem, or by an in-process in-memory
-/// database for testing) to claim tasks without duplication.
+/// database for testing) to claim tasks without duplication.  Fleets without
+/// shared storage use
+/// [`ReplicatedTaskBoard`](crate::replicated_board::ReplicatedTaskBoard)
+/// instead.
 #[derive(Clone)]
 pub struct TaskBoard {
     conn: Arc<Mutex<Connection>>,
+    claim_lease: Duration,
+    bus: Option<EventBus>,
 }
 
 impl TaskBoard {
@@ -151,19 +297,64 @@ impl TaskBoard {
     pub fn open(path: &str) -> Result<Self, TaskBoardError> {
         let conn = Connection::open(path)?;
         conn.execute_batch("PRAGMA journal_mode=WAL;")?;
-        let board = Self { conn: Arc::new(Mutex::new(conn)) };
-        board.init_schema()?;
-        Ok(board)
+        Self::from_connection(conn)
+    }
+
+    /// Like [`open`][Self::open], but encrypts the database file with `key`
+    /// (see [`crate::encryption`]).
+    ///
+    /// Returns [`TaskBoardError::Encryption`] if `key` does not unlock an
+    /// existing file or SQLCipher support is not compiled in.
+    pub fn open_encrypted(path: &str, key: &DatabaseKey) -> Result<Self, TaskBoardError> {
+        let conn = Connection::open(path)?;
+        apply_key(&conn, key)?;
+        conn.execute_batch("PRAGMA journal_mode=WAL;")?;
+        Self::from_connection(conn)
     }
 
     /// Open a temporary in-memory task board (useful for testing).
     pub fn open_in_memory() -> Result<Self, TaskBoardError> {
-        let conn = Connection::open_in_memory()?;
-        let board = Self { conn: Arc::new(Mutex::new(conn)) };
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    fn from_connection(conn: Connection) -> Result<Self, TaskBoardError> {
+        let board = Self {
+            conn: Arc::new(Mutex::new(conn)),
+            claim_lease: Duration::from_secs(DEFAULT_CLAIM_LEASE_SECS),
+            bus: None,
+        };
         board.init_schema()?;
         Ok(board)
     }
 
+    /// Grant claims made or renewed through this handle a lease of
+    /// `claim_lease`.
+    pub fn with_claim_lease(mut self, claim_lease: Duration) -> Self {
+        self.claim_lease = claim_lease;
+        self
+    }
+
+    /// Publish progress reports and completions made through this handle on
+    /// `bus`.
+    pub fn with_bus(mut self, bus: EventBus) -> Self {
+        self.bus = Some(bus);
+        self
+    }
+
+    /// How long a claim made or renewed through this handle stays valid.
+    pub fn claim_lease(&self) -> Duration {
+        self.claim_lease
+    }
+
+    /// RFC-3339 time at which a lease granted now lapses.
+    fn lease_deadline(&self) -> String {
+        let lease = chrono::Duration::from_std(self.claim_lease).unwrap_or(chrono::Duration::MAX);
+        Utc::now()
+            .checked_add_signed(lease)
+            .unwrap_or(DateTime::<Utc>::MAX_UTC)
+            .to_rfc3339()
+    }
+
     fn init_schema(&self) -> Result<(), TaskBoardError> {
         let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
         conn.execute_batch(
@@ -174,9 +365,37 @@ impl TaskBoard {
                 status      TEXT NOT NULL DEFAULT 'open',
                 claimed_by  TEXT,
                 created_at  TEXT NOT NULL,
-                updated_at  TEXT NOT NULL
+                updated_at   TEXT NOT NULL,
+                depends_on   TEXT NOT NULL DEFAULT '[]',
+                priority     INTEGER NOT NULL DEFAULT 0,
+                due_at       TEXT,
+                heartbeat_at TEXT,
+                lease_expires_at TEXT,
+                progress     REAL NOT NULL DEFAULT 0,
+                progress_note TEXT,
+                result       TEXT
             );",
         )?;
+        // Migrate boards created before these columns existed.
+        for (column, definition) in [
+            ("depends_on", "TEXT NOT NULL DEFAULT '[]'"),
+            ("priority", "INTEGER NOT NULL DEFAULT 0"),
+            ("due_at", "TEXT"),
+            ("heartbeat_at", "TEXT"),
+            ("lease_expires_at", "TEXT"),
+            ("progress", "REAL NOT NULL DEFAULT 0"),
+            ("progress_note", "TEXT"),
+            ("result", "TEXT"),
+        ] {
+            let exists = conn
+                .prepare("SELECT 1 FROM pragma_table_info('fleet_tasks') WHERE name = ?1")?
+                .exists([column])?;
+            if !exists {
+                conn.execute_batch(&format!(
+                    "ALTER TABLE fleet_tasks ADD COLUMN {column} {definition};"
+                ))?;
+            }
+        }
         Ok(())
     }
 
@@ -185,18 +404,56 @@ impl TaskBoard {
     /// The task starts with [`TaskStatus::Open`] and is immediately available
     /// for any robot to claim.
     pub async fn post(&self, title: &str, description: &str) -> Result<String, TaskBoardError> {
+        self.post_with_dependencies(title, description, &[]).await
+    }
+
+    /// Post a new task that cannot be claimed until every task in
+    /// `depends_on` is completed, and return its UUID.
+    ///
+    /// Returns [`TaskBoardError::NotFound`] if a dependency does not exist.
+    /// Dependencies must be posted first, so cycles cannot form.
+    pub async fn post_with_dependencies(
+        &self,
+        title: &str,
+        description: &str,
+        depends_on: &[String],
+    ) -> Result<String, TaskBoardError> {
+        self.post_task(&TaskSpec::new(title, description).with_dependencies(depends_on.to_vec()))
+            .await
+    }
+
+    /// Post the task described by `spec` and return its UUID.
+    ///
+    /// Returns [`TaskBoardError::NotFound`] if a dependency does not exist.
+    pub async fn post_task(&self, spec: &TaskSpec) -> Result<String, TaskBoardError> {
         let conn = Arc::clone(&self.conn);
-        let title = title.to_owned();
-        let description = description.to_owned();
+        let spec = spec.clone();
         tokio::task::spawn_blocking(move || {
             let conn = conn.lock().unwrap_or_else(|e| e.into_inner());
+            for dependency in &spec.depends_on {
+                get_entry(&conn, dependency)?;
+            }
             let id = Uuid::new_v4().to_string();
             let now = Utc::now().to_rfc3339();
             let status = TaskStatus::Open.as_str();
+            let depends_on = serde_json::to_string(&spec.depends_on)?;
+            let due_at = spec.due_at.map(|t| t.to_rfc3339());
             conn.execute(
-                "INSERT INTO fleet_tasks (id, title, description, status, claimed_by, created_at, updated_at)
-                 VALUES (?1, ?2, ?3, ?4, NULL, ?5, ?6)",
-                params![id, title, description, status, now, now],
+                "INSERT INTO fleet_tasks
+                     (id, title, description, status, claimed_by, created_at, updated_at,
+                      depends_on, priority, due_at)
+                 VALUES (?1, ?2, ?3, ?4, NULL, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    id,
+                    spec.title,
+                    spec.description,
+                    status,
+                    now,
+                    now,
+                    depends_on,
+                    spec.priority,
+                    due_at
+                ],
             )?;
             Ok(id)
         })
@@ -207,10 +464,12 @@ impl TaskBoard {
     /// Claim a task on behalf of `robot_id`.
     ///
     /// Returns [`TaskBoardError::AlreadyClaimed`] if another robot already
-    /// holds the task, and [`TaskBoardError::AlreadyCompleted`] if the task
-    /// has already been finished.
+    /// holds the task, [`TaskBoardError::AlreadyCompleted`] if the task
+    /// has already been finished, and [`TaskBoardError::DependenciesPending`]
+    /// (listing the unfinished ones) if a dependency is not completed yet.
     pub async fn claim(&self, task_id: &str, robot_id: &str) -> Result<(), TaskBoardError> {
         let conn = Arc::clone(&self.conn);
+        let lease_expires_at = self.lease_deadline();
         let task_id = task_id.to_owned();
         let robot_id = robot_id.to_owned();
         tokio::task::spawn_blocking(move || {
@@ -221,12 +480,22 @@ impl TaskBoard {
                 TaskStatus::Completed => return Err(TaskBoardError::AlreadyCompleted),
                 TaskStatus::Open => {}
             }
+            let mut pending = Vec::new();
+            for dependency in &entry.depends_on {
+                if get_entry(&conn, dependency)?.status != TaskStatus::Completed {
+                    pending.push(dependency.clone());
+                }
+            }
+            if !pending.is_empty() {
+                return Err(TaskBoardError::DependenciesPending(pending));
+            }
             let now = Utc::now().to_rfc3339();
             let status = TaskStatus::Claimed.as_str();
             conn.execute(
-                "UPDATE fleet_tasks SET status = ?1, claimed_by = ?2, updated_at = ?3
-                 WHERE id = ?4",
-                params![status, robot_id, now, task_id],
+                "UPDATE fleet_tasks SET status = ?1, claimed_by = ?2, updated_at = ?3,
+                     heartbeat_at = ?3, lease_expires_at = ?4
+                 WHERE id = ?5",
+                params![status, robot_id, now, lease_expires_at, task_id],
             )?;
             Ok(())
         })
@@ -239,7 +508,124 @@ impl TaskBoard {
     /// Returns [`TaskBoardError::NotClaimed`] if `robot_id` does not hold the
     /// claim, preventing a robot from completing another robot's task.
     pub async fn complete(&self, task_id: &str, robot_id: &str) -> Result<(), TaskBoardError> {
+        self.complete_with_result(task_id, robot_id, serde_json::Value::Null)
+            .await
+    }
+
+    /// Mark a task as completed by `robot_id`, storing `result` (`null` for
+    /// none) and setting its progress to 100 %.
+    ///
+    /// Fails like [`complete`][Self::complete].
+    pub async fn complete_with_result(
+        &self,
+        task_id: &str,
+        robot_id: &str,
+        result: serde_json::Value,
+    ) -> Result<(), TaskBoardError> {
+        let conn = Arc::clone(&self.conn);
+        let task_id = task_id.to_owned();
+        let robot_id = robot_id.to_owned();
+        let stored = (!result.is_null())
+            .then(|| serde_json::to_string(&result))
+            .transpose()?;
+        let (id, robot) = (task_id.clone(), robot_id.clone());
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap_or_else(|e| e.into_inner());
+            let entry = get_entry(&conn, &id)?;
+            if entry.status == TaskStatus::Completed {
+                return Err(TaskBoardError::AlreadyCompleted);
+            }
+            if entry.claimed_by.as_deref() != Some(&robot) {
+                return Err(TaskBoardError::NotClaimed(robot));
+            }
+            let now = Utc::now().to_rfc3339();
+            let status = TaskStatus::Completed.as_str();
+            conn.execute(
+                "UPDATE fleet_tasks SET status = ?1, updated_at = ?2, progress = 100, result = ?3
+                 WHERE id = ?4",
+                params![status, now, stored, id],
+            )?;
+            Ok(())
+        })
+        .await
+        .map_err(|e| TaskBoardError::TaskPanic(e.to_string()))??;
+        if let Some(bus) = &self.bus {
+            let _ = bus.publish_typed(
+                EVENT_SOURCE,
+                TaskCompleted {
+                    task_id,
+                    robot_id,
+                    result,
+                },
+            );
+        }
+        Ok(())
+    }
+
+    /// Record that `robot_id` is `percent` (clamped to `0`–`100`) done with
+    /// the task it holds, with a free-form `note`.  Also renews the lease,
+    /// like [`renew`][Self::renew].
+    ///
+    /// Fails like [`renew`][Self::renew].
+    pub async fn report_progress(
+        &self,
+        task_id: &str,
+        robot_id: &str,
+        percent: f32,
+        note: &str,
+    ) -> Result<(), TaskBoardError> {
+        let conn = Arc::clone(&self.conn);
+        let lease_expires_at = self.lease_deadline();
+        let percent = percent.clamp(0.0, 100.0);
+        let (id, robot, text) = (task_id.to_owned(), robot_id.to_owned(), note.to_owned());
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap_or_else(|e| e.into_inner());
+            let entry = get_entry(&conn, &id)?;
+            if entry.status == TaskStatus::Completed {
+                return Err(TaskBoardError::AlreadyCompleted);
+            }
+            if entry.claimed_by.as_deref() != Some(&robot) {
+                return Err(TaskBoardError::NotClaimed(robot));
+            }
+            conn.execute(
+                "UPDATE fleet_tasks
+                 SET progress = ?1, progress_note = ?2, heartbeat_at = ?3, lease_expires_at = ?4
+                 WHERE id = ?5",
+                params![
+                    f64::from(percent),
+                    text,
+                    Utc::now().to_rfc3339(),
+                    lease_expires_at,
+                    id
+                ],
+            )?;
+            Ok(())
+        })
+        .await
+        .map_err(|e| TaskBoardError::TaskPanic(e.to_string()))??;
+        if let Some(bus) = &self.bus {
+            let _ = bus.publish_typed(
+                EVENT_SOURCE,
+                TaskProgress {
+                    task_id: task_id.to_owned(),
+                    robot_id: robot_id.to_owned(),
+                    percent,
+                    note: note.to_owned(),
+                },
+            );
+        }
+        Ok(())
+    }
+
+    /// Renew `robot_id`'s lease on a claimed task for another
+    /// [`claim_lease`][Self::claim_lease].
+    ///
+    /// Returns [`TaskBoardError::AlreadyCompleted`] if the task is finished
+    /// and [`TaskBoardError::NotClaimed`] if `robot_id` does not hold the
+    /// claim, e.g. because its lease already expired.
+    pub async fn renew(&self, task_id: &str, robot_id: &str) -> Result<(), TaskBoardError> {
         let conn = Arc::clone(&self.conn);
+        let lease_expires_at = self.lease_deadline();
         let task_id = task_id.to_owned();
         let robot_id = robot_id.to_owned();
         tokio::task::spawn_blocking(move || {
@@ -251,11 +637,9 @@ impl TaskBoard {
             if entry.claimed_by.as_deref() != Some(&robot_id) {
                 return Err(TaskBoardError::NotClaimed(robot_id));
             }
-            let now = Utc::now().to_rfc3339();
-            let status = TaskStatus::Completed.as_str();
             conn.execute(
-                "UPDATE fleet_tasks SET status = ?1, updated_at = ?2 WHERE id = ?3",
-                params![status, now, task_id],
+                "UPDATE fleet_tasks SET heartbeat_at = ?1, lease_expires_at = ?2 WHERE id = ?3",
+                params![Utc::now().to_rfc3339(), lease_expires_at, task_id],
             )?;
             Ok(())
         })
@@ -263,6 +647,84 @@ impl TaskBoard {
         .map_err(|e| TaskBoardError::TaskPanic(e.to_string()))?
     }
 
+    /// Re-open every claimed task whose claimer has not renewed it within
+    /// `lease`, regardless of its recorded lease expiry, and return their
+    /// IDs.
+    pub async fn expire_stale_claims(
+        &self,
+        lease: Duration,
+    ) -> Result<Vec<String>, TaskBoardError> {
+        let conn = Arc::clone(&self.conn);
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap_or_else(|e| e.into_inner());
+            let lease = chrono::Duration::from_std(lease).unwrap_or(chrono::Duration::MAX);
+            let Some(cutoff) = Utc::now().checked_sub_signed(lease) else {
+                return Ok(Vec::new());
+            };
+            let cutoff = cutoff.to_rfc3339();
+            let claimed = TaskStatus::Claimed.as_str();
+            // Claims made before heartbeats existed fall back to updated_at.
+            let mut stmt = conn.prepare(
+                "SELECT id FROM fleet_tasks
+                 WHERE status = ?1 AND COALESCE(heartbeat_at, updated_at) < ?2",
+            )?;
+            let expired = stmt
+                .query_map(params![claimed, cutoff], |row| row.get::<_, String>(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+            reopen(&conn, &expired)?;
+            Ok(expired)
+        })
+        .await
+        .map_err(|e| TaskBoardError::TaskPanic(e.to_string()))?
+    }
+
+    /// Re-open every claimed task whose lease has lapsed without renewal and
+    /// return their IDs.
+    ///
+    /// Claims made before leases existed have no expiry and are left alone;
+    /// use [`expire_stale_claims`][Self::expire_stale_claims] for those.
+    pub async fn reassign_stale(&self) -> Result<Vec<String>, TaskBoardError> {
+        let conn = Arc::clone(&self.conn);
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap_or_else(|e| e.into_inner());
+            let mut stmt = conn.prepare(
+                "SELECT id FROM fleet_tasks WHERE status = ?1 AND lease_expires_at < ?2",
+            )?;
+            let expired = stmt
+                .query_map(
+                    params![TaskStatus::Claimed.as_str(), Utc::now().to_rfc3339()],
+                    |row| row.get::<_, String>(0),
+                )?
+                .collect::<Result<Vec<_>, _>>()?;
+            reopen(&conn, &expired)?;
+            Ok(expired)
+        })
+        .await
+        .map_err(|e| TaskBoardError::TaskPanic(e.to_string()))?
+    }
+
+    /// Spawn a background task that calls
+    /// [`reassign_stale`][Self::reassign_stale] every `interval`.
+    ///
+    /// Must be called from within a Tokio runtime.  Abort the returned
+    /// handle to stop sweeping.
+    pub fn spawn_lease_sweeper(&self, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let board = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                match board.reassign_stale().await {
+                    Ok(expired) if !expired.is_empty() => {
+                        tracing::warn!(tasks = ?expired, "re-opened tasks with expired claims");
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!(error = %e, "task lease sweep failed"),
+                }
+            }
+        })
+    }
+
     /// Fetch a single task by its UUID.
     pub async fn get(&self, task_id: &str) -> Result<TaskEntry, TaskBoardError> {
         let conn = Arc::clone(&self.conn);
@@ -275,23 +737,48 @@ impl TaskBoard {
         .map_err(|e| TaskBoardError::TaskPanic(e.to_string()))?
     }
 
-    /// Return all tasks with [`TaskStatus::Open`], ordered by creation time
-    /// (oldest first).
+    /// Return all tasks with [`TaskStatus::Open`], highest priority first,
+    /// then earliest deadline, then oldest.
     pub async fn list_available(&self) -> Result<Vec<TaskEntry>, TaskBoardError> {
         self.list_by_status(TaskStatus::Open.as_str()).await
     }
 
+    /// Return the open tasks whose dependencies are all completed, i.e. the
+    /// ones a robot can claim right now, in the same order as
+    /// [`list_available`][Self::list_available].
+    pub async fn list_ready(&self) -> Result<Vec<TaskEntry>, TaskBoardError> {
+        let conn = Arc::clone(&self.conn);
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap_or_else(|e| e.into_inner());
+            let mut stmt = conn.prepare(&format!(
+                "{SELECT_TASKS} AS t
+                 WHERE status = ?1 AND NOT EXISTS (
+                     SELECT 1 FROM json_each(t.depends_on) AS d
+                     LEFT JOIN fleet_tasks AS p ON p.id = d.value
+                     WHERE p.status IS NOT ?2
+                 )
+                 {TASK_ORDER}"
+            ))?;
+            let rows = stmt.query_map(
+                params![TaskStatus::Open.as_str(), TaskStatus::Completed.as_str()],
+                row_to_entry,
+            )?;
+            rows.collect::<Result<Vec<_>, _>>()
+                .map_err(TaskBoardError::Sqlite)
+        })
+        .await
+        .map_err(|e| TaskBoardError::TaskPanic(e.to_string()))?
+    }
+
     /// Return all tasks regardless of status, ordered by creation time.
     pub async fn list_all(&self) -> Result<Vec<TaskEntry>, TaskBoardError> {
         let conn = Arc::clone(&self.conn);
         tokio::task::spawn_blocking(move || {
             let conn = conn.lock().unwrap_or_else(|e| e.into_inner());
-            let mut stmt = conn.prepare(
-                "SELECT id, title, description, status, claimed_by, created_at, updated_at
-                 FROM fleet_tasks ORDER BY created_at ASC",
-            )?;
+            let mut stmt = conn.prepare(&format!("{SELECT_TASKS} ORDER BY created_at ASC"))?;
             let rows = stmt.query_map([], row_to_entry)?;
-            rows.collect::<Result<Vec<_>, _>>().map_err(TaskBoardError::Sqlite)
+            rows.collect::<Result<Vec<_>, _>>()
+                .map_err(TaskBoardError::Sqlite)
         })
         .await
         .map_err(|e| TaskBoardError::TaskPanic(e.to_string()))?
@@ -302,23 +789,34 @@ impl TaskBoard {
         let status = status.to_owned();
         tokio::task::spawn_blocking(move || {
             let conn = conn.lock().unwrap_or_else(|e| e.into_inner());
-            let mut stmt = conn.prepare(
-                "SELECT id, title, description, status, claimed_by, created_at, updated_at
-                 FROM fleet_tasks WHERE status = ?1 ORDER BY created_at ASC",
-            )?;
+            let mut stmt =
+                conn.prepare(&format!("{SELECT_TASKS} WHERE status = ?1 {TASK_ORDER}"))?;
             let rows = stmt.query_map(params![status], row_to_entry)?;
-            rows.collect::<Result<Vec<_>, _>>().map_err(TaskBoardError::Sqlite)
+            rows.collect::<Result<Vec<_>, _>>()
+                .map_err(TaskBoardError::Sqlite)
         })
         .await
         .map_err(|e| TaskBoardError::TaskPanic(e.to_string()))?
     }
 }
 
+/// Return the tasks `ids` to [`TaskStatus::Open`] with no claimer.
+fn reopen(conn: &Connection, ids: &[String]) -> Result<(), TaskBoardError> {
+    let now = Utc::now().to_rfc3339();
+    for id in ids {
+        conn.execute(
+            "UPDATE fleet_tasks
+             SET status = ?1, claimed_by = NULL, heartbeat_at = NULL, lease_expires_at = NULL,
+                 progress = 0, progress_note = NULL, updated_at = ?2
+             WHERE id = ?3",
+            params![TaskStatus::Open.as_str(), now, id],
+        )?;
+    }
+    Ok(())
+}
+
 fn get_entry(conn: &Connection, task_id: &str) -> Result<TaskEntry, TaskBoardError> {
-    let mut stmt = conn.prepare(
-        "SELECT id, title, description, status, claimed_by, created_at, updated_at
-         FROM fleet_tasks WHERE id = ?1",
-    )?;
+    let mut stmt = conn.prepare(&format!("{SELECT_TASKS} WHERE id = ?1"))?;
     let mut rows = stmt.query_map(params![task_id], row_to_entry)?;
     rows.next()
         .ok_or_else(|| TaskBoardError::NotFound(task_id.to_string()))?
@@ -333,6 +831,23 @@ fn row_to_entry(row: &rusqlite::Row<'_>) -> rusqlite::Result<TaskEntry> {
     let claimed_by: Option<String> = row.get(4)?;
     let created_at: String = row.get(5)?;
     let updated_at: String = row.get(6)?;
+    let depends_on: String = row.get(7)?;
+    let depends_on = serde_json::from_str(&depends_on).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(7, rusqlite::types::Type::Text, Box::new(e))
+    })?;
+    let priority: i32 = row.get(8)?;
+    let due_at: Option<String> = row.get(9)?;
+    let heartbeat_at: Option<String> = row.get(10)?;
+    let lease_expires_at: Option<String> = row.get(11)?;
+    let progress_percent: f64 = row.get(12)?;
+    let progress_note: Option<String> = row.get(13)?;
+    let result: Option<String> = row.get(14)?;
+    let result = result
+        .map(|json| serde_json::from_str(&json))
+        .transpose()
+        .map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(14, rusqlite::types::Type::Text, Box::new(e))
+        })?;
     let status = TaskStatus::from_str(&status_str).ok_or_else(|| {
         rusqlite::Error::InvalidColumnType(3, status_str, rusqlite::types::Type::Text)
     })?;
@@ -344,6 +859,14 @@ fn row_to_entry(row: &rusqlite::Row<'_>) -> rusqlite::Result<TaskEntry> {
         claimed_by,
         created_at,
         updated_at,
+        depends_on,
+        priority,
+        due_at,
+        heartbeat_at,
+        lease_expires_at,
+        progress_percent: progress_percent as f32,
+        progress_note,
+        result,
     })
 }
 
@@ -465,4 +988,243 @@ mod tests {
         assert!(json.contains("Serialization test"));
         assert!(json.contains("open"));
     }
+
+    // ── dependencies ─────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn dependent_task_waits_for_prerequisite() {
+        let board = make_board();
+        let clear = board
+            .post("Clear shelf A", "Remove old stock.")
+            .await
+            .unwrap();
+        let restock = board
+            .post_with_dependencies(
+                "Restock shelf A",
+                "Fill shelf A.",
+                std::slice::from_ref(&clear),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            board.get(&restock).await.unwrap().depends_on,
+            vec![clear.clone()]
+        );
+
+        let ready: Vec<_> = board
+            .list_ready()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|t| t.id)
+            .collect();
+        assert_eq!(ready, vec![clear.clone()]);
+        let err = board.claim(&restock, "robot_bravo").await.unwrap_err();
+        assert!(
+            matches!(err, TaskBoardError::DependenciesPending(ref p) if *p == vec![clear.clone()])
+        );
+
+        board.claim(&clear, "robot_alpha").await.unwrap();
+        assert!(board.list_ready().await.unwrap().is_empty());
+        board.complete(&clear, "robot_alpha").await.unwrap();
+
+        let ready: Vec<_> = board
+            .list_ready()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|t| t.id)
+            .collect();
+        assert_eq!(ready, vec![restock.clone()]);
+        board.claim(&restock, "robot_bravo").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn post_with_unknown_dependency_is_rejected() {
+        let board = make_board();
+        let err = board
+            .post_with_dependencies("Restock", "Fill shelf.", &["missing".to_string()])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, TaskBoardError::NotFound(_)));
+        assert!(board.list_all().await.unwrap().is_empty());
+    }
+
+    // ── priorities, deadlines & leases ───────────────────────────────────────
+
+    #[tokio::test]
+    async fn list_available_orders_by_priority_then_deadline() {
+        let board = make_board();
+        let now = Utc::now();
+        let routine = board.post("Routine", "No rush.").await.unwrap();
+        let later = board
+            .post_task(
+                &TaskSpec::new("Later", "Due tomorrow.")
+                    .with_priority(5)
+                    .with_due_at(now + chrono::Duration::days(1)),
+            )
+            .await
+            .unwrap();
+        let soon = board
+            .post_task(
+                &TaskSpec::new("Soon", "Due in an hour.")
+                    .with_priority(5)
+                    .with_due_at(now + chrono::Duration::hours(1)),
+            )
+            .await
+            .unwrap();
+        let urgent = board
+            .post_task(&TaskSpec::new("Urgent", "Spill in aisle 3.").with_priority(10))
+            .await
+            .unwrap();
+
+        let order: Vec<_> = board
+            .list_available()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|t| t.id)
+            .collect();
+        assert_eq!(order, vec![urgent.clone(), soon.clone(), later, routine]);
+        let ready: Vec<_> = board
+            .list_ready()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|t| t.id)
+            .collect();
+        assert_eq!(ready[..2], [urgent, soon.clone()]);
+        assert_eq!(board.get(&soon).await.unwrap().priority, 5);
+    }
+
+    #[tokio::test]
+    async fn stale_claim_is_reopened_but_live_claim_is_kept() {
+        let board = make_board();
+        let crashed = board.post("Crashed", "Claimer went silent.").await.unwrap();
+        let alive = board.post("Alive", "Claimer heartbeats.").await.unwrap();
+        board.claim(&crashed, "robot_alpha").await.unwrap();
+        board.claim(&alive, "robot_bravo").await.unwrap();
+        assert!(board.get(&alive).await.unwrap().heartbeat_at.is_some());
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        board.renew(&alive, "robot_bravo").await.unwrap();
+        let expired = board
+            .expire_stale_claims(Duration::from_millis(20))
+            .await
+            .unwrap();
+        assert_eq!(expired, vec![crashed.clone()]);
+
+        let task = board.get(&crashed).await.unwrap();
+        assert_eq!(task.status, TaskStatus::Open);
+        assert!(task.claimed_by.is_none());
+        assert_eq!(board.get(&alive).await.unwrap().status, TaskStatus::Claimed);
+
+        // The crashed robot lost its lease; another robot can take over.
+        let err = board.renew(&crashed, "robot_alpha").await.unwrap_err();
+        assert!(matches!(err, TaskBoardError::NotClaimed(_)));
+        board.claim(&crashed, "robot_charlie").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn lease_sweeper_reopens_expired_claims() {
+        let board = make_board().with_claim_lease(Duration::from_millis(10));
+        let id = board.post("Crashed", "Claimer went silent.").await.unwrap();
+        board.claim(&id, "robot_alpha").await.unwrap();
+        let sweeper = board.spawn_lease_sweeper(Duration::from_millis(5));
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        sweeper.abort();
+        assert_eq!(board.get(&id).await.unwrap().status, TaskStatus::Open);
+    }
+
+    #[tokio::test]
+    async fn renew_extends_the_lease_and_reassign_stale_reopens_lapsed_claims() {
+        let board = make_board().with_claim_lease(Duration::from_millis(40));
+        assert_eq!(board.claim_lease(), Duration::from_millis(40));
+        let renewed = board.post("Renewed", "Claimer renews.").await.unwrap();
+        let lapsed = board.post("Lapsed", "Claimer crashed.").await.unwrap();
+        board.claim(&renewed, "robot_alpha").await.unwrap();
+        board.claim(&lapsed, "robot_bravo").await.unwrap();
+        let first_lease = board.get(&renewed).await.unwrap().lease_expires_at.unwrap();
+        assert!(board.reassign_stale().await.unwrap().is_empty());
+
+        tokio::time::sleep(Duration::from_millis(25)).await;
+        board.renew(&renewed, "robot_alpha").await.unwrap();
+        assert!(board.get(&renewed).await.unwrap().lease_expires_at.unwrap() > first_lease);
+        tokio::time::sleep(Duration::from_millis(25)).await;
+
+        assert_eq!(board.reassign_stale().await.unwrap(), vec![lapsed.clone()]);
+        let task = board.get(&lapsed).await.unwrap();
+        assert_eq!(task.status, TaskStatus::Open);
+        assert!(task.lease_expires_at.is_none());
+        assert_eq!(
+            board.get(&renewed).await.unwrap().status,
+            TaskStatus::Claimed
+        );
+    }
+
+    // ── progress & results ───────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn progress_and_result_are_stored_and_published() {
+        let bus = EventBus::default();
+        let mut updates = bus.subscribe_typed::<TaskProgress>();
+        let mut completions = bus.subscribe_typed::<TaskCompleted>();
+        let board = make_board().with_bus(bus);
+        let id = board
+            .post("Move boxes", "Move 5 boxes to bay 2.")
+            .await
+            .unwrap();
+        board.claim(&id, "robot_alpha").await.unwrap();
+
+        let err = board
+            .report_progress(&id, "robot_bravo", 10.0, "cheating")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, TaskBoardError::NotClaimed(_)));
+        board
+            .report_progress(&id, "robot_alpha", 40.0, "2 of 5 boxes moved")
+            .await
+            .unwrap();
+        let task = board.get(&id).await.unwrap();
+        assert_eq!(task.progress_percent, 40.0);
+        assert_eq!(task.progress_note.as_deref(), Some("2 of 5 boxes moved"));
+        let update = updates.recv().await.unwrap();
+        assert_eq!(
+            (update.percent, update.robot_id.as_str()),
+            (40.0, "robot_alpha")
+        );
+
+        board
+            .complete_with_result(&id, "robot_alpha", serde_json::json!({ "boxes": 5 }))
+            .await
+            .unwrap();
+        let task = board.get(&id).await.unwrap();
+        assert_eq!(task.progress_percent, 100.0);
+        assert_eq!(task.result, Some(serde_json::json!({ "boxes": 5 })));
+        let done = completions.recv().await.unwrap();
+        assert_eq!(done.task_id, id);
+        assert_eq!(done.result["boxes"], 5);
+    }
+
+    #[tokio::test]
+    async fn plain_completion_has_no_result_and_reopen_resets_progress() {
+        let board = make_board();
+        let done = board.post("Done", "No result.").await.unwrap();
+        board.claim(&done, "robot_alpha").await.unwrap();
+        board.complete(&done, "robot_alpha").await.unwrap();
+        assert!(board.get(&done).await.unwrap().result.is_none());
+
+        let crashed = board.post("Crashed", "Half done.").await.unwrap();
+        board.claim(&crashed, "robot_bravo").await.unwrap();
+        board
+            .report_progress(&crashed, "robot_bravo", 150.0, "almost")
+            .await
+            .unwrap();
+        assert_eq!(board.get(&crashed).await.unwrap().progress_percent, 100.0);
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        board.expire_stale_claims(Duration::ZERO).await.unwrap();
+        let task = board.get(&crashed).await.unwrap();
+        assert_eq!(task.progress_percent, 0.0);
+        assert!(task.progress_note.is_none());
+    }
 }
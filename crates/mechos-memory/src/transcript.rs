@@ -0,0 +1,366 @@
+//! LLM Transcript Store.
+//!
+//! Records every prompt/response pair exchanged with the LLM, tagged with the
+//! mission it belongs to, the agent that issued it, a timestamp and the trace
+//! ID of the active span, so operators can audit *why* the agent behaved a
+//! certain way during a given mission.
+//!
+//! # Storage layout
+//!
+//! A single table `llm_transcripts` is created (if it does not already
+//! exist) with the following columns:
+//!
+//! | column      | type    | description                                    |
+//! |-------------|---------|------------------------------------------------|
+//! | id          | TEXT    | UUID v4 primary key                            |
+//! | mission_id  | TEXT    | Mission the exchange belongs to (indexed)      |
+//! | agent_id    | TEXT    | Identity of the issuing agent loop             |
+//! | timestamp   | TEXT    | RFC-3339 request time (UTC)                    |
+//! | trace_id    | TEXT    | W3C traceparent / tracing span ID, or NULL     |
+//! | prompt      | TEXT    | Prompt sent to the model (JSON message list)   |
+//! | response    | TEXT    | Raw model reply (empty on failure)             |
+//! | error       | TEXT    | Failure description, or NULL on success        |
+//! | latency_ms  | INTEGER | Round-trip time of the request                 |
+//!
+//! # Rotation
+//!
+//! The store keeps at most [`TranscriptStore::max_entries`] rows (default
+//! [`DEFAULT_MAX_TRANSCRIPT_ENTRIES`]); the oldest rows are discarded after
+//! every insert.  [`TranscriptStore::prune_before`] additionally removes rows
+//! older than a cutoff.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mechos_memory::transcript::{TranscriptEntry, TranscriptStore};
+//!
+//! #[tokio::main(flavor = "current_thread")]
+//! async fn main() {
+//!     let store = TranscriptStore::open_in_memory().unwrap();
+//!
+//!     let mut entry = TranscriptEntry::new("mission-42", "agent", "[prompt]", "{\"Drive\":{}}");
+//!     entry.latency_ms = 120;
+//!     store.record(&entry).await.unwrap();
+//!
+//!     let transcript = store.mission_transcript("mission-42").await.unwrap();
+//!     assert_eq!(transcript.len(), 1);
+//! }
+//! ```
+
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, params};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use std::sync::{Arc, Mutex};
+
+/// Default row cap applied by [`TranscriptStore`] rotation.
+pub const DEFAULT_MAX_TRANSCRIPT_ENTRIES: usize = 10_000;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Error type
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Errors that can arise from transcript operations.
+#[derive(Error, Debug)]
+pub enum TranscriptError {
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+    #[error("blocking task panicked: {0}")]
+    TaskPanic(String),
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// TranscriptEntry
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A single prompt/response exchange.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptEntry {
+    /// Unique identifier for this exchange.
+    pub id: Uuid,
+    /// Mission the exchange belongs to.
+    pub mission_id: String,
+    /// Identity of the agent loop that issued the request.
+    pub agent_id: String,
+    /// Wall-clock time at which the request was sent.
+    pub timestamp: DateTime<Utc>,
+    /// Trace ID of the span the request was made in, if any.
+    pub trace_id: Option<String>,
+    /// Prompt sent to the model.
+    pub prompt: String,
+    /// Raw model reply (empty when the request failed).
+    pub response: String,
+    /// Failure description when the request did not produce a reply.
+    pub error: Option<String>,
+    /// Round-trip time of the request in milliseconds.
+    pub latency_ms: u64,
+}
+
+impl TranscriptEntry {
+    /// Construct a successful exchange with a fresh UUID and the current UTC
+    /// timestamp.  `trace_id`, `error` and `latency_ms` start empty.
+    pub fn new(mission_id: &str, agent_id: &str, prompt: &str, response: &str) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            mission_id: mission_id.to_string(),
+            agent_id: agent_id.to_string(),
+            timestamp: Utc::now(),
+            trace_id: None,
+            prompt: prompt.to_string(),
+            response: response.to_string(),
+            error: None,
+            latency_ms: 0,
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// TranscriptStore
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// SQLite-backed, rotating store of [`TranscriptEntry`] records.
+#[derive(Clone)]
+pub struct TranscriptStore {
+    conn: Arc<Mutex<Connection>>,
+    max_entries: usize,
+}
+
+impl TranscriptStore {
+    /// Open (or create) a persistent SQLite database at `path`.
+    ///
+    /// Enables WAL (Write-Ahead Logging) mode so that concurrent readers are
+    /// not blocked by an active writer.
+    pub fn open(path: &str) -> Result<Self, TranscriptError> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch("PRAGMA journal_mode=WAL;")?;
+        Self::from_connection(conn)
+    }
+
+    /// Open a temporary in-memory database (useful for testing).
+    pub fn open_in_memory() -> Result<Self, TranscriptError> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    /// Set the maximum number of rows retained (minimum 1).
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries.max(1);
+        self
+    }
+
+    /// Maximum number of rows retained by rotation.
+    pub fn max_entries(&self) -> usize {
+        self.max_entries
+    }
+
+    fn from_connection(conn: Connection) -> Result<Self, TranscriptError> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS llm_transcripts (
+                id         TEXT NOT NULL PRIMARY KEY,
+                mission_id TEXT NOT NULL,
+                agent_id   TEXT NOT NULL,
+                timestamp  TEXT NOT NULL,
+                trace_id   TEXT,
+                prompt     TEXT NOT NULL,
+                response   TEXT NOT NULL,
+                error      TEXT,
+                latency_ms INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS llm_transcripts_mission
+                ON llm_transcripts (mission_id, timestamp);",
+        )?;
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+            max_entries: DEFAULT_MAX_TRANSCRIPT_ENTRIES,
+        })
+    }
+
+    /// Persist an exchange, then discard the oldest rows beyond
+    /// [`max_entries`][Self::max_entries].
+    pub async fn record(&self, entry: &TranscriptEntry) -> Result<(), TranscriptError> {
+        let conn = Arc::clone(&self.conn);
+        let entry = entry.clone();
+        let max_entries = self.max_entries as i64;
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap_or_else(|e| e.into_inner());
+            conn.execute(
+                "INSERT OR REPLACE INTO llm_transcripts
+                     (id, mission_id, agent_id, timestamp, trace_id, prompt, response, error, latency_ms)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    entry.id.to_string(),
+                    entry.mission_id,
+                    entry.agent_id,
+                    entry.timestamp.to_rfc3339(),
+                    entry.trace_id,
+                    entry.prompt,
+                    entry.response,
+                    entry.error,
+                    entry.latency_ms as i64,
+                ],
+            )?;
+            conn.execute(
+                "DELETE FROM llm_transcripts WHERE rowid NOT IN (
+                     SELECT rowid FROM llm_transcripts
+                     ORDER BY timestamp DESC, rowid DESC
+                     LIMIT ?1
+                 )",
+                params![max_entries],
+            )?;
+            Ok(())
+        })
+        .await
+        .map_err(|e| TranscriptError::TaskPanic(e.to_string()))?
+    }
+
+    /// Return every exchange recorded for `mission_id`, oldest first.
+    pub async fn mission_transcript(
+        &self,
+        mission_id: &str,
+    ) -> Result<Vec<TranscriptEntry>, TranscriptError> {
+        let conn = Arc::clone(&self.conn);
+        let mission_id = mission_id.to_string();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap_or_else(|e| e.into_inner());
+            let mut stmt = conn.prepare(
+                "SELECT id, mission_id, agent_id, timestamp, trace_id, prompt, response, error, latency_ms
+                 FROM llm_transcripts
+                 WHERE mission_id = ?1
+                 ORDER BY timestamp ASC, rowid ASC",
+            )?;
+            let rows = stmt.query_map(params![mission_id], |row| {
+                let id_str: String = row.get(0)?;
+                let ts_str: String = row.get(3)?;
+                let id = Uuid::parse_str(&id_str).map_err(|e| {
+                    rusqlite::Error::InvalidColumnType(0, e.to_string(), rusqlite::types::Type::Text)
+                })?;
+                let timestamp = ts_str.parse::<DateTime<Utc>>().map_err(|e| {
+                    rusqlite::Error::InvalidColumnType(3, e.to_string(), rusqlite::types::Type::Text)
+                })?;
+                let latency_ms: i64 = row.get(8)?;
+                Ok(TranscriptEntry {
+                    id,
+                    mission_id: row.get(1)?,
+                    agent_id: row.get(2)?,
+                    timestamp,
+                    trace_id: row.get(4)?,
+                    prompt: row.get(5)?,
+                    response: row.get(6)?,
+                    error: row.get(7)?,
+                    latency_ms: latency_ms.max(0) as u64,
+                })
+            })?;
+            Ok(rows.collect::<Result<Vec<_>, _>>()?)
+        })
+        .await
+        .map_err(|e| TranscriptError::TaskPanic(e.to_string()))?
+    }
+
+    /// Distinct mission IDs present in the store, most recently active first.
+    pub async fn missions(&self) -> Result<Vec<String>, TranscriptError> {
+        let conn = Arc::clone(&self.conn);
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap_or_else(|e| e.into_inner());
+            let mut stmt = conn.prepare(
+                "SELECT mission_id FROM llm_transcripts
+                 GROUP BY mission_id
+                 ORDER BY MAX(timestamp) DESC",
+            )?;
+            let rows = stmt.query_map([], |row| row.get(0))?;
+            Ok(rows.collect::<Result<Vec<String>, _>>()?)
+        })
+        .await
+        .map_err(|e| TranscriptError::TaskPanic(e.to_string()))?
+    }
+
+    /// Delete every exchange recorded before `cutoff`.  Returns the number of
+    /// rows removed.
+    pub async fn prune_before(&self, cutoff: DateTime<Utc>) -> Result<usize, TranscriptError> {
+        let conn = Arc::clone(&self.conn);
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap_or_else(|e| e.into_inner());
+            let removed = conn.execute(
+                "DELETE FROM llm_transcripts WHERE timestamp < ?1",
+                params![cutoff.to_rfc3339()],
+            )?;
+            Ok(removed)
+        })
+        .await
+        .map_err(|e| TranscriptError::TaskPanic(e.to_string()))?
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn entry_at(mission: &str, offset_s: i64) -> TranscriptEntry {
+        let mut e = TranscriptEntry::new(mission, "agent", "[prompt]", "{}");
+        e.timestamp = Utc::now() + Duration::seconds(offset_s);
+        e
+    }
+
+    #[tokio::test]
+    async fn record_and_fetch_by_mission() {
+        let store = TranscriptStore::open_in_memory().unwrap();
+        let mut first = entry_at("m1", 0);
+        first.trace_id = Some("00-abc-def-01".to_string());
+        first.latency_ms = 42;
+        store.record(&first).await.unwrap();
+        store.record(&entry_at("m1", 1)).await.unwrap();
+        store.record(&entry_at("m2", 2)).await.unwrap();
+
+        let m1 = store.mission_transcript("m1").await.unwrap();
+        assert_eq!(m1.len(), 2);
+        assert_eq!(m1[0], first);
+        assert!(store.mission_transcript("ghost").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn failed_exchange_round_trips_error() {
+        let store = TranscriptStore::open_in_memory().unwrap();
+        let mut e = TranscriptEntry::new("m", "agent", "[prompt]", "");
+        e.error = Some("timeout".to_string());
+        store.record(&e).await.unwrap();
+        let fetched = store.mission_transcript("m").await.unwrap();
+        assert_eq!(fetched[0].error.as_deref(), Some("timeout"));
+    }
+
+    #[tokio::test]
+    async fn rotation_discards_oldest_rows() {
+        let store = TranscriptStore::open_in_memory().unwrap().with_max_entries(2);
+        for i in 0..4 {
+            store.record(&entry_at("m", i)).await.unwrap();
+        }
+        let kept = store.mission_transcript("m").await.unwrap();
+        assert_eq!(kept.len(), 2);
+        assert!(kept[0].timestamp > Utc::now() + Duration::seconds(1));
+    }
+
+    #[tokio::test]
+    async fn missions_lists_most_recent_first() {
+        let store = TranscriptStore::open_in_memory().unwrap();
+        store.record(&entry_at("old", 0)).await.unwrap();
+        store.record(&entry_at("new", 5)).await.unwrap();
+        assert_eq!(store.missions().await.unwrap(), vec!["new", "old"]);
+    }
+
+    #[tokio::test]
+    async fn prune_before_removes_old_rows() {
+        let store = TranscriptStore::open_in_memory().unwrap();
+        store.record(&entry_at("m", -3600)).await.unwrap();
+        store.record(&entry_at("m", 0)).await.unwrap();
+        let removed = store
+            .prune_before(Utc::now() - Duration::seconds(60))
+            .await
+            .unwrap();
+        assert_eq!(removed, 1);
+        assert_eq!(store.mission_transcript("m").await.unwrap().len(), 1);
+    }
+}
@@ -11,10 +11,22 @@
 //!   robot via ROS 2 MoveIt 2 / `/cmd_vel`.
 //! - [`DashboardSimAdapter`][crate::dashboard_sim_adapter::DashboardSimAdapter]
 //!   – drives the React / Three.js simulation over a WebSocket.
+//! - [`SimAdapter`][crate::sim_adapter::SimAdapter] – drives a Gazebo or
+//!   Webots full-physics simulation.
+//! - [`MqttAdapter`][crate::mqtt_adapter::MqttAdapter] – drives
+//!   microcontroller-based robots over MQTT.
+//! - [`SerialAdapter`][crate::serial_adapter::SerialAdapter] – drives
+//!   microcontroller bases over a serial port.
+//! - [`CanAdapter`][crate::can_adapter::CanAdapter] – drives industrial
+//!   chassis over a CAN bus.
+//! - [`MavlinkAdapter`][crate::mavlink_adapter::MavlinkAdapter] – flies
+//!   ArduPilot / PX4 vehicles over MAVLink.
+//! - [`DirectHalAdapter`][crate::direct_hal_adapter::DirectHalAdapter] –
+//!   drives `mechos-hal` motor, servo and relay drivers in-process.
 
 use async_trait::async_trait;
 use futures_util::stream::BoxStream;
-use mechos_types::{EventPayload, HardwareIntent, MechError};
+use mechos_types::{EventPayload, FaultCode, HardwareIntent, IntentEnvelope, MechError};
 
 /// Every external-protocol adapter must implement this trait.
 ///
@@ -24,14 +36,54 @@ use mechos_types::{EventPayload, HardwareIntent, MechError};
 ///   EventBus and translates it into external commands (e.g. ROS 2 `/cmd_vel`,
 ///   a WebSocket JSON frame, …).
 ///
+/// * `execute_envelope` – receives an [`IntentEnvelope`] from the bus, drops
+///   it if its deadline has passed and otherwise forwards the intent to
+///   `execute_intent`.  The default implementation is usually sufficient.
+///
 /// * `sensor_stream` – returns a live stream of [`EventPayload`] values that
 ///   the adapter produces by translating inbound sensor data (e.g. LiDAR scans)
 ///   into MechOS events.
+///
+/// * `heartbeat` – tells the adapter the runtime is alive.  Adapters whose
+///   hardware stops on a command deadline use it to keep executing the last
+///   command between intents; the default does nothing.
 #[async_trait]
 pub trait MechAdapter: Send + Sync {
     /// Translate a high-level [`HardwareIntent`] into external commands.
     async fn execute_intent(&self, intent: HardwareIntent) -> Result<(), MechError>;
 
+    /// Execute an enveloped intent unless it is stale.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::HardwareFault`] from component `"adapter"` when
+    /// the envelope's deadline has passed; otherwise whatever
+    /// [`execute_intent`][Self::execute_intent] returns.
+    async fn execute_envelope(&self, envelope: IntentEnvelope) -> Result<(), MechError> {
+        if envelope.is_expired() {
+            return Err(MechError::HardwareFault {
+                code: FaultCode::DeadlineExpired,
+                component: "adapter".to_string(),
+                details: format!(
+                    "intent {} from '{}' dropped: deadline passed",
+                    envelope.correlation_id, envelope.issued_by
+                ),
+            });
+        }
+        self.execute_intent(envelope.intent).await
+    }
+
     /// Translate external sensor data into a stream of [`EventPayload`] values.
     async fn sensor_stream(&self) -> BoxStream<'static, EventPayload>;
+
+    /// The runtime is alive; refresh any hardware command deadline.
+    fn heartbeat(&self) {}
+}
+
+/// The intent's `action` tag as it appears on the wire (e.g. `"Drive"`).
+pub(crate) fn action_name(intent: &HardwareIntent) -> String {
+    serde_json::to_value(intent)
+        .ok()
+        .and_then(|v| v["action"].as_str().map(str::to_string))
+        .unwrap_or_default()
 }
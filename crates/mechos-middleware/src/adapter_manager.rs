@@ -0,0 +1,948 @@
+//! [`AdapterManager`] – several adapters behind one [`MechAdapter`].
+//!
+//! A robot is often driven through more than one protocol: a ROS 2 arm on a
+//! CAN chassis, or a serial base with an MQTT relay board.  The manager holds
+//! every registered adapter under an ID and:
+//!
+//! * **routes** each approved intent to the adapter(s) responsible for it,
+//!   following a [`RoutingTable`].  Routes are keyed by the intent's action
+//!   (`"Drive"`), or by action and target for per-actuator intents
+//!   (`"MoveJoint:elbow"`, `"TriggerRelay:pump"`); the more specific key
+//!   wins.  Intents without a route go to the table's `default` adapters.
+//!   [`HardwareIntent::Halt`] is always sent to every adapter.
+//! * **fans in** every adapter's sensor stream with
+//!   [`AdapterManager::sensor_stream`] / [`AdapterManager::forward_sensors`].
+//! * **reports** per-adapter [`AdapterHealth`].
+//! * **holds back** intents for adapters whose link is down.  An adapter
+//!   registered with [`AdapterManager::with_link`] follows the
+//!   [`EventPayload::ConnectionState`] reports of its supervised link; while
+//!   the link is disconnected its intents are rejected with
+//!   [`MechError::AdapterUnavailable`], or, with
+//!   [`AdapterManager::with_offline_queue`], buffered and executed once the
+//!   link is back.  Buffered intents whose deadline passes meanwhile, or
+//!   that waited longer than the queue's maximum age, are dropped, and a
+//!   `Halt` discards the buffer instead of joining it.
+//!
+//! The manager is itself a [`MechAdapter`], so it can be wired in wherever
+//! a single adapter was.  [`AdapterManager::run`] executes the
+//! [`EventPayload::Intent`] envelopes published on the bus.
+//!
+//! A routing table can be loaded from TOML:
+//!
+//! ```toml
+//! default = ["base"]
+//!
+//! [routes]
+//! MoveJoint = ["arm"]
+//! SetGripper = ["arm"]
+//! "TriggerRelay:pump" = ["relays"]
+//! ```
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use futures_util::future::join_all;
+use futures_util::stream::{self, BoxStream, StreamExt};
+use mechos_types::{
+    Event, EventPayload, FaultCode, HardwareIntent, IntentEnvelope, LinkState, MechError,
+};
+use serde::Deserialize;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{Instrument, info, info_span, warn};
+use uuid::Uuid;
+
+use crate::adapter::{MechAdapter, action_name};
+use crate::bus::EventBus;
+
+/// Which adapters receive which intents.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RoutingTable {
+    /// Adapters for intents without a matching route.
+    #[serde(default)]
+    pub default: Vec<String>,
+    /// Adapter IDs by route key: an action name (`"Drive"`) or
+    /// `"<action>:<target>"` for `MoveJoint` joints and `TriggerRelay`
+    /// relays.
+    #[serde(default)]
+    pub routes: HashMap<String, Vec<String>>,
+}
+
+impl RoutingTable {
+    /// Parse a TOML routing table.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Parsing`] for malformed TOML or unknown keys.
+    pub fn from_toml(text: &str) -> Result<Self, MechError> {
+        toml::from_str(text).map_err(|e| MechError::Parsing(format!("invalid routing table: {e}")))
+    }
+
+    /// Load a TOML routing table from `path`.
+    ///
+    /// # Errors
+    ///
+    /// - [`MechError::Serialization`] – the file cannot be read.
+    /// - [`MechError::Parsing`] – see [`Self::from_toml`].
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, MechError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            MechError::Serialization(format!(
+                "failed to read routing table '{}': {e}",
+                path.display()
+            ))
+        })?;
+        Self::from_toml(&text)
+    }
+
+    /// Adapter IDs responsible for `intent` (excluding the `Halt`
+    /// broadcast, which the manager handles).
+    pub fn adapters_for(&self, intent: &HardwareIntent) -> &[String] {
+        let action = action_name(intent);
+        let target = match intent {
+            HardwareIntent::MoveJoint { joint, .. } => Some(joint),
+            HardwareIntent::TriggerRelay { relay_id, .. } => Some(relay_id),
+            _ => None,
+        };
+        target
+            .and_then(|target| self.routes.get(&format!("{action}:{target}")))
+            .or_else(|| self.routes.get(&action))
+            .unwrap_or(&self.default)
+    }
+}
+
+/// Per-adapter counters kept by the [`AdapterManager`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AdapterHealth {
+    /// Intents the adapter executed successfully.
+    pub intents_executed: u64,
+    /// Intents the adapter returned an error for.
+    pub intents_failed: u64,
+    /// Failures since the last success.
+    pub consecutive_failures: u32,
+    /// Message of the most recent failure.
+    pub last_error: Option<String>,
+    /// When the adapter last executed an intent successfully.
+    pub last_success_at: Option<Instant>,
+    /// Sensor payloads received through [`AdapterManager::forward_sensors`].
+    pub sensor_events: u64,
+}
+
+impl AdapterHealth {
+    /// `true` unless the adapter's most recent intent failed.
+    pub fn is_healthy(&self) -> bool {
+        self.consecutive_failures == 0
+    }
+}
+
+/// Routes intents to, and collects sensor data from, several adapters.
+#[derive(Default)]
+pub struct AdapterManager {
+    /// Registered adapters in ID order.
+    adapters: BTreeMap<String, Arc<dyn MechAdapter>>,
+    routing: RoutingTable,
+    health: Mutex<BTreeMap<String, AdapterHealth>>,
+    /// Supervised link component of each adapter that reports one.
+    links: BTreeMap<String, String>,
+    /// Intents buffered per disconnected adapter; `0` rejects them instead.
+    offline_capacity: usize,
+    /// Longest a buffered intent may wait for its link.
+    offline_max_age: Duration,
+    /// Adapters whose link is down, with the intents buffered for them and
+    /// when each was buffered.
+    offline: Mutex<BTreeMap<String, VecDeque<(Instant, IntentEnvelope)>>>,
+}
+
+impl AdapterManager {
+    /// Component name used in the manager's own errors.
+    pub const COMPONENT: &'static str = "adapter_manager";
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `adapter` under `id`, replacing any adapter with that ID.
+    pub fn with_adapter(mut self, id: impl Into<String>, adapter: Arc<dyn MechAdapter>) -> Self {
+        let id = id.into();
+        self.health_mut()
+            .insert(id.clone(), AdapterHealth::default());
+        self.adapters.insert(id, adapter);
+        self
+    }
+
+    pub fn with_routing(mut self, routing: RoutingTable) -> Self {
+        self.routing = routing;
+        self
+    }
+
+    /// Route intents matching `key` (see [`RoutingTable::routes`]) to
+    /// `adapter_ids`.
+    pub fn with_route<I, S>(mut self, key: impl Into<String>, adapter_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let ids = adapter_ids.into_iter().map(Into::into).collect();
+        self.routing.routes.insert(key.into(), ids);
+        self
+    }
+
+    /// Send intents without a route to `adapter_ids`.
+    pub fn with_default_route<I, S>(mut self, adapter_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.routing.default = adapter_ids.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Follow the [`EventPayload::ConnectionState`] reports of `component`
+    /// (e.g. `"ros2_bridge"`) as the link state of adapter `id`.
+    pub fn with_link(mut self, id: impl Into<String>, component: impl Into<String>) -> Self {
+        self.links.insert(id.into(), component.into());
+        self
+    }
+
+    /// Buffer up to `capacity` intents per disconnected adapter, dropping
+    /// the oldest when full, instead of rejecting them.  An intent still
+    /// buffered `max_age` after it arrived is dropped rather than executed,
+    /// deadline or not, so a reconnect never replays stale decisions.
+    pub fn with_offline_queue(mut self, capacity: usize, max_age: Duration) -> Self {
+        self.offline_capacity = capacity;
+        self.offline_max_age = max_age;
+        self
+    }
+
+    /// IDs of the registered adapters, in order.
+    pub fn adapter_ids(&self) -> impl Iterator<Item = &str> {
+        self.adapters.keys().map(String::as_str)
+    }
+
+    pub fn routing(&self) -> &RoutingTable {
+        &self.routing
+    }
+
+    /// IDs of the adapters `intent` is sent to.  `Halt` goes to all of them.
+    pub fn route(&self, intent: &HardwareIntent) -> Vec<&str> {
+        if matches!(intent, HardwareIntent::Halt { .. }) {
+            return self.adapter_ids().collect();
+        }
+        self.routing
+            .adapters_for(intent)
+            .iter()
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Health of every registered adapter, keyed by ID.
+    pub fn health(&self) -> BTreeMap<String, AdapterHealth> {
+        self.health_mut().clone()
+    }
+
+    /// Health of adapter `id`, if registered.
+    pub fn health_of(&self, id: &str) -> Option<AdapterHealth> {
+        self.health_mut().get(id).cloned()
+    }
+
+    /// `false` while the link of adapter `id` is reported disconnected.
+    pub fn is_connected(&self, id: &str) -> bool {
+        !self.offline_mut().contains_key(id)
+    }
+
+    /// Number of intents buffered for adapter `id`.
+    pub fn queued(&self, id: &str) -> usize {
+        self.offline_mut().get(id).map_or(0, VecDeque::len)
+    }
+
+    /// Apply a [`EventPayload::ConnectionState`] report of `component`.
+    ///
+    /// A disconnected link takes its adapters offline; once it is connected
+    /// again their buffered intents are executed in order, except those
+    /// whose deadline has passed or that are older than the queue's maximum
+    /// age.
+    pub async fn link_changed(&self, component: &str, state: LinkState) {
+        let ids = self
+            .links
+            .iter()
+            .filter(|(_, link)| *link == component)
+            .map(|(id, _)| id);
+        for id in ids {
+            match state {
+                LinkState::Disconnected => {
+                    self.offline_mut().entry(id.clone()).or_default();
+                }
+                LinkState::Connecting => {}
+                LinkState::Connected => {
+                    let Some(queued) = self.offline_mut().remove(id) else {
+                        continue;
+                    };
+                    if !queued.is_empty() {
+                        info!(
+                            adapter = %id,
+                            intents = queued.len(),
+                            "flushing intents buffered while offline"
+                        );
+                    }
+                    for (queued_at, envelope) in queued {
+                        if envelope.is_expired() || queued_at.elapsed() > self.offline_max_age {
+                            warn!(
+                                adapter = %id,
+                                correlation_id = %envelope.correlation_id,
+                                "dropping buffered intent: too old to execute"
+                            );
+                            continue;
+                        }
+                        let result = self.adapters[id]
+                            .execute_intent(envelope.intent)
+                            .instrument(info_span!("adapter.execute_intent", adapter = %id))
+                            .await;
+                        self.record(id, &result);
+                        if let Err(e) = result {
+                            warn!(adapter = %id, error = %e, "adapter failed to execute buffered intent");
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Publish every fanned-in sensor payload on `bus` with the source
+    /// `mechos-middleware::adapter/<id>`, until all sensor streams end.
+    pub async fn forward_sensors(&self, bus: &EventBus) {
+        let mut sensors = self.tagged_sensor_streams().await;
+        while let Some((id, payload)) = sensors.next().await {
+            if let Some(health) = self.health_mut().get_mut(&id) {
+                health.sensor_events += 1;
+            }
+            let event = Event {
+                id: Uuid::new_v4(),
+                timestamp: Utc::now(),
+                source: format!("mechos-middleware::adapter/{id}"),
+                payload,
+                trace_id: None,
+                correlation_id: None,
+            };
+            if let Err(e) = bus.publish(event) {
+                warn!(adapter = %id, error = %e, "dropping adapter sensor event");
+            }
+        }
+    }
+
+    /// Execute every [`EventPayload::Intent`] envelope published on `bus`
+    /// until the bus closes.  Failures are logged and recorded in
+    /// [`Self::health`]; [`EventPayload::ConnectionState`] reports are
+    /// passed to [`Self::link_changed`].
+    ///
+    /// Each envelope executes in an `adapter_manager.execute` span that
+    /// continues the trace in the event's `trace_id`, with one
+    /// `adapter.execute_intent` child per routed adapter, so events the
+    /// adapters publish while executing carry the originating trace.
+    pub async fn run(&self, bus: &EventBus) {
+        let mut rx = bus.subscribe();
+        loop {
+            match rx.recv().await {
+                Ok(Event {
+                    payload: EventPayload::Intent(envelope),
+                    trace_id,
+                    ..
+                }) => {
+                    let correlation_id = envelope.correlation_id;
+                    let span = info_span!(
+                        "adapter_manager.execute",
+                        %correlation_id,
+                        action = action_name(&envelope.intent),
+                    );
+                    EventBus::link_span(&span, trace_id.as_deref());
+                    if let Err(e) = self.execute_envelope(envelope).instrument(span).await {
+                        warn!(%correlation_id, error = %e, "intent not executed");
+                    }
+                }
+                Ok(Event {
+                    payload: EventPayload::ConnectionState { component, state, .. },
+                    ..
+                }) => self.link_changed(&component, state).await,
+                Ok(_) => {}
+                Err(RecvError::Lagged(n)) => warn!(skipped = n, "adapter manager lagged"),
+                Err(RecvError::Closed) => return,
+            }
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    // Private helpers
+    // -------------------------------------------------------------------------
+
+    fn offline_mut(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, VecDeque<(Instant, IntentEnvelope)>>> {
+        self.offline.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// The adapters routed for `intent`, all of them registered.
+    fn targets(&self, intent: &HardwareIntent) -> Result<Vec<&str>, MechError> {
+        let targets = self.route(intent);
+        if targets.is_empty() {
+            return Err(MechError::HardwareFault {
+                code: FaultCode::Unsupported,
+                component: Self::COMPONENT.to_string(),
+                details: format!("no adapter is routed for '{}'", action_name(intent)),
+            });
+        }
+        if let Some(missing) = targets.iter().find(|id| !self.adapters.contains_key(**id)) {
+            return Err(MechError::HardwareFault {
+                code: FaultCode::DeviceNotRegistered,
+                component: Self::COMPONENT.to_string(),
+                details: format!("routed adapter '{missing}' is not registered"),
+            });
+        }
+        Ok(targets)
+    }
+
+    /// Buffer `envelope` for, or refuse it to, the offline adapters among
+    /// `targets`, and return the connected ones.
+    fn hold_offline<'a>(
+        &self,
+        targets: Vec<&'a str>,
+        envelope: &IntentEnvelope,
+    ) -> (Vec<&'a str>, Option<MechError>) {
+        let mut offline = self.offline_mut();
+        let mut refused = None;
+        let mut connected = Vec::with_capacity(targets.len());
+        for id in targets {
+            let Some(queue) = offline.get_mut(id) else {
+                connected.push(id);
+                continue;
+            };
+            let halt = matches!(envelope.intent, HardwareIntent::Halt { .. });
+            if halt || self.offline_capacity == 0 {
+                if halt {
+                    // Nothing buffered may move the robot after a stop.
+                    queue.clear();
+                }
+                refused.get_or_insert_with(|| MechError::AdapterUnavailable {
+                    adapter: id.to_string(),
+                    details: format!(
+                        "link down, '{}' intent {} not sent",
+                        action_name(&envelope.intent),
+                        envelope.correlation_id
+                    ),
+                });
+                continue;
+            }
+            if queue.len() == self.offline_capacity
+                && let Some((_, dropped)) = queue.pop_front()
+            {
+                warn!(
+                    adapter = %id,
+                    correlation_id = %dropped.correlation_id,
+                    "offline queue full, dropping oldest intent"
+                );
+            }
+            queue.push_back((Instant::now(), envelope.clone()));
+        }
+        (connected, refused)
+    }
+
+    /// Send `intent` to each of `targets` concurrently.
+    async fn dispatch(&self, targets: &[&str], intent: HardwareIntent) -> Result<(), MechError> {
+        let results = join_all(
+            targets
+                .iter()
+                .map(|id| {
+                    self.adapters[*id]
+                        .execute_intent(intent.clone())
+                        .instrument(info_span!("adapter.execute_intent", adapter = %id))
+                }),
+        )
+        .await;
+        let mut outcome = Ok(());
+        for (id, result) in targets.iter().zip(results) {
+            self.record(id, &result);
+            if let Err(e) = result {
+                warn!(adapter = %id, error = %e, "adapter failed to execute intent");
+                if outcome.is_ok() {
+                    outcome = Err(e);
+                }
+            }
+        }
+        outcome
+    }
+
+    fn health_mut(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, AdapterHealth>> {
+        self.health.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    async fn tagged_sensor_streams(&self) -> BoxStream<'static, (String, EventPayload)> {
+        let mut streams = Vec::with_capacity(self.adapters.len());
+        for (id, adapter) in &self.adapters {
+            let id = id.clone();
+            let stream = adapter.sensor_stream().await;
+            streams.push(stream.map(move |payload| (id.clone(), payload)).boxed());
+        }
+        stream::select_all(streams).boxed()
+    }
+
+    fn record(&self, id: &str, result: &Result<(), MechError>) {
+        let mut health = self.health_mut();
+        let Some(health) = health.get_mut(id) else {
+            return;
+        };
+        match result {
+            Ok(()) => {
+                health.intents_executed += 1;
+                health.consecutive_failures = 0;
+                health.last_success_at = Some(Instant::now());
+            }
+            Err(e) => {
+                health.intents_failed += 1;
+                health.consecutive_failures += 1;
+                health.last_error = Some(e.to_string());
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl MechAdapter for AdapterManager {
+    /// Send the intent to every adapter [`route`][AdapterManager::route]d to
+    /// it, concurrently, as an envelope without a deadline.
+    ///
+    /// # Errors
+    ///
+    /// See [`execute_envelope`][Self::execute_envelope].
+    async fn execute_intent(&self, intent: HardwareIntent) -> Result<(), MechError> {
+        self.execute_envelope(IntentEnvelope::new(intent, Self::COMPONENT))
+            .await
+    }
+
+    /// Send the intent to every connected adapter routed to it,
+    /// concurrently, and buffer or refuse it for the disconnected ones.
+    ///
+    /// # Errors
+    ///
+    /// - [`MechError::HardwareFault`] with [`FaultCode::DeadlineExpired`] –
+    ///   the envelope's deadline has passed.
+    /// - [`MechError::HardwareFault`] with [`FaultCode::Unsupported`] – no
+    ///   adapter is routed to the intent.
+    /// - [`MechError::HardwareFault`] with [`FaultCode::DeviceNotRegistered`]
+    ///   – a route names an adapter that is not registered.
+    /// - Otherwise the first error returned by a routed adapter, in route
+    ///   order, after all of them have been tried.
+    /// - [`MechError::AdapterUnavailable`] – a routed adapter is offline and
+    ///   the intent was not buffered for it.
+    async fn execute_envelope(&self, envelope: IntentEnvelope) -> Result<(), MechError> {
+        if envelope.is_expired() {
+            return Err(MechError::HardwareFault {
+                code: FaultCode::DeadlineExpired,
+                component: Self::COMPONENT.to_string(),
+                details: format!(
+                    "intent {} from '{}' dropped: deadline passed",
+                    envelope.correlation_id, envelope.issued_by
+                ),
+            });
+        }
+        let targets = self.targets(&envelope.intent)?;
+        let (connected, refused) = self.hold_offline(targets, &envelope);
+        let outcome = if connected.is_empty() {
+            Ok(())
+        } else {
+            self.dispatch(&connected, envelope.intent).await
+        };
+        match refused {
+            Some(e) if outcome.is_ok() => Err(e),
+            _ => outcome,
+        }
+    }
+
+    /// All adapters' sensor streams merged, in arrival order.
+    async fn sensor_stream(&self) -> BoxStream<'static, EventPayload> {
+        self.tagged_sensor_streams()
+            .await
+            .map(|(_, payload)| payload)
+            .boxed()
+    }
+
+    /// Pass the heartbeat to every registered adapter.
+    fn heartbeat(&self) {
+        for adapter in self.adapters.values() {
+            adapter.heartbeat();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mechos_types::{IntentEnvelope, MetersPerSecond, RadiansPerSecond};
+
+    /// Records the action and trace of every intent it receives; optionally
+    /// fails.
+    struct RecordingAdapter {
+        received: Mutex<Vec<String>>,
+        traces: Mutex<Vec<Option<String>>>,
+        fail: bool,
+        sensors: Vec<f32>,
+    }
+
+    impl RecordingAdapter {
+        fn new() -> Arc<Self> {
+            Self::with(false, Vec::new())
+        }
+
+        fn with(fail: bool, sensors: Vec<f32>) -> Arc<Self> {
+            Arc::new(Self {
+                received: Mutex::new(Vec::new()),
+                traces: Mutex::new(Vec::new()),
+                fail,
+                sensors,
+            })
+        }
+
+        fn received(&self) -> Vec<String> {
+            self.received.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl MechAdapter for RecordingAdapter {
+        async fn execute_intent(&self, intent: HardwareIntent) -> Result<(), MechError> {
+            self.received.lock().unwrap().push(action_name(&intent));
+            self.traces.lock().unwrap().push(EventBus::current_trace_id());
+            if self.fail {
+                return Err(MechError::Channel("link down".to_string()));
+            }
+            Ok(())
+        }
+
+        async fn sensor_stream(&self) -> BoxStream<'static, EventPayload> {
+            let payloads: Vec<_> = self
+                .sensors
+                .iter()
+                .map(|&percent| EventPayload::PowerStatus {
+                    voltage: 12.0,
+                    current: 0.0,
+                    charging: false,
+                    percent,
+                })
+                .collect();
+            stream::iter(payloads).boxed()
+        }
+    }
+
+    fn drive() -> HardwareIntent {
+        HardwareIntent::Drive {
+            linear_velocity: MetersPerSecond(0.2),
+            angular_velocity: RadiansPerSecond(0.0),
+        }
+    }
+
+    fn move_joint(joint: &str) -> HardwareIntent {
+        HardwareIntent::MoveJoint {
+            joint: joint.to_string(),
+            angle_rad: 0.5,
+            max_velocity: RadiansPerSecond(0.2),
+        }
+    }
+
+    #[tokio::test]
+    async fn intents_follow_the_routing_table() {
+        let (base, arm, wrist) = (
+            RecordingAdapter::new(),
+            RecordingAdapter::new(),
+            RecordingAdapter::new(),
+        );
+        let manager = AdapterManager::new()
+            .with_adapter("base", base.clone())
+            .with_adapter("arm", arm.clone())
+            .with_adapter("wrist", wrist.clone())
+            .with_routing(
+                RoutingTable::from_toml(
+                    r#"
+                    default = ["base"]
+                    [routes]
+                    MoveJoint = ["arm"]
+                    "MoveJoint:wrist" = ["wrist"]
+                    "#,
+                )
+                .unwrap(),
+            );
+
+        manager.execute_intent(drive()).await.unwrap();
+        manager.execute_intent(move_joint("elbow")).await.unwrap();
+        manager.execute_intent(move_joint("wrist")).await.unwrap();
+        manager
+            .execute_intent(HardwareIntent::Halt {
+                reason: "test".to_string(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(base.received(), vec!["Drive", "Halt"]);
+        assert_eq!(arm.received(), vec!["MoveJoint", "Halt"]);
+        assert_eq!(wrist.received(), vec!["MoveJoint", "Halt"]);
+    }
+
+    #[tokio::test]
+    async fn unrouted_and_misrouted_intents_are_rejected() {
+        let manager = AdapterManager::new()
+            .with_adapter("base", RecordingAdapter::new())
+            .with_route("Drive", ["base"])
+            .with_route("Dock", ["charger"]);
+
+        let err = manager
+            .execute_intent(move_joint("elbow"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.fault_code(), Some(FaultCode::Unsupported));
+        let err = manager
+            .execute_intent(HardwareIntent::Dock)
+            .await
+            .unwrap_err();
+        assert_eq!(err.fault_code(), Some(FaultCode::DeviceNotRegistered));
+        assert!(RoutingTable::from_toml("unknown = 1").is_err());
+    }
+
+    #[tokio::test]
+    async fn failures_are_reported_per_adapter() {
+        let (relays, lights) = (
+            RecordingAdapter::with(true, Vec::new()),
+            RecordingAdapter::new(),
+        );
+        let manager = AdapterManager::new()
+            .with_adapter("relays", relays.clone())
+            .with_adapter("lights", lights.clone())
+            .with_default_route(["relays", "lights"]);
+        let relay = HardwareIntent::TriggerRelay {
+            relay_id: "pump".to_string(),
+            state: true,
+        };
+
+        // The healthy adapter still receives the intent.
+        assert!(matches!(
+            manager.execute_intent(relay).await,
+            Err(MechError::Channel(_))
+        ));
+        assert_eq!(lights.received(), vec!["TriggerRelay"]);
+
+        let health = manager.health();
+        assert!(!health["relays"].is_healthy());
+        assert_eq!(health["relays"].intents_failed, 1);
+        assert!(
+            health["relays"]
+                .last_error
+                .as_deref()
+                .unwrap()
+                .contains("link down")
+        );
+        assert!(health["lights"].is_healthy());
+        assert_eq!(health["lights"].intents_executed, 1);
+        assert!(manager.health_of("missing").is_none());
+    }
+
+    #[tokio::test]
+    async fn offline_adapters_refuse_intents_without_a_queue() {
+        let (base, arm) = (RecordingAdapter::new(), RecordingAdapter::new());
+        let manager = AdapterManager::new()
+            .with_adapter("base", base.clone())
+            .with_adapter("arm", arm.clone())
+            .with_link("base", "ros2_bridge")
+            .with_default_route(["base", "arm"]);
+
+        manager.link_changed("ros2_bridge", LinkState::Disconnected).await;
+        assert!(!manager.is_connected("base"));
+        assert!(manager.is_connected("arm"));
+        let err = manager.execute_intent(drive()).await.unwrap_err();
+        assert!(
+            matches!(&err, MechError::AdapterUnavailable { adapter, .. } if adapter == "base"),
+            "{err:?}"
+        );
+        // The connected adapter still receives the intent.
+        assert_eq!(arm.received(), vec!["Drive"]);
+        assert!(base.received().is_empty());
+
+        manager.link_changed("ros2_bridge", LinkState::Connected).await;
+        manager.execute_intent(drive()).await.unwrap();
+        assert_eq!(base.received(), vec!["Drive"]);
+    }
+
+    #[tokio::test]
+    async fn offline_queue_flushes_fresh_intents_on_reconnect() {
+        let base = RecordingAdapter::new();
+        let manager = AdapterManager::new()
+            .with_adapter("base", base.clone())
+            .with_link("base", "dashboard_sim")
+            .with_default_route(["base"])
+            .with_offline_queue(2, Duration::from_secs(60));
+        let soon = Utc::now() + chrono::Duration::milliseconds(20);
+
+        manager.link_changed("dashboard_sim", LinkState::Disconnected).await;
+        manager.link_changed("dashboard_sim", LinkState::Connecting).await;
+        manager.execute_intent(HardwareIntent::Dock).await.unwrap();
+        manager
+            .execute_envelope(IntentEnvelope::new(drive(), "runtime").with_deadline(soon))
+            .await
+            .unwrap();
+        manager.execute_intent(move_joint("elbow")).await.unwrap();
+        // The oldest intent made way for the newest.
+        assert_eq!(manager.queued("base"), 2);
+        assert!(base.received().is_empty());
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        manager.link_changed("dashboard_sim", LinkState::Connected).await;
+        // The expired Drive was dropped on the way out.
+        assert_eq!(base.received(), vec!["MoveJoint"]);
+        assert_eq!(manager.queued("base"), 0);
+        assert!(manager.is_connected("base"));
+    }
+
+    #[tokio::test]
+    async fn offline_queue_drops_intents_older_than_its_maximum_age() {
+        let arm = RecordingAdapter::new();
+        let manager = AdapterManager::new()
+            .with_adapter("arm", arm.clone())
+            .with_link("arm", "ros2_bridge")
+            .with_default_route(["arm"])
+            .with_offline_queue(8, Duration::from_millis(20));
+
+        manager.link_changed("ros2_bridge", LinkState::Disconnected).await;
+        // No deadline: only the queue's maximum age keeps it from replaying.
+        manager.execute_intent(move_joint("elbow")).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        manager.execute_intent(HardwareIntent::Dock).await.unwrap();
+        assert_eq!(manager.queued("arm"), 2);
+
+        manager.link_changed("ros2_bridge", LinkState::Connected).await;
+        assert_eq!(arm.received(), vec!["Dock"]);
+    }
+
+    #[tokio::test]
+    async fn halt_discards_the_offline_queue() {
+        let base = RecordingAdapter::new();
+        let manager = AdapterManager::new()
+            .with_adapter("base", base.clone())
+            .with_link("base", "ros2_bridge")
+            .with_default_route(["base"])
+            .with_offline_queue(8, Duration::from_secs(60));
+
+        manager.link_changed("ros2_bridge", LinkState::Disconnected).await;
+        manager.execute_intent(drive()).await.unwrap();
+        let halt = HardwareIntent::Halt {
+            reason: "test".to_string(),
+        };
+        assert!(matches!(
+            manager.execute_intent(halt).await,
+            Err(MechError::AdapterUnavailable { .. })
+        ));
+        assert_eq!(manager.queued("base"), 0);
+
+        manager.link_changed("ros2_bridge", LinkState::Connected).await;
+        assert!(base.received().is_empty());
+    }
+
+    #[tokio::test]
+    async fn sensor_streams_are_fanned_in() {
+        let manager = AdapterManager::new()
+            .with_adapter("a", RecordingAdapter::with(false, vec![10.0, 20.0]))
+            .with_adapter("b", RecordingAdapter::with(false, vec![30.0]));
+        let bus = EventBus::default();
+        let mut rx = bus.subscribe();
+
+        manager.forward_sensors(&bus).await;
+        let mut sources = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            sources.push(event.source);
+        }
+        sources.sort();
+        assert_eq!(
+            sources,
+            vec![
+                "mechos-middleware::adapter/a",
+                "mechos-middleware::adapter/a",
+                "mechos-middleware::adapter/b",
+            ]
+        );
+        assert_eq!(manager.health_of("a").unwrap().sensor_events, 2);
+        assert_eq!(manager.sensor_stream().await.count().await, 3);
+    }
+
+    #[tokio::test]
+    async fn run_executes_intents_from_the_bus() {
+        let base = RecordingAdapter::new();
+        let manager = Arc::new(
+            AdapterManager::new()
+                .with_adapter("base", base.clone())
+                .with_default_route(["base"]),
+        );
+        let bus = Arc::new(EventBus::default());
+        let running = tokio::spawn({
+            let (manager, bus) = (Arc::clone(&manager), Arc::clone(&bus));
+            async move { manager.run(&bus).await }
+        });
+        let intent = Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: "test".to_string(),
+            payload: EventPayload::Intent(IntentEnvelope::new(drive(), "runtime")),
+            trace_id: None,
+            correlation_id: None,
+        };
+        // Publishing fails until the manager has subscribed.
+        while bus.publish(intent.clone()).is_err() {
+            tokio::task::yield_now().await;
+        }
+        while manager.health_of("base").unwrap().intents_executed == 0 {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(base.received(), vec!["Drive"]);
+        running.abort();
+    }
+
+    #[tokio::test]
+    async fn run_continues_the_intent_events_trace() {
+        use opentelemetry::trace::TracerProvider as _;
+
+        let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder().build();
+        let subscriber = tracing_subscriber::layer::SubscriberExt::with(
+            tracing_subscriber::registry(),
+            tracing_opentelemetry::layer().with_tracer(provider.tracer("test")),
+        );
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let base = RecordingAdapter::new();
+        let manager = Arc::new(
+            AdapterManager::new()
+                .with_adapter("base", base.clone())
+                .with_default_route(["base"]),
+        );
+        let bus = Arc::new(EventBus::default());
+        let running = tokio::spawn({
+            let (manager, bus) = (Arc::clone(&manager), Arc::clone(&bus));
+            async move { manager.run(&bus).await }
+        });
+        let trace = "4bf92f3577b34da6a3ce929d0e0e4736";
+        let intent = Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: "test".to_string(),
+            payload: EventPayload::Intent(IntentEnvelope::new(drive(), "runtime")),
+            trace_id: Some(format!("00-{trace}-00f067aa0ba902b7-01")),
+            correlation_id: None,
+        };
+        while bus.publish(intent.clone()).is_err() {
+            tokio::task::yield_now().await;
+        }
+        while manager.health_of("base").unwrap().intents_executed == 0 {
+            tokio::task::yield_now().await;
+        }
+        running.abort();
+
+        // The adapter ran in a child span of the same trace.
+        let traces = base.traces.lock().unwrap().clone();
+        let traceparent = traces[0].as_deref().expect("adapter ran inside a span");
+        assert!(traceparent.starts_with(&format!("00-{trace}-")), "{traceparent}");
+        assert!(!traceparent.contains("00f067aa0ba902b7"), "{traceparent}");
+    }
+}
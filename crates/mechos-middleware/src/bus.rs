@@ -1,32 +1,67 @@
 //! Headless, typed, topic-based publish/subscribe event bus.
 //!
-//! Uses [`tokio::sync::broadcast`] channels under the hood so that every
-//! subscriber receives every message without any single subscriber blocking
-//! the others.
+//! The global (legacy) channel is a [`tokio::sync::broadcast`] channel.  Each
+//! topic lane instead gives every subscriber its own bounded queue, so a slow
+//! consumer only ever loses its own messages and never blocks the others.
 //!
 //! # Topics
 //!
-//! Traffic is partitioned into five [`Topic`] lanes so components only
+//! Traffic is partitioned into six [`Topic`] lanes so components only
 //! receive the messages they care about:
 //!
 //! | Topic | Typical traffic |
 //! |---|---|
-//! | [`Topic::Telemetry`] | High-frequency sensor data (odometry, LiDAR, battery) |
+//! | [`Topic::Telemetry`] | Low-rate robot state (odometry, battery, GPS) |
+//! | [`Topic::SensorHighRate`] | Bulk sensor streams (LiDAR scans, camera frames) |
 //! | [`Topic::HardwareCommands`] | Low-frequency, high-priority actuation intents |
 //! | [`Topic::SystemAlerts`] | Critical OS-level events (faults, manual overrides) |
 //! | [`Topic::SwarmComm`] | Peer-to-peer fleet messages |
 //! | [`Topic::CognitiveStream`] | LLM "thoughts" and `AskHuman` requests |
+//!
+//! # Backpressure
+//!
+//! What happens when a subscriber's queue is full is decided per topic by a
+//! [`BackpressurePolicy`]:
+//!
+//! | Policy | Full queue |
+//! |---|---|
+//! | [`BackpressurePolicy::DropOldest`] (default) | The oldest queued event is evicted |
+//! | [`BackpressurePolicy::DropNewest`] | The incoming event is discarded |
+//! | [`BackpressurePolicy::Block`] | [`EventBus::publish_to_async`] waits for room, up to a timeout |
+//!
+//! Either way the subscriber's next [`TopicReceiver::recv`] reports
+//! `Lagged(n)` before resuming, and [`EventBus::subscriber_stats`] exposes
+//! per-subscriber queue depth and drop counters.
+//!
+//! [`Topic::SensorHighRate`] subscribers get [`SENSOR_HIGH_RATE_CAPACITY_FACTOR`]
+//! times the bus capacity, so a burst of scans is absorbed (or evicts stale
+//! scans) on its own lane instead of competing with state and alerts.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+use std::time::Duration;
 
+use chrono::Utc;
 use mechos_types::{Event, EventPayload, MechError};
-use tokio::sync::broadcast;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Notify, broadcast};
+use tokio::time::Instant;
 use tracing::warn;
+use uuid::Uuid;
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 use opentelemetry::trace::TraceContextExt;
 
-/// Default channel capacity (number of buffered events before old ones are
-/// dropped for slow subscribers).
+use crate::typed::{TopicPayload, TypedReceiver};
+
+/// Default channel capacity (number of buffered events per subscriber before
+/// the topic's [`BackpressurePolicy`] kicks in).
 const DEFAULT_CAPACITY: usize = 256;
 
+/// Multiplier applied to the bus capacity for [`Topic::SensorHighRate`]
+/// subscriber queues.
+pub const SENSOR_HIGH_RATE_CAPACITY_FACTOR: usize = 4;
+
 /// Maximum estimated serialized byte size of an [`Event`] placed on the bus.
 ///
 /// This is a defense-in-depth limit: individual adapters and WebSocket
@@ -48,7 +83,7 @@ fn estimate_event_size(event: &Event) -> usize {
     //   "payload":    key + quotes + colon = ~11
     //   JSON object braces and outer punctuation = ~10
     // Total structural overhead ≈ 121; rounded up to 200 as a safe margin
-    // that also covers optional "trace_id" when present.
+    // that also covers the optional "trace_id" key and "correlation_id" UUID.
     let base = 200
         + event.source.len()
         + event.trace_id.as_deref().map_or(0, |t| t.len());
@@ -63,8 +98,12 @@ fn estimate_event_size(event: &Event) -> usize {
         EventPayload::HardwareFault { component, message, .. } => {
             component.len() + message.len() + VARIANT_OVERHEAD
         }
-        EventPayload::AgentThought(s) => s.len(),
-        EventPayload::HumanResponse(s) => s.len(),
+        EventPayload::Reasoning(s) => s.len(),
+        EventPayload::HumanResponse(a) => {
+            a.answer.len()
+                + a.question_id.as_ref().map_or(0, String::len)
+                + a.operator.as_ref().map_or(0, String::len)
+        }
         EventPayload::PeerMessage { from_robot_id, message } => {
             from_robot_id.len() + message.len() + VARIANT_OVERHEAD
         }
@@ -73,17 +112,138 @@ fn estimate_event_size(event: &Event) -> usize {
         // field names, brackets, and punctuation.
         EventPayload::LidarScan { ranges, .. } => ranges.len() * 15 + VARIANT_OVERHEAD,
         EventPayload::AgentModeToggle { .. } => 30,
+        EventPayload::SafetyRequest { request_id, .. } => request_id.len() + VARIANT_OVERHEAD,
+        EventPayload::SafetyStatus { request_id, .. } => {
+            request_id.as_deref().map_or(0, str::len) + VARIANT_OVERHEAD
+        }
+        EventPayload::ApprovalRequest {
+            request_id,
+            envelope,
+            report,
+        } => {
+            let mut counter = ByteCounter(0);
+            let _ = serde_json::to_writer(&mut counter, envelope);
+            let _ = serde_json::to_writer(&mut counter, report);
+            request_id.len() + counter.0 + VARIANT_OVERHEAD
+        }
+        EventPayload::ApprovalDecision {
+            request_id,
+            operator,
+            reason,
+            ..
+        } => {
+            request_id.len()
+                + operator.len()
+                + reason.as_deref().map_or(0, str::len)
+                + VARIANT_OVERHEAD
+        }
+        EventPayload::PolicyRequest {
+            request_id,
+            operator,
+            command,
+        } => {
+            let mut counter = ByteCounter(0);
+            let _ = serde_json::to_writer(&mut counter, command);
+            request_id.len() + operator.len() + counter.0 + VARIANT_OVERHEAD
+        }
+        EventPayload::PolicyStatus {
+            request_id,
+            report,
+            error,
+        } => {
+            let mut counter = ByteCounter(0);
+            let _ = serde_json::to_writer(&mut counter, report);
+            request_id.len() + error.as_deref().map_or(0, str::len) + counter.0 + VARIANT_OVERHEAD
+        }
+        EventPayload::PowerStatus { .. } => VARIANT_OVERHEAD,
+        EventPayload::Unknown(raw) => {
+            let mut counter = ByteCounter(0);
+            let _ = serde_json::to_writer(&mut counter, raw);
+            counter.0
+        }
+        EventPayload::CameraFrame {
+            image_id, data_b64, ..
+        } => image_id.len() + data_b64.len() + VARIANT_OVERHEAD,
+        EventPayload::Attitude { .. }
+        | EventPayload::GpsFix { .. }
+        | EventPayload::TrackedObstacle { .. } => VARIANT_OVERHEAD,
+        EventPayload::ConnectionState { component, .. } => component.len() + VARIANT_OVERHEAD,
+        // Five fields, so more structure than two-string variants.
+        EventPayload::IntentResult {
+            goal_id,
+            action,
+            message,
+            ..
+        } => goal_id.len() + action.len() + message.len() + 2 * VARIANT_OVERHEAD,
+        // Nine fields, all numbers except the cell data.
+        EventPayload::MapSnapshot { cells_b64, .. } => cells_b64.len() + 2 * VARIANT_OVERHEAD,
+        EventPayload::TaskProgress {
+            task_id,
+            robot_id,
+            note,
+            ..
+        } => task_id.len() + robot_id.len() + note.len() + VARIANT_OVERHEAD,
+        EventPayload::TaskCompleted {
+            task_id,
+            robot_id,
+            result,
+        } => {
+            let mut counter = ByteCounter(0);
+            let _ = serde_json::to_writer(&mut counter, result);
+            task_id.len() + robot_id.len() + counter.0 + VARIANT_OVERHEAD
+        }
+        // Six fields per lane: names, numbers and punctuation stay well under
+        // 160 bytes besides the lane name.
+        EventPayload::BusHealth { lanes } => {
+            lanes
+                .iter()
+                .map(|lane| lane.lane.len() + 160)
+                .sum::<usize>()
+                + VARIANT_OVERHEAD
+        }
+        // Intents carry free-form, variable-length fields (speech text,
+        // waypoint lists); count their exact encoding without buffering it.
+        EventPayload::Intent(envelope) => {
+            let mut counter = ByteCounter(0);
+            let _ = serde_json::to_writer(&mut counter, envelope);
+            counter.0
+        }
+        EventPayload::IntentDispatched(intent) => {
+            let mut counter = ByteCounter(0);
+            let _ = serde_json::to_writer(&mut counter, intent);
+            counter.0
+        }
+        EventPayload::AdapterCommand { topic, json } => {
+            let mut counter = ByteCounter(0);
+            let _ = serde_json::to_writer(&mut counter, json);
+            topic.len() + counter.0 + VARIANT_OVERHEAD
+        }
     };
     base + payload_size
 }
 
+/// [`std::io::Write`] sink that only counts the bytes written to it.
+struct ByteCounter(usize);
+
+impl std::io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
 /// Enumeration of all first-class routing topics on the event bus.
 ///
 /// Publishers and subscribers reference a `Topic` variant to ensure
 /// messages are delivered only to the correct topic channel.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Topic {
-    /// High-frequency sensor data: odometry, LiDAR scans, battery levels.
+    /// Low-rate robot state: odometry, battery levels, attitude, GPS.
     Telemetry,
     /// Low-frequency, high-priority actuation intents sent to the HAL.
     HardwareCommands,
@@ -93,50 +253,164 @@ pub enum Topic {
     SwarmComm,
     /// Internal LLM reasoning output and `AskHuman` requests.
     CognitiveStream,
+    /// Bulk, high-frequency sensor streams: LiDAR scans and camera frames.
+    /// Has a larger queue per subscriber and drops the oldest sample when
+    /// full.
+    SensorHighRate,
+}
+
+impl Topic {
+    /// Every topic, in lane order.
+    pub const ALL: [Topic; 6] = [
+        Topic::Telemetry,
+        Topic::HardwareCommands,
+        Topic::SystemAlerts,
+        Topic::SwarmComm,
+        Topic::CognitiveStream,
+        Topic::SensorHighRate,
+    ];
+
+    /// The topic's snake_case name, as serialised, e.g. `"system_alerts"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Topic::Telemetry => "telemetry",
+            Topic::HardwareCommands => "hardware_commands",
+            Topic::SystemAlerts => "system_alerts",
+            Topic::SwarmComm => "swarm_comm",
+            Topic::CognitiveStream => "cognitive_stream",
+            Topic::SensorHighRate => "sensor_high_rate",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Per-subscriber queue size of this topic on a bus of `capacity`.
+    fn lane_capacity(self, capacity: usize) -> usize {
+        match self {
+            Topic::SensorHighRate => capacity.saturating_mul(SENSOR_HIGH_RATE_CAPACITY_FACTOR),
+            _ => capacity,
+        }
+    }
+}
+
+/// What a topic lane does when a subscriber's queue is full.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BackpressurePolicy {
+    /// Evict the subscriber's oldest queued event to make room.  Suits
+    /// streams where only the latest sample matters (LiDAR, odometry).
+    #[default]
+    DropOldest,
+    /// Discard the incoming event for that subscriber and keep its backlog.
+    DropNewest,
+    /// Let [`EventBus::publish_to_async`] wait up to the given timeout for
+    /// the subscriber to make room, then discard the event for it.  The
+    /// synchronous [`EventBus::publish_to`] cannot wait and treats a full
+    /// queue like [`DropNewest`](Self::DropNewest).
+    Block(Duration),
+}
+
+/// Queue metrics for one topic subscriber, from [`EventBus::subscriber_stats`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubscriberStats {
+    /// Bus-assigned subscriber id, unique per topic.
+    pub id: u64,
+    /// Label given to [`EventBus::subscribe_to_named`], if any.
+    pub name: Option<String>,
+    /// Events waiting to be received.
+    pub queued: usize,
+    /// Maximum number of queued events.
+    pub capacity: usize,
+    /// Events handed to the subscriber over its lifetime.
+    pub delivered: u64,
+    /// Events the subscriber lost to backpressure over its lifetime.
+    pub dropped: u64,
+    /// Dropped events not yet reported to the subscriber as `Lagged`.
+    pub lagging: u64,
+}
+
+/// Traffic counters of one topic lane, from [`EventBus::stats`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TopicStats {
+    pub topic: Topic,
+    /// Events published to the topic (with at least one subscriber).
+    pub published: u64,
+    /// Current number of subscribers.
+    pub subscribers: usize,
+    /// Dropped events not yet reported to their subscribers as `Lagged`.
+    pub lagging: u64,
+    /// Events lost to backpressure, including by departed subscribers.
+    pub dropped: u64,
+}
+
+/// Snapshot of the whole bus, from [`EventBus::stats`].  Counters are
+/// cumulative since the bus was created.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BusStats {
+    /// Events accepted by the global channel.
+    pub global_published: u64,
+    /// Current receivers of the global channel.
+    pub global_subscribers: usize,
+    /// One entry per topic, in [`Topic::ALL`] order.
+    pub topics: Vec<TopicStats>,
 }
 
 /// Shared event bus. Clone it cheaply – all clones share the same underlying
-/// broadcast channels.
+/// channels.
 ///
 /// The bus exposes two APIs:
 ///
 /// * **Topic-based** (`publish_to` / `subscribe_to`) – routes events to one
-///   of the five [`Topic`] lanes.  Preferred for new code.
+///   of the six [`Topic`] lanes.  Preferred for new code; `publish_typed` /
+///   `subscribe_typed` do the same with decoded [`crate::typed`] payloads,
+///   and `subscribe_many` / `subscribe_all_topics` merge several lanes.
 /// * **Global** (`publish` / `subscribe`) – a single broadcast channel used
 ///   by legacy adapters and bridges that pre-date topic routing.
 #[derive(Clone, Debug)]
 pub struct EventBus {
     // Global (legacy) channel
     sender: broadcast::Sender<Event>,
-    // Per-topic channels
-    telemetry: broadcast::Sender<Event>,
-    hardware_commands: broadcast::Sender<Event>,
-    system_alerts: broadcast::Sender<Event>,
-    swarm_comm: broadcast::Sender<Event>,
-    cognitive_stream: broadcast::Sender<Event>,
+    // Events accepted by the global channel
+    global_published: Arc<AtomicU64>,
+    // Per-topic lanes, indexed by `Topic::index`
+    lanes: Arc<[Arc<TopicLane>; 6]>,
 }
 
 impl EventBus {
     /// Create a new bus with the given channel capacity.
     ///
-    /// The `capacity` is applied to every topic channel independently.
+    /// The `capacity` is applied to the global channel and to every topic
+    /// subscriber's queue independently; [`Topic::SensorHighRate`] queues
+    /// hold [`SENSOR_HIGH_RATE_CAPACITY_FACTOR`] times as many events.
     pub fn new(capacity: usize) -> Self {
         let (sender, _) = broadcast::channel(capacity);
-        let (telemetry, _) = broadcast::channel(capacity);
-        let (hardware_commands, _) = broadcast::channel(capacity);
-        let (system_alerts, _) = broadcast::channel(capacity);
-        let (swarm_comm, _) = broadcast::channel(capacity);
-        let (cognitive_stream, _) = broadcast::channel(capacity);
         Self {
             sender,
-            telemetry,
-            hardware_commands,
-            system_alerts,
-            swarm_comm,
-            cognitive_stream,
+            global_published: Arc::new(AtomicU64::new(0)),
+            lanes: Arc::new(
+                Topic::ALL.map(|topic| Arc::new(TopicLane::new(topic.lane_capacity(capacity)))),
+            ),
         }
     }
 
+    /// Builder: set the [`BackpressurePolicy`] of `topic`.
+    pub fn with_policy(self, topic: Topic, policy: BackpressurePolicy) -> Self {
+        self.set_policy(topic, policy);
+        self
+    }
+
+    /// Change the [`BackpressurePolicy`] of `topic`.  Applies to every clone
+    /// of this bus and to events published from now on.
+    pub fn set_policy(&self, topic: Topic, policy: BackpressurePolicy) {
+        *lock(&self.lane(topic).policy) = policy;
+    }
+
+    /// The [`BackpressurePolicy`] currently applied to `topic`.
+    pub fn policy(&self, topic: Topic) -> BackpressurePolicy {
+        *lock(&self.lane(topic).policy)
+    }
+
     // -----------------------------------------------------------------------
     // Topic-based API
     // -----------------------------------------------------------------------
@@ -150,37 +424,210 @@ impl EventBus {
     /// The event's `trace_id` field is automatically populated from the
     /// current OpenTelemetry span context (or the tracing span ID when no
     /// OTel provider is active) if `trace_id` is `None`.
-    pub fn publish_to(&self, topic: Topic, mut event: Event) -> Result<usize, MechError> {
-        // ── Payload size guard ─────────────────────────────────────────────
-        let size = estimate_event_size(&event);
-        if size > MAX_EVENT_PAYLOAD_BYTES {
-            return Err(MechError::Parsing(format!(
-                "event payload estimated at {size} bytes exceeds limit of {MAX_EVENT_PAYLOAD_BYTES}"
-            )));
-        }
-        if event.trace_id.is_none() {
-            event.trace_id = Self::current_trace_id();
-        }
-        let sender = self.topic_sender(topic);
-        match sender.send(event) {
-            Ok(n) => Ok(n),
-            Err(tokio::sync::broadcast::error::SendError(_)) => {
-                // Return an error when there are no subscribers on the topic,
-                // handling broadcast::error::SendError.
-                Err(MechError::Channel(format!("No subscribers for topic {:?}", topic)))
+    ///
+    /// Subscribers with a full queue are handled by the topic's
+    /// [`BackpressurePolicy`] without waiting; use
+    /// [`publish_to_async`](Self::publish_to_async) to honour
+    /// [`BackpressurePolicy::Block`].
+    pub fn publish_to(&self, topic: Topic, event: Event) -> Result<usize, MechError> {
+        let (event, subscribers) = self.prepare_topic_publish(topic, event)?;
+        let policy = self.policy(topic);
+        Ok(subscribers
+            .iter()
+            .filter(|queue| queue.offer(event.clone(), policy))
+            .count())
+    }
+
+    /// Publish `event` to the given [`Topic`] channel, waiting for room in
+    /// full subscriber queues when the topic's policy is
+    /// [`BackpressurePolicy::Block`].
+    ///
+    /// The timeout bounds the whole call, not each subscriber: once it
+    /// elapses the event is discarded for every subscriber still full.
+    /// Under the other policies this behaves exactly like
+    /// [`publish_to`](Self::publish_to).
+    pub async fn publish_to_async(&self, topic: Topic, event: Event) -> Result<usize, MechError> {
+        let (event, subscribers) = self.prepare_topic_publish(topic, event)?;
+        let policy = self.policy(topic);
+        let BackpressurePolicy::Block(timeout) = policy else {
+            return Ok(subscribers
+                .iter()
+                .filter(|queue| queue.offer(event.clone(), policy))
+                .count());
+        };
+        let deadline = Instant::now() + timeout;
+        let mut delivered = 0;
+        for queue in &subscribers {
+            loop {
+                let room = queue.room.notified();
+                match queue.try_push(&event) {
+                    Ok(()) => {
+                        delivered += 1;
+                        break;
+                    }
+                    Err(PushError::Full) => {}
+                    Err(PushError::Detached) => break,
+                }
+                if tokio::time::timeout_at(deadline, room).await.is_err() {
+                    queue.record_drop();
+                    break;
+                }
             }
         }
+        Ok(delivered)
     }
 
     /// Subscribe to a specific [`Topic`] channel.
     ///
-    /// The returned [`broadcast::Receiver`] yields only events published to
-    /// that topic.  Wrap it with [`TopicReceiver`] for ergonomic async
-    /// iteration.
+    /// The returned [`TopicReceiver`] owns a bounded queue of
+    /// [`new`](Self::new)'s `capacity` events (scaled up for
+    /// [`Topic::SensorHighRate`]) and yields only events published to that
+    /// topic.
     pub fn subscribe_to(&self, topic: Topic) -> TopicReceiver {
-        TopicReceiver {
-            topic,
-            receiver: self.topic_sender(topic).subscribe(),
+        self.lane(topic).subscribe(topic, None)
+    }
+
+    /// Like [`subscribe_to`](Self::subscribe_to), labelling the subscriber
+    /// with `name` in [`subscriber_stats`](Self::subscriber_stats).
+    pub fn subscribe_to_named(&self, topic: Topic, name: impl Into<String>) -> TopicReceiver {
+        self.lane(topic).subscribe(topic, Some(name.into()))
+    }
+
+    /// Subscribe to several topics through one receiver that interleaves
+    /// them fairly; see [`MultiTopicReceiver`].  Duplicate topics are
+    /// subscribed once.
+    pub fn subscribe_many(&self, topics: &[Topic]) -> MultiTopicReceiver {
+        MultiTopicReceiver::new(topics, |topic| self.subscribe_to(topic))
+    }
+
+    /// Like [`subscribe_many`](Self::subscribe_many), labelling each lane's
+    /// subscriber with `name` in [`subscriber_stats`](Self::subscriber_stats).
+    pub fn subscribe_many_named(
+        &self,
+        topics: &[Topic],
+        name: impl Into<String>,
+    ) -> MultiTopicReceiver {
+        let name = name.into();
+        MultiTopicReceiver::new(topics, |topic| self.subscribe_to_named(topic, name.clone()))
+    }
+
+    /// Subscribe to every topic lane through one receiver; shorthand for
+    /// `subscribe_many(&Topic::ALL)`.
+    pub fn subscribe_all_topics(&self) -> MultiTopicReceiver {
+        self.subscribe_many(&Topic::ALL)
+    }
+
+    /// Queue metrics for every live subscriber of `topic`, oldest first.
+    pub fn subscriber_stats(&self, topic: Topic) -> Vec<SubscriberStats> {
+        lock(&self.lane(topic).subscribers)
+            .iter()
+            .map(|queue| queue.stats())
+            .collect()
+    }
+
+    /// Traffic counters of every lane; see [`BusMonitor`](crate::monitor::BusMonitor)
+    /// for periodic reports with publish rates.
+    pub fn stats(&self) -> BusStats {
+        let topics = Topic::ALL
+            .iter()
+            .map(|&topic| {
+                let lane = self.lane(topic);
+                let subscribers = lock(&lane.subscribers);
+                let (lagging, dropped) = subscribers.iter().map(|queue| queue.stats()).fold(
+                    (0, lane.retired_dropped.load(Ordering::Relaxed)),
+                    |(l, d), s| (l + s.lagging, d + s.dropped),
+                );
+                TopicStats {
+                    topic,
+                    published: lane.published.load(Ordering::Relaxed),
+                    subscribers: subscribers.len(),
+                    lagging,
+                    dropped,
+                }
+            })
+            .collect();
+        BusStats {
+            global_published: self.global_published.load(Ordering::Relaxed),
+            global_subscribers: self.sender.receiver_count(),
+            topics,
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Typed API
+    // -----------------------------------------------------------------------
+
+    /// Wrap `payload` in a fresh [`Event`] from `source` and publish it on
+    /// its [`TopicPayload::TOPIC`].  See [`publish_to`](Self::publish_to).
+    pub fn publish_typed<T: TopicPayload>(
+        &self,
+        source: impl Into<String>,
+        payload: T,
+    ) -> Result<usize, MechError> {
+        let event = Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: source.into(),
+            payload: payload.into(),
+            trace_id: None,
+            correlation_id: None,
+        };
+        self.publish_to(T::TOPIC, event)
+    }
+
+    /// Subscribe to `T`'s topic, receiving only `T` payloads, decoded.
+    pub fn subscribe_typed<T: TopicPayload>(&self) -> TypedReceiver<T> {
+        TypedReceiver::new(self.subscribe_to(T::TOPIC))
+    }
+
+    // -----------------------------------------------------------------------
+    // Request / response
+    // -----------------------------------------------------------------------
+
+    /// Publish `request` on `topic` and wait for the reply: the first event
+    /// on the same topic whose `correlation_id` is the request's `id`.
+    ///
+    /// Replies are sent by a [`Responder`](crate::rpc::Responder) (or anything
+    /// else that sets `correlation_id`); unrelated traffic on the topic is
+    /// skipped.
+    ///
+    /// # Errors
+    ///
+    /// - [`MechError::Parsing`] – the request exceeds
+    ///   [`MAX_EVENT_PAYLOAD_BYTES`].
+    /// - [`MechError::Channel`] – nobody else subscribes to `topic`, no reply
+    ///   arrived within `timeout`, or the bus shut down.
+    pub async fn request(
+        &self,
+        topic: Topic,
+        request: Event,
+        timeout: Duration,
+    ) -> Result<Event, MechError> {
+        let id = request.id;
+        // Subscribe first so a fast reply cannot be missed.
+        let mut replies = self.subscribe_to(topic);
+        if self.publish_to(topic, request)? <= 1 {
+            return Err(MechError::Channel(format!(
+                "No subscribers for topic {topic:?}"
+            )));
+        }
+        let deadline = Instant::now() + timeout;
+        loop {
+            let received = tokio::time::timeout_at(deadline, replies.recv())
+                .await
+                .map_err(|_| {
+                    MechError::Channel(format!("no reply to request {id} within {timeout:?}"))
+                })?;
+            match received {
+                Ok(event) if event.correlation_id == Some(id) => return Ok(event),
+                Ok(_) => continue,
+                Err(broadcast::error::RecvError::Lagged(n)) => {
+                    warn!(%id, lagged_by = n, "request reply lane lagged");
+                }
+                Err(broadcast::error::RecvError::Closed) => {
+                    return Err(MechError::Channel("event bus closed".to_string()));
+                }
+            }
         }
     }
 
@@ -207,9 +654,12 @@ impl EventBus {
         if event.trace_id.is_none() {
             event.trace_id = Self::current_trace_id();
         }
-        self.sender.send(event).map_err(|e| {
-            MechError::Channel(format!("event bus send error: {e}"))
-        })
+        let delivered = self
+            .sender
+            .send(event)
+            .map_err(|e| MechError::Channel(format!("event bus send error: {e}")))?;
+        self.global_published.fetch_add(1, Ordering::Relaxed);
+        Ok(delivered)
     }
 
     /// Subscribe to all events on the global broadcast channel.
@@ -233,14 +683,44 @@ impl EventBus {
     // Internal helpers
     // -----------------------------------------------------------------------
 
-    fn topic_sender(&self, topic: Topic) -> &broadcast::Sender<Event> {
-        match topic {
-            Topic::Telemetry => &self.telemetry,
-            Topic::HardwareCommands => &self.hardware_commands,
-            Topic::SystemAlerts => &self.system_alerts,
-            Topic::SwarmComm => &self.swarm_comm,
-            Topic::CognitiveStream => &self.cognitive_stream,
+    /// Receivers for the global channel (if `global`) and for `topics`
+    /// (merged into one, if any).
+    pub(crate) fn subscribe_lanes(&self, global: bool, topics: &[Topic]) -> Vec<LaneReceiver> {
+        let global = global.then(|| LaneReceiver::Global(self.subscribe()));
+        let topics = (!topics.is_empty()).then(|| LaneReceiver::Topics(self.subscribe_many(topics)));
+        global.into_iter().chain(topics).collect()
+    }
+
+    fn lane(&self, topic: Topic) -> &Arc<TopicLane> {
+        &self.lanes[topic.index()]
+    }
+
+    /// Size-check and trace-stamp `event`, and snapshot the topic's
+    /// subscribers.
+    fn prepare_topic_publish(
+        &self,
+        topic: Topic,
+        mut event: Event,
+    ) -> Result<(Event, Vec<Arc<SubscriberQueue>>), MechError> {
+        // ── Payload size guard ─────────────────────────────────────────────
+        let size = estimate_event_size(&event);
+        if size > MAX_EVENT_PAYLOAD_BYTES {
+            return Err(MechError::Parsing(format!(
+                "event payload estimated at {size} bytes exceeds limit of {MAX_EVENT_PAYLOAD_BYTES}"
+            )));
         }
+        if event.trace_id.is_none() {
+            event.trace_id = Self::current_trace_id();
+        }
+        let subscribers = lock(&self.lane(topic).subscribers).clone();
+        if subscribers.is_empty() {
+            return Err(MechError::Channel(format!(
+                "No subscribers for topic {:?}",
+                topic
+            )));
+        }
+        self.lane(topic).published.fetch_add(1, Ordering::Relaxed);
+        Ok((event, subscribers))
     }
 
     /// Extract a W3C `traceparent` header from the currently active span.
@@ -250,7 +730,7 @@ impl EventBus {
     /// that downstream consumers can use to re-link their own spans to the
     /// originating trace.  Otherwise the tracing-local span ID is returned as
     /// `"tracing:<id>"`.  Returns `None` when no span is currently active.
-    fn current_trace_id() -> Option<String> {
+    pub fn current_trace_id() -> Option<String> {
         let span = tracing::Span::current();
         let ctx = span.context();
         let otel_span = ctx.span();
@@ -267,6 +747,50 @@ impl EventBus {
         }
         span.id().map(|id| format!("tracing:{id:?}"))
     }
+
+    /// Parse a W3C `traceparent` (as produced by [`current_trace_id`]) into
+    /// an OpenTelemetry context holding it as the remote parent span.
+    ///
+    /// Returns `None` for `"tracing:<id>"` fallbacks and malformed values.
+    ///
+    /// [`current_trace_id`]: Self::current_trace_id
+    pub fn trace_context(traceparent: &str) -> Option<opentelemetry::Context> {
+        use opentelemetry::trace::{SpanContext, SpanId, TraceFlags, TraceId, TraceState};
+
+        let mut parts = traceparent.split('-');
+        let (Some("00"), Some(trace_id), Some(span_id), Some(flags), None) = (
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+        ) else {
+            return None;
+        };
+        if trace_id.len() != 32 || span_id.len() != 16 || flags.len() != 2 {
+            return None;
+        }
+        let sc = SpanContext::new(
+            TraceId::from_hex(trace_id).ok()?,
+            SpanId::from_hex(span_id).ok()?,
+            TraceFlags::new(u8::from_str_radix(flags, 16).ok()?),
+            true,
+            TraceState::default(),
+        );
+        sc.is_valid()
+            .then(|| opentelemetry::Context::new().with_remote_span_context(sc))
+    }
+
+    /// Make `span` a child of the span an event's `trace_id` points to, so
+    /// work done on behalf of the event joins the originating trace.
+    ///
+    /// Does nothing unless `trace_id` is a W3C `traceparent`.
+    pub fn link_span(span: &tracing::Span, trace_id: Option<&str>) {
+        if let Some(cx) = trace_id.and_then(Self::trace_context) {
+            // Fails only when no OpenTelemetry layer is installed.
+            let _ = span.set_parent(cx);
+        }
+    }
 }
 
 impl Default for EventBus {
@@ -281,10 +805,12 @@ impl Default for EventBus {
 
 /// An async receiver bound to a single [`Topic`] channel.
 ///
-/// Obtained via [`EventBus::subscribe_to`].
+/// Obtained via [`EventBus::subscribe_to`].  Owns a bounded queue on its
+/// topic lane; dropping the receiver unsubscribes it.
 pub struct TopicReceiver {
     topic: Topic,
-    receiver: broadcast::Receiver<Event>,
+    queue: Arc<SubscriberQueue>,
+    lane: Weak<TopicLane>,
 }
 
 impl TopicReceiver {
@@ -293,17 +819,334 @@ impl TopicReceiver {
     /// Returns:
     /// * `Ok(event)` – a successfully received event.
     /// * `Err(broadcast::error::RecvError::Lagged(n))` – the subscriber fell
-    ///   behind and `n` messages were dropped.  The caller decides whether to
-    ///   continue or abort.
+    ///   behind and `n` messages were dropped by the topic's
+    ///   [`BackpressurePolicy`].  The caller decides whether to continue or
+    ///   abort.
     /// * `Err(broadcast::error::RecvError::Closed)` – the bus has shut down.
+    ///
+    /// Cancel-safe: no event is lost when the future is dropped.
     pub async fn recv(&mut self) -> Result<Event, broadcast::error::RecvError> {
-        self.receiver.recv().await
+        loop {
+            match self.try_recv() {
+                Ok(event) => return Ok(event),
+                Err(broadcast::error::TryRecvError::Empty) => self.queue.ready.notified().await,
+                Err(broadcast::error::TryRecvError::Lagged(n)) => {
+                    return Err(broadcast::error::RecvError::Lagged(n));
+                }
+                Err(broadcast::error::TryRecvError::Closed) => {
+                    return Err(broadcast::error::RecvError::Closed);
+                }
+            }
+        }
+    }
+
+    /// Take the next queued event without waiting.
+    pub fn try_recv(&mut self) -> Result<Event, broadcast::error::TryRecvError> {
+        let mut state = lock(&self.queue.state);
+        if state.lagging > 0 {
+            return Err(broadcast::error::TryRecvError::Lagged(std::mem::take(
+                &mut state.lagging,
+            )));
+        }
+        match state.events.pop_front() {
+            Some(event) => {
+                state.delivered += 1;
+                drop(state);
+                self.queue.room.notify_one();
+                Ok(event)
+            }
+            None if state.closed => Err(broadcast::error::TryRecvError::Closed),
+            None => Err(broadcast::error::TryRecvError::Empty),
+        }
     }
 
     /// The [`Topic`] this receiver is bound to.
     pub fn topic(&self) -> Topic {
         self.topic
     }
+
+    /// This subscriber's current queue metrics.
+    pub fn stats(&self) -> SubscriberStats {
+        self.queue.stats()
+    }
+}
+
+impl Drop for TopicReceiver {
+    fn drop(&mut self) {
+        self.queue.close();
+        if let Some(lane) = self.lane.upgrade() {
+            lock(&lane.subscribers).retain(|queue| !Arc::ptr_eq(queue, &self.queue));
+            lane.retired_dropped
+                .fetch_add(self.queue.stats().dropped, Ordering::Relaxed);
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Multi-topic receiver
+// ---------------------------------------------------------------------------
+
+/// An async receiver merging several [`Topic`] lanes.
+///
+/// Obtained via [`EventBus::subscribe_many`] or
+/// [`EventBus::subscribe_all_topics`].  It owns one [`TopicReceiver`] per
+/// lane and polls them round-robin, starting after the lane that yielded
+/// last, so a flooded lane cannot starve a quiet one.
+pub struct MultiTopicReceiver {
+    receivers: Vec<TopicReceiver>,
+    next: usize,
+}
+
+impl MultiTopicReceiver {
+    fn new(topics: &[Topic], mut subscribe: impl FnMut(Topic) -> TopicReceiver) -> Self {
+        let mut receivers: Vec<TopicReceiver> = Vec::with_capacity(topics.len());
+        for &topic in topics {
+            if receivers.iter().all(|rx| rx.topic != topic) {
+                receivers.push(subscribe(topic));
+            }
+        }
+        Self { receivers, next: 0 }
+    }
+
+    /// Wait for the next event on any of the topics, returned with the
+    /// topic it arrived on.
+    ///
+    /// Errors as [`TopicReceiver::recv`]: `Lagged(n)` reports drops on one
+    /// lane, and `Closed` is returned once every lane has closed (at once
+    /// when subscribed to no topic).
+    ///
+    /// Cancel-safe: no event is lost when the future is dropped.
+    pub async fn recv(&mut self) -> Result<(Topic, Event), broadcast::error::RecvError> {
+        loop {
+            match self.try_recv() {
+                Ok(received) => return Ok(received),
+                Err(broadcast::error::TryRecvError::Empty) => {}
+                Err(broadcast::error::TryRecvError::Lagged(n)) => {
+                    return Err(broadcast::error::RecvError::Lagged(n));
+                }
+                Err(broadcast::error::TryRecvError::Closed) => {
+                    return Err(broadcast::error::RecvError::Closed);
+                }
+            }
+            // An event queued since `try_recv` leaves a stored permit, so
+            // this cannot miss a wakeup.
+            let ready = self
+                .receivers
+                .iter()
+                .map(|rx| Box::pin(rx.queue.ready.notified()));
+            futures_util::future::select_all(ready).await;
+        }
+    }
+
+    /// Take the next queued event from any of the topics without waiting.
+    pub fn try_recv(&mut self) -> Result<(Topic, Event), broadcast::error::TryRecvError> {
+        let count = self.receivers.len();
+        let mut closed = 0;
+        for offset in 0..count {
+            let index = (self.next + offset) % count;
+            let rx = &mut self.receivers[index];
+            match rx.try_recv() {
+                Err(broadcast::error::TryRecvError::Empty) => continue,
+                Err(broadcast::error::TryRecvError::Closed) => {
+                    closed += 1;
+                    continue;
+                }
+                received => {
+                    self.next = (index + 1) % count;
+                    return received.map(|event| (rx.topic, event));
+                }
+            }
+        }
+        Err(if closed == count {
+            broadcast::error::TryRecvError::Closed
+        } else {
+            broadcast::error::TryRecvError::Empty
+        })
+    }
+
+    /// The topics this receiver merges, in subscription order.
+    pub fn topics(&self) -> Vec<Topic> {
+        self.receivers.iter().map(TopicReceiver::topic).collect()
+    }
+}
+
+/// The global channel's broadcast receiver or a set of topic lanes.
+pub(crate) enum LaneReceiver {
+    Global(broadcast::Receiver<Event>),
+    Topics(MultiTopicReceiver),
+}
+
+impl LaneReceiver {
+    /// The next event, with its topic (`None` for the global channel).
+    pub(crate) async fn recv(
+        &mut self,
+    ) -> Result<(Option<Topic>, Event), broadcast::error::RecvError> {
+        match self {
+            Self::Global(rx) => rx.recv().await.map(|event| (None, event)),
+            Self::Topics(rx) => rx.recv().await.map(|(topic, event)| (Some(topic), event)),
+        }
+    }
+
+    pub(crate) fn try_recv(
+        &mut self,
+    ) -> Result<(Option<Topic>, Event), broadcast::error::TryRecvError> {
+        match self {
+            Self::Global(rx) => rx.try_recv().map(|event| (None, event)),
+            Self::Topics(rx) => rx.try_recv().map(|(topic, event)| (Some(topic), event)),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Topic lanes
+// ---------------------------------------------------------------------------
+
+/// One topic's policy and subscriber queues.
+#[derive(Debug)]
+struct TopicLane {
+    capacity: usize,
+    policy: Mutex<BackpressurePolicy>,
+    subscribers: Mutex<Vec<Arc<SubscriberQueue>>>,
+    next_id: AtomicU64,
+    published: AtomicU64,
+    /// Drops counted by subscribers that have since gone away.
+    retired_dropped: AtomicU64,
+}
+
+impl TopicLane {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            policy: Mutex::new(BackpressurePolicy::default()),
+            subscribers: Mutex::new(Vec::new()),
+            next_id: AtomicU64::new(0),
+            published: AtomicU64::new(0),
+            retired_dropped: AtomicU64::new(0),
+        }
+    }
+
+    fn subscribe(self: &Arc<Self>, topic: Topic, name: Option<String>) -> TopicReceiver {
+        let queue = Arc::new(SubscriberQueue {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            name,
+            capacity: self.capacity,
+            state: Mutex::new(QueueState::default()),
+            ready: Notify::new(),
+            room: Notify::new(),
+        });
+        lock(&self.subscribers).push(Arc::clone(&queue));
+        TopicReceiver {
+            topic,
+            queue,
+            lane: Arc::downgrade(self),
+        }
+    }
+}
+
+impl Drop for TopicLane {
+    /// The last bus clone is gone: wake every receiver so it sees `Closed`.
+    fn drop(&mut self) {
+        for queue in lock(&self.subscribers).iter() {
+            queue.close();
+        }
+    }
+}
+
+/// A single subscriber's bounded queue.
+#[derive(Debug)]
+struct SubscriberQueue {
+    id: u64,
+    name: Option<String>,
+    capacity: usize,
+    state: Mutex<QueueState>,
+    /// Signalled when an event is queued or the queue closes.
+    ready: Notify,
+    /// Signalled when the receiver frees a slot or goes away.
+    room: Notify,
+}
+
+#[derive(Debug, Default)]
+struct QueueState {
+    events: VecDeque<Event>,
+    delivered: u64,
+    dropped: u64,
+    lagging: u64,
+    closed: bool,
+}
+
+/// Why [`SubscriberQueue::try_push`] did not queue an event.
+enum PushError {
+    Full,
+    Detached,
+}
+
+impl SubscriberQueue {
+    /// Queue `event`, applying `policy` if the queue is full.  Returns
+    /// whether the event was queued.
+    fn offer(&self, event: Event, policy: BackpressurePolicy) -> bool {
+        let mut state = lock(&self.state);
+        if state.closed {
+            return false;
+        }
+        if state.events.len() >= self.capacity {
+            state.dropped += 1;
+            state.lagging += 1;
+            match policy {
+                BackpressurePolicy::DropOldest => {
+                    state.events.pop_front();
+                }
+                BackpressurePolicy::DropNewest | BackpressurePolicy::Block(_) => return false,
+            }
+        }
+        state.events.push_back(event);
+        drop(state);
+        self.ready.notify_one();
+        true
+    }
+
+    /// Queue a copy of `event` only if there is room.
+    fn try_push(&self, event: &Event) -> Result<(), PushError> {
+        let mut state = lock(&self.state);
+        if state.closed {
+            return Err(PushError::Detached);
+        }
+        if state.events.len() >= self.capacity {
+            return Err(PushError::Full);
+        }
+        state.events.push_back(event.clone());
+        drop(state);
+        self.ready.notify_one();
+        Ok(())
+    }
+
+    fn record_drop(&self) {
+        let mut state = lock(&self.state);
+        state.dropped += 1;
+        state.lagging += 1;
+    }
+
+    fn close(&self) {
+        lock(&self.state).closed = true;
+        self.ready.notify_one();
+        self.room.notify_waiters();
+    }
+
+    fn stats(&self) -> SubscriberStats {
+        let state = lock(&self.state);
+        SubscriberStats {
+            id: self.id,
+            name: self.name.clone(),
+            queued: state.events.len(),
+            capacity: self.capacity,
+            delivered: state.delivered,
+            dropped: state.dropped,
+            lagging: state.lagging,
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
 }
 
 // ---------------------------------------------------------------------------
@@ -342,7 +1185,7 @@ impl TopicSubscriber {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use mechos_types::{EventPayload, TelemetryData};
+    use mechos_types::{EventPayload, Meters, TelemetryData};
     use uuid::Uuid;
     use chrono::Utc;
 
@@ -352,12 +1195,14 @@ mod tests {
             timestamp: Utc::now(),
             source: source.to_string(),
             payload: EventPayload::Telemetry(TelemetryData {
-                position_x: 1.0,
-                position_y: 2.0,
+                position_x: Meters(1.0),
+                position_y: Meters(2.0),
                 heading_rad: 0.0,
                 battery_percent: 90,
+                stamp: None,
             }),
             trace_id: None,
+            correlation_id: None,
         }
     }
 
@@ -429,8 +1274,9 @@ mod tests {
             id: uuid::Uuid::new_v4(),
             timestamp: chrono::Utc::now(),
             source: "test".to_string(),
-            payload: EventPayload::AgentThought(huge),
+            payload: EventPayload::Reasoning(huge),
             trace_id: None,
+            correlation_id: None,
         };
         let result = bus.publish(event);
         assert!(
@@ -447,8 +1293,9 @@ mod tests {
             id: uuid::Uuid::new_v4(),
             timestamp: chrono::Utc::now(),
             source: "test".to_string(),
-            payload: EventPayload::HumanResponse(huge),
+            payload: EventPayload::HumanResponse(mechos_types::HumanAnswer::new(huge)),
             trace_id: None,
+            correlation_id: None,
         };
         let result = bus.publish_to(Topic::CognitiveStream, event);
         assert!(
@@ -457,6 +1304,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn oversized_operator_name_counts_towards_the_payload_limit() {
+        let bus = EventBus::default();
+        let answer = mechos_types::HumanAnswer::new("Yes")
+            .with_operator("o".repeat(MAX_EVENT_PAYLOAD_BYTES + 1));
+        let event = Event {
+            id: uuid::Uuid::new_v4(),
+            timestamp: chrono::Utc::now(),
+            source: "test".to_string(),
+            payload: EventPayload::HumanResponse(answer),
+            trace_id: None,
+            correlation_id: None,
+        };
+        assert!(matches!(
+            bus.publish_to(Topic::CognitiveStream, event),
+            Err(MechError::Parsing(_))
+        ));
+    }
+
     #[test]
     fn test_bus_publish_on_full_channel_returns_error() {
         // Wait, tokio's broadcast channel does not return an error when full; it drops the oldest message
@@ -586,6 +1452,30 @@ mod tests {
         }
     }
 
+    /// A W3C traceparent parses into a remote parent span context; the
+    /// tracing-local fallback and malformed values do not.
+    #[test]
+    fn trace_context_parses_only_w3c_traceparents() {
+        let cx = EventBus::trace_context("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01")
+            .expect("valid traceparent");
+        let sc = cx.span().span_context().clone();
+        assert!(sc.is_remote());
+        assert!(sc.is_sampled());
+        assert_eq!(sc.trace_id().to_string(), "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(sc.span_id().to_string(), "00f067aa0ba902b7");
+
+        for bad in [
+            "tracing:Id(1)",
+            "01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+            "00-xyz-00f067aa0ba902b7-01",
+        ] {
+            assert!(EventBus::trace_context(bad).is_none(), "{bad}");
+        }
+    }
+
     /// Events published from within a tracing span have their `trace_id`
     /// auto-populated by the bus.
     #[tokio::test]
@@ -617,4 +1507,192 @@ mod tests {
         // Drain the receiver so the test doesn't hang.
         let _ = rx.try_recv();
     }
+
+    // -----------------------------------------------------------------------
+    // Backpressure tests
+    // -----------------------------------------------------------------------
+
+    /// A burst on the sensor lane fills its larger queues, evicting the
+    /// oldest scans, and leaves alert subscribers untouched.
+    #[tokio::test]
+    async fn sensor_burst_stays_on_its_own_lane() {
+        let bus = EventBus::new(4);
+        let _scans = bus.subscribe_to(Topic::SensorHighRate);
+        let mut alerts = bus.subscribe_to(Topic::SystemAlerts);
+        for _ in 0..40 {
+            bus.publish_to(Topic::SensorHighRate, make_event("ros2::lidar"))
+                .unwrap();
+        }
+        let fault = make_event("hal::motor");
+        bus.publish_to(Topic::SystemAlerts, fault.clone()).unwrap();
+
+        let scans = &bus.subscriber_stats(Topic::SensorHighRate)[0];
+        assert_eq!(scans.capacity, 4 * SENSOR_HIGH_RATE_CAPACITY_FACTOR);
+        assert_eq!((scans.queued, scans.dropped), (16, 24));
+        assert_eq!(bus.policy(Topic::SensorHighRate), BackpressurePolicy::DropOldest);
+        assert_eq!(bus.subscriber_stats(Topic::SystemAlerts)[0].capacity, 4);
+        assert_eq!(alerts.recv().await.unwrap().id, fault.id);
+    }
+
+    /// A merged receiver alternates between lanes with queued events and
+    /// wakes for whichever lane publishes next.
+    #[tokio::test]
+    async fn subscribe_many_interleaves_lanes_fairly() {
+        let bus = EventBus::new(16);
+        let mut merged = bus.subscribe_many(&[
+            Topic::SensorHighRate,
+            Topic::SystemAlerts,
+            Topic::SensorHighRate,
+        ]);
+        assert_eq!(merged.topics(), [Topic::SensorHighRate, Topic::SystemAlerts]);
+        for _ in 0..4 {
+            bus.publish_to(Topic::SensorHighRate, make_event("ros2::lidar"))
+                .unwrap();
+        }
+        bus.publish_to(Topic::SystemAlerts, make_event("hal::motor"))
+            .unwrap();
+
+        let mut order = Vec::new();
+        while let Ok((topic, _)) = merged.try_recv() {
+            order.push(topic);
+        }
+        assert_eq!(order[..2], [Topic::SensorHighRate, Topic::SystemAlerts]);
+        assert_eq!(order.len(), 5);
+
+        let publisher = bus.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            publisher
+                .publish_to(Topic::SystemAlerts, make_event("kernel::watchdog"))
+                .unwrap();
+        });
+        let (topic, event) = tokio::time::timeout(Duration::from_secs(2), merged.recv())
+            .await
+            .expect("woken by the alert lane")
+            .unwrap();
+        assert_eq!((topic, event.source.as_str()), (Topic::SystemAlerts, "kernel::watchdog"));
+
+        assert_eq!(bus.subscribe_all_topics().topics(), Topic::ALL);
+        assert!(matches!(
+            bus.subscribe_many(&[]).recv().await,
+            Err(broadcast::error::RecvError::Closed)
+        ));
+    }
+
+    /// A slow subscriber under `DropOldest` loses its own backlog only; a
+    /// fast one on the same topic sees every event.
+    #[tokio::test]
+    async fn drop_oldest_isolates_slow_subscriber() {
+        let bus = EventBus::new(4);
+        let mut slow = bus.subscribe_to_named(Topic::Telemetry, "cockpit");
+        let mut fast = bus.subscribe_to(Topic::Telemetry);
+        let mut ids = Vec::new();
+        for _ in 0..10 {
+            let event = make_event("ros2::lidar");
+            ids.push(event.id);
+            bus.publish_to(Topic::Telemetry, event).unwrap();
+            assert_eq!(fast.recv().await.unwrap().id, *ids.last().unwrap());
+        }
+
+        let stats = bus.subscriber_stats(Topic::Telemetry);
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].name.as_deref(), Some("cockpit"));
+        assert_eq!(
+            (stats[0].queued, stats[0].dropped, stats[0].lagging),
+            (4, 6, 6)
+        );
+        assert_eq!((stats[1].delivered, stats[1].dropped), (10, 0));
+
+        assert!(matches!(
+            slow.recv().await,
+            Err(broadcast::error::RecvError::Lagged(6))
+        ));
+        assert_eq!(
+            slow.recv().await.unwrap().id,
+            ids[6],
+            "oldest survivors come next"
+        );
+        assert_eq!(slow.stats().lagging, 0);
+    }
+
+    #[tokio::test]
+    async fn drop_newest_keeps_backlog() {
+        let bus = EventBus::new(2).with_policy(Topic::SystemAlerts, BackpressurePolicy::DropNewest);
+        let mut rx = bus.subscribe_to(Topic::SystemAlerts);
+        let first = make_event("kernel");
+        bus.publish_to(Topic::SystemAlerts, first.clone()).unwrap();
+        bus.publish_to(Topic::SystemAlerts, make_event("kernel"))
+            .unwrap();
+        assert_eq!(
+            bus.publish_to(Topic::SystemAlerts, make_event("kernel"))
+                .unwrap(),
+            0,
+            "the full subscriber is not handed the event"
+        );
+
+        assert!(matches!(
+            rx.recv().await,
+            Err(broadcast::error::RecvError::Lagged(1))
+        ));
+        assert_eq!(rx.recv().await.unwrap().id, first.id);
+    }
+
+    #[tokio::test]
+    async fn block_waits_for_room_then_gives_up() {
+        let bus = EventBus::new(1).with_policy(
+            Topic::HardwareCommands,
+            BackpressurePolicy::Block(Duration::from_millis(100)),
+        );
+        let mut rx = bus.subscribe_to(Topic::HardwareCommands);
+        bus.publish_to_async(Topic::HardwareCommands, make_event("hal"))
+            .await
+            .unwrap();
+
+        // A reader frees the slot while the publisher waits.
+        let reader = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            let first = rx.recv().await.unwrap();
+            (rx, first)
+        });
+        let second = make_event("hal");
+        let delivered = bus
+            .publish_to_async(Topic::HardwareCommands, second.clone())
+            .await
+            .unwrap();
+        assert_eq!(delivered, 1);
+        let (mut rx, _) = reader.await.unwrap();
+
+        // Nobody reads now: the timeout elapses and the event is dropped.
+        let started = Instant::now();
+        let delivered = bus
+            .publish_to_async(Topic::HardwareCommands, make_event("hal"))
+            .await
+            .unwrap();
+        assert_eq!(delivered, 0);
+        assert!(started.elapsed() >= Duration::from_millis(90));
+        assert!(matches!(
+            rx.recv().await,
+            Err(broadcast::error::RecvError::Lagged(1))
+        ));
+        assert_eq!(rx.recv().await.unwrap().id, second.id);
+    }
+
+    #[tokio::test]
+    async fn dropping_receivers_and_bus_is_observed() {
+        let bus = EventBus::default();
+        let rx = bus.subscribe_to(Topic::SwarmComm);
+        drop(rx);
+        assert!(bus.subscriber_stats(Topic::SwarmComm).is_empty());
+        assert!(
+            bus.publish_to(Topic::SwarmComm, make_event("peer"))
+                .is_err()
+        );
+
+        let mut rx = bus.subscribe_to(Topic::SwarmComm);
+        drop(bus);
+        assert!(matches!(
+            rx.recv().await,
+            Err(broadcast::error::RecvError::Closed)
+        ));
+    }
 }
@@ -0,0 +1,576 @@
+//! [`BusBridge`] – mirror bus traffic between processes.
+//!
+//! Two [`EventBus`] instances – in different processes, or on different
+//! hosts – are joined by running a bridge on each side of a byte stream:
+//! one side [`listen`](BusBridge::listen)s, the other
+//! [`connect`](BusBridge::connect)s (and reconnects with exponential
+//! backoff).  Each bridge forwards the lanes it is configured for and
+//! re-publishes what the peer sends on the same lane, so the CLI, the
+//! Cockpit and the runtime can run as separate OS processes.
+//!
+//! # Wire format
+//!
+//! Every frame is a 4-byte big-endian length followed by that many bytes of
+//! JSON: a [`JournalEntry`], i.e. the event plus the `topic` it was
+//! published on (`null` for the global channel).  Frames larger than
+//! [`MAX_BRIDGE_FRAME_BYTES`] end the connection.
+//!
+//! Events that arrived over the bridge are remembered by id and never sent
+//! back, so two bridged buses do not echo each other's traffic.  Events
+//! published while the link is down are not mirrored.
+
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use mechos_types::{EventPayload, LinkState, MechError};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinSet;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::bus::{EventBus, LaneReceiver, MAX_EVENT_PAYLOAD_BYTES, Topic};
+use crate::journal::JournalEntry;
+use crate::supervisor::Backoff;
+use crate::typed::ConnectionState;
+
+/// Largest frame accepted from a peer: one maximum-size event plus its
+/// JSON envelope.
+pub const MAX_BRIDGE_FRAME_BYTES: usize = MAX_EVENT_PAYLOAD_BYTES + 4096;
+
+/// Events that may wait for the socket before further ones are dropped.
+const BRIDGE_QUEUE_CAPACITY: usize = 1024;
+
+/// Ids of bridged-in events remembered for echo suppression.
+const SEEN_CAPACITY: usize = 4096;
+
+/// Where a [`BusBridge`] listens or connects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BridgeEndpoint {
+    /// A TCP `host:port`.
+    Tcp(String),
+    /// A Unix domain socket path.
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl FromStr for BridgeEndpoint {
+    type Err = MechError;
+
+    /// `unix:<path>` for a Unix socket, `host:port` for TCP.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(path) = s.strip_prefix("unix:") {
+            #[cfg(unix)]
+            return Ok(BridgeEndpoint::Unix(PathBuf::from(path)));
+            #[cfg(not(unix))]
+            return Err(MechError::Parsing(format!(
+                "Unix socket '{path}' is not supported on this platform"
+            )));
+        }
+        if s.rsplit_once(':')
+            .is_some_and(|(_, port)| port.parse::<u16>().is_ok())
+        {
+            Ok(BridgeEndpoint::Tcp(s.to_string()))
+        } else {
+            Err(MechError::Parsing(format!(
+                "bridge endpoint '{s}' is neither host:port nor unix:<path>"
+            )))
+        }
+    }
+}
+
+impl fmt::Display for BridgeEndpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BridgeEndpoint::Tcp(addr) => f.write_str(addr),
+            #[cfg(unix)]
+            BridgeEndpoint::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+/// Mirrors selected lanes of an [`EventBus`] over a stream; see the
+/// [module docs](self).
+#[derive(Clone)]
+pub struct BusBridge {
+    bus: Arc<EventBus>,
+    global: bool,
+    topics: Vec<Topic>,
+    backoff: Backoff,
+    seen: Arc<Mutex<SeenIds>>,
+}
+
+impl BusBridge {
+    /// Bridge the global channel and every topic lane of `bus`.
+    pub fn new(bus: Arc<EventBus>) -> Self {
+        Self {
+            bus,
+            global: true,
+            topics: Topic::ALL.to_vec(),
+            backoff: Backoff::default(),
+            seen: Arc::default(),
+        }
+    }
+
+    /// Mirror only these topic lanes (default: all of them).  Frames from
+    /// the peer on other lanes are ignored.
+    pub fn with_topics(mut self, topics: impl IntoIterator<Item = Topic>) -> Self {
+        self.topics = topics.into_iter().collect();
+        self.topics.dedup();
+        self
+    }
+
+    /// Whether to mirror the global (legacy) channel (default `true`).
+    pub fn with_global(mut self, global: bool) -> Self {
+        self.global = global;
+        self
+    }
+
+    /// Override the reconnection backoff used by [`connect`](Self::connect).
+    pub fn with_backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Connect to a listening peer and keep the link up forever,
+    /// reconnecting with backoff.  Each transition is published on
+    /// [`Topic::SystemAlerts`] as a [`ConnectionState`] for component
+    /// `"bus_bridge"`.
+    pub async fn connect(&self, endpoint: BridgeEndpoint) {
+        let mut attempt = 0u32;
+        loop {
+            self.report(&endpoint, LinkState::Connecting, attempt);
+            let error = match self.open(&endpoint).await {
+                Ok(Stream::Tcp(stream)) => {
+                    let _ = stream.set_nodelay(true);
+                    attempt = 0;
+                    self.report(&endpoint, LinkState::Connected, 0);
+                    self.bridge(stream).await
+                }
+                #[cfg(unix)]
+                Ok(Stream::Unix(stream)) => {
+                    attempt = 0;
+                    self.report(&endpoint, LinkState::Connected, 0);
+                    self.bridge(stream).await
+                }
+                Err(e) => e,
+            };
+            warn!(%endpoint, error = %error, "bus bridge link down");
+            attempt = attempt.saturating_add(1);
+            self.report(&endpoint, LinkState::Disconnected, attempt);
+            tokio::time::sleep(self.backoff.delay(attempt)).await;
+        }
+    }
+
+    /// Accept peers on `endpoint` forever, bridging each connection on its
+    /// own task.  A stale Unix socket file is replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Channel`] if `endpoint` cannot be bound.
+    pub async fn listen(&self, endpoint: BridgeEndpoint) -> Result<(), MechError> {
+        let bind_error = |e: std::io::Error| {
+            MechError::Channel(format!("bus bridge cannot listen on {endpoint}: {e}"))
+        };
+        match &endpoint {
+            BridgeEndpoint::Tcp(addr) => {
+                let listener = tokio::net::TcpListener::bind(addr)
+                    .await
+                    .map_err(bind_error)?;
+                info!(%endpoint, "bus bridge listening");
+                loop {
+                    match listener.accept().await {
+                        Ok((stream, peer)) => {
+                            let _ = stream.set_nodelay(true);
+                            self.spawn_peer(stream, peer.to_string());
+                        }
+                        Err(e) => warn!(error = %e, "bus bridge accept failed"),
+                    }
+                }
+            }
+            #[cfg(unix)]
+            BridgeEndpoint::Unix(path) => {
+                let _ = std::fs::remove_file(path);
+                let listener = tokio::net::UnixListener::bind(path).map_err(bind_error)?;
+                info!(%endpoint, "bus bridge listening");
+                loop {
+                    match listener.accept().await {
+                        Ok((stream, _)) => self.spawn_peer(stream, endpoint.to_string()),
+                        Err(e) => warn!(error = %e, "bus bridge accept failed"),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Bridge one already-open stream until it fails, returning why.
+    pub async fn bridge<S>(&self, stream: S) -> MechError
+    where
+        S: AsyncRead + AsyncWrite + Send + 'static,
+    {
+        let (mut reader, mut writer) = tokio::io::split(stream);
+        let (tx, mut outbox) = mpsc::channel::<JournalEntry>(BRIDGE_QUEUE_CAPACITY);
+        // Dropping the set at the end of the connection aborts the lanes.
+        let mut lanes = JoinSet::new();
+        for rx in self.bus.subscribe_lanes(self.global, &self.topics) {
+            lanes.spawn(forward_lane(rx, Arc::clone(&self.seen), tx.clone()));
+        }
+        drop(tx);
+
+        let outbound = async {
+            while let Some(entry) = outbox.recv().await {
+                let json = serde_json::to_vec(&entry)
+                    .map_err(|e| MechError::Serialization(e.to_string()))?;
+                let mut frame = Vec::with_capacity(4 + json.len());
+                frame.extend_from_slice(&(json.len() as u32).to_be_bytes());
+                frame.extend_from_slice(&json);
+                writer.write_all(&frame).await.map_err(io_error)?;
+            }
+            Ok::<(), MechError>(())
+        };
+        let inbound = async {
+            loop {
+                let len = reader.read_u32().await.map_err(io_error)? as usize;
+                if len > MAX_BRIDGE_FRAME_BYTES {
+                    return Err(MechError::Parsing(format!(
+                        "bridge frame of {len} bytes exceeds limit of {MAX_BRIDGE_FRAME_BYTES}"
+                    )));
+                }
+                let mut json = vec![0; len];
+                reader.read_exact(&mut json).await.map_err(io_error)?;
+                let entry: JournalEntry = serde_json::from_slice(&json)
+                    .map_err(|e| MechError::Parsing(format!("invalid bridge frame: {e}")))?;
+                self.inject(entry);
+            }
+        };
+        let result = tokio::select! {
+            result = outbound => result,
+            result = inbound => result,
+        };
+        match result {
+            Ok(()) => MechError::Channel("event bus closed".to_string()),
+            Err(e) => e,
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    // Private helpers
+    // -------------------------------------------------------------------------
+
+    async fn open(&self, endpoint: &BridgeEndpoint) -> Result<Stream, MechError> {
+        let connect_error =
+            |e: std::io::Error| MechError::Channel(format!("bus bridge {endpoint}: {e}"));
+        match endpoint {
+            BridgeEndpoint::Tcp(addr) => tokio::net::TcpStream::connect(addr)
+                .await
+                .map(Stream::Tcp)
+                .map_err(connect_error),
+            #[cfg(unix)]
+            BridgeEndpoint::Unix(path) => tokio::net::UnixStream::connect(path)
+                .await
+                .map(Stream::Unix)
+                .map_err(connect_error),
+        }
+    }
+
+    fn spawn_peer<S>(&self, stream: S, peer: String)
+    where
+        S: AsyncRead + AsyncWrite + Send + 'static,
+    {
+        let bridge = self.clone();
+        tokio::spawn(async move {
+            info!(%peer, "bus bridge peer connected");
+            let error = bridge.bridge(stream).await;
+            info!(%peer, error = %error, "bus bridge peer disconnected");
+        });
+    }
+
+    /// Re-publish a frame from the peer on its lane.  Payloads this release
+    /// does not know (from a newer peer) are logged and skipped.
+    fn inject(&self, entry: JournalEntry) {
+        if let EventPayload::Unknown(raw) = &entry.event.payload {
+            let kind = raw
+                .as_object()
+                .and_then(|variant| variant.keys().next().map(String::as_str))
+                .or_else(|| raw.as_str())
+                .unwrap_or_default();
+            warn!(
+                kind,
+                source = %entry.event.source,
+                "skipping bridged event with an unknown payload; is the peer newer?"
+            );
+            return;
+        }
+        let mirrored = match entry.topic {
+            Some(topic) => self.topics.contains(&topic),
+            None => self.global,
+        };
+        if !mirrored {
+            debug!(topic = ?entry.topic, "ignoring bridged event on an unmirrored lane");
+            return;
+        }
+        self.seen
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(entry.event.id);
+        let result = match entry.topic {
+            Some(topic) => self.bus.publish_to(topic, entry.event),
+            None => self.bus.publish(entry.event),
+        };
+        if let Err(e) = result {
+            debug!(error = %e, "bridged event was not delivered");
+        }
+    }
+
+    fn report(&self, endpoint: &BridgeEndpoint, state: LinkState, attempt: u32) {
+        let update = ConnectionState {
+            component: "bus_bridge".to_string(),
+            state,
+            attempt,
+        };
+        // No subscriber on SystemAlerts is not an error for the link itself.
+        let _ = self
+            .bus
+            .publish_typed(format!("mechos-middleware::bus_bridge/{endpoint}"), update);
+    }
+}
+
+enum Stream {
+    Tcp(tokio::net::TcpStream),
+    #[cfg(unix)]
+    Unix(tokio::net::UnixStream),
+}
+
+/// Recently bridged-in event ids, oldest evicted first.
+#[derive(Debug, Default)]
+struct SeenIds {
+    order: VecDeque<Uuid>,
+    ids: HashSet<Uuid>,
+}
+
+impl SeenIds {
+    fn insert(&mut self, id: Uuid) {
+        if self.ids.insert(id) {
+            self.order.push_back(id);
+            if self.order.len() > SEEN_CAPACITY
+                && let Some(oldest) = self.order.pop_front()
+            {
+                self.ids.remove(&oldest);
+            }
+        }
+    }
+
+    fn contains(&self, id: &Uuid) -> bool {
+        self.ids.contains(id)
+    }
+}
+
+/// Queue the local traffic of the global channel, or of the selected topic
+/// lanes, for the peer, skipping bridged-in events.
+async fn forward_lane(
+    mut rx: LaneReceiver,
+    seen: Arc<Mutex<SeenIds>>,
+    tx: mpsc::Sender<JournalEntry>,
+) {
+    loop {
+        match rx.recv().await {
+            Ok((topic, event)) => {
+                if seen
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .contains(&event.id)
+                {
+                    continue;
+                }
+                match tx.try_send(JournalEntry { event, topic }) {
+                    Ok(()) => {}
+                    Err(mpsc::error::TrySendError::Full(_)) => {
+                        warn!(?topic, "bus bridge queue full, dropping event");
+                    }
+                    Err(mpsc::error::TrySendError::Closed(_)) => return,
+                }
+            }
+            Err(broadcast::error::RecvError::Lagged(n)) => {
+                warn!(lagged_by = n, "bus bridge lane lagged");
+            }
+            Err(broadcast::error::RecvError::Closed) => return,
+        }
+    }
+}
+
+fn io_error(e: std::io::Error) -> MechError {
+    MechError::Channel(format!("bus bridge stream: {e}"))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use mechos_types::Event;
+    use std::time::Duration;
+
+    fn thought(text: &str) -> Event {
+        Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: "mechos-runtime::agent".to_string(),
+            payload: EventPayload::Reasoning(text.to_string()),
+            trace_id: None,
+            correlation_id: None,
+        }
+    }
+
+    async fn next(rx: &mut crate::bus::TopicReceiver) -> Event {
+        tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("timed out waiting for a bridged event")
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn mirrors_selected_lanes_without_echo() {
+        let bus_a = Arc::new(EventBus::default());
+        let bus_b = Arc::new(EventBus::default());
+        let mut on_a = bus_a.subscribe_to(Topic::CognitiveStream);
+        let mut on_b = bus_b.subscribe_to(Topic::CognitiveStream);
+        let mut swarm_b = bus_b.subscribe_to(Topic::SwarmComm);
+        let (a, b) = tokio::io::duplex(64 * 1024);
+        let bridge_a = BusBridge::new(Arc::clone(&bus_a)).with_topics([Topic::CognitiveStream]);
+        let bridge_b = BusBridge::new(Arc::clone(&bus_b))
+            .with_topics([Topic::CognitiveStream, Topic::SwarmComm]);
+        tokio::spawn(async move { bridge_a.bridge(a).await });
+        tokio::spawn(async move { bridge_b.bridge(b).await });
+        // Let both sides subscribe their lanes.
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let sent = thought("a to b");
+        bus_a
+            .publish_to(Topic::CognitiveStream, sent.clone())
+            .unwrap();
+        let _ = bus_a.publish_to(Topic::SwarmComm, thought("not mirrored by a"));
+        assert_eq!(next(&mut on_a).await.id, sent.id);
+        let received = next(&mut on_b).await;
+        assert_eq!(received.id, sent.id);
+        assert_eq!(received.source, "mechos-runtime::agent");
+
+        let reply = thought("b to a");
+        bus_b
+            .publish_to(Topic::CognitiveStream, reply.clone())
+            .unwrap();
+        assert_eq!(next(&mut on_b).await.id, reply.id);
+        assert_eq!(next(&mut on_a).await.id, reply.id);
+
+        // Neither event bounced back, and A never mirrored SwarmComm.
+        let quiet = Duration::from_millis(100);
+        assert!(tokio::time::timeout(quiet, on_a.recv()).await.is_err());
+        assert!(tokio::time::timeout(quiet, on_b.recv()).await.is_err());
+        assert!(tokio::time::timeout(quiet, swarm_b.recv()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn unknown_payloads_from_newer_peers_are_skipped() {
+        let bus = Arc::new(EventBus::default());
+        let mut received = bus.subscribe_to(Topic::CognitiveStream);
+        let bridge = BusBridge::new(Arc::clone(&bus));
+        let (local, mut peer) = tokio::io::duplex(64 * 1024);
+        tokio::spawn(async move { bridge.bridge(local).await });
+
+        let known = thought("still understood");
+        let mut unknown = serde_json::to_value(JournalEntry {
+            event: thought("placeholder"),
+            topic: Some(Topic::CognitiveStream),
+        })
+        .unwrap();
+        unknown["payload"] = serde_json::json!({ "Teleport": { "to": "moon" } });
+        let frames = [
+            serde_json::to_vec(&unknown).unwrap(),
+            serde_json::to_vec(&JournalEntry {
+                event: known.clone(),
+                topic: Some(Topic::CognitiveStream),
+            })
+            .unwrap(),
+        ];
+        for frame in frames {
+            peer.write_u32(frame.len() as u32).await.unwrap();
+            peer.write_all(&frame).await.unwrap();
+        }
+
+        assert_eq!(next(&mut received).await.id, known.id);
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), received.recv())
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn oversized_frame_ends_the_connection() {
+        let bridge = BusBridge::new(Arc::new(EventBus::default()));
+        let (local, mut peer) = tokio::io::duplex(1024);
+        peer.write_u32(u32::MAX).await.unwrap();
+        let error = bridge.bridge(local).await;
+        assert!(matches!(error, MechError::Parsing(_)), "{error:?}");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn reconnecting_client_over_a_unix_socket() {
+        let path = std::env::temp_dir().join(format!("mechos-bridge-{}.sock", Uuid::new_v4()));
+        let endpoint: BridgeEndpoint = format!("unix:{}", path.display()).parse().unwrap();
+        let server_bus = Arc::new(EventBus::default());
+        let client_bus = Arc::new(EventBus::default());
+        let mut received = server_bus.subscribe_to(Topic::Telemetry);
+        let mut alerts = client_bus.subscribe_typed::<ConnectionState>();
+
+        let client = BusBridge::new(Arc::clone(&client_bus)).with_backoff(Backoff {
+            initial: Duration::from_millis(20),
+            max: Duration::from_millis(100),
+        });
+        let connect_to = endpoint.clone();
+        tokio::spawn(async move { client.connect(connect_to).await });
+        // Nobody listens yet: the first attempt fails.
+        let first = tokio::time::timeout(Duration::from_secs(5), alerts.recv())
+            .await
+            .unwrap();
+        assert_eq!(first.unwrap().state, LinkState::Connecting);
+
+        let server = BusBridge::new(Arc::clone(&server_bus));
+        tokio::spawn(async move { server.listen(endpoint).await });
+
+        let sent = thought("over the socket");
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        loop {
+            // Publishing fails until the client's lanes are subscribed.
+            if client_bus
+                .publish_to(Topic::Telemetry, sent.clone())
+                .is_ok()
+            {
+                break;
+            }
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "client never connected"
+            );
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(next(&mut received).await.id, sent.id);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn endpoints_parse() {
+        assert_eq!(
+            "127.0.0.1:7400".parse::<BridgeEndpoint>().unwrap(),
+            BridgeEndpoint::Tcp("127.0.0.1:7400".to_string())
+        );
+        assert!("no-port".parse::<BridgeEndpoint>().is_err());
+    }
+}
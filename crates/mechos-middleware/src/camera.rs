@@ -0,0 +1,189 @@
+//! Camera frame ingestion – size limits and downscaling for
+//! [`EventPayload::CameraFrame`].
+//!
+//! Images are the largest payloads on the bus.  [`camera_frame_payload`]
+//! bounds every frame before it is published:
+//!
+//! * **Raw frames** (`mono8`, `rgb8`, `bgr8`, `rgba8`) larger than
+//!   [`MAX_CAMERA_WIDTH`] × [`MAX_CAMERA_HEIGHT`] or
+//!   [`MAX_CAMERA_FRAME_BYTES`] are downscaled by the smallest integer factor
+//!   that fits, averaging each block of source pixels.
+//! * **Compressed frames** (`jpeg`, `png`) cannot be resized without a codec
+//!   and are rejected when they exceed the same limits.
+//!
+//! The resulting bytes are base64-encoded into `data_b64`.
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use mechos_types::{EventPayload, ImageFormat, MechError};
+
+/// Maximum width, in pixels, of a published camera frame.
+pub const MAX_CAMERA_WIDTH: u32 = 640;
+
+/// Maximum height, in pixels, of a published camera frame.
+pub const MAX_CAMERA_HEIGHT: u32 = 480;
+
+/// Maximum size of a published frame's image data before base64 encoding.
+///
+/// Base64 adds a third, so a frame at this limit still fits in the bus's
+/// 1 MiB event limit.
+pub const MAX_CAMERA_FRAME_BYTES: usize = 512 * 1024; // 512 KiB
+
+/// Validate, downscale if needed, and encode one camera image as an
+/// [`EventPayload::CameraFrame`].
+///
+/// # Errors
+///
+/// Returns [`MechError::Parsing`] when the frame has a zero dimension, when
+/// a raw frame's `data` length does not match `width × height ×` bytes per
+/// pixel, or when a compressed frame exceeds the resolution or size limits.
+pub fn camera_frame_payload(
+    image_id: impl Into<String>,
+    format: ImageFormat,
+    width: u32,
+    height: u32,
+    data: &[u8],
+) -> Result<EventPayload, MechError> {
+    if width == 0 || height == 0 {
+        return Err(MechError::Parsing(format!(
+            "camera frame has an empty {width}x{height} resolution"
+        )));
+    }
+
+    let (data, width, height) = match format.bytes_per_pixel() {
+        Some(bpp) => {
+            let expected = width as usize * height as usize * bpp;
+            if data.len() != expected {
+                return Err(MechError::Parsing(format!(
+                    "{width}x{height} {format:?} frame has {} bytes, expected {expected}",
+                    data.len()
+                )));
+            }
+            let factor = downscale_factor(width, height, bpp);
+            if factor == 1 {
+                (data.to_vec(), width, height)
+            } else {
+                downscale(data, width, height, bpp, factor)
+            }
+        }
+        None => {
+            if width > MAX_CAMERA_WIDTH
+                || height > MAX_CAMERA_HEIGHT
+                || data.len() > MAX_CAMERA_FRAME_BYTES
+            {
+                return Err(MechError::Parsing(format!(
+                    "{width}x{height} {format:?} frame of {} bytes exceeds the \
+                     {MAX_CAMERA_WIDTH}x{MAX_CAMERA_HEIGHT} / {MAX_CAMERA_FRAME_BYTES}-byte \
+                     limit; compressed frames cannot be downscaled",
+                    data.len()
+                )));
+            }
+            (data.to_vec(), width, height)
+        }
+    };
+
+    Ok(EventPayload::CameraFrame {
+        image_id: image_id.into(),
+        format,
+        width,
+        height,
+        data_b64: STANDARD.encode(data),
+    })
+}
+
+/// Smallest integer factor that brings a raw frame within all limits.
+fn downscale_factor(width: u32, height: u32, bpp: usize) -> u32 {
+    let mut factor = 1;
+    loop {
+        let w = width.div_ceil(factor);
+        let h = height.div_ceil(factor);
+        if w <= MAX_CAMERA_WIDTH
+            && h <= MAX_CAMERA_HEIGHT
+            && w as usize * h as usize * bpp <= MAX_CAMERA_FRAME_BYTES
+        {
+            return factor;
+        }
+        factor += 1;
+    }
+}
+
+/// Shrink a raw frame by `factor`, averaging each `factor × factor` block
+/// (partial blocks at the right and bottom edges average what they cover).
+fn downscale(data: &[u8], width: u32, height: u32, bpp: usize, factor: u32) -> (Vec<u8>, u32, u32) {
+    let (width, height, factor) = (width as usize, height as usize, factor as usize);
+    let out_w = width.div_ceil(factor);
+    let out_h = height.div_ceil(factor);
+    let mut out = Vec::with_capacity(out_w * out_h * bpp);
+    for oy in 0..out_h {
+        let rows = oy * factor..((oy + 1) * factor).min(height);
+        for ox in 0..out_w {
+            let cols = ox * factor..((ox + 1) * factor).min(width);
+            let count = rows.len() * cols.len();
+            for channel in 0..bpp {
+                let sum: usize = rows
+                    .clone()
+                    .flat_map(|y| cols.clone().map(move |x| (y * width + x) * bpp + channel))
+                    .map(|i| data[i] as usize)
+                    .sum();
+                out.push((sum / count) as u8);
+            }
+        }
+    }
+    (out, out_w as u32, out_h as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(payload: &EventPayload) -> (u32, u32, Vec<u8>) {
+        let EventPayload::CameraFrame {
+            width,
+            height,
+            data_b64,
+            ..
+        } = payload
+        else {
+            panic!("expected CameraFrame");
+        };
+        (*width, *height, STANDARD.decode(data_b64).unwrap())
+    }
+
+    #[test]
+    fn small_raw_frame_is_passed_through() {
+        let data = [10, 20, 30, 40, 50, 60];
+        let payload = camera_frame_payload("f1", ImageFormat::Rgb8, 2, 1, &data).unwrap();
+        assert_eq!(decode(&payload), (2, 1, data.to_vec()));
+    }
+
+    #[test]
+    fn oversized_raw_frame_is_downscaled_within_limits() {
+        let (w, h) = (1280, 960);
+        let data = vec![200u8; w * h * 3];
+        let payload =
+            camera_frame_payload("f2", ImageFormat::Rgb8, w as u32, h as u32, &data).unwrap();
+        let (out_w, out_h, out) = decode(&payload);
+        assert!(out_w <= MAX_CAMERA_WIDTH && out_h <= MAX_CAMERA_HEIGHT);
+        assert!(out.len() <= MAX_CAMERA_FRAME_BYTES);
+        assert_eq!(out.len(), out_w as usize * out_h as usize * 3);
+        assert!(out.iter().all(|&b| b == 200));
+    }
+
+    #[test]
+    fn downscale_averages_blocks() {
+        // 3x1 mono frame halved: [0, 100] averages to 50, the edge pixel stays.
+        let (out, w, h) = downscale(&[0, 100, 7], 3, 1, 1, 2);
+        assert_eq!((w, h), (2, 1));
+        assert_eq!(out, vec![50, 7]);
+    }
+
+    #[test]
+    fn malformed_and_oversized_compressed_frames_are_rejected() {
+        assert!(camera_frame_payload("f", ImageFormat::Mono8, 2, 2, &[0; 3]).is_err());
+        assert!(camera_frame_payload("f", ImageFormat::Mono8, 0, 2, &[]).is_err());
+        assert!(camera_frame_payload("f", ImageFormat::Jpeg, 1920, 1080, &[0; 16]).is_err());
+        let too_big = vec![0; MAX_CAMERA_FRAME_BYTES + 1];
+        assert!(camera_frame_payload("f", ImageFormat::Png, 64, 64, &too_big).is_err());
+        assert!(camera_frame_payload("f", ImageFormat::Jpeg, 640, 480, &[0xFF, 0xD8]).is_ok());
+    }
+}
@@ -0,0 +1,850 @@
+//! CAN bus adapter with DBC-style signal mapping.
+//!
+//! [`CanAdapter`] drives industrial chassis that speak CAN instead of ROS 2.
+//! A [`CanMapping`], usually loaded from a TOML file, describes which frame
+//! each intent becomes and how status frames decode into events:
+//!
+//! ```toml
+//! [[command]]
+//! intent = "drive"
+//! id = 0x101
+//! signals = [
+//!   { field = "linear_velocity",  start_bit = 0,  length = 16, signed = true, scale = 0.001 },
+//!   { field = "angular_velocity", start_bit = 16, length = 16, signed = true, scale = 0.001 },
+//! ]
+//!
+//! [[command]]
+//! intent = "trigger_relay"
+//! relay_id = "pump"
+//! id = 0x102
+//! dlc = 1
+//! signals = [{ field = "state", start_bit = 0, length = 1 }]
+//!
+//! [[status]]
+//! payload = "power_status"
+//! id = 0x201
+//! signals = [
+//!   { field = "voltage", start_bit = 7, length = 16, byte_order = "big_endian", scale = 0.01 },
+//!   { field = "percent", start_bit = 16, length = 8 },
+//! ]
+//! ```
+//!
+//! Signals follow DBC conventions: `start_bit` is the least significant bit
+//! for `little_endian` (Intel) signals and the most significant bit, in DBC
+//! sawtooth numbering, for `big_endian` (Motorola) ones; the physical value
+//! is `raw × scale + offset`.
+//!
+//! | Kind | Fields |
+//! |---|---|
+//! | `drive` command | `linear_velocity` (m/s), `angular_velocity` (rad/s) |
+//! | `trigger_relay` command | `state` (0 / 1) |
+//! | `telemetry` status | `position_x`, `position_y`, `heading_rad`, `battery_percent` |
+//! | `power_status` status | `voltage`, `current`, `charging`, `percent` |
+//!
+//! A status payload may be spread over several frames: the adapter keeps the
+//! latest value of every field and publishes the merged payload whenever one
+//! of its frames arrives.  Frames without a mapping are ignored.
+//!
+//! On Linux, [`CanSocket`] opens a raw SocketCAN interface (e.g. `can0` or
+//! `vcan0`) for [`CanAdapter::with_socket`].
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use futures_util::stream::{self, BoxStream};
+use mechos_types::{
+    Event, EventPayload, FaultCode, HardwareIntent, MechError, Meters, TelemetryData,
+};
+use serde::Deserialize;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::adapter::MechAdapter;
+use crate::bus::EventBus;
+
+/// Largest classic CAN payload.
+pub const CAN_MAX_DLC: u8 = 8;
+
+/// Largest standard (11-bit) identifier.
+const CAN_SFF_MAX_ID: u32 = 0x7FF;
+
+/// Largest extended (29-bit) identifier.
+const CAN_EFF_MAX_ID: u32 = 0x1FFF_FFFF;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Mapping
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Bit layout of a signal within the frame payload.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ByteOrder {
+    /// Intel: `start_bit` is the least significant bit.
+    #[default]
+    LittleEndian,
+    /// Motorola: `start_bit` is the most significant bit.
+    BigEndian,
+}
+
+/// One scaled value packed into a frame.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CanSignal {
+    /// The intent or payload field this signal carries.
+    pub field: String,
+    pub start_bit: u8,
+    /// Width in bits (1–64).
+    pub length: u8,
+    #[serde(default)]
+    pub byte_order: ByteOrder,
+    /// Two's-complement raw value.
+    #[serde(default)]
+    pub signed: bool,
+    #[serde(default = "default_scale")]
+    pub scale: f64,
+    #[serde(default)]
+    pub offset: f64,
+}
+
+fn default_scale() -> f64 {
+    1.0
+}
+
+fn default_dlc() -> u8 {
+    CAN_MAX_DLC
+}
+
+/// Intents a [`CanAdapter`] can encode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CanCommandKind {
+    Drive,
+    TriggerRelay,
+}
+
+impl CanCommandKind {
+    fn fields(self) -> &'static [&'static str] {
+        match self {
+            CanCommandKind::Drive => &["linear_velocity", "angular_velocity"],
+            CanCommandKind::TriggerRelay => &["state"],
+        }
+    }
+}
+
+/// Payloads a [`CanAdapter`] can decode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CanStatusKind {
+    Telemetry,
+    PowerStatus,
+}
+
+impl CanStatusKind {
+    fn fields(self) -> &'static [&'static str] {
+        match self {
+            CanStatusKind::Telemetry => {
+                &["position_x", "position_y", "heading_rad", "battery_percent"]
+            }
+            CanStatusKind::PowerStatus => &["voltage", "current", "charging", "percent"],
+        }
+    }
+}
+
+/// The frame one intent is encoded into.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CanCommandMap {
+    pub intent: CanCommandKind,
+    /// For `trigger_relay`: only this relay uses the frame.  `None` matches
+    /// every relay.
+    #[serde(default)]
+    pub relay_id: Option<String>,
+    pub id: u32,
+    #[serde(default)]
+    pub extended: bool,
+    #[serde(default = "default_dlc")]
+    pub dlc: u8,
+    pub signals: Vec<CanSignal>,
+}
+
+/// A status frame and the payload it decodes into.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CanStatusMap {
+    pub payload: CanStatusKind,
+    pub id: u32,
+    #[serde(default)]
+    pub extended: bool,
+    pub signals: Vec<CanSignal>,
+}
+
+/// The full frame mapping of a chassis.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct CanMapping {
+    #[serde(default, rename = "command")]
+    pub commands: Vec<CanCommandMap>,
+    #[serde(default, rename = "status")]
+    pub statuses: Vec<CanStatusMap>,
+}
+
+impl CanMapping {
+    /// Parse and validate a TOML mapping.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Parsing`] for invalid TOML, identifiers out of
+    /// range, DLCs above [`CAN_MAX_DLC`], unknown fields and signals that do
+    /// not fit in their frame.
+    pub fn from_toml(text: &str) -> Result<Self, MechError> {
+        let mapping: Self = toml::from_str(text)
+            .map_err(|e| MechError::Parsing(format!("invalid CAN mapping: {e}")))?;
+        mapping.validate()?;
+        Ok(mapping)
+    }
+
+    /// Load a TOML mapping from `path`.
+    ///
+    /// # Errors
+    ///
+    /// - [`MechError::Serialization`] – the file cannot be read.
+    /// - [`MechError::Parsing`] – see [`Self::from_toml`].
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, MechError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            MechError::Serialization(format!(
+                "failed to read CAN mapping '{}': {e}",
+                path.display()
+            ))
+        })?;
+        Self::from_toml(&text)
+    }
+
+    fn validate(&self) -> Result<(), MechError> {
+        let frames = self
+            .commands
+            .iter()
+            .map(|c| (c.id, c.extended, c.dlc, c.intent.fields(), &c.signals))
+            .chain(self.statuses.iter().map(|s| {
+                (
+                    s.id,
+                    s.extended,
+                    CAN_MAX_DLC,
+                    s.payload.fields(),
+                    &s.signals,
+                )
+            }));
+        for (id, extended, dlc, fields, signals) in frames {
+            let max_id = if extended {
+                CAN_EFF_MAX_ID
+            } else {
+                CAN_SFF_MAX_ID
+            };
+            let invalid =
+                |reason: String| MechError::Parsing(format!("CAN frame {id:#x}: {reason}"));
+            if id > max_id {
+                return Err(invalid(format!("identifier exceeds {max_id:#x}")));
+            }
+            if dlc > CAN_MAX_DLC {
+                return Err(invalid(format!("dlc {dlc} exceeds {CAN_MAX_DLC}")));
+            }
+            for signal in signals {
+                if !fields.contains(&signal.field.as_str()) {
+                    return Err(invalid(format!(
+                        "unknown field '{}' (expected one of {fields:?})",
+                        signal.field
+                    )));
+                }
+                if signal.scale == 0.0 || !signal.scale.is_finite() {
+                    return Err(invalid(format!(
+                        "signal '{}' has a zero scale",
+                        signal.field
+                    )));
+                }
+                if !(1..=64).contains(&signal.length)
+                    || signal.bits().iter().any(|&(byte, _)| byte >= dlc as usize)
+                {
+                    return Err(invalid(format!(
+                        "signal '{}' does not fit in {dlc} bytes",
+                        signal.field
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CanSignal {
+    /// `(byte, bit)` positions of the raw value, least significant first.
+    fn bits(&self) -> Vec<(usize, u8)> {
+        let (mut byte, mut bit) = ((self.start_bit / 8) as usize, self.start_bit % 8);
+        let mut positions = Vec::with_capacity(self.length as usize);
+        for _ in 0..self.length {
+            positions.push((byte, bit));
+            match self.byte_order {
+                ByteOrder::LittleEndian if bit == 7 => (byte, bit) = (byte + 1, 0),
+                ByteOrder::LittleEndian => bit += 1,
+                ByteOrder::BigEndian if bit == 0 => (byte, bit) = (byte + 1, 7),
+                ByteOrder::BigEndian => bit -= 1,
+            }
+        }
+        if self.byte_order == ByteOrder::BigEndian {
+            positions.reverse();
+        }
+        positions
+    }
+
+    /// Scale `value` into a raw integer, saturating at the signal's range.
+    fn encode(&self, value: f64, data: &mut [u8]) {
+        let raw = ((value - self.offset) / self.scale).round();
+        let bits = self.length as u32;
+        let raw = if self.signed {
+            let max = (i64::MAX >> (64 - bits)) as f64;
+            raw.clamp(-max - 1.0, max) as i64 as u64
+        } else {
+            raw.clamp(0.0, (u64::MAX >> (64 - bits)) as f64) as u64
+        };
+        for (i, (byte, bit)) in self.bits().into_iter().enumerate() {
+            if raw >> i & 1 == 1 {
+                data[byte] |= 1 << bit;
+            } else {
+                data[byte] &= !(1 << bit);
+            }
+        }
+    }
+
+    fn decode(&self, data: &[u8]) -> f64 {
+        let mut raw = 0u64;
+        for (i, (byte, bit)) in self.bits().into_iter().enumerate() {
+            let set = data.get(byte).is_some_and(|b| b >> bit & 1 == 1);
+            raw |= u64::from(set) << i;
+        }
+        let shift = 64 - self.length as u32;
+        let raw = if self.signed {
+            ((raw << shift) as i64 >> shift) as f64
+        } else {
+            raw as f64
+        };
+        raw * self.scale + self.offset
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Frames and sockets
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A classic CAN data frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanFrame {
+    pub id: u32,
+    pub extended: bool,
+    /// Up to [`CAN_MAX_DLC`] bytes.
+    pub data: Vec<u8>,
+}
+
+pub use socket::CanSocket;
+
+#[cfg(target_os = "linux")]
+mod socket {
+    use std::ffi::CString;
+    use std::io;
+    use std::mem;
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+    use tokio::io::unix::AsyncFd;
+
+    use super::{CAN_MAX_DLC, CanFrame};
+
+    /// A raw SocketCAN socket bound to one interface.
+    pub struct CanSocket {
+        fd: AsyncFd<OwnedFd>,
+    }
+
+    impl CanSocket {
+        /// Open and bind a raw CAN socket on `interface` (e.g. `"can0"`).
+        pub fn open(interface: &str) -> io::Result<Self> {
+            let name = CString::new(interface)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            // SAFETY: `name` is a valid NUL-terminated string.
+            let index = unsafe { libc::if_nametoindex(name.as_ptr()) };
+            if index == 0 {
+                return Err(io::Error::last_os_error());
+            }
+            // SAFETY: plain socket(2) call; the result is checked below.
+            let raw = unsafe {
+                libc::socket(
+                    libc::PF_CAN,
+                    libc::SOCK_RAW | libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC,
+                    libc::CAN_RAW,
+                )
+            };
+            if raw < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            // SAFETY: `raw` is a freshly created descriptor owned by nobody else.
+            let fd = unsafe { OwnedFd::from_raw_fd(raw) };
+            // SAFETY: `sockaddr_can` is plain data; all-zero is a valid value.
+            let mut addr: libc::sockaddr_can = unsafe { mem::zeroed() };
+            addr.can_family = libc::AF_CAN as libc::sa_family_t;
+            addr.can_ifindex = index as libc::c_int;
+            // SAFETY: `addr` is a valid `sockaddr_can` of the given size.
+            let rc = unsafe {
+                libc::bind(
+                    fd.as_raw_fd(),
+                    (&addr as *const libc::sockaddr_can).cast(),
+                    mem::size_of::<libc::sockaddr_can>() as libc::socklen_t,
+                )
+            };
+            if rc < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(Self {
+                fd: AsyncFd::new(fd)?,
+            })
+        }
+
+        /// Send one frame.
+        pub async fn send(&self, frame: &CanFrame) -> io::Result<()> {
+            // SAFETY: `can_frame` is plain data; all-zero is a valid value.
+            let mut raw: libc::can_frame = unsafe { mem::zeroed() };
+            raw.can_id = if frame.extended {
+                frame.id | libc::CAN_EFF_FLAG
+            } else {
+                frame.id
+            };
+            let len = frame.data.len().min(CAN_MAX_DLC as usize);
+            raw.can_dlc = len as u8;
+            raw.data[..len].copy_from_slice(&frame.data[..len]);
+            loop {
+                let mut guard = self.fd.writable().await?;
+                let result = guard.try_io(|fd| {
+                    // SAFETY: writes exactly one `can_frame` from a valid value.
+                    let n = unsafe {
+                        libc::write(
+                            fd.as_raw_fd(),
+                            (&raw as *const libc::can_frame).cast(),
+                            mem::size_of::<libc::can_frame>(),
+                        )
+                    };
+                    if n < 0 {
+                        Err(io::Error::last_os_error())
+                    } else {
+                        Ok(())
+                    }
+                });
+                if let Ok(result) = result {
+                    return result;
+                }
+            }
+        }
+
+        /// Receive the next data frame, skipping remote and error frames.
+        pub async fn recv(&self) -> io::Result<CanFrame> {
+            loop {
+                let mut guard = self.fd.readable().await?;
+                // SAFETY: `can_frame` is plain data; all-zero is a valid value.
+                let mut raw: libc::can_frame = unsafe { mem::zeroed() };
+                let result = guard.try_io(|fd| {
+                    // SAFETY: reads at most one `can_frame` into a valid value.
+                    let n = unsafe {
+                        libc::read(
+                            fd.as_raw_fd(),
+                            (&mut raw as *mut libc::can_frame).cast(),
+                            mem::size_of::<libc::can_frame>(),
+                        )
+                    };
+                    if n < 0 {
+                        Err(io::Error::last_os_error())
+                    } else {
+                        Ok(())
+                    }
+                });
+                match result {
+                    Ok(Ok(())) if raw.can_id & (libc::CAN_RTR_FLAG | libc::CAN_ERR_FLAG) == 0 => {
+                        let extended = raw.can_id & libc::CAN_EFF_FLAG != 0;
+                        let mask = if extended {
+                            libc::CAN_EFF_MASK
+                        } else {
+                            libc::CAN_SFF_MASK
+                        };
+                        let len = (raw.can_dlc as usize).min(CAN_MAX_DLC as usize);
+                        return Ok(CanFrame {
+                            id: raw.can_id & mask,
+                            extended,
+                            data: raw.data[..len].to_vec(),
+                        });
+                    }
+                    Ok(Ok(())) | Err(_) => continue,
+                    Ok(Err(e)) => return Err(e),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod socket {
+    use std::io;
+
+    use super::CanFrame;
+
+    /// SocketCAN is only available on Linux.
+    pub struct CanSocket(());
+
+    impl CanSocket {
+        pub fn open(_interface: &str) -> io::Result<Self> {
+            Err(io::ErrorKind::Unsupported.into())
+        }
+
+        pub async fn send(&self, _frame: &CanFrame) -> io::Result<()> {
+            Err(io::ErrorKind::Unsupported.into())
+        }
+
+        pub async fn recv(&self) -> io::Result<CanFrame> {
+            Err(io::ErrorKind::Unsupported.into())
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// CanAdapter
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Adapter that encodes intents into, and decodes events from, CAN frames.
+pub struct CanAdapter {
+    bus: Arc<EventBus>,
+    mapping: CanMapping,
+    socket: Option<CanSocket>,
+    /// Latest decoded value of every status field, per payload.
+    state: Mutex<HashMap<CanStatusKind, HashMap<String, f64>>>,
+}
+
+impl CanAdapter {
+    /// Create a new [`CanAdapter`] using `mapping`.  Without a socket,
+    /// frames can be encoded and ingested but not sent.
+    pub fn new(bus: Arc<EventBus>, mapping: CanMapping) -> Self {
+        Self {
+            bus,
+            mapping,
+            socket: None,
+            state: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Send commands on, and receive status frames from, `socket`.
+    pub fn with_socket(mut self, socket: CanSocket) -> Self {
+        self.socket = Some(socket);
+        self
+    }
+
+    /// Encode `intent` with the first matching command mapping.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::HardwareFault`] with [`FaultCode::Unsupported`]
+    /// when no command mapping matches the intent.
+    pub fn encode_intent(&self, intent: &HardwareIntent) -> Result<CanFrame, MechError> {
+        let unsupported = || MechError::HardwareFault {
+            code: FaultCode::Unsupported,
+            component: "can".to_string(),
+            details: format!("no CAN command mapping for intent: {intent:?}"),
+        };
+        let (kind, relay, values) = match intent {
+            HardwareIntent::Drive {
+                linear_velocity,
+                angular_velocity,
+            } => (
+                CanCommandKind::Drive,
+                None,
+                vec![linear_velocity.get() as f64, angular_velocity.get() as f64],
+            ),
+            HardwareIntent::TriggerRelay { relay_id, state } => (
+                CanCommandKind::TriggerRelay,
+                Some(relay_id.as_str()),
+                vec![f64::from(u8::from(*state))],
+            ),
+            _ => return Err(unsupported()),
+        };
+        let command = self
+            .mapping
+            .commands
+            .iter()
+            .find(|c| c.intent == kind && (c.relay_id.is_none() || c.relay_id.as_deref() == relay))
+            .ok_or_else(unsupported)?;
+
+        let mut data = vec![0u8; command.dlc as usize];
+        for signal in &command.signals {
+            let index = kind.fields().iter().position(|f| *f == signal.field);
+            if let Some(value) = index.and_then(|i| values.get(i)) {
+                signal.encode(*value, &mut data);
+            }
+        }
+        Ok(CanFrame {
+            id: command.id,
+            extended: command.extended,
+            data,
+        })
+    }
+
+    /// Decode a received frame and publish the merged status payload.
+    /// Returns `Ok(0)` for frames without a status mapping.
+    ///
+    /// # Errors
+    ///
+    /// Propagates [`EventBus::publish`] errors.
+    pub fn ingest_frame(&self, frame: &CanFrame) -> Result<usize, MechError> {
+        let Some(status) = self
+            .mapping
+            .statuses
+            .iter()
+            .find(|s| s.id == frame.id && s.extended == frame.extended)
+        else {
+            return Ok(0);
+        };
+
+        let payload = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            let fields = state.entry(status.payload).or_default();
+            for signal in &status.signals {
+                fields.insert(signal.field.clone(), signal.decode(&frame.data));
+            }
+            let field = |name: &str| fields.get(name).copied().unwrap_or_default();
+            match status.payload {
+                CanStatusKind::Telemetry => EventPayload::Telemetry(TelemetryData {
+                    position_x: Meters(field("position_x") as f32),
+                    position_y: Meters(field("position_y") as f32),
+                    heading_rad: field("heading_rad") as f32,
+                    battery_percent: field("battery_percent").clamp(0.0, 100.0) as u8,
+                    stamp: None,
+                }),
+                CanStatusKind::PowerStatus => EventPayload::PowerStatus {
+                    voltage: field("voltage") as f32,
+                    current: field("current") as f32,
+                    charging: field("charging") != 0.0,
+                    percent: field("percent") as f32,
+                },
+            }
+        };
+
+        self.bus.publish(Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: format!("mechos-middleware::can/{:#05x}", frame.id),
+            payload,
+            trace_id: None,
+            correlation_id: None,
+        })
+    }
+
+    /// Receive frames from the socket and ingest them until the socket
+    /// fails.  Frames that cannot be published are logged and dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Channel`] when no socket is installed or reading
+    /// from it fails.
+    pub async fn run(&self) -> Result<(), MechError> {
+        let socket = self.socket()?;
+        loop {
+            let frame = socket
+                .recv()
+                .await
+                .map_err(|e| MechError::Channel(format!("CAN receive failed: {e}")))?;
+            if let Err(e) = self.ingest_frame(&frame) {
+                warn!(id = frame.id, error = %e, "dropping CAN frame");
+            }
+        }
+    }
+
+    fn socket(&self) -> Result<&CanSocket, MechError> {
+        self.socket
+            .as_ref()
+            .ok_or_else(|| MechError::Channel("CAN adapter has no socket".to_string()))
+    }
+}
+
+#[async_trait]
+impl MechAdapter for CanAdapter {
+    /// Encode the intent with [`CanAdapter::encode_intent`] and send it.
+    ///
+    /// # Errors
+    ///
+    /// Besides unmapped intents, returns [`MechError::Channel`] when there
+    /// is no socket or the frame cannot be sent.
+    async fn execute_intent(&self, intent: HardwareIntent) -> Result<(), MechError> {
+        let frame = self.encode_intent(&intent)?;
+        self.socket()?
+            .send(&frame)
+            .await
+            .map_err(|e| MechError::Channel(format!("CAN send of {:#x} failed: {e}", frame.id)))
+    }
+
+    /// Status frames are pushed onto the bus by [`CanAdapter::run`]; this
+    /// returns an empty stream.
+    async fn sensor_stream(&self) -> BoxStream<'static, EventPayload> {
+        Box::pin(stream::empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mechos_types::{MetersPerSecond, RadiansPerSecond};
+
+    const MAPPING: &str = r#"
+        [[command]]
+        intent = "drive"
+        id = 0x101
+        dlc = 4
+        signals = [
+          { field = "linear_velocity",  start_bit = 0,  length = 16, signed = true, scale = 0.001 },
+          { field = "angular_velocity", start_bit = 16, length = 16, signed = true, scale = 0.001 },
+        ]
+
+        [[command]]
+        intent = "trigger_relay"
+        relay_id = "pump"
+        id = 0x102
+        dlc = 1
+        signals = [{ field = "state", start_bit = 0, length = 1 }]
+
+        [[status]]
+        payload = "power_status"
+        id = 0x201
+        signals = [
+          { field = "voltage", start_bit = 7, length = 16, byte_order = "big_endian", scale = 0.01 },
+          { field = "percent", start_bit = 16, length = 8 },
+        ]
+
+        [[status]]
+        payload = "power_status"
+        id = 0x202
+        signals = [{ field = "charging", start_bit = 0, length = 1 }]
+    "#;
+
+    fn make_adapter() -> (Arc<EventBus>, CanAdapter) {
+        let bus = Arc::new(EventBus::default());
+        let adapter = CanAdapter::new(Arc::clone(&bus), CanMapping::from_toml(MAPPING).unwrap());
+        (bus, adapter)
+    }
+
+    #[test]
+    fn drive_encodes_scaled_little_endian_signals() {
+        let (_, adapter) = make_adapter();
+        let frame = adapter
+            .encode_intent(&HardwareIntent::Drive {
+                linear_velocity: MetersPerSecond(0.5),
+                angular_velocity: RadiansPerSecond(-0.25),
+            })
+            .unwrap();
+        assert_eq!(frame.id, 0x101);
+        // 500 = 0x01F4, -250 = 0xFF06.
+        assert_eq!(frame.data, vec![0xF4, 0x01, 0x06, 0xFF]);
+    }
+
+    #[test]
+    fn relay_mapping_is_selected_by_relay_id() {
+        let (_, adapter) = make_adapter();
+        let relay = |id: &str| HardwareIntent::TriggerRelay {
+            relay_id: id.to_string(),
+            state: true,
+        };
+        let frame = adapter.encode_intent(&relay("pump")).unwrap();
+        assert_eq!((frame.id, frame.data), (0x102, vec![0x01]));
+        assert!(matches!(
+            adapter.encode_intent(&relay("horn")),
+            Err(MechError::HardwareFault {
+                code: FaultCode::Unsupported,
+                ..
+            })
+        ));
+        assert!(adapter.encode_intent(&HardwareIntent::Dock).is_err());
+    }
+
+    #[test]
+    fn big_endian_signals_round_trip() {
+        let signal = CanSignal {
+            field: "voltage".to_string(),
+            start_bit: 7,
+            length: 16,
+            byte_order: ByteOrder::BigEndian,
+            signed: false,
+            scale: 0.01,
+            offset: 0.0,
+        };
+        let mut data = [0u8; 8];
+        signal.encode(24.5, &mut data);
+        // 2450 = 0x0992, most significant byte first.
+        assert_eq!(data[..2], [0x09, 0x92]);
+        assert!((signal.decode(&data) - 24.5).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn status_frames_merge_into_one_payload() {
+        let (bus, adapter) = make_adapter();
+        let mut rx = bus.subscribe();
+
+        let frame = |id, data: &[u8]| CanFrame {
+            id,
+            extended: false,
+            data: data.to_vec(),
+        };
+        adapter
+            .ingest_frame(&frame(0x201, &[0x04, 0xD8, 72]))
+            .unwrap();
+        adapter.ingest_frame(&frame(0x202, &[0x01])).unwrap();
+        assert_eq!(adapter.ingest_frame(&frame(0x300, &[0xFF])).unwrap(), 0);
+
+        let first = rx.recv().await.unwrap();
+        assert_eq!(first.source, "mechos-middleware::can/0x201");
+        let EventPayload::PowerStatus {
+            voltage,
+            percent,
+            charging,
+            ..
+        } = rx.recv().await.unwrap().payload
+        else {
+            panic!("expected PowerStatus");
+        };
+        assert!((voltage - 12.4).abs() < 1e-4);
+        assert_eq!(percent, 72.0);
+        assert!(charging, "second frame updates the merged payload");
+    }
+
+    #[test]
+    fn invalid_mappings_are_rejected() {
+        let bad = [
+            r#"[[command]]
+               intent = "drive"
+               id = 0x800
+               signals = []"#,
+            r#"[[command]]
+               intent = "drive"
+               id = 1
+               dlc = 2
+               signals = [{ field = "linear_velocity", start_bit = 8, length = 16 }]"#,
+            r#"[[status]]
+               payload = "telemetry"
+               id = 1
+               signals = [{ field = "speed", start_bit = 0, length = 8 }]"#,
+        ];
+        for text in bad {
+            assert!(
+                matches!(CanMapping::from_toml(text), Err(MechError::Parsing(_))),
+                "mapping should be rejected: {text}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn execute_without_socket_is_a_channel_error() {
+        let (_, adapter) = make_adapter();
+        let result = adapter
+            .execute_intent(HardwareIntent::Drive {
+                linear_velocity: MetersPerSecond(0.1),
+                angular_velocity: RadiansPerSecond(0.0),
+            })
+            .await;
+        assert!(matches!(result, Err(MechError::Channel(_))));
+    }
+}
@@ -11,17 +11,45 @@
 //! * **Inbound (Simulated LiDAR)** – `/sim_scan` messages from the dashboard
 //!   (packed `sensor_msgs/msg/LaserScan` arrays produced by virtual raycasts)
 //!   are parsed and fed into the [`EventBus`] as [`EventPayload::Telemetry`].
+//!
+//! * **Virtual world** – [`SimParams`] sets the robot footprint, its
+//!   acceleration limits and the LiDAR's field of view, resolution and noise.
+//!   The parameters and the [`VirtualObstacle`]s added through
+//!   [`add_obstacle`][DashboardSimAdapter::add_obstacle] are published on
+//!   `/sim/config` and `/sim/obstacles`, and again whenever the dashboard
+//!   announces itself on `/sim/ready`.  Obstacles are also ray-cast into every
+//!   ingested scan, and touching one with the footprint raises a
+//!   [`FaultCode::CollisionPredicted`] fault.  Scans without a battery reading
+//!   drain a virtual battery with time and distance travelled, published as
+//!   [`EventPayload::PowerStatus`], so the battery interlock and the
+//!   stale-data rule (via
+//!   [`set_odometry_dropout`][DashboardSimAdapter::set_odometry_dropout]) can
+//!   be exercised without hardware.
+//!
+//! [`DashboardSimAdapter::run`] holds the WebSocket open through a
+//! [`WsSupervisor`]: dropped connections are retried with exponential
+//! backoff, the `/sim_scan`, `/sim/ready` and `/hitl/human_response`
+//! subscriptions are replayed on every reconnect, and link state changes are
+//! published on [`Topic::SystemAlerts`][crate::bus::Topic::SystemAlerts].
 
 use async_trait::async_trait;
 use futures_util::stream::{self, BoxStream};
-use mechos_types::{Event, EventPayload, HardwareIntent, MechError, TelemetryData};
-use serde_json::json;
-use std::sync::Arc;
+use mechos_types::{
+    Event, EventPayload, FaultCode, HardwareIntent, HumanAnswer, MechError, Meters, MetersPerSecond,
+    RadiansPerSecond, SCHEMA_VERSION, TelemetryData,
+};
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Instant;
+use tracing::warn;
 use uuid::Uuid;
 use chrono::Utc;
 
 use crate::adapter::MechAdapter;
 use crate::bus::EventBus;
+use crate::supervisor::{Backoff, WsSupervisor};
 
 /// Maximum number of LiDAR range readings accepted in a single simulated scan.
 ///
@@ -36,12 +64,117 @@ pub const MAX_SIM_LIDAR_RANGES: usize = 4096;
 /// Responses longer than this are rejected before they reach the event bus.
 pub const MAX_HUMAN_RESPONSE_BYTES: usize = 64 * 1024; // 64 KiB
 
+/// Maximum number of virtual obstacles held at once.
+pub const MAX_VIRTUAL_OBSTACLES: usize = 256;
+
+/// Physical and sensor parameters of the simulated robot.
+///
+/// Sent to the dashboard on `/sim/config`; the footprint, field of view and
+/// noise are also applied by the adapter itself.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SimParams {
+    /// Radius (m) of the robot's circular footprint.
+    pub footprint_radius_m: f32,
+    /// Linear acceleration limit (m/s²) of the physics body.
+    pub max_linear_accel: f32,
+    /// Angular acceleration limit (rad/s²) of the physics body.
+    pub max_angular_accel: f32,
+    /// LiDAR field of view (rad), centred on the robot's heading.
+    pub lidar_fov_rad: f32,
+    /// Number of LiDAR rays cast per scan.
+    pub lidar_samples: usize,
+    /// Standard deviation (m) of the Gaussian noise added to each range.
+    pub lidar_noise_std_m: f32,
+    /// Battery drain (percentage points per second) while powered.
+    pub battery_drain_per_s: f32,
+    /// Additional battery drain (percentage points per metre driven).
+    pub battery_drain_per_m: f32,
+}
+
+impl Default for SimParams {
+    fn default() -> Self {
+        Self {
+            footprint_radius_m: 0.3,
+            max_linear_accel: 1.0,
+            max_angular_accel: 2.0,
+            lidar_fov_rad: std::f32::consts::PI,
+            lidar_samples: 181,
+            lidar_noise_std_m: 0.0,
+            battery_drain_per_s: 0.01,
+            battery_drain_per_m: 0.2,
+        }
+    }
+}
+
+/// A round obstacle injected into the virtual world.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct VirtualObstacle {
+    /// Centre X (m) in the map frame.
+    pub x: f32,
+    /// Centre Y (m) in the map frame.
+    pub y: f32,
+    /// Radius (m).
+    pub radius: f32,
+}
+
+impl VirtualObstacle {
+    /// Distance along the ray from `(x, y)` at `angle` to this obstacle's
+    /// edge, or `None` when the ray misses or starts inside it.
+    fn ray_hit(&self, x: f32, y: f32, angle: f32) -> Option<f32> {
+        let (sin, cos) = angle.sin_cos();
+        let (fx, fy) = (x - self.x, y - self.y);
+        let b = fx * cos + fy * sin;
+        let c = fx * fx + fy * fy - self.radius * self.radius;
+        let discriminant = b * b - c;
+        if c <= 0.0 || discriminant < 0.0 {
+            return None;
+        }
+        let t = -b - discriminant.sqrt();
+        (t > 0.0).then_some(t)
+    }
+}
+
+/// Mutable state of the virtual world.
+struct SimState {
+    /// Virtual battery charge, `0.0`–`100.0`.
+    battery_percent: f32,
+    /// When and where the previous scan was ingested.
+    last_scan: Option<(Instant, f32, f32)>,
+    obstacles: BTreeMap<u32, VirtualObstacle>,
+    next_obstacle_id: u32,
+    /// While `true`, scans publish no pose telemetry.
+    odometry_dropout: bool,
+    /// Obstacles the footprint currently touches, so each contact is
+    /// reported once.
+    contacts: Vec<u32>,
+    /// xorshift64 state for the LiDAR noise.
+    rng: u64,
+}
+
+impl SimState {
+    /// Draw from a standard normal distribution (Box–Muller).
+    fn next_gaussian(&mut self) -> f32 {
+        let mut uniform = || {
+            self.rng ^= self.rng << 13;
+            self.rng ^= self.rng >> 7;
+            self.rng ^= self.rng << 17;
+            ((self.rng >> 11) as f64 + 1.0) / (1u64 << 53) as f64
+        };
+        let (u1, u2) = (uniform(), uniform());
+        ((-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()) as f32
+    }
+}
+
 /// Adapter that communicates with the React / Three.js simulation dashboard
 /// over a `rosbridge_server`-compatible WebSocket.
 pub struct DashboardSimAdapter {
     bus: Arc<EventBus>,
     /// `ws://host:port` of the dashboard's rosbridge endpoint.
     rosbridge_url: String,
+    /// Keeps the rosbridge WebSocket alive while [`run`][Self::run] is active.
+    link: WsSupervisor,
+    params: SimParams,
+    state: Mutex<SimState>,
 }
 
 impl DashboardSimAdapter {
@@ -50,10 +183,128 @@ impl DashboardSimAdapter {
     /// `rosbridge_url` should be the WebSocket URL of the dashboard's
     /// `rosbridge_server` (e.g. `"ws://localhost:9090"`).
     pub fn new(bus: Arc<EventBus>, rosbridge_url: impl Into<String>) -> Self {
+        let rosbridge_url = rosbridge_url.into();
+        let link = WsSupervisor::new(Arc::clone(&bus), "dashboard_sim", rosbridge_url.clone())
+            .with_subscription(json!({ "op": "subscribe", "topic": "/sim_scan" }).to_string())
+            .with_subscription(json!({ "op": "subscribe", "topic": "/sim/ready" }).to_string())
+            .with_subscription(
+                json!({ "op": "subscribe", "topic": "/hitl/human_response" }).to_string(),
+            );
         Self {
             bus,
-            rosbridge_url: rosbridge_url.into(),
+            rosbridge_url,
+            link,
+            params: SimParams::default(),
+            state: Mutex::new(SimState {
+                battery_percent: 100.0,
+                last_scan: None,
+                obstacles: BTreeMap::new(),
+                next_obstacle_id: 1,
+                odometry_dropout: false,
+                contacts: Vec::new(),
+                rng: Uuid::new_v4().as_u64_pair().0 | 1,
+            }),
+        }
+    }
+
+    /// Replace the reconnection backoff used by [`run`][Self::run].
+    pub fn with_backoff(mut self, backoff: Backoff) -> Self {
+        self.link = self.link.with_backoff(backoff);
+        self
+    }
+
+    /// Replace the simulation parameters (default: [`SimParams::default`]).
+    pub fn with_params(mut self, params: SimParams) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// The simulation parameters in use.
+    pub fn params(&self) -> &SimParams {
+        &self.params
+    }
+
+    /// Add `obstacle` to the virtual world and return its id.  The updated
+    /// obstacle list is sent to the dashboard while connected.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Parsing`] for a non-finite position, a radius
+    /// that is not positive, or when [`MAX_VIRTUAL_OBSTACLES`] are held.
+    pub fn add_obstacle(&self, obstacle: VirtualObstacle) -> Result<u32, MechError> {
+        if !obstacle.x.is_finite() || !obstacle.y.is_finite() {
+            return Err(MechError::Parsing(
+                "virtual obstacle position must be finite".to_string(),
+            ));
+        }
+        if !obstacle.radius.is_finite() || obstacle.radius <= 0.0 {
+            return Err(MechError::Parsing(format!(
+                "virtual obstacle radius {} must be positive",
+                obstacle.radius
+            )));
+        }
+        let id = {
+            let mut state = self.state();
+            if state.obstacles.len() >= MAX_VIRTUAL_OBSTACLES {
+                return Err(MechError::Parsing(format!(
+                    "at most {MAX_VIRTUAL_OBSTACLES} virtual obstacles are allowed"
+                )));
+            }
+            let id = state.next_obstacle_id;
+            state.next_obstacle_id += 1;
+            state.obstacles.insert(id, obstacle);
+            id
+        };
+        self.forward(&self.obstacles_frame());
+        Ok(id)
+    }
+
+    /// Remove the obstacle `id`; returns `false` if there is none.
+    pub fn remove_obstacle(&self, id: u32) -> bool {
+        let removed = {
+            let mut state = self.state();
+            state.contacts.retain(|&c| c != id);
+            state.obstacles.remove(&id).is_some()
+        };
+        if removed {
+            self.forward(&self.obstacles_frame());
+        }
+        removed
+    }
+
+    /// Remove every virtual obstacle.
+    pub fn clear_obstacles(&self) {
+        {
+            let mut state = self.state();
+            state.obstacles.clear();
+            state.contacts.clear();
         }
+        self.forward(&self.obstacles_frame());
+    }
+
+    /// The virtual obstacles with their ids, in id order.
+    pub fn obstacles(&self) -> Vec<(u32, VirtualObstacle)> {
+        self.state().obstacles.iter().map(|(&id, &o)| (id, o)).collect()
+    }
+
+    /// Charge of the virtual battery, `0.0`–`100.0`.
+    pub fn battery_percent(&self) -> f32 {
+        self.state().battery_percent
+    }
+
+    /// Set the virtual battery's charge, e.g. to start a scenario near empty.
+    pub fn set_battery_percent(&self, percent: f32) {
+        self.state().battery_percent = if percent.is_nan() {
+            0.0
+        } else {
+            percent.clamp(0.0, 100.0)
+        };
+    }
+
+    /// While `dropout` is `true`, ingested scans publish no pose
+    /// [`EventPayload::Telemetry`], so odometry goes stale.
+    pub fn set_odometry_dropout(&self, dropout: bool) {
+        self.state().odometry_dropout = dropout;
     }
 
     /// Return the rosbridge URL this adapter is configured to use.
@@ -61,6 +312,82 @@ impl DashboardSimAdapter {
         &self.rosbridge_url
     }
 
+    /// `true` while [`run`][Self::run] holds an open WebSocket.
+    pub fn is_connected(&self) -> bool {
+        self.link.is_connected()
+    }
+
+    /// Connect to the dashboard's `rosbridge_server` forever, reconnecting
+    /// with exponential backoff.  Incoming frames are handled by
+    /// [`handle_rosbridge_message`][Self::handle_rosbridge_message].
+    pub async fn run(&self) {
+        self.link
+            .run(|text| {
+                if let Err(e) = self.handle_rosbridge_message(text) {
+                    warn!(error = %e, "dropping dashboard message");
+                }
+            })
+            .await;
+    }
+
+    /// Dispatch one `rosbridge_server` frame received from the dashboard.
+    ///
+    /// * `/sim_scan` – `msg.ranges` plus the optional pose fields
+    ///   `position_x`, `position_y` and `heading_rad` (defaulting to the
+    ///   origin) and `battery_percent` (defaulting to the virtual battery) are
+    ///   passed to [`ingest_sim_scan`][Self::ingest_sim_scan].
+    /// * `/sim/ready` – the dashboard loaded its scene; the `/sim/config` and
+    ///   `/sim/obstacles` frames are sent back.
+    /// * `/hitl/human_response` – `msg.response` and the optional
+    ///   `msg.question_id` are passed to
+    ///   [`ingest_human_response`][Self::ingest_human_response].
+    ///
+    /// Frames on any other topic are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Parsing`] for malformed JSON or a rejected
+    /// payload.
+    pub fn handle_rosbridge_message(&self, text: &str) -> Result<(), MechError> {
+        let json: serde_json::Value =
+            serde_json::from_str(text).map_err(|e| MechError::Parsing(e.to_string()))?;
+        let topic = json.get("topic").and_then(|t| t.as_str()).unwrap_or("");
+        let msg = json.get("msg").cloned().unwrap_or_default();
+        let field = |name: &str| msg.get(name).and_then(|v| v.as_f64());
+        match topic {
+            "/sim_scan" => {
+                let ranges: Vec<f32> = msg
+                    .get("ranges")
+                    .and_then(|r| r.as_array())
+                    .ok_or_else(|| MechError::Parsing("/sim_scan without ranges".to_string()))?
+                    .iter()
+                    .map(|r| r.as_f64().unwrap_or(f64::INFINITY) as f32)
+                    .collect();
+                self.ingest_sim_scan(
+                    &ranges,
+                    Meters(field("position_x").unwrap_or(0.0) as f32),
+                    Meters(field("position_y").unwrap_or(0.0) as f32),
+                    field("heading_rad").unwrap_or(0.0) as f32,
+                    field("battery_percent").map(|b| b.clamp(0.0, 100.0) as u8),
+                )
+                .map(|_| ())
+            }
+            "/sim/ready" => {
+                self.forward(&Self::build_sim_config_frame(&self.params));
+                self.forward(&self.obstacles_frame());
+                Ok(())
+            }
+            "/hitl/human_response" => {
+                let response = msg.get("response").and_then(|r| r.as_str()).ok_or_else(|| {
+                    MechError::Parsing("/hitl/human_response without response".to_string())
+                })?;
+                let question_id = msg.get("question_id").and_then(|q| q.as_str());
+                self.ingest_human_response(question_id, response).map(|_| ())
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Ingest a `sensor_msgs/msg/LaserScan` message received from the
     /// dashboard's `/sim_scan` topic and publish it as a
     /// [`EventPayload::Telemetry`] event on the internal bus.
@@ -69,17 +396,27 @@ impl DashboardSimAdapter {
     /// [`EventPayload::LidarScan`] event is also published so that the Cockpit
     /// Sensory Visualizer can render the raw scan points.
     ///
-    /// `_ranges` contains virtual distances (metres) produced by the
-    /// dashboard's Three.js raycasts.  The scan uses the ROS-standard field of
-    /// view: `angle_min = -π/2`, `angle_increment = π / (N − 1)` where `N` is
-    /// the number of range samples.
+    /// `ranges` contains virtual distances (metres) produced by the
+    /// dashboard's Three.js raycasts.  The beams span
+    /// [`SimParams::lidar_fov_rad`] centred on the heading, so
+    /// `angle_min = -fov / 2` and `angle_increment = fov / (N − 1)` where `N`
+    /// is the number of range samples.  Virtual obstacles shorten the beams
+    /// that hit them, and [`SimParams::lidar_noise_std_m`] noise is added to
+    /// every finite range.
+    ///
+    /// With `battery_percent` of `None` the virtual battery is drained for the
+    /// time and distance since the previous scan, reported in the telemetry
+    /// and published as an [`EventPayload::PowerStatus`] event.  A footprint
+    /// newly touching a virtual obstacle publishes an
+    /// [`EventPayload::HardwareFault`].  Returns the number of subscribers
+    /// that received the telemetry, which is `0` during an odometry dropout.
     pub fn ingest_sim_scan(
         &self,
         ranges: &[f32],
-        position_x: f32,
-        position_y: f32,
+        position_x: Meters,
+        position_y: Meters,
         heading_rad: f32,
-        battery_percent: u8,
+        battery_percent: Option<u8>,
     ) -> Result<usize, MechError> {
         // ── Input validation ───────────────────────────────────────────────
         if ranges.len() > MAX_SIM_LIDAR_RANGES {
@@ -89,39 +426,106 @@ impl DashboardSimAdapter {
                 MAX_SIM_LIDAR_RANGES,
             )));
         }
-        let event = Event {
-            id: Uuid::new_v4(),
-            timestamp: Utc::now(),
-            source: "mechos-middleware::dashboard/sim_scan".to_string(),
-            payload: EventPayload::Telemetry(TelemetryData {
-                position_x,
-                position_y,
-                heading_rad,
-                battery_percent,
-            }),
-            trace_id: None,
+        let (x, y) = (position_x.get(), position_y.get());
+        let fov = self.params.lidar_fov_rad;
+        let angle_min_rad = -fov / 2.0;
+        let angle_increment_rad = if ranges.len() > 1 {
+            fov / (ranges.len() - 1) as f32
+        } else {
+            0.0
+        };
+
+        let mut state = self.state();
+        let now = Instant::now();
+        let virtual_battery = battery_percent.is_none();
+        if virtual_battery && let Some((at, last_x, last_y)) = state.last_scan {
+            let drain = self.params.battery_drain_per_s * now.duration_since(at).as_secs_f32()
+                + self.params.battery_drain_per_m * (x - last_x).hypot(y - last_y);
+            state.battery_percent = (state.battery_percent - drain).max(0.0);
+        }
+        state.last_scan = Some((now, x, y));
+        let battery = battery_percent.map_or(state.battery_percent, f32::from);
+
+        let mut scan = ranges.to_vec();
+        for (i, range) in scan.iter_mut().enumerate() {
+            let angle = heading_rad + angle_min_rad + i as f32 * angle_increment_rad;
+            for obstacle in state.obstacles.values() {
+                if let Some(hit) = obstacle.ray_hit(x, y, angle)
+                    && (range.is_nan() || *range > hit)
+                {
+                    *range = hit;
+                }
+            }
+        }
+        if self.params.lidar_noise_std_m > 0.0 {
+            for range in scan.iter_mut().filter(|r| r.is_finite()) {
+                *range = (*range + self.params.lidar_noise_std_m * state.next_gaussian()).max(0.0);
+            }
+        }
+
+        let touching: Vec<u32> = state
+            .obstacles
+            .iter()
+            .filter(|(_, o)| (x - o.x).hypot(y - o.y) <= o.radius + self.params.footprint_radius_m)
+            .map(|(&id, _)| id)
+            .collect();
+        let new_contacts: Vec<u32> = touching
+            .iter()
+            .copied()
+            .filter(|id| !state.contacts.contains(id))
+            .collect();
+        state.contacts = touching;
+        let odometry_dropout = state.odometry_dropout;
+        drop(state);
+
+        let n = if odometry_dropout {
+            0
+        } else {
+            self.publish(
+                "sim_scan",
+                EventPayload::Telemetry(TelemetryData {
+                    position_x,
+                    position_y,
+                    heading_rad,
+                    battery_percent: battery.round() as u8,
+                    stamp: None,
+                }),
+            )?
         };
-        let n = self.bus.publish(event)?;
-
-        if !ranges.is_empty() {
-            let n_samples = ranges.len() as f32;
-            let angle_increment_rad = if n_samples > 1.0 {
-                std::f32::consts::PI / (n_samples - 1.0)
-            } else {
-                0.0
-            };
-            let scan_event = Event {
-                id: Uuid::new_v4(),
-                timestamp: Utc::now(),
-                source: "mechos-middleware::dashboard/sim_scan/lidar".to_string(),
-                payload: EventPayload::LidarScan {
-                    ranges: ranges.to_vec(),
-                    angle_min_rad: -std::f32::consts::FRAC_PI_2,
+
+        if virtual_battery {
+            let _ = self.publish(
+                "sim_scan/battery",
+                EventPayload::PowerStatus {
+                    voltage: 0.0,
+                    current: 0.0,
+                    charging: false,
+                    percent: battery,
+                },
+            );
+        }
+
+        if !scan.is_empty() {
+            let _ = self.publish(
+                "sim_scan/lidar",
+                EventPayload::LidarScan {
+                    ranges: scan,
+                    angle_min_rad,
                     angle_increment_rad,
+                    stamp: None,
                 },
-                trace_id: None,
-            };
-            let _ = self.bus.publish(scan_event);
+            );
+        }
+
+        for id in new_contacts {
+            let _ = self.publish(
+                "sim_scan/contact",
+                EventPayload::HardwareFault {
+                    component: "dashboard_sim".to_string(),
+                    code: FaultCode::CollisionPredicted,
+                    message: format!("robot footprint touches virtual obstacle {id}"),
+                },
+            );
         }
 
         Ok(n)
@@ -132,12 +536,14 @@ impl DashboardSimAdapter {
     ///
     /// Call this when the dashboard WebSocket sends a message on the
     /// `/hitl/human_response` topic.  The response is published as a
-    /// [`EventPayload::HumanResponse`] event so that the [`AgentLoop`] can
+    /// [`EventPayload::HumanResponse`] event answering `question_id` (the
+    /// oldest open question when `None`) so that the [`AgentLoop`] can
     /// inject it into the LLM context window and resume the OODA cycle.
     ///
     /// [`AgentLoop`]: mechos_runtime::AgentLoop
     pub fn ingest_human_response(
         &self,
+        question_id: Option<&str>,
         response: impl Into<String>,
     ) -> Result<usize, MechError> {
         let response = response.into();
@@ -153,8 +559,13 @@ impl DashboardSimAdapter {
             id: Uuid::new_v4(),
             timestamp: Utc::now(),
             source: "mechos-middleware::dashboard/human_response".to_string(),
-            payload: EventPayload::HumanResponse(response),
+            payload: EventPayload::HumanResponse(HumanAnswer {
+                question_id: question_id.map(str::to_string),
+                answer: response,
+                operator: None,
+            }),
             trace_id: None,
+            correlation_id: None,
         };
         self.bus.publish(event)
     }
@@ -163,24 +574,49 @@ impl DashboardSimAdapter {
     ///
     /// Returns a serialised publish command that the dashboard can display as a
     /// UI alert.  The human operator's answer should be sent back on the
-    /// `/hitl/human_response` topic.
-    pub fn build_ask_human_frame(question: &str, context_image_id: Option<&str>) -> String {
+    /// `/hitl/human_response` topic with the same `question_id`.
+    pub fn build_ask_human_frame(
+        question: &str,
+        context_image_id: Option<&str>,
+        question_id: Option<&str>,
+    ) -> Value {
         json!({
             "op": "publish",
             "topic": "/hitl/ask_human",
             "msg": {
                 "question": question,
-                "context_image_id": context_image_id
+                "context_image_id": context_image_id,
+                "question_id": question_id
             }
         })
-        .to_string()
+    }
+
+    /// Build the `/sim/config` frame carrying `params`.
+    pub fn build_sim_config_frame(params: &SimParams) -> Value {
+        json!({ "op": "publish", "topic": "/sim/config", "msg": params })
+    }
+
+    /// Build the `/sim/obstacles` frame listing `obstacles` with their ids.
+    pub fn build_obstacles_frame(obstacles: &[(u32, VirtualObstacle)]) -> Value {
+        let obstacles: Vec<_> = obstacles
+            .iter()
+            .map(|(id, o)| json!({ "id": id, "x": o.x, "y": o.y, "radius": o.radius }))
+            .collect();
+        json!({
+            "op": "publish",
+            "topic": "/sim/obstacles",
+            "msg": { "obstacles": obstacles }
+        })
     }
 
     /// Build the `rosbridge_server` JSON frame for a `Drive` intent.
     ///
     /// Returns the serialised `geometry_msgs/msg/Twist` publish command that
     /// should be forwarded to the dashboard WebSocket.
-    pub fn build_twist_frame(linear_velocity: f32, angular_velocity: f32) -> String {
+    pub fn build_twist_frame(
+        linear_velocity: MetersPerSecond,
+        angular_velocity: RadiansPerSecond,
+    ) -> Value {
         json!({
             "op": "publish",
             "topic": "/cmd_vel",
@@ -189,7 +625,70 @@ impl DashboardSimAdapter {
                 "angular": { "x": 0.0, "y": 0.0, "z": angular_velocity }
             }
         })
-        .to_string()
+    }
+
+    /// Build the `rosbridge_server` JSON frame for a `MoveJoint` or
+    /// `SetGripper` intent.
+    ///
+    /// Returns a single-point `trajectory_msgs/msg/JointTrajectory` publish
+    /// command; the gripper is addressed as the `"gripper"` joint.
+    pub fn build_joint_trajectory_frame(
+        joint: &str,
+        position: f32,
+        max_velocity: Option<RadiansPerSecond>,
+    ) -> Value {
+        json!({
+            "op": "publish",
+            "topic": "/sim/joint_trajectory",
+            "msg": {
+                "joint_names": [joint],
+                "points": [{ "positions": [position] }],
+                "max_velocity": max_velocity
+            }
+        })
+    }
+
+    fn publish_joint_frame(&self, frame: Value) -> Result<(), MechError> {
+        self.forward(&frame);
+        let event = Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: "mechos-middleware::dashboard/joint_trajectory".to_string(),
+            payload: EventPayload::adapter_command(frame),
+            trace_id: None,
+            correlation_id: None,
+        };
+        self.bus.publish(event).map(|_| ())
+    }
+
+    fn obstacles_frame(&self) -> Value {
+        Self::build_obstacles_frame(&self.obstacles())
+    }
+
+    fn state(&self) -> MutexGuard<'_, SimState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Publish `payload` from `mechos-middleware::dashboard/{path}`.
+    fn publish(&self, path: &str, payload: EventPayload) -> Result<usize, MechError> {
+        self.bus.publish(Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: format!("mechos-middleware::dashboard/{path}"),
+            payload,
+            trace_id: None,
+            correlation_id: None,
+        })
+    }
+
+    /// Send an actuation frame straight to the dashboard when the supervised
+    /// WebSocket is up.  The bus copy is published either way.
+    fn forward(&self, frame: &Value) {
+        if self.link.is_connected()
+            && let Err(e) = self.link.handle().send(frame.to_string())
+        {
+            warn!(error = %e, "cannot forward frame to the dashboard");
+        }
     }
 }
 
@@ -198,13 +697,22 @@ impl MechAdapter for DashboardSimAdapter {
     /// Translate a [`HardwareIntent`] into a simulated dashboard command.
     ///
     /// * `Drive` – serialises a `geometry_msgs/msg/Twist` JSON frame and
-    ///   publishes it onto the bus as an [`EventPayload::AgentThought`].  In a
-    ///   real deployment the frame would be forwarded to the dashboard's
-    ///   `rosbridge_server` WebSocket; the Three.js / Rapier physics engine
-    ///   then moves the virtual robot.
+    ///   publishes it onto the bus as an [`EventPayload::AdapterCommand`].  While
+    ///   [`run`][DashboardSimAdapter::run] holds the dashboard's
+    ///   `rosbridge_server` WebSocket open, the frame is also sent there; the
+    ///   Three.js / Rapier physics engine then moves the virtual robot.
+    ///
+    /// * `Halt` – publishes (and forwards) a zero-velocity `Twist` frame on
+    ///   `/cmd_vel`.
     ///
-    /// * All other intents – publish an [`EventPayload::AgentThought`]
-    ///   containing a JSON-encoded description so the dashboard can display or
+    /// * `MoveJoint` / `SetGripper` – publish (and forward) a
+    ///   `JointTrajectory` frame.
+    ///
+    /// * `Arm` / `SetAltitude` / `Goto` – rejected with
+    ///   [`FaultCode::Unsupported`]; the simulator has no flight model.
+    ///
+    /// * All other intents – publish an [`EventPayload::AdapterCommand`]
+    ///   carrying a `/sim/...` or fleet frame so the dashboard can display or
     ///   log the intent.
     async fn execute_intent(&self, intent: HardwareIntent) -> Result<(), MechError> {
         match &intent {
@@ -213,16 +721,18 @@ impl MechAdapter for DashboardSimAdapter {
                 angular_velocity,
             } => {
                 let frame = Self::build_twist_frame(*linear_velocity, *angular_velocity);
+                self.forward(&frame);
                 let event = Event {
                     id: Uuid::new_v4(),
                     timestamp: Utc::now(),
                     source: "mechos-middleware::dashboard/cmd_vel".to_string(),
-                    payload: EventPayload::AgentThought(frame),
+                    payload: EventPayload::adapter_command(frame),
                     trace_id: None,
+                    correlation_id: None,
                 };
                 self.bus.publish(event).map(|_| ())
             }
-            HardwareIntent::MoveEndEffector { x, y, z } => {
+            HardwareIntent::MoveEndEffector { x, y, z, .. } => {
                 let msg = json!({
                     "op": "publish",
                     "topic": "/sim/end_effector",
@@ -232,8 +742,9 @@ impl MechAdapter for DashboardSimAdapter {
                     id: Uuid::new_v4(),
                     timestamp: Utc::now(),
                     source: "mechos-middleware::dashboard/end_effector".to_string(),
-                    payload: EventPayload::AgentThought(msg.to_string()),
+                    payload: EventPayload::adapter_command(msg),
                     trace_id: None,
+                    correlation_id: None,
                 };
                 self.bus.publish(event).map(|_| ())
             }
@@ -247,22 +758,29 @@ impl MechAdapter for DashboardSimAdapter {
                     id: Uuid::new_v4(),
                     timestamp: Utc::now(),
                     source: format!("mechos-middleware::dashboard/relay/{relay_id}"),
-                    payload: EventPayload::AgentThought(msg.to_string()),
+                    payload: EventPayload::adapter_command(msg),
                     trace_id: None,
+                    correlation_id: None,
                 };
                 self.bus.publish(event).map(|_| ())
             }
-            HardwareIntent::AskHuman { question, context_image_id } => {
+            HardwareIntent::AskHuman {
+                question,
+                context_image_id,
+                question_id,
+            } => {
                 let frame = Self::build_ask_human_frame(
                     question,
                     context_image_id.as_deref(),
+                    question_id.as_deref(),
                 );
                 let event = Event {
                     id: Uuid::new_v4(),
                     timestamp: Utc::now(),
                     source: "mechos-middleware::dashboard/ask_human".to_string(),
-                    payload: EventPayload::AgentThought(frame),
+                    payload: EventPayload::adapter_command(frame),
                     trace_id: None,
+                    correlation_id: None,
                 };
                 self.bus.publish(event).map(|_| ())
             }
@@ -281,8 +799,9 @@ impl MechAdapter for DashboardSimAdapter {
                     source: format!(
                         "mechos-middleware::dashboard/fleet/robot/{target_robot_id}/inbox"
                     ),
-                    payload: EventPayload::AgentThought(msg.to_string()),
+                    payload: EventPayload::adapter_command(msg),
                     trace_id: None,
+                    correlation_id: None,
                 };
                 self.bus.publish(event).map(|_| ())
             }
@@ -296,8 +815,9 @@ impl MechAdapter for DashboardSimAdapter {
                     id: Uuid::new_v4(),
                     timestamp: Utc::now(),
                     source: "mechos-middleware::dashboard/fleet/communications".to_string(),
-                    payload: EventPayload::AgentThought(msg.to_string()),
+                    payload: EventPayload::adapter_command(msg),
                     trace_id: None,
+                    correlation_id: None,
                 };
                 self.bus.publish(event).map(|_| ())
             }
@@ -307,6 +827,7 @@ impl MechAdapter for DashboardSimAdapter {
                     "topic": "/fleet/tasks",
                     "msg": {
                         "data": serde_json::to_string(&json!({
+                            "schema_version": SCHEMA_VERSION,
                             "title": title,
                             "description": description
                         })).unwrap_or_default()
@@ -316,8 +837,107 @@ impl MechAdapter for DashboardSimAdapter {
                     id: Uuid::new_v4(),
                     timestamp: Utc::now(),
                     source: "mechos-middleware::dashboard/fleet/tasks".to_string(),
-                    payload: EventPayload::AgentThought(msg.to_string()),
+                    payload: EventPayload::adapter_command(msg),
+                    trace_id: None,
+                    correlation_id: None,
+                };
+                self.bus.publish(event).map(|_| ())
+            }
+            HardwareIntent::Speak { text, voice } => {
+                let msg = json!({
+                    "op": "publish",
+                    "topic": "/sim/tts",
+                    "msg": { "data": text, "voice": voice }
+                });
+                let event = Event {
+                    id: Uuid::new_v4(),
+                    timestamp: Utc::now(),
+                    source: "mechos-middleware::dashboard/tts".to_string(),
+                    payload: EventPayload::adapter_command(msg),
+                    trace_id: None,
+                    correlation_id: None,
+                };
+                self.bus.publish(event).map(|_| ())
+            }
+            HardwareIntent::PlaySound { sound_id } => {
+                let msg = json!({
+                    "op": "publish",
+                    "topic": "/sim/sound",
+                    "msg": { "data": sound_id }
+                });
+                let event = Event {
+                    id: Uuid::new_v4(),
+                    timestamp: Utc::now(),
+                    source: "mechos-middleware::dashboard/sound".to_string(),
+                    payload: EventPayload::adapter_command(msg),
+                    trace_id: None,
+                    correlation_id: None,
+                };
+                self.bus.publish(event).map(|_| ())
+            }
+            HardwareIntent::FollowWaypoints { points, max_speed } => {
+                let waypoints: Vec<_> =
+                    points.iter().map(|(x, y)| json!({ "x": x, "y": y })).collect();
+                let msg = json!({
+                    "op": "publish",
+                    "topic": "/sim/waypoints",
+                    "msg": { "waypoints": waypoints, "max_speed": max_speed }
+                });
+                let event = Event {
+                    id: Uuid::new_v4(),
+                    timestamp: Utc::now(),
+                    source: "mechos-middleware::dashboard/waypoints".to_string(),
+                    payload: EventPayload::adapter_command(msg),
+                    trace_id: None,
+                    correlation_id: None,
+                };
+                self.bus.publish(event).map(|_| ())
+            }
+            HardwareIntent::MoveJoint {
+                joint,
+                angle_rad,
+                max_velocity,
+            } => self.publish_joint_frame(Self::build_joint_trajectory_frame(
+                joint,
+                *angle_rad,
+                Some(*max_velocity),
+            )),
+            HardwareIntent::SetGripper { position } => self.publish_joint_frame(
+                Self::build_joint_trajectory_frame("gripper", *position, None),
+            ),
+            HardwareIntent::Dock | HardwareIntent::Undock => {
+                let msg = json!({
+                    "op": "publish",
+                    "topic": "/sim/dock",
+                    "msg": { "docked": matches!(intent, HardwareIntent::Dock) }
+                });
+                let event = Event {
+                    id: Uuid::new_v4(),
+                    timestamp: Utc::now(),
+                    source: "mechos-middleware::dashboard/dock".to_string(),
+                    payload: EventPayload::adapter_command(msg),
+                    trace_id: None,
+                    correlation_id: None,
+                };
+                self.bus.publish(event).map(|_| ())
+            }
+            HardwareIntent::Arm { .. }
+            | HardwareIntent::SetAltitude { .. }
+            | HardwareIntent::Goto { .. } => Err(MechError::HardwareFault {
+                code: FaultCode::Unsupported,
+                component: "flight_controller".to_string(),
+                details: "the dashboard simulator has no flight model".to_string(),
+            }),
+            HardwareIntent::Halt { .. } => {
+                let frame = Self::build_twist_frame(MetersPerSecond::ZERO, RadiansPerSecond::ZERO);
+                self.forward(&frame);
+                let event = Event {
+                    id: Uuid::new_v4(),
+                    timestamp: Utc::now(),
+                    source: "mechos-middleware::dashboard/cmd_vel".to_string(),
+                    payload: EventPayload::adapter_command(frame),
                     trace_id: None,
+                    correlation_id: None,
                 };
                 self.bus.publish(event).map(|_| ())
             }
@@ -355,21 +975,21 @@ mod tests {
 
         adapter
             .execute_intent(HardwareIntent::Drive {
-                linear_velocity: 0.5,
-                angular_velocity: -0.2,
+                linear_velocity: MetersPerSecond(0.5),
+                angular_velocity: RadiansPerSecond(-0.2),
             })
             .await
             .unwrap();
 
         let event = rx.recv().await.unwrap();
         assert_eq!(event.source, "mechos-middleware::dashboard/cmd_vel");
-        if let EventPayload::AgentThought(json_str) = event.payload {
-            assert!(json_str.contains("/cmd_vel"));
-            assert!(json_str.contains("linear"));
-            assert!(json_str.contains("angular"));
-        } else {
-            panic!("expected AgentThought");
-        }
+        let EventPayload::AdapterCommand { topic, json } = event.payload else {
+            panic!("expected AdapterCommand");
+        };
+        assert_eq!(topic, "/cmd_vel");
+        assert_eq!(json["msg"]["linear"]["x"], 0.5);
+        let angular = json["msg"]["angular"]["z"].as_f64().unwrap();
+        assert!((angular + 0.2).abs() < 1e-6);
     }
 
     #[tokio::test]
@@ -378,15 +998,15 @@ mod tests {
         let mut rx = bus.subscribe();
 
         adapter
-            .ingest_sim_scan(&[0.5, 1.0, 1.5], 1.0, 2.0, 0.3, 75)
+            .ingest_sim_scan(&[0.5, 1.0, 1.5], Meters(1.0), Meters(2.0), 0.3, Some(75))
             .unwrap();
 
         let event = rx.recv().await.unwrap();
         assert_eq!(event.source, "mechos-middleware::dashboard/sim_scan");
         assert!(matches!(event.payload, EventPayload::Telemetry(_)));
         if let EventPayload::Telemetry(t) = event.payload {
-            assert!((t.position_x - 1.0).abs() < f32::EPSILON);
-            assert!((t.position_y - 2.0).abs() < f32::EPSILON);
+            assert!((t.position_x.get() - 1.0).abs() < f32::EPSILON);
+            assert!((t.position_y.get() - 2.0).abs() < f32::EPSILON);
             assert_eq!(t.battery_percent, 75);
         }
     }
@@ -400,14 +1020,14 @@ mod tests {
 
     #[test]
     fn build_twist_frame_contains_expected_fields() {
-        let frame = DashboardSimAdapter::build_twist_frame(1.0, -0.5);
-        assert!(frame.contains("/cmd_vel"));
-        assert!(frame.contains("linear"));
-        assert!(frame.contains("angular"));
+        let frame = DashboardSimAdapter::build_twist_frame(MetersPerSecond(1.0), RadiansPerSecond(-0.5));
+        assert_eq!(frame["topic"], "/cmd_vel");
+        assert_eq!(frame["msg"]["linear"]["x"], 1.0);
+        assert_eq!(frame["msg"]["angular"]["z"], -0.5);
     }
 
     #[tokio::test]
-    async fn execute_ask_human_publishes_agent_thought() {
+    async fn execute_ask_human_publishes_adapter_command() {
         let (bus, adapter) = make_adapter();
         let mut rx = bus.subscribe();
 
@@ -415,18 +1035,18 @@ mod tests {
             .execute_intent(HardwareIntent::AskHuman {
                 question: "Ready to proceed?".to_string(),
                 context_image_id: None,
+                question_id: None,
             })
             .await
             .unwrap();
 
         let event = rx.recv().await.unwrap();
         assert_eq!(event.source, "mechos-middleware::dashboard/ask_human");
-        if let EventPayload::AgentThought(json_str) = event.payload {
-            assert!(json_str.contains("/hitl/ask_human"), "must target the HITL topic");
-            assert!(json_str.contains("Ready to proceed?"));
-        } else {
-            panic!("expected AgentThought");
-        }
+        let EventPayload::AdapterCommand { topic, json } = event.payload else {
+            panic!("expected AdapterCommand");
+        };
+        assert_eq!(topic, "/hitl/ask_human", "must target the HITL topic");
+        assert_eq!(json["msg"]["question"], "Ready to proceed?");
     }
 
     #[test]
@@ -434,17 +1054,19 @@ mod tests {
         let frame = DashboardSimAdapter::build_ask_human_frame(
             "Should I push the box?",
             Some("frame_042"),
+            Some("q-1"),
         );
-        assert!(frame.contains("/hitl/ask_human"));
-        assert!(frame.contains("Should I push the box?"));
-        assert!(frame.contains("frame_042"));
+        assert_eq!(frame["topic"], "/hitl/ask_human");
+        assert_eq!(frame["msg"]["question"], "Should I push the box?");
+        assert_eq!(frame["msg"]["context_image_id"], "frame_042");
+        assert_eq!(frame["msg"]["question_id"], "q-1");
     }
 
     #[test]
     fn build_ask_human_frame_no_image() {
-        let frame = DashboardSimAdapter::build_ask_human_frame("Proceed?", None);
-        assert!(frame.contains("/hitl/ask_human"));
-        assert!(frame.contains("Proceed?"));
+        let frame = DashboardSimAdapter::build_ask_human_frame("Proceed?", None, None);
+        assert_eq!(frame["topic"], "/hitl/ask_human");
+        assert!(frame["msg"]["context_image_id"].is_null());
     }
 
     #[tokio::test]
@@ -452,12 +1074,16 @@ mod tests {
         let (bus, adapter) = make_adapter();
         let mut rx = bus.subscribe();
 
-        adapter.ingest_human_response("Yes, push it").unwrap();
+        adapter
+            .handle_rosbridge_message(
+                r#"{"op":"publish","topic":"/hitl/human_response","msg":{"response":"Yes, push it","question_id":"q-1"}}"#,
+            )
+            .unwrap();
 
         let event = rx.recv().await.unwrap();
         assert_eq!(event.source, "mechos-middleware::dashboard/human_response");
         if let EventPayload::HumanResponse(resp) = event.payload {
-            assert_eq!(resp, "Yes, push it");
+            assert_eq!(resp, HumanAnswer::to_question("q-1", "Yes, push it"));
         } else {
             panic!("expected HumanResponse");
         }
@@ -467,7 +1093,7 @@ mod tests {
     fn ingest_sim_scan_rejects_oversized_ranges() {
         let (_, adapter) = make_adapter();
         let oversized_ranges: Vec<f32> = vec![1.0; MAX_SIM_LIDAR_RANGES + 1];
-        let result = adapter.ingest_sim_scan(&oversized_ranges, 0.0, 0.0, 0.0, 100);
+        let result = adapter.ingest_sim_scan(&oversized_ranges, Meters(0.0), Meters(0.0), 0.0, Some(100));
         assert!(
             matches!(result, Err(MechError::Parsing(_))),
             "expected Parsing error for oversized simulated LiDAR scan, got: {result:?}"
@@ -480,7 +1106,7 @@ mod tests {
         let mut rx = bus.subscribe();
 
         let max_ranges: Vec<f32> = vec![1.0; MAX_SIM_LIDAR_RANGES];
-        adapter.ingest_sim_scan(&max_ranges, 0.0, 0.0, 0.0, 100).unwrap();
+        adapter.ingest_sim_scan(&max_ranges, Meters(0.0), Meters(0.0), 0.0, Some(100)).unwrap();
 
         let event = rx.recv().await.unwrap();
         assert!(matches!(event.payload, EventPayload::Telemetry(_)));
@@ -490,7 +1116,7 @@ mod tests {
     fn ingest_human_response_rejects_oversized_response() {
         let (_, adapter) = make_adapter();
         let oversized = "x".repeat(MAX_HUMAN_RESPONSE_BYTES + 1);
-        let result = adapter.ingest_human_response(oversized);
+        let result = adapter.ingest_human_response(None, oversized);
         assert!(
             matches!(result, Err(MechError::Parsing(_))),
             "expected Parsing error for oversized human response, got: {result:?}"
@@ -503,13 +1129,127 @@ mod tests {
         let mut rx = bus.subscribe();
 
         let at_limit = "y".repeat(MAX_HUMAN_RESPONSE_BYTES);
-        adapter.ingest_human_response(at_limit.clone()).unwrap();
+        adapter.ingest_human_response(None, at_limit.clone()).unwrap();
 
         let event = rx.recv().await.unwrap();
         if let EventPayload::HumanResponse(resp) = event.payload {
-            assert_eq!(resp.len(), MAX_HUMAN_RESPONSE_BYTES);
+            assert_eq!(resp.answer.len(), MAX_HUMAN_RESPONSE_BYTES);
         } else {
             panic!("expected HumanResponse");
         }
     }
+
+    #[tokio::test]
+    async fn virtual_obstacles_shorten_beams_and_report_contact() {
+        let (bus, adapter) = make_adapter();
+        let mut rx = bus.subscribe();
+        let params = SimParams {
+            lidar_fov_rad: std::f32::consts::PI,
+            ..SimParams::default()
+        };
+        let adapter = adapter.with_params(params);
+        let id = adapter
+            .add_obstacle(VirtualObstacle { x: 2.0, y: 0.0, radius: 0.5 })
+            .unwrap();
+        assert!(adapter.add_obstacle(VirtualObstacle { x: 0.0, y: 0.0, radius: 0.0 }).is_err());
+
+        // Three beams: right, straight ahead, left.
+        adapter
+            .ingest_sim_scan(&[5.0, 5.0, 5.0], Meters(0.0), Meters(0.0), 0.0, Some(90))
+            .unwrap();
+        let mut scan = None;
+        while let Ok(event) = rx.try_recv() {
+            if let EventPayload::LidarScan { ranges, .. } = event.payload {
+                scan = Some(ranges);
+            }
+        }
+        let scan = scan.expect("scan published");
+        assert_eq!(scan[0], 5.0);
+        assert!((scan[1] - 1.5).abs() < 1e-5);
+        assert_eq!(scan[2], 5.0);
+
+        // Driving into the obstacle raises one contact fault, not one per scan.
+        let mut faults = 0;
+        for _ in 0..2 {
+            adapter
+                .ingest_sim_scan(&[], Meters(1.3), Meters(0.0), 0.0, Some(90))
+                .unwrap();
+        }
+        while let Ok(event) = rx.try_recv() {
+            if let EventPayload::HardwareFault { code, .. } = event.payload {
+                assert_eq!(code, FaultCode::CollisionPredicted);
+                faults += 1;
+            }
+        }
+        assert_eq!(faults, 1);
+
+        assert!(adapter.remove_obstacle(id));
+        assert!(!adapter.remove_obstacle(id));
+        assert!(adapter.obstacles().is_empty());
+    }
+
+    #[tokio::test]
+    async fn virtual_battery_drains_with_distance() {
+        let (bus, adapter) = make_adapter();
+        let adapter = adapter.with_params(SimParams {
+            battery_drain_per_s: 0.0,
+            battery_drain_per_m: 2.0,
+            ..SimParams::default()
+        });
+        adapter.set_battery_percent(50.0);
+        let mut rx = bus.subscribe();
+
+        adapter.ingest_sim_scan(&[], Meters(0.0), Meters(0.0), 0.0, None).unwrap();
+        adapter.ingest_sim_scan(&[], Meters(3.0), Meters(4.0), 0.0, None).unwrap();
+        assert!((adapter.battery_percent() - 40.0).abs() < 1e-3);
+
+        let mut reported = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            match event.payload {
+                EventPayload::Telemetry(t) => reported.push(t.battery_percent),
+                EventPayload::PowerStatus { percent, .. } => {
+                    assert_eq!(percent.round() as u8, *reported.last().unwrap());
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(reported, [50, 40]);
+
+        // A dashboard-supplied reading is passed through untouched.
+        adapter.ingest_sim_scan(&[], Meters(9.0), Meters(9.0), 0.0, Some(77)).unwrap();
+        assert!((adapter.battery_percent() - 40.0).abs() < 1e-3);
+    }
+
+    #[tokio::test]
+    async fn odometry_dropout_withholds_telemetry() {
+        let (bus, adapter) = make_adapter();
+        let mut rx = bus.subscribe();
+        adapter.set_odometry_dropout(true);
+
+        let n = adapter
+            .ingest_sim_scan(&[1.0], Meters(0.0), Meters(0.0), 0.0, Some(100))
+            .unwrap();
+        assert_eq!(n, 0);
+        let event = rx.recv().await.unwrap();
+        assert!(matches!(event.payload, EventPayload::LidarScan { .. }));
+    }
+
+    #[test]
+    fn sim_ready_is_answered_with_config_and_obstacles() {
+        let config = DashboardSimAdapter::build_sim_config_frame(&SimParams::default());
+        assert_eq!(config["topic"], "/sim/config");
+        assert_eq!(config["msg"]["lidar_samples"], 181);
+
+        let frame = DashboardSimAdapter::build_obstacles_frame(&[(
+            7,
+            VirtualObstacle { x: 1.0, y: -2.0, radius: 0.25 },
+        )]);
+        assert_eq!(frame["topic"], "/sim/obstacles");
+        assert_eq!(frame["msg"]["obstacles"][0]["id"], 7);
+
+        let (_, adapter) = make_adapter();
+        adapter
+            .handle_rosbridge_message(r#"{"op":"publish","topic":"/sim/ready","msg":{}}"#)
+            .unwrap();
+    }
 }
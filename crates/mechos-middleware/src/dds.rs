@@ -0,0 +1,468 @@
+//! [`DdsTransport`] – native ROS 2 (DDS) message transport for
+//! [`Ros2Adapter`][crate::Ros2Adapter].
+//!
+//! Without a transport the adapter only describes its ROS 2 traffic as
+//! rosbridge-style JSON on the [`EventBus`][crate::EventBus].  With one
+//! installed via [`Ros2Adapter::with_transport`][crate::Ros2Adapter::with_transport],
+//! velocity commands leave the process as real `geometry_msgs/msg/Twist`
+//! samples on `/cmd_vel`.
+//!
+//! ROS 2 messages travel as CDR (the DDS wire encoding).  This module holds
+//! the small subset of the codec MechOS needs:
+//!
+//! | Message | Direction | Function |
+//! |---|---|---|
+//! | `geometry_msgs/msg/Twist` | outbound | [`encode_twist`] |
+//! | `sensor_msgs/msg/LaserScan` | inbound | [`decode_laser_scan`] |
+//! | `nav_msgs/msg/Odometry` | inbound | [`decode_odometry`] |
+//!
+//! # Zenoh
+//!
+//! With the `zenoh` cargo feature enabled, [`ZenohTransport`] connects to a
+//! [`zenoh-bridge-ros2dds`](https://github.com/eclipse-zenoh/zenoh-plugin-ros2dds)
+//! (or any Zenoh router running the ROS 2 plugin), which maps each ROS 2
+//! topic to a Zenoh key without its leading slash (`/cmd_vel` → `cmd_vel`)
+//! and carries the CDR bytes unchanged.  [`ZenohTransport::forward_sensors`]
+//! subscribes to `scan` and `odom` and feeds every sample into the adapter,
+//! so live sensor data reaches the bus without a rosbridge in between.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mechos_types::{MechError, MetersPerSecond, RadiansPerSecond};
+
+use crate::ros2_adapter::MAX_LIDAR_RANGES;
+
+/// CDR encapsulation header for little-endian plain CDR (`CDR_LE`).
+const CDR_LE_HEADER: [u8; 4] = [0x00, 0x01, 0x00, 0x00];
+
+/// CDR encapsulation header for big-endian plain CDR (`CDR_BE`).
+const CDR_BE_HEADER: [u8; 4] = [0x00, 0x00, 0x00, 0x00];
+
+/// Number of `f64` entries in a ROS 2 6×6 covariance matrix.
+const COVARIANCE_LEN: usize = 36;
+
+/// A transport that publishes serialized ROS 2 messages onto the robot's
+/// DDS graph.
+#[async_trait]
+pub trait DdsTransport: Send + Sync {
+    /// Publish one CDR-encoded message (including its encapsulation header)
+    /// on `topic`, a ROS 2 topic name such as `/cmd_vel`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Channel`] when the sample cannot be delivered.
+    async fn publish(&self, topic: &str, cdr: Vec<u8>) -> Result<(), MechError>;
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Messages
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// The fields MechOS uses from a `sensor_msgs/msg/LaserScan`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaserScanMsg {
+    /// Header stamp: when the scan was captured; `None` when unset (zero).
+    pub stamp: Option<DateTime<Utc>>,
+    pub angle_min: f32,
+    pub angle_increment: f32,
+    pub ranges: Vec<f32>,
+}
+
+/// The fields MechOS uses from a `nav_msgs/msg/Odometry`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OdometryMsg {
+    /// Header stamp: when the pose was measured; `None` when unset (zero).
+    pub stamp: Option<DateTime<Utc>>,
+    pub position_x: f64,
+    pub position_y: f64,
+    /// Rotation about the z axis, derived from the pose quaternion.
+    pub yaw_rad: f64,
+    pub linear_x: f64,
+    pub angular_z: f64,
+}
+
+/// Encode a planar `geometry_msgs/msg/Twist` (linear x, angular z) as
+/// little-endian CDR.
+pub fn encode_twist(linear: MetersPerSecond, angular: RadiansPerSecond) -> Vec<u8> {
+    let values = [
+        linear.get() as f64,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        angular.get() as f64,
+    ];
+    let mut out = Vec::with_capacity(CDR_LE_HEADER.len() + values.len() * 8);
+    out.extend_from_slice(&CDR_LE_HEADER);
+    for value in values {
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+    out
+}
+
+/// Decode a CDR `sensor_msgs/msg/LaserScan`.
+///
+/// # Errors
+///
+/// Returns [`MechError::Parsing`] for truncated or malformed messages and
+/// for scans with more than [`MAX_LIDAR_RANGES`] readings.
+pub fn decode_laser_scan(cdr: &[u8]) -> Result<LaserScanMsg, MechError> {
+    let mut r = CdrReader::new(cdr)?;
+    let stamp = r.header()?;
+    let angle_min = r.f32()?;
+    let _angle_max = r.f32()?;
+    let angle_increment = r.f32()?;
+    // time_increment, scan_time, range_min, range_max
+    for _ in 0..4 {
+        r.f32()?;
+    }
+    let count = r.u32()? as usize;
+    if count > MAX_LIDAR_RANGES {
+        return Err(MechError::Parsing(format!(
+            "laser scan has {count} range readings, exceeding the limit of {MAX_LIDAR_RANGES}"
+        )));
+    }
+    let ranges = (0..count).map(|_| r.f32()).collect::<Result<_, _>>()?;
+    Ok(LaserScanMsg {
+        stamp,
+        angle_min,
+        angle_increment,
+        ranges,
+    })
+}
+
+/// Decode a CDR `nav_msgs/msg/Odometry`.
+///
+/// # Errors
+///
+/// Returns [`MechError::Parsing`] for truncated or malformed messages.
+pub fn decode_odometry(cdr: &[u8]) -> Result<OdometryMsg, MechError> {
+    let mut r = CdrReader::new(cdr)?;
+    let stamp = r.header()?;
+    r.string()?; // child_frame_id
+    let position_x = r.f64()?;
+    let position_y = r.f64()?;
+    let _z = r.f64()?;
+    let (qx, qy, qz, qw) = (r.f64()?, r.f64()?, r.f64()?, r.f64()?);
+    r.skip_f64s(COVARIANCE_LEN)?;
+    let linear_x = r.f64()?;
+    r.skip_f64s(4)?; // linear y/z, angular x/y
+    let angular_z = r.f64()?;
+    Ok(OdometryMsg {
+        stamp,
+        position_x,
+        position_y,
+        yaw_rad: (2.0 * (qw * qz + qx * qy)).atan2(1.0 - 2.0 * (qy * qy + qz * qz)),
+        linear_x,
+        angular_z,
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// CDR reader
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Cursor over a CDR body.  Primitive alignment is relative to the first
+/// byte after the 4-byte encapsulation header.
+struct CdrReader<'a> {
+    body: &'a [u8],
+    pos: usize,
+    little_endian: bool,
+}
+
+impl<'a> CdrReader<'a> {
+    fn new(cdr: &'a [u8]) -> Result<Self, MechError> {
+        let little_endian = match cdr.get(..4) {
+            Some(h) if h == CDR_LE_HEADER => true,
+            Some(h) if h == CDR_BE_HEADER => false,
+            _ => {
+                return Err(MechError::Parsing(
+                    "missing or unsupported CDR encapsulation header".to_string(),
+                ));
+            }
+        };
+        Ok(Self {
+            body: &cdr[4..],
+            pos: 0,
+            little_endian,
+        })
+    }
+
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], MechError> {
+        self.pos = self.pos.next_multiple_of(N);
+        let bytes = self
+            .body
+            .get(self.pos..self.pos + N)
+            .ok_or_else(|| MechError::Parsing("truncated CDR message".to_string()))?;
+        self.pos += N;
+        Ok(bytes.try_into().expect("slice has length N"))
+    }
+
+    fn u32(&mut self) -> Result<u32, MechError> {
+        let b = self.take::<4>()?;
+        Ok(if self.little_endian {
+            u32::from_le_bytes(b)
+        } else {
+            u32::from_be_bytes(b)
+        })
+    }
+
+    fn f32(&mut self) -> Result<f32, MechError> {
+        self.u32().map(f32::from_bits)
+    }
+
+    fn f64(&mut self) -> Result<f64, MechError> {
+        let b = self.take::<8>()?;
+        Ok(f64::from_bits(if self.little_endian {
+            u64::from_le_bytes(b)
+        } else {
+            u64::from_be_bytes(b)
+        }))
+    }
+
+    fn skip_f64s(&mut self, n: usize) -> Result<(), MechError> {
+        (0..n).try_for_each(|_| self.f64().map(|_| ()))
+    }
+
+    /// Skip a string (length including the NUL terminator, then bytes).
+    fn string(&mut self) -> Result<(), MechError> {
+        let len = self.u32()? as usize;
+        if self.body.len() < self.pos + len {
+            return Err(MechError::Parsing("truncated CDR string".to_string()));
+        }
+        self.pos += len;
+        Ok(())
+    }
+
+    /// Read a `std_msgs/msg/Header`, returning its stamp (`None` when
+    /// unset) and skipping the frame id.
+    fn header(&mut self) -> Result<Option<DateTime<Utc>>, MechError> {
+        let sec = self.u32()? as i32; // builtin_interfaces/Time.sec is int32
+        let nanosec = self.u32()?;
+        self.string()?;
+        Ok(match (sec, nanosec) {
+            (0, 0) => None,
+            _ => DateTime::from_timestamp(i64::from(sec), nanosec),
+        })
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Zenoh transport
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(feature = "zenoh")]
+pub use zenoh_transport::ZenohTransport;
+
+#[cfg(feature = "zenoh")]
+mod zenoh_transport {
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use mechos_types::MechError;
+    use tokio::task::JoinHandle;
+    use tracing::warn;
+
+    use super::DdsTransport;
+    use crate::ros2_adapter::Ros2Adapter;
+
+    /// [`DdsTransport`] over a Zenoh session bridged to ROS 2 by
+    /// `zenoh-bridge-ros2dds`.
+    pub struct ZenohTransport {
+        session: zenoh::Session,
+    }
+
+    impl ZenohTransport {
+        /// Wrap an already-open Zenoh session.
+        pub fn new(session: zenoh::Session) -> Self {
+            Self { session }
+        }
+
+        /// Open a Zenoh session with `config` (e.g. connecting to the
+        /// bridge's `tcp/<host>:7447` endpoint).
+        ///
+        /// # Errors
+        ///
+        /// Returns [`MechError::Channel`] when the session cannot be opened.
+        pub async fn open(config: zenoh::Config) -> Result<Self, MechError> {
+            let session = zenoh::open(config)
+                .await
+                .map_err(|e| MechError::Channel(format!("failed to open zenoh session: {e}")))?;
+            Ok(Self::new(session))
+        }
+
+        /// Subscribe to `/scan` and `/odom` and feed every sample into
+        /// `adapter` via [`Ros2Adapter::ingest_scan_cdr`] and
+        /// [`Ros2Adapter::ingest_odom_cdr`].  Malformed samples are logged
+        /// and dropped.  Abort the returned task to stop forwarding.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`MechError::Channel`] when a subscriber cannot be
+        /// declared.
+        pub async fn forward_sensors(
+            &self,
+            adapter: Arc<Ros2Adapter>,
+        ) -> Result<JoinHandle<()>, MechError> {
+            let declare = |key: &'static str| async move {
+                self.session.declare_subscriber(key).await.map_err(|e| {
+                    MechError::Channel(format!("failed to subscribe to zenoh key '{key}': {e}"))
+                })
+            };
+            let scan = declare("scan").await?;
+            let odom = declare("odom").await?;
+
+            Ok(tokio::spawn(async move {
+                loop {
+                    let (topic, result) = tokio::select! {
+                        Ok(sample) = scan.recv_async() => {
+                            ("/scan", adapter.ingest_scan_cdr(&sample.payload().to_bytes()))
+                        }
+                        Ok(sample) = odom.recv_async() => {
+                            ("/odom", adapter.ingest_odom_cdr(&sample.payload().to_bytes()))
+                        }
+                        else => break,
+                    };
+                    if let Err(e) = result {
+                        warn!(topic, error = %e, "dropping ROS 2 sample");
+                    }
+                }
+            }))
+        }
+    }
+
+    #[async_trait]
+    impl DdsTransport for ZenohTransport {
+        async fn publish(&self, topic: &str, cdr: Vec<u8>) -> Result<(), MechError> {
+            let key = topic.trim_start_matches('/');
+            self.session
+                .put(key, cdr)
+                .await
+                .map_err(|e| MechError::Channel(format!("zenoh put on '{key}' failed: {e}")))
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Minimal little-endian CDR writer for building test messages.
+    #[derive(Default)]
+    struct Writer(Vec<u8>);
+
+    impl Writer {
+        fn align(&mut self, n: usize) {
+            while !self.0.len().is_multiple_of(n) {
+                self.0.push(0);
+            }
+        }
+        fn u32(&mut self, v: u32) -> &mut Self {
+            self.align(4);
+            self.0.extend_from_slice(&v.to_le_bytes());
+            self
+        }
+        fn f32(&mut self, v: f32) -> &mut Self {
+            self.u32(v.to_bits())
+        }
+        fn f64(&mut self, v: f64) -> &mut Self {
+            self.align(8);
+            self.0.extend_from_slice(&v.to_le_bytes());
+            self
+        }
+        fn string(&mut self, s: &str) -> &mut Self {
+            self.u32(s.len() as u32 + 1);
+            self.0.extend_from_slice(s.as_bytes());
+            self.0.push(0);
+            self
+        }
+        fn header(&mut self, frame_id: &str) -> &mut Self {
+            self.u32(1_700_000_000).u32(0).string(frame_id)
+        }
+        fn finish(&self) -> Vec<u8> {
+            [&CDR_LE_HEADER[..], &self.0].concat()
+        }
+    }
+
+    pub(crate) fn laser_scan_cdr(angle_min: f32, angle_increment: f32, ranges: &[f32]) -> Vec<u8> {
+        let mut w = Writer::default();
+        w.header("laser")
+            .f32(angle_min)
+            .f32(3.1)
+            .f32(angle_increment);
+        w.f32(0.0).f32(0.1).f32(0.05).f32(12.0);
+        w.u32(ranges.len() as u32);
+        for &r in ranges {
+            w.f32(r);
+        }
+        w.u32(0).finish()
+    }
+
+    pub(crate) fn odometry_cdr(x: f64, y: f64, yaw: f64, linear_x: f64) -> Vec<u8> {
+        let mut w = Writer::default();
+        w.header("odom").string("base_link");
+        w.f64(x).f64(y).f64(0.0);
+        w.f64(0.0)
+            .f64(0.0)
+            .f64((yaw / 2.0).sin())
+            .f64((yaw / 2.0).cos());
+        for _ in 0..COVARIANCE_LEN {
+            w.f64(0.0);
+        }
+        w.f64(linear_x)
+            .f64(0.0)
+            .f64(0.0)
+            .f64(0.0)
+            .f64(0.0)
+            .f64(0.25);
+        for _ in 0..COVARIANCE_LEN {
+            w.f64(0.0);
+        }
+        w.finish()
+    }
+
+    #[test]
+    fn twist_encodes_as_six_little_endian_doubles() {
+        let cdr = encode_twist(MetersPerSecond(0.5), RadiansPerSecond(-1.0));
+        assert_eq!(cdr.len(), 4 + 48);
+        assert_eq!(cdr[..4], CDR_LE_HEADER);
+        let field = |i: usize| f64::from_le_bytes(cdr[4 + i * 8..12 + i * 8].try_into().unwrap());
+        assert_eq!(field(0), 0.5);
+        assert_eq!(field(5), -1.0);
+        assert!((1..5).all(|i| field(i) == 0.0));
+    }
+
+    #[test]
+    fn laser_scan_round_trips() {
+        let scan = decode_laser_scan(&laser_scan_cdr(-1.5, 0.25, &[1.0, 2.5, 4.0])).unwrap();
+        assert_eq!(scan.angle_min, -1.5);
+        assert_eq!(scan.angle_increment, 0.25);
+        assert_eq!(scan.ranges, vec![1.0, 2.5, 4.0]);
+        assert_eq!(scan.stamp.map(|t| t.timestamp()), Some(1_700_000_000));
+    }
+
+    #[test]
+    fn odometry_round_trips_with_yaw() {
+        let odom = decode_odometry(&odometry_cdr(3.0, -2.0, 1.0, 0.4)).unwrap();
+        assert_eq!((odom.position_x, odom.position_y), (3.0, -2.0));
+        assert!((odom.yaw_rad - 1.0).abs() < 1e-9);
+        assert_eq!(odom.linear_x, 0.4);
+        assert_eq!(odom.angular_z, 0.25);
+        assert_eq!(odom.stamp.map(|t| t.timestamp()), Some(1_700_000_000));
+    }
+
+    #[test]
+    fn malformed_messages_are_rejected() {
+        assert!(decode_laser_scan(&[]).is_err());
+        assert!(decode_laser_scan(&[0x00, 0x07, 0x00, 0x00]).is_err());
+        let scan = laser_scan_cdr(0.0, 0.1, &[1.0; 8]);
+        assert!(decode_laser_scan(&scan[..scan.len() - 12]).is_err());
+        let odom = odometry_cdr(0.0, 0.0, 0.0, 0.0);
+        assert!(decode_odometry(&odom[..100]).is_err());
+        let oversized = laser_scan_cdr(0.0, 0.1, &vec![1.0; MAX_LIDAR_RANGES + 1]);
+        assert!(matches!(
+            decode_laser_scan(&oversized),
+            Err(MechError::Parsing(_))
+        ));
+    }
+}
@@ -0,0 +1,299 @@
+//! Direct HAL adapter: drives `mechos-hal` drivers in-process, without ROS.
+//!
+//! [`DirectHalAdapter`] is the adapter for small robots whose motors, servos
+//! and relays hang straight off the computer running MechOS: a RoboClaw or
+//! ODrive on a USB serial port, hobby servos on PWM pins, relays on GPIO
+//! lines.
+//!
+//! * `Drive` – split into wheel speeds by the [`DiffDrive`] base.
+//! * `Halt` – stops the base; succeeds even if a motor does not answer.
+//! * [`heartbeat`][MechAdapter::heartbeat] – refreshes the base's command
+//!   deadline.  A [`MotorFailsafe`] thread zeroes the motors when neither a
+//!   `Drive` nor a heartbeat arrives in time (200 ms by default), and the
+//!   trip is published as an [`EventPayload::HardwareFault`].
+//! * Everything else – dispatched through a [`HardwareRegistry`] holding the
+//!   registered [`Actuator`]s (joints, the `"gripper"`) and [`Relay`]s.
+//!   Intents with no local hardware (navigation, docking, flight) are
+//!   rejected with [`FaultCode::Unsupported`].
+//!
+//! ```rust,no_run
+//! use mechos_hal::serial_motor::{RoboClaw, RoboClawChannel};
+//! use mechos_hal::{DiffDrive, SysfsGpioRelay};
+//! use mechos_middleware::{DirectHalAdapter, EventBus};
+//! use mechos_types::Meters;
+//! use std::sync::Arc;
+//!
+//! let claw = RoboClaw::open("/dev/ttyACM0", 38_400)?;
+//! let base = DiffDrive::new(
+//!     Box::new(claw.motor("left_wheel", RoboClawChannel::M1, 2_000.0)),
+//!     Box::new(claw.motor("right_wheel", RoboClawChannel::M2, 2_000.0)),
+//!     Meters(0.3),
+//! )?;
+//! let adapter = DirectHalAdapter::new(Arc::new(EventBus::default()))
+//!     .with_drive(base)
+//!     .with_relay(Box::new(SysfsGpioRelay::new("pump", 17)));
+//! # Ok::<(), mechos_types::MechError>(())
+//! ```
+//!
+//! Drivers do blocking I/O (short serial packets, sysfs writes) on the
+//! calling task.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use futures_util::stream::{self, BoxStream};
+use mechos_hal::{Actuator, DiffDrive, HardwareRegistry, MotorFailsafe, Relay};
+use mechos_types::{Event, EventPayload, FaultCode, HardwareIntent, MechError};
+use uuid::Uuid;
+
+use crate::adapter::MechAdapter;
+use crate::bus::EventBus;
+
+/// Adapter executing intents on locally attached `mechos-hal` drivers.
+pub struct DirectHalAdapter {
+    bus: Arc<EventBus>,
+    drive: Option<Arc<Mutex<DiffDrive>>>,
+    /// Watches `drive`; stopped when the adapter drops.
+    failsafe: Option<MotorFailsafe>,
+    registry: Mutex<HardwareRegistry>,
+}
+
+impl DirectHalAdapter {
+    /// An adapter with no hardware registered; failsafe trips are published
+    /// on `bus`.
+    pub fn new(bus: Arc<EventBus>) -> Self {
+        Self {
+            bus,
+            drive: None,
+            failsafe: None,
+            registry: Mutex::new(HardwareRegistry::new()),
+        }
+    }
+
+    /// Execute `Drive` and `Halt` on `drive`, and start its
+    /// [`MotorFailsafe`].
+    pub fn with_drive(mut self, drive: DiffDrive) -> Self {
+        let drive = Arc::new(Mutex::new(drive));
+        let bus = Arc::clone(&self.bus);
+        self.failsafe = Some(MotorFailsafe::spawn(Arc::clone(&drive), move |fault| {
+            let _ = bus.publish(Event {
+                id: Uuid::new_v4(),
+                timestamp: Utc::now(),
+                source: "mechos-middleware::direct_hal/failsafe".to_string(),
+                payload: fault,
+                trace_id: None,
+                correlation_id: None,
+            });
+        }));
+        self.drive = Some(drive);
+        self
+    }
+
+    /// Register a joint, gripper or other position-controlled actuator.
+    pub fn with_actuator(self, actuator: Box<dyn Actuator>) -> Self {
+        lock(&self.registry).register_actuator(actuator);
+        self
+    }
+
+    /// Register a relay for `TriggerRelay`.
+    pub fn with_relay(self, relay: Box<dyn Relay>) -> Self {
+        lock(&self.registry).register_relay(relay);
+        self
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[async_trait]
+impl MechAdapter for DirectHalAdapter {
+    /// Execute `intent` on the registered drivers; see the
+    /// [module docs](self).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::HardwareFault`] with
+    /// [`FaultCode::DeviceNotRegistered`] when no driver is registered for
+    /// the intent, or the driver's own fault when it fails.
+    async fn execute_intent(&self, intent: HardwareIntent) -> Result<(), MechError> {
+        match intent {
+            HardwareIntent::Drive {
+                linear_velocity,
+                angular_velocity,
+            } => match &self.drive {
+                Some(drive) => lock(drive).drive(linear_velocity, angular_velocity),
+                None => Err(MechError::HardwareFault {
+                    code: FaultCode::DeviceNotRegistered,
+                    component: "drive_base".to_string(),
+                    details: "no drive base is registered".to_string(),
+                }),
+            },
+            HardwareIntent::Halt { .. } => {
+                if let Some(drive) = &self.drive {
+                    lock(drive).stop();
+                }
+                Ok(())
+            }
+            other => lock(&self.registry).dispatch(other),
+        }
+    }
+
+    /// The drivers report no sensor data; this returns an empty stream.
+    async fn sensor_stream(&self) -> BoxStream<'static, EventPayload> {
+        Box::pin(stream::empty())
+    }
+
+    /// Refresh the drive base's command deadline.
+    fn heartbeat(&self) {
+        if let Some(drive) = &self.drive {
+            lock(drive).refresh();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mechos_hal::MotorController;
+    use mechos_hal::sim::{SimActuator, SimRelay};
+    use mechos_types::{Meters, MetersPerSecond, RadiansPerSecond};
+    use std::sync::Arc;
+
+    /// A motor sharing its last commanded velocity with the test.
+    struct SharedMotor {
+        id: &'static str,
+        velocity: Arc<Mutex<MetersPerSecond>>,
+    }
+
+    impl MotorController for SharedMotor {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn set_velocity(&mut self, velocity: MetersPerSecond) -> Result<(), MechError> {
+            *self.velocity.lock().unwrap() = velocity;
+            Ok(())
+        }
+
+        fn velocity(&self) -> MetersPerSecond {
+            *self.velocity.lock().unwrap()
+        }
+    }
+
+    #[tokio::test]
+    async fn drive_and_halt_reach_the_motors() {
+        let (left, right) = (
+            Arc::new(Mutex::new(MetersPerSecond::ZERO)),
+            Arc::new(Mutex::new(MetersPerSecond::ZERO)),
+        );
+        let base = DiffDrive::new(
+            Box::new(SharedMotor { id: "left_wheel", velocity: Arc::clone(&left) }),
+            Box::new(SharedMotor { id: "right_wheel", velocity: Arc::clone(&right) }),
+            Meters(0.5),
+        )
+        .unwrap();
+        let adapter = DirectHalAdapter::new(Arc::new(EventBus::default())).with_drive(base);
+
+        adapter
+            .execute_intent(HardwareIntent::Drive {
+                linear_velocity: MetersPerSecond(0.4),
+                angular_velocity: RadiansPerSecond(0.4),
+            })
+            .await
+            .unwrap();
+        assert!((left.lock().unwrap().get() - 0.3).abs() < 1e-6);
+        assert!((right.lock().unwrap().get() - 0.5).abs() < 1e-6);
+
+        adapter
+            .execute_intent(HardwareIntent::Halt { reason: String::new() })
+            .await
+            .unwrap();
+        assert_eq!(*left.lock().unwrap(), MetersPerSecond::ZERO);
+        assert_eq!(*right.lock().unwrap(), MetersPerSecond::ZERO);
+    }
+
+    #[tokio::test]
+    async fn other_intents_go_through_the_registry() {
+        let adapter = DirectHalAdapter::new(Arc::new(EventBus::default()))
+            .with_actuator(SimActuator::new("gripper"))
+            .with_relay(SimRelay::new("pump"));
+
+        adapter
+            .execute_intent(HardwareIntent::SetGripper { position: 0.8 })
+            .await
+            .unwrap();
+        adapter
+            .execute_intent(HardwareIntent::TriggerRelay {
+                relay_id: "pump".to_string(),
+                state: true,
+            })
+            .await
+            .unwrap();
+        {
+            let registry = lock(&adapter.registry);
+            assert_eq!(registry.actuator_position("gripper"), Some(0.8));
+            assert_eq!(registry.relay_state("pump"), Some(true));
+        }
+
+        let no_base = adapter
+            .execute_intent(HardwareIntent::Drive {
+                linear_velocity: MetersPerSecond(0.1),
+                angular_velocity: RadiansPerSecond::ZERO,
+            })
+            .await;
+        assert!(matches!(
+            no_base,
+            Err(MechError::HardwareFault {
+                code: FaultCode::DeviceNotRegistered,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn failsafe_trip_is_published_unless_heartbeats_arrive() {
+        let bus = Arc::new(EventBus::default());
+        let mut rx = bus.subscribe();
+        let left = Arc::new(Mutex::new(MetersPerSecond::ZERO));
+        let base = DiffDrive::new(
+            Box::new(SharedMotor { id: "left_wheel", velocity: Arc::clone(&left) }),
+            Box::new(SharedMotor {
+                id: "right_wheel",
+                velocity: Arc::new(Mutex::new(MetersPerSecond::ZERO)),
+            }),
+            Meters(0.5),
+        )
+        .unwrap()
+        .with_command_deadline(std::time::Duration::from_millis(50));
+        let adapter = DirectHalAdapter::new(Arc::clone(&bus)).with_drive(base);
+
+        adapter
+            .execute_intent(HardwareIntent::Drive {
+                linear_velocity: MetersPerSecond(0.2),
+                angular_velocity: RadiansPerSecond::ZERO,
+            })
+            .await
+            .unwrap();
+        for _ in 0..4 {
+            tokio::time::sleep(std::time::Duration::from_millis(25)).await;
+            adapter.heartbeat();
+        }
+        assert_eq!(*left.lock().unwrap(), MetersPerSecond(0.2));
+
+        let event = tokio::time::timeout(std::time::Duration::from_secs(2), rx.recv())
+            .await
+            .expect("failsafe trip")
+            .unwrap();
+        assert_eq!(event.source, "mechos-middleware::direct_hal/failsafe");
+        assert!(matches!(
+            event.payload,
+            EventPayload::HardwareFault {
+                code: FaultCode::DeadlineExpired,
+                ..
+            }
+        ));
+        assert_eq!(*left.lock().unwrap(), MetersPerSecond::ZERO);
+    }
+}
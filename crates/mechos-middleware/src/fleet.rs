@@ -0,0 +1,1229 @@
+//! [`FleetTransport`] – robot-to-robot messaging over the network.
+//!
+//! Robots that share a fleet secret find each other and exchange the
+//! messages behind [`HardwareIntent::MessagePeer`] and
+//! [`HardwareIntent::BroadcastFleet`]:
+//!
+//! * **Discovery** – every robot periodically multicasts a UDP announcement
+//!   (its robot ID and link port, signed with HMAC-SHA256 over the fleet
+//!   secret) to [`DEFAULT_DISCOVERY_GROUP`].  Unsigned or stale
+//!   announcements are ignored and peers that fall silent for the peer
+//!   timeout are forgotten.  Networks without multicast can list peers with
+//!   [`FleetTransport::with_peer`] instead.
+//! * **Links** – messages travel over TCP links opened on first use and
+//!   encrypted with the Noise `XXpsk3` handshake (X25519, ChaCha20-Poly1305,
+//!   BLAKE2s).  Every robot holds a static [`FleetIdentity`] key; a link is
+//!   only accepted when the peer's key is the one trusted for the robot ID
+//!   it claims ([`FleetTransport::with_trusted_peer`]) and the peer knows
+//!   the fleet secret, which is mixed in as the pre-shared key.  Records
+//!   carry strictly increasing nonces, so a replayed, reordered or forged
+//!   record ends the link.
+//! * **Delivery** – inbound messages are published as
+//!   [`EventPayload::PeerMessage`] events on [`Topic::SwarmComm`] and the
+//!   global channel, with `from_robot_id` set to the authenticated peer.
+//!
+//! The transport is a [`MechAdapter`] for the two fleet intents; route them
+//! to it in the [`AdapterManager`](crate::AdapterManager):
+//!
+//! ```toml
+//! [routes]
+//! MessagePeer = ["fleet"]
+//! BroadcastFleet = ["fleet"]
+//! ```
+//!
+//! # Wire format
+//!
+//! Announcements are single JSON datagrams.  On a link every record is a
+//! 4-byte big-endian length followed by one Noise message: the three
+//! handshake messages (the last two carry the sender's robot ID), an empty
+//! record from the responder confirming the link, then encrypted chunks of
+//! a stream of frames – each a 4-byte length and a JSON object tagged by
+//! `"type"`.
+
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chrono::Utc;
+use futures_util::stream::{self, BoxStream};
+use hmac::{Hmac, Mac};
+use mechos_types::{Event, EventPayload, FaultCode, HardwareIntent, MechError};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use snow::{HandshakeState, StatelessTransportState};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream, UdpSocket};
+use tokio::sync::mpsc;
+use tokio::task::JoinSet;
+use tokio::time::{Instant, MissedTickBehavior};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::adapter::MechAdapter;
+use crate::bus::{EventBus, Topic};
+use crate::ros2_adapter::MAX_FLEET_MESSAGE_BYTES;
+
+/// Multicast group and port robots announce themselves on.
+pub const DEFAULT_DISCOVERY_GROUP: SocketAddrV4 =
+    SocketAddrV4::new(Ipv4Addr::new(239, 255, 77, 77), 7446);
+
+/// Default address fleet links are accepted on.
+pub const DEFAULT_LINK_ADDR: SocketAddr =
+    SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 7447));
+
+/// Default interval between discovery announcements.
+pub const DEFAULT_ANNOUNCE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Default silence after which a discovered peer is forgotten.
+pub const DEFAULT_PEER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Largest link frame accepted: one maximum-size message plus envelope.
+const MAX_FRAME_BYTES: usize = MAX_FLEET_MESSAGE_BYTES + 1024;
+
+/// Announcements whose timestamp is further off than this are replays or
+/// badly unsynchronised clocks.
+const MAX_CLOCK_SKEW_MS: i64 = 30_000;
+
+/// Time allowed for connecting and completing the handshake.
+const LINK_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Messages that may wait for one link before sends fail.
+const LINK_QUEUE_CAPACITY: usize = 256;
+
+const NOISE_PATTERN: &str = "Noise_XXpsk3_25519_ChaChaPoly_BLAKE2s";
+const NOISE_PROLOGUE: &[u8] = b"mechos-fleet/1";
+/// Largest Noise message, and the AEAD tag each encrypted one carries.
+const NOISE_MAX_MESSAGE: usize = 65_535;
+const NOISE_TAG: usize = 16;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// A robot's fleet identity: its robot ID and static X25519 key pair.
+///
+/// Share [`public_key`](Self::public_key) with the other robots, which
+/// trust it with [`FleetTransport::with_trusted_peer`]; keep the private
+/// key on the robot.
+#[derive(Clone)]
+pub struct FleetIdentity {
+    robot_id: String,
+    public_key: Vec<u8>,
+    private_key: Vec<u8>,
+}
+
+/// On-disk form of a [`FleetIdentity`], keys in base64.
+#[derive(Serialize, Deserialize)]
+struct IdentityFile {
+    robot_id: String,
+    public_key: String,
+    private_key: String,
+}
+
+impl FleetIdentity {
+    /// Generate a fresh key pair for `robot_id`.
+    pub fn generate(robot_id: impl Into<String>) -> Self {
+        let keypair = noise_builder()
+            .generate_keypair()
+            .expect("the system RNG is available");
+        Self {
+            robot_id: robot_id.into(),
+            public_key: keypair.public,
+            private_key: keypair.private,
+        }
+    }
+
+    /// Load the identity stored at `path`, or generate one for `robot_id`
+    /// and store it there (readable by the owner only on Unix).
+    ///
+    /// # Errors
+    ///
+    /// - [`MechError::Serialization`] – the file cannot be read or written.
+    /// - [`MechError::Parsing`] – the file is malformed or belongs to
+    ///   another robot ID.
+    pub fn load_or_generate(
+        robot_id: impl Into<String>,
+        path: impl AsRef<Path>,
+    ) -> Result<Self, MechError> {
+        let robot_id = robot_id.into();
+        let path = path.as_ref();
+        let io_error = |e: std::io::Error| {
+            MechError::Serialization(format!("fleet identity '{}': {e}", path.display()))
+        };
+        if path.exists() {
+            let text = std::fs::read_to_string(path).map_err(io_error)?;
+            let parse_error = |e: String| {
+                MechError::Parsing(format!("invalid fleet identity '{}': {e}", path.display()))
+            };
+            let file: IdentityFile =
+                serde_json::from_str(&text).map_err(|e| parse_error(e.to_string()))?;
+            if file.robot_id != robot_id {
+                return Err(parse_error(format!("it belongs to '{}'", file.robot_id)));
+            }
+            let decode = |key: &str| {
+                BASE64
+                    .decode(key)
+                    .ok()
+                    .filter(|key| key.len() == 32)
+                    .ok_or_else(|| parse_error("keys must be 32 bytes of base64".to_string()))
+            };
+            return Ok(Self {
+                public_key: decode(&file.public_key)?,
+                private_key: decode(&file.private_key)?,
+                robot_id,
+            });
+        }
+        let identity = Self::generate(robot_id);
+        let file = IdentityFile {
+            robot_id: identity.robot_id.clone(),
+            public_key: BASE64.encode(&identity.public_key),
+            private_key: BASE64.encode(&identity.private_key),
+        };
+        let json = serde_json::to_string_pretty(&file)
+            .map_err(|e| MechError::Serialization(e.to_string()))?;
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        std::io::Write::write_all(&mut options.open(path).map_err(io_error)?, json.as_bytes())
+            .map_err(io_error)?;
+        Ok(identity)
+    }
+
+    /// The robot ID this identity speaks for.
+    pub fn robot_id(&self) -> &str {
+        &self.robot_id
+    }
+
+    /// The 32-byte public key other robots trust.
+    pub fn public_key(&self) -> &[u8] {
+        &self.public_key
+    }
+}
+
+impl std::fmt::Debug for FleetIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FleetIdentity")
+            .field("robot_id", &self.robot_id)
+            .field("public_key", &BASE64.encode(&self.public_key))
+            .finish_non_exhaustive()
+    }
+}
+
+/// A robot the transport can currently reach.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FleetPeer {
+    /// The peer's robot ID.
+    pub robot_id: String,
+    /// Where its links are accepted, if known.
+    pub addr: Option<SocketAddr>,
+    /// Whether a link to it is open.
+    pub connected: bool,
+}
+
+/// Discovery and links for one robot; see the [module docs](self).
+#[derive(Clone)]
+pub struct FleetTransport {
+    bus: Arc<EventBus>,
+    identity: Arc<FleetIdentity>,
+    secret: Arc<[u8]>,
+    /// The fleet secret hashed to the 32 bytes Noise wants as its PSK.
+    psk: [u8; 32],
+    trusted: Arc<HashMap<String, Vec<u8>>>,
+    listen: SocketAddr,
+    discovery: Option<SocketAddrV4>,
+    announce_interval: Duration,
+    peer_timeout: Duration,
+    state: Arc<FleetState>,
+}
+
+#[derive(Default)]
+struct FleetState {
+    /// Known peer addresses; `None` as last-seen marks a static peer.
+    peers: Mutex<HashMap<String, (SocketAddr, Option<Instant>)>>,
+    /// Writer queues of the open links.
+    links: Mutex<HashMap<String, mpsc::Sender<String>>>,
+}
+
+impl FleetTransport {
+    /// Join the fleet sharing `secret` as `identity`, with the default link
+    /// address, discovery group and timings, and no trusted peers yet.
+    pub fn new(bus: Arc<EventBus>, identity: FleetIdentity, secret: impl AsRef<[u8]>) -> Self {
+        let secret: Arc<[u8]> = Arc::from(secret.as_ref());
+        Self {
+            bus,
+            identity: Arc::new(identity),
+            psk: Sha256::digest(&secret).into(),
+            secret,
+            trusted: Arc::default(),
+            listen: DEFAULT_LINK_ADDR,
+            discovery: Some(DEFAULT_DISCOVERY_GROUP),
+            announce_interval: DEFAULT_ANNOUNCE_INTERVAL,
+            peer_timeout: DEFAULT_PEER_TIMEOUT,
+            state: Arc::default(),
+        }
+    }
+
+    /// Accept links from, and open links to, `robot_id` only when it
+    /// presents `public_key`.
+    pub fn with_trusted_peer(
+        mut self,
+        robot_id: impl Into<String>,
+        public_key: impl Into<Vec<u8>>,
+    ) -> Self {
+        Arc::make_mut(&mut self.trusted).insert(robot_id.into(), public_key.into());
+        self
+    }
+
+    /// Accept links on `addr` instead of [`DEFAULT_LINK_ADDR`].  Port `0`
+    /// picks a free port; [`FleetHandle::local_addr`] reports it.
+    pub fn with_listen_addr(mut self, addr: SocketAddr) -> Self {
+        self.listen = addr;
+        self
+    }
+
+    /// Announce and discover on `group` instead of
+    /// [`DEFAULT_DISCOVERY_GROUP`].
+    pub fn with_discovery_group(mut self, group: SocketAddrV4) -> Self {
+        self.discovery = Some(group);
+        self
+    }
+
+    /// Disable multicast discovery; only [`with_peer`](Self::with_peer)
+    /// peers and robots that link in are reachable.
+    pub fn without_discovery(mut self) -> Self {
+        self.discovery = None;
+        self
+    }
+
+    /// Override the announcement interval.
+    pub fn with_announce_interval(mut self, interval: Duration) -> Self {
+        self.announce_interval = interval;
+        self
+    }
+
+    /// Override how long a silent discovered peer is remembered.
+    pub fn with_peer_timeout(mut self, timeout: Duration) -> Self {
+        self.peer_timeout = timeout;
+        self
+    }
+
+    /// Add a static peer address that is never forgotten.
+    pub fn with_peer(self, robot_id: impl Into<String>, addr: SocketAddr) -> Self {
+        self.state
+            .peers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(robot_id.into(), (addr, None));
+        self
+    }
+
+    /// This robot's ID.
+    pub fn robot_id(&self) -> &str {
+        &self.identity.robot_id
+    }
+
+    /// Every known or linked peer, sorted by robot ID.
+    pub fn peers(&self) -> Vec<FleetPeer> {
+        let links = self.state.links.lock().unwrap_or_else(|e| e.into_inner());
+        let peers = self.state.peers.lock().unwrap_or_else(|e| e.into_inner());
+        let mut all: Vec<FleetPeer> = peers
+            .iter()
+            .map(|(robot_id, (addr, _))| FleetPeer {
+                robot_id: robot_id.clone(),
+                addr: Some(*addr),
+                connected: links.contains_key(robot_id),
+            })
+            .chain(
+                links
+                    .keys()
+                    .filter(|robot_id| !peers.contains_key(*robot_id))
+                    .map(|robot_id| FleetPeer {
+                        robot_id: robot_id.clone(),
+                        addr: None,
+                        connected: true,
+                    }),
+            )
+            .collect();
+        all.sort_by(|a, b| a.robot_id.cmp(&b.robot_id));
+        all
+    }
+
+    /// Bind the link listener (and discovery socket) and start accepting
+    /// links, announcing and discovering in the background.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Channel`] if a socket cannot be bound or the
+    /// discovery group cannot be joined.
+    pub async fn start(&self) -> Result<FleetHandle, MechError> {
+        let listener = TcpListener::bind(self.listen).await.map_err(|e| {
+            MechError::Channel(format!("fleet cannot listen on {}: {e}", self.listen))
+        })?;
+        let local_addr = listener.local_addr().map_err(io_error)?;
+        let mut tasks = JoinSet::new();
+        let fleet = self.clone();
+        tasks.spawn(async move { fleet.accept_links(listener).await });
+        if let Some(group) = self.discovery {
+            let socket = Arc::new(join_group(group)?);
+            let fleet = self.clone();
+            let announcer = Arc::clone(&socket);
+            tasks.spawn(async move { fleet.announce(&announcer, group, local_addr.port()).await });
+            let fleet = self.clone();
+            tasks.spawn(async move { fleet.discover(&socket).await });
+        }
+        info!(robot_id = %self.robot_id(), %local_addr, "fleet transport started");
+        Ok(FleetHandle { local_addr, tasks })
+    }
+
+    /// Send `message` to `robot_id`, opening a link if none is open.
+    ///
+    /// # Errors
+    ///
+    /// - [`MechError::Parsing`] – `message` exceeds
+    ///   [`MAX_FLEET_MESSAGE_BYTES`].
+    /// - [`MechError::Channel`] – the peer is unknown, unreachable, fails
+    ///   authentication, or its link is saturated.
+    pub async fn send_to(&self, robot_id: &str, message: &str) -> Result<(), MechError> {
+        if message.len() > MAX_FLEET_MESSAGE_BYTES {
+            return Err(MechError::Parsing(format!(
+                "fleet message to '{robot_id}' is {} bytes, exceeding the limit of {MAX_FLEET_MESSAGE_BYTES}",
+                message.len()
+            )));
+        }
+        let link = self.link(robot_id);
+        let link = match link {
+            Some(link) if !link.is_closed() => link,
+            _ => self.open_link(robot_id).await?,
+        };
+        link.try_send(message.to_string())
+            .map_err(|e| MechError::Channel(format!("fleet link to '{robot_id}': {e}")))
+    }
+
+    /// Send `message` to every known or linked peer, returning how many
+    /// accepted it.
+    ///
+    /// # Errors
+    ///
+    /// Returns the last failure when there were peers but none accepted
+    /// the message.
+    pub async fn broadcast(&self, message: &str) -> Result<usize, MechError> {
+        let mut delivered = 0;
+        let mut last_error = None;
+        for peer in self.peers() {
+            match self.send_to(&peer.robot_id, message).await {
+                Ok(()) => delivered += 1,
+                Err(e) => {
+                    warn!(peer = %peer.robot_id, error = %e, "fleet broadcast not delivered");
+                    last_error = Some(e);
+                }
+            }
+        }
+        match last_error {
+            Some(e) if delivered == 0 => Err(e),
+            _ => Ok(delivered),
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    // Discovery
+    // -------------------------------------------------------------------------
+
+    async fn announce(&self, socket: &UdpSocket, group: SocketAddrV4, port: u16) {
+        let mut ticker = tokio::time::interval(self.announce_interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            let sent_at_ms = Utc::now().timestamp_millis();
+            let announcement = Announcement {
+                robot_id: self.robot_id().to_string(),
+                port,
+                sent_at_ms,
+                mac: self.sign(&announce_text(self.robot_id(), port, sent_at_ms)),
+            };
+            let datagram = serde_json::to_vec(&announcement).unwrap_or_default();
+            if let Err(e) = socket.send_to(&datagram, group).await {
+                debug!(error = %e, "fleet announcement not sent");
+            }
+            self.expire_peers();
+        }
+    }
+
+    async fn discover(&self, socket: &UdpSocket) {
+        let mut buf = vec![0u8; 2048];
+        loop {
+            match socket.recv_from(&mut buf).await {
+                Ok((len, from)) => {
+                    if let Err(e) = self.handle_announcement(&buf[..len], from) {
+                        debug!(%from, error = %e, "ignoring fleet announcement");
+                    }
+                }
+                Err(e) => warn!(error = %e, "fleet discovery receive failed"),
+            }
+        }
+    }
+
+    /// Verify an announcement datagram from `from` and remember its sender.
+    fn handle_announcement(&self, datagram: &[u8], from: SocketAddr) -> Result<(), MechError> {
+        let announcement: Announcement = serde_json::from_slice(datagram)
+            .map_err(|e| MechError::Parsing(format!("invalid announcement: {e}")))?;
+        if announcement.robot_id == self.robot_id() {
+            return Ok(());
+        }
+        let text = announce_text(
+            &announcement.robot_id,
+            announcement.port,
+            announcement.sent_at_ms,
+        );
+        if !self.verify(&text, &announcement.mac) {
+            return Err(MechError::Parsing(format!(
+                "announcement from '{}' has an invalid signature",
+                announcement.robot_id
+            )));
+        }
+        if (Utc::now().timestamp_millis() - announcement.sent_at_ms).abs() > MAX_CLOCK_SKEW_MS {
+            return Err(MechError::Parsing(format!(
+                "announcement from '{}' is stale",
+                announcement.robot_id
+            )));
+        }
+        let addr = SocketAddr::new(from.ip(), announcement.port);
+        let mut peers = self.state.peers.lock().unwrap_or_else(|e| e.into_inner());
+        match peers.get_mut(&announcement.robot_id) {
+            // Static peers keep their configured address.
+            Some((_, None)) => {}
+            Some(entry) => *entry = (addr, Some(Instant::now())),
+            None => {
+                info!(peer = %announcement.robot_id, %addr, "fleet peer discovered");
+                peers.insert(announcement.robot_id, (addr, Some(Instant::now())));
+            }
+        }
+        Ok(())
+    }
+
+    fn expire_peers(&self) {
+        let timeout = self.peer_timeout;
+        self.state
+            .peers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|robot_id, (_, last_seen)| {
+                let alive = last_seen.is_none_or(|seen| seen.elapsed() < timeout);
+                if !alive {
+                    info!(peer = %robot_id, "fleet peer lost");
+                }
+                alive
+            });
+    }
+
+    // -------------------------------------------------------------------------
+    // Links
+    // -------------------------------------------------------------------------
+
+    async fn accept_links(&self, listener: TcpListener) {
+        loop {
+            match listener.accept().await {
+                Ok((stream, from)) => {
+                    let fleet = self.clone();
+                    tokio::spawn(async move {
+                        let _ = stream.set_nodelay(true);
+                        match tokio::time::timeout(LINK_TIMEOUT, fleet.handshake(stream, None))
+                            .await
+                        {
+                            Ok(Ok((stream, peer, link))) => {
+                                let _ = fleet.register_link(stream, peer, link);
+                            }
+                            Ok(Err(e)) => warn!(%from, error = %e, "fleet link rejected"),
+                            Err(_) => warn!(%from, "fleet handshake timed out"),
+                        }
+                    });
+                }
+                Err(e) => warn!(error = %e, "fleet accept failed"),
+            }
+        }
+    }
+
+    async fn open_link(&self, robot_id: &str) -> Result<mpsc::Sender<String>, MechError> {
+        let addr = self
+            .state
+            .peers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(robot_id)
+            .map(|(addr, _)| *addr)
+            .ok_or_else(|| MechError::Channel(format!("unknown fleet peer '{robot_id}'")))?;
+        let link = async {
+            let stream = TcpStream::connect(addr).await.map_err(io_error)?;
+            let _ = stream.set_nodelay(true);
+            self.handshake(stream, Some(robot_id)).await
+        };
+        let (stream, peer, link) = tokio::time::timeout(LINK_TIMEOUT, link)
+            .await
+            .map_err(|_| MechError::Channel(format!("fleet link to '{robot_id}' timed out")))??;
+        Ok(self.register_link(stream, peer, link))
+    }
+
+    /// Run the Noise handshake on a fresh stream, returning the
+    /// authenticated peer's robot ID and the link's ciphers.  `expected` is
+    /// the peer an outbound link was opened to; the opener is the Noise
+    /// initiator.
+    async fn handshake<S>(
+        &self,
+        mut stream: S,
+        expected: Option<&str>,
+    ) -> Result<(S, String, (Sealer, Opener)), MechError>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let builder = noise_builder()
+            .local_private_key(&self.identity.private_key)
+            .psk(3, &self.psk)
+            .prologue(NOISE_PROLOGUE);
+        let mut noise = match expected {
+            Some(_) => builder.build_initiator(),
+            None => builder.build_responder(),
+        }
+        .map_err(noise_error)?;
+        let own_id = self.robot_id().as_bytes();
+        let mut payload = vec![0u8; NOISE_MAX_MESSAGE];
+
+        let peer = if let Some(expected) = expected {
+            write_handshake(&mut stream, &mut noise, &[]).await?;
+            let len = read_handshake(&mut stream, &mut noise, &mut payload).await?;
+            let peer = self.authenticate(&noise, &payload[..len], Some(expected))?;
+            write_handshake(&mut stream, &mut noise, own_id).await?;
+            peer
+        } else {
+            read_handshake(&mut stream, &mut noise, &mut payload).await?;
+            write_handshake(&mut stream, &mut noise, own_id).await?;
+            let len = read_handshake(&mut stream, &mut noise, &mut payload)
+                .await
+                .map_err(|e| {
+                    MechError::Channel(format!("fleet link failed authentication: {e}"))
+                })?;
+            self.authenticate(&noise, &payload[..len], None)?
+        };
+
+        let noise = Arc::new(noise.into_stateless_transport_mode().map_err(noise_error)?);
+        let mut sealer = Sealer {
+            noise: Arc::clone(&noise),
+            nonce: 0,
+        };
+        let mut opener = Opener {
+            noise,
+            nonce: 0,
+            buf: Vec::new(),
+        };
+        // The initiator only learns that the responder accepted its key and
+        // PSK from this first (empty) record.
+        if expected.is_some() {
+            let record = read_record(&mut stream).await;
+            record
+                .and_then(|record| opener.open(&record))
+                .map_err(|e| {
+                    MechError::Channel(format!("fleet peer '{peer}' failed authentication: {e}"))
+                })?;
+        } else {
+            write_record(&mut stream, &sealer.seal_record(&[])?).await?;
+        }
+        Ok((stream, peer, (sealer, opener)))
+    }
+
+    /// Check that the robot ID a peer claims is trusted with the static key
+    /// it proved during the handshake.
+    fn authenticate(
+        &self,
+        noise: &HandshakeState,
+        claimed: &[u8],
+        expected: Option<&str>,
+    ) -> Result<String, MechError> {
+        let peer = String::from_utf8(claimed.to_vec())
+            .map_err(|_| MechError::Parsing("fleet robot ID is not UTF-8".to_string()))?;
+        if peer == self.robot_id() || expected.is_some_and(|expected| expected != peer) {
+            return Err(MechError::Channel(format!(
+                "fleet link answered by unexpected robot '{peer}'"
+            )));
+        }
+        match (self.trusted.get(&peer), noise.get_remote_static()) {
+            (Some(trusted), Some(presented)) if trusted.as_slice() == presented => Ok(peer),
+            _ => Err(MechError::Channel(format!(
+                "fleet peer '{peer}' failed authentication: identity key not trusted"
+            ))),
+        }
+    }
+
+    /// Serve an authenticated link on its own task and return its queue.
+    fn register_link<S>(
+        &self,
+        stream: S,
+        peer: String,
+        link: (Sealer, Opener),
+    ) -> mpsc::Sender<String>
+    where
+        S: AsyncRead + AsyncWrite + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel(LINK_QUEUE_CAPACITY);
+        self.state
+            .links
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(peer.clone(), tx.clone());
+        info!(%peer, "fleet link up");
+        let fleet = self.clone();
+        let queue = tx.clone();
+        tokio::spawn(async move {
+            let error = fleet.serve_link(stream, &peer, link, rx).await;
+            info!(%peer, error = %error, "fleet link down");
+            let mut links = fleet.state.links.lock().unwrap_or_else(|e| e.into_inner());
+            if links
+                .get(&peer)
+                .is_some_and(|open| open.same_channel(&queue))
+            {
+                links.remove(&peer);
+            }
+        });
+        tx
+    }
+
+    async fn serve_link<S>(
+        &self,
+        stream: S,
+        peer: &str,
+        (mut sealer, mut opener): (Sealer, Opener),
+        mut outbox: mpsc::Receiver<String>,
+    ) -> MechError
+    where
+        S: AsyncRead + AsyncWrite,
+    {
+        let (mut reader, mut writer) = tokio::io::split(stream);
+        let outbound = async {
+            while let Some(message) = outbox.recv().await {
+                for record in sealer.seal(&Frame::Message { message })? {
+                    write_record(&mut writer, &record).await?;
+                }
+            }
+            Ok::<(), MechError>(())
+        };
+        let inbound = async {
+            loop {
+                opener.open(&read_record(&mut reader).await?)?;
+                while let Some(Frame::Message { message }) = opener.next_frame()? {
+                    self.deliver(peer, message);
+                }
+            }
+        };
+        let result = tokio::select! {
+            result = outbound => result,
+            result = inbound => result,
+        };
+        match result {
+            Ok(()) => MechError::Channel("fleet link closed".to_string()),
+            Err(e) => e,
+        }
+    }
+
+    /// Publish a message received from the authenticated `peer` on the bus.
+    fn deliver(&self, peer: &str, message: String) {
+        let event = Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: format!("mechos-middleware::fleet/{peer}"),
+            payload: EventPayload::PeerMessage {
+                from_robot_id: peer.to_string(),
+                message,
+            },
+            trace_id: None,
+            correlation_id: None,
+        };
+        // Nobody listening is not an error for the link.
+        let _ = self.bus.publish_to(Topic::SwarmComm, event.clone());
+        let _ = self.bus.publish(event);
+    }
+
+    fn link(&self, robot_id: &str) -> Option<mpsc::Sender<String>> {
+        self.state
+            .links
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(robot_id)
+            .cloned()
+    }
+
+    // -------------------------------------------------------------------------
+    // Announcement signatures
+    // -------------------------------------------------------------------------
+
+    fn sign(&self, text: &str) -> String {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts any key length");
+        mac.update(text.as_bytes());
+        BASE64.encode(mac.finalize().into_bytes())
+    }
+
+    fn verify(&self, text: &str, signature: &str) -> bool {
+        let Ok(signature) = BASE64.decode(signature) else {
+            return false;
+        };
+        let mut mac =
+            HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts any key length");
+        mac.update(text.as_bytes());
+        mac.verify_slice(&signature).is_ok()
+    }
+}
+
+#[async_trait]
+impl MechAdapter for FleetTransport {
+    async fn execute_intent(&self, intent: HardwareIntent) -> Result<(), MechError> {
+        match intent {
+            HardwareIntent::MessagePeer {
+                target_robot_id,
+                message,
+            } => self.send_to(&target_robot_id, &message).await,
+            HardwareIntent::BroadcastFleet { message } => {
+                self.broadcast(&message).await.map(|_| ())
+            }
+            other => Err(MechError::HardwareFault {
+                code: FaultCode::Unsupported,
+                component: "fleet".to_string(),
+                details: format!("the fleet transport cannot execute {other:?}"),
+            }),
+        }
+    }
+
+    /// Inbound messages are published on the bus as they arrive.
+    async fn sensor_stream(&self) -> BoxStream<'static, EventPayload> {
+        Box::pin(stream::empty())
+    }
+}
+
+/// The background tasks of a started [`FleetTransport`]; dropping the
+/// handle stops discovery and stops accepting links.
+#[must_use = "dropping the handle stops the fleet transport"]
+pub struct FleetHandle {
+    local_addr: SocketAddr,
+    tasks: JoinSet<()>,
+}
+
+impl FleetHandle {
+    /// The address links are accepted on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Stop the background tasks.
+    pub fn shutdown(mut self) {
+        self.tasks.abort_all();
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Announcement {
+    robot_id: String,
+    port: u16,
+    sent_at_ms: i64,
+    mac: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Frame {
+    Message { message: String },
+}
+
+/// Encrypts outgoing frames into records with increasing nonces.
+struct Sealer {
+    noise: Arc<StatelessTransportState>,
+    nonce: u64,
+}
+
+impl Sealer {
+    /// Encrypt `frame`, length-prefixed, into as many records as it needs.
+    fn seal(&mut self, frame: &Frame) -> Result<Vec<Vec<u8>>, MechError> {
+        let json =
+            serde_json::to_vec(frame).map_err(|e| MechError::Serialization(e.to_string()))?;
+        let mut plaintext = Vec::with_capacity(4 + json.len());
+        plaintext.extend_from_slice(&(json.len() as u32).to_be_bytes());
+        plaintext.extend_from_slice(&json);
+        plaintext
+            .chunks(NOISE_MAX_MESSAGE - NOISE_TAG)
+            .map(|chunk| self.seal_record(chunk))
+            .collect()
+    }
+
+    fn seal_record(&mut self, chunk: &[u8]) -> Result<Vec<u8>, MechError> {
+        let mut record = vec![0; chunk.len() + NOISE_TAG];
+        let len = self
+            .noise
+            .write_message(self.nonce, chunk, &mut record)
+            .map_err(noise_error)?;
+        self.nonce += 1;
+        record.truncate(len);
+        Ok(record)
+    }
+}
+
+/// Decrypts incoming records, expecting each nonce exactly once and in
+/// order, and reassembles the frames they carry.
+struct Opener {
+    noise: Arc<StatelessTransportState>,
+    nonce: u64,
+    buf: Vec<u8>,
+}
+
+impl Opener {
+    fn open(&mut self, record: &[u8]) -> Result<(), MechError> {
+        let mut plaintext = vec![0; record.len()];
+        let len = self
+            .noise
+            .read_message(self.nonce, record, &mut plaintext)
+            .map_err(|e| MechError::Channel(format!("fleet record rejected: {e}")))?;
+        self.nonce += 1;
+        self.buf.extend_from_slice(&plaintext[..len]);
+        Ok(())
+    }
+
+    /// The next complete frame, if one has arrived.
+    fn next_frame(&mut self) -> Result<Option<Frame>, MechError> {
+        let Some(prefix) = self.buf.first_chunk::<4>() else {
+            return Ok(None);
+        };
+        let len = u32::from_be_bytes(*prefix) as usize;
+        if len > MAX_FRAME_BYTES {
+            return Err(MechError::Parsing(format!(
+                "fleet frame of {len} bytes exceeds limit of {MAX_FRAME_BYTES}"
+            )));
+        }
+        if self.buf.len() < 4 + len {
+            return Ok(None);
+        }
+        let frame = serde_json::from_slice(&self.buf[4..4 + len])
+            .map_err(|e| MechError::Parsing(format!("invalid fleet frame: {e}")))?;
+        self.buf.drain(..4 + len);
+        Ok(Some(frame))
+    }
+}
+
+fn noise_builder() -> snow::Builder<'static> {
+    snow::Builder::new(NOISE_PATTERN.parse().expect("valid Noise pattern"))
+}
+
+fn announce_text(robot_id: &str, port: u16, sent_at_ms: i64) -> String {
+    format!("announce|{robot_id}|{port}|{sent_at_ms}")
+}
+
+/// Bind a UDP socket to `group`'s port (shared with other robots on the
+/// host) and join the multicast group.
+fn join_group(group: SocketAddrV4) -> Result<UdpSocket, MechError> {
+    use socket2::{Domain, Protocol, Socket, Type};
+
+    let join = || -> std::io::Result<UdpSocket> {
+        let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
+        socket.set_reuse_address(true)?;
+        socket.bind(&SocketAddr::from((Ipv4Addr::UNSPECIFIED, group.port())).into())?;
+        socket.join_multicast_v4(group.ip(), &Ipv4Addr::UNSPECIFIED)?;
+        socket.set_multicast_loop_v4(true)?;
+        socket.set_nonblocking(true)?;
+        UdpSocket::from_std(socket.into())
+    };
+    join()
+        .map_err(|e| MechError::Channel(format!("fleet cannot join discovery group {group}: {e}")))
+}
+
+async fn write_handshake<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    noise: &mut HandshakeState,
+    payload: &[u8],
+) -> Result<(), MechError> {
+    let mut message = vec![0; NOISE_MAX_MESSAGE];
+    let len = noise
+        .write_message(payload, &mut message)
+        .map_err(noise_error)?;
+    write_record(writer, &message[..len]).await
+}
+
+async fn read_handshake<R: AsyncRead + Unpin>(
+    reader: &mut R,
+    noise: &mut HandshakeState,
+    payload: &mut [u8],
+) -> Result<usize, MechError> {
+    let message = read_record(reader).await?;
+    noise.read_message(&message, payload).map_err(noise_error)
+}
+
+async fn write_record<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    record: &[u8],
+) -> Result<(), MechError> {
+    let mut buf = Vec::with_capacity(4 + record.len());
+    buf.extend_from_slice(&(record.len() as u32).to_be_bytes());
+    buf.extend_from_slice(record);
+    writer.write_all(&buf).await.map_err(io_error)
+}
+
+async fn read_record<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Vec<u8>, MechError> {
+    let len = reader.read_u32().await.map_err(io_error)? as usize;
+    if len > NOISE_MAX_MESSAGE {
+        return Err(MechError::Parsing(format!(
+            "fleet record of {len} bytes exceeds limit of {NOISE_MAX_MESSAGE}"
+        )));
+    }
+    let mut record = vec![0; len];
+    reader.read_exact(&mut record).await.map_err(io_error)?;
+    Ok(record)
+}
+
+fn noise_error(e: snow::Error) -> MechError {
+    MechError::Channel(format!("fleet handshake: {e}"))
+}
+
+fn io_error(e: std::io::Error) -> MechError {
+    MechError::Channel(format!("fleet link: {e}"))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::TopicReceiver;
+
+    const LOOPBACK: SocketAddr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 0));
+
+    fn robot(
+        bus: &Arc<EventBus>,
+        identity: &FleetIdentity,
+        secret: &str,
+        trusts: &[&FleetIdentity],
+    ) -> FleetTransport {
+        trusts.iter().fold(
+            FleetTransport::new(Arc::clone(bus), identity.clone(), secret)
+                .with_listen_addr(LOOPBACK)
+                .without_discovery(),
+            |fleet, peer| fleet.with_trusted_peer(peer.robot_id(), peer.public_key()),
+        )
+    }
+
+    async fn next_message(rx: &mut TopicReceiver) -> (String, String) {
+        let event = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("timed out waiting for a fleet message")
+            .unwrap();
+        match event.payload {
+            EventPayload::PeerMessage {
+                from_robot_id,
+                message,
+            } => (from_robot_id, message),
+            other => panic!("expected PeerMessage, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn intents_become_peer_messages_on_the_other_robot() {
+        let (bus_a, bus_b) = (Arc::new(EventBus::default()), Arc::new(EventBus::default()));
+        let (id_a, id_b) = (
+            FleetIdentity::generate("rover-a"),
+            FleetIdentity::generate("rover-b"),
+        );
+        let mut inbox_a = bus_a.subscribe_to(Topic::SwarmComm);
+        let mut inbox_b = bus_b.subscribe_to(Topic::SwarmComm);
+        let b = robot(&bus_b, &id_b, "fleet-secret", &[&id_a]);
+        let handle_b = b.start().await.unwrap();
+        let a = robot(&bus_a, &id_a, "fleet-secret", &[&id_b])
+            .with_peer("rover-b", handle_b.local_addr());
+        let _handle_a = a.start().await.unwrap();
+
+        a.execute_intent(HardwareIntent::MessagePeer {
+            target_robot_id: "rover-b".to_string(),
+            message: "meet at dock 3".to_string(),
+        })
+        .await
+        .unwrap();
+        assert_eq!(
+            next_message(&mut inbox_b).await,
+            ("rover-a".to_string(), "meet at dock 3".to_string())
+        );
+
+        // B learnt A from the inbound link and can broadcast back over it;
+        // a message spanning several Noise records arrives whole.
+        let long = "x".repeat(MAX_FLEET_MESSAGE_BYTES);
+        assert_eq!(b.broadcast(&long).await.unwrap(), 1);
+        assert_eq!(
+            next_message(&mut inbox_a).await,
+            ("rover-b".to_string(), long)
+        );
+        assert!(a.peers()[0].connected);
+
+        let err = a
+            .execute_intent(HardwareIntent::MessagePeer {
+                target_robot_id: "rover-z".to_string(),
+                message: "hello?".to_string(),
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, MechError::Channel(ref m) if m.contains("unknown fleet peer")));
+    }
+
+    #[tokio::test]
+    async fn links_need_the_secret_and_a_trusted_identity() {
+        let bus_b = Arc::new(EventBus::default());
+        let (id_a, id_b) = (
+            FleetIdentity::generate("rover-a"),
+            FleetIdentity::generate("rover-b"),
+        );
+        let mut inbox_b = bus_b.subscribe_to(Topic::SwarmComm);
+        let handle_b = robot(&bus_b, &id_b, "fleet-secret", &[&id_a])
+            .start()
+            .await
+            .unwrap();
+        let bus_a = Arc::new(EventBus::default());
+
+        // The right key without the fleet secret.
+        let guesser =
+            robot(&bus_a, &id_a, "guess", &[&id_b]).with_peer("rover-b", handle_b.local_addr());
+        // The fleet secret with a rogue device's key, claiming to be rover-a.
+        let impostor = FleetIdentity::generate("rover-a");
+        let rogue = robot(&bus_a, &impostor, "fleet-secret", &[&id_b])
+            .with_peer("rover-b", handle_b.local_addr());
+        // A genuine robot that does not trust the key rover-b presents.
+        let wary = robot(&bus_a, &id_a, "fleet-secret", &[&impostor])
+            .with_peer("rover-b", handle_b.local_addr());
+
+        for fleet in [guesser, rogue, wary] {
+            let err = fleet.send_to("rover-b", "halt all").await.unwrap_err();
+            assert!(
+                matches!(err, MechError::Channel(ref m) if m.contains("failed authentication")),
+                "{err:?}"
+            );
+        }
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), inbox_b.recv())
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn replayed_records_are_rejected() {
+        let bus = Arc::new(EventBus::default());
+        let (id_a, id_b) = (
+            FleetIdentity::generate("rover-a"),
+            FleetIdentity::generate("rover-b"),
+        );
+        let a = robot(&bus, &id_a, "fleet-secret", &[&id_b]);
+        let b = robot(&bus, &id_b, "fleet-secret", &[&id_a]);
+        let (stream_a, stream_b) = tokio::io::duplex(NOISE_MAX_MESSAGE * 2);
+        let (link_a, link_b) = tokio::join!(
+            a.handshake(stream_a, Some("rover-b")),
+            b.handshake(stream_b, None)
+        );
+        let (_, peer, (mut sealer, _)) = link_a.unwrap();
+        let (_, _, (_, mut opener)) = link_b.unwrap();
+        assert_eq!(peer, "rover-b");
+
+        let records = sealer
+            .seal(&Frame::Message {
+                message: "stop".to_string(),
+            })
+            .unwrap();
+        opener.open(&records[0]).unwrap();
+        assert!(matches!(
+            opener.next_frame().unwrap(),
+            Some(Frame::Message { ref message }) if message == "stop"
+        ));
+        assert!(opener.open(&records[0]).is_err());
+    }
+
+    #[tokio::test]
+    async fn announcements_must_be_signed_and_fresh() {
+        let bus = Arc::new(EventBus::default());
+        let fleet = robot(
+            &bus,
+            &FleetIdentity::generate("rover-a"),
+            "fleet-secret",
+            &[],
+        );
+        let id_b = FleetIdentity::generate("rover-b");
+        let peer = robot(&bus, &id_b, "fleet-secret", &[]);
+        let from: SocketAddr = "10.0.0.7:7446".parse().unwrap();
+        let announce = |by: &FleetTransport, sent_at_ms: i64| {
+            serde_json::to_vec(&Announcement {
+                robot_id: "rover-b".to_string(),
+                port: 7447,
+                sent_at_ms,
+                mac: by.sign(&announce_text("rover-b", 7447, sent_at_ms)),
+            })
+            .unwrap()
+        };
+        let now = Utc::now().timestamp_millis();
+
+        let forged = robot(&bus, &id_b, "other-secret", &[]);
+        assert!(
+            fleet
+                .handle_announcement(&announce(&forged, now), from)
+                .is_err()
+        );
+        assert!(
+            fleet
+                .handle_announcement(&announce(&peer, now - 60_000), from)
+                .is_err()
+        );
+        assert!(fleet.peers().is_empty());
+
+        fleet
+            .handle_announcement(&announce(&peer, now), from)
+            .unwrap();
+        assert_eq!(
+            fleet.peers(),
+            vec![FleetPeer {
+                robot_id: "rover-b".to_string(),
+                addr: Some("10.0.0.7:7447".parse().unwrap()),
+                connected: false,
+            }]
+        );
+        let fleet = fleet.with_peer_timeout(Duration::ZERO);
+        fleet.expire_peers();
+        assert!(fleet.peers().is_empty());
+    }
+
+    #[test]
+    fn identity_is_generated_once_and_reloaded() {
+        let path = std::env::temp_dir().join(format!("mechos-fleet-{}.json", Uuid::new_v4()));
+        let created = FleetIdentity::load_or_generate("rover-a", &path).unwrap();
+        let loaded = FleetIdentity::load_or_generate("rover-a", &path).unwrap();
+        assert_eq!(loaded.public_key(), created.public_key());
+        assert_eq!(loaded.public_key().len(), 32);
+        assert!(matches!(
+            FleetIdentity::load_or_generate("rover-b", &path),
+            Err(MechError::Parsing(_))
+        ));
+        assert!(!format!("{created:?}").contains(&BASE64.encode(&created.private_key)));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn oversized_and_unsupported_intents_are_rejected() {
+        let fleet = robot(
+            &Arc::new(EventBus::default()),
+            &FleetIdentity::generate("rover-a"),
+            "fleet-secret",
+            &[],
+        );
+        let err = fleet
+            .send_to("rover-b", &"x".repeat(MAX_FLEET_MESSAGE_BYTES + 1))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, MechError::Parsing(_)));
+        let err = fleet
+            .execute_intent(HardwareIntent::Halt {
+                reason: "test".to_string(),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.fault_code(), Some(FaultCode::Unsupported));
+    }
+}
@@ -0,0 +1,378 @@
+//! GPS/GNSS adapter for outdoor robots.
+//!
+//! [`GpsAdapter`] turns satellite fixes into [`EventPayload::GpsFix`] events
+//! on the bus.  Two sources are supported:
+//!
+//! * **NMEA 0183** – `GGA` and `RMC` sentences from any talker (`$GPGGA`,
+//!   `$GNRMC`, …), as written by serial GNSS receivers.  Sentences must carry
+//!   a valid `*hh` checksum; other sentence types are ignored.
+//!   [`GpsAdapter::run_serial`] reads them from a serial port.
+//! * **ROS `sensor_msgs/NavSatFix`** – the JSON form of the message, bare or
+//!   inside a rosbridge `publish` frame, via [`GpsAdapter::ingest_navsatfix`].
+//!
+//! Sentences and messages without a fix are dropped.  Like MAVLink's
+//! `GLOBAL_POSITION_INT`, the published altitude is relative to the *home*
+//! position, here the first fix that carried an altitude.  The agent loop
+//! converts fixes into its local map frame with a
+//! `mechos_perception::transform::GeodeticDatum`.
+
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::Utc;
+use mechos_types::{Event, EventPayload, MechError, Meters};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio_serial::SerialPortBuilderExt;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::bus::EventBus;
+
+/// Maximum length of an NMEA sentence accepted by [`GpsAdapter::serve`].
+///
+/// The standard caps sentences at 82 characters; receivers with proprietary
+/// extensions go somewhat beyond, so longer lines are dropped only past this.
+pub const MAX_NMEA_SENTENCE_BYTES: usize = 256;
+
+/// Pause before reopening the serial port after it failed or disappeared.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// A decoded position fix.
+struct Fix {
+    latitude_deg: f64,
+    longitude_deg: f64,
+    altitude_m: Option<f64>,
+}
+
+/// Adapter that publishes GNSS fixes from NMEA sentences or ROS
+/// `NavSatFix` messages.
+pub struct GpsAdapter {
+    bus: Arc<EventBus>,
+    /// Altitude of the first fix that carried one, and of the latest.
+    altitudes: Mutex<(Option<f64>, Meters)>,
+}
+
+impl GpsAdapter {
+    /// Create a new [`GpsAdapter`] publishing to `bus`.
+    pub fn new(bus: Arc<EventBus>) -> Self {
+        Self {
+            bus,
+            altitudes: Mutex::new((None, Meters(0.0))),
+        }
+    }
+
+    /// Ingest one NMEA 0183 sentence, e.g.
+    /// `$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47`.
+    ///
+    /// Returns the number of bus subscribers the fix reached, or `Ok(0)` for
+    /// sentence types other than `GGA`/`RMC` and for sentences without a fix.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Parsing`] for malformed sentences, checksum
+    /// mismatches and out-of-range coordinates.
+    pub fn ingest_nmea(&self, sentence: &str) -> Result<usize, MechError> {
+        match parse_nmea(sentence)? {
+            Some(fix) => self.publish(fix, "nmea"),
+            None => Ok(0),
+        }
+    }
+
+    /// Ingest a `sensor_msgs/NavSatFix` in its JSON form, either the message
+    /// itself or a rosbridge frame carrying it under `"msg"`.
+    ///
+    /// Returns `Ok(0)` when `status.status` reports no fix.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Parsing`] for invalid JSON, missing fields and
+    /// out-of-range coordinates.
+    pub fn ingest_navsatfix(&self, json: &str) -> Result<usize, MechError> {
+        let value: serde_json::Value = serde_json::from_str(json)
+            .map_err(|e| MechError::Parsing(format!("invalid NavSatFix JSON: {e}")))?;
+        let msg = value.get("msg").unwrap_or(&value);
+        // STATUS_NO_FIX = -1.
+        if msg["status"]["status"].as_i64().is_some_and(|s| s < 0) {
+            return Ok(0);
+        }
+        let field = |name: &str| {
+            msg[name]
+                .as_f64()
+                .ok_or_else(|| MechError::Parsing(format!("NavSatFix is missing '{name}'")))
+        };
+        let fix = Fix {
+            latitude_deg: field("latitude")?,
+            longitude_deg: field("longitude")?,
+            altitude_m: msg["altitude"].as_f64().filter(|a| a.is_finite()),
+        };
+        check_range(&fix)?;
+        self.publish(fix, "navsatfix")
+    }
+
+    /// Read NMEA sentences from the serial port at `path` forever, reopening
+    /// it [`RECONNECT_DELAY`] after every failure or unplug.
+    pub async fn run_serial(&self, path: &str, baud_rate: u32) {
+        loop {
+            match tokio_serial::new(path, baud_rate).open_native_async() {
+                Ok(port) => {
+                    info!(path, "GPS serial port opened");
+                    let e = self.serve(port).await;
+                    warn!(path, error = %e, "GPS serial port lost; reconnecting");
+                }
+                Err(e) => warn!(path, error = %e, "cannot open GPS serial port"),
+            }
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    }
+
+    /// Ingest newline-terminated NMEA sentences from `reader` until it
+    /// fails, returning the error that ended it.  Invalid sentences are
+    /// logged and skipped.
+    pub async fn serve<R>(&self, reader: R) -> io::Error
+    where
+        R: AsyncRead + Unpin,
+    {
+        let mut reader = BufReader::new(reader);
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            match reader.read_until(b'\n', &mut line).await {
+                Ok(0) => return io::ErrorKind::UnexpectedEof.into(),
+                Ok(_) if line.len() > MAX_NMEA_SENTENCE_BYTES => {
+                    warn!("dropping oversized NMEA sentence");
+                }
+                Ok(_) => {
+                    let sentence = String::from_utf8_lossy(&line);
+                    if let Err(e) = self.ingest_nmea(&sentence) {
+                        warn!(error = %e, "dropping NMEA sentence");
+                    }
+                }
+                Err(e) => return e,
+            }
+        }
+    }
+
+    fn publish(&self, fix: Fix, source: &str) -> Result<usize, MechError> {
+        let altitude = {
+            let mut altitudes = self.altitudes.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(altitude) = fix.altitude_m {
+                let home = *altitudes.0.get_or_insert(altitude);
+                altitudes.1 = Meters((altitude - home) as f32);
+            }
+            altitudes.1
+        };
+        self.bus.publish(Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: format!("mechos-middleware::gps/{source}"),
+            payload: EventPayload::GpsFix {
+                latitude_deg: fix.latitude_deg,
+                longitude_deg: fix.longitude_deg,
+                altitude,
+            },
+            trace_id: None,
+            correlation_id: None,
+        })
+    }
+}
+
+/// Decode a `GGA` or `RMC` sentence; `None` for other types or no fix.
+fn parse_nmea(sentence: &str) -> Result<Option<Fix>, MechError> {
+    let sentence = sentence.trim();
+    let body = sentence
+        .strip_prefix('$')
+        .ok_or_else(|| MechError::Parsing("NMEA sentence must start with '$'".to_string()))?;
+    let (body, checksum) = body
+        .rsplit_once('*')
+        .ok_or_else(|| MechError::Parsing("NMEA sentence has no checksum".to_string()))?;
+    let expected = u8::from_str_radix(checksum, 16)
+        .map_err(|_| MechError::Parsing(format!("invalid NMEA checksum '{checksum}'")))?;
+    let actual = body.bytes().fold(0u8, |acc, b| acc ^ b);
+    if actual != expected {
+        return Err(MechError::Parsing(format!(
+            "NMEA checksum mismatch: expected {expected:02X}, computed {actual:02X}"
+        )));
+    }
+
+    let fields: Vec<&str> = body.split(',').collect();
+    let kind = fields[0].get(2..).unwrap_or_default();
+    let field = |i: usize| fields.get(i).copied().unwrap_or_default();
+    let (lat, lon, altitude_m) = match kind {
+        "GGA" => {
+            if matches!(field(6), "" | "0") {
+                return Ok(None);
+            }
+            let altitude = field(9).parse::<f64>().ok().filter(|a| a.is_finite());
+            ((field(2), field(3)), (field(4), field(5)), altitude)
+        }
+        "RMC" => {
+            if field(2) != "A" {
+                return Ok(None);
+            }
+            ((field(3), field(4)), (field(5), field(6)), None)
+        }
+        _ => return Ok(None),
+    };
+    let fix = Fix {
+        latitude_deg: coordinate(lat.0, lat.1, 'N', 'S')?,
+        longitude_deg: coordinate(lon.0, lon.1, 'E', 'W')?,
+        altitude_m,
+    };
+    check_range(&fix)?;
+    Ok(Some(fix))
+}
+
+/// Decode an NMEA `(d)ddmm.mmmm` value with its hemisphere letter.
+fn coordinate(
+    value: &str,
+    hemisphere: &str,
+    positive: char,
+    negative: char,
+) -> Result<f64, MechError> {
+    let raw: f64 = value
+        .parse()
+        .map_err(|_| MechError::Parsing(format!("invalid NMEA coordinate '{value}'")))?;
+    let degrees = (raw / 100.0).trunc();
+    let decimal = degrees + (raw - degrees * 100.0) / 60.0;
+    match hemisphere.chars().next() {
+        Some(h) if h == positive => Ok(decimal),
+        Some(h) if h == negative => Ok(-decimal),
+        _ => Err(MechError::Parsing(format!(
+            "invalid NMEA hemisphere '{hemisphere}'"
+        ))),
+    }
+}
+
+fn check_range(fix: &Fix) -> Result<(), MechError> {
+    if !(-90.0..=90.0).contains(&fix.latitude_deg) || !(-180.0..=180.0).contains(&fix.longitude_deg)
+    {
+        return Err(MechError::Parsing(format!(
+            "GPS coordinates ({}, {}) are out of range",
+            fix.latitude_deg, fix.longitude_deg
+        )));
+    }
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GGA: &str = "$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47";
+
+    fn make_adapter() -> (Arc<EventBus>, GpsAdapter) {
+        let bus = Arc::new(EventBus::default());
+        let adapter = GpsAdapter::new(Arc::clone(&bus));
+        (bus, adapter)
+    }
+
+    fn fix_of(event: Event) -> (f64, f64, f32) {
+        match event.payload {
+            EventPayload::GpsFix {
+                latitude_deg,
+                longitude_deg,
+                altitude,
+            } => (latitude_deg, longitude_deg, altitude.0),
+            other => panic!("expected GpsFix, got {other:?}"),
+        }
+    }
+
+    /// Append the checksum to an NMEA body.
+    fn sentence(body: &str) -> String {
+        let checksum = body.bytes().fold(0u8, |acc, b| acc ^ b);
+        format!("${body}*{checksum:02X}")
+    }
+
+    #[tokio::test]
+    async fn nmea_gga_and_rmc_publish_fixes_relative_to_home() {
+        let (bus, adapter) = make_adapter();
+        let mut rx = bus.subscribe();
+
+        assert_eq!(adapter.ingest_nmea(GGA).unwrap(), 1);
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.source, "mechos-middleware::gps/nmea");
+        let (lat, lon, alt) = fix_of(event);
+        assert!((lat - 48.1173).abs() < 1e-6 && (lon - 11.516_666).abs() < 1e-5);
+        assert_eq!(alt, 0.0);
+
+        let higher = sentence("GNGGA,123520,4807.038,S,01131.000,W,2,08,0.9,550.4,M,46.9,M,,");
+        adapter.ingest_nmea(&higher).unwrap();
+        let (lat, lon, alt) = fix_of(rx.recv().await.unwrap());
+        assert!(lat < 0.0 && lon < 0.0);
+        assert!((alt - 5.0).abs() < 1e-3);
+
+        // RMC carries no altitude, so the latest one is repeated.
+        let rmc = sentence("GPRMC,123521,A,4807.100,N,01131.000,E,022.4,084.4,230394,003.1,W");
+        adapter.ingest_nmea(&rmc).unwrap();
+        let (lat, _, alt) = fix_of(rx.recv().await.unwrap());
+        assert!((lat - (48.0 + 7.1 / 60.0)).abs() < 1e-9);
+        assert!((alt - 5.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn nmea_without_fix_or_of_other_types_is_ignored() {
+        let (_bus, adapter) = make_adapter();
+        let no_fix = sentence("GPGGA,123519,,,,,0,00,,,M,,M,,");
+        let void = sentence("GPRMC,123519,V,,,,,,,230394,,");
+        let gsv = sentence("GPGSV,3,1,11,03,03,111,00,04,15,270,00,06,01,010,00,13,06,292,00");
+        for s in [no_fix, void, gsv] {
+            assert_eq!(adapter.ingest_nmea(&s).unwrap(), 0, "{s}");
+        }
+    }
+
+    #[test]
+    fn malformed_nmea_is_rejected() {
+        let (_bus, adapter) = make_adapter();
+        let corrupted = GGA.replace("4807", "4808");
+        let out_of_range = sentence("GPGGA,123519,9107.038,N,01131.000,E,1,08,0.9,545.4,M,,M,,");
+        let bad_hemisphere = sentence("GPGGA,123519,4807.038,X,01131.000,E,1,08,0.9,545.4,M,,M,,");
+        for s in [
+            corrupted.as_str(),
+            "GPGGA,123519*00",
+            "$GPGGA,123519",
+            &out_of_range,
+            &bad_hemisphere,
+        ] {
+            assert!(
+                matches!(adapter.ingest_nmea(s), Err(MechError::Parsing(_))),
+                "{s}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn navsatfix_json_publishes_fix() {
+        let (bus, adapter) = make_adapter();
+        let mut rx = bus.subscribe();
+        let frame = r#"{"op":"publish","topic":"/fix","msg":{
+            "status":{"status":0,"service":1},
+            "latitude":47.3977419,"longitude":8.5455938,"altitude":488.0}}"#;
+        assert_eq!(adapter.ingest_navsatfix(frame).unwrap(), 1);
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.source, "mechos-middleware::gps/navsatfix");
+        assert_eq!(fix_of(event), (47.3977419, 8.5455938, 0.0));
+
+        let no_fix = r#"{"status":{"status":-1},"latitude":0.0,"longitude":0.0}"#;
+        assert_eq!(adapter.ingest_navsatfix(no_fix).unwrap(), 0);
+        assert!(matches!(
+            adapter.ingest_navsatfix(r#"{"latitude":47.0}"#),
+            Err(MechError::Parsing(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn serve_reads_sentences_until_eof() {
+        let (bus, adapter) = make_adapter();
+        let mut rx = bus.subscribe();
+        let input = format!("{GGA}\r\ngarbage\r\n{GGA}\r\n");
+        let e = adapter.serve(input.as_bytes()).await;
+        assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof);
+        assert!(rx.try_recv().is_ok() && rx.try_recv().is_ok());
+        assert!(rx.try_recv().is_err());
+    }
+}
@@ -5,26 +5,112 @@
 //!
 //! # Modules
 //!
+//! - [`camera`] – Size limits and downscaling for camera frames published as
+//!   [`EventPayload::CameraFrame`][mechos_types::EventPayload::CameraFrame].
+//! - [`dds`] – [`DdsTransport`][dds::DdsTransport] and the CDR codec for the
+//!   native ROS 2 messages the [`Ros2Adapter`] exchanges; the `zenoh` feature
+//!   adds a transport over `zenoh-bridge-ros2dds`.
 //! - [`bus`] – Headless, typed, topic-based publish/subscribe event bus built
-//!   on Tokio broadcast channels.
+//!   on Tokio broadcast channels and bounded per-subscriber queues.
+//! - [`bus_bridge`] – [`BusBridge`]: mirrors bus lanes between processes
+//!   over a length-prefixed TCP or Unix-socket stream, with reconnect.
+//! - [`monitor`] – [`BusMonitor`]: periodic bus health reports with
+//!   per-lane publish rates, subscriber counts and drops.
+//! - [`rpc`] – [`rpc::Responder`]: answers [`EventBus::request`]s by
+//!   correlation ID.
+//! - [`typed`] – Payload types paired with their [`Topic`], for
+//!   [`EventBus::publish_typed`] / [`EventBus::subscribe_typed`].
+//! - [`fleet`] – [`FleetTransport`]: robot-to-robot messaging with signed
+//!   multicast discovery and Noise-encrypted links between trusted
+//!   [`fleet::FleetIdentity`] keys.
+//! - [`log_buffer`] – [`LogBuffer`]: the newest `tracing` events in a ring
+//!   buffer, filled by the [`log_buffer::LogCapture`] layer.
+//! - [`journal`] – [`BusRecorder`]: appends bus traffic to a size-rotated
+//!   JSONL or SQLite journal that [`journal::JournalReader`] reads back.
+//! - [`replayer`] – [`BusReplayer`]: re-publishes a recorded journal onto a
+//!   live bus with its original (or accelerated) timing.
 //! - [`ros2_bridge`] – Universal ROS2-to-WebSocket bridge that translates DDS
 //!   robotics traffic into lightweight JSON for web clients.
 //! - [`adapter`] – The [`MechAdapter`] trait: the Universal Adapter Pattern
 //!   that decouples MechOS from any specific external protocol.
+//! - [`tls`] – [`TlsConfig`]: rustls TLS termination for the WebSocket
+//!   servers, with optional self-signed certificate generation.
+//! - [`supervisor`] – [`WsSupervisor`]: keeps an adapter's client WebSocket
+//!   alive with backoff, subscription replay and connection-state alerts.
+//! - [`adapter_manager`] – [`AdapterManager`]: routes intents to several
+//!   registered adapters, fans in their sensor streams and tracks their
+//!   health.
 //! - [`ros2_adapter`] – [`Ros2Adapter`]: drives a physical robot via ROS 2
 //!   MoveIt 2 and reads LiDAR data from `/scan`.
+//! - [`mqtt_adapter`] – [`MqttAdapter`]: drives ESP32-class robots over MQTT
+//!   and ingests their sensor topics.
+//! - [`serial_adapter`] – [`SerialAdapter`]: drives microcontroller bases
+//!   over a serial port with line or COBS framing.
+//! - [`can_adapter`] – [`CanAdapter`]: drives CAN chassis through a
+//!   DBC-style frame mapping over SocketCAN.
+//! - [`mavlink_adapter`] – [`MavlinkAdapter`]: flies ArduPilot / PX4 drones
+//!   and rovers over MAVLink and streams their attitude and GPS fixes.
+//! - [`direct_hal_adapter`] – [`DirectHalAdapter`]: drives RoboClaw /
+//!   ODrive motors, PWM servos and GPIO relays through `mechos-hal`, without
+//!   ROS.
+//! - [`gps_adapter`] – [`GpsAdapter`]: publishes GNSS fixes from NMEA
+//!   sentences (e.g. a serial receiver) or ROS `NavSatFix` messages.
+//! - [`sim_adapter`] – [`SimAdapter`]: drives Gazebo or Webots simulations
+//!   and streams their simulated LiDAR and odometry.
 //! - [`dashboard_sim_adapter`] – [`DashboardSimAdapter`]: drives the React /
 //!   Three.js simulation over a `rosbridge_server`-compatible WebSocket and
-//!   ingests virtual LiDAR data from `/sim_scan`.
+//!   ingests virtual LiDAR data from `/sim_scan`, with configurable
+//!   [`SimParams`][dashboard_sim_adapter::SimParams], a virtual battery and
+//!   injectable obstacles.
 
 pub mod adapter;
+pub mod adapter_manager;
 pub mod bus;
+pub mod bus_bridge;
+pub mod camera;
+pub mod can_adapter;
 pub mod dashboard_sim_adapter;
+pub mod dds;
+pub mod direct_hal_adapter;
+pub mod fleet;
+pub mod gps_adapter;
+pub mod journal;
+pub mod log_buffer;
+pub mod mavlink_adapter;
+pub mod monitor;
+pub mod mqtt_adapter;
+pub mod replayer;
 pub mod ros2_adapter;
 pub mod ros2_bridge;
+pub mod rpc;
+pub mod serial_adapter;
+pub mod sim_adapter;
+pub mod supervisor;
+pub mod tls;
+pub mod typed;
 
 pub use adapter::MechAdapter;
-pub use bus::{EventBus, Topic, TopicReceiver, TopicSubscriber};
+pub use adapter_manager::AdapterManager;
+pub use bus::{
+    BackpressurePolicy, BusStats, EventBus, MultiTopicReceiver, SubscriberStats, Topic,
+    TopicReceiver, TopicStats, TopicSubscriber,
+};
+pub use bus_bridge::BusBridge;
+pub use can_adapter::CanAdapter;
 pub use dashboard_sim_adapter::DashboardSimAdapter;
+pub use direct_hal_adapter::DirectHalAdapter;
+pub use fleet::FleetTransport;
+pub use gps_adapter::GpsAdapter;
+pub use journal::BusRecorder;
+pub use log_buffer::LogBuffer;
+pub use mavlink_adapter::MavlinkAdapter;
+pub use monitor::BusMonitor;
+pub use mqtt_adapter::MqttAdapter;
+pub use replayer::BusReplayer;
 pub use ros2_adapter::Ros2Adapter;
 pub use ros2_bridge::Ros2Bridge;
+pub use serial_adapter::SerialAdapter;
+pub use sim_adapter::SimAdapter;
+pub use supervisor::WsSupervisor;
+pub use tls::TlsConfig;
+pub use typed::{TopicPayload, TypedEvent, TypedReceiver};
@@ -0,0 +1,295 @@
+//! [`LogBuffer`] – the most recent `tracing` events, kept in memory.
+//!
+//! A [`LogCapture`] layer added to the process's subscriber copies every
+//! event that passes the log filter into a bounded ring buffer, so operators
+//! can read warnings such as gate rejections or adapter reconnects from the
+//! REPL's `/logs` or the Cockpit's `GET /api/logs` without reading journald
+//! on the robot.  [`LogBuffer::global`] is the buffer the MechOS subscriber
+//! fills; tests and embedders may create their own.
+//!
+//! ```rust
+//! use mechos_middleware::log_buffer::{LogBuffer, LogCapture, LogFilter};
+//! use tracing_subscriber::layer::SubscriberExt;
+//!
+//! let logs = LogBuffer::new(100);
+//! let subscriber = tracing_subscriber::registry().with(LogCapture::new(logs.clone()));
+//! tracing::subscriber::with_default(subscriber, || {
+//!     tracing::warn!(rule = "speed_cap", "intent rejected");
+//! });
+//! let warnings = logs.query(&LogFilter::at_least(tracing::Level::WARN));
+//! assert_eq!(warnings[0].message, r#"intent rejected rule="speed_cap""#);
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt::{self, Write as _};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Serializer};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// Records kept by [`LogBuffer::global`].
+pub const DEFAULT_LOG_CAPACITY: usize = 2000;
+
+static GLOBAL: OnceLock<LogBuffer> = OnceLock::new();
+
+/// One captured `tracing` event.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LogRecord {
+    pub timestamp: DateTime<Utc>,
+    #[serde(serialize_with = "level_name")]
+    pub level: Level,
+    /// Module path (or explicit target) the event was emitted from.
+    pub target: String,
+    /// The event's message followed by its other fields as `key=value`.
+    pub message: String,
+}
+
+fn level_name<S: Serializer>(level: &Level, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(level.as_str())
+}
+
+/// Which records [`LogBuffer::query`] returns.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LogFilter {
+    /// Only records at least this severe; `None` keeps every level.
+    pub min_level: Option<Level>,
+    /// Only records whose target contains this text.
+    pub target: Option<String>,
+    /// Only the newest `limit` matches; `None` returns all of them.
+    pub limit: Option<usize>,
+}
+
+impl LogFilter {
+    /// Records at `level` or more severe.
+    pub fn at_least(level: Level) -> Self {
+        Self {
+            min_level: Some(level),
+            ..Self::default()
+        }
+    }
+
+    /// Whether `record` passes the level and target filters.
+    pub fn matches(&self, record: &LogRecord) -> bool {
+        // `Level` orders by verbosity: ERROR < WARN < … < TRACE.
+        self.min_level.is_none_or(|min| record.level <= min)
+            && self
+                .target
+                .as_deref()
+                .is_none_or(|target| record.target.contains(target))
+    }
+}
+
+/// A shared ring buffer of the newest [`LogRecord`]s; clones share it.
+#[derive(Debug, Clone)]
+pub struct LogBuffer {
+    records: Arc<Mutex<VecDeque<LogRecord>>>,
+    capacity: usize,
+}
+
+impl LogBuffer {
+    /// An empty buffer keeping the newest `capacity` records.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            records: Arc::new(Mutex::new(VecDeque::with_capacity(capacity.min(1024)))),
+            capacity,
+        }
+    }
+
+    /// The process-wide buffer of [`DEFAULT_LOG_CAPACITY`] records.
+    pub fn global() -> &'static LogBuffer {
+        GLOBAL.get_or_init(|| LogBuffer::new(DEFAULT_LOG_CAPACITY))
+    }
+
+    /// Append `record`, dropping the oldest one when full.
+    pub fn push(&self, record: LogRecord) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut records = self.records.lock().unwrap_or_else(|e| e.into_inner());
+        if records.len() == self.capacity {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+
+    /// The records matching `filter`, oldest first.
+    pub fn query(&self, filter: &LogFilter) -> Vec<LogRecord> {
+        let records = self.records.lock().unwrap_or_else(|e| e.into_inner());
+        let mut matches: Vec<LogRecord> = records
+            .iter()
+            .rev()
+            .filter(|r| filter.matches(r))
+            .take(filter.limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect();
+        matches.reverse();
+        matches
+    }
+
+    /// Number of records held.
+    pub fn len(&self) -> usize {
+        self.records.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Whether no record is held.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// `tracing_subscriber` layer copying every event into a [`LogBuffer`].
+///
+/// It captures what reaches it: placed after a global filter such as an
+/// `EnvFilter`, it keeps the same events the console shows.
+#[derive(Debug, Clone)]
+pub struct LogCapture {
+    buffer: LogBuffer,
+}
+
+impl LogCapture {
+    /// Capture into `buffer`.
+    pub fn new(buffer: LogBuffer) -> Self {
+        Self { buffer }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogCapture {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut fields = MessageVisitor::default();
+        event.record(&mut fields);
+        let metadata = event.metadata();
+        self.buffer.push(LogRecord {
+            timestamp: Utc::now(),
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message: fields.finish(),
+        });
+    }
+}
+
+/// Collects an event's `message` and its other fields.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl MessageVisitor {
+    fn finish(self) -> String {
+        match (self.message.is_empty(), self.fields.is_empty()) {
+            (_, true) => self.message,
+            (true, false) => self.fields,
+            (false, false) => format!("{} {}", self.message, self.fields),
+        }
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            self.record_debug(field, &value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{value:?}");
+            return;
+        }
+        if !self.fields.is_empty() {
+            self.fields.push(' ');
+        }
+        let _ = write!(self.fields, "{}={value:?}", field.name());
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    fn record(level: Level, target: &str, message: &str) -> LogRecord {
+        LogRecord {
+            timestamp: Utc::now(),
+            level,
+            target: target.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn capture_formats_message_and_fields() {
+        let logs = LogBuffer::new(10);
+        let subscriber = tracing_subscriber::registry().with(LogCapture::new(logs.clone()));
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(target: "mechos_kernel::gate", rule = "speed_cap", "intent rejected");
+            tracing::info!(url = "ws://sim:9090", attempt = 2_u32, "adapter reconnecting");
+            tracing::debug!(lagged_by = 3_u64);
+        });
+        let all = logs.query(&LogFilter::default());
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].target, "mechos_kernel::gate");
+        assert_eq!(all[0].message, "intent rejected rule=\"speed_cap\"");
+        assert_eq!(all[1].message, "adapter reconnecting url=\"ws://sim:9090\" attempt=2");
+        assert_eq!(all[2].message, "lagged_by=3");
+    }
+
+    #[test]
+    fn buffer_keeps_the_newest_records() {
+        let logs = LogBuffer::new(2);
+        for n in 0..3 {
+            logs.push(record(Level::INFO, "t", &n.to_string()));
+        }
+        let kept: Vec<_> = logs.query(&LogFilter::default()).into_iter().map(|r| r.message).collect();
+        assert_eq!(kept, ["1", "2"]);
+        assert!(LogBuffer::new(0).is_empty());
+    }
+
+    #[test]
+    fn filter_by_level_target_and_limit() {
+        let logs = LogBuffer::new(10);
+        logs.push(record(Level::ERROR, "mechos_middleware::supervisor", "gave up"));
+        logs.push(record(Level::WARN, "mechos_kernel::gate", "rejected"));
+        logs.push(record(Level::INFO, "mechos_kernel::gate", "approved"));
+        logs.push(record(Level::WARN, "mechos_middleware::supervisor", "reconnecting"));
+
+        let messages = |filter: LogFilter| -> Vec<String> {
+            logs.query(&filter).into_iter().map(|r| r.message).collect()
+        };
+        assert_eq!(
+            messages(LogFilter::at_least(Level::WARN)),
+            ["gave up", "rejected", "reconnecting"]
+        );
+        assert_eq!(
+            messages(LogFilter {
+                target: Some("kernel".to_string()),
+                ..LogFilter::default()
+            }),
+            ["rejected", "approved"]
+        );
+        assert_eq!(
+            messages(LogFilter {
+                min_level: Some(Level::WARN),
+                limit: Some(1),
+                ..LogFilter::default()
+            }),
+            ["reconnecting"]
+        );
+    }
+
+    #[test]
+    fn records_serialize_level_names() {
+        let json = serde_json::to_value(record(Level::WARN, "t", "m")).unwrap();
+        assert_eq!(json["level"], "WARN");
+        assert_eq!(json["target"], "t");
+    }
+}
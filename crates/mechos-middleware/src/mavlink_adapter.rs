@@ -0,0 +1,950 @@
+//! MAVLink adapter for ArduPilot / PX4 drones and rovers.
+//!
+//! [`MavlinkAdapter`] talks MAVLink 2 over UDP to a flight controller (or to
+//! SITL / `mavlink-router`, which forward to `udp:14550` by default) as a
+//! ground-control station:
+//!
+//! | Intent | MAVLink message |
+//! |---|---|
+//! | [`HardwareIntent::Drive`] | `SET_POSITION_TARGET_LOCAL_NED`, body-frame forward velocity and yaw rate |
+//! | [`HardwareIntent::Halt`] | `SET_POSITION_TARGET_LOCAL_NED` with zero velocity (hover / stop) |
+//! | [`HardwareIntent::Arm`] | `COMMAND_LONG` `MAV_CMD_COMPONENT_ARM_DISARM` |
+//! | [`HardwareIntent::SetAltitude`] | `COMMAND_LONG` `MAV_CMD_NAV_TAKEOFF` on the ground, `MAV_CMD_DO_CHANGE_ALTITUDE` in the air |
+//! | [`HardwareIntent::Goto`] | `SET_POSITION_TARGET_GLOBAL_INT`, altitude relative to home |
+//!
+//! | MAVLink message | Published as |
+//! |---|---|
+//! | `ATTITUDE` | [`EventPayload::Attitude`] |
+//! | `GLOBAL_POSITION_INT` | [`EventPayload::GpsFix`] |
+//! | `HEARTBEAT` | [`MavlinkAdapter::health`] (armed state, last heartbeat) |
+//!
+//! The vehicle is discovered from its first autopilot `HEARTBEAT`; the
+//! adapter then asks it to stream attitude and position at the configured
+//! interval.  Setpoints are only followed in the autopilot's guided mode
+//! (`GUIDED` on ArduPilot, `OFFBOARD` on PX4), which the operator selects.
+//!
+//! `Halt` never disarms: cutting the motors of a flying vehicle drops it.
+//! Arming is an intent of its own so that the kernel can gate it behind the
+//! `HardwareInvoke("arming")` capability.
+
+use std::io;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use futures_util::stream::{self, BoxStream};
+use mechos_types::{
+    Event, EventPayload, FaultCode, HardwareIntent, MechError, Meters, MetersPerSecond,
+    RadiansPerSecond,
+};
+use tokio::net::{ToSocketAddrs, UdpSocket};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::adapter::MechAdapter;
+use crate::bus::EventBus;
+
+/// UDP port ground-control stations listen on by convention.
+pub const DEFAULT_MAVLINK_PORT: u16 = 14550;
+
+/// MAVLink system ID this adapter sends as (the conventional GCS ID).
+pub const GCS_SYSTEM_ID: u8 = 255;
+
+/// MAVLink component ID this adapter sends as (`MAV_COMP_ID_MISSIONPLANNER`).
+const GCS_COMPONENT_ID: u8 = 190;
+
+/// Default interval at which attitude and position are requested.
+const DEFAULT_TELEMETRY_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Interval of the adapter's own GCS heartbeat.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Below this altitude above home the vehicle counts as on the ground, so
+/// [`HardwareIntent::SetAltitude`] becomes a take-off.
+const TAKEOFF_THRESHOLD: Meters = Meters(0.5);
+
+// Message IDs and their CRC_EXTRA seeds (from the `common` dialect).
+const MSG_HEARTBEAT: u32 = 0;
+const MSG_ATTITUDE: u32 = 30;
+const MSG_GLOBAL_POSITION_INT: u32 = 33;
+const MSG_COMMAND_LONG: u32 = 76;
+const MSG_SET_POSITION_TARGET_LOCAL_NED: u32 = 84;
+const MSG_SET_POSITION_TARGET_GLOBAL_INT: u32 = 86;
+
+const MAV_CMD_NAV_TAKEOFF: u16 = 22;
+const MAV_CMD_DO_CHANGE_ALTITUDE: u16 = 186;
+const MAV_CMD_COMPONENT_ARM_DISARM: u16 = 400;
+const MAV_CMD_SET_MESSAGE_INTERVAL: u16 = 511;
+
+const MAV_FRAME_GLOBAL_RELATIVE_ALT: u8 = 3;
+const MAV_FRAME_GLOBAL_RELATIVE_ALT_INT: u8 = 6;
+const MAV_FRAME_BODY_NED: u8 = 8;
+
+/// `POSITION_TARGET_TYPEMASK`: use only vx/vy/vz and yaw rate.
+const TYPEMASK_VELOCITY_AND_YAW_RATE: u16 = 0b0000_0101_1100_0111;
+/// `POSITION_TARGET_TYPEMASK`: use only the position.
+const TYPEMASK_POSITION: u16 = 0b0000_1101_1111_1000;
+
+const MAV_TYPE_GCS: u8 = 6;
+const MAV_AUTOPILOT_INVALID: u8 = 8;
+const MAV_STATE_ACTIVE: u8 = 4;
+const MAV_MODE_FLAG_SAFETY_ARMED: u8 = 0x80;
+
+const MAVLINK_STX_V1: u8 = 0xFE;
+const MAVLINK_STX_V2: u8 = 0xFD;
+const MAVLINK_IFLAG_SIGNED: u8 = 0x01;
+const MAVLINK_SIGNATURE_LEN: usize = 13;
+
+/// CRC_EXTRA seed of the messages this adapter understands.
+fn crc_extra(message_id: u32) -> Option<u8> {
+    match message_id {
+        MSG_HEARTBEAT => Some(50),
+        MSG_ATTITUDE => Some(39),
+        MSG_GLOBAL_POSITION_INT => Some(104),
+        MSG_COMMAND_LONG => Some(152),
+        MSG_SET_POSITION_TARGET_LOCAL_NED => Some(143),
+        MSG_SET_POSITION_TARGET_GLOBAL_INT => Some(5),
+        _ => None,
+    }
+}
+
+/// One MAVLink packet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MavlinkFrame {
+    pub sequence: u8,
+    pub system_id: u8,
+    pub component_id: u8,
+    pub message_id: u32,
+    /// Message fields in wire order, little-endian.  Trailing zero bytes
+    /// may be truncated, as MAVLink 2 allows.
+    pub payload: Vec<u8>,
+}
+
+impl MavlinkFrame {
+    /// Serialise as an unsigned MAVLink 2 packet, truncating trailing zero
+    /// bytes of the payload.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `message_id` is not a message this module knows the
+    /// CRC_EXTRA of.
+    pub fn encode(&self) -> Vec<u8> {
+        let extra = crc_extra(self.message_id).expect("MAVLink message without CRC_EXTRA");
+        let len = self
+            .payload
+            .iter()
+            .rposition(|&b| b != 0)
+            .map_or(1, |i| i + 1)
+            .min(self.payload.len());
+        let id = self.message_id.to_le_bytes();
+        let mut out = Vec::with_capacity(12 + len);
+        out.extend_from_slice(&[
+            MAVLINK_STX_V2,
+            len as u8,
+            0,
+            0,
+            self.sequence,
+            self.system_id,
+            self.component_id,
+            id[0],
+            id[1],
+            id[2],
+        ]);
+        out.extend_from_slice(&self.payload[..len]);
+        let crc = crc_x25(&out[1..], extra);
+        out.extend_from_slice(&crc.to_le_bytes());
+        out
+    }
+
+    /// Parse one MAVLink 1 or 2 packet from the start of `buf`, returning it
+    /// and the number of bytes it occupied.  Signatures are skipped, not
+    /// verified.  Messages this module does not know cannot be checksummed
+    /// and are returned unverified.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Parsing`] when `buf` does not start with a
+    /// complete packet, uses unknown incompatibility flags, or fails its
+    /// checksum.
+    pub fn decode(buf: &[u8]) -> Result<(Self, usize), MechError> {
+        let truncated = || MechError::Parsing("truncated MAVLink packet".to_string());
+        let (header_len, message_id, sequence, system_id, component_id, signature_len) =
+            match buf.first() {
+                Some(&MAVLINK_STX_V2) => {
+                    let header = buf.get(..10).ok_or_else(truncated)?;
+                    if header[2] & !MAVLINK_IFLAG_SIGNED != 0 {
+                        return Err(MechError::Parsing(format!(
+                            "MAVLink packet has unsupported incompatibility flags {:#04x}",
+                            header[2]
+                        )));
+                    }
+                    let id = u32::from_le_bytes([header[7], header[8], header[9], 0]);
+                    let signature_len = if header[2] & MAVLINK_IFLAG_SIGNED != 0 {
+                        MAVLINK_SIGNATURE_LEN
+                    } else {
+                        0
+                    };
+                    (10, id, header[4], header[5], header[6], signature_len)
+                }
+                Some(&MAVLINK_STX_V1) => {
+                    let header = buf.get(..6).ok_or_else(truncated)?;
+                    (6, u32::from(header[5]), header[2], header[3], header[4], 0)
+                }
+                _ => {
+                    return Err(MechError::Parsing(
+                        "MAVLink packet does not start with a magic byte".to_string(),
+                    ));
+                }
+            };
+        let payload_end = header_len + buf[1] as usize;
+        let total = payload_end + 2 + signature_len;
+        if buf.len() < total {
+            return Err(truncated());
+        }
+        if let Some(extra) = crc_extra(message_id) {
+            let expected = u16::from_le_bytes([buf[payload_end], buf[payload_end + 1]]);
+            if crc_x25(&buf[1..payload_end], extra) != expected {
+                return Err(MechError::Parsing(format!(
+                    "MAVLink message {message_id} failed its checksum"
+                )));
+            }
+        }
+        let frame = Self {
+            sequence,
+            system_id,
+            component_id,
+            message_id,
+            payload: buf[header_len..payload_end].to_vec(),
+        };
+        Ok((frame, total))
+    }
+}
+
+/// MAVLink's CRC-16/MCRF4XX over `bytes`, seeded with the message's
+/// CRC_EXTRA.
+fn crc_x25(bytes: &[u8], extra: u8) -> u16 {
+    bytes
+        .iter()
+        .chain(std::iter::once(&extra))
+        .fold(0xFFFF, |crc, &byte| {
+            let mut tmp = byte ^ (crc & 0xFF) as u8;
+            tmp ^= tmp << 4;
+            let tmp = u16::from(tmp);
+            (crc >> 8) ^ (tmp << 8) ^ (tmp << 3) ^ (tmp >> 4)
+        })
+}
+
+/// Snapshot of the link to the flight controller.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MavlinkHealth {
+    /// System ID of the discovered vehicle, `None` until its first
+    /// heartbeat.
+    pub system_id: Option<u8>,
+    /// Armed state reported by the last heartbeat.
+    pub armed: bool,
+    /// When the last heartbeat from the vehicle was received.
+    pub last_heartbeat_at: Option<Instant>,
+}
+
+#[derive(Default)]
+struct LinkState {
+    health: MavlinkHealth,
+    component_id: u8,
+    /// Where packets for the vehicle are sent.
+    peer: Option<SocketAddr>,
+    /// Last reported altitude above home.
+    altitude: Option<Meters>,
+}
+
+/// Adapter that flies (or drives) an ArduPilot / PX4 vehicle over MAVLink.
+pub struct MavlinkAdapter {
+    bus: Arc<EventBus>,
+    socket: UdpSocket,
+    /// Peer set with [`Self::with_target`]; otherwise learned from the
+    /// vehicle's heartbeat.
+    target: Option<SocketAddr>,
+    telemetry_interval: Duration,
+    sequence: AtomicU8,
+    started_at: Instant,
+    state: Mutex<LinkState>,
+}
+
+impl MavlinkAdapter {
+    /// Create a new [`MavlinkAdapter`] exchanging packets over `socket`.
+    pub fn new(bus: Arc<EventBus>, socket: UdpSocket) -> Self {
+        Self {
+            bus,
+            socket,
+            target: None,
+            telemetry_interval: DEFAULT_TELEMETRY_INTERVAL,
+            sequence: AtomicU8::new(0),
+            started_at: Instant::now(),
+            state: Mutex::new(LinkState::default()),
+        }
+    }
+
+    /// Bind a UDP socket on `addr` (e.g. `("0.0.0.0", DEFAULT_MAVLINK_PORT)`)
+    /// and create an adapter on it.
+    pub async fn bind(bus: Arc<EventBus>, addr: impl ToSocketAddrs) -> io::Result<Self> {
+        Ok(Self::new(bus, UdpSocket::bind(addr).await?))
+    }
+
+    /// Send to `addr` (e.g. a flight controller listening on
+    /// `udpin:0.0.0.0:14555`) instead of replying to wherever the vehicle's
+    /// heartbeat came from.
+    pub fn with_target(mut self, addr: SocketAddr) -> Self {
+        self.target = Some(addr);
+        self.state_mut().peer = Some(addr);
+        self
+    }
+
+    /// How often the vehicle is asked to send `ATTITUDE` and
+    /// `GLOBAL_POSITION_INT` (default 200 ms).
+    pub fn with_telemetry_interval(mut self, interval: Duration) -> Self {
+        self.telemetry_interval = interval;
+        self
+    }
+
+    /// Current link state.
+    pub fn health(&self) -> MavlinkHealth {
+        self.state_mut().health.clone()
+    }
+
+    /// Encode `intent` as the MAVLink packet sent to the vehicle.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Parsing`] for non-finite velocities or
+    /// altitudes, a negative altitude, or coordinates outside ±90° / ±180°,
+    /// and [`MechError::HardwareFault`] with [`FaultCode::Unsupported`] for
+    /// intents a flight controller cannot execute.
+    pub fn encode_intent(&self, intent: &HardwareIntent) -> Result<MavlinkFrame, MechError> {
+        let (target_system, target_component) = {
+            let state = self.state_mut();
+            (state.health.system_id.unwrap_or(1), state.component_id)
+        };
+        let (message_id, payload) = match intent {
+            HardwareIntent::Drive {
+                linear_velocity,
+                angular_velocity,
+            } => {
+                // ── Input validation ───────────────────────────────────────
+                if !linear_velocity.get().is_finite() || !angular_velocity.get().is_finite() {
+                    return Err(MechError::Parsing(
+                        "MAVLink drive velocities must be finite".to_string(),
+                    ));
+                }
+                (
+                    MSG_SET_POSITION_TARGET_LOCAL_NED,
+                    self.velocity_setpoint(
+                        *linear_velocity,
+                        *angular_velocity,
+                        target_system,
+                        target_component,
+                    ),
+                )
+            }
+            HardwareIntent::Halt { .. } => (
+                MSG_SET_POSITION_TARGET_LOCAL_NED,
+                self.velocity_setpoint(
+                    MetersPerSecond::ZERO,
+                    RadiansPerSecond::ZERO,
+                    target_system,
+                    target_component,
+                ),
+            ),
+            HardwareIntent::Arm { armed } => (
+                MSG_COMMAND_LONG,
+                command_long(
+                    MAV_CMD_COMPONENT_ARM_DISARM,
+                    [if *armed { 1.0 } else { 0.0 }, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+                    target_system,
+                    target_component,
+                ),
+            ),
+            HardwareIntent::SetAltitude { altitude } => {
+                // ── Input validation ───────────────────────────────────────
+                validate_altitude(*altitude)?;
+                let airborne = self
+                    .state_mut()
+                    .altitude
+                    .is_some_and(|current| current > TAKEOFF_THRESHOLD);
+                let (command, params) = if airborne {
+                    let frame = f32::from(MAV_FRAME_GLOBAL_RELATIVE_ALT);
+                    (
+                        MAV_CMD_DO_CHANGE_ALTITUDE,
+                        [altitude.get(), frame, 0.0, 0.0, 0.0, 0.0, 0.0],
+                    )
+                } else {
+                    (
+                        MAV_CMD_NAV_TAKEOFF,
+                        [0.0, 0.0, 0.0, f32::NAN, 0.0, 0.0, altitude.get()],
+                    )
+                };
+                (
+                    MSG_COMMAND_LONG,
+                    command_long(command, params, target_system, target_component),
+                )
+            }
+            HardwareIntent::Goto {
+                latitude_deg,
+                longitude_deg,
+                altitude,
+            } => {
+                // ── Input validation ───────────────────────────────────────
+                if !(-90.0..=90.0).contains(latitude_deg)
+                    || !(-180.0..=180.0).contains(longitude_deg)
+                {
+                    return Err(MechError::Parsing(format!(
+                        "Goto coordinates ({latitude_deg}, {longitude_deg}) are out of range"
+                    )));
+                }
+                validate_altitude(*altitude)?;
+                let mut p = Vec::with_capacity(53);
+                p.extend_from_slice(&self.time_boot_ms().to_le_bytes());
+                p.extend_from_slice(&((latitude_deg * 1e7).round() as i32).to_le_bytes());
+                p.extend_from_slice(&((longitude_deg * 1e7).round() as i32).to_le_bytes());
+                p.extend_from_slice(&altitude.get().to_le_bytes());
+                p.extend_from_slice(&[0; 8 * 4]); // velocity, acceleration, yaw, yaw rate
+                p.extend_from_slice(&TYPEMASK_POSITION.to_le_bytes());
+                p.extend_from_slice(&[
+                    target_system,
+                    target_component,
+                    MAV_FRAME_GLOBAL_RELATIVE_ALT_INT,
+                ]);
+                (MSG_SET_POSITION_TARGET_GLOBAL_INT, p)
+            }
+            other => {
+                return Err(MechError::HardwareFault {
+                    code: FaultCode::Unsupported,
+                    component: "mavlink".to_string(),
+                    details: format!("a MAVLink vehicle cannot execute {other:?}"),
+                });
+            }
+        };
+        Ok(self.frame(message_id, payload))
+    }
+
+    /// Apply one packet received from the vehicle: heartbeats update
+    /// [`Self::health`]; attitude and position are published on the bus.
+    /// Packets from other systems and other messages are ignored.
+    ///
+    /// Returns the number of subscribers that received an event (0 when
+    /// nothing was published).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Parsing`] when the payload is too short for its
+    /// message.
+    pub fn ingest_frame(&self, frame: &MavlinkFrame) -> Result<usize, MechError> {
+        if self
+            .state_mut()
+            .health
+            .system_id
+            .is_some_and(|id| id != frame.system_id)
+        {
+            return Ok(0);
+        }
+        let (source, payload) = match frame.message_id {
+            MSG_HEARTBEAT => {
+                let p = padded::<9>(&frame.payload);
+                // GCS and companion heartbeats share the link; only an
+                // autopilot identifies the vehicle.
+                if p[5] == MAV_AUTOPILOT_INVALID {
+                    return Ok(0);
+                }
+                let mut state = self.state_mut();
+                if state.health.system_id.is_none() {
+                    info!(system_id = frame.system_id, "MAVLink vehicle discovered");
+                }
+                state.health.system_id = Some(frame.system_id);
+                state.health.armed = p[6] & MAV_MODE_FLAG_SAFETY_ARMED != 0;
+                state.health.last_heartbeat_at = Some(Instant::now());
+                state.component_id = frame.component_id;
+                return Ok(0);
+            }
+            MSG_ATTITUDE => {
+                let p = padded::<28>(&frame.payload);
+                (
+                    "attitude",
+                    EventPayload::Attitude {
+                        roll_rad: f32_at(&p, 4),
+                        pitch_rad: f32_at(&p, 8),
+                        yaw_rad: f32_at(&p, 12),
+                    },
+                )
+            }
+            MSG_GLOBAL_POSITION_INT => {
+                let p = padded::<28>(&frame.payload);
+                let altitude = Meters(i32_at(&p, 16) as f32 / 1000.0);
+                self.state_mut().altitude = Some(altitude);
+                (
+                    "global_position_int",
+                    EventPayload::GpsFix {
+                        latitude_deg: f64::from(i32_at(&p, 4)) / 1e7,
+                        longitude_deg: f64::from(i32_at(&p, 8)) / 1e7,
+                        altitude,
+                    },
+                )
+            }
+            _ => return Ok(0),
+        };
+        self.bus.publish(Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: format!("mechos-middleware::mavlink/{source}"),
+            payload,
+            trace_id: None,
+            correlation_id: None,
+        })
+    }
+
+    /// Exchange packets with the vehicle forever: ingest every received
+    /// datagram, send a GCS heartbeat every second, and request the
+    /// telemetry streams once the vehicle is discovered.  Malformed packets
+    /// and socket errors are logged.
+    pub async fn run(&self) {
+        let mut buf = [0u8; 2048];
+        let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
+        loop {
+            tokio::select! {
+                received = self.socket.recv_from(&mut buf) => match received {
+                    Ok((n, from)) => self.ingest_datagram(from, &buf[..n]).await,
+                    Err(e) => warn!(error = %e, "MAVLink receive failed"),
+                },
+                _ = heartbeat.tick() => {
+                    if let Err(e) = self.send(self.heartbeat_frame()).await {
+                        debug!(error = %e, "MAVLink heartbeat not sent");
+                    }
+                }
+            }
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    // Private helpers
+    // -------------------------------------------------------------------------
+
+    fn state_mut(&self) -> std::sync::MutexGuard<'_, LinkState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn time_boot_ms(&self) -> u32 {
+        self.started_at.elapsed().as_millis() as u32
+    }
+
+    fn frame(&self, message_id: u32, payload: Vec<u8>) -> MavlinkFrame {
+        MavlinkFrame {
+            sequence: self.sequence.fetch_add(1, Ordering::Relaxed),
+            system_id: GCS_SYSTEM_ID,
+            component_id: GCS_COMPONENT_ID,
+            message_id,
+            payload,
+        }
+    }
+
+    fn heartbeat_frame(&self) -> MavlinkFrame {
+        let mut p = vec![0; 4]; // custom_mode
+        p.extend_from_slice(&[MAV_TYPE_GCS, MAV_AUTOPILOT_INVALID, 0, MAV_STATE_ACTIVE, 3]);
+        self.frame(MSG_HEARTBEAT, p)
+    }
+
+    /// `SET_POSITION_TARGET_LOCAL_NED` payload commanding a body-frame
+    /// forward velocity and yaw rate.  MechOS yaw rates are counter-clockwise
+    /// positive (REP 103); NED yaw rates are clockwise positive.
+    fn velocity_setpoint(
+        &self,
+        linear: MetersPerSecond,
+        angular: RadiansPerSecond,
+        target_system: u8,
+        target_component: u8,
+    ) -> Vec<u8> {
+        let mut p = Vec::with_capacity(53);
+        p.extend_from_slice(&self.time_boot_ms().to_le_bytes());
+        p.extend_from_slice(&[0; 3 * 4]); // x, y, z
+        for v in [linear.get(), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, -angular.get()] {
+            p.extend_from_slice(&v.to_le_bytes()); // vx..vz, afx..afz, yaw, yaw_rate
+        }
+        p.extend_from_slice(&TYPEMASK_VELOCITY_AND_YAW_RATE.to_le_bytes());
+        p.extend_from_slice(&[target_system, target_component, MAV_FRAME_BODY_NED]);
+        p
+    }
+
+    async fn send(&self, frame: MavlinkFrame) -> Result<(), MechError> {
+        let Some(peer) = self.state_mut().peer else {
+            return Err(MechError::Channel(
+                "no MAVLink vehicle has been discovered yet".to_string(),
+            ));
+        };
+        self.socket
+            .send_to(&frame.encode(), peer)
+            .await
+            .map(|_| ())
+            .map_err(|e| MechError::Channel(format!("MAVLink send to {peer}: {e}")))
+    }
+
+    async fn ingest_datagram(&self, from: SocketAddr, mut datagram: &[u8]) {
+        while let Some(start) = datagram
+            .iter()
+            .position(|&b| b == MAVLINK_STX_V2 || b == MAVLINK_STX_V1)
+        {
+            datagram = &datagram[start..];
+            let (frame, len) = match MavlinkFrame::decode(datagram) {
+                Ok(decoded) => decoded,
+                Err(e) => {
+                    debug!(%from, error = %e, "dropping MAVLink packet");
+                    datagram = &datagram[1..];
+                    continue;
+                }
+            };
+            datagram = &datagram[len..];
+
+            let undiscovered = self.state_mut().health.system_id.is_none();
+            if let Err(e) = self.ingest_frame(&frame) {
+                warn!(%from, error = %e, "dropping MAVLink message");
+            }
+            if undiscovered && self.state_mut().health.system_id.is_some() {
+                if self.target.is_none() {
+                    self.state_mut().peer = Some(from);
+                }
+                self.request_telemetry().await;
+            }
+        }
+    }
+
+    async fn request_telemetry(&self) {
+        let (target_system, target_component) = {
+            let state = self.state_mut();
+            (state.health.system_id.unwrap_or(1), state.component_id)
+        };
+        let interval_us = self.telemetry_interval.as_micros() as f32;
+        for message_id in [MSG_ATTITUDE, MSG_GLOBAL_POSITION_INT] {
+            let payload = command_long(
+                MAV_CMD_SET_MESSAGE_INTERVAL,
+                [message_id as f32, interval_us, 0.0, 0.0, 0.0, 0.0, 0.0],
+                target_system,
+                target_component,
+            );
+            if let Err(e) = self.send(self.frame(MSG_COMMAND_LONG, payload)).await {
+                warn!(error = %e, "MAVLink telemetry request failed");
+            }
+        }
+    }
+}
+
+/// `COMMAND_LONG` payload.
+fn command_long(
+    command: u16,
+    params: [f32; 7],
+    target_system: u8,
+    target_component: u8,
+) -> Vec<u8> {
+    let mut p = Vec::with_capacity(33);
+    for param in params {
+        p.extend_from_slice(&param.to_le_bytes());
+    }
+    p.extend_from_slice(&command.to_le_bytes());
+    p.extend_from_slice(&[target_system, target_component, 0]); // confirmation
+    p
+}
+
+fn validate_altitude(altitude: Meters) -> Result<(), MechError> {
+    if !altitude.get().is_finite() || altitude.get() < 0.0 {
+        return Err(MechError::Parsing(format!(
+            "altitude {} m must be finite and not below home",
+            altitude.get()
+        )));
+    }
+    Ok(())
+}
+
+/// Zero-extend a (possibly truncated) payload to its full length.
+fn padded<const N: usize>(payload: &[u8]) -> [u8; N] {
+    let mut out = [0; N];
+    let len = payload.len().min(N);
+    out[..len].copy_from_slice(&payload[..len]);
+    out
+}
+
+fn f32_at(p: &[u8], offset: usize) -> f32 {
+    f32::from_le_bytes(p[offset..offset + 4].try_into().unwrap())
+}
+
+fn i32_at(p: &[u8], offset: usize) -> i32 {
+    i32::from_le_bytes(p[offset..offset + 4].try_into().unwrap())
+}
+
+#[async_trait]
+impl MechAdapter for MavlinkAdapter {
+    /// Send the intent's MAVLink packet to the vehicle.
+    ///
+    /// Returns [`MechError::Channel`] until a vehicle has been discovered
+    /// (or a target set with [`MavlinkAdapter::with_target`]).
+    async fn execute_intent(&self, intent: HardwareIntent) -> Result<(), MechError> {
+        let frame = self.encode_intent(&intent)?;
+        self.send(frame).await
+    }
+
+    /// Telemetry is pushed onto the bus by [`MavlinkAdapter::run`]; this
+    /// returns an empty stream.
+    async fn sensor_stream(&self) -> BoxStream<'static, EventPayload> {
+        Box::pin(stream::empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn make_adapter() -> (Arc<EventBus>, Arc<MavlinkAdapter>) {
+        let bus = Arc::new(EventBus::default());
+        let adapter = MavlinkAdapter::bind(Arc::clone(&bus), "127.0.0.1:0")
+            .await
+            .unwrap();
+        (bus, Arc::new(adapter))
+    }
+
+    /// A packet as sent by an ArduPilot autopilot (system 1, component 1).
+    fn vehicle_frame(message_id: u32, payload: Vec<u8>) -> MavlinkFrame {
+        MavlinkFrame {
+            sequence: 7,
+            system_id: 1,
+            component_id: 1,
+            message_id,
+            payload,
+        }
+    }
+
+    fn heartbeat(armed: bool) -> MavlinkFrame {
+        // custom_mode, MAV_TYPE_QUADROTOR, MAV_AUTOPILOT_ARDUPILOTMEGA,
+        // base_mode, MAV_STATE_STANDBY, version.
+        let base_mode = if armed { 0x81 } else { 0x01 };
+        vehicle_frame(MSG_HEARTBEAT, vec![4, 0, 0, 0, 2, 3, base_mode, 3, 3])
+    }
+
+    fn global_position(lat: i32, lon: i32, relative_alt_mm: i32) -> MavlinkFrame {
+        let mut p = vec![0; 4];
+        for v in [lat, lon, 488_000, relative_alt_mm] {
+            p.extend_from_slice(&v.to_le_bytes());
+        }
+        p.extend_from_slice(&[0; 8]);
+        vehicle_frame(MSG_GLOBAL_POSITION_INT, p)
+    }
+
+    fn command_of(frame: &MavlinkFrame) -> (u16, [f32; 7]) {
+        let p = padded::<33>(&frame.payload);
+        let params = std::array::from_fn(|i| f32_at(&p, i * 4));
+        (u16::from_le_bytes([p[28], p[29]]), params)
+    }
+
+    #[test]
+    fn crc_matches_mcrf4xx_check_value() {
+        // CRC-16/MCRF4XX of "123456789" is 0x6F91.  CRC_EXTRA is accumulated
+        // after the data, so the last digit can stand in for it.
+        assert_eq!(crc_x25(b"12345678", b'9'), 0x6F91);
+    }
+
+    #[test]
+    fn frames_roundtrip_and_reject_corruption() {
+        let frame = heartbeat(true);
+        let bytes = frame.encode();
+        assert_eq!(bytes[0], MAVLINK_STX_V2);
+        let (decoded, len) = MavlinkFrame::decode(&bytes).unwrap();
+        assert_eq!(decoded, frame);
+        assert_eq!(len, bytes.len());
+
+        let mut corrupted = bytes.clone();
+        corrupted[12] ^= 0xFF;
+        assert!(MavlinkFrame::decode(&corrupted).is_err());
+        assert!(MavlinkFrame::decode(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn encode_truncates_trailing_zeros() {
+        let frame = vehicle_frame(MSG_ATTITUDE, vec![1, 0, 0, 0, 0, 0]);
+        let bytes = frame.encode();
+        assert_eq!(bytes[1], 1);
+        let (decoded, _) = MavlinkFrame::decode(&bytes).unwrap();
+        assert_eq!(decoded.payload, vec![1]);
+    }
+
+    #[tokio::test]
+    async fn drive_becomes_a_body_frame_velocity_setpoint() {
+        let (_, adapter) = make_adapter().await;
+        let frame = adapter
+            .encode_intent(&HardwareIntent::Drive {
+                linear_velocity: MetersPerSecond(1.5),
+                angular_velocity: RadiansPerSecond(0.5),
+            })
+            .unwrap();
+        assert_eq!(frame.message_id, MSG_SET_POSITION_TARGET_LOCAL_NED);
+        assert_eq!(frame.payload.len(), 53);
+        assert_eq!(f32_at(&frame.payload, 16), 1.5); // vx
+        assert_eq!(f32_at(&frame.payload, 44), -0.5); // yaw_rate, NED
+        assert_eq!(
+            u16::from_le_bytes([frame.payload[48], frame.payload[49]]),
+            TYPEMASK_VELOCITY_AND_YAW_RATE
+        );
+        assert_eq!(frame.payload[52], MAV_FRAME_BODY_NED);
+
+        assert!(matches!(
+            adapter.encode_intent(&HardwareIntent::Dock),
+            Err(MechError::HardwareFault {
+                code: FaultCode::Unsupported,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn flight_intents_become_commands_and_setpoints() {
+        let (bus, adapter) = make_adapter().await;
+        let _rx = bus.subscribe();
+
+        let arm = adapter
+            .encode_intent(&HardwareIntent::Arm { armed: true })
+            .unwrap();
+        assert_eq!(arm.message_id, MSG_COMMAND_LONG);
+        assert_eq!(command_of(&arm).0, MAV_CMD_COMPONENT_ARM_DISARM);
+        assert_eq!(command_of(&arm).1[0], 1.0);
+
+        // On the ground, SetAltitude takes off; in the air it climbs.
+        let set_altitude = HardwareIntent::SetAltitude {
+            altitude: Meters(10.0),
+        };
+        let (command, params) = command_of(&adapter.encode_intent(&set_altitude).unwrap());
+        assert_eq!((command, params[6]), (MAV_CMD_NAV_TAKEOFF, 10.0));
+        adapter.ingest_frame(&global_position(0, 0, 5_000)).unwrap();
+        let (command, params) = command_of(&adapter.encode_intent(&set_altitude).unwrap());
+        assert_eq!((command, params[0]), (MAV_CMD_DO_CHANGE_ALTITUDE, 10.0));
+
+        let goto = adapter
+            .encode_intent(&HardwareIntent::Goto {
+                latitude_deg: 47.3977419,
+                longitude_deg: 8.5455938,
+                altitude: Meters(20.0),
+            })
+            .unwrap();
+        assert_eq!(goto.message_id, MSG_SET_POSITION_TARGET_GLOBAL_INT);
+        assert_eq!(i32_at(&goto.payload, 4), 473_977_419);
+        assert_eq!(i32_at(&goto.payload, 8), 85_455_938);
+        assert_eq!(goto.payload[52], MAV_FRAME_GLOBAL_RELATIVE_ALT_INT);
+
+        for bad in [
+            HardwareIntent::Goto {
+                latitude_deg: 91.0,
+                longitude_deg: 0.0,
+                altitude: Meters(5.0),
+            },
+            HardwareIntent::SetAltitude {
+                altitude: Meters(-1.0),
+            },
+        ] {
+            assert!(matches!(
+                adapter.encode_intent(&bad),
+                Err(MechError::Parsing(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn telemetry_is_published_and_heartbeats_update_health() {
+        let (bus, adapter) = make_adapter().await;
+        let mut rx = bus.subscribe();
+
+        assert_eq!(adapter.ingest_frame(&heartbeat(true)).unwrap(), 0);
+        let health = adapter.health();
+        assert_eq!(health.system_id, Some(1));
+        assert!(health.armed && health.last_heartbeat_at.is_some());
+
+        let mut attitude = vec![0; 4];
+        for v in [0.1f32, -0.2, 1.5] {
+            attitude.extend_from_slice(&v.to_le_bytes());
+        }
+        adapter
+            .ingest_frame(&vehicle_frame(MSG_ATTITUDE, attitude))
+            .unwrap();
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.source, "mechos-middleware::mavlink/attitude");
+        assert!(matches!(
+            event.payload,
+            EventPayload::Attitude { pitch_rad, yaw_rad, .. } if pitch_rad == -0.2 && yaw_rad == 1.5
+        ));
+
+        adapter
+            .ingest_frame(&global_position(-338_688_197, 1_512_092_955, 12_500))
+            .unwrap();
+        match rx.recv().await.unwrap().payload {
+            EventPayload::GpsFix {
+                latitude_deg,
+                longitude_deg,
+                altitude,
+            } => {
+                assert!((latitude_deg + 33.8688197).abs() < 1e-9);
+                assert!((longitude_deg - 151.2092955).abs() < 1e-9);
+                assert_eq!(altitude, Meters(12.5));
+            }
+            other => panic!("expected GpsFix, got {other:?}"),
+        }
+
+        // Another vehicle on the same link is ignored.
+        let mut other = global_position(0, 0, 0);
+        other.system_id = 2;
+        assert_eq!(adapter.ingest_frame(&other).unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn run_discovers_the_vehicle_and_sends_intents_to_it() {
+        let (_, adapter) = make_adapter().await;
+        assert!(matches!(
+            adapter
+                .execute_intent(HardwareIntent::Arm { armed: true })
+                .await,
+            Err(MechError::Channel(_))
+        ));
+
+        let vehicle = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let running = tokio::spawn({
+            let adapter = Arc::clone(&adapter);
+            async move { adapter.run().await }
+        });
+        let gcs = adapter.socket.local_addr().unwrap();
+        vehicle
+            .send_to(&heartbeat(false).encode(), gcs)
+            .await
+            .unwrap();
+
+        // The vehicle is asked for both telemetry streams ...
+        let mut buf = [0u8; 512];
+        let mut requested = Vec::new();
+        while requested.len() < 2 {
+            let n = vehicle.recv(&mut buf).await.unwrap();
+            let (frame, _) = MavlinkFrame::decode(&buf[..n]).unwrap();
+            if frame.message_id == MSG_COMMAND_LONG {
+                let (command, params) = command_of(&frame);
+                assert_eq!(command, MAV_CMD_SET_MESSAGE_INTERVAL);
+                requested.push(params[0] as u32);
+            }
+        }
+        assert_eq!(requested, vec![MSG_ATTITUDE, MSG_GLOBAL_POSITION_INT]);
+
+        // ... and then receives intents.
+        adapter
+            .execute_intent(HardwareIntent::Arm { armed: true })
+            .await
+            .unwrap();
+        loop {
+            let n = vehicle.recv(&mut buf).await.unwrap();
+            let (frame, _) = MavlinkFrame::decode(&buf[..n]).unwrap();
+            if frame.message_id == MSG_COMMAND_LONG {
+                assert_eq!(command_of(&frame).0, MAV_CMD_COMPONENT_ARM_DISARM);
+                assert_eq!(frame.system_id, GCS_SYSTEM_ID);
+                break;
+            }
+        }
+        running.abort();
+    }
+}
@@ -0,0 +1,200 @@
+//! [`BusMonitor`] – periodic health reports of the event bus itself.
+//!
+//! [`EventBus::stats`] gives cumulative counters on demand.  The monitor
+//! samples them every interval, derives per-lane publish rates and publishes
+//! the result as an [`EventPayload::BusHealth`] event, on both the global
+//! channel (which the Cockpit forwards) and the [`Topic::Telemetry`] lane, so
+//! operators can watch the nervous system's load, slow subscribers and
+//! dropped traffic.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use mechos_types::{Event, EventPayload, LaneStats};
+use tokio::time::{Instant, MissedTickBehavior};
+use uuid::Uuid;
+
+use crate::bus::{BusStats, EventBus, Topic};
+
+/// Default interval between [`BusMonitor`] reports.
+pub const DEFAULT_REPORT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Publishes [`EventPayload::BusHealth`] reports; see the
+/// [module docs](self).
+#[derive(Debug)]
+pub struct BusMonitor {
+    bus: Arc<EventBus>,
+    interval: Duration,
+    last: Option<(Instant, BusStats)>,
+}
+
+impl BusMonitor {
+    /// Monitor `bus`, reporting every [`DEFAULT_REPORT_INTERVAL`].
+    pub fn new(bus: Arc<EventBus>) -> Self {
+        Self {
+            bus,
+            interval: DEFAULT_REPORT_INTERVAL,
+            last: None,
+        }
+    }
+
+    /// Report every `interval` instead.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Snapshot every lane, with publish rates since the previous sample
+    /// (`0.0` on the first).  The global channel comes first.
+    pub fn sample(&mut self) -> Vec<LaneStats> {
+        let now = Instant::now();
+        let stats = self.bus.stats();
+        let rate = |published: u64, previous: Option<u64>| match (&self.last, previous) {
+            (Some((at, _)), Some(previous)) => {
+                let elapsed = now.duration_since(*at).as_secs_f32();
+                if elapsed > 0.0 {
+                    published.saturating_sub(previous) as f32 / elapsed
+                } else {
+                    0.0
+                }
+            }
+            _ => 0.0,
+        };
+        let previous = self.last.as_ref().map(|(_, stats)| stats);
+
+        let mut lanes = vec![LaneStats {
+            lane: "global".to_string(),
+            published: stats.global_published,
+            publish_rate_hz: rate(stats.global_published, previous.map(|p| p.global_published)),
+            subscribers: stats.global_subscribers as u32,
+            lagging: 0,
+            dropped: 0,
+        }];
+        for topic in &stats.topics {
+            let before = previous
+                .and_then(|p| p.topics.iter().find(|t| t.topic == topic.topic))
+                .map(|t| t.published);
+            lanes.push(LaneStats {
+                lane: topic.topic.as_str().to_string(),
+                published: topic.published,
+                publish_rate_hz: rate(topic.published, before),
+                subscribers: topic.subscribers as u32,
+                lagging: topic.lagging,
+                dropped: topic.dropped,
+            });
+        }
+        self.last = Some((now, stats));
+        lanes
+    }
+
+    /// Publish a report every interval, forever.
+    pub async fn run(mut self) {
+        let mut ticker = tokio::time::interval(self.interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            let lanes = self.sample();
+            self.report(lanes);
+        }
+    }
+
+    fn report(&self, lanes: Vec<LaneStats>) {
+        let event = Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: "mechos-middleware::bus/monitor".to_string(),
+            payload: EventPayload::BusHealth { lanes },
+            trace_id: None,
+            correlation_id: None,
+        };
+        // Nobody listening is not an error for a health report.
+        let _ = self.bus.publish_to(Topic::Telemetry, event.clone());
+        let _ = self.bus.publish(event);
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::BackpressurePolicy;
+
+    fn ping() -> Event {
+        Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: "test".to_string(),
+            payload: EventPayload::Reasoning("ping".to_string()),
+            trace_id: None,
+            correlation_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn stats_count_publishes_subscribers_and_drops() {
+        let bus = EventBus::new(2).with_policy(Topic::SwarmComm, BackpressurePolicy::DropNewest);
+        let _global = bus.subscribe();
+        let slow = bus.subscribe_to(Topic::SwarmComm);
+        for _ in 0..5 {
+            bus.publish_to(Topic::SwarmComm, ping()).unwrap();
+        }
+        bus.publish(ping()).unwrap();
+
+        let stats = bus.stats();
+        assert_eq!((stats.global_published, stats.global_subscribers), (1, 1));
+        let swarm = &stats.topics[3];
+        assert_eq!(swarm.topic, Topic::SwarmComm);
+        assert_eq!((swarm.published, swarm.subscribers), (5, 1));
+        assert_eq!((swarm.lagging, swarm.dropped), (3, 3));
+
+        // Drops outlive the subscriber that suffered them.
+        drop(slow);
+        let swarm = &bus.stats().topics[3];
+        assert_eq!((swarm.subscribers, swarm.dropped), (0, 3));
+    }
+
+    #[tokio::test]
+    async fn monitor_reports_rates_on_both_channels() {
+        let bus = Arc::new(EventBus::default());
+        let mut global = bus.subscribe();
+        let mut telemetry = bus.subscribe_to(Topic::Telemetry);
+        let mut monitor = BusMonitor::new(Arc::clone(&bus));
+        assert!(
+            monitor
+                .sample()
+                .iter()
+                .all(|lane| lane.publish_rate_hz == 0.0)
+        );
+
+        let _alerts = bus.subscribe_to(Topic::SystemAlerts);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        for _ in 0..10 {
+            bus.publish_to(Topic::SystemAlerts, ping()).unwrap();
+        }
+        let lanes = monitor.sample();
+        assert_eq!(lanes[0].lane, "global");
+        let alerts = lanes.iter().find(|l| l.lane == "system_alerts").unwrap();
+        assert_eq!(alerts.published, 10);
+        assert!(alerts.publish_rate_hz > 10.0, "{}", alerts.publish_rate_hz);
+        assert!(
+            alerts.publish_rate_hz <= 100.0,
+            "{}",
+            alerts.publish_rate_hz
+        );
+
+        monitor.report(lanes);
+        for event in [
+            telemetry.recv().await.unwrap(),
+            global.recv().await.unwrap(),
+        ] {
+            assert_eq!(event.source, "mechos-middleware::bus/monitor");
+            assert!(
+                matches!(event.payload, EventPayload::BusHealth { ref lanes } if lanes.len() == 7)
+            );
+        }
+    }
+}
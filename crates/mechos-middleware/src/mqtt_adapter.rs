@@ -0,0 +1,410 @@
+//! MQTT adapter for lightweight IoT hardware.
+//!
+//! [`MqttAdapter`] connects MechOS to robots built from ESP32-class
+//! microcontrollers that speak MQTT instead of ROS 2:
+//!
+//! * **Outbound (Intents)** – every [`HardwareIntent`] is published as its
+//!   JSON wire form (`{"action":"Drive","payload":{…}}`) on a per-action
+//!   topic.  By default that is `<prefix>/intent/<action>` (e.g.
+//!   `mechos/intent/drive`) with QoS 1 and no retain flag; any action can be
+//!   re-routed with [`MqttAdapter::with_route`] and an [`MqttRoute`] carrying
+//!   its own topic, QoS and retained-message option.
+//!
+//! * **Inbound (Sensors)** – topics registered with
+//!   [`MqttAdapter::with_sensor`] are subscribed on connect.  Each message is
+//!   parsed as the JSON body of the chosen [`MqttSensor`] payload (e.g.
+//!   `{"voltage":12.1,"current":-0.4,"charging":false,"percent":71}` for
+//!   [`MqttSensor::PowerStatus`]) and published on the [`EventBus`].
+//!
+//! The adapter owns the [`AsyncClient`]; drive the matching [`EventLoop`]
+//! with [`MqttAdapter::run`], which also resubscribes after reconnects.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use futures_util::stream::{self, BoxStream};
+use mechos_types::{Event, EventPayload, HardwareIntent, MechError};
+use rumqttc::{AsyncClient, EventLoop, MqttOptions, Packet, QoS};
+use serde_json::json;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::adapter::{MechAdapter, action_name};
+use crate::bus::EventBus;
+use crate::ros2_adapter::MAX_LIDAR_RANGES;
+
+/// Topic prefix used for default intent routes.
+pub const DEFAULT_MQTT_TOPIC_PREFIX: &str = "mechos";
+
+/// Maximum byte length of an inbound sensor message.
+///
+/// Larger messages are rejected before they are parsed.
+pub const MAX_MQTT_PAYLOAD_BYTES: usize = 64 * 1024; // 64 KiB
+
+/// Capacity of the client's outgoing request queue.
+const REQUEST_QUEUE_CAPACITY: usize = 64;
+
+/// Pause after a connection error before the event loop reconnects.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Where and how one intent action is published.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MqttRoute {
+    pub topic: String,
+    pub qos: QoS,
+    /// Ask the broker to keep the last message for late subscribers (useful
+    /// for state-like commands such as relays).
+    pub retain: bool,
+}
+
+impl MqttRoute {
+    /// Route to `topic` with QoS 1 (at least once) and no retain flag.
+    pub fn new(topic: impl Into<String>) -> Self {
+        Self {
+            topic: topic.into(),
+            qos: QoS::AtLeastOnce,
+            retain: false,
+        }
+    }
+
+    pub fn with_qos(mut self, qos: QoS) -> Self {
+        self.qos = qos;
+        self
+    }
+
+    pub fn with_retain(mut self, retain: bool) -> Self {
+        self.retain = retain;
+        self
+    }
+}
+
+/// The [`EventPayload`] an inbound sensor topic carries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MqttSensor {
+    /// A [`TelemetryData`][mechos_types::TelemetryData] object.
+    Telemetry,
+    /// The fields of [`EventPayload::LidarScan`].
+    LidarScan,
+    /// The fields of [`EventPayload::PowerStatus`].
+    PowerStatus,
+}
+
+impl MqttSensor {
+    /// Name of the matching [`EventPayload`] variant.
+    fn variant(self) -> &'static str {
+        match self {
+            MqttSensor::Telemetry => "Telemetry",
+            MqttSensor::LidarScan => "LidarScan",
+            MqttSensor::PowerStatus => "PowerStatus",
+        }
+    }
+}
+
+/// Adapter that publishes intents to, and ingests sensor data from, an MQTT
+/// broker.
+pub struct MqttAdapter {
+    bus: Arc<EventBus>,
+    client: AsyncClient,
+    prefix: String,
+    /// Per-action overrides, keyed by the intent's `action` name.
+    routes: HashMap<String, MqttRoute>,
+    /// Subscribed sensor topics.
+    sensors: HashMap<String, (MqttSensor, QoS)>,
+}
+
+impl MqttAdapter {
+    /// Create a new [`MqttAdapter`] for the broker described by `options`.
+    ///
+    /// The returned [`EventLoop`] must be driven with [`Self::run`] for any
+    /// message to leave or arrive.
+    pub fn new(bus: Arc<EventBus>, options: MqttOptions) -> (Self, EventLoop) {
+        let (client, eventloop) = AsyncClient::new(options, REQUEST_QUEUE_CAPACITY);
+        let adapter = Self {
+            bus,
+            client,
+            prefix: DEFAULT_MQTT_TOPIC_PREFIX.to_string(),
+            routes: HashMap::new(),
+            sensors: HashMap::new(),
+        };
+        (adapter, eventloop)
+    }
+
+    /// Topic prefix for default intent routes (default
+    /// [`DEFAULT_MQTT_TOPIC_PREFIX`]), e.g. `"robots/esp32-01"`.
+    pub fn with_topic_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Publish intents whose `action` is `action` (e.g. `"Drive"`) on
+    /// `route` instead of the default topic.
+    pub fn with_route(mut self, action: impl Into<String>, route: MqttRoute) -> Self {
+        self.routes.insert(action.into(), route);
+        self
+    }
+
+    /// Subscribe to `topic` (wildcards allowed) and publish its messages as
+    /// `sensor` events.
+    pub fn with_sensor(mut self, topic: impl Into<String>, sensor: MqttSensor, qos: QoS) -> Self {
+        self.sensors.insert(topic.into(), (sensor, qos));
+        self
+    }
+
+    /// The route an intent is published on.
+    pub fn route_for(&self, intent: &HardwareIntent) -> MqttRoute {
+        let action = action_name(intent);
+        self.routes.get(&action).cloned().unwrap_or_else(|| {
+            MqttRoute::new(format!("{}/intent/{}", self.prefix, to_snake_case(&action)))
+        })
+    }
+
+    /// Queue a subscription for every registered sensor topic.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Channel`] when the client's request queue is
+    /// closed or full.
+    pub fn subscribe_sensors(&self) -> Result<(), MechError> {
+        for (topic, (_, qos)) in &self.sensors {
+            self.client
+                .try_subscribe(topic, *qos)
+                .map_err(|e| MechError::Channel(format!("MQTT subscribe to '{topic}': {e}")))?;
+        }
+        Ok(())
+    }
+
+    /// Parse an inbound message on `topic` and publish it on the bus.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Parsing`] when the topic is not a registered
+    /// sensor, the message is larger than [`MAX_MQTT_PAYLOAD_BYTES`], does
+    /// not match the sensor's payload, or is a scan with more than
+    /// [`MAX_LIDAR_RANGES`] readings.
+    pub fn ingest_message(&self, topic: &str, payload: &[u8]) -> Result<usize, MechError> {
+        // ── Input validation ───────────────────────────────────────────────
+        let Some((sensor, _)) = self
+            .sensors
+            .iter()
+            .find(|(filter, _)| rumqttc::matches(topic, filter))
+            .map(|(_, s)| *s)
+        else {
+            return Err(MechError::Parsing(format!(
+                "MQTT topic '{topic}' is not a registered sensor"
+            )));
+        };
+        if payload.len() > MAX_MQTT_PAYLOAD_BYTES {
+            return Err(MechError::Parsing(format!(
+                "MQTT message on '{topic}' is {} bytes, exceeding the limit of {}",
+                payload.len(),
+                MAX_MQTT_PAYLOAD_BYTES,
+            )));
+        }
+        let body: serde_json::Value = serde_json::from_slice(payload)
+            .map_err(|e| MechError::Parsing(format!("MQTT message on '{topic}': {e}")))?;
+        let payload: EventPayload = serde_json::from_value(json!({ sensor.variant(): body }))
+            .map_err(|e| {
+                MechError::Parsing(format!(
+                    "MQTT message on '{topic}' is not a {}: {e}",
+                    sensor.variant()
+                ))
+            })?;
+        if let EventPayload::LidarScan { ranges, .. } = &payload
+            && ranges.len() > MAX_LIDAR_RANGES
+        {
+            return Err(MechError::Parsing(format!(
+                "MQTT scan on '{topic}' has {} range readings, exceeding the limit of {}",
+                ranges.len(),
+                MAX_LIDAR_RANGES,
+            )));
+        }
+
+        self.bus.publish(Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: format!("mechos-middleware::mqtt/{topic}"),
+            payload,
+            trace_id: None,
+            correlation_id: None,
+        })
+    }
+
+    /// Drive the MQTT connection forever: (re)subscribe to sensor topics on
+    /// every connect and ingest incoming messages.  Malformed messages and
+    /// connection errors are logged; the event loop reconnects on its own.
+    pub async fn run(&self, mut eventloop: EventLoop) {
+        loop {
+            match eventloop.poll().await {
+                Ok(rumqttc::Event::Incoming(Packet::ConnAck(_))) => {
+                    if let Err(e) = self.subscribe_sensors() {
+                        warn!(error = %e, "MQTT sensor subscription failed");
+                    }
+                }
+                Ok(rumqttc::Event::Incoming(Packet::Publish(publish))) => {
+                    if let Err(e) = self.ingest_message(&publish.topic, &publish.payload) {
+                        warn!(topic = %publish.topic, error = %e, "dropping MQTT message");
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    warn!(error = %e, "MQTT connection error; reconnecting");
+                    tokio::time::sleep(RECONNECT_DELAY).await;
+                }
+            }
+        }
+    }
+}
+
+/// `"MoveEndEffector"` → `"move_end_effector"`.
+fn to_snake_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[async_trait]
+impl MechAdapter for MqttAdapter {
+    /// Publish the intent's JSON wire form on its [`MqttRoute`].
+    async fn execute_intent(&self, intent: HardwareIntent) -> Result<(), MechError> {
+        let route = self.route_for(&intent);
+        let body = serde_json::to_vec(&intent)
+            .map_err(|e| MechError::Serialization(format!("failed to encode intent: {e}")))?;
+        self.client
+            .publish(&route.topic, route.qos, route.retain, body)
+            .await
+            .map_err(|e| MechError::Channel(format!("MQTT publish to '{}': {e}", route.topic)))
+    }
+
+    /// Sensor topics are pushed onto the bus by [`MqttAdapter::run`]; this
+    /// returns an empty stream.
+    async fn sensor_stream(&self) -> BoxStream<'static, EventPayload> {
+        Box::pin(stream::empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mechos_types::{MetersPerSecond, RadiansPerSecond};
+
+    fn make_adapter() -> (Arc<EventBus>, MqttAdapter) {
+        let bus = Arc::new(EventBus::default());
+        let (adapter, _eventloop) = MqttAdapter::new(
+            Arc::clone(&bus),
+            MqttOptions::new("test", "localhost", 1883),
+        );
+        (bus, adapter)
+    }
+
+    #[test]
+    fn intents_use_default_or_configured_routes() {
+        let (_, adapter) = make_adapter();
+        let adapter = adapter.with_topic_prefix("robots/esp32").with_route(
+            "TriggerRelay",
+            MqttRoute::new("robots/esp32/relay")
+                .with_qos(QoS::ExactlyOnce)
+                .with_retain(true),
+        );
+
+        let route = adapter.route_for(&HardwareIntent::Drive {
+            linear_velocity: MetersPerSecond(0.2),
+            angular_velocity: RadiansPerSecond(0.0),
+        });
+        assert_eq!(route, MqttRoute::new("robots/esp32/intent/drive"));
+        assert_eq!(
+            adapter.route_for(&HardwareIntent::Dock).topic,
+            "robots/esp32/intent/dock"
+        );
+
+        let relay = adapter.route_for(&HardwareIntent::TriggerRelay {
+            relay_id: "pump".to_string(),
+            state: true,
+        });
+        assert_eq!(relay.topic, "robots/esp32/relay");
+        assert_eq!(relay.qos, QoS::ExactlyOnce);
+        assert!(relay.retain);
+    }
+
+    #[test]
+    fn snake_case_action_names() {
+        assert_eq!(to_snake_case("MoveEndEffector"), "move_end_effector");
+        assert_eq!(to_snake_case("Halt"), "halt");
+    }
+
+    #[tokio::test]
+    async fn execute_intent_queues_publish_while_event_loop_lives() {
+        let bus = Arc::new(EventBus::default());
+        let (adapter, eventloop) =
+            MqttAdapter::new(bus, MqttOptions::new("test", "localhost", 1883));
+        let halt = HardwareIntent::Halt {
+            reason: "test".to_string(),
+        };
+
+        adapter.execute_intent(halt.clone()).await.unwrap();
+        drop(eventloop);
+        assert!(matches!(
+            adapter.execute_intent(halt).await,
+            Err(MechError::Channel(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn sensor_messages_are_published_on_the_bus() {
+        let (bus, adapter) = make_adapter();
+        let adapter = adapter
+            .with_sensor("esp32/+/power", MqttSensor::PowerStatus, QoS::AtMostOnce)
+            .with_sensor("esp32/scan", MqttSensor::LidarScan, QoS::AtMostOnce);
+        let mut rx = bus.subscribe();
+
+        adapter
+            .ingest_message(
+                "esp32/base/power",
+                br#"{"voltage":12.1,"current":-0.4,"charging":false,"percent":71.0}"#,
+            )
+            .unwrap();
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.source, "mechos-middleware::mqtt/esp32/base/power");
+        assert!(matches!(
+            event.payload,
+            EventPayload::PowerStatus { percent, .. } if percent == 71.0
+        ));
+
+        adapter
+            .ingest_message(
+                "esp32/scan",
+                br#"{"ranges":[0.5,0.7],"angle_min_rad":0.0,"angle_increment_rad":0.1}"#,
+            )
+            .unwrap();
+        assert!(matches!(
+            rx.recv().await.unwrap().payload,
+            EventPayload::LidarScan { ref ranges, .. } if ranges.len() == 2
+        ));
+    }
+
+    #[test]
+    fn bad_sensor_messages_are_rejected() {
+        let (_, adapter) = make_adapter();
+        let adapter = adapter.with_sensor("esp32/odom", MqttSensor::Telemetry, QoS::AtMostOnce);
+
+        assert!(adapter.ingest_message("esp32/unknown", b"{}").is_err());
+        assert!(adapter.ingest_message("esp32/odom", b"not json").is_err());
+        assert!(adapter.ingest_message("esp32/odom", br#"{"x":1}"#).is_err());
+        let oversized = vec![b' '; MAX_MQTT_PAYLOAD_BYTES + 1];
+        assert!(matches!(
+            adapter.ingest_message("esp32/odom", &oversized),
+            Err(MechError::Parsing(_))
+        ));
+    }
+}
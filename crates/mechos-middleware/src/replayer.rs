@@ -0,0 +1,230 @@
+//! [`BusReplayer`] – re-publish a recorded journal onto a live bus.
+//!
+//! Where the runtime's `ReplayDriver` feeds a journal into a single
+//! `AgentLoop` on a simulated clock, the replayer plays captured field data
+//! back onto a real [`EventBus`] in wall-clock time.  Everything listening on
+//! that bus – the agent loop, the kernel's rules, the Cockpit, bridges – sees
+//! the recorded traffic as if it were live, which makes it suitable for
+//! end-to-end regression runs.
+//!
+//! * Each entry goes back to the lane it was recorded on: the global channel
+//!   or its [`Topic`].
+//! * Relative timing is preserved and divided by the `speed` factor;
+//!   [`BusReplayer::UNPACED`] publishes back-to-back.
+//! * Timestamps are re-stamped to the moment of publication (staleness
+//!   checks would otherwise reject every recorded reading).  Ids and sources
+//!   are kept, so replayed events can be traced back to the journal.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::Utc;
+use mechos_types::MechError;
+use tokio::time::Instant;
+use tracing::{debug, instrument};
+
+use crate::bus::{EventBus, Topic};
+use crate::journal::{JournalEntry, JournalReader};
+
+/// What a [`BusReplayer::replay`] run did.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayStats {
+    /// Entries published onto the bus.
+    pub published: usize,
+    /// Published entries that no subscriber received.
+    pub unheard: usize,
+    /// Entries excluded by the topic filter.
+    pub filtered: usize,
+}
+
+/// Re-publishes recorded journals onto a live [`EventBus`].
+#[derive(Debug, Clone)]
+pub struct BusReplayer {
+    bus: Arc<EventBus>,
+}
+
+impl BusReplayer {
+    /// Speed value that disables pacing: entries are published back-to-back.
+    pub const UNPACED: f64 = f64::INFINITY;
+
+    /// Create a replayer publishing onto `bus`.
+    pub fn new(bus: Arc<EventBus>) -> Self {
+        Self { bus }
+    }
+
+    /// Replay the journal at `journal_path` (a [`BusRecorder`] directory, a
+    /// single segment, or a plain JSONL event log).
+    ///
+    /// `speed` divides the recorded gaps between entries: `1.0` is the
+    /// original pace, `10.0` ten times faster, and [`Self::UNPACED`] (or any
+    /// non-positive value) as fast as possible.  With `topic_filter` set,
+    /// only entries recorded on those topics are replayed; global-channel
+    /// entries are then skipped too.
+    ///
+    /// Resolves once the last entry has been published.
+    ///
+    /// # Errors
+    ///
+    /// - [`MechError::Serialization`] – the journal cannot be read.
+    /// - [`MechError::Parsing`] – a record is not a valid entry.
+    ///
+    /// [`BusRecorder`]: crate::journal::BusRecorder
+    #[instrument(name = "bus_replay.replay", skip_all, fields(speed = speed))]
+    pub async fn replay(
+        &self,
+        journal_path: impl AsRef<Path>,
+        speed: f64,
+        topic_filter: Option<&[Topic]>,
+    ) -> Result<ReplayStats, MechError> {
+        let entries = JournalReader::open(journal_path)?.entries()?;
+        Ok(self.replay_entries(entries, speed, topic_filter).await)
+    }
+
+    /// Replay already-loaded entries; see [`replay`][Self::replay].
+    pub async fn replay_entries(
+        &self,
+        mut entries: Vec<JournalEntry>,
+        speed: f64,
+        topic_filter: Option<&[Topic]>,
+    ) -> ReplayStats {
+        let mut stats = ReplayStats::default();
+        entries.retain(|entry| {
+            let keep = topic_filter
+                .is_none_or(|topics| entry.topic.is_some_and(|topic| topics.contains(&topic)));
+            if !keep {
+                stats.filtered += 1;
+            }
+            keep
+        });
+        // Stable: entries with equal timestamps keep their recorded order.
+        entries.sort_by_key(|entry| entry.event.timestamp);
+        let Some(first) = entries.first().map(|entry| entry.event.timestamp) else {
+            return stats;
+        };
+
+        let paced = speed.is_finite() && speed > 0.0;
+        let start = Instant::now();
+        for JournalEntry { mut event, topic } in entries {
+            if paced {
+                let offset = (event.timestamp - first).to_std().unwrap_or_default();
+                tokio::time::sleep_until(start + offset.div_f64(speed)).await;
+            }
+            event.timestamp = Utc::now();
+            let result = match topic {
+                Some(topic) => self.bus.publish_to(topic, event),
+                None => self.bus.publish(event),
+            };
+            stats.published += 1;
+            if let Err(e) = result {
+                debug!(error = %e, "replayed event was not delivered");
+                stats.unheard += 1;
+            }
+        }
+        stats
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+    use mechos_types::{Event, EventPayload};
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    fn entry(ms: i64, topic: Option<Topic>, text: &str) -> JournalEntry {
+        JournalEntry {
+            event: Event {
+                id: Uuid::new_v4(),
+                timestamp: Utc::now() + TimeDelta::milliseconds(ms),
+                source: "mechos-middleware::dashboard_override".to_string(),
+                payload: EventPayload::Reasoning(text.to_string()),
+                trace_id: None,
+                correlation_id: None,
+            },
+            topic,
+        }
+    }
+
+    #[tokio::test]
+    async fn replays_lanes_with_scaled_timing() {
+        let bus = Arc::new(EventBus::default());
+        let mut global = bus.subscribe();
+        let mut cognitive = bus.subscribe_to(Topic::CognitiveStream);
+        let entries = vec![
+            entry(200, Some(Topic::CognitiveStream), "second"),
+            entry(0, None, "first"),
+            entry(300, Some(Topic::Telemetry), "unheard"),
+        ];
+
+        let started = Instant::now();
+        let stats = BusReplayer::new(Arc::clone(&bus))
+            .replay_entries(entries, 2.0, None)
+            .await;
+        let elapsed = started.elapsed();
+        assert!(elapsed >= Duration::from_millis(149), "{elapsed:?}");
+        assert!(elapsed < Duration::from_millis(300), "{elapsed:?}");
+        assert_eq!(
+            stats,
+            ReplayStats {
+                published: 3,
+                unheard: 1,
+                filtered: 0
+            }
+        );
+
+        let first = global.recv().await.unwrap();
+        assert!(matches!(&first.payload, EventPayload::Reasoning(t) if t == "first"));
+        assert_eq!(first.source, "mechos-middleware::dashboard_override");
+        let second = cognitive.recv().await.unwrap();
+        assert!(matches!(&second.payload, EventPayload::Reasoning(t) if t == "second"));
+    }
+
+    #[tokio::test]
+    async fn topic_filter_skips_other_lanes() {
+        let bus = Arc::new(EventBus::default());
+        let mut telemetry = bus.subscribe_to(Topic::Telemetry);
+        let _global = bus.subscribe();
+        let entries = vec![
+            entry(0, None, "global"),
+            entry(1, Some(Topic::Telemetry), "kept"),
+            entry(2, Some(Topic::SwarmComm), "other"),
+        ];
+
+        let stats = BusReplayer::new(Arc::clone(&bus))
+            .replay_entries(entries, BusReplayer::UNPACED, Some(&[Topic::Telemetry]))
+            .await;
+        assert_eq!(stats.published, 1);
+        assert_eq!(stats.filtered, 2);
+        let event = telemetry.recv().await.unwrap();
+        assert!(matches!(&event.payload, EventPayload::Reasoning(t) if t == "kept"));
+        assert!(
+            (Utc::now() - event.timestamp).num_seconds() < 5,
+            "timestamps are re-stamped"
+        );
+    }
+
+    #[tokio::test]
+    async fn replays_a_plain_jsonl_event_log() {
+        let path = std::env::temp_dir().join(format!("mechos-bus-replay-{}.jsonl", Uuid::new_v4()));
+        let line = serde_json::to_string(&entry(0, None, "logged").event).unwrap();
+        std::fs::write(&path, format!("{line}\n")).unwrap();
+        let bus = Arc::new(EventBus::default());
+        let mut rx = bus.subscribe();
+
+        let stats = BusReplayer::new(Arc::clone(&bus))
+            .replay(&path, BusReplayer::UNPACED, None)
+            .await
+            .unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(stats.published, 1);
+        assert!(matches!(
+            rx.recv().await.unwrap().payload,
+            EventPayload::Reasoning(_)
+        ));
+    }
+}
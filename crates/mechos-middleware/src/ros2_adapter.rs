@@ -8,22 +8,46 @@
 //!   publishes them to `/joint_states`.
 //!
 //! * **Outbound (Drive)** – a [`HardwareIntent::Drive`] is translated into a
-//!   `geometry_msgs/msg/Twist` JSON payload and published to `/cmd_vel`.
+//!   `geometry_msgs/msg/Twist` and published to `/cmd_vel`: as CDR through
+//!   the [`DdsTransport`] when one is installed, otherwise as a JSON payload
+//!   on the bus.
 //!
 //! * **Inbound (Perception)** – an incoming `/scan` laser-scan message is
 //!   converted into a [`EventPayload::Telemetry`] event and streamed into the
-//!   [`EventBus`].
+//!   [`EventBus`].  Native CDR `/scan` and `/odom` samples are accepted by
+//!   [`Ros2Adapter::ingest_scan_cdr`] and [`Ros2Adapter::ingest_odom_cdr`].
+//!   A [`ScanFilter`] downsamples, clips and rate-limits scans before they
+//!   reach the bus.
+//!
+//! * **Inbound (Vision)** – camera images are bounded and downscaled by
+//!   [`camera_frame_payload`] and published as [`EventPayload::CameraFrame`]
+//!   on [`Topic::SensorHighRate`].
+//!
+//! * **Feedback** – with [`Ros2Adapter::with_result_timeout`], goals (MoveIt,
+//!   Nav2 and docking) carry an `id` and `execute_intent` waits for the
+//!   matching rosbridge `action_result` or `service_response`, fed in with
+//!   [`Ros2Adapter::ingest_rosbridge_reply`].  The outcome, or the timeout,
+//!   is published as an [`EventPayload::IntentResult`] with its duration.
 
 use async_trait::async_trait;
 use futures_util::stream::{self, BoxStream};
-use mechos_types::{Event, EventPayload, HardwareIntent, MechError, TelemetryData};
-use serde_json::json;
-use std::sync::Arc;
+use mechos_types::{
+    BASE_FRAME, Event, EventPayload, FaultCode, HardwareIntent, ImageFormat, MechError, Meters,
+    MetersPerSecond, RadiansPerSecond, SCHEMA_VERSION, TelemetryData,
+};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+use tracing::instrument;
 use uuid::Uuid;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 
 use crate::adapter::MechAdapter;
-use crate::bus::EventBus;
+use crate::bus::{EventBus, Topic};
+use crate::camera::camera_frame_payload;
+use crate::dds::{self, DdsTransport};
 
 /// Maximum number of LiDAR range readings accepted in a single scan.
 ///
@@ -37,16 +61,254 @@ pub const MAX_LIDAR_RANGES: usize = 4096;
 /// internal event bus.
 pub const MAX_FLEET_MESSAGE_BYTES: usize = 64 * 1024; // 64 KiB
 
+/// Reduction applied to laser scans before they are published as
+/// [`EventPayload::LidarScan`] events.
+///
+/// A 4096-point scan at sensor rate is far more than the agent loop can
+/// insert into its octree each tick, so deployments usually keep a fraction
+/// of the points and scans.  The default filter passes scans through
+/// unchanged.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScanFilter {
+    /// Keep one reading per group of this many consecutive readings: the
+    /// nearest valid return in the group, so no obstacle is thinned away.
+    /// It is reported at the group's centre bearing, so its bearing is off
+    /// by at most `(downsample − 1) / 2` of the sensor's angle increments.
+    /// `1` disables downsampling.
+    pub downsample: usize,
+    /// Readings nearer than this (metres) are treated as no return.
+    pub min_range_m: f32,
+    /// Readings farther than this (metres) are treated as no return.
+    pub max_range_m: f32,
+    /// Publish at most this many scans per second, dropping the scans that
+    /// arrive in between.  `None` publishes every scan.
+    pub max_rate_hz: Option<f32>,
+}
+
+impl Default for ScanFilter {
+    fn default() -> Self {
+        Self {
+            downsample: 1,
+            min_range_m: 0.0,
+            max_range_m: f32::INFINITY,
+            max_rate_hz: None,
+        }
+    }
+}
+
+impl ScanFilter {
+    /// Downsample and clip `ranges`, returning the new readings, the bearing
+    /// of the first one and the angular step between them.
+    ///
+    /// Each kept reading is placed at the centre of its group, `(step − 1)
+    /// / 2` increments past the group's first bearing, which bounds the
+    /// bearing error to half a group either way.  Readings outside the
+    /// range window, and non-finite ones, become `0.0` ("no return", which
+    /// consumers skip) so the result stays serialisable.
+    pub fn apply(
+        &self,
+        ranges: &[f32],
+        angle_min_rad: f32,
+        angle_increment_rad: f32,
+    ) -> (Vec<f32>, f32, f32) {
+        if *self == Self::default() {
+            return (ranges.to_vec(), angle_min_rad, angle_increment_rad);
+        }
+        let step = self.downsample.max(1);
+        let valid = |r: &f32| r.is_finite() && *r >= self.min_range_m && *r <= self.max_range_m;
+        let ranges = ranges
+            .chunks(step)
+            .map(|group| {
+                group
+                    .iter()
+                    .copied()
+                    .filter(|r| valid(r) && *r > 0.0)
+                    .reduce(f32::min)
+                    .unwrap_or(0.0)
+            })
+            .collect();
+        let centre = angle_min_rad + (step - 1) as f32 / 2.0 * angle_increment_rad;
+        (ranges, centre, angle_increment_rad * step as f32)
+    }
+
+    fn min_interval(&self) -> Option<Duration> {
+        self.max_rate_hz
+            .filter(|hz| *hz > 0.0 && hz.is_finite())
+            .map(|hz| Duration::from_secs_f32(1.0 / hz))
+    }
+}
+
+/// A goal's outcome: whether it succeeded and the robot's status text.
+type GoalReply = (bool, String);
+
 /// Adapter that translates MechOS intents into ROS 2 messages and ingests
 /// physical sensor data from the robot.
 pub struct Ros2Adapter {
     bus: Arc<EventBus>,
+    transport: Option<Arc<dyn DdsTransport>>,
+    /// Latest pose from `/odom`, attached to scans ingested from CDR.
+    last_pose: Mutex<TelemetryData>,
+    scan_filter: ScanFilter,
+    /// When the last `LidarScan` was published, for rate limiting.
+    last_scan_at: Mutex<Option<Instant>>,
+    /// How long a goal may take to report its result; `None` sends goals
+    /// without waiting.
+    result_timeout: Option<Duration>,
+    /// Goals awaiting their result, by goal ID.
+    pending: Mutex<HashMap<String, oneshot::Sender<GoalReply>>>,
 }
 
 impl Ros2Adapter {
     /// Create a new [`Ros2Adapter`] backed by the given [`EventBus`].
     pub fn new(bus: Arc<EventBus>) -> Self {
-        Self { bus }
+        Self {
+            bus,
+            transport: None,
+            last_pose: Mutex::new(TelemetryData {
+                position_x: Meters(0.0),
+                position_y: Meters(0.0),
+                heading_rad: 0.0,
+                battery_percent: 0,
+                stamp: None,
+            }),
+            scan_filter: ScanFilter::default(),
+            last_scan_at: Mutex::new(None),
+            result_timeout: None,
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Publish velocity commands as native `geometry_msgs/msg/Twist`
+    /// samples through `transport` instead of as JSON on the bus.
+    pub fn with_transport(mut self, transport: Arc<dyn DdsTransport>) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Downsample, clip and rate-limit ingested laser scans with `filter`.
+    pub fn with_scan_filter(mut self, filter: ScanFilter) -> Self {
+        self.scan_filter = filter;
+        self
+    }
+
+    /// Wait up to `timeout` for the result of every goal sent, instead of
+    /// returning once it is on the bus.
+    ///
+    /// `execute_intent` then publishes an [`EventPayload::IntentResult`] and
+    /// fails with [`FaultCode::Unknown`] when the robot reports failure or
+    /// no result arrives in time.  Results are fed in with
+    /// [`ingest_rosbridge_reply`][Self::ingest_rosbridge_reply].
+    pub fn with_result_timeout(mut self, timeout: Duration) -> Self {
+        self.result_timeout = Some(timeout);
+        self
+    }
+
+    /// Resolve the pending goal named by a rosbridge `action_result` or
+    /// `service_response` frame.
+    ///
+    /// The frame's `id` is the goal ID, `result` its success and
+    /// `values.message` (or the whole of `values`) its status text.
+    /// Returns `false` for other operations and for IDs no goal is waiting
+    /// on, e.g. after its timeout.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Parsing`] when `frame` is not a JSON object.
+    pub fn ingest_rosbridge_reply(&self, frame: &str) -> Result<bool, MechError> {
+        let frame: Value = serde_json::from_str(frame)
+            .map_err(|e| MechError::Parsing(format!("rosbridge reply: {e}")))?;
+        if !frame.is_object() {
+            return Err(MechError::Parsing("rosbridge reply is not an object".to_string()));
+        }
+        let is_reply = matches!(
+            frame["op"].as_str(),
+            Some("action_result" | "service_response")
+        );
+        let Some(goal_id) = frame["id"].as_str().filter(|_| is_reply) else {
+            return Ok(false);
+        };
+        let success = frame["result"].as_bool().unwrap_or(false);
+        let message = match &frame["values"] {
+            Value::Null => String::new(),
+            values => values["message"]
+                .as_str()
+                .map_or_else(|| values.to_string(), str::to_string),
+        };
+        let waiter = self.pending().remove(goal_id);
+        Ok(waiter.is_some_and(|tx| tx.send((success, message)).is_ok()))
+    }
+
+    fn pending(&self) -> std::sync::MutexGuard<'_, HashMap<String, oneshot::Sender<GoalReply>>> {
+        self.pending.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Publish the goal `frame` for `action`; with a result timeout, tag it
+    /// with a goal ID, await its result and report it as an
+    /// [`EventPayload::IntentResult`].
+    ///
+    /// Runs in a `ros2_adapter.goal` span, so the result event carries the
+    /// trace of the intent that caused it.
+    #[instrument(name = "ros2_adapter.goal", skip(self, source, frame))]
+    async fn send_goal(&self, action: &str, source: String, mut frame: Value) -> Result<(), MechError> {
+        let Some(timeout) = self.result_timeout else {
+            return self.publish_frame(source, frame);
+        };
+        let goal_id = Uuid::new_v4().to_string();
+        frame["id"] = Value::from(goal_id.clone());
+        let (tx, rx) = oneshot::channel();
+        self.pending().insert(goal_id.clone(), tx);
+        let started = Instant::now();
+        if let Err(e) = self.publish_frame(source, frame) {
+            self.pending().remove(&goal_id);
+            return Err(e);
+        }
+
+        let reply = tokio::time::timeout(timeout, rx).await;
+        self.pending().remove(&goal_id);
+        let (success, message) = match reply {
+            Ok(Ok(reply)) => reply,
+            Ok(Err(_)) => (false, "goal dropped".to_string()),
+            Err(_) => (false, format!("no result within {timeout:?}")),
+        };
+        // Nobody listening for results is not a failure of the goal.
+        let _ = self.bus.publish(Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: format!("mechos-middleware::ros2/{action}/result"),
+            payload: EventPayload::IntentResult {
+                goal_id,
+                action: action.to_string(),
+                success,
+                duration_ms: started.elapsed().as_millis().try_into().unwrap_or(u64::MAX),
+                message: message.clone(),
+            },
+            trace_id: None,
+            correlation_id: None,
+        });
+        if success {
+            Ok(())
+        } else {
+            Err(MechError::HardwareFault {
+                code: FaultCode::Unknown,
+                component: action.to_string(),
+                details: message,
+            })
+        }
+    }
+
+    /// Publish a rosbridge `frame` on the bus as an
+    /// [`EventPayload::AdapterCommand`].
+    fn publish_frame(&self, source: String, frame: Value) -> Result<(), MechError> {
+        self.bus
+            .publish(Event {
+                id: Uuid::new_v4(),
+                timestamp: Utc::now(),
+                source,
+                payload: EventPayload::adapter_command(frame),
+                trace_id: None,
+                correlation_id: None,
+            })
+            .map(|_| ())
     }
 
     /// Ingest a `/scan` laser-scan message, publish it as a
@@ -58,16 +320,40 @@ impl Ros2Adapter {
     /// LiDAR.  `angle_min_rad` is the bearing of the first range reading
     /// (radians, in the robot frame) and `angle_increment_rad` is the angular
     /// step between consecutive readings.
+    ///
+    /// The `LidarScan` event carries the ranges after the adapter's
+    /// [`ScanFilter`].  When the filter's rate limit drops the scan, only the
+    /// telemetry is published and `Ok(0)` is returned.
     #[allow(clippy::too_many_arguments)]
     pub fn ingest_laser_scan(
         &self,
         ranges: &[f32],
         angle_min_rad: f32,
         angle_increment_rad: f32,
-        position_x: f32,
-        position_y: f32,
+        position_x: Meters,
+        position_y: Meters,
         heading_rad: f32,
         battery_percent: u8,
+    ) -> Result<usize, MechError> {
+        let pose = TelemetryData {
+            position_x,
+            position_y,
+            heading_rad,
+            battery_percent,
+            stamp: None,
+        };
+        self.publish_scan(ranges, angle_min_rad, angle_increment_rad, pose, None)
+    }
+
+    /// Publish `pose` and the filtered scan, stamped with its capture time
+    /// when known.
+    fn publish_scan(
+        &self,
+        ranges: &[f32],
+        angle_min_rad: f32,
+        angle_increment_rad: f32,
+        pose: TelemetryData,
+        stamp: Option<DateTime<Utc>>,
     ) -> Result<usize, MechError> {
         // ── Input validation ───────────────────────────────────────────────
         if ranges.len() > MAX_LIDAR_RANGES {
@@ -81,30 +367,128 @@ impl Ros2Adapter {
             id: Uuid::new_v4(),
             timestamp: Utc::now(),
             source: "mechos-middleware::ros2/scan".to_string(),
-            payload: EventPayload::Telemetry(TelemetryData {
-                position_x,
-                position_y,
-                heading_rad,
-                battery_percent,
-            }),
+            payload: EventPayload::Telemetry(pose),
             trace_id: None,
+            correlation_id: None,
         };
         self.bus.publish(telemetry_event)?;
 
+        if let Some(min_interval) = self.scan_filter.min_interval() {
+            let mut last = self.last_scan_at.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            if last.is_some_and(|at| now.duration_since(at) < min_interval) {
+                return Ok(0);
+            }
+            *last = Some(now);
+        }
+        let (ranges, angle_min_rad, angle_increment_rad) =
+            self.scan_filter.apply(ranges, angle_min_rad, angle_increment_rad);
         let lidar_event = Event {
             id: Uuid::new_v4(),
             timestamp: Utc::now(),
             source: "mechos-middleware::ros2/scan".to_string(),
             payload: EventPayload::LidarScan {
-                ranges: ranges.to_vec(),
+                ranges,
                 angle_min_rad,
                 angle_increment_rad,
+                stamp,
             },
             trace_id: None,
+            correlation_id: None,
         };
         self.bus.publish(lidar_event)
     }
 
+    /// Ingest a CDR-encoded `sensor_msgs/msg/LaserScan` from `/scan`.
+    ///
+    /// The scan is published like [`ingest_laser_scan`][Self::ingest_laser_scan],
+    /// tagged with the latest pose received by
+    /// [`ingest_odom_cdr`][Self::ingest_odom_cdr], and carries its header
+    /// stamp so consumers can look up the pose at capture time.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Parsing`] for malformed or oversized scans.
+    pub fn ingest_scan_cdr(&self, cdr: &[u8]) -> Result<usize, MechError> {
+        let scan = dds::decode_laser_scan(cdr)?;
+        let pose = self
+            .last_pose
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        self.publish_scan(
+            &scan.ranges,
+            scan.angle_min,
+            scan.angle_increment,
+            pose,
+            scan.stamp,
+        )
+    }
+
+    /// Ingest a CDR-encoded `nav_msgs/msg/Odometry` from `/odom`, remember
+    /// the pose for later scans and publish it as an
+    /// [`EventPayload::Telemetry`] event stamped with the message's header
+    /// time.
+    ///
+    /// Odometry carries no charge level, so `battery_percent` is `0`;
+    /// battery state arrives separately as [`EventPayload::PowerStatus`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Parsing`] for malformed messages.
+    pub fn ingest_odom_cdr(&self, cdr: &[u8]) -> Result<usize, MechError> {
+        let odom = dds::decode_odometry(cdr)?;
+        let telemetry = TelemetryData {
+            position_x: Meters(odom.position_x as f32),
+            position_y: Meters(odom.position_y as f32),
+            heading_rad: odom.yaw_rad as f32,
+            battery_percent: 0,
+            stamp: odom.stamp,
+        };
+        *self.last_pose.lock().unwrap_or_else(|e| e.into_inner()) = telemetry.clone();
+        self.bus.publish(Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: "mechos-middleware::ros2/odom".to_string(),
+            payload: EventPayload::Telemetry(telemetry),
+            trace_id: None,
+            correlation_id: None,
+        })
+    }
+
+    /// Ingest a camera image (e.g. from `/camera/image_raw` or
+    /// `/camera/image_raw/compressed`) and publish it as an
+    /// [`EventPayload::CameraFrame`] on [`Topic::SensorHighRate`].
+    ///
+    /// Raw frames above the [`camera`][crate::camera] limits are downscaled;
+    /// the frame is also sent on the global channel so the Cockpit server
+    /// and agent loops, which subscribe there, receive it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Parsing`] for malformed frames and compressed
+    /// frames that exceed the limits.
+    pub fn ingest_camera_frame(
+        &self,
+        image_id: &str,
+        format: ImageFormat,
+        width: u32,
+        height: u32,
+        data: &[u8],
+    ) -> Result<usize, MechError> {
+        let event = Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: "mechos-middleware::ros2/camera".to_string(),
+            payload: camera_frame_payload(image_id, format, width, height, data)?,
+            trace_id: None,
+            correlation_id: None,
+        };
+        // No sensor-lane subscriber is not an error for a sensor stream.
+        let on_lane = self.bus.publish_to(Topic::SensorHighRate, event.clone()).unwrap_or(0);
+        Ok(on_lane + self.bus.publish(event)?)
+    }
+
     /// Ingest a fleet broadcast message arriving on `/fleet/communications` and
     /// publish it as a [`EventPayload::PeerMessage`] event on the internal bus.
     ///
@@ -133,53 +517,115 @@ impl Ros2Adapter {
                 message: message.to_string(),
             },
             trace_id: None,
+            correlation_id: None,
         };
         self.bus.publish(event)
     }
+
+    /// Publish a single-point `trajectory_msgs/msg/JointTrajectory` frame
+    /// moving `joint` to `position` on `/joint_trajectory`.
+    fn publish_joint_trajectory(
+        &self,
+        joint: &str,
+        position: f32,
+        max_velocity: Option<RadiansPerSecond>,
+    ) -> Result<(), MechError> {
+        let trajectory = json!({
+            "op": "publish",
+            "topic": "/joint_trajectory",
+            "msg": {
+                "joint_names": [joint],
+                "points": [{ "positions": [position], "velocities": [0.0] }],
+                "max_velocity": max_velocity
+            }
+        });
+        let event = Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: "mechos-middleware::ros2/joint_trajectory".to_string(),
+            payload: EventPayload::adapter_command(trajectory),
+            trace_id: None,
+            correlation_id: None,
+        };
+        self.bus.publish(event).map(|_| ())
+    }
 }
 
 #[async_trait]
 impl MechAdapter for Ros2Adapter {
     /// Translate a [`HardwareIntent`] into a ROS 2 command.
     ///
-    /// * `MoveEndEffector` – serialises the target coordinates as a MoveIt 2
-    ///   goal JSON and logs it (in a real deployment this would be sent over
-    ///   `ros2_bridge` to `/move_group/goal`).
+    /// * `MoveEndEffector` – serialises the target coordinates and frame as a
+    ///   MoveIt 2 goal JSON and logs it (in a real deployment this would be
+    ///   sent over `ros2_bridge` to `/move_group/goal`).
     ///
-    /// * `Drive` – serialises a `geometry_msgs/msg/Twist` JSON payload for
-    ///   `/cmd_vel`.
+    /// * `Drive` – publishes a `geometry_msgs/msg/Twist` on `/cmd_vel`
+    ///   through the [`DdsTransport`], or serialises it as a JSON payload on
+    ///   the bus when no transport is installed.
     ///
     /// * `TriggerRelay` – serialises a relay command for the appropriate GPIO
     ///   topic.
     ///
-    /// * `AskHuman` – publishes an [`EventPayload::AgentThought`] onto the bus
+    /// * `AskHuman` – publishes an [`EventPayload::Reasoning`] onto the bus
     ///   so the dashboard can display the question.
+    ///
+    /// * `Speak` – serialises the text (and optional voice) for the `/tts`
+    ///   text-to-speech node.
+    ///
+    /// * `PlaySound` – serialises the clip identifier for `/sound`.
+    ///
+    /// * `FollowWaypoints` – serialises a Nav2 `NavigateThroughPoses` goal
+    ///   (one `geometry_msgs/msg/PoseStamped` per waypoint, in the `map`
+    ///   frame) for `/navigate_through_poses/goal`.
+    ///
+    /// * `MoveJoint` / `SetGripper` – serialise a single-point
+    ///   `trajectory_msgs/msg/JointTrajectory` for `/joint_trajectory`; the
+    ///   gripper is addressed as the `"gripper"` joint.
+    ///
+    /// * `Halt` – publishes a zero `Twist` on `/cmd_vel` (through the
+    ///   transport when installed), then an empty
+    ///   `JointTrajectory` on `/joint_trajectory`, which makes the trajectory
+    ///   controller abandon its current goal.
+    ///
+    /// * `Dock` / `Undock` – serialise an `opennav_docking` `DockRobot` /
+    ///   `UndockRobot` goal for `/dock_robot/goal` / `/undock_robot/goal`.
+    ///
+    /// With [`with_result_timeout`][Ros2Adapter::with_result_timeout], the
+    /// MoveIt, Nav2 and docking goals return only once their result arrives
+    /// (or the timeout passes), and fail if the goal did; the other intents
+    /// are plain topic publishes and return at once.
+    ///
+    /// * `Arm` / `SetAltitude` / `Goto` – rejected with
+    ///   [`FaultCode::Unsupported`]; flight controllers are driven by the
+    ///   [`MavlinkAdapter`][crate::MavlinkAdapter].
     async fn execute_intent(&self, intent: HardwareIntent) -> Result<(), MechError> {
         match &intent {
-            HardwareIntent::MoveEndEffector { x, y, z } => {
+            HardwareIntent::MoveEndEffector { x, y, z, frame_id } => {
                 // Hand coordinates to MoveIt 2: compute IK then publish to /joint_states.
+                let frame_id = frame_id.as_deref().unwrap_or(BASE_FRAME);
                 let moveit_goal = json!({
                     "op": "publish",
                     "topic": "/move_group/goal",
                     "msg": {
-                        "target_pose": { "x": x, "y": y, "z": z }
+                        "target_pose": {
+                            "header": { "frame_id": frame_id },
+                            "x": x, "y": y, "z": z
+                        }
                     }
                 });
                 // In production this is forwarded to ros2_bridge; here we publish
-                // it as an AgentThought so the rest of the system can observe it.
-                let event = Event {
-                    id: Uuid::new_v4(),
-                    timestamp: Utc::now(),
-                    source: "mechos-middleware::ros2/joint_states".to_string(),
-                    payload: EventPayload::AgentThought(moveit_goal.to_string()),
-                    trace_id: None,
-                };
-                self.bus.publish(event).map(|_| ())
+                // it as an AdapterCommand so the rest of the system can observe it.
+                let source = "mechos-middleware::ros2/joint_states".to_string();
+                self.send_goal("move_group", source, moveit_goal).await
             }
             HardwareIntent::Drive {
                 linear_velocity,
                 angular_velocity,
             } => {
+                if let Some(transport) = &self.transport {
+                    let cdr = dds::encode_twist(*linear_velocity, *angular_velocity);
+                    return transport.publish("/cmd_vel", cdr).await;
+                }
                 let twist = json!({
                     "op": "publish",
                     "topic": "/cmd_vel",
@@ -192,8 +638,9 @@ impl MechAdapter for Ros2Adapter {
                     id: Uuid::new_v4(),
                     timestamp: Utc::now(),
                     source: "mechos-middleware::ros2/cmd_vel".to_string(),
-                    payload: EventPayload::AgentThought(twist.to_string()),
+                    payload: EventPayload::adapter_command(twist),
                     trace_id: None,
+                    correlation_id: None,
                 };
                 self.bus.publish(event).map(|_| ())
             }
@@ -207,8 +654,9 @@ impl MechAdapter for Ros2Adapter {
                     id: Uuid::new_v4(),
                     timestamp: Utc::now(),
                     source: format!("mechos-middleware::ros2/relay/{relay_id}"),
-                    payload: EventPayload::AgentThought(relay_msg.to_string()),
+                    payload: EventPayload::adapter_command(relay_msg),
                     trace_id: None,
+                    correlation_id: None,
                 };
                 self.bus.publish(event).map(|_| ())
             }
@@ -217,8 +665,9 @@ impl MechAdapter for Ros2Adapter {
                     id: Uuid::new_v4(),
                     timestamp: Utc::now(),
                     source: "mechos-middleware::ros2/ask_human".to_string(),
-                    payload: EventPayload::AgentThought(question.clone()),
+                    payload: EventPayload::Reasoning(question.clone()),
                     trace_id: None,
+                    correlation_id: None,
                 };
                 self.bus.publish(event).map(|_| ())
             }
@@ -239,8 +688,9 @@ impl MechAdapter for Ros2Adapter {
                     source: format!(
                         "mechos-middleware::ros2/fleet/robot/{target_robot_id}/inbox"
                     ),
-                    payload: EventPayload::AgentThought(peer_msg.to_string()),
+                    payload: EventPayload::adapter_command(peer_msg),
                     trace_id: None,
+                    correlation_id: None,
                 };
                 self.bus.publish(event).map(|_| ())
             }
@@ -255,19 +705,22 @@ impl MechAdapter for Ros2Adapter {
                     id: Uuid::new_v4(),
                     timestamp: Utc::now(),
                     source: "mechos-middleware::ros2/fleet/communications".to_string(),
-                    payload: EventPayload::AgentThought(broadcast_msg.to_string()),
+                    payload: EventPayload::adapter_command(broadcast_msg),
                     trace_id: None,
+                    correlation_id: None,
                 };
                 self.bus.publish(event).map(|_| ())
             }
             HardwareIntent::PostTask { title, description } => {
                 // Publish the task intent to the fleet task topic so remote
-                // robots (and the task board consumer) can process it.
+                // robots (and the task board consumer) can process it.  The
+                // schema version lets peers on other releases read the task.
                 let task_msg = json!({
                     "op": "publish",
                     "topic": "/fleet/tasks",
                     "msg": {
                         "data": serde_json::to_string(&json!({
+                            "schema_version": SCHEMA_VERSION,
                             "title": title,
                             "description": description
                         })).unwrap_or_default()
@@ -277,11 +730,129 @@ impl MechAdapter for Ros2Adapter {
                     id: Uuid::new_v4(),
                     timestamp: Utc::now(),
                     source: "mechos-middleware::ros2/fleet/tasks".to_string(),
-                    payload: EventPayload::AgentThought(task_msg.to_string()),
+                    payload: EventPayload::adapter_command(task_msg),
                     trace_id: None,
+                    correlation_id: None,
                 };
                 self.bus.publish(event).map(|_| ())
             }
+            HardwareIntent::Speak { text, voice } => {
+                let tts_msg = json!({
+                    "op": "publish",
+                    "topic": "/tts",
+                    "msg": { "data": text, "voice": voice }
+                });
+                let event = Event {
+                    id: Uuid::new_v4(),
+                    timestamp: Utc::now(),
+                    source: "mechos-middleware::ros2/tts".to_string(),
+                    payload: EventPayload::adapter_command(tts_msg),
+                    trace_id: None,
+                    correlation_id: None,
+                };
+                self.bus.publish(event).map(|_| ())
+            }
+            HardwareIntent::PlaySound { sound_id } => {
+                let sound_msg = json!({
+                    "op": "publish",
+                    "topic": "/sound",
+                    "msg": { "data": sound_id }
+                });
+                let event = Event {
+                    id: Uuid::new_v4(),
+                    timestamp: Utc::now(),
+                    source: "mechos-middleware::ros2/sound".to_string(),
+                    payload: EventPayload::adapter_command(sound_msg),
+                    trace_id: None,
+                    correlation_id: None,
+                };
+                self.bus.publish(event).map(|_| ())
+            }
+            HardwareIntent::FollowWaypoints { points, max_speed } => {
+                let poses: Vec<_> = points
+                    .iter()
+                    .map(|(x, y)| {
+                        json!({
+                            "header": { "frame_id": "map" },
+                            "pose": {
+                                "position": { "x": x, "y": y, "z": 0.0 },
+                                "orientation": { "x": 0.0, "y": 0.0, "z": 0.0, "w": 1.0 }
+                            }
+                        })
+                    })
+                    .collect();
+                let nav_goal = json!({
+                    "op": "publish",
+                    "topic": "/navigate_through_poses/goal",
+                    "msg": { "poses": poses, "max_speed": max_speed }
+                });
+                let source = "mechos-middleware::ros2/navigate_through_poses".to_string();
+                self.send_goal("navigate_through_poses", source, nav_goal).await
+            }
+            HardwareIntent::MoveJoint {
+                joint,
+                angle_rad,
+                max_velocity,
+            } => self.publish_joint_trajectory(joint, *angle_rad, Some(*max_velocity)),
+            HardwareIntent::SetGripper { position } => {
+                self.publish_joint_trajectory("gripper", *position, None)
+            }
+            HardwareIntent::Dock | HardwareIntent::Undock => {
+                let (action, msg) = match intent {
+                    HardwareIntent::Dock => {
+                        ("dock_robot", json!({ "navigate_to_staging_pose": true }))
+                    }
+                    _ => ("undock_robot", json!({})),
+                };
+                let goal = json!({
+                    "op": "publish",
+                    "topic": format!("/{action}/goal"),
+                    "msg": msg
+                });
+                self.send_goal(action, format!("mechos-middleware::ros2/{action}"), goal)
+                    .await
+            }
+            HardwareIntent::Arm { .. }
+            | HardwareIntent::SetAltitude { .. }
+            | HardwareIntent::Goto { .. } => Err(MechError::HardwareFault {
+                code: FaultCode::Unsupported,
+                component: "flight_controller".to_string(),
+                details: "flight intents are executed by the MAVLink adapter".to_string(),
+            }),
+            HardwareIntent::Halt { .. } => {
+                let cancel = json!({
+                    "op": "publish",
+                    "topic": "/joint_trajectory",
+                    "msg": { "joint_names": [], "points": [] }
+                });
+                let mut frames = vec![("joint_trajectory", cancel)];
+                if let Some(transport) = &self.transport {
+                    let cdr = dds::encode_twist(MetersPerSecond::ZERO, RadiansPerSecond::ZERO);
+                    transport.publish("/cmd_vel", cdr).await?;
+                } else {
+                    let stop = json!({
+                        "op": "publish",
+                        "topic": "/cmd_vel",
+                        "msg": {
+                            "linear":  { "x": 0.0, "y": 0.0, "z": 0.0 },
+                            "angular": { "x": 0.0, "y": 0.0, "z": 0.0 }
+                        }
+                    });
+                    frames.insert(0, ("cmd_vel", stop));
+                }
+                for (topic, frame) in frames {
+                    let event = Event {
+                        id: Uuid::new_v4(),
+                        timestamp: Utc::now(),
+                        source: format!("mechos-middleware::ros2/{topic}"),
+                        payload: EventPayload::adapter_command(frame),
+                        trace_id: None,
+                        correlation_id: None,
+                    };
+                    self.bus.publish(event)?;
+                }
+                Ok(())
+            }
         }
     }
 
@@ -291,7 +862,8 @@ impl MechAdapter for Ros2Adapter {
     /// `ros2_bridge` and yield events continuously.  This implementation
     /// returns an empty stream as a correct skeleton; callers that need live
     /// data should use [`ingest_laser_scan`][Self::ingest_laser_scan] to push
-    /// frames directly onto the bus.
+    /// frames directly onto the bus, or forward native samples with
+    /// `ZenohTransport::forward_sensors` (`zenoh` feature).
     async fn sensor_stream(&self) -> BoxStream<'static, EventPayload> {
         Box::pin(stream::empty())
     }
@@ -300,7 +872,7 @@ impl MechAdapter for Ros2Adapter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use mechos_types::EventPayload;
+    use mechos_types::{EventPayload, IntentEnvelope, MetersPerSecond};
 
     fn make_adapter() -> (Arc<EventBus>, Ros2Adapter) {
         let bus = Arc::new(EventBus::default());
@@ -308,6 +880,18 @@ mod tests {
         (bus, adapter)
     }
 
+    /// Records every sample instead of sending it to a DDS graph.
+    #[derive(Default)]
+    struct RecordingTransport(Mutex<Vec<(String, Vec<u8>)>>);
+
+    #[async_trait]
+    impl DdsTransport for RecordingTransport {
+        async fn publish(&self, topic: &str, cdr: Vec<u8>) -> Result<(), MechError> {
+            self.0.lock().unwrap().push((topic.to_string(), cdr));
+            Ok(())
+        }
+    }
+
     #[tokio::test]
     async fn ingest_laser_scan_rejects_oversized_ranges() {
         let (_, adapter) = make_adapter();
@@ -316,8 +900,8 @@ mod tests {
             &oversized_ranges,
             0.0,
             0.1,
-            0.0,
-            0.0,
+            Meters(0.0),
+            Meters(0.0),
             0.0,
             100,
         );
@@ -345,18 +929,21 @@ mod tests {
 
         adapter
             .execute_intent(HardwareIntent::Drive {
-                linear_velocity: 1.0,
-                angular_velocity: 0.5,
+                linear_velocity: MetersPerSecond(1.0),
+                angular_velocity: RadiansPerSecond(0.5),
             })
             .await
             .unwrap();
 
         let event = rx.recv().await.unwrap();
         assert_eq!(event.source, "mechos-middleware::ros2/cmd_vel");
-        assert!(matches!(event.payload, EventPayload::AgentThought(_)));
-        if let EventPayload::AgentThought(json_str) = event.payload {
-            assert!(json_str.contains("/cmd_vel"));
-            assert!(json_str.contains("linear"));
+        assert!(matches!(
+            &event.payload,
+            EventPayload::AdapterCommand { topic, .. } if topic == "/cmd_vel"
+        ));
+        if let EventPayload::AdapterCommand { json: frame, .. } = event.payload {
+            assert!(frame.to_string().contains("/cmd_vel"));
+            assert!(frame.to_string().contains("linear"));
         }
     }
 
@@ -367,17 +954,19 @@ mod tests {
 
         adapter
             .execute_intent(HardwareIntent::MoveEndEffector {
-                x: 1.5,
-                y: 0.0,
-                z: 0.2,
+                x: Meters(1.5),
+                y: Meters(0.0),
+                z: Meters(0.2),
+                frame_id: None,
             })
             .await
             .unwrap();
 
         let event = rx.recv().await.unwrap();
         assert_eq!(event.source, "mechos-middleware::ros2/joint_states");
-        if let EventPayload::AgentThought(json_str) = event.payload {
-            assert!(json_str.contains("target_pose"));
+        if let EventPayload::AdapterCommand { json: frame, .. } = event.payload {
+            assert!(frame.to_string().contains("target_pose"));
+            assert!(frame.to_string().contains(r#""frame_id":"base_link""#));
         }
     }
 
@@ -391,8 +980,8 @@ mod tests {
                 &[1.0, 2.0, 3.0],
                 -std::f32::consts::FRAC_PI_2,
                 0.1,
-                0.0,
-                0.0,
+                Meters(0.0),
+                Meters(0.0),
                 0.0,
                 100,
             )
@@ -412,13 +1001,14 @@ mod tests {
             .execute_intent(HardwareIntent::AskHuman {
                 question: "Which shelf?".to_string(),
                 context_image_id: None,
+                question_id: None,
             })
             .await
             .unwrap();
 
         let event = rx.recv().await.unwrap();
         assert_eq!(event.source, "mechos-middleware::ros2/ask_human");
-        if let EventPayload::AgentThought(q) = event.payload {
+        if let EventPayload::Reasoning(q) = event.payload {
             assert_eq!(q, "Which shelf?");
         }
     }
@@ -440,9 +1030,9 @@ mod tests {
             event.source,
             "mechos-middleware::ros2/fleet/communications"
         );
-        if let EventPayload::AgentThought(json_str) = event.payload {
-            assert!(json_str.contains("/fleet/communications"));
-            assert!(json_str.contains("Kitchen Door"));
+        if let EventPayload::AdapterCommand { json: frame, .. } = event.payload {
+            assert!(frame.to_string().contains("/fleet/communications"));
+            assert!(frame.to_string().contains("Kitchen Door"));
         }
     }
 
@@ -463,8 +1053,8 @@ mod tests {
         assert!(event
             .source
             .contains("mechos-middleware::ros2/fleet/robot/robot_bravo"));
-        if let EventPayload::AgentThought(json_str) = event.payload {
-            assert!(json_str.contains("robot_bravo"));
+        if let EventPayload::AdapterCommand { json: frame, .. } = event.payload {
+            assert!(frame.to_string().contains("robot_bravo"));
         }
     }
 
@@ -483,12 +1073,296 @@ mod tests {
 
         let event = rx.recv().await.unwrap();
         assert_eq!(event.source, "mechos-middleware::ros2/fleet/tasks");
-        if let EventPayload::AgentThought(json_str) = event.payload {
-            assert!(json_str.contains("/fleet/tasks"));
-            assert!(json_str.contains("Move Box 1"));
+        let EventPayload::AdapterCommand { json: frame, .. } = event.payload else {
+            panic!("expected AdapterCommand");
+        };
+        assert!(frame.to_string().contains("/fleet/tasks"));
+        assert!(frame.to_string().contains("Move Box 1"));
+        let task: serde_json::Value =
+            serde_json::from_str(frame["msg"]["data"].as_str().unwrap()).unwrap();
+        assert_eq!(task["schema_version"], SCHEMA_VERSION);
+    }
+
+    #[tokio::test]
+    async fn execute_speak_publishes_to_tts() {
+        let (bus, adapter) = make_adapter();
+        let mut rx = bus.subscribe();
+
+        adapter
+            .execute_intent(HardwareIntent::Speak {
+                text: "Excuse me, coming through.".to_string(),
+                voice: Some("en-GB".to_string()),
+            })
+            .await
+            .unwrap();
+
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.source, "mechos-middleware::ros2/tts");
+        let EventPayload::AdapterCommand { json: frame, .. } = event.payload else {
+            panic!("expected AdapterCommand");
+        };
+        assert_eq!(frame["topic"], "/tts");
+        assert_eq!(frame["msg"]["data"], "Excuse me, coming through.");
+        assert_eq!(frame["msg"]["voice"], "en-GB");
+    }
+
+    #[tokio::test]
+    async fn execute_play_sound_publishes_to_sound() {
+        let (bus, adapter) = make_adapter();
+        let mut rx = bus.subscribe();
+
+        adapter
+            .execute_intent(HardwareIntent::PlaySound {
+                sound_id: "chime".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.source, "mechos-middleware::ros2/sound");
+        if let EventPayload::AdapterCommand { json: frame, .. } = event.payload {
+            assert!(frame.to_string().contains("/sound"));
+            assert!(frame.to_string().contains("chime"));
         }
     }
 
+    #[tokio::test]
+    async fn execute_follow_waypoints_publishes_nav_goal() {
+        let (bus, adapter) = make_adapter();
+        let mut rx = bus.subscribe();
+
+        adapter
+            .execute_intent(HardwareIntent::FollowWaypoints {
+                points: vec![(Meters(1.0), Meters(2.0)), (Meters(3.0), Meters(4.0))],
+                max_speed: MetersPerSecond(0.5),
+            })
+            .await
+            .unwrap();
+
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.source, "mechos-middleware::ros2/navigate_through_poses");
+        let EventPayload::AdapterCommand { json: frame, .. } = event.payload else {
+            panic!("expected AdapterCommand");
+        };
+        assert_eq!(frame["topic"], "/navigate_through_poses/goal");
+        let poses = frame["msg"]["poses"].as_array().unwrap();
+        assert_eq!(poses.len(), 2);
+        assert_eq!(poses[1]["pose"]["position"]["x"], 3.0);
+        assert_eq!(poses[1]["pose"]["position"]["y"], 4.0);
+        assert_eq!(frame["msg"]["max_speed"], 0.5);
+    }
+
+    #[tokio::test]
+    async fn execute_move_joint_publishes_joint_trajectory() {
+        let (bus, adapter) = make_adapter();
+        let mut rx = bus.subscribe();
+
+        adapter
+            .execute_intent(HardwareIntent::MoveJoint {
+                joint: "elbow".to_string(),
+                angle_rad: 1.25,
+                max_velocity: RadiansPerSecond(0.5),
+            })
+            .await
+            .unwrap();
+
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.source, "mechos-middleware::ros2/joint_trajectory");
+        let EventPayload::AdapterCommand { json: frame, .. } = event.payload else {
+            panic!("expected AdapterCommand");
+        };
+        assert_eq!(frame["topic"], "/joint_trajectory");
+        assert_eq!(frame["msg"]["joint_names"][0], "elbow");
+        assert_eq!(frame["msg"]["points"][0]["positions"][0], 1.25);
+        assert_eq!(frame["msg"]["max_velocity"], 0.5);
+    }
+
+    #[tokio::test]
+    async fn execute_set_gripper_publishes_gripper_joint() {
+        let (bus, adapter) = make_adapter();
+        let mut rx = bus.subscribe();
+
+        adapter
+            .execute_intent(HardwareIntent::SetGripper { position: 1.0 })
+            .await
+            .unwrap();
+
+        let event = rx.recv().await.unwrap();
+        let EventPayload::AdapterCommand { json: frame, .. } = event.payload else {
+            panic!("expected AdapterCommand");
+        };
+        assert_eq!(frame["topic"], "/joint_trajectory");
+        assert_eq!(frame["msg"]["joint_names"][0], "gripper");
+        assert_eq!(frame["msg"]["points"][0]["positions"][0], 1.0);
+    }
+
+    #[tokio::test]
+    async fn execute_dock_and_undock_publish_docking_goals() {
+        let (bus, adapter) = make_adapter();
+        let mut rx = bus.subscribe();
+
+        adapter.execute_intent(HardwareIntent::Dock).await.unwrap();
+        adapter
+            .execute_intent(HardwareIntent::Undock)
+            .await
+            .unwrap();
+
+        for (source, topic) in [
+            ("mechos-middleware::ros2/dock_robot", "/dock_robot/goal"),
+            ("mechos-middleware::ros2/undock_robot", "/undock_robot/goal"),
+        ] {
+            let event = rx.recv().await.unwrap();
+            assert_eq!(event.source, source);
+            let EventPayload::AdapterCommand { json: frame, .. } = event.payload else {
+                panic!("expected AdapterCommand");
+            };
+            assert_eq!(frame["topic"], topic);
+        }
+    }
+
+    #[tokio::test]
+    async fn goal_waits_for_its_result_and_reports_it() {
+        let bus = Arc::new(EventBus::default());
+        let adapter = Arc::new(
+            Ros2Adapter::new(Arc::clone(&bus)).with_result_timeout(Duration::from_secs(5)),
+        );
+        let mut rx = bus.subscribe();
+
+        let running = Arc::clone(&adapter);
+        let goal = tokio::spawn(async move {
+            running
+                .execute_intent(HardwareIntent::FollowWaypoints {
+                    points: vec![(Meters(1.0), Meters(2.0))],
+                    max_speed: MetersPerSecond(0.5),
+                })
+                .await
+        });
+        let EventPayload::AdapterCommand { json: frame, .. } = rx.recv().await.unwrap().payload else {
+            panic!("expected AdapterCommand");
+        };
+        let goal_id = frame["id"].as_str().unwrap().to_string();
+        assert!(!goal.is_finished(), "the goal waits for its result");
+
+        let unrelated = json!({ "op": "action_result", "id": "other", "result": true });
+        assert!(!adapter.ingest_rosbridge_reply(&unrelated.to_string()).unwrap());
+        let reply = json!({
+            "op": "action_result",
+            "id": goal_id,
+            "result": true,
+            "values": { "message": "reached" }
+        });
+        assert!(adapter.ingest_rosbridge_reply(&reply.to_string()).unwrap());
+        goal.await.unwrap().unwrap();
+
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.source, "mechos-middleware::ros2/navigate_through_poses/result");
+        let EventPayload::IntentResult {
+            goal_id: reported,
+            action,
+            success,
+            message,
+            ..
+        } = event.payload
+        else {
+            panic!("expected IntentResult");
+        };
+        assert_eq!(reported, goal_id);
+        assert_eq!(action, "navigate_through_poses");
+        assert!(success);
+        assert_eq!(message, "reached");
+        assert!(adapter.ingest_rosbridge_reply("not json").is_err());
+    }
+
+    #[tokio::test]
+    async fn failed_or_silent_goals_are_errors() {
+        let bus = Arc::new(EventBus::default());
+        let adapter = Arc::new(
+            Ros2Adapter::new(Arc::clone(&bus)).with_result_timeout(Duration::from_millis(50)),
+        );
+        let mut rx = bus.subscribe();
+
+        let running = Arc::clone(&adapter);
+        let dock = tokio::spawn(async move { running.execute_intent(HardwareIntent::Dock).await });
+        let EventPayload::AdapterCommand { json: frame, .. } = rx.recv().await.unwrap().payload else {
+            panic!("expected AdapterCommand");
+        };
+        let reply = json!({
+            "op": "service_response",
+            "id": frame["id"],
+            "result": false,
+            "values": { "message": "no dock in range" }
+        });
+        adapter.ingest_rosbridge_reply(&reply.to_string()).unwrap();
+        assert!(matches!(
+            dock.await.unwrap(),
+            Err(MechError::HardwareFault { ref component, ref details, .. })
+                if component == "dock_robot" && details == "no dock in range"
+        ));
+        assert!(matches!(
+            rx.recv().await.unwrap().payload,
+            EventPayload::IntentResult { success: false, .. }
+        ));
+
+        let silent = adapter.execute_intent(HardwareIntent::Undock).await;
+        assert!(matches!(
+            silent,
+            Err(MechError::HardwareFault { ref details, .. }) if details.contains("no result")
+        ));
+        let _goal = rx.recv().await.unwrap();
+        let EventPayload::IntentResult { duration_ms, .. } = rx.recv().await.unwrap().payload else {
+            panic!("expected IntentResult");
+        };
+        assert!(duration_ms >= 50);
+        assert!(adapter.pending().is_empty());
+    }
+
+    #[tokio::test]
+    async fn execute_halt_publishes_zero_twist_and_controller_stop() {
+        let (bus, adapter) = make_adapter();
+        let mut rx = bus.subscribe();
+
+        adapter
+            .execute_intent(HardwareIntent::Halt {
+                reason: "operator e-stop".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let twist = rx.recv().await.unwrap();
+        assert_eq!(twist.source, "mechos-middleware::ros2/cmd_vel");
+        let EventPayload::AdapterCommand { json: frame, .. } = twist.payload else {
+            panic!("expected AdapterCommand");
+        };
+        assert_eq!(frame["msg"]["linear"]["x"], 0.0);
+        assert_eq!(frame["msg"]["angular"]["z"], 0.0);
+
+        let cancel = rx.recv().await.unwrap();
+        assert_eq!(cancel.source, "mechos-middleware::ros2/joint_trajectory");
+    }
+
+    #[tokio::test]
+    async fn execute_envelope_drops_stale_drive() {
+        let (bus, adapter) = make_adapter();
+        let mut rx = bus.subscribe();
+        let drive = HardwareIntent::Drive {
+            linear_velocity: MetersPerSecond(0.5),
+            angular_velocity: RadiansPerSecond(0.0),
+        };
+
+        let stale = IntentEnvelope::new(drive.clone(), "agent")
+            .with_deadline(Utc::now() - chrono::Duration::milliseconds(10));
+        assert!(matches!(
+            adapter.execute_envelope(stale).await,
+            Err(MechError::HardwareFault { ref details, .. }) if details.contains("deadline")
+        ));
+        assert!(rx.try_recv().is_err(), "stale intent must not be published");
+
+        let fresh = IntentEnvelope::new(drive, "agent")
+            .with_deadline(Utc::now() + chrono::Duration::seconds(5));
+        adapter.execute_envelope(fresh).await.unwrap();
+        assert_eq!(rx.recv().await.unwrap().source, "mechos-middleware::ros2/cmd_vel");
+    }
+
     #[tokio::test]
     async fn ingest_laser_scan_also_publishes_lidar_scan_event() {
         let (bus, adapter) = make_adapter();
@@ -499,8 +1373,8 @@ mod tests {
                 &[1.5, 2.5],
                 -std::f32::consts::FRAC_PI_2,
                 0.1,
-                0.0,
-                0.0,
+                Meters(0.0),
+                Meters(0.0),
                 0.0,
                 80,
             )
@@ -517,16 +1391,117 @@ mod tests {
             ranges,
             angle_min_rad,
             angle_increment_rad,
+            stamp,
         } = second.payload
         {
             assert_eq!(ranges, vec![1.5, 2.5]);
             assert!((angle_min_rad - (-std::f32::consts::FRAC_PI_2)).abs() < 1e-5);
             assert!((angle_increment_rad - 0.1).abs() < 1e-5);
+            assert_eq!(stamp, None);
         } else {
             panic!("expected LidarScan payload as second event");
         }
     }
 
+    #[test]
+    fn scan_filter_keeps_nearest_return_per_group_and_clips() {
+        let filter = ScanFilter {
+            downsample: 3,
+            min_range_m: 0.2,
+            max_range_m: 10.0,
+            max_rate_hz: None,
+        };
+        let ranges = [5.0, 2.0, 3.0, 0.1, f32::NAN, 12.0, 4.0, f32::INFINITY];
+        let (kept, _, increment) = filter.apply(&ranges, 0.0, 0.01);
+        assert_eq!(kept, vec![2.0, 0.0, 4.0]);
+        assert!((increment - 0.03).abs() < 1e-6);
+        // The default filter is a pass-through.
+        let (kept, first, increment) =
+            ScanFilter::default().apply(&[1.0, f32::INFINITY], -0.5, 0.01);
+        assert_eq!(
+            (kept[0], kept[1], first, increment),
+            (1.0, f32::INFINITY, -0.5, 0.01)
+        );
+    }
+
+    #[test]
+    fn scan_filter_keeps_off_centre_obstacles_within_half_a_group() {
+        let filter = ScanFilter {
+            downsample: 5,
+            ..ScanFilter::default()
+        };
+        let (angle_min, increment) = (-1.0, 0.1);
+        // One obstacle at the last reading of the second group.
+        let mut ranges = vec![8.0; 15];
+        ranges[9] = 1.0;
+        let (kept, first, step) = filter.apply(&ranges, angle_min, increment);
+
+        let nearest = kept.iter().position(|&r| r == 1.0).unwrap();
+        let bearing = first + nearest as f32 * step;
+        let actual = angle_min + 9.0 * increment;
+        assert!(
+            (bearing - actual).abs() <= 2.0 * increment + 1e-5,
+            "reported at {bearing}, actually at {actual}"
+        );
+    }
+
+    #[tokio::test]
+    async fn ingest_laser_scan_applies_scan_filter_and_rate_limit() {
+        let bus = Arc::new(EventBus::default());
+        let adapter = Ros2Adapter::new(Arc::clone(&bus)).with_scan_filter(ScanFilter {
+            downsample: 1024,
+            max_rate_hz: Some(5.0),
+            ..ScanFilter::default()
+        });
+        let mut global = bus.subscribe();
+        let scan = vec![3.0; MAX_LIDAR_RANGES];
+        let ingest =
+            || adapter.ingest_laser_scan(&scan, 0.0, 0.001, Meters(0.0), Meters(0.0), 0.0, 80);
+
+        assert_eq!(ingest().unwrap(), 1);
+        assert_eq!(ingest().unwrap(), 0, "second scan within 200 ms is dropped");
+        tokio::time::sleep(Duration::from_millis(220)).await;
+        assert_eq!(ingest().unwrap(), 1);
+
+        let scans: Vec<_> = std::iter::from_fn(|| global.try_recv().ok())
+            .filter_map(|e| match e.payload {
+                EventPayload::LidarScan {
+                    ranges,
+                    angle_increment_rad,
+                    ..
+                } => Some((ranges, angle_increment_rad)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(scans.len(), 2);
+        assert_eq!(scans[0].0, vec![3.0; 4]);
+        assert!((scans[0].1 - 1.024).abs() < 1e-4);
+    }
+
+    #[tokio::test]
+    async fn ingest_camera_frame_publishes_on_sensor_lane() {
+        let (bus, adapter) = make_adapter();
+        let mut lane = bus.subscribe_to(Topic::SensorHighRate);
+        let mut global = bus.subscribe();
+
+        let data = vec![0u8; 1280 * 720];
+        let delivered = adapter
+            .ingest_camera_frame("front/1", ImageFormat::Mono8, 1280, 720, &data)
+            .unwrap();
+        assert_eq!(delivered, 2);
+
+        let event = lane.recv().await.unwrap();
+        assert_eq!(event.source, "mechos-middleware::ros2/camera");
+        let EventPayload::CameraFrame { width, height, .. } = event.payload else {
+            panic!("expected CameraFrame");
+        };
+        assert_eq!((width, height), (640, 360));
+        assert!(matches!(
+            global.recv().await.unwrap().payload,
+            EventPayload::CameraFrame { .. }
+        ));
+    }
+
     #[tokio::test]
     async fn ingest_fleet_message_publishes_peer_message() {
         let (bus, adapter) = make_adapter();
@@ -552,4 +1527,86 @@ mod tests {
             panic!("expected PeerMessage payload");
         }
     }
+
+    #[tokio::test]
+    async fn drive_and_halt_use_dds_transport_when_installed() {
+        let (bus, adapter) = make_adapter();
+        let transport = Arc::new(RecordingTransport::default());
+        let adapter = adapter.with_transport(transport.clone());
+        let mut rx = bus.subscribe();
+
+        adapter
+            .execute_intent(HardwareIntent::Drive {
+                linear_velocity: MetersPerSecond(0.5),
+                angular_velocity: RadiansPerSecond(0.2),
+            })
+            .await
+            .unwrap();
+        assert!(rx.try_recv().is_err(), "Drive must not be wrapped as JSON");
+
+        adapter
+            .execute_intent(HardwareIntent::Halt {
+                reason: "stop".to_string(),
+            })
+            .await
+            .unwrap();
+        let cancel = rx.recv().await.unwrap();
+        assert_eq!(cancel.source, "mechos-middleware::ros2/joint_trajectory");
+
+        let sent = transport.0.lock().unwrap();
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[0].0, "/cmd_vel");
+        assert_eq!(
+            sent[0].1,
+            dds::encode_twist(MetersPerSecond(0.5), RadiansPerSecond(0.2))
+        );
+        assert_eq!(
+            sent[1].1,
+            dds::encode_twist(MetersPerSecond::ZERO, RadiansPerSecond::ZERO)
+        );
+    }
+
+    #[tokio::test]
+    async fn cdr_odom_and_scan_flow_into_the_bus() {
+        use crate::dds::tests::{laser_scan_cdr, odometry_cdr};
+
+        let (bus, adapter) = make_adapter();
+        let mut rx = bus.subscribe();
+
+        adapter
+            .ingest_odom_cdr(&odometry_cdr(2.0, 1.0, 0.5, 0.3))
+            .unwrap();
+        let odom = rx.recv().await.unwrap();
+        assert_eq!(odom.source, "mechos-middleware::ros2/odom");
+        let EventPayload::Telemetry(t) = odom.payload else {
+            panic!("expected Telemetry");
+        };
+        assert_eq!((t.position_x, t.position_y), (Meters(2.0), Meters(1.0)));
+        assert!((t.heading_rad - 0.5).abs() < 1e-6);
+        assert_eq!(
+            t.stamp.map(|s| s.timestamp()),
+            Some(1_700_000_000),
+            "odom carries its header stamp"
+        );
+
+        adapter
+            .ingest_scan_cdr(&laser_scan_cdr(-0.5, 0.1, &[1.0, 2.0]))
+            .unwrap();
+        let EventPayload::Telemetry(t) = rx.recv().await.unwrap().payload else {
+            panic!("expected Telemetry");
+        };
+        assert_eq!(
+            t.position_x,
+            Meters(2.0),
+            "scan carries the latest odom pose"
+        );
+        let EventPayload::LidarScan { ranges, stamp, .. } = rx.recv().await.unwrap().payload
+        else {
+            panic!("expected LidarScan");
+        };
+        assert_eq!(ranges, vec![1.0, 2.0]);
+        assert_eq!(stamp.map(|s| s.timestamp()), Some(1_700_000_000));
+
+        assert!(adapter.ingest_scan_cdr(&[0, 1, 0, 0, 1]).is_err());
+    }
 }
@@ -2,14 +2,27 @@
 //!
 //! This module provides [`Ros2Bridge`], which:
 //!
-//! 1. **Ingests** ROS2-style messages (odometry, laser scan, hardware faults)
-//!    and translates them into [`Event`] values that are published onto the
+//! 1. **Ingests** ROS2-style messages (odometry, laser scan, battery state,
+//!    hardware faults) and translates them into [`Event`] values that are published onto the
 //!    internal [`EventBus`].
 //!
 //! 2. **Serves** a lightweight WebSocket endpoint where external clients (web
 //!    dashboards, telemetry UIs) can subscribe to the live event stream as
 //!    newline-delimited JSON.
 //!
+//!    The endpoint speaks the rosbridge v2 protocol: `subscribe` (with
+//!    `throttle_rate`) / `unsubscribe`, `advertise` / `unadvertise`,
+//!    `publish`, and `call_service` against handlers registered with
+//!    [`Ros2Bridge::with_service`].  Bus events are published on the topics
+//!    given by [`rosbridge_message`].  A client that never subscribes keeps
+//!    receiving every event as raw JSON, as before.
+//!
+//! 3. **Dials out** in client mode ([`Ros2Bridge::run_ws_client`]) to a
+//!    remote `rosbridge_server`, supervised by a [`WsSupervisor`] that
+//!    reconnects with backoff and replays the bridge's subscriptions.
+//!    [`Ros2Bridge::call_service`] invokes the remote end's ROS services
+//!    (e.g. MoveIt 2's `/plan_kinematic_path`) over that link.
+//!
 //! The bridge is intentionally agnostic about the *meaning* of the data it
 //! routes; it only handles serialisation and transport.
 //!
@@ -24,25 +37,38 @@
 //! * **Rate limit** – the bridge accepts at most
 //!   [`MAX_INCOMING_MESSAGES_PER_SEC`] messages per second across all
 //!   connections.  Connections that exceed this quota are closed.
+//!
+//! A bridge built with [`Ros2Bridge::with_tls`] serves `wss://` instead of
+//! `ws://`, so teleop commands and human responses are encrypted on the
+//! wire.
 
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::num::NonZeroU32;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
 
+use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
 use governor::clock::DefaultClock;
 use governor::middleware::NoOpMiddleware;
 use governor::state::{InMemoryState, NotKeyed};
 use governor::{Quota, RateLimiter};
-use mechos_types::{Event, EventPayload, MechError, TelemetryData};
-use serde_json;
-use tokio::net::{TcpListener, TcpStream};
+use mechos_types::{Event, EventPayload, FaultCode, HumanAnswer, MechError, Meters, TelemetryData};
+use serde_json::{Value, json};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpListener;
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::Instant;
 use tokio_tungstenite::{accept_async_with_config, tungstenite::{Message, protocol::WebSocketConfig}};
 use uuid::Uuid;
 use chrono::Utc;
 use tracing::{error, warn};
 
 use crate::bus::EventBus;
+use crate::supervisor::{Backoff, SupervisorHandle, WsSupervisor};
+use crate::tls::TlsConfig;
 
 /// Maximum size (in bytes) of an incoming WebSocket payload.
 ///
@@ -55,8 +81,52 @@ pub const MAX_INCOMING_PAYLOAD_BYTES: usize = 1024 * 1024; // 1 MiB
 /// are closed.
 pub const MAX_INCOMING_MESSAGES_PER_SEC: u32 = 100;
 
+/// Maximum number of topics a single served client may subscribe to.
+pub const MAX_SUBSCRIPTIONS_PER_CLIENT: usize = 256;
+
+/// How long [`Ros2Bridge::call_service`] waits for a `service_response`.
+/// Generous because motion planning services can take several seconds.
+pub const SERVICE_CALL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// `sensor_msgs/msg/BatteryState` `power_supply_status` value for a pack
+/// that is currently charging.
+const POWER_SUPPLY_STATUS_CHARGING: u8 = 1;
+
+/// Message types of the topics the bridge consumes; an `advertise` of one of
+/// these topics with a different type is refused.
+const KNOWN_TOPIC_TYPES: &[(&str, &str)] = &[
+    ("/cmd_vel", "geometry_msgs/msg/Twist"),
+    ("/battery_state", "sensor_msgs/msg/BatteryState"),
+];
+
 type DirectRateLimiter = RateLimiter<NotKeyed, InMemoryState, DefaultClock, NoOpMiddleware>;
 
+/// A ROS service that served rosbridge clients can invoke with
+/// `call_service`.
+#[async_trait]
+pub trait RosService: Send + Sync {
+    /// Handle one call.  `args` is the request message; the returned value
+    /// becomes the response's `values`.  An error is reported to the client
+    /// as `"result": false`.
+    async fn call(&self, args: Value) -> Result<Value, MechError>;
+}
+
+/// Outstanding [`Ros2Bridge::call_service`] requests of client mode.
+#[derive(Default)]
+struct ServiceClient {
+    link: OnceLock<SupervisorHandle>,
+    next_id: AtomicU64,
+    pending: Mutex<HashMap<String, oneshot::Sender<(bool, Value)>>>,
+}
+
+impl ServiceClient {
+    fn pending(
+        &self,
+    ) -> std::sync::MutexGuard<'_, HashMap<String, oneshot::Sender<(bool, Value)>>> {
+        self.pending.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
 /// Bridge between ROS2 topics and the internal [`EventBus`] / WebSocket
 /// clients.
 #[derive(Clone)]
@@ -65,6 +135,12 @@ pub struct Ros2Bridge {
     /// Rate limiter for incoming WebSocket messages (shared across all
     /// connections served by this bridge instance).
     incoming_limiter: Arc<DirectRateLimiter>,
+    /// Services served to rosbridge clients, by name.
+    services: Arc<HashMap<String, Arc<dyn RosService>>>,
+    service_client: Arc<ServiceClient>,
+    /// Certificate the WebSocket server terminates TLS with; `None` serves
+    /// plaintext.
+    tls: Option<TlsConfig>,
 }
 
 impl Ros2Bridge {
@@ -77,9 +153,29 @@ impl Ros2Bridge {
         Self {
             bus,
             incoming_limiter: Arc::new(RateLimiter::direct(quota)),
+            services: Arc::new(HashMap::new()),
+            service_client: Arc::new(ServiceClient::default()),
+            tls: None,
         }
     }
 
+    /// Serve `wss://` from [`run_ws_server`][Self::run_ws_server], with the
+    /// certificate and key named by `tls` (builder-style).
+    pub fn with_tls(mut self, tls: TlsConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    /// Serve `service` (e.g. `"/mechos/get_status"`) to rosbridge clients.
+    pub fn with_service(
+        mut self,
+        service: impl Into<String>,
+        handler: Arc<dyn RosService>,
+    ) -> Self {
+        Arc::make_mut(&mut self.services).insert(service.into(), handler);
+        self
+    }
+
     // -----------------------------------------------------------------------
     // ROS2 ingest helpers
     // -----------------------------------------------------------------------
@@ -88,8 +184,8 @@ impl Ros2Bridge {
     /// [`EventPayload::Telemetry`] event.
     pub fn ingest_odom(
         &self,
-        position_x: f32,
-        position_y: f32,
+        position_x: Meters,
+        position_y: Meters,
         heading_rad: f32,
         battery_percent: u8,
     ) -> Result<usize, MechError> {
@@ -102,8 +198,50 @@ impl Ros2Bridge {
                 position_y,
                 heading_rad,
                 battery_percent,
+                stamp: None,
             }),
             trace_id: None,
+            correlation_id: None,
+        };
+        self.bus.publish(event)
+    }
+
+    /// Ingest a `sensor_msgs/msg/BatteryState` message from `/battery_state`
+    /// and publish it as an [`EventPayload::PowerStatus`] event.
+    ///
+    /// `percentage` uses the ROS range `0.0`–`1.0` and is rescaled to
+    /// `0.0`–`100.0`; `power_supply_status` is the ROS status enum, where
+    /// `1` means charging.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Parsing`] if `percentage` is not finite; ROS
+    /// drivers report `NaN` when the charge is unmeasured, and the kernel's
+    /// battery interlock must not act on it.
+    pub fn ingest_battery_state(
+        &self,
+        voltage: f32,
+        current: f32,
+        percentage: f32,
+        power_supply_status: u8,
+    ) -> Result<usize, MechError> {
+        if !percentage.is_finite() {
+            return Err(MechError::Parsing(format!(
+                "battery percentage {percentage} is not a number"
+            )));
+        }
+        let event = Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: "mechos-middleware::ros2/battery_state".to_string(),
+            payload: EventPayload::PowerStatus {
+                voltage,
+                current,
+                charging: power_supply_status == POWER_SUPPLY_STATUS_CHARGING,
+                percent: percentage.clamp(0.0, 1.0) * 100.0,
+            },
+            trace_id: None,
+            correlation_id: None,
         };
         self.bus.publish(event)
     }
@@ -113,7 +251,7 @@ impl Ros2Bridge {
     pub fn ingest_fault(
         &self,
         component: impl Into<String>,
-        code: u32,
+        code: FaultCode,
         message: impl Into<String>,
     ) -> Result<usize, MechError> {
         let event = Event {
@@ -126,6 +264,7 @@ impl Ros2Bridge {
                 message: message.into(),
             },
             trace_id: None,
+            correlation_id: None,
         };
         self.bus.publish(event)
     }
@@ -138,13 +277,15 @@ impl Ros2Bridge {
     ///
     /// Every connecting client receives a stream of newline-delimited JSON
     /// objects, one per event on the bus.  The server runs until it
-    /// encounters a fatal bind error.
+    /// encounters a fatal bind error.  With [`with_tls`][Self::with_tls]
+    /// every connection is TLS-encrypted first.
     ///
     /// # Errors
     ///
-    /// Returns [`MechError::Serialization`] if the TCP listener cannot be
-    /// bound.
+    /// Returns [`MechError::Serialization`] if the TLS certificate cannot be
+    /// loaded or the TCP listener cannot be bound.
     pub async fn run_ws_server(self, addr: SocketAddr) -> Result<(), MechError> {
+        let acceptor = self.tls.as_ref().map(TlsConfig::acceptor).transpose()?;
         let listener = TcpListener::bind(addr).await.map_err(|e| {
             MechError::Serialization(format!("ws bind error on {addr}: {e}"))
         })?;
@@ -153,8 +294,18 @@ impl Ros2Bridge {
             match listener.accept().await {
                 Ok((stream, peer)) => {
                     let bridge = self.clone();
+                    let acceptor = acceptor.clone();
                     tokio::spawn(async move {
-                        if let Err(e) = bridge.handle_ws_client(stream, peer).await {
+                        let result = match acceptor {
+                            Some(acceptor) => match acceptor.accept(stream).await {
+                                Ok(stream) => bridge.handle_ws_client(stream, peer).await,
+                                Err(e) => Err(MechError::Serialization(format!(
+                                    "TLS handshake from {peer}: {e}"
+                                ))),
+                            },
+                            None => bridge.handle_ws_client(stream, peer).await,
+                        };
+                        if let Err(e) = result {
                             error!(peer = %peer, error = %e, "ws client error");
                         }
                     });
@@ -166,11 +317,114 @@ impl Ros2Bridge {
         }
     }
 
-    async fn handle_ws_client(
-        &self,
-        stream: TcpStream,
-        peer: SocketAddr,
-    ) -> Result<(), MechError> {
+    /// Connect to a remote `rosbridge_server` at `url` and keep the link up
+    /// forever.
+    ///
+    /// The bridge subscribes to `/cmd_vel`, `/hitl/human_response` and
+    /// `/battery_state` (re-subscribing after every reconnect) and handles
+    /// those frames exactly like frames from a served client.  Events that
+    /// carry an adapter's rosbridge `publish` frame (see
+    /// [`rosbridge_message`]) are sent to the remote end while the link is
+    /// up; events raised while it is down are not replayed.  Frames over the
+    /// rate limit are dropped rather than closing the connection, which would
+    /// only trigger a reconnect.
+    ///
+    /// While this runs, [`call_service`][Self::call_service] can invoke the
+    /// remote end's services.
+    pub async fn run_ws_client(self, url: impl Into<String>, backoff: Backoff) {
+        let mut link =
+            WsSupervisor::new(Arc::clone(&self.bus), "ros2_bridge", url).with_backoff(backoff);
+        for topic in ["/cmd_vel", "/hitl/human_response", "/battery_state"] {
+            link = link.with_subscription(
+                serde_json::json!({ "op": "subscribe", "topic": topic }).to_string(),
+            );
+        }
+        let handle = link.handle();
+        if self.service_client.link.set(handle.clone()).is_err() {
+            warn!("run_ws_client called twice; call_service uses the first link");
+        }
+        let mut rx = self.bus.subscribe();
+
+        let forward = async move {
+            loop {
+                match rx.recv().await {
+                    Ok(event) => {
+                        if handle.is_connected()
+                            && let EventPayload::AdapterCommand { json, .. } = &event.payload
+                            && is_publish_frame(json)
+                            && let Err(e) = handle.send(json.to_string())
+                        {
+                            warn!(error = %e, "cannot forward event to rosbridge");
+                        }
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                        warn!(lagged_by = n, "rosbridge client lagged");
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        };
+        let receive = link.run(|text| {
+            if self.incoming_limiter.check().is_err() {
+                warn!("incoming rosbridge rate limit exceeded; dropping frame");
+                return;
+            }
+            if let Ok(json) = serde_json::from_str::<Value>(text)
+                && json.get("op").and_then(|o| o.as_str()) == Some("service_response")
+            {
+                self.complete_service_call(&json);
+                return;
+            }
+            self.handle_incoming_ws_message(text);
+        });
+        tokio::join!(forward, receive);
+    }
+
+    /// Invoke `service` on the remote `rosbridge_server` that
+    /// [`run_ws_client`][Self::run_ws_client] is connected to, returning the
+    /// response's `values`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Channel`] if client mode is not running or the
+    /// link is down, if no response arrives within
+    /// [`SERVICE_CALL_TIMEOUT`], or if the service reports failure.
+    pub async fn call_service(&self, service: &str, args: Value) -> Result<Value, MechError> {
+        let link = self.service_client.link.get().ok_or_else(|| {
+            MechError::Channel("rosbridge client mode is not running".to_string())
+        })?;
+        let id = format!(
+            "mechos_call_{}",
+            self.service_client.next_id.fetch_add(1, Ordering::Relaxed)
+        );
+        let (tx, rx) = oneshot::channel();
+        self.service_client.pending().insert(id.clone(), tx);
+        let request = json!({ "op": "call_service", "id": id, "service": service, "args": args });
+        if let Err(e) = link.send(request.to_string()) {
+            self.service_client.pending().remove(&id);
+            return Err(e);
+        }
+
+        let response = tokio::time::timeout(SERVICE_CALL_TIMEOUT, rx).await;
+        self.service_client.pending().remove(&id);
+        match response {
+            Ok(Ok((true, values))) => Ok(values),
+            Ok(Ok((false, values))) => Err(MechError::Channel(format!(
+                "service '{service}' failed: {values}"
+            ))),
+            Ok(Err(_)) => Err(MechError::Channel(format!(
+                "service '{service}' call dropped"
+            ))),
+            Err(_) => Err(MechError::Channel(format!(
+                "service '{service}' did not respond within {SERVICE_CALL_TIMEOUT:?}"
+            ))),
+        }
+    }
+
+    async fn handle_ws_client<S>(&self, stream: S, peer: SocketAddr) -> Result<(), MechError>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
         let mut ws_config = WebSocketConfig::default();
         ws_config.max_message_size = Some(MAX_INCOMING_PAYLOAD_BYTES);
         let ws_stream = accept_async_with_config(stream, Some(ws_config)).await.map_err(|e| {
@@ -179,6 +433,8 @@ impl Ros2Bridge {
 
         let (mut ws_tx, mut ws_rx) = ws_stream.split();
         let mut rx = self.bus.subscribe();
+        let mut session = Session::default();
+        let (reply_tx, mut reply_rx) = mpsc::unbounded_channel::<String>();
 
         loop {
             tokio::select! {
@@ -186,10 +442,10 @@ impl Ros2Bridge {
                 result = rx.recv() => {
                     match result {
                         Ok(event) => {
-                            let json = serde_json::to_string(&event).map_err(|e| {
-                                MechError::Serialization(e.to_string())
-                            })?;
-                            if ws_tx.send(Message::Text(json.into())).await.is_err() {
+                            let Some(frame) = session.outgoing(&event, Instant::now())? else {
+                                continue;
+                            };
+                            if ws_tx.send(Message::Text(frame.into())).await.is_err() {
                                 break;
                             }
                         }
@@ -223,20 +479,131 @@ impl Ros2Bridge {
                                 );
                                 break;
                             }
-                            self.handle_incoming_ws_message(text.as_str());
+                            self.handle_client_op(&mut session, text.as_str(), &reply_tx);
                         }
                         _ => {}
                     }
                 }
+                // Send service responses and status reports.
+                Some(reply) = reply_rx.recv() => {
+                    if ws_tx.send(Message::Text(reply.into())).await.is_err() {
+                        break;
+                    }
+                }
             }
         }
 
         Ok(())
     }
 
+    /// Apply one rosbridge v2 op from a served client.
+    ///
+    /// `publish` frames, and frames without an `op`, go to
+    /// [`handle_incoming_ws_message`][Self::handle_incoming_ws_message].
+    /// Refused ops are answered with a `status` frame on `replies`.
+    fn handle_client_op(
+        &self,
+        session: &mut Session,
+        text: &str,
+        replies: &mpsc::UnboundedSender<String>,
+    ) {
+        let Ok(json) = serde_json::from_str::<Value>(text) else {
+            return;
+        };
+        let op = json.get("op").and_then(|o| o.as_str()).unwrap_or("");
+        let topic = json.get("topic").and_then(|t| t.as_str());
+        let id = json.get("id").cloned();
+        let refused = match (op, topic) {
+            ("subscribe", Some(topic)) => {
+                let throttle_ms = json
+                    .get("throttle_rate")
+                    .and_then(|t| t.as_u64())
+                    .unwrap_or(0);
+                session
+                    .subscribe(topic, Duration::from_millis(throttle_ms))
+                    .err()
+            }
+            ("unsubscribe", Some(topic)) => {
+                session.unsubscribe(topic);
+                None
+            }
+            ("advertise", Some(topic)) => {
+                let msg_type = json.get("type").and_then(|t| t.as_str()).unwrap_or("");
+                session.advertise(topic, msg_type).err()
+            }
+            ("unadvertise", Some(topic)) => {
+                session.advertised.remove(topic);
+                None
+            }
+            ("call_service", _) => {
+                self.spawn_service_call(&json, replies.clone());
+                None
+            }
+            ("publish", _) | ("", _) => {
+                self.handle_incoming_ws_message(text);
+                None
+            }
+            _ => Some(format!("unsupported rosbridge op '{op}'")),
+        };
+        if let Some(message) = refused {
+            let mut status = json!({ "op": "status", "level": "error", "msg": message });
+            if let Some(id) = id {
+                status["id"] = id;
+            }
+            let _ = replies.send(status.to_string());
+        }
+    }
+
+    /// Run a served `call_service` request and queue its `service_response`.
+    fn spawn_service_call(&self, request: &Value, replies: mpsc::UnboundedSender<String>) {
+        let service = request
+            .get("service")
+            .and_then(|s| s.as_str())
+            .unwrap_or("")
+            .to_string();
+        let args = request.get("args").cloned().unwrap_or_else(|| json!({}));
+        let id = request.get("id").cloned();
+        let handler = self.services.get(&service).cloned();
+        tokio::spawn(async move {
+            let (values, result) = match handler {
+                Some(handler) => match handler.call(args).await {
+                    Ok(values) => (values, true),
+                    Err(e) => (Value::String(e.to_string()), false),
+                },
+                None => (Value::String(format!("unknown service '{service}'")), false),
+            };
+            let mut response = json!({
+                "op": "service_response",
+                "service": service,
+                "values": values,
+                "result": result
+            });
+            if let Some(id) = id {
+                response["id"] = id;
+            }
+            let _ = replies.send(response.to_string());
+        });
+    }
+
+    /// Resolve a pending [`call_service`][Self::call_service] from the remote
+    /// end's `service_response`.
+    fn complete_service_call(&self, response: &Value) {
+        let Some(id) = response.get("id").and_then(|i| i.as_str()) else {
+            return;
+        };
+        if let Some(tx) = self.service_client.pending().remove(id) {
+            let result = response
+                .get("result")
+                .and_then(|r| r.as_bool())
+                .unwrap_or(false);
+            let values = response.get("values").cloned().unwrap_or(Value::Null);
+            let _ = tx.send((result, values));
+        }
+    }
+
     /// Parse an incoming WebSocket text message from the dashboard.
     ///
-    /// Two message kinds are recognised:
+    /// Three message kinds are recognised:
     ///
     /// * **Manual override** – a `rosbridge_server` publish on `/cmd_vel` that
     ///   carries the extra field `"source": "dashboard_override"`.  The Twist
@@ -245,11 +612,17 @@ impl Ros2Bridge {
     ///   [`AgentLoop`] can arm its 10-second AI suspension.
     ///
     /// * **Human response** – a publish on `/hitl/human_response` whose `msg`
-    ///   contains a `"response"` string.  Published as
+    ///   contains a `"response"` string and optionally the `"question_id"`
+    ///   it answers.  Published as
     ///   [`EventPayload::HumanResponse`] so that the [`AgentLoop`] can inject
     ///   it back into the LLM context window.
     ///
-    /// Any message that does not match either pattern is silently ignored.
+    /// * **Battery state** – a publish on `/battery_state` carrying a
+    ///   `sensor_msgs/msg/BatteryState`.  Forwarded to
+    ///   [`ingest_battery_state`][Self::ingest_battery_state].
+    ///
+    /// Any message that does not match one of these patterns is silently
+    /// ignored.
     fn handle_incoming_ws_message(&self, text: &str) {
         let Ok(json) = serde_json::from_str::<serde_json::Value>(text) else {
             return;
@@ -264,8 +637,9 @@ impl Ros2Bridge {
                 id: Uuid::new_v4(),
                 timestamp: Utc::now(),
                 source: "mechos-middleware::dashboard_override".to_string(),
-                payload: EventPayload::AgentThought(text.to_string()),
+                payload: EventPayload::adapter_command(json),
                 trace_id: None,
+                correlation_id: None,
             };
             let _ = self.bus.publish(event);
             return;
@@ -273,20 +647,175 @@ impl Ros2Bridge {
 
         // ── Human response to AskHuman ───────────────────────────────────────
         if topic == "/hitl/human_response"
-            && let Some(response) = json
-                .get("msg")
-                .and_then(|m| m.get("response"))
-                .and_then(|r| r.as_str())
+            && let Some(msg) = json.get("msg")
+            && let Some(response) = msg.get("response").and_then(|r| r.as_str())
         {
             let event = Event {
                 id: Uuid::new_v4(),
                 timestamp: Utc::now(),
                 source: "mechos-middleware::dashboard/human_response".to_string(),
-                payload: EventPayload::HumanResponse(response.to_string()),
+                payload: EventPayload::HumanResponse(HumanAnswer {
+                    question_id: msg
+                        .get("question_id")
+                        .and_then(|q| q.as_str())
+                        .map(str::to_string),
+                    answer: response.to_string(),
+                    operator: None,
+                }),
                 trace_id: None,
+                correlation_id: None,
             };
             let _ = self.bus.publish(event);
+            return;
+        }
+
+        // ── Battery state ────────────────────────────────────────────────────
+        if topic == "/battery_state"
+            && let Some(msg) = json.get("msg")
+        {
+            let field = |name: &str| msg.get(name).and_then(|v| v.as_f64());
+            let status = msg
+                .get("power_supply_status")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0);
+            if let Err(e) = self.ingest_battery_state(
+                field("voltage").unwrap_or(0.0) as f32,
+                field("current").unwrap_or(0.0) as f32,
+                field("percentage").unwrap_or(f64::NAN) as f32,
+                u8::try_from(status).unwrap_or(0),
+            ) {
+                warn!(error = %e, "dropping /battery_state message");
+            }
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// rosbridge v2 protocol
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// The rosbridge topic and message an [`Event`] is published as.
+///
+/// [`EventPayload::AdapterCommand`]s holding a rosbridge `publish` frame
+/// (the adapters' `/cmd_vel`, `/sim/joint_trajectory`, … frames) keep that
+/// frame's topic and message.  Every other event is
+/// published whole on `/mechos/<payload kind>`, e.g. `/mechos/telemetry` or
+/// `/mechos/hardware_fault`.
+pub fn rosbridge_message(event: &Event) -> Result<(String, Value), MechError> {
+    if let EventPayload::AdapterCommand { topic, json } = &event.payload
+        && is_publish_frame(json)
+    {
+        let msg = json.get("msg").cloned().unwrap_or(Value::Null);
+        return Ok((topic.clone(), msg));
+    }
+    let msg = serde_json::to_value(event).map_err(|e| MechError::Serialization(e.to_string()))?;
+    let kind = msg
+        .get("payload")
+        .and_then(|p| match p {
+            Value::Object(map) => map.keys().next().cloned(),
+            Value::String(unit) => Some(unit.clone()),
+            _ => None,
+        })
+        .unwrap_or_default();
+    Ok((format!("/mechos/{}", snake_case(&kind)), msg))
+}
+
+/// `true` if `frame` is a rosbridge `publish` op.
+fn is_publish_frame(frame: &Value) -> bool {
+    frame.get("op").and_then(Value::as_str) == Some("publish")
+}
+
+fn snake_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// One client subscription and its throttle.
+struct TopicSubscription {
+    throttle: Duration,
+    last_sent: Option<Instant>,
+}
+
+/// rosbridge v2 state of one served WebSocket client.
+#[derive(Default)]
+struct Session {
+    /// `None` until the client's first `subscribe`; until then it receives
+    /// every bus event as raw JSON.
+    subscriptions: Option<HashMap<String, TopicSubscription>>,
+    /// Advertised topic → message type.
+    advertised: HashMap<String, String>,
+}
+
+impl Session {
+    fn subscribe(&mut self, topic: &str, throttle: Duration) -> Result<(), String> {
+        let subscriptions = self.subscriptions.get_or_insert_with(HashMap::new);
+        if !subscriptions.contains_key(topic) && subscriptions.len() >= MAX_SUBSCRIPTIONS_PER_CLIENT
+        {
+            return Err(format!(
+                "subscription limit of {MAX_SUBSCRIPTIONS_PER_CLIENT} topics reached"
+            ));
+        }
+        subscriptions.insert(
+            topic.to_string(),
+            TopicSubscription {
+                throttle,
+                last_sent: None,
+            },
+        );
+        Ok(())
+    }
+
+    fn unsubscribe(&mut self, topic: &str) {
+        if let Some(subscriptions) = &mut self.subscriptions {
+            subscriptions.remove(topic);
+        }
+    }
+
+    fn advertise(&mut self, topic: &str, msg_type: &str) -> Result<(), String> {
+        if msg_type.is_empty() {
+            return Err(format!("advertise of '{topic}' has no type"));
+        }
+        if let Some((_, expected)) = KNOWN_TOPIC_TYPES.iter().find(|(t, _)| *t == topic)
+            && msg_type.replace("/msg/", "/") != expected.replace("/msg/", "/")
+        {
+            return Err(format!("'{topic}' carries {expected}, not {msg_type}"));
+        }
+        self.advertised
+            .insert(topic.to_string(), msg_type.to_string());
+        Ok(())
+    }
+
+    /// The frame to send this client for `event`, if any.
+    fn outgoing(&mut self, event: &Event, now: Instant) -> Result<Option<String>, MechError> {
+        let Some(subscriptions) = &mut self.subscriptions else {
+            let json = serde_json::to_string(event)
+                .map_err(|e| MechError::Serialization(e.to_string()))?;
+            return Ok(Some(json));
+        };
+        let (topic, msg) = rosbridge_message(event)?;
+        let Some(subscription) = subscriptions.get_mut(&topic) else {
+            return Ok(None);
+        };
+        if subscription
+            .last_sent
+            .is_some_and(|last| now.duration_since(last) < subscription.throttle)
+        {
+            return Ok(None);
         }
+        subscription.last_sent = Some(now);
+        Ok(Some(
+            json!({ "op": "publish", "topic": topic, "msg": msg }).to_string(),
+        ))
     }
 }
 
@@ -307,14 +836,14 @@ mod tests {
         let (bus, bridge) = make_bridge();
         let mut rx = bus.subscribe();
 
-        bridge.ingest_odom(1.0, 2.0, 0.5, 85)?;
+        bridge.ingest_odom(Meters(1.0), Meters(2.0), 0.5, 85)?;
 
         let event = rx.recv().await?;
         assert_eq!(event.source, "mechos-middleware::ros2/odom");
         assert!(matches!(event.payload, EventPayload::Telemetry(_)));
         if let EventPayload::Telemetry(t) = event.payload {
-            assert!((t.position_x - 1.0).abs() < f32::EPSILON);
-            assert!((t.position_y - 2.0).abs() < f32::EPSILON);
+            assert!((t.position_x.get() - 1.0).abs() < f32::EPSILON);
+            assert!((t.position_y.get() - 2.0).abs() < f32::EPSILON);
             assert!((t.heading_rad - 0.5).abs() < f32::EPSILON);
             assert_eq!(t.battery_percent, 85);
         }
@@ -326,14 +855,14 @@ mod tests {
         let (bus, bridge) = make_bridge();
         let mut rx = bus.subscribe();
 
-        bridge.ingest_fault("motor_left", 42, "overcurrent")?;
+        bridge.ingest_fault("motor_left", FaultCode::StaleSensor, "overcurrent")?;
 
         let event = rx.recv().await?;
         assert_eq!(event.source, "mechos-middleware::ros2/fault");
         assert!(matches!(event.payload, EventPayload::HardwareFault { .. }));
         if let EventPayload::HardwareFault { component, code, message } = event.payload {
             assert_eq!(component, "motor_left");
-            assert_eq!(code, 42);
+            assert_eq!(code, FaultCode::StaleSensor);
             assert_eq!(message, "overcurrent");
         }
         Ok(())
@@ -344,7 +873,7 @@ mod tests {
         let (bus, bridge) = make_bridge();
         let mut rx = bus.subscribe();
 
-        bridge.ingest_odom(3.0, 4.0, 1.57, 60)?;
+        bridge.ingest_odom(Meters(3.0), Meters(4.0), 1.57, 60)?;
 
         let event = rx.recv().await?;
         let json = serde_json::to_string(&event)?;
@@ -363,11 +892,11 @@ mod tests {
 
         let event = rx.recv().await?;
         assert_eq!(event.source, "mechos-middleware::dashboard_override");
-        if let EventPayload::AgentThought(raw) = &event.payload {
-            assert!(raw.contains("dashboard_override"));
-        } else {
-            panic!("expected AgentThought for override");
-        }
+        let EventPayload::AdapterCommand { topic, json } = &event.payload else {
+            panic!("expected AdapterCommand for override");
+        };
+        assert_eq!(topic, "/cmd_vel");
+        assert_eq!(json["source"], "dashboard_override");
         Ok(())
     }
 
@@ -382,20 +911,69 @@ mod tests {
         let event = rx.recv().await?;
         assert_eq!(event.source, "mechos-middleware::dashboard/human_response");
         if let EventPayload::HumanResponse(resp) = event.payload {
-            assert_eq!(resp, "Yes, push it");
+            assert_eq!(resp, HumanAnswer::new("Yes, push it"));
         } else {
             panic!("expected HumanResponse");
         }
         Ok(())
     }
 
+    #[tokio::test]
+    async fn ingest_battery_state_publishes_power_status() -> Result<(), Box<dyn std::error::Error>> {
+        let (bus, bridge) = make_bridge();
+        let mut rx = bus.subscribe();
+
+        bridge.ingest_battery_state(24.2, 1.5, 0.42, 1)?;
+
+        let event = rx.recv().await?;
+        assert_eq!(event.source, "mechos-middleware::ros2/battery_state");
+        let EventPayload::PowerStatus {
+            charging, percent, ..
+        } = event.payload
+        else {
+            panic!("expected PowerStatus");
+        };
+        assert!(charging);
+        assert!((percent - 42.0).abs() < 1e-4);
+
+        assert!(matches!(
+            bridge.ingest_battery_state(24.2, 0.0, f32::NAN, 0),
+            Err(MechError::Parsing(_))
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_incoming_battery_state_publishes_power_status() -> Result<(), Box<dyn std::error::Error>> {
+        let (bus, bridge) = make_bridge();
+        let mut rx = bus.subscribe();
+
+        let battery_msg = r#"{"op":"publish","topic":"/battery_state","msg":{"voltage":23.9,"current":-2.0,"percentage":0.15,"power_supply_status":2}}"#;
+        bridge.handle_incoming_ws_message(battery_msg);
+
+        let event = rx.recv().await?;
+        let EventPayload::PowerStatus {
+            voltage,
+            charging,
+            percent,
+            ..
+        } = event.payload
+        else {
+            panic!("expected PowerStatus");
+        };
+        assert!((voltage - 23.9).abs() < 1e-4);
+        assert!(!charging);
+        assert!((percent - 15.0).abs() < 1e-4);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn handle_incoming_unknown_message_is_ignored() -> Result<(), Box<dyn std::error::Error>> {
         let (bus, bridge) = make_bridge();
         let mut rx = bus.subscribe();
 
         // Publish a real event first so we have something to compare.
-        bridge.ingest_odom(0.0, 0.0, 0.0, 100)?;
+        bridge.ingest_odom(Meters(0.0), Meters(0.0), 0.0, 100)?;
         // Now try a message that matches neither override nor HITL pattern.
         bridge.handle_incoming_ws_message(r#"{"op":"subscribe","topic":"/unknown"}"#);
 
@@ -449,4 +1027,246 @@ mod tests {
         let result = rx.try_recv();
         assert!(result.is_err(), "Bus should not receive any event for malformed JSON");
     }
+
+    struct Echo;
+
+    #[async_trait]
+    impl RosService for Echo {
+        async fn call(&self, args: Value) -> Result<Value, MechError> {
+            Ok(json!({ "echo": args }))
+        }
+    }
+
+    fn event(payload: EventPayload) -> Event {
+        Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: "test".to_string(),
+            payload,
+            trace_id: None,
+            correlation_id: None,
+        }
+    }
+
+    #[test]
+    fn rosbridge_message_keeps_adapter_frames_and_names_other_events() {
+        let twist = event(EventPayload::adapter_command(serde_json::json!({
+            "op": "publish",
+            "topic": "/cmd_vel",
+            "msg": { "linear": { "x": 0.5 } }
+        })));
+        let (topic, msg) = rosbridge_message(&twist).unwrap();
+        assert_eq!(topic, "/cmd_vel");
+        assert_eq!(msg["linear"]["x"], 0.5);
+
+        let fault = event(EventPayload::HardwareFault {
+            component: "wheel".to_string(),
+            code: FaultCode::Unknown,
+            message: "stall".to_string(),
+        });
+        let (topic, msg) = rosbridge_message(&fault).unwrap();
+        assert_eq!(topic, "/mechos/hardware_fault");
+        assert_eq!(msg["source"], "test");
+    }
+
+    #[test]
+    fn session_sends_everything_until_the_first_subscribe_then_throttles() {
+        let mut session = Session::default();
+        let thought = event(EventPayload::Reasoning("thinking".to_string()));
+        assert!(
+            session
+                .outgoing(&thought, Instant::now())
+                .unwrap()
+                .is_some()
+        );
+
+        session
+            .subscribe("/mechos/reasoning", Duration::from_millis(100))
+            .unwrap();
+        let start = Instant::now();
+        let frame = session.outgoing(&thought, start).unwrap().unwrap();
+        let frame: Value = serde_json::from_str(&frame).unwrap();
+        assert_eq!(frame["op"], "publish");
+        assert_eq!(frame["topic"], "/mechos/reasoning");
+        assert!(
+            session
+                .outgoing(&thought, start + Duration::from_millis(50))
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            session
+                .outgoing(&thought, start + Duration::from_millis(100))
+                .unwrap()
+                .is_some()
+        );
+
+        let other = event(EventPayload::AgentModeToggle { paused: true });
+        assert!(session.outgoing(&other, start).unwrap().is_none());
+        session.unsubscribe("/mechos/reasoning");
+        assert!(
+            session
+                .outgoing(&thought, start + Duration::from_secs(1))
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn advertise_checks_known_topic_types() {
+        let mut session = Session::default();
+        session
+            .advertise("/cmd_vel", "geometry_msgs/Twist")
+            .unwrap();
+        session.advertise("/custom", "std_msgs/msg/String").unwrap();
+        assert!(
+            session
+                .advertise("/cmd_vel", "std_msgs/msg/String")
+                .is_err()
+        );
+        assert!(session.advertise("/custom", "").is_err());
+        assert_eq!(session.advertised.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn served_call_service_and_unknown_ops_are_answered() {
+        let (bus, bridge) = make_bridge();
+        let bridge = bridge.with_service("/echo", Arc::new(Echo));
+        let _rx = bus.subscribe();
+        let mut session = Session::default();
+        let (tx, mut replies) = mpsc::unbounded_channel();
+
+        bridge.handle_client_op(
+            &mut session,
+            r#"{"op":"call_service","id":"c1","service":"/echo","args":{"a":1}}"#,
+            &tx,
+        );
+        let response: Value = serde_json::from_str(&replies.recv().await.unwrap()).unwrap();
+        assert_eq!(response["op"], "service_response");
+        assert_eq!(response["id"], "c1");
+        assert_eq!(response["result"], true);
+        assert_eq!(response["values"]["echo"]["a"], 1);
+
+        bridge.handle_client_op(
+            &mut session,
+            r#"{"op":"call_service","service":"/nope"}"#,
+            &tx,
+        );
+        let response: Value = serde_json::from_str(&replies.recv().await.unwrap()).unwrap();
+        assert_eq!(response["result"], false);
+
+        bridge.handle_client_op(&mut session, r#"{"op":"fragment","id":"f1"}"#, &tx);
+        let status: Value = serde_json::from_str(&replies.recv().await.unwrap()).unwrap();
+        assert_eq!(status["op"], "status");
+        assert_eq!(status["id"], "f1");
+    }
+
+    #[tokio::test]
+    async fn client_mode_calls_remote_services() {
+        let (bus, bridge) = make_bridge();
+        let _rx = bus.subscribe();
+        assert!(matches!(
+            bridge.call_service("/plan_kinematic_path", json!({})).await,
+            Err(MechError::Channel(_))
+        ));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let client = tokio::spawn(bridge.clone().run_ws_client(url, Backoff::default()));
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut server = tokio_tungstenite::accept_async(stream).await.unwrap();
+        for _ in 0..3 {
+            let subscribe = server.next().await.unwrap().unwrap();
+            assert!(
+                subscribe
+                    .into_text()
+                    .unwrap()
+                    .contains(r#""op":"subscribe""#)
+            );
+        }
+
+        let call = tokio::spawn({
+            let bridge = bridge.clone();
+            async move {
+                bridge
+                    .call_service("/plan_kinematic_path", json!({ "group_name": "arm" }))
+                    .await
+            }
+        });
+        let request: Value =
+            serde_json::from_str(server.next().await.unwrap().unwrap().to_text().unwrap()).unwrap();
+        assert_eq!(request["op"], "call_service");
+        assert_eq!(request["args"]["group_name"], "arm");
+        let response = json!({
+            "op": "service_response",
+            "id": request["id"],
+            "service": "/plan_kinematic_path",
+            "values": { "error_code": { "val": 1 } },
+            "result": true
+        });
+        server
+            .send(Message::Text(response.to_string().into()))
+            .await
+            .unwrap();
+        assert_eq!(call.await.unwrap().unwrap()["error_code"]["val"], 1);
+
+        client.abort();
+    }
+
+    #[tokio::test]
+    async fn tls_server_streams_events_over_wss() {
+        use tokio_rustls::rustls::crypto::ring;
+        use tokio_rustls::rustls::pki_types::ServerName;
+        use tokio_rustls::rustls::{ClientConfig, RootCertStore};
+
+        let dir = std::env::temp_dir().join(format!("mechos-wss-{}", Uuid::new_v4()));
+        let tls = TlsConfig::new(dir.join("cert.pem"), dir.join("key.pem"))
+            .with_self_signed(["localhost"]);
+        let (_bus, bridge) = make_bridge();
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let server = tokio::spawn(bridge.clone().with_tls(tls.clone()).run_ws_server(addr));
+        let tcp = loop {
+            match tokio::net::TcpStream::connect(addr).await {
+                Ok(stream) => break stream,
+                Err(_) => tokio::time::sleep(Duration::from_millis(10)).await,
+            }
+        };
+
+        let mut roots = RootCertStore::empty();
+        let pem = std::fs::read(&tls.cert_path).unwrap();
+        for cert in rustls_pemfile::certs(&mut pem.as_slice()) {
+            roots.add(cert.unwrap()).unwrap();
+        }
+        let config = ClientConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        let stream = tokio_rustls::TlsConnector::from(Arc::new(config))
+            .connect(ServerName::try_from("localhost").unwrap(), tcp)
+            .await
+            .unwrap();
+        let (mut ws, _) = tokio_tungstenite::client_async("wss://localhost/", stream)
+            .await
+            .unwrap();
+
+        // The server subscribes to the bus just after the handshake.
+        let mut frame = None;
+        for _ in 0..50 {
+            let _ = bridge.ingest_fault("lidar", FaultCode::StaleSensor, "no scans");
+            if let Ok(Some(Ok(msg))) =
+                tokio::time::timeout(Duration::from_millis(100), ws.next()).await
+            {
+                frame = Some(msg.into_text().unwrap());
+                break;
+            }
+        }
+        assert!(frame.unwrap().contains("mechos-middleware::ros2/fault"));
+
+        server.abort();
+        std::fs::remove_dir_all(&dir).ok();
+    }
 }
@@ -0,0 +1,207 @@
+//! [`Responder`] – the serving side of request/response over the bus.
+//!
+//! A requester calls [`EventBus::request`], which publishes an event on a
+//! [`Topic`] and waits for the event whose `correlation_id` names it.  A
+//! responder subscribes to the same topic, picks out the requests it handles
+//! and answers each with [`Responder::reply`], which fills in the
+//! `correlation_id` (and carries the request's `trace_id` over):
+//!
+//! ```rust,no_run
+//! # async fn demo(bus: std::sync::Arc<mechos_middleware::EventBus>) {
+//! use mechos_middleware::{Topic, rpc::Responder};
+//! use mechos_types::{EventPayload, HumanAnswer};
+//!
+//! Responder::new(bus, Topic::CognitiveStream, "mechos-cockpit::hitl")
+//!     .serve(|request| async move {
+//!         match request.payload {
+//!             EventPayload::Reasoning(question) => {
+//!                 Some(HumanAnswer::new(format!("ack: {question}")).into())
+//!             }
+//!             _ => None,
+//!         }
+//!     })
+//!     .await;
+//! # }
+//! ```
+
+use std::future::Future;
+use std::sync::Arc;
+
+use chrono::Utc;
+use mechos_types::{Event, EventPayload, MechError};
+use tokio::sync::broadcast;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::bus::{EventBus, Topic, TopicReceiver};
+
+/// Answers [`EventBus::request`]s arriving on one topic.
+pub struct Responder {
+    bus: Arc<EventBus>,
+    topic: Topic,
+    source: String,
+    requests: TopicReceiver,
+}
+
+impl Responder {
+    /// Subscribe to `topic` on `bus`; replies are published with `source`.
+    pub fn new(bus: Arc<EventBus>, topic: Topic, source: impl Into<String>) -> Self {
+        let requests = bus.subscribe_to(topic);
+        Self {
+            bus,
+            topic,
+            source: source.into(),
+            requests,
+        }
+    }
+
+    /// The topic requests are read from and replies published to.
+    pub fn topic(&self) -> Topic {
+        self.topic
+    }
+
+    /// Wait for the next event on the topic that is not itself a reply.
+    ///
+    /// Returns `None` once the bus has shut down.
+    pub async fn next_request(&mut self) -> Option<Event> {
+        loop {
+            match self.requests.recv().await {
+                Ok(event) if event.correlation_id.is_none() => return Some(event),
+                Ok(_) => continue,
+                Err(broadcast::error::RecvError::Lagged(n)) => {
+                    warn!(topic = ?self.topic, lagged_by = n, "responder lagged");
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// Publish `payload` as the reply to `request`.
+    ///
+    /// # Errors
+    ///
+    /// As [`EventBus::publish_to`]; a requester that already timed out
+    /// shows up as [`MechError::Channel`] when it was the only listener.
+    pub fn reply(&self, request: &Event, payload: EventPayload) -> Result<usize, MechError> {
+        self.reply_to(request.id, request.trace_id.clone(), payload)
+    }
+
+    /// Answer requests with `handler` until the bus shuts down.
+    ///
+    /// `handler` returns the reply payload, or `None` for events this
+    /// responder does not handle (they are left unanswered).
+    pub async fn serve<F, Fut>(mut self, mut handler: F)
+    where
+        F: FnMut(Event) -> Fut,
+        Fut: Future<Output = Option<EventPayload>>,
+    {
+        while let Some(request) = self.next_request().await {
+            let id = request.id;
+            let trace_id = request.trace_id.clone();
+            if let Some(payload) = handler(request).await
+                && let Err(e) = self.reply_to(id, trace_id, payload)
+            {
+                warn!(%id, error = %e, "reply not delivered");
+            }
+        }
+    }
+
+    fn reply_to(
+        &self,
+        request_id: Uuid,
+        trace_id: Option<String>,
+        payload: EventPayload,
+    ) -> Result<usize, MechError> {
+        let reply = Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: self.source.clone(),
+            payload,
+            trace_id,
+            correlation_id: Some(request_id),
+        };
+        self.bus.publish_to(self.topic, reply)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use mechos_types::HumanAnswer;
+
+    fn question(text: &str) -> Event {
+        Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: "mechos-runtime::agent".to_string(),
+            payload: EventPayload::Reasoning(text.to_string()),
+            trace_id: Some("tracing:1".to_string()),
+            correlation_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn request_gets_the_matching_reply() {
+        let bus = Arc::new(EventBus::default());
+        let responder = Responder::new(Arc::clone(&bus), Topic::CognitiveStream, "operator");
+        tokio::spawn(responder.serve(|request| async move {
+            match request.payload {
+                EventPayload::Reasoning(q) if q != "ignore me" => {
+                    // Answer slowly so concurrent requests overlap.
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    Some(HumanAnswer::new(format!("re: {q}")).into())
+                }
+                _ => None,
+            }
+        }));
+
+        let first = question("first");
+        let (a, b) = tokio::join!(
+            bus.request(
+                Topic::CognitiveStream,
+                first.clone(),
+                Duration::from_secs(2)
+            ),
+            bus.request(
+                Topic::CognitiveStream,
+                question("second"),
+                Duration::from_secs(2)
+            ),
+        );
+        let a = a.unwrap();
+        assert_eq!(a.correlation_id, Some(first.id));
+        assert_eq!(a.trace_id.as_deref(), Some("tracing:1"));
+        assert_eq!(a.source, "operator");
+        assert!(matches!(a.payload, EventPayload::HumanResponse(ref r) if r.answer == "re: first"));
+        assert!(
+            matches!(b.unwrap().payload, EventPayload::HumanResponse(ref r) if r.answer == "re: second")
+        );
+
+        let unanswered = bus
+            .request(
+                Topic::CognitiveStream,
+                question("ignore me"),
+                Duration::from_millis(50),
+            )
+            .await;
+        assert!(matches!(unanswered, Err(MechError::Channel(_))));
+    }
+
+    #[tokio::test]
+    async fn request_without_a_listener_fails_fast() {
+        let bus = EventBus::default();
+        let result = bus
+            .request(
+                Topic::SystemAlerts,
+                question("anyone?"),
+                Duration::from_secs(5),
+            )
+            .await;
+        assert!(matches!(result, Err(MechError::Channel(ref m)) if m.contains("No subscribers")));
+    }
+}
@@ -0,0 +1,564 @@
+//! Serial/UART adapter for microcontroller-driven bases.
+//!
+//! [`SerialAdapter`] drives robots whose base is a microcontroller (e.g. an
+//! Arduino on `/dev/ttyUSB0`) over a serial port.  Two framings are
+//! supported, selected with [`SerialAdapter::with_framing`]:
+//!
+//! * [`SerialFraming::Line`] (default) – ASCII, one space-separated frame per
+//!   `\n`-terminated line, easy to parse with `Serial.readStringUntil('\n')`.
+//! * [`SerialFraming::Cobs`] – compact binary frames (a one-byte tag
+//!   followed by little-endian fields), COBS-encoded and `0x00`-delimited.
+//!
+//! | Tag | Direction | Line form | Meaning |
+//! |---|---|---|---|
+//! | `D` | out | `D <linear m/s> <angular rad/s>` | [`HardwareIntent::Drive`] |
+//! | `H` | out | `H` | [`HardwareIntent::Halt`] |
+//! | `R` | out | `R <relay_id> <0\|1>` | [`HardwareIntent::TriggerRelay`] |
+//! | `J` | out | `J <joint> <angle rad> <max rad/s>` | [`HardwareIntent::MoveJoint`] |
+//! | `G` | out | `G <position>` | [`HardwareIntent::SetGripper`] |
+//! | `K` / `U` | out | `K` / `U` | [`HardwareIntent::Dock`] / [`HardwareIntent::Undock`] |
+//! | `T` | in | `T <x m> <y m> <heading rad> <battery %>` | [`EventPayload::Telemetry`] |
+//! | `B` | in | `B <volts> <amps> <0\|1 charging> <percent>` | [`EventPayload::PowerStatus`] |
+//! | `S` | in | `S <angle_min rad> <increment rad> <range>…` | [`EventPayload::LidarScan`] |
+//!
+//! Binary frames carry the same fields in the same order: numbers as `f32`,
+//! flags and the battery percentage as `u8`, and the relay or joint name as
+//! the trailing UTF-8 bytes.
+//!
+//! [`SerialAdapter::run`] opens the port and reopens it after the device is
+//! unplugged.  Intents are refused while the port is disconnected, so no
+//! stale command is replayed on reconnect.  [`SerialAdapter::health`]
+//! reports the connection state and the time of the last valid frame; feed
+//! the latter to the kernel's `Watchdog::heartbeat_at` under
+//! [`SerialAdapter::WATCHDOG_COMPONENT`] to detect a silent base.
+
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use futures_util::stream::{self, BoxStream};
+use mechos_types::{
+    Event, EventPayload, FaultCode, HardwareIntent, MechError, Meters, TelemetryData,
+};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::sync::mpsc;
+use tokio_serial::SerialPortBuilderExt;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::adapter::MechAdapter;
+use crate::bus::EventBus;
+use crate::ros2_adapter::MAX_LIDAR_RANGES;
+
+/// Maximum length of one inbound frame, before COBS decoding.
+///
+/// Longer frames are discarded up to the next delimiter.
+pub const MAX_SERIAL_FRAME_BYTES: usize = 32 * 1024; // 32 KiB
+
+/// Number of encoded commands that may wait for the port.
+const COMMAND_QUEUE_CAPACITY: usize = 8;
+
+/// Pause before reopening the port after it failed or disappeared.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// How frames are delimited on the wire.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SerialFraming {
+    /// ASCII frames terminated by `\n` (a preceding `\r` is ignored).
+    #[default]
+    Line,
+    /// Binary frames, COBS-encoded and terminated by `0x00`.
+    Cobs,
+}
+
+impl SerialFraming {
+    fn delimiter(self) -> u8 {
+        match self {
+            SerialFraming::Line => b'\n',
+            SerialFraming::Cobs => 0x00,
+        }
+    }
+}
+
+/// Snapshot of a [`SerialAdapter`]'s link state.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SerialHealth {
+    /// `true` while the port is open.
+    pub connected: bool,
+    /// When the last valid frame was received from the device.
+    pub last_frame_at: Option<Instant>,
+    /// How many times the port has been (re)opened.
+    pub connections: u64,
+}
+
+/// Adapter that exchanges compact frames with a microcontroller over a
+/// serial port.
+pub struct SerialAdapter {
+    bus: Arc<EventBus>,
+    path: String,
+    baud_rate: u32,
+    framing: SerialFraming,
+    commands: mpsc::Sender<Vec<u8>>,
+    outbox: tokio::sync::Mutex<mpsc::Receiver<Vec<u8>>>,
+    health: Mutex<SerialHealth>,
+}
+
+impl SerialAdapter {
+    /// Component ID to register with the kernel watchdog.
+    pub const WATCHDOG_COMPONENT: &'static str = "serial_adapter";
+
+    /// Create a new [`SerialAdapter`] for the device at `path` (e.g.
+    /// `"/dev/ttyUSB0"` or `"COM3"`) using line framing.
+    pub fn new(bus: Arc<EventBus>, path: impl Into<String>, baud_rate: u32) -> Self {
+        let (commands, outbox) = mpsc::channel(COMMAND_QUEUE_CAPACITY);
+        Self {
+            bus,
+            path: path.into(),
+            baud_rate,
+            framing: SerialFraming::default(),
+            commands,
+            outbox: tokio::sync::Mutex::new(outbox),
+            health: Mutex::new(SerialHealth::default()),
+        }
+    }
+
+    pub fn with_framing(mut self, framing: SerialFraming) -> Self {
+        self.framing = framing;
+        self
+    }
+
+    /// Return the serial device path this adapter is configured to use.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Current link state.
+    pub fn health(&self) -> SerialHealth {
+        self.health
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Encode `intent` as one delimited frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::HardwareFault`] with [`FaultCode::Unsupported`]
+    /// for intents a serial base cannot perform (speech, navigation goals,
+    /// fleet messages, …).
+    pub fn encode_intent(&self, intent: &HardwareIntent) -> Result<Vec<u8>, MechError> {
+        let (line, binary) = match intent {
+            HardwareIntent::Drive {
+                linear_velocity,
+                angular_velocity,
+            } => {
+                let (v, w) = (linear_velocity.get(), angular_velocity.get());
+                (format!("D {v:.3} {w:.3}"), tagged(b'D', &[v, w], &[]))
+            }
+            HardwareIntent::Halt { .. } => ("H".to_string(), vec![b'H']),
+            HardwareIntent::TriggerRelay { relay_id, state } => {
+                let mut binary = vec![b'R', u8::from(*state)];
+                binary.extend_from_slice(relay_id.as_bytes());
+                (format!("R {relay_id} {}", u8::from(*state)), binary)
+            }
+            HardwareIntent::MoveJoint {
+                joint,
+                angle_rad,
+                max_velocity,
+            } => {
+                let max = max_velocity.get();
+                (
+                    format!("J {joint} {angle_rad:.4} {max:.3}"),
+                    tagged(b'J', &[*angle_rad, max], joint.as_bytes()),
+                )
+            }
+            HardwareIntent::SetGripper { position } => {
+                (format!("G {position:.3}"), tagged(b'G', &[*position], &[]))
+            }
+            HardwareIntent::Dock => ("K".to_string(), vec![b'K']),
+            HardwareIntent::Undock => ("U".to_string(), vec![b'U']),
+            other => {
+                return Err(MechError::HardwareFault {
+                    code: FaultCode::Unsupported,
+                    component: "serial".to_string(),
+                    details: format!("intent not supported over serial: {other:?}"),
+                });
+            }
+        };
+        let mut frame = match self.framing {
+            SerialFraming::Line => line.into_bytes(),
+            SerialFraming::Cobs => cobs::encode_vec(&binary),
+        };
+        frame.push(self.framing.delimiter());
+        Ok(frame)
+    }
+
+    /// Parse one received frame (without its delimiter) and publish it on
+    /// the bus.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Parsing`] for malformed frames, unknown tags and
+    /// scans with more than [`MAX_LIDAR_RANGES`] readings.
+    pub fn ingest_frame(&self, frame: &[u8]) -> Result<usize, MechError> {
+        let payload = match self.framing {
+            SerialFraming::Line => parse_line(frame)?,
+            SerialFraming::Cobs => {
+                let decoded = cobs::decode_vec(frame)
+                    .map_err(|e| MechError::Parsing(format!("invalid COBS frame: {e}")))?;
+                parse_binary(&decoded)?
+            }
+        };
+        self.health
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .last_frame_at = Some(Instant::now());
+        self.bus.publish(Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: format!("mechos-middleware::serial/{}", self.path),
+            payload,
+            trace_id: None,
+            correlation_id: None,
+        })
+    }
+
+    /// Open the port and exchange frames with it forever, reopening it
+    /// [`RECONNECT_DELAY`] after every failure or unplug.
+    pub async fn run(&self) {
+        loop {
+            match tokio_serial::new(&self.path, self.baud_rate).open_native_async() {
+                Ok(port) => {
+                    info!(path = %self.path, "serial port opened");
+                    let e = self.serve(port).await;
+                    warn!(path = %self.path, error = %e, "serial port lost; reconnecting");
+                }
+                Err(e) => warn!(path = %self.path, error = %e, "cannot open serial port"),
+            }
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    }
+
+    /// Exchange frames over an open `port` until it fails, returning the
+    /// error that ended the connection.  Commands still queued when the
+    /// connection ends are discarded.
+    pub async fn serve<P>(&self, port: P) -> io::Error
+    where
+        P: AsyncRead + AsyncWrite + Unpin,
+    {
+        let mut outbox = self.outbox.lock().await;
+        self.set_connected(true);
+        let error = self.exchange(port, &mut outbox).await;
+        self.set_connected(false);
+        while outbox.try_recv().is_ok() {}
+        error
+    }
+
+    // -------------------------------------------------------------------------
+    // Private helpers
+    // -------------------------------------------------------------------------
+
+    async fn exchange<P>(&self, mut port: P, outbox: &mut mpsc::Receiver<Vec<u8>>) -> io::Error
+    where
+        P: AsyncRead + AsyncWrite + Unpin,
+    {
+        let delimiter = self.framing.delimiter();
+        let mut frame = Vec::new();
+        let mut overflow = false;
+        let mut buf = [0u8; 1024];
+        loop {
+            tokio::select! {
+                read = port.read(&mut buf) => {
+                    let n = match read {
+                        Ok(0) => return io::ErrorKind::UnexpectedEof.into(),
+                        Ok(n) => n,
+                        Err(e) => return e,
+                    };
+                    for &byte in &buf[..n] {
+                        if byte != delimiter {
+                            overflow |= frame.len() >= MAX_SERIAL_FRAME_BYTES;
+                            if !overflow {
+                                frame.push(byte);
+                            }
+                            continue;
+                        }
+                        if overflow {
+                            warn!(path = %self.path, "dropping oversized serial frame");
+                        } else if !frame.is_empty()
+                            && let Err(e) = self.ingest_frame(&frame)
+                        {
+                            warn!(path = %self.path, error = %e, "dropping serial frame");
+                        }
+                        frame.clear();
+                        overflow = false;
+                    }
+                }
+                Some(command) = outbox.recv() => {
+                    if let Err(e) = port.write_all(&command).await {
+                        return e;
+                    }
+                }
+            }
+        }
+    }
+
+    fn set_connected(&self, connected: bool) {
+        let mut health = self.health.lock().unwrap_or_else(|e| e.into_inner());
+        health.connected = connected;
+        if connected {
+            health.connections += 1;
+        }
+    }
+}
+
+/// A binary frame: `tag`, then `values` as little-endian `f32`, then `tail`.
+fn tagged(tag: u8, values: &[f32], tail: &[u8]) -> Vec<u8> {
+    let mut out = vec![tag];
+    for v in values {
+        out.extend_from_slice(&v.to_le_bytes());
+    }
+    out.extend_from_slice(tail);
+    out
+}
+
+fn parse_line(frame: &[u8]) -> Result<EventPayload, MechError> {
+    let text = std::str::from_utf8(frame)
+        .map_err(|_| MechError::Parsing("serial frame is not UTF-8".to_string()))?;
+    let mut fields = text.split_whitespace();
+    let tag = fields.next().unwrap_or_default();
+    let numbers = fields
+        .map(|f| f.parse::<f32>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| MechError::Parsing(format!("bad number in serial frame '{text}': {e}")))?;
+    match (tag, numbers.as_slice()) {
+        ("T", &[x, y, heading, battery]) => Ok(telemetry(x, y, heading, battery as u8)),
+        ("B", &[voltage, current, charging, percent]) => {
+            Ok(power_status(voltage, current, charging != 0.0, percent))
+        }
+        ("S", [angle_min, increment, ranges @ ..]) => lidar_scan(*angle_min, *increment, ranges),
+        _ => Err(MechError::Parsing(format!(
+            "unrecognised serial frame '{text}'"
+        ))),
+    }
+}
+
+fn parse_binary(frame: &[u8]) -> Result<EventPayload, MechError> {
+    let malformed = || MechError::Parsing(format!("malformed binary serial frame {frame:02x?}"));
+    let (&tag, body) = frame.split_first().ok_or_else(malformed)?;
+    let f32_at = |i: usize| -> Result<f32, MechError> {
+        body.get(i..i + 4)
+            .map(|b| f32::from_le_bytes(b.try_into().expect("4 bytes")))
+            .ok_or_else(malformed)
+    };
+    match (tag, body.len()) {
+        (b'T', 13) => Ok(telemetry(f32_at(0)?, f32_at(4)?, f32_at(8)?, body[12])),
+        (b'B', 13) => Ok(power_status(
+            f32_at(0)?,
+            f32_at(4)?,
+            body[8] != 0,
+            f32_at(9)?,
+        )),
+        (b'S', len) if len >= 8 && len % 4 == 0 => {
+            let ranges = (8..len)
+                .step_by(4)
+                .map(f32_at)
+                .collect::<Result<Vec<_>, _>>()?;
+            lidar_scan(f32_at(0)?, f32_at(4)?, &ranges)
+        }
+        _ => Err(malformed()),
+    }
+}
+
+fn telemetry(x: f32, y: f32, heading_rad: f32, battery_percent: u8) -> EventPayload {
+    EventPayload::Telemetry(TelemetryData {
+        position_x: Meters(x),
+        position_y: Meters(y),
+        heading_rad,
+        battery_percent,
+        stamp: None,
+    })
+}
+
+fn power_status(voltage: f32, current: f32, charging: bool, percent: f32) -> EventPayload {
+    EventPayload::PowerStatus {
+        voltage,
+        current,
+        charging,
+        percent,
+    }
+}
+
+fn lidar_scan(
+    angle_min_rad: f32,
+    angle_increment_rad: f32,
+    ranges: &[f32],
+) -> Result<EventPayload, MechError> {
+    if ranges.len() > MAX_LIDAR_RANGES {
+        return Err(MechError::Parsing(format!(
+            "serial scan has {} range readings, exceeding the limit of {}",
+            ranges.len(),
+            MAX_LIDAR_RANGES,
+        )));
+    }
+    Ok(EventPayload::LidarScan {
+        ranges: ranges.to_vec(),
+        angle_min_rad,
+        angle_increment_rad,
+        stamp: None,
+    })
+}
+
+#[async_trait]
+impl MechAdapter for SerialAdapter {
+    /// Encode the intent with [`SerialAdapter::encode_intent`] and queue it
+    /// for the port.
+    ///
+    /// # Errors
+    ///
+    /// Besides unsupported intents, returns [`MechError::Channel`] while the
+    /// port is disconnected or its command queue is full.
+    async fn execute_intent(&self, intent: HardwareIntent) -> Result<(), MechError> {
+        let frame = self.encode_intent(&intent)?;
+        if !self.health().connected {
+            return Err(MechError::Channel(format!(
+                "serial port '{}' is disconnected",
+                self.path
+            )));
+        }
+        self.commands.try_send(frame).map_err(|e| {
+            MechError::Channel(format!("serial port '{}' command queue: {e}", self.path))
+        })
+    }
+
+    /// Frames are pushed onto the bus by [`SerialAdapter::run`]; this
+    /// returns an empty stream.
+    async fn sensor_stream(&self) -> BoxStream<'static, EventPayload> {
+        Box::pin(stream::empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mechos_types::{MetersPerSecond, RadiansPerSecond};
+    use tokio::io::{AsyncBufReadExt, BufReader};
+
+    fn make_adapter(framing: SerialFraming) -> (Arc<EventBus>, Arc<SerialAdapter>) {
+        let bus = Arc::new(EventBus::default());
+        let adapter =
+            SerialAdapter::new(Arc::clone(&bus), "/dev/ttyUSB0", 115_200).with_framing(framing);
+        (bus, Arc::new(adapter))
+    }
+
+    fn drive() -> HardwareIntent {
+        HardwareIntent::Drive {
+            linear_velocity: MetersPerSecond(0.25),
+            angular_velocity: RadiansPerSecond(-0.5),
+        }
+    }
+
+    #[test]
+    fn intents_encode_as_lines() {
+        let (_, adapter) = make_adapter(SerialFraming::Line);
+        assert_eq!(
+            adapter.encode_intent(&drive()).unwrap(),
+            b"D 0.250 -0.500\n"
+        );
+        let relay = HardwareIntent::TriggerRelay {
+            relay_id: "pump".to_string(),
+            state: true,
+        };
+        assert_eq!(adapter.encode_intent(&relay).unwrap(), b"R pump 1\n");
+        assert!(matches!(
+            adapter.encode_intent(&HardwareIntent::PlaySound {
+                sound_id: "chime".to_string()
+            }),
+            Err(MechError::HardwareFault {
+                code: FaultCode::Unsupported,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn cobs_frames_round_trip() {
+        let (_, adapter) = make_adapter(SerialFraming::Cobs);
+        let frame = adapter.encode_intent(&drive()).unwrap();
+        assert_eq!(frame.last(), Some(&0));
+        assert!(!frame[..frame.len() - 1].contains(&0));
+        let decoded = cobs::decode_vec(&frame[..frame.len() - 1]).unwrap();
+        assert_eq!(decoded, tagged(b'D', &[0.25, -0.5], &[]));
+
+        let scan = cobs::encode_vec(&tagged(b'S', &[0.0, 0.1, 1.5, 2.5], &[]));
+        assert!(matches!(
+            parse_binary(&cobs::decode_vec(&scan).unwrap()).unwrap(),
+            EventPayload::LidarScan { ranges, .. } if ranges == vec![1.5, 2.5]
+        ));
+        let mut power = tagged(b'B', &[12.0, -1.0], &[1]);
+        power.extend_from_slice(&55.0f32.to_le_bytes());
+        assert!(matches!(
+            parse_binary(&power).unwrap(),
+            EventPayload::PowerStatus { charging: true, percent, .. } if percent == 55.0
+        ));
+        assert!(parse_binary(&[b'T', 1, 2]).is_err());
+    }
+
+    #[test]
+    fn line_frames_parse_into_payloads() {
+        assert!(matches!(
+            parse_line(b"T 1.5 -2 0.5 80").unwrap(),
+            EventPayload::Telemetry(t) if t.position_x == Meters(1.5) && t.battery_percent == 80
+        ));
+        assert!(matches!(
+            parse_line(b"B 12.4 -0.8 0 64").unwrap(),
+            EventPayload::PowerStatus {
+                charging: false,
+                ..
+            }
+        ));
+        assert!(parse_line(b"T 1 2").is_err());
+        assert!(parse_line(b"X 1").is_err());
+        assert!(parse_line(b"T a b c d").is_err());
+    }
+
+    #[tokio::test]
+    async fn serve_exchanges_frames_and_tracks_health() {
+        let (bus, adapter) = make_adapter(SerialFraming::Line);
+        let mut rx = bus.subscribe();
+        assert!(matches!(
+            adapter.execute_intent(drive()).await,
+            Err(MechError::Channel(_))
+        ));
+
+        let (port, device) = tokio::io::duplex(1024);
+        let serving = tokio::spawn({
+            let adapter = Arc::clone(&adapter);
+            async move { adapter.serve(port).await }
+        });
+        let (device_rx, mut device_tx) = tokio::io::split(device);
+        let mut device_rx = BufReader::new(device_rx);
+
+        device_tx.write_all(b"T 1 2 0.5 90\r\n").await.unwrap();
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.source, "mechos-middleware::serial//dev/ttyUSB0");
+        assert!(matches!(event.payload, EventPayload::Telemetry(_)));
+        let health = adapter.health();
+        assert!(health.connected && health.last_frame_at.is_some());
+        assert_eq!(health.connections, 1);
+
+        adapter.execute_intent(drive()).await.unwrap();
+        let mut line = String::new();
+        device_rx.read_line(&mut line).await.unwrap();
+        assert_eq!(line, "D 0.250 -0.500\n");
+
+        // Unplugging the device ends the session and refuses new intents.
+        drop((device_rx, device_tx));
+        let error = serving.await.unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+        assert!(!adapter.health().connected);
+        assert!(adapter.execute_intent(drive()).await.is_err());
+    }
+}
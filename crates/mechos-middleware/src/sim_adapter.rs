@@ -0,0 +1,886 @@
+//! Full-physics simulation adapter for Gazebo and Webots.
+//!
+//! [`SimAdapter`] drives a robot simulated by Gazebo or Webots over a
+//! WebSocket, as a physics-accurate alternative to the Three.js
+//! [`DashboardSimAdapter`][crate::DashboardSimAdapter]:
+//!
+//! * [`SimBackend::Gazebo`] – the `websocket_server` plugin of `gz launch`
+//!   (default [`DEFAULT_GAZEBO_URL`]), which exposes gz-transport topics.
+//!   Frames are `<op>,<topic>,<type>,<payload>` with protobuf payloads:
+//!   the adapter subscribes to the scan and odometry topics on connect,
+//!   decodes `gz.msgs.LaserScan` / `gz.msgs.Odometry`, and publishes
+//!   velocity commands as `gz.msgs.Twist`.
+//! * [`SimBackend::Webots`] – a Webots robot controller that relays JSON
+//!   text messages, each naming its topic:
+//!
+//! | Direction | Message |
+//! |---|---|
+//! | out | `{"topic":"/cmd_vel","linear":0.2,"angular":-0.1}` |
+//! | in | `{"topic":"/scan","angle_min":-1.57,"angle_increment":0.01,"ranges":[…]}` |
+//! | in | `{"topic":"/odom","x":1.0,"y":2.0,"yaw":0.5}` |
+//!
+//! Scans are published as [`EventPayload::LidarScan`] and odometry as
+//! [`EventPayload::Telemetry`].  `Drive` and `Halt` are supported; other
+//! intents are refused with [`FaultCode::Unsupported`].
+//!
+//! [`SimAdapter::run`] connects and reconnects after the simulator
+//! restarts.  Intents are refused while disconnected.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use futures_util::stream::{self, BoxStream};
+use futures_util::{Sink, SinkExt, Stream, StreamExt};
+use mechos_types::{
+    Event, EventPayload, FaultCode, HardwareIntent, MechError, Meters, MetersPerSecond,
+    RadiansPerSecond, TelemetryData,
+};
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::{self, Message};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::adapter::MechAdapter;
+use crate::bus::EventBus;
+use crate::dds::{LaserScanMsg, OdometryMsg};
+use crate::ros2_adapter::MAX_LIDAR_RANGES;
+
+/// Default URL of the Gazebo `websocket_server` plugin.
+pub const DEFAULT_GAZEBO_URL: &str = "ws://localhost:9002";
+
+/// Number of encoded commands that may wait for the connection.
+const COMMAND_QUEUE_CAPACITY: usize = 8;
+
+/// Pause before reconnecting after the simulator went away.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Which simulator is on the other end of the WebSocket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimBackend {
+    /// Gazebo (gz-sim) through the `websocket_server` plugin.
+    Gazebo,
+    /// Webots through a JSON-relaying robot controller.
+    Webots,
+}
+
+impl SimBackend {
+    fn name(self) -> &'static str {
+        match self {
+            SimBackend::Gazebo => "gazebo",
+            SimBackend::Webots => "webots",
+        }
+    }
+}
+
+/// Simulator topic names (e.g. `/model/vehicle/cmd_vel` for a Gazebo
+/// `DiffDrive` system).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimTopics {
+    pub cmd_vel: String,
+    pub scan: String,
+    pub odom: String,
+}
+
+impl Default for SimTopics {
+    fn default() -> Self {
+        Self {
+            cmd_vel: "/cmd_vel".to_string(),
+            scan: "/scan".to_string(),
+            odom: "/odom".to_string(),
+        }
+    }
+}
+
+/// Adapter that drives a Gazebo or Webots simulation.
+pub struct SimAdapter {
+    bus: Arc<EventBus>,
+    backend: SimBackend,
+    url: String,
+    topics: SimTopics,
+    commands: mpsc::Sender<Message>,
+    outbox: tokio::sync::Mutex<mpsc::Receiver<Message>>,
+    connected: AtomicBool,
+}
+
+impl SimAdapter {
+    /// Create a new [`SimAdapter`] for the simulator endpoint at `url`.
+    pub fn new(bus: Arc<EventBus>, backend: SimBackend, url: impl Into<String>) -> Self {
+        let (commands, outbox) = mpsc::channel(COMMAND_QUEUE_CAPACITY);
+        Self {
+            bus,
+            backend,
+            url: url.into(),
+            topics: SimTopics::default(),
+            commands,
+            outbox: tokio::sync::Mutex::new(outbox),
+            connected: AtomicBool::new(false),
+        }
+    }
+
+    pub fn with_topics(mut self, topics: SimTopics) -> Self {
+        self.topics = topics;
+        self
+    }
+
+    pub fn backend(&self) -> SimBackend {
+        self.backend
+    }
+
+    /// Return the simulator URL this adapter is configured to use.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// `true` while connected to the simulator.
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
+    /// Encode `intent` as the message sent to the simulator.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::HardwareFault`] with [`FaultCode::Unsupported`]
+    /// for intents other than `Drive` and `Halt`.
+    pub fn encode_intent(&self, intent: &HardwareIntent) -> Result<Message, MechError> {
+        let (linear, angular) = match intent {
+            HardwareIntent::Drive {
+                linear_velocity,
+                angular_velocity,
+            } => (*linear_velocity, *angular_velocity),
+            HardwareIntent::Halt { .. } => (MetersPerSecond::ZERO, RadiansPerSecond::ZERO),
+            other => {
+                return Err(MechError::HardwareFault {
+                    code: FaultCode::Unsupported,
+                    component: "sim".to_string(),
+                    details: format!(
+                        "the {} adapter cannot execute {other:?}",
+                        self.backend.name()
+                    ),
+                });
+            }
+        };
+        Ok(match self.backend {
+            SimBackend::Gazebo => {
+                let mut frame = format!("pub,{},gz.msgs.Twist,", self.topics.cmd_vel).into_bytes();
+                frame.extend_from_slice(&encode_gz_twist(linear, angular));
+                Message::binary(frame)
+            }
+            SimBackend::Webots => Message::text(
+                json!({
+                    "topic": self.topics.cmd_vel,
+                    "linear": linear,
+                    "angular": angular,
+                })
+                .to_string(),
+            ),
+        })
+    }
+
+    /// Messages sent right after connecting: Gazebo subscriptions to the
+    /// scan and odometry topics.
+    pub fn handshake(&self) -> Vec<Message> {
+        match self.backend {
+            SimBackend::Gazebo => [&self.topics.scan, &self.topics.odom]
+                .into_iter()
+                .map(|topic| Message::text(format!("sub,{topic},,")))
+                .collect(),
+            SimBackend::Webots => Vec::new(),
+        }
+    }
+
+    /// Decode one message from the simulator and publish it on the bus.
+    ///
+    /// Returns the number of subscribers that received the event, or `0`
+    /// for messages on other topics and control frames.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Parsing`] for malformed scan or odometry
+    /// messages and scans with more than [`MAX_LIDAR_RANGES`] readings.
+    pub fn ingest_message(&self, message: &Message) -> Result<usize, MechError> {
+        let sensor = match (self.backend, message) {
+            (SimBackend::Gazebo, Message::Binary(bytes)) => self.decode_gazebo(bytes)?,
+            (SimBackend::Gazebo, Message::Text(text)) => self.decode_gazebo(text.as_bytes())?,
+            (SimBackend::Webots, Message::Text(text)) => self.decode_webots(text.as_str())?,
+            _ => None,
+        };
+        let Some((topic, payload)) = sensor else {
+            return Ok(0);
+        };
+        self.bus.publish(Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: format!("mechos-middleware::{}/{}", self.backend.name(), topic),
+            payload,
+            trace_id: None,
+            correlation_id: None,
+        })
+    }
+
+    /// Connect to the simulator forever, reconnecting after it restarts.
+    pub async fn run(&self) {
+        loop {
+            match tokio_tungstenite::connect_async(&self.url).await {
+                Ok((ws, _)) => {
+                    info!(url = %self.url, backend = self.backend.name(), "simulator connected");
+                    let e = self.serve(ws).await;
+                    warn!(url = %self.url, error = %e, "simulator connection lost; reconnecting");
+                }
+                Err(e) => warn!(url = %self.url, error = %e, "cannot connect to simulator"),
+            }
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    }
+
+    /// Exchange messages over an open WebSocket until it closes, returning
+    /// why.  Commands still queued when the connection ends are discarded.
+    pub async fn serve<S>(&self, ws: S) -> MechError
+    where
+        S: Stream<Item = Result<Message, tungstenite::Error>>
+            + Sink<Message, Error = tungstenite::Error>
+            + Unpin,
+    {
+        let mut outbox = self.outbox.lock().await;
+        self.connected.store(true, Ordering::Relaxed);
+        let error = self.exchange(ws, &mut outbox).await;
+        self.connected.store(false, Ordering::Relaxed);
+        while outbox.try_recv().is_ok() {}
+        error
+    }
+
+    // -------------------------------------------------------------------------
+    // Private helpers
+    // -------------------------------------------------------------------------
+
+    async fn exchange<S>(&self, mut ws: S, outbox: &mut mpsc::Receiver<Message>) -> MechError
+    where
+        S: Stream<Item = Result<Message, tungstenite::Error>>
+            + Sink<Message, Error = tungstenite::Error>
+            + Unpin,
+    {
+        let channel_error = |e: tungstenite::Error| {
+            MechError::Channel(format!("simulator WebSocket '{}': {e}", self.url))
+        };
+        for message in self.handshake() {
+            if let Err(e) = ws.send(message).await {
+                return channel_error(e);
+            }
+        }
+        loop {
+            tokio::select! {
+                received = ws.next() => match received {
+                    Some(Ok(Message::Close(_))) | None => {
+                        return MechError::Channel(format!(
+                            "simulator WebSocket '{}' closed",
+                            self.url
+                        ));
+                    }
+                    Some(Ok(message)) => {
+                        if let Err(e) = self.ingest_message(&message) {
+                            warn!(url = %self.url, error = %e, "dropping simulator message");
+                        }
+                    }
+                    Some(Err(e)) => return channel_error(e),
+                },
+                Some(command) = outbox.recv() => {
+                    if let Err(e) = ws.send(command).await {
+                        return channel_error(e);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Split a `<op>,<topic>,<type>,<payload>` frame and decode `pub`
+    /// frames on the scan and odometry topics.
+    fn decode_gazebo(
+        &self,
+        frame: &[u8],
+    ) -> Result<Option<(&'static str, EventPayload)>, MechError> {
+        let mut parts = frame.splitn(4, |&b| b == b',');
+        let (Some(op), Some(topic), Some(_), Some(payload)) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Ok(None);
+        };
+        if op != b"pub" {
+            return Ok(None);
+        }
+        if topic == self.topics.scan.as_bytes() {
+            let scan = decode_gz_laser_scan(payload)?;
+            Ok(Some(("scan", scan_payload(scan)?)))
+        } else if topic == self.topics.odom.as_bytes() {
+            Ok(Some(("odom", odom_payload(decode_gz_odometry(payload)?))))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn decode_webots(&self, text: &str) -> Result<Option<(&'static str, EventPayload)>, MechError> {
+        #[derive(Deserialize)]
+        struct Header {
+            topic: String,
+        }
+        #[derive(Deserialize)]
+        struct Scan {
+            angle_min: f32,
+            angle_increment: f32,
+            ranges: Vec<f32>,
+        }
+        #[derive(Deserialize)]
+        struct Odom {
+            x: f64,
+            y: f64,
+            yaw: f64,
+        }
+        let parse_error = |e: serde_json::Error| MechError::Parsing(format!("Webots message: {e}"));
+
+        let header: Header = serde_json::from_str(text).map_err(parse_error)?;
+        if header.topic == self.topics.scan {
+            let scan: Scan = serde_json::from_str(text).map_err(parse_error)?;
+            let scan = LaserScanMsg {
+                stamp: None,
+                angle_min: scan.angle_min,
+                angle_increment: scan.angle_increment,
+                ranges: scan.ranges,
+            };
+            Ok(Some(("scan", scan_payload(scan)?)))
+        } else if header.topic == self.topics.odom {
+            let odom: Odom = serde_json::from_str(text).map_err(parse_error)?;
+            Ok(Some((
+                "odom",
+                odom_payload(OdometryMsg {
+                    stamp: None,
+                    position_x: odom.x,
+                    position_y: odom.y,
+                    yaw_rad: odom.yaw,
+                    linear_x: 0.0,
+                    angular_z: 0.0,
+                }),
+            )))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+fn scan_payload(scan: LaserScanMsg) -> Result<EventPayload, MechError> {
+    if scan.ranges.len() > MAX_LIDAR_RANGES {
+        return Err(MechError::Parsing(format!(
+            "simulated scan has {} range readings, exceeding the limit of {}",
+            scan.ranges.len(),
+            MAX_LIDAR_RANGES,
+        )));
+    }
+    Ok(EventPayload::LidarScan {
+        ranges: scan.ranges,
+        angle_min_rad: scan.angle_min,
+        angle_increment_rad: scan.angle_increment,
+        stamp: scan.stamp,
+    })
+}
+
+/// Odometry carries no charge level, so `battery_percent` is `0`.
+fn odom_payload(odom: OdometryMsg) -> EventPayload {
+    EventPayload::Telemetry(TelemetryData {
+        position_x: Meters(odom.position_x as f32),
+        position_y: Meters(odom.position_y as f32),
+        heading_rad: odom.yaw_rad as f32,
+        battery_percent: 0,
+        stamp: odom.stamp,
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// gz-msgs protobuf
+// ─────────────────────────────────────────────────────────────────────────────
+
+const WIRE_VARINT: u64 = 0;
+const WIRE_FIXED64: u64 = 1;
+const WIRE_LEN: u64 = 2;
+const WIRE_FIXED32: u64 = 5;
+
+/// One decoded protobuf field value.
+enum Field<'a> {
+    Fixed64(u64),
+    Len(&'a [u8]),
+    /// A varint or 32-bit value; gz-msgs fields MechOS reads use neither.
+    Other,
+}
+
+impl Field<'_> {
+    fn as_f64(&self) -> f64 {
+        match *self {
+            Field::Fixed64(bits) => f64::from_bits(bits),
+            _ => 0.0,
+        }
+    }
+
+    fn as_message(&self) -> &[u8] {
+        match *self {
+            Field::Len(bytes) => bytes,
+            _ => &[],
+        }
+    }
+}
+
+/// Iterate over the `(field number, value)` pairs of a protobuf message.
+/// Iteration stops after the first malformed field.
+fn proto_fields(mut buf: &[u8]) -> impl Iterator<Item = Result<(u64, Field<'_>), MechError>> {
+    std::iter::from_fn(move || {
+        if buf.is_empty() {
+            return None;
+        }
+        let field = next_field(&mut buf);
+        if field.is_err() {
+            buf = &[];
+        }
+        Some(field)
+    })
+}
+
+fn next_field<'a>(buf: &mut &'a [u8]) -> Result<(u64, Field<'a>), MechError> {
+    let malformed = || MechError::Parsing("malformed protobuf message".to_string());
+    let key = read_varint(buf).ok_or_else(malformed)?;
+    let field = match key & 0x7 {
+        WIRE_VARINT => {
+            read_varint(buf).ok_or_else(malformed)?;
+            Field::Other
+        }
+        WIRE_FIXED64 => {
+            let (bytes, rest) = buf.split_first_chunk::<8>().ok_or_else(malformed)?;
+            *buf = rest;
+            Field::Fixed64(u64::from_le_bytes(*bytes))
+        }
+        WIRE_LEN => {
+            let len = read_varint(buf).ok_or_else(malformed)? as usize;
+            if len > buf.len() {
+                return Err(malformed());
+            }
+            let (bytes, rest) = buf.split_at(len);
+            *buf = rest;
+            Field::Len(bytes)
+        }
+        WIRE_FIXED32 => {
+            let (_, rest) = buf.split_first_chunk::<4>().ok_or_else(malformed)?;
+            *buf = rest;
+            Field::Other
+        }
+        _ => return Err(malformed()),
+    };
+    Ok((key >> 3, field))
+}
+
+fn read_varint(buf: &mut &[u8]) -> Option<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = buf.split_first()?;
+        *buf = rest;
+        value |= u64::from(byte & 0x7F) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn write_f64(out: &mut Vec<u8>, field: u64, value: f64) {
+    write_varint(out, (field << 3) | WIRE_FIXED64);
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn write_message(out: &mut Vec<u8>, field: u64, message: &[u8]) {
+    write_varint(out, (field << 3) | WIRE_LEN);
+    write_varint(out, message.len() as u64);
+    out.extend_from_slice(message);
+}
+
+/// `gz.msgs.Twist` with `linear.x` and `angular.z` set.
+fn encode_gz_twist(linear: MetersPerSecond, angular: RadiansPerSecond) -> Vec<u8> {
+    let mut linear_vec = Vec::new();
+    write_f64(&mut linear_vec, 2, f64::from(linear.get())); // Vector3d.x
+    let mut angular_vec = Vec::new();
+    write_f64(&mut angular_vec, 4, f64::from(angular.get())); // Vector3d.z
+    let mut out = Vec::new();
+    write_message(&mut out, 2, &linear_vec); // Twist.linear
+    write_message(&mut out, 3, &angular_vec); // Twist.angular
+    out
+}
+
+/// Decode a `gz.msgs.LaserScan` (`angle_min` = 4, `angle_step` = 6,
+/// `ranges` = 14).
+fn decode_gz_laser_scan(buf: &[u8]) -> Result<LaserScanMsg, MechError> {
+    let mut scan = LaserScanMsg {
+        stamp: None,
+        angle_min: 0.0,
+        angle_increment: 0.0,
+        ranges: Vec::new(),
+    };
+    for field in proto_fields(buf) {
+        match field? {
+            (4, value) => scan.angle_min = value.as_f64() as f32,
+            (6, value) => scan.angle_increment = value.as_f64() as f32,
+            (14, Field::Len(packed)) => {
+                if scan.ranges.len() + packed.len() / 8 > MAX_LIDAR_RANGES {
+                    return Err(MechError::Parsing(format!(
+                        "simulated scan exceeds the limit of {MAX_LIDAR_RANGES} range readings"
+                    )));
+                }
+                scan.ranges.extend(
+                    packed
+                        .chunks_exact(8)
+                        .map(|b| f64::from_le_bytes(b.try_into().unwrap()) as f32),
+                );
+            }
+            (14, value) => scan.ranges.push(value.as_f64() as f32),
+            _ => {}
+        }
+    }
+    Ok(scan)
+}
+
+/// Decode a `gz.msgs.Odometry`: `pose` = 2 (`position` = 4,
+/// `orientation` = 5) and `twist` = 3 (`linear` = 2, `angular` = 3).
+fn decode_gz_odometry(buf: &[u8]) -> Result<OdometryMsg, MechError> {
+    /// `gz.msgs.Vector3d` / `gz.msgs.Quaternion` components by field number.
+    fn components(buf: &[u8]) -> Result<[f64; 4], MechError> {
+        let mut xyzw = [0.0; 4];
+        for field in proto_fields(buf) {
+            if let (number @ 2..=5, value) = field? {
+                xyzw[number as usize - 2] = value.as_f64();
+            }
+        }
+        Ok(xyzw)
+    }
+
+    let mut odom = OdometryMsg {
+        stamp: None,
+        position_x: 0.0,
+        position_y: 0.0,
+        yaw_rad: 0.0,
+        linear_x: 0.0,
+        angular_z: 0.0,
+    };
+    for field in proto_fields(buf) {
+        match field? {
+            (2, pose) => {
+                for field in proto_fields(pose.as_message()) {
+                    match field? {
+                        (4, position) => {
+                            let [x, y, ..] = components(position.as_message())?;
+                            odom.position_x = x;
+                            odom.position_y = y;
+                        }
+                        (5, orientation) => {
+                            let [qx, qy, qz, qw] = components(orientation.as_message())?;
+                            odom.yaw_rad =
+                                (2.0 * (qw * qz + qx * qy)).atan2(1.0 - 2.0 * (qy * qy + qz * qz));
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            (3, twist) => {
+                for field in proto_fields(twist.as_message()) {
+                    match field? {
+                        (2, linear) => odom.linear_x = components(linear.as_message())?[0],
+                        (3, angular) => odom.angular_z = components(angular.as_message())?[2],
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(odom)
+}
+
+#[async_trait]
+impl MechAdapter for SimAdapter {
+    /// Encode the intent with [`SimAdapter::encode_intent`] and queue it for
+    /// the simulator.
+    ///
+    /// # Errors
+    ///
+    /// Besides unsupported intents, returns [`MechError::Channel`] while
+    /// disconnected or when the command queue is full.
+    async fn execute_intent(&self, intent: HardwareIntent) -> Result<(), MechError> {
+        let message = self.encode_intent(&intent)?;
+        if !self.is_connected() {
+            return Err(MechError::Channel(format!(
+                "simulator '{}' is disconnected",
+                self.url
+            )));
+        }
+        self.commands
+            .try_send(message)
+            .map_err(|e| MechError::Channel(format!("simulator '{}' command queue: {e}", self.url)))
+    }
+
+    /// Sensor messages are pushed onto the bus by [`SimAdapter::run`]; this
+    /// returns an empty stream.
+    async fn sensor_stream(&self) -> BoxStream<'static, EventPayload> {
+        Box::pin(stream::empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    fn make_adapter(backend: SimBackend) -> (Arc<EventBus>, Arc<SimAdapter>) {
+        let bus = Arc::new(EventBus::default());
+        let adapter = SimAdapter::new(Arc::clone(&bus), backend, "ws://127.0.0.1:1");
+        (bus, Arc::new(adapter))
+    }
+
+    fn drive() -> HardwareIntent {
+        HardwareIntent::Drive {
+            linear_velocity: MetersPerSecond(0.5),
+            angular_velocity: RadiansPerSecond(-0.25),
+        }
+    }
+
+    fn gz_scan(ranges: &[f64]) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_f64(&mut out, 4, -1.5);
+        write_f64(&mut out, 6, 0.5);
+        let packed: Vec<u8> = ranges.iter().flat_map(|r| r.to_le_bytes()).collect();
+        write_message(&mut out, 14, &packed);
+        out
+    }
+
+    fn gz_odometry(x: f64, y: f64, yaw: f64) -> Vec<u8> {
+        let mut position = Vec::new();
+        write_f64(&mut position, 2, x);
+        write_f64(&mut position, 3, y);
+        let mut orientation = Vec::new();
+        write_f64(&mut orientation, 4, (yaw / 2.0).sin());
+        write_f64(&mut orientation, 5, (yaw / 2.0).cos());
+        let mut pose = Vec::new();
+        write_message(&mut pose, 4, &position);
+        write_message(&mut pose, 5, &orientation);
+        let mut out = Vec::new();
+        write_message(&mut out, 2, &pose);
+        out
+    }
+
+    fn components_of(buf: &[u8]) -> [f64; 3] {
+        let mut xyz = [0.0; 3];
+        for field in proto_fields(buf) {
+            let (number, value) = field.unwrap();
+            xyz[number as usize - 2] = value.as_f64();
+        }
+        xyz
+    }
+
+    fn gz_frame(topic: &str, msg_type: &str, payload: &[u8]) -> Message {
+        let mut frame = format!("pub,{topic},{msg_type},").into_bytes();
+        frame.extend_from_slice(payload);
+        Message::binary(frame)
+    }
+
+    #[test]
+    fn gazebo_twist_is_protobuf() {
+        let (_, adapter) = make_adapter(SimBackend::Gazebo);
+        let Message::Binary(frame) = adapter.encode_intent(&drive()).unwrap() else {
+            panic!("expected a binary frame");
+        };
+        let header = b"pub,/cmd_vel,gz.msgs.Twist,";
+        assert!(frame.starts_with(header));
+
+        let mut velocities = (0.0, 0.0);
+        for field in proto_fields(&frame[header.len()..]) {
+            match field.unwrap() {
+                (2, linear) => velocities.0 = components_of(linear.as_message())[0],
+                (3, angular) => velocities.1 = components_of(angular.as_message())[2],
+                _ => panic!("unexpected Twist field"),
+            }
+        }
+        assert_eq!(velocities, (0.5, -0.25));
+    }
+
+    #[test]
+    fn webots_twist_is_json_and_other_intents_are_unsupported() {
+        let (_, adapter) = make_adapter(SimBackend::Webots);
+        let Message::Text(text) = adapter
+            .encode_intent(&HardwareIntent::Halt {
+                reason: "test".to_string(),
+            })
+            .unwrap()
+        else {
+            panic!("expected a text frame");
+        };
+        let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(value["topic"], "/cmd_vel");
+        assert_eq!(value["linear"], 0.0);
+
+        assert!(matches!(
+            adapter.encode_intent(&HardwareIntent::Dock),
+            Err(MechError::HardwareFault {
+                code: FaultCode::Unsupported,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn gazebo_scan_and_odometry_are_published() {
+        let (bus, adapter) = make_adapter(SimBackend::Gazebo);
+        let mut rx = bus.subscribe();
+
+        adapter
+            .ingest_message(&gz_frame(
+                "/scan",
+                "gz.msgs.LaserScan",
+                &gz_scan(&[1.0, 2.5]),
+            ))
+            .unwrap();
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.source, "mechos-middleware::gazebo/scan");
+        match event.payload {
+            EventPayload::LidarScan {
+                ranges,
+                angle_min_rad,
+                angle_increment_rad,
+                stamp,
+            } => {
+                assert_eq!(ranges, vec![1.0, 2.5]);
+                assert_eq!((angle_min_rad, angle_increment_rad), (-1.5, 0.5));
+                assert_eq!(stamp, None);
+            }
+            other => panic!("expected LidarScan, got {other:?}"),
+        }
+
+        adapter
+            .ingest_message(&gz_frame(
+                "/odom",
+                "gz.msgs.Odometry",
+                &gz_odometry(1.0, -2.0, 0.5),
+            ))
+            .unwrap();
+        match rx.recv().await.unwrap().payload {
+            EventPayload::Telemetry(t) => {
+                assert_eq!((t.position_x, t.position_y), (Meters(1.0), Meters(-2.0)));
+                assert!((t.heading_rad - 0.5).abs() < 1e-6);
+            }
+            other => panic!("expected Telemetry, got {other:?}"),
+        }
+
+        // Other topics and malformed payloads.
+        assert_eq!(
+            adapter
+                .ingest_message(&gz_frame("/clock", "gz.msgs.Clock", &[]))
+                .unwrap(),
+            0
+        );
+        assert!(
+            adapter
+                .ingest_message(&gz_frame("/scan", "gz.msgs.LaserScan", &[0x72, 0xFF]))
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn webots_messages_are_published_and_validated() {
+        let bus = Arc::new(EventBus::default());
+        let adapter = SimAdapter::new(Arc::clone(&bus), SimBackend::Webots, "ws://127.0.0.1:1")
+            .with_topics(SimTopics {
+                scan: "/lidar".to_string(),
+                ..SimTopics::default()
+            });
+        let mut rx = bus.subscribe();
+
+        adapter
+            .ingest_message(&Message::text(
+                r#"{"topic":"/lidar","angle_min":-1.0,"angle_increment":0.1,"ranges":[0.4]}"#,
+            ))
+            .unwrap();
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.source, "mechos-middleware::webots/scan");
+        assert!(matches!(event.payload, EventPayload::LidarScan { .. }));
+
+        let oversized = json!({
+            "topic": "/lidar",
+            "angle_min": 0.0,
+            "angle_increment": 0.0,
+            "ranges": vec![1.0; MAX_LIDAR_RANGES + 1],
+        });
+        assert!(
+            adapter
+                .ingest_message(&Message::text(oversized.to_string()))
+                .is_err()
+        );
+        assert!(
+            adapter
+                .ingest_message(&Message::text(r#"{"topic":"/odom","x":1.0}"#))
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn serve_subscribes_streams_and_sends_commands() {
+        let (bus, adapter) = make_adapter(SimBackend::Gazebo);
+        let mut rx = bus.subscribe();
+        assert!(matches!(
+            adapter.execute_intent(drive()).await,
+            Err(MechError::Channel(_))
+        ));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let serving = tokio::spawn({
+            let adapter = Arc::clone(&adapter);
+            async move {
+                let (ws, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+                adapter.serve(ws).await
+            }
+        });
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut simulator = tokio_tungstenite::accept_async(stream).await.unwrap();
+
+        for topic in ["/scan", "/odom"] {
+            let message = simulator.next().await.unwrap().unwrap();
+            assert_eq!(
+                message.into_text().unwrap().as_str(),
+                format!("sub,{topic},,")
+            );
+        }
+        simulator
+            .send(gz_frame("/scan", "gz.msgs.LaserScan", &gz_scan(&[3.0])))
+            .await
+            .unwrap();
+        assert!(matches!(
+            rx.recv().await.unwrap().payload,
+            EventPayload::LidarScan { .. }
+        ));
+        assert!(adapter.is_connected());
+
+        adapter.execute_intent(drive()).await.unwrap();
+        let command = simulator.next().await.unwrap().unwrap();
+        assert!(
+            command
+                .into_data()
+                .starts_with(b"pub,/cmd_vel,gz.msgs.Twist,")
+        );
+
+        simulator.close(None).await.unwrap();
+        assert!(matches!(serving.await.unwrap(), MechError::Channel(_)));
+        assert!(!adapter.is_connected());
+    }
+}
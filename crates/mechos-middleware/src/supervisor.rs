@@ -0,0 +1,478 @@
+//! WebSocket connection supervision for rosbridge-style adapters.
+//!
+//! [`WsSupervisor`] owns the client side of a WebSocket link – the
+//! dashboard's `rosbridge_server` behind a
+//! [`DashboardSimAdapter`][crate::dashboard_sim_adapter::DashboardSimAdapter],
+//! or a remote peer of a [`Ros2Bridge`][crate::ros2_bridge::Ros2Bridge] in
+//! client mode – and keeps it alive:
+//!
+//! * **Drop detection** – a close frame, a read/write error, or silence for
+//!   longer than three keep-alive intervals (a ping is sent every interval)
+//!   ends the connection.
+//! * **Exponential backoff** – reconnection attempts are spaced by
+//!   [`Backoff`], doubling from `initial` up to `max`; the schedule restarts
+//!   once a connection succeeds.
+//! * **Subscription replay** – every rosbridge `subscribe` / `advertise` op
+//!   sent through a [`SupervisorHandle`] is remembered (and forgotten again by
+//!   the matching `unsubscribe` / `unadvertise`), then re-sent first on every
+//!   new connection.
+//! * **State reporting** – each transition is published on
+//!   [`Topic::SystemAlerts`](crate::Topic::SystemAlerts) as a [`ConnectionState`].
+//!
+//! Frames are refused while the link is down rather than queued, so a stale
+//! velocity command is never delivered after a reconnect.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures_util::{Sink, SinkExt, Stream, StreamExt};
+use mechos_types::{LinkState, MechError};
+use tokio::sync::mpsc;
+use tokio::time::{Instant, MissedTickBehavior};
+use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
+use tokio_tungstenite::tungstenite::{self, Message};
+use tracing::{info, warn};
+
+use crate::bus::EventBus;
+use crate::ros2_bridge::MAX_INCOMING_PAYLOAD_BYTES;
+use crate::typed::ConnectionState;
+
+/// Number of outbound frames that may wait for the socket.
+const OUTBOX_CAPACITY: usize = 64;
+
+/// Default interval between keep-alive pings.
+pub const DEFAULT_KEEPALIVE: Duration = Duration::from_secs(5);
+
+/// Exponential reconnection backoff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backoff {
+    /// Delay before the first retry.
+    pub initial: Duration,
+    /// Upper bound on any single delay.
+    pub max: Duration,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_millis(500),
+            max: Duration::from_secs(30),
+        }
+    }
+}
+
+impl Backoff {
+    /// Delay before retry number `attempt` (1-based): `initial × 2^(attempt−1)`,
+    /// capped at `max`.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 1u32
+            .checked_shl(attempt.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        self.initial.saturating_mul(factor).min(self.max)
+    }
+}
+
+/// Cloneable sending side of a [`WsSupervisor`].
+#[derive(Clone)]
+pub struct SupervisorHandle {
+    outbox: mpsc::Sender<String>,
+    connected: Arc<AtomicBool>,
+    /// Replayable ops keyed by `(op, topic)`, in a stable order.
+    subscriptions: Arc<Mutex<BTreeMap<(String, String), String>>>,
+}
+
+impl SupervisorHandle {
+    /// `true` while the supervised link is up.
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
+    /// Send a text frame over the link.
+    ///
+    /// `subscribe` / `advertise` ops are remembered for replay, and
+    /// `unsubscribe` / `unadvertise` ops forget them.  Both are accepted
+    /// while disconnected; the next connection sends the updated set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Channel`] if the link is down or its outbox is
+    /// full.
+    pub fn send(&self, frame: impl Into<String>) -> Result<(), MechError> {
+        let frame = frame.into();
+        let deferrable = self.track(&frame);
+        if !self.is_connected() {
+            return if deferrable {
+                Ok(())
+            } else {
+                Err(MechError::Channel(
+                    "supervised link is not connected".to_string(),
+                ))
+            };
+        }
+        self.outbox
+            .try_send(frame)
+            .map_err(|e| MechError::Channel(format!("supervised link outbox: {e}")))
+    }
+
+    /// The frames replayed on every new connection.
+    pub fn subscriptions(&self) -> Vec<String> {
+        self.lock_subscriptions().values().cloned().collect()
+    }
+
+    // -------------------------------------------------------------------------
+    // Private helpers
+    // -------------------------------------------------------------------------
+
+    /// Record or forget a rosbridge subscription op; returns `true` when the
+    /// frame only changes the subscription set and so can wait for the next
+    /// connection.
+    fn track(&self, frame: &str) -> bool {
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(frame) else {
+            return false;
+        };
+        let op = json.get("op").and_then(|o| o.as_str()).unwrap_or("");
+        let Some(topic) = json.get("topic").and_then(|t| t.as_str()) else {
+            return false;
+        };
+        let mut subscriptions = self.lock_subscriptions();
+        match op {
+            "subscribe" | "advertise" => {
+                subscriptions.insert((op.to_string(), topic.to_string()), frame.to_string());
+                true
+            }
+            "unsubscribe" | "unadvertise" => {
+                subscriptions.remove(&(op[2..].to_string(), topic.to_string()));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn lock_subscriptions(&self) -> std::sync::MutexGuard<'_, BTreeMap<(String, String), String>> {
+        self.subscriptions.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Keeps a client WebSocket connected; see the [module docs](self).
+pub struct WsSupervisor {
+    bus: Arc<EventBus>,
+    component: String,
+    url: String,
+    backoff: Backoff,
+    keepalive: Duration,
+    handle: SupervisorHandle,
+    /// Locked by whichever connection is live so only one drains it.
+    outbox: tokio::sync::Mutex<mpsc::Receiver<String>>,
+}
+
+impl WsSupervisor {
+    /// Create a supervisor for the WebSocket at `url`.  `component` names the
+    /// adapter in [`ConnectionState`] events.
+    pub fn new(bus: Arc<EventBus>, component: impl Into<String>, url: impl Into<String>) -> Self {
+        let (tx, rx) = mpsc::channel(OUTBOX_CAPACITY);
+        Self {
+            bus,
+            component: component.into(),
+            url: url.into(),
+            backoff: Backoff::default(),
+            keepalive: DEFAULT_KEEPALIVE,
+            handle: SupervisorHandle {
+                outbox: tx,
+                connected: Arc::new(AtomicBool::new(false)),
+                subscriptions: Arc::new(Mutex::new(BTreeMap::new())),
+            },
+            outbox: tokio::sync::Mutex::new(rx),
+        }
+    }
+
+    /// Replace the reconnection backoff.
+    pub fn with_backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Replace the keep-alive ping interval.
+    pub fn with_keepalive(mut self, keepalive: Duration) -> Self {
+        self.keepalive = keepalive;
+        self
+    }
+
+    /// Seed the replayed subscription set with a rosbridge `subscribe` (or
+    /// `advertise`) frame.
+    pub fn with_subscription(self, frame: impl Into<String>) -> Self {
+        self.handle.track(&frame.into());
+        self
+    }
+
+    /// A cloneable handle for sending frames over the link.
+    pub fn handle(&self) -> SupervisorHandle {
+        self.handle.clone()
+    }
+
+    /// The supervised WebSocket URL.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// `true` while the link is up.
+    pub fn is_connected(&self) -> bool {
+        self.handle.is_connected()
+    }
+
+    /// Connect forever, reconnecting with exponential backoff.  Every text
+    /// frame received is passed to `on_message`.
+    pub async fn run<F>(&self, mut on_message: F)
+    where
+        F: FnMut(&str) + Send,
+    {
+        let mut ws_config = WebSocketConfig::default();
+        ws_config.max_message_size = Some(MAX_INCOMING_PAYLOAD_BYTES);
+        let mut attempt = 0u32;
+        loop {
+            self.report(LinkState::Connecting, attempt);
+            match tokio_tungstenite::connect_async_with_config(&self.url, Some(ws_config), false)
+                .await
+            {
+                Ok((ws, _)) => {
+                    info!(url = %self.url, component = %self.component, "link connected");
+                    attempt = 0;
+                    let e = self.serve(ws, &mut on_message).await;
+                    warn!(url = %self.url, component = %self.component, error = %e, "link lost");
+                }
+                Err(e) => {
+                    warn!(url = %self.url, component = %self.component, error = %e, "cannot connect");
+                }
+            }
+            attempt = attempt.saturating_add(1);
+            self.report(LinkState::Disconnected, attempt);
+            tokio::time::sleep(self.backoff.delay(attempt)).await;
+        }
+    }
+
+    /// Supervise an already-open WebSocket until it drops, returning why.
+    /// The subscription set is replayed first; frames still queued when the
+    /// connection ends are discarded.
+    pub async fn serve<S, F>(&self, ws: S, on_message: &mut F) -> MechError
+    where
+        S: Stream<Item = Result<Message, tungstenite::Error>>
+            + Sink<Message, Error = tungstenite::Error>
+            + Unpin,
+        F: FnMut(&str) + Send,
+    {
+        let mut outbox = self.outbox.lock().await;
+        self.handle.connected.store(true, Ordering::Relaxed);
+        self.report(LinkState::Connected, 0);
+        let error = self.exchange(ws, &mut outbox, on_message).await;
+        self.handle.connected.store(false, Ordering::Relaxed);
+        let mut dropped = 0usize;
+        while outbox.try_recv().is_ok() {
+            dropped += 1;
+        }
+        if dropped > 0 {
+            warn!(component = %self.component, dropped, "discarding frames queued for a dropped link");
+        }
+        error
+    }
+
+    // -------------------------------------------------------------------------
+    // Private helpers
+    // -------------------------------------------------------------------------
+
+    async fn exchange<S, F>(
+        &self,
+        mut ws: S,
+        outbox: &mut mpsc::Receiver<String>,
+        on_message: &mut F,
+    ) -> MechError
+    where
+        S: Stream<Item = Result<Message, tungstenite::Error>>
+            + Sink<Message, Error = tungstenite::Error>
+            + Unpin,
+        F: FnMut(&str) + Send,
+    {
+        let channel_error =
+            |e: tungstenite::Error| MechError::Channel(format!("WebSocket '{}': {e}", self.url));
+        for frame in self.handle.subscriptions() {
+            if let Err(e) = ws.send(Message::Text(frame.into())).await {
+                return channel_error(e);
+            }
+        }
+
+        let mut keepalive =
+            tokio::time::interval_at(Instant::now() + self.keepalive, self.keepalive);
+        keepalive.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut last_seen = Instant::now();
+        loop {
+            tokio::select! {
+                received = ws.next() => {
+                    last_seen = Instant::now();
+                    match received {
+                        Some(Ok(Message::Close(_))) | None => {
+                            return MechError::Channel(format!("WebSocket '{}' closed", self.url));
+                        }
+                        Some(Ok(Message::Text(text))) => on_message(text.as_str()),
+                        Some(Ok(_)) => {}
+                        Some(Err(e)) => return channel_error(e),
+                    }
+                }
+                Some(frame) = outbox.recv() => {
+                    if let Err(e) = ws.send(Message::Text(frame.into())).await {
+                        return channel_error(e);
+                    }
+                }
+                _ = keepalive.tick() => {
+                    if last_seen.elapsed() > self.keepalive * 3 {
+                        return MechError::Channel(format!(
+                            "WebSocket '{}' silent for {:?}",
+                            self.url,
+                            last_seen.elapsed()
+                        ));
+                    }
+                    if let Err(e) = ws.send(Message::Ping(Default::default())).await {
+                        return channel_error(e);
+                    }
+                }
+            }
+        }
+    }
+
+    fn report(&self, state: LinkState, attempt: u32) {
+        let update = ConnectionState {
+            component: self.component.clone(),
+            state,
+            attempt,
+        };
+        // No subscriber on SystemAlerts is not an error for the link itself.
+        let _ = self.bus.publish_typed(
+            format!("mechos-middleware::supervisor/{}", self.component),
+            update,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::typed::TypedReceiver;
+    use tokio::net::TcpListener;
+
+    async fn next_state(rx: &mut TypedReceiver<ConnectionState>) -> (LinkState, u32) {
+        let update = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("timed out waiting for a connection state")
+            .unwrap();
+        (update.state, update.attempt)
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let backoff = Backoff {
+            initial: Duration::from_millis(100),
+            max: Duration::from_secs(1),
+        };
+        assert_eq!(backoff.delay(1), Duration::from_millis(100));
+        assert_eq!(backoff.delay(2), Duration::from_millis(200));
+        assert_eq!(backoff.delay(4), Duration::from_millis(800));
+        assert_eq!(backoff.delay(5), Duration::from_secs(1));
+        assert_eq!(backoff.delay(u32::MAX), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn handle_tracks_subscribe_and_unsubscribe() {
+        let supervisor = WsSupervisor::new(Arc::new(EventBus::default()), "test", "ws://x")
+            .with_subscription(r#"{"op":"subscribe","topic":"/scan"}"#);
+        let handle = supervisor.handle();
+
+        // Subscriptions are accepted while down; plain frames are refused.
+        handle
+            .send(r#"{"op":"advertise","topic":"/cmd_vel","type":"geometry_msgs/msg/Twist"}"#)
+            .unwrap();
+        assert!(matches!(
+            handle.send(r#"{"op":"publish","topic":"/cmd_vel","msg":{}}"#),
+            Err(MechError::Channel(_))
+        ));
+        assert_eq!(handle.subscriptions().len(), 2);
+
+        handle
+            .send(r#"{"op":"unsubscribe","topic":"/scan"}"#)
+            .unwrap();
+        assert_eq!(
+            handle.subscriptions(),
+            vec![r#"{"op":"advertise","topic":"/cmd_vel","type":"geometry_msgs/msg/Twist"}"#]
+        );
+    }
+
+    #[tokio::test]
+    async fn reconnects_and_replays_subscriptions() {
+        let bus = Arc::new(EventBus::default());
+        let mut alerts = bus.subscribe_typed::<ConnectionState>();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let supervisor = Arc::new(
+            WsSupervisor::new(Arc::clone(&bus), "dashboard_sim", url)
+                .with_backoff(Backoff {
+                    initial: Duration::from_millis(10),
+                    max: Duration::from_millis(50),
+                })
+                .with_subscription(r#"{"op":"subscribe","topic":"/sim_scan"}"#),
+        );
+        let (seen_tx, mut seen_rx) = mpsc::unbounded_channel();
+        let running = tokio::spawn({
+            let supervisor = Arc::clone(&supervisor);
+            async move {
+                supervisor
+                    .run(move |text| {
+                        let _ = seen_tx.send(text.to_string());
+                    })
+                    .await
+            }
+        });
+
+        assert_eq!(next_state(&mut alerts).await, (LinkState::Connecting, 0));
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut server = tokio_tungstenite::accept_async(stream).await.unwrap();
+        assert_eq!(next_state(&mut alerts).await, (LinkState::Connected, 0));
+        let first = server.next().await.unwrap().unwrap();
+        assert_eq!(
+            first.into_text().unwrap().as_str(),
+            r#"{"op":"subscribe","topic":"/sim_scan"}"#
+        );
+
+        // A subscription made while connected is sent now and replayed later.
+        let handle = supervisor.handle();
+        handle
+            .send(r#"{"op":"subscribe","topic":"/hitl/human_response"}"#)
+            .unwrap();
+        assert!(server.next().await.unwrap().unwrap().is_text());
+        server.send(Message::Text("hello".into())).await.unwrap();
+        assert_eq!(seen_rx.recv().await.unwrap(), "hello");
+
+        // Drop the connection: the supervisor reports it and comes back.
+        drop(server);
+        assert_eq!(next_state(&mut alerts).await, (LinkState::Disconnected, 1));
+        assert!(!handle.is_connected());
+        assert_eq!(next_state(&mut alerts).await, (LinkState::Connecting, 1));
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut server = tokio_tungstenite::accept_async(stream).await.unwrap();
+        assert_eq!(next_state(&mut alerts).await, (LinkState::Connected, 0));
+        let mut replayed = Vec::new();
+        for _ in 0..2 {
+            replayed.push(
+                server
+                    .next()
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .into_text()
+                    .unwrap()
+                    .to_string(),
+            );
+        }
+        assert_eq!(replayed, handle.subscriptions());
+        assert!(handle.is_connected());
+
+        running.abort();
+    }
+}
@@ -0,0 +1,176 @@
+//! TLS termination for the WebSocket servers.
+//!
+//! [`Ros2Bridge::run_ws_server`][crate::Ros2Bridge::run_ws_server] and the
+//! Cockpit server carry teleop commands and human responses, which should
+//! not cross the shop-floor Wi-Fi in plaintext.  A [`TlsConfig`] names the
+//! PEM certificate chain and private key to serve; with
+//! [`generate_self_signed`][TlsConfig::generate_self_signed] set, a
+//! self-signed pair is generated on first run when the files do not exist
+//! yet, so a robot can serve `wss://` without any provisioning.
+//!
+//! ```toml
+//! [tls]
+//! cert_path = "/home/robot/.mechos/tls/cert.pem"
+//! key_path = "/home/robot/.mechos/tls/key.pem"
+//! generate_self_signed = true
+//! ```
+
+use std::fs;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use mechos_types::MechError;
+use serde::{Deserialize, Serialize};
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::rustls::ServerConfig;
+use tokio_rustls::rustls::crypto::ring;
+use tracing::info;
+
+/// Certificate and key a server terminates TLS with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TlsConfig {
+    /// PEM file holding the certificate chain, leaf first.
+    pub cert_path: PathBuf,
+    /// PEM file holding the private key.
+    pub key_path: PathBuf,
+    /// Generate a self-signed certificate and key at the paths above when
+    /// neither file exists yet.
+    #[serde(default)]
+    pub generate_self_signed: bool,
+    /// Subject alternative names of a generated certificate.
+    #[serde(default = "default_hostnames")]
+    pub hostnames: Vec<String>,
+}
+
+fn default_hostnames() -> Vec<String> {
+    vec!["localhost".to_string()]
+}
+
+impl TlsConfig {
+    /// Serve the certificate at `cert_path` with the key at `key_path`.
+    pub fn new(cert_path: impl Into<PathBuf>, key_path: impl Into<PathBuf>) -> Self {
+        Self {
+            cert_path: cert_path.into(),
+            key_path: key_path.into(),
+            generate_self_signed: false,
+            hostnames: default_hostnames(),
+        }
+    }
+
+    /// Generate a self-signed certificate for `hostnames` on first run
+    /// (builder-style).
+    pub fn with_self_signed<I, S>(mut self, hostnames: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.generate_self_signed = true;
+        self.hostnames = hostnames.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Load the certificate and key (generating them first if configured)
+    /// and build an acceptor for incoming connections.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Serialization`] if the files cannot be read,
+    /// written or parsed, or rustls rejects the pair.
+    pub fn acceptor(&self) -> Result<TlsAcceptor, MechError> {
+        if self.generate_self_signed && !self.cert_path.exists() && !self.key_path.exists() {
+            generate_self_signed(&self.cert_path, &self.key_path, &self.hostnames)?;
+        }
+        let certs = rustls_pemfile::certs(&mut BufReader::new(open(&self.cert_path)?))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| tls_error(&self.cert_path, e))?;
+        if certs.is_empty() {
+            return Err(tls_error(&self.cert_path, "no certificate found"));
+        }
+        let key = rustls_pemfile::private_key(&mut BufReader::new(open(&self.key_path)?))
+            .map_err(|e| tls_error(&self.key_path, e))?
+            .ok_or_else(|| tls_error(&self.key_path, "no private key found"))?;
+        let config = ServerConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .and_then(|builder| builder.with_no_client_auth().with_single_cert(certs, key))
+            .map_err(|e| MechError::Serialization(format!("invalid TLS configuration: {e}")))?;
+        Ok(TlsAcceptor::from(Arc::new(config)))
+    }
+}
+
+/// Write a fresh self-signed certificate for `hostnames` to `cert_path` and
+/// its private key to `key_path`, creating parent directories as needed.
+///
+/// # Errors
+///
+/// Returns [`MechError::Serialization`] if generation or writing fails.
+pub fn generate_self_signed(
+    cert_path: &Path,
+    key_path: &Path,
+    hostnames: &[String],
+) -> Result<(), MechError> {
+    let generated = rcgen::generate_simple_self_signed(hostnames.to_vec()).map_err(|e| {
+        MechError::Serialization(format!("self-signed certificate generation failed: {e}"))
+    })?;
+    write(cert_path, &generated.cert.pem())?;
+    write(key_path, &generated.signing_key.serialize_pem())?;
+    info!(cert = %cert_path.display(), "generated self-signed TLS certificate");
+    Ok(())
+}
+
+fn open(path: &Path) -> Result<fs::File, MechError> {
+    fs::File::open(path).map_err(|e| tls_error(path, e))
+}
+
+fn write(path: &Path, pem: &str) -> Result<(), MechError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| tls_error(parent, e))?;
+    }
+    fs::write(path, pem).map_err(|e| tls_error(path, e))
+}
+
+fn tls_error(path: &Path, e: impl std::fmt::Display) -> MechError {
+    MechError::Serialization(format!("TLS file {}: {e}", path.display()))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("mechos-tls-{}", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn self_signed_pair_is_generated_once_and_reused() {
+        let dir = temp_dir();
+        let config = TlsConfig::new(dir.join("cert.pem"), dir.join("key.pem"))
+            .with_self_signed(["robot.local"]);
+        config.acceptor().unwrap();
+        let cert = fs::read_to_string(&config.cert_path).unwrap();
+        assert!(cert.starts_with("-----BEGIN CERTIFICATE-----"));
+
+        config.acceptor().unwrap();
+        assert_eq!(fs::read_to_string(&config.cert_path).unwrap(), cert);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn missing_files_fail_without_self_signed_generation() {
+        let dir = temp_dir();
+        let config = TlsConfig::new(dir.join("cert.pem"), dir.join("key.pem"));
+        assert!(matches!(config.acceptor(), Err(MechError::Serialization(_))));
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn config_deserializes_with_defaults() {
+        let config: TlsConfig =
+            toml::from_str("cert_path = \"c.pem\"\nkey_path = \"k.pem\"").unwrap();
+        assert_eq!(config, TlsConfig::new("c.pem", "k.pem"));
+    }
+}
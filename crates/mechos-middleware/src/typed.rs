@@ -0,0 +1,595 @@
+//! Typed topic API – payload types paired with the [`Topic`] they travel on.
+//!
+//! Every [`EventPayload`] variant has a [`TopicPayload`] type here (or in
+//! `mechos-types`) that knows its lane, so publishers no longer assemble
+//! [`Event`]s by hand and consumers no longer filter with
+//! `matches!(event.payload, EventPayload::X { .. })`:
+//!
+//! ```rust,no_run
+//! # async fn demo(bus: mechos_middleware::EventBus) -> Result<(), mechos_types::MechError> {
+//! use mechos_middleware::typed::LidarScan;
+//!
+//! let mut scans = bus.subscribe_typed::<LidarScan>();
+//! bus.publish_typed("mechos-middleware::ros2/scan", LidarScan {
+//!     ranges: vec![1.0, 1.2],
+//!     angle_min_rad: 0.0,
+//!     angle_increment_rad: 0.01,
+//!     stamp: None,
+//! })?;
+//! let scan = scans.recv().await.expect("bus open");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! | Topic | Payload types |
+//! |---|---|
+//! | [`Topic::Telemetry`] | [`TelemetryData`], [`PowerStatus`], [`Attitude`], [`GpsFix`], [`BusHealth`], [`TrackedObstacle`], [`MapSnapshot`] |
+//! | [`Topic::HardwareCommands`] | [`IntentEnvelope`], [`IntentDispatched`], [`AdapterCommand`] |
+//! | [`Topic::SystemAlerts`] | [`HardwareFault`], [`ConnectionState`], [`AgentModeToggle`], [`SafetyRequest`], [`SafetyStatus`], [`ApprovalRequest`], [`ApprovalDecision`], [`PolicyRequest`], [`PolicyStatus`], [`IntentResult`], [`EventPayload::Unknown`] (no typed counterpart) |
+//! | [`Topic::SwarmComm`] | [`PeerMessage`], [`TaskProgress`], [`TaskCompleted`] |
+//! | [`Topic::CognitiveStream`] | [`Reasoning`], [`HumanAnswer`] |
+//! | [`Topic::SensorHighRate`] | [`LidarScan`], [`CameraFrame`] |
+
+use std::marker::PhantomData;
+
+use chrono::{DateTime, Utc};
+use mechos_types::{
+    Event, EventPayload, FaultCode, HardwareIntent, HumanAnswer, ImageFormat, IntentEnvelope, LaneStats, LinkState, Meters,
+    MetersPerSecond, PolicyCommand, PolicyReport, RuleVerdict, SafetyCommand, SafetyMode, TelemetryData,
+};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::bus::{Topic, TopicReceiver};
+
+/// A payload type with a fixed home [`Topic`].
+pub trait TopicPayload: Into<EventPayload> + Sized {
+    /// The lane this payload is published on.
+    const TOPIC: Topic;
+
+    /// Decode `payload`, handing it back unchanged if it is another kind.
+    // The error is the caller's own payload, returned rather than cloned.
+    #[allow(clippy::result_large_err)]
+    fn from_payload(payload: EventPayload) -> Result<Self, EventPayload>;
+}
+
+impl TopicPayload for TelemetryData {
+    const TOPIC: Topic = Topic::Telemetry;
+
+    fn from_payload(payload: EventPayload) -> Result<Self, EventPayload> {
+        match payload {
+            EventPayload::Telemetry(data) => Ok(data),
+            other => Err(other),
+        }
+    }
+}
+
+impl TopicPayload for HumanAnswer {
+    const TOPIC: Topic = Topic::CognitiveStream;
+
+    fn from_payload(payload: EventPayload) -> Result<Self, EventPayload> {
+        match payload {
+            EventPayload::HumanResponse(answer) => Ok(answer),
+            other => Err(other),
+        }
+    }
+}
+
+impl TopicPayload for IntentEnvelope {
+    const TOPIC: Topic = Topic::HardwareCommands;
+
+    fn from_payload(payload: EventPayload) -> Result<Self, EventPayload> {
+        match payload {
+            EventPayload::Intent(envelope) => Ok(envelope),
+            other => Err(other),
+        }
+    }
+}
+
+/// [`EventPayload::IntentDispatched`].
+#[derive(Debug, Clone)]
+pub struct IntentDispatched(pub HardwareIntent);
+
+impl From<IntentDispatched> for EventPayload {
+    fn from(value: IntentDispatched) -> Self {
+        EventPayload::IntentDispatched(value.0)
+    }
+}
+
+impl TopicPayload for IntentDispatched {
+    const TOPIC: Topic = Topic::HardwareCommands;
+
+    fn from_payload(payload: EventPayload) -> Result<Self, EventPayload> {
+        match payload {
+            EventPayload::IntentDispatched(intent) => Ok(Self(intent)),
+            other => Err(other),
+        }
+    }
+}
+
+/// Declare a struct mirroring an `EventPayload` struct variant of the same
+/// name, its conversions and its topic.
+macro_rules! struct_payload {
+    (
+        $(#[$meta:meta])*
+        $name:ident on $topic:ident {
+            $($(#[$field_meta:meta])* $field:ident: $ty:ty),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct $name {
+            $($(#[$field_meta])* pub $field: $ty,)*
+        }
+
+        impl From<$name> for EventPayload {
+            fn from(value: $name) -> Self {
+                EventPayload::$name { $($field: value.$field),* }
+            }
+        }
+
+        impl TopicPayload for $name {
+            const TOPIC: Topic = Topic::$topic;
+
+            fn from_payload(payload: EventPayload) -> Result<Self, EventPayload> {
+                match payload {
+                    EventPayload::$name { $($field),* } => Ok(Self { $($field),* }),
+                    other => Err(other),
+                }
+            }
+        }
+    };
+}
+
+/// Declare a newtype over the `String` of an `EventPayload` tuple variant of
+/// the same name.
+macro_rules! text_payload {
+    ($(#[$meta:meta])* $name:ident on $topic:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $name(pub String);
+
+        impl From<$name> for EventPayload {
+            fn from(value: $name) -> Self {
+                EventPayload::$name(value.0)
+            }
+        }
+
+        impl TopicPayload for $name {
+            const TOPIC: Topic = Topic::$topic;
+
+            fn from_payload(payload: EventPayload) -> Result<Self, EventPayload> {
+                match payload {
+                    EventPayload::$name(text) => Ok(Self(text)),
+                    other => Err(other),
+                }
+            }
+        }
+    };
+}
+
+struct_payload! {
+    /// [`EventPayload::AdapterCommand`].
+    AdapterCommand on HardwareCommands {
+        topic: String,
+        json: serde_json::Value,
+    }
+}
+
+struct_payload! {
+    /// [`EventPayload::LidarScan`].
+    LidarScan on SensorHighRate {
+        ranges: Vec<f32>,
+        angle_min_rad: f32,
+        angle_increment_rad: f32,
+        /// Capture time, when the sensor reported one.
+        stamp: Option<DateTime<Utc>>,
+    }
+}
+
+struct_payload! {
+    /// [`EventPayload::PowerStatus`].
+    PowerStatus on Telemetry {
+        voltage: f32,
+        current: f32,
+        charging: bool,
+        percent: f32,
+    }
+}
+
+struct_payload! {
+    /// [`EventPayload::CameraFrame`].
+    CameraFrame on SensorHighRate {
+        image_id: String,
+        format: ImageFormat,
+        width: u32,
+        height: u32,
+        data_b64: String,
+    }
+}
+
+struct_payload! {
+    /// [`EventPayload::Attitude`].
+    Attitude on Telemetry {
+        roll_rad: f32,
+        pitch_rad: f32,
+        yaw_rad: f32,
+    }
+}
+
+struct_payload! {
+    /// [`EventPayload::GpsFix`].
+    GpsFix on Telemetry {
+        latitude_deg: f64,
+        longitude_deg: f64,
+        altitude: Meters,
+    }
+}
+
+struct_payload! {
+    /// [`EventPayload::BusHealth`].
+    BusHealth on Telemetry {
+        lanes: Vec<LaneStats>,
+    }
+}
+
+struct_payload! {
+    /// [`EventPayload::TrackedObstacle`].
+    TrackedObstacle on Telemetry {
+        id: u64,
+        x: Meters,
+        y: Meters,
+        velocity_x: MetersPerSecond,
+        velocity_y: MetersPerSecond,
+        radius: Meters,
+    }
+}
+
+struct_payload! {
+    /// [`EventPayload::MapSnapshot`].
+    MapSnapshot on Telemetry {
+        origin_x: Meters,
+        origin_y: Meters,
+        resolution: Meters,
+        width: u32,
+        height: u32,
+        cells_b64: String,
+        robot_x: Meters,
+        robot_y: Meters,
+        robot_heading_rad: f32,
+    }
+}
+
+struct_payload! {
+    /// [`EventPayload::HardwareFault`].
+    HardwareFault on SystemAlerts {
+        component: String,
+        code: FaultCode,
+        message: String,
+    }
+}
+
+struct_payload! {
+    /// [`EventPayload::ConnectionState`].
+    ConnectionState on SystemAlerts {
+        component: String,
+        state: LinkState,
+        attempt: u32,
+    }
+}
+
+struct_payload! {
+    /// [`EventPayload::SafetyRequest`].
+    SafetyRequest on SystemAlerts {
+        request_id: String,
+        command: SafetyCommand,
+    }
+}
+
+struct_payload! {
+    /// [`EventPayload::SafetyStatus`].
+    SafetyStatus on SystemAlerts {
+        request_id: Option<String>,
+        estopped: bool,
+        mode: SafetyMode,
+    }
+}
+
+/// [`EventPayload::ApprovalRequest`].
+///
+/// Declared by hand: [`IntentEnvelope`] is not `PartialEq`.
+#[derive(Debug, Clone)]
+pub struct ApprovalRequest {
+    pub request_id: String,
+    pub envelope: Box<IntentEnvelope>,
+    pub report: Vec<RuleVerdict>,
+}
+
+impl From<ApprovalRequest> for EventPayload {
+    fn from(value: ApprovalRequest) -> Self {
+        EventPayload::ApprovalRequest {
+            request_id: value.request_id,
+            envelope: value.envelope,
+            report: value.report,
+        }
+    }
+}
+
+impl TopicPayload for ApprovalRequest {
+    const TOPIC: Topic = Topic::SystemAlerts;
+
+    fn from_payload(payload: EventPayload) -> Result<Self, EventPayload> {
+        match payload {
+            EventPayload::ApprovalRequest {
+                request_id,
+                envelope,
+                report,
+            } => Ok(Self {
+                request_id,
+                envelope,
+                report,
+            }),
+            other => Err(other),
+        }
+    }
+}
+
+struct_payload! {
+    /// [`EventPayload::ApprovalDecision`].
+    ApprovalDecision on SystemAlerts {
+        request_id: String,
+        approved: bool,
+        operator: String,
+        reason: Option<String>,
+    }
+}
+
+struct_payload! {
+    /// [`EventPayload::IntentResult`].
+    IntentResult on SystemAlerts {
+        goal_id: String,
+        action: String,
+        success: bool,
+        duration_ms: u64,
+        message: String,
+    }
+}
+
+struct_payload! {
+    /// [`EventPayload::PolicyRequest`].
+    PolicyRequest on SystemAlerts {
+        request_id: String,
+        operator: String,
+        command: PolicyCommand,
+    }
+}
+
+struct_payload! {
+    /// [`EventPayload::PolicyStatus`].
+    PolicyStatus on SystemAlerts {
+        request_id: String,
+        report: Box<PolicyReport>,
+        error: Option<String>,
+    }
+}
+
+struct_payload! {
+    /// [`EventPayload::AgentModeToggle`].
+    AgentModeToggle on SystemAlerts {
+        paused: bool,
+    }
+}
+
+struct_payload! {
+    /// [`EventPayload::PeerMessage`].
+    PeerMessage on SwarmComm {
+        from_robot_id: String,
+        message: String,
+    }
+}
+
+struct_payload! {
+    /// [`EventPayload::TaskProgress`].
+    TaskProgress on SwarmComm {
+        task_id: String,
+        robot_id: String,
+        percent: f32,
+        note: String,
+    }
+}
+
+struct_payload! {
+    /// [`EventPayload::TaskCompleted`].
+    TaskCompleted on SwarmComm {
+        task_id: String,
+        robot_id: String,
+        result: serde_json::Value,
+    }
+}
+
+text_payload! {
+    /// [`EventPayload::Reasoning`].
+    Reasoning on CognitiveStream
+}
+
+/// The lane `payload`'s kind travels on, as listed in the table above.
+pub fn home_topic(payload: &EventPayload) -> Topic {
+    match payload {
+        EventPayload::Telemetry(_)
+        | EventPayload::PowerStatus { .. }
+        | EventPayload::Attitude { .. }
+        | EventPayload::GpsFix { .. }
+        | EventPayload::BusHealth { .. }
+        | EventPayload::TrackedObstacle { .. }
+        | EventPayload::MapSnapshot { .. } => Topic::Telemetry,
+        EventPayload::Intent(_)
+        | EventPayload::IntentDispatched(_)
+        | EventPayload::AdapterCommand { .. } => Topic::HardwareCommands,
+        EventPayload::HardwareFault { .. }
+        | EventPayload::ConnectionState { .. }
+        | EventPayload::AgentModeToggle { .. }
+        | EventPayload::SafetyRequest { .. }
+        | EventPayload::SafetyStatus { .. }
+        | EventPayload::ApprovalRequest { .. }
+        | EventPayload::ApprovalDecision { .. }
+        | EventPayload::PolicyRequest { .. }
+        | EventPayload::PolicyStatus { .. }
+        | EventPayload::IntentResult { .. }
+        | EventPayload::Unknown(_) => Topic::SystemAlerts,
+        EventPayload::PeerMessage { .. }
+        | EventPayload::TaskProgress { .. }
+        | EventPayload::TaskCompleted { .. } => Topic::SwarmComm,
+        EventPayload::Reasoning(_) | EventPayload::HumanResponse(_) => Topic::CognitiveStream,
+        EventPayload::LidarScan { .. } | EventPayload::CameraFrame { .. } => Topic::SensorHighRate,
+    }
+}
+
+/// A decoded payload with the envelope fields of the [`Event`] it came in.
+#[derive(Debug, Clone)]
+pub struct TypedEvent<T> {
+    pub id: Uuid,
+    pub timestamp: DateTime<Utc>,
+    pub source: String,
+    pub trace_id: Option<String>,
+    pub correlation_id: Option<Uuid>,
+    pub payload: T,
+}
+
+impl<T: TopicPayload> TypedEvent<T> {
+    fn decode(event: Event) -> Option<Self> {
+        let payload = T::from_payload(event.payload).ok()?;
+        Some(Self {
+            id: event.id,
+            timestamp: event.timestamp,
+            source: event.source,
+            trace_id: event.trace_id,
+            correlation_id: event.correlation_id,
+            payload,
+        })
+    }
+}
+
+/// A [`TopicReceiver`] on `T`'s topic that yields only `T` payloads.
+///
+/// Obtained via [`EventBus::subscribe_typed`](crate::EventBus::subscribe_typed).
+/// Other payload kinds on the same lane are skipped.
+pub struct TypedReceiver<T> {
+    inner: TopicReceiver,
+    _payload: PhantomData<fn() -> T>,
+}
+
+impl<T: TopicPayload> TypedReceiver<T> {
+    pub(crate) fn new(inner: TopicReceiver) -> Self {
+        Self {
+            inner,
+            _payload: PhantomData,
+        }
+    }
+
+    /// Wait for the next `T` on the topic.
+    ///
+    /// Errors are those of [`TopicReceiver::recv`]; `Lagged(n)` counts every
+    /// dropped event on the lane, whatever its kind.
+    pub async fn recv(&mut self) -> Result<T, broadcast::error::RecvError> {
+        Ok(self.recv_event().await?.payload)
+    }
+
+    /// Like [`recv`](Self::recv), keeping the event's id, timestamp, source
+    /// and trace id.
+    pub async fn recv_event(&mut self) -> Result<TypedEvent<T>, broadcast::error::RecvError> {
+        loop {
+            if let Some(event) = TypedEvent::decode(self.inner.recv().await?) {
+                return Ok(event);
+            }
+        }
+    }
+
+    /// The underlying untyped receiver.
+    pub fn inner(&self) -> &TopicReceiver {
+        &self.inner
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::EventBus;
+    use std::time::Duration;
+
+    #[test]
+    fn struct_payloads_roundtrip_through_event_payload() {
+        let fault = HardwareFault {
+            component: "cli".to_string(),
+            code: FaultCode::EmergencyStop,
+            message: "operator /halt".to_string(),
+        };
+        let payload: EventPayload = fault.clone().into();
+        assert!(matches!(
+            &payload,
+            EventPayload::HardwareFault { component, .. } if component == "cli"
+        ));
+        assert_eq!(HardwareFault::from_payload(payload).unwrap(), fault);
+
+        let other = EventPayload::Reasoning("hmm".to_string());
+        assert!(matches!(
+            ConnectionState::from_payload(other),
+            Err(EventPayload::Reasoning(_))
+        ));
+    }
+
+    #[test]
+    fn home_topic_matches_payload_types() {
+        let fault = HardwareFault {
+            component: "cli".to_string(),
+            code: FaultCode::EmergencyStop,
+            message: "operator /halt".to_string(),
+        };
+        assert_eq!(home_topic(&fault.into()), HardwareFault::TOPIC);
+        assert_eq!(
+            home_topic(&HumanAnswer::new("yes").into()),
+            HumanAnswer::TOPIC
+        );
+        assert_eq!(
+            home_topic(&AgentModeToggle { paused: true }.into()),
+            AgentModeToggle::TOPIC
+        );
+        let scan = LidarScan {
+            ranges: vec![1.0],
+            angle_min_rad: 0.0,
+            angle_increment_rad: 0.1,
+            stamp: None,
+        };
+        assert_eq!(home_topic(&scan.into()), Topic::SensorHighRate);
+        assert_eq!(LidarScan::TOPIC, Topic::SensorHighRate);
+    }
+
+    #[tokio::test]
+    async fn typed_subscriber_skips_other_payloads_on_its_lane() {
+        let bus = EventBus::default();
+        let mut thoughts = bus.subscribe_typed::<Reasoning>();
+        let mut responses = bus.subscribe_typed::<HumanAnswer>();
+
+        bus.publish_typed("mechos-runtime::agent", Reasoning("plan".to_string()))
+            .unwrap();
+        bus.publish_typed("mechos-cockpit::server", HumanAnswer::to_question("q-1", "yes"))
+            .unwrap();
+
+        assert_eq!(
+            responses.recv().await.unwrap(),
+            HumanAnswer::to_question("q-1", "yes")
+        );
+        let thought = thoughts.recv_event().await.unwrap();
+        assert_eq!(thought.payload.0, "plan");
+        assert_eq!(thought.source, "mechos-runtime::agent");
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), thoughts.recv())
+                .await
+                .is_err(),
+            "the human response is not Reasoning"
+        );
+    }
+}
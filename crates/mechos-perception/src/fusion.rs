@@ -1,7 +1,8 @@
 //! Sensor Fusion Engine.
 //!
-//! Combines heterogeneous sensor data streams (Odometry + IMU) into a single,
-//! unified [`FusedState`] estimate using a complementary filter.
+//! Combines heterogeneous sensor data streams (Odometry + IMU, plus GPS for
+//! outdoor robots) into a single, unified [`FusedState`] estimate using a
+//! complementary filter.
 //!
 //! The filter blends:
 //! - **Odometry** – absolute position and heading derived from wheel encoders
@@ -9,6 +10,9 @@
 //!   to drift.
 //! - **IMU** – gyroscope angular velocity; high-frequency and locally accurate
 //!   but unbounded drift over time.
+//! - **GPS** – absolute position in the local map frame (see
+//!   [`GeodeticDatum`](crate::transform::GeodeticDatum)); noisy but
+//!   drift-free, blended into the odometry position with a fixed weight.
 //!
 //! The complementary filter formula for heading is:
 //! ```text
@@ -16,6 +20,18 @@
 //! ```
 //! where α ∈ [0, 1] controls how much the IMU integration is trusted.
 //!
+//! # IMU bias and dead reckoning
+//!
+//! IMU samples are corrected by an [`ImuBias`] before use.  The bias is
+//! estimated by [`SensorFusion::calibrate`] from samples taken while the
+//! robot stands still.
+//!
+//! When odometry stops arriving for longer than the odometry timeout the
+//! filter falls back to IMU-only dead reckoning: every IMU sample integrates
+//! the gyro and accelerometer from the last known pose.  The estimate drifts
+//! quickly, so [`FusedState::degraded`] is set until odometry returns; the
+//! kernel's `StaleDataRule` reads it to restrict motion.
+//!
 //! # Example
 //!
 //! ```rust
@@ -39,6 +55,10 @@
 //! assert!((state.position_x - 1.0).abs() < 1e-5);
 //! ```
 
+use std::time::{Duration, Instant};
+
+use crate::types::{MAP_FRAME, Pose};
+
 // ────────────────────────────────────────────────────────────────────────────
 // Input types
 // ────────────────────────────────────────────────────────────────────────────
@@ -70,6 +90,37 @@ pub struct ImuData {
     pub linear_accel_y: f32,
 }
 
+/// Constant offsets an IMU reports while at rest, subtracted from every
+/// sample.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ImuBias {
+    /// Gyroscope bias around the vertical (Z) axis (rad/s).
+    pub angular_velocity_z: f32,
+    /// Accelerometer bias along the robot's X axis (m/s²).
+    pub linear_accel_x: f32,
+    /// Accelerometer bias along the robot's Y axis (m/s²).
+    pub linear_accel_y: f32,
+}
+
+impl ImuBias {
+    fn correct(&self, data: ImuData) -> ImuData {
+        ImuData {
+            angular_velocity_z: data.angular_velocity_z - self.angular_velocity_z,
+            linear_accel_x: data.linear_accel_x - self.linear_accel_x,
+            linear_accel_y: data.linear_accel_y - self.linear_accel_y,
+        }
+    }
+}
+
+/// A GPS position fix converted to the local map frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GpsData {
+    /// Robot X position in the world frame (metres east of the datum).
+    pub position_x: f32,
+    /// Robot Y position in the world frame (metres north of the datum).
+    pub position_y: f32,
+}
+
 // ────────────────────────────────────────────────────────────────────────────
 // Output type
 // ────────────────────────────────────────────────────────────────────────────
@@ -87,17 +138,45 @@ pub struct FusedState {
     pub velocity_x: f32,
     /// Estimated linear velocity along the robot's Y axis (m/s).
     pub velocity_y: f32,
+    /// `true` while odometry has dropped out and the pose is dead-reckoned
+    /// from the IMU alone (or frozen at the last odometry reading).
+    pub degraded: bool,
+}
+
+impl FusedState {
+    /// The estimated pose, in [`MAP_FRAME`].
+    pub fn pose(&self) -> Pose {
+        Pose::new(
+            MAP_FRAME,
+            self.position_x,
+            self.position_y,
+            self.heading_rad,
+        )
+    }
 }
 
 // ────────────────────────────────────────────────────────────────────────────
 // SensorFusion
 // ────────────────────────────────────────────────────────────────────────────
 
-/// Complementary filter that fuses [`OdometryData`] and [`ImuData`] into a
-/// single [`FusedState`].
+/// Default weight of a GPS fix against odometry; see
+/// [`SensorFusion::with_gps_weight`].
+pub const DEFAULT_GPS_WEIGHT: f32 = 0.2;
+
+/// Default time without odometry after which the filter falls back to
+/// IMU-only dead reckoning; see [`SensorFusion::with_odometry_timeout`].
+pub const DEFAULT_ODOMETRY_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Longest gap between IMU samples integrated as one dead-reckoning step
+/// (seconds), so a stalled IMU cannot fling the estimate away.
+const MAX_DEAD_RECKONING_STEP: f32 = 0.5;
+
+/// Complementary filter that fuses [`OdometryData`], [`ImuData`] and
+/// [`GpsData`] into a single [`FusedState`].
 ///
 /// Construct with [`SensorFusion::new`], feed measurements via
-/// [`SensorFusion::update_odometry`] and [`SensorFusion::update_imu`], then
+/// [`SensorFusion::update_odometry`], [`SensorFusion::update_imu`] and
+/// [`SensorFusion::update_gps`], then
 /// call [`SensorFusion::fused_state`] with the elapsed time `dt` to obtain
 /// the current estimate.
 #[derive(Debug)]
@@ -105,8 +184,18 @@ pub struct SensorFusion {
     /// Complementary filter coefficient (0–1).  Higher values trust the IMU
     /// gyroscope more for heading estimation.
     alpha: f32,
+    /// Weight (0–1) of the GPS position against the odometry position.
+    gps_weight: f32,
+    odometry_timeout: Duration,
+    bias: ImuBias,
     last_odometry: Option<OdometryData>,
+    last_odometry_at: Option<Instant>,
+    /// Latest IMU sample, bias-corrected.
     last_imu: Option<ImuData>,
+    last_imu_at: Option<Instant>,
+    last_gps: Option<GpsData>,
+    /// IMU-only pose while odometry is stale.
+    dead_reckoned: Option<OdometryData>,
 }
 
 impl SensorFusion {
@@ -118,19 +207,117 @@ impl SensorFusion {
     pub fn new(alpha: f32) -> Self {
         Self {
             alpha: alpha.clamp(0.0, 1.0),
+            gps_weight: DEFAULT_GPS_WEIGHT,
+            odometry_timeout: DEFAULT_ODOMETRY_TIMEOUT,
+            bias: ImuBias::default(),
             last_odometry: None,
+            last_odometry_at: None,
             last_imu: None,
+            last_imu_at: None,
+            last_gps: None,
+            dead_reckoned: None,
         }
     }
 
+    /// Fall back to IMU-only dead reckoning after `timeout` without odometry
+    /// instead of [`DEFAULT_ODOMETRY_TIMEOUT`].
+    pub fn with_odometry_timeout(mut self, timeout: Duration) -> Self {
+        self.odometry_timeout = timeout;
+        self
+    }
+
+    /// Weight GPS positions by `weight` (clamped to `[0, 1]`) against
+    /// odometry instead of [`DEFAULT_GPS_WEIGHT`].  Without odometry the GPS
+    /// position is used as is.
+    pub fn with_gps_weight(mut self, weight: f32) -> Self {
+        self.gps_weight = weight.clamp(0.0, 1.0);
+        self
+    }
+
     /// Feed a new odometry measurement into the filter.
     pub fn update_odometry(&mut self, data: OdometryData) {
+        self.update_odometry_at(data, Instant::now());
+    }
+
+    /// Like [`update_odometry`](Self::update_odometry), with an explicit
+    /// measurement time.  Ends IMU-only dead reckoning.
+    pub fn update_odometry_at(&mut self, data: OdometryData, at: Instant) {
         self.last_odometry = Some(data);
+        self.last_odometry_at = Some(at);
+        self.dead_reckoned = None;
     }
 
     /// Feed a new IMU measurement into the filter.
     pub fn update_imu(&mut self, data: ImuData) {
+        self.update_imu_at(data, Instant::now());
+    }
+
+    /// Like [`update_imu`](Self::update_imu), with an explicit measurement
+    /// time.  While odometry is stale the bias-corrected sample advances the
+    /// dead-reckoned pose.
+    pub fn update_imu_at(&mut self, raw: ImuData, at: Instant) {
+        let data = self.bias.correct(raw);
+        let dt = self.last_imu_at.map_or(0.0, |last| {
+            at.saturating_duration_since(last)
+                .as_secs_f32()
+                .min(MAX_DEAD_RECKONING_STEP)
+        });
         self.last_imu = Some(data);
+        self.last_imu_at = Some(at);
+
+        if !self.odometry_stale_at(at) {
+            return;
+        }
+        let Some(mut pose) = self.dead_reckoned.or(self.last_odometry) else {
+            return;
+        };
+        pose.heading_rad += data.angular_velocity_z * dt;
+        pose.velocity_x += data.linear_accel_x * dt;
+        pose.velocity_y += data.linear_accel_y * dt;
+        let (sin, cos) = pose.heading_rad.sin_cos();
+        pose.position_x += (pose.velocity_x * cos - pose.velocity_y * sin) * dt;
+        pose.position_y += (pose.velocity_x * sin + pose.velocity_y * cos) * dt;
+        self.dead_reckoned = Some(pose);
+    }
+
+    /// Estimate the IMU bias as the mean of `stationary_samples`, taken while
+    /// the robot stands still, and use it from now on.
+    ///
+    /// Returns the new bias, or `None` (keeping the current one) when no
+    /// samples are given.
+    pub fn calibrate(&mut self, stationary_samples: &[ImuData]) -> Option<ImuBias> {
+        if stationary_samples.is_empty() {
+            return None;
+        }
+        let n = stationary_samples.len() as f32;
+        let mean =
+            |field: fn(&ImuData) -> f32| stationary_samples.iter().map(field).sum::<f32>() / n;
+        self.bias = ImuBias {
+            angular_velocity_z: mean(|s| s.angular_velocity_z),
+            linear_accel_x: mean(|s| s.linear_accel_x),
+            linear_accel_y: mean(|s| s.linear_accel_y),
+        };
+        Some(self.bias)
+    }
+
+    /// The IMU bias currently subtracted from every sample.
+    pub fn imu_bias(&self) -> ImuBias {
+        self.bias
+    }
+
+    /// `true` while odometry has dropped out; see [`FusedState::degraded`].
+    pub fn is_degraded(&self) -> bool {
+        self.dead_reckoned.is_some() || self.odometry_stale_at(Instant::now())
+    }
+
+    /// Feed a new GPS fix, already in the local map frame, into the filter.
+    pub fn update_gps(&mut self, data: GpsData) {
+        self.last_gps = Some(data);
+    }
+
+    fn odometry_stale_at(&self, at: Instant) -> bool {
+        self.last_odometry_at
+            .is_some_and(|last| at.saturating_duration_since(last) > self.odometry_timeout)
     }
 
     /// Compute the current fused state estimate.
@@ -138,15 +325,31 @@ impl SensorFusion {
     /// `dt` is the time elapsed since the last call (seconds, must be ≥ 0).
     ///
     /// - Position and velocity are taken directly from the most recent
-    ///   odometry reading (or zero if none has been received yet).
+    ///   odometry reading (or zero if none has been received yet).  A GPS
+    ///   fix, when present, is blended into the position with the GPS
+    ///   weight, or replaces it when there is no odometry.
     /// - Heading is blended: the IMU-integrated heading prediction
     ///   (`heading_odom + ω * dt`) is weighted by `alpha`; the raw odometry
     ///   heading is weighted by `(1 − alpha)`.
+    /// - While odometry is stale, the dead-reckoned pose stands in for it and
+    ///   the state is marked [`degraded`](FusedState::degraded).
     pub fn fused_state(&self, dt: f32) -> FusedState {
         let dt = dt.max(0.0);
+        let degraded = self.is_degraded();
+        let odometry = if degraded {
+            self.dead_reckoned.or(self.last_odometry)
+        } else {
+            self.last_odometry
+        };
 
-        let (pos_x, pos_y, odom_heading, vel_x, vel_y) = match &self.last_odometry {
-            Some(o) => (o.position_x, o.position_y, o.heading_rad, o.velocity_x, o.velocity_y),
+        let (pos_x, pos_y, odom_heading, vel_x, vel_y) = match &odometry {
+            Some(o) => (
+                o.position_x,
+                o.position_y,
+                o.heading_rad,
+                o.velocity_x,
+                o.velocity_y,
+            ),
             None => (0.0, 0.0, 0.0, 0.0, 0.0),
         };
 
@@ -158,12 +361,25 @@ impl SensorFusion {
             None => odom_heading,
         };
 
+        let (pos_x, pos_y) = match (&self.last_gps, &self.last_odometry) {
+            (Some(gps), Some(_)) => {
+                let w = self.gps_weight;
+                (
+                    w * gps.position_x + (1.0 - w) * pos_x,
+                    w * gps.position_y + (1.0 - w) * pos_y,
+                )
+            }
+            (Some(gps), None) => (gps.position_x, gps.position_y),
+            (None, _) => (pos_x, pos_y),
+        };
+
         FusedState {
             position_x: pos_x,
             position_y: pos_y,
             heading_rad: heading,
             velocity_x: vel_x,
             velocity_y: vel_y,
+            degraded,
         }
     }
 }
@@ -295,4 +511,62 @@ mod tests {
         assert!((state.velocity_x - 1.2).abs() < 1e-5);
         assert!((state.velocity_y - 0.3).abs() < 1e-5);
     }
+
+    #[test]
+    fn gps_alone_sets_position_and_blends_with_odometry() {
+        let mut fusion = SensorFusion::new(0.98).with_gps_weight(0.25);
+        fusion.update_gps(GpsData {
+            position_x: 8.0,
+            position_y: -4.0,
+        });
+        let state = fusion.fused_state(0.01);
+        assert_eq!((state.position_x, state.position_y), (8.0, -4.0));
+
+        fusion.update_odometry(odom(4.0, 0.0, 0.0));
+        let state = fusion.fused_state(0.01);
+        assert!((state.position_x - 5.0).abs() < 1e-5);
+        assert!((state.position_y + 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn calibrate_estimates_and_removes_the_imu_bias() {
+        let mut fusion = SensorFusion::new(1.0);
+        assert!(fusion.calibrate(&[]).is_none());
+        let samples = [imu(0.02), imu(0.04), imu(0.03)];
+        let bias = fusion.calibrate(&samples).unwrap();
+        assert!((bias.angular_velocity_z - 0.03).abs() < 1e-6);
+
+        // A bias-only reading no longer turns the robot.
+        fusion.update_odometry(odom(0.0, 0.0, 0.0));
+        fusion.update_imu(imu(0.03));
+        assert!(fusion.fused_state(1.0).heading_rad.abs() < 1e-6);
+        assert_eq!(fusion.imu_bias(), bias);
+    }
+
+    #[test]
+    fn stale_odometry_falls_back_to_imu_dead_reckoning() {
+        let t0 = Instant::now();
+        let at = |ms: u64| t0 + Duration::from_millis(ms);
+        let mut fusion = SensorFusion::new(0.0).with_odometry_timeout(Duration::from_millis(200));
+        let mut moving = odom(1.0, 0.0, 0.0);
+        moving.velocity_x = 1.0;
+        fusion.update_odometry_at(moving, t0);
+        fusion.update_imu_at(imu(0.0), at(100));
+        assert!(!fusion.fused_state(0.0).degraded);
+
+        // Odometry drops out: integrate 1 m/s straight ahead from the last
+        // IMU sample, 0.7 s in all.
+        for ms in (300..=800).step_by(100) {
+            fusion.update_imu_at(imu(0.0), at(ms));
+        }
+        let state = fusion.fused_state(0.0);
+        assert!(state.degraded);
+        assert!((state.position_x - 1.7).abs() < 1e-4, "{state:?}");
+
+        // Odometry comes back and takes over again.
+        fusion.update_odometry_at(odom(1.5, 0.0, 0.0), at(900));
+        fusion.update_imu_at(imu(0.0), at(950));
+        assert!(!fusion.is_degraded());
+        assert_eq!(fusion.fused_state(0.0).position_x, 1.5);
+    }
 }
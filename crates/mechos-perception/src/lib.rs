@@ -7,14 +7,37 @@
 //!
 //! - [`transform`] – [`TfEngine`][transform::TfEngine]: directed graph that
 //!   computes spatial transforms (translations, rotations) between named
-//!   reference frames.
+//!   reference frames, optionally interpolated at a past time;
+//!   [`GeodeticDatum`][transform::GeodeticDatum] converts GPS
+//!   latitude/longitude into the local map frame.
 //! - [`fusion`] – [`SensorFusion`][fusion::SensorFusion]: complementary filter
-//!   that combines heterogeneous data streams (Odometry + IMU) into a unified
-//!   [`FusedState`][fusion::FusedState].
+//!   that combines heterogeneous data streams (Odometry + IMU + GPS) into a
+//!   unified [`FusedState`][fusion::FusedState].
 //! - [`octree`] – [`Octree`][octree::Octree]: uses an Octree to partition 3-D
 //!   space, providing fast collision detection so the LLM knows if a path is
 //!   clear.
+//! - [`occupancy`] – [`OccupancyGrid`][occupancy::OccupancyGrid]: rasterises
+//!   obstacle points into a 2-D log-odds grid of occupied, free and unknown
+//!   cells for the Cockpit map view and path planners.
+//! - [`planner`] – [`AStarPlanner`][planner::AStarPlanner] and
+//!   [`RrtStarPlanner`][planner::RrtStarPlanner]: plan collision-free
+//!   waypoint paths on the occupancy grid or against the octree.
+//! - [`tracking`] – [`ObstacleTracker`][tracking::ObstacleTracker]: clusters
+//!   LiDAR points across frames and estimates the velocity of each obstacle,
+//!   so moving ones can be given a wider berth.
+//! - [`trajectory`] – [`TrajectoryPredictor`][trajectory::TrajectoryPredictor]:
+//!   forward-simulates a `Drive` command with unicycle kinematics and sweeps
+//!   the robot footprint through the octree to predict collisions before the
+//!   command is dispatched.
+//! - [`types`] – [`Pose`][types::Pose] and
+//!   [`FrameAwareTwist`][types::FrameAwareTwist]: planar poses and velocities
+//!   tagged with the frame they are expressed in.
 
 pub mod fusion;
+pub mod occupancy;
 pub mod octree;
+pub mod planner;
+pub mod tracking;
+pub mod trajectory;
 pub mod transform;
+pub mod types;
@@ -0,0 +1,342 @@
+//! 2-D Occupancy Grid.
+//!
+//! Rasterises obstacle points into a fixed-resolution grid over the XY plane
+//! for the Cockpit map view and for path planners, which want dense cells
+//! rather than a sparse point index.
+//!
+//! Every cell holds the log-odds of being occupied.  A LiDAR return raises
+//! the cell it lands in; the cells the beam crossed on the way are lowered,
+//! so obstacles that move away fade out of the map:
+//!
+//! ```text
+//! l(cell) ← clamp(l(cell) + l_hit | l_miss, ±l_max)
+//! ```
+//!
+//! A cell reads as [`CellState::Occupied`] or [`CellState::Free`] once its
+//! log-odds clear the corresponding threshold, and [`CellState::Unknown`]
+//! until then.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mechos_perception::occupancy::{CellState, OccupancyGrid};
+//! use mechos_perception::octree::{Aabb, Point3};
+//!
+//! let bounds = Aabb::new(Point3::new(-5.0, -5.0, -1.0), Point3::new(5.0, 5.0, 1.0));
+//! let mut grid = OccupancyGrid::new(&bounds, 0.1);
+//!
+//! // A LiDAR at the origin sees a wall 2 m ahead.
+//! grid.integrate_ray(Point3::new(0.0, 0.0, 0.0), Point3::new(2.0, 0.0, 0.0));
+//! assert_eq!(grid.state(2.0, 0.0), CellState::Occupied);
+//! assert_eq!(grid.state(1.0, 0.0), CellState::Free);
+//! assert_eq!(grid.state(0.0, 3.0), CellState::Unknown);
+//! ```
+
+use crate::octree::{Aabb, Octree, Point3};
+
+/// Log-odds added to a cell a return landed in.
+const LOG_ODDS_HIT: f32 = 0.85;
+/// Log-odds added to a cell a beam passed through.
+const LOG_ODDS_MISS: f32 = -0.4;
+/// Bound on a cell's log-odds, so a long-observed cell can still change.
+const LOG_ODDS_MAX: f32 = 3.5;
+/// A cell is occupied above this log-odds and free below its negation.
+const LOG_ODDS_THRESHOLD: f32 = 0.2;
+
+/// Smallest accepted grid resolution (metres per cell).
+const MIN_RESOLUTION: f32 = 0.01;
+
+// ────────────────────────────────────────────────────────────────────────────
+// CellState
+// ────────────────────────────────────────────────────────────────────────────
+
+/// Classification of a grid cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellState {
+    /// Not observed, or observed inconclusively.
+    Unknown,
+    /// A beam passed through: the cell is traversable.
+    Free,
+    /// A return landed here: the cell is blocked.
+    Occupied,
+}
+
+impl CellState {
+    /// Byte encoding used by [`OccupancyGrid::to_bytes`], following ROS
+    /// `nav_msgs/OccupancyGrid`: `0` free, `100` occupied, `255` (`-1` as
+    /// `i8`) unknown.
+    pub fn to_byte(self) -> u8 {
+        match self {
+            CellState::Unknown => 255,
+            CellState::Free => 0,
+            CellState::Occupied => 100,
+        }
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// OccupancyGrid
+// ────────────────────────────────────────────────────────────────────────────
+
+/// A 2-D log-odds occupancy grid over the XY plane.
+///
+/// Cells are square, `resolution` metres on a side, stored row-major with
+/// row 0 at the minimum Y and column 0 at the minimum X of the grid's
+/// bounds.  Points outside the bounds are ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OccupancyGrid {
+    origin_x: f32,
+    origin_y: f32,
+    resolution: f32,
+    width: usize,
+    height: usize,
+    log_odds: Vec<f32>,
+}
+
+impl OccupancyGrid {
+    /// Create an all-unknown grid covering the XY extent of `bounds` with
+    /// cells `resolution` metres wide (at least 1 cm).
+    pub fn new(bounds: &Aabb, resolution: f32) -> Self {
+        let resolution = resolution.max(MIN_RESOLUTION);
+        let cells = |extent: f32| ((extent / resolution).ceil() as usize).max(1);
+        let width = cells(bounds.max.x - bounds.min.x);
+        let height = cells(bounds.max.y - bounds.min.y);
+        Self {
+            origin_x: bounds.min.x,
+            origin_y: bounds.min.y,
+            resolution,
+            width,
+            height,
+            log_odds: vec![0.0; width * height],
+        }
+    }
+
+    /// Rasterise every point of `octree` into a grid over the octree's
+    /// bounds.  Points carry no sensor origin, so cells are only ever marked
+    /// occupied; the rest stay unknown.
+    pub fn from_octree(octree: &Octree, resolution: f32) -> Self {
+        let mut grid = Self::new(&octree.bounds(), resolution);
+        for p in octree.export_points() {
+            grid.mark(p.x, p.y, true);
+        }
+        grid
+    }
+
+    /// Number of columns (along X).
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of rows (along Y).
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Cell size in metres.
+    pub fn resolution(&self) -> f32 {
+        self.resolution
+    }
+
+    /// World coordinates of the grid's minimum corner.
+    pub fn origin(&self) -> (f32, f32) {
+        (self.origin_x, self.origin_y)
+    }
+
+    /// Column and row of the cell containing `(x, y)`; `None` outside the
+    /// grid.
+    pub fn cell_index(&self, x: f32, y: f32) -> Option<(usize, usize)> {
+        self.cell_at(x, y)
+    }
+
+    /// World coordinates of the centre of cell (`col`, `row`).
+    pub fn cell_centre(&self, col: usize, row: usize) -> (f32, f32) {
+        (
+            self.origin_x + (col as f32 + 0.5) * self.resolution,
+            self.origin_y + (row as f32 + 0.5) * self.resolution,
+        )
+    }
+
+    /// Record one observation of the cell containing `(x, y)`: a return
+    /// (`occupied = true`) or a beam passing through (`false`).
+    pub fn mark(&mut self, x: f32, y: f32, occupied: bool) {
+        if let Some((col, row)) = self.cell_at(x, y) {
+            self.update(col, row, occupied);
+        }
+    }
+
+    /// Integrate one LiDAR beam from the sensor at `origin` to its return at
+    /// `hit`: cells along the beam become freer, the cell at `hit` more
+    /// occupied.
+    pub fn integrate_ray(&mut self, origin: Point3, hit: Point3) {
+        let (from_col, from_row) = self.cell_coords(origin.x, origin.y);
+        let (to_col, to_row) = self.cell_coords(hit.x, hit.y);
+        // Bresenham walk over the cells between the two endpoints.
+        let (dx, dy) = ((to_col - from_col).abs(), -(to_row - from_row).abs());
+        let (step_x, step_y) = ((to_col - from_col).signum(), (to_row - from_row).signum());
+        let (mut col, mut row, mut err) = (from_col, from_row, dx + dy);
+        while (col, row) != (to_col, to_row) {
+            if let Some((c, r)) = self.in_bounds(col, row) {
+                self.update(c, r, false);
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                col += step_x;
+            }
+            if e2 <= dx {
+                err += dx;
+                row += step_y;
+            }
+        }
+        if let Some((c, r)) = self.in_bounds(to_col, to_row) {
+            self.update(c, r, true);
+        }
+    }
+
+    /// Integrate a whole scan taken from `origin`; see
+    /// [`integrate_ray`](Self::integrate_ray).
+    pub fn integrate_scan(&mut self, origin: Point3, hits: &[Point3]) {
+        for &hit in hits {
+            self.integrate_ray(origin, hit);
+        }
+    }
+
+    /// State of the cell containing `(x, y)`; [`CellState::Unknown`] outside
+    /// the grid.
+    pub fn state(&self, x: f32, y: f32) -> CellState {
+        self.cell_at(x, y)
+            .map_or(CellState::Unknown, |(col, row)| self.cell(col, row))
+    }
+
+    /// State of the cell at column `col`, row `row`; [`CellState::Unknown`]
+    /// outside the grid.
+    pub fn cell(&self, col: usize, row: usize) -> CellState {
+        if col >= self.width || row >= self.height {
+            return CellState::Unknown;
+        }
+        let l = self.log_odds[row * self.width + col];
+        if l > LOG_ODDS_THRESHOLD {
+            CellState::Occupied
+        } else if l < -LOG_ODDS_THRESHOLD {
+            CellState::Free
+        } else {
+            CellState::Unknown
+        }
+    }
+
+    /// Probability (0–1) that the cell containing `(x, y)` is occupied;
+    /// `None` outside the grid.
+    pub fn probability(&self, x: f32, y: f32) -> Option<f32> {
+        let (col, row) = self.cell_at(x, y)?;
+        let l = self.log_odds[row * self.width + col];
+        Some(1.0 - 1.0 / (1.0 + l.exp()))
+    }
+
+    /// Export the grid as one byte per cell, row-major, in the
+    /// [`CellState::to_byte`] encoding.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        (0..self.height)
+            .flat_map(|row| (0..self.width).map(move |col| (col, row)))
+            .map(|(col, row)| self.cell(col, row).to_byte())
+            .collect()
+    }
+
+    // -----------------------------------------------------------------------
+
+    fn update(&mut self, col: usize, row: usize, occupied: bool) {
+        let delta = if occupied {
+            LOG_ODDS_HIT
+        } else {
+            LOG_ODDS_MISS
+        };
+        let l = &mut self.log_odds[row * self.width + col];
+        *l = (*l + delta).clamp(-LOG_ODDS_MAX, LOG_ODDS_MAX);
+    }
+
+    /// Cell coordinates of `(x, y)`, possibly outside the grid.
+    fn cell_coords(&self, x: f32, y: f32) -> (i64, i64) {
+        (
+            ((x - self.origin_x) / self.resolution).floor() as i64,
+            ((y - self.origin_y) / self.resolution).floor() as i64,
+        )
+    }
+
+    fn in_bounds(&self, col: i64, row: i64) -> Option<(usize, usize)> {
+        let col = usize::try_from(col).ok().filter(|c| *c < self.width)?;
+        let row = usize::try_from(row).ok().filter(|r| *r < self.height)?;
+        Some((col, row))
+    }
+
+    fn cell_at(&self, x: f32, y: f32) -> Option<(usize, usize)> {
+        let (col, row) = self.cell_coords(x, y);
+        self.in_bounds(col, row)
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Tests
+// ────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounds() -> Aabb {
+        Aabb::new(Point3::new(0.0, 0.0, -1.0), Point3::new(4.0, 2.0, 1.0))
+    }
+
+    #[test]
+    fn new_grid_covers_bounds_and_is_unknown() {
+        let grid = OccupancyGrid::new(&bounds(), 0.5);
+        assert_eq!((grid.width(), grid.height()), (8, 4));
+        assert_eq!(grid.origin(), (0.0, 0.0));
+        assert_eq!(grid.state(1.0, 1.0), CellState::Unknown);
+        assert_eq!(grid.state(-1.0, 1.0), CellState::Unknown);
+        assert_eq!(grid.probability(1.0, 1.0), Some(0.5));
+        assert_eq!(grid.probability(9.0, 1.0), None);
+    }
+
+    #[test]
+    fn from_octree_marks_points_occupied() {
+        let mut octree = Octree::new(bounds(), 8);
+        octree.insert(Point3::new(1.2, 0.3, 0.0));
+        octree.insert(Point3::new(3.9, 1.9, 0.5));
+        let grid = OccupancyGrid::from_octree(&octree, 0.5);
+        assert_eq!(grid.state(1.0, 0.1), CellState::Occupied);
+        assert_eq!(grid.cell(7, 3), CellState::Occupied);
+        assert_eq!(grid.state(2.0, 1.0), CellState::Unknown);
+    }
+
+    #[test]
+    fn rays_clear_their_path_and_misses_outvote_old_hits() {
+        let mut grid = OccupancyGrid::new(&bounds(), 0.5);
+        let origin = Point3::new(0.25, 0.25, 0.0);
+        grid.integrate_scan(
+            origin,
+            &[Point3::new(3.25, 0.25, 0.0), Point3::new(0.25, 1.75, 0.0)],
+        );
+        for x in [0.25, 1.25, 2.75] {
+            assert_eq!(grid.state(x, 0.25), CellState::Free, "x={x}");
+        }
+        assert_eq!(grid.state(3.25, 0.25), CellState::Occupied);
+        assert_eq!(grid.state(0.25, 1.75), CellState::Occupied);
+        assert_eq!(grid.state(3.75, 0.25), CellState::Unknown);
+
+        // The obstacle moves away: a longer beam now passes through it.
+        for _ in 0..5 {
+            grid.integrate_ray(origin, Point3::new(3.75, 0.25, 0.0));
+        }
+        assert_eq!(grid.state(3.25, 0.25), CellState::Free);
+        assert_eq!(grid.state(3.75, 0.25), CellState::Occupied);
+    }
+
+    #[test]
+    fn to_bytes_is_row_major_nav_msgs_encoding() {
+        let mut grid = OccupancyGrid::new(&bounds(), 1.0);
+        grid.integrate_ray(Point3::new(0.5, 0.5, 0.0), Point3::new(2.5, 1.5, 0.0));
+        let bytes = grid.to_bytes();
+        assert_eq!(bytes.len(), 4 * 2);
+        // Row 0 then row 1; the beam crosses (0, 0) and (1, 1).
+        assert_eq!(bytes, vec![0, 255, 255, 255, 255, 0, 100, 255]);
+    }
+}
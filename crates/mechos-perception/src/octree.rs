@@ -11,6 +11,32 @@
 //! | [`Point3`]   | A 3-D coordinate.                                      |
 //! | [`Aabb`]     | An axis-aligned bounding box.                          |
 //! | [`Octree`]   | Spatial index; insert points, query for collisions.    |
+//! | [`RayHit`]   | The first point a ray meets ([`Octree::raycast`]).     |
+//! | [`Label`]    | What a point belongs to (`"person"`, `"door"`, …).     |
+//!
+//! Beyond boolean box hits, [`Octree::nearest`] and [`Octree::k_nearest`]
+//! answer "how far is the closest obstacle?" with a best-first traversal that
+//! only opens nodes closer than the best candidates found so far.
+//!
+//! [`Octree::clear_ray`] carves free space: it removes every point along a
+//! sensor beam, so obstacles that moved away disappear once a beam passes
+//! through where they used to be.
+//!
+//! Every point remembers when it was last inserted.  Re-inserting a point
+//! refreshes it, [`Octree::evict_older_than`] drops points nobody has seen
+//! for a while (people walking by), and [`Octree::with_max_points`] caps the
+//! tree, evicting the least recently seen points, so a long mission cannot
+//! grow the map without bound.
+//!
+//! Points inserted with [`Octree::insert_labeled`] also carry a semantic
+//! [`Label`] from an object detector, so a query can say *what* occupies a
+//! region ([`Octree::labels_in`]) or where all objects of a class are
+//! ([`Octree::query_label`]).  Unlabelled re-inserts, such as LiDAR returns
+//! from the same surface, keep the label.
+//!
+//! All points share one frame, [`Octree::frame_id`] (`map` unless set with
+//! [`Octree::with_frame_id`]); convert sensor data into it with the
+//! [`TfEngine`](crate::transform::TfEngine) before inserting.
 //!
 //! # Example
 //!
@@ -33,6 +59,12 @@
 //! assert!(tree.query_aabb(&probe));
 //! ```
 
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::time::{Duration, Instant};
+
+use crate::types::MAP_FRAME;
+
 // ────────────────────────────────────────────────────────────────────────────
 // Point3
 // ────────────────────────────────────────────────────────────────────────────
@@ -93,6 +125,17 @@ impl Aabb {
             && p.z <= self.max.z
     }
 
+    /// Squared distance from `p` to the nearest point of the box; `0` inside.
+    fn distance_sq(&self, p: Point3) -> f32 {
+        let axis = |v: f32, lo: f32, hi: f32| (lo - v).max(0.0).max(v - hi);
+        let (dx, dy, dz) = (
+            axis(p.x, self.min.x, self.max.x),
+            axis(p.y, self.min.y, self.max.y),
+            axis(p.z, self.min.z, self.max.z),
+        );
+        dx * dx + dy * dy + dz * dz
+    }
+
     /// True when `other` overlaps (intersects or touches) this box.
     pub fn overlaps(&self, other: &Aabb) -> bool {
         self.min.x <= other.max.x
@@ -104,10 +147,46 @@ impl Aabb {
     }
 }
 
+// ────────────────────────────────────────────────────────────────────────────
+// Label
+// ────────────────────────────────────────────────────────────────────────────
+
+/// The semantic class of a stored point and the detector's confidence in it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Label {
+    /// Object class, e.g. `"person"`, `"shelf"` or `"door"`.
+    pub class: String,
+    /// Detection confidence in `[0.0, 1.0]`.
+    pub confidence: f32,
+}
+
+impl Label {
+    /// Create a label; `confidence` is clamped to `[0.0, 1.0]`.
+    pub fn new(class: impl Into<String>, confidence: f32) -> Self {
+        Self {
+            class: class.into(),
+            confidence: confidence.clamp(0.0, 1.0),
+        }
+    }
+}
+
 // ────────────────────────────────────────────────────────────────────────────
 // Octree
 // ────────────────────────────────────────────────────────────────────────────
 
+/// Points within this distance (metres) of a ray count as hit by
+/// [`Octree::raycast`].
+pub const RAY_HIT_RADIUS: f32 = 0.05;
+
+/// The first point a ray meets.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit {
+    /// The stored point that was hit.
+    pub point: Point3,
+    /// Distance along the ray to the point's projection (metres).
+    pub distance: f32,
+}
+
 /// A recursive spatial index that subdivides 3-D space into eight octants.
 ///
 /// Points are stored in the deepest node whose bounding box still contains
@@ -122,6 +201,11 @@ impl Aabb {
 pub struct Octree {
     root: OctreeNode,
     max_depth: usize,
+    /// Cap on stored points; `None` for unbounded.
+    max_points: Option<usize>,
+    len: usize,
+    /// Frame every stored point is expressed in.
+    frame_id: String,
 }
 
 impl Octree {
@@ -132,6 +216,9 @@ impl Octree {
         Self {
             root: OctreeNode::new(bounds, capacity),
             max_depth: 8,
+            max_points: None,
+            len: 0,
+            frame_id: MAP_FRAME.to_string(),
         }
     }
 
@@ -140,19 +227,70 @@ impl Octree {
         Self {
             root: OctreeNode::new(bounds, capacity),
             max_depth,
+            max_points: None,
+            len: 0,
+            frame_id: MAP_FRAME.to_string(),
         }
     }
 
+    /// Hold at most `max_points` points.  When an insert goes over the cap,
+    /// the least recently inserted points are evicted, along with a further
+    /// tenth of the cap so the next inserts do not each pay for an eviction.
+    pub fn with_max_points(mut self, max_points: usize) -> Self {
+        self.max_points = Some(max_points);
+        self.enforce_cap();
+        self
+    }
+
+    /// Declare the frame the tree's points are expressed in.
+    pub fn with_frame_id(mut self, frame_id: impl Into<String>) -> Self {
+        self.frame_id = frame_id.into();
+        self
+    }
+
+    /// The frame the tree's points are expressed in.
+    pub fn frame_id(&self) -> &str {
+        &self.frame_id
+    }
+
     /// Insert a point into the tree.
     ///
     /// Points outside the root bounding box are silently ignored.
+    /// Re-inserting a stored point refreshes its timestamp instead of storing
+    /// it twice.
     pub fn insert(&mut self, point: Point3) {
-        self.root.insert(point, self.max_depth, 0);
+        self.insert_at(point, Instant::now());
+    }
+
+    /// Like [`insert`](Self::insert), stamping the point with `at` instead
+    /// of the current time.
+    pub fn insert_at(&mut self, point: Point3, at: Instant) {
+        self.insert_entry(Entry {
+            point,
+            stamp: at,
+            label: None,
+        });
+    }
+
+    /// Insert a point that an object detector classified as `label`.
+    ///
+    /// Re-inserting a stored point replaces its label.
+    pub fn insert_labeled(&mut self, point: Point3, label: Label) {
+        self.insert_entry(Entry {
+            point,
+            stamp: Instant::now(),
+            label: Some(label),
+        });
+    }
+
+    /// The bounding box covered by the tree.
+    pub fn bounds(&self) -> Aabb {
+        self.root.bounds
     }
 
     /// Return the total number of points stored in the tree.
     pub fn len(&self) -> usize {
-        self.root.count()
+        self.len
     }
 
     /// True when the tree contains no points.
@@ -162,7 +300,34 @@ impl Octree {
 
     /// True when the tree contains a point equal to `p`.
     pub fn contains(&self, p: Point3) -> bool {
-        self.root.contains(p)
+        self.root.find(p).is_some()
+    }
+
+    /// The label of the stored point equal to `p`, if it has one.
+    pub fn label_at(&self, p: Point3) -> Option<&Label> {
+        self.root.find(p).and_then(|entry| entry.label.as_ref())
+    }
+
+    /// Every labelled point inside `region`, with its label.
+    pub fn labels_in(&self, region: &Aabb) -> Vec<(Point3, &Label)> {
+        let mut found = Vec::new();
+        self.root.visit(region, &mut |entry| {
+            if let Some(label) = &entry.label {
+                found.push((entry.point, label));
+            }
+        });
+        found
+    }
+
+    /// Every point labelled `class`, with its detection confidence.
+    pub fn query_label(&self, class: &str) -> Vec<(Point3, f32)> {
+        let mut found = Vec::new();
+        self.root.visit(&self.root.bounds, &mut |entry| {
+            if let Some(label) = entry.label.as_ref().filter(|l| l.class == class) {
+                found.push((entry.point, label.confidence));
+            }
+        });
+        found
     }
 
     /// True when any point in the tree lies inside `region`.
@@ -173,6 +338,108 @@ impl Octree {
         self.root.query_aabb(region)
     }
 
+    /// The stored point closest to `p`, with its distance in metres.
+    pub fn nearest(&self, p: Point3) -> Option<(Point3, f32)> {
+        self.k_nearest(p, 1).pop()
+    }
+
+    /// Up to `k` stored points closest to `p`, nearest first, with their
+    /// distances in metres.
+    pub fn k_nearest(&self, p: Point3, k: usize) -> Vec<(Point3, f32)> {
+        let mut found = Vec::with_capacity(k.min(self.len));
+        if k == 0 {
+            return found;
+        }
+        // Min-heap of nodes and points keyed by their distance to `p`.  A
+        // point popped off the heap is closer than anything still queued.
+        let mut queue = BinaryHeap::new();
+        queue.push(Candidate {
+            distance_sq: self.root.bounds.distance_sq(p),
+            item: CandidateItem::Node(&self.root),
+        });
+        while let Some(Candidate { distance_sq, item }) = queue.pop() {
+            match item {
+                CandidateItem::Point(point) => {
+                    found.push((point, distance_sq.sqrt()));
+                    if found.len() == k {
+                        break;
+                    }
+                }
+                CandidateItem::Node(node) => {
+                    for entry in &node.points {
+                        let (dx, dy, dz) = (
+                            entry.point.x - p.x,
+                            entry.point.y - p.y,
+                            entry.point.z - p.z,
+                        );
+                        queue.push(Candidate {
+                            distance_sq: dx * dx + dy * dy + dz * dz,
+                            item: CandidateItem::Point(entry.point),
+                        });
+                    }
+                    for child in node.children.iter().flat_map(|c| c.iter()) {
+                        queue.push(Candidate {
+                            distance_sq: child.bounds.distance_sq(p),
+                            item: CandidateItem::Node(child),
+                        });
+                    }
+                }
+            }
+        }
+        found
+    }
+
+    /// Cast a ray from `origin` along `direction` (any non-zero length) and
+    /// return the nearest point within [`RAY_HIT_RADIUS`] of it, up to
+    /// `max_range` metres away.
+    pub fn raycast(&self, origin: Point3, direction: Point3, max_range: f32) -> Option<RayHit> {
+        let len =
+            (direction.x * direction.x + direction.y * direction.y + direction.z * direction.z)
+                .sqrt();
+        if len <= f32::EPSILON || !len.is_finite() {
+            return None;
+        }
+        let dir = Point3::new(direction.x / len, direction.y / len, direction.z / len);
+        let mut best = None;
+        self.root.raycast(origin, dir, max_range, &mut best);
+        best
+    }
+
+    /// Remove every point within [`RAY_HIT_RADIUS`] of the ray from
+    /// `origin` along `direction`, up to `max_range` metres away, and return
+    /// how many were removed.
+    ///
+    /// Call it with the free part of a sensor beam (short of its return) to
+    /// carve out obstacles that are no longer there.
+    pub fn clear_ray(&mut self, origin: Point3, direction: Point3, max_range: f32) -> usize {
+        let mut removed = 0;
+        while let Some(hit) = self.raycast(origin, direction, max_range) {
+            if !self.remove(hit.point) {
+                break;
+            }
+            removed += 1;
+        }
+        removed
+    }
+
+    /// Remove the point equal to `p`; `true` when one was found.
+    pub fn remove(&mut self, p: Point3) -> bool {
+        let removed = self.root.remove(p);
+        if removed {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    /// Remove every point last inserted more than `age` ago and return how
+    /// many were removed.
+    pub fn evict_older_than(&mut self, age: Duration) -> usize {
+        let Some(cutoff) = Instant::now().checked_sub(age) else {
+            return 0;
+        };
+        self.retain_entries(|entry| entry.stamp >= cutoff)
+    }
+
     /// Export all points currently stored in the tree.
     ///
     /// This is used for Octree map sharing: a robot serialises its spatial map
@@ -195,18 +462,110 @@ impl Octree {
             self.insert(p);
         }
     }
+
+    /// Keep only the points for which `keep` returns `true`.
+    ///
+    /// Used to drop points that went stale, such as obstacles merged from a
+    /// peer's map that the peer no longer reports.  The tree is not
+    /// re-balanced; emptied leaves stay in place.
+    pub fn retain(&mut self, mut keep: impl FnMut(&Point3) -> bool) {
+        self.retain_entries(|entry| keep(&entry.point));
+    }
+
+    // -----------------------------------------------------------------------
+
+    fn insert_entry(&mut self, entry: Entry) {
+        if self.root.insert(entry, self.max_depth, 0) {
+            self.len += 1;
+            self.enforce_cap();
+        }
+    }
+
+    fn retain_entries(&mut self, mut keep: impl FnMut(&Entry) -> bool) -> usize {
+        let removed = self.root.retain(&mut keep);
+        self.len -= removed;
+        removed
+    }
+
+    /// Evict the least recently inserted points once over `max_points`.
+    fn enforce_cap(&mut self) {
+        let Some(max_points) = self.max_points else {
+            return;
+        };
+        if self.len <= max_points {
+            return;
+        }
+        let evict = (self.len - max_points + max_points / 10).min(self.len);
+        let mut stamps = Vec::with_capacity(self.len);
+        self.root.collect_stamps(&mut stamps);
+        let (_, &mut cutoff, _) = stamps.select_nth_unstable(evict - 1);
+        // Everything older than the cutoff goes, plus as many points stamped
+        // exactly at the cutoff as are needed to reach `evict`.
+        let older = stamps.iter().filter(|s| **s < cutoff).count();
+        let mut ties = evict - older;
+        self.retain_entries(|entry| {
+            if entry.stamp < cutoff {
+                false
+            } else if entry.stamp == cutoff && ties > 0 {
+                ties -= 1;
+                false
+            } else {
+                true
+            }
+        });
+    }
 }
 
 // ────────────────────────────────────────────────────────────────────────────
 // OctreeNode – internal implementation
 // ────────────────────────────────────────────────────────────────────────────
 
+/// An entry of the [`Octree::k_nearest`] search queue.
+struct Candidate<'a> {
+    distance_sq: f32,
+    item: CandidateItem<'a>,
+}
+
+enum CandidateItem<'a> {
+    Node(&'a OctreeNode),
+    Point(Point3),
+}
+
+// Reversed so that `BinaryHeap` pops the nearest candidate first.
+impl Ord for Candidate<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.distance_sq.total_cmp(&self.distance_sq)
+    }
+}
+
+impl PartialOrd for Candidate<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Candidate<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate<'_> {}
+
+/// A stored point, when it was last inserted and what it belongs to.
+#[derive(Debug, Clone)]
+struct Entry {
+    point: Point3,
+    stamp: Instant,
+    label: Option<Label>,
+}
+
 #[derive(Debug)]
 struct OctreeNode {
     bounds: Aabb,
     capacity: usize,
     /// Points stored at this node (only non-empty when the node is a leaf).
-    points: Vec<Point3>,
+    points: Vec<Entry>,
     /// Eight children; `None` while this node is a leaf.
     children: Option<Box<[OctreeNode; 8]>>,
 }
@@ -225,56 +584,76 @@ impl OctreeNode {
         self.children.is_none()
     }
 
-    fn count(&self) -> usize {
-        if self.is_leaf() {
-            self.points.len()
-        } else if let Some(children) = &self.children {
-            children.iter().map(|c| c.count()).sum()
-        } else {
-            unreachable!("non-leaf OctreeNode must have children")
-        }
-    }
-
-    fn insert(&mut self, point: Point3, max_depth: usize, depth: usize) {
-        if !self.bounds.contains_point(point) {
-            return;
+    /// Insert `entry`; `true` when its point was not stored yet.  A stored
+    /// point is refreshed, keeping its label unless `entry` has one.
+    fn insert(&mut self, entry: Entry, max_depth: usize, depth: usize) -> bool {
+        if !self.bounds.contains_point(entry.point) {
+            return false;
         }
 
         if self.is_leaf() {
-            self.points.push(point);
+            if let Some(stored) = self.points.iter_mut().find(|e| e.point == entry.point) {
+                stored.stamp = entry.stamp;
+                if entry.label.is_some() {
+                    stored.label = entry.label;
+                }
+                return false;
+            }
+            self.points.push(entry);
             // Subdivide when over capacity and depth budget remains.
             if self.points.len() > self.capacity && depth < max_depth {
                 self.subdivide(max_depth, depth);
             }
+            true
         } else if let Some(children) = self.children.as_mut() {
-            for child in children.iter_mut() {
-                if child.bounds.contains_point(point) {
-                    child.insert(point, max_depth, depth + 1);
-                    return;
-                }
-            }
+            children
+                .iter_mut()
+                .find(|child| child.bounds.contains_point(entry.point))
+                .is_some_and(|child| child.insert(entry, max_depth, depth + 1))
+        } else {
+            unreachable!("non-leaf OctreeNode must have children")
         }
     }
 
-    fn contains(&self, p: Point3) -> bool {
+    fn find(&self, p: Point3) -> Option<&Entry> {
         if !self.bounds.contains_point(p) {
-            return false;
+            return None;
         }
         if self.is_leaf() {
-            self.points.contains(&p)
+            self.points.iter().find(|e| e.point == p)
         } else if let Some(children) = &self.children {
-            children.iter().any(|c| c.contains(p))
+            children.iter().find_map(|c| c.find(p))
         } else {
             unreachable!("non-leaf OctreeNode must have children")
         }
     }
 
+    /// Call `f` with every entry inside `region`.
+    fn visit<'a>(&'a self, region: &Aabb, f: &mut impl FnMut(&'a Entry)) {
+        if !self.bounds.overlaps(region) {
+            return;
+        }
+        if self.is_leaf() {
+            for entry in self
+                .points
+                .iter()
+                .filter(|e| region.contains_point(e.point))
+            {
+                f(entry);
+            }
+        } else if let Some(children) = &self.children {
+            for child in children.iter() {
+                child.visit(region, f);
+            }
+        }
+    }
+
     fn query_aabb(&self, region: &Aabb) -> bool {
         if !self.bounds.overlaps(region) {
             return false;
         }
         if self.is_leaf() {
-            self.points.iter().any(|p| region.contains_point(*p))
+            self.points.iter().any(|e| region.contains_point(e.point))
         } else if let Some(children) = &self.children {
             children.iter().any(|c| c.query_aabb(region))
         } else {
@@ -282,10 +661,84 @@ impl OctreeNode {
         }
     }
 
+    fn raycast(&self, origin: Point3, dir: Point3, max_range: f32, best: &mut Option<RayHit>) {
+        let range = best.map_or(max_range, |hit| hit.distance);
+        if !self.ray_overlaps(origin, dir, range) {
+            return;
+        }
+        if self.is_leaf() {
+            for &Entry { point: p, .. } in &self.points {
+                let v = Point3::new(p.x - origin.x, p.y - origin.y, p.z - origin.z);
+                let t = v.x * dir.x + v.y * dir.y + v.z * dir.z;
+                let off_ray_sq = v.x * v.x + v.y * v.y + v.z * v.z - t * t;
+                let nearer = best.is_none_or(|hit| t < hit.distance);
+                if (0.0..=max_range).contains(&t)
+                    && off_ray_sq <= RAY_HIT_RADIUS * RAY_HIT_RADIUS
+                    && nearer
+                {
+                    *best = Some(RayHit {
+                        point: p,
+                        distance: t,
+                    });
+                }
+            }
+        } else if let Some(children) = &self.children {
+            for child in children.iter() {
+                child.raycast(origin, dir, max_range, best);
+            }
+        }
+    }
+
+    /// Slab test: does the ray segment `[0, range]` pass within
+    /// [`RAY_HIT_RADIUS`] of this node's bounds?
+    fn ray_overlaps(&self, origin: Point3, dir: Point3, range: f32) -> bool {
+        let (mut t_min, mut t_max) = (0.0f32, range);
+        let axes = [
+            (origin.x, dir.x, self.bounds.min.x, self.bounds.max.x),
+            (origin.y, dir.y, self.bounds.min.y, self.bounds.max.y),
+            (origin.z, dir.z, self.bounds.min.z, self.bounds.max.z),
+        ];
+        for (o, d, lo, hi) in axes {
+            let (lo, hi) = (lo - RAY_HIT_RADIUS, hi + RAY_HIT_RADIUS);
+            if d.abs() <= f32::EPSILON {
+                if o < lo || o > hi {
+                    return false;
+                }
+                continue;
+            }
+            let (a, b) = ((lo - o) / d, (hi - o) / d);
+            t_min = t_min.max(a.min(b));
+            t_max = t_max.min(a.max(b));
+            if t_min > t_max {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn remove(&mut self, p: Point3) -> bool {
+        if !self.bounds.contains_point(p) {
+            return false;
+        }
+        if self.is_leaf() {
+            match self.points.iter().position(|e| e.point == p) {
+                Some(i) => {
+                    self.points.swap_remove(i);
+                    true
+                }
+                None => false,
+            }
+        } else if let Some(children) = self.children.as_mut() {
+            children.iter_mut().any(|c| c.remove(p))
+        } else {
+            unreachable!("non-leaf OctreeNode must have children")
+        }
+    }
+
     /// Collect all stored points into `out` (depth-first traversal).
     fn collect_points(&self, out: &mut Vec<Point3>) {
         if self.is_leaf() {
-            out.extend_from_slice(&self.points);
+            out.extend(self.points.iter().map(|e| e.point));
         } else if let Some(children) = &self.children {
             for child in children.iter() {
                 child.collect_points(out);
@@ -293,6 +746,30 @@ impl OctreeNode {
         }
     }
 
+    fn collect_stamps(&self, out: &mut Vec<Instant>) {
+        if self.is_leaf() {
+            out.extend(self.points.iter().map(|e| e.stamp));
+        } else if let Some(children) = &self.children {
+            for child in children.iter() {
+                child.collect_stamps(out);
+            }
+        }
+    }
+
+    /// Keep the entries for which `keep` returns `true`; returns how many
+    /// were dropped.
+    fn retain(&mut self, keep: &mut impl FnMut(&Entry) -> bool) -> usize {
+        if self.is_leaf() {
+            let before = self.points.len();
+            self.points.retain(|e| keep(e));
+            before - self.points.len()
+        } else if let Some(children) = self.children.as_mut() {
+            children.iter_mut().map(|child| child.retain(keep)).sum()
+        } else {
+            unreachable!("non-leaf OctreeNode must have children")
+        }
+    }
+
     /// Split this leaf into eight children and redistribute existing points.
     fn subdivide(&mut self, max_depth: usize, depth: usize) {
         let c = self.bounds.centre();
@@ -316,12 +793,12 @@ impl OctreeNode {
 
         // Redistribute points that were in this leaf into the children.
         let points = std::mem::take(&mut self.points);
-        for p in points {
-            for child in children.iter_mut() {
-                if child.bounds.contains_point(p) {
-                    child.insert(p, max_depth, depth + 1);
-                    break;
-                }
+        for entry in points {
+            if let Some(child) = children
+                .iter_mut()
+                .find(|child| child.bounds.contains_point(entry.point))
+            {
+                child.insert(entry, max_depth, depth + 1);
             }
         }
 
@@ -579,5 +1056,204 @@ mod tests {
         assert_eq!(tree.len(), 1);
         assert!(tree.contains(Point3::new(0.5, 0.5, 0.5)));
     }
-}
+    #[test]
+    fn retain_drops_rejected_points_across_subdivisions() {
+        let mut tree = unit_tree(2);
+        for i in 0..10 {
+            tree.insert(Point3::new(i as f32 / 10.0, 0.5, 0.5));
+        }
+        tree.retain(|p| p.x < 0.45);
+        assert_eq!(tree.len(), 5);
+        assert!(tree.contains(Point3::new(0.4, 0.5, 0.5)));
+        assert!(!tree.contains(Point3::new(0.5, 0.5, 0.5)));
+        assert!(!tree.query_aabb(&Aabb::new(
+            Point3::new(0.45, 0.0, 0.0),
+            Point3::new(1.0, 1.0, 1.0)
+        )));
+    }
+
+    // ── raycast / clear_ray ──────────────────────────────────────────────────
+
+    #[test]
+    fn raycast_returns_nearest_point_near_the_ray() {
+        let mut tree = unit_tree(2);
+        tree.insert(Point3::new(0.8, 0.52, 0.5));
+        tree.insert(Point3::new(0.6, 0.49, 0.5));
+        tree.insert(Point3::new(0.3, 0.7, 0.5)); // off the ray
+        tree.insert(Point3::new(0.05, 0.5, 0.5)); // behind the origin
+        let origin = Point3::new(0.1, 0.5, 0.5);
+        let dir = Point3::new(2.0, 0.0, 0.0);
+
+        let hit = tree.raycast(origin, dir, 1.0).unwrap();
+        assert_eq!(hit.point, Point3::new(0.6, 0.49, 0.5));
+        assert!((hit.distance - 0.5).abs() < 1e-5);
+        assert!(tree.raycast(origin, dir, 0.4).is_none());
+        assert!(
+            tree.raycast(origin, Point3::new(0.0, 0.0, 0.0), 1.0)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn clear_ray_removes_points_along_the_beam_only() {
+        let mut tree = unit_tree(2);
+        for i in 1..10 {
+            tree.insert(Point3::new(i as f32 / 10.0, 0.5, 0.5));
+        }
+        tree.insert(Point3::new(0.5, 0.9, 0.5));
+        let removed = tree.clear_ray(Point3::new(0.0, 0.5, 0.5), Point3::new(1.0, 0.0, 0.0), 0.65);
+        assert_eq!(removed, 6);
+        assert_eq!(tree.len(), 4);
+        assert!(tree.contains(Point3::new(0.7, 0.5, 0.5)));
+        assert!(tree.contains(Point3::new(0.5, 0.9, 0.5)));
+        assert!(!tree.remove(Point3::new(0.3, 0.5, 0.5)));
+    }
+
+    // ── decay / cap ──────────────────────────────────────────────────────────
+
+    #[test]
+    fn reinserting_refreshes_instead_of_duplicating() {
+        let mut tree = unit_tree(2);
+        let p = Point3::new(0.5, 0.5, 0.5);
+        tree.insert_at(p, Instant::now() - Duration::from_secs(60));
+        tree.insert(p);
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree.evict_older_than(Duration::from_secs(30)), 0);
+        assert!(tree.contains(p));
+    }
+
+    #[test]
+    fn evict_older_than_drops_stale_points() {
+        let mut tree = unit_tree(2);
+        let now = Instant::now();
+        for i in 0..6 {
+            let seen = now - Duration::from_secs(10 * i);
+            tree.insert_at(Point3::new(i as f32 / 10.0, 0.5, 0.5), seen);
+        }
+        assert_eq!(tree.evict_older_than(Duration::from_secs(25)), 3);
+        assert_eq!(tree.len(), 3);
+        assert!(tree.contains(Point3::new(0.2, 0.5, 0.5)));
+        assert!(!tree.contains(Point3::new(0.3, 0.5, 0.5)));
+    }
 
+    #[test]
+    fn max_points_evicts_least_recently_inserted() {
+        let mut tree = unit_tree(2).with_max_points(10);
+        let start = Instant::now();
+        for i in 0..10 {
+            tree.insert_at(
+                Point3::new(i as f32 / 10.0, 0.5, 0.5),
+                start + Duration::from_millis(i),
+            );
+        }
+        // Point 0 is seen again, so point 1 is now the least recent.
+        tree.insert_at(
+            Point3::new(0.0, 0.5, 0.5),
+            start + Duration::from_millis(20),
+        );
+        assert_eq!(tree.len(), 10);
+
+        // Going over the cap evicts the excess plus a tenth of the cap.
+        tree.insert_at(
+            Point3::new(0.5, 0.1, 0.5),
+            start + Duration::from_millis(30),
+        );
+        assert_eq!(tree.len(), 9);
+        assert!(!tree.contains(Point3::new(0.1, 0.5, 0.5)));
+        assert!(!tree.contains(Point3::new(0.2, 0.5, 0.5)));
+        assert!(tree.contains(Point3::new(0.0, 0.5, 0.5)));
+        assert!(tree.contains(Point3::new(0.5, 0.1, 0.5)));
+    }
+
+    // ── nearest / k_nearest ──────────────────────────────────────────────────
+
+    #[test]
+    fn nearest_finds_closest_point_across_octants() {
+        let mut tree = unit_tree(1);
+        assert!(tree.nearest(Point3::new(0.5, 0.5, 0.5)).is_none());
+        for p in [
+            (0.1, 0.1, 0.1),
+            (0.9, 0.9, 0.9),
+            (0.45, 0.6, 0.5),
+            (0.7, 0.2, 0.5),
+        ] {
+            tree.insert(Point3::new(p.0, p.1, p.2));
+        }
+        let (point, distance) = tree.nearest(Point3::new(0.55, 0.5, 0.5)).unwrap();
+        assert_eq!(point, Point3::new(0.45, 0.6, 0.5));
+        assert!((distance - 0.02f32.sqrt()).abs() < 1e-5);
+        // Queries outside the bounds work too.
+        let (point, _) = tree.nearest(Point3::new(2.0, 2.0, 2.0)).unwrap();
+        assert_eq!(point, Point3::new(0.9, 0.9, 0.9));
+    }
+
+    #[test]
+    fn k_nearest_is_sorted_and_bounded_by_len() {
+        let mut tree = unit_tree(2);
+        for i in 0..10 {
+            tree.insert(Point3::new(i as f32 / 10.0, 0.5, 0.5));
+        }
+        let near = tree.k_nearest(Point3::new(0.0, 0.5, 0.5), 3);
+        let xs: Vec<f32> = near.iter().map(|(p, _)| p.x).collect();
+        assert_eq!(xs, vec![0.0, 0.1, 0.2]);
+        assert!(near.windows(2).all(|w| w[0].1 <= w[1].1));
+        assert_eq!(tree.k_nearest(Point3::new(0.0, 0.0, 0.0), 50).len(), 10);
+        assert!(tree.k_nearest(Point3::new(0.0, 0.0, 0.0), 0).is_empty());
+    }
+
+    // ── frame ───────────────────────────────────────────────────────────────
+
+    #[test]
+    fn frame_defaults_to_map() {
+        assert_eq!(unit_tree(2).frame_id(), "map");
+        assert_eq!(unit_tree(2).with_frame_id("odom").frame_id(), "odom");
+    }
+
+    // ── labels ──────────────────────────────────────────────────────────────
+
+    #[test]
+    fn labels_survive_refreshes_and_subdivision() {
+        let mut tree = unit_tree(2);
+        let person = Point3::new(0.2, 0.2, 0.2);
+        tree.insert_labeled(person, Label::new("person", 0.9));
+        // LiDAR hits on the same spot keep the label…
+        tree.insert(person);
+        // …and so does splitting the leaf.
+        for i in 0..6 {
+            tree.insert(Point3::new(0.5 + i as f32 * 0.05, 0.8, 0.8));
+        }
+        assert_eq!(tree.len(), 7);
+        assert_eq!(tree.label_at(person), Some(&Label::new("person", 0.9)));
+        assert_eq!(tree.label_at(Point3::new(0.5, 0.8, 0.8)), None);
+
+        tree.insert_labeled(person, Label::new("chair", 2.0));
+        assert_eq!(tree.label_at(person).unwrap().class, "chair");
+        assert_eq!(tree.label_at(person).unwrap().confidence, 1.0);
+        assert_eq!(tree.len(), 7);
+    }
+
+    #[test]
+    fn query_by_label_and_region() {
+        let mut tree = unit_tree(2);
+        tree.insert_labeled(Point3::new(0.1, 0.1, 0.1), Label::new("door", 0.7));
+        tree.insert_labeled(Point3::new(0.9, 0.9, 0.9), Label::new("door", 0.6));
+        tree.insert_labeled(Point3::new(0.5, 0.5, 0.5), Label::new("shelf", 0.8));
+        tree.insert(Point3::new(0.4, 0.4, 0.4));
+
+        let mut doors = tree.query_label("door");
+        doors.sort_by(|a, b| a.1.total_cmp(&b.1));
+        assert_eq!(
+            doors,
+            vec![
+                (Point3::new(0.9, 0.9, 0.9), 0.6),
+                (Point3::new(0.1, 0.1, 0.1), 0.7)
+            ]
+        );
+        assert!(tree.query_label("person").is_empty());
+
+        let region = Aabb::new(Point3::new(0.3, 0.3, 0.3), Point3::new(0.6, 0.6, 0.6));
+        let inside = tree.labels_in(&region);
+        assert_eq!(inside.len(), 1);
+        assert_eq!(inside[0].1.class, "shelf");
+    }
+}
@@ -0,0 +1,547 @@
+//! Local Path Planner.
+//!
+//! Turns a goal position into a collision-free list of waypoints that the
+//! runtime can dispatch as a `FollowWaypoints` intent, so the LLM can decide
+//! *where* to go ("go to the kitchen") without working out *how*.
+//!
+//! | Planner | Searches | Best for |
+//! |---|---|---|
+//! | [`AStarPlanner`] | an [`OccupancyGrid`], 8-connected | optimal paths on a rasterised map |
+//! | [`RrtStarPlanner`] | continuous space against an [`Octree`] | large maps where a fine grid is too costly |
+//!
+//! Both return the waypoints after the start, ending exactly at the goal, or
+//! `None` when no collision-free path exists.  [`path_feasible`] checks an
+//! existing path against the octree.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mechos_perception::occupancy::OccupancyGrid;
+//! use mechos_perception::octree::{Aabb, Octree, Point3};
+//! use mechos_perception::planner::{AStarPlanner, path_feasible};
+//!
+//! let mut octree = Octree::new(
+//!     Aabb::new(Point3::new(-5.0, -5.0, -1.0), Point3::new(5.0, 5.0, 1.0)),
+//!     8,
+//! );
+//! // A wall across the direct route.
+//! for i in -20..=20 {
+//!     octree.insert(Point3::new(0.0, i as f32 * 0.1, 0.0));
+//! }
+//! let grid = OccupancyGrid::from_octree(&octree, 0.1);
+//!
+//! let path = AStarPlanner::new(0.2).plan(&grid, (-3.0, 0.0), (3.0, 0.0)).unwrap();
+//! assert_eq!(path.last(), Some(&(3.0, 0.0)));
+//! assert!(path_feasible(&octree, (-3.0, 0.0), &path, 0.2));
+//! assert!(!path_feasible(&octree, (-3.0, 0.0), &[(3.0, 0.0)], 0.2));
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use crate::occupancy::{CellState, OccupancyGrid};
+use crate::octree::{Aabb, Octree, Point3};
+use crate::trajectory::FOOTPRINT_HALF_HEIGHT;
+
+/// A planar waypoint `(x, y)` in metres.
+pub type Waypoint = (f32, f32);
+
+// ────────────────────────────────────────────────────────────────────────────
+// Feasibility
+// ────────────────────────────────────────────────────────────────────────────
+
+/// True when a robot of half-width `robot_radius` can drive straight from
+/// `start` through every point of `path` without its footprint touching an
+/// octree point.
+pub fn path_feasible(
+    octree: &Octree,
+    start: Waypoint,
+    path: &[Waypoint],
+    robot_radius: f32,
+) -> bool {
+    let mut from = start;
+    path.iter().all(|&to| {
+        let clear = segment_clear(octree, from, to, robot_radius);
+        from = to;
+        clear
+    })
+}
+
+/// Sweep the square footprint along `a → b` in steps of half its width.
+fn segment_clear(octree: &Octree, a: Waypoint, b: Waypoint, robot_radius: f32) -> bool {
+    let length = (b.0 - a.0).hypot(b.1 - a.1);
+    let step = (robot_radius * 0.5).max(0.01);
+    let steps = (length / step).ceil() as usize;
+    (0..=steps).all(|i| {
+        let t = if steps == 0 {
+            0.0
+        } else {
+            i as f32 / steps as f32
+        };
+        let (x, y) = (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t);
+        !octree.query_aabb(&Aabb::new(
+            Point3::new(x - robot_radius, y - robot_radius, -FOOTPRINT_HALF_HEIGHT),
+            Point3::new(x + robot_radius, y + robot_radius, FOOTPRINT_HALF_HEIGHT),
+        ))
+    })
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// AStarPlanner
+// ────────────────────────────────────────────────────────────────────────────
+
+/// A* search over an [`OccupancyGrid`].
+///
+/// Occupied cells are inflated by `robot_radius` so the robot's centre can
+/// be planned as a point.  The raw cell path is then shortened to the
+/// corners where line of sight breaks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AStarPlanner {
+    /// Half-width of the robot footprint (metres).
+    pub robot_radius: f32,
+    /// Whether [`CellState::Unknown`] cells may be crossed.
+    pub allow_unknown: bool,
+}
+
+impl AStarPlanner {
+    /// Create a planner for a robot of half-width `robot_radius` that may
+    /// cross unknown cells.
+    pub fn new(robot_radius: f32) -> Self {
+        Self {
+            robot_radius: robot_radius.max(0.0),
+            allow_unknown: true,
+        }
+    }
+
+    /// Treat unknown cells as blocked when `allow_unknown` is `false`.
+    pub fn with_allow_unknown(mut self, allow_unknown: bool) -> Self {
+        self.allow_unknown = allow_unknown;
+        self
+    }
+
+    /// Plan from `start` to `goal`.
+    ///
+    /// Returns `None` when either end lies outside the grid, the goal is
+    /// blocked, or no path exists.  A start inside an inflated obstacle is
+    /// tolerated so the robot can plan its way out.
+    pub fn plan(
+        &self,
+        grid: &OccupancyGrid,
+        start: Waypoint,
+        goal: Waypoint,
+    ) -> Option<Vec<Waypoint>> {
+        let (width, height) = (grid.width(), grid.height());
+        let blocked = self.blocked_mask(grid);
+        let start_cell = grid.cell_index(start.0, start.1)?;
+        let goal_cell = grid.cell_index(goal.0, goal.1)?;
+        let index = |(col, row): (usize, usize)| row * width + col;
+        if blocked[index(goal_cell)] {
+            return None;
+        }
+
+        let heuristic = |(col, row): (usize, usize)| {
+            let dx = col.abs_diff(goal_cell.0) as f32;
+            let dy = row.abs_diff(goal_cell.1) as f32;
+            dx.max(dy) + (std::f32::consts::SQRT_2 - 1.0) * dx.min(dy)
+        };
+        let mut cost = vec![f32::INFINITY; width * height];
+        let mut came_from: Vec<Option<usize>> = vec![None; width * height];
+        let mut open = BinaryHeap::new();
+        cost[index(start_cell)] = 0.0;
+        open.push(Open {
+            f: heuristic(start_cell),
+            cell: start_cell,
+        });
+
+        while let Some(Open { cell, .. }) = open.pop() {
+            if cell == goal_cell {
+                let mut cells = vec![cell];
+                let mut at = index(cell);
+                while let Some(prev) = came_from[at] {
+                    cells.push((prev % width, prev / width));
+                    at = prev;
+                }
+                cells.reverse();
+                return Some(self.shorten(grid, &blocked, &cells, goal));
+            }
+            let g = cost[index(cell)];
+            for (dc, dr) in NEIGHBOURS {
+                let (Some(col), Some(row)) =
+                    (cell.0.checked_add_signed(dc), cell.1.checked_add_signed(dr))
+                else {
+                    continue;
+                };
+                if col >= width || row >= height || blocked[index((col, row))] {
+                    continue;
+                }
+                // No corner cutting past a blocked cell.
+                if dc != 0
+                    && dr != 0
+                    && (blocked[index((col, cell.1))] || blocked[index((cell.0, row))])
+                {
+                    continue;
+                }
+                let step = if dc != 0 && dr != 0 {
+                    std::f32::consts::SQRT_2
+                } else {
+                    1.0
+                };
+                let next = index((col, row));
+                if g + step < cost[next] {
+                    cost[next] = g + step;
+                    came_from[next] = Some(index(cell));
+                    open.push(Open {
+                        f: g + step + heuristic((col, row)),
+                        cell: (col, row),
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    /// Per-cell flag: occupied (or unknown, when disallowed) cells grown by
+    /// the robot radius.
+    fn blocked_mask(&self, grid: &OccupancyGrid) -> Vec<bool> {
+        let (width, height) = (grid.width(), grid.height());
+        let reach = (self.robot_radius / grid.resolution()).ceil() as usize;
+        let mut blocked = vec![false; width * height];
+        for row in 0..height {
+            for col in 0..width {
+                match grid.cell(col, row) {
+                    CellState::Occupied => {}
+                    CellState::Unknown if !self.allow_unknown => {
+                        blocked[row * width + col] = true;
+                        continue;
+                    }
+                    _ => continue,
+                }
+                for r in row.saturating_sub(reach)..=(row + reach).min(height - 1) {
+                    for c in col.saturating_sub(reach)..=(col + reach).min(width - 1) {
+                        blocked[r * width + c] = true;
+                    }
+                }
+            }
+        }
+        blocked
+    }
+
+    /// Keep only the cells where line of sight from the previous kept cell
+    /// breaks, drop the start and end exactly at `goal`.
+    fn shorten(
+        &self,
+        grid: &OccupancyGrid,
+        blocked: &[bool],
+        cells: &[(usize, usize)],
+        goal: Waypoint,
+    ) -> Vec<Waypoint> {
+        let mut path = Vec::new();
+        let mut anchor = cells[0];
+        for pair in cells.windows(2).skip(1) {
+            if !line_of_sight(grid, blocked, anchor, pair[1]) {
+                path.push(grid.cell_centre(pair[0].0, pair[0].1));
+                anchor = pair[0];
+            }
+        }
+        path.push(goal);
+        path
+    }
+}
+
+/// 8-connected neighbour offsets.
+const NEIGHBOURS: [(isize, isize); 8] = [
+    (1, 0),
+    (-1, 0),
+    (0, 1),
+    (0, -1),
+    (1, 1),
+    (1, -1),
+    (-1, 1),
+    (-1, -1),
+];
+
+/// True when the straight line between two cell centres crosses no blocked
+/// cell (sampled at a quarter-cell spacing).
+fn line_of_sight(
+    grid: &OccupancyGrid,
+    blocked: &[bool],
+    a: (usize, usize),
+    b: (usize, usize),
+) -> bool {
+    let (ax, ay) = grid.cell_centre(a.0, a.1);
+    let (bx, by) = grid.cell_centre(b.0, b.1);
+    let steps = ((bx - ax).hypot(by - ay) / (grid.resolution() * 0.25)).ceil() as usize;
+    (0..=steps).all(|i| {
+        let t = if steps == 0 {
+            0.0
+        } else {
+            i as f32 / steps as f32
+        };
+        grid.cell_index(ax + (bx - ax) * t, ay + (by - ay) * t)
+            .is_some_and(|(col, row)| !blocked[row * grid.width() + col])
+    })
+}
+
+/// An A* open-set entry, ordered so `BinaryHeap` pops the lowest `f` first.
+struct Open {
+    f: f32,
+    cell: (usize, usize),
+}
+
+impl Ord for Open {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.f.total_cmp(&self.f)
+    }
+}
+
+impl PartialOrd for Open {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Open {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Open {}
+
+// ────────────────────────────────────────────────────────────────────────────
+// RrtStarPlanner
+// ────────────────────────────────────────────────────────────────────────────
+
+/// Default number of samples drawn by [`RrtStarPlanner`].
+pub const DEFAULT_RRT_ITERATIONS: usize = 2000;
+
+/// Share of samples drawn at the goal itself, steering the tree towards it.
+const GOAL_BIAS: f32 = 0.1;
+
+/// RRT* search in continuous space against an [`Octree`].
+///
+/// Grows a tree of collision-free segments from the start by random
+/// sampling within the octree's XY bounds, re-parenting nodes whenever a
+/// cheaper route is found, and returns the cheapest path that reaches the
+/// goal.  Sampling is seeded, so the same inputs give the same path.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RrtStarPlanner {
+    /// Half-width of the robot footprint (metres).
+    pub robot_radius: f32,
+    /// Longest edge added to the tree (metres).
+    pub step: f32,
+    /// Samples drawn before giving up.
+    pub max_iterations: usize,
+    /// Seed of the sampler.
+    pub seed: u64,
+}
+
+impl RrtStarPlanner {
+    /// Create a planner for a robot of half-width `robot_radius` with 0.5 m
+    /// edges and [`DEFAULT_RRT_ITERATIONS`] samples.
+    pub fn new(robot_radius: f32) -> Self {
+        Self {
+            robot_radius: robot_radius.max(0.0),
+            step: 0.5,
+            max_iterations: DEFAULT_RRT_ITERATIONS,
+            seed: 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+
+    /// Draw at most `max_iterations` samples.
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Seed the sampler.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Plan from `start` to `goal`; `None` when the goal was not reached
+    /// within the sample budget.
+    pub fn plan(&self, octree: &Octree, start: Waypoint, goal: Waypoint) -> Option<Vec<Waypoint>> {
+        let clear = |a: Waypoint, b: Waypoint| segment_clear(octree, a, b, self.robot_radius);
+        if clear(start, goal) {
+            return Some(vec![goal]);
+        }
+        let bounds = octree.bounds();
+        let step = self.step.max(0.01);
+        let rewire_radius = 2.0 * step;
+        let mut rng = self.seed.max(1);
+        let mut nodes = vec![Node {
+            at: start,
+            parent: None,
+            cost: 0.0,
+        }];
+        let mut best: Option<(usize, f32)> = None;
+
+        for _ in 0..self.max_iterations {
+            let sample = if next_unit(&mut rng) < GOAL_BIAS {
+                goal
+            } else {
+                (
+                    bounds.min.x + next_unit(&mut rng) * (bounds.max.x - bounds.min.x),
+                    bounds.min.y + next_unit(&mut rng) * (bounds.max.y - bounds.min.y),
+                )
+            };
+            let nearest = (0..nodes.len())
+                .min_by(|&a, &b| {
+                    distance(nodes[a].at, sample).total_cmp(&distance(nodes[b].at, sample))
+                })
+                .unwrap_or(0);
+            let from = nodes[nearest].at;
+            let d = distance(from, sample);
+            let at = if d <= step {
+                sample
+            } else {
+                (
+                    from.0 + (sample.0 - from.0) * step / d,
+                    from.1 + (sample.1 - from.1) * step / d,
+                )
+            };
+            if !clear(from, at) {
+                continue;
+            }
+
+            // Choose the cheapest collision-free parent nearby.
+            let near: Vec<usize> = (0..nodes.len())
+                .filter(|&i| distance(nodes[i].at, at) <= rewire_radius)
+                .collect();
+            let (parent, cost) = near
+                .iter()
+                .map(|&i| (i, nodes[i].cost + distance(nodes[i].at, at)))
+                .filter(|&(i, _)| i == nearest || clear(nodes[i].at, at))
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .unwrap_or((nearest, nodes[nearest].cost + distance(from, at)));
+            let id = nodes.len();
+            nodes.push(Node {
+                at,
+                parent: Some(parent),
+                cost,
+            });
+
+            // Rewire neighbours through the new node when cheaper.
+            for &i in &near {
+                let via = cost + distance(at, nodes[i].at);
+                if via < nodes[i].cost && clear(at, nodes[i].at) {
+                    nodes[i].parent = Some(id);
+                    nodes[i].cost = via;
+                }
+            }
+
+            for &candidate in near.iter().chain(std::iter::once(&id)) {
+                let total = nodes[candidate].cost + distance(nodes[candidate].at, goal);
+                if best.is_none_or(|(_, c)| total < c)
+                    && distance(nodes[candidate].at, goal) <= rewire_radius
+                    && clear(nodes[candidate].at, goal)
+                {
+                    best = Some((candidate, total));
+                }
+            }
+        }
+
+        let (mut at, _) = best?;
+        let mut path = vec![goal];
+        while let Some(parent) = nodes[at].parent {
+            path.push(nodes[at].at);
+            at = parent;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    at: Waypoint,
+    parent: Option<usize>,
+    cost: f32,
+}
+
+fn distance(a: Waypoint, b: Waypoint) -> f32 {
+    (b.0 - a.0).hypot(b.1 - a.1)
+}
+
+/// xorshift64* step mapped to `[0, 1)`.
+fn next_unit(state: &mut u64) -> f32 {
+    *state ^= *state >> 12;
+    *state ^= *state << 25;
+    *state ^= *state >> 27;
+    (state.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 40) as f32 / (1u64 << 24) as f32
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Tests
+// ────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 10 m square map with a wall at x = 0 from y = -3 to y = 3.
+    fn walled_map() -> Octree {
+        let mut octree = Octree::new(
+            Aabb::new(Point3::new(-5.0, -5.0, -1.0), Point3::new(5.0, 5.0, 1.0)),
+            8,
+        );
+        for i in -30..=30 {
+            octree.insert(Point3::new(0.0, i as f32 * 0.1, 0.0));
+        }
+        octree
+    }
+
+    #[test]
+    fn astar_goes_straight_on_an_empty_map() {
+        let octree = Octree::new(
+            Aabb::new(Point3::new(-5.0, -5.0, -1.0), Point3::new(5.0, 5.0, 1.0)),
+            8,
+        );
+        let grid = OccupancyGrid::from_octree(&octree, 0.1);
+        let path = AStarPlanner::new(0.3).plan(&grid, (-2.0, -1.0), (3.0, 2.5));
+        assert_eq!(path, Some(vec![(3.0, 2.5)]));
+    }
+
+    #[test]
+    fn astar_routes_around_a_wall() {
+        let octree = walled_map();
+        let grid = OccupancyGrid::from_octree(&octree, 0.1);
+        let path = AStarPlanner::new(0.3)
+            .plan(&grid, (-2.0, 0.0), (2.0, 0.0))
+            .unwrap();
+        assert!(path.len() > 1);
+        assert_eq!(path.last(), Some(&(2.0, 0.0)));
+        assert!(path.iter().any(|p| p.1.abs() > 3.0), "{path:?}");
+        assert!(path_feasible(&octree, (-2.0, 0.0), &path, 0.25));
+    }
+
+    #[test]
+    fn astar_rejects_blocked_or_unreachable_goals() {
+        let octree = walled_map();
+        let grid = OccupancyGrid::from_octree(&octree, 0.1);
+        let planner = AStarPlanner::new(0.3);
+        assert!(planner.plan(&grid, (-2.0, 0.0), (0.1, 0.0)).is_none());
+        assert!(planner.plan(&grid, (-2.0, 0.0), (9.0, 0.0)).is_none());
+        // Unknown space is off limits when disallowed.
+        let strict = planner.with_allow_unknown(false);
+        assert!(strict.plan(&grid, (-2.0, 0.0), (2.0, 0.0)).is_none());
+    }
+
+    #[test]
+    fn rrt_star_finds_a_feasible_path_around_a_wall() {
+        let octree = walled_map();
+        let planner = RrtStarPlanner::new(0.25);
+        let path = planner.plan(&octree, (-2.0, 0.0), (2.0, 0.0)).unwrap();
+        assert_eq!(path.last(), Some(&(2.0, 0.0)));
+        assert!(path_feasible(&octree, (-2.0, 0.0), &path, 0.25));
+        // Deterministic for a given seed.
+        assert_eq!(planner.plan(&octree, (-2.0, 0.0), (2.0, 0.0)), Some(path));
+        // A direct line needs no tree.
+        assert_eq!(
+            planner.plan(&octree, (-2.0, -4.0), (2.0, -4.0)),
+            Some(vec![(2.0, -4.0)])
+        );
+    }
+}
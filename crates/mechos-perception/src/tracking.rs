@@ -0,0 +1,415 @@
+//! Dynamic Obstacle Tracking.
+//!
+//! The octree records *where* obstacles are, but not whether they move.
+//! [`ObstacleTracker`] groups each LiDAR frame's points into clusters,
+//! matches them to the clusters of earlier frames and estimates a velocity
+//! per track, so the kernel can keep a wider berth around people than around
+//! walls.
+//!
+//! Clusters wider than [`ObstacleTracker::max_cluster_radius`] are treated
+//! as static structure and not tracked: the visible part of a wall shifts as
+//! the robot drives past, which would otherwise read as motion.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::time::{Duration, SystemTime};
+//! use mechos_perception::tracking::ObstacleTracker;
+//!
+//! let mut tracker = ObstacleTracker::new();
+//! let t0 = SystemTime::UNIX_EPOCH;
+//! // A person 2 m ahead walks across at 1 m/s.
+//! tracker.update(&[(2.0, 0.0), (2.1, 0.0)], t0);
+//! tracker.update(&[(2.0, 0.5), (2.1, 0.5)], t0 + Duration::from_millis(500));
+//!
+//! let person = &tracker.tracks()[0];
+//! assert!((person.velocity_y - 1.0).abs() < 1e-3);
+//! assert!(person.is_moving(0.2));
+//! ```
+
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+/// Default largest gap (metres) between neighbouring points of one cluster.
+pub const DEFAULT_CLUSTER_DISTANCE: f32 = 0.3;
+/// Default largest distance (metres) a track may move between two frames.
+pub const DEFAULT_MATCH_DISTANCE: f32 = 1.0;
+/// Default radius (metres) above which a cluster counts as static structure.
+pub const DEFAULT_MAX_CLUSTER_RADIUS: f32 = 1.0;
+/// Default number of frames a track survives without a matching cluster.
+pub const DEFAULT_MAX_MISSED: u32 = 3;
+
+/// Weight of a new velocity measurement in the smoothed estimate.
+const VELOCITY_SMOOTHING: f32 = 0.5;
+/// Frames a track must be matched in before it is reported.
+const MIN_HITS: u32 = 2;
+
+// ────────────────────────────────────────────────────────────────────────────
+// Cluster
+// ────────────────────────────────────────────────────────────────────────────
+
+/// A group of nearby points from one frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cluster {
+    /// Centroid X (metres).
+    pub x: f32,
+    /// Centroid Y (metres).
+    pub y: f32,
+    /// Distance from the centroid to the farthest point (metres).
+    pub radius: f32,
+    /// Number of points in the cluster.
+    pub len: usize,
+}
+
+/// Group `points` into clusters whose neighbouring points lie at most
+/// `max_gap` apart (single-linkage, bucketed on a `max_gap` grid).
+pub fn cluster(points: &[(f32, f32)], max_gap: f32) -> Vec<Cluster> {
+    let max_gap = max_gap.max(f32::EPSILON);
+    let cell = |(x, y): (f32, f32)| ((x / max_gap).floor() as i64, (y / max_gap).floor() as i64);
+    let mut buckets: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+    for (i, &p) in points.iter().enumerate() {
+        buckets.entry(cell(p)).or_default().push(i);
+    }
+
+    let mut visited = vec![false; points.len()];
+    let mut clusters = Vec::new();
+    for seed in 0..points.len() {
+        if visited[seed] {
+            continue;
+        }
+        visited[seed] = true;
+        let mut members = vec![seed];
+        let mut next = 0;
+        while next < members.len() {
+            let (px, py) = points[members[next]];
+            let (cx, cy) = cell((px, py));
+            next += 1;
+            for dx in -1..=1 {
+                for dy in -1..=1 {
+                    let Some(bucket) = buckets.get(&(cx + dx, cy + dy)) else {
+                        continue;
+                    };
+                    for &j in bucket {
+                        let (qx, qy) = points[j];
+                        if !visited[j] && (qx - px).hypot(qy - py) <= max_gap {
+                            visited[j] = true;
+                            members.push(j);
+                        }
+                    }
+                }
+            }
+        }
+
+        let n = members.len() as f32;
+        let x = members.iter().map(|&i| points[i].0).sum::<f32>() / n;
+        let y = members.iter().map(|&i| points[i].1).sum::<f32>() / n;
+        let radius = members
+            .iter()
+            .map(|&i| (points[i].0 - x).hypot(points[i].1 - y))
+            .fold(0.0, f32::max);
+        clusters.push(Cluster {
+            x,
+            y,
+            radius,
+            len: members.len(),
+        });
+    }
+    clusters
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// TrackedObstacle
+// ────────────────────────────────────────────────────────────────────────────
+
+/// An obstacle followed across frames.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrackedObstacle {
+    /// Identifier, stable for the lifetime of the track.
+    pub id: u64,
+    /// Position X (metres).
+    pub x: f32,
+    /// Position Y (metres).
+    pub y: f32,
+    /// Smoothed velocity X (m/s).
+    pub velocity_x: f32,
+    /// Smoothed velocity Y (m/s).
+    pub velocity_y: f32,
+    /// Radius of the last matched cluster (metres).
+    pub radius: f32,
+    /// Frames the track was matched in.
+    pub hits: u32,
+    /// Consecutive frames without a match.
+    pub missed: u32,
+}
+
+impl TrackedObstacle {
+    /// Magnitude of the velocity (m/s).
+    pub fn speed(&self) -> f32 {
+        self.velocity_x.hypot(self.velocity_y)
+    }
+
+    /// `true` when the obstacle moves faster than `min_speed` (m/s).
+    pub fn is_moving(&self, min_speed: f32) -> bool {
+        self.speed() > min_speed
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// ObstacleTracker
+// ────────────────────────────────────────────────────────────────────────────
+
+/// Multi-object tracker over clustered LiDAR frames.
+///
+/// Each [`update`][Self::update] clusters the frame, greedily matches the
+/// closest cluster–track pairs within
+/// [`match_distance`][Self::match_distance], updates the matched tracks'
+/// velocities, starts tracks for unmatched clusters and coasts unmatched
+/// tracks along their velocity until they have been missed for more than
+/// [`max_missed`][Self::max_missed] frames.
+#[derive(Debug, Clone)]
+pub struct ObstacleTracker {
+    /// Largest gap between neighbouring points of one cluster (metres).
+    pub cluster_distance: f32,
+    /// Largest distance a track may move between two frames (metres).
+    pub match_distance: f32,
+    /// Clusters wider than this are static structure (metres).
+    pub max_cluster_radius: f32,
+    /// Frames a track survives without a match.
+    pub max_missed: u32,
+    tracks: Vec<TrackedObstacle>,
+    next_id: u64,
+    last_update: Option<SystemTime>,
+}
+
+impl Default for ObstacleTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ObstacleTracker {
+    /// Create a tracker with the `DEFAULT_*` parameters.
+    pub fn new() -> Self {
+        Self {
+            cluster_distance: DEFAULT_CLUSTER_DISTANCE,
+            match_distance: DEFAULT_MATCH_DISTANCE,
+            max_cluster_radius: DEFAULT_MAX_CLUSTER_RADIUS,
+            max_missed: DEFAULT_MAX_MISSED,
+            tracks: Vec::new(),
+            next_id: 1,
+            last_update: None,
+        }
+    }
+
+    /// Override the largest gap between neighbouring cluster points.
+    pub fn with_cluster_distance(mut self, cluster_distance: f32) -> Self {
+        self.cluster_distance = cluster_distance;
+        self
+    }
+
+    /// Override the largest per-frame displacement of a track.
+    pub fn with_match_distance(mut self, match_distance: f32) -> Self {
+        self.match_distance = match_distance;
+        self
+    }
+
+    /// Override the radius above which clusters are not tracked.
+    pub fn with_max_cluster_radius(mut self, max_cluster_radius: f32) -> Self {
+        self.max_cluster_radius = max_cluster_radius;
+        self
+    }
+
+    /// Override how many frames a track survives without a match.
+    pub fn with_max_missed(mut self, max_missed: u32) -> Self {
+        self.max_missed = max_missed;
+        self
+    }
+
+    /// Feed one frame of obstacle points (world frame, metres) captured at
+    /// `at`.
+    pub fn update(&mut self, points: &[(f32, f32)], at: SystemTime) {
+        let dt = self
+            .last_update
+            .and_then(|last| at.duration_since(last).ok())
+            .map_or(0.0, |d| d.as_secs_f32());
+        self.last_update = Some(at);
+
+        let clusters: Vec<Cluster> = cluster(points, self.cluster_distance)
+            .into_iter()
+            .filter(|c| c.radius <= self.max_cluster_radius)
+            .collect();
+
+        // Coast every track to the frame time before matching.
+        for track in &mut self.tracks {
+            track.x += track.velocity_x * dt;
+            track.y += track.velocity_y * dt;
+        }
+
+        let mut pairs: Vec<(f32, usize, usize)> = Vec::new();
+        for (t, track) in self.tracks.iter().enumerate() {
+            for (c, cluster) in clusters.iter().enumerate() {
+                let d = (cluster.x - track.x).hypot(cluster.y - track.y);
+                if d <= self.match_distance {
+                    pairs.push((d, t, c));
+                }
+            }
+        }
+        pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut track_matched = vec![false; self.tracks.len()];
+        let mut cluster_matched = vec![false; clusters.len()];
+        for (_, t, c) in pairs {
+            if track_matched[t] || cluster_matched[c] {
+                continue;
+            }
+            track_matched[t] = true;
+            cluster_matched[c] = true;
+            let track = &mut self.tracks[t];
+            let cluster = clusters[c];
+            if dt > 0.0 {
+                // Undo the coast to measure the displacement since the
+                // previous position.
+                let prev_x = track.x - track.velocity_x * dt;
+                let prev_y = track.y - track.velocity_y * dt;
+                let vx = (cluster.x - prev_x) / dt;
+                let vy = (cluster.y - prev_y) / dt;
+                let weight = if track.hits == 1 {
+                    1.0
+                } else {
+                    VELOCITY_SMOOTHING
+                };
+                track.velocity_x += weight * (vx - track.velocity_x);
+                track.velocity_y += weight * (vy - track.velocity_y);
+            }
+            track.x = cluster.x;
+            track.y = cluster.y;
+            track.radius = cluster.radius;
+            track.hits += 1;
+            track.missed = 0;
+        }
+
+        for (track, matched) in self.tracks.iter_mut().zip(&track_matched) {
+            if !matched {
+                track.missed += 1;
+            }
+        }
+        let max_missed = self.max_missed;
+        self.tracks.retain(|track| track.missed <= max_missed);
+
+        for (cluster, _) in clusters
+            .iter()
+            .zip(&cluster_matched)
+            .filter(|(_, matched)| !**matched)
+        {
+            self.tracks.push(TrackedObstacle {
+                id: self.next_id,
+                x: cluster.x,
+                y: cluster.y,
+                velocity_x: 0.0,
+                velocity_y: 0.0,
+                radius: cluster.radius,
+                hits: 1,
+                missed: 0,
+            });
+            self.next_id += 1;
+        }
+    }
+
+    /// Tracks matched in at least two frames, including the latest one.
+    pub fn tracks(&self) -> Vec<&TrackedObstacle> {
+        self.tracks
+            .iter()
+            .filter(|track| track.hits >= MIN_HITS && track.missed == 0)
+            .collect()
+    }
+
+    /// Distance from `(x, y)` to the edge of the closest track moving faster
+    /// than `min_speed` (m/s); `None` when no tracked obstacle moves.
+    pub fn nearest_moving(&self, x: f32, y: f32, min_speed: f32) -> Option<f32> {
+        self.tracks()
+            .into_iter()
+            .filter(|track| track.is_moving(min_speed))
+            .map(|track| ((track.x - x).hypot(track.y - y) - track.radius).max(0.0))
+            .min_by(f32::total_cmp)
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Tests
+// ────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn at(ms: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_millis(ms)
+    }
+
+    /// A 0.2 m wide blob of points centred on `(x, y)`.
+    fn blob(x: f32, y: f32) -> Vec<(f32, f32)> {
+        vec![
+            (x - 0.1, y),
+            (x, y),
+            (x + 0.1, y),
+            (x, y + 0.1),
+            (x, y - 0.1),
+        ]
+    }
+
+    #[test]
+    fn cluster_separates_distant_groups() {
+        let mut points = blob(0.0, 0.0);
+        points.extend(blob(3.0, 0.0));
+        let mut clusters = cluster(&points, 0.3);
+        clusters.sort_by(|a, b| a.x.total_cmp(&b.x));
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].len, 5);
+        assert!(clusters[1].x > 2.9 && clusters[1].x < 3.1);
+        assert!((clusters[0].radius - 0.1).abs() < 1e-4);
+    }
+
+    #[test]
+    fn tracker_estimates_velocity_and_keeps_ids() {
+        let mut tracker = ObstacleTracker::new();
+        // A static box and a walker moving +x at 1 m/s.
+        for step in 0..5u64 {
+            let mut points = blob(0.0, 3.0);
+            points.extend(blob(step as f32 * 0.1, 0.0));
+            tracker.update(&points, at(step * 100));
+        }
+        let tracks = tracker.tracks();
+        assert_eq!(tracks.len(), 2);
+        let walker = tracks.iter().find(|t| t.y.abs() < 0.5).unwrap();
+        let static_box = tracks.iter().find(|t| t.y > 2.5).unwrap();
+        assert!((walker.velocity_x - 1.0).abs() < 0.05, "{walker:?}");
+        assert!(walker.is_moving(0.2));
+        assert!(!static_box.is_moving(0.2));
+        assert_eq!(walker.hits, 5);
+        assert!(walker.id != static_box.id);
+
+        let distance = tracker.nearest_moving(0.4, -1.0, 0.2).unwrap();
+        assert!((distance - 0.9).abs() < 0.05, "{distance}");
+    }
+
+    #[test]
+    fn tracker_ignores_walls_and_drops_lost_tracks() {
+        let mut tracker = ObstacleTracker::new().with_max_missed(1);
+        let wall: Vec<(f32, f32)> = (0..40).map(|i| (i as f32 * 0.1, 2.0)).collect();
+        tracker.update(&wall, at(0));
+        tracker.update(&wall, at(100));
+        assert!(tracker.tracks().is_empty());
+
+        tracker.update(&blob(0.0, 0.0), at(200));
+        tracker.update(&blob(0.0, 0.0), at(300));
+        assert_eq!(tracker.tracks().len(), 1);
+        // Out of view: coasts for one frame, then is dropped.
+        tracker.update(&[], at(400));
+        assert!(tracker.tracks().is_empty());
+        tracker.update(&[], at(500));
+        tracker.update(&blob(0.0, 0.0), at(600));
+        tracker.update(&blob(0.0, 0.0), at(700));
+        assert_eq!(tracker.tracks()[0].id, 2);
+    }
+}
@@ -5,6 +5,21 @@
 //! two frame names the engine can compose a chain of transforms via BFS to
 //! produce the combined `Transform3D`.
 //!
+//! Like ROS tf2, each edge can also keep a short history of time-stamped
+//! transforms.  [`TfEngine::lookup_at`] resolves the chain at an arbitrary
+//! past instant, interpolating translations linearly and rotations by slerp
+//! between the bracketing samples, so sensor data is projected with the pose
+//! the robot had when it was captured.
+//!
+//! [`TfEngine::transform_point`], [`TfEngine::transform_pose`] and
+//! [`TfEngine::transform_twist`] re-express data from one frame in another,
+//! walking an edge backwards (inverted) when only the opposite direction is
+//! registered.
+//!
+//! [`GeodeticDatum`] places GPS fixes in the local map frame: it converts
+//! WGS-84 latitude/longitude/altitude into east-north-up metres relative to a
+//! configurable origin.
+//!
 //! # Example
 //!
 //! ```rust
@@ -25,6 +40,9 @@
 //! ```
 
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, SystemTime};
+
+use crate::types::{FrameAwareTwist, Pose};
 
 // ────────────────────────────────────────────────────────────────────────────
 // Primitive types
@@ -52,6 +70,15 @@ impl Vec3 {
     pub fn add_tf(self, rhs: Self) -> Self {
         Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
     }
+
+    /// Linear interpolation: `self` at `t = 0`, `other` at `t = 1`.
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        Self::new(
+            self.x + (other.x - self.x) * t,
+            self.y + (other.y - self.y) * t,
+            self.z + (other.z - self.z) * t,
+        )
+    }
 }
 
 /// A unit quaternion representing a 3-D rotation (w, x, y, z convention).
@@ -90,6 +117,47 @@ impl Quaternion {
         Self::new(self.w, -self.x, -self.y, -self.z)
     }
 
+    /// A rotation of `yaw_rad` around the Z axis.
+    pub fn from_yaw(yaw_rad: f32) -> Self {
+        let (sin, cos) = (yaw_rad / 2.0).sin_cos();
+        Self::new(cos, 0.0, 0.0, sin)
+    }
+
+    /// Heading around the Z axis (radians, in `(-π, π]`).
+    pub fn yaw(self) -> f32 {
+        (2.0 * (self.w * self.z + self.x * self.y))
+            .atan2(1.0 - 2.0 * (self.y * self.y + self.z * self.z))
+    }
+
+    /// Spherical linear interpolation along the shortest arc: `self` at
+    /// `t = 0`, `other` at `t = 1`.
+    pub fn slerp(self, other: Self, t: f32) -> Self {
+        let mut dot = self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z;
+        // q and -q are the same rotation; flip to take the shorter way round.
+        let other = if dot < 0.0 {
+            dot = -dot;
+            Self::new(-other.w, -other.x, -other.y, -other.z)
+        } else {
+            other
+        };
+        let (a, b) = if dot > 0.9995 {
+            // Nearly parallel: sin θ → 0, fall back to a normalised lerp.
+            (1.0 - t, t)
+        } else {
+            let theta = dot.acos();
+            let sin = theta.sin();
+            (((1.0 - t) * theta).sin() / sin, (t * theta).sin() / sin)
+        };
+        let q = Self::new(
+            a * self.w + b * other.w,
+            a * self.x + b * other.x,
+            a * self.y + b * other.y,
+            a * self.z + b * other.z,
+        );
+        let norm = (q.w * q.w + q.x * q.x + q.y * q.y + q.z * q.z).sqrt();
+        Self::new(q.w / norm, q.x / norm, q.y / norm, q.z / norm)
+    }
+
     /// Rotate a vector by this quaternion: p' = q * p * q*.
     pub fn rotate(self, v: Vec3) -> Vec3 {
         // Express v as a pure quaternion.
@@ -137,12 +205,36 @@ impl Transform3D {
         let rotated = self.rotation.mul_tf(other.rotation);
         Self::new(translated, rotated)
     }
+
+    /// Map a point expressed in the child frame into the parent frame.
+    pub fn apply(self, point: Vec3) -> Vec3 {
+        self.translation.add_tf(self.rotation.rotate(point))
+    }
+
+    /// The opposite transform: T_B_A for `self` = T_A_B.
+    pub fn inverse(self) -> Self {
+        let rotation = self.rotation.conjugate();
+        let back = rotation.rotate(self.translation);
+        Self::new(Vec3::new(-back.x, -back.y, -back.z), rotation)
+    }
+
+    /// Interpolate between two poses: translation linearly, rotation by
+    /// [`Quaternion::slerp`].
+    pub fn interpolate(self, other: Self, t: f32) -> Self {
+        Self::new(
+            self.translation.lerp(other.translation, t),
+            self.rotation.slerp(other.rotation, t),
+        )
+    }
 }
 
 // ────────────────────────────────────────────────────────────────────────────
 // TfEngine
 // ────────────────────────────────────────────────────────────────────────────
 
+/// Default history kept per time-stamped edge, as in tf2.
+pub const DEFAULT_TF_CACHE_DURATION: Duration = Duration::from_secs(10);
+
 /// A directed graph of named reference frames and the [`Transform3D`]s that
 /// relate them.
 ///
@@ -152,10 +244,58 @@ impl Transform3D {
 ///
 /// [`TfEngine::lookup`] performs BFS to find the shortest path from source
 /// to target and returns the composed transform.
-#[derive(Debug, Default)]
+///
+/// An edge is either static ([`TfEngine::set_transform`], valid at every
+/// time) or a time-stamped history ([`TfEngine::set_transform_at`]) kept for
+/// the engine's cache duration and queried with [`TfEngine::lookup_at`].
+#[derive(Debug)]
 pub struct TfEngine {
-    /// `edges[from][to] = Transform3D`
-    edges: HashMap<String, HashMap<String, Transform3D>>,
+    /// `edges[from][to] = TransformBuffer`
+    edges: HashMap<String, HashMap<String, TransformBuffer>>,
+    /// How far behind its newest sample an edge's history reaches.
+    cache_duration: Duration,
+}
+
+/// The transforms recorded for one edge.
+#[derive(Debug, Default)]
+struct TransformBuffer {
+    /// Set by [`TfEngine::set_transform`]: valid at every time.
+    fixed: Option<Transform3D>,
+    /// Time-stamped samples, oldest first.
+    samples: VecDeque<(SystemTime, Transform3D)>,
+}
+
+impl TransformBuffer {
+    fn latest(&self) -> Option<Transform3D> {
+        self.fixed
+            .or_else(|| self.samples.back().map(|&(_, tf)| tf))
+    }
+
+    /// The transform at `time`, interpolated between the bracketing samples.
+    /// `None` outside the recorded history (no extrapolation).
+    fn at(&self, time: SystemTime) -> Option<Transform3D> {
+        if self.fixed.is_some() {
+            return self.fixed;
+        }
+        let i = self.samples.partition_point(|&(stamp, _)| stamp < time);
+        let &(after_stamp, after) = self.samples.get(i)?;
+        if after_stamp == time {
+            return Some(after);
+        }
+        let &(before_stamp, before) = self.samples.get(i.checked_sub(1)?)?;
+        let span = after_stamp.duration_since(before_stamp).ok()?.as_secs_f64();
+        let elapsed = time.duration_since(before_stamp).ok()?.as_secs_f64();
+        Some(before.interpolate(after, (elapsed / span) as f32))
+    }
+}
+
+impl Default for TfEngine {
+    fn default() -> Self {
+        Self {
+            edges: HashMap::new(),
+            cache_duration: DEFAULT_TF_CACHE_DURATION,
+        }
+    }
 }
 
 impl TfEngine {
@@ -164,24 +304,141 @@ impl TfEngine {
         Self::default()
     }
 
-    /// Register or update the transform from `parent_frame` to `child_frame`.
+    /// Keep `duration` of history per time-stamped edge instead of
+    /// [`DEFAULT_TF_CACHE_DURATION`].
+    pub fn with_cache_duration(mut self, duration: Duration) -> Self {
+        self.cache_duration = duration;
+        self
+    }
+
+    /// Register or update the static transform from `parent_frame` to
+    /// `child_frame`, replacing any time-stamped history on that edge.
     pub fn set_transform(
         &mut self,
         parent_frame: &str,
         child_frame: &str,
         transform: Transform3D,
     ) {
-        self.edges
-            .entry(parent_frame.to_string())
-            .or_default()
-            .insert(child_frame.to_string(), transform);
+        let buffer = self.buffer_mut(parent_frame, child_frame);
+        buffer.fixed = Some(transform);
+        buffer.samples.clear();
+    }
+
+    /// Record the transform from `parent_frame` to `child_frame` at `stamp`.
+    ///
+    /// Samples may arrive out of order; those older than the cache duration
+    /// behind the newest sample are dropped.
+    pub fn set_transform_at(
+        &mut self,
+        parent_frame: &str,
+        child_frame: &str,
+        transform: Transform3D,
+        stamp: SystemTime,
+    ) {
+        let cache_duration = self.cache_duration;
+        let buffer = self.buffer_mut(parent_frame, child_frame);
+        buffer.fixed = None;
+        let i = buffer.samples.partition_point(|&(t, _)| t <= stamp);
+        buffer.samples.insert(i, (stamp, transform));
+        let newest = buffer.samples.back().map_or(stamp, |&(t, _)| t);
+        while buffer.samples.front().is_some_and(|&(t, _)| {
+            newest
+                .duration_since(t)
+                .is_ok_and(|age| age > cache_duration)
+        }) {
+            buffer.samples.pop_front();
+        }
     }
 
     /// Compute the composed [`Transform3D`] that maps points in `source_frame`
-    /// into `target_frame`.
+    /// into `target_frame`, using the latest transform on every edge.
     ///
     /// Returns `None` if no path exists between the two frames.
     pub fn lookup(&self, source_frame: &str, target_frame: &str) -> Option<Transform3D> {
+        self.resolve(source_frame, target_frame, TransformBuffer::latest)
+    }
+
+    /// Like [`lookup`](Self::lookup), with every edge evaluated at `time`.
+    ///
+    /// Returns `None` if no path exists whose edges all cover `time`: static
+    /// edges always do, time-stamped ones only between their oldest and
+    /// newest samples.
+    pub fn lookup_at(
+        &self,
+        source_frame: &str,
+        target_frame: &str,
+        time: SystemTime,
+    ) -> Option<Transform3D> {
+        self.resolve(source_frame, target_frame, |buffer| buffer.at(time))
+    }
+
+    /// Re-express `point`, given in `source_frame`, in `target_frame`.
+    ///
+    /// Returns `None` if the frames are not connected in either direction.
+    pub fn transform_point(
+        &self,
+        point: Vec3,
+        source_frame: &str,
+        target_frame: &str,
+    ) -> Option<Vec3> {
+        Some(self.between(source_frame, target_frame)?.apply(point))
+    }
+
+    /// Re-express `pose` in `target_frame`.
+    ///
+    /// Returns `None` if the frames are not connected in either direction.
+    pub fn transform_pose(&self, pose: &Pose, target_frame: &str) -> Option<Pose> {
+        let tf = self.between(&pose.frame_id, target_frame)?;
+        Some(Pose::from_transform(
+            target_frame,
+            &tf.compose(pose.to_transform()),
+        ))
+    }
+
+    /// Re-express `twist` in `target_frame` by rotating its linear velocity.
+    ///
+    /// The frames are assumed not to move relative to each other, so no
+    /// velocity is added by their relative motion.  Returns `None` if the
+    /// frames are not connected in either direction.
+    pub fn transform_twist(
+        &self,
+        twist: &FrameAwareTwist,
+        target_frame: &str,
+    ) -> Option<FrameAwareTwist> {
+        let tf = self.between(&twist.frame_id, target_frame)?;
+        let linear = tf
+            .rotation
+            .rotate(Vec3::new(twist.linear_x, twist.linear_y, 0.0));
+        Some(FrameAwareTwist::new(
+            target_frame,
+            linear.x,
+            linear.y,
+            twist.angular_z,
+        ))
+    }
+
+    /// The transform mapping points in `source_frame` into `target_frame`:
+    /// the registered chain from target to source, or the inverse of the
+    /// chain from source to target.
+    fn between(&self, source_frame: &str, target_frame: &str) -> Option<Transform3D> {
+        self.lookup(target_frame, source_frame)
+            .or_else(|| Some(self.lookup(source_frame, target_frame)?.inverse()))
+    }
+
+    fn buffer_mut(&mut self, parent_frame: &str, child_frame: &str) -> &mut TransformBuffer {
+        self.edges
+            .entry(parent_frame.to_string())
+            .or_default()
+            .entry(child_frame.to_string())
+            .or_default()
+    }
+
+    fn resolve(
+        &self,
+        source_frame: &str,
+        target_frame: &str,
+        edge: impl Fn(&TransformBuffer) -> Option<Transform3D>,
+    ) -> Option<Transform3D> {
         if source_frame == target_frame {
             return Some(Transform3D::identity());
         }
@@ -196,11 +453,14 @@ impl TfEngine {
 
         while let Some((current, accumulated)) = queue.pop_front() {
             if let Some(neighbours) = self.edges.get(&current) {
-                for (next, edge_tf) in neighbours {
+                for (next, buffer) in neighbours {
                     if visited.contains(next) {
                         continue;
                     }
-                    let composed = accumulated.compose(*edge_tf);
+                    let Some(edge_tf) = edge(buffer) else {
+                        continue;
+                    };
+                    let composed = accumulated.compose(edge_tf);
                     if next == target_frame {
                         return Some(composed);
                     }
@@ -214,6 +474,68 @@ impl TfEngine {
     }
 }
 
+// ────────────────────────────────────────────────────────────────────────────
+// Geodetic conversion
+// ────────────────────────────────────────────────────────────────────────────
+
+/// WGS-84 semi-major axis (metres).
+const WGS84_A: f64 = 6_378_137.0;
+/// WGS-84 flattening.
+const WGS84_F: f64 = 1.0 / 298.257_223_563;
+
+/// Origin of a local map frame on the WGS-84 ellipsoid.
+///
+/// [`GeodeticDatum::to_local`] maps a geodetic position to the local
+/// east-north-up (ENU) frame at the datum: `x` east, `y` north, `z` up, in
+/// metres.  The conversion goes through Earth-centred coordinates, so it
+/// stays accurate over the kilometre scale of outdoor missions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeodeticDatum {
+    /// Latitude of the origin (degrees).
+    pub latitude_deg: f64,
+    /// Longitude of the origin (degrees).
+    pub longitude_deg: f64,
+    /// Ellipsoidal height of the origin (metres).
+    pub altitude_m: f64,
+}
+
+impl GeodeticDatum {
+    /// Create a datum at the given origin.
+    pub fn new(latitude_deg: f64, longitude_deg: f64, altitude_m: f64) -> Self {
+        Self {
+            latitude_deg,
+            longitude_deg,
+            altitude_m,
+        }
+    }
+
+    /// Convert a geodetic position to local ENU metres relative to the datum.
+    pub fn to_local(&self, latitude_deg: f64, longitude_deg: f64, altitude_m: f64) -> Vec3 {
+        let [x0, y0, z0] = ecef(self.latitude_deg, self.longitude_deg, self.altitude_m);
+        let [x, y, z] = ecef(latitude_deg, longitude_deg, altitude_m);
+        let (dx, dy, dz) = (x - x0, y - y0, z - z0);
+        let (sin_lat, cos_lat) = self.latitude_deg.to_radians().sin_cos();
+        let (sin_lon, cos_lon) = self.longitude_deg.to_radians().sin_cos();
+        let east = -sin_lon * dx + cos_lon * dy;
+        let north = -sin_lat * cos_lon * dx - sin_lat * sin_lon * dy + cos_lat * dz;
+        let up = cos_lat * cos_lon * dx + cos_lat * sin_lon * dy + sin_lat * dz;
+        Vec3::new(east as f32, north as f32, up as f32)
+    }
+}
+
+/// Earth-centred, Earth-fixed coordinates of a WGS-84 position.
+fn ecef(latitude_deg: f64, longitude_deg: f64, altitude_m: f64) -> [f64; 3] {
+    let e2 = WGS84_F * (2.0 - WGS84_F);
+    let (sin_lat, cos_lat) = latitude_deg.to_radians().sin_cos();
+    let (sin_lon, cos_lon) = longitude_deg.to_radians().sin_cos();
+    let n = WGS84_A / (1.0 - e2 * sin_lat * sin_lat).sqrt();
+    [
+        (n + altitude_m) * cos_lat * cos_lon,
+        (n + altitude_m) * cos_lat * sin_lon,
+        (n * (1.0 - e2) + altitude_m) * sin_lat,
+    ]
+}
+
 // ────────────────────────────────────────────────────────────────────────────
 // Tests
 // ────────────────────────────────────────────────────────────────────────────
@@ -389,4 +711,147 @@ mod tests {
         assert!((t.translation.y - 1.0).abs() < 1e-5, "y={}", t.translation.y);
         assert!(t.translation.z.abs() < 1e-5);
     }
+
+    // ── Frame conversion ────────────────────────────────────────────────────
+
+    #[test]
+    fn inverse_undoes_a_transform() {
+        let tf = Transform3D::new(Vec3::new(1.0, -2.0, 0.5), Quaternion::from_yaw(0.7));
+        let p = Vec3::new(0.3, 0.4, -0.1);
+        let back = tf.inverse().apply(tf.apply(p));
+        assert!((back.x - p.x).abs() < 1e-5);
+        assert!((back.y - p.y).abs() < 1e-5);
+        assert!((back.z - p.z).abs() < 1e-5);
+    }
+
+    #[test]
+    fn poses_and_twists_convert_both_ways() {
+        use crate::types::{BASE_FRAME, FrameAwareTwist, MAP_FRAME, Pose};
+        use std::f32::consts::FRAC_PI_2;
+
+        let mut tf = TfEngine::new();
+        tf.set_transform(
+            MAP_FRAME,
+            BASE_FRAME,
+            Pose::new(MAP_FRAME, 2.0, 1.0, FRAC_PI_2).to_transform(),
+        );
+
+        // Forwards along the registered edge…
+        let ahead = tf
+            .transform_pose(&Pose::new(BASE_FRAME, 1.0, 0.0, 0.0), MAP_FRAME)
+            .unwrap();
+        assert!((ahead.x - 2.0).abs() < 1e-5 && (ahead.y - 2.0).abs() < 1e-5);
+        assert!((ahead.theta - FRAC_PI_2).abs() < 1e-5);
+
+        // …and backwards through its inverse.
+        let origin = tf
+            .transform_pose(&Pose::new(MAP_FRAME, 2.0, 3.0, FRAC_PI_2), BASE_FRAME)
+            .unwrap();
+        assert_eq!(origin.frame_id, BASE_FRAME);
+        assert!((origin.x - 2.0).abs() < 1e-5 && origin.y.abs() < 1e-5);
+        assert!(origin.theta.abs() < 1e-5);
+
+        let twist = tf
+            .transform_twist(&FrameAwareTwist::new(BASE_FRAME, 0.5, 0.0, 0.2), MAP_FRAME)
+            .unwrap();
+        assert!(twist.linear_x.abs() < 1e-5 && (twist.linear_y - 0.5).abs() < 1e-5);
+        assert_eq!(twist.angular_z, 0.2);
+
+        assert!(
+            tf.transform_point(Vec3::zero(), BASE_FRAME, "camera")
+                .is_none()
+        );
+    }
+
+    // ── Time-stamped lookup ─────────────────────────────────────────────────
+
+    #[test]
+    fn slerp_halfway_between_yaws() {
+        let q = Quaternion::identity().slerp(Quaternion::from_yaw(1.0), 0.5);
+        assert!((q.yaw() - 0.5).abs() < 1e-5);
+        // Shortest arc across ±π.
+        let q = Quaternion::from_yaw(3.0).slerp(Quaternion::from_yaw(-3.0), 0.5);
+        assert!(
+            (q.yaw().abs() - std::f32::consts::PI).abs() < 1e-4,
+            "yaw={}",
+            q.yaw()
+        );
+    }
+
+    #[test]
+    fn lookup_at_interpolates_between_samples() {
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(100);
+        let ms = |n: u64| t0 + Duration::from_millis(n);
+        let mut tf = TfEngine::new();
+        tf.set_transform_at(
+            "map",
+            "base_link",
+            Transform3D::new(Vec3::new(2.0, 0.0, 0.0), Quaternion::from_yaw(1.0)),
+            ms(100),
+        );
+        // Out of order: the older sample still lands first.
+        tf.set_transform_at("map", "base_link", Transform3D::identity(), ms(0));
+        tf.set_transform(
+            "base_link",
+            "laser",
+            Transform3D::new(Vec3::new(0.1, 0.0, 0.0), Quaternion::identity()),
+        );
+
+        let t = tf.lookup_at("map", "base_link", ms(20)).unwrap();
+        assert!((t.translation.x - 0.4).abs() < 1e-5);
+        assert!((t.rotation.yaw() - 0.2).abs() < 1e-5);
+        // Static edges compose with interpolated ones.
+        let t = tf.lookup_at("map", "laser", ms(50)).unwrap();
+        let expected = 1.0 + 0.1 * 0.5f32.cos();
+        assert!(
+            (t.translation.x - expected).abs() < 1e-5,
+            "x={}",
+            t.translation.x
+        );
+        // No extrapolation outside the history; lookup uses the newest.
+        assert!(tf.lookup_at("map", "base_link", ms(150)).is_none());
+        assert!((tf.lookup("map", "base_link").unwrap().translation.x - 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn history_older_than_cache_duration_is_dropped() {
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(100);
+        let mut tf = TfEngine::new().with_cache_duration(Duration::from_secs(1));
+        for s in 0..5 {
+            tf.set_transform_at(
+                "map",
+                "base_link",
+                Transform3D::new(Vec3::new(s as f32, 0.0, 0.0), Quaternion::identity()),
+                t0 + Duration::from_secs(s),
+            );
+        }
+        assert!(
+            tf.lookup_at("map", "base_link", t0 + Duration::from_secs(2))
+                .is_none()
+        );
+        let t = tf
+            .lookup_at("map", "base_link", t0 + Duration::from_millis(3500))
+            .unwrap();
+        assert!((t.translation.x - 3.5).abs() < 1e-5);
+    }
+
+    // ── GeodeticDatum ───────────────────────────────────────────────────────
+
+    #[test]
+    fn geodetic_datum_maps_offsets_to_east_north_up() {
+        let datum = GeodeticDatum::new(0.0, 0.0, 0.0);
+        let p = datum.to_local(0.001, 0.0, 0.0);
+        assert!(p.x.abs() < 1e-3 && (p.y - 110.574).abs() < 0.01, "{p:?}");
+        let p = datum.to_local(0.0, 0.001, 0.0);
+        assert!((p.x - 111.319).abs() < 0.01 && p.y.abs() < 1e-3, "{p:?}");
+        let p = datum.to_local(0.0, 0.0, 12.5);
+        assert!((p.z - 12.5).abs() < 1e-3, "{p:?}");
+
+        // Zurich: one arc-second of longitude is about 21 m at 47.4° N.
+        let datum = GeodeticDatum::new(47.397_742, 8.545_594, 488.0);
+        let origin = datum.to_local(47.397_742, 8.545_594, 488.0);
+        assert!(origin.x.abs() < 1e-3 && origin.y.abs() < 1e-3 && origin.z.abs() < 1e-3);
+        let p = datum.to_local(47.397_742, 8.545_594 + 1.0 / 3600.0, 488.0);
+        assert!((p.x - 20.94).abs() < 0.05 && p.y.abs() < 0.01, "{p:?}");
+    }
 }
@@ -0,0 +1,107 @@
+//! Frame-aware Pose and Twist.
+//!
+//! Planar poses and velocities that carry the name of the frame they are
+//! expressed in, so "0.5 m ahead" (`base_link`) and "0.5 m east of the
+//! origin" (`map`) cannot be mixed up by accident.  Convert between frames
+//! with [`TfEngine::transform_pose`] and [`TfEngine::transform_twist`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use mechos_perception::transform::{Quaternion, TfEngine, Transform3D, Vec3};
+//! use mechos_perception::types::{BASE_FRAME, MAP_FRAME, Pose};
+//!
+//! // The robot stands at (2, 1) facing +Y.
+//! let mut tf = TfEngine::new();
+//! let robot = Pose::new(MAP_FRAME, 2.0, 1.0, std::f32::consts::FRAC_PI_2);
+//! tf.set_transform(MAP_FRAME, BASE_FRAME, robot.to_transform());
+//!
+//! // 1 m ahead of the robot is (2, 2) on the map.
+//! let ahead = tf.transform_pose(&Pose::new(BASE_FRAME, 1.0, 0.0, 0.0), MAP_FRAME).unwrap();
+//! assert_eq!(ahead.frame_id, MAP_FRAME);
+//! assert!((ahead.x - 2.0).abs() < 1e-3 && (ahead.y - 2.0).abs() < 1e-3);
+//! ```
+
+use crate::transform::{Quaternion, Transform3D, Vec3};
+
+#[cfg(doc)]
+use crate::transform::TfEngine;
+
+pub use mechos_types::{BASE_FRAME, MAP_FRAME};
+
+// ────────────────────────────────────────────────────────────────────────────
+// Pose
+// ────────────────────────────────────────────────────────────────────────────
+
+/// A planar position and heading in a named frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pose {
+    /// Frame the pose is expressed in, e.g. [`MAP_FRAME`].
+    pub frame_id: String,
+    /// X position (metres).
+    pub x: f32,
+    /// Y position (metres).
+    pub y: f32,
+    /// Heading, counter-clockwise from the frame's X axis (radians).
+    pub theta: f32,
+}
+
+impl Pose {
+    /// Create a pose in `frame_id`.
+    pub fn new(frame_id: impl Into<String>, x: f32, y: f32, theta: f32) -> Self {
+        Self {
+            frame_id: frame_id.into(),
+            x,
+            y,
+            theta,
+        }
+    }
+
+    /// The transform from `frame_id` to a frame located at this pose, ready
+    /// for [`TfEngine::set_transform`].
+    pub fn to_transform(&self) -> Transform3D {
+        Transform3D::new(
+            Vec3::new(self.x, self.y, 0.0),
+            Quaternion::from_yaw(self.theta),
+        )
+    }
+
+    /// The planar part of `transform`, as a pose in `frame_id`.
+    pub fn from_transform(frame_id: impl Into<String>, transform: &Transform3D) -> Self {
+        Self::new(
+            frame_id,
+            transform.translation.x,
+            transform.translation.y,
+            transform.rotation.yaw(),
+        )
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// FrameAwareTwist
+// ────────────────────────────────────────────────────────────────────────────
+
+/// A planar velocity in a named frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameAwareTwist {
+    /// Frame the velocity is expressed in, e.g. [`BASE_FRAME`].
+    pub frame_id: String,
+    /// Linear velocity along the frame's X axis (m/s).
+    pub linear_x: f32,
+    /// Linear velocity along the frame's Y axis (m/s).
+    pub linear_y: f32,
+    /// Angular velocity about the Z axis (rad/s).
+    pub angular_z: f32,
+}
+
+impl FrameAwareTwist {
+    /// Create a twist in `frame_id`.
+    pub fn new(frame_id: impl Into<String>, linear_x: f32, linear_y: f32, angular_z: f32) -> Self {
+        Self {
+            frame_id: frame_id.into(),
+            linear_x,
+            linear_y,
+            angular_z,
+        }
+    }
+}
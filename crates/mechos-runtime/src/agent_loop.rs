@@ -5,24 +5,58 @@
 //!
 //! 1. **Observe** – query [`SensorFusion`] for the latest [`FusedState`] and
 //!    check the [`Octree`] for collision data.
-//! 2. **Orient** – format the state into a strict system prompt and retrieve
-//!    relevant memories from the [`EpisodicStore`].
+//! 2. **Orient** – retrieve relevant memories from the [`EpisodicStore`] and
+//!    render the state into the system prompt from a [`PromptTemplate`]
+//!    (built-in, or loaded from `~/.mechos/prompts/` via
+//!    [`AgentLoopConfig::prompt_template`]).
 //! 3. **Decide** – call [`LlmDriver::complete`].  The returned JSON is hashed
 //!    and checked against [`LoopGuard`] to ensure the agent isn't stuck in a
 //!    repetitive hallucination loop.
 //! 4. **Gatekeep** – the parsed [`HardwareIntent`] is checked by
 //!    [`CapabilityManager`] (permission) and [`StateVerifier`] (physical
 //!    invariants) via [`KernelGate`].
-//! 5. **Act** – the approved intent is published to the [`EventBus`].
+//! 5. **Simulate** – an approved `Drive` intent is forward-simulated over
+//!    [`AgentLoopConfig::trajectory_horizon_ms`] by a
+//!    [`TrajectoryPredictor`]; if the swept footprint hits the [`Octree`] the
+//!    command is scaled down to stop short of the obstacle, or rejected when
+//!    no collision-free prefix exists.
+//! 6. **Act** – the approved intent is published to the [`EventBus`] as an
+//!    [`IntentEnvelope`] carrying the tick's correlation ID and, for `Drive`,
+//!    a deadline after which adapters drop it.
+//!
+//! # Halting
+//!
+//! [`AgentLoop::halt`] publishes a [`HardwareIntent::Halt`], which the
+//! [`KernelGate`] always permits and every adapter turns into a
+//! zero-velocity / controller stop.  The loop halts itself when the
+//! [`LoopGuard`] trips.
 //!
 //! # Human-in-the-Loop (HITL)
 //!
 //! When the LLM outputs an [`HardwareIntent::AskHuman`] intent the loop
-//! automatically pauses.  Subsequent calls to [`AgentLoop::tick`] return
+//! stamps it with a fresh `question_id` and automatically pauses.
+//! Subsequent calls to [`AgentLoop::tick`] return
 //! [`MechError::LlmInferenceFailed`] until a human operator supplies an answer
-//! via [`AgentLoop::submit_human_response`].  The answer is then injected into
-//! the LLM context window as a [`Role::User`] message and the OODA cycle
-//! resumes normally.
+//! via [`AgentLoop::submit_human_answer`] or a bus
+//! [`EventPayload::HumanResponse`].  The answer is then injected into the LLM
+//! context window as a [`Role::User`] message and the OODA cycle resumes
+//! normally.
+//!
+//! Several questions can be outstanding at once.  An answer naming a
+//! `question_id` only answers that question, so loops sharing a bus never
+//! consume each other's answers; an answer without one (from an older peer)
+//! answers the oldest open question.  The loop resumes whenever an answer
+//! arrives and pauses again while questions remain open.
+//!
+//! A question left unanswered for [`AgentLoopConfig::hitl_timeout_secs`]
+//! (5 minutes by default) is recorded to episodic memory and the configured
+//! [`HitlFallback`] runs instead: hold position and keep waiting, return to
+//! the dock, or resume with a default answer.
+//!
+//! With [`AgentLoopConfig::operator_grants`] set, bus answers and approval
+//! decisions are only honoured from operators granted
+//! [`Capability::AnswerQuestions`] or [`Capability::ApproveIntents`]; the
+//! rest are logged and ignored.
 //!
 //! # Manual Override (Safety Interlock)
 //!
@@ -35,6 +69,59 @@
 //! suspension is cleared automatically once the configured duration has elapsed
 //! since the last call to `handle_manual_override`.
 //!
+//! # E-stop and safety mode
+//!
+//! [`EventPayload::SafetyRequest`]s from the bus (the Cockpit's e-stop and
+//! mode controls) drive the loop's [`SafetyState`], which a
+//! [`SafetyInterlock`] enforces on the [`StateVerifier`].  Engaging the
+//! e-stop halts the robot and [`tick`] refuses to run until it is reset; an
+//! [`FaultCode::EmergencyStop`] fault on the bus (the CLI's `/halt`) latches
+//! it too.  Every request is confirmed with an
+//! [`EventPayload::SafetyStatus`] echoing its `request_id`.
+//!
+//! # Operator approval
+//!
+//! Intents matching [`AgentLoop::set_approval_policy`] are not dispatched
+//! when they pass the [`KernelGate`]: they are held and published as an
+//! [`EventPayload::ApprovalRequest`] with every rule's verdict, and [`tick`]
+//! waits until an [`EventPayload::ApprovalDecision`] from the bus (the
+//! Cockpit's approve / reject buttons) answers it.  An approved intent is
+//! re-checked by the gate before it is dispatched.
+//!
+//! # Runtime safety policy
+//!
+//! [`EventPayload::PolicyRequest`]s from the bus (the Cockpit's
+//! authenticated policy endpoint) switch [`KernelGate`] rules between
+//! blocking and warning or adjust their parameters; the kernel validates
+//! every change and records it in its audit log.  Each request is answered
+//! with an [`EventPayload::PolicyStatus`] listing every rule, capability
+//! grant and audited change.
+//!
+//! # Deliberation (self-consistency)
+//!
+//! Setting [`AgentLoopConfig::deliberation_samples`] above `1` makes the
+//! Decide step request that many completions (sampled at
+//! [`AgentLoopConfig::deliberation_temperature`]) for the same observation.
+//! Candidates that fail to parse or are rejected by the [`KernelGate`] are
+//! discarded and the most frequent remaining answer wins; ties go to the
+//! earliest sample.
+//!
+//! # Skills
+//!
+//! Skills registered via [`AgentLoop::register_skill`] are listed in the
+//! system prompt and the LLM may answer with a [`SkillCall`] instead of a raw
+//! intent.  Intent-sequence skills are queued and dispatched one intent per
+//! tick (each still gated); behavior-tree skills are ticked once per
+//! [`tick`] until they finish, without consulting the LLM.
+//!
+//! # Transcripts
+//!
+//! When [`AgentLoopConfig::transcript_path`] is set, every prompt/response
+//! pair (including failed requests and every deliberation sample) is
+//! recorded to a [`TranscriptStore`] with its timestamp, trace ID and
+//! latency, filed under [`AgentLoop::mission_id`].  Fetch a mission's log with
+//! [`TranscriptStore::mission_transcript`].
+//!
 //! # Example
 //!
 //! ```rust,no_run
@@ -46,26 +133,53 @@
 //! // agent.tick() drives one full OODA cycle.
 //! ```
 
+use std::collections::{HashMap, VecDeque};
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 use std::sync::{
     Arc,
-    atomic::{AtomicBool, Ordering},
+    atomic::{AtomicBool, AtomicU8, AtomicU32, Ordering},
 };
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 
-use mechos_kernel::{CapabilityManager, KernelGate, ManualOverrideInterlock, StateVerifier};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use mechos_kernel::{
+    BatteryInterlock, CapabilityManager, KernelGate, ManualOverrideInterlock, MovingObstacleRule,
+    PendingApproval, Rule, SafetyInterlock, SafetyState, SpeechRule, StaleDataRule, StateVerifier,
+};
+use mechos_memory::encryption::KeySource;
 use mechos_memory::episodic::EpisodicStore;
+use mechos_memory::procedural::ProceduralStore;
+use mechos_memory::semantic::SemanticStateEstimator;
+use mechos_memory::task_board::TaskBoard;
+use mechos_memory::transcript::{TranscriptEntry, TranscriptStore};
 use mechos_middleware::EventBus;
-use mechos_perception::fusion::{FusedState, ImuData, OdometryData, SensorFusion};
-use mechos_perception::octree::{Aabb, Octree, Point3};
-use mechos_types::{Capability, Event, EventPayload, HardwareIntent, MechError};
+use mechos_perception::fusion::{
+    FusedState, GpsData, ImuBias, ImuData, OdometryData, SensorFusion,
+};
+use mechos_perception::occupancy::OccupancyGrid;
+use mechos_perception::octree::{Aabb, Label, Octree, Point3};
+use mechos_perception::planner::{self, AStarPlanner, Waypoint};
+use mechos_perception::tracking::{ObstacleTracker, TrackedObstacle};
+use mechos_perception::trajectory::TrajectoryPredictor;
+use mechos_perception::transform::{GeodeticDatum, TfEngine, Vec3};
+use mechos_types::{
+    BASE_FRAME, Capability, CapabilityGrant, Event, EventPayload, FaultCode, HardwareIntent, HumanAnswer, IntentEnvelope,
+    MAP_FRAME, MechError, Meters, MetersPerSecond, PolicyCommand, PolicyReport, SafetyCommand,
+    SafetyMode,
+};
 use tokio::sync::broadcast;
 use tracing::{debug, info, instrument, warn};
 use uuid::Uuid;
 
+use crate::behavior_tree::{BehaviorNode, NodeStatus};
 use crate::llm_driver::{ChatMessage, LlmDriver, Role};
 use crate::loop_guard::LoopGuard;
+use crate::map_sync::MapSync;
+use crate::metrics::RuntimeMetrics;
+use crate::prompt::PromptTemplate;
+use crate::skill::{Skill, SkillCall, SkillError, SkillExpansion, SkillRegistry};
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Constants
@@ -76,6 +190,61 @@ use crate::loop_guard::LoopGuard;
 /// [`AgentLoopConfig::override_suspension_secs`].
 const DEFAULT_OVERRIDE_SUSPENSION_SECS: u64 = 10;
 
+/// Default time an `AskHuman` question waits for an answer before the
+/// [`HitlFallback`] runs.  Tunable at construction time via
+/// [`AgentLoopConfig::hitl_timeout_secs`].
+const DEFAULT_HITL_TIMEOUT_SECS: u64 = 300;
+
+/// Default look-ahead for the pre-action trajectory check.
+const DEFAULT_TRAJECTORY_HORIZON_MS: u64 = 1000;
+
+/// Integration step of the pre-action trajectory check.
+const TRAJECTORY_STEP_SECS: f32 = 0.05;
+
+/// Default half-width of the robot footprint used by the trajectory check.
+const DEFAULT_ROBOT_RADIUS_M: f32 = 0.3;
+
+/// Default sampling temperature used when deliberation is enabled.
+const DEFAULT_DELIBERATION_TEMPERATURE: f32 = 0.7;
+
+/// Default lifetime of a dispatched `Drive` intent before adapters drop it.
+const DEFAULT_INTENT_TTL_MS: u64 = 500;
+
+/// Default cap on points in the collision octree.
+const DEFAULT_MAX_OBSTACLE_POINTS: usize = 100_000;
+
+/// Length of each LiDAR beam, short of its return, left uncarved so the
+/// obstacle's own surface points from earlier scans survive (metres).
+const SCAN_CARVE_MARGIN_M: f32 = 0.1;
+
+/// Cell size of the occupancy grid that `FollowWaypoints` legs are planned
+/// on (metres).
+const PLANNER_RESOLUTION_M: f32 = 0.1;
+
+/// Per-tick decay of the confidence in detected object classes.
+const SEMANTIC_DECAY_FACTOR: f32 = 0.95;
+
+/// Object classes whose confidence decays below this are forgotten.
+const SEMANTIC_PRUNE_THRESHOLD: f32 = 0.05;
+
+/// Most object locations listed in the `{{object_locations}}` placeholder.
+const PROMPT_OBJECT_LOCATIONS: usize = 5;
+
+/// Most learned procedures listed in the `{{procedures}}` placeholder.
+const PROMPT_PROCEDURES: usize = 2;
+
+/// Learned procedures whose goal is less similar than this to the current
+/// goal are not offered to the LLM.
+const PROCEDURE_MIN_SIMILARITY: f32 = 0.5;
+
+/// Longest intent sequence remembered for the current goal; older intents
+/// are dropped first.
+const MAX_PROCEDURE_STEPS: usize = 32;
+
+/// Tracked obstacles faster than this (m/s) count as moving for the
+/// [`MovingObstacleRule`].
+const MOVING_OBSTACLE_MIN_SPEED: f32 = 0.2;
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Configuration
 // ─────────────────────────────────────────────────────────────────────────────
@@ -88,12 +257,44 @@ pub struct AgentLoopConfig {
     pub llm_model: String,
     /// Number of consecutive identical LLM outputs that trigger a loop fault.
     pub loop_guard_threshold: usize,
-    /// Capability grants to issue to the `"agent"` identity at startup.
+    /// Identity under which this loop is authorized by the [`KernelGate`]
+    /// (e.g. `"navigator"`, `"manipulator"`).  Also namespaces the `source`
+    /// of every event the loop publishes
+    /// (`"mechos-runtime::agent_loop/<agent_id>"`).  Defaults to `"agent"`.
+    pub agent_id: String,
+    /// Optional role description prepended to the system prompt (e.g. "You
+    /// are the navigation specialist; you only drive the base.").  Lets
+    /// several loops sharing one bus specialise on different tasks.
+    pub role_prompt: Option<String>,
+    /// Name of a custom system-prompt template in `~/.mechos/prompts/`
+    /// (without the `.hbs` extension).  When `None` the built-in
+    /// [`DEFAULT_TEMPLATE`][crate::prompt::DEFAULT_TEMPLATE] is used.  See
+    /// [`PromptTemplate`] for the placeholder syntax.
+    pub prompt_template: Option<String>,
+    /// Initial mission goal rendered into the `{{goal}}` placeholder.
+    pub goal: Option<String>,
+    /// Capability grants to issue to [`agent_id`][Self::agent_id] at startup.
     pub capabilities: Vec<Capability>,
     /// Optional path to a persistent SQLite episodic memory database
     /// (e.g. `~/.mechos/memory.db`).  When `None` an in-memory database is
     /// used and memories are lost on shutdown.
     pub memory_path: Option<String>,
+    /// Key the database at [`memory_path`][Self::memory_path] is encrypted
+    /// with, from the configuration or the OS keyring.  Requires
+    /// `mechos-memory`'s `sqlcipher` feature.  When `None` the database is
+    /// stored in plaintext.
+    pub memory_key: Option<KeySource>,
+    /// Optional path to a SQLite database in which the object locations of
+    /// the [`SemanticStateEstimator`] are persisted.  When `None` they are
+    /// kept in memory and forgotten on shutdown.
+    pub semantic_path: Option<String>,
+    /// Optional path to a SQLite database in which every LLM prompt/response
+    /// pair is recorded (see [`TranscriptStore`]).  When `None` no
+    /// transcript is kept.
+    pub transcript_path: Option<String>,
+    /// Mission the loop's transcript entries are filed under.  When `None` a
+    /// fresh UUID is generated at startup.
+    pub mission_id: Option<String>,
     /// Optional shared [`EventBus`].  When supplied the agent loop publishes
     /// and receives events on the provided bus, allowing external adapters
     /// (e.g. [`mechos_middleware::Ros2Adapter`]) to share the same channel.
@@ -104,6 +305,59 @@ pub struct AgentLoopConfig {
     /// [`DEFAULT_OVERRIDE_SUSPENSION_SECS`] (10 s).  Tune this to match the
     /// reaction time requirements of your robot's hardware.
     pub override_suspension_secs: u64,
+    /// How long (in seconds) the loop waits for the answer to an `AskHuman`
+    /// question before applying [`hitl_fallback`][Self::hitl_fallback].  `0`
+    /// waits forever.  Defaults to 300 s.
+    pub hitl_timeout_secs: u64,
+    /// What the loop does when an `AskHuman` question times out.  Defaults to
+    /// [`HitlFallback::Hold`].
+    pub hitl_fallback: HitlFallback,
+    /// Human operators' grants.  When `Some`, a bus
+    /// [`EventPayload::HumanResponse`] counts only from an operator holding
+    /// [`Capability::AnswerQuestions`] and an
+    /// [`EventPayload::ApprovalDecision`] only from one holding
+    /// [`Capability::ApproveIntents`].  `None` (the default) accepts any
+    /// operator.
+    pub operator_grants: Option<Vec<CapabilityGrant>>,
+    /// Number of completions sampled per decision.  `1` (the default)
+    /// disables deliberation; higher values trade latency and tokens for a
+    /// majority vote over gate-approved candidates.
+    pub deliberation_samples: usize,
+    /// Sampling temperature applied to the LLM when
+    /// [`deliberation_samples`][Self::deliberation_samples] is above `1`, so
+    /// that the samples actually differ.  Defaults to `0.7`.
+    pub deliberation_temperature: f32,
+    /// How long (in milliseconds) a dispatched `Drive` intent stays valid.
+    /// The published [`IntentEnvelope`] carries the resulting deadline, so
+    /// adapters drop velocity commands that arrive late.  `0` disables the
+    /// deadline.  Defaults to 500 ms.
+    pub intent_ttl_ms: u64,
+    /// How far ahead (in milliseconds) an approved `Drive` intent is
+    /// forward-simulated against the collision octree before dispatch.
+    /// `0` disables the check.  Defaults to 1000 ms.
+    pub trajectory_horizon_ms: u64,
+    /// Half-width (metres) of the square robot footprint swept by the
+    /// trajectory check.  Defaults to 0.3 m.
+    pub robot_radius_m: f32,
+    /// How long (in seconds) an obstacle stays in the collision octree after
+    /// it was last seen, so transient obstacles such as passers-by stop
+    /// blocking paths.  `0` (the default) keeps obstacles until a LiDAR beam
+    /// passes through them or the point cap evicts them.
+    pub obstacle_ttl_secs: u64,
+    /// Most points the collision octree holds; the least recently seen are
+    /// evicted beyond it.  `0` disables the cap.  Defaults to 100 000.
+    pub max_obstacle_points: usize,
+    /// How often (in milliseconds) the collision octree is published as an
+    /// [`EventPayload::MapSnapshot`] with the robot's pose for the Cockpit
+    /// map view.  `0` (the default) disables the snapshots.
+    pub map_snapshot_interval_ms: u64,
+    /// Site-specific limits, such as a [`SpeedCapRule`] or a
+    /// [`GeofenceRule`] from the robot's configuration, checked by the
+    /// [`KernelGate`] after the built-in interlocks.  Empty by default.
+    ///
+    /// [`SpeedCapRule`]: mechos_kernel::SpeedCapRule
+    /// [`GeofenceRule`]: mechos_kernel::GeofenceRule
+    pub safety_rules: Vec<Box<dyn Rule>>,
 }
 
 impl Default for AgentLoopConfig {
@@ -112,18 +366,97 @@ impl Default for AgentLoopConfig {
             llm_base_url: "http://localhost:11434".to_string(),
             llm_model: "llama3".to_string(),
             loop_guard_threshold: 3,
+            agent_id: "agent".to_string(),
+            role_prompt: None,
+            prompt_template: None,
+            goal: None,
             capabilities: vec![
                 Capability::HardwareInvoke("end_effector".to_string()),
                 Capability::HardwareInvoke("drive_base".to_string()),
                 Capability::HardwareInvoke("hitl".to_string()),
+                Capability::HardwareInvoke("speaker".to_string()),
             ],
             memory_path: None,
+            memory_key: None,
+            semantic_path: None,
+            transcript_path: None,
+            mission_id: None,
             bus: None,
             override_suspension_secs: DEFAULT_OVERRIDE_SUSPENSION_SECS,
+            hitl_timeout_secs: DEFAULT_HITL_TIMEOUT_SECS,
+            hitl_fallback: HitlFallback::default(),
+            operator_grants: None,
+            deliberation_samples: 1,
+            deliberation_temperature: DEFAULT_DELIBERATION_TEMPERATURE,
+            trajectory_horizon_ms: DEFAULT_TRAJECTORY_HORIZON_MS,
+            robot_radius_m: DEFAULT_ROBOT_RADIUS_M,
+            intent_ttl_ms: DEFAULT_INTENT_TTL_MS,
+            obstacle_ttl_secs: 0,
+            max_obstacle_points: DEFAULT_MAX_OBSTACLE_POINTS,
+            map_snapshot_interval_ms: 0,
+            safety_rules: Vec::new(),
+        }
+    }
+}
+
+/// What [`AgentLoop`] does when an `AskHuman` question goes unanswered for
+/// [`AgentLoopConfig::hitl_timeout_secs`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum HitlFallback {
+    /// Halt the robot and keep waiting; a late answer still resumes the loop.
+    #[default]
+    Hold,
+    /// Stop waiting and dispatch a `Dock` intent through the gate.
+    ReturnToDock,
+    /// Stop waiting and resume as if the operator had answered with this
+    /// text.
+    DefaultAnswer(String),
+}
+
+impl std::fmt::Display for HitlFallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HitlFallback::Hold => f.write_str("hold position"),
+            HitlFallback::ReturnToDock => f.write_str("return to dock"),
+            HitlFallback::DefaultAnswer(answer) => {
+                write!(f, "resume with default answer \"{answer}\"")
+            }
+        }
+    }
+}
+
+/// One step of the in-progress skill.
+#[derive(Debug)]
+enum SkillStatus {
+    /// The next intent to dispatch.
+    Intent(HardwareIntent),
+    /// The named behavior-tree skill is still running and has nothing to
+    /// dispatch this tick.
+    Running(String),
+}
+
+impl SkillStatus {
+    /// The intent to dispatch, or – while a skill runs – the same kind of
+    /// skipped tick as waiting for a human answer.
+    fn into_intent(self) -> Result<HardwareIntent, MechError> {
+        match self {
+            Self::Intent(intent) => Ok(intent),
+            Self::Running(name) => Err(MechError::LlmInferenceFailed(format!(
+                "AgentLoop busy: skill '{name}' running"
+            ))),
         }
     }
 }
 
+/// A dispatched `AskHuman` question awaiting its answer.
+struct OpenQuestion {
+    id: String,
+    question: String,
+    asked: Instant,
+    /// `true` once the [`HitlFallback::Hold`] fallback has run for it.
+    timed_out: bool,
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // AgentLoop
 // ─────────────────────────────────────────────────────────────────────────────
@@ -134,19 +467,76 @@ impl Default for AgentLoopConfig {
 /// Act–Gatekeep cycle.  Call [`AgentLoop::tick`] from an event loop or async
 /// task to advance the agent by one step.
 pub struct AgentLoop {
+    /// Identity used for capability checks and event-source namespacing.
+    agent_id: String,
+    /// Optional role description injected into the system prompt.
+    role_prompt: Option<String>,
+    /// Template the system prompt is rendered from on every tick.
+    prompt: PromptTemplate,
+    /// Current mission goal, rendered into the prompt when set.
+    goal: Option<String>,
+    /// Capabilities granted at startup, listed in the prompt.
+    capabilities: Vec<Capability>,
     llm: LlmDriver,
     fusion: SensorFusion,
+    /// Origin of the local map frame for [`EventPayload::GpsFix`] events;
+    /// the first fix when not configured.
+    gps_datum: Option<GeodeticDatum>,
+    /// Recent fused poses as time-stamped `map → base_link` transforms, so
+    /// LiDAR scans are projected with the pose at capture time.
+    tf: TfEngine,
     octree: Octree,
+    /// Age after which unseen obstacles leave the octree; `None` keeps them.
+    obstacle_ttl: Option<Duration>,
+    /// Spacing of published map snapshots; `None` when disabled.
+    map_snapshot_interval: Option<Duration>,
+    /// When the last map snapshot was published.
+    last_map_snapshot: Option<Instant>,
+    /// Forward simulator for approved `Drive` intents; `None` when disabled.
+    trajectory: Option<TrajectoryPredictor>,
+    /// Half-width of the robot footprint, used when planning paths.
+    robot_radius: f32,
+    /// Time-decayed belief in the object classes labelled in the octree.
+    semantic: SemanticStateEstimator,
     memory: EpisodicStore,
+    /// Intent sequences that achieved past goals; `None` when procedural
+    /// memory is disabled.
+    procedures: Option<ProceduralStore>,
+    /// Intents dispatched since the current goal was set, stored as a
+    /// procedure by [`AgentLoop::record_goal_success`].
+    goal_trace: VecDeque<HardwareIntent>,
+    /// Audit log of LLM exchanges; `None` when transcripts are disabled.
+    transcript: Option<TranscriptStore>,
+    /// Mission that transcript entries are filed under.
+    mission_id: String,
     bus: EventBus,
     gate: KernelGate,
     loop_guard: LoopGuard,
+    /// Completions sampled per decision (`1` = no deliberation).
+    deliberation_samples: usize,
+    /// Lifetime of a dispatched `Drive` intent; `None` = no deadline.
+    intent_ttl: Option<chrono::Duration>,
+    /// Correlation ID of the current (or most recent) tick, stamped on every
+    /// intent envelope the tick publishes.
+    correlation_id: Uuid,
+    // ── Skill state ───────────────────────────────────────────────────────────
+    /// Skills the LLM may invoke by name.
+    skills: SkillRegistry,
+    /// Remaining intents of an in-progress intent-sequence skill.
+    skill_queue: VecDeque<HardwareIntent>,
+    /// In-progress behavior-tree skill and its name.
+    active_skill_tree: Option<(String, BehaviorNode)>,
     // ── HITL state ────────────────────────────────────────────────────────────
-    /// `true` after the LLM has issued an `AskHuman` intent and before the
-    /// human operator's response has been consumed.
-    waiting_for_human: bool,
-    /// The human operator's answer, ready to be injected into the next tick.
-    pending_human_response: Option<String>,
+    /// Dispatched `AskHuman` questions without an answer, oldest first.
+    open_questions: Vec<OpenQuestion>,
+    /// `(question, answer)` pairs ready to be injected into the next tick.
+    answered_questions: Vec<(String, String)>,
+    /// How long a question waits before the fallback; `None` = forever.
+    hitl_timeout: Option<Duration>,
+    /// Applied when a question times out.
+    hitl_fallback: HitlFallback,
+    /// `true` when operators need a grant to answer or approve.
+    authorize_operators: bool,
     // ── Manual override state ─────────────────────────────────────────────────
     /// Shared flag that is `true` while the dashboard manual-override joystick
     /// is held.  Also registered in the [`StateVerifier`] as a
@@ -157,6 +547,34 @@ pub struct AgentLoop {
     override_last_seen: Option<Instant>,
     /// How long the AI remains suspended after each manual-override command.
     override_suspension_duration: Duration,
+    // ── Power state ───────────────────────────────────────────────────────────
+    /// Battery level from the most recent [`EventPayload::PowerStatus`], in
+    /// percent.  Registered in the [`StateVerifier`] as a
+    /// [`BatteryInterlock`]; `0` until the first reading, so the robot stays
+    /// docked until its charge is known.
+    battery_percent: Arc<AtomicU8>,
+    // ── Localization state ────────────────────────────────────────────────────
+    /// `true` while odometry has dropped out and sensor fusion is
+    /// dead-reckoning from the IMU.  Refreshed every tick and registered in
+    /// the [`StateVerifier`] as a [`StaleDataRule`].
+    localization_degraded: Arc<AtomicBool>,
+    // ── Dynamic obstacles ─────────────────────────────────────────────────────
+    /// Follows LiDAR clusters across scans and estimates their velocities.
+    tracker: ObstacleTracker,
+    /// Distance to the nearest moving obstacle as `f32` bits, registered in
+    /// the [`StateVerifier`] as a [`MovingObstacleRule`].
+    moving_obstacle_clearance: Arc<AtomicU32>,
+    // ── Operator safety controls ──────────────────────────────────────────────
+    /// E-stop latch and safety mode, registered in the [`StateVerifier`] as a
+    /// [`SafetyInterlock`].
+    safety: Arc<SafetyState>,
+    // ── Fleet map sharing ─────────────────────────────────────────────────────
+    /// Shares the collision octree with peer robots when installed with
+    /// [`AgentLoop::set_map_sync`].
+    map_sync: Option<MapSync>,
+    /// Fleet task this loop works on, whose claim lease is renewed every
+    /// tick; set with [`AgentLoop::set_active_task`].
+    active_task: Option<(TaskBoard, String)>,
     // ── Cockpit pause/resume state ────────────────────────────────────────────
     /// `true` when the Cockpit operator has explicitly paused the autonomous
     /// OODA cycle via the mode-toggle button.  Independent of the joystick
@@ -173,10 +591,22 @@ impl AgentLoop {
     /// # Errors
     ///
     /// Returns [`MechError::Serialization`] if the in-memory episodic store
-    /// cannot be initialised (e.g. SQLite is unavailable).
+    /// cannot be initialised (e.g. SQLite is unavailable), or
+    /// [`MechError::Parsing`] if the configured prompt template cannot be
+    /// loaded or fails validation.
     pub fn new(config: AgentLoopConfig) -> Result<Self, MechError> {
-        let llm = LlmDriver::new(&config.llm_base_url, &config.llm_model)
+        let mut llm = LlmDriver::new(&config.llm_base_url, &config.llm_model)
             .map_err(|e| MechError::Serialization(format!("failed to create LLM driver: {e}")))?;
+        let deliberation_samples = config.deliberation_samples.max(1);
+        if deliberation_samples > 1 {
+            llm.set_temperature(Some(config.deliberation_temperature));
+        }
+
+        let prompt = match config.prompt_template {
+            Some(ref name) => PromptTemplate::load_named(name)
+                .map_err(|e| MechError::Parsing(format!("prompt template '{name}': {e}")))?,
+            None => PromptTemplate::default(),
+        };
 
         // Sensor fusion with a strong IMU weight.
         let fusion = SensorFusion::new(0.98);
@@ -186,16 +616,58 @@ impl AgentLoop {
             Point3::new(-10.0, -10.0, -10.0),
             Point3::new(10.0, 10.0, 10.0),
         );
-        let octree = Octree::new(world_bounds, 8);
+        let mut octree = Octree::new(world_bounds, 8);
+        if config.max_obstacle_points > 0 {
+            octree = octree.with_max_points(config.max_obstacle_points);
+        }
+        let obstacle_ttl =
+            (config.obstacle_ttl_secs > 0).then(|| Duration::from_secs(config.obstacle_ttl_secs));
+
+        let trajectory = (config.trajectory_horizon_ms > 0).then(|| {
+            TrajectoryPredictor::new(
+                config.trajectory_horizon_ms as f32 / 1000.0,
+                TRAJECTORY_STEP_SECS,
+                config.robot_radius_m,
+            )
+        });
 
         // In-memory episodic store or persistent file-backed store.
-        let memory = match config.memory_path {
-            Some(ref path) => EpisodicStore::open(path)
+        let memory = match (&config.memory_path, &config.memory_key) {
+            (Some(path), Some(source)) => source
+                .resolve()
+                .map_err(|e| MechError::Serialization(format!("failed to resolve memory key: {e}")))
+                .and_then(|key| {
+                    EpisodicStore::open_encrypted(path, &key).map_err(|e| {
+                        MechError::Serialization(format!(
+                            "failed to open encrypted episodic store at '{path}': {e}"
+                        ))
+                    })
+                })?,
+            (Some(path), None) => EpisodicStore::open(path)
                 .map_err(|e| MechError::Serialization(format!("failed to open episodic store at '{path}': {e}")))?,
-            None => EpisodicStore::open_in_memory()
+            (None, _) => EpisodicStore::open_in_memory()
                 .map_err(|e| MechError::Serialization(format!("failed to open in-memory episodic store: {e}")))?,
         };
 
+        let semantic = match config.semantic_path {
+            Some(ref path) => {
+                SemanticStateEstimator::open(path, SEMANTIC_DECAY_FACTOR).map_err(|e| {
+                    MechError::Serialization(format!(
+                        "failed to open semantic store at '{path}': {e}"
+                    ))
+                })?
+            }
+            None => SemanticStateEstimator::new(SEMANTIC_DECAY_FACTOR),
+        };
+
+        let transcript = match config.transcript_path {
+            Some(ref path) => Some(TranscriptStore::open(path).map_err(|e| {
+                MechError::Serialization(format!("failed to open transcript store at '{path}': {e}"))
+            })?),
+            None => None,
+        };
+        let mission_id = config.mission_id.unwrap_or_else(|| Uuid::new_v4().to_string());
+
         let bus = config.bus.unwrap_or_default();
 
         // Subscribe to the bus for HITL responses and override events.
@@ -204,36 +676,106 @@ impl AgentLoop {
         // Shared override flag – registered in the StateVerifier so AI Drive
         // commands are rejected whenever the human has the joystick.
         let override_active = Arc::new(AtomicBool::new(false));
+        // Shared battery level – registered so `Undock` is refused on a low
+        // charge.
+        let battery_percent = Arc::new(AtomicU8::new(0));
+        // Shared localization flag – registered so motion is restricted while
+        // the pose is dead-reckoned.
+        let localization_degraded = Arc::new(AtomicBool::new(false));
+        // Shared moving-obstacle clearance – registered so the robot slows
+        // down near people.
+        let moving_obstacle_clearance = Arc::new(AtomicU32::new(f32::INFINITY.to_bits()));
+        // Operator e-stop latch and safety mode.
+        let safety = Arc::new(SafetyState::new());
 
         // Capability manager: grant the agent identity all configured caps.
         let mut caps = CapabilityManager::new();
-        for cap in config.capabilities {
-            caps.grant("agent", cap);
+        for cap in config.capabilities.clone() {
+            caps.grant(&config.agent_id, cap);
+        }
+        let authorize_operators = config.operator_grants.is_some();
+        for grant in config.operator_grants.into_iter().flatten() {
+            for cap in grant.capabilities {
+                caps.grant(&grant.agent_id, cap);
+            }
         }
         let mut verifier = StateVerifier::new();
         verifier.add_rule(Box::new(ManualOverrideInterlock::new(Arc::clone(
             &override_active,
         ))));
+        verifier.add_rule(Box::new(BatteryInterlock::new(Arc::clone(
+            &battery_percent,
+        ))));
+        verifier.add_rule(Box::new(StaleDataRule::new(Arc::clone(
+            &localization_degraded,
+        ))));
+        verifier.add_rule(Box::new(MovingObstacleRule::new(Arc::clone(
+            &moving_obstacle_clearance,
+        ))));
+        verifier.add_rule(Box::new(SafetyInterlock::new(Arc::clone(&safety))));
+        verifier.add_rule(Box::new(SpeechRule::default()));
+        for rule in config.safety_rules {
+            verifier.add_rule(rule);
+        }
         let gate = KernelGate::new(caps, verifier);
 
         let loop_guard = LoopGuard::new(config.loop_guard_threshold);
 
         let override_suspension_duration =
             Duration::from_secs(config.override_suspension_secs);
+        let hitl_timeout =
+            (config.hitl_timeout_secs > 0).then(|| Duration::from_secs(config.hitl_timeout_secs));
+
+        let intent_ttl = (config.intent_ttl_ms > 0)
+            .then(|| chrono::Duration::milliseconds(config.intent_ttl_ms as i64));
 
         Ok(Self {
+            agent_id: config.agent_id,
+            role_prompt: config.role_prompt,
+            prompt,
+            goal: config.goal,
+            capabilities: config.capabilities,
             llm,
             fusion,
+            gps_datum: None,
+            tf: TfEngine::new(),
+            obstacle_ttl,
+            map_snapshot_interval: (config.map_snapshot_interval_ms > 0)
+                .then(|| Duration::from_millis(config.map_snapshot_interval_ms)),
+            last_map_snapshot: None,
             octree,
+            trajectory,
+            robot_radius: config.robot_radius_m,
+            semantic,
             memory,
+            procedures: None,
+            goal_trace: VecDeque::new(),
+            transcript,
+            mission_id,
             bus,
             gate,
             loop_guard,
-            waiting_for_human: false,
-            pending_human_response: None,
+            deliberation_samples,
+            intent_ttl,
+            correlation_id: Uuid::new_v4(),
+            skills: SkillRegistry::new(),
+            skill_queue: VecDeque::new(),
+            active_skill_tree: None,
+            open_questions: Vec::new(),
+            answered_questions: Vec::new(),
+            hitl_timeout,
+            hitl_fallback: config.hitl_fallback,
+            authorize_operators,
             override_active,
             override_last_seen: None,
             override_suspension_duration,
+            battery_percent,
+            localization_degraded,
+            tracker: ObstacleTracker::new(),
+            moving_obstacle_clearance,
+            safety,
+            map_sync: None,
+            active_task: None,
             paused: false,
             bus_rx,
         })
@@ -243,19 +785,122 @@ impl AgentLoop {
     // Subsystem accessors (for testing / external wiring)
     // -------------------------------------------------------------------------
 
+    /// The identity this loop is authorized under.
+    pub fn agent_id(&self) -> &str {
+        &self.agent_id
+    }
+
+    /// Replace the system-prompt template (e.g. one built with
+    /// [`PromptTemplate::parse`] rather than loaded from disk).
+    pub fn set_prompt_template(&mut self, template: PromptTemplate) {
+        self.prompt = template;
+    }
+
+    /// Set or clear the mission goal rendered into the system prompt.
+    ///
+    /// A different goal starts a fresh intent sequence for
+    /// [`record_goal_success`][Self::record_goal_success].
+    pub fn set_goal(&mut self, goal: Option<String>) {
+        if goal != self.goal {
+            self.goal_trace.clear();
+        }
+        self.goal = goal;
+    }
+
+    /// The current mission goal, if any.
+    pub fn goal(&self) -> Option<&str> {
+        self.goal.as_deref()
+    }
+
+    /// Correlation ID of the current (or most recent) tick.  Every intent the
+    /// tick dispatched carries it in its [`IntentEnvelope`], so adapter
+    /// results can be traced back to the tick.
+    pub fn last_correlation_id(&self) -> Uuid {
+        self.correlation_id
+    }
+
+    /// Mission under which LLM exchanges are currently recorded.
+    pub fn mission_id(&self) -> &str {
+        &self.mission_id
+    }
+
+    /// Start filing LLM exchanges under a new mission.
+    pub fn set_mission_id(&mut self, mission_id: impl Into<String>) {
+        self.mission_id = mission_id.into();
+    }
+
+    /// Attach (or detach) a transcript store, e.g. one shared by several
+    /// loops or opened in memory for tests.
+    pub fn set_transcript_store(&mut self, store: Option<TranscriptStore>) {
+        self.transcript = store;
+    }
+
+    /// The transcript store, if transcripts are enabled.  Use
+    /// [`TranscriptStore::mission_transcript`] to fetch a mission's log.
+    pub fn transcript(&self) -> Option<&TranscriptStore> {
+        self.transcript.as_ref()
+    }
+
+    /// Replace the LLM driver, e.g. with [`LlmDriver::scripted`] for a
+    /// deterministic replay.  The new driver is used as-is; deliberation
+    /// temperature is not re-applied.
+    pub fn set_llm_driver(&mut self, llm: LlmDriver) {
+        self.llm = llm;
+    }
+
     /// Return a clone of the [`EventBus`] so callers can subscribe to intents.
     pub fn bus(&self) -> EventBus {
         self.bus.clone()
     }
 
-    /// Provide a fresh odometry sample to the sensor fusion engine.
+    /// Provide a fresh odometry sample, measured just now, to the sensor
+    /// fusion engine.
     pub fn update_odometry(&mut self, data: OdometryData) {
+        self.update_odometry_at(data, SystemTime::now());
+    }
+
+    /// Provide an odometry sample measured at `measured_at` (the odometry
+    /// message's stamp) to the sensor fusion engine.  The fused pose enters
+    /// the pose history under that time, so scans are projected with the
+    /// pose at their capture time despite transport latency.
+    pub fn update_odometry_at(&mut self, data: OdometryData, measured_at: SystemTime) {
         self.fusion.update_odometry(data);
+        self.record_pose(measured_at);
     }
 
     /// Provide a fresh IMU sample to the sensor fusion engine.
     pub fn update_imu(&mut self, data: ImuData) {
         self.fusion.update_imu(data);
+        self.record_pose(SystemTime::now());
+    }
+
+    /// Stamp the current fused pose into the pose history at `at`.
+    fn record_pose(&mut self, at: SystemTime) {
+        let pose = self.fusion.fused_state(0.0).pose().to_transform();
+        self.tf.set_transform_at(MAP_FRAME, BASE_FRAME, pose, at);
+    }
+
+    /// Estimate the IMU bias from samples taken while the robot stands
+    /// still; see [`SensorFusion::calibrate`].
+    pub fn calibrate_imu(&mut self, stationary_samples: &[ImuData]) -> Option<ImuBias> {
+        self.fusion.calibrate(stationary_samples)
+    }
+
+    /// `true` while odometry has dropped out and the pose is dead-reckoned
+    /// from the IMU, as of the last tick.
+    pub fn is_localization_degraded(&self) -> bool {
+        self.localization_degraded.load(Ordering::Acquire)
+    }
+
+    /// Set the origin of the local map frame that GPS fixes are converted
+    /// into.  Without one, the first fix received becomes the origin.
+    pub fn set_gps_datum(&mut self, datum: GeodeticDatum) {
+        self.gps_datum = Some(datum);
+    }
+
+    /// The origin of the local map frame for GPS fixes, once known.
+    pub fn gps_datum(&self) -> Option<GeodeticDatum> {
+        self.gps_datum
     }
 
     /// Insert a known obstacle point into the collision octree.
@@ -263,25 +908,221 @@ impl AgentLoop {
         self.octree.insert(p);
     }
 
+    /// Record an object detection: insert `position` into the collision
+    /// octree labelled `label`, and fuse the detection's `embedding` and
+    /// `confidence` into the [`SemanticStateEstimator`], so the prompt can
+    /// name what the nearest obstacle is.
+    pub fn observe_object(
+        &mut self,
+        label: &str,
+        position: Point3,
+        embedding: &[f32],
+        confidence: f32,
+    ) {
+        self.octree
+            .insert_labeled(position, Label::new(label, confidence));
+        self.semantic.observe(label, embedding, confidence);
+    }
+
+    /// Record that `object` was just seen at the named `location` (e.g.
+    /// `"kitchen table"`), so the prompt can tell the LLM where to find it.
+    pub fn observe_object_location(&mut self, object: &str, location: &str) {
+        self.semantic.update_observation(object, location);
+    }
+
+    /// Belief in the object classes detected so far.
+    pub fn semantic_state(&self) -> &SemanticStateEstimator {
+        &self.semantic
+    }
+
+    /// Install or remove the [`MapSync`] sharing the collision octree with
+    /// the fleet.  While installed, every tick merges peer map updates and,
+    /// once per sync interval, broadcasts this robot's own obstacles.
+    pub fn set_map_sync(&mut self, map_sync: Option<MapSync>) {
+        self.map_sync = map_sync;
+    }
+
+    /// The installed [`MapSync`], if any.
+    pub fn map_sync(&self) -> Option<&MapSync> {
+        self.map_sync.as_ref()
+    }
+
+    /// Work on `task_id`, which this agent has claimed on `board`: every
+    /// tick renews the claim's lease under the agent ID, so the task is only
+    /// reassigned if this loop stops ticking.
+    pub fn set_active_task(&mut self, board: TaskBoard, task_id: impl Into<String>) {
+        self.active_task = Some((board, task_id.into()));
+    }
+
+    /// Stop renewing the active task's lease, e.g. after completing it.
+    pub fn clear_active_task(&mut self) {
+        self.active_task = None;
+    }
+
+    /// ID of the task whose lease this loop renews, if any.
+    pub fn active_task(&self) -> Option<&str> {
+        self.active_task.as_ref().map(|(_, id)| id.as_str())
+    }
+
+    /// Renew the active task's lease.  When the claim is gone (completed,
+    /// or already reassigned after a lapse) the task is dropped with a
+    /// warning so the loop stops renewing it.
+    async fn renew_active_task(&mut self) {
+        let Some((board, task_id)) = &self.active_task else {
+            return;
+        };
+        if let Err(e) = board.renew(task_id, &self.agent_id).await {
+            warn!(
+                agent_id = %self.agent_id,
+                task_id = %task_id,
+                error = %e,
+                "dropping active task: lease renewal failed"
+            );
+            self.active_task = None;
+        }
+    }
+
+    /// Install or remove the [`ProceduralStore`] that remembers which intent
+    /// sequences achieved past goals.  While installed, the Orient step
+    /// lists the procedures learned for goals similar to the current one.
+    pub fn set_procedural_store(&mut self, store: Option<ProceduralStore>) {
+        self.procedures = store;
+    }
+
+    /// The installed [`ProceduralStore`], if any.
+    pub fn procedural_store(&self) -> Option<&ProceduralStore> {
+        self.procedures.as_ref()
+    }
+
+    /// Report that the current goal was achieved: the intents dispatched
+    /// since it was set are stored as a procedure in the
+    /// [`ProceduralStore`] and the sequence starts afresh.
+    ///
+    /// Returns `Ok(false)` without storing anything when no store is
+    /// installed, no goal is set or no intent was dispatched.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Serialization`] if the store rejects the
+    /// procedure.
+    pub async fn record_goal_success(&mut self) -> Result<bool, MechError> {
+        let (Some(store), Some(goal)) = (&self.procedures, &self.goal) else {
+            return Ok(false);
+        };
+        if self.goal_trace.is_empty() {
+            return Ok(false);
+        }
+        let steps: Vec<HardwareIntent> = self.goal_trace.drain(..).collect();
+        store
+            .record_text(goal, &steps)
+            .await
+            .map_err(|e| MechError::Serialization(format!("failed to record procedure: {e}")))?;
+        Ok(true)
+    }
+
+    /// Procedures learned for goals similar to the current one, one
+    /// `- Last time you did "<goal>", this sequence worked (<n>×): <steps>`
+    /// line each; empty when there is no goal, store or close match.
+    async fn describe_procedures(&self) -> String {
+        let (Some(store), Some(goal)) = (&self.procedures, &self.goal) else {
+            return String::new();
+        };
+        let recalled = match store.recall_text(goal, PROMPT_PROCEDURES).await {
+            Ok(recalled) => recalled,
+            Err(e) => {
+                warn!(error = %e, "failed to recall learned procedures");
+                return String::new();
+            }
+        };
+        recalled
+            .into_iter()
+            .filter(|(_, similarity)| *similarity >= PROCEDURE_MIN_SIMILARITY)
+            .map(|(procedure, _)| {
+                let steps = procedure
+                    .steps
+                    .iter()
+                    .map(|step| serde_json::to_string(step).unwrap_or_default())
+                    .collect::<Vec<_>>()
+                    .join(" → ");
+                format!(
+                    "- Last time you did \"{}\", this sequence worked ({}×): {steps}",
+                    procedure.goal, procedure.successes
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    // -------------------------------------------------------------------------
+    // Skill API
+    // -------------------------------------------------------------------------
+
+    /// Register a [`Skill`] the LLM may invoke by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SkillError::Duplicate`] if a skill with the same name is
+    /// already registered.
+    pub fn register_skill(&mut self, skill: Skill) -> Result<(), SkillError> {
+        self.skills.register(skill)
+    }
+
+    /// The skills available to this loop.
+    pub fn skills(&self) -> &SkillRegistry {
+        &self.skills
+    }
+
+    /// `true` while an invoked skill still has work left to do.
+    pub fn is_skill_active(&self) -> bool {
+        !self.skill_queue.is_empty() || self.active_skill_tree.is_some()
+    }
+
     // -------------------------------------------------------------------------
     // HITL API
     // -------------------------------------------------------------------------
 
-    /// Inject a human operator's response into the OODA loop.
+    /// Inject a human operator's answer into the OODA loop.
     ///
     /// Call this when the dashboard WebSocket sends a reply to an earlier
-    /// [`HardwareIntent::AskHuman`] prompt.  The response is stored and
-    /// consumed by the next [`tick`][Self::tick] call: it is prepended to the
-    /// LLM context window as a [`Role::User`] message and the OODA cycle
-    /// resumes normally.
-    pub fn submit_human_response(&mut self, response: impl Into<String>) {
-        self.pending_human_response = Some(response.into());
-        self.waiting_for_human = false;
+    /// [`HardwareIntent::AskHuman`] prompt.  The answer closes the open
+    /// question it names (the oldest one when it names none) and is consumed
+    /// by the next [`tick`][Self::tick] call: it is appended to the LLM
+    /// context window as a [`Role::User`] message and the OODA cycle resumes
+    /// normally.
+    ///
+    /// Returns `false`, ignoring the answer, when no open question matches
+    /// (e.g. it answers another loop's question).
+    pub fn submit_human_answer(&mut self, answer: &HumanAnswer) -> bool {
+        let index = match &answer.question_id {
+            Some(id) => self.open_questions.iter().position(|q| q.id == *id),
+            None => (!self.open_questions.is_empty()).then_some(0),
+        };
+        let Some(index) = index else {
+            debug!(question_id = ?answer.question_id, "ignoring answer to no open question");
+            return false;
+        };
+        let question = self.open_questions.remove(index);
+        self.answered_questions
+            .push((question.question, answer.answer.clone()));
+        true
+    }
+
+    /// Answer the oldest open question; see
+    /// [`submit_human_answer`][Self::submit_human_answer].
+    pub fn submit_human_response(&mut self, response: impl Into<String>) -> bool {
+        self.submit_human_answer(&HumanAnswer::new(response))
     }
 
-    /// `true` if the loop is currently paused waiting for a human response.
+    /// `true` while an `AskHuman` question is unanswered.
     pub fn is_waiting_for_human(&self) -> bool {
-        self.waiting_for_human
+        !self.open_questions.is_empty()
+    }
+
+    /// IDs and text of the unanswered `AskHuman` questions, oldest first.
+    pub fn open_questions(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.open_questions
+            .iter()
+            .map(|q| (q.id.as_str(), q.question.as_str()))
     }
 
     // -------------------------------------------------------------------------
@@ -307,11 +1148,244 @@ impl AgentLoop {
         let _ = self.bus.publish(event);
     }
 
+    /// Stop the robot and lift the AI suspension at once.
+    ///
+    /// Used when the Cockpit's dead-man switch trips: the operator's tab
+    /// went silent mid-drive, so nobody is holding the joystick any more.
+    /// Publishes a zero-velocity override command instead of letting the
+    /// last Twist coast.
+    pub fn release_manual_override(&mut self) {
+        self.override_active.store(false, Ordering::Release);
+        self.override_last_seen = None;
+        let _ = self.bus.publish(Self::build_override_event(0.0, 0.0));
+    }
+
+    /// Command an immediate stop.
+    ///
+    /// Publishes a [`HardwareIntent::Halt`] (which the [`KernelGate`] always
+    /// permits, so it is not gated here) and abandons any in-progress skill.
+    /// Called automatically when the [`LoopGuard`] trips.
+    pub fn halt(&mut self, reason: impl Into<String>) {
+        self.skill_queue.clear();
+        self.active_skill_tree = None;
+        self.act(&mut HardwareIntent::Halt {
+            reason: reason.into(),
+        });
+    }
+
+    /// Shared manual-override flag, so a supervisor's gate can register its
+    /// own [`ManualOverrideInterlock`] against this loop's joystick state.
+    pub(crate) fn override_flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.override_active)
+    }
+
+    /// Shared battery level, so a supervisor's gate can register its own
+    /// [`BatteryInterlock`] against this loop's power readings.
+    pub(crate) fn battery_level(&self) -> Arc<AtomicU8> {
+        Arc::clone(&self.battery_percent)
+    }
+
+    /// Shared localization flag, so a supervisor's gate can register its own
+    /// [`StaleDataRule`] against this loop's sensor fusion.
+    pub(crate) fn localization_flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.localization_degraded)
+    }
+
+    /// Shared moving-obstacle clearance, so a supervisor's gate can register
+    /// its own [`MovingObstacleRule`] against this loop's obstacle tracker.
+    pub(crate) fn moving_obstacle_clearance(&self) -> Arc<AtomicU32> {
+        Arc::clone(&self.moving_obstacle_clearance)
+    }
+
+    /// Shared e-stop latch and safety mode, so a supervisor's gate can
+    /// register its own [`SafetyInterlock`] against this loop's state.
+    pub(crate) fn safety_state(&self) -> Arc<SafetyState> {
+        Arc::clone(&self.safety)
+    }
+
+    /// Obstacles currently tracked across LiDAR scans, in the map frame.
+    pub fn tracked_obstacles(&self) -> Vec<&TrackedObstacle> {
+        self.tracker.tracks()
+    }
+
+    /// Battery level from the most recent power reading, in percent.
+    pub fn battery_percent(&self) -> u8 {
+        self.battery_percent.load(Ordering::Acquire)
+    }
+
     /// `true` if the AI is currently suspended due to a manual override.
     pub fn is_override_active(&self) -> bool {
         self.override_active.load(Ordering::Acquire)
     }
 
+    // -------------------------------------------------------------------------
+    // E-stop / safety-mode API
+    // -------------------------------------------------------------------------
+
+    /// Apply an operator [`SafetyCommand`] and confirm the resulting state
+    /// on the bus as an [`EventPayload::SafetyStatus`] echoing `request_id`.
+    ///
+    /// Engaging the e-stop also halts the robot; repeating it is harmless.
+    /// A mode change while latched is refused and the unchanged state is
+    /// confirmed.
+    pub fn apply_safety_command(&mut self, command: SafetyCommand, request_id: Option<String>) {
+        if let Err(e) = self.safety.apply(command) {
+            warn!(error = %e, "safety command refused");
+        }
+        if command == SafetyCommand::EStop {
+            self.halt("emergency stop");
+        }
+        let event = Event {
+            id: Uuid::new_v4(),
+            timestamp: chrono::Utc::now(),
+            source: format!("mechos-runtime::agent_loop/{}", self.agent_id),
+            payload: EventPayload::SafetyStatus {
+                request_id,
+                estopped: self.safety.is_estopped(),
+                mode: self.safety.mode(),
+            },
+            trace_id: None,
+            correlation_id: None,
+        };
+        let _ = self.bus.publish(event);
+    }
+
+    /// `true` while the operator e-stop is latched.
+    pub fn is_estopped(&self) -> bool {
+        self.safety.is_estopped()
+    }
+
+    /// The operator-selected safety mode.
+    pub fn safety_mode(&self) -> SafetyMode {
+        self.safety.mode()
+    }
+
+    // -------------------------------------------------------------------------
+    // Runtime safety policy API
+    // -------------------------------------------------------------------------
+
+    /// Apply `operator`'s [`PolicyCommand`] to the gate and answer on the bus
+    /// with an [`EventPayload::PolicyStatus`] echoing `request_id`.  A refused
+    /// change is reported in the status's `error`.
+    pub fn apply_policy_command(
+        &mut self,
+        request_id: &str,
+        operator: &str,
+        command: PolicyCommand,
+    ) {
+        let error = self
+            .gate
+            .apply_policy(operator, command)
+            .err()
+            .map(|e| e.to_string());
+        let event = Event {
+            id: Uuid::new_v4(),
+            timestamp: chrono::Utc::now(),
+            source: format!("mechos-runtime::agent_loop/{}", self.agent_id),
+            payload: EventPayload::PolicyStatus {
+                request_id: request_id.to_string(),
+                report: Box::new(self.gate.policy_report()),
+                error,
+            },
+            trace_id: None,
+            correlation_id: None,
+        };
+        let _ = self.bus.publish(event);
+    }
+
+    /// The gate's rules, capability grants and policy audit log.
+    pub fn policy_report(&self) -> PolicyReport {
+        self.gate.policy_report()
+    }
+
+    // -------------------------------------------------------------------------
+    // Operator approval API
+    // -------------------------------------------------------------------------
+
+    /// Hold every gate-approved intent `policy` returns `true` for until an
+    /// operator approves it.  `Halt` is never held.
+    pub fn set_approval_policy(
+        &mut self,
+        policy: impl Fn(&HardwareIntent) -> bool + Send + Sync + 'static,
+    ) {
+        self.gate.set_approval_policy(policy);
+    }
+
+    /// Intents awaiting an operator's decision, oldest first.
+    pub fn pending_approvals(&self) -> &[PendingApproval] {
+        self.gate.pending_approvals()
+    }
+
+    /// Apply `operator`'s decision on the intent held under `request_id`.
+    ///
+    /// An approved intent that still passes the [`KernelGate`] and the
+    /// trajectory check is dispatched; one that no longer does is dropped
+    /// with a warning, as is a rejected one.  Unknown IDs (another robot's
+    /// approvals, or a repeated decision) are ignored.
+    pub fn decide_approval(&mut self, request_id: &str, approved: bool, operator: &str) {
+        let envelope = match self.gate.decide(request_id, approved) {
+            Ok(Some(envelope)) => envelope,
+            Ok(None) => {
+                if !approved {
+                    info!(request_id, operator, "intent rejected by operator");
+                }
+                return;
+            }
+            Err(e) => {
+                warn!(request_id, operator, error = %e, "approved intent no longer passes the kernel gate");
+                return;
+            }
+        };
+        info!(request_id, operator, "intent approved by operator");
+        match self.check_trajectory(envelope.intent) {
+            Ok(mut intent) => {
+                self.correlation_id = envelope.correlation_id;
+                self.act(&mut intent);
+            }
+            Err(e) => warn!(request_id, error = %e, "approved intent failed the trajectory check"),
+        }
+    }
+
+    /// `true` when `operator` may act with `capability`: always without
+    /// [`AgentLoopConfig::operator_grants`], otherwise only for an operator
+    /// holding it.
+    fn operator_may(&self, operator: Option<&str>, capability: Capability) -> bool {
+        if !self.authorize_operators {
+            return true;
+        }
+        let grants = self.gate.capability_manager();
+        match operator.filter(|operator| grants.holds(operator, &capability)) {
+            Some(operator) => {
+                grants.record_use(operator, &capability);
+                true
+            }
+            None => {
+                warn!(operator, capability = ?capability, "operator is not authorized; ignoring");
+                false
+            }
+        }
+    }
+
+    /// Hold a gate-bound envelope for approval and publish the
+    /// [`EventPayload::ApprovalRequest`].
+    fn request_approval(&mut self, envelope: IntentEnvelope) -> Result<(), MechError> {
+        let pending = self.gate.hold_for_approval(envelope)?;
+        let event = Event {
+            id: Uuid::new_v4(),
+            timestamp: chrono::Utc::now(),
+            source: format!("mechos-runtime::agent_loop/{}", self.agent_id),
+            payload: EventPayload::ApprovalRequest {
+                request_id: pending.request_id.clone(),
+                envelope: Box::new(pending.envelope.clone()),
+                report: pending.report.clone(),
+            },
+            trace_id: None,
+            correlation_id: Some(pending.envelope.correlation_id),
+        };
+        let _ = self.bus.publish(event);
+        Ok(())
+    }
+
     // -------------------------------------------------------------------------
     // Cockpit pause/resume API
     // -------------------------------------------------------------------------
@@ -342,24 +1416,337 @@ impl AgentLoop {
     /// - The Cockpit operator has paused the loop via the mode-toggle.
     /// - A manual override is active (AI suspended for up to 10 s).
     /// - The loop is waiting for a human response to an `AskHuman` intent.
+    /// - A behavior-tree skill is still running.  This is not a fault; the
+    ///   tick has nothing to dispatch until the tree succeeds or fails.
+    /// - An intent is held for, or waiting for, operator approval.
     /// - The LLM response cannot be parsed as a [`HardwareIntent`].
     /// - The [`KernelGate`] rejects the intent.
     /// - The [`LoopGuard`] detects a repetitive hallucination loop.
-    #[instrument(name = "agent_loop.tick", skip(self), fields(dt = dt))]
+    #[instrument(name = "agent_loop.tick", skip(self), fields(dt = dt, agent_id = %self.agent_id))]
     pub async fn tick(&mut self, dt: f32) -> Result<HardwareIntent, MechError> {
-        // ── Drain pending bus events ───────────────────────────────────────────
-        // Pick up any human responses or override notifications that arrived
-        // between ticks without blocking.
-        self.drain_bus_events();
+        let started = Instant::now();
+        let result = self.tick_inner(dt).await;
+        RuntimeMetrics::global().record_tick(started.elapsed());
+        result
+    }
 
-        // ── Cockpit pause guard ────────────────────────────────────────────────
-        if self.paused {
+    async fn tick_inner(&mut self, dt: f32) -> Result<HardwareIntent, MechError> {
+        // Renew first: a paused or overridden loop is still alive.
+        self.renew_active_task().await;
+        let intent = self.propose(dt).await?;
+
+        // ── 4. Gatekeep ───────────────────────────────────────────────────────
+        let mut envelope = self.envelope(intent);
+        if self.gate.requires_approval(&envelope.intent) {
+            let correlation_id = envelope.correlation_id;
+            let verdict = self.request_approval(envelope);
+            RuntimeMetrics::global().record_gate_decision(&self.agent_id, verdict.is_ok());
+            verdict?;
             return Err(MechError::HardwareFault {
-                component: "agent_loop".to_string(),
+                code: FaultCode::ApprovalPending,
+                component: "kernel_gate".to_string(),
+                details: format!("intent {correlation_id} held for operator approval"),
+            });
+        }
+        {
+            let _span = tracing::info_span!("ooda.gatekeep").entered();
+            let verdict = self.gate.authorize_envelope(&mut envelope);
+            RuntimeMetrics::global().record_gate_decision(&self.agent_id, verdict.is_ok());
+            verdict?;
+        }
+
+        // ── 5. Simulate ───────────────────────────────────────────────────────
+        let mut intent = self.check_trajectory(envelope.intent)?;
+
+        self.act(&mut intent);
+        Ok(intent)
+    }
+
+    /// Render the system prompt for the current tick from [`Self::prompt`].
+    fn render_system_prompt(
+        &self,
+        state: &FusedState,
+        path_clear: bool,
+        memories: String,
+        procedures: String,
+    ) -> String {
+        let capabilities = self
+            .capabilities
+            .iter()
+            .map(|c| format!("- {c:?}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let skills = if self.skills.is_empty() {
+            String::new()
+        } else {
+            self.skills.prompt_section()
+        };
+        let vars = HashMap::from([
+            ("role", self.role_prompt.clone().unwrap_or_default()),
+            ("goal", self.goal.clone().unwrap_or_default()),
+            ("capabilities", capabilities),
+            ("position", format!("x={:.3}, y={:.3}", state.position_x, state.position_y)),
+            ("heading", format!("{:.3}", state.heading_rad)),
+            ("velocity", format!("vx={:.3}, vy={:.3}", state.velocity_x, state.velocity_y)),
+            ("path", if path_clear { "CLEAR" } else { "BLOCKED" }.to_string()),
+            ("nearest_obstacle", self.describe_nearest_obstacle(state)),
+            ("object_locations", self.describe_object_locations()),
+            ("procedures", procedures),
+            ("memories", memories),
+            ("skills", skills),
+        ]);
+        self.prompt.render(&vars)
+    }
+
+    /// Distance and bearing (degrees, counter-clockwise from the heading) of
+    /// the closest obstacle, e.g. `"0.80 m at bearing 45°"`, followed by its
+    /// class and the fused confidence in it when the point is labelled, e.g.
+    /// `"0.80 m at bearing 45° (person, 85% confident)"`; empty when the
+    /// octree is empty.
+    fn describe_nearest_obstacle(&self, state: &FusedState) -> String {
+        let robot = Point3::new(state.position_x, state.position_y, 0.0);
+        let Some((point, distance)) = self.octree.nearest(robot) else {
+            return String::new();
+        };
+        // Wrap into (-180°, 180°].
+        let (sin, cos) =
+            ((point.y - robot.y).atan2(point.x - robot.x) - state.heading_rad).sin_cos();
+        let bearing_deg = sin.atan2(cos).to_degrees();
+        let description = format!("{distance:.2} m at bearing {bearing_deg:.0}°");
+        match self.octree.label_at(point) {
+            Some(label) => {
+                let confidence = self
+                    .semantic
+                    .query(&label.class)
+                    .map_or(label.confidence, |state| state.confidence);
+                format!(
+                    "{description} ({}, {:.0}% confident)",
+                    label.class,
+                    confidence * 100.0
+                )
+            }
+            None => description,
+        }
+    }
+
+    /// The most likely location of up to [`PROMPT_OBJECT_LOCATIONS`]
+    /// objects, one `- <object>: <location> (<p>%, seen <n> s ago)` line
+    /// each, most confident first; empty when no location is known.
+    fn describe_object_locations(&self) -> String {
+        let now = chrono::Utc::now();
+        self.semantic
+            .most_likely_locations()
+            .into_iter()
+            .take(PROMPT_OBJECT_LOCATIONS)
+            .map(|(object, belief)| {
+                format!(
+                    "- {object}: {} ({:.0}%, seen {} s ago)",
+                    belief.location,
+                    belief.probability * 100.0,
+                    (now - belief.last_seen).num_seconds().max(0)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// `true` when the robot can drive from its current pose straight to
+    /// `(x, y)` without its footprint touching a known obstacle.
+    ///
+    /// Lets callers check a goal before handing it to the LLM as a
+    /// destination; [`propose`][Self::propose] plans around obstacles
+    /// anyway.
+    pub fn path_feasible(&self, x: f32, y: f32) -> bool {
+        let state = self.fusion.fused_state(0.0);
+        planner::path_feasible(
+            &self.octree,
+            (state.position_x, state.position_y),
+            &[(x, y)],
+            self.robot_radius,
+        )
+    }
+
+    /// Re-express a `MoveEndEffector` target given in another frame (e.g.
+    /// `map`) in [`BASE_FRAME`], using the latest recorded pose, so the
+    /// kernel checks and the adapters receive robot-relative coordinates.
+    /// Other intents pass through unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::Parsing`] when the target's frame is not
+    /// connected to [`BASE_FRAME`].
+    fn resolve_frame(&self, intent: HardwareIntent) -> Result<HardwareIntent, MechError> {
+        let HardwareIntent::MoveEndEffector {
+            x,
+            y,
+            z,
+            frame_id: Some(frame),
+        } = &intent
+        else {
+            return Ok(intent);
+        };
+        if frame == BASE_FRAME {
+            return Ok(intent);
+        }
+        let target = self
+            .tf
+            .transform_point(Vec3::new(x.get(), y.get(), z.get()), frame, BASE_FRAME)
+            .ok_or_else(|| {
+                MechError::Parsing(format!(
+                    "MoveEndEffector target in unknown frame \"{frame}\""
+                ))
+            })?;
+        Ok(HardwareIntent::MoveEndEffector {
+            x: Meters(target.x),
+            y: Meters(target.y),
+            z: Meters(target.z),
+            frame_id: Some(BASE_FRAME.to_string()),
+        })
+    }
+
+    /// Expand each leg of a `FollowWaypoints` intent into a collision-free
+    /// path planned with A* on the occupancy grid of the collision octree.
+    ///
+    /// Legs that are already clear are kept as they are, as are legs with an
+    /// end outside the mapped area.  Other intents pass through unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::HardwareFault`] from component `"path_planner"`
+    /// when no collision-free path reaches one of the waypoints.
+    fn plan_waypoints(&self, intent: HardwareIntent) -> Result<HardwareIntent, MechError> {
+        let HardwareIntent::FollowWaypoints { points, max_speed } = intent else {
+            return Ok(intent);
+        };
+        let _span = tracing::info_span!("ooda.plan").entered();
+        let state = self.fusion.fused_state(0.0);
+        let mut from: Waypoint = (state.position_x, state.position_y);
+        let mut grid = None;
+        let mut planned = Vec::with_capacity(points.len());
+        for &(x, y) in &points {
+            let to = (x.get(), y.get());
+            let leg = if planner::path_feasible(&self.octree, from, &[to], self.robot_radius) {
+                Some(vec![to])
+            } else {
+                let grid = grid.get_or_insert_with(|| {
+                    OccupancyGrid::from_octree(&self.octree, PLANNER_RESOLUTION_M)
+                });
+                match (grid.cell_index(from.0, from.1), grid.cell_index(to.0, to.1)) {
+                    (Some(_), Some(_)) => AStarPlanner::new(self.robot_radius).plan(grid, from, to),
+                    _ => Some(vec![to]),
+                }
+            };
+            let Some(leg) = leg else {
+                warn!(x = to.0, y = to.1, "path planner: waypoint unreachable");
+                return Err(MechError::HardwareFault {
+                    code: FaultCode::CollisionPredicted,
+                    component: "path_planner".to_string(),
+                    details: format!(
+                        "no collision-free path to waypoint ({:.2}, {:.2})",
+                        to.0, to.1
+                    ),
+                });
+            };
+            planned.extend(leg.into_iter().map(|(x, y)| (Meters(x), Meters(y))));
+            from = to;
+        }
+        Ok(HardwareIntent::FollowWaypoints {
+            points: planned,
+            max_speed,
+        })
+    }
+
+    /// Forward-simulate an approved `Drive` intent against the collision
+    /// octree.
+    ///
+    /// Returns the intent unchanged when the sweep is clear (or the intent is
+    /// not a `Drive`).  When a collision is predicted part-way through the
+    /// horizon both velocities are scaled down by the same factor, which keeps
+    /// the arc shape but stops the robot short of the obstacle within the
+    /// horizon.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MechError::HardwareFault`] from component
+    /// `"trajectory_check"` if a collision is predicted before the first
+    /// integration step completes.
+    pub(crate) fn check_trajectory(
+        &self,
+        intent: HardwareIntent,
+    ) -> Result<HardwareIntent, MechError> {
+        let (Some(predictor), HardwareIntent::Drive { linear_velocity, angular_velocity }) =
+            (&self.trajectory, &intent)
+        else {
+            return Ok(intent);
+        };
+        // Rotating in place does not move the footprint.
+        if *linear_velocity == MetersPerSecond::ZERO {
+            return Ok(intent);
+        }
+        let _span = tracing::info_span!("ooda.simulate").entered();
+        let state = self.fusion.fused_state(0.0);
+        let Some(hit_s) = predictor.first_collision(
+            &state,
+            linear_velocity.get(),
+            angular_velocity.get(),
+            &self.octree,
+        ) else {
+            return Ok(intent);
+        };
+
+        let safe_s = hit_s - predictor.step_s;
+        if safe_s <= 0.0 {
+            warn!(hit_s, "trajectory check: imminent collision; Drive rejected");
+            return Err(MechError::HardwareFault {
+                code: FaultCode::CollisionPredicted,
+                component: "trajectory_check".to_string(),
+                details: format!("collision predicted in {hit_s:.2} s; Drive rejected"),
+            });
+        }
+        let scale = safe_s / predictor.horizon_s;
+        warn!(hit_s, scale, "trajectory check: collision predicted; Drive shortened");
+        Ok(HardwareIntent::Drive {
+            linear_velocity: *linear_velocity * scale,
+            angular_velocity: *angular_velocity * scale,
+        })
+    }
+
+    /// Run the Observe–Orient–Decide half of the cycle and return the parsed
+    /// (but **not yet gated**) intent.
+    ///
+    /// Used by [`tick`][Self::tick] and by the
+    /// [`AgentSupervisor`][crate::supervisor::AgentSupervisor], which gates
+    /// the proposals of all its agents through a single shared [`KernelGate`].
+    pub(crate) async fn propose(&mut self, dt: f32) -> Result<HardwareIntent, MechError> {
+        self.correlation_id = Uuid::new_v4();
+
+        // ── Drain pending bus events ───────────────────────────────────────────
+        // Pick up any human responses or override notifications that arrived
+        // between ticks without blocking.
+        self.drain_bus_events();
+        self.sync_map();
+        self.publish_map_snapshot();
+        self.refresh_localization();
+        self.decay_obstacles();
+
+        // ── Cockpit pause guard ────────────────────────────────────────────────
+        if self.paused {
+            return Err(MechError::HardwareFault {
+                code: FaultCode::AgentPaused,
+                component: "agent_loop".to_string(),
                 details: "agent loop paused by operator".to_string(),
             });
         }
 
+        // ── E-stop guard ───────────────────────────────────────────────────────
+        if self.safety.is_estopped() {
+            return Err(MechError::HardwareFault {
+                code: FaultCode::EmergencyStop,
+                component: "agent_loop".to_string(),
+                details: "e-stop latched; reset to resume".to_string(),
+            });
+        }
+
         // ── Manual override guard ──────────────────────────────────────────────
         if self.override_active.load(Ordering::Acquire)
             && let Some(last) = self.override_last_seen {
@@ -369,23 +1756,33 @@ impl AgentLoop {
                     self.override_last_seen = None;
                 } else {
                     return Err(MechError::HardwareFault {
+                        code: FaultCode::OverrideActive,
                         component: "agent_loop".to_string(),
                         details: "manual override active; AI suspended".to_string(),
                     });
                 }
             }
 
+        // ── Operator approval guard ────────────────────────────────────────────
+        if !self.gate.pending_approvals().is_empty() {
+            return Err(MechError::HardwareFault {
+                code: FaultCode::ApprovalPending,
+                component: "agent_loop".to_string(),
+                details: "waiting for operator approval".to_string(),
+            });
+        }
+
         // ── HITL: waiting for human response ───────────────────────────────────
-        // If the last LLM turn produced an AskHuman intent and no response has
-        // arrived yet, pause the loop.
-        let extra_user_message: Option<ChatMessage> = if self.waiting_for_human {
-            match self.pending_human_response.take() {
-                Some(response) => {
-                    self.waiting_for_human = false;
-                    Some(ChatMessage {
-                        role: Role::User,
-                        content: response,
-                    })
+        // While AskHuman questions are open and no answer has arrived, pause
+        // the loop until one of them times out.
+        if self.is_waiting_for_human() && self.answered_questions.is_empty() {
+            match self.expire_question().await {
+                Some(HitlFallback::DefaultAnswer(_)) => {}
+                Some(HitlFallback::ReturnToDock) => return Ok(HardwareIntent::Dock),
+                Some(HitlFallback::Hold) => {
+                    return Ok(HardwareIntent::Halt {
+                        reason: "no human response; holding position".to_string(),
+                    });
                 }
                 None => {
                     return Err(MechError::LlmInferenceFailed(
@@ -393,9 +1790,21 @@ impl AgentLoop {
                     ));
                 }
             }
-        } else {
-            None
-        };
+        }
+        let answers: Vec<ChatMessage> = self
+            .answered_questions
+            .drain(..)
+            .map(|(question, answer)| ChatMessage {
+                role: Role::User,
+                content: format!("Answer to your question \"{question}\": {answer}"),
+            })
+            .collect();
+
+        // ── Skill continuation ────────────────────────────────────────────────
+        // An in-progress skill takes precedence over a fresh LLM decision.
+        if let Some(status) = self.step_skill() {
+            return status?.into_intent();
+        }
 
         // ── 1. Observe ────────────────────────────────────────────────────────
         let state: FusedState = {
@@ -408,7 +1817,10 @@ impl AgentLoop {
             Point3::new(state.position_x - 0.5, state.position_y - 0.5, -0.5),
             Point3::new(state.position_x + 0.5, state.position_y + 0.5, 0.5),
         );
-        let path_clear = !self.octree.query_aabb(&probe);
+        let path_clear = {
+            let _span = tracing::info_span!("ooda.probe").entered();
+            !self.octree.query_aabb(&probe)
+        };
 
         // ── 2. Orient ─────────────────────────────────────────────────────────
         // Retrieve the most recent episodic memories as context.
@@ -428,23 +1840,10 @@ impl AgentLoop {
             }
         };
 
-        let system_prompt = format!(
-            "You are the cognitive brain of a physical robot.\n\
-             Output ONLY a single valid JSON object matching the HardwareIntent schema.\n\
-             ## System State\n\
-             Position: x={:.3}, y={:.3}\n\
-             Heading:  {:.3} rad\n\
-             Velocity: vx={:.3}, vy={:.3}\n\
-             Path: {}\n\
-             ## Recent Memories\n{}\n",
-            state.position_x,
-            state.position_y,
-            state.heading_rad,
-            state.velocity_x,
-            state.velocity_y,
-            if path_clear { "CLEAR" } else { "BLOCKED" },
-            memory_context,
-        );
+        let procedures = self.describe_procedures().await;
+
+        let system_prompt =
+            self.render_system_prompt(&state, path_clear, memory_context, procedures);
 
         let mut messages = vec![
             ChatMessage {
@@ -456,30 +1855,39 @@ impl AgentLoop {
                 content: "What is your next action? Reply with a single HardwareIntent JSON object.".to_string(),
             },
         ];
-        // If a human response was pending, inject it as the next user turn so
-        // the LLM has the operator's answer in its context window.
-        if let Some(human_msg) = extra_user_message {
-            messages.push(human_msg);
-        }
+        // Inject answers to earlier questions as the next user turns so the
+        // LLM has the operator's answers in its context window.
+        messages.extend(answers);
 
         // ── 3. Decide ─────────────────────────────────────────────────────────
         let raw = {
             let _span = tracing::info_span!("ooda.decide").entered();
-            self.llm.complete(&messages).await.map_err(|e| {
-                MechError::LlmInferenceFailed(e.to_string())
-            })?
+            if self.deliberation_samples > 1 {
+                self.deliberate(&messages).await?
+            } else {
+                self.complete_once(&messages).await?
+            }
         };
 
         // Hash the raw response and check for repetitive loops.
         let hash = Self::hash_str(&raw);
         if self.loop_guard.record(&hash.to_string()) {
+            RuntimeMetrics::global().record_loop_guard_trip();
             warn!("LoopGuard: repetitive LLM output detected; human intervention required");
+            self.halt("LoopGuard: repetitive LLM output detected");
             return Err(MechError::LlmInferenceFailed(
                 "LoopGuard: repetitive LLM output detected; human intervention required"
                     .to_string(),
             ));
         }
 
+        // A skill invocation replaces the raw intent.
+        if !self.skills.is_empty()
+            && let Ok(call) = serde_json::from_str::<SkillCall>(&raw)
+        {
+            return self.start_skill(call);
+        }
+
         // Parse the JSON response into a HardwareIntent.
         let intent: HardwareIntent =
             serde_json::from_str(&raw).map_err(|e| {
@@ -488,122 +1896,594 @@ impl AgentLoop {
 
         debug!(intent = ?intent, "LLM decided intent");
 
-        // ── 4. Gatekeep ───────────────────────────────────────────────────────
-        {
-            let _span = tracing::info_span!("ooda.gatekeep").entered();
-            self.gate.authorize_and_verify("agent", &intent)?;
+        let intent = self.resolve_frame(intent)?;
+        self.plan_waypoints(intent)
+    }
+
+    /// Wrap `intent` in an [`IntentEnvelope`] issued by this loop and
+    /// stamped with the current tick's correlation ID.  `Drive` intents get a
+    /// deadline of [`AgentLoopConfig::intent_ttl_ms`] from now.
+    fn envelope(&self, intent: HardwareIntent) -> IntentEnvelope {
+        let deadline = match (&intent, self.intent_ttl) {
+            (HardwareIntent::Drive { .. }, Some(ttl)) => Some(chrono::Utc::now() + ttl),
+            _ => None,
+        };
+        let envelope = IntentEnvelope::new(intent, &self.agent_id)
+            .with_correlation_id(self.correlation_id);
+        match deadline {
+            Some(deadline) => envelope.with_deadline(deadline),
+            None => envelope,
         }
+    }
+
+    /// Publish an approved intent to the bus as an [`EventPayload::Intent`]
+    /// envelope and perform HITL bookkeeping.  An `AskHuman` without a
+    /// `question_id` is given a fresh one first.
+    ///
+    /// The caller is responsible for having passed `intent` through a
+    /// [`KernelGate`] first.
+    pub(crate) fn act(&mut self, intent: &mut HardwareIntent) {
+        if let HardwareIntent::AskHuman { question_id, .. } = intent {
+            question_id.get_or_insert_with(|| Uuid::new_v4().to_string());
+        }
+        let intent = &*intent;
 
         // ── 5. Act ────────────────────────────────────────────────────────────
-        info!(intent = ?intent, "dispatching approved intent");
+        info!(intent = ?intent, correlation_id = %self.correlation_id, "dispatching approved intent");
         {
             let _span = tracing::info_span!("ooda.act", intent = ?intent).entered();
             let event = Event {
                 id: Uuid::new_v4(),
                 timestamp: chrono::Utc::now(),
-                source: "mechos-runtime::agent_loop".to_string(),
-                payload: EventPayload::AgentThought(
-                    serde_json::to_string(&intent)
-                        .unwrap_or_else(|_| "(serialisation error)".to_string()),
-                ),
+                source: format!("mechos-runtime::agent_loop/{}", self.agent_id),
+                payload: EventPayload::Intent(self.envelope(intent.clone())),
                 trace_id: None,
+                correlation_id: None,
             };
             // Best-effort publish – no subscribers is not an error.
             let _ = self.bus.publish(event);
         }
 
-        // ── 6. HITL bookkeeping ───────────────────────────────────────────────
-        // If the LLM asked for human guidance, park the loop until a response
-        // arrives via `submit_human_response` or a bus `HumanResponse` event.
-        if matches!(intent, HardwareIntent::AskHuman { .. }) {
-            self.waiting_for_human = true;
+        // Remember the step towards the current goal, for procedural memory.
+        if self.goal.is_some() && !matches!(intent, HardwareIntent::Halt { .. }) {
+            if self.goal_trace.len() == MAX_PROCEDURE_STEPS {
+                self.goal_trace.pop_front();
+            }
+            self.goal_trace.push_back(intent.clone());
         }
 
-        Ok(intent)
+        // ── 6. HITL bookkeeping ───────────────────────────────────────────────
+        // If the LLM asked for human guidance, park the loop until an answer
+        // arrives via `submit_human_answer` or a bus `HumanResponse` event.
+        if let HardwareIntent::AskHuman {
+            question,
+            question_id: Some(id),
+            ..
+        } = intent
+        {
+            self.open_questions.push(OpenQuestion {
+                id: id.clone(),
+                question: question.clone(),
+                asked: Instant::now(),
+                timed_out: false,
+            });
+        }
     }
 
     // -------------------------------------------------------------------------
     // Private helpers
     // -------------------------------------------------------------------------
 
+    /// If an open `AskHuman` question has waited past the HITL timeout,
+    /// record it to episodic memory and return the fallback to apply.
+    ///
+    /// `Hold` leaves the question open for a late answer; the other
+    /// fallbacks close it, `DefaultAnswer` by answering it.  Each question
+    /// falls back at most once.
+    async fn expire_question(&mut self) -> Option<HitlFallback> {
+        let timeout = self.hitl_timeout?;
+        let index = self
+            .open_questions
+            .iter()
+            .position(|q| !q.timed_out && q.asked.elapsed() >= timeout)?;
+        let fallback = self.hitl_fallback.clone();
+        let question = match &fallback {
+            HitlFallback::Hold => {
+                self.open_questions[index].timed_out = true;
+                self.open_questions[index].question.clone()
+            }
+            HitlFallback::ReturnToDock => self.open_questions.remove(index).question,
+            HitlFallback::DefaultAnswer(answer) => {
+                let question = self.open_questions.remove(index).question;
+                self.answered_questions
+                    .push((question.clone(), answer.clone()));
+                question
+            }
+        };
+        warn!(
+            agent_id = %self.agent_id,
+            question = %question,
+            fallback = %fallback,
+            "no human response before the HITL timeout"
+        );
+        let summary = format!(
+            "Nobody answered \"{question}\" within {} s; fell back to: {fallback}",
+            timeout.as_secs()
+        );
+        if let Err(e) = self.memory.store_text("hitl", summary).await {
+            warn!(error = %e, "failed to record the unanswered question");
+        }
+        Some(fallback)
+    }
+
+    /// Request a single completion, constrained to the skill-aware schema
+    /// when skills are registered.
+    async fn complete_once(&self, messages: &[ChatMessage]) -> Result<String, MechError> {
+        let started = Instant::now();
+        let completion = if self.skills.is_empty() {
+            self.llm.complete(messages).await
+        } else {
+            self.llm
+                .complete_with_schema(messages, SkillRegistry::response_schema())
+                .await
+        };
+        let completion = completion.map_err(|e| MechError::LlmInferenceFailed(e.to_string()));
+        self.record_transcript(messages, &completion, started.elapsed()).await;
+        completion
+    }
+
+    /// Append one prompt/response pair to the transcript, if enabled.
+    /// Recording failures are logged and never fail the tick.
+    async fn record_transcript(
+        &self,
+        messages: &[ChatMessage],
+        completion: &Result<String, MechError>,
+        latency: Duration,
+    ) {
+        let Some(store) = &self.transcript else {
+            return;
+        };
+        let prompt = serde_json::to_string(messages).unwrap_or_default();
+        let mut entry = TranscriptEntry::new(&self.mission_id, &self.agent_id, &prompt, "");
+        entry.timestamp -= chrono::Duration::from_std(latency).unwrap_or_default();
+        entry.trace_id = EventBus::current_trace_id();
+        entry.latency_ms = latency.as_millis() as u64;
+        match completion {
+            Ok(reply) => entry.response = reply.clone(),
+            Err(e) => entry.error = Some(e.to_string()),
+        }
+        if let Err(e) = store.record(&entry).await {
+            warn!(error = %e, "failed to record LLM transcript entry");
+        }
+    }
+
+    /// Sample [`deliberation_samples`][AgentLoopConfig::deliberation_samples]
+    /// completions and return the canonical JSON of the winning candidate.
+    async fn deliberate(&self, messages: &[ChatMessage]) -> Result<String, MechError> {
+        let mut replies = Vec::with_capacity(self.deliberation_samples);
+        let mut last_err = None;
+        for _ in 0..self.deliberation_samples {
+            match self.complete_once(messages).await {
+                Ok(reply) => replies.push(reply),
+                Err(e) => last_err = Some(e),
+            }
+        }
+        debug!(
+            samples = self.deliberation_samples,
+            received = replies.len(),
+            "deliberation samples collected"
+        );
+        self.vote(&replies).ok_or_else(|| {
+            MechError::LlmInferenceFailed(match last_err {
+                Some(e) => format!("deliberation: no valid candidate ({e})"),
+                None => format!(
+                    "deliberation: no valid candidate among {} samples",
+                    self.deliberation_samples
+                ),
+            })
+        })
+    }
+
+    /// Majority vote over raw LLM replies.
+    ///
+    /// Each reply is normalised to canonical JSON; replies that parse neither
+    /// as a [`SkillCall`] nor as a gate-approved [`HardwareIntent`] are
+    /// discarded.  Returns `None` if no candidate survives.
+    fn vote(&self, replies: &[String]) -> Option<String> {
+        // (canonical JSON, votes) in order of first appearance.
+        let mut tally: Vec<(String, usize)> = Vec::new();
+        for raw in replies {
+            let canonical = if !self.skills.is_empty()
+                && let Ok(call) = serde_json::from_str::<SkillCall>(raw)
+            {
+                serde_json::to_value(&call).ok()
+            } else if let Ok(mut intent) = serde_json::from_str::<HardwareIntent>(raw) {
+                if self.gate.preview(&self.agent_id, &mut intent).is_err() {
+                    continue;
+                }
+                serde_json::to_value(&intent).ok()
+            } else {
+                None
+            };
+            let Some(key) = canonical.map(|v| v.to_string()) else {
+                continue;
+            };
+            match tally.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, votes)) => *votes += 1,
+                None => tally.push((key, 1)),
+            }
+        }
+        // `max_by_key` returns the last maximum; iterate in reverse so ties
+        // resolve to the earliest sample.
+        tally
+            .into_iter()
+            .rev()
+            .max_by_key(|(_, votes)| *votes)
+            .map(|(key, _)| key)
+    }
+
+    /// Expand `call` and run its first step.
+    fn start_skill(&mut self, call: SkillCall) -> Result<HardwareIntent, MechError> {
+        let expansion = self
+            .skills
+            .expand(&call)
+            .map_err(|e| MechError::LlmInferenceFailed(format!("skill error: {e}")))?;
+        info!(skill = %call.skill, "LLM invoked skill");
+        match expansion {
+            SkillExpansion::Intents(intents) => self.skill_queue = intents.into(),
+            SkillExpansion::Tree(tree) => self.active_skill_tree = Some((call.skill.clone(), tree)),
+        }
+        self.step_skill()
+            .unwrap_or_else(|| {
+                Err(MechError::HardwareFault {
+                    code: FaultCode::SkillFailed,
+                    component: "agent_loop".to_string(),
+                    details: format!("skill '{}' completed without emitting an intent", call.skill),
+                })
+            })
+            .and_then(SkillStatus::into_intent)
+    }
+
+    /// Advance the in-progress skill by one step.
+    ///
+    /// Returns `None` when no skill is active (or a behavior-tree skill just
+    /// succeeded), meaning the tick should fall through to the LLM.  A tree
+    /// that is still running is [`SkillStatus::Running`]; only a failed one
+    /// is a [`FaultCode::SkillFailed`] fault.
+    fn step_skill(&mut self) -> Option<Result<SkillStatus, MechError>> {
+        if let Some(intent) = self.skill_queue.pop_front() {
+            return Some(Ok(SkillStatus::Intent(intent)));
+        }
+        let (name, tree) = self.active_skill_tree.as_ref()?;
+        match tree.tick() {
+            NodeStatus::Success => {
+                self.active_skill_tree = None;
+                None
+            }
+            NodeStatus::Running => Some(Ok(SkillStatus::Running(name.clone()))),
+            NodeStatus::Failure => {
+                let details = format!("skill '{name}' failed");
+                self.active_skill_tree = None;
+                Some(Err(MechError::HardwareFault {
+                    code: FaultCode::SkillFailed,
+                    component: "agent_loop".to_string(),
+                    details,
+                }))
+            }
+        }
+    }
+
     /// Non-blocking drain of pending bus events.
     ///
     /// Processes every event that is already waiting in the broadcast buffer:
     ///
     /// * [`EventPayload::HumanResponse`] – stores the response so the next
-    ///   tick can inject it into the LLM context.
+    ///   tick can inject it into the LLM context.  Like approval decisions,
+    ///   it is ignored from an operator without the grant when
+    ///   [`AgentLoopConfig::operator_grants`] is set.
     /// * `source: "mechos-middleware::dashboard_override"` – extracts the
-    ///   Twist velocities and arms the manual-override interlock.
+    ///   Twist velocities and arms the manual-override interlock, or with
+    ///   `"release": true` stops the robot and lifts the interlock.
     /// * [`EventPayload::AgentModeToggle`] – sets or clears the Cockpit
     ///   pause flag.
+    /// * [`EventPayload::ApprovalDecision`] – releases or drops an intent
+    ///   held for operator approval.
+    /// * [`EventPayload::PolicyRequest`] – changes the gate's safety policy
+    ///   and answers with an [`EventPayload::PolicyStatus`].
+    /// * [`EventPayload::LidarScan`] – projected into the map frame with the
+    ///   pose at the event's timestamp; each beam clears the octree along its
+    ///   path and inserts its return.  The returns also feed the obstacle
+    ///   tracker.
+    /// * [`EventPayload::GpsFix`] – converted into the local map frame and
+    ///   fed to sensor fusion.
+    /// * [`EventPayload::PeerMessage`] – merged into the collision octree
+    ///   when it is a map update and a [`MapSync`] is installed.
     fn drain_bus_events(&mut self) {
         loop {
             match self.bus_rx.try_recv() {
                 Ok(event) => {
                     match &event.payload {
-                        EventPayload::HumanResponse(response) => {
-                            self.pending_human_response = Some(response.clone());
-                            self.waiting_for_human = false;
+                        EventPayload::HumanResponse(answer)
+                            if self.operator_may(answer.operator.as_deref(), Capability::AnswerQuestions) =>
+                        {
+                            self.submit_human_answer(answer);
                         }
                         EventPayload::AgentModeToggle { paused } => {
                             self.paused = *paused;
                         }
+                        EventPayload::SafetyRequest {
+                            request_id,
+                            command,
+                        } => {
+                            self.apply_safety_command(*command, Some(request_id.clone()));
+                        }
+                        EventPayload::ApprovalDecision {
+                            request_id,
+                            approved,
+                            operator,
+                            ..
+                        } if self.operator_may(Some(operator), Capability::ApproveIntents) => {
+                            self.decide_approval(request_id, *approved, operator);
+                        }
+                        EventPayload::PolicyRequest {
+                            request_id,
+                            operator,
+                            command,
+                        } => {
+                            self.apply_policy_command(request_id, operator, command.clone());
+                        }
+                        EventPayload::HardwareFault {
+                            code: FaultCode::EmergencyStop,
+                            ..
+                        } if !self.safety.is_estopped() => {
+                            self.apply_safety_command(SafetyCommand::EStop, None);
+                        }
+                        EventPayload::PowerStatus { percent, .. } => {
+                            let percent = percent.clamp(0.0, 100.0).round() as u8;
+                            self.battery_percent.store(percent, Ordering::Release);
+                        }
                         EventPayload::LidarScan {
                             ranges,
                             angle_min_rad,
                             angle_increment_rad,
+                            stamp,
                         } => {
                             // Convert the polar scan into world-frame 3-D obstacle
                             // points and insert them into the collision octree so
-                            // the OODA loop can detect blocked paths.
-                            let state = self.fusion.fused_state(0.0);
+                            // the OODA loop can detect blocked paths.  Use the pose
+                            // at capture time (the sensor's stamp, else the event's)
+                            // when the history covers it, so a moving robot does not
+                            // smear the map.
+                            let captured = SystemTime::from(stamp.unwrap_or(event.timestamp));
+                            let (position_x, position_y, heading_rad) = match self.tf.lookup_at(
+                                MAP_FRAME,
+                                BASE_FRAME,
+                                captured,
+                            ) {
+                                Some(pose) => {
+                                    (pose.translation.x, pose.translation.y, pose.rotation.yaw())
+                                }
+                                None => {
+                                    let state = self.fusion.fused_state(0.0);
+                                    (state.position_x, state.position_y, state.heading_rad)
+                                }
+                            };
+                            let mut returns = Vec::with_capacity(ranges.len());
                             for (i, &range) in ranges.iter().enumerate() {
                                 if range <= 0.0 || !range.is_finite() {
                                     continue;
                                 }
                                 let sensor_angle = angle_min_rad + i as f32 * angle_increment_rad;
-                                let world_angle = state.heading_rad + sensor_angle;
-                                let x = state.position_x + range * world_angle.cos();
-                                let y = state.position_y + range * world_angle.sin();
+                                let world_angle = heading_rad + sensor_angle;
+                                // Carve free space along the beam so obstacles
+                                // that moved away leave the map.
+                                let (sin, cos) = world_angle.sin_cos();
+                                self.octree.clear_ray(
+                                    Point3::new(position_x, position_y, 0.0),
+                                    Point3::new(cos, sin, 0.0),
+                                    range - SCAN_CARVE_MARGIN_M,
+                                );
+                                let x = position_x + range * cos;
+                                let y = position_y + range * sin;
                                 self.octree.insert(Point3::new(x, y, 0.0));
+                                returns.push((x, y));
+                            }
+                            self.track_obstacles(&returns, captured);
+                        }
+                        EventPayload::GpsFix {
+                            latitude_deg,
+                            longitude_deg,
+                            ..
+                        } => {
+                            let datum = *self.gps_datum.get_or_insert_with(|| {
+                                info!(
+                                    latitude_deg,
+                                    longitude_deg, "first GPS fix becomes the map origin"
+                                );
+                                GeodeticDatum::new(*latitude_deg, *longitude_deg, 0.0)
+                            });
+                            let local =
+                                datum.to_local(*latitude_deg, *longitude_deg, datum.altitude_m);
+                            self.fusion.update_gps(GpsData {
+                                position_x: local.x,
+                                position_y: local.y,
+                            });
+                            self.record_pose(SystemTime::from(event.timestamp));
+                        }
+                        EventPayload::PeerMessage {
+                            from_robot_id,
+                            message,
+                        } => {
+                            if let Some(map_sync) = self.map_sync.as_mut() {
+                                map_sync.merge_message(from_robot_id, message, &mut self.octree);
                             }
                         }
-                        EventPayload::AgentThought(json_str)
+                        EventPayload::AdapterCommand { json, .. }
                             if event.source
                                 == "mechos-middleware::dashboard_override" =>
                         {
-                            // Extract Twist velocities from the rosbridge JSON.
-                            if let Ok(json) =
-                                serde_json::from_str::<serde_json::Value>(json_str)
-                            {
-                                let linear_opt = json["msg"]["linear"]["x"].as_f64();
-                                let angular_opt = json["msg"]["angular"]["z"].as_f64();
-                                if linear_opt.is_none() || angular_opt.is_none() {
-                                    warn!(
-                                        "dashboard_override: missing linear.x or angular.z in Twist frame"
-                                    );
-                                }
-                                let linear = linear_opt.unwrap_or(0.0) as f32;
-                                let angular = angular_opt.unwrap_or(0.0) as f32;
-                                self.override_active.store(true, Ordering::Release);
-                                self.override_last_seen = Some(Instant::now());
-                                // Re-publish the manual override command with the
-                                // kernel source tag so downstream adapters can
-                                // route it to the HAL.
-                                let fwd = Self::build_override_event(linear, angular);
-                                let _ = self.bus.publish(fwd);
+                            // Extract Twist velocities from the rosbridge frame.
+                            if json["release"].as_bool() == Some(true) {
+                                self.release_manual_override();
+                                continue;
                             }
+                            let linear_opt = json["msg"]["linear"]["x"].as_f64();
+                            let angular_opt = json["msg"]["angular"]["z"].as_f64();
+                            if linear_opt.is_none() || angular_opt.is_none() {
+                                warn!(
+                                    "dashboard_override: missing linear.x or angular.z in Twist frame"
+                                );
+                            }
+                            let linear = linear_opt.unwrap_or(0.0) as f32;
+                            let angular = angular_opt.unwrap_or(0.0) as f32;
+                            self.override_active.store(true, Ordering::Release);
+                            self.override_last_seen = Some(Instant::now());
+                            // Re-publish the manual override command with the
+                            // kernel source tag so downstream adapters can
+                            // route it to the HAL.
+                            let fwd = Self::build_override_event(linear, angular);
+                            let _ = self.bus.publish(fwd);
                         }
                         _ => {}
                     }
                 }
                 Err(broadcast::error::TryRecvError::Empty) => break,
-                Err(broadcast::error::TryRecvError::Lagged(_)) => continue,
+                Err(broadcast::error::TryRecvError::Lagged(skipped)) => {
+                    RuntimeMetrics::global().record_bus_lag(skipped);
+                    continue;
+                }
                 Err(broadcast::error::TryRecvError::Closed) => break,
             }
         }
     }
 
+    /// Run a [`MapSync`] round when one is installed and due, publishing its
+    /// map updates as gated `BroadcastFleet` intents.
+    ///
+    /// The updates go straight to the bus rather than through
+    /// [`Self::act`], which would log every (large) payload at `info`.
+    fn sync_map(&mut self) {
+        let Some(map_sync) = self.map_sync.as_mut() else {
+            return;
+        };
+        for message in map_sync.poll(&mut self.octree) {
+            let mut envelope = self.envelope(HardwareIntent::BroadcastFleet { message });
+            if let Err(e) = self.gate.authorize_envelope(&mut envelope) {
+                warn!(error = %e, "map update rejected by the kernel gate");
+                continue;
+            }
+            let event = Event {
+                id: Uuid::new_v4(),
+                timestamp: chrono::Utc::now(),
+                source: format!("mechos-runtime::map_sync/{}", self.agent_id),
+                payload: EventPayload::Intent(envelope),
+                trace_id: None,
+                correlation_id: None,
+            };
+            let _ = self.bus.publish(event);
+        }
+    }
+
+    /// Publish the collision octree, rasterised to a 2-D grid, with the
+    /// robot's pose as an [`EventPayload::MapSnapshot`] when one is due.
+    fn publish_map_snapshot(&mut self) {
+        let Some(interval) = self.map_snapshot_interval else {
+            return;
+        };
+        let now = Instant::now();
+        if self
+            .last_map_snapshot
+            .is_some_and(|last| now.duration_since(last) < interval)
+        {
+            return;
+        }
+        self.last_map_snapshot = Some(now);
+
+        let grid = OccupancyGrid::from_octree(&self.octree, PLANNER_RESOLUTION_M);
+        let (origin_x, origin_y) = grid.origin();
+        let state = self.fusion.fused_state(0.0);
+        let event = Event {
+            id: Uuid::new_v4(),
+            timestamp: chrono::Utc::now(),
+            source: format!("mechos-runtime::agent_loop/{}", self.agent_id),
+            payload: EventPayload::MapSnapshot {
+                origin_x: Meters(origin_x),
+                origin_y: Meters(origin_y),
+                resolution: Meters(grid.resolution()),
+                width: grid.width() as u32,
+                height: grid.height() as u32,
+                cells_b64: BASE64.encode(grid.to_bytes()),
+                robot_x: Meters(state.position_x),
+                robot_y: Meters(state.position_y),
+                robot_heading_rad: state.heading_rad,
+            },
+            trace_id: None,
+            correlation_id: None,
+        };
+        // Best-effort publish – no subscribers is not an error.
+        let _ = self.bus.publish(event);
+    }
+
+    /// Drop obstacles not seen within the configured TTL, and decay the
+    /// belief in detected object classes by one tick.
+    fn decay_obstacles(&mut self) {
+        if let Some(ttl) = self.obstacle_ttl {
+            self.octree.evict_older_than(ttl);
+        }
+        self.semantic.decay_all();
+        self.semantic.prune(SEMANTIC_PRUNE_THRESHOLD);
+    }
+
+    /// Feed one scan's returns to the obstacle tracker, publish an
+    /// [`EventPayload::TrackedObstacle`] per track and refresh the
+    /// clearance read by the [`MovingObstacleRule`].
+    fn track_obstacles(&mut self, returns: &[(f32, f32)], at: SystemTime) {
+        self.tracker.update(returns, at);
+        for track in self.tracker.tracks() {
+            let event = Event {
+                id: Uuid::new_v4(),
+                timestamp: chrono::Utc::now(),
+                source: format!("mechos-runtime::agent_loop/{}", self.agent_id),
+                payload: EventPayload::TrackedObstacle {
+                    id: track.id,
+                    x: Meters(track.x),
+                    y: Meters(track.y),
+                    velocity_x: MetersPerSecond(track.velocity_x),
+                    velocity_y: MetersPerSecond(track.velocity_y),
+                    radius: Meters(track.radius),
+                },
+                trace_id: None,
+                correlation_id: None,
+            };
+            // Best-effort publish – no subscribers is not an error.
+            let _ = self.bus.publish(event);
+        }
+        let state = self.fusion.fused_state(0.0);
+        let clearance = self
+            .tracker
+            .nearest_moving(
+                state.position_x,
+                state.position_y,
+                MOVING_OBSTACLE_MIN_SPEED,
+            )
+            .unwrap_or(f32::INFINITY);
+        self.moving_obstacle_clearance
+            .store(clearance.to_bits(), Ordering::Release);
+    }
+
+    /// Publish sensor fusion's degraded flag to the [`StaleDataRule`],
+    /// logging each transition.
+    fn refresh_localization(&self) {
+        let degraded = self.fusion.is_degraded();
+        if self.localization_degraded.swap(degraded, Ordering::AcqRel) != degraded {
+            if degraded {
+                warn!("odometry lost; dead-reckoning from the IMU, motion restricted");
+            } else {
+                info!("odometry restored; localization no longer degraded");
+            }
+        }
+    }
+
     /// Build an [`Event`] that carries a manual-override Twist command with
     /// the `"mechos-kernel::manual_override"` source tag.
     fn build_override_event(linear_velocity: f32, angular_velocity: f32) -> Event {
@@ -614,14 +2494,14 @@ impl AgentLoop {
                 "linear":  { "x": linear_velocity, "y": 0.0, "z": 0.0 },
                 "angular": { "x": 0.0, "y": 0.0, "z": angular_velocity }
             }
-        })
-        .to_string();
+        });
         Event {
             id: Uuid::new_v4(),
             timestamp: chrono::Utc::now(),
             source: "mechos-kernel::manual_override".to_string(),
-            payload: EventPayload::AgentThought(frame),
+            payload: EventPayload::adapter_command(frame),
             trace_id: None,
+            correlation_id: None,
         }
     }
 
@@ -639,6 +2519,9 @@ impl AgentLoop {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use mechos_perception::transform::{Quaternion, Transform3D};
+    use mechos_types::RadiansPerSecond;
+    use std::sync::atomic::AtomicUsize;
 
     fn default_agent() -> AgentLoop {
         AgentLoop::new(AgentLoopConfig::default()).expect("AgentLoop::new should not fail in tests")
@@ -695,8 +2578,50 @@ mod tests {
         assert!(matches!(result, Err(MechError::LlmInferenceFailed(_))));
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn tick_renews_the_active_task_lease() {
+        let board = TaskBoard::open_in_memory()
+            .unwrap()
+            .with_claim_lease(std::time::Duration::from_millis(40));
+        let id = board.post("Restock", "Fill shelf A.").await.unwrap();
+        board.claim(&id, "agent").await.unwrap();
+        let mut agent = default_agent();
+        agent.set_paused(true);
+        agent.set_active_task(board.clone(), id.clone());
+
+        for _ in 0..3 {
+            tokio::time::sleep(std::time::Duration::from_millis(25)).await;
+            let _ = agent.tick(0.1).await;
+        }
+        assert!(board.reassign_stale().await.unwrap().is_empty());
+        assert_eq!(agent.active_task(), Some(id.as_str()));
+
+        // Once the task is completed the claim is gone and renewal stops.
+        board.complete(&id, "agent").await.unwrap();
+        let _ = agent.tick(0.1).await;
+        assert!(agent.active_task().is_none());
+    }
+
     // ── HITL tests ────────────────────────────────────────────────────────────
 
+    /// Dispatch an `AskHuman` for `question` and return its assigned ID.
+    fn ask(agent: &mut AgentLoop, question: &str) -> String {
+        let mut intent = HardwareIntent::AskHuman {
+            question: question.to_string(),
+            context_image_id: None,
+            question_id: None,
+        };
+        agent.act(&mut intent);
+        let HardwareIntent::AskHuman {
+            question_id: Some(id),
+            ..
+        } = intent
+        else {
+            panic!("act assigns a question ID");
+        };
+        id
+    }
+
     #[test]
     fn initial_state_not_waiting_for_human() {
         let agent = default_agent();
@@ -706,43 +2631,202 @@ mod tests {
     #[test]
     fn submit_human_response_clears_waiting_state() {
         let mut agent = default_agent();
-        agent.waiting_for_human = true;
-        agent.submit_human_response("Yes, proceed");
+        ask(&mut agent, "Proceed?");
+        assert!(agent.is_waiting_for_human());
+        assert!(agent.submit_human_response("Yes, proceed"));
         assert!(!agent.is_waiting_for_human());
-        assert_eq!(agent.pending_human_response.as_deref(), Some("Yes, proceed"));
-    }
-
-    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
-    async fn tick_pauses_when_waiting_for_human_with_no_response() {
-        let mut agent = default_agent();
-        agent.waiting_for_human = true;
-        // No pending response – tick must pause.
-        let result = agent.tick(0.1).await;
+        assert_eq!(
+            agent.answered_questions,
+            [("Proceed?".to_string(), "Yes, proceed".to_string())]
+        );
         assert!(
-            matches!(&result, Err(MechError::LlmInferenceFailed(msg)) if msg.contains("waiting for human")),
-            "expected waiting-for-human pause, got: {result:?}"
+            !agent.submit_human_response("Nobody asked"),
+            "an answer with no open question is ignored"
         );
-        // Still waiting after the pause.
-        assert!(agent.is_waiting_for_human());
     }
 
-    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
-    async fn tick_resumes_when_human_response_is_available() {
-        // inject a pending response; tick should proceed to LLM (which will
-        // fail because no server is running, but not with the "waiting" error).
+    #[test]
+    fn answers_are_matched_to_questions_by_id() {
         let mut agent = default_agent();
-        agent.waiting_for_human = true;
-        agent.pending_human_response = Some("Yes, push it".to_string());
-        let result = agent.tick(0.1).await;
-        // The "waiting" state should have been cleared.
-        assert!(!agent.is_waiting_for_human());
-        // With no LLM, we expect LlmInferenceFailed (not the waiting error).
-        assert!(
-            matches!(&result, Err(MechError::LlmInferenceFailed(msg)) if !msg.contains("waiting for human")),
-            "expected LLM error after resume, got: {result:?}"
+        let shelf = ask(&mut agent, "Which shelf?");
+        let door = ask(&mut agent, "Open the door?");
+        assert_ne!(shelf, door);
+        assert_eq!(
+            agent.open_questions().collect::<Vec<_>>(),
+            [(shelf.as_str(), "Which shelf?"), (door.as_str(), "Open the door?")]
+        );
+
+        assert!(!agent.submit_human_answer(&HumanAnswer::to_question("other-robot", "no")));
+        assert!(agent.submit_human_answer(&HumanAnswer::to_question(door.as_str(), "Yes")));
+        assert_eq!(
+            agent.open_questions().map(|(id, _)| id).collect::<Vec<_>>(),
+            [shelf.as_str()]
+        );
+        assert_eq!(
+            agent.answered_questions,
+            [("Open the door?".to_string(), "Yes".to_string())]
+        );
+    }
+
+    #[test]
+    fn operators_need_a_grant_to_answer_or_approve() {
+        let mut agent = AgentLoop::new(AgentLoopConfig {
+            operator_grants: Some(vec![CapabilityGrant {
+                agent_id: "alice".to_string(),
+                capabilities: vec![Capability::AnswerQuestions],
+            }]),
+            ..AgentLoopConfig::default()
+        })
+        .unwrap();
+        agent.set_approval_policy(|_| true);
+        let id = ask(&mut agent, "Which shelf?");
+        let publish = |agent: &AgentLoop, payload| {
+            let _ = agent.bus.publish(Event {
+                id: Uuid::new_v4(),
+                timestamp: chrono::Utc::now(),
+                source: "mechos-cockpit::server".to_string(),
+                payload,
+                trace_id: None,
+                correlation_id: None,
+            });
+        };
+
+        publish(&agent, HumanAnswer::to_question(id.as_str(), "Top").into());
+        publish(
+            &agent,
+            HumanAnswer::to_question(id.as_str(), "Top").with_operator("mallory").into(),
+        );
+        agent.drain_bus_events();
+        assert!(agent.is_waiting_for_human(), "anonymous and ungranted answers are ignored");
+        publish(
+            &agent,
+            HumanAnswer::to_question(id.as_str(), "Top").with_operator("alice").into(),
+        );
+        agent.drain_bus_events();
+        assert!(!agent.is_waiting_for_human());
+
+        let envelope = IntentEnvelope::new(
+            HardwareIntent::Drive {
+                linear_velocity: MetersPerSecond(0.1),
+                angular_velocity: RadiansPerSecond::ZERO,
+            },
+            "agent",
+        );
+        agent.request_approval(envelope).unwrap();
+        let request_id = agent.pending_approvals()[0].request_id.clone();
+        publish(
+            &agent,
+            EventPayload::ApprovalDecision {
+                request_id,
+                approved: true,
+                operator: "alice".to_string(),
+                reason: None,
+            },
+        );
+        agent.drain_bus_events();
+        assert_eq!(
+            agent.pending_approvals().len(),
+            1,
+            "answering questions does not let alice approve intents"
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn tick_pauses_when_waiting_for_human_with_no_response() {
+        let mut agent = default_agent();
+        ask(&mut agent, "Proceed?");
+        // No pending response – tick must pause.
+        let result = agent.tick(0.1).await;
+        assert!(
+            matches!(&result, Err(MechError::LlmInferenceFailed(msg)) if msg.contains("waiting for human")),
+            "expected waiting-for-human pause, got: {result:?}"
+        );
+        // Still waiting after the pause.
+        assert!(agent.is_waiting_for_human());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn tick_resumes_when_human_response_is_available() {
+        // Answer one of two questions; tick should proceed to the LLM and
+        // then pause again for the other one.
+        let mut agent = default_agent();
+        ask(&mut agent, "Push the box?");
+        let door = ask(&mut agent, "Open the door?");
+        agent.submit_human_response("Yes, push it");
+        agent.set_llm_driver(LlmDriver::scripted(vec![drive_json(0.1)]).unwrap());
+        let result = agent.tick(0.1).await;
+        assert!(matches!(result, Ok(HardwareIntent::Drive { .. })), "got: {result:?}");
+        assert!(agent.answered_questions.is_empty());
+        assert_eq!(agent.open_questions().map(|(id, _)| id).collect::<Vec<_>>(), [door.as_str()]);
+        let result = agent.tick(0.1).await;
+        assert!(
+            matches!(&result, Err(MechError::LlmInferenceFailed(msg)) if msg.contains("waiting for human")),
+            "expected waiting-for-human pause, got: {result:?}"
         );
     }
 
+    /// An agent whose question `question` has just timed out.
+    fn agent_with_expired_question(fallback: HitlFallback, question: &str) -> AgentLoop {
+        let mut agent = AgentLoop::new(AgentLoopConfig {
+            hitl_fallback: fallback,
+            ..Default::default()
+        })
+        .unwrap();
+        ask(&mut agent, question);
+        agent.hitl_timeout = Some(Duration::ZERO);
+        agent
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn unanswered_question_holds_once_and_is_remembered() {
+        let mut agent = agent_with_expired_question(HitlFallback::Hold, "Which shelf?");
+        let held = agent.tick(0.1).await.unwrap();
+        assert!(matches!(held, HardwareIntent::Halt { .. }));
+        assert!(agent.is_waiting_for_human(), "Hold keeps waiting for a late answer");
+        let memories = agent.memory.all_entries().await.unwrap();
+        assert_eq!(memories.len(), 1);
+        assert_eq!(memories[0].source, "hitl");
+        assert!(memories[0].summary.contains("Which shelf?"));
+        assert!(memories[0].summary.contains("hold position"));
+
+        // The fallback runs once; the loop then waits as before.
+        let result = agent.tick(0.1).await;
+        assert!(
+            matches!(&result, Err(MechError::LlmInferenceFailed(msg)) if msg.contains("waiting for human")),
+            "expected waiting-for-human pause, got: {result:?}"
+        );
+        assert!(agent.submit_human_response("The top one"), "a late answer is accepted");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn unanswered_question_can_return_to_dock_or_resume_with_default() {
+        let mut agent = agent_with_expired_question(HitlFallback::ReturnToDock, "Door locked?");
+        assert!(matches!(agent.tick(0.1).await.unwrap(), HardwareIntent::Dock));
+        assert!(!agent.is_waiting_for_human());
+
+        let mut agent = agent_with_expired_question(
+            HitlFallback::DefaultAnswer("Skip it and continue".to_string()),
+            "Push the box?",
+        );
+        agent.set_llm_driver(LlmDriver::scripted(vec![drive_json(0.1)]).unwrap());
+        assert!(matches!(
+            agent.tick(0.1).await.unwrap(),
+            HardwareIntent::Drive { .. }
+        ));
+        assert!(!agent.is_waiting_for_human());
+        assert!(agent.answered_questions.is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn answer_before_timeout_cancels_the_fallback() {
+        let mut agent = default_agent();
+        ask(&mut agent, "Am I clear?");
+        assert!(agent.is_waiting_for_human());
+        agent.submit_human_response("Yes");
+        agent.hitl_timeout = Some(Duration::ZERO);
+        assert!(agent.expire_question().await.is_none());
+    }
+
     // ── Manual override tests ─────────────────────────────────────────────────
 
     #[test]
@@ -794,11 +2878,189 @@ mod tests {
         agent.handle_manual_override(1.0, -0.5);
         let event = rx.try_recv().expect("event should be published");
         assert_eq!(event.source, "mechos-kernel::manual_override");
-        if let EventPayload::AgentThought(json_str) = event.payload {
-            assert!(json_str.contains("/cmd_vel"));
-        } else {
-            panic!("expected AgentThought");
+        let EventPayload::AdapterCommand { topic, json } = event.payload else {
+            panic!("expected AdapterCommand");
+        };
+        assert_eq!(topic, "/cmd_vel");
+        assert_eq!(json["msg"]["linear"]["x"], 1.0);
+    }
+
+    #[test]
+    fn drain_bus_events_tracks_power_status() {
+        let mut agent = default_agent();
+        assert_eq!(agent.battery_percent(), 0);
+        let event = Event {
+            id: Uuid::new_v4(),
+            timestamp: chrono::Utc::now(),
+            source: "mechos-middleware::ros2/battery_state".to_string(),
+            payload: EventPayload::PowerStatus {
+                voltage: 24.0,
+                current: 2.0,
+                charging: true,
+                percent: 63.6,
+            },
+            trace_id: None,
+            correlation_id: None,
+        };
+        let _ = agent.bus.publish(event);
+        agent.drain_bus_events();
+        assert_eq!(agent.battery_percent(), 64);
+    }
+
+    #[test]
+    fn stale_odometry_degrades_localization_and_restricts_motion() {
+        let mut agent = default_agent();
+        let still = OdometryData {
+            position_x: 0.0,
+            position_y: 0.0,
+            heading_rad: 0.0,
+            velocity_x: 0.0,
+            velocity_y: 0.0,
+        };
+        agent
+            .fusion
+            .update_odometry_at(still, Instant::now() - Duration::from_secs(5));
+        agent.refresh_localization();
+        assert!(agent.is_localization_degraded());
+        let follow = HardwareIntent::FollowWaypoints {
+            points: vec![(Meters(1.0), Meters(0.0))],
+            max_speed: MetersPerSecond(0.1),
+        };
+        let err = agent.gate.state_verifier_mut().verify(&follow).unwrap_err();
+        assert_eq!(err.fault_code(), Some(FaultCode::StaleSensor));
+
+        agent.update_odometry(still);
+        agent.refresh_localization();
+        assert!(!agent.is_localization_degraded());
+        assert!(agent.gate.state_verifier_mut().verify(&follow).is_ok());
+    }
+
+    #[test]
+    fn follow_waypoints_are_planned_around_obstacles() {
+        let mut agent = default_agent();
+        // A wall between the robot at the origin and the goal.
+        for i in -20..=20 {
+            agent.octree.insert(Point3::new(1.0, i as f32 * 0.1, 0.0));
+        }
+        assert!(!agent.path_feasible(2.0, 0.0));
+        assert!(agent.path_feasible(0.0, 3.0));
+
+        let follow = HardwareIntent::FollowWaypoints {
+            points: vec![(Meters(2.0), Meters(0.0))],
+            max_speed: MetersPerSecond(0.3),
+        };
+        let HardwareIntent::FollowWaypoints { points, .. } = agent.plan_waypoints(follow).unwrap()
+        else {
+            panic!("expected FollowWaypoints");
+        };
+        assert!(points.len() > 1);
+        assert_eq!(points.last(), Some(&(Meters(2.0), Meters(0.0))));
+        let path: Vec<_> = points.iter().map(|(x, y)| (x.get(), y.get())).collect();
+        assert!(planner::path_feasible(
+            &agent.octree,
+            (0.0, 0.0),
+            &path,
+            0.3
+        ));
+
+        // A goal inside the wall cannot be reached.
+        let blocked = HardwareIntent::FollowWaypoints {
+            points: vec![(Meters(1.0), Meters(0.0))],
+            max_speed: MetersPerSecond(0.3),
+        };
+        let err = agent.plan_waypoints(blocked).unwrap_err();
+        assert_eq!(err.fault_code(), Some(FaultCode::CollisionPredicted));
+    }
+
+    #[test]
+    fn end_effector_targets_are_resolved_into_the_base_frame() {
+        let mut agent = default_agent();
+        agent.update_odometry(OdometryData {
+            position_x: 1.0,
+            position_y: 2.0,
+            heading_rad: std::f32::consts::FRAC_PI_2,
+            velocity_x: 0.0,
+            velocity_y: 0.0,
+        });
+        let target = |frame_id: Option<&str>| HardwareIntent::MoveEndEffector {
+            x: Meters(1.0),
+            y: Meters(2.5),
+            z: Meters(0.3),
+            frame_id: frame_id.map(str::to_string),
+        };
+
+        // Half a metre ahead of a robot facing +Y.
+        let HardwareIntent::MoveEndEffector { x, y, z, frame_id } =
+            agent.resolve_frame(target(Some(MAP_FRAME))).unwrap()
+        else {
+            panic!("expected MoveEndEffector");
+        };
+        assert!(
+            (x.get() - 0.5).abs() < 1e-4 && y.get().abs() < 1e-4,
+            "{x} {y}"
+        );
+        assert!((z.get() - 0.3).abs() < 1e-6);
+        assert_eq!(frame_id.as_deref(), Some(BASE_FRAME));
+
+        // Base-frame and frameless targets are left alone.
+        assert!(matches!(
+            agent.resolve_frame(target(None)).unwrap(),
+            HardwareIntent::MoveEndEffector { x, frame_id: None, .. } if x == Meters(1.0)
+        ));
+        assert!(matches!(
+            agent.resolve_frame(target(Some("camera"))),
+            Err(MechError::Parsing(_))
+        ));
+    }
+
+    #[test]
+    fn configured_safety_rules_are_enforced() {
+        let mut agent = AgentLoop::new(AgentLoopConfig {
+            safety_rules: vec![Box::new(mechos_kernel::SpeedCapRule {
+                max_linear: MetersPerSecond(0.3),
+                max_angular: RadiansPerSecond(0.5),
+            })],
+            ..AgentLoopConfig::default()
+        })
+        .unwrap();
+        let drive = |linear| HardwareIntent::Drive {
+            linear_velocity: MetersPerSecond(linear),
+            angular_velocity: RadiansPerSecond(0.0),
+        };
+        assert!(agent.gate.state_verifier_mut().verify(&drive(0.2)).is_ok());
+        assert!(agent.gate.state_verifier_mut().verify(&drive(0.5)).is_err());
+    }
+
+    #[test]
+    fn moving_obstacles_slow_the_robot_down() {
+        let mut agent = default_agent();
+        let mut rx = agent.bus.subscribe();
+        let drive = HardwareIntent::Drive {
+            linear_velocity: MetersPerSecond(0.5),
+            angular_velocity: RadiansPerSecond(0.0),
+        };
+        let t0 = SystemTime::now();
+        // A static box 3 m away does not restrict motion.
+        let static_box = [(3.0, 0.0), (3.1, 0.0), (3.0, 0.1)];
+        agent.track_obstacles(&static_box, t0);
+        agent.track_obstacles(&static_box, t0 + Duration::from_millis(100));
+        assert_eq!(agent.tracked_obstacles().len(), 1);
+        assert!(agent.gate.state_verifier_mut().verify(&drive).is_ok());
+
+        // A person crossing 0.6 m ahead at 1 m/s does.
+        agent.track_obstacles(&[(0.6, -0.5), (0.6, -0.4)], t0 + Duration::from_millis(200));
+        agent.track_obstacles(&[(0.6, -0.4), (0.6, -0.3)], t0 + Duration::from_millis(300));
+        let err = agent.gate.state_verifier_mut().verify(&drive).unwrap_err();
+        assert_eq!(err.fault_code(), Some(FaultCode::CollisionPredicted));
+
+        let mut tracked = 0;
+        while let Ok(event) = rx.try_recv() {
+            if matches!(event.payload, EventPayload::TrackedObstacle { .. }) {
+                tracked += 1;
+            }
         }
+        // The box after its second scan, the person after theirs.
+        assert_eq!(tracked, 2);
     }
 
     #[test]
@@ -809,33 +3071,73 @@ mod tests {
             id: Uuid::new_v4(),
             timestamp: chrono::Utc::now(),
             source: "mechos-middleware::dashboard/human_response".to_string(),
-            payload: EventPayload::HumanResponse("Yes, go ahead".to_string()),
+            payload: EventPayload::HumanResponse(HumanAnswer::new("Yes, go ahead")),
             trace_id: None,
+            correlation_id: None,
         };
+        ask(&mut agent, "Go ahead?");
         let _ = agent.bus.publish(event);
         agent.drain_bus_events();
         assert_eq!(
-            agent.pending_human_response.as_deref(),
-            Some("Yes, go ahead")
+            agent.answered_questions,
+            [("Go ahead?".to_string(), "Yes, go ahead".to_string())]
         );
     }
 
     #[test]
     fn drain_bus_events_picks_up_dashboard_override() {
         let mut agent = default_agent();
-        let override_json = r#"{"op":"publish","topic":"/cmd_vel","msg":{"linear":{"x":0.8,"y":0,"z":0},"angular":{"x":0,"y":0,"z":0.3}},"source":"dashboard_override"}"#;
+        let override_json = serde_json::json!({
+            "op": "publish",
+            "topic": "/cmd_vel",
+            "msg": { "linear": { "x": 0.8, "y": 0, "z": 0 }, "angular": { "x": 0, "y": 0, "z": 0.3 } },
+            "source": "dashboard_override"
+        });
         let event = Event {
             id: Uuid::new_v4(),
             timestamp: chrono::Utc::now(),
             source: "mechos-middleware::dashboard_override".to_string(),
-            payload: EventPayload::AgentThought(override_json.to_string()),
+            payload: EventPayload::adapter_command(override_json),
             trace_id: None,
+            correlation_id: None,
         };
         let _ = agent.bus.publish(event);
         agent.drain_bus_events();
         assert!(agent.is_override_active());
     }
 
+    #[test]
+    fn released_override_stops_the_robot_and_resumes_ai() {
+        let mut agent = default_agent();
+        agent.handle_manual_override(0.8, 0.0);
+        let mut rx = agent.bus.subscribe();
+        let release_json = serde_json::json!({
+            "op": "publish",
+            "topic": "/cmd_vel",
+            "msg": { "linear": { "x": 0.0, "y": 0, "z": 0 }, "angular": { "x": 0, "y": 0, "z": 0.0 } },
+            "source": "dashboard_override",
+            "release": true
+        });
+        let event = Event {
+            id: Uuid::new_v4(),
+            timestamp: chrono::Utc::now(),
+            source: "mechos-middleware::dashboard_override".to_string(),
+            payload: EventPayload::adapter_command(release_json),
+            trace_id: None,
+            correlation_id: None,
+        };
+        let _ = agent.bus.publish(event);
+        agent.drain_bus_events();
+        assert!(!agent.is_override_active());
+        let stop = std::iter::from_fn(|| rx.try_recv().ok())
+            .find(|event| event.source == "mechos-kernel::manual_override")
+            .expect("no stop command was forwarded");
+        let EventPayload::AdapterCommand { json: frame, .. } = stop.payload else {
+            panic!("expected an AdapterCommand");
+        };
+        assert_eq!(frame["msg"]["linear"]["x"], 0.0);
+    }
+
     // ── Cockpit pause/resume tests ────────────────────────────────────────────
 
     #[test]
@@ -867,7 +3169,7 @@ mod tests {
         assert!(
             matches!(
                 &result,
-                Err(MechError::HardwareFault { component, details })
+                Err(MechError::HardwareFault { code: FaultCode::AgentPaused, component, details })
                     if component == "agent_loop" && details.contains("paused")
             ),
             "expected paused HardwareFault, got: {result:?}"
@@ -887,8 +3189,10 @@ mod tests {
                 ranges: vec![2.0],
                 angle_min_rad: 0.0,
                 angle_increment_rad: 0.0,
+                stamp: None,
             },
             trace_id: None,
+            correlation_id: None,
         };
         let _ = agent.bus.publish(event);
         agent.drain_bus_events();
@@ -899,6 +3203,267 @@ mod tests {
         );
     }
 
+    #[test]
+    fn prompt_reports_the_nearest_obstacle() {
+        let mut agent = default_agent();
+        let state = origin_state();
+        assert!(agent.describe_nearest_obstacle(&state).is_empty());
+        agent.add_obstacle(Point3::new(4.0, 1.0, 0.0));
+        agent.add_obstacle(Point3::new(1.8, -1.2, 0.0));
+        assert_eq!(
+            agent.describe_nearest_obstacle(&state),
+            "1.13 m at bearing 45°"
+        );
+        let prompt = agent.render_system_prompt(&state, true, String::new(), String::new());
+        assert!(prompt.contains("Nearest obstacle: 1.13 m at bearing 45°"));
+
+        // A detection names what is there, with the fused confidence.
+        agent.observe_object("person", Point3::new(1.0, -1.5, 0.0), &[1.0, 0.0], 0.8);
+        assert_eq!(
+            agent.describe_nearest_obstacle(&state),
+            "0.50 m at bearing 90° (person, 80% confident)"
+        );
+        agent.decay_obstacles();
+        assert_eq!(
+            agent.describe_nearest_obstacle(&state),
+            "0.50 m at bearing 90° (person, 76% confident)"
+        );
+    }
+
+    #[test]
+    fn prompt_lists_known_object_locations() {
+        let mut agent = default_agent();
+        assert!(agent.describe_object_locations().is_empty());
+        agent.observe_object_location("red box", "garage");
+        agent.observe_object_location("red box", "kitchen table");
+        agent.observe_object_location("keys", "hallway");
+        let prompt =
+            agent.render_system_prompt(&origin_state(), true, String::new(), String::new());
+        assert!(prompt.contains("## Known Object Locations"));
+        assert!(prompt.contains("- red box: kitchen table (80%, seen 0 s ago)"));
+        assert!(prompt.contains("- keys: hallway (80%, seen 0 s ago)"));
+        assert!(!prompt.contains("garage"));
+    }
+
+    #[tokio::test]
+    async fn successful_goal_sequence_is_recalled_as_a_procedure() {
+        let mut agent = default_agent();
+        agent.set_procedural_store(Some(ProceduralStore::open_in_memory().unwrap()));
+        agent.set_goal(Some("open the east door".to_string()));
+        assert!(!agent.record_goal_success().await.unwrap());
+        let mut open = HardwareIntent::TriggerRelay {
+            relay_id: "east_door".to_string(),
+            state: true,
+        };
+        agent.act(&mut open);
+        agent.act(&mut HardwareIntent::Halt {
+            reason: "test".to_string(),
+        });
+        assert!(agent.record_goal_success().await.unwrap());
+        assert!(!agent.record_goal_success().await.unwrap());
+
+        let lines = agent.describe_procedures().await;
+        assert!(lines.starts_with("- Last time you did \"open the east door\""));
+        assert!(lines.contains("(1×): {") && lines.contains("east_door"));
+        assert!(!lines.contains("Halt"));
+        let prompt = agent.render_system_prompt(&origin_state(), true, String::new(), lines);
+        assert!(prompt.contains("## Learned Procedures\n- Last time you did"));
+
+        agent.set_goal(Some("water the plants".to_string()));
+        assert!(agent.describe_procedures().await.is_empty());
+    }
+
+    #[test]
+    fn unseen_obstacles_decay_after_the_ttl() {
+        let mut agent = AgentLoop::new(AgentLoopConfig {
+            obstacle_ttl_secs: 30,
+            max_obstacle_points: 2,
+            ..Default::default()
+        })
+        .unwrap();
+        let passer_by = Point3::new(1.0, 0.0, 0.0);
+        agent
+            .octree
+            .insert_at(passer_by, Instant::now() - Duration::from_secs(60));
+        agent.add_obstacle(Point3::new(2.0, 0.0, 0.0));
+        agent.decay_obstacles();
+        assert!(!agent.octree.contains(passer_by));
+        assert_eq!(agent.octree.len(), 1);
+
+        agent.add_obstacle(Point3::new(3.0, 0.0, 0.0));
+        agent.add_obstacle(Point3::new(4.0, 0.0, 0.0));
+        assert!(agent.octree.len() <= 2);
+        assert!(agent.octree.contains(Point3::new(4.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn lidar_scan_carves_obstacles_that_moved_away() {
+        let mut agent = default_agent();
+        agent.add_obstacle(Point3::new(1.0, 0.0, 0.0));
+        agent.add_obstacle(Point3::new(1.0, 1.0, 0.0));
+        let event = Event {
+            id: Uuid::new_v4(),
+            timestamp: chrono::Utc::now(),
+            source: "mechos-middleware::ros2/scan".to_string(),
+            payload: EventPayload::LidarScan {
+                ranges: vec![3.0],
+                angle_min_rad: 0.0,
+                angle_increment_rad: 0.0,
+                stamp: None,
+            },
+            trace_id: None,
+            correlation_id: None,
+        };
+        let _ = agent.bus.publish(event);
+        agent.drain_bus_events();
+        assert!(!agent.octree.contains(Point3::new(1.0, 0.0, 0.0)));
+        assert!(agent.octree.contains(Point3::new(1.0, 1.0, 0.0)));
+        assert!(agent.octree.contains(Point3::new(3.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn lidar_scan_is_projected_with_the_pose_at_capture_time() {
+        let mut agent = default_agent();
+        let t0 = SystemTime::now() - Duration::from_secs(1);
+        let ms = |n: u64| t0 + Duration::from_millis(n);
+        agent
+            .tf
+            .set_transform_at(MAP_FRAME, BASE_FRAME, Transform3D::identity(), ms(0));
+        agent.tf.set_transform_at(
+            MAP_FRAME,
+            BASE_FRAME,
+            Transform3D::new(Vec3::new(1.0, 0.0, 0.0), Quaternion::identity()),
+            ms(100),
+        );
+        // Captured 80 ms in, when the robot was 0.8 m along, and published
+        // after 120 ms in transit.
+        let event = Event {
+            id: Uuid::new_v4(),
+            timestamp: ms(200).into(),
+            source: "mechos-middleware::ros2/scan".to_string(),
+            payload: EventPayload::LidarScan {
+                ranges: vec![2.0],
+                angle_min_rad: 0.0,
+                angle_increment_rad: 0.0,
+                stamp: Some(ms(80).into()),
+            },
+            trace_id: None,
+            correlation_id: None,
+        };
+        let _ = agent.bus.publish(event);
+        agent.drain_bus_events();
+        let near = |x: f32| {
+            Aabb::new(
+                Point3::new(x - 0.01, -0.01, -0.01),
+                Point3::new(x + 0.01, 0.01, 0.01),
+            )
+        };
+        assert!(agent.octree.query_aabb(&near(2.8)));
+        assert!(!agent.octree.query_aabb(&near(3.0)));
+    }
+
+    #[test]
+    fn odometry_poses_are_recorded_at_their_measurement_time() {
+        let mut agent = default_agent();
+        let t0 = SystemTime::now() - Duration::from_secs(1);
+        let ms = |n: u64| t0 + Duration::from_millis(n);
+        let odom = |x: f32| OdometryData {
+            position_x: x,
+            position_y: 0.0,
+            heading_rad: 0.0,
+            velocity_x: 0.0,
+            velocity_y: 0.0,
+        };
+        agent.update_odometry_at(odom(0.0), ms(0));
+        agent.update_odometry_at(odom(1.0), ms(100));
+        let at = agent.tf.lookup_at(MAP_FRAME, BASE_FRAME, ms(50)).unwrap();
+        assert!((at.translation.x - 0.5).abs() < 1e-3, "x={}", at.translation.x);
+    }
+
+    #[test]
+    fn map_sync_merges_peer_maps_and_broadcasts_own_obstacles() {
+        let mut config = AgentLoopConfig::default();
+        config.capabilities.push(Capability::FleetCommunicate);
+        let mut agent = AgentLoop::new(config).unwrap();
+        agent.set_map_sync(Some(MapSync::new("rover-b").with_interval(Duration::ZERO)));
+        agent.add_obstacle(Point3::new(3.0, 0.0, 0.0));
+        let mut rx = agent.bus().subscribe();
+
+        let mut peer_map = Octree::new(
+            Aabb::new(Point3::new(-5.0, -5.0, -5.0), Point3::new(5.0, 5.0, 5.0)),
+            8,
+        );
+        peer_map.insert(Point3::new(-1.0, 2.0, 0.0));
+        let message = MapSync::new("rover-a").poll(&mut peer_map).remove(0);
+        let _ = agent.bus.publish(Event {
+            id: Uuid::new_v4(),
+            timestamp: chrono::Utc::now(),
+            source: "mechos-middleware::fleet/rover-a".to_string(),
+            payload: EventPayload::PeerMessage {
+                from_robot_id: "rover-a".to_string(),
+                message,
+            },
+            trace_id: None,
+            correlation_id: None,
+        });
+        agent.drain_bus_events();
+        assert_eq!(agent.octree.len(), 2);
+        assert!(agent.octree.query_aabb(&Aabb::new(
+            Point3::new(-1.1, 1.9, -0.1),
+            Point3::new(-0.9, 2.1, 0.1),
+        )));
+
+        agent.sync_map();
+        let broadcast = std::iter::from_fn(|| rx.try_recv().ok())
+            .find(|e| e.source == "mechos-runtime::map_sync/agent")
+            .expect("map update must be published");
+        let EventPayload::Intent(envelope) = broadcast.payload else {
+            panic!("expected an intent, got {:?}", broadcast.payload);
+        };
+        let HardwareIntent::BroadcastFleet { message } = envelope.intent else {
+            panic!("expected BroadcastFleet, got {:?}", envelope.intent);
+        };
+        // Only our own obstacle goes out; the merged peer voxel does not.
+        let mut echo = Octree::new(
+            Aabb::new(Point3::new(-5.0, -5.0, -5.0), Point3::new(5.0, 5.0, 5.0)),
+            8,
+        );
+        let mut peer = MapSync::new("rover-a");
+        assert_eq!(peer.merge_message("rover-b", &message, &mut echo), Some(1));
+        assert_eq!(
+            peer.provenance(Point3::new(3.0, 0.0, 0.0)).unwrap().origin,
+            "rover-b"
+        );
+    }
+
+    #[test]
+    fn drain_bus_events_feeds_gps_fixes_to_fusion() {
+        let mut agent = default_agent();
+        let fix = |latitude_deg: f64, longitude_deg: f64| Event {
+            id: Uuid::new_v4(),
+            timestamp: chrono::Utc::now(),
+            source: "mechos-middleware::gps/nmea".to_string(),
+            payload: EventPayload::GpsFix {
+                latitude_deg,
+                longitude_deg,
+                altitude: mechos_types::Meters(0.0),
+            },
+            trace_id: None,
+            correlation_id: None,
+        };
+        let _ = agent.bus.publish(fix(47.0, 8.0));
+        agent.drain_bus_events();
+        assert_eq!(agent.gps_datum().unwrap().latitude_deg, 47.0);
+        let state = agent.fusion.fused_state(0.0);
+        assert!(state.position_x.abs() < 1e-3 && state.position_y.abs() < 1e-3);
+
+        // 0.0001° north is about 11 m.
+        let _ = agent.bus.publish(fix(47.0001, 8.0));
+        agent.drain_bus_events();
+        let state = agent.fusion.fused_state(0.0);
+        assert!((state.position_y - 11.12).abs() < 0.05, "{state:?}");
+    }
+
     #[test]
     fn drain_bus_events_skips_invalid_lidar_ranges() {
         let mut agent = default_agent();
@@ -911,8 +3476,10 @@ mod tests {
                 ranges: vec![0.0, -1.0, f32::NAN, f32::INFINITY],
                 angle_min_rad: 0.0,
                 angle_increment_rad: 0.1,
+                stamp: None,
             },
             trace_id: None,
+            correlation_id: None,
         };
         let _ = agent.bus.publish(event);
         agent.drain_bus_events();
@@ -928,6 +3495,7 @@ mod tests {
             source: "mechos-cockpit::server".to_string(),
             payload: EventPayload::AgentModeToggle { paused: true },
             trace_id: None,
+            correlation_id: None,
         };
         let _ = agent.bus.publish(event);
         agent.drain_bus_events();
@@ -944,9 +3512,632 @@ mod tests {
             source: "mechos-cockpit::server".to_string(),
             payload: EventPayload::AgentModeToggle { paused: false },
             trace_id: None,
+            correlation_id: None,
         };
         let _ = agent.bus.publish(event);
         agent.drain_bus_events();
         assert!(!agent.is_paused());
     }
+
+    // ── Halt tests ────────────────────────────────────────────────────────────
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn loop_guard_trip_publishes_halt() {
+        let mut agent = AgentLoop::new(AgentLoopConfig {
+            loop_guard_threshold: 2,
+            ..Default::default()
+        })
+        .unwrap();
+        agent.set_llm_driver(LlmDriver::scripted(vec![drive_json(0.1), drive_json(0.1)]).unwrap());
+        let mut rx = agent.bus().subscribe();
+
+        assert!(agent.tick(0.1).await.is_ok());
+        assert!(agent.tick(0.1).await.is_err());
+
+        let halted = std::iter::from_fn(|| rx.try_recv().ok()).any(|e| {
+            matches!(&e.payload, EventPayload::Intent(env) if matches!(env.intent, HardwareIntent::Halt { .. }))
+        });
+        assert!(halted, "a LoopGuard trip must publish a Halt intent");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn dispatched_intent_is_enveloped_with_tick_correlation_id() {
+        let mut agent = default_agent();
+        agent.set_llm_driver(LlmDriver::scripted(vec![drive_json(0.2)]).unwrap());
+        let mut rx = agent.bus().subscribe();
+
+        agent.tick(0.1).await.unwrap();
+
+        let event = rx.try_recv().unwrap();
+        let EventPayload::Intent(envelope) = event.payload else {
+            panic!("expected an intent envelope, got {:?}", event.payload);
+        };
+        assert_eq!(envelope.correlation_id, agent.last_correlation_id());
+        assert_eq!(envelope.issued_by, "agent");
+        assert!(envelope.deadline.is_some(), "Drive intents carry a deadline");
+        assert!(!envelope.is_expired());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn map_snapshot_is_published_at_most_once_per_interval() {
+        let mut agent = AgentLoop::new(AgentLoopConfig {
+            map_snapshot_interval_ms: 60_000,
+            ..Default::default()
+        })
+        .unwrap();
+        // Mid-cell, so rounding cannot move it to a neighbour.
+        agent.add_obstacle(Point3::new(2.05, 0.05, 0.0));
+        agent.set_llm_driver(LlmDriver::scripted(vec![drive_json(0.1), drive_json(0.2)]).unwrap());
+        let mut rx = agent.bus().subscribe();
+
+        agent.tick(0.1).await.unwrap();
+        agent.tick(0.1).await.unwrap();
+
+        let snapshots: Vec<_> = std::iter::from_fn(|| rx.try_recv().ok())
+            .filter_map(|e| match e.payload {
+                EventPayload::MapSnapshot {
+                    origin_x,
+                    resolution,
+                    width,
+                    cells_b64,
+                    ..
+                } => Some((origin_x, resolution, width, cells_b64)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(snapshots.len(), 1, "the second tick falls within the interval");
+        let (origin_x, resolution, width, cells_b64) = &snapshots[0];
+        let cells = BASE64.decode(cells_b64).unwrap();
+        let col = ((2.05 - origin_x.get()) / resolution.get()) as usize;
+        let row = ((0.05 + 10.0) / resolution.get()) as usize;
+        assert_eq!(cells[row * *width as usize + col], 100, "obstacle cell is occupied");
+    }
+
+    #[tokio::test]
+    async fn safety_request_latches_estop_and_is_confirmed() {
+        let mut agent = default_agent();
+        let mut rx = agent.bus().subscribe();
+        let request = |request_id: &str, command| Event {
+            id: Uuid::new_v4(),
+            timestamp: chrono::Utc::now(),
+            source: "mechos-cockpit::server".to_string(),
+            payload: EventPayload::SafetyRequest {
+                request_id: request_id.to_string(),
+                command,
+            },
+            trace_id: None,
+            correlation_id: None,
+        };
+        let _ = agent.bus.publish(request("stop-1", SafetyCommand::EStop));
+        agent.drain_bus_events();
+        assert!(agent.is_estopped());
+        assert!(matches!(
+            agent.tick(0.1).await,
+            Err(MechError::HardwareFault {
+                code: FaultCode::EmergencyStop,
+                ..
+            })
+        ));
+
+        let _ = agent.bus.publish(request(
+            "mode-1",
+            SafetyCommand::SetMode(SafetyMode::Reduced),
+        ));
+        let _ = agent.bus.publish(request("reset-1", SafetyCommand::Reset));
+        agent.drain_bus_events();
+        assert!(!agent.is_estopped());
+        assert_eq!(
+            agent.safety_mode(),
+            SafetyMode::Normal,
+            "refused while latched"
+        );
+
+        let mut halted = false;
+        let mut confirmed = Vec::new();
+        for event in std::iter::from_fn(|| rx.try_recv().ok()) {
+            match event.payload {
+                EventPayload::Intent(envelope) => {
+                    halted |= matches!(envelope.intent, HardwareIntent::Halt { .. });
+                }
+                EventPayload::SafetyStatus {
+                    request_id: Some(id),
+                    estopped,
+                    ..
+                } => confirmed.push((id, estopped)),
+                _ => {}
+            }
+        }
+        assert!(halted, "the e-stop halts the robot");
+        assert_eq!(
+            confirmed,
+            [
+                ("stop-1".to_string(), true),
+                ("mode-1".to_string(), true),
+                ("reset-1".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn emergency_stop_fault_latches_estop() {
+        let mut agent = default_agent();
+        let _ = agent.bus.publish(Event {
+            id: Uuid::new_v4(),
+            timestamp: chrono::Utc::now(),
+            source: "mechos-cli::repl".to_string(),
+            payload: EventPayload::HardwareFault {
+                component: "cli".to_string(),
+                code: FaultCode::EmergencyStop,
+                message: "operator /halt".to_string(),
+            },
+            trace_id: None,
+            correlation_id: None,
+        });
+        agent.drain_bus_events();
+        assert!(agent.is_estopped());
+        let drive = HardwareIntent::Drive {
+            linear_velocity: MetersPerSecond(0.1),
+            angular_velocity: RadiansPerSecond(0.0),
+        };
+        assert!(agent.gate.state_verifier_mut().verify(&drive).is_err());
+    }
+
+    // ── Operator approval tests ───────────────────────────────────────────────
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn held_intent_runs_only_once_approved() {
+        let mut agent = default_agent();
+        agent.set_llm_driver(LlmDriver::scripted(vec![drive_json(0.1), drive_json(0.2)]).unwrap());
+        agent.set_approval_policy(|intent| matches!(intent, HardwareIntent::Drive { .. }));
+        let mut rx = agent.bus().subscribe();
+
+        let held = agent.tick(0.1).await;
+        assert!(matches!(
+            held,
+            Err(MechError::HardwareFault {
+                code: FaultCode::ApprovalPending,
+                ..
+            })
+        ));
+        let (request_id, envelope, report) = std::iter::from_fn(|| rx.try_recv().ok())
+            .find_map(|e| match e.payload {
+                EventPayload::ApprovalRequest {
+                    request_id,
+                    envelope,
+                    report,
+                } => Some((request_id, envelope, report)),
+                _ => None,
+            })
+            .expect("the held intent is published for approval");
+        assert!(matches!(envelope.intent, HardwareIntent::Drive { .. }));
+        assert!(!report.is_empty() && report.iter().all(|v| v.passed));
+        assert!(
+            agent.tick(0.1).await.is_err(),
+            "the loop waits for the decision"
+        );
+
+        let _ = agent.bus.publish(Event {
+            id: Uuid::new_v4(),
+            timestamp: chrono::Utc::now(),
+            source: "mechos-cockpit::server".to_string(),
+            payload: EventPayload::ApprovalDecision {
+                request_id: request_id.clone(),
+                approved: true,
+                operator: "alice".to_string(),
+                reason: None,
+            },
+            trace_id: None,
+            correlation_id: None,
+        });
+        agent.drain_bus_events();
+        assert!(agent.pending_approvals().is_empty());
+        let dispatched = std::iter::from_fn(|| rx.try_recv().ok()).find_map(|e| match e.payload {
+            EventPayload::Intent(envelope) => Some(envelope),
+            _ => None,
+        });
+        assert_eq!(
+            dispatched.unwrap().correlation_id.to_string(),
+            request_id,
+            "the approved intent keeps its correlation ID"
+        );
+    }
+
+    // ── Runtime safety policy tests ───────────────────────────────────────────
+
+    #[test]
+    fn policy_request_is_applied_and_answered() {
+        let mut agent = default_agent();
+        let mut rx = agent.bus().subscribe();
+        let request = |request_id: &str, command| Event {
+            id: Uuid::new_v4(),
+            timestamp: chrono::Utc::now(),
+            source: "mechos-cockpit::server".to_string(),
+            payload: EventPayload::PolicyRequest {
+                request_id: request_id.to_string(),
+                operator: "alice".to_string(),
+                command,
+            },
+            trace_id: None,
+            correlation_id: None,
+        };
+        let _ = agent.bus.publish(request(
+            "p1",
+            PolicyCommand::SetRuleMode {
+                rule: "safety_interlock".to_string(),
+                mode: mechos_types::RuleMode::Warn,
+            },
+        ));
+        let _ = agent.bus.publish(request(
+            "p2",
+            PolicyCommand::SetRuleParam {
+                rule: "stale_data".to_string(),
+                param: "max_linear".to_string(),
+                value: 0.3,
+            },
+        ));
+        agent.drain_bus_events();
+
+        let statuses: Vec<(String, Option<String>)> = std::iter::from_fn(|| rx.try_recv().ok())
+            .filter_map(|e| match e.payload {
+                EventPayload::PolicyStatus {
+                    request_id, error, ..
+                } => Some((request_id, error)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(statuses.len(), 2);
+        assert!(
+            statuses[0].1.is_some(),
+            "the e-stop interlock always blocks"
+        );
+        assert_eq!(statuses[1], ("p2".to_string(), None));
+
+        let report = agent.policy_report();
+        let stale_data = report
+            .rules
+            .iter()
+            .find(|r| r.rule == "stale_data")
+            .unwrap();
+        assert_eq!(stale_data.params["max_linear"], 0.3);
+        assert_eq!(report.audit.len(), 2);
+    }
+
+    #[test]
+    fn halt_abandons_active_skill() {
+        let mut agent = default_agent();
+        agent.skill_queue.push_back(HardwareIntent::Drive {
+            linear_velocity: MetersPerSecond(0.1),
+            angular_velocity: RadiansPerSecond(0.0),
+        });
+        agent.halt("operator");
+        assert!(!agent.is_skill_active());
+    }
+
+    // ── Deliberation tests ────────────────────────────────────────────────────
+
+    fn drive_json(linear: f32) -> String {
+        format!(r#"{{"action":"Drive","payload":{{"linear_velocity":{linear},"angular_velocity":0.0}}}}"#)
+    }
+
+    #[test]
+    fn deliberation_disabled_by_default() {
+        let agent = default_agent();
+        assert_eq!(agent.deliberation_samples, 1);
+        assert_eq!(agent.llm.temperature(), None);
+    }
+
+    #[test]
+    fn deliberation_sets_sampling_temperature() {
+        let agent = AgentLoop::new(AgentLoopConfig {
+            deliberation_samples: 5,
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(agent.llm.temperature(), Some(DEFAULT_DELIBERATION_TEMPERATURE));
+    }
+
+    #[test]
+    fn vote_picks_majority_intent() {
+        let agent = default_agent();
+        let replies = vec![drive_json(0.1), drive_json(0.3), drive_json(0.3)];
+        let winner = agent.vote(&replies).unwrap();
+        let intent: HardwareIntent = serde_json::from_str(&winner).unwrap();
+        assert!(matches!(intent, HardwareIntent::Drive { linear_velocity, .. } if (linear_velocity.get() - 0.3).abs() < 1e-6));
+    }
+
+    #[test]
+    fn vote_discards_unparseable_and_gate_failing_candidates() {
+        let agent = default_agent();
+        // TriggerRelay is not granted by the default capabilities.
+        let relay = r#"{"action":"TriggerRelay","payload":{"relay_id":"pump","state":true}}"#;
+        let replies = vec![
+            "not json".to_string(),
+            relay.to_string(),
+            relay.to_string(),
+            drive_json(0.2),
+        ];
+        let winner = agent.vote(&replies).unwrap();
+        assert!(winner.contains("Drive"));
+    }
+
+    #[test]
+    fn vote_breaks_ties_in_favour_of_earliest_sample() {
+        let agent = default_agent();
+        let winner = agent.vote(&[drive_json(0.1), drive_json(0.2)]).unwrap();
+        assert!(winner.contains("0.1"));
+    }
+
+    #[test]
+    fn vote_returns_none_when_no_candidate_survives() {
+        let agent = default_agent();
+        assert!(agent.vote(&["{}".to_string()]).is_none());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn deliberation_without_server_reports_no_valid_candidate() {
+        let mut agent = AgentLoop::new(AgentLoopConfig {
+            deliberation_samples: 3,
+            ..Default::default()
+        })
+        .unwrap();
+        let result = agent.tick(0.1).await;
+        assert!(
+            matches!(&result, Err(MechError::LlmInferenceFailed(msg)) if msg.contains("deliberation")),
+            "expected deliberation failure, got: {result:?}"
+        );
+    }
+
+    // ── Trajectory check tests ────────────────────────────────────────────────
+
+    #[test]
+    fn trajectory_check_passes_clear_drive_unchanged() {
+        let agent = default_agent();
+        let intent = agent
+            .check_trajectory(HardwareIntent::Drive { linear_velocity: MetersPerSecond(1.0), angular_velocity: RadiansPerSecond(0.0) })
+            .unwrap();
+        assert!(matches!(intent, HardwareIntent::Drive { linear_velocity, .. } if linear_velocity == MetersPerSecond(1.0)));
+    }
+
+    #[test]
+    fn trajectory_check_shortens_drive_towards_obstacle() {
+        let mut agent = default_agent();
+        agent.add_obstacle(Point3::new(1.0, 0.0, 0.0));
+        let intent = agent
+            .check_trajectory(HardwareIntent::Drive { linear_velocity: MetersPerSecond(1.0), angular_velocity: RadiansPerSecond(0.2) })
+            .unwrap();
+        match intent {
+            HardwareIntent::Drive { linear_velocity, angular_velocity } => {
+                assert!(linear_velocity > MetersPerSecond::ZERO && linear_velocity < MetersPerSecond(1.0));
+                // Both velocities are scaled by the same factor.
+                assert!((angular_velocity.get() / linear_velocity.get() - 0.2).abs() < 1e-4);
+            }
+            other => panic!("expected Drive, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn trajectory_check_rejects_imminent_collision() {
+        let mut agent = default_agent();
+        agent.add_obstacle(Point3::new(0.35, 0.0, 0.0));
+        let result = agent
+            .check_trajectory(HardwareIntent::Drive { linear_velocity: MetersPerSecond(1.0), angular_velocity: RadiansPerSecond(0.0) });
+        assert!(matches!(
+            result,
+            Err(MechError::HardwareFault { component, .. }) if component == "trajectory_check"
+        ));
+    }
+
+    #[test]
+    fn trajectory_check_lets_the_robot_back_away_from_an_obstacle_in_contact() {
+        let mut agent = default_agent();
+        agent.add_obstacle(Point3::new(0.1, 0.0, 0.0));
+        let intent = agent
+            .check_trajectory(HardwareIntent::Drive { linear_velocity: MetersPerSecond(-0.5), angular_velocity: RadiansPerSecond(0.0) })
+            .unwrap();
+        assert!(matches!(intent, HardwareIntent::Drive { linear_velocity, .. } if linear_velocity == MetersPerSecond(-0.5)));
+        assert!(agent
+            .check_trajectory(HardwareIntent::Drive { linear_velocity: MetersPerSecond(0.5), angular_velocity: RadiansPerSecond(0.0) })
+            .is_err());
+    }
+
+    #[test]
+    fn trajectory_check_ignores_non_drive_and_rotation_in_place() {
+        let mut agent = default_agent();
+        agent.add_obstacle(Point3::new(0.1, 0.0, 0.0));
+        assert!(
+            agent
+                .check_trajectory(HardwareIntent::Drive {
+                    linear_velocity: MetersPerSecond(0.0),
+                    angular_velocity: RadiansPerSecond(1.0)
+                })
+                .is_ok()
+        );
+        assert!(
+            agent
+                .check_trajectory(HardwareIntent::MoveEndEffector {
+                    x: Meters(0.1),
+                    y: Meters(0.0),
+                    z: Meters(0.0),
+                    frame_id: None
+                })
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn trajectory_check_can_be_disabled() {
+        let mut agent = AgentLoop::new(AgentLoopConfig {
+            trajectory_horizon_ms: 0,
+            ..Default::default()
+        })
+        .unwrap();
+        agent.add_obstacle(Point3::new(0.35, 0.0, 0.0));
+        assert!(agent
+            .check_trajectory(HardwareIntent::Drive { linear_velocity: MetersPerSecond(1.0), angular_velocity: RadiansPerSecond(0.0) })
+            .is_ok());
+    }
+
+    // ── Skill tests ───────────────────────────────────────────────────────────
+
+    #[test]
+    fn register_skill_rejects_duplicates() {
+        let mut agent = default_agent();
+        agent
+            .register_skill(Skill::intents("dock", "Dock", |_| vec![]))
+            .unwrap();
+        assert!(agent
+            .register_skill(Skill::intents("dock", "Dock", |_| vec![]))
+            .is_err());
+        assert_eq!(agent.skills().names(), vec!["dock"]);
+    }
+
+    #[test]
+    fn start_skill_queues_intents_one_per_step() {
+        let mut agent = default_agent();
+        agent
+            .register_skill(Skill::intents("wiggle", "Turn left then right", |_| {
+                vec![
+                    HardwareIntent::Drive { linear_velocity: MetersPerSecond(0.0), angular_velocity: RadiansPerSecond(0.5) },
+                    HardwareIntent::Drive { linear_velocity: MetersPerSecond(0.0), angular_velocity: RadiansPerSecond(-0.5) },
+                ]
+            }))
+            .unwrap();
+        let first = agent
+            .start_skill(SkillCall { skill: "wiggle".into(), args: Default::default() })
+            .unwrap();
+        assert!(matches!(first, HardwareIntent::Drive { angular_velocity, .. } if angular_velocity > RadiansPerSecond::ZERO));
+        assert!(agent.is_skill_active());
+
+        let second = agent.step_skill().unwrap().unwrap();
+        assert!(matches!(second, SkillStatus::Intent(HardwareIntent::Drive { angular_velocity, .. }) if angular_velocity < RadiansPerSecond::ZERO));
+        assert!(!agent.is_skill_active());
+        assert!(agent.step_skill().is_none());
+    }
+
+    #[test]
+    fn start_skill_with_unknown_name_is_llm_error() {
+        let mut agent = default_agent();
+        let result = agent.start_skill(SkillCall { skill: "fly".into(), args: Default::default() });
+        assert!(matches!(result, Err(MechError::LlmInferenceFailed(msg)) if msg.contains("unknown skill")));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn running_tree_skill_preempts_llm() {
+        let mut agent = default_agent();
+        agent
+            .register_skill(Skill::tree("scan_room", "Spin in place", |_| {
+                BehaviorNode::leaf("spin", || NodeStatus::Running)
+            }))
+            .unwrap();
+        let _ = agent.start_skill(SkillCall { skill: "scan_room".into(), args: Default::default() });
+
+        let result = agent.tick(0.1).await;
+        assert!(
+            matches!(&result, Err(MechError::LlmInferenceFailed(msg)) if msg.contains("skill 'scan_room' running")),
+            "expected a busy tick, got: {result:?}"
+        );
+        assert!(agent.is_skill_active());
+    }
+
+    #[test]
+    fn only_a_failed_tree_skill_is_a_fault() {
+        let mut agent = default_agent();
+        let ticks = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&ticks);
+        agent
+            .register_skill(Skill::tree("scan_room", "Spin in place", move |_| {
+                let counter = Arc::clone(&counter);
+                BehaviorNode::leaf("spin", move || {
+                    if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                        NodeStatus::Running
+                    } else {
+                        NodeStatus::Failure
+                    }
+                })
+            }))
+            .unwrap();
+        let first = agent.start_skill(SkillCall { skill: "scan_room".into(), args: Default::default() });
+        assert!(matches!(first, Err(MechError::LlmInferenceFailed(_))), "{first:?}");
+
+        assert!(matches!(agent.step_skill(), Some(Ok(SkillStatus::Running(name))) if name == "scan_room"));
+        assert!(agent.is_skill_active());
+
+        assert!(matches!(
+            agent.step_skill(),
+            Some(Err(MechError::HardwareFault { code: FaultCode::SkillFailed, .. }))
+        ));
+        assert!(!agent.is_skill_active());
+        assert!(agent.step_skill().is_none());
+    }
+
+    fn origin_state() -> FusedState {
+        FusedState {
+            position_x: 1.0,
+            position_y: -2.0,
+            heading_rad: 0.0,
+            velocity_x: 0.0,
+            velocity_y: 0.0,
+            degraded: false,
+        }
+    }
+
+    #[test]
+    fn default_prompt_lists_role_goal_and_capabilities() {
+        let mut agent = AgentLoop::new(AgentLoopConfig {
+            role_prompt: Some("You only drive the base.".to_string()),
+            capabilities: vec![Capability::HardwareInvoke("drive_base".to_string())],
+            ..Default::default()
+        })
+        .unwrap();
+        agent.set_goal(Some("Reach the charging dock".to_string()));
+
+        let prompt =
+            agent.render_system_prompt(&origin_state(), false, "(none)".to_string(), String::new());
+        assert!(prompt.contains("## Role\nYou only drive the base.\n"));
+        assert!(prompt.contains("## Goal\nReach the charging dock\n"));
+        assert!(prompt.contains("- HardwareInvoke(\"drive_base\")"));
+        assert!(prompt.contains("Position: x=1.000, y=-2.000\n"));
+        assert!(prompt.contains("Path: BLOCKED\n"));
+    }
+
+    #[test]
+    fn custom_prompt_template_replaces_default() {
+        let mut agent = default_agent();
+        agent.set_prompt_template(
+            PromptTemplate::parse("Forklift at {{position}}; path {{path}}; {{memories}}").unwrap(),
+        );
+        let prompt =
+            agent.render_system_prompt(&origin_state(), true, "m".to_string(), String::new());
+        assert_eq!(prompt, "Forklift at x=1.000, y=-2.000; path CLEAR; m");
+    }
+
+    #[test]
+    fn missing_named_prompt_template_fails_construction() {
+        let result = AgentLoop::new(AgentLoopConfig {
+            prompt_template: Some(format!("absent-{}", Uuid::new_v4())),
+            ..Default::default()
+        });
+        assert!(matches!(result, Err(MechError::Parsing(_))));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn llm_exchanges_are_recorded_under_mission_id() {
+        let mut agent = default_agent();
+        agent.set_transcript_store(Some(TranscriptStore::open_in_memory().unwrap()));
+        agent.set_mission_id("dock-run-7");
+
+        // No live LLM server – the failed exchange is still recorded.
+        let _ = agent.tick(0.1).await;
+
+        let entries = agent
+            .transcript()
+            .unwrap()
+            .mission_transcript("dock-run-7")
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].agent_id, "agent");
+        assert!(entries[0].prompt.contains("System State"));
+        assert!(entries[0].error.is_some());
+    }
 }
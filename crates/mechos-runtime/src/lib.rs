@@ -25,10 +25,39 @@
 //! - [`loop_guard`] – [`LoopGuard`][loop_guard::LoopGuard]:
 //!   a safety mechanism that detects when the LLM is stuck requesting the same
 //!   failing action repeatedly and signals that an intervention is required.
-//! - [`telemetry`] – [`init_tracing`][telemetry::init_tracing]:
+//! - [`map_sync`] – [`MapSync`][map_sync::MapSync]:
+//!   shares the collision octree across the fleet as delta-compressed voxel
+//!   broadcasts, merging peer maps with per-voxel provenance and expiring
+//!   peer obstacles nobody has reported recently.
+//! - [`metrics`] – [`RuntimeMetrics`][metrics::RuntimeMetrics]:
+//!   OpenTelemetry metrics for tick duration, LLM latency and token spend,
+//!   gate decisions/rejections, loop-guard trips and bus lag, plus an
+//!   optional Prometheus scrape endpoint
+//!   ([`serve_prometheus`][metrics::serve_prometheus]) for deployments
+//!   without an OTLP collector.
+//! - [`prompt`] – [`PromptTemplate`][prompt::PromptTemplate]:
+//!   handlebars-style system-prompt templates (placeholders for position,
+//!   memories, goal, capabilities, …) loadable from `~/.mechos/prompts/`,
+//!   validated so required placeholders are always present.
+//! - [`replay`] – [`ReplayDriver`][replay::ReplayDriver]:
+//!   feeds a recorded event log (odometry, LiDAR, human responses) into an
+//!   [`AgentLoop`][agent_loop::AgentLoop] at original or accelerated timing,
+//!   with a live or scripted LLM, for regression-testing safety rules
+//!   against real incidents.
+//! - [`skill`] – [`SkillRegistry`][skill::SkillRegistry]:
+//!   named, parameterised skills ("dock", "scan_room", "pick(object)") built
+//!   from intent sequences or behavior sub-trees.  Registered skills are
+//!   listed in the LLM prompt so decisions happen at the skill level.
+//! - [`supervisor`] – [`AgentSupervisor`][supervisor::AgentSupervisor]:
+//!   spawns several [`AgentLoop`][agent_loop::AgentLoop]s (e.g. `"navigator"`,
+//!   `"manipulator"`) on one bus with namespaced identities, distinct
+//!   capability sets and prompt roles, and serialises their intents through a
+//!   single [`KernelGate`] with a priority-based conflict arbiter.
+//! - [`telemetry`] – [`init_observability`][telemetry::init_observability]:
 //!   initialises the global `tracing` subscriber with an optional OTLP span
-//!   exporter.  Set `OTEL_EXPORTER_OTLP_ENDPOINT` to enable live trace export
-//!   to Jaeger, Grafana Tempo, or any OTLP-compatible collector.
+//!   exporter and the global OTel meter provider.  Set
+//!   `OTEL_EXPORTER_OTLP_ENDPOINT` to enable live trace and metric export to
+//!   Jaeger, Grafana Tempo, or any OTLP-compatible collector.
 //!
 //! # Kernel gating
 //!
@@ -42,13 +71,25 @@ pub mod agent_loop;
 pub mod behavior_tree;
 pub mod llm_driver;
 pub mod loop_guard;
+pub mod map_sync;
+pub mod metrics;
+pub mod prompt;
+pub mod replay;
+pub mod skill;
+pub mod supervisor;
 pub mod telemetry;
 
-pub use agent_loop::{AgentLoop, AgentLoopConfig};
+pub use agent_loop::{AgentLoop, AgentLoopConfig, HitlFallback};
 pub use behavior_tree::{BehaviorNode, NodeStatus};
 pub use llm_driver::{ChatMessage, LlmDriver, LlmError, Role, STABILITY_GUIDELINES};
 pub use loop_guard::LoopGuard;
-pub use telemetry::{init_tracing, TracerProviderGuard};
+pub use map_sync::MapSync;
+pub use metrics::{serve_prometheus, RuntimeMetrics};
+pub use prompt::{PromptError, PromptTemplate};
+pub use replay::{ReplayDriver, ReplayReport};
+pub use skill::{Skill, SkillCall, SkillError, SkillRegistry};
+pub use supervisor::AgentSupervisor;
+pub use telemetry::{init_observability, init_tracing, ObservabilityGuard, TracerProviderGuard};
 
 // Re-export the kernel gate so the runtime can use it as its hardware dispatch
 // interception point without callers needing a direct dependency on
@@ -41,7 +41,8 @@
 
 use std::num::NonZeroU32;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::{Arc, RwLock};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::Instant;
 
 use governor::clock::DefaultClock;
@@ -54,6 +55,8 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tracing::{debug, instrument, warn};
 
+use crate::metrics::RuntimeMetrics;
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Safety limits
 // ─────────────────────────────────────────────────────────────────────────────
@@ -158,6 +161,8 @@ struct ChatRequest<'a> {
     messages: &'a [ChatMessage],
     stream: bool,
     response_format: ResponseFormat,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
 }
 
 #[derive(Deserialize)]
@@ -201,6 +206,12 @@ pub struct LlmDriver {
     /// Wrapped in `RwLock` so that [`set_rpm`][Self::set_rpm] can replace it
     /// at runtime without rebuilding the whole driver.
     rate_limiter: Arc<RwLock<DirectRateLimiter>>,
+    /// Sampling temperature sent with every request.  `None` leaves the
+    /// server default in place.
+    temperature: Option<f32>,
+    /// Canned replies returned instead of calling the model server (see
+    /// [`scripted`][Self::scripted]).  `None` for a live driver.
+    scripted: Option<Mutex<VecDeque<String>>>,
 }
 
 impl LlmDriver {
@@ -302,9 +313,35 @@ impl LlmDriver {
             total_tokens: Arc::new(AtomicU64::new(0)),
             token_budget,
             rate_limiter,
+            temperature: None,
+            scripted: None,
         })
     }
 
+    /// Create a mock driver that answers each request with the next entry of
+    /// `replies`, without any network I/O, rate limiting or token
+    /// accounting.
+    ///
+    /// Useful for deterministic replays and tests (e.g. feeding back the
+    /// responses of a recorded transcript).  Once the script is exhausted
+    /// every call returns [`LlmError::BadResponse`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LlmError::Configuration`] if the (unused) HTTP client
+    /// cannot be initialised.
+    pub fn scripted(replies: impl IntoIterator<Item = String>) -> Result<Self, LlmError> {
+        let mut driver = Self::new("http://localhost", "scripted")?;
+        driver.scripted = Some(Mutex::new(replies.into_iter().collect()));
+        Ok(driver)
+    }
+
+    /// `true` if this driver replays canned replies instead of calling a
+    /// model server.
+    pub fn is_scripted(&self) -> bool {
+        self.scripted.is_some()
+    }
+
     /// Return the cumulative number of tokens consumed since construction (or
     /// the last call to [`reset_token_counter`][Self::reset_token_counter]).
     ///
@@ -341,8 +378,40 @@ impl LlmDriver {
         *self.rate_limiter.write().unwrap_or_else(|e| e.into_inner()) = new_limiter;
     }
 
+    /// Set the sampling temperature sent with every request, or `None` to use
+    /// the model server's default.
+    pub fn set_temperature(&mut self, temperature: Option<f32>) {
+        self.temperature = temperature;
+    }
+
+    /// Return the configured sampling temperature, if any.
+    pub fn temperature(&self) -> Option<f32> {
+        self.temperature
+    }
+
     /// Send `messages` to the model and return the assistant's reply text.
     ///
+    /// The reply is constrained to the [`HardwareIntent`] JSON Schema.  This is
+    /// shorthand for [`complete_with_schema`][Self::complete_with_schema] with
+    /// [`hardware_intent_schema`][Self::hardware_intent_schema].
+    ///
+    /// # Errors
+    ///
+    /// See [`complete_with_schema`][Self::complete_with_schema].
+    pub async fn complete(&self, messages: &[ChatMessage]) -> Result<String, LlmError> {
+        self.complete_with_schema(messages, Self::hardware_intent_schema())
+            .await
+    }
+
+    /// The [`HardwareIntent`] JSON Schema injected via `response_format` by
+    /// [`complete`][Self::complete].
+    pub fn hardware_intent_schema() -> serde_json::Value {
+        serde_json::to_value(schema_for!(HardwareIntent)).unwrap_or(serde_json::Value::Null)
+    }
+
+    /// Send `messages` to the model and return the assistant's reply text,
+    /// constraining the reply to `schema` via `response_format`.
+    ///
     /// `STABILITY_GUIDELINES` are automatically appended to every
     /// [`Role::System`] message so the model always receives the anti-loop
     /// rules regardless of how the caller constructs the conversation.  If no
@@ -368,7 +437,20 @@ impl LlmDriver {
             inference_latency_ms = tracing::field::Empty,
         )
     )]
-    pub async fn complete(&self, messages: &[ChatMessage]) -> Result<String, LlmError> {
+    pub async fn complete_with_schema(
+        &self,
+        messages: &[ChatMessage],
+        schema: serde_json::Value,
+    ) -> Result<String, LlmError> {
+        // ── Scripted replies (replay / tests) ──────────────────────────────
+        if let Some(script) = &self.scripted {
+            return script
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .pop_front()
+                .ok_or_else(|| LlmError::BadResponse("scripted replies exhausted".into()));
+        }
+
         // ── TLS enforcement ────────────────────────────────────────────────
         // Reject plaintext HTTP connections to non-localhost hosts.
         if !Self::is_secure_url(&self.base_url) {
@@ -422,8 +504,6 @@ impl LlmDriver {
         }
 
         let url = format!("{}/v1/chat/completions", self.base_url);
-        let schema = serde_json::to_value(schema_for!(HardwareIntent))
-            .unwrap_or(serde_json::Value::Null);
         let body = ChatRequest {
             model: &self.model,
             messages: &augmented,
@@ -432,6 +512,7 @@ impl LlmDriver {
                 kind: "json_schema",
                 json_schema: schema,
             },
+            temperature: self.temperature,
         };
 
         let inference_start = Instant::now();
@@ -457,7 +538,8 @@ impl LlmDriver {
         }
         let response: ChatResponse = serde_json::from_slice(&body_bytes)
             .map_err(|e| LlmError::BadResponse(e.to_string()))?;
-        let inference_latency_ms = inference_start.elapsed().as_millis() as u64;
+        let inference_latency = inference_start.elapsed();
+        let inference_latency_ms = inference_latency.as_millis() as u64;
 
         let reply = response
             .choices
@@ -488,6 +570,7 @@ impl LlmDriver {
         span.record("reply_tokens", reply_tokens);
         span.record("tokens_used_after", new_total);
         span.record("inference_latency_ms", inference_latency_ms);
+        RuntimeMetrics::global().record_llm_call(inference_latency, prompt_tokens + reply_tokens);
         debug!(
             model = %self.model,
             prompt_tokens,
@@ -695,6 +778,30 @@ mod tests {
         assert!(schema_str.contains("TriggerRelay"));
     }
 
+    #[test]
+    fn temperature_defaults_to_none_and_is_settable() {
+        let mut driver = LlmDriver::new("http://localhost:11434", "llama3").unwrap();
+        assert_eq!(driver.temperature(), None);
+        driver.set_temperature(Some(0.8));
+        assert_eq!(driver.temperature(), Some(0.8));
+    }
+
+    #[test]
+    fn temperature_is_omitted_from_request_when_unset() {
+        let body = ChatRequest {
+            model: "llama3",
+            messages: &[],
+            stream: false,
+            response_format: ResponseFormat {
+                kind: "json_schema",
+                json_schema: serde_json::Value::Null,
+            },
+            temperature: None,
+        };
+        let json = serde_json::to_string(&body).unwrap();
+        assert!(!json.contains("temperature"));
+    }
+
     // ── Cost-control tests ────────────────────────────────────────────────────
 
     #[test]
@@ -885,4 +992,13 @@ mod tests {
             "response body at the exact limit must pass the size check"
         );
     }
+
+    #[tokio::test]
+    async fn scripted_driver_replays_replies_in_order() {
+        let driver = LlmDriver::scripted(["a".to_string(), "b".to_string()]).unwrap();
+        assert!(driver.is_scripted());
+        assert_eq!(driver.complete(&[]).await.unwrap(), "a");
+        assert_eq!(driver.complete(&[]).await.unwrap(), "b");
+        assert!(matches!(driver.complete(&[]).await, Err(LlmError::BadResponse(_))));
+    }
 }
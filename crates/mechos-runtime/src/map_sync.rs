@@ -0,0 +1,505 @@
+//! [`MapSync`] – shared obstacle maps across the fleet.
+//!
+//! Each robot's collision [`Octree`] only knows what its own sensors saw.
+//! `MapSync` turns it into a common fleet map:
+//!
+//! * **Outbound** – every sync interval [`MapSync::poll`] snapshots the
+//!   local tree with [`Octree::export_points`], quantises the points to
+//!   voxels and returns fleet broadcast messages carrying only the voxels
+//!   that are new since the last round (a *delta*).  Every
+//!   `refresh_every`-th round carries all of the robot's own voxels instead,
+//!   so peers keep them fresh.
+//! * **Inbound** – [`MapSync::merge_message`] decodes a peer's update and
+//!   inserts the voxel centres it did not know yet into the local tree,
+//!   recording which robot reported each voxel and when
+//!   ([`Provenance`]).
+//! * **Expiry** – peer voxels nobody has reported within the maximum age
+//!   are removed from the tree again, so a moved obstacle does not block
+//!   the fleet forever.  The robot's own observations never expire.
+//!
+//! The [`AgentLoop`](crate::agent_loop::AgentLoop) drives all three once a
+//! `MapSync` is installed with
+//! [`set_map_sync`](crate::agent_loop::AgentLoop::set_map_sync).
+//!
+//! # Wire format
+//!
+//! An update is a JSON object sent as the `BroadcastFleet` message:
+//! `{"mechos_map": 1, "resolution": 0.1, "voxels": "<base64>"}`.  The
+//! voxel keys are sorted and each axis is written as the zig-zag varint
+//! difference from the previous key, which keeps dense scans to a few bytes
+//! per voxel.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mechos_perception::octree::{Aabb, Octree, Point3};
+//! use mechos_runtime::map_sync::MapSync;
+//!
+//! let bounds = Aabb::new(Point3::new(-10.0, -10.0, -10.0), Point3::new(10.0, 10.0, 10.0));
+//! let (mut map_a, mut map_b) = (Octree::new(bounds, 8), Octree::new(bounds, 8));
+//! map_a.insert(Point3::new(1.02, 2.04, 0.0));
+//!
+//! let mut sync_a = MapSync::new("rover-a");
+//! let mut sync_b = MapSync::new("rover-b");
+//! for message in sync_a.poll(&mut map_a) {
+//!     sync_b.merge_message("rover-a", &message, &mut map_b);
+//! }
+//! assert!(map_b.query_aabb(&Aabb::new(Point3::new(1.0, 2.0, 0.0), Point3::new(1.1, 2.1, 0.1))));
+//! assert_eq!(sync_b.provenance(Point3::new(1.05, 2.05, 0.05)).unwrap().origin, "rover-a");
+//! ```
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use mechos_perception::octree::{Octree, Point3};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+/// Default voxel edge length, in metres.
+pub const DEFAULT_RESOLUTION_M: f32 = 0.1;
+
+/// Default interval between map broadcasts.
+pub const DEFAULT_SYNC_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Default age after which an unreported peer voxel is dropped.
+pub const DEFAULT_MAX_AGE: Duration = Duration::from_secs(60);
+
+/// Default number of rounds between full refreshes.
+pub const DEFAULT_REFRESH_EVERY: u32 = 6;
+
+/// Version of the update format, carried in its `mechos_map` field.
+const MAP_FORMAT_VERSION: u32 = 1;
+
+/// Voxels per update, keeping each well under the fleet message limit.
+const MAX_VOXELS_PER_MESSAGE: usize = 2_000;
+
+/// Integer voxel coordinates.
+type VoxelKey = [i32; 3];
+
+/// Who reported a voxel of the shared map, and when.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Provenance {
+    /// Robot ID of the latest robot to report the voxel.
+    pub origin: String,
+    /// When that report arrived (or the voxel was observed locally).
+    pub last_seen: Instant,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct MapUpdate {
+    mechos_map: u32,
+    resolution: f32,
+    voxels: String,
+}
+
+/// Shares one robot's collision map with the fleet; see the
+/// [module docs](self).
+#[derive(Debug)]
+pub struct MapSync {
+    robot_id: String,
+    resolution: f32,
+    interval: Duration,
+    max_age: Duration,
+    refresh_every: u32,
+    voxels: HashMap<VoxelKey, Provenance>,
+    /// Own voxels not broadcast yet.
+    pending: BTreeSet<VoxelKey>,
+    last_round: Option<Instant>,
+    rounds: u32,
+}
+
+impl MapSync {
+    /// Share the map of `robot_id` with the default resolution, interval,
+    /// maximum age and refresh period.
+    pub fn new(robot_id: impl Into<String>) -> Self {
+        Self {
+            robot_id: robot_id.into(),
+            resolution: DEFAULT_RESOLUTION_M,
+            interval: DEFAULT_SYNC_INTERVAL,
+            max_age: DEFAULT_MAX_AGE,
+            refresh_every: DEFAULT_REFRESH_EVERY,
+            voxels: HashMap::new(),
+            pending: BTreeSet::new(),
+            last_round: None,
+            rounds: 0,
+        }
+    }
+
+    /// Quantise points to voxels of `metres` instead of
+    /// [`DEFAULT_RESOLUTION_M`].  Non-positive values are ignored.
+    pub fn with_resolution(mut self, metres: f32) -> Self {
+        if metres > 0.0 && metres.is_finite() {
+            self.resolution = metres;
+        }
+        self
+    }
+
+    /// Broadcast every `interval` instead of [`DEFAULT_SYNC_INTERVAL`].
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Drop peer voxels unreported for `max_age` instead of
+    /// [`DEFAULT_MAX_AGE`].
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Send a full refresh every `rounds` rounds instead of
+    /// [`DEFAULT_REFRESH_EVERY`]; keep it well under the peers' maximum
+    /// age divided by the interval.
+    pub fn with_refresh_every(mut self, rounds: u32) -> Self {
+        self.refresh_every = rounds.max(1);
+        self
+    }
+
+    /// The robot whose map this shares.
+    pub fn robot_id(&self) -> &str {
+        &self.robot_id
+    }
+
+    /// Voxels in the shared map, own and peer.
+    pub fn len(&self) -> usize {
+        self.voxels.len()
+    }
+
+    /// `true` when no voxel is known yet.
+    pub fn is_empty(&self) -> bool {
+        self.voxels.is_empty()
+    }
+
+    /// Who reported the voxel containing `point`, if anyone.
+    pub fn provenance(&self, point: Point3) -> Option<&Provenance> {
+        self.voxels.get(&self.key(point))
+    }
+
+    /// Run one sync round if the interval has elapsed: record new local
+    /// observations, expire stale peer voxels and return the fleet
+    /// broadcast messages to send (empty when nothing is due).
+    pub fn poll(&mut self, octree: &mut Octree) -> Vec<String> {
+        let now = Instant::now();
+        if self
+            .last_round
+            .is_some_and(|last| now.duration_since(last) < self.interval)
+        {
+            return Vec::new();
+        }
+        self.last_round = Some(now);
+        self.observe_local(octree);
+        self.expire(octree);
+
+        let full = self.rounds.is_multiple_of(self.refresh_every);
+        self.rounds = self.rounds.wrapping_add(1);
+        let pending = std::mem::take(&mut self.pending);
+        let keys: Vec<VoxelKey> = if full {
+            let mut own: Vec<VoxelKey> = self
+                .voxels
+                .iter()
+                .filter(|(_, p)| p.origin == self.robot_id)
+                .map(|(key, _)| *key)
+                .collect();
+            own.sort_unstable();
+            own
+        } else {
+            pending.into_iter().collect()
+        };
+        keys.chunks(MAX_VOXELS_PER_MESSAGE)
+            .map(|chunk| self.encode(chunk))
+            .collect()
+    }
+
+    /// Merge a fleet message from `from_robot_id` into `octree`.
+    ///
+    /// Returns the number of voxels that were new to this robot, or `None`
+    /// when the message is not a map update.
+    pub fn merge_message(
+        &mut self,
+        from_robot_id: &str,
+        message: &str,
+        octree: &mut Octree,
+    ) -> Option<usize> {
+        let update: MapUpdate = serde_json::from_str(message).ok()?;
+        if update.mechos_map != MAP_FORMAT_VERSION
+            || !(update.resolution > 0.0 && update.resolution.is_finite())
+        {
+            debug!(from = %from_robot_id, "ignoring map update in an unknown format");
+            return None;
+        }
+        let keys = match decode_voxels(&update.voxels) {
+            Ok(keys) => keys,
+            Err(e) => {
+                debug!(from = %from_robot_id, error = %e, "ignoring malformed map update");
+                return None;
+            }
+        };
+        if from_robot_id == self.robot_id {
+            return Some(0);
+        }
+        let now = Instant::now();
+        let mut added = 0;
+        for peer_key in keys {
+            let point = centre(peer_key, update.resolution);
+            let key = self.key(point);
+            match self.voxels.get_mut(&key) {
+                Some(provenance) if provenance.origin == self.robot_id => {}
+                Some(provenance) => {
+                    provenance.origin = from_robot_id.to_string();
+                    provenance.last_seen = now;
+                }
+                None => {
+                    self.voxels.insert(
+                        key,
+                        Provenance {
+                            origin: from_robot_id.to_string(),
+                            last_seen: now,
+                        },
+                    );
+                    octree.insert(centre(key, self.resolution));
+                    added += 1;
+                }
+            }
+        }
+        Some(added)
+    }
+
+    /// Record voxels of `octree` this robot observed itself.  Points merged
+    /// from peers (exact voxel centres) are not mistaken for observations.
+    fn observe_local(&mut self, octree: &Octree) {
+        let now = Instant::now();
+        for point in octree.export_points() {
+            let key = self.key(point);
+            match self.voxels.get(&key) {
+                Some(provenance) if provenance.origin == self.robot_id => continue,
+                Some(_) if point == centre(key, self.resolution) => continue,
+                _ => {}
+            }
+            self.voxels.insert(
+                key,
+                Provenance {
+                    origin: self.robot_id.clone(),
+                    last_seen: now,
+                },
+            );
+            self.pending.insert(key);
+        }
+    }
+
+    /// Forget peer voxels unreported for the maximum age and remove their
+    /// points from `octree`.  Returns how many expired.
+    fn expire(&mut self, octree: &mut Octree) -> usize {
+        let expired: HashSet<VoxelKey> = self
+            .voxels
+            .iter()
+            .filter(|(_, p)| p.origin != self.robot_id && p.last_seen.elapsed() >= self.max_age)
+            .map(|(key, _)| *key)
+            .collect();
+        if expired.is_empty() {
+            return 0;
+        }
+        self.voxels.retain(|key, _| !expired.contains(key));
+        let resolution = self.resolution;
+        octree.retain(|p| {
+            let key = voxel_key(*p, resolution);
+            !(expired.contains(&key) && *p == centre(key, resolution))
+        });
+        expired.len()
+    }
+
+    fn key(&self, point: Point3) -> VoxelKey {
+        voxel_key(point, self.resolution)
+    }
+
+    fn encode(&self, keys: &[VoxelKey]) -> String {
+        let update = MapUpdate {
+            mechos_map: MAP_FORMAT_VERSION,
+            resolution: self.resolution,
+            voxels: encode_voxels(keys),
+        };
+        serde_json::to_string(&update).unwrap_or_default()
+    }
+}
+
+fn voxel_key(point: Point3, resolution: f32) -> VoxelKey {
+    [
+        (point.x / resolution).floor() as i32,
+        (point.y / resolution).floor() as i32,
+        (point.z / resolution).floor() as i32,
+    ]
+}
+
+fn centre(key: VoxelKey, resolution: f32) -> Point3 {
+    Point3::new(
+        (key[0] as f32 + 0.5) * resolution,
+        (key[1] as f32 + 0.5) * resolution,
+        (key[2] as f32 + 0.5) * resolution,
+    )
+}
+
+/// Delta- and varint-encode sorted voxel keys as base64.
+fn encode_voxels(keys: &[VoxelKey]) -> String {
+    let mut bytes = Vec::with_capacity(keys.len() * 3);
+    let mut previous = [0i32; 3];
+    for key in keys {
+        for axis in 0..3 {
+            let delta = key[axis].wrapping_sub(previous[axis]);
+            let mut zigzag = ((delta << 1) ^ (delta >> 31)) as u32;
+            while zigzag >= 0x80 {
+                bytes.push(zigzag as u8 | 0x80);
+                zigzag >>= 7;
+            }
+            bytes.push(zigzag as u8);
+        }
+        previous = *key;
+    }
+    BASE64.encode(bytes)
+}
+
+fn decode_voxels(encoded: &str) -> Result<Vec<VoxelKey>, String> {
+    let bytes = BASE64.decode(encoded).map_err(|e| e.to_string())?;
+    let mut bytes = bytes.iter();
+    let mut next_delta = || -> Result<Option<i32>, String> {
+        let mut value = 0u32;
+        for shift in (0..35).step_by(7) {
+            let Some(&byte) = bytes.next() else {
+                return if shift == 0 {
+                    Ok(None)
+                } else {
+                    Err("truncated varint".to_string())
+                };
+            };
+            value |= u32::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(Some((value >> 1) as i32 ^ -((value & 1) as i32)));
+            }
+        }
+        Err("varint too long".to_string())
+    };
+    let mut keys = Vec::new();
+    let mut previous = [0i32; 3];
+    while let Some(dx) = next_delta()? {
+        let (Some(dy), Some(dz)) = (next_delta()?, next_delta()?) else {
+            return Err("truncated voxel".to_string());
+        };
+        let key = [
+            previous[0].wrapping_add(dx),
+            previous[1].wrapping_add(dy),
+            previous[2].wrapping_add(dz),
+        ];
+        keys.push(key);
+        previous = key;
+    }
+    Ok(keys)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mechos_perception::octree::Aabb;
+
+    fn world() -> Octree {
+        Octree::new(
+            Aabb::new(
+                Point3::new(-10.0, -10.0, -10.0),
+                Point3::new(10.0, 10.0, 10.0),
+            ),
+            8,
+        )
+    }
+
+    #[test]
+    fn voxel_encoding_round_trips() {
+        let keys = vec![
+            [-100, 3, 0],
+            [-100, 4, 0],
+            [-99, -7, 2],
+            [i32::MAX, i32::MIN, 0],
+        ];
+        assert_eq!(decode_voxels(&encode_voxels(&keys)).unwrap(), keys);
+        assert!(decode_voxels(&BASE64.encode([0x80])).is_err());
+        // Neighbouring voxels cost three bytes each.
+        let dense: Vec<VoxelKey> = (0..100).map(|x| [x, 0, 0]).collect();
+        assert_eq!(BASE64.decode(encode_voxels(&dense)).unwrap().len(), 300);
+    }
+
+    #[test]
+    fn deltas_carry_only_new_voxels_and_refreshes_carry_all() {
+        let mut map = world();
+        let mut sync = MapSync::new("rover-a")
+            .with_interval(Duration::ZERO)
+            .with_refresh_every(3);
+        map.insert(Point3::new(1.0, 1.0, 0.0));
+        map.insert(Point3::new(1.01, 1.01, 0.01)); // same voxel
+
+        let mut peer = MapSync::new("rover-b");
+        let mut peer_map = world();
+        let mut received = |messages: Vec<String>| -> usize {
+            messages
+                .iter()
+                .map(|m| peer.merge_message("rover-a", m, &mut peer_map).unwrap())
+                .sum()
+        };
+        assert_eq!(received(sync.poll(&mut map)), 1);
+
+        map.insert(Point3::new(-2.0, 3.0, 0.0));
+        let delta = sync.poll(&mut map);
+        assert_eq!(decode_voxels(&voxels_of(&delta[0])).unwrap().len(), 1);
+        assert_eq!(received(delta), 1);
+        assert!(sync.poll(&mut map).is_empty());
+
+        let refresh = sync.poll(&mut map);
+        assert_eq!(decode_voxels(&voxels_of(&refresh[0])).unwrap().len(), 2);
+        assert_eq!(received(refresh), 0);
+    }
+
+    fn voxels_of(message: &str) -> String {
+        serde_json::from_str::<MapUpdate>(message).unwrap().voxels
+    }
+
+    #[test]
+    fn peer_voxels_carry_provenance_and_expire() {
+        let mut map = world();
+        map.insert(Point3::new(5.0, 5.0, 0.0)); // own observation
+        let mut sync = MapSync::new("rover-b")
+            .with_interval(Duration::ZERO)
+            .with_max_age(Duration::from_millis(50));
+        sync.poll(&mut map);
+
+        let mut peer = MapSync::new("rover-a");
+        let mut peer_map = world();
+        peer_map.insert(Point3::new(1.0, 1.0, 0.0));
+        peer_map.insert(Point3::new(5.02, 5.02, 0.0)); // also seen by rover-b
+        for message in peer.poll(&mut peer_map) {
+            assert_eq!(sync.merge_message("rover-a", &message, &mut map), Some(1));
+        }
+        assert_eq!(map.len(), 2);
+        assert_eq!(
+            sync.provenance(Point3::new(1.0, 1.0, 0.0)).unwrap().origin,
+            "rover-a"
+        );
+        assert_eq!(
+            sync.provenance(Point3::new(5.0, 5.0, 0.0)).unwrap().origin,
+            "rover-b"
+        );
+        assert!(
+            sync.merge_message("rover-a", "meet at dock 3", &mut map)
+                .is_none()
+        );
+
+        // The merged centre is not re-broadcast as our own observation.
+        assert!(sync.poll(&mut map).is_empty());
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(sync.poll(&mut map).is_empty());
+        assert_eq!((map.len(), sync.len()), (1, 1));
+        assert!(sync.provenance(Point3::new(1.0, 1.0, 0.0)).is_none());
+        assert!(map.contains(Point3::new(5.0, 5.0, 0.0)));
+    }
+}
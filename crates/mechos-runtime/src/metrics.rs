@@ -0,0 +1,368 @@
+//! [`RuntimeMetrics`] – OpenTelemetry metrics for the OODA loop.
+//!
+//! A single process-wide [`RuntimeMetrics`] instance (see
+//! [`RuntimeMetrics::global`]) records:
+//!
+//! | Metric | Kind | Recorded by |
+//! |---|---|---|
+//! | `mechos.tick.duration` (s) | histogram | [`AgentLoop::tick`][crate::agent_loop::AgentLoop::tick] |
+//! | `mechos.llm.latency` (s) | histogram | [`LlmDriver`][crate::llm_driver::LlmDriver] |
+//! | `mechos.llm.tokens` | counter | [`LlmDriver`][crate::llm_driver::LlmDriver] |
+//! | `mechos.gate.decisions` / `mechos.gate.rejections` | counter | agent loop and supervisor |
+//! | `mechos.loop_guard.trips` | counter | agent loop |
+//! | `mechos.bus.lagged_events` | counter | agent loop bus drain |
+//!
+//! Every measurement is forwarded to the OpenTelemetry meter installed by
+//! [`init_observability`][crate::telemetry::init_observability] **and**
+//! accumulated in plain atomics so that [`serve_prometheus`] can expose a
+//! Prometheus text-format scrape endpoint without an OTLP collector.
+//!
+//! The gate rejection rate is derived at query time as
+//! `mechos_gate_rejections_total / mechos_gate_decisions_total`.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! # async fn run() -> Result<(), mechos_types::MechError> {
+//! let _guard = mechos_runtime::telemetry::init_observability("mechos");
+//! mechos_runtime::metrics::serve_prometheus("127.0.0.1:9464".parse().unwrap()).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::net::SocketAddr;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use mechos_types::MechError;
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::{Counter, Histogram, Meter};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tracing::{info, warn};
+
+/// Maximum number of request bytes read from a scrape client.  Only the
+/// request line is inspected; anything beyond this is ignored.
+const MAX_SCRAPE_REQUEST_BYTES: usize = 1024;
+
+/// Content type of the Prometheus text exposition format.
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+static GLOBAL: OnceLock<RuntimeMetrics> = OnceLock::new();
+
+// ─────────────────────────────────────────────────────────────────────────────
+// RuntimeMetrics
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Process-wide runtime metrics.
+pub struct RuntimeMetrics {
+    tick_duration: Histogram<f64>,
+    llm_latency: Histogram<f64>,
+    llm_tokens: Counter<u64>,
+    gate_decisions: Counter<u64>,
+    gate_rejections: Counter<u64>,
+    loop_guard_trips: Counter<u64>,
+    bus_lagged_events: Counter<u64>,
+
+    // Prometheus-side accumulators.
+    tick_count: AtomicU64,
+    tick_micros_sum: AtomicU64,
+    llm_calls: AtomicU64,
+    llm_micros_sum: AtomicU64,
+    llm_tokens_total: AtomicU64,
+    gate_decisions_total: AtomicU64,
+    gate_rejections_total: AtomicU64,
+    loop_guard_trips_total: AtomicU64,
+    bus_lagged_total: AtomicU64,
+}
+
+impl RuntimeMetrics {
+    /// Return the process-wide instance, creating it on first use.
+    ///
+    /// Instruments are bound to the global meter provider at creation time,
+    /// so [`init_observability`][crate::telemetry::init_observability] must
+    /// run before the first call for OTLP export to work.  The Prometheus
+    /// accumulators work regardless.
+    pub fn global() -> &'static RuntimeMetrics {
+        GLOBAL.get_or_init(|| Self::new(&opentelemetry::global::meter("mechos")))
+    }
+
+    fn new(meter: &Meter) -> Self {
+        Self {
+            tick_duration: meter
+                .f64_histogram("mechos.tick.duration")
+                .with_description("Duration of one OODA tick")
+                .with_unit("s")
+                .build(),
+            llm_latency: meter
+                .f64_histogram("mechos.llm.latency")
+                .with_description("Latency of one LLM completion request")
+                .with_unit("s")
+                .build(),
+            llm_tokens: meter
+                .u64_counter("mechos.llm.tokens")
+                .with_description("Estimated prompt + reply tokens spent")
+                .build(),
+            gate_decisions: meter
+                .u64_counter("mechos.gate.decisions")
+                .with_description("Intents submitted to the kernel gate")
+                .build(),
+            gate_rejections: meter
+                .u64_counter("mechos.gate.rejections")
+                .with_description("Intents rejected by the kernel gate")
+                .build(),
+            loop_guard_trips: meter
+                .u64_counter("mechos.loop_guard.trips")
+                .with_description("Times the loop guard detected repetitive LLM output")
+                .build(),
+            bus_lagged_events: meter
+                .u64_counter("mechos.bus.lagged_events")
+                .with_description("Bus events dropped because a subscriber lagged")
+                .build(),
+            tick_count: AtomicU64::new(0),
+            tick_micros_sum: AtomicU64::new(0),
+            llm_calls: AtomicU64::new(0),
+            llm_micros_sum: AtomicU64::new(0),
+            llm_tokens_total: AtomicU64::new(0),
+            gate_decisions_total: AtomicU64::new(0),
+            gate_rejections_total: AtomicU64::new(0),
+            loop_guard_trips_total: AtomicU64::new(0),
+            bus_lagged_total: AtomicU64::new(0),
+        }
+    }
+
+    /// Record the wall-clock duration of one OODA tick.
+    pub fn record_tick(&self, duration: Duration) {
+        self.tick_duration.record(duration.as_secs_f64(), &[]);
+        self.tick_count.fetch_add(1, Ordering::Relaxed);
+        self.tick_micros_sum
+            .fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    /// Record one successful LLM completion and the tokens it consumed.
+    pub fn record_llm_call(&self, latency: Duration, tokens: u64) {
+        self.llm_latency.record(latency.as_secs_f64(), &[]);
+        self.llm_tokens.add(tokens, &[]);
+        self.llm_calls.fetch_add(1, Ordering::Relaxed);
+        self.llm_micros_sum
+            .fetch_add(latency.as_micros() as u64, Ordering::Relaxed);
+        self.llm_tokens_total.fetch_add(tokens, Ordering::Relaxed);
+    }
+
+    /// Record one kernel gate decision for `agent_id`.
+    pub fn record_gate_decision(&self, agent_id: &str, approved: bool) {
+        let attrs = [KeyValue::new("agent_id", agent_id.to_string())];
+        self.gate_decisions.add(1, &attrs);
+        self.gate_decisions_total.fetch_add(1, Ordering::Relaxed);
+        if !approved {
+            self.gate_rejections.add(1, &attrs);
+            self.gate_rejections_total.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Record one loop-guard trip.
+    pub fn record_loop_guard_trip(&self) {
+        self.loop_guard_trips.add(1, &[]);
+        self.loop_guard_trips_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record `skipped` bus events lost to subscriber lag.
+    pub fn record_bus_lag(&self, skipped: u64) {
+        self.bus_lagged_events.add(skipped, &[]);
+        self.bus_lagged_total.fetch_add(skipped, Ordering::Relaxed);
+    }
+
+    /// Render all accumulated metrics in the Prometheus text exposition
+    /// format.
+    pub fn render_prometheus(&self) -> String {
+        let load = |a: &AtomicU64| a.load(Ordering::Relaxed);
+        let micros_to_secs = |a: &AtomicU64| load(a) as f64 / 1_000_000.0;
+
+        let mut out = String::new();
+        let mut summary = |name: &str, help: &str, sum: f64, count: u64| {
+            out.push_str(&format!(
+                "# HELP {name} {help}\n# TYPE {name} summary\n{name}_sum {sum}\n{name}_count {count}\n"
+            ));
+        };
+        summary(
+            "mechos_tick_duration_seconds",
+            "Duration of one OODA tick.",
+            micros_to_secs(&self.tick_micros_sum),
+            load(&self.tick_count),
+        );
+        summary(
+            "mechos_llm_latency_seconds",
+            "Latency of one LLM completion request.",
+            micros_to_secs(&self.llm_micros_sum),
+            load(&self.llm_calls),
+        );
+
+        let counters = [
+            (
+                "mechos_llm_tokens_total",
+                "Estimated prompt + reply tokens spent.",
+                &self.llm_tokens_total,
+            ),
+            (
+                "mechos_gate_decisions_total",
+                "Intents submitted to the kernel gate.",
+                &self.gate_decisions_total,
+            ),
+            (
+                "mechos_gate_rejections_total",
+                "Intents rejected by the kernel gate.",
+                &self.gate_rejections_total,
+            ),
+            (
+                "mechos_loop_guard_trips_total",
+                "Times the loop guard detected repetitive LLM output.",
+                &self.loop_guard_trips_total,
+            ),
+            (
+                "mechos_bus_lagged_events_total",
+                "Bus events dropped because a subscriber lagged.",
+                &self.bus_lagged_total,
+            ),
+        ];
+        for (name, help, value) in counters {
+            out.push_str(&format!(
+                "# HELP {name} {help}\n# TYPE {name} counter\n{name} {}\n",
+                load(value)
+            ));
+        }
+        out
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Prometheus scrape endpoint
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Serve `GET /metrics` in the Prometheus text format on `addr`.
+///
+/// Intended for deployments without an OTLP collector.  Runs until the
+/// enclosing task is cancelled.
+///
+/// # Errors
+///
+/// Returns [`MechError::Serialization`] if the address cannot be bound.
+pub async fn serve_prometheus(addr: SocketAddr) -> Result<(), MechError> {
+    let listener = TcpListener::bind(addr)
+        .await
+        .map_err(|e| MechError::Serialization(format!("Prometheus bind failed on {addr}: {e}")))?;
+    info!(%addr, "Prometheus metrics endpoint listening");
+    serve_listener(listener, RuntimeMetrics::global()).await;
+    Ok(())
+}
+
+async fn serve_listener(listener: TcpListener, metrics: &'static RuntimeMetrics) {
+    loop {
+        let (mut stream, peer) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                warn!(error = %e, "Prometheus endpoint accept failed");
+                continue;
+            }
+        };
+        tokio::spawn(async move {
+            let mut buf = [0u8; MAX_SCRAPE_REQUEST_BYTES];
+            let n = match stream.read(&mut buf).await {
+                Ok(n) => n,
+                Err(e) => {
+                    warn!(%peer, error = %e, "Prometheus scrape read failed");
+                    return;
+                }
+            };
+            let request = String::from_utf8_lossy(&buf[..n]);
+            let path = request.split_whitespace().nth(1).unwrap_or("");
+            let (status, body) = if path == "/metrics" {
+                ("200 OK", metrics.render_prometheus())
+            } else {
+                ("404 Not Found", String::from("not found\n"))
+            };
+            let response = format!(
+                "HTTP/1.1 {status}\r\nContent-Type: {PROMETHEUS_CONTENT_TYPE}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            if let Err(e) = stream.write_all(response.as_bytes()).await {
+                warn!(%peer, error = %e, "Prometheus scrape write failed");
+            }
+        });
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fresh() -> RuntimeMetrics {
+        RuntimeMetrics::new(&opentelemetry::global::meter("test"))
+    }
+
+    #[test]
+    fn render_prometheus_reports_accumulated_values() {
+        let m = fresh();
+        m.record_tick(Duration::from_millis(250));
+        m.record_tick(Duration::from_millis(250));
+        m.record_llm_call(Duration::from_millis(1500), 120);
+        m.record_gate_decision("agent", true);
+        m.record_gate_decision("agent", false);
+        m.record_loop_guard_trip();
+        m.record_bus_lag(7);
+
+        let text = m.render_prometheus();
+        assert!(text.contains("mechos_tick_duration_seconds_sum 0.5\n"));
+        assert!(text.contains("mechos_tick_duration_seconds_count 2\n"));
+        assert!(text.contains("mechos_llm_latency_seconds_sum 1.5\n"));
+        assert!(text.contains("mechos_llm_tokens_total 120\n"));
+        assert!(text.contains("mechos_gate_decisions_total 2\n"));
+        assert!(text.contains("mechos_gate_rejections_total 1\n"));
+        assert!(text.contains("mechos_loop_guard_trips_total 1\n"));
+        assert!(text.contains("mechos_bus_lagged_events_total 7\n"));
+    }
+
+    #[test]
+    fn render_prometheus_declares_metric_types() {
+        let text = fresh().render_prometheus();
+        assert!(text.contains("# TYPE mechos_tick_duration_seconds summary"));
+        assert!(text.contains("# TYPE mechos_gate_rejections_total counter"));
+    }
+
+    #[tokio::test]
+    async fn scrape_endpoint_serves_metrics_and_404s_other_paths() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve_listener(listener, RuntimeMetrics::global()));
+
+        let fetch = |path: &'static str| async move {
+            let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+            stream
+                .write_all(format!("GET {path} HTTP/1.1\r\nHost: x\r\n\r\n").as_bytes())
+                .await
+                .unwrap();
+            let mut resp = String::new();
+            stream.read_to_string(&mut resp).await.unwrap();
+            resp
+        };
+
+        let ok = fetch("/metrics").await;
+        assert!(ok.starts_with("HTTP/1.1 200 OK"));
+        assert!(ok.contains(PROMETHEUS_CONTENT_TYPE));
+        assert!(ok.contains("mechos_gate_decisions_total"));
+
+        let missing = fetch("/").await;
+        assert!(missing.starts_with("HTTP/1.1 404"));
+    }
+
+    #[tokio::test]
+    async fn serve_prometheus_reports_bind_failure() {
+        let taken = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let result = serve_prometheus(taken.local_addr().unwrap()).await;
+        assert!(matches!(result, Err(MechError::Serialization(_))));
+    }
+}
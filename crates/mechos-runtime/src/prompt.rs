@@ -0,0 +1,396 @@
+//! [`PromptTemplate`] – customisable system prompts for the agent loop.
+//!
+//! Different robots need very different personas and constraints, so the
+//! system prompt built on every OODA tick is rendered from a template rather
+//! than hard-coded.  Templates use a small handlebars-style syntax:
+//!
+//! * `{{name}}` – replaced by the value of placeholder `name`;
+//! * `{{#if name}} … {{/if}}` – the enclosed block is rendered only when
+//!   `name` has a non-empty value.  Blocks may be nested.
+//!
+//! # Placeholders
+//!
+//! | Placeholder | Value |
+//! |---|---|
+//! | `role` | The agent's role prompt (empty when none is configured). |
+//! | `goal` | The current mission goal (empty when none is set). |
+//! | `capabilities` | One `- <capability>` line per granted capability. |
+//! | `position` | `x=…, y=…` from the fused state. |
+//! | `heading` | Heading in radians. |
+//! | `velocity` | `vx=…, vy=…` from the fused state. |
+//! | `path` | `CLEAR` or `BLOCKED`. |
+//! | `nearest_obstacle` | Distance and bearing of the closest mapped obstacle, e.g. `0.80 m at bearing 45°`, plus its class when detected, e.g. `(person, 85% confident)` (empty when the map is empty). |
+//! | `object_locations` | Most likely location of up to five remembered objects, one `- <object>: <location> (<p>%, seen <n> s ago)` line each (empty when none is known). |
+//! | `procedures` | Intent sequences that achieved goals similar to the current one, one `- Last time you did "<goal>", this sequence worked (<n>×): <steps>` line each (empty when none is known). |
+//! | `memories` | Most recent episodic memories, one per line. |
+//! | `skills` | The registered-skills section (empty when none). |
+//!
+//! Every template must reference [`REQUIRED_PLACEHOLDERS`] so the model is
+//! never deprived of the safety-relevant state, and may not reference
+//! unknown placeholders (which usually indicates a typo).
+//!
+//! # Loading
+//!
+//! Named templates live in `~/.mechos/prompts/<name>.hbs` and are loaded with
+//! [`PromptTemplate::load_named`] (or via
+//! [`AgentLoopConfig::prompt_template`][crate::agent_loop::AgentLoopConfig::prompt_template]).
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::collections::HashMap;
+//! use mechos_runtime::prompt::PromptTemplate;
+//!
+//! let template = PromptTemplate::parse(
+//!     "You are a warehouse forklift.\n{{#if goal}}Goal: {{goal}}\n{{/if}}\
+//!      Position: {{position}}\nPath: {{path}}\nMemories:\n{{memories}}\n",
+//! )
+//! .unwrap();
+//!
+//! let vars = HashMap::from([
+//!     ("position", "x=1.000, y=2.000".to_string()),
+//!     ("path", "CLEAR".to_string()),
+//!     ("memories", "(none)".to_string()),
+//! ]);
+//! let prompt = template.render(&vars);
+//! assert!(prompt.contains("Position: x=1.000, y=2.000"));
+//! assert!(!prompt.contains("Goal:"));
+//! ```
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+/// Every placeholder a template may reference.
+pub const PLACEHOLDERS: &[&str] = &[
+    "role",
+    "goal",
+    "capabilities",
+    "position",
+    "heading",
+    "velocity",
+    "path",
+    "nearest_obstacle",
+    "object_locations",
+    "procedures",
+    "memories",
+    "skills",
+];
+
+/// Placeholders every template must reference.
+pub const REQUIRED_PLACEHOLDERS: &[&str] = &["position", "path", "memories"];
+
+/// File extension of named templates in [`prompts_dir`].
+pub const TEMPLATE_EXTENSION: &str = "hbs";
+
+/// The built-in system prompt used when no custom template is configured.
+pub const DEFAULT_TEMPLATE: &str = "\
+You are the cognitive brain of a physical robot.
+{{#if role}}## Role
+{{role}}
+{{/if}}{{#if goal}}## Goal
+{{goal}}
+{{/if}}{{#if capabilities}}## Capabilities
+{{capabilities}}
+{{/if}}Output ONLY a single valid JSON object matching the HardwareIntent schema.
+## System State
+Position: {{position}}
+Heading:  {{heading}} rad
+Velocity: {{velocity}}
+Path: {{path}}
+{{#if nearest_obstacle}}Nearest obstacle: {{nearest_obstacle}}
+{{/if}}{{#if object_locations}}## Known Object Locations
+{{object_locations}}
+{{/if}}{{#if procedures}}## Learned Procedures
+{{procedures}}
+{{/if}}## Recent Memories
+{{memories}}
+{{skills}}";
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Error type
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Errors that can arise while loading or validating a prompt template.
+#[derive(Error, Debug)]
+pub enum PromptError {
+    /// The template file could not be read.
+    #[error("failed to read prompt template '{path}': {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// A required placeholder is not referenced anywhere in the template.
+    #[error("prompt template is missing required placeholder '{{{{{0}}}}}'")]
+    MissingPlaceholder(String),
+    /// The template references a placeholder that does not exist.
+    #[error("prompt template references unknown placeholder '{{{{{0}}}}}'")]
+    UnknownPlaceholder(String),
+    /// A `{{` tag is malformed, or `{{#if}}` / `{{/if}}` are unbalanced.
+    #[error("malformed prompt template: {0}")]
+    Syntax(String),
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// PromptTemplate
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Text(String),
+    Var(String),
+    If(String, Vec<Segment>),
+}
+
+/// A parsed and validated system-prompt template.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromptTemplate {
+    segments: Vec<Segment>,
+}
+
+impl Default for PromptTemplate {
+    fn default() -> Self {
+        Self::parse(DEFAULT_TEMPLATE).expect("built-in prompt template must be valid")
+    }
+}
+
+impl PromptTemplate {
+    /// Parse and validate a template.
+    ///
+    /// # Errors
+    ///
+    /// - [`PromptError::Syntax`] – unterminated tag or unbalanced `{{#if}}`.
+    /// - [`PromptError::UnknownPlaceholder`] – a tag names an unknown
+    ///   placeholder.
+    /// - [`PromptError::MissingPlaceholder`] – one of
+    ///   [`REQUIRED_PLACEHOLDERS`] is not referenced.
+    pub fn parse(source: &str) -> Result<Self, PromptError> {
+        // Stack of open blocks: (condition, segments so far).  The bottom
+        // entry is the template root and has no condition.
+        let mut stack: Vec<(Option<String>, Vec<Segment>)> = vec![(None, Vec::new())];
+        let mut referenced: Vec<String> = Vec::new();
+        let mut rest = source;
+
+        while let Some(start) = rest.find("{{") {
+            if start > 0 {
+                Self::top(&mut stack).push(Segment::Text(rest[..start].to_string()));
+            }
+            let after = &rest[start + 2..];
+            let end = after
+                .find("}}")
+                .ok_or_else(|| PromptError::Syntax("unterminated '{{' tag".to_string()))?;
+            let tag = after[..end].trim();
+            rest = &after[end + 2..];
+
+            if let Some(name) = tag.strip_prefix("#if") {
+                let name = Self::checked_name(name.trim())?;
+                referenced.push(name.clone());
+                stack.push((Some(name), Vec::new()));
+            } else if tag == "/if" {
+                if stack.len() == 1 {
+                    return Err(PromptError::Syntax("'{{/if}}' without matching '{{#if}}'".to_string()));
+                }
+                let (cond, body) = stack.pop().expect("stack holds an open block");
+                Self::top(&mut stack).push(Segment::If(cond.expect("non-root block"), body));
+            } else {
+                let name = Self::checked_name(tag)?;
+                referenced.push(name.clone());
+                Self::top(&mut stack).push(Segment::Var(name));
+            }
+        }
+        if !rest.is_empty() {
+            Self::top(&mut stack).push(Segment::Text(rest.to_string()));
+        }
+        if stack.len() != 1 {
+            return Err(PromptError::Syntax("unclosed '{{#if}}' block".to_string()));
+        }
+
+        for required in REQUIRED_PLACEHOLDERS {
+            if !referenced.iter().any(|r| r == required) {
+                return Err(PromptError::MissingPlaceholder(required.to_string()));
+            }
+        }
+
+        let (_, segments) = stack.pop().expect("root block");
+        Ok(Self { segments })
+    }
+
+    /// Read and validate a template from `path`.
+    ///
+    /// # Errors
+    ///
+    /// [`PromptError::Io`] if the file cannot be read, or any error from
+    /// [`PromptTemplate::parse`].
+    pub fn load(path: &Path) -> Result<Self, PromptError> {
+        let source = std::fs::read_to_string(path).map_err(|source| PromptError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::parse(&source)
+    }
+
+    /// Load `~/.mechos/prompts/<name>.hbs`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`PromptTemplate::load`].
+    pub fn load_named(name: &str) -> Result<Self, PromptError> {
+        Self::load(&prompts_dir().join(format!("{name}.{TEMPLATE_EXTENSION}")))
+    }
+
+    /// Render the template.  Placeholders absent from `vars` render as the
+    /// empty string.
+    pub fn render(&self, vars: &HashMap<&str, String>) -> String {
+        let mut out = String::new();
+        Self::render_into(&self.segments, vars, &mut out);
+        out
+    }
+
+    // -------------------------------------------------------------------------
+    // Private helpers
+    // -------------------------------------------------------------------------
+
+    fn render_into(segments: &[Segment], vars: &HashMap<&str, String>, out: &mut String) {
+        for segment in segments {
+            match segment {
+                Segment::Text(text) => out.push_str(text),
+                Segment::Var(name) => {
+                    if let Some(value) = vars.get(name.as_str()) {
+                        out.push_str(value);
+                    }
+                }
+                Segment::If(name, body) => {
+                    if vars.get(name.as_str()).is_some_and(|v| !v.is_empty()) {
+                        Self::render_into(body, vars, out);
+                    }
+                }
+            }
+        }
+    }
+
+    fn top(stack: &mut [(Option<String>, Vec<Segment>)]) -> &mut Vec<Segment> {
+        &mut stack.last_mut().expect("stack is never empty").1
+    }
+
+    fn checked_name(name: &str) -> Result<String, PromptError> {
+        if PLACEHOLDERS.contains(&name) {
+            Ok(name.to_string())
+        } else {
+            Err(PromptError::UnknownPlaceholder(name.to_string()))
+        }
+    }
+}
+
+/// Directory holding named prompt templates (`~/.mechos/prompts`).
+pub fn prompts_dir() -> PathBuf {
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(home).join(".mechos").join("prompts")
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_vars() -> HashMap<&'static str, String> {
+        HashMap::from([
+            ("position", "x=1.000, y=2.000".to_string()),
+            ("heading", "0.500".to_string()),
+            ("velocity", "vx=0.000, vy=0.000".to_string()),
+            ("path", "CLEAR".to_string()),
+            ("memories", "(none)".to_string()),
+        ])
+    }
+
+    #[test]
+    fn default_template_renders_state() {
+        let prompt = PromptTemplate::default().render(&state_vars());
+        assert!(prompt.starts_with("You are the cognitive brain of a physical robot.\n"));
+        assert!(prompt.contains("Position: x=1.000, y=2.000\n"));
+        assert!(prompt.contains("Heading:  0.500 rad\n"));
+        assert!(prompt.contains("## Recent Memories\n(none)\n"));
+        assert!(!prompt.contains("## Role"));
+        assert!(!prompt.contains("{{"));
+    }
+
+    #[test]
+    fn conditional_block_renders_only_with_value() {
+        let template = PromptTemplate::default();
+        let mut vars = state_vars();
+        vars.insert("goal", "Deliver the parcel to dock B".to_string());
+        let prompt = template.render(&vars);
+        assert!(prompt.contains("## Goal\nDeliver the parcel to dock B\n"));
+
+        vars.insert("goal", String::new());
+        assert!(!template.render(&vars).contains("## Goal"));
+    }
+
+    #[test]
+    fn nested_blocks_are_supported() {
+        let template = PromptTemplate::parse(
+            "{{#if role}}R:{{role}}{{#if goal}} G:{{goal}}{{/if}}{{/if}}|{{position}}{{path}}{{memories}}",
+        )
+        .unwrap();
+        let mut vars = state_vars();
+        vars.insert("goal", "g".to_string());
+        assert!(template.render(&vars).starts_with('|'));
+        vars.insert("role", "r".to_string());
+        assert!(template.render(&vars).starts_with("R:r G:g|"));
+    }
+
+    #[test]
+    fn missing_required_placeholder_is_rejected() {
+        let err = PromptTemplate::parse("Position: {{position}}\nPath: {{path}}").unwrap_err();
+        assert!(matches!(err, PromptError::MissingPlaceholder(p) if p == "memories"));
+    }
+
+    #[test]
+    fn unknown_placeholder_is_rejected() {
+        let err =
+            PromptTemplate::parse("{{position}} {{path}} {{memories}} {{posiiton}}").unwrap_err();
+        assert!(matches!(err, PromptError::UnknownPlaceholder(p) if p == "posiiton"));
+    }
+
+    #[test]
+    fn unbalanced_blocks_are_rejected() {
+        let body = "{{position}}{{path}}{{memories}}";
+        assert!(matches!(
+            PromptTemplate::parse(&format!("{{{{#if goal}}}}{body}")),
+            Err(PromptError::Syntax(_))
+        ));
+        assert!(matches!(
+            PromptTemplate::parse(&format!("{body}{{{{/if}}}}")),
+            Err(PromptError::Syntax(_))
+        ));
+        assert!(matches!(
+            PromptTemplate::parse(&format!("{body}{{{{goal")),
+            Err(PromptError::Syntax(_))
+        ));
+    }
+
+    #[test]
+    fn load_reads_template_from_file() {
+        let dir = std::env::temp_dir().join(format!("mechos-prompt-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("forklift.hbs");
+        std::fs::write(&path, "Forklift. {{position}} {{path}} {{memories}}").unwrap();
+
+        let prompt = PromptTemplate::load(&path).unwrap().render(&state_vars());
+        assert_eq!(prompt, "Forklift. x=1.000, y=2.000 CLEAR (none)");
+
+        let missing = PromptTemplate::load(&dir.join("absent.hbs"));
+        assert!(matches!(missing, Err(PromptError::Io { .. })));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn prompts_dir_is_under_mechos_home() {
+        assert!(prompts_dir().ends_with(".mechos/prompts"));
+    }
+}
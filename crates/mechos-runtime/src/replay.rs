@@ -0,0 +1,449 @@
+//! [`ReplayDriver`] – re-run a recorded incident through an [`AgentLoop`].
+//!
+//! A replay reads a previously recorded event log (one JSON-serialised
+//! [`Event`] per line, or a [`BusRecorder`] journal directory) and feeds the
+//! sensor and operator events it contains into an [`AgentLoop`], ticking the
+//! loop on a simulated clock:
+//!
+//! | Recorded payload | Fed as |
+//! |---|---|
+//! | [`EventPayload::Telemetry`] | [`AgentLoop::update_odometry_at`], at its recorded stamp |
+//! | [`EventPayload::LidarScan`] | published on the loop's bus (octree insert) |
+//! | [`EventPayload::HumanResponse`] | [`AgentLoop::submit_human_answer`] |
+//! | [`EventPayload::AgentModeToggle`], dashboard overrides | published on the loop's bus |
+//!
+//! All other payloads (faults, the agent's own thoughts, peer traffic) are
+//! skipped.
+//!
+//! The loop is ticked every [`ReplayDriver::with_tick_interval`] of simulated
+//! time.  With a speed of `1.0` the replay sleeps in real time between
+//! ticks; higher values accelerate it and [`ReplayDriver::UNPACED`] runs it
+//! as fast as possible.
+//!
+//! The LLM can stay live, or be mocked by installing
+//! [`LlmDriver::scripted`][crate::llm_driver::LlmDriver::scripted] (e.g. with
+//! the responses of a recorded transcript) via
+//! [`AgentLoop::set_llm_driver`].  Every tick outcome is collected in the
+//! [`ReplayReport`], which makes replays suitable for regression-testing
+//! safety rules against real incidents.
+//!
+//! [`BusRecorder`]: mechos_middleware::BusRecorder
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use mechos_runtime::llm_driver::LlmDriver;
+//! use mechos_runtime::replay::ReplayDriver;
+//! use mechos_runtime::{AgentLoop, AgentLoopConfig};
+//!
+//! # async fn run() -> Result<(), mechos_types::MechError> {
+//! let mut agent = AgentLoop::new(AgentLoopConfig::default())?;
+//! agent.set_llm_driver(
+//!     LlmDriver::scripted(vec![r#"{"action":"Drive","payload":{"linear_velocity":0.5,"angular_velocity":0.0}}"#.to_string()])
+//!         .expect("scripted driver"),
+//! );
+//!
+//! let report = ReplayDriver::from_jsonl("incident-2024-05-01.jsonl")?
+//!     .with_speed(ReplayDriver::UNPACED)
+//!     .run(&mut agent)
+//!     .await;
+//! assert_eq!(report.approved().count(), 0, "the unsafe Drive must be rejected");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use mechos_middleware::journal::JournalReader;
+use mechos_perception::fusion::OdometryData;
+use mechos_types::{Event, EventPayload, HardwareIntent, MechError};
+use tracing::{debug, instrument};
+
+use crate::agent_loop::AgentLoop;
+
+/// Default simulated time between two agent ticks (matches the CLI's 10 Hz
+/// tick rate).
+pub const DEFAULT_REPLAY_TICK_INTERVAL: Duration = Duration::from_millis(100);
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Report
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Outcome of one agent tick during a replay.
+#[derive(Debug)]
+pub struct ReplayTick {
+    /// Simulated time since the first recorded event.
+    pub at: Duration,
+    /// What [`AgentLoop::tick`] returned.
+    pub result: Result<HardwareIntent, MechError>,
+}
+
+/// Everything observed while replaying an event log.
+#[derive(Debug, Default)]
+pub struct ReplayReport {
+    /// Number of recorded events fed into the agent loop.
+    pub events_fed: usize,
+    /// Number of recorded events that were skipped.
+    pub events_skipped: usize,
+    /// One entry per agent tick, in order.
+    pub ticks: Vec<ReplayTick>,
+}
+
+impl ReplayReport {
+    /// Intents that passed the gate and were dispatched.
+    pub fn approved(&self) -> impl Iterator<Item = &HardwareIntent> {
+        self.ticks.iter().filter_map(|t| t.result.as_ref().ok())
+    }
+
+    /// Ticks that produced no intent, with their errors.
+    pub fn rejections(&self) -> impl Iterator<Item = &ReplayTick> {
+        self.ticks.iter().filter(|t| t.result.is_err())
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// ReplayDriver
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Feeds a recorded event log into an [`AgentLoop`] on a simulated clock.
+#[derive(Debug, Clone)]
+pub struct ReplayDriver {
+    /// Recorded events, ordered by timestamp.
+    events: Vec<Event>,
+    speed: f64,
+    tick_interval: Duration,
+}
+
+impl ReplayDriver {
+    /// Speed value that disables pacing: ticks run back-to-back.
+    pub const UNPACED: f64 = f64::INFINITY;
+
+    /// Build a replay from in-memory events.  Events are sorted by
+    /// timestamp; the replay runs at original speed.
+    pub fn new(mut events: Vec<Event>) -> Self {
+        events.sort_by_key(|e| e.timestamp);
+        Self {
+            events,
+            speed: 1.0,
+            tick_interval: DEFAULT_REPLAY_TICK_INTERVAL,
+        }
+    }
+
+    /// Load an event log containing one JSON-serialised [`Event`] per line.
+    /// Blank lines are ignored.
+    ///
+    /// # Errors
+    ///
+    /// - [`MechError::Serialization`] – the file cannot be read.
+    /// - [`MechError::Parsing`] – a line is not a valid [`Event`].
+    pub fn from_jsonl(path: impl AsRef<Path>) -> Result<Self, MechError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            MechError::Serialization(format!("failed to read event log '{}': {e}", path.display()))
+        })?;
+        let events = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                serde_json::from_str::<Event>(line).map_err(|e| {
+                    MechError::Parsing(format!("{}:{}: invalid event: {e}", path.display(), i + 1))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::new(events))
+    }
+
+    /// Load every event of a [`BusRecorder`] journal directory, in either
+    /// format.
+    ///
+    /// # Errors
+    ///
+    /// - [`MechError::Serialization`] – the journal cannot be read.
+    /// - [`MechError::Parsing`] – a record is not a valid entry.
+    pub fn from_journal(dir: impl AsRef<Path>) -> Result<Self, MechError> {
+        Ok(Self::new(JournalReader::open(dir)?.events()?))
+    }
+
+    /// Playback speed multiplier: `1.0` replays at the recorded pace, `10.0`
+    /// ten times faster, and [`Self::UNPACED`] (or any non-positive value)
+    /// as fast as possible.
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Simulated time between two agent ticks (default
+    /// [`DEFAULT_REPLAY_TICK_INTERVAL`]).  Also used as the `dt` passed to
+    /// [`AgentLoop::tick`].  Clamped to at least 1 ms.
+    pub fn with_tick_interval(mut self, interval: Duration) -> Self {
+        self.tick_interval = interval.max(Duration::from_millis(1));
+        self
+    }
+
+    /// Number of recorded events.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// `true` if the log contains no events.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Replay the whole log into `agent`.
+    ///
+    /// At every simulated tick all events recorded up to that instant are
+    /// fed first, then the loop is ticked once.  The replay ends with the
+    /// tick that follows the last recorded event.
+    #[instrument(name = "replay.run", skip_all, fields(events = self.events.len(), speed = self.speed))]
+    pub async fn run(&self, agent: &mut AgentLoop) -> ReplayReport {
+        let mut report = ReplayReport::default();
+        let Some(first) = self.events.first() else {
+            return report;
+        };
+        let start = first.timestamp;
+        let offset = |e: &Event| (e.timestamp - start).to_std().unwrap_or_default();
+        let dt = self.tick_interval.as_secs_f32();
+        let pause = (self.speed.is_finite() && self.speed > 0.0)
+            .then(|| self.tick_interval.div_f64(self.speed));
+
+        let mut next = 0;
+        let mut clock = Duration::ZERO;
+        loop {
+            while let Some(event) = self.events.get(next).filter(|e| offset(e) <= clock) {
+                if Self::feed(agent, event) {
+                    report.events_fed += 1;
+                } else {
+                    report.events_skipped += 1;
+                }
+                next += 1;
+            }
+
+            let result = agent.tick(dt).await;
+            debug!(at_ms = clock.as_millis() as u64, ok = result.is_ok(), "replay tick");
+            report.ticks.push(ReplayTick { at: clock, result });
+
+            if next >= self.events.len() {
+                break;
+            }
+            clock += self.tick_interval;
+            if let Some(pause) = pause {
+                tokio::time::sleep(pause).await;
+            }
+        }
+        report
+    }
+
+    // -------------------------------------------------------------------------
+    // Private helpers
+    // -------------------------------------------------------------------------
+
+    /// Feed one recorded event into `agent`.  Returns `false` if the event
+    /// type is not replayed.
+    fn feed(agent: &mut AgentLoop, event: &Event) -> bool {
+        match &event.payload {
+            EventPayload::Telemetry(t) => {
+                agent.update_odometry_at(
+                    OdometryData {
+                        position_x: t.position_x.get(),
+                        position_y: t.position_y.get(),
+                        heading_rad: t.heading_rad,
+                        velocity_x: 0.0,
+                        velocity_y: 0.0,
+                    },
+                    SystemTime::from(t.stamp.unwrap_or(event.timestamp)),
+                );
+                true
+            }
+            EventPayload::HumanResponse(answer) => {
+                agent.submit_human_answer(answer);
+                true
+            }
+            EventPayload::LidarScan { .. } | EventPayload::AgentModeToggle { .. } => {
+                let _ = agent.bus().publish(event.clone());
+                true
+            }
+            EventPayload::AdapterCommand { .. }
+                if event.source == "mechos-middleware::dashboard_override" =>
+            {
+                let _ = agent.bus().publish(event.clone());
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent_loop::AgentLoopConfig;
+    use crate::llm_driver::LlmDriver;
+    use chrono::{TimeDelta, Utc};
+    use mechos_types::{FaultCode, Meters, TelemetryData};
+    use uuid::Uuid;
+
+    const DRIVE_FORWARD: &str =
+        r#"{"action":"Drive","payload":{"linear_velocity":0.5,"angular_velocity":0.0}}"#;
+
+    fn event_at(ms: i64, source: &str, payload: EventPayload) -> Event {
+        Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now() + TimeDelta::milliseconds(ms),
+            source: source.to_string(),
+            payload,
+            trace_id: None,
+            correlation_id: None,
+        }
+    }
+
+    fn scripted_agent(replies: usize) -> AgentLoop {
+        // Identical scripted replies would otherwise trip the loop guard.
+        let mut agent = AgentLoop::new(AgentLoopConfig {
+            loop_guard_threshold: 100,
+            ..Default::default()
+        })
+        .unwrap();
+        agent.set_llm_driver(
+            LlmDriver::scripted(std::iter::repeat_n(DRIVE_FORWARD.to_string(), replies)).unwrap(),
+        );
+        agent
+    }
+
+    fn incident() -> Vec<Event> {
+        vec![
+            event_at(
+                0,
+                "mechos-middleware::ros2/odom",
+                EventPayload::Telemetry(TelemetryData {
+                    position_x: Meters(0.0),
+                    position_y: Meters(0.0),
+                    heading_rad: 0.0,
+                    battery_percent: 80,
+                    stamp: None,
+                }),
+            ),
+            // A wall appears 0.2 m ahead of the robot.
+            event_at(
+                250,
+                "mechos-middleware::ros2/scan",
+                EventPayload::LidarScan {
+                    ranges: vec![0.2],
+                    angle_min_rad: 0.0,
+                    angle_increment_rad: 0.0,
+                    stamp: None,
+                },
+            ),
+        ]
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn replay_ticks_until_last_event() {
+        let mut agent = scripted_agent(10);
+        let report = ReplayDriver::new(incident())
+            .with_speed(ReplayDriver::UNPACED)
+            .run(&mut agent)
+            .await;
+
+        assert_eq!(report.events_fed, 2);
+        // Ticks at 0, 100, 200 and 300 ms.
+        assert_eq!(report.ticks.len(), 4);
+        assert_eq!(report.ticks[3].at, Duration::from_millis(300));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn replayed_obstacle_triggers_trajectory_rejection() {
+        let mut agent = scripted_agent(10);
+        let report = ReplayDriver::new(incident())
+            .with_speed(ReplayDriver::UNPACED)
+            .run(&mut agent)
+            .await;
+
+        // Before the scan the path is clear and the Drive is dispatched.
+        assert!(report.ticks[0].result.is_ok());
+        // After the scan the same Drive must be stopped by the trajectory check.
+        let last = &report.ticks.last().unwrap().result;
+        assert!(
+            matches!(last, Err(MechError::HardwareFault { component, .. }) if component == "trajectory_check"),
+            "expected trajectory rejection, got: {last:?}"
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn unreplayed_payloads_are_skipped() {
+        let mut agent = scripted_agent(1);
+        let events = vec![event_at(
+            0,
+            "mechos-hal",
+            EventPayload::HardwareFault {
+                component: "motor".to_string(),
+                code: FaultCode::Unknown,
+                message: "stall".to_string(),
+            },
+        )];
+        let report = ReplayDriver::new(events).run(&mut agent).await;
+        assert_eq!(report.events_skipped, 1);
+        assert_eq!(report.ticks.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn empty_log_produces_empty_report() {
+        let mut agent = scripted_agent(0);
+        let report = ReplayDriver::new(Vec::new()).run(&mut agent).await;
+        assert!(report.ticks.is_empty());
+    }
+
+    #[test]
+    fn from_jsonl_round_trips_events_in_timestamp_order() {
+        let mut events = incident();
+        events.reverse();
+        let log: String = events
+            .iter()
+            .map(|e| serde_json::to_string(e).unwrap() + "\n\n")
+            .collect();
+        let path = std::env::temp_dir().join(format!("mechos-replay-{}.jsonl", Uuid::new_v4()));
+        std::fs::write(&path, log).unwrap();
+
+        let replay = ReplayDriver::from_jsonl(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(replay.len(), 2);
+        assert!(matches!(replay.events[0].payload, EventPayload::Telemetry(_)));
+    }
+
+    #[test]
+    fn from_jsonl_reports_bad_line() {
+        let path = std::env::temp_dir().join(format!("mechos-replay-{}.jsonl", Uuid::new_v4()));
+        std::fs::write(&path, "not json\n").unwrap();
+        let result = ReplayDriver::from_jsonl(&path);
+        std::fs::remove_file(&path).ok();
+        assert!(matches!(result, Err(MechError::Parsing(msg)) if msg.contains(":1:")));
+    }
+
+    #[tokio::test]
+    async fn from_journal_reads_recorded_bus_traffic() {
+        use mechos_middleware::{BusRecorder, EventBus};
+        use std::sync::Arc;
+
+        let dir = std::env::temp_dir().join(format!("mechos-replay-journal-{}", Uuid::new_v4()));
+        let bus = Arc::new(EventBus::default());
+        let recorder = BusRecorder::new(Arc::clone(&bus), &dir).start().unwrap();
+        for event in incident() {
+            bus.publish(event).unwrap();
+        }
+        recorder.stop().await;
+
+        let replay = ReplayDriver::from_journal(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+        assert_eq!(replay.len(), 2);
+        assert!(matches!(
+            replay.events[1].payload,
+            EventPayload::LidarScan { .. }
+        ));
+    }
+}
@@ -0,0 +1,445 @@
+//! [`SkillRegistry`] – named, parameterised skills the LLM can invoke.
+//!
+//! Small local models are far more reliable when they choose *what* to do
+//! ("dock", "scan_room", "pick(object)") than when they emit raw motor
+//! commands.  A [`Skill`] packages such a behaviour behind a name, a
+//! description and a list of string parameters.  Its body is either:
+//!
+//! * an **intent sequence** – a closure that expands the call arguments into
+//!   a list of [`HardwareIntent`]s, executed one per OODA tick, each passing
+//!   through the [`KernelGate`][mechos_kernel::KernelGate] like any other
+//!   intent; or
+//! * a **behavior sub-tree** – a closure that builds a [`BehaviorNode`] which
+//!   is ticked once per OODA tick until it stops returning
+//!   [`NodeStatus::Running`][crate::behavior_tree::NodeStatus::Running].
+//!
+//! When an [`AgentLoop`][crate::agent_loop::AgentLoop] has skills registered,
+//! their names and parameter schemas are listed in the system prompt
+//! ([`SkillRegistry::prompt_section`]) and the LLM may answer with a
+//! [`SkillCall`] instead of a raw intent.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mechos_runtime::skill::{Skill, SkillCall, SkillRegistry};
+//! use mechos_types::HardwareIntent;
+//!
+//! let mut skills = SkillRegistry::new();
+//! skills
+//!     .register(
+//!         Skill::intents("pick", "Close the gripper on an object", |args| {
+//!             vec![HardwareIntent::TriggerRelay {
+//!                 relay_id: format!("gripper_{}", args["object"]),
+//!                 state: true,
+//!             }]
+//!         })
+//!         .with_param("object", "Name of the object to grasp"),
+//!     )
+//!     .unwrap();
+//!
+//! let call: SkillCall =
+//!     serde_json::from_str(r#"{"skill":"pick","args":{"object":"cup"}}"#).unwrap();
+//! assert!(skills.expand(&call).is_ok());
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+
+use mechos_types::HardwareIntent;
+use schemars::{JsonSchema, schema_for};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::behavior_tree::BehaviorNode;
+
+/// Arguments supplied to a skill invocation, keyed by parameter name.
+pub type SkillArgs = HashMap<String, String>;
+
+type IntentBuilder = Box<dyn Fn(&SkillArgs) -> Vec<HardwareIntent> + Send + Sync>;
+type TreeBuilder = Box<dyn Fn(&SkillArgs) -> BehaviorNode + Send + Sync>;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Error type
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Errors that can arise from skill registration or invocation.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum SkillError {
+    /// A skill with the same name is already registered.
+    #[error("skill '{0}' is already registered")]
+    Duplicate(String),
+    /// The requested skill does not exist.
+    #[error("unknown skill '{0}'")]
+    Unknown(String),
+    /// A declared parameter was not supplied.
+    #[error("skill '{skill}' is missing argument '{param}'")]
+    MissingArgument { skill: String, param: String },
+    /// An argument was supplied that the skill does not declare.
+    #[error("skill '{skill}' has no parameter '{param}'")]
+    UnexpectedArgument { skill: String, param: String },
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Skill
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A declared skill parameter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkillParam {
+    pub name: String,
+    pub description: String,
+}
+
+/// How a skill is executed.
+pub enum SkillBody {
+    /// Expands into a sequence of intents, dispatched one per tick.
+    Intents(IntentBuilder),
+    /// Builds a behavior sub-tree that is ticked once per OODA tick.
+    Tree(TreeBuilder),
+}
+
+/// A named, parameterised robot skill.
+pub struct Skill {
+    name: String,
+    description: String,
+    params: Vec<SkillParam>,
+    body: SkillBody,
+}
+
+impl Skill {
+    /// Create a skill that expands into a sequence of [`HardwareIntent`]s.
+    pub fn intents(
+        name: impl Into<String>,
+        description: impl Into<String>,
+        build: impl Fn(&SkillArgs) -> Vec<HardwareIntent> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            description: description.into(),
+            params: Vec::new(),
+            body: SkillBody::Intents(Box::new(build)),
+        }
+    }
+
+    /// Create a skill backed by a behavior sub-tree.
+    pub fn tree(
+        name: impl Into<String>,
+        description: impl Into<String>,
+        build: impl Fn(&SkillArgs) -> BehaviorNode + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            description: description.into(),
+            params: Vec::new(),
+            body: SkillBody::Tree(Box::new(build)),
+        }
+    }
+
+    /// Declare a required string parameter.
+    pub fn with_param(mut self, name: impl Into<String>, description: impl Into<String>) -> Self {
+        self.params.push(SkillParam {
+            name: name.into(),
+            description: description.into(),
+        });
+        self
+    }
+
+    /// The skill's unique name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Human-readable description shown to the LLM.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Declared parameters, in declaration order.
+    pub fn params(&self) -> &[SkillParam] {
+        &self.params
+    }
+
+    /// One-line signature such as `pick(object: string)`.
+    pub fn signature(&self) -> String {
+        let params: Vec<String> = self
+            .params
+            .iter()
+            .map(|p| format!("{}: string", p.name))
+            .collect();
+        format!("{}({})", self.name, params.join(", "))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// SkillCall / SkillExpansion
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A skill invocation as produced by the LLM.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SkillCall {
+    /// Name of the registered skill to run.
+    pub skill: String,
+    /// Arguments keyed by parameter name.
+    #[serde(default)]
+    pub args: SkillArgs,
+}
+
+/// The result of expanding a [`SkillCall`].
+pub enum SkillExpansion {
+    Intents(Vec<HardwareIntent>),
+    Tree(BehaviorNode),
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// SkillRegistry
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A catalogue of [`Skill`]s, ordered by name so the rendered prompt is
+/// stable between ticks.
+#[derive(Default)]
+pub struct SkillRegistry {
+    skills: BTreeMap<String, Skill>,
+}
+
+impl SkillRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `skill`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SkillError::Duplicate`] if a skill with the same name exists.
+    pub fn register(&mut self, skill: Skill) -> Result<(), SkillError> {
+        if self.skills.contains_key(&skill.name) {
+            return Err(SkillError::Duplicate(skill.name));
+        }
+        self.skills.insert(skill.name.clone(), skill);
+        Ok(())
+    }
+
+    /// Look up a skill by name.
+    pub fn get(&self, name: &str) -> Option<&Skill> {
+        self.skills.get(name)
+    }
+
+    /// Names of all registered skills, sorted.
+    pub fn names(&self) -> Vec<&str> {
+        self.skills.keys().map(String::as_str).collect()
+    }
+
+    /// Number of registered skills.
+    pub fn len(&self) -> usize {
+        self.skills.len()
+    }
+
+    /// `true` if no skills are registered.
+    pub fn is_empty(&self) -> bool {
+        self.skills.is_empty()
+    }
+
+    /// Validate `call` against the skill's declared parameters and expand it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SkillError::Unknown`], [`SkillError::MissingArgument`] or
+    /// [`SkillError::UnexpectedArgument`].
+    pub fn expand(&self, call: &SkillCall) -> Result<SkillExpansion, SkillError> {
+        let skill = self
+            .skills
+            .get(&call.skill)
+            .ok_or_else(|| SkillError::Unknown(call.skill.clone()))?;
+
+        for param in &skill.params {
+            if !call.args.contains_key(&param.name) {
+                return Err(SkillError::MissingArgument {
+                    skill: skill.name.clone(),
+                    param: param.name.clone(),
+                });
+            }
+        }
+        if let Some(extra) = call
+            .args
+            .keys()
+            .find(|k| !skill.params.iter().any(|p| &p.name == *k))
+        {
+            return Err(SkillError::UnexpectedArgument {
+                skill: skill.name.clone(),
+                param: extra.clone(),
+            });
+        }
+
+        Ok(match &skill.body {
+            SkillBody::Intents(build) => SkillExpansion::Intents(build(&call.args)),
+            SkillBody::Tree(build) => SkillExpansion::Tree(build(&call.args)),
+        })
+    }
+
+    /// Render the registry as a system-prompt section listing every skill's
+    /// signature, description and parameters.
+    pub fn prompt_section(&self) -> String {
+        let mut out = String::from(
+            "## Skills\n\
+             Prefer a skill over a raw HardwareIntent. To run one, reply with \
+             {\"skill\": \"<name>\", \"args\": {\"<param>\": \"<value>\"}}.\n",
+        );
+        for skill in self.skills.values() {
+            out.push_str(&format!("- {} – {}\n", skill.signature(), skill.description));
+            for p in &skill.params {
+                out.push_str(&format!("    - {}: {}\n", p.name, p.description));
+            }
+        }
+        out
+    }
+
+    /// JSON Schema accepting either a [`HardwareIntent`] or a [`SkillCall`],
+    /// for use as the LLM `response_format` when skills are registered.
+    pub fn response_schema() -> serde_json::Value {
+        let intent = serde_json::to_value(schema_for!(HardwareIntent))
+            .unwrap_or(serde_json::Value::Null);
+        let call = serde_json::to_value(schema_for!(SkillCall)).unwrap_or(serde_json::Value::Null);
+
+        // Hoist both root schemas' definitions so `#/definitions/..` refs in
+        // either branch still resolve.
+        let mut definitions = serde_json::Map::new();
+        let mut branches = Vec::new();
+        for mut root in [intent, call] {
+            if let Some(obj) = root.as_object_mut() {
+                obj.remove("$schema");
+                if let Some(serde_json::Value::Object(defs)) = obj.remove("definitions") {
+                    definitions.extend(defs);
+                }
+            }
+            branches.push(root);
+        }
+        serde_json::json!({
+            "anyOf": branches,
+            "definitions": definitions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::behavior_tree::NodeStatus;
+    use mechos_types::{MetersPerSecond, RadiansPerSecond};
+
+    fn registry() -> SkillRegistry {
+        let mut reg = SkillRegistry::new();
+        reg.register(Skill::intents("dock", "Drive onto the charging dock", |_| {
+            vec![
+                HardwareIntent::Drive {
+                    linear_velocity: MetersPerSecond(0.1),
+                    angular_velocity: RadiansPerSecond(0.0),
+                },
+                HardwareIntent::Drive {
+                    linear_velocity: MetersPerSecond(0.0),
+                    angular_velocity: RadiansPerSecond(0.0),
+                },
+            ]
+        }))
+        .unwrap();
+        reg.register(
+            Skill::intents("pick", "Grasp an object", |args| {
+                vec![HardwareIntent::TriggerRelay {
+                    relay_id: format!("gripper_{}", args["object"]),
+                    state: true,
+                }]
+            })
+            .with_param("object", "Name of the object"),
+        )
+        .unwrap();
+        reg.register(Skill::tree("scan_room", "Rotate and scan", |_| {
+            BehaviorNode::leaf("spin", || NodeStatus::Success)
+        }))
+        .unwrap();
+        reg
+    }
+
+    fn call(skill: &str, args: &[(&str, &str)]) -> SkillCall {
+        SkillCall {
+            skill: skill.to_string(),
+            args: args
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn names_are_sorted() {
+        assert_eq!(registry().names(), vec!["dock", "pick", "scan_room"]);
+    }
+
+    #[test]
+    fn duplicate_registration_is_rejected() {
+        let mut reg = registry();
+        let result = reg.register(Skill::intents("dock", "again", |_| vec![]));
+        assert_eq!(result, Err(SkillError::Duplicate("dock".to_string())));
+    }
+
+    #[test]
+    fn expand_intent_skill_substitutes_arguments() {
+        let reg = registry();
+        match reg.expand(&call("pick", &[("object", "cup")])).unwrap() {
+            SkillExpansion::Intents(intents) => {
+                assert!(matches!(
+                    &intents[..],
+                    [HardwareIntent::TriggerRelay { relay_id, state: true }] if relay_id == "gripper_cup"
+                ));
+            }
+            SkillExpansion::Tree(_) => panic!("expected intents"),
+        }
+    }
+
+    #[test]
+    fn expand_tree_skill_builds_tree() {
+        let reg = registry();
+        match reg.expand(&call("scan_room", &[])).unwrap() {
+            SkillExpansion::Tree(tree) => assert_eq!(tree.tick(), NodeStatus::Success),
+            SkillExpansion::Intents(_) => panic!("expected tree"),
+        }
+    }
+
+    #[test]
+    fn expand_validates_arguments() {
+        let reg = registry();
+        assert!(matches!(
+            reg.expand(&call("teleport", &[])),
+            Err(SkillError::Unknown(_))
+        ));
+        assert!(matches!(
+            reg.expand(&call("pick", &[])),
+            Err(SkillError::MissingArgument { .. })
+        ));
+        assert!(matches!(
+            reg.expand(&call("dock", &[("speed", "fast")])),
+            Err(SkillError::UnexpectedArgument { .. })
+        ));
+    }
+
+    #[test]
+    fn prompt_section_lists_signatures_and_params() {
+        let section = registry().prompt_section();
+        assert!(section.contains("## Skills"));
+        assert!(section.contains("pick(object: string) – Grasp an object"));
+        assert!(section.contains("object: Name of the object"));
+        assert!(section.contains("dock()"));
+    }
+
+    #[test]
+    fn skill_call_parses_without_args() {
+        let parsed: SkillCall = serde_json::from_str(r#"{"skill":"dock"}"#).unwrap();
+        assert_eq!(parsed, call("dock", &[]));
+    }
+
+    #[test]
+    fn response_schema_accepts_intents_and_skill_calls() {
+        let schema = SkillRegistry::response_schema().to_string();
+        assert!(schema.contains("anyOf"));
+        assert!(schema.contains("MoveEndEffector"));
+        assert!(schema.contains("skill"));
+    }
+}
@@ -0,0 +1,374 @@
+//! [`AgentSupervisor`] – several [`AgentLoop`]s on one bus.
+//!
+//! A single robot often benefits from specialised "brains": a `"navigator"`
+//! that only drives the base and a `"manipulator"` that only moves the arm.
+//! The supervisor spawns one [`AgentLoop`] per role, each with:
+//!
+//! * a **namespaced identity** ([`AgentLoopConfig::agent_id`]) that is used
+//!   for capability checks and as the suffix of every event source it
+//!   publishes (`"mechos-runtime::agent_loop/<agent_id>"`),
+//! * its **own capability set**, granted on a single shared [`KernelGate`],
+//! * its **own prompt role** ([`AgentLoopConfig::role_prompt`]).
+//!
+//! # Conflict arbitration
+//!
+//! On every [`AgentSupervisor::tick`] each agent proposes one intent.  The
+//! proposals are then serialised through the shared gate in priority order
+//! (the order in which agents were spawned).  Two intents that require the
+//! same [`Capability::HardwareInvoke`] resource in the same tick conflict:
+//! the higher-priority agent wins and the other proposal is rejected with a
+//! [`MechError::HardwareFault`] from component `"agent_supervisor"`.
+//! Non-hardware capabilities (fleet messaging, task board) never conflict.
+//! A [`HardwareIntent::Halt`] from any agent pre-empts the whole tick: it is
+//! always dispatched, and every other hardware proposal in the same tick is
+//! rejected.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use mechos_runtime::{AgentLoopConfig, AgentSupervisor};
+//! use mechos_types::Capability;
+//!
+//! let mut supervisor = AgentSupervisor::new(Default::default());
+//! supervisor
+//!     .spawn(AgentLoopConfig {
+//!         agent_id: "navigator".to_string(),
+//!         role_prompt: Some("You only drive the mobile base.".to_string()),
+//!         capabilities: vec![Capability::HardwareInvoke("drive_base".to_string())],
+//!         ..Default::default()
+//!     })
+//!     .expect("spawn navigator");
+//! ```
+
+use std::collections::HashMap;
+
+use mechos_kernel::{
+    BatteryInterlock, CapabilityManager, KernelGate, ManualOverrideInterlock, MovingObstacleRule,
+    SafetyInterlock, SpeechRule, StaleDataRule, StateVerifier,
+};
+use mechos_middleware::EventBus;
+use mechos_types::{Capability, FaultCode, HardwareIntent, MechError};
+use tracing::{instrument, warn};
+
+use crate::agent_loop::{AgentLoop, AgentLoopConfig};
+use crate::metrics::RuntimeMetrics;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// AgentSupervisor
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Spawns and drives multiple [`AgentLoop`]s that share one [`EventBus`] and
+/// one [`KernelGate`].
+pub struct AgentSupervisor {
+    bus: EventBus,
+    gate: KernelGate,
+    /// Agents in priority order (first spawned = highest priority).
+    agents: Vec<AgentLoop>,
+}
+
+impl AgentSupervisor {
+    /// Create an empty supervisor whose agents will all publish on `bus`.
+    pub fn new(bus: EventBus) -> Self {
+        // One speaker budget for the whole robot, however many agents talk.
+        let mut verifier = StateVerifier::new();
+        verifier.add_rule(Box::new(SpeechRule::default()));
+        Self {
+            bus,
+            gate: KernelGate::new(CapabilityManager::new(), verifier),
+            agents: Vec::new(),
+        }
+    }
+
+    /// Spawn a new [`AgentLoop`] from `config`.
+    ///
+    /// `config.bus` is replaced with the supervisor's bus, and
+    /// `config.capabilities` are granted to `config.agent_id` on the shared
+    /// gate.  Agents spawned earlier take priority during conflict
+    /// arbitration.
+    ///
+    /// # Errors
+    ///
+    /// - [`MechError::HardwareFault`] – an agent with the same id already
+    ///   exists.
+    /// - Any error returned by [`AgentLoop::new`].
+    pub fn spawn(&mut self, mut config: AgentLoopConfig) -> Result<(), MechError> {
+        if self.agent(&config.agent_id).is_some() {
+            return Err(MechError::HardwareFault {
+                code: FaultCode::InvalidConfiguration,
+                component: "agent_supervisor".to_string(),
+                details: format!("agent '{}' is already registered", config.agent_id),
+            });
+        }
+
+        let agent_id = config.agent_id.clone();
+        let capabilities = config.capabilities.clone();
+        config.bus = Some(self.bus.clone());
+        let agent = AgentLoop::new(config)?;
+
+        for cap in capabilities {
+            self.gate.capability_manager_mut().grant(&agent_id, cap);
+        }
+        // Each agent owns a joystick interlock flag, a battery level, a
+        // localization flag, a moving-obstacle clearance and an e-stop
+        // latch; the shared gate must honour all of them.
+        let verifier = self.gate.state_verifier_mut();
+        verifier.add_rule(Box::new(ManualOverrideInterlock::new(
+            agent.override_flag(),
+        )));
+        verifier.add_rule(Box::new(BatteryInterlock::new(agent.battery_level())));
+        verifier.add_rule(Box::new(StaleDataRule::new(agent.localization_flag())));
+        verifier.add_rule(Box::new(MovingObstacleRule::new(
+            agent.moving_obstacle_clearance(),
+        )));
+        verifier.add_rule(Box::new(SafetyInterlock::new(agent.safety_state())));
+
+        self.agents.push(agent);
+        Ok(())
+    }
+
+    /// Identities of all spawned agents, in priority order.
+    pub fn agent_ids(&self) -> Vec<&str> {
+        self.agents.iter().map(|a| a.agent_id()).collect()
+    }
+
+    /// Look up an agent by id.
+    pub fn agent(&self, agent_id: &str) -> Option<&AgentLoop> {
+        self.agents.iter().find(|a| a.agent_id() == agent_id)
+    }
+
+    /// Mutable lookup of an agent by id (e.g. to feed it sensor data or a
+    /// human response).
+    pub fn agent_mut(&mut self, agent_id: &str) -> Option<&mut AgentLoop> {
+        self.agents.iter_mut().find(|a| a.agent_id() == agent_id)
+    }
+
+    /// Return a clone of the shared [`EventBus`].
+    pub fn bus(&self) -> EventBus {
+        self.bus.clone()
+    }
+
+    /// Number of spawned agents.
+    pub fn len(&self) -> usize {
+        self.agents.len()
+    }
+
+    /// `true` if no agents have been spawned.
+    pub fn is_empty(&self) -> bool {
+        self.agents.is_empty()
+    }
+
+    /// Run one OODA cycle for every agent.
+    ///
+    /// Each agent proposes an intent; the proposals are arbitrated and then
+    /// authorized one at a time through the shared [`KernelGate`].  Returns
+    /// one `(agent_id, result)` pair per agent, in priority order.
+    #[instrument(name = "agent_supervisor.tick", skip(self), fields(dt = dt))]
+    pub async fn tick(&mut self, dt: f32) -> Vec<(String, Result<HardwareIntent, MechError>)> {
+        let mut proposals = Vec::with_capacity(self.agents.len());
+        for agent in &mut self.agents {
+            proposals.push(agent.propose(dt).await);
+        }
+        self.resolve(proposals)
+    }
+
+    // -------------------------------------------------------------------------
+    // Private helpers
+    // -------------------------------------------------------------------------
+
+    /// Arbitrate, gate and act on one proposal per agent (indexed like
+    /// `self.agents`).
+    fn resolve(
+        &mut self,
+        proposals: Vec<Result<HardwareIntent, MechError>>,
+    ) -> Vec<(String, Result<HardwareIntent, MechError>)> {
+        // Hardware resource → id of the agent that claimed it this tick.
+        let mut claimed: HashMap<Capability, String> = HashMap::new();
+        let mut outcomes = Vec::with_capacity(proposals.len());
+        let halting = proposals
+            .iter()
+            .any(|p| matches!(p, Ok(HardwareIntent::Halt { .. })));
+
+        for (agent, proposal) in self.agents.iter_mut().zip(proposals) {
+            let agent_id = agent.agent_id().to_string();
+            let result = proposal.and_then(|mut intent| {
+                let cap = KernelGate::capability_for(&intent);
+                if halting && matches!(cap, Some(Capability::HardwareInvoke(_))) {
+                    return Err(MechError::HardwareFault {
+                        code: FaultCode::HaltPreempted,
+                        component: "agent_supervisor".to_string(),
+                        details: format!("intent from '{agent_id}' pre-empted by a halt"),
+                    });
+                }
+                if let Some(cap) = &cap
+                    && let Some(winner) = claimed.get(cap)
+                {
+                    warn!(agent_id = %agent_id, winner = %winner, "intent conflict; proposal rejected");
+                    return Err(MechError::HardwareFault {
+                        code: FaultCode::ResourceConflict,
+                        component: "agent_supervisor".to_string(),
+                        details: format!(
+                            "intent from '{agent_id}' conflicts with '{winner}' on {cap:?}"
+                        ),
+                    });
+                }
+                let verdict = self.gate.authorize_and_verify(&agent_id, &mut intent);
+                RuntimeMetrics::global().record_gate_decision(&agent_id, verdict.is_ok());
+                verdict?;
+                let mut intent = agent.check_trajectory(intent)?;
+                if let Some(cap @ Capability::HardwareInvoke(_)) = cap {
+                    claimed.insert(cap, agent_id.clone());
+                }
+                agent.act(&mut intent);
+                Ok(intent)
+            });
+            outcomes.push((agent_id, result));
+        }
+
+        outcomes
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mechos_types::{EventPayload, MetersPerSecond, RadiansPerSecond};
+
+    fn config(agent_id: &str, caps: Vec<Capability>) -> AgentLoopConfig {
+        AgentLoopConfig {
+            agent_id: agent_id.to_string(),
+            capabilities: caps,
+            ..Default::default()
+        }
+    }
+
+    fn drive(linear_velocity: f32) -> HardwareIntent {
+        HardwareIntent::Drive {
+            linear_velocity: MetersPerSecond(linear_velocity),
+            angular_velocity: RadiansPerSecond(0.0),
+        }
+    }
+
+    fn two_drivers() -> AgentSupervisor {
+        let mut sup = AgentSupervisor::new(EventBus::default());
+        let drive_cap = Capability::HardwareInvoke("drive_base".to_string());
+        sup.spawn(config("navigator", vec![drive_cap.clone()])).unwrap();
+        sup.spawn(config("explorer", vec![drive_cap])).unwrap();
+        sup
+    }
+
+    #[test]
+    fn spawn_registers_agents_in_priority_order() {
+        let sup = two_drivers();
+        assert_eq!(sup.agent_ids(), vec!["navigator", "explorer"]);
+        assert_eq!(sup.len(), 2);
+        assert!(sup.agent("navigator").is_some());
+        assert!(sup.agent("ghost").is_none());
+    }
+
+    #[test]
+    fn spawn_rejects_duplicate_agent_id() {
+        let mut sup = two_drivers();
+        let result = sup.spawn(config("navigator", vec![]));
+        assert!(matches!(result, Err(MechError::HardwareFault { .. })));
+        assert_eq!(sup.len(), 2);
+    }
+
+    #[test]
+    fn conflicting_hardware_intents_are_serialised_by_priority() {
+        let mut sup = two_drivers();
+        let outcomes = sup.resolve(vec![Ok(drive(0.2)), Ok(drive(-0.2))]);
+
+        assert_eq!(outcomes[0].0, "navigator");
+        assert!(outcomes[0].1.is_ok());
+        assert_eq!(outcomes[1].0, "explorer");
+        assert!(
+            matches!(
+                &outcomes[1].1,
+                Err(MechError::HardwareFault { code: FaultCode::ResourceConflict, component, details })
+                    if component == "agent_supervisor" && details.contains("navigator")
+            ),
+            "expected conflict fault, got: {:?}",
+            outcomes[1].1
+        );
+    }
+
+    #[test]
+    fn shared_gate_enforces_per_agent_capabilities() {
+        let mut sup = AgentSupervisor::new(EventBus::default());
+        sup.spawn(config(
+            "manipulator",
+            vec![Capability::HardwareInvoke("end_effector".to_string())],
+        ))
+        .unwrap();
+
+        let outcomes = sup.resolve(vec![Ok(drive(0.1))]);
+        assert!(matches!(outcomes[0].1, Err(MechError::Unauthorized(_))));
+    }
+
+    #[test]
+    fn non_hardware_intents_do_not_conflict() {
+        let mut sup = AgentSupervisor::new(EventBus::default());
+        sup.spawn(config("a", vec![Capability::FleetCommunicate])).unwrap();
+        sup.spawn(config("b", vec![Capability::FleetCommunicate])).unwrap();
+
+        let msg = |m: &str| HardwareIntent::BroadcastFleet {
+            message: m.to_string(),
+        };
+        let outcomes = sup.resolve(vec![Ok(msg("one")), Ok(msg("two"))]);
+        assert!(outcomes.iter().all(|(_, r)| r.is_ok()));
+    }
+
+    #[test]
+    fn halt_preempts_hardware_intents_of_other_agents() {
+        let mut sup = two_drivers();
+        let halt = HardwareIntent::Halt {
+            reason: "loop guard".to_string(),
+        };
+        let outcomes = sup.resolve(vec![Ok(drive(0.2)), Ok(halt)]);
+
+        assert!(
+            matches!(&outcomes[0].1, Err(MechError::HardwareFault { details, .. }) if details.contains("halt")),
+            "expected pre-emption, got: {:?}",
+            outcomes[0].1
+        );
+        assert!(matches!(outcomes[1].1, Ok(HardwareIntent::Halt { .. })));
+    }
+
+    #[test]
+    fn approved_intent_is_published_with_namespaced_source() {
+        let mut sup = two_drivers();
+        let mut rx = sup.bus().subscribe();
+        sup.resolve(vec![Ok(drive(0.3)), Err(MechError::LlmInferenceFailed("x".into()))]);
+
+        let event = rx.try_recv().expect("approved intent must be published");
+        assert_eq!(event.source, "mechos-runtime::agent_loop/navigator");
+        assert!(matches!(event.payload, EventPayload::Intent(ref e) if e.issued_by == "navigator"));
+        assert!(rx.try_recv().is_err(), "failed proposal must not publish");
+    }
+
+    #[test]
+    fn manual_override_on_any_agent_blocks_drive_through_shared_gate() {
+        let mut sup = two_drivers();
+        sup.agent_mut("explorer").unwrap().handle_manual_override(0.5, 0.0);
+
+        let outcomes = sup.resolve(vec![Ok(drive(0.2)), Err(MechError::LlmInferenceFailed("x".into()))]);
+        assert!(matches!(outcomes[0].1, Err(MechError::HardwareFault { .. })));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn tick_returns_one_outcome_per_agent() {
+        // No live LLM server – every agent reports an inference error.
+        let mut sup = two_drivers();
+        let outcomes = sup.tick(0.1).await;
+        assert_eq!(outcomes.len(), 2);
+        assert!(
+            outcomes
+                .iter()
+                .all(|(_, r)| matches!(r, Err(MechError::LlmInferenceFailed(_))))
+        );
+    }
+}
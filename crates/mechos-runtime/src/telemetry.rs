@@ -1,13 +1,18 @@
 //! OpenTelemetry pipeline initialisation for MechOS.
 //!
-//! Call [`init_tracing`] once at process startup to wire up the `tracing`
-//! subscriber with an optional OTLP span exporter.
+//! Call [`init_observability`] once at process startup to wire up the
+//! `tracing` subscriber with an optional OTLP span exporter **and** the global
+//! meter provider used by [`RuntimeMetrics`][crate::metrics::RuntimeMetrics].
+//! [`init_tracing`] sets up tracing only.
+//!
+//! Either also copies every logged event into [`LogBuffer::global`], which
+//! the REPL's `/logs` and the Cockpit's `GET /api/logs` read.
 //!
 //! # Environment variables
 //!
 //! | Variable | Effect |
 //! |---|---|
-//! | `OTEL_EXPORTER_OTLP_ENDPOINT` | OTLP collector base URL (e.g. `http://localhost:4318`). When set the OTLP HTTP exporter is activated. |
+//! | `OTEL_EXPORTER_OTLP_ENDPOINT` | OTLP collector base URL (e.g. `http://localhost:4318`). When set the OTLP HTTP span (and, with [`init_observability`], metric) exporters are activated. |
 //! | `RUST_LOG` | Log filter (default `"info"`). |
 //! | `MECHOS_LOG_FORMAT=json` | Emit newline-delimited JSON logs. |
 //!
@@ -15,12 +20,21 @@
 //!
 //! ```rust,no_run
 //! // Hold the guard for the entire lifetime of the process.
-//! let _guard = mechos_runtime::telemetry::init_tracing("mechos");
+//! let _guard = mechos_runtime::telemetry::init_observability("mechos");
 //! ```
 
 use opentelemetry::trace::TracerProvider as _;
 use opentelemetry_otlp::WithExportConfig;
-use opentelemetry_sdk::{trace::SdkTracerProvider, Resource};
+use opentelemetry_sdk::{
+    metrics::{PeriodicReader, SdkMeterProvider},
+    trace::SdkTracerProvider,
+    Resource,
+};
+
+use mechos_middleware::LogBuffer;
+use mechos_middleware::log_buffer::LogCapture;
+
+use crate::metrics::RuntimeMetrics;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -40,12 +54,91 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilte
 /// The returned [`TracerProviderGuard`] **must** be held for the lifetime of
 /// the process; dropping it flushes all pending span batches.
 pub fn init_tracing(service_name: &str) -> TracerProviderGuard {
+    TracerProviderGuard(install_subscriber(service_name))
+}
+
+/// Initialise tracing (as [`init_tracing`]) plus OpenTelemetry metrics.
+///
+/// When `OTEL_EXPORTER_OTLP_ENDPOINT` is set an OTLP/HTTP metric exporter is
+/// installed as the global meter provider, so the runtime metrics (tick
+/// duration, LLM latency, gate decisions, loop-guard trips, bus lag and token
+/// spend) are pushed to the collector periodically.  Without a collector the
+/// same metrics remain available through
+/// [`serve_prometheus`][crate::metrics::serve_prometheus].
+///
+/// The returned [`ObservabilityGuard`] **must** be held for the lifetime of
+/// the process; dropping it flushes pending spans and metrics.
+pub fn init_observability(service_name: &str) -> ObservabilityGuard {
+    let tracer = init_tracing(service_name);
+    let meter = build_meter_provider(service_name);
+    if let Some(ref p) = meter {
+        opentelemetry::global::set_meter_provider(p.clone());
+    }
+    // Bind the runtime instruments now that the meter provider is in place.
+    RuntimeMetrics::global();
+    ObservabilityGuard {
+        _tracer: tracer,
+        meter,
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// RAII guards
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// RAII guard that shuts down the OTel [`SdkTracerProvider`] on drop.
+///
+/// Dropping this guard calls [`SdkTracerProvider::shutdown`], flushing all
+/// pending spans before the process exits.  Hold an instance of this type
+/// in `main` for the entire program lifetime.
+pub struct TracerProviderGuard(Option<SdkTracerProvider>);
+
+impl Drop for TracerProviderGuard {
+    #[allow(clippy::collapsible_if)]
+    fn drop(&mut self) {
+        if let Some(provider) = self.0.take() {
+            if let Err(e) = provider.shutdown() {
+                eprintln!("[mechos] OpenTelemetry provider shutdown error: {e}");
+            }
+        }
+    }
+}
+
+/// RAII guard returned by [`init_observability`].
+///
+/// Dropping it shuts down the OTel meter provider (flushing the final metric
+/// export) and then the tracer provider.  Hold an instance of this type in
+/// `main` for the entire program lifetime.
+pub struct ObservabilityGuard {
+    _tracer: TracerProviderGuard,
+    meter: Option<SdkMeterProvider>,
+}
+
+impl Drop for ObservabilityGuard {
+    fn drop(&mut self) {
+        if let Some(provider) = self.meter.take()
+            && let Err(e) = provider.shutdown()
+        {
+            eprintln!("[mechos] OpenTelemetry meter provider shutdown error: {e}");
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Internal helpers
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Install the global `tracing` subscriber, with an OTel layer when an OTLP
+/// endpoint is configured and the [`LogBuffer::global`] capture, and return
+/// the tracer provider (if any).
+fn install_subscriber(service_name: &str) -> Option<SdkTracerProvider> {
     let log_level = std::env::var("RUST_LOG").unwrap_or_else(|_| "info".to_string());
     let env_filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new(log_level));
     let use_json = std::env::var("MECHOS_LOG_FORMAT").as_deref() == Ok("json");
 
     let provider = build_provider(service_name);
+    let capture = LogCapture::new(LogBuffer::global().clone());
 
     if let Some(ref p) = provider {
         let tracer = p.tracer("mechos");
@@ -54,55 +147,34 @@ pub fn init_tracing(service_name: &str) -> TracerProviderGuard {
             tracing_subscriber::registry()
                 .with(env_filter)
                 .with(otel_layer)
+                .with(capture)
                 .with(tracing_subscriber::fmt::layer().json())
                 .init();
         } else {
             tracing_subscriber::registry()
                 .with(env_filter)
                 .with(otel_layer)
+                .with(capture)
                 .with(tracing_subscriber::fmt::layer().compact())
                 .init();
         }
     } else if use_json {
         tracing_subscriber::registry()
             .with(env_filter)
+            .with(capture)
             .with(tracing_subscriber::fmt::layer().json())
             .init();
     } else {
         tracing_subscriber::registry()
             .with(env_filter)
+            .with(capture)
             .with(tracing_subscriber::fmt::layer().compact())
             .init();
     }
 
-    TracerProviderGuard(provider)
-}
-
-// ─────────────────────────────────────────────────────────────────────────────
-// RAII guard
-// ─────────────────────────────────────────────────────────────────────────────
-
-/// RAII guard that shuts down the OTel [`SdkTracerProvider`] on drop.
-///
-/// Dropping this guard calls [`SdkTracerProvider::shutdown`], flushing all
-/// pending spans before the process exits.  Hold an instance of this type
-/// in `main` for the entire program lifetime.
-pub struct TracerProviderGuard(Option<SdkTracerProvider>);
-
-impl Drop for TracerProviderGuard {
-    fn drop(&mut self) {
-        if let Some(provider) = self.0.take() {
-            if let Err(e) = provider.shutdown() {
-                eprintln!("[mechos] OpenTelemetry provider shutdown error: {e}");
-            }
-        }
-    }
+    provider
 }
 
-// ─────────────────────────────────────────────────────────────────────────────
-// Internal helpers
-// ─────────────────────────────────────────────────────────────────────────────
-
 /// Build an [`SdkTracerProvider`] when `OTEL_EXPORTER_OTLP_ENDPOINT` is set.
 ///
 /// Returns `None` when the env-var is absent or the exporter cannot be
@@ -134,6 +206,35 @@ fn build_provider(service_name: &str) -> Option<SdkTracerProvider> {
     )
 }
 
+/// Build an [`SdkMeterProvider`] when `OTEL_EXPORTER_OTLP_ENDPOINT` is set.
+///
+/// Returns `None` when the env-var is absent or the exporter cannot be
+/// initialised.  The periodic reader exports from its own background thread,
+/// so no Tokio runtime is required at init time.
+fn build_meter_provider(service_name: &str) -> Option<SdkMeterProvider> {
+    let endpoint = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT").ok()?;
+
+    // Unlike the env-var path, an explicit endpoint is used verbatim, so the
+    // signal path must be appended here.
+    let exporter = opentelemetry_otlp::MetricExporter::builder()
+        .with_http()
+        .with_endpoint(format!("{}/v1/metrics", endpoint.trim_end_matches('/')))
+        .build()
+        .map_err(|e| eprintln!("[mechos] OTLP metric exporter init failed: {e}"))
+        .ok()?;
+
+    let resource = Resource::builder()
+        .with_service_name(service_name.to_string())
+        .build();
+
+    Some(
+        SdkMeterProvider::builder()
+            .with_resource(resource)
+            .with_reader(PeriodicReader::builder(exporter).build())
+            .build(),
+    )
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Tests
 // ─────────────────────────────────────────────────────────────────────────────
@@ -154,6 +255,15 @@ mod tests {
         );
     }
 
+    /// Verify that `build_meter_provider` returns `None` when no endpoint is
+    /// set.
+    #[test]
+    fn build_meter_provider_returns_none_without_endpoint() {
+        // SAFETY: single-threaded test; no other thread reads this env-var.
+        unsafe { std::env::remove_var("OTEL_EXPORTER_OTLP_ENDPOINT") };
+        assert!(build_meter_provider("test-service").is_none());
+    }
+
     /// Verify that `TracerProviderGuard` drops without panicking when it holds
     /// no provider.
     #[test]
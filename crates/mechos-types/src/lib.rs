@@ -1,9 +1,44 @@
 use chrono::{DateTime, Utc};
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
 use thiserror::Error;
 use uuid::Uuid;
 
+pub mod units;
+
+pub use units::{Meters, MetersPerSecond, RadiansPerSecond};
+
+/// Wire-format version of [`Event`] and [`IntentEnvelope`].
+///
+/// Both carry a `schema_version` field when serialised so that robots running
+/// different MechOS releases in one fleet can still decode each other's
+/// messages:
+///
+/// | Version | Change |
+/// |---|---|
+/// | `1` | Implicit version of messages without the field.  Approved intents travelled as JSON strings inside an `AgentThought`. |
+/// | `2` | Approved intents travel as [`EventPayload::Intent`]. |
+/// | `3` | Fault codes are [`FaultCode`] names instead of bare integers. |
+/// | `4` | `AgentThought` is renamed [`EventPayload::Reasoning`]; adapter frames travel as [`EventPayload::AdapterCommand`] and operator intents as [`EventPayload::IntentDispatched`] instead of JSON strings inside it. |
+/// | `5` | [`EventPayload::HumanResponse`] carries a [`HumanAnswer`] naming the question it answers instead of a bare string. |
+///
+/// Older messages are migrated to the current shape on deserialisation and
+/// unknown fields sent by newer peers are ignored.  An event whose payload
+/// (or the intent inside it) is a variant added by a newer peer decodes as
+/// [`EventPayload::Unknown`].  A field renamed in a later version keeps its
+/// old name as a `#[serde(alias)]`.
+pub const SCHEMA_VERSION: u32 = 5;
+
+/// Version assumed for messages that carry no `schema_version` field.
+pub const LEGACY_SCHEMA_VERSION: u32 = 1;
+
+fn legacy_schema_version() -> u32 {
+    LEGACY_SCHEMA_VERSION
+}
+
 /// Capability-based security model: defines what an agent or process is allowed to do.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Capability {
@@ -19,20 +54,43 @@ pub enum Capability {
     FleetCommunicate,
     /// Permission to read from and write to the shared Fleet Task Board
     TaskBoardAccess,
+    /// Permission for a human operator to answer the agent's `AskHuman`
+    /// questions
+    AnswerQuestions,
+    /// Permission for a human operator to approve or reject intents held
+    /// for approval
+    ApproveIntents,
 }
 
+/// Name of the fixed world frame that maps, waypoints and fused poses are
+/// expressed in.
+pub const MAP_FRAME: &str = "map";
+
+/// Name of the frame attached to the robot body, the default frame of
+/// [`HardwareIntent::MoveEndEffector`] targets.
+pub const BASE_FRAME: &str = "base_link";
+
 /// Strict definition of physical actions the LLM is allowed to request.
 /// `mechos-hal` parses these intents and translates them into motor currents.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "action", content = "payload")]
 pub enum HardwareIntent {
-    /// High-level: move the gripper/end-effector to a 3D world coordinate.
+    /// High-level: move the gripper/end-effector to a 3D coordinate.
     /// The Universal Integration Adapter resolves the Inverse Kinematics.
-    MoveEndEffector { x: f32, y: f32, z: f32 },
+    MoveEndEffector {
+        x: Meters,
+        y: Meters,
+        z: Meters,
+        /// Frame the coordinates are expressed in; `None` means
+        /// [`BASE_FRAME`].  The runtime converts other frames into
+        /// [`BASE_FRAME`] before the kernel checks the workspace.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        frame_id: Option<String>,
+    },
     /// Standard differential drive command
     Drive {
-        linear_velocity: f32,
-        angular_velocity: f32,
+        linear_velocity: MetersPerSecond,
+        angular_velocity: RadiansPerSecond,
     },
     /// Command to trigger a discrete hardware action
     TriggerRelay { relay_id: String, state: bool },
@@ -40,6 +98,12 @@ pub enum HardwareIntent {
     AskHuman {
         question: String,
         context_image_id: Option<String>,
+        /// Identifies the question so that answers can be matched to it.
+        /// Assigned by the agent loop on dispatch; not part of the LLM
+        /// schema.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[schemars(skip)]
+        question_id: Option<String>,
     },
     /// Send a direct message/request to another specific robot.
     MessagePeer { target_robot_id: String, message: String },
@@ -47,10 +111,149 @@ pub enum HardwareIntent {
     BroadcastFleet { message: String },
     /// Post a task to the shared Fleet Task Board.
     PostTask { title: String, description: String },
+    /// Speak `text` aloud through the robot's text-to-speech channel.
+    /// `voice` selects a TTS voice; `None` uses the platform default.
+    Speak { text: String, voice: Option<String> },
+    /// Play a pre-recorded sound clip (chime, alarm, ...) by identifier.
+    PlaySound { sound_id: String },
+    /// Path-level navigation: drive through `points` (world-frame `(x, y)`)
+    /// in order without exceeding `max_speed`.  The navigation stack behind
+    /// the adapter plans the velocity commands.
+    FollowWaypoints {
+        points: Vec<(Meters, Meters)>,
+        max_speed: MetersPerSecond,
+    },
+    /// Open or close the gripper: `0.0` is fully open, `1.0` fully closed.
+    SetGripper { position: f32 },
+    /// Joint-space command: move a single named arm joint to `angle_rad`
+    /// without exceeding `max_velocity`.
+    MoveJoint {
+        joint: String,
+        angle_rad: f32,
+        max_velocity: RadiansPerSecond,
+    },
+    /// Stop everything now: zero all velocities and stop every controller.
+    /// Always permitted by the kernel and never subject to LLM-related checks.
+    Halt { reason: String },
+    /// Drive onto the charging dock and start charging.
+    Dock,
+    /// Leave the charging dock.  Refused by the kernel while the battery is
+    /// below its undock threshold.
+    Undock,
+    /// Arm (`true`) or disarm (`false`) the motors of a flight controller.
+    /// Gated by its own `"arming"` capability so that an agent allowed to
+    /// fly is not implicitly allowed to spin up the propellers.
+    Arm { armed: bool },
+    /// Take off to, or change, the vehicle's altitude above its home
+    /// position.
+    SetAltitude { altitude: Meters },
+    /// Fly (or drive) to a global position: WGS-84 latitude and longitude in
+    /// degrees and `altitude` above the home position.
+    Goto {
+        latitude_deg: f64,
+        longitude_deg: f64,
+        altitude: Meters,
+    },
 }
 
-/// Unified event wrapper for the headless event bus.
+/// Urgency of a dispatched intent.  Ordered: `Low < Normal < High < Critical`.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum IntentPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+    /// Reserved for safety stops; [`IntentEnvelope::new`] assigns it to
+    /// [`HardwareIntent::Halt`].
+    Critical,
+}
+
+/// A [`HardwareIntent`] together with its dispatch metadata.
+///
+/// The envelope is what travels from the kernel over the bus to the adapters:
+/// `deadline` lets consumers drop stale commands (a `Drive` that arrives late
+/// is worse than none), and `correlation_id` ties every downstream result back
+/// to the tick that issued the intent.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntentEnvelope {
+    pub intent: HardwareIntent,
+    #[serde(default)]
+    pub priority: IntentPriority,
+    /// Instant after which the intent must no longer be executed; `None`
+    /// never expires.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline: Option<DateTime<Utc>>,
+    /// Agent identity that issued the intent.
+    pub issued_by: String,
+    pub correlation_id: Uuid,
+    /// [`SCHEMA_VERSION`] of the sender.
+    #[serde(default = "legacy_schema_version")]
+    pub schema_version: u32,
+}
+
+impl IntentEnvelope {
+    /// Wrap `intent` with a fresh correlation ID, no deadline and
+    /// [`IntentPriority::Normal`] ([`IntentPriority::Critical`] for `Halt`).
+    pub fn new(intent: HardwareIntent, issued_by: impl Into<String>) -> Self {
+        let priority = match intent {
+            HardwareIntent::Halt { .. } => IntentPriority::Critical,
+            _ => IntentPriority::Normal,
+        };
+        Self {
+            intent,
+            priority,
+            deadline: None,
+            issued_by: issued_by.into(),
+            correlation_id: Uuid::new_v4(),
+            schema_version: SCHEMA_VERSION,
+        }
+    }
+
+    /// Override the priority.
+    pub fn with_priority(mut self, priority: IntentPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Drop the intent if it has not been executed by `deadline`.
+    pub fn with_deadline(mut self, deadline: DateTime<Utc>) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Reuse an existing correlation ID, e.g. the issuing tick's.
+    pub fn with_correlation_id(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+
+    /// Record the sender's [`SCHEMA_VERSION`] when rewrapping an older
+    /// message.
+    pub fn with_schema_version(mut self, schema_version: u32) -> Self {
+        self.schema_version = schema_version;
+        self
+    }
+
+    /// `true` if the deadline has passed at `now`.  A `Halt` never expires.
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        !matches!(self.intent, HardwareIntent::Halt { .. })
+            && self.deadline.is_some_and(|deadline| now > deadline)
+    }
+
+    /// [`is_expired_at`][Self::is_expired_at] the current time.
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(Utc::now())
+    }
+}
+
+/// Unified event wrapper for the headless event bus.
+///
+/// The serialised form adds a `schema_version` field (see
+/// [`SCHEMA_VERSION`]); events from older peers are migrated when decoded.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(from = "WireEvent")]
 pub struct Event {
     pub id: Uuid,
     pub timestamp: DateTime<Utc>,
@@ -68,9 +271,149 @@ pub struct Event {
     /// lifecycle—from LLM generation, through Kernel validation, to HAL
     /// execution—in any OTLP-compatible observability backend.
     ///
-    /// Set to `None` when no span is active at publish time.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Set to `None` when no span is active at publish time.  Also accepted
+    /// under the W3C header name `traceparent` when decoding.
     pub trace_id: Option<String>,
+    /// For a reply, the `id` of the request event it answers (see
+    /// `EventBus::request`).  `None` for everything else.
+    pub correlation_id: Option<Uuid>,
+}
+
+impl Serialize for Event {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        WireEventRef {
+            schema_version: SCHEMA_VERSION,
+            id: &self.id,
+            timestamp: &self.timestamp,
+            source: &self.source,
+            payload: &self.payload,
+            trace_id: self.trace_id.as_deref(),
+            correlation_id: self.correlation_id.as_ref(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Serialised form of an [`Event`] being written.
+#[derive(Serialize)]
+struct WireEventRef<'a> {
+    schema_version: u32,
+    id: &'a Uuid,
+    timestamp: &'a DateTime<Utc>,
+    source: &'a str,
+    #[serde(serialize_with = "serialize_payload")]
+    payload: &'a EventPayload,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trace_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    correlation_id: Option<&'a Uuid>,
+}
+
+/// Serialised form of an [`Event`] being read, at any schema version.
+#[derive(Deserialize)]
+struct WireEvent {
+    #[serde(default = "legacy_schema_version")]
+    schema_version: u32,
+    id: Uuid,
+    timestamp: DateTime<Utc>,
+    source: String,
+    #[serde(deserialize_with = "deserialize_payload")]
+    payload: EventPayload,
+    #[serde(default, alias = "traceparent")]
+    trace_id: Option<String>,
+    #[serde(default)]
+    correlation_id: Option<Uuid>,
+}
+
+/// Write an [`EventPayload::Unknown`] back as the JSON it was read from.
+fn serialize_payload<S: Serializer>(
+    payload: &&EventPayload,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match payload {
+        EventPayload::Unknown(raw) => raw.serialize(serializer),
+        known => known.serialize(serializer),
+    }
+}
+
+/// Decode a payload, keeping one whose variant (or the variant of an intent
+/// inside it) this release does not know as [`EventPayload::Unknown`].
+/// Malformed payloads of known variants are still rejected.
+fn deserialize_payload<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<EventPayload, D::Error> {
+    let raw = serde_json::Value::deserialize(deserializer)?;
+    match EventPayload::deserialize(&raw) {
+        Ok(payload) => Ok(payload),
+        Err(e) if e.to_string().starts_with("unknown variant") => Ok(EventPayload::Unknown(raw)),
+        Err(e) => Err(serde::de::Error::custom(e)),
+    }
+}
+
+impl From<WireEvent> for Event {
+    /// Migration shim: upgrade the payload of an older event to the current
+    /// schema.
+    fn from(wire: WireEvent) -> Self {
+        let mut payload = wire.payload;
+        if wire.schema_version < 2 {
+            payload = upgrade_v1_payload(&wire.id, &wire.source, payload);
+        }
+        if wire.schema_version < 4 {
+            payload = upgrade_v3_payload(payload);
+        }
+        Event {
+            id: wire.id,
+            timestamp: wire.timestamp,
+            source: wire.source,
+            payload,
+            trace_id: wire.trace_id,
+            correlation_id: wire.correlation_id,
+        }
+    }
+}
+
+/// v1 → v2: an agent loop published its approved intent as an
+/// `AgentThought` JSON string under the source
+/// `mechos-runtime::agent_loop/<agent_id>`.  Rewrap it as an
+/// [`EventPayload::Intent`] correlated by the event ID.
+fn upgrade_v1_payload(id: &Uuid, source: &str, payload: EventPayload) -> EventPayload {
+    let EventPayload::Reasoning(json) = payload else {
+        return payload;
+    };
+    let intent = source
+        .strip_prefix("mechos-runtime::agent_loop/")
+        .and_then(|agent_id| {
+            Some((
+                agent_id,
+                serde_json::from_str::<HardwareIntent>(&json).ok()?,
+            ))
+        });
+    match intent {
+        Some((agent_id, intent)) => EventPayload::Intent(
+            IntentEnvelope::new(intent, agent_id)
+                .with_correlation_id(*id)
+                .with_schema_version(LEGACY_SCHEMA_VERSION),
+        ),
+        None => EventPayload::Reasoning(json),
+    }
+}
+
+/// v3 → v4: adapter frames and operator intents travelled as JSON strings
+/// inside an `AgentThought`.  Give them their own variants; anything else
+/// stays [`EventPayload::Reasoning`].
+fn upgrade_v3_payload(payload: EventPayload) -> EventPayload {
+    let EventPayload::Reasoning(text) = payload else {
+        return payload;
+    };
+    if let Ok(intent) = serde_json::from_str::<HardwareIntent>(&text) {
+        return EventPayload::IntentDispatched(intent);
+    }
+    match serde_json::from_str::<serde_json::Value>(&text) {
+        Ok(frame) if frame.get("topic").is_some_and(serde_json::Value::is_string) => {
+            EventPayload::adapter_command(frame)
+        }
+        _ => EventPayload::Reasoning(text),
+    }
 }
 
 /// Variants of data that can be routed over the internal event bus.
@@ -79,14 +422,27 @@ pub enum EventPayload {
     Telemetry(TelemetryData),
     HardwareFault {
         component: String,
-        code: u32,
+        code: FaultCode,
         message: String,
     },
-    /// The LLM's internal reasoning output
-    AgentThought(String),
+    /// The LLM's internal reasoning output, or other free text meant for
+    /// the operator.  Decoded from its former name `AgentThought` too.
+    #[serde(alias = "AgentThought")]
+    Reasoning(String),
+    /// An intent issued outside the agent loop (e.g. by an operator at the
+    /// CLI), announced for dashboards and logs.
+    IntentDispatched(HardwareIntent),
+    /// A frame an adapter sends to (or a dashboard sends through) a
+    /// transport, such as a rosbridge `{"op": "publish", ...}` frame.
+    AdapterCommand {
+        /// Destination topic, e.g. `"/cmd_vel"`.
+        topic: String,
+        /// The complete frame.
+        json: serde_json::Value,
+    },
     /// A human operator's response to an [`HardwareIntent::AskHuman`] prompt,
     /// injected from the monitoring dashboard via the WebSocket API.
-    HumanResponse(String),
+    HumanResponse(HumanAnswer),
     /// A message received from a peer robot over the fleet network.
     PeerMessage {
         /// The robot ID that sent the message.
@@ -103,6 +459,10 @@ pub enum EventPayload {
         ranges: Vec<f32>,
         angle_min_rad: f32,
         angle_increment_rad: f32,
+        /// When the sensor captured the scan (its ROS header stamp), if the
+        /// adapter knows; otherwise the event's `timestamp` stands in.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        stamp: Option<DateTime<Utc>>,
     },
     /// Cockpit mode-toggle command sent by the human operator.
     ///
@@ -110,15 +470,482 @@ pub enum EventPayload {
     /// cycle; `false` resumes it.  This is independent of the joystick
     /// manual-override interlock.
     AgentModeToggle { paused: bool },
+    /// A kernel-approved intent on its way to the adapters.
+    Intent(IntentEnvelope),
+    /// Battery and power-supply reading, e.g. from a ROS 2 `/battery_state`
+    /// topic.
+    PowerStatus {
+        /// Pack voltage (V).
+        voltage: f32,
+        /// Pack current (A); negative while discharging.
+        current: f32,
+        /// `true` while the robot is charging.
+        charging: bool,
+        /// State of charge, `0.0`–`100.0`.
+        percent: f32,
+    },
+    /// One camera image for the Cockpit and vision-enabled decisions.
+    ///
+    /// Ingestion downscales raw frames and rejects oversized compressed ones,
+    /// so `width` × `height` is at most the middleware's frame limit.
+    CameraFrame {
+        /// Identifier the frame can be referred to by (e.g. from
+        /// [`HardwareIntent::AskHuman`]'s `context_image_id`).
+        image_id: String,
+        format: ImageFormat,
+        width: u32,
+        height: u32,
+        /// Pixel data (raw formats) or the encoded file (`jpeg` / `png`),
+        /// base64-encoded.
+        data_b64: String,
+    },
+    /// Vehicle orientation from a flight controller (radians, body frame
+    /// relative to NED).
+    Attitude {
+        roll_rad: f32,
+        pitch_rad: f32,
+        yaw_rad: f32,
+    },
+    /// Global position fix from a GPS / flight controller.
+    GpsFix {
+        /// WGS-84 latitude (degrees).
+        latitude_deg: f64,
+        /// WGS-84 longitude (degrees).
+        longitude_deg: f64,
+        /// Altitude above the home position.
+        altitude: Meters,
+    },
+    /// An adapter's link to its external endpoint (e.g. a rosbridge
+    /// WebSocket) changed state.
+    ConnectionState {
+        /// The adapter reporting the change, e.g. `"dashboard_sim"`.
+        component: String,
+        state: LinkState,
+        /// Reconnection attempts since the link was last up; `0` once
+        /// connected.
+        attempt: u32,
+    },
+    /// Periodic health report of the event bus itself, one entry per lane.
+    BusHealth { lanes: Vec<LaneStats> },
+    /// An obstacle followed across LiDAR scans, with its estimated velocity,
+    /// in the map frame.
+    TrackedObstacle {
+        /// Stable identifier of the track while it stays in view.
+        id: u64,
+        x: Meters,
+        y: Meters,
+        velocity_x: MetersPerSecond,
+        velocity_y: MetersPerSecond,
+        /// Radius of the circle enclosing the obstacle's points.
+        radius: Meters,
+    },
+    /// Progress report from the robot working on a fleet task.
+    TaskProgress {
+        task_id: String,
+        robot_id: String,
+        /// Estimated completion, `0.0`–`100.0`.
+        percent: f32,
+        /// Free-form status note, e.g. `"3 of 5 boxes moved"`.
+        note: String,
+    },
+    /// A fleet task was completed.
+    TaskCompleted {
+        task_id: String,
+        robot_id: String,
+        /// Result reported by the robot; `null` when it reported none.
+        result: serde_json::Value,
+    },
+    /// 2-D occupancy map of the robot's surroundings with its pose, for the
+    /// Cockpit map view.
+    MapSnapshot {
+        /// Map-frame position of the grid's minimum corner.
+        origin_x: Meters,
+        origin_y: Meters,
+        /// Side length of one square cell.
+        resolution: Meters,
+        /// Number of columns (along X).
+        width: u32,
+        /// Number of rows (along Y).
+        height: u32,
+        /// `width × height` cell bytes, row-major from the minimum corner,
+        /// base64-encoded.  Values follow ROS `nav_msgs/OccupancyGrid`:
+        /// `0` free, `100` occupied, `255` unknown.
+        cells_b64: String,
+        robot_x: Meters,
+        robot_y: Meters,
+        robot_heading_rad: f32,
+    },
+    /// An operator command to the kernel's safety state machine, e.g. from
+    /// the Cockpit's e-stop button.
+    SafetyRequest {
+        /// Echoed by the [`EventPayload::SafetyStatus`] confirming it.
+        request_id: String,
+        command: SafetyCommand,
+    },
+    /// State of the kernel's safety state machine, published in reply to
+    /// every [`EventPayload::SafetyRequest`] and whenever the e-stop latches.
+    SafetyStatus {
+        /// The request this confirms; `None` for unsolicited updates.
+        request_id: Option<String>,
+        /// `true` while the e-stop latch is engaged.
+        estopped: bool,
+        mode: SafetyMode,
+    },
+    /// An intent that passed the kernel gate but is held for an operator's
+    /// decision before it may run.
+    ApprovalRequest {
+        /// Echoed by the [`EventPayload::ApprovalDecision`] answering it.
+        request_id: String,
+        /// Boxed to keep every event small.
+        envelope: Box<IntentEnvelope>,
+        /// The verdict of every safety rule on the intent, in evaluation
+        /// order.
+        report: Vec<RuleVerdict>,
+    },
+    /// An operator's answer to an [`EventPayload::ApprovalRequest`].
+    ApprovalDecision {
+        request_id: String,
+        approved: bool,
+        /// Who decided, for the audit trail.
+        operator: String,
+        reason: Option<String>,
+    },
+    /// A request to list or change the kernel's safety policy at runtime,
+    /// e.g. from the Cockpit's authenticated policy endpoint.
+    PolicyRequest {
+        /// Echoed by the [`EventPayload::PolicyStatus`] answering it.
+        request_id: String,
+        /// Who asked, for the audit log.
+        operator: String,
+        command: PolicyCommand,
+    },
+    /// The kernel's safety policy, published in reply to every
+    /// [`EventPayload::PolicyRequest`].
+    PolicyStatus {
+        request_id: String,
+        /// Boxed to keep every event small.
+        report: Box<PolicyReport>,
+        /// Why the requested change was refused; `None` when it was applied.
+        error: Option<String>,
+    },
+    /// Outcome of an intent an adapter sent as a goal or service call: the
+    /// action result, the service response or a timeout.
+    IntentResult {
+        /// Identifier the adapter sent the goal with.
+        goal_id: String,
+        /// The ROS action or service, e.g. `"navigate_through_poses"`.
+        action: String,
+        success: bool,
+        /// Time from sending the goal to its result (or the timeout).
+        duration_ms: u64,
+        /// The robot's status text, or why the intent failed.
+        message: String,
+    },
+    /// A payload this release does not know, e.g. a variant added by a newer
+    /// peer, as its raw JSON.  Produced only when decoding an [`Event`], and
+    /// written back unchanged when the event is serialised; consumers should
+    /// log and skip it.
+    #[serde(skip)]
+    Unknown(serde_json::Value),
+}
+
+impl EventPayload {
+    /// An [`EventPayload::AdapterCommand`] for `frame`, addressed to its
+    /// `"topic"` field (empty when there is none).
+    pub fn adapter_command(frame: serde_json::Value) -> Self {
+        let topic = frame
+            .get("topic")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        EventPayload::AdapterCommand { topic, json: frame }
+    }
+}
+
+impl From<TelemetryData> for EventPayload {
+    fn from(data: TelemetryData) -> Self {
+        EventPayload::Telemetry(data)
+    }
+}
+
+impl From<IntentEnvelope> for EventPayload {
+    fn from(envelope: IntentEnvelope) -> Self {
+        EventPayload::Intent(envelope)
+    }
+}
+
+impl From<HumanAnswer> for EventPayload {
+    fn from(answer: HumanAnswer) -> Self {
+        EventPayload::HumanResponse(answer)
+    }
+}
+
+/// Encoding of a [`EventPayload::CameraFrame`], named after the ROS 2
+/// `sensor_msgs/Image` encodings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageFormat {
+    /// 8-bit greyscale, one byte per pixel.
+    Mono8,
+    /// 8-bit RGB, three bytes per pixel.
+    Rgb8,
+    /// 8-bit BGR, three bytes per pixel.
+    Bgr8,
+    /// 8-bit RGBA, four bytes per pixel.
+    Rgba8,
+    /// JPEG-compressed image.
+    Jpeg,
+    /// PNG-compressed image.
+    Png,
+}
+
+impl ImageFormat {
+    /// Bytes per pixel of a raw format; `None` for compressed formats.
+    pub fn bytes_per_pixel(self) -> Option<usize> {
+        match self {
+            ImageFormat::Mono8 => Some(1),
+            ImageFormat::Rgb8 | ImageFormat::Bgr8 => Some(3),
+            ImageFormat::Rgba8 => Some(4),
+            ImageFormat::Jpeg | ImageFormat::Png => None,
+        }
+    }
+}
+
+/// State of a supervised adapter link, carried by
+/// [`EventPayload::ConnectionState`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkState {
+    /// A connection attempt is in progress.
+    Connecting,
+    /// The link is up and subscriptions have been (re)sent.
+    Connected,
+    /// The link dropped or could not be established; a retry is pending.
+    Disconnected,
+}
+
+/// Operating mode of the kernel's safety state machine.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SafetyMode {
+    /// Every rule at its configured limits.
+    #[default]
+    Normal,
+    /// Motion capped at a crawl, e.g. while people work near the robot.
+    Reduced,
+}
+
+/// Command carried by [`EventPayload::SafetyRequest`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SafetyCommand {
+    /// Stop the robot and latch the e-stop.
+    EStop,
+    /// Release the e-stop latch.
+    Reset,
+    /// Switch the operating mode; refused while the e-stop is latched.
+    SetMode(SafetyMode),
+}
+
+/// The verdict of one kernel safety rule on an intent, carried by
+/// [`EventPayload::ApprovalRequest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleVerdict {
+    /// The rule's name, e.g. `"speed_cap"`.
+    pub rule: String,
+    pub passed: bool,
+    /// The violation, when the rule rejected the intent.
+    pub details: Option<String>,
+}
+
+/// What a kernel safety rule does with the intents it rejects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleMode {
+    /// A violation rejects the intent.
+    #[default]
+    Block,
+    /// A violation is logged and reported, but the intent still runs.
+    Warn,
+}
+
+/// A kernel safety rule as listed in a [`PolicyReport`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleInfo {
+    /// The rule's name, e.g. `"speed_cap"`.
+    pub rule: String,
+    pub mode: RuleMode,
+    /// `false` for interlocks that must always block, e.g. the e-stop.
+    pub may_warn: bool,
+    /// Numeric parameters an operator may adjust, e.g. `max_linear`.
+    pub params: BTreeMap<String, f32>,
+}
+
+/// The capabilities granted to one agent identity, as listed in a
+/// [`PolicyReport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapabilityGrant {
+    pub agent_id: String,
+    pub capabilities: Vec<Capability>,
+}
+
+/// How often one agent identity exercised one of its granted capabilities,
+/// as listed in a [`PolicyReport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapabilityUsage {
+    pub agent_id: String,
+    pub capability: Capability,
+    /// Successful capability checks since the kernel started.
+    pub uses: u64,
+    /// When the capability was last exercised; `None` if it never was.
+    pub last_used: Option<DateTime<Utc>>,
+}
+
+/// Command carried by [`EventPayload::PolicyRequest`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyCommand {
+    /// Change nothing; only report the current policy.
+    List,
+    /// Make a rule block or only warn about the intents it rejects.
+    SetRuleMode { rule: String, mode: RuleMode },
+    /// Set one of a rule's numeric parameters, validated by the rule.
+    SetRuleParam {
+        rule: String,
+        param: String,
+        value: f32,
+    },
+}
+
+/// A policy change an operator attempted, kept by the kernel.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicyAuditEntry {
+    pub timestamp: DateTime<Utc>,
+    pub operator: String,
+    pub command: PolicyCommand,
+    /// Why the change was refused; `None` when it was applied.
+    pub error: Option<String>,
+}
+
+/// The kernel's safety policy, carried by [`EventPayload::PolicyStatus`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PolicyReport {
+    /// Every safety rule, in evaluation order.
+    pub rules: Vec<RuleInfo>,
+    /// Every agent's capabilities, sorted by agent ID.
+    pub grants: Vec<CapabilityGrant>,
+    /// How often each grant was exercised, in the same order.
+    #[serde(default)]
+    pub usage: Vec<CapabilityUsage>,
+    /// Recent policy changes, oldest first.
+    pub audit: Vec<PolicyAuditEntry>,
+}
+
+/// Traffic counters of one event-bus lane, carried by
+/// [`EventPayload::BusHealth`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LaneStats {
+    /// `"global"` or the topic name, e.g. `"telemetry"`.
+    pub lane: String,
+    /// Events published since the bus was created.
+    pub published: u64,
+    /// Publish rate over the last report interval (events/s).
+    pub publish_rate_hz: f32,
+    /// Current number of subscribers.
+    pub subscribers: u32,
+    /// Dropped events not yet reported to their subscribers as `Lagged`.
+    pub lagging: u64,
+    /// Events lost to backpressure since the bus was created.
+    pub dropped: u64,
+}
+
+/// An operator's answer to an [`HardwareIntent::AskHuman`] question.
+///
+/// Decoded from the bare answer string sent before schema version 5 too,
+/// as an answer to no particular question.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "WireHumanAnswer")]
+pub struct HumanAnswer {
+    /// `question_id` of the `AskHuman` being answered; `None` answers the
+    /// oldest open question.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub question_id: Option<String>,
+    pub answer: String,
+    /// Identity of the operator who answered, checked against their
+    /// [`Capability::AnswerQuestions`] grant when the runtime authorizes
+    /// operators; `None` when unknown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operator: Option<String>,
+}
+
+impl HumanAnswer {
+    /// An answer to the oldest open question.
+    pub fn new(answer: impl Into<String>) -> Self {
+        Self {
+            question_id: None,
+            answer: answer.into(),
+            operator: None,
+        }
+    }
+
+    /// An answer to the question with `question_id`.
+    pub fn to_question(question_id: impl Into<String>, answer: impl Into<String>) -> Self {
+        Self {
+            question_id: Some(question_id.into()),
+            answer: answer.into(),
+            operator: None,
+        }
+    }
+
+    /// Name `operator` as the one who answered (builder-style).
+    pub fn with_operator(mut self, operator: impl Into<String>) -> Self {
+        self.operator = Some(operator.into());
+        self
+    }
+}
+
+/// Serialised form of a [`HumanAnswer`] being read, at any schema version.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum WireHumanAnswer {
+    Legacy(String),
+    Current {
+        #[serde(default)]
+        question_id: Option<String>,
+        answer: String,
+        #[serde(default)]
+        operator: Option<String>,
+    },
+}
+
+impl From<WireHumanAnswer> for HumanAnswer {
+    fn from(wire: WireHumanAnswer) -> Self {
+        match wire {
+            WireHumanAnswer::Legacy(answer) => HumanAnswer::new(answer),
+            WireHumanAnswer::Current {
+                question_id,
+                answer,
+                operator,
+            } => HumanAnswer {
+                question_id,
+                answer,
+                operator,
+            },
+        }
+    }
 }
 
 /// Robot telemetry snapshot.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TelemetryData {
-    pub position_x: f32,
-    pub position_y: f32,
+    pub position_x: Meters,
+    pub position_y: Meters,
     pub heading_rad: f32,
     pub battery_percent: u8,
+    /// When the pose was measured (the odometry message's header stamp), if
+    /// the adapter knows; otherwise the event's `timestamp` stands in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stamp: Option<DateTime<Utc>>,
 }
 
 /// Returns the full set of [`Capability`] grants that a standard MechOS agent
@@ -146,14 +973,181 @@ pub fn required_capabilities() -> Vec<Capability> {
     ]
 }
 
+/// Machine-readable reason of a hardware fault, carried by
+/// [`MechError::HardwareFault`] and [`EventPayload::HardwareFault`] so that
+/// the Cockpit, fleet peers and tests can react to a specific fault without
+/// parsing its message.
+///
+/// Serialised as its snake_case name (`"speed_cap_exceeded"`).  Names this
+/// release does not know, sent by newer peers, decode as
+/// [`Unknown`][Self::Unknown], and the bare integers used before
+/// [`SCHEMA_VERSION`] 3 are mapped with [`from_legacy`][Self::from_legacy].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum FaultCode {
+    /// A velocity exceeds the configured speed cap.
+    SpeedCapExceeded,
+    /// The end-effector target is outside its safe workspace.
+    WorkspaceViolation,
+    /// A waypoint path leaves the geofence or is malformed.
+    GeofenceViolation,
+    /// A joint or gripper command is outside its declared limits.
+    JointLimitExceeded,
+    /// A speech utterance is too long or the audio rate limit is reached.
+    SpeechLimitExceeded,
+    /// A human holds the manual override; AI motion is suspended.
+    OverrideActive,
+    /// The operator paused the agent loop.
+    AgentPaused,
+    /// Sensor data is too old to act on.
+    StaleSensor,
+    /// The trajectory check predicts a collision.
+    CollisionPredicted,
+    /// The intent arrived after its deadline.
+    DeadlineExpired,
+    /// The intent was pre-empted by a `Halt`.
+    HaltPreempted,
+    /// Two agents asked for the same hardware in one cycle.
+    ResourceConflict,
+    /// The addressed actuator, relay or device is not registered.
+    DeviceNotRegistered,
+    /// The component cannot execute this kind of intent.
+    Unsupported,
+    /// A skill failed or finished without producing an intent.
+    SkillFailed,
+    /// The runtime was configured inconsistently.
+    InvalidConfiguration,
+    /// The operator triggered an emergency stop.
+    EmergencyStop,
+    /// The battery is too low for the requested action.
+    BatteryLow,
+    /// The intent is held until an operator approves it.
+    ApprovalPending,
+    /// Unclassified fault, including codes from newer releases.
+    #[default]
+    Unknown,
+}
+
+impl FaultCode {
+    /// Every fault code, in declaration order.
+    pub const ALL: [FaultCode; 20] = [
+        FaultCode::SpeedCapExceeded,
+        FaultCode::WorkspaceViolation,
+        FaultCode::GeofenceViolation,
+        FaultCode::JointLimitExceeded,
+        FaultCode::SpeechLimitExceeded,
+        FaultCode::OverrideActive,
+        FaultCode::AgentPaused,
+        FaultCode::StaleSensor,
+        FaultCode::CollisionPredicted,
+        FaultCode::DeadlineExpired,
+        FaultCode::HaltPreempted,
+        FaultCode::ResourceConflict,
+        FaultCode::DeviceNotRegistered,
+        FaultCode::Unsupported,
+        FaultCode::SkillFailed,
+        FaultCode::InvalidConfiguration,
+        FaultCode::EmergencyStop,
+        FaultCode::BatteryLow,
+        FaultCode::ApprovalPending,
+        FaultCode::Unknown,
+    ];
+
+    /// The wire name of this code.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FaultCode::SpeedCapExceeded => "speed_cap_exceeded",
+            FaultCode::WorkspaceViolation => "workspace_violation",
+            FaultCode::GeofenceViolation => "geofence_violation",
+            FaultCode::JointLimitExceeded => "joint_limit_exceeded",
+            FaultCode::SpeechLimitExceeded => "speech_limit_exceeded",
+            FaultCode::OverrideActive => "override_active",
+            FaultCode::AgentPaused => "agent_paused",
+            FaultCode::StaleSensor => "stale_sensor",
+            FaultCode::CollisionPredicted => "collision_predicted",
+            FaultCode::DeadlineExpired => "deadline_expired",
+            FaultCode::HaltPreempted => "halt_preempted",
+            FaultCode::ResourceConflict => "resource_conflict",
+            FaultCode::DeviceNotRegistered => "device_not_registered",
+            FaultCode::Unsupported => "unsupported",
+            FaultCode::SkillFailed => "skill_failed",
+            FaultCode::InvalidConfiguration => "invalid_configuration",
+            FaultCode::EmergencyStop => "emergency_stop",
+            FaultCode::BatteryLow => "battery_low",
+            FaultCode::ApprovalPending => "approval_pending",
+            FaultCode::Unknown => "unknown",
+        }
+    }
+
+    /// Map a pre-v3 integer code.  Only `911` (emergency stop) had a fixed
+    /// meaning; everything else becomes [`Unknown`][Self::Unknown].
+    pub fn from_legacy(code: u64) -> Self {
+        match code {
+            911 => FaultCode::EmergencyStop,
+            _ => FaultCode::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for FaultCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for FaultCode {
+    type Err = MechError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        FaultCode::ALL
+            .into_iter()
+            .find(|code| code.as_str() == s)
+            .ok_or_else(|| MechError::Parsing(format!("unknown fault code '{s}'")))
+    }
+}
+
+impl Serialize for FaultCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for FaultCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FaultCodeVisitor;
+
+        impl serde::de::Visitor<'_> for FaultCodeVisitor {
+            type Value = FaultCode;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a fault code name or a legacy integer code")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<FaultCode, E> {
+                Ok(v.parse().unwrap_or_default())
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<FaultCode, E> {
+                Ok(FaultCode::from_legacy(v))
+            }
+        }
+
+        deserializer.deserialize_any(FaultCodeVisitor)
+    }
+}
+
 /// Global error type spanning hardware failures, LLM timeouts, and authorization rejections.
 #[derive(Error, Debug, Serialize, Deserialize)]
 pub enum MechError {
     #[error("Capability Denied: {0:?}")]
     Unauthorized(Capability),
 
-    #[error("Hardware Fault on {component}: {details}")]
-    HardwareFault { component: String, details: String },
+    #[error("Hardware Fault on {component} ({code}): {details}")]
+    HardwareFault {
+        #[serde(default)]
+        code: FaultCode,
+        component: String,
+        details: String,
+    },
 
     #[error("LLM Driver Error: {0}")]
     LlmInferenceFailed(String),
@@ -166,6 +1160,21 @@ pub enum MechError {
 
     #[error("Parsing Error: {0}")]
     Parsing(String),
+
+    /// The adapter's link is down, so the intent was not sent.
+    #[error("Adapter Unavailable: {adapter}: {details}")]
+    AdapterUnavailable { adapter: String, details: String },
+}
+
+impl MechError {
+    /// The [`FaultCode`] of a [`MechError::HardwareFault`], `None` for every
+    /// other variant.
+    pub fn fault_code(&self) -> Option<FaultCode> {
+        match self {
+            MechError::HardwareFault { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -183,8 +1192,8 @@ mod tests {
     #[test]
     fn hardware_intent_drive_roundtrip() {
         let intent = HardwareIntent::Drive {
-            linear_velocity: 1.5,
-            angular_velocity: -0.3,
+            linear_velocity: MetersPerSecond(1.5),
+            angular_velocity: RadiansPerSecond(-0.3),
         };
         let json = serde_json::to_string(&intent).unwrap();
         let back: HardwareIntent = serde_json::from_str(&json).unwrap();
@@ -193,8 +1202,8 @@ mod tests {
                 linear_velocity,
                 angular_velocity,
             } => {
-                assert!((linear_velocity - 1.5).abs() < f32::EPSILON);
-                assert!((angular_velocity - (-0.3)).abs() < f32::EPSILON);
+                assert!((linear_velocity.get() - 1.5).abs() < f32::EPSILON);
+                assert!((angular_velocity.get() - (-0.3)).abs() < f32::EPSILON);
             }
             _ => panic!("unexpected variant"),
         }
@@ -203,20 +1212,30 @@ mod tests {
     #[test]
     fn hardware_intent_move_end_effector_roundtrip() {
         let intent = HardwareIntent::MoveEndEffector {
-            x: 0.5,
-            y: -0.1,
-            z: 0.3,
+            x: Meters(0.5),
+            y: Meters(-0.1),
+            z: Meters(0.3),
+            frame_id: None,
         };
         let json = serde_json::to_string(&intent).unwrap();
         let back: HardwareIntent = serde_json::from_str(&json).unwrap();
         match back {
-            HardwareIntent::MoveEndEffector { x, y, z } => {
-                assert!((x - 0.5).abs() < f32::EPSILON);
-                assert!((y - (-0.1)).abs() < f32::EPSILON);
-                assert!((z - 0.3).abs() < f32::EPSILON);
+            HardwareIntent::MoveEndEffector { x, y, z, .. } => {
+                assert!((x.get() - 0.5).abs() < f32::EPSILON);
+                assert!((y.get() - (-0.1)).abs() < f32::EPSILON);
+                assert!((z.get() - 0.3).abs() < f32::EPSILON);
             }
             _ => panic!("unexpected variant"),
         }
+        // The frame is omitted when defaulted and kept when named.
+        assert!(!json.contains("frame_id"));
+        let json =
+            r#"{"action":"MoveEndEffector","payload":{"x":2.0,"y":1.0,"z":0.5,"frame_id":"map"}}"#;
+        let intent: HardwareIntent = serde_json::from_str(json).unwrap();
+        assert!(matches!(
+            intent,
+            HardwareIntent::MoveEndEffector { frame_id: Some(ref f), .. } if f == MAP_FRAME
+        ));
     }
 
     #[test]
@@ -224,6 +1243,7 @@ mod tests {
         let intent = HardwareIntent::AskHuman {
             question: "Which shelf should I pick from?".to_string(),
             context_image_id: Some("frame_042".to_string()),
+            question_id: Some("q-7".to_string()),
         };
         let json = serde_json::to_string(&intent).unwrap();
         let back: HardwareIntent = serde_json::from_str(&json).unwrap();
@@ -231,9 +1251,11 @@ mod tests {
             HardwareIntent::AskHuman {
                 question,
                 context_image_id,
+                question_id,
             } => {
                 assert_eq!(question, "Which shelf should I pick from?");
                 assert_eq!(context_image_id.as_deref(), Some("frame_042"));
+                assert_eq!(question_id.as_deref(), Some("q-7"));
             }
             _ => panic!("unexpected variant"),
         }
@@ -244,6 +1266,7 @@ mod tests {
         let intent = HardwareIntent::AskHuman {
             question: "Am I clear to proceed?".to_string(),
             context_image_id: None,
+            question_id: None,
         };
         let json = serde_json::to_string(&intent).unwrap();
         let back: HardwareIntent = serde_json::from_str(&json).unwrap();
@@ -268,6 +1291,149 @@ mod tests {
         assert!(json.contains("MessagePeer"));
         assert!(json.contains("BroadcastFleet"));
         assert!(json.contains("PostTask"));
+        assert!(json.contains("Speak"));
+        assert!(json.contains("PlaySound"));
+        assert!(json.contains("FollowWaypoints"));
+        assert!(json.contains("SetGripper"));
+        assert!(json.contains("MoveJoint"));
+        assert!(json.contains("Halt"));
+        assert!(json.contains("Dock"));
+        assert!(json.contains("Undock"));
+    }
+
+    #[test]
+    fn hardware_intent_dock_and_undock_roundtrip() {
+        assert_eq!(
+            serde_json::to_string(&HardwareIntent::Dock).unwrap(),
+            r#"{"action":"Dock"}"#
+        );
+        let back: HardwareIntent = serde_json::from_str(r#"{"action":"Undock"}"#).unwrap();
+        assert!(matches!(back, HardwareIntent::Undock));
+    }
+
+    #[test]
+    fn hardware_intent_flight_roundtrip() {
+        assert_eq!(
+            serde_json::to_string(&HardwareIntent::Arm { armed: true }).unwrap(),
+            r#"{"action":"Arm","payload":{"armed":true}}"#
+        );
+        let back: HardwareIntent = serde_json::from_str(
+            r#"{"action":"Goto","payload":{"latitude_deg":47.3977419,"longitude_deg":8.5455938,"altitude":12.5}}"#,
+        )
+        .unwrap();
+        match back {
+            HardwareIntent::Goto {
+                latitude_deg,
+                longitude_deg,
+                altitude,
+            } => {
+                assert_eq!(latitude_deg, 47.3977419);
+                assert_eq!(longitude_deg, 8.5455938);
+                assert_eq!(altitude, Meters(12.5));
+            }
+            other => panic!("expected Goto, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn hardware_intent_move_joint_roundtrip() {
+        let intent = HardwareIntent::MoveJoint {
+            joint: "elbow".to_string(),
+            angle_rad: 1.2,
+            max_velocity: RadiansPerSecond(0.5),
+        };
+        let json = serde_json::to_string(&intent).unwrap();
+        let back: HardwareIntent = serde_json::from_str(&json).unwrap();
+        match back {
+            HardwareIntent::MoveJoint {
+                joint,
+                angle_rad,
+                max_velocity,
+            } => {
+                assert_eq!(joint, "elbow");
+                assert!((angle_rad - 1.2).abs() < f32::EPSILON);
+                assert!((max_velocity.get() - 0.5).abs() < f32::EPSILON);
+            }
+            _ => panic!("unexpected variant"),
+        }
+    }
+
+    #[test]
+    fn hardware_intent_follow_waypoints_roundtrip() {
+        let json = r#"{"action":"FollowWaypoints","payload":{"points":[[1.0,2.0],[3.5,-1.0]],"max_speed":0.4}}"#;
+        let intent: HardwareIntent = serde_json::from_str(json).unwrap();
+        match intent {
+            HardwareIntent::FollowWaypoints { points, max_speed } => {
+                assert_eq!(
+                    points,
+                    vec![(Meters(1.0), Meters(2.0)), (Meters(3.5), Meters(-1.0))]
+                );
+                assert!((max_speed.get() - 0.4).abs() < f32::EPSILON);
+            }
+            _ => panic!("unexpected variant"),
+        }
+    }
+
+    #[test]
+    fn hardware_intent_speak_roundtrip() {
+        let intent = HardwareIntent::Speak {
+            text: "Excuse me, coming through.".to_string(),
+            voice: Some("en-GB".to_string()),
+        };
+        let json = serde_json::to_string(&intent).unwrap();
+        assert!(json.contains(r#""action":"Speak""#));
+        let back: HardwareIntent = serde_json::from_str(&json).unwrap();
+        match back {
+            HardwareIntent::Speak { text, voice } => {
+                assert_eq!(text, "Excuse me, coming through.");
+                assert_eq!(voice.as_deref(), Some("en-GB"));
+            }
+            _ => panic!("unexpected variant"),
+        }
+    }
+
+    #[test]
+    fn intent_envelope_expiry() {
+        let now = Utc::now();
+        let drive = IntentEnvelope::new(
+            HardwareIntent::Drive {
+                linear_velocity: MetersPerSecond(0.5),
+                angular_velocity: RadiansPerSecond(0.0),
+            },
+            "agent",
+        )
+        .with_deadline(now);
+        assert_eq!(drive.priority, IntentPriority::Normal);
+        assert!(!drive.is_expired_at(now));
+        assert!(drive.is_expired_at(now + chrono::Duration::milliseconds(1)));
+
+        let halt = IntentEnvelope::new(
+            HardwareIntent::Halt {
+                reason: "test".to_string(),
+            },
+            "agent",
+        )
+        .with_deadline(now);
+        assert_eq!(halt.priority, IntentPriority::Critical);
+        assert!(!halt.is_expired_at(now + chrono::Duration::seconds(60)));
+    }
+
+    #[test]
+    fn intent_envelope_event_roundtrip() {
+        let envelope = IntentEnvelope::new(
+            HardwareIntent::TriggerRelay {
+                relay_id: "pump".to_string(),
+                state: true,
+            },
+            "agent",
+        );
+        let id = envelope.correlation_id;
+        let json = serde_json::to_string(&EventPayload::Intent(envelope)).unwrap();
+        assert!(!json.contains("deadline"));
+        let back: EventPayload = serde_json::from_str(&json).unwrap();
+        assert!(
+            matches!(back, EventPayload::Intent(ref e) if e.correlation_id == id && e.issued_by == "agent")
+        );
     }
 
     #[test]
@@ -277,12 +1443,14 @@ mod tests {
             timestamp: Utc::now(),
             source: "mechos-middleware::ros2".to_string(),
             payload: EventPayload::Telemetry(TelemetryData {
-                position_x: 1.0,
-                position_y: 2.0,
+                position_x: Meters(1.0),
+                position_y: Meters(2.0),
                 heading_rad: 0.5,
                 battery_percent: 80,
+                stamp: None,
             }),
             trace_id: None,
+            correlation_id: None,
         };
         let json = serde_json::to_string(&event).unwrap();
         let back: Event = serde_json::from_str(&json).unwrap();
@@ -290,6 +1458,170 @@ mod tests {
         assert_eq!(event.source, back.source);
     }
 
+    #[test]
+    fn unknown_payload_variants_from_newer_peers_decode_as_unknown() {
+        let json = r#"{"schema_version":99,"id":"6f1c1f7e-2b7e-4a53-9d5c-0c1d2e3f4a5b",
+            "timestamp":"2026-01-01T00:00:00Z","source":"mechos-future",
+            "payload":{"Teleport":{"to":"moon"}}}"#;
+        let event: Event = serde_json::from_str(json).unwrap();
+        let EventPayload::Unknown(raw) = &event.payload else {
+            panic!("expected Unknown, got {:?}", event.payload);
+        };
+        assert_eq!(raw["Teleport"]["to"], "moon");
+        // Forwarded unchanged.
+        let value = serde_json::to_value(&event).unwrap();
+        assert_eq!(value["payload"], serde_json::json!({"Teleport": {"to": "moon"}}));
+
+        // An intent variant this release does not know, inside a known payload.
+        let json = r#"{"id":"6f1c1f7e-2b7e-4a53-9d5c-0c1d2e3f4a5b",
+            "timestamp":"2026-01-01T00:00:00Z","source":"mechos-future",
+            "payload":{"IntentDispatched":{"action":"Backflip","payload":{"height":1.0}}}}"#;
+        let event: Event = serde_json::from_str(json).unwrap();
+        assert!(matches!(event.payload, EventPayload::Unknown(_)));
+
+        // A malformed payload of a known variant is still an error.
+        let json = r#"{"id":"6f1c1f7e-2b7e-4a53-9d5c-0c1d2e3f4a5b",
+            "timestamp":"2026-01-01T00:00:00Z","source":"mechos-future",
+            "payload":{"Reasoning":42}}"#;
+        assert!(serde_json::from_str::<Event>(json).is_err());
+    }
+
+    #[test]
+    fn event_serialises_current_schema_version() {
+        let request_id = Uuid::new_v4();
+        let event = Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: "mechos-middleware::ros2".to_string(),
+            payload: EventPayload::HumanResponse(HumanAnswer::new("ok")),
+            trace_id: Some("00-abc-def-01".to_string()),
+            correlation_id: Some(request_id),
+        };
+        let value = serde_json::to_value(&event).unwrap();
+        assert_eq!(value["schema_version"], SCHEMA_VERSION);
+        assert_eq!(value["trace_id"], "00-abc-def-01");
+        let back: Event = serde_json::from_value(value).unwrap();
+        assert_eq!(back.correlation_id, Some(request_id));
+
+        let envelope = IntentEnvelope::new(HardwareIntent::SetGripper { position: 0.5 }, "a");
+        let value = serde_json::to_value(&envelope).unwrap();
+        assert_eq!(value["schema_version"], SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn v1_event_without_version_is_decoded() {
+        let json = r#"{
+            "id": "5f0c6c2e-3c1b-4f47-9a59-0b1f5c7e2d11",
+            "timestamp": "2025-01-01T00:00:00Z",
+            "source": "mechos-middleware::ros2/fleet/communications",
+            "payload": {"PeerMessage": {"from_robot_id": "robot_b", "message": "hi"}},
+            "traceparent": "00-abc-def-01"
+        }"#;
+        let event: Event = serde_json::from_str(json).unwrap();
+        assert!(matches!(event.payload, EventPayload::PeerMessage { .. }));
+        assert_eq!(event.trace_id.as_deref(), Some("00-abc-def-01"));
+    }
+
+    #[test]
+    fn v1_agent_loop_intent_is_migrated_to_envelope() {
+        let json = r#"{
+            "id": "5f0c6c2e-3c1b-4f47-9a59-0b1f5c7e2d11",
+            "timestamp": "2025-01-01T00:00:00Z",
+            "source": "mechos-runtime::agent_loop/robot_b",
+            "payload": {"AgentThought": "{\"action\":\"PostTask\",\"payload\":{\"title\":\"t\",\"description\":\"d\"}}"}
+        }"#;
+        let event: Event = serde_json::from_str(json).unwrap();
+        match event.payload {
+            EventPayload::Intent(envelope) => {
+                assert!(matches!(envelope.intent, HardwareIntent::PostTask { .. }));
+                assert_eq!(envelope.issued_by, "robot_b");
+                assert_eq!(envelope.correlation_id, event.id);
+                assert_eq!(envelope.schema_version, LEGACY_SCHEMA_VERSION);
+            }
+            other => panic!("expected Intent, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn v1_thought_from_other_source_is_not_migrated() {
+        let json = r#"{
+            "id": "5f0c6c2e-3c1b-4f47-9a59-0b1f5c7e2d11",
+            "timestamp": "2025-01-01T00:00:00Z",
+            "source": "mechos-middleware::dashboard_override",
+            "payload": {"AgentThought": "{\"action\":\"Halt\",\"payload\":{\"reason\":\"x\"}}"}
+        }"#;
+        let event: Event = serde_json::from_str(json).unwrap();
+        // Not an approved intent, but still an intent rather than free text.
+        assert!(matches!(
+            event.payload,
+            EventPayload::IntentDispatched(HardwareIntent::Halt { .. })
+        ));
+    }
+
+    #[test]
+    fn v3_thoughts_are_split_into_structured_payloads() {
+        let decode = |payload: &str| -> EventPayload {
+            let json = format!(
+                r#"{{"schema_version": 3, "id": "5f0c6c2e-3c1b-4f47-9a59-0b1f5c7e2d11",
+                    "timestamp": "2025-01-01T00:00:00Z", "source": "mechos-middleware::ros2/cmd_vel",
+                    "payload": {{"AgentThought": {payload}}}}}"#
+            );
+            serde_json::from_str::<Event>(&json).unwrap().payload
+        };
+
+        match decode(r#""{\"op\":\"publish\",\"topic\":\"/cmd_vel\",\"msg\":{}}""#) {
+            EventPayload::AdapterCommand { topic, json } => {
+                assert_eq!(topic, "/cmd_vel");
+                assert_eq!(json["op"], "publish");
+            }
+            other => panic!("expected AdapterCommand, got {other:?}"),
+        }
+        assert!(matches!(
+            decode(r#""{\"action\":\"Halt\",\"payload\":{\"reason\":\"x\"}}""#),
+            EventPayload::IntentDispatched(HardwareIntent::Halt { .. })
+        ));
+        assert!(matches!(
+            decode(r#""heading to the dock""#),
+            EventPayload::Reasoning(text) if text == "heading to the dock"
+        ));
+    }
+
+    #[test]
+    fn current_reasoning_is_never_reinterpreted() {
+        let event = Event {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            source: "mechos-runtime::agent".to_string(),
+            payload: EventPayload::Reasoning(r#"{"topic":"/cmd_vel"}"#.to_string()),
+            trace_id: None,
+            correlation_id: None,
+        };
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains(r#""Reasoning""#), "{json}");
+        let back: Event = serde_json::from_str(&json).unwrap();
+        assert!(matches!(back.payload, EventPayload::Reasoning(_)));
+    }
+
+    #[test]
+    fn newer_event_with_unknown_fields_is_decoded() {
+        let json = r#"{
+            "schema_version": 99,
+            "id": "5f0c6c2e-3c1b-4f47-9a59-0b1f5c7e2d11",
+            "timestamp": "2025-01-01T00:00:00Z",
+            "source": "mechos-runtime::agent_loop/robot_c",
+            "payload": {"Intent": {
+                "intent": {"action": "BroadcastFleet", "payload": {"message": "hello"}},
+                "issued_by": "robot_c",
+                "correlation_id": "5f0c6c2e-3c1b-4f47-9a59-0b1f5c7e2d11",
+                "schema_version": 99,
+                "hop_count": 3
+            }},
+            "region": "eu"
+        }"#;
+        let event: Event = serde_json::from_str(json).unwrap();
+        assert!(matches!(event.payload, EventPayload::Intent(ref e) if e.schema_version == 99));
+    }
+
     #[test]
     fn required_capabilities_contains_hardware_invoke_and_sensor_read() {
         let caps = required_capabilities();
@@ -407,33 +1739,100 @@ mod tests {
 
     #[test]
     fn human_response_roundtrip() {
-        let payload = EventPayload::HumanResponse("Yes, push it".to_string());
-        let json = serde_json::to_string(&payload).unwrap();
+        let answer = HumanAnswer::to_question("q-1", "Yes, push it").with_operator("alice");
+        let json = serde_json::to_string(&EventPayload::HumanResponse(answer.clone())).unwrap();
         let back: EventPayload = serde_json::from_str(&json).unwrap();
         assert!(
-            matches!(back, EventPayload::HumanResponse(ref s) if s == "Yes, push it"),
+            matches!(back, EventPayload::HumanResponse(ref a) if *a == answer),
             "HumanResponse must survive a JSON round-trip"
         );
     }
 
+    #[test]
+    fn legacy_human_response_answers_no_particular_question() {
+        let back: EventPayload = serde_json::from_str(r#"{"HumanResponse":"Yes"}"#).unwrap();
+        assert!(matches!(back, EventPayload::HumanResponse(ref a) if a == &HumanAnswer::new("Yes")));
+        let schema = serde_json::to_string(&schemars::schema_for!(HardwareIntent)).unwrap();
+        assert!(!schema.contains("question_id"), "the LLM never picks question IDs");
+    }
+
     #[test]
     fn mech_error_display() {
         let err = MechError::Unauthorized(Capability::ModelInference);
         assert!(err.to_string().contains("Capability Denied"));
 
         let err2 = MechError::HardwareFault {
+            code: FaultCode::Unknown,
             component: "arm_joint_1".to_string(),
             details: "overcurrent".to_string(),
         };
         assert!(err2.to_string().contains("arm_joint_1"));
     }
 
+    #[test]
+    fn fault_code_wire_names_roundtrip() {
+        for code in FaultCode::ALL {
+            let json = serde_json::to_string(&code).unwrap();
+            assert_eq!(json, format!("\"{code}\""));
+            let back: FaultCode = serde_json::from_str(&json).unwrap();
+            assert_eq!(back, code);
+        }
+        assert_eq!(
+            "geofence_violation".parse::<FaultCode>().unwrap(),
+            FaultCode::GeofenceViolation
+        );
+        assert!("not_a_fault".parse::<FaultCode>().is_err());
+    }
+
+    #[test]
+    fn fault_code_decodes_legacy_integers_and_unknown_names() {
+        let legacy: EventPayload = serde_json::from_str(
+            r#"{"HardwareFault":{"component":"cli","code":911,"message":"stop"}}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            legacy,
+            EventPayload::HardwareFault {
+                code: FaultCode::EmergencyStop,
+                ..
+            }
+        ));
+
+        let newer: FaultCode = serde_json::from_str(r#""battery_swelling""#).unwrap();
+        assert_eq!(newer, FaultCode::Unknown);
+        assert_eq!(FaultCode::from_legacy(42), FaultCode::Unknown);
+    }
+
+    #[test]
+    fn hardware_fault_error_carries_its_code() {
+        let err = MechError::HardwareFault {
+            code: FaultCode::StaleSensor,
+            component: "lidar".to_string(),
+            details: "scan is 2 s old".to_string(),
+        };
+        assert_eq!(err.fault_code(), Some(FaultCode::StaleSensor));
+        assert!(err.to_string().contains("(stale_sensor)"));
+        assert_eq!(MechError::Parsing("x".into()).fault_code(), None);
+
+        let json = serde_json::to_string(&err).unwrap();
+        assert!(json.contains(r#""code":"stale_sensor""#));
+        let back: MechError = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.fault_code(), Some(FaultCode::StaleSensor));
+
+        // Errors serialised before the code existed decode as Unknown.
+        let old: MechError =
+            serde_json::from_str(r#"{"HardwareFault":{"component":"arm","details":"stall"}}"#)
+                .unwrap();
+        assert_eq!(old.fault_code(), Some(FaultCode::Unknown));
+    }
+
     #[test]
     fn lidar_scan_roundtrip() {
         let payload = EventPayload::LidarScan {
             ranges: vec![0.5, 1.0, 1.5, 2.0],
             angle_min_rad: -std::f32::consts::FRAC_PI_2,
             angle_increment_rad: 0.017453293,
+            stamp: DateTime::from_timestamp(1_700_000_000, 0),
         };
         let json = serde_json::to_string(&payload).unwrap();
         let back: EventPayload = serde_json::from_str(&json).unwrap();
@@ -442,15 +1841,239 @@ mod tests {
                 ranges,
                 angle_min_rad,
                 angle_increment_rad,
+                stamp,
             } => {
                 assert_eq!(ranges.len(), 4);
                 assert!((angle_min_rad - (-std::f32::consts::FRAC_PI_2)).abs() < 1e-6);
                 assert!((angle_increment_rad - 0.017453293).abs() < 1e-9);
+                assert_eq!(stamp, DateTime::from_timestamp(1_700_000_000, 0));
             }
             _ => panic!("expected LidarScan"),
         }
     }
 
+    #[test]
+    fn power_status_roundtrip() {
+        let payload = EventPayload::PowerStatus {
+            voltage: 24.6,
+            current: -3.2,
+            charging: false,
+            percent: 57.5,
+        };
+        let json = serde_json::to_string(&payload).unwrap();
+        let back: EventPayload = serde_json::from_str(&json).unwrap();
+        match back {
+            EventPayload::PowerStatus {
+                voltage,
+                current,
+                charging,
+                percent,
+            } => {
+                assert!((voltage - 24.6).abs() < f32::EPSILON);
+                assert!((current + 3.2).abs() < f32::EPSILON);
+                assert!(!charging);
+                assert!((percent - 57.5).abs() < f32::EPSILON);
+            }
+            _ => panic!("expected PowerStatus"),
+        }
+    }
+
+    #[test]
+    fn flight_telemetry_roundtrip() {
+        let json = serde_json::to_string(&EventPayload::GpsFix {
+            latitude_deg: -33.8688197,
+            longitude_deg: 151.2092955,
+            altitude: Meters(40.0),
+        })
+        .unwrap();
+        assert!(matches!(
+            serde_json::from_str::<EventPayload>(&json).unwrap(),
+            EventPayload::GpsFix { latitude_deg, .. } if latitude_deg == -33.8688197
+        ));
+        let back: EventPayload =
+            serde_json::from_str(r#"{"Attitude":{"roll_rad":0.1,"pitch_rad":-0.2,"yaw_rad":1.5}}"#)
+                .unwrap();
+        assert!(matches!(back, EventPayload::Attitude { yaw_rad, .. } if yaw_rad == 1.5));
+    }
+
+    #[test]
+    fn tracked_obstacle_roundtrip() {
+        let json = serde_json::to_string(&EventPayload::TrackedObstacle {
+            id: 7,
+            x: Meters(2.0),
+            y: Meters(-1.0),
+            velocity_x: MetersPerSecond(0.8),
+            velocity_y: MetersPerSecond(0.0),
+            radius: Meters(0.25),
+        })
+        .unwrap();
+        assert!(json.contains("TrackedObstacle"));
+        assert!(matches!(
+            serde_json::from_str::<EventPayload>(&json).unwrap(),
+            EventPayload::TrackedObstacle { id: 7, velocity_x, .. } if velocity_x == MetersPerSecond(0.8)
+        ));
+    }
+
+    #[test]
+    fn task_progress_and_completion_roundtrip() {
+        let json = serde_json::to_string(&EventPayload::TaskProgress {
+            task_id: "t1".to_string(),
+            robot_id: "robot_a".to_string(),
+            percent: 40.0,
+            note: "2 of 5 boxes moved".to_string(),
+        })
+        .unwrap();
+        assert!(matches!(
+            serde_json::from_str::<EventPayload>(&json).unwrap(),
+            EventPayload::TaskProgress { percent, ref note, .. } if percent == 40.0 && note == "2 of 5 boxes moved"
+        ));
+        let back: EventPayload = serde_json::from_str(
+            r#"{"TaskCompleted":{"task_id":"t1","robot_id":"robot_a","result":{"boxes":5}}}"#,
+        )
+        .unwrap();
+        assert!(matches!(back, EventPayload::TaskCompleted { ref result, .. } if result["boxes"] == 5));
+    }
+
+    #[test]
+    fn safety_request_and_status_wire_format() {
+        let back: EventPayload = serde_json::from_str(
+            r#"{"SafetyRequest":{"request_id":"r1","command":{"set_mode":"reduced"}}}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            back,
+            EventPayload::SafetyRequest {
+                command: SafetyCommand::SetMode(SafetyMode::Reduced),
+                ..
+            }
+        ));
+        let json = serde_json::to_string(&EventPayload::SafetyRequest {
+            request_id: "r2".to_string(),
+            command: SafetyCommand::EStop,
+        })
+        .unwrap();
+        assert!(json.contains(r#""command":"e_stop""#));
+        let json = serde_json::to_string(&EventPayload::SafetyStatus {
+            request_id: None,
+            estopped: true,
+            mode: SafetyMode::Normal,
+        })
+        .unwrap();
+        assert!(json.contains(r#""mode":"normal""#));
+    }
+
+    #[test]
+    fn approval_request_carries_intent_and_report() {
+        let request = EventPayload::ApprovalRequest {
+            request_id: "a1".to_string(),
+            envelope: Box::new(IntentEnvelope::new(HardwareIntent::Dock, "agent")),
+            report: vec![RuleVerdict {
+                rule: "speed_cap".to_string(),
+                passed: true,
+                details: None,
+            }],
+        };
+        let json: serde_json::Value = serde_json::to_value(&request).unwrap();
+        let body = &json["ApprovalRequest"];
+        assert_eq!(body["envelope"]["intent"]["action"], "Dock");
+        assert_eq!(body["report"][0]["rule"], "speed_cap");
+
+        let back: EventPayload = serde_json::from_str(
+            r#"{"ApprovalDecision":{"request_id":"a1","approved":false,"operator":"alice","reason":"too close"}}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            back,
+            EventPayload::ApprovalDecision { approved: false, ref operator, .. } if operator == "alice"
+        ));
+    }
+
+    #[test]
+    fn policy_commands_roundtrip() {
+        let back: EventPayload = serde_json::from_str(
+            r#"{"PolicyRequest":{"request_id":"p1","operator":"alice","command":{"set_rule_mode":{"rule":"geofence","mode":"warn"}}}}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            back,
+            EventPayload::PolicyRequest {
+                command: PolicyCommand::SetRuleMode {
+                    mode: RuleMode::Warn,
+                    ..
+                },
+                ..
+            }
+        ));
+        let json = serde_json::to_string(&PolicyCommand::SetRuleParam {
+            rule: "speed_cap".to_string(),
+            param: "max_linear".to_string(),
+            value: 0.5,
+        })
+        .unwrap();
+        assert_eq!(
+            json,
+            r#"{"set_rule_param":{"rule":"speed_cap","param":"max_linear","value":0.5}}"#
+        );
+        assert_eq!(serde_json::to_string(&PolicyCommand::List).unwrap(), r#""list""#);
+    }
+
+    #[test]
+    fn connection_state_roundtrip() {
+        let back: EventPayload = serde_json::from_str(
+            r#"{"ConnectionState":{"component":"dashboard_sim","state":"disconnected","attempt":3}}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            back,
+            EventPayload::ConnectionState { state: LinkState::Disconnected, attempt: 3, .. }
+        ));
+    }
+
+    #[test]
+    fn bus_health_roundtrip() {
+        let payload = EventPayload::BusHealth {
+            lanes: vec![LaneStats {
+                lane: "telemetry".to_string(),
+                published: 1200,
+                publish_rate_hz: 40.0,
+                subscribers: 3,
+                lagging: 0,
+                dropped: 17,
+            }],
+        };
+        let json = serde_json::to_string(&payload).unwrap();
+        let back: EventPayload = serde_json::from_str(&json).unwrap();
+        assert!(matches!(
+            back,
+            EventPayload::BusHealth { ref lanes } if lanes[0].dropped == 17 && lanes[0].lane == "telemetry"
+        ));
+    }
+
+    #[test]
+    fn camera_frame_roundtrip() {
+        let payload = EventPayload::CameraFrame {
+            image_id: "front/42".to_string(),
+            format: ImageFormat::Rgb8,
+            width: 2,
+            height: 1,
+            data_b64: "AAAAAAAA".to_string(),
+        };
+        let json = serde_json::to_string(&payload).unwrap();
+        assert!(json.contains(r#""format":"rgb8""#));
+        let back: EventPayload = serde_json::from_str(&json).unwrap();
+        assert!(matches!(
+            back,
+            EventPayload::CameraFrame {
+                format: ImageFormat::Rgb8,
+                width: 2,
+                height: 1,
+                ..
+            }
+        ));
+        assert_eq!(ImageFormat::Jpeg.bytes_per_pixel(), None);
+        assert_eq!(ImageFormat::Rgba8.bytes_per_pixel(), Some(4));
+    }
+
     #[test]
     fn agent_mode_toggle_paused_roundtrip() {
         let payload = EventPayload::AgentModeToggle { paused: true };
@@ -0,0 +1,202 @@
+//! [`Meters`], [`MetersPerSecond`] and [`RadiansPerSecond`] – typed physical
+//! units.
+//!
+//! Thin `f32` newtypes that make the unit part of the type, so an adapter
+//! cannot hand a speed in km/h to a field that expects m/s, or a distance to a
+//! field that expects a velocity.  Each one serialises transparently as a
+//! bare number, so the JSON wire format and the LLM-facing schema are
+//! unchanged.
+//!
+//! ```rust
+//! use mechos_types::{Meters, MetersPerSecond};
+//!
+//! let speed = MetersPerSecond::from_km_per_hour(3.6);
+//! assert_eq!(speed, MetersPerSecond(1.0));
+//! assert_eq!(Meters::from_millimeters(250.0).get(), 0.25);
+//! assert_eq!(serde_json::to_string(&speed).unwrap(), "1.0");
+//! ```
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+macro_rules! unit {
+    ($(#[$meta:meta])* $name:ident, $suffix:literal) => {
+        $(#[$meta])*
+        #[derive(
+            Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize, JsonSchema,
+        )]
+        #[serde(transparent)]
+        pub struct $name(pub f32);
+
+        impl $name {
+            pub const ZERO: Self = Self(0.0);
+
+            pub const fn new(value: f32) -> Self {
+                Self(value)
+            }
+
+            /// The raw value in this unit.
+            pub const fn get(self) -> f32 {
+                self.0
+            }
+
+            pub fn abs(self) -> Self {
+                Self(self.0.abs())
+            }
+
+            pub fn is_finite(self) -> bool {
+                self.0.is_finite()
+            }
+        }
+
+        impl From<f32> for $name {
+            fn from(value: f32) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for f32 {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)?;
+                write!(f, " {}", $suffix)
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 - rhs.0)
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl Mul<f32> for $name {
+            type Output = Self;
+            fn mul(self, rhs: f32) -> Self {
+                Self(self.0 * rhs)
+            }
+        }
+    };
+}
+
+unit!(
+    /// A distance or coordinate in metres.
+    Meters,
+    "m"
+);
+
+unit!(
+    /// A linear velocity in metres per second.
+    MetersPerSecond,
+    "m/s"
+);
+
+unit!(
+    /// An angular velocity in radians per second.
+    RadiansPerSecond,
+    "rad/s"
+);
+
+impl Meters {
+    pub fn from_millimeters(mm: f32) -> Self {
+        Self(mm / 1000.0)
+    }
+
+    pub fn to_millimeters(self) -> f32 {
+        self.0 * 1000.0
+    }
+
+    /// Average speed needed to cover this distance in `seconds`.
+    pub fn per_seconds(self, seconds: f32) -> MetersPerSecond {
+        MetersPerSecond(self.0 / seconds)
+    }
+}
+
+impl MetersPerSecond {
+    pub fn from_km_per_hour(kmh: f32) -> Self {
+        Self(kmh / 3.6)
+    }
+
+    pub fn to_km_per_hour(self) -> f32 {
+        self.0 * 3.6
+    }
+
+    /// Distance covered at this speed in `seconds`.
+    pub fn over_seconds(self, seconds: f32) -> Meters {
+        Meters(self.0 * seconds)
+    }
+}
+
+impl RadiansPerSecond {
+    pub fn from_degrees_per_second(deg: f32) -> Self {
+        Self(deg.to_radians())
+    }
+
+    pub fn to_degrees_per_second(self) -> f32 {
+        self.0.to_degrees()
+    }
+}
+
+/// Tangential speed of a point `radius` away from the rotation axis.
+impl Mul<Meters> for RadiansPerSecond {
+    type Output = MetersPerSecond;
+    fn mul(self, radius: Meters) -> MetersPerSecond {
+        MetersPerSecond(self.0 * radius.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn units_serialise_as_bare_numbers() {
+        assert_eq!(serde_json::to_string(&Meters(1.5)).unwrap(), "1.5");
+        let back: RadiansPerSecond = serde_json::from_str("0.25").unwrap();
+        assert_eq!(back, RadiansPerSecond(0.25));
+    }
+
+    #[test]
+    fn conversions() {
+        assert!((MetersPerSecond::from_km_per_hour(36.0).get() - 10.0).abs() < 1e-5);
+        assert!((MetersPerSecond(2.0).to_km_per_hour() - 7.2).abs() < 1e-5);
+        assert!(
+            (RadiansPerSecond::from_degrees_per_second(180.0).get() - std::f32::consts::PI).abs()
+                < 1e-6
+        );
+        assert_eq!(Meters::from_millimeters(1500.0), Meters(1.5));
+        assert_eq!(Meters(4.0).per_seconds(2.0), MetersPerSecond(2.0));
+        assert_eq!(MetersPerSecond(0.5).over_seconds(4.0), Meters(2.0));
+        assert_eq!(RadiansPerSecond(2.0) * Meters(0.25), MetersPerSecond(0.5));
+    }
+
+    #[test]
+    fn arithmetic_and_display() {
+        assert_eq!(Meters(1.0) + Meters(2.0), Meters(3.0));
+        assert_eq!(-MetersPerSecond(1.0), MetersPerSecond(-1.0));
+        assert_eq!((Meters(1.0) - Meters(3.0)).abs(), Meters(2.0));
+        assert_eq!(RadiansPerSecond(0.5).to_string(), "0.5 rad/s");
+        assert_eq!(format!("{:.2}", Meters(1.0)), "1.00 m");
+    }
+}